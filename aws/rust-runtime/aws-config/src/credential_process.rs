@@ -217,8 +217,9 @@ fn parse_expiration(expiration: impl AsRef<str>) -> Result<SystemTime, InvalidJs
 
 #[cfg(test)]
 mod test {
-    use crate::credential_process::CredentialProcessProvider;
+    use crate::credential_process::{parse_expiration, CredentialProcessProvider};
     use aws_credential_types::provider::ProvideCredentials;
+    use aws_smithy_types::error::display::DisplayErrorContext;
     use std::time::{Duration, SystemTime};
     use time::format_description::well_known::Rfc3339;
     use time::OffsetDateTime;
@@ -268,4 +269,48 @@ mod test {
             .await
             .expect_err("timeout forced");
     }
+
+    // TODO(https://github.com/awslabs/aws-sdk-rust/issues/1117) This test is ignored on Windows because it uses Unix-style paths
+    #[tokio::test]
+    #[cfg_attr(windows, ignore)]
+    async fn credentials_process_failures_include_stderr() {
+        let provider =
+            CredentialProcessProvider::new(String::from("echo 'no session' >&2; exit 3"));
+        let err = provider
+            .provide_credentials()
+            .await
+            .expect_err("the process failed");
+        let message = format!("{}", DisplayErrorContext(&err));
+        assert!(message.contains("exited with code"), "{message}");
+        assert!(message.contains("no session"), "{message}");
+    }
+
+    // TODO(https://github.com/awslabs/aws-sdk-rust/issues/1117) This test is ignored on Windows because it uses Unix-style paths
+    #[tokio::test]
+    #[cfg_attr(windows, ignore)]
+    async fn credentials_process_malformed_output() {
+        let provider = CredentialProcessProvider::new(String::from(
+            r#"echo '{ "Version": 1, "AccessKeyId": "ASIARTESTID", '"#,
+        ));
+        let err = provider
+            .provide_credentials()
+            .await
+            .expect_err("the output is truncated");
+        let message = format!("{}", DisplayErrorContext(&err));
+        assert!(message.contains("could not parse response"), "{message}");
+    }
+
+    #[test]
+    fn expiration_is_rfc3339() {
+        let expected = SystemTime::UNIX_EPOCH + Duration::from_millis(1_653_568_496_789);
+        assert_eq!(
+            expected,
+            parse_expiration("2022-05-26T12:34:56.789Z").unwrap()
+        );
+        assert_eq!(
+            expected,
+            parse_expiration("2022-05-26T14:34:56.789+02:00").unwrap()
+        );
+        parse_expiration("2022-05-26 12:34:56").expect_err("not RFC 3339");
+    }
 }
@@ -88,6 +88,14 @@ impl Credentials {
         }))
     }
 
+    /// Returns a builder for `Credentials`.
+    ///
+    /// Like [`Credentials::new`], this is intended to be used from a custom credentials provider
+    /// implementation.
+    pub fn builder() -> CredentialsBuilder {
+        CredentialsBuilder::default()
+    }
+
     /// Creates `Credentials` from hardcoded access key, secret key, and session token.
     ///
     /// _Note: In general, you should prefer to use the credential providers that come
@@ -168,6 +176,98 @@ impl Credentials {
     pub fn session_token(&self) -> Option<&str> {
         self.0.session_token.as_deref()
     }
+
+    /// Returns the name of the provider that loaded the credentials.
+    pub fn provider_name(&self) -> &'static str {
+        self.0.provider_name
+    }
+}
+
+/// Builder for [`Credentials`].
+///
+/// The access key ID, secret access key, and provider name are required.
+#[derive(Clone, Default)]
+pub struct CredentialsBuilder {
+    access_key_id: Option<Zeroizing<String>>,
+    secret_access_key: Option<Zeroizing<String>>,
+    session_token: Zeroizing<Option<String>>,
+    expires_after: Option<SystemTime>,
+    provider_name: Option<&'static str>,
+}
+
+impl Debug for CredentialsBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CredentialsBuilder")
+            .field("provider_name", &self.provider_name)
+            .field("access_key_id", &self.access_key_id.as_deref())
+            .field("secret_access_key", &"** redacted **")
+            .field("session_token", &"** redacted **")
+            .field("expires_after", &self.expires_after)
+            .finish()
+    }
+}
+
+impl CredentialsBuilder {
+    /// Sets the access key ID.
+    pub fn access_key_id(mut self, access_key_id: impl Into<String>) -> Self {
+        self.access_key_id = Some(Zeroizing::new(access_key_id.into()));
+        self
+    }
+
+    /// Sets the secret access key.
+    pub fn secret_access_key(mut self, secret_access_key: impl Into<String>) -> Self {
+        self.secret_access_key = Some(Zeroizing::new(secret_access_key.into()));
+        self
+    }
+
+    /// Sets the session token.
+    pub fn session_token(mut self, session_token: impl Into<String>) -> Self {
+        self.set_session_token(Some(session_token.into()));
+        self
+    }
+
+    /// Sets the session token.
+    pub fn set_session_token(&mut self, session_token: Option<String>) {
+        self.session_token = Zeroizing::new(session_token);
+    }
+
+    /// Sets the time when the credentials will expire.
+    pub fn expiry(mut self, expiry: SystemTime) -> Self {
+        self.set_expiry(Some(expiry));
+        self
+    }
+
+    /// Sets the time when the credentials will expire.
+    pub fn set_expiry(&mut self, expiry: Option<SystemTime>) {
+        self.expires_after = expiry;
+    }
+
+    /// Sets the name of the provider that loaded the credentials, for diagnostics.
+    pub fn provider_name(mut self, provider_name: &'static str) -> Self {
+        self.provider_name = Some(provider_name);
+        self
+    }
+
+    /// Builds [`Credentials`].
+    ///
+    /// # Panics
+    ///
+    /// If the access key ID, secret access key, or provider name hasn't been set.
+    pub fn build(self) -> Credentials {
+        Credentials(Arc::new(Inner {
+            access_key_id: self
+                .access_key_id
+                .expect("required field `access_key_id` missing"),
+            secret_access_key: self
+                .secret_access_key
+                .expect("required field `secret_access_key` missing"),
+            session_token: self.session_token,
+            expires_after: self.expires_after,
+            provider_name: self
+                .provider_name
+                .expect("required field `provider_name` missing"),
+        }))
+    }
 }
 
 #[cfg(feature = "test-util")]
@@ -207,6 +307,57 @@ mod test {
     use crate::Credentials;
     use std::time::{Duration, UNIX_EPOCH};
 
+    #[test]
+    fn builder() {
+        let expiry = UNIX_EPOCH + Duration::from_millis(1234567890123);
+        let creds = Credentials::builder()
+            .access_key_id("akid")
+            .secret_access_key("secret")
+            .session_token("token")
+            .expiry(expiry)
+            .provider_name("builder tester")
+            .build();
+        assert_eq!("akid", creds.access_key_id());
+        assert_eq!("secret", creds.secret_access_key());
+        assert_eq!(Some("token"), creds.session_token());
+        assert_eq!(Some(expiry), creds.expiry());
+        assert_eq!("builder tester", creds.provider_name());
+        assert_eq!(
+            Credentials::new(
+                "akid",
+                "secret",
+                Some("token".into()),
+                Some(expiry),
+                "builder tester"
+            ),
+            creds
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "required field `provider_name` missing")]
+    fn builder_requires_provider_name() {
+        Credentials::builder()
+            .access_key_id("akid")
+            .secret_access_key("secret")
+            .build();
+    }
+
+    #[test]
+    fn debug_redacts_secrets() {
+        let builder = Credentials::builder()
+            .access_key_id("akid")
+            .secret_access_key("SECRETKEY")
+            .session_token("SESSIONTOKEN")
+            .provider_name("debug tester");
+        let creds = builder.clone().build();
+        for debug in [format!("{:?}", builder), format!("{:?}", creds)] {
+            assert!(debug.contains("akid"), "{debug}");
+            assert!(!debug.contains("SECRETKEY"), "{debug}");
+            assert!(!debug.contains("SESSIONTOKEN"), "{debug}");
+        }
+    }
+
     #[test]
     fn debug_impl() {
         let creds = Credentials::new(
@@ -23,9 +23,10 @@
 pub mod credential_fn;
 mod credentials_impl;
 pub mod provider;
+pub mod rotating;
 pub mod token_fn;
 
-pub use credentials_impl::Credentials;
+pub use credentials_impl::{Credentials, CredentialsBuilder};
 
 /// AWS Access Token
 ///
@@ -73,7 +73,7 @@ construct credentials from hardcoded values.
 
 use crate::Credentials;
 use aws_smithy_runtime_api::client::identity::{
-    Identity, IdentityCachePartition, IdentityFuture, ResolveIdentity,
+    Identity, IdentityCacheLocation, IdentityCachePartition, IdentityFuture, ResolveIdentity,
 };
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
@@ -101,6 +101,16 @@ pub trait ProvideCredentials: Send + Sync + std::fmt::Debug {
     fn fallback_on_interrupt(&self) -> Option<Credentials> {
         None
     }
+
+    /// Returns where the credentials from this provider are cached.
+    ///
+    /// By default, they're cached in the identity cache of the client. Providers that keep their
+    /// credentials in memory and can replace them at any time should return
+    /// [`IdentityCacheLocation::IdentityResolver`], so that the identity cache doesn't keep
+    /// serving replaced credentials.
+    fn cache_location(&self) -> IdentityCacheLocation {
+        IdentityCacheLocation::RuntimeComponents
+    }
 }
 
 impl ProvideCredentials for Credentials {
@@ -119,6 +129,10 @@ impl ProvideCredentials for Arc<dyn ProvideCredentials> {
     {
         self.as_ref().provide_credentials()
     }
+
+    fn cache_location(&self) -> IdentityCacheLocation {
+        self.as_ref().cache_location()
+    }
 }
 
 /// Credentials Provider wrapper that may be shared
@@ -157,6 +171,10 @@ impl ProvideCredentials for SharedCredentialsProvider {
     {
         self.0.provide_credentials()
     }
+
+    fn cache_location(&self) -> IdentityCacheLocation {
+        self.0.cache_location()
+    }
 }
 
 impl Storable for SharedCredentialsProvider {
@@ -176,6 +194,10 @@ impl ResolveIdentity for SharedCredentialsProvider {
         ProvideCredentials::fallback_on_interrupt(self).map(|creds| creds.into())
     }
 
+    fn cache_location(&self) -> IdentityCacheLocation {
+        ProvideCredentials::cache_location(self)
+    }
+
     fn cache_partition(&self) -> Option<IdentityCachePartition> {
         Some(self.1)
     }
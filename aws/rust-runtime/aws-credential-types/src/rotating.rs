@@ -0,0 +1,139 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A credentials provider with static credentials that can be replaced while clients use it
+//!
+//! This is useful when credentials are rotated by something outside of the SDK, such as a secrets
+//! vault that pushes new keys to the application: the new keys can be set on the existing
+//! clients, rather than building new clients with new connection pools.
+//!
+//! ```rust
+//! use aws_credential_types::rotating::RotatingCredentialsProvider;
+//! use aws_credential_types::Credentials;
+//!
+//! let provider = RotatingCredentialsProvider::new(Credentials::new(
+//!     "AKIDOLD", "SECRETOLD", None, None, "vault",
+//! ));
+//! let handle = provider.handle();
+//! // ... configure clients with `provider`, and keep `handle` ...
+//!
+//! // When the keys are rotated, requests sent from then on are signed with the new keys:
+//! handle.set(Credentials::new("AKIDNEW", "SECRETNEW", None, None, "vault"));
+//! ```
+
+use crate::provider::{future, ProvideCredentials};
+use crate::Credentials;
+use aws_smithy_runtime_api::client::identity::IdentityCacheLocation;
+use std::sync::{Arc, RwLock};
+
+/// Credentials provider that provides the credentials most recently set with its
+/// [`RotatingCredentialsHandle`].
+///
+/// Requests resolve credentials when they're signed: requests that already resolved them keep
+/// them, and the requests that resolve them after [`RotatingCredentialsHandle::set`] get the new
+/// ones. Since the credentials are already in memory, they aren't cached in the client's identity
+/// cache, so it can't keep serving credentials that were replaced.
+#[derive(Clone, Debug)]
+pub struct RotatingCredentialsProvider {
+    current: Arc<RwLock<Credentials>>,
+}
+
+impl RotatingCredentialsProvider {
+    /// Creates a provider that provides `credentials` until they're replaced.
+    pub fn new(credentials: Credentials) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(credentials)),
+        }
+    }
+
+    /// Returns a handle that replaces the credentials of this provider.
+    pub fn handle(&self) -> RotatingCredentialsHandle {
+        RotatingCredentialsHandle {
+            current: self.current.clone(),
+        }
+    }
+
+    /// Returns the current credentials.
+    pub fn current(&self) -> Credentials {
+        self.current.read().unwrap().clone()
+    }
+}
+
+impl ProvideCredentials for RotatingCredentialsProvider {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        // The lock is released before the future is returned, so it's never held across an await
+        future::ProvideCredentials::ready(Ok(self.current()))
+    }
+
+    fn fallback_on_interrupt(&self) -> Option<Credentials> {
+        Some(self.current())
+    }
+
+    fn cache_location(&self) -> IdentityCacheLocation {
+        IdentityCacheLocation::IdentityResolver
+    }
+}
+
+/// Handle that replaces the credentials of a [`RotatingCredentialsProvider`].
+#[derive(Clone, Debug)]
+pub struct RotatingCredentialsHandle {
+    current: Arc<RwLock<Credentials>>,
+}
+
+impl RotatingCredentialsHandle {
+    /// Replaces the credentials of the provider, and of every client using it.
+    pub fn set(&self, credentials: Credentials) {
+        *self.current.write().unwrap() = credentials;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RotatingCredentialsProvider;
+    use crate::provider::{ProvideCredentials, SharedCredentialsProvider};
+    use crate::Credentials;
+    use aws_smithy_runtime_api::client::identity::{IdentityCacheLocation, ResolveIdentity};
+
+    fn credentials(access_key_id: &str) -> Credentials {
+        Credentials::new(access_key_id, "secret", None, None, "test")
+    }
+
+    #[tokio::test]
+    async fn provides_the_credentials_that_were_set_last() {
+        let provider = RotatingCredentialsProvider::new(credentials("AKID1"));
+        let handle = provider.handle();
+        let resolved = provider.provide_credentials().await.unwrap();
+        assert_eq!("AKID1", resolved.access_key_id());
+
+        handle.set(credentials("AKID2"));
+        assert_eq!(
+            "AKID2",
+            provider
+                .provide_credentials()
+                .await
+                .unwrap()
+                .access_key_id()
+        );
+        assert_eq!(
+            "AKID2",
+            provider.fallback_on_interrupt().unwrap().access_key_id()
+        );
+        // Credentials that were already resolved aren't changed
+        assert_eq!("AKID1", resolved.access_key_id());
+    }
+
+    #[test]
+    fn credentials_arent_cached_by_the_client() {
+        let provider =
+            SharedCredentialsProvider::new(RotatingCredentialsProvider::new(credentials("AKID")));
+        assert_eq!(
+            IdentityCacheLocation::IdentityResolver,
+            ResolveIdentity::cache_location(&provider)
+        );
+    }
+}
@@ -22,6 +22,7 @@ impl<E> RequestIdExt for SdkError<E, Response> {
         match self {
             Self::ResponseError(err) => err.raw().headers().extended_request_id(),
             Self::ServiceError(err) => err.raw().headers().extended_request_id(),
+            Self::PreconditionFailed(err) => err.raw().headers().extended_request_id(),
             _ => None,
         }
     }
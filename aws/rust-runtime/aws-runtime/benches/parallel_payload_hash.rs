@@ -0,0 +1,140 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_credential_types::Credentials;
+use aws_runtime::auth::payload_hash::ParallelPayloadHashInterceptor;
+use aws_runtime::auth::sigv4::SigV4Signer;
+use aws_runtime::auth::SigV4OperationSigningConfig;
+use aws_smithy_async::time::StaticTimeSource;
+use aws_smithy_runtime_api::client::auth::{AuthSchemeEndpointConfig, PendingSigningWork, Sign};
+use aws_smithy_runtime_api::client::identity::Identity;
+use aws_smithy_runtime_api::client::interceptors::context::{Input, InterceptorContext};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+use aws_smithy_runtime_api::client::runtime_components::{
+    RuntimeComponents, RuntimeComponentsBuilder,
+};
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::{ConfigBag, Layer};
+use aws_types::region::SigningRegion;
+use aws_types::SigningName;
+use bytes::Bytes;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::time::Duration;
+
+const SIZES: [usize; 3] = [1024 * 1024, 16 * 1024 * 1024, 64 * 1024 * 1024];
+
+/// Stands in for endpoint and identity resolution, which the parallel hash overlaps with.
+const RESOLUTION_LATENCY: Duration = Duration::from_millis(20);
+
+fn config() -> ConfigBag {
+    let mut layer = Layer::new("bench");
+    layer.store_put(SigV4OperationSigningConfig {
+        region: Some(SigningRegion::from_static("us-east-1")),
+        name: Some(SigningName::from_static("s3")),
+        ..Default::default()
+    });
+    ConfigBag::of_layers(vec![layer])
+}
+
+fn request(body: &Bytes) -> HttpRequest {
+    let mut request = HttpRequest::new(SdkBody::from(body.clone()));
+    request
+        .set_uri("https://bucket.s3.us-east-1.amazonaws.com/key")
+        .unwrap();
+    request
+}
+
+/// Runs the interceptor the way the orchestrator does before the first attempt.
+fn start_hashing(
+    interceptor: &ParallelPayloadHashInterceptor,
+    request: HttpRequest,
+    components: &RuntimeComponents,
+    cfg: &mut ConfigBag,
+) -> HttpRequest {
+    let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+    ctx.enter_serialization_phase();
+    ctx.set_request(request);
+    let _ = ctx.take_input();
+    ctx.enter_before_transmit_phase();
+    interceptor
+        .read_before_attempt(&(&ctx).into(), components, cfg)
+        .unwrap();
+    ctx.take_request().unwrap()
+}
+
+fn sign(
+    request: &mut HttpRequest,
+    identity: &Identity,
+    components: &RuntimeComponents,
+    cfg: &ConfigBag,
+) {
+    SigV4Signer::new()
+        .sign_http_request(
+            request,
+            identity,
+            AuthSchemeEndpointConfig::empty(),
+            components,
+            cfg,
+        )
+        .unwrap();
+}
+
+/// Compares the time from the start of an attempt until its in-memory body is signed, when the
+/// payload is hashed by the signer, and when it's hashed in parallel with endpoint and identity
+/// resolution by `ParallelPayloadHashInterceptor`.
+pub fn parallel_payload_hash(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+    let identity: Identity = Credentials::for_tests().into();
+    let components = RuntimeComponentsBuilder::for_tests()
+        .with_time_source(Some(StaticTimeSource::from_secs(1_700_000_000)))
+        .build()
+        .unwrap();
+    let interceptor = ParallelPayloadHashInterceptor::new().threshold(0);
+
+    let mut group = c.benchmark_group("sign_in_memory_body_after_resolution");
+    group.sample_size(10);
+    for size in SIZES {
+        let body = Bytes::from(vec![0x5a_u8; size]);
+        group.bench_with_input(BenchmarkId::new("inline_hash", size), &body, |b, body| {
+            b.iter(|| {
+                runtime.block_on(async {
+                    let cfg = config();
+                    let mut request = request(body);
+                    tokio::time::sleep(RESOLUTION_LATENCY).await;
+                    sign(&mut request, &identity, &components, &cfg);
+                })
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("parallel_hash", size), &body, |b, body| {
+            b.iter(|| {
+                runtime.block_on(async {
+                    let mut cfg = config();
+                    let mut request =
+                        start_hashing(&interceptor, request(body), &components, &mut cfg);
+                    tokio::time::sleep(RESOLUTION_LATENCY).await;
+                    for work in cfg.load::<PendingSigningWork>() {
+                        work.wait().await;
+                    }
+                    sign(&mut request, &identity, &components, &cfg);
+                })
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+
+    config = Criterion::default();
+
+    targets = parallel_payload_hash
+}
+
+criterion_main!(benches);
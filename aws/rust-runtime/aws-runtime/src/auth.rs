@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::content_encoding::header_value::AWS_CHUNKED;
 use aws_sigv4::http_request::{
     PayloadChecksumKind, PercentEncodingMode, SessionTokenMode, SignableBody, SignatureLocation,
     SigningInstructions, SigningSettings, UriPathNormalizationMode,
@@ -23,6 +24,11 @@ use std::time::Duration;
 /// Auth implementations for SigV4.
 pub mod sigv4;
 
+/// Support for computing SigV4 payload hashes ahead of signing.
+pub mod payload_hash;
+
+pub mod signing_debug;
+
 #[cfg(feature = "sigv4a")]
 /// Auth implementations for SigV4a.
 pub mod sigv4a;
@@ -185,6 +191,11 @@ enum SigV4SigningError {
     MissingSigningName,
     WrongIdentityType(Identity),
     BadTypeInEndpointAuthSchemeConfig(&'static str),
+    PrecomputedPayloadHashMismatch,
+    IncompatiblePayloadSigningPolicy {
+        policy: PayloadSigningPolicy,
+        reason: &'static str,
+    },
 }
 
 impl fmt::Display for SigV4SigningError {
@@ -206,6 +217,15 @@ impl fmt::Display for SigV4SigningError {
                     "unexpected type for `{field_name}` in endpoint auth scheme config",
                 )
             }
+            PrecomputedPayloadHashMismatch => {
+                w("the precomputed payload hash does not match the request body")
+            }
+            IncompatiblePayloadSigningPolicy { policy, reason } => {
+                write!(
+                    f,
+                    "the `{policy}` payload signing policy can't be used for this request: {reason}"
+                )
+            }
         }
     }
 }
@@ -326,6 +346,39 @@ impl PayloadSigningOverrideRuntimePlugin {
             inner: layer.freeze(),
         }
     }
+
+    /// Create a new runtime plugin that will make the signer use the given payload hash
+    /// instead of hashing the request body.
+    ///
+    /// The hash must be the SHA-256 of the request body, lowercase hex encoded. It is not
+    /// checked against the body unless [`with_validation`](Self::with_validation) is enabled.
+    pub fn precomputed(sha256_hex: impl Into<String>) -> Self {
+        let mut layer = Layer::new("PayloadSigningOverrideRuntimePlugin");
+        layer.store_put(PayloadSigningOverride::Precomputed(sha256_hex.into()));
+
+        Self {
+            inner: layer.freeze(),
+        }
+    }
+
+    /// When enabled, a precomputed payload hash is checked against an in-memory request
+    /// body before signing, and signing fails if they don't match.
+    ///
+    /// This is disabled by default since it requires hashing the body anyway.
+    pub fn with_validation(self, validate: bool) -> Self {
+        let mut layer = self
+            .inner
+            .try_modify()
+            .expect("the layer is only shared after the plugin is built");
+        if validate {
+            layer.store_put(payload_hash::ValidatePrecomputedPayloadHash);
+        } else {
+            layer.unset::<payload_hash::ValidatePrecomputedPayloadHash>();
+        }
+        Self {
+            inner: layer.freeze(),
+        }
+    }
 }
 
 impl RuntimePlugin for PayloadSigningOverrideRuntimePlugin {
@@ -333,3 +386,136 @@ impl RuntimePlugin for PayloadSigningOverrideRuntimePlugin {
         Some(self.inner.clone())
     }
 }
+
+/// How request payloads are signed, regardless of the operation's default.
+///
+/// By default, a request body that is in memory is signed, and a streaming body is sent as
+/// `UNSIGNED-PAYLOAD`. Some operations change that default, like the operations with the
+/// `unsignedPayload` trait. When a policy is in the config bag, it takes precedence over both the
+/// default and any [`PayloadSigningOverride`], and the `x-amz-content-sha256` header is always
+/// sent so that the service knows how the payload was signed.
+///
+/// Signing fails before the request is sent when the policy can't be used for it:
+/// - `Signed` requires a request body that is in memory and isn't aws-chunked encoded.
+/// - `Unsigned` requires an HTTPS endpoint, since nothing else protects the payload's integrity.
+/// - `Streaming` requires an aws-chunked encoded request body.
+///
+/// Presigned requests never sign their payload, and ignore the policy.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PayloadSigningPolicy {
+    /// Sign the SHA-256 of the request body.
+    Signed,
+
+    /// Send `UNSIGNED-PAYLOAD` in place of the payload hash.
+    Unsigned,
+
+    /// Sign an aws-chunked encoded request body as `STREAMING-UNSIGNED-PAYLOAD-TRAILER`.
+    ///
+    /// The individual chunks aren't signed: the payload is protected by the checksum trailer
+    /// that follows them.
+    Streaming,
+}
+
+impl PayloadSigningPolicy {
+    /// Returns the name of this policy.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Signed => "signed",
+            Self::Unsigned => "unsigned",
+            Self::Streaming => "streaming",
+        }
+    }
+
+    /// Returns the policy that `body` is signed with.
+    pub(crate) fn of(body: &SignableBody<'_>) -> Option<Self> {
+        match body {
+            SignableBody::Bytes(_) | SignableBody::Precomputed(_) => Some(Self::Signed),
+            SignableBody::UnsignedPayload => Some(Self::Unsigned),
+            SignableBody::StreamingUnsignedPayloadTrailer => Some(Self::Streaming),
+            _ => None,
+        }
+    }
+
+    /// Returns the policy to sign a request with, if one is set.
+    ///
+    /// The signing settings are updated to send the `x-amz-content-sha256` header.
+    pub(crate) fn for_request(
+        settings: &mut SigningSettings,
+        config_bag: &ConfigBag,
+    ) -> Option<Self> {
+        let policy = *config_bag.load::<Self>()?;
+        if settings.signature_location == SignatureLocation::QueryParams {
+            return None;
+        }
+        settings.payload_checksum_kind = PayloadChecksumKind::XAmzSha256;
+        Some(policy)
+    }
+
+    /// Returns how the payload of `request` is signed with this policy, or an error if this
+    /// policy can't be used for `request`.
+    ///
+    /// `default` is how the payload would have been signed without a policy.
+    pub(crate) fn signable_body<'a>(
+        self,
+        request: &'a HttpRequest,
+        default: SignableBody<'a>,
+    ) -> Result<SignableBody<'a>, SigV4SigningError> {
+        let incompatible = |reason| SigV4SigningError::IncompatiblePayloadSigningPolicy {
+            policy: self,
+            reason,
+        };
+        let aws_chunked = request
+            .headers()
+            .get("content-encoding")
+            .is_some_and(|encodings| {
+                encodings
+                    .split(',')
+                    .any(|encoding| encoding.trim().eq_ignore_ascii_case(AWS_CHUNKED))
+            });
+        match self {
+            Self::Signed if aws_chunked => Err(incompatible(
+                "the request body is aws-chunked encoded, which requires the `streaming` policy",
+            )),
+            Self::Signed => match default {
+                // Keep a hash that was already computed
+                SignableBody::Bytes(_) | SignableBody::Precomputed(_) => Ok(default),
+                _ => request
+                    .body()
+                    .bytes()
+                    .map(SignableBody::Bytes)
+                    .ok_or_else(|| {
+                        incompatible(
+                            "the request body is streaming, so it can't be hashed before it's sent",
+                        )
+                    }),
+            },
+            Self::Unsigned if is_plain_http(request.uri()) => Err(incompatible(
+                "the endpoint uses plain HTTP, which doesn't protect the payload's integrity",
+            )),
+            Self::Unsigned if aws_chunked => Err(incompatible(
+                "the request body is aws-chunked encoded, which requires the `streaming` policy",
+            )),
+            Self::Unsigned => Ok(SignableBody::UnsignedPayload),
+            Self::Streaming if !aws_chunked => {
+                Err(incompatible("the request body isn't aws-chunked encoded"))
+            }
+            Self::Streaming => Ok(SignableBody::StreamingUnsignedPayloadTrailer),
+        }
+    }
+}
+
+impl fmt::Display for PayloadSigningPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Storable for PayloadSigningPolicy {
+    type Storer = StoreReplace<Self>;
+}
+
+fn is_plain_http(uri: &str) -> bool {
+    uri.get(..7)
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case("http://"))
+}
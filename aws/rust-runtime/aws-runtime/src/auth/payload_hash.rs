@@ -0,0 +1,398 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::auth::{PayloadSigningOverride, SigV4OperationSigningConfig};
+use aws_sigv4::sign::v4::sha256_hex_string;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::auth::PendingSigningWork;
+use aws_smithy_runtime_api::client::interceptors::context::BeforeTransmitInterceptorContextRef;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// The default body size (in bytes) at or above which [`ParallelPayloadHashInterceptor`]
+/// computes the payload hash off of the request path.
+pub const DEFAULT_PARALLEL_HASH_THRESHOLD: usize = 8 * 1024 * 1024;
+
+type Task = Box<dyn FnOnce() + Send + 'static>;
+
+/// Spawns the blocking task that computes a payload hash.
+///
+/// The SDK is runtime agnostic, so by default the hash is computed on a dedicated
+/// [`std::thread`]. Users of Tokio can route the work to the blocking pool instead:
+///
+/// ```no_run
+/// use aws_runtime::auth::payload_hash::PayloadHashSpawner;
+///
+/// # fn spawn_blocking<F: FnOnce() + Send + 'static>(_f: F) {}
+/// let spawner = PayloadHashSpawner::new(|task| {
+///     // e.g. `tokio::task::spawn_blocking(task);`
+///     spawn_blocking(task);
+/// });
+/// ```
+#[derive(Clone)]
+pub struct PayloadHashSpawner(Arc<dyn Fn(Task) + Send + Sync>);
+
+impl PayloadHashSpawner {
+    /// Creates a new `PayloadHashSpawner` from the given spawn function.
+    pub fn new(spawn: impl Fn(Box<dyn FnOnce() + Send + 'static>) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(spawn))
+    }
+
+    /// Creates a `PayloadHashSpawner` that runs each task on a new [`std::thread`].
+    pub fn std_thread() -> Self {
+        Self::new(|task| {
+            std::thread::spawn(task);
+        })
+    }
+
+    fn spawn(&self, task: Task) {
+        (self.0)(task)
+    }
+}
+
+impl Default for PayloadHashSpawner {
+    fn default() -> Self {
+        Self::std_thread()
+    }
+}
+
+impl fmt::Debug for PayloadHashSpawner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PayloadHashSpawner")
+    }
+}
+
+/// Interceptor that computes the SigV4 payload hash of large in-memory bodies in parallel
+/// with endpoint and identity resolution.
+///
+/// Before the first attempt, if the request body is in memory and at least `threshold` bytes
+/// long, hashing is started on a task created by the configured [`PayloadHashSpawner`]. The
+/// orchestrator waits for the hash asynchronously before signing, so the SigV4 signer finds it
+/// ready. The resulting signature is identical to the one that would be computed without this
+/// interceptor.
+///
+/// Bodies whose payload signing is overridden (e.g. `UNSIGNED-PAYLOAD` or a precomputed hash)
+/// are left alone.
+#[derive(Debug)]
+pub struct ParallelPayloadHashInterceptor {
+    threshold: usize,
+    spawner: PayloadHashSpawner,
+}
+
+impl Default for ParallelPayloadHashInterceptor {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_PARALLEL_HASH_THRESHOLD,
+            spawner: PayloadHashSpawner::default(),
+        }
+    }
+}
+
+impl ParallelPayloadHashInterceptor {
+    /// Creates a new `ParallelPayloadHashInterceptor` with the default threshold and spawner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the minimum body size, in bytes, for which the hash is computed in parallel.
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets the spawner used to run the hashing task.
+    pub fn spawner(mut self, spawner: PayloadHashSpawner) -> Self {
+        self.spawner = spawner;
+        self
+    }
+}
+
+impl Intercept for ParallelPayloadHashInterceptor {
+    fn name(&self) -> &'static str {
+        "ParallelPayloadHashInterceptor"
+    }
+
+    fn read_before_attempt(
+        &self,
+        context: &BeforeTransmitInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        // The hash is started once and shared by all attempts.
+        if cfg.load::<PendingPayloadHash>().is_some()
+            || cfg.load::<PayloadSigningOverride>().is_some()
+            || cfg
+                .load::<SigV4OperationSigningConfig>()
+                .map(|config| config.signing_options.payload_override.is_some())
+                .unwrap_or_default()
+        {
+            return Ok(());
+        }
+
+        let body = match context.request().body().try_clone() {
+            Some(body) => body,
+            None => return Ok(()),
+        };
+        let (ptr, len) = match body.bytes() {
+            Some(bytes) if bytes.len() >= self.threshold => (bytes.as_ptr() as usize, bytes.len()),
+            _ => return Ok(()),
+        };
+
+        let (tx, rx) = watch::channel(None);
+        self.spawner.spawn(Box::new(move || {
+            let hash = sha256_hex_string(body.bytes().expect("checked above"));
+            // The receivers are gone if the operation was dropped
+            let _ = tx.send(Some(hash));
+        }));
+        tracing::trace!(len, "computing payload hash in parallel");
+        let pending = PendingPayloadHash { ptr, len, hash: rx };
+        let layer = cfg.interceptor_state();
+        layer.store_append(PendingSigningWork::new({
+            let pending = pending.clone();
+            move || pending.clone().finished()
+        }));
+        layer.store_put(pending);
+        Ok(())
+    }
+}
+
+/// A payload hash that is being computed by [`ParallelPayloadHashInterceptor`].
+#[derive(Clone)]
+pub(crate) struct PendingPayloadHash {
+    ptr: usize,
+    len: usize,
+    // `None` until the hashing task is done
+    hash: watch::Receiver<Option<String>>,
+}
+
+impl PendingPayloadHash {
+    /// Completes once the hashing task is done, or has failed.
+    async fn finished(mut self) {
+        loop {
+            let done = self.hash.borrow().is_some();
+            if done || self.hash.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Returns the hash of `body`, if it's ready.
+    ///
+    /// Returns `None` if `body` isn't the body that was hashed (e.g. an interceptor replaced it),
+    /// or if the hashing task failed or isn't done, which only happens if the request is signed
+    /// without waiting for the [`PendingSigningWork`]. The caller should then hash the body itself.
+    pub(crate) fn get(&self, body: &[u8]) -> Option<String> {
+        if body.as_ptr() as usize != self.ptr || body.len() != self.len {
+            return None;
+        }
+        self.hash.borrow().clone()
+    }
+}
+
+impl fmt::Debug for PendingPayloadHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingPayloadHash")
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl Storable for PendingPayloadHash {
+    type Storer = StoreReplace<Self>;
+}
+
+/// When present in the config bag, a precomputed payload hash set with
+/// [`PayloadSigningOverride::Precomputed`] is checked against the request body before signing.
+///
+/// This is off by default since it removes the benefit of precomputing the hash.
+#[derive(Clone, Debug)]
+pub(crate) struct ValidatePrecomputedPayloadHash;
+
+impl Storable for ValidatePrecomputedPayloadHash {
+    type Storer = StoreReplace<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::sigv4::SigV4Signer;
+    use crate::auth::PayloadSigningOverrideRuntimePlugin;
+    use aws_credential_types::Credentials;
+    use aws_smithy_runtime_api::client::auth::{AuthSchemeEndpointConfig, Sign};
+    use aws_smithy_runtime_api::client::identity::Identity;
+    use aws_smithy_runtime_api::client::interceptors::context::{Input, InterceptorContext};
+    use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugin;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::config_bag::Layer;
+    use aws_types::region::SigningRegion;
+    use aws_types::SigningName;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn base_layer() -> Layer {
+        let mut layer = Layer::new("test");
+        layer.store_put(SigV4OperationSigningConfig {
+            region: Some(SigningRegion::from_static("us-east-1")),
+            name: Some(SigningName::from_static("s3")),
+            ..Default::default()
+        });
+        layer
+    }
+
+    fn cfg_with(plugin: PayloadSigningOverrideRuntimePlugin) -> ConfigBag {
+        let mut cfg = ConfigBag::of_layers(vec![base_layer()]);
+        cfg.push_shared_layer(plugin.config().unwrap());
+        cfg
+    }
+
+    fn request(body: SdkBody) -> HttpRequest {
+        let mut request = HttpRequest::new(body);
+        request.set_uri("https://example.com/some-key").unwrap();
+        request
+    }
+
+    fn sign(request: &mut HttpRequest, cfg: &ConfigBag) -> Result<(), BoxError> {
+        let identity: Identity = Credentials::for_tests().into();
+        let rc = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(aws_smithy_async::time::StaticTimeSource::from_secs(
+                1_700_000_000,
+            )))
+            .build()
+            .unwrap();
+        SigV4Signer::new().sign_http_request(
+            request,
+            &identity,
+            AuthSchemeEndpointConfig::empty(),
+            &rc,
+            cfg,
+        )
+    }
+
+    fn authorization(request: &HttpRequest) -> String {
+        request.headers().get("authorization").unwrap().to_owned()
+    }
+
+    fn spawn_counting(count: Arc<AtomicUsize>) -> PayloadHashSpawner {
+        PayloadHashSpawner::new(move |task| {
+            count.fetch_add(1, Ordering::SeqCst);
+            std::thread::spawn(task);
+        })
+    }
+
+    fn run_interceptor(
+        interceptor: &ParallelPayloadHashInterceptor,
+        request: HttpRequest,
+        cfg: &mut ConfigBag,
+    ) -> HttpRequest {
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.enter_serialization_phase();
+        ctx.set_request(request);
+        let _ = ctx.take_input();
+        ctx.enter_before_transmit_phase();
+        interceptor
+            .read_before_attempt(&(&ctx).into(), &rc, cfg)
+            .unwrap();
+        ctx.take_request().unwrap()
+    }
+
+    #[tokio::test]
+    async fn parallel_hash_produces_identical_signature() {
+        let body = vec![7u8; 4096];
+
+        let cfg = ConfigBag::of_layers(vec![base_layer()]);
+        let mut expected = request(SdkBody::from(body.clone()));
+        sign(&mut expected, &cfg).unwrap();
+
+        let spawned = Arc::new(AtomicUsize::new(0));
+        let interceptor = ParallelPayloadHashInterceptor::new()
+            .threshold(1024)
+            .spawner(spawn_counting(spawned.clone()));
+        let mut cfg = ConfigBag::of_layers(vec![base_layer()]);
+        let mut actual = run_interceptor(&interceptor, request(SdkBody::from(body)), &mut cfg);
+        // The orchestrator waits for the hash before signing
+        cfg.load::<PendingSigningWork>()
+            .next()
+            .expect("hashing was started")
+            .wait()
+            .await;
+        let pending = cfg.load::<PendingPayloadHash>().unwrap();
+        assert!(pending.get(actual.body().bytes().unwrap()).is_some());
+        sign(&mut actual, &cfg).unwrap();
+
+        assert_eq!(1, spawned.load(Ordering::SeqCst));
+        assert_eq!(authorization(&expected), authorization(&actual));
+    }
+
+    #[test]
+    fn small_bodies_are_hashed_inline() {
+        let spawned = Arc::new(AtomicUsize::new(0));
+        let interceptor = ParallelPayloadHashInterceptor::new()
+            .threshold(1024)
+            .spawner(spawn_counting(spawned.clone()));
+        let mut cfg = ConfigBag::of_layers(vec![base_layer()]);
+        run_interceptor(&interceptor, request(SdkBody::from("small")), &mut cfg);
+        assert_eq!(0, spawned.load(Ordering::SeqCst));
+        assert!(cfg.load::<PendingPayloadHash>().is_none());
+        assert!(cfg.load::<PendingSigningWork>().next().is_none());
+    }
+
+    #[test]
+    fn replaced_body_is_not_signed_with_stale_hash() {
+        let interceptor = ParallelPayloadHashInterceptor::new().threshold(16);
+        let mut cfg = ConfigBag::of_layers(vec![base_layer()]);
+        run_interceptor(
+            &interceptor,
+            request(SdkBody::from(vec![1u8; 64])),
+            &mut cfg,
+        );
+
+        let mut expected = request(SdkBody::from(vec![2u8; 64]));
+        sign(&mut expected, &ConfigBag::of_layers(vec![base_layer()])).unwrap();
+        let mut actual = request(SdkBody::from(vec![2u8; 64]));
+        sign(&mut actual, &cfg).unwrap();
+        assert_eq!(authorization(&expected), authorization(&actual));
+    }
+
+    #[test]
+    fn precomputed_payload_hash_skips_hashing() {
+        let body = b"hello world".to_vec();
+        let cfg = ConfigBag::of_layers(vec![base_layer()]);
+        let mut expected = request(SdkBody::from(body.clone()));
+        sign(&mut expected, &cfg).unwrap();
+
+        let cfg = cfg_with(PayloadSigningOverrideRuntimePlugin::precomputed(
+            sha256_hex_string(&body),
+        ));
+        let mut actual = request(SdkBody::from(body));
+        sign(&mut actual, &cfg).unwrap();
+        assert_eq!(authorization(&expected), authorization(&actual));
+    }
+
+    #[test]
+    fn precomputed_payload_hash_validation() {
+        let wrong_hash = sha256_hex_string(b"something else");
+
+        // validation is off by default, so a wrong hash is signed as-is
+        let cfg = cfg_with(PayloadSigningOverrideRuntimePlugin::precomputed(
+            wrong_hash.clone(),
+        ));
+        sign(&mut request(SdkBody::from("hello world")), &cfg).unwrap();
+
+        let cfg = cfg_with(
+            PayloadSigningOverrideRuntimePlugin::precomputed(wrong_hash).with_validation(true),
+        );
+        let err = sign(&mut request(SdkBody::from("hello world")), &cfg).unwrap_err();
+        assert!(
+            format!("{err}").contains("precomputed payload hash"),
+            "unexpected error: {err}"
+        );
+    }
+}
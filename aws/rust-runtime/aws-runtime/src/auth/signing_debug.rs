@@ -0,0 +1,548 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Debugging aid for SigV4 signature mismatches.
+//!
+//! When signing debug is enabled, the SigV4 signer keeps the canonical request and string to
+//! sign that it calculated for each attempt, and how the payload was signed. If the service then
+//! rejects the signature, these are added to the error metadata, along with the service's versions
+//! of the canonical request and string to sign when the service includes them in its response.
+//! Comparing the two is usually enough to find the header or query param that was changed after
+//! signing.
+//!
+//! Signing debug is off by default. It can be enabled with the `signing_debug` method on a
+//! service config builder, or by setting the `AWS_SIGV4_DEBUG` environment variable to `true`.
+//! The retained values never contain key material, and session tokens are redacted from them.
+
+use crate::auth::PayloadSigningPolicy;
+use aws_sigv4::http_request::SigningDebugArtifacts;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeTransmitInterceptorContextMut, BeforeTransmitInterceptorContextRef,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use aws_smithy_types::error::metadata::{
+    current_error_metadata_extras, Builder as ErrorMetadataBuilder, ErrorMetadataExtras,
+};
+use aws_types::os_shim_internal::Env;
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+
+/// Environment variable that enables signing debug when set to `true`.
+pub const SIGNING_DEBUG_ENV_VAR: &str = "AWS_SIGV4_DEBUG";
+
+/// Error metadata key for the canonical request that the SDK signed.
+pub const CANONICAL_REQUEST: &str = "sigv4_canonical_request";
+
+/// Error metadata key for the string to sign that the SDK signed.
+pub const STRING_TO_SIGN: &str = "sigv4_string_to_sign";
+
+/// Error metadata key for how the payload was signed: `signed`, `unsigned` or `streaming`.
+///
+/// See [`PayloadSigningPolicy`] for what these mean.
+pub const PAYLOAD_SIGNING: &str = "sigv4_payload_signing";
+
+/// Error metadata key for the canonical request that the service expected.
+pub const SERVICE_CANONICAL_REQUEST: &str = "sigv4_service_canonical_request";
+
+/// Error metadata key for the string to sign that the service expected.
+pub const SERVICE_STRING_TO_SIGN: &str = "sigv4_service_string_to_sign";
+
+const SIGNATURE_ERROR_CODES: &[&str] = &["SignatureDoesNotMatch", "InvalidSignatureException"];
+
+/// Config for enabling signing debug.
+///
+/// When this isn't set, signing debug is enabled by the [`SIGNING_DEBUG_ENV_VAR`] environment variable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SigningDebug {
+    enabled: bool,
+}
+
+impl SigningDebug {
+    /// Creates a new `SigningDebug` config.
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// Returns true if signing debug is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl Storable for SigningDebug {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Where the signer records its debug artifacts for the current attempt.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SigningDebugRecorder {
+    recorded: Arc<Mutex<Option<Recorded>>>,
+}
+
+/// The signing debug artifacts of an attempt, and how its payload was signed.
+type Recorded = (SigningDebugArtifacts, Option<PayloadSigningPolicy>);
+
+impl SigningDebugRecorder {
+    pub(crate) fn record(
+        &self,
+        artifacts: SigningDebugArtifacts,
+        payload_signing: Option<PayloadSigningPolicy>,
+    ) {
+        *self.recorded.lock().unwrap() = Some((artifacts, payload_signing));
+    }
+
+    fn take(&self) -> Option<Recorded> {
+        self.recorded.lock().unwrap().take()
+    }
+}
+
+impl Storable for SigningDebugRecorder {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Interceptor that retains the SigV4 canonical request, string to sign and payload signing for
+/// each attempt when signing debug is enabled.
+///
+/// The retained values are stored in the interceptor state as [`ErrorMetadataExtras`], where
+/// [`populate_error_metadata`] picks them up if the service rejects the signature.
+#[derive(Debug, Default)]
+pub struct SigningDebugInterceptor {
+    enabled_by_env: bool,
+}
+
+impl SigningDebugInterceptor {
+    /// Creates a new `SigningDebugInterceptor`.
+    ///
+    /// The [`SIGNING_DEBUG_ENV_VAR`] environment variable is read once, when this is called.
+    pub fn new() -> Self {
+        Self::from_env(&Env::real())
+    }
+
+    fn from_env(env: &Env) -> Self {
+        Self {
+            enabled_by_env: env
+                .get(SIGNING_DEBUG_ENV_VAR)
+                .map(|value| value.eq_ignore_ascii_case("true"))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Intercept for SigningDebugInterceptor {
+    fn name(&self) -> &'static str {
+        "SigningDebugInterceptor"
+    }
+
+    fn modify_before_signing(
+        &self,
+        _context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let enabled = cfg
+            .load::<SigningDebug>()
+            .map(SigningDebug::is_enabled)
+            .unwrap_or(self.enabled_by_env);
+        if enabled {
+            // A fresh recorder for every attempt, so that a retry never reports stale values
+            cfg.interceptor_state()
+                .store_put(SigningDebugRecorder::default());
+        }
+        Ok(())
+    }
+
+    fn read_after_signing(
+        &self,
+        _context: &BeforeTransmitInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let Some(recorder) = cfg.load::<SigningDebugRecorder>() else {
+            return Ok(());
+        };
+        let recorded = recorder.take();
+        let mut extras = cfg
+            .load::<ErrorMetadataExtras>()
+            .cloned()
+            .unwrap_or_default();
+        extras.remove(CANONICAL_REQUEST);
+        extras.remove(STRING_TO_SIGN);
+        extras.remove(PAYLOAD_SIGNING);
+        if let Some((artifacts, payload_signing)) = recorded {
+            extras
+                .insert(CANONICAL_REQUEST, artifacts.canonical_request())
+                .insert(STRING_TO_SIGN, artifacts.string_to_sign());
+            if let Some(payload_signing) = payload_signing {
+                extras.insert(PAYLOAD_SIGNING, payload_signing.as_str());
+            }
+        }
+        cfg.interceptor_state().store_put(extras);
+        Ok(())
+    }
+}
+
+/// Adds the retained signing debug values to the metadata of a signature error.
+///
+/// This is called by generated code while an error response is deserialized, after the error
+/// code has been parsed. It does nothing unless signing debug is enabled and the error code
+/// is a signature error code.
+pub fn populate_error_metadata(
+    builder: ErrorMetadataBuilder,
+    response_body: &[u8],
+) -> ErrorMetadataBuilder {
+    let Some(extras) = current_error_metadata_extras() else {
+        return builder;
+    };
+    let (Some(canonical_request), Some(string_to_sign)) =
+        (extras.get(CANONICAL_REQUEST), extras.get(STRING_TO_SIGN))
+    else {
+        return builder;
+    };
+    let metadata = builder.build();
+    let is_signature_error = metadata
+        .code()
+        .is_some_and(|code| SIGNATURE_ERROR_CODES.contains(&code));
+    let builder = metadata.into_builder();
+    if !is_signature_error {
+        return builder;
+    }
+
+    let body = String::from_utf8_lossy(response_body);
+    let (service_canonical_request, service_string_to_sign) = parse_service_artifacts(&body);
+    let payload_signing = extras.get(PAYLOAD_SIGNING);
+    tracing::debug!(
+        canonical_request = canonical_request,
+        string_to_sign = string_to_sign,
+        payload_signing = ?payload_signing,
+        service_canonical_request = ?service_canonical_request,
+        service_string_to_sign = ?service_string_to_sign,
+        "the service rejected the request signature"
+    );
+    let mut builder = builder
+        .custom(CANONICAL_REQUEST, canonical_request)
+        .custom(STRING_TO_SIGN, string_to_sign);
+    if let Some(value) = payload_signing {
+        builder = builder.custom(PAYLOAD_SIGNING, value);
+    }
+    if let Some(value) = service_canonical_request {
+        builder = builder.custom(SERVICE_CANONICAL_REQUEST, value);
+    }
+    if let Some(value) = service_string_to_sign {
+        builder = builder.custom(SERVICE_STRING_TO_SIGN, value);
+    }
+    builder
+}
+
+/// Extracts the canonical request and string to sign that the service expected.
+///
+/// S3 returns these as `CanonicalRequest` and `StringToSign` elements. Most other services
+/// include them in the error message.
+fn parse_service_artifacts(body: &str) -> (Option<String>, Option<String>) {
+    if let (Some(canonical_request), Some(string_to_sign)) = (
+        xml_element(body, "CanonicalRequest"),
+        xml_element(body, "StringToSign"),
+    ) {
+        return (
+            Some(unescape_xml(canonical_request).into_owned()),
+            Some(unescape_xml(string_to_sign).into_owned()),
+        );
+    }
+
+    let message = if body.trim_start().starts_with('<') {
+        unescape_xml(body)
+    } else {
+        unescape_json(body)
+    };
+    let canonical_request = between(
+        &message,
+        "The Canonical String for this request should have been\n'",
+        "'\n\nThe String-to-Sign should have been",
+    );
+    let string_to_sign = between(&message, "The String-to-Sign should have been\n'", "'");
+    (
+        canonical_request.map(str::to_owned),
+        string_to_sign.map(str::to_owned),
+    )
+}
+
+fn xml_element<'a>(body: &'a str, name: &str) -> Option<&'a str> {
+    between(body, &format!("<{name}>"), &format!("</{name}>"))
+}
+
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let rest = &text[text.find(start)? + start.len()..];
+    Some(&rest[..rest.find(end)?])
+}
+
+fn unescape_xml(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(
+        text.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&#10;", "\n")
+            .replace("&amp;", "&"),
+    )
+}
+
+fn unescape_json(text: &str) -> Cow<'_, str> {
+    if !text.contains('\\') {
+        return Cow::Borrowed(text);
+    }
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    Cow::Owned(unescaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::sigv4::{SigV4AuthScheme, SCHEME_ID};
+    use crate::auth::SigV4OperationSigningConfig;
+    use aws_credential_types::provider::SharedCredentialsProvider;
+    use aws_credential_types::Credentials;
+    use aws_sigv4::sign::v4::{calculate_signature, generate_signing_key, sha256_hex_string};
+    use aws_smithy_async::time::StaticTimeSource;
+    use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+    use aws_smithy_runtime::client::identity::IdentityCache;
+    use aws_smithy_runtime::client::orchestrator::operation::Operation;
+    use aws_smithy_runtime_api::client::auth::static_resolver::StaticAuthSchemeOptionResolver;
+    use aws_smithy_runtime_api::client::auth::AuthSchemeOptionResolverParams;
+    use aws_smithy_runtime_api::client::identity::SharedIdentityResolver;
+    use aws_smithy_runtime_api::client::orchestrator::{
+        HttpRequest, HttpResponse, OrchestratorError,
+    };
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::config_bag::Layer;
+    use aws_smithy_types::error::ErrorMetadata;
+    use aws_types::region::SigningRegion;
+    use aws_types::SigningName;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    const SIGNATURE_DOES_NOT_MATCH: &str = "<Error>\
+        <Code>SignatureDoesNotMatch</Code>\
+        <Message>The request signature we calculated does not match the signature you provided.</Message>\
+        <StringToSign>AWS4-HMAC-SHA256\n20200913T122640Z\nservice-sts</StringToSign>\
+        <CanonicalRequest>GET\n/\n\nhost:example.amazonaws.com\n\nhost\nservice-hash</CanonicalRequest>\
+        </Error>";
+
+    fn request_time() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_600_000_000)
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn deserialize_error(response: &HttpResponse) -> Result<(), OrchestratorError<ErrorMetadata>> {
+        let body = response.body().bytes().unwrap();
+        let code = match response.status().as_u16() {
+            403 => "SignatureDoesNotMatch",
+            _ => "InternalError",
+        };
+        let builder = populate_error_metadata(ErrorMetadata::builder().code(code), body);
+        Err(OrchestratorError::operation(builder.build()))
+    }
+
+    /// Sends a signed request that fails with `status` and `body`, and returns the error along
+    /// with the `authorization` header that was sent.
+    async fn send(
+        interceptor: SigningDebugInterceptor,
+        signing_debug: Option<bool>,
+        status: u16,
+        body: &'static str,
+    ) -> (ErrorMetadata, String) {
+        let authorization = Arc::new(Mutex::new(None));
+        let http_client = infallible_client_fn({
+            let authorization = authorization.clone();
+            move |request| {
+                *authorization.lock().unwrap() = request
+                    .headers()
+                    .get("authorization")
+                    .map(|value| value.to_str().unwrap().to_string());
+                http_02x::Response::builder()
+                    .status(status)
+                    .body(body)
+                    .unwrap()
+            }
+        });
+
+        let mut layer = Layer::new("test");
+        layer.store_put(AuthSchemeOptionResolverParams::new(()));
+        layer.store_put(SigV4OperationSigningConfig {
+            region: Some(SigningRegion::from_static("us-east-1")),
+            name: Some(SigningName::from_static("service")),
+            ..Default::default()
+        });
+        if let Some(enabled) = signing_debug {
+            layer.store_put(SigningDebug::new(enabled));
+        }
+        let components = RuntimeComponentsBuilder::new("test")
+            .with_auth_scheme(SigV4AuthScheme::new())
+            .with_auth_scheme_option_resolver(Some(StaticAuthSchemeOptionResolver::new(vec![
+                SCHEME_ID,
+            ])))
+            .with_identity_cache(Some(IdentityCache::no_cache()))
+            .with_identity_resolver(
+                SCHEME_ID,
+                SharedIdentityResolver::new(SharedCredentialsProvider::new(
+                    Credentials::for_tests(),
+                )),
+            );
+
+        let operation = Operation::builder()
+            .service_name("test")
+            .operation_name("test")
+            .http_client(http_client)
+            .endpoint_url("https://example.amazonaws.com")
+            .no_retry()
+            .time_source(StaticTimeSource::new(request_time()))
+            .interceptor(interceptor)
+            .runtime_plugin(
+                StaticRuntimePlugin::new()
+                    .with_config(layer.freeze())
+                    .with_runtime_components(components),
+            )
+            .serializer(|body: &'static str| Ok(HttpRequest::new(SdkBody::from(body))))
+            .deserializer(deserialize_error)
+            .build();
+
+        let err = operation.invoke("request body").await.unwrap_err();
+        let authorization = authorization
+            .lock()
+            .unwrap()
+            .take()
+            .expect("request was sent");
+        (
+            err.as_service_error().expect("service error").clone(),
+            authorization,
+        )
+    }
+
+    fn disabled_by_env() -> SigningDebugInterceptor {
+        SigningDebugInterceptor::from_env(&Env::from_slice(&[]))
+    }
+
+    #[tokio::test]
+    async fn signature_errors_include_the_signed_canonical_request() {
+        let (err, authorization) =
+            send(disabled_by_env(), Some(true), 403, SIGNATURE_DOES_NOT_MATCH).await;
+
+        let canonical_request = err.extra(CANONICAL_REQUEST).expect("canonical request");
+        let string_to_sign = err.extra(STRING_TO_SIGN).expect("string to sign");
+        assert!(
+            canonical_request.starts_with("GET\n/\n\nhost:example.amazonaws.com\n"),
+            "{canonical_request}"
+        );
+        assert_eq!(
+            Some(sha256_hex_string(canonical_request.as_bytes()).as_str()),
+            string_to_sign.lines().last()
+        );
+        // The string to sign is the one that the sent signature was calculated from
+        let credentials = Credentials::for_tests();
+        let signing_key = generate_signing_key(
+            credentials.secret_access_key(),
+            request_time(),
+            "us-east-1",
+            "service",
+        );
+        let signature = calculate_signature(signing_key, string_to_sign.as_bytes());
+        assert!(
+            authorization.ends_with(&format!("Signature={signature}")),
+            "{authorization}"
+        );
+
+        assert_eq!(Some("signed"), err.extra(PAYLOAD_SIGNING));
+
+        assert_eq!(
+            Some("GET\n/\n\nhost:example.amazonaws.com\n\nhost\nservice-hash"),
+            err.extra(SERVICE_CANONICAL_REQUEST)
+        );
+        assert_eq!(
+            Some("AWS4-HMAC-SHA256\n20200913T122640Z\nservice-sts"),
+            err.extra(SERVICE_STRING_TO_SIGN)
+        );
+    }
+
+    #[tokio::test]
+    async fn signing_debug_is_off_by_default() {
+        let (err, _) = send(disabled_by_env(), None, 403, SIGNATURE_DOES_NOT_MATCH).await;
+        assert_eq!(None, err.extra(CANONICAL_REQUEST));
+        assert_eq!(None, err.extra(SERVICE_CANONICAL_REQUEST));
+    }
+
+    #[tokio::test]
+    async fn signing_debug_can_be_enabled_by_env_var() {
+        let interceptor =
+            SigningDebugInterceptor::from_env(&Env::from_slice(&[(SIGNING_DEBUG_ENV_VAR, "true")]));
+        let (err, _) = send(interceptor, None, 403, SIGNATURE_DOES_NOT_MATCH).await;
+        assert!(err.extra(CANONICAL_REQUEST).is_some());
+
+        // Config takes precedence over the environment
+        let interceptor =
+            SigningDebugInterceptor::from_env(&Env::from_slice(&[(SIGNING_DEBUG_ENV_VAR, "true")]));
+        let (err, _) = send(interceptor, Some(false), 403, SIGNATURE_DOES_NOT_MATCH).await;
+        assert_eq!(None, err.extra(CANONICAL_REQUEST));
+    }
+
+    #[tokio::test]
+    async fn other_errors_are_left_alone() {
+        let (err, _) = send(
+            disabled_by_env(),
+            Some(true),
+            500,
+            "<Error><Code>InternalError</Code></Error>",
+        )
+        .await;
+        assert_eq!(None, err.extra(CANONICAL_REQUEST));
+    }
+
+    #[test]
+    fn service_artifacts_are_parsed_from_error_messages() {
+        let json = r#"{"__type":"InvalidSignatureException","message":"The request signature we calculated does not match the signature you provided.\n\nThe Canonical String for this request should have been\n'POST\n/\n\ncontent-type:application/x-amz-json-1.0\nhost:example.amazonaws.com\n\ncontent-type;host\nabc'\n\nThe String-to-Sign should have been\n'AWS4-HMAC-SHA256\n20200913T122640Z\n20200913/us-east-1/service/aws4_request\ndef'\n"}"#;
+        assert_eq!(
+            (
+                Some("POST\n/\n\ncontent-type:application/x-amz-json-1.0\nhost:example.amazonaws.com\n\ncontent-type;host\nabc".to_string()),
+                Some("AWS4-HMAC-SHA256\n20200913T122640Z\n20200913/us-east-1/service/aws4_request\ndef".to_string()),
+            ),
+            parse_service_artifacts(json)
+        );
+
+        let xml = "<ErrorResponse><Error><Code>SignatureDoesNotMatch</Code><Message>The request signature we calculated does not match the signature you provided.\n\nThe Canonical String for this request should have been\n'GET\n/\nAction=List&amp;Version=1\nhost:example.amazonaws.com\n\nhost\nabc'\n\nThe String-to-Sign should have been\n'AWS4-HMAC-SHA256\ndef'\n</Message></Error></ErrorResponse>";
+        assert_eq!(
+            (
+                Some(
+                    "GET\n/\nAction=List&Version=1\nhost:example.amazonaws.com\n\nhost\nabc"
+                        .to_string()
+                ),
+                Some("AWS4-HMAC-SHA256\ndef".to_string()),
+            ),
+            parse_service_artifacts(xml)
+        );
+
+        assert_eq!(
+            (None, None),
+            parse_service_artifacts(r#"{"message":"Signature expired"}"#)
+        );
+    }
+}
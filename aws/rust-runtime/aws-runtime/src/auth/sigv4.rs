@@ -4,16 +4,19 @@
  */
 
 use crate::auth;
+use crate::auth::payload_hash::{PendingPayloadHash, ValidatePrecomputedPayloadHash};
+use crate::auth::signing_debug::SigningDebugRecorder;
 use crate::auth::{
     extract_endpoint_auth_scheme_signing_name, extract_endpoint_auth_scheme_signing_region,
-    PayloadSigningOverride, SigV4OperationSigningConfig, SigV4SessionTokenNameOverride,
-    SigV4SigningError,
+    PayloadSigningOverride, PayloadSigningPolicy, SigV4OperationSigningConfig,
+    SigV4SessionTokenNameOverride, SigV4SigningError,
 };
 use aws_credential_types::Credentials;
 use aws_sigv4::http_request::{
     sign, SignableBody, SignableRequest, SigningParams, SigningSettings,
 };
 use aws_sigv4::sign::v4;
+use aws_sigv4::sign::v4::sha256_hex_string;
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::auth::{
     AuthScheme, AuthSchemeEndpointConfig, AuthSchemeId, Sign,
@@ -161,7 +164,7 @@ impl Sign for SigV4Signer {
             Self::extract_operation_config(auth_scheme_endpoint_config, config_bag)?;
         let request_time = runtime_components.time_source().unwrap_or_default().now();
 
-        let settings = if let Some(session_token_name_override) =
+        let mut settings = if let Some(session_token_name_override) =
             config_bag.load::<SigV4SessionTokenNameOverride>()
         {
             let mut settings = Self::settings(&operation_config);
@@ -171,10 +174,14 @@ impl Sign for SigV4Signer {
         } else {
             Self::settings(&operation_config)
         };
+        let debug_recorder = config_bag.load::<SigningDebugRecorder>();
+        settings.retain_debug_artifacts = debug_recorder.is_some();
+        let payload_signing_policy = PayloadSigningPolicy::for_request(&mut settings, config_bag);
 
         let signing_params =
             Self::signing_params(settings, identity, &operation_config, request_time)?;
 
+        let payload_signing;
         let (signing_instructions, _signature) = {
             // A body that is already in memory can be signed directly. A body that is not in memory
             // (any sort of streaming body or presigned request) will be signed via UNSIGNED-PAYLOAD.
@@ -185,12 +192,14 @@ impl Sign for SigV4Signer {
                 // the payload_override is a cheap clone because it contains either a
                 // reference or a short checksum (we're not cloning the entire body)
                 .cloned()
-                .unwrap_or_else(|| {
-                    request
-                        .body()
-                        .bytes()
-                        .map(SignableBody::Bytes)
-                        .unwrap_or(SignableBody::UnsignedPayload)
+                .unwrap_or_else(|| match request.body().bytes() {
+                    // If the hash of this body was computed ahead of time, use it instead
+                    Some(bytes) => config_bag
+                        .load::<PendingPayloadHash>()
+                        .and_then(|pending| pending.get(bytes))
+                        .map(SignableBody::Precomputed)
+                        .unwrap_or(SignableBody::Bytes(bytes)),
+                    None => SignableBody::UnsignedPayload,
                 });
 
             // Sometimes it's necessary to override the payload signing scheme.
@@ -199,9 +208,26 @@ impl Sign for SigV4Signer {
                 tracing::trace!(
                     "payload signing was overridden, now set to {payload_signing_override:?}"
                 );
+                if let (PayloadSigningOverride::Precomputed(hash), Some(bytes)) =
+                    (payload_signing_override, request.body().bytes())
+                {
+                    if config_bag
+                        .load::<ValidatePrecomputedPayloadHash>()
+                        .is_some()
+                        && *hash != sha256_hex_string(bytes)
+                    {
+                        return Err(SigV4SigningError::PrecomputedPayloadHashMismatch.into());
+                    }
+                }
                 signable_body = payload_signing_override.clone().to_signable_body();
             }
 
+            // A payload signing policy takes precedence over everything else
+            if let Some(policy) = payload_signing_policy {
+                signable_body = policy.signable_body(request, signable_body)?;
+            }
+            payload_signing = PayloadSigningPolicy::of(&signable_body);
+
             let signable_request = SignableRequest::new(
                 request.method(),
                 request.uri(),
@@ -211,6 +237,11 @@ impl Sign for SigV4Signer {
             sign(signable_request, &SigningParams::V4(signing_params))?
         }
         .into_parts();
+        if let (Some(recorder), Some(artifacts)) =
+            (debug_recorder, signing_instructions.debug_artifacts())
+        {
+            recorder.record(artifacts.clone(), payload_signing);
+        }
 
         // If this is an event stream operation, set up the event stream signer
         #[cfg(feature = "event-stream")]
@@ -364,13 +395,26 @@ mod event_stream {
 mod tests {
     use super::*;
     use crate::auth::{HttpSignatureType, SigningOptions};
+    use crate::content_encoding::header_value::AWS_CHUNKED;
+    use aws_credential_types::provider::SharedCredentialsProvider;
     use aws_credential_types::Credentials;
     use aws_sigv4::http_request::SigningSettings;
+    use aws_smithy_runtime::client::http::test_util::capture_request;
+    use aws_smithy_runtime::client::identity::IdentityCache;
+    use aws_smithy_runtime::client::orchestrator::operation::Operation;
+    use aws_smithy_runtime_api::client::auth::static_resolver::StaticAuthSchemeOptionResolver;
+    use aws_smithy_runtime_api::client::auth::AuthSchemeOptionResolverParams;
+    use aws_smithy_runtime_api::client::orchestrator::{HttpResponse, OrchestratorError};
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin;
+    use aws_smithy_types::body::SdkBody;
     use aws_smithy_types::config_bag::Layer;
+    use aws_smithy_types::error::display::DisplayErrorContext;
     use aws_smithy_types::Document;
     use aws_types::region::SigningRegion;
     use aws_types::SigningName;
     use std::collections::HashMap;
+    use std::convert::Infallible;
     use std::time::{Duration, SystemTime};
     use tracing_test::traced_test;
 
@@ -467,4 +511,193 @@ mod tests {
         assert_eq!(result.name, Some(SigningName::from_static("qldb")));
         assert!(matches!(result, Cow::Borrowed(_)));
     }
+
+    const BODY: &str = "Hello, world!";
+
+    fn in_memory_request() -> HttpRequest {
+        HttpRequest::new(SdkBody::from(BODY))
+    }
+
+    fn streaming_request() -> HttpRequest {
+        HttpRequest::new(SdkBody::from_body_0_4(SdkBody::from(BODY)))
+    }
+
+    fn aws_chunked_request() -> HttpRequest {
+        let mut request = streaming_request();
+        request
+            .headers_mut()
+            .insert("content-encoding", AWS_CHUNKED);
+        request
+    }
+
+    fn with_policy(policy: PayloadSigningPolicy) -> Layer {
+        let mut layer = Layer::new("policy");
+        layer.store_put(policy);
+        layer
+    }
+
+    /// Signs and sends `request` to `endpoint_url` with the given `config`, and returns the
+    /// `x-amz-content-sha256` header that was sent, or the error if no request was sent.
+    async fn content_sha256(
+        config: Layer,
+        endpoint_url: &str,
+        request: fn() -> HttpRequest,
+    ) -> Result<Option<String>, String> {
+        let (http_client, captured) = capture_request(None);
+        let mut layer = Layer::new("test");
+        layer.store_put(AuthSchemeOptionResolverParams::new(()));
+        layer.store_put(SigV4OperationSigningConfig {
+            region: Some(SigningRegion::from_static("us-east-1")),
+            name: Some(SigningName::from_static("service")),
+            ..Default::default()
+        });
+        let components = RuntimeComponentsBuilder::new("test")
+            .with_auth_scheme(SigV4AuthScheme::new())
+            .with_auth_scheme_option_resolver(Some(StaticAuthSchemeOptionResolver::new(vec![
+                SCHEME_ID,
+            ])))
+            .with_identity_cache(Some(IdentityCache::no_cache()))
+            .with_identity_resolver(
+                SCHEME_ID,
+                SharedIdentityResolver::new(SharedCredentialsProvider::new(
+                    Credentials::for_tests(),
+                )),
+            );
+
+        let operation = Operation::builder()
+            .service_name("test")
+            .operation_name("test")
+            .http_client(http_client)
+            .endpoint_url(endpoint_url)
+            .no_retry()
+            .runtime_plugin(
+                StaticRuntimePlugin::new()
+                    .with_config(layer.freeze())
+                    .with_runtime_components(components),
+            )
+            .runtime_plugin(StaticRuntimePlugin::new().with_config(config.freeze()))
+            .serializer(move |_: ()| Ok(request()))
+            .deserializer(|_: &HttpResponse| Ok::<_, OrchestratorError<Infallible>>(()))
+            .build();
+
+        match operation.invoke(()).await {
+            Ok(()) => Ok(captured
+                .expect_request()
+                .headers()
+                .get("x-amz-content-sha256")
+                .map(str::to_owned)),
+            Err(err) => {
+                captured.expect_no_request();
+                Err(DisplayErrorContext(err).to_string())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn payload_signing_policy_sets_the_content_sha256_header() {
+        let https = "https://example.amazonaws.com";
+        // Without a policy, the operation's default is used
+        assert_eq!(
+            Ok(None),
+            content_sha256(Layer::new("none"), https, in_memory_request).await
+        );
+
+        assert_eq!(
+            Ok(Some(sha256_hex_string(BODY))),
+            content_sha256(
+                with_policy(PayloadSigningPolicy::Signed),
+                https,
+                in_memory_request
+            )
+            .await
+        );
+        assert_eq!(
+            Ok(Some("UNSIGNED-PAYLOAD".to_string())),
+            content_sha256(
+                with_policy(PayloadSigningPolicy::Unsigned),
+                https,
+                in_memory_request
+            )
+            .await
+        );
+        assert_eq!(
+            Ok(Some("UNSIGNED-PAYLOAD".to_string())),
+            content_sha256(
+                with_policy(PayloadSigningPolicy::Unsigned),
+                https,
+                streaming_request
+            )
+            .await
+        );
+        assert_eq!(
+            Ok(Some("STREAMING-UNSIGNED-PAYLOAD-TRAILER".to_string())),
+            content_sha256(
+                with_policy(PayloadSigningPolicy::Streaming),
+                https,
+                aws_chunked_request
+            )
+            .await
+        );
+        // Signed payloads don't need TLS
+        assert_eq!(
+            Ok(Some(sha256_hex_string(BODY))),
+            content_sha256(
+                with_policy(PayloadSigningPolicy::Signed),
+                "http://localhost:1234",
+                in_memory_request
+            )
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn payload_signing_policy_takes_precedence_over_overrides() {
+        let mut config = with_policy(PayloadSigningPolicy::Signed);
+        config.store_put(PayloadSigningOverride::UnsignedPayload);
+        assert_eq!(
+            Ok(Some(sha256_hex_string(BODY))),
+            content_sha256(config, "https://example.amazonaws.com", in_memory_request).await
+        );
+    }
+
+    #[tokio::test]
+    async fn incompatible_payload_signing_policies_fail_before_sending() {
+        let https = "https://example.amazonaws.com";
+        let cases: [(PayloadSigningPolicy, &str, fn() -> HttpRequest, &str); 4] = [
+            (
+                PayloadSigningPolicy::Unsigned,
+                "http://localhost:1234",
+                in_memory_request,
+                "the `unsigned` payload signing policy can't be used for this request: \
+                the endpoint uses plain HTTP",
+            ),
+            (
+                PayloadSigningPolicy::Streaming,
+                https,
+                in_memory_request,
+                "the `streaming` payload signing policy can't be used for this request: \
+                the request body isn't aws-chunked encoded",
+            ),
+            (
+                PayloadSigningPolicy::Signed,
+                https,
+                streaming_request,
+                "the `signed` payload signing policy can't be used for this request: \
+                the request body is streaming",
+            ),
+            (
+                PayloadSigningPolicy::Signed,
+                https,
+                aws_chunked_request,
+                "the `signed` payload signing policy can't be used for this request: \
+                the request body is aws-chunked encoded",
+            ),
+        ];
+        for (policy, endpoint_url, request, expected) in cases {
+            let err = content_sha256(with_policy(policy), endpoint_url, request)
+                .await
+                .expect_err("the request can't be signed");
+            assert!(err.contains(expected), "{err}");
+        }
+    }
 }
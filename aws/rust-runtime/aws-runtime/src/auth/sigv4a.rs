@@ -3,8 +3,9 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::auth::signing_debug::SigningDebugRecorder;
 use crate::auth::{
-    apply_signing_instructions, extract_endpoint_auth_scheme_signing_name,
+    apply_signing_instructions, extract_endpoint_auth_scheme_signing_name, PayloadSigningPolicy,
     SigV4OperationSigningConfig, SigV4SigningError,
 };
 use aws_credential_types::Credentials;
@@ -174,14 +175,18 @@ impl Sign for SigV4aSigner {
             return Err(SigV4SigningError::WrongIdentityType(identity.clone()).into());
         }
 
-        let settings = Self::settings(&operation_config);
+        let mut settings = Self::settings(&operation_config);
+        let debug_recorder = config_bag.load::<SigningDebugRecorder>();
+        settings.retain_debug_artifacts = debug_recorder.is_some();
+        let payload_signing_policy = PayloadSigningPolicy::for_request(&mut settings, config_bag);
         let signing_params =
             Self::signing_params(settings, identity, &operation_config, request_time)?;
 
+        let payload_signing;
         let (signing_instructions, _signature) = {
             // A body that is already in memory can be signed directly. A body that is not in memory
             // (any sort of streaming body or presigned request) will be signed via UNSIGNED-PAYLOAD.
-            let signable_body = operation_config
+            let mut signable_body = operation_config
                 .signing_options
                 .payload_override
                 .as_ref()
@@ -195,6 +200,10 @@ impl Sign for SigV4aSigner {
                         .map(SignableBody::Bytes)
                         .unwrap_or(SignableBody::UnsignedPayload)
                 });
+            if let Some(policy) = payload_signing_policy {
+                signable_body = policy.signable_body(request, signable_body)?;
+            }
+            payload_signing = PayloadSigningPolicy::of(&signable_body);
 
             let signable_request = SignableRequest::new(
                 request.method(),
@@ -205,6 +214,11 @@ impl Sign for SigV4aSigner {
             sign(signable_request, &signing_params.into())?
         }
         .into_parts();
+        if let (Some(recorder), Some(artifacts)) =
+            (debug_recorder, signing_instructions.debug_artifacts())
+        {
+            recorder.record(artifacts.clone(), payload_signing);
+        }
 
         apply_signing_instructions(signing_instructions, request)?;
         Ok(())
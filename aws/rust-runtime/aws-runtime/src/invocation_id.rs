@@ -12,6 +12,7 @@ use http_02x::{HeaderName, HeaderValue};
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::interceptors::context::BeforeTransmitInterceptorContextMut;
 use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::random::RandomSource;
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
 #[cfg(feature = "test-util")]
@@ -73,13 +74,14 @@ impl InvocationIdGenerator for DefaultInvocationIdGenerator {
         let mut rng = self.rng.lock().unwrap();
         let mut random_bytes = [0u8; 16];
         rng.fill(&mut random_bytes);
-
-        let id = uuid::Builder::from_random_bytes(random_bytes).into_uuid();
-        Ok(Some(InvocationId::new(id.to_string())))
+        Ok(Some(InvocationId::from_random_bytes(random_bytes)))
     }
 }
 
 /// This interceptor generates a UUID and attaches it to all request attempts made as part of this operation.
+///
+/// The UUID is generated by the [`SharedInvocationIdGenerator`] in the config bag if there is one,
+/// and drawn from the random source of the runtime components otherwise.
 #[non_exhaustive]
 #[derive(Debug, Default)]
 pub struct InvocationIdInterceptor {
@@ -101,14 +103,22 @@ impl Intercept for InvocationIdInterceptor {
     fn modify_before_retry_loop(
         &self,
         _ctx: &mut BeforeTransmitInterceptorContextMut<'_>,
-        _runtime_components: &RuntimeComponents,
+        runtime_components: &RuntimeComponents,
         cfg: &mut ConfigBag,
     ) -> Result<(), BoxError> {
-        let gen = cfg
-            .load::<SharedInvocationIdGenerator>()
-            .map(|gen| gen as &dyn InvocationIdGenerator)
-            .unwrap_or(&self.default);
-        if let Some(id) = gen.generate()? {
+        let id = match (
+            cfg.load::<SharedInvocationIdGenerator>(),
+            runtime_components.random_source(),
+        ) {
+            (Some(gen), _) => gen.generate()?,
+            (None, Some(random_source)) => {
+                let mut random_bytes = [0u8; 16];
+                random_source.fill_bytes(&mut random_bytes);
+                Some(InvocationId::from_random_bytes(random_bytes))
+            }
+            (None, None) => self.default.generate()?,
+        };
+        if let Some(id) = id {
             cfg.interceptor_state().store_put::<InvocationId>(id);
         }
 
@@ -144,6 +154,11 @@ impl InvocationId {
                 .expect("invocation ID must be a valid HTTP header value"),
         )
     }
+
+    fn from_random_bytes(random_bytes: [u8; 16]) -> Self {
+        let id = uuid::Builder::from_random_bytes(random_bytes).into_uuid();
+        Self::new(id.to_string())
+    }
 }
 
 impl Storable for InvocationId {
@@ -255,6 +270,32 @@ mod tests {
         assert_eq!(header.len(), 36);
     }
 
+    #[test]
+    fn id_is_drawn_from_the_random_source() {
+        use aws_smithy_runtime::client::random::SeededRandomSource;
+
+        let invocation_id = |seed| {
+            let rc = RuntimeComponentsBuilder::for_tests()
+                .with_random_source(Some(SeededRandomSource::new(seed)))
+                .build()
+                .unwrap();
+            let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+            ctx.enter_serialization_phase();
+            ctx.set_request(HttpRequest::empty());
+            let _ = ctx.take_input();
+            ctx.enter_before_transmit_phase();
+
+            let mut cfg = ConfigBag::base();
+            InvocationIdInterceptor::new()
+                .modify_before_retry_loop(&mut Into::into(&mut ctx), &rc, &mut cfg)
+                .unwrap();
+            cfg.load::<InvocationId>().unwrap().clone()
+        };
+
+        assert_eq!(invocation_id(1), invocation_id(1));
+        assert_ne!(invocation_id(1), invocation_id(2));
+    }
+
     #[cfg(feature = "test-util")]
     #[test]
     fn custom_id_generator() {
@@ -0,0 +1,149 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_credential_types::Credentials;
+use aws_runtime::auth::sigv4::{SigV4AuthScheme, SCHEME_ID};
+use aws_runtime::auth::SigV4OperationSigningConfig;
+use aws_smithy_async::test_util::instant_time_and_sleep;
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::identity::IdentityCache;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::retries::classifiers::HttpStatusCodeClassifier;
+use aws_smithy_runtime_api::client::auth::static_resolver::StaticAuthSchemeOptionResolver;
+use aws_smithy_runtime_api::client::auth::AuthSchemeOptionResolverParams;
+use aws_smithy_runtime_api::client::identity::{IdentityFuture, ResolveIdentity};
+use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
+use aws_smithy_runtime_api::client::runtime_components::{
+    RuntimeComponents, RuntimeComponentsBuilder,
+};
+use aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::{ConfigBag, Layer};
+use aws_smithy_types::error::ErrorMetadata;
+use aws_smithy_types::retry::RetryConfig;
+use aws_types::region::SigningRegion;
+use aws_types::SigningName;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// Resolves new credentials every time it's called, with the access key `AKID<n>`.
+#[derive(Debug, Default)]
+struct RotatingCredentials(AtomicUsize);
+
+impl ResolveIdentity for RotatingCredentials {
+    fn resolve_identity<'a>(
+        &'a self,
+        _runtime_components: &'a RuntimeComponents,
+        _config_bag: &'a ConfigBag,
+    ) -> IdentityFuture<'a> {
+        let n = self.0.fetch_add(1, Ordering::Relaxed);
+        IdentityFuture::ready(Ok(Credentials::new(
+            format!("AKID{n}"),
+            "secret",
+            None,
+            None,
+            "test",
+        )
+        .into()))
+    }
+}
+
+/// The `x-amz-date` and `authorization` headers of an attempt.
+#[derive(Debug)]
+struct Attempt {
+    date: String,
+    authorizations: Vec<String>,
+}
+
+fn sigv4() -> StaticRuntimePlugin {
+    let mut layer = Layer::new("sigv4");
+    layer.store_put(AuthSchemeOptionResolverParams::new(()));
+    layer.store_put(SigV4OperationSigningConfig {
+        region: Some(SigningRegion::from_static("us-east-1")),
+        name: Some(SigningName::from_static("test")),
+        ..Default::default()
+    });
+    StaticRuntimePlugin::new()
+        .with_config(layer.freeze())
+        .with_runtime_components(
+            RuntimeComponentsBuilder::new("sigv4")
+                .with_auth_scheme_option_resolver(Some(StaticAuthSchemeOptionResolver::new(vec![
+                    SCHEME_ID,
+                ])))
+                .with_auth_scheme(SigV4AuthScheme::new())
+                .with_identity_cache(Some(IdentityCache::no_cache()))
+                .with_identity_resolver(SCHEME_ID, RotatingCredentials::default()),
+        )
+}
+
+#[tokio::test]
+async fn retries_are_signed_again_with_the_current_time() {
+    let (time, sleep) =
+        instant_time_and_sleep(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+    let attempts = Arc::new(Mutex::new(Vec::new()));
+    let client_attempts = attempts.clone();
+    let client_time = time.clone();
+    let operation: Operation<(), (), ErrorMetadata> = Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .endpoint_url("https://test.us-east-1.amazonaws.com")
+        .standard_retry(&RetryConfig::standard().with_max_attempts(2))
+        .retry_classifier(HttpStatusCodeClassifier::default())
+        .time_source(time.clone())
+        .sleep_impl(sleep)
+        .runtime_plugin(sigv4())
+        .http_client(infallible_client_fn(move |request| {
+            let headers = request.headers();
+            let mut attempts = client_attempts.lock().unwrap();
+            attempts.push(Attempt {
+                date: headers["x-amz-date"].to_str().unwrap().to_string(),
+                authorizations: headers
+                    .get_all("authorization")
+                    .iter()
+                    .map(|value| value.to_str().unwrap().to_string())
+                    .collect(),
+            });
+            // The first attempt is throttled for long enough that its signature would go stale
+            let status = if attempts.len() == 1 {
+                client_time.advance(Duration::from_secs(600));
+                503
+            } else {
+                200
+            };
+            http_02x::Response::builder()
+                .status(status)
+                .body(SdkBody::empty())
+                .unwrap()
+        }))
+        .serializer(|_| Ok(http_02x::Request::new(SdkBody::empty()).try_into().unwrap()))
+        .deserializer(|response| {
+            if response.status().is_success() {
+                Ok(())
+            } else {
+                Err(OrchestratorError::operation(ErrorMetadata::default()))
+            }
+        })
+        .build();
+
+    operation.invoke(()).await.expect("the retry succeeds");
+
+    let attempts = attempts.lock().unwrap();
+    assert_eq!(2, attempts.len(), "{attempts:?}");
+    assert_eq!("20231114T221320Z", attempts[0].date);
+    assert!(
+        attempts[1].date.as_str() >= "20231114T222320Z",
+        "the retry should be signed after the throttled attempt: {attempts:?}"
+    );
+    for (n, attempt) in attempts.iter().enumerate() {
+        assert_eq!(1, attempt.authorizations.len(), "{attempts:?}");
+        let authorization = &attempt.authorizations[0];
+        assert!(
+            authorization.contains(&format!("Credential=AKID{n}/")),
+            "each attempt should be signed with freshly resolved credentials: {authorization}"
+        );
+    }
+    assert_ne!(attempts[0].authorizations, attempts[1].authorizations);
+}
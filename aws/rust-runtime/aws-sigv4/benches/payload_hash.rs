@@ -0,0 +1,64 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_credential_types::Credentials;
+use aws_sigv4::http_request::{sign, SignableBody, SignableRequest, SigningSettings};
+use aws_sigv4::sign::v4;
+use aws_smithy_runtime_api::client::identity::Identity;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use std::time::{Duration, UNIX_EPOCH};
+
+const SIZES: [usize; 3] = [1024 * 1024, 16 * 1024 * 1024, 64 * 1024 * 1024];
+
+fn sign_with(identity: &Identity, body: SignableBody<'_>) {
+    let params = v4::SigningParams::builder()
+        .identity(identity)
+        .region("us-east-1")
+        .name("s3")
+        .time(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+        .settings(SigningSettings::default())
+        .build()
+        .unwrap()
+        .into();
+    let request = SignableRequest::new(
+        "PUT",
+        "https://bucket.s3.us-east-1.amazonaws.com/key",
+        std::iter::empty(),
+        body,
+    )
+    .unwrap();
+    black_box(sign(request, &params).unwrap());
+}
+
+/// Compares the request-path cost of signing an in-memory body that is hashed inline with one
+/// whose payload hash was computed ahead of time (either precomputed by the caller or hashed in
+/// parallel with endpoint and identity resolution).
+pub fn payload_hash(c: &mut Criterion) {
+    let identity = Credentials::for_tests().into();
+    let mut group = c.benchmark_group("sign_in_memory_body");
+    group.sample_size(10);
+    for size in SIZES {
+        let body = vec![0x5a_u8; size];
+        let hash = v4::sha256_hex_string(&body);
+        group.bench_with_input(BenchmarkId::new("inline_hash", size), &body, |b, body| {
+            b.iter(|| sign_with(&identity, SignableBody::Bytes(body)))
+        });
+        group.bench_with_input(BenchmarkId::new("precomputed", size), &hash, |b, hash| {
+            b.iter(|| sign_with(&identity, SignableBody::Precomputed(hash.clone())))
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+
+    config = Criterion::default();
+
+    targets = payload_hash
+}
+
+criterion_main!(benches);
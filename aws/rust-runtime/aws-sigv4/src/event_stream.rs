@@ -159,7 +159,9 @@ fn sign_payload<'a>(
 
 #[cfg(test)]
 mod tests {
-    use crate::event_stream::{calculate_string_to_sign, sign_message, SigningParams};
+    use crate::event_stream::{
+        calculate_string_to_sign, sign_empty_message, sign_message, SigningParams,
+    };
     use crate::sign::v4::sha256_hex_string;
     use aws_credential_types::Credentials;
     use aws_smithy_eventstream::frame::write_message_to;
@@ -238,4 +240,63 @@ mod tests {
             panic!("expected timestamp for :date header");
         }
     }
+
+    #[test]
+    fn signatures_chain_across_messages() {
+        let identity = Credentials::for_tests().into();
+        let params = |time| SigningParams {
+            identity: &identity,
+            region: "us-east-1",
+            name: "testservice",
+            time,
+            settings: (),
+        };
+        let message = |payload: &'static [u8]| {
+            Message::new(payload).add_header(Header::new(
+                "some-header",
+                HeaderValue::String("value".into()),
+            ))
+        };
+
+        let last_signature = sha256_hex_string(b"last message sts");
+        let (_, first) = sign_message(
+            &message(b"test payload"),
+            &last_signature,
+            &params(UNIX_EPOCH + Duration::new(123_456_789_u64, 1234u32)),
+        )
+        .unwrap()
+        .into_parts();
+        assert_eq!(
+            "08fed460df836999e1c4137962a3d6ad66a9e8c1bf8e8888e3cbeee26bbf774d",
+            first
+        );
+
+        let (_, second) = sign_message(
+            &message(b"second payload"),
+            &first,
+            &params(UNIX_EPOCH + Duration::from_millis(123_456_790_500)),
+        )
+        .unwrap()
+        .into_parts();
+        assert_eq!(
+            "0cce613c91737ff6a0014d1927b8253e190917f0ca402feb7e33760d84694659",
+            second
+        );
+
+        let (signed, last) = sign_empty_message(
+            &second,
+            &params(UNIX_EPOCH + Duration::from_secs(123_456_791)),
+        )
+        .unwrap()
+        .into_parts();
+        assert_eq!(
+            "e6f6260098a7a4b3661108d9ca00b138e51f79be397af079cc206563a0c4ed56",
+            last
+        );
+        assert!(signed.payload().is_empty());
+        assert_eq!(
+            &HeaderValue::ByteArray(hex::decode(&last).unwrap().into()),
+            signed.headers()[0].value()
+        );
+    }
 }
@@ -54,6 +54,21 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # Canonical headers
+//!
+//! The signature only depends on the headers of the request, never on the order they were added in,
+//! except for headers that are repeated:
+//! - Header names are lowercased, and headers are sorted by name.
+//! - Headers whose names only differ in case are the same header.
+//! - The values of a repeated header are joined with `,` in the order they appear in the request.
+//!   They are never sorted, since the order of the values of a header can be meaningful. Requests
+//!   that repeat a header must add its values in a well-defined order for their signature to be
+//!   reproducible. The SDKs add them in the order defined by the model.
+//! - Leading and trailing spaces are removed from each value, and runs of spaces are collapsed into a
+//!   single space. Other whitespace, such as tabs, is left as is, like Amazon S3 expects.
+//!
+//! These rules are checked against the [SigV4 test suite](https://github.com/smithy-lang/smithy-rs/tree/main/aws/rust-runtime/aws-sigv4/aws-sig-v4-test-suite).
 
 mod canonical_request;
 mod error;
@@ -75,7 +90,7 @@ pub use settings::{
     PayloadChecksumKind, PercentEncodingMode, SessionTokenMode, SignatureLocation, SigningSettings,
     UriPathNormalizationMode,
 };
-pub use sign::{sign, SignableBody, SignableRequest, SigningInstructions};
+pub use sign::{sign, SignableBody, SignableRequest, SigningDebugArtifacts, SigningInstructions};
 use std::time::SystemTime;
 
 // Individual Debug impls are responsible for redacting sensitive fields.
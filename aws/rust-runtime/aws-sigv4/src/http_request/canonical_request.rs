@@ -240,7 +240,8 @@ impl<'a> CanonicalRequest<'a> {
         let mut canonical_headers = HeaderMap::with_capacity(req.headers().len());
         for (name, value) in req.headers().iter() {
             // Header names and values need to be normalized according to Step 4 of https://docs.aws.amazon.com/general/latest/gr/sigv4-create-canonical-request.html
-            // Using append instead of insert means this will not clobber headers that have the same lowercased name
+            // Using append instead of insert means this will not clobber headers that have the same lowercased name,
+            // and keeps their values in the order they appear in the request (see the "Canonical headers" docs of `http_request`)
             canonical_headers.append(
                 HeaderName::from_str(&name.to_lowercase())?,
                 normalize_header_value(value)?,
@@ -632,8 +633,8 @@ mod tests {
     };
     use crate::http_request::test;
     use crate::http_request::{
-        PayloadChecksumKind, SessionTokenMode, SignableBody, SignableRequest, SignatureLocation,
-        SigningParams, SigningSettings,
+        PayloadChecksumKind, PercentEncodingMode, SessionTokenMode, SignableBody, SignableRequest,
+        SignatureLocation, SigningParams, SigningSettings, UriPathNormalizationMode,
     };
     use crate::sign::v4;
     use crate::sign::v4::sha256_hex_string;
@@ -689,6 +690,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_repeated_header_with_different_cases() {
+        let mut req = test::v4::test_request("get-vanilla-query-order-key-case");
+        req.headers
+            .push(("X-Amz-Meta-Color".to_string(), "  red ".to_string()));
+        req.headers
+            .push(("x-amz-meta-size".to_string(), "L".to_string()));
+        req.headers
+            .push(("x-amz-meta-color".to_string(), "dark   blue".to_string()));
+        let req = SignableRequest::from(&req);
+        let settings = SigningSettings {
+            session_token_mode: SessionTokenMode::Exclude,
+            ..Default::default()
+        };
+        let identity = Credentials::for_tests().into();
+        let signing_params = signing_params(&identity, settings);
+        let creq = CanonicalRequest::from(&req, &signing_params).unwrap();
+
+        assert_eq!(
+            creq.values.signed_headers().to_string(),
+            "host;x-amz-date;x-amz-meta-color;x-amz-meta-size"
+        );
+        assert_eq!(creq.header_values_for("x-amz-meta-color"), "red,dark blue");
+    }
+
     #[test]
     fn test_set_xamz_sha_256() {
         let req = test::v4::test_request("get-vanilla-query-order-key-case");
@@ -806,6 +832,55 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn uri_path_encoding_and_normalization_follow_the_settings() {
+        // Most services double-encode and normalize the path, while S3 signs it as it was sent
+        let default_style = SigningSettings::default();
+        let s3_style = SigningSettings {
+            percent_encoding_mode: PercentEncodingMode::Single,
+            uri_path_normalization_mode: UriPathNormalizationMode::Disabled,
+            ..Default::default()
+        };
+        // (path as sent, default-style canonical path, S3-style canonical path)
+        let cases = [
+            ("/", "/", "/"),
+            ("", "/", "/"),
+            ("/foo%20bar", "/foo%2520bar", "/foo%20bar"),
+            (
+                "/caf%C3%A9/%F0%9F%A6%80",
+                "/caf%25C3%25A9/%25F0%259F%25A6%2580",
+                "/caf%C3%A9/%F0%9F%A6%80",
+            ),
+            ("/foo//bar", "/foo/bar", "/foo//bar"),
+            ("//foo///bar//", "/foo/bar/", "//foo///bar//"),
+            ("/foo/./bar", "/foo/bar", "/foo/./bar"),
+            ("/./foo/.", "/foo/", "/./foo/."),
+            ("/foo/../bar", "/bar", "/foo/../bar"),
+            (
+                "/a%2Fb/./c%20d//e",
+                "/a%252Fb/c%2520d/e",
+                "/a%2Fb/./c%20d//e",
+            ),
+        ];
+        let identity = Credentials::for_tests().into();
+        for (path, default_style_path, s3_style_path) in cases {
+            let req = http0::Request::builder()
+                .uri(format!("https://example.amazonaws.com{path}"))
+                .body("")
+                .unwrap()
+                .into();
+            let req = SignableRequest::from(&req);
+            for (settings, expected) in [
+                (default_style.clone(), default_style_path),
+                (s3_style.clone(), s3_style_path),
+            ] {
+                let signing_params = signing_params(&identity, settings.clone());
+                let creq = CanonicalRequest::from(&req, &signing_params).unwrap();
+                assert_eq!(expected, creq.path, "signing `{path}` with {settings:?}");
+            }
+        }
+    }
+
     #[test]
     fn test_tilde_in_uri() {
         let req = http0::Request::builder()
@@ -41,6 +41,11 @@ pub struct SigningSettings {
     /// Some services require an alternative session token header or query param instead of
     /// `x-amz-security-token` or `X-Amz-Security-Token`.
     pub session_token_name_override: Option<&'static str>,
+
+    /// Whether to keep the canonical request and string to sign in the returned
+    /// [`SigningInstructions`](crate::http_request::SigningInstructions), so that signature
+    /// mismatches can be debugged. Off by default.
+    pub retain_debug_artifacts: bool,
 }
 
 /// HTTP payload checksum type
@@ -138,6 +143,7 @@ impl Default for SigningSettings {
             uri_path_normalization_mode: UriPathNormalizationMode::Enabled,
             session_token_mode: SessionTokenMode::Include,
             session_token_name_override: None,
+            retain_debug_artifacts: false,
         }
     }
 }
@@ -9,7 +9,7 @@ use crate::http_request::canonical_request::header;
 use crate::http_request::canonical_request::param;
 use crate::http_request::canonical_request::{CanonicalRequest, StringToSign};
 use crate::http_request::error::CanonicalRequestError;
-use crate::http_request::SigningParams;
+use crate::http_request::{SigningParams, SigningSettings};
 use crate::sign::v4;
 #[cfg(feature = "sigv4a")]
 use crate::sign::v4a;
@@ -131,6 +131,58 @@ impl SignableBody<'_> {
 pub struct SigningInstructions {
     headers: Vec<Header>,
     params: Vec<(&'static str, Cow<'static, str>)>,
+    debug_artifacts: Option<SigningDebugArtifacts>,
+}
+
+/// The intermediate values that a signature was calculated from.
+///
+/// These are only retained when [`SigningSettings::retain_debug_artifacts`](crate::http_request::SigningSettings::retain_debug_artifacts)
+/// is set. They contain no key material, and any session token is redacted, so they are safe
+/// to log. Comparing them with the values a service reports is the quickest way to find out
+/// why a signature didn't match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SigningDebugArtifacts {
+    canonical_request: String,
+    string_to_sign: String,
+}
+
+impl SigningDebugArtifacts {
+    /// Creates new debug artifacts.
+    pub fn new(canonical_request: impl Into<String>, string_to_sign: impl Into<String>) -> Self {
+        Self {
+            canonical_request: canonical_request.into(),
+            string_to_sign: string_to_sign.into(),
+        }
+    }
+
+    /// Returns the canonical request that was signed.
+    pub fn canonical_request(&self) -> &str {
+        &self.canonical_request
+    }
+
+    /// Returns the string to sign that was derived from the canonical request.
+    pub fn string_to_sign(&self) -> &str {
+        &self.string_to_sign
+    }
+
+    fn collect(
+        settings: &SigningSettings,
+        session_token: Option<&str>,
+        canonical_request: &CanonicalRequest<'_>,
+        string_to_sign: &str,
+    ) -> Option<Self> {
+        if !settings.retain_debug_artifacts {
+            return None;
+        }
+        let mut canonical_request = canonical_request.to_string();
+        if let Some(session_token) = session_token {
+            let encoded = aws_smithy_http::query::fmt_string(session_token);
+            for token in [session_token, encoded.as_str()] {
+                canonical_request = canonical_request.replace(token, "** REDACTED **");
+            }
+        }
+        Some(Self::new(canonical_request, string_to_sign))
+    }
 }
 
 /// Header representation for use in [`SigningInstructions`]
@@ -173,7 +225,16 @@ impl Header {
 
 impl SigningInstructions {
     fn new(headers: Vec<Header>, params: Vec<(&'static str, Cow<'static, str>)>) -> Self {
-        Self { headers, params }
+        Self {
+            headers,
+            params,
+            debug_artifacts: None,
+        }
+    }
+
+    fn with_debug_artifacts(mut self, debug_artifacts: Option<SigningDebugArtifacts>) -> Self {
+        self.debug_artifacts = debug_artifacts;
+        self
     }
 
     /// Returns the headers and query params that should be applied to this request
@@ -193,6 +254,14 @@ impl SigningInstructions {
         self.params.as_slice()
     }
 
+    /// Returns the canonical request and string to sign that the signature was calculated from.
+    ///
+    /// This is only set when [`SigningSettings::retain_debug_artifacts`](crate::http_request::SigningSettings::retain_debug_artifacts)
+    /// is enabled.
+    pub fn debug_artifacts(&self) -> Option<&SigningDebugArtifacts> {
+        self.debug_artifacts.as_ref()
+    }
+
     #[cfg(any(feature = "http0-compat", test))]
     /// Applies the instructions to the given `request`.
     pub fn apply_to_request_http0x<B>(self, request: &mut http0::Request<B>) {
@@ -250,17 +319,18 @@ pub fn sign<'a>(
     tracing::trace!(request = ?request, params = ?params, "signing request");
     match params.settings().signature_location {
         SignatureLocation::Headers => {
-            let (signing_headers, signature) =
-                calculate_signing_headers(&request, params)?.into_parts();
+            let (output, debug_artifacts) = calculate_signing_headers(&request, params)?;
+            let (signing_headers, signature) = output.into_parts();
             Ok(SigningOutput::new(
-                SigningInstructions::new(signing_headers, vec![]),
+                SigningInstructions::new(signing_headers, vec![])
+                    .with_debug_artifacts(debug_artifacts),
                 signature,
             ))
         }
         SignatureLocation::QueryParams => {
-            let (params, signature) = calculate_signing_params(&request, params)?;
+            let (params, signature, debug_artifacts) = calculate_signing_params(&request, params)?;
             Ok(SigningOutput::new(
-                SigningInstructions::new(vec![], params),
+                SigningInstructions::new(vec![], params).with_debug_artifacts(debug_artifacts),
                 signature,
             ))
         }
@@ -272,7 +342,7 @@ type CalculatedParams = Vec<(&'static str, Cow<'static, str>)>;
 fn calculate_signing_params<'a>(
     request: &'a SignableRequest<'a>,
     params: &'a SigningParams<'a>,
-) -> Result<(CalculatedParams, String), SigningError> {
+) -> Result<(CalculatedParams, String, Option<SigningDebugArtifacts>), SigningError> {
     let creds = params.credentials()?;
     let creq = CanonicalRequest::from(request, params)?;
     let encoded_creq = &v4::sha256_hex_string(creq.to_string().as_bytes());
@@ -304,6 +374,12 @@ fn calculate_signing_params<'a>(
         }
     };
     tracing::trace!(canonical_request = %creq, string_to_sign = %string_to_sign, "calculated signing parameters");
+    let debug_artifacts = SigningDebugArtifacts::collect(
+        params.settings(),
+        creds.session_token(),
+        &creq,
+        &string_to_sign,
+    );
 
     let values = creq.values.into_query_params().expect("signing with query");
     let mut signing_params = vec![
@@ -338,7 +414,7 @@ fn calculate_signing_params<'a>(
         ));
     }
 
-    Ok((signing_params, signature))
+    Ok((signing_params, signature, debug_artifacts))
 }
 
 /// Calculates the signature headers that need to get added to the given `request`.
@@ -350,7 +426,7 @@ fn calculate_signing_params<'a>(
 fn calculate_signing_headers<'a>(
     request: &'a SignableRequest<'a>,
     params: &'a SigningParams<'a>,
-) -> Result<SigningOutput<Vec<Header>>, SigningError> {
+) -> Result<(SigningOutput<Vec<Header>>, Option<SigningDebugArtifacts>), SigningError> {
     let creds = params.credentials()?;
 
     // Step 1: https://docs.aws.amazon.com/en_pv/general/latest/gr/sigv4-create-canonical-request.html.
@@ -360,7 +436,7 @@ fn calculate_signing_headers<'a>(
     tracing::trace!(canonical_request = %creq);
     let mut headers = vec![];
 
-    let signature = match params {
+    let (signature, debug_artifacts) = match params {
         SigningParams::V4(params) => {
             let sts = StringToSign::new_v4(
                 params.time,
@@ -376,7 +452,14 @@ fn calculate_signing_headers<'a>(
                 params.region,
                 params.name,
             );
-            let signature = v4::calculate_signature(signing_key, sts.to_string().as_bytes());
+            let string_to_sign = sts.to_string();
+            let signature = v4::calculate_signature(signing_key, string_to_sign.as_bytes());
+            let debug_artifacts = SigningDebugArtifacts::collect(
+                &params.settings,
+                creds.session_token(),
+                &creq,
+                &string_to_sign,
+            );
 
             // Step 4: https://docs.aws.amazon.com/en_pv/general/latest/gr/sigv4-add-signature-to-request.html
             let values = creq.values.as_headers().expect("signing with headers");
@@ -412,7 +495,7 @@ fn calculate_signing_headers<'a>(
                     true,
                 );
             }
-            signature
+            (signature, debug_artifacts)
         }
         #[cfg(feature = "sigv4a")]
         SigningParams::V4a(params) => {
@@ -425,7 +508,14 @@ fn calculate_signing_headers<'a>(
 
             let signing_key =
                 v4a::generate_signing_key(creds.access_key_id(), creds.secret_access_key());
-            let signature = v4a::calculate_signature(&signing_key, sts.to_string().as_bytes());
+            let string_to_sign = sts.to_string();
+            let signature = v4a::calculate_signature(&signing_key, string_to_sign.as_bytes());
+            let debug_artifacts = SigningDebugArtifacts::collect(
+                &params.settings,
+                creds.session_token(),
+                &creq,
+                &string_to_sign,
+            );
 
             let values = creq.values.as_headers().expect("signing with headers");
             add_header(&mut headers, header::X_AMZ_DATE, &values.date_time, false);
@@ -464,11 +554,11 @@ fn calculate_signing_headers<'a>(
                     true,
                 );
             }
-            signature
+            (signature, debug_artifacts)
         }
     };
 
-    Ok(SigningOutput::new(headers, signature))
+    Ok((SigningOutput::new(headers, signature), debug_artifacts))
 }
 
 fn add_header(map: &mut Vec<Header>, key: &'static str, value: &str, sensitive: bool) {
@@ -504,6 +594,7 @@ fn build_authorization_header(
 #[cfg(test)]
 mod tests {
     use crate::date_time::test_parsers::parse_date_time;
+    use crate::http_request::canonical_request::CanonicalRequest;
     use crate::http_request::sign::{add_header, SignableRequest};
     use crate::http_request::{
         sign, test, SessionTokenMode, SignableBody, SignatureLocation, SigningInstructions,
@@ -565,6 +656,243 @@ mod tests {
         assert_req_eq!(expected, signed);
     }
 
+    mod sigv4_tests {
+        use super::*;
+        use crate::http_request::canonical_request::StringToSign;
+        use crate::http_request::PercentEncodingMode;
+
+        fn run_v4_test_suite(test_name: &str) {
+            run_v4_test_suite_with_session_token(test_name, None, SessionTokenMode::Include)
+        }
+
+        /// Runs a test case of the SigV4 test suite, checking the canonical request, the string to
+        /// sign and the `Authorization` header against the ones it expects.
+        fn run_v4_test_suite_with_session_token(
+            test_name: &str,
+            session_token: Option<&str>,
+            session_token_mode: SessionTokenMode,
+        ) {
+            let identity = Credentials::new(
+                test::v4::ACCESS_KEY_ID,
+                test::v4::SECRET_ACCESS_KEY,
+                session_token.map(str::to_string),
+                None,
+                "test",
+            )
+            .into();
+            let settings = SigningSettings {
+                // The test harness already percent-encodes the path of the requests, see `test::parse_request`
+                percent_encoding_mode: PercentEncodingMode::Single,
+                session_token_mode,
+                ..Default::default()
+            };
+            let time = parse_date_time("20150830T123600Z").unwrap();
+            let params = v4::SigningParams {
+                identity: &identity,
+                region: "us-east-1",
+                name: "service",
+                time,
+                settings,
+            }
+            .into();
+
+            let req = test::v4::test_request(test_name);
+            let signable_req = SignableRequest::from(&req);
+            let actual_creq = CanonicalRequest::from(&signable_req, &params)
+                .unwrap()
+                .to_string();
+            assert_eq!(
+                test::v4::test_canonical_request(test_name),
+                actual_creq,
+                "creq didn't match"
+            );
+
+            let hashed_creq = v4::sha256_hex_string(actual_creq.as_bytes());
+            let actual_string_to_sign =
+                StringToSign::new_v4(time, "us-east-1", "service", &hashed_creq).to_string();
+            assert_eq!(
+                test::v4::test_sts(test_name),
+                actual_string_to_sign,
+                "'string to sign' didn't match"
+            );
+
+            let out = sign(signable_req, &params).unwrap();
+            let (_, actual_authz) = out
+                .output
+                .headers()
+                .find(|(name, _)| *name == "authorization")
+                .expect("the request is signed with headers");
+            assert_eq!(
+                test::v4::test_authz(test_name),
+                actual_authz,
+                "authorization header didn't match"
+            );
+        }
+
+        #[test]
+        fn test_get_header_key_duplicate() {
+            run_v4_test_suite("get-header-key-duplicate");
+        }
+
+        #[test]
+        fn test_get_header_value_multiline() {
+            run_v4_test_suite("get-header-value-multiline");
+        }
+
+        #[test]
+        fn test_get_header_value_order() {
+            run_v4_test_suite("get-header-value-order");
+        }
+
+        #[test]
+        fn test_get_header_value_trim() {
+            run_v4_test_suite("get-header-value-trim");
+        }
+
+        #[test]
+        fn test_get_unreserved() {
+            run_v4_test_suite("get-unreserved");
+        }
+
+        #[test]
+        fn test_get_utf8() {
+            run_v4_test_suite("get-utf8");
+        }
+
+        #[test]
+        fn test_get_vanilla() {
+            run_v4_test_suite("get-vanilla");
+        }
+
+        #[test]
+        fn test_get_vanilla_empty_query_key() {
+            run_v4_test_suite("get-vanilla-empty-query-key");
+        }
+
+        #[test]
+        fn test_get_vanilla_query() {
+            run_v4_test_suite("get-vanilla-query");
+        }
+
+        #[test]
+        fn test_get_vanilla_query_order_key() {
+            run_v4_test_suite("get-vanilla-query-order-key");
+        }
+
+        #[test]
+        fn test_get_vanilla_query_order_key_case() {
+            run_v4_test_suite("get-vanilla-query-order-key-case");
+        }
+
+        #[test]
+        fn test_get_vanilla_query_order_value() {
+            run_v4_test_suite("get-vanilla-query-order-value");
+        }
+
+        #[test]
+        fn test_get_vanilla_query_unreserved() {
+            run_v4_test_suite("get-vanilla-query-unreserved");
+        }
+
+        #[test]
+        fn test_get_vanilla_utf8_query() {
+            run_v4_test_suite("get-vanilla-utf8-query");
+        }
+
+        #[test]
+        fn test_get_relative() {
+            run_v4_test_suite("normalize-path/get-relative");
+        }
+
+        #[test]
+        fn test_get_relative_relative() {
+            run_v4_test_suite("normalize-path/get-relative-relative");
+        }
+
+        #[test]
+        fn test_get_slash() {
+            run_v4_test_suite("normalize-path/get-slash");
+        }
+
+        #[test]
+        fn test_get_slash_dot_slash() {
+            run_v4_test_suite("normalize-path/get-slash-dot-slash");
+        }
+
+        #[test]
+        fn test_get_slash_pointless_dot() {
+            run_v4_test_suite("normalize-path/get-slash-pointless-dot");
+        }
+
+        #[test]
+        fn test_get_slashes() {
+            run_v4_test_suite("normalize-path/get-slashes");
+        }
+
+        #[test]
+        fn test_get_space() {
+            run_v4_test_suite("normalize-path/get-space");
+        }
+
+        #[test]
+        fn test_post_header_key_case() {
+            run_v4_test_suite("post-header-key-case");
+        }
+
+        #[test]
+        fn test_post_header_key_sort() {
+            run_v4_test_suite("post-header-key-sort");
+        }
+
+        #[test]
+        fn test_post_header_value_case() {
+            run_v4_test_suite("post-header-value-case");
+        }
+
+        #[test]
+        fn test_post_sts_header_after() {
+            run_v4_test_suite_with_session_token(
+                "post-sts-token/post-sts-header-after",
+                Some(test::v4::SESSION_TOKEN),
+                SessionTokenMode::Exclude,
+            );
+        }
+
+        #[test]
+        fn test_post_sts_header_before() {
+            run_v4_test_suite_with_session_token(
+                "post-sts-token/post-sts-header-before",
+                Some(test::v4::SESSION_TOKEN),
+                SessionTokenMode::Include,
+            );
+        }
+
+        #[test]
+        fn test_post_vanilla() {
+            run_v4_test_suite("post-vanilla");
+        }
+
+        #[test]
+        fn test_post_vanilla_empty_query_value() {
+            run_v4_test_suite("post-vanilla-empty-query-value");
+        }
+
+        #[test]
+        fn test_post_vanilla_query() {
+            run_v4_test_suite("post-vanilla-query");
+        }
+
+        #[test]
+        fn test_post_x_www_form_urlencoded() {
+            run_v4_test_suite("post-x-www-form-urlencoded");
+        }
+
+        #[test]
+        fn test_post_x_www_form_urlencoded_parameters() {
+            run_v4_test_suite("post-x-www-form-urlencoded-parameters");
+        }
+    }
+
     #[cfg(feature = "sigv4a")]
     mod sigv4a_tests {
         use super::*;
@@ -999,6 +1327,86 @@ mod tests {
         assert_req_eq!(http: expected, signed);
     }
 
+    #[test]
+    fn debug_artifacts_are_only_retained_when_enabled() {
+        let identity = &Credentials::for_tests().into();
+        let signing_params = |settings| {
+            v4::SigningParams {
+                identity,
+                region: "us-east-1",
+                name: "service",
+                time: parse_date_time("20150830T123600Z").unwrap(),
+                settings,
+            }
+            .into()
+        };
+        let original = test::v4::test_request("get-vanilla-query-order-key-case");
+
+        let out = sign(
+            SignableRequest::from(&original),
+            &signing_params(SigningSettings::default()),
+        )
+        .unwrap();
+        assert_eq!(None, out.output.debug_artifacts());
+
+        for signature_location in [SignatureLocation::Headers, SignatureLocation::QueryParams] {
+            let settings = SigningSettings {
+                signature_location,
+                expires_in: Some(Duration::from_secs(35)),
+                retain_debug_artifacts: true,
+                ..Default::default()
+            };
+            let params = signing_params(settings);
+            let out = sign(SignableRequest::from(&original), &params).unwrap();
+            let artifacts = out.output.debug_artifacts().expect("retained");
+
+            let expected_creq = CanonicalRequest::from(&SignableRequest::from(&original), &params)
+                .unwrap()
+                .to_string();
+            assert_eq!(expected_creq, artifacts.canonical_request());
+            assert_eq!(
+                format!(
+                    "AWS4-HMAC-SHA256\n20150830T123600Z\n20150830/us-east-1/service/aws4_request\n{}",
+                    v4::sha256_hex_string(expected_creq.as_bytes())
+                ),
+                artifacts.string_to_sign()
+            );
+        }
+    }
+
+    #[test]
+    fn debug_artifacts_redact_the_session_token() {
+        let identity = &Credentials::for_tests_with_session_token().into();
+        for signature_location in [SignatureLocation::Headers, SignatureLocation::QueryParams] {
+            let settings = SigningSettings {
+                signature_location,
+                expires_in: Some(Duration::from_secs(35)),
+                retain_debug_artifacts: true,
+                ..Default::default()
+            };
+            let params = v4::SigningParams {
+                identity,
+                region: "us-east-1",
+                name: "service",
+                time: parse_date_time("20150830T123600Z").unwrap(),
+                settings,
+            }
+            .into();
+            let original = test::v4::test_request("get-vanilla-query-order-key-case");
+            let out = sign(SignableRequest::from(&original), &params).unwrap();
+
+            let canonical_request = out.output.debug_artifacts().unwrap().canonical_request();
+            assert!(
+                !canonical_request.contains("notarealsessiontoken"),
+                "{canonical_request}"
+            );
+            assert!(
+                canonical_request.contains("** REDACTED **"),
+                "{canonical_request}"
+            );
+        }
+    }
+
     #[test]
     fn test_sign_headers_space_trimming() {
         let settings = SigningSettings::default();
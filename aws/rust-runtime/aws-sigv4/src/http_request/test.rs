@@ -7,13 +7,23 @@
 
 use crate::http_request::{SignableBody, SignableRequest};
 use http0::{Method, Uri};
+use percent_encoding::{utf8_percent_encode, CONTROLS};
 use std::error::Error as StdError;
 
 pub(crate) mod v4 {
     use super::*;
 
+    /// The access key of the credentials the test suite is signed with.
+    pub(crate) const ACCESS_KEY_ID: &str = "AKIDEXAMPLE";
+    /// The secret key of the credentials the test suite is signed with.
+    pub(crate) const SECRET_ACCESS_KEY: &str = "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY";
+    /// The session token used by the `post-sts-token` test cases.
+    pub(crate) const SESSION_TOKEN: &str = "AQoDYXdzEPT//////////wEXAMPLEtc764bNrC9SAPBSM22wDOk4x4HIZ8j4FZTwdQWLWsKWHGBuFqwAeMicRXmxfpSPfIeoIYRqTflfKD8YUuwthAx7mSEI/qkPpKPi/kMcGdQrmGdeehM4IC1NtBmUpp2wUE8phUZampKsburEDy0KPkyQDYwT7WZ0wq5VSXDvp75YU9HFvlRd8Tx6q6fE8YQcHNVXAkiY9q6d+xo0rKwT38xVqr7ZD0u0iPPkUL64lIZbqBAz+scqKmlzm8FDrypNC9Yjc8fPOLn9FX9KSYvKTr4rvx3iSIlTJabIQwj2ICCR/oLxBA==";
+
     fn path(name: &str, ext: &str) -> String {
-        format!("aws-sig-v4-test-suite/{}/{}.{}", name, name, ext)
+        // Some test cases are grouped into a directory, e.g. `normalize-path/get-slash`
+        let file_name = name.rsplit('/').next().unwrap_or(name);
+        format!("aws-sig-v4-test-suite/{}/{}.{}", name, file_name, ext)
     }
 
     pub(crate) fn test_canonical_request(name: &str) -> String {
@@ -25,6 +35,10 @@ pub(crate) mod v4 {
         read(&path(name, "sts"))
     }
 
+    pub(crate) fn test_authz(name: &str) -> String {
+        read(&path(name, "authz"))
+    }
+
     pub(crate) fn test_request(name: &str) -> TestRequest {
         test_parsed_request(name, "req")
     }
@@ -54,6 +68,39 @@ pub(crate) mod v4 {
     fn test_read_query_params() {
         test_request("get-vanilla-query-order-key-case");
     }
+
+    #[test]
+    fn test_parse_body() {
+        let req = test_request("post-x-www-form-urlencoded-parameters");
+        assert_eq!(
+            req.body.as_signable_body(),
+            SignableBody::Bytes(b"Param1=value1")
+        );
+    }
+
+    #[test]
+    fn test_parse_multiline_header() {
+        let req = test_request("get-header-value-multiline");
+        let values: Vec<_> = req
+            .headers
+            .iter()
+            .filter(|(name, _)| name == "My-Header1")
+            .map(|(_, value)| value.as_str())
+            .collect();
+        assert_eq!(values, ["value1", "value2", "value3"]);
+    }
+
+    #[test]
+    fn test_parse_unencoded_path() {
+        assert_eq!(
+            test_request("normalize-path/get-space").uri,
+            "https://example.amazonaws.com/example%20space/"
+        );
+        assert_eq!(
+            test_request("get-utf8").uri,
+            "https://example.amazonaws.com/%E1%88%B4"
+        );
+    }
 }
 
 #[cfg(feature = "sigv4a")]
@@ -96,7 +143,11 @@ pub(crate) mod v4a {
 
     fn test_parsed_request(path: &str) -> TestRequest {
         match parse_request(read(path).as_bytes()) {
-            Ok(parsed) => parsed,
+            Ok(mut parsed) => {
+                // The canonical requests of this test suite are computed without the request body
+                parsed.body = TestSignedBody::Bytes(vec![]);
+                parsed
+            }
             Err(err) => panic!("Failed to parse {}: {}", path, err),
         }
     }
@@ -311,13 +362,46 @@ impl<'a> From<&'a TestRequest> for SignableRequest<'a> {
     }
 }
 
+/// Parses a request of the test suite, along with its body.
+///
+/// The test suite writes requests the way they are canonicalized rather than the way they are sent,
+/// so they are first normalized into valid HTTP/1.1:
+/// - The request target is percent-encoded once, like an HTTP client would before sending it, since
+///   it can contain characters that aren't allowed in a URI (`get-space`, `get-utf8`).
+/// - Header values folded over several lines (`get-header-value-multiline`) become one header per
+///   line, which is how the test suite canonicalizes them.
 fn parse_request(s: &[u8]) -> Result<TestRequest, Box<dyn StdError + Send + Sync + 'static>> {
-    let mut headers = [httparse::EMPTY_HEADER; 64];
+    let s = std::str::from_utf8(s)?;
+    let (head, body) = s.split_once("\n\n").unwrap_or((s, ""));
+    let mut lines = head.lines();
+    let request_line = lines.next().ok_or("the request is empty")?;
+    let (method, request_line) = request_line
+        .split_once(' ')
+        .ok_or("the request line has no method")?;
+    let (target, version) = request_line
+        .rsplit_once(' ')
+        .ok_or("the request line has no version")?;
+    let mut normalized = format!(
+        "{method} {} {version}\n",
+        utf8_percent_encode(target, &CONTROLS.add(b' '))
+    );
+    let mut header_name = None;
+    for line in lines {
+        if line.starts_with(char::is_whitespace) {
+            let name = header_name.ok_or("a folded header value has no header")?;
+            normalized.push_str(&format!("{}:{}\n", name, line.trim()));
+        } else {
+            header_name = line.split_once(':').map(|(name, _)| name);
+            normalized.push_str(line);
+            normalized.push('\n');
+        }
+    }
     // httparse 1.5 requires two trailing newlines to head the header section.
-    let mut with_newline = Vec::from(s);
-    with_newline.push(b'\n');
+    normalized.push('\n');
+
+    let mut headers = [httparse::EMPTY_HEADER; 64];
     let mut req = httparse::Request::new(&mut headers);
-    let _ = req.parse(&with_newline).unwrap();
+    let _ = req.parse(normalized.as_bytes()).unwrap();
 
     let mut uri_builder = Uri::builder().scheme("https");
     if let Some(path) = req.path {
@@ -341,7 +425,7 @@ fn parse_request(s: &[u8]) -> Result<TestRequest, Box<dyn StdError + Send + Sync
         uri: uri_builder.build()?.to_string(),
         method: req.method.unwrap().to_string(),
         headers,
-        body: TestSignedBody::Bytes(vec![]),
+        body: TestSignedBody::Bytes(body.as_bytes().to_vec()),
     })
 }
 
@@ -10,8 +10,10 @@ use sha2::{Digest, Sha256};
 use std::time::SystemTime;
 
 /// HashedPayload = Lowercase(HexEncode(Hash(requestPayload)))
-#[allow(dead_code)] // Unused when compiling without certain features
-pub(crate) fn sha256_hex_string(bytes: impl AsRef<[u8]>) -> String {
+///
+/// This is the value expected by `SignableBody::Precomputed` when the payload hash is
+/// computed ahead of signing.
+pub fn sha256_hex_string(bytes: impl AsRef<[u8]>) -> String {
     let mut hasher = Sha256::new();
     hasher.update(bytes);
     hex::encode(hasher.finalize_fixed())
@@ -24,6 +24,7 @@ impl<E> RequestId for SdkError<E, Response> {
         match self {
             Self::ResponseError(err) => err.raw().headers().request_id(),
             Self::ServiceError(err) => err.raw().headers().request_id(),
+            Self::PreconditionFailed(err) => err.raw().headers().request_id(),
             _ => None,
         }
     }
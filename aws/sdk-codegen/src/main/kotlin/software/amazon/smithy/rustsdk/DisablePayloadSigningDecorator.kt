@@ -53,6 +53,26 @@ class DisablePayloadSigningDecorator : ClientCodegenDecorator {
                                         pub fn disable_payload_signing(self) -> Self {
                                             self.runtime_plugin(#{PayloadSigningOverrideRuntimePlugin}::unsigned())
                                         }
+
+                                        /// Sign this request with a precomputed payload hash instead of hashing the request body.
+                                        ///
+                                        /// `sha256_hex` must be the SHA-256 of the request body, lowercase hex encoded.
+                                        /// It is **not** checked against the body, so an incorrect hash will cause the
+                                        /// service to reject the request. Use [`validated_payload_hash`](Self::validated_payload_hash)
+                                        /// to check it.
+                                        pub fn payload_hash(self, sha256_hex: impl #{Into}<#{String}>) -> Self {
+                                            self.runtime_plugin(#{PayloadSigningOverrideRuntimePlugin}::precomputed(sha256_hex))
+                                        }
+
+                                        /// Sign this request with a precomputed payload hash, after checking it against the request body.
+                                        ///
+                                        /// `sha256_hex` must be the SHA-256 of the request body, lowercase hex encoded. If the body
+                                        /// is in memory and its hash doesn't match, the request fails before it's sent. Since this
+                                        /// hashes the body anyway, it's meant to catch incorrect hashes, e.g. in tests, rather than
+                                        /// to save the cost of hashing.
+                                        pub fn validated_payload_hash(self, sha256_hex: impl #{Into}<#{String}>) -> Self {
+                                            self.runtime_plugin(#{PayloadSigningOverrideRuntimePlugin}::precomputed(sha256_hex).with_validation(true))
+                                        }
                                         """,
                                         *preludeScope,
                                         "PayloadSigningOverrideRuntimePlugin" to
@@ -8,14 +8,17 @@ package software.amazon.smithy.rustsdk
 import software.amazon.smithy.model.knowledge.TopDownIndex
 import software.amazon.smithy.model.traits.RequestCompressionTrait
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
 import software.amazon.smithy.rust.codegen.core.smithy.customize.AdHocCustomization
 import software.amazon.smithy.rust.codegen.core.smithy.customize.adhocCustomization
 import software.amazon.smithy.rust.codegen.core.util.thenSingletonListOf
@@ -40,6 +43,21 @@ class HttpRequestCompressionDecorator : ClientCodegenDecorator {
             }
     }
 
+    override fun extras(
+        codegenContext: ClientCodegenContext,
+        rustCrate: RustCrate,
+    ) {
+        if (usesRequestCompression(codegenContext)) {
+            rustCrate.withModule(ClientRustModule.config) {
+                // Re-export the compression policy so that it can be configured without an explicit dependency
+                rustTemplate(
+                    "pub use #{policy} as request_compression;",
+                    "policy" to CargoDependency.smithyCompression(codegenContext.runtimeConfig).toType().resolve("policy"),
+                )
+            }
+        }
+    }
+
     override fun extraSections(codegenContext: ClientCodegenContext): List<AdHocCustomization> {
         return usesRequestCompression(codegenContext).thenSingletonListOf {
             adhocCustomization<SdkConfigSection.CopySdkConfigToClientConfig> { section ->
@@ -62,6 +80,7 @@ class HttpRequestCompressionConfigCustomization(codegenContext: ClientCodegenCon
         arrayOf(
             "DisableRequestCompression" to RuntimeType.clientRequestCompression(runtimeConfig).resolve("DisableRequestCompression"),
             "RequestMinCompressionSizeBytes" to RuntimeType.clientRequestCompression(runtimeConfig).resolve("RequestMinCompressionSizeBytes"),
+            "CompressionPolicy" to CargoDependency.smithyCompression(runtimeConfig).toType().resolve("policy::CompressionPolicy"),
             "Storable" to RuntimeType.smithyTypes(runtimeConfig).resolve("config_bag::Storable"),
             "StoreReplace" to RuntimeType.smithyTypes(runtimeConfig).resolve("config_bag::StoreReplace"),
             *preludeScope,
@@ -82,6 +101,11 @@ class HttpRequestCompressionConfigCustomization(codegenContext: ClientCodegenCon
                         pub fn request_min_compression_size_bytes(&self) -> #{Option}<u32> {
                             self.config.load::<#{RequestMinCompressionSizeBytes}>().map(|it| it.0)
                         }
+
+                        /// Returns the request compression policy, if it was provided.
+                        pub fn request_compression_policy(&self) -> #{Option}<&#{CompressionPolicy}> {
+                            self.config.load::<#{CompressionPolicy}>()
+                        }
                         """,
                         *codegenScope,
                     )
@@ -101,6 +125,15 @@ class HttpRequestCompressionConfigCustomization(codegenContext: ClientCodegenCon
                             self.set_request_min_compression_size_bytes(request_min_compression_size_bytes.into());
                             self
                         }
+
+                        /// Sets the policy deciding which requests are worth compressing.
+                        ///
+                        /// By default, requests whose content is already compressed, such as `image/png`, aren't compressed again.
+                        /// See [`CompressionPolicy`](crate::config::request_compression::CompressionPolicy) for the other options.
+                        pub fn request_compression_policy(mut self, request_compression_policy: #{CompressionPolicy}) -> Self {
+                            self.set_request_compression_policy(#{Some}(request_compression_policy));
+                            self
+                        }
                         """,
                         *codegenScope,
                     )
@@ -118,6 +151,12 @@ class HttpRequestCompressionConfigCustomization(codegenContext: ClientCodegenCon
                             self.config.store_or_unset::<#{RequestMinCompressionSizeBytes}>(request_min_compression_size_bytes.map(Into::into));
                             self
                         }
+
+                        /// Sets the policy deciding which requests are worth compressing.
+                        pub fn set_request_compression_policy(&mut self, request_compression_policy: #{Option}<#{CompressionPolicy}>) -> &mut Self {
+                            self.config.store_or_unset(request_compression_policy);
+                            self
+                        }
                         """,
                         *codegenScope,
                     )
@@ -130,6 +169,8 @@ class HttpRequestCompressionConfigCustomization(codegenContext: ClientCodegenCon
                             ${section.configBag}.load::<#{DisableRequestCompression}>().cloned().map(|it| it.0));
                         ${section.builder}.set_request_min_compression_size_bytes(
                             ${section.configBag}.load::<#{RequestMinCompressionSizeBytes}>().cloned().map(|it| it.0));
+                        ${section.builder}.set_request_compression_policy(
+                            ${section.configBag}.load::<#{CompressionPolicy}>().cloned());
                         """,
                         *codegenScope,
                     )
@@ -14,6 +14,7 @@ import software.amazon.smithy.model.shapes.ShapeId
 import software.amazon.smithy.rulesengine.language.EndpointRuleSet
 import software.amazon.smithy.rulesengine.traits.EndpointRuleSetTrait
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
 import software.amazon.smithy.rust.codegen.client.smithy.customize.AuthSchemeOption
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ConditionalDecorator
@@ -32,6 +33,7 @@ import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
 import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.getTrait
@@ -109,12 +111,23 @@ class SigV4AuthDecorator : ConditionalDecorator(
                 codegenContext: ClientCodegenContext,
                 baseCustomizations: List<ConfigCustomization>,
             ): List<ConfigCustomization> =
-                baseCustomizations + SigV4SigningConfig(codegenContext.runtimeConfig, codegenContext.serviceShape.getTrait())
+                baseCustomizations + SigV4SigningConfig(codegenContext.runtimeConfig, codegenContext.serviceShape.getTrait()) +
+                    SigningDebugConfig(codegenContext.runtimeConfig) +
+                    PayloadSigningPolicyConfig(codegenContext.runtimeConfig)
 
             override fun extras(
                 codegenContext: ClientCodegenContext,
                 rustCrate: RustCrate,
             ) {
+                rustCrate.withModule(ClientRustModule.config) {
+                    // Re-export the payload signing policy so that it can be configured without an explicit dependency
+                    rustTemplate(
+                        "pub use #{PayloadSigningPolicy};",
+                        "PayloadSigningPolicy" to
+                            AwsRuntimeType.awsRuntime(codegenContext.runtimeConfig)
+                                .resolve("auth::PayloadSigningPolicy"),
+                    )
+                }
                 if (codegenContext.usesSigV4a()) {
                     // Add optional feature for SigV4a support
                     rustCrate.mergeFeature(Feature("sigv4a", true, listOf("aws-runtime/sigv4a")))
@@ -184,6 +197,133 @@ private class SigV4SigningConfig(
         }
 }
 
+private class SigningDebugConfig(runtimeConfig: RuntimeConfig) : ConfigCustomization() {
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "SigningDebug" to AwsRuntimeType.awsRuntime(runtimeConfig).resolve("auth::signing_debug::SigningDebug"),
+        )
+
+    override fun section(section: ServiceConfig): Writable =
+        writable {
+            when (section) {
+                ServiceConfig.ConfigImpl -> {
+                    rustTemplate(
+                        """
+                        /// Returns whether signing debug was enabled, if it was set.
+                        pub fn signing_debug(&self) -> #{Option}<bool> {
+                            self.config.load::<#{SigningDebug}>().map(|it| it.is_enabled())
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Enables or disables signing debug.
+                        ///
+                        /// When enabled, the SigV4 canonical request and string to sign are retained for each
+                        /// attempt. If the service rejects the request signature, they're added to the error
+                        /// metadata, along with the service's versions when the service returns them.
+                        /// These values contain no key material, and session tokens are redacted from them.
+                        ///
+                        /// When this isn't set, signing debug is enabled by setting the `AWS_SIGV4_DEBUG`
+                        /// environment variable to `true`.
+                        pub fn signing_debug(mut self, signing_debug: bool) -> Self {
+                            self.set_signing_debug(#{Some}(signing_debug));
+                            self
+                        }
+
+                        /// Enables or disables signing debug.
+                        ///
+                        /// See [`Self::signing_debug`] for details.
+                        pub fn set_signing_debug(&mut self, signing_debug: #{Option}<bool>) -> &mut Self {
+                            self.config.store_or_unset(signing_debug.map(#{SigningDebug}::new));
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                is ServiceConfig.BuilderFromConfigBag -> {
+                    rustTemplate(
+                        "${section.builder}.set_signing_debug(${section.configBag}.load::<#{SigningDebug}>().map(|it| it.is_enabled()));",
+                        *codegenScope,
+                    )
+                }
+
+                else -> {}
+            }
+        }
+}
+
+private class PayloadSigningPolicyConfig(runtimeConfig: RuntimeConfig) : ConfigCustomization() {
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "PayloadSigningPolicy" to AwsRuntimeType.awsRuntime(runtimeConfig).resolve("auth::PayloadSigningPolicy"),
+        )
+
+    override fun section(section: ServiceConfig): Writable =
+        writable {
+            when (section) {
+                ServiceConfig.ConfigImpl -> {
+                    rustTemplate(
+                        """
+                        /// Returns the payload signing policy, if it was set.
+                        pub fn payload_signing_policy(&self) -> #{Option}<#{PayloadSigningPolicy}> {
+                            self.config.load::<#{PayloadSigningPolicy}>().cloned()
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Sets how request payloads are signed, regardless of each operation's default.
+                        ///
+                        /// By default, a request body that is in memory is signed, and a streaming body is
+                        /// sent as `UNSIGNED-PAYLOAD`. Some operations never sign their payload. When a policy
+                        /// is set, it's used instead, and requests that can't be signed with it fail before
+                        /// they are sent: unsigned payloads require an HTTPS endpoint, and streaming payloads
+                        /// require an aws-chunked encoded request body.
+                        ///
+                        /// To set the policy of a single request, use this method on the config override of
+                        /// the request's `customize()`.
+                        pub fn payload_signing_policy(mut self, payload_signing_policy: #{PayloadSigningPolicy}) -> Self {
+                            self.set_payload_signing_policy(#{Some}(payload_signing_policy));
+                            self
+                        }
+
+                        /// Sets how request payloads are signed, regardless of each operation's default.
+                        ///
+                        /// See [`Self::payload_signing_policy`] for details.
+                        pub fn set_payload_signing_policy(&mut self, payload_signing_policy: #{Option}<#{PayloadSigningPolicy}>) -> &mut Self {
+                            self.config.store_or_unset(payload_signing_policy);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                is ServiceConfig.BuilderFromConfigBag -> {
+                    rustTemplate(
+                        "${section.builder}.set_payload_signing_policy(${section.configBag}.load::<#{PayloadSigningPolicy}>().cloned());",
+                        *codegenScope,
+                    )
+                }
+
+                else -> {}
+            }
+        }
+}
+
 private class AuthServiceRuntimePluginCustomization(private val codegenContext: ClientCodegenContext) :
     ServiceRuntimePluginCustomization() {
     private val runtimeConfig = codegenContext.runtimeConfig
@@ -192,6 +332,7 @@ private class AuthServiceRuntimePluginCustomization(private val codegenContext:
         arrayOf(
             "SigV4AuthScheme" to awsRuntime.resolve("auth::sigv4::SigV4AuthScheme"),
             "SigV4aAuthScheme" to awsRuntime.resolve("auth::sigv4a::SigV4aAuthScheme"),
+            "SigningDebugInterceptor" to awsRuntime.resolve("auth::signing_debug::SigningDebugInterceptor"),
             "SharedAuthScheme" to
                 RuntimeType.smithyRuntimeApiClient(runtimeConfig)
                     .resolve("client::auth::SharedAuthScheme"),
@@ -222,6 +363,9 @@ private class AuthServiceRuntimePluginCustomization(private val codegenContext:
                             }
                         }
                     }
+                    section.registerInterceptor(this) {
+                        rustTemplate("#{SigningDebugInterceptor}::new()", *codegenScope)
+                    }
                 }
 
                 else -> {}
@@ -257,6 +401,7 @@ private class AuthOperationCustomization(private val codegenContext: ClientCodeg
             "SigningOptions" to awsRuntime.resolve("auth::SigningOptions"),
             "SignableBody" to AwsRuntimeType.awsSigv4(runtimeConfig).resolve("http_request::SignableBody"),
             "Default" to RuntimeType.Default,
+            "populate_signing_debug" to awsRuntime.resolve("auth::signing_debug::populate_error_metadata"),
         )
     }
     private val serviceIndex = ServiceIndex.of(codegenContext.model)
@@ -301,6 +446,13 @@ private class AuthOperationCustomization(private val codegenContext: ClientCodeg
                     }
                 }
 
+                is OperationSection.PopulateErrorMetadataExtras -> {
+                    rustTemplate(
+                        "${section.builderName} = #{populate_signing_debug}(${section.builderName}, ${section.responseBodyName});",
+                        *codegenScope,
+                    )
+                }
+
                 else -> {}
             }
         }
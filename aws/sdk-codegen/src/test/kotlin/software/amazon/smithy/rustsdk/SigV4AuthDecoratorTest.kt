@@ -69,6 +69,168 @@ class SigV4AuthDecoratorTest {
         awsSdkIntegrationTest(modelWithSigV4AuthScheme) { _, _ -> }
     }
 
+    @Test
+    fun signatureErrorsIncludeCanonicalRequestWhenSigningDebugIsEnabled() {
+        awsSdkIntegrationTest(modelWithSigV4AuthScheme) { clientCodegenContext, rustCrate ->
+            val moduleUseName = clientCodegenContext.moduleUseName()
+            val rc = clientCodegenContext.runtimeConfig
+            val sigv4 = AwsRuntimeType.awsSigv4(rc)
+
+            rustCrate.integrationTest("signing_debug") {
+                Attribute.featureGate("test-util").render(this)
+                tokioTest("signature_errors_include_the_signed_canonical_request") {
+                    rustTemplate(
+                        """
+                        let (http_client, request) = #{capture_request}(Some(
+                            #{Response}::builder()
+                                .status(403)
+                                .header("x-amzn-errortype", "SignatureDoesNotMatch")
+                                .body(#{SdkBody}::from(r##"{"message":"The request signature we calculated does not match the signature you provided.\n\nThe Canonical String for this request should have been\n'POST\n/\n\nhost:example.com\n\nhost\nUNSIGNED-PAYLOAD'\n\nThe String-to-Sign should have been\n'AWS4-HMAC-SHA256\n20090213T233130Z\n20090213/us-east-1/dontcare/aws4_request\nabc'\n"}"##))
+                                .unwrap(),
+                        ));
+                        let config = $moduleUseName::Config::builder()
+                            .http_client(http_client)
+                            .endpoint_url("https://example.com")
+                            .region(#{Region}::new("us-east-1"))
+                            .behavior_version_latest()
+                            .with_test_defaults()
+                            .signing_debug(true)
+                            .build();
+                        let client = $moduleUseName::Client::from_conf(config);
+                        let err = client
+                            .some_operation()
+                            .something(#{ByteStream}::from_static(b"Hello, world!"))
+                            .send()
+                            .await
+                            .expect_err("the signature was rejected")
+                            .into_service_error();
+                        let request = request.expect_request();
+
+                        let meta = #{ProvideErrorMetadata}::meta(&err);
+                        let canonical_request = meta.extra("sigv4_canonical_request").expect("canonical request");
+                        let string_to_sign = meta.extra("sigv4_string_to_sign").expect("string to sign");
+                        assert_eq!(
+                            Some(#{sha256_hex_string}(canonical_request.as_bytes()).as_str()),
+                            string_to_sign.lines().last(),
+                        );
+                        // The string to sign is the one that the sent signature was calculated from
+                        let signing_key = #{generate_signing_key}(
+                            "notrealrnrELgWzOk3IfjzDKtFBhDby",
+                            ::std::time::UNIX_EPOCH + ::std::time::Duration::from_secs(1234567890),
+                            "us-east-1",
+                            "dontcare",
+                        );
+                        let signature = #{calculate_signature}(signing_key, string_to_sign.as_bytes());
+                        let authorization = request.headers().get("authorization").unwrap();
+                        assert!(authorization.ends_with(&::std::format!("Signature={signature}")), "{authorization}");
+
+                        assert_eq!(
+                            Some("POST\n/\n\nhost:example.com\n\nhost\nUNSIGNED-PAYLOAD"),
+                            meta.extra("sigv4_service_canonical_request"),
+                        );
+                        """,
+                        "ByteStream" to RuntimeType.byteStream(rc),
+                        "calculate_signature" to sigv4.resolve("sign::v4::calculate_signature"),
+                        "capture_request" to RuntimeType.captureRequest(rc),
+                        "generate_signing_key" to sigv4.resolve("sign::v4::generate_signing_key"),
+                        "ProvideErrorMetadata" to RuntimeType.provideErrorMetadataTrait(rc),
+                        "Region" to AwsRuntimeType.awsTypes(rc).resolve("region::Region"),
+                        "Response" to RuntimeType.HttpResponse,
+                        "SdkBody" to RuntimeType.sdkBody(rc),
+                        "sha256_hex_string" to sigv4.resolve("sign::v4::sha256_hex_string"),
+                    )
+                }
+            }
+        }
+    }
+
+    @Test
+    fun payloadSigningPolicyCanBeSetInConfigAndPerRequest() {
+        awsSdkIntegrationTest(modelWithSigV4AuthScheme) { clientCodegenContext, rustCrate ->
+            val moduleUseName = clientCodegenContext.moduleUseName()
+            val rc = clientCodegenContext.runtimeConfig
+
+            rustCrate.integrationTest("payload_signing_policy") {
+                Attribute.featureGate("test-util").render(this)
+                tokioTest("payload_signing_policy_overrides_the_operation_default") {
+                    rustTemplate(
+                        """
+                        use $moduleUseName::config::PayloadSigningPolicy;
+
+                        fn config(http_client: impl #{HttpClient} + 'static, endpoint_url: &str) -> $moduleUseName::config::Builder {
+                            $moduleUseName::Config::builder()
+                                .http_client(http_client)
+                                .endpoint_url(endpoint_url)
+                                .region(#{Region}::new("us-east-1"))
+                                .behavior_version_latest()
+                                .with_test_defaults()
+                        }
+
+                        // The operation doesn't sign its payload by default, but the client config says otherwise
+                        let (http_client, request) = #{capture_request}(None);
+                        let client = $moduleUseName::Client::from_conf(
+                            config(http_client, "https://example.com")
+                                .payload_signing_policy(PayloadSigningPolicy::Signed)
+                                .build(),
+                        );
+                        client
+                            .some_operation()
+                            .something(#{ByteStream}::from_static(b"Hello, world!"))
+                            .send()
+                            .await
+                            .expect("success");
+                        assert_eq!(
+                            Some(#{sha256_hex_string}(b"Hello, world!").as_str()),
+                            request.expect_request().headers().get("x-amz-content-sha256"),
+                        );
+
+                        // A single request can override the client config
+                        let (http_client, request) = #{capture_request}(None);
+                        let client = $moduleUseName::Client::from_conf(
+                            config(http_client, "https://example.com")
+                                .payload_signing_policy(PayloadSigningPolicy::Signed)
+                                .build(),
+                        );
+                        client
+                            .some_operation()
+                            .something(#{ByteStream}::from_static(b"Hello, world!"))
+                            .customize()
+                            .config_override($moduleUseName::Config::builder().payload_signing_policy(PayloadSigningPolicy::Unsigned))
+                            .send()
+                            .await
+                            .expect("success");
+                        assert_eq!(
+                            Some("UNSIGNED-PAYLOAD"),
+                            request.expect_request().headers().get("x-amz-content-sha256"),
+                        );
+
+                        // Unsigned payloads aren't sent over plain HTTP
+                        let (http_client, request) = #{capture_request}(None);
+                        let client = $moduleUseName::Client::from_conf(config(http_client, "http://localhost:1234").build());
+                        let err = client
+                            .some_operation()
+                            .something(#{ByteStream}::from_static(b"Hello, world!"))
+                            .customize()
+                            .config_override($moduleUseName::Config::builder().payload_signing_policy(PayloadSigningPolicy::Unsigned))
+                            .send()
+                            .await
+                            .expect_err("unsigned payloads require HTTPS");
+                        let message = #{DisplayErrorContext}(&err).to_string();
+                        assert!(message.contains("the endpoint uses plain HTTP"), "{message}");
+                        request.expect_no_request();
+                        """,
+                        "ByteStream" to RuntimeType.byteStream(rc),
+                        "capture_request" to RuntimeType.captureRequest(rc),
+                        "DisplayErrorContext" to RuntimeType.smithyTypes(rc).resolve("error::display::DisplayErrorContext"),
+                        "HttpClient" to RuntimeType.smithyRuntimeApiClient(rc).resolve("client::http::HttpClient"),
+                        "Region" to AwsRuntimeType.awsTypes(rc).resolve("region::Region"),
+                        "sha256_hex_string" to AwsRuntimeType.awsSigv4(rc).resolve("sign::v4::sha256_hex_string"),
+                    )
+                }
+            }
+        }
+    }
+
     private val modelWithSigV4aAuthScheme =
         """
         namespace test
@@ -0,0 +1,285 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Measures the overhead the client adds to a request: everything from `send()` to handing bytes
+//! to the HTTP client, and from response bytes to a typed output. The HTTP client answers from
+//! memory, so no time is spent on the network.
+
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_types::body::SdkBody;
+use bytes::Bytes;
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use sdk_overhead_bench_client::primitives::{Blob, DateTime};
+use sdk_overhead_bench_client::types::Wide;
+use sdk_overhead_bench_client::{Client, Config};
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+
+const BLOB_SIZE: usize = 1024 * 1024;
+const PAGES: usize = 10;
+const ITEMS_PER_PAGE: usize = 100;
+
+/// Iterations used to compute latency percentiles. These are fixed so that numbers are comparable
+/// across commits.
+const PERCENTILE_ITERATIONS: usize = 10_000;
+const BLOB_PERCENTILE_ITERATIONS: usize = 1_000;
+const PAGINATED_PERCENTILE_ITERATIONS: usize = 1_000;
+
+/// Canned responses, serialized once so that building them isn't measured.
+struct Responses {
+    empty: Bytes,
+    wide: Bytes,
+    blob: Bytes,
+    pages: Vec<Bytes>,
+}
+
+impl Responses {
+    fn new() -> Self {
+        let pages = (0..PAGES)
+            .map(|page| {
+                let items = (0..ITEMS_PER_PAGE)
+                    .map(|item| format!(r#"{{"id":"item-{page}-{item}","value":{item}}}"#))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                let next_token = if page + 1 < PAGES {
+                    format!(r#","nextToken":"{}""#, page + 1)
+                } else {
+                    String::new()
+                };
+                Bytes::from(format!(r#"{{"items":[{items}]{next_token}}}"#))
+            })
+            .collect();
+        Self {
+            empty: Bytes::from_static(b"{}"),
+            wide: Bytes::from(format!(r#"{{"item":{}}}"#, wide_json())),
+            blob: Bytes::from(vec![0x5a; BLOB_SIZE]),
+            pages,
+        }
+    }
+
+    fn respond(&self, request: &http::Request<SdkBody>) -> http::Response<SdkBody> {
+        let body = match request.uri().path() {
+            "/trivial" => self.empty.clone(),
+            "/large-structure" => self.wide.clone(),
+            "/blob" => self.blob.clone(),
+            "/items" => {
+                let page = request
+                    .uri()
+                    .query()
+                    .and_then(|query| {
+                        query
+                            .split('&')
+                            .find_map(|pair| pair.strip_prefix("nextToken="))
+                    })
+                    .map(|token| token.parse::<usize>().expect("valid token"))
+                    .unwrap_or_default();
+                self.pages[page].clone()
+            }
+            path => panic!("unexpected request to {path}"),
+        };
+        http::Response::builder()
+            .status(200)
+            .body(SdkBody::from(body))
+            .unwrap()
+    }
+}
+
+fn client() -> Client {
+    let responses = Responses::new();
+    let config = Config::builder()
+        .endpoint_url("https://bench.localhost")
+        .http_client(infallible_client_fn(move |request| {
+            responses.respond(&request)
+        }))
+        .build();
+    Client::from_conf(config)
+}
+
+/// A `Wide` with all 50 members set, matching [`wide_json`].
+fn wide() -> Wide {
+    let timestamp = DateTime::from_secs(1_700_000_000);
+    Wide::builder()
+        .string01("value-01")
+        .string02("value-02")
+        .string03("value-03")
+        .string04("value-04")
+        .string05("value-05")
+        .string06("value-06")
+        .string07("value-07")
+        .string08("value-08")
+        .string09("value-09")
+        .string10("value-10")
+        .string11("value-11")
+        .string12("value-12")
+        .string13("value-13")
+        .string14("value-14")
+        .string15("value-15")
+        .string16("value-16")
+        .string17("value-17")
+        .string18("value-18")
+        .string19("value-19")
+        .string20("value-20")
+        .integer01(1)
+        .integer02(2)
+        .integer03(3)
+        .integer04(4)
+        .integer05(5)
+        .integer06(6)
+        .integer07(7)
+        .integer08(8)
+        .integer09(9)
+        .integer10(10)
+        .boolean01(true)
+        .boolean02(false)
+        .boolean03(true)
+        .boolean04(false)
+        .boolean05(true)
+        .boolean06(false)
+        .boolean07(true)
+        .boolean08(false)
+        .boolean09(true)
+        .boolean10(false)
+        .double01(1.5)
+        .double02(2.5)
+        .double03(3.5)
+        .double04(4.5)
+        .double05(5.5)
+        .timestamp01(timestamp)
+        .timestamp02(timestamp)
+        .timestamp03(timestamp)
+        .timestamp04(timestamp)
+        .timestamp05(timestamp)
+        .build()
+}
+
+fn wide_json() -> String {
+    let mut members = Vec::new();
+    members.extend((1..=20).map(|i| format!(r#""string{i:02}":"value-{i:02}""#)));
+    members.extend((1..=10).map(|i| format!(r#""integer{i:02}":{i}"#)));
+    members.extend((1..=10).map(|i| format!(r#""boolean{i:02}":{}"#, i % 2 == 1)));
+    members.extend((1..=5).map(|i| format!(r#""double{i:02}":{i}.5"#)));
+    members.extend((1..=5).map(|i| format!(r#""timestamp{i:02}":1700000000"#)));
+    format!("{{{}}}", members.join(","))
+}
+
+/// Prints the p50 and p99 latency of `operation` over a fixed number of iterations.
+///
+/// Criterion reports means and medians over batches of iterations, which hide tail latency.
+fn report_percentiles<F, Fut>(runtime: &Runtime, name: &str, iterations: usize, operation: F)
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut latencies = runtime.block_on(async {
+        let mut latencies = Vec::with_capacity(iterations);
+        for _ in 0..iterations {
+            let start = Instant::now();
+            operation().await;
+            latencies.push(start.elapsed());
+        }
+        latencies
+    });
+    latencies.sort_unstable();
+    let percentile = |p: usize| latencies[(latencies.len() * p / 100).min(latencies.len() - 1)];
+    println!(
+        "{name:<40} p50: {:>12?}  p99: {:>12?}  ({iterations} iterations)",
+        percentile(50),
+        percentile(99)
+    );
+}
+
+fn bench(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let client = client();
+    let wide = wide();
+    let blob = Blob::new(vec![0xa5; BLOB_SIZE]);
+
+    let trivial = || async {
+        client.trivial().send().await.expect("success");
+    };
+    let large_structure = || async {
+        let output = client
+            .large_structure()
+            .item(wide.clone())
+            .send()
+            .await
+            .expect("success");
+        assert!(output.item().is_some());
+    };
+    let echo_blob = || async {
+        let output = client
+            .echo_blob()
+            .data(blob.clone())
+            .send()
+            .await
+            .expect("success");
+        assert_eq!(
+            Some(BLOB_SIZE),
+            output.data().map(|data| data.as_ref().len())
+        );
+    };
+    let paginated = || async {
+        let items = client
+            .list_items()
+            .max_results(ITEMS_PER_PAGE as i32)
+            .into_paginator()
+            .items()
+            .send()
+            .try_collect()
+            .await
+            .expect("success");
+        assert_eq!(PAGES * ITEMS_PER_PAGE, items.len());
+    };
+
+    let mut group = c.benchmark_group("operation_overhead");
+    group.bench_function("trivial", |b| b.to_async(&runtime).iter(trivial));
+    group.bench_function("large_structure", |b| {
+        b.to_async(&runtime).iter(large_structure)
+    });
+    group.throughput(Throughput::Bytes(2 * BLOB_SIZE as u64));
+    group.bench_function("echo_1mb_blob", |b| b.to_async(&runtime).iter(echo_blob));
+    group.throughput(Throughput::Elements((PAGES * ITEMS_PER_PAGE) as u64));
+    group.bench_function("paginated_10_pages", |b| {
+        b.to_async(&runtime).iter(paginated)
+    });
+    group.finish();
+
+    report_percentiles(&runtime, "trivial", PERCENTILE_ITERATIONS, trivial);
+    report_percentiles(
+        &runtime,
+        "large_structure",
+        PERCENTILE_ITERATIONS,
+        large_structure,
+    );
+    report_percentiles(
+        &runtime,
+        "echo_1mb_blob",
+        BLOB_PERCENTILE_ITERATIONS,
+        echo_blob,
+    );
+    report_percentiles(
+        &runtime,
+        "paginated_10_pages",
+        PAGINATED_PERCENTILE_ITERATIONS,
+        paginated,
+    );
+}
+
+fn config() -> Criterion {
+    // Fixed settings so that runs on the same machine are comparable across commits
+    Criterion::default()
+        .sample_size(100)
+        .warm_up_time(Duration::from_secs(3))
+        .measurement_time(Duration::from_secs(10))
+        .noise_threshold(0.03)
+}
+
+criterion_group! {
+    name = benches;
+    config = config();
+    targets = bench
+}
+criterion_main!(benches);
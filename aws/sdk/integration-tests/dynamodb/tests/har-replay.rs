@@ -0,0 +1,44 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_credential_types::Credentials;
+use aws_sdk_dynamodb::types::TableStatus;
+use aws_sdk_dynamodb::{Client, Config};
+use aws_smithy_runtime::client::http::test_util::har::HarImporter;
+use aws_smithy_runtime::client::http::test_util::Redactions;
+use aws_types::region::Region;
+
+/// Traffic captured with an HTTP proxy and exported as a HAR file can be replayed in tests
+#[tokio::test]
+async fn replay_har_traffic() {
+    let import = HarImporter::new()
+        .redactions(Redactions::credentials())
+        .import_file("tests/list-and-describe-tables.har")
+        .expect("valid HAR file");
+    assert!(import.warnings().is_empty(), "{:?}", import.warnings());
+    let http_client = import.into_replay_client();
+
+    let config = Config::builder()
+        .region(Region::new("us-east-1"))
+        .credentials_provider(Credentials::for_tests())
+        .http_client(http_client.clone())
+        .build();
+    let client = Client::from_conf(config);
+
+    let tables = client.list_tables().send().await.expect("success");
+    assert_eq!(&["Movies".to_string()], tables.table_names());
+    let table = client
+        .describe_table()
+        .table_name("Movies")
+        .send()
+        .await
+        .expect("success")
+        .table
+        .expect("table is set");
+    assert_eq!(Some(&TableStatus::Active), table.table_status());
+    assert_eq!(Some(2), table.item_count());
+
+    http_client.assert_requests_match(&["authorization", "x-amz-date"]);
+}
@@ -0,0 +1,85 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(feature = "test-util")]
+
+use aws_sdk_kms as kms;
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use bytes::Bytes;
+use kms::config::retry::RetryConfig;
+use kms::config::Region;
+use std::sync::{Arc, Mutex};
+
+/// Sends a `GenerateRandom` request that fails twice with a retryable error before succeeding,
+/// and returns every attempt as it was sent.
+async fn captured_attempts() -> Vec<String> {
+    let attempts = Arc::new(Mutex::new(Vec::new()));
+    let http_client = infallible_client_fn({
+        let attempts = attempts.clone();
+        move |request| {
+            let mut attempts = attempts.lock().unwrap();
+            let mut headers: Vec<_> = request
+                .headers()
+                .iter()
+                .map(|(name, value)| format!("{name}: {}", value.to_str().unwrap()))
+                .collect();
+            headers.sort();
+            attempts.push(format!(
+                "{} {}\n{}\n\n{}",
+                request.method(),
+                request.uri(),
+                headers.join("\n"),
+                std::str::from_utf8(request.body().bytes().unwrap()).unwrap()
+            ));
+            let response = match attempts.len() {
+                1 | 2 => http::Response::builder()
+                    .status(500)
+                    .body(Bytes::from_static(br#"{"__type":"KMSInternalException"}"#)),
+                _ => http::Response::builder()
+                    .status(200)
+                    .body(Bytes::from_static(br#"{"Plaintext":"aGVsbG8="}"#)),
+            };
+            response.unwrap()
+        }
+    });
+    let config = kms::Config::builder()
+        .with_test_defaults()
+        .panic_on_real_sleep(true)
+        .http_client(http_client)
+        .region(Region::new("us-east-1"))
+        .retry_config(RetryConfig::standard().with_max_attempts(3))
+        .build();
+    let client = kms::Client::from_conf(config);
+    client
+        .generate_random()
+        .number_of_bytes(5)
+        .send()
+        .await
+        .expect("the third attempt succeeds");
+
+    let attempts = attempts.lock().unwrap().clone();
+    attempts
+}
+
+#[tokio::test]
+async fn test_defaults_send_identical_requests() {
+    let first_run = captured_attempts().await;
+    let second_run = captured_attempts().await;
+
+    assert_eq!(3, first_run.len());
+    for header in [
+        "amz-sdk-invocation-id: ",
+        "amz-sdk-request: attempt=3; max=3",
+        "authorization: AWS4-HMAC-SHA256 ",
+        "x-amz-date: 20090213T233130Z",
+    ] {
+        assert!(
+            first_run[2].contains(header),
+            "expected `{header}` in {}",
+            first_run[2]
+        );
+    }
+    assert_eq!(first_run, second_run);
+}
@@ -6,8 +6,8 @@
 use aws_sdk_s3::config::IdentityCache;
 
 use aws_sdk_s3::config::{
-    retry::RetryConfig, timeout::TimeoutConfig, BehaviorVersion, Config, Credentials, Region,
-    SharedAsyncSleep, Sleep, StalledStreamProtectionConfig,
+    retry::RetryConfig, timeout::TimeoutConfig, BehaviorVersion, Config, ConfigIssueCode,
+    Credentials, Region, SharedAsyncSleep, Sleep, StalledStreamProtectionConfig,
 };
 use aws_sdk_s3::primitives::SdkBody;
 use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
@@ -76,17 +76,36 @@ async fn test_missing_behavior_version() {
         .credentials_provider(Credentials::for_tests())
         .http_client(http_client)
         .build();
+    assert!(config
+        .validation_report()
+        .codes()
+        .any(|code| code == ConfigIssueCode::MissingBehaviorVersion));
     // This line panics
     let _client = aws_sdk_s3::Client::from_conf(config);
 }
 
+/// Sends a request with a client created from `config`, which is expected to fail with the
+/// config's validation report before anything is sent.
+async fn expect_validation_failure(
+    config: Config,
+    request: aws_smithy_runtime::client::http::test_util::CaptureRequestReceiver,
+) -> String {
+    assert!(config.validation_report().has_fatal_issues());
+    // Constructing the client doesn't panic; sending a request fails instead
+    let client = aws_sdk_s3::Client::from_conf(config);
+    let err = client
+        .list_buckets()
+        .send()
+        .await
+        .expect_err("the config is invalid");
+    request.expect_no_request();
+    format!("{}", DisplayErrorContext(err))
+}
+
 #[tokio::test]
-#[should_panic(
-    expected = "Invalid client configuration: An async sleep implementation is required for retry to work."
-)]
 async fn test_missing_async_sleep_time_source_retries() {
     let _logs = capture_test_logs();
-    let (http_client, _) = capture_request(None);
+    let (http_client, request) = capture_request(None);
 
     // Configure retry and timeouts without providing a sleep impl
     let config = Config::builder()
@@ -98,24 +117,38 @@ async fn test_missing_async_sleep_time_source_retries() {
         .behavior_version(BehaviorVersion::latest())
         .build();
 
-    // should panic with a validation error
-    let _client = aws_sdk_s3::Client::from_conf(config);
+    // Every problem is reported together
+    assert_eq!(
+        vec![
+            ConfigIssueCode::RetriesEnabledWithoutSleep,
+            ConfigIssueCode::MissingSleepImpl
+        ],
+        config.validation_report().codes().collect::<Vec<_>>()
+    );
+    let msg = expect_validation_failure(config, request).await;
+    assert!(
+        msg.contains("[RetriesEnabledWithoutSleep] Retries are enabled"),
+        "{msg}"
+    );
+    assert!(
+        msg.contains("[MissingSleepImpl] An async sleep implementation is required for stalled stream protection"),
+        "{msg}"
+    );
 }
 
 #[tokio::test]
-#[should_panic(
-    expected = "Invalid client configuration: An async sleep implementation is required for timeouts to work."
-)]
 async fn test_missing_async_sleep_time_source_timeouts() {
     let _logs = capture_test_logs();
-    let (http_client, _) = capture_request(None);
+    let (http_client, request) = capture_request(None);
 
-    // Configure retry and timeouts without providing a sleep impl
+    // Configure timeouts without providing a sleep impl
     let config = Config::builder()
         .http_client(http_client)
         .region(Region::new("us-east-1"))
+        .identity_cache(IdentityCache::no_cache())
         .credentials_provider(Credentials::for_tests())
         .retry_config(RetryConfig::disabled())
+        .stalled_stream_protection(StalledStreamProtectionConfig::disabled())
         .behavior_version(BehaviorVersion::latest())
         .timeout_config(
             TimeoutConfig::builder()
@@ -124,19 +157,23 @@ async fn test_missing_async_sleep_time_source_timeouts() {
         )
         .build();
 
-    // should panic with a validation error
-    let _client = aws_sdk_s3::Client::from_conf(config);
+    assert_eq!(
+        vec![ConfigIssueCode::MissingSleepImpl],
+        config.validation_report().codes().collect::<Vec<_>>()
+    );
+    let msg = expect_validation_failure(config, request).await;
+    assert!(
+        msg.contains("An async sleep implementation is required for timeouts"),
+        "{msg}"
+    );
 }
 
 #[tokio::test]
-#[should_panic(
-    expected = "Invalid client configuration: Lazy identity caching requires an async sleep implementation to be configured."
-)]
 async fn test_time_source_for_identity_cache() {
     let _logs = capture_test_logs();
-    let (http_client, _) = capture_request(None);
+    let (http_client, request) = capture_request(None);
 
-    // Configure an identity cache without providing a sleep impl or time source
+    // Configure an identity cache without providing a sleep impl
     let config = Config::builder()
         .http_client(http_client)
         .region(Region::new("us-east-1"))
@@ -148,8 +185,66 @@ async fn test_time_source_for_identity_cache() {
         .behavior_version(BehaviorVersion::latest())
         .build();
 
-    // should panic with a validation error
-    let _client = aws_sdk_s3::Client::from_conf(config);
+    assert_eq!(
+        vec![ConfigIssueCode::MissingSleepImpl],
+        config.validation_report().codes().collect::<Vec<_>>()
+    );
+    let msg = expect_validation_failure(config, request).await;
+    assert!(
+        msg.contains("An async sleep implementation is required for identity caching"),
+        "{msg}"
+    );
+}
+
+#[tokio::test]
+async fn test_valid_config_has_an_empty_report() {
+    let (http_client, _request) = capture_request(None);
+    let config = Config::builder()
+        .http_client(http_client)
+        .region(Region::new("us-east-1"))
+        .identity_cache(IdentityCache::no_cache())
+        .credentials_provider(Credentials::for_tests())
+        .retry_config(RetryConfig::disabled())
+        .stalled_stream_protection(StalledStreamProtectionConfig::disabled())
+        .timeout_config(TimeoutConfig::disabled())
+        .behavior_version(BehaviorVersion::latest())
+        .build();
+    assert!(
+        config.validation_report().is_empty(),
+        "{}",
+        config.validation_report()
+    );
+}
+
+#[tokio::test]
+async fn test_config_override_is_validated() {
+    let (http_client, request) = capture_request(None);
+    let config = Config::builder()
+        .http_client(http_client)
+        .region(Region::new("us-east-1"))
+        .identity_cache(IdentityCache::no_cache())
+        .credentials_provider(Credentials::for_tests())
+        .retry_config(RetryConfig::disabled())
+        .stalled_stream_protection(StalledStreamProtectionConfig::disabled())
+        .timeout_config(TimeoutConfig::disabled())
+        .behavior_version(BehaviorVersion::latest())
+        .build();
+    let client = aws_sdk_s3::Client::from_conf(config);
+
+    // Enabling retries for a single operation without a sleep impl is caught too
+    let err = client
+        .list_buckets()
+        .customize()
+        .config_override(Config::builder().retry_config(RetryConfig::standard()))
+        .send()
+        .await
+        .expect_err("the overridden config is invalid");
+    request.expect_no_request();
+    let msg = format!("{}", DisplayErrorContext(err));
+    assert!(
+        msg.contains("[RetriesEnabledWithoutSleep] Retries are enabled"),
+        "{msg}"
+    );
 }
 
 #[allow(deprecated)] // intentionally testing an old behavior version
@@ -4,11 +4,12 @@
  */
 
 use std::sync::atomic::{AtomicI32, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use aws_config::{BehaviorVersion, Region};
 use aws_credential_types::{
     provider::{future::ProvideCredentials as ProvideCredentialsFuture, ProvideCredentials},
+    rotating::RotatingCredentialsProvider,
     Credentials,
 };
 use aws_sdk_s3::Client;
@@ -64,6 +65,53 @@ async fn test_identity_cache_ga_behavior_version() {
     assert_eq!(2, provider.invoke_count.load(Ordering::SeqCst));
 }
 
+#[tokio::test]
+async fn test_rotated_credentials_are_used_by_the_next_request() {
+    let authorization_headers = Arc::new(Mutex::new(Vec::new()));
+    let http_client = infallible_client_fn({
+        let authorization_headers = authorization_headers.clone();
+        move |req| {
+            let authorization = req.headers()["authorization"].to_str().unwrap().to_owned();
+            authorization_headers.lock().unwrap().push(authorization);
+            http::Response::builder().status(200).body("OK!").unwrap()
+        }
+    });
+
+    let provider = RotatingCredentialsProvider::new(Credentials::new(
+        "AKIDBEFORE",
+        "secret",
+        None,
+        None,
+        "test",
+    ));
+    let handle = provider.handle();
+    // The identity cache is enabled by default in this behavior version
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .http_client(http_client)
+        .credentials_provider(provider)
+        .region(Region::new("us-west-2"))
+        .load()
+        .await;
+    let client = Client::new(&config);
+
+    let _ = client.list_buckets().send().await;
+    handle.set(Credentials::new("AKIDAFTER", "secret", None, None, "test"));
+    let _ = client.list_buckets().send().await;
+
+    let authorization_headers = authorization_headers.lock().unwrap();
+    assert_eq!(2, authorization_headers.len());
+    assert!(
+        authorization_headers[0].contains("Credential=AKIDBEFORE/"),
+        "{}",
+        authorization_headers[0]
+    );
+    assert!(
+        authorization_headers[1].contains("Credential=AKIDAFTER/"),
+        "{}",
+        authorization_headers[1]
+    );
+}
+
 #[derive(Clone, Debug)]
 struct TestCredProvider {
     invoke_count: Arc<AtomicI32>,
@@ -12,6 +12,7 @@ use aws_sdk_s3::types::{
 };
 use aws_sdk_s3::{Client, Config};
 use aws_smithy_async::assert_elapsed;
+use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
 use aws_smithy_runtime::client::http::test_util::NeverClient;
 use std::future::Future;
 use std::net::SocketAddr;
@@ -23,6 +24,7 @@ use tokio::time::timeout;
 async fn test_event_stream_request_times_out_if_server_is_unresponsive() {
     let config = Config::builder()
         .with_test_defaults()
+        .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
         .region(Region::new("us-east-2"))
         .http_client(NeverClient::new())
         .timeout_config(
@@ -73,6 +75,7 @@ async fn test_event_stream_request_times_out_if_server_is_unresponsive() {
 async fn test_upload_request_times_out_if_server_is_unresponsive() {
     let config = Config::builder()
         .with_test_defaults()
+        .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
         .region(Region::new("us-east-2"))
         .http_client(NeverClient::new())
         .timeout_config(
@@ -131,6 +134,7 @@ async fn test_read_timeout() {
 
     let config = Config::builder()
         .with_test_defaults()
+        .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
         .region(Region::new("us-east-1"))
         .timeout_config(
             TimeoutConfig::builder()
@@ -170,6 +174,7 @@ async fn test_read_timeout() {
 async fn test_connect_timeout() {
     let config = Config::builder()
         .with_test_defaults()
+        .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
         .region(Region::new("us-east-1"))
         .timeout_config(
             TimeoutConfig::builder()
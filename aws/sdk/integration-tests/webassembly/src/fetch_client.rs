@@ -0,0 +1,68 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_credential_types::Credentials;
+use aws_sdk_s3::config::Region;
+use aws_sdk_s3::{Client, Config};
+use aws_smithy_wasm::js::{Fetch, FetchFuture, FetchHttpClientBuilder, JsSleep, JsTimeSource};
+use bytes::Bytes;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const LIST_OBJECTS_RESPONSE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+    <Name>test-bucket</Name>
+    <Prefix></Prefix>
+    <KeyCount>1</KeyCount>
+    <MaxKeys>5</MaxKeys>
+    <IsTruncated>false</IsTruncated>
+    <Contents>
+        <Key>pikachu.json</Key>
+        <Size>42</Size>
+    </Contents>
+</ListBucketResult>"#;
+
+/// Responds to `ListObjectsV2` requests in place of the `fetch` of the browser.
+#[derive(Debug)]
+struct MockFetch;
+
+impl Fetch for MockFetch {
+    fn fetch(&self, request: http_1x::Request<Bytes>) -> FetchFuture {
+        assert!(request.uri().query().unwrap().contains("list-type=2"));
+        assert!(request.headers().contains_key("authorization"));
+        Box::pin(async {
+            Ok(http_1x::Response::builder()
+                .status(200)
+                .body(Bytes::from_static(LIST_OBJECTS_RESPONSE.as_bytes()))
+                .unwrap())
+        })
+    }
+}
+
+#[wasm_bindgen_test]
+async fn test_fetch_client() {
+    let config = Config::builder()
+        .behavior_version_latest()
+        .region(Region::new("us-east-2"))
+        .credentials_provider(Credentials::from_keys(
+            "ANOTREAL",
+            "notrealrnrELgWzOk3IfjzDKtFBhDby",
+            None,
+        ))
+        .http_client(FetchHttpClientBuilder::new().fetch(MockFetch).build())
+        .sleep_impl(JsSleep::new())
+        .time_source(JsTimeSource::new())
+        .build();
+    let client = Client::from_conf(config);
+
+    let output = client
+        .list_objects_v2()
+        .bucket("test-bucket")
+        .max_keys(5)
+        .send()
+        .await
+        .unwrap();
+    let keys: Vec<_> = output.contents().iter().filter_map(|o| o.key()).collect();
+    assert_eq!(vec!["pikachu.json"], keys);
+}
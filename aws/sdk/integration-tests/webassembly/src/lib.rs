@@ -5,6 +5,8 @@
 
 #![allow(dead_code)]
 
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+mod fetch_client;
 #[cfg(target_family = "wasm")]
 mod http_client;
 #[cfg(all(target_family = "wasm", target_os = "wasi"))]
@@ -121,6 +121,11 @@ val allCodegenTests = listOf(
         dependsOn = listOf("pokemon-awsjson.smithy", "pokemon-common.smithy"),
     ),
     ClientTest("aws.protocoltests.misc#QueryCompatService", "query-compat-test", dependsOn = listOf("aws-json-query-compat.smithy")),
+    ClientTest(
+        "com.amazonaws.benchmarks#SdkOverheadBench",
+        "sdk-overhead-bench-client",
+        dependsOn = listOf("sdk-overhead-bench.smithy"),
+    ),
 ).map(ClientTest::toCodegenTest)
 
 project.registerGenerateSmithyBuildTask(rootProject, pluginName, allCodegenTests)
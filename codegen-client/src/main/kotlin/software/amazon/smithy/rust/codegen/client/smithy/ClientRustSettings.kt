@@ -98,6 +98,12 @@ data class ClientCodegenConfig(
     /** If true, adds `endpoint_url`/`set_endpoint_url` methods to the service config */
     val includeEndpointUrlConfig: Boolean = DEFAULT_INCLUDE_ENDPOINT_URL_CONFIG,
     val enableUserConfigurableRuntimePlugins: Boolean = DEFAULT_ENABLE_USER_CONFIGURABLE_RUNTIME_PLUGINS,
+    /** If true, adds `send_incremental` to operations whose large JSON list responses can be deserialized incrementally */
+    val incrementalJsonLists: Boolean = DEFAULT_INCREMENTAL_JSON_LISTS,
+    /** If true, generated request protocol tests also fail on headers and query params that the test doesn't expect */
+    val strictProtocolTests: Boolean = DEFAULT_STRICT_PROTOCOL_TESTS,
+    /** If true, generated structures and unions implement `SmithyShape` so their fields can be navigated by name */
+    val shapeIntrospection: Boolean = DEFAULT_SHAPE_INTROSPECTION,
 ) : CoreCodegenConfig(
         formatTimeoutSeconds, debugMode, DEFAULT_FLATTEN_ACCESSORS,
     ) {
@@ -108,6 +114,9 @@ data class ClientCodegenConfig(
         private val DEFAULT_EVENT_STREAM_ALLOW_LIST: Set<String> = emptySet()
         private const val DEFAULT_INCLUDE_ENDPOINT_URL_CONFIG = true
         private const val DEFAULT_ENABLE_USER_CONFIGURABLE_RUNTIME_PLUGINS = true
+        private const val DEFAULT_INCREMENTAL_JSON_LISTS = false
+        private const val DEFAULT_STRICT_PROTOCOL_TESTS = false
+        private const val DEFAULT_SHAPE_INTROSPECTION = false
         private const val DEFAULT_NULLABILITY_CHECK_MODE = "CLIENT"
 
         // Note: only clients default to true, servers default to false
@@ -132,6 +141,9 @@ data class ClientCodegenConfig(
                 addMessageToErrors = node.get().getBooleanMemberOrDefault("addMessageToErrors", DEFAULT_ADD_MESSAGE_TO_ERRORS),
                 includeEndpointUrlConfig = node.get().getBooleanMemberOrDefault("includeEndpointUrlConfig", DEFAULT_INCLUDE_ENDPOINT_URL_CONFIG),
                 enableUserConfigurableRuntimePlugins = node.get().getBooleanMemberOrDefault("enableUserConfigurableRuntimePlugins", DEFAULT_ENABLE_USER_CONFIGURABLE_RUNTIME_PLUGINS),
+                incrementalJsonLists = node.get().getBooleanMemberOrDefault("incrementalJsonLists", DEFAULT_INCREMENTAL_JSON_LISTS),
+                strictProtocolTests = node.get().getBooleanMemberOrDefault("strictProtocolTests", DEFAULT_STRICT_PROTOCOL_TESTS),
+                shapeIntrospection = node.get().getBooleanMemberOrDefault("shapeIntrospection", DEFAULT_SHAPE_INTROSPECTION),
                 nullabilityCheckMode = NullableIndex.CheckMode.valueOf(node.get().getStringMemberOrDefault("nullabilityCheckMode", DEFAULT_NULLABILITY_CHECK_MODE)),
             )
         } else {
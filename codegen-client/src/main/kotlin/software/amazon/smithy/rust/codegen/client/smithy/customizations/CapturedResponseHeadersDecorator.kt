@@ -0,0 +1,353 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.model.knowledge.HttpBinding
+import software.amazon.smithy.model.knowledge.HttpBindingIndex
+import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.client.smithy.configReexport
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationSection
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.client.smithy.generators.error.ErrorCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.error.ErrorSection
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustBlockTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderSection
+import software.amazon.smithy.rust.codegen.core.smithy.generators.StructureCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.generators.StructureSection
+import software.amazon.smithy.rust.codegen.core.smithy.generators.error.ErrorImplCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.generators.error.ErrorImplSection
+import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticOutputTrait
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
+
+private fun capturedHeadersModule(rc: RuntimeConfig) = RuntimeType.smithyTypes(rc).resolve("captured_headers")
+
+private fun capturedHeadersScope(rc: RuntimeConfig) =
+    arrayOf(
+        *preludeScope,
+        "CapturedHeaders" to capturedHeadersModule(rc).resolve("CapturedHeaders"),
+        "ProvideCapturedHeaders" to capturedHeadersModule(rc).resolve("ProvideCapturedHeaders"),
+        "current_captured_headers" to capturedHeadersModule(rc).resolve("current"),
+    )
+
+/**
+ * Adds a `capture_response_headers` config option. The listed response headers are captured by the orchestrator
+ * before deserialization, and attached to outputs and to the error metadata of errors regardless of model bindings.
+ *
+ * The `Content-Type` of outputs with a string or blob `@httpPayload` is always captured, parameters included.
+ */
+class CapturedResponseHeadersDecorator : ClientCodegenDecorator {
+    override val name: String = "CapturedResponseHeaders"
+    override val order: Byte = 0
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> = baseCustomizations + CapturedResponseHeadersConfigCustomization(codegenContext)
+
+    override fun operationCustomizations(
+        codegenContext: ClientCodegenContext,
+        operation: OperationShape,
+        baseCustomizations: List<OperationCustomization>,
+    ): List<OperationCustomization> = baseCustomizations + CapturedResponseHeadersOperationCustomization(codegenContext)
+
+    override fun errorCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ErrorCustomization>,
+    ): List<ErrorCustomization> = baseCustomizations + CapturedResponseHeadersErrorCustomization(codegenContext)
+
+    override fun errorImplCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ErrorImplCustomization>,
+    ): List<ErrorImplCustomization> = baseCustomizations + CapturedResponseHeadersErrorImplCustomization(codegenContext)
+
+    override fun structureCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<StructureCustomization>,
+    ): List<StructureCustomization> = baseCustomizations + CapturedResponseHeadersStructureCustomization(codegenContext)
+
+    override fun builderCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<BuilderCustomization>,
+    ): List<BuilderCustomization> = baseCustomizations + CapturedResponseHeadersBuilderCustomization(codegenContext)
+
+    override fun extras(
+        codegenContext: ClientCodegenContext,
+        rustCrate: RustCrate,
+    ) {
+        rustCrate.withModule(ClientRustModule.Operation) {
+            rustTemplate(
+                "pub use #{captured_headers}::{CapturedHeaders, ProvideCapturedHeaders};",
+                "captured_headers" to capturedHeadersModule(codegenContext.runtimeConfig),
+            )
+        }
+    }
+}
+
+private class CapturedResponseHeadersConfigCustomization(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val rc = codegenContext.runtimeConfig
+    private val moduleUseName = codegenContext.moduleUseName()
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "CaptureResponseHeaders" to
+                configReexport(RuntimeType.smithyRuntime(rc).resolve("client::captured_headers::CaptureResponseHeaders")),
+        )
+
+    override fun section(section: ServiceConfig): Writable =
+        writable {
+            when (section) {
+                ServiceConfig.ConfigImpl ->
+                    rustTemplate(
+                        """
+                        /// Returns the response headers that are captured into outputs and errors, if any.
+                        pub fn capture_response_headers(&self) -> #{Option}<&#{CaptureResponseHeaders}> {
+                            self.config.load::<#{CaptureResponseHeaders}>()
+                        }
+                        """,
+                        *codegenScope,
+                    )
+
+                ServiceConfig.BuilderImpl ->
+                    rustTemplate(
+                        """
+                        /// Captures the given response headers from every response into operation outputs and errors,
+                        /// regardless of whether they're bound to a member in the model.
+                        ///
+                        /// Captured headers are available with `ProvideCapturedHeaders::captured_headers` on outputs and
+                        /// errors. Multi-value headers are captured as a list, and values longer than
+                        /// [`max_value_len`](#{CaptureResponseHeaders}::max_value_len) bytes are truncated.
+                        ///
+                        /// The `content-type` of outputs whose payload is a string or a blob is always captured,
+                        /// parameters included, even if it isn't listed.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::{CaptureResponseHeaders, Config};
+                        ///
+                        /// let config = Config::builder()
+                        ///     .capture_response_headers(&["x-rate-limit-remaining", "x-cache"])
+                        ///     .build();
+                        ///
+                        /// // Truncate captured values after 64 bytes instead of the default
+                        /// let config = Config::builder()
+                        ///     .capture_response_headers(CaptureResponseHeaders::new(&["x-cache"]).max_value_len(64))
+                        ///     .build();
+                        /// ```
+                        pub fn capture_response_headers(mut self, capture: impl #{Into}<#{CaptureResponseHeaders}>) -> Self {
+                            self.set_capture_response_headers(#{Some}(capture.into()));
+                            self
+                        }
+
+                        /// Sets the response headers that are captured into operation outputs and errors.
+                        pub fn set_capture_response_headers(&mut self, capture: #{Option}<#{CaptureResponseHeaders}>) -> &mut Self {
+                            self.config.store_or_unset(capture);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+
+                is ServiceConfig.BuilderFromConfigBag ->
+                    rustTemplate(
+                        "${section.builder}.set_capture_response_headers(${section.configBag}.load::<#{CaptureResponseHeaders}>().cloned());",
+                        *codegenScope,
+                    )
+
+                else -> {}
+            }
+        }
+}
+
+private class CapturedResponseHeadersOperationCustomization(codegenContext: ClientCodegenContext) :
+    OperationCustomization() {
+    private val model = codegenContext.model
+    private val codegenScope = capturedHeadersScope(codegenContext.runtimeConfig)
+
+    private fun hasStringOrBlobPayload(operationShape: OperationShape): Boolean =
+        HttpBindingIndex.of(model).getResponseBindings(operationShape).values.any { binding ->
+            binding.location == HttpBinding.Location.PAYLOAD &&
+                model.expectShape(binding.member.target).let { it.isStringShape || it.isBlobShape }
+        }
+
+    override fun section(section: OperationSection): Writable =
+        writable {
+            when (section) {
+                is OperationSection.PopulateErrorMetadataExtras ->
+                    rustTemplate(
+                        """
+                        if let #{Some}(captured_headers) = #{current_captured_headers}() {
+                            ${section.builderName} = ${section.builderName}.captured_headers(captured_headers);
+                        }
+                        """,
+                        *codegenScope,
+                    )
+
+                is OperationSection.MutateOutput ->
+                    if (hasStringOrBlobPayload(section.operationShape)) {
+                        // The payload is only meaningful along with its content type, which may have parameters that
+                        // aren't modeled, like a `charset`, so it's always captured.
+                        rustTemplate(
+                            """
+                            let mut captured_headers = #{current_captured_headers}();
+                            if let #{Some}(content_type) = ${section.responseHeadersName}.get("content-type") {
+                                let captured_headers = captured_headers.get_or_insert_with(#{CapturedHeaders}::new);
+                                if captured_headers.get("content-type").is_none() {
+                                    captured_headers.append("content-type", content_type);
+                                }
+                            }
+                            output._set_captured_headers(captured_headers);
+                            """,
+                            *codegenScope,
+                        )
+                    } else {
+                        rustTemplate("output._set_captured_headers(#{current_captured_headers}());", *codegenScope)
+                    }
+
+                else -> {}
+            }
+        }
+}
+
+private class CapturedResponseHeadersErrorCustomization(codegenContext: ClientCodegenContext) : ErrorCustomization() {
+    private val symbolProvider = codegenContext.symbolProvider
+    private val codegenScope = capturedHeadersScope(codegenContext.runtimeConfig)
+
+    override fun section(section: ErrorSection): Writable =
+        writable {
+            when (section) {
+                is ErrorSection.OperationErrorAdditionalTraitImpls ->
+                    rustTemplate(
+                        """
+                        impl #{ProvideCapturedHeaders} for #{error} {
+                            fn captured_headers(&self) -> #{Option}<&#{CapturedHeaders}> {
+                                self.meta().captured_headers()
+                            }
+                        }
+                        """,
+                        *codegenScope,
+                        "error" to section.errorSymbol,
+                    )
+
+                is ErrorSection.ServiceErrorAdditionalTraitImpls ->
+                    rustBlockTemplate("impl #{ProvideCapturedHeaders} for Error", *codegenScope) {
+                        rustBlockTemplate("fn captured_headers(&self) -> #{Option}<&#{CapturedHeaders}>", *codegenScope) {
+                            rustBlockTemplate("match self") {
+                                section.allErrors.forEach { error ->
+                                    val sym = symbolProvider.toSymbol(error)
+                                    rustTemplate(
+                                        "Self::${sym.name}(e) => #{ProvideCapturedHeaders}::captured_headers(e),",
+                                        *codegenScope,
+                                    )
+                                }
+                                rust("Self::Unhandled(e) => e.meta.captured_headers(),")
+                            }
+                        }
+                    }
+            }
+        }
+}
+
+private class CapturedResponseHeadersErrorImplCustomization(codegenContext: ClientCodegenContext) :
+    ErrorImplCustomization() {
+    private val rc = codegenContext.runtimeConfig
+    private val codegenScope = capturedHeadersScope(rc)
+
+    override fun section(section: ErrorImplSection): Writable =
+        writable {
+            when (section) {
+                is ErrorImplSection.ErrorAdditionalTraitImpls ->
+                    rustTemplate(
+                        """
+                        impl #{ProvideCapturedHeaders} for #{error} {
+                            fn captured_headers(&self) -> #{Option}<&#{CapturedHeaders}> {
+                                #{ProvideErrorMetadata}::meta(self).captured_headers()
+                            }
+                        }
+                        """,
+                        *codegenScope,
+                        "error" to section.errorType,
+                        "ProvideErrorMetadata" to RuntimeType.provideErrorMetadataTrait(rc),
+                    )
+
+                else -> {}
+            }
+        }
+}
+
+private class CapturedResponseHeadersStructureCustomization(codegenContext: ClientCodegenContext) :
+    StructureCustomization() {
+    private val codegenScope = capturedHeadersScope(codegenContext.runtimeConfig)
+
+    override fun section(section: StructureSection): Writable =
+        writable {
+            if (section.shape.hasTrait<SyntheticOutputTrait>()) {
+                when (section) {
+                    is StructureSection.AdditionalFields ->
+                        rustTemplate("_captured_headers: #{Option}<#{CapturedHeaders}>,", *codegenScope)
+
+                    is StructureSection.AdditionalTraitImpls ->
+                        rustTemplate(
+                            """
+                            impl #{ProvideCapturedHeaders} for ${section.structName} {
+                                fn captured_headers(&self) -> #{Option}<&#{CapturedHeaders}> {
+                                    self._captured_headers.as_ref()
+                                }
+                            }
+                            """,
+                            *codegenScope,
+                        )
+
+                    is StructureSection.AdditionalDebugFields ->
+                        rust("""${section.formatterName}.field("_captured_headers", &self._captured_headers);""")
+                }
+            }
+        }
+}
+
+private class CapturedResponseHeadersBuilderCustomization(codegenContext: ClientCodegenContext) :
+    BuilderCustomization() {
+    private val codegenScope = capturedHeadersScope(codegenContext.runtimeConfig)
+
+    override fun section(section: BuilderSection): Writable =
+        writable {
+            if (section.shape.hasTrait<SyntheticOutputTrait>()) {
+                when (section) {
+                    is BuilderSection.AdditionalFields ->
+                        rustTemplate("_captured_headers: #{Option}<#{CapturedHeaders}>,", *codegenScope)
+
+                    is BuilderSection.AdditionalMethods ->
+                        rustTemplate(
+                            """
+                            pub(crate) fn _set_captured_headers(&mut self, captured_headers: #{Option}<#{CapturedHeaders}>) -> &mut Self {
+                                self._captured_headers = captured_headers;
+                                self
+                            }
+                            """,
+                            *codegenScope,
+                        )
+
+                    is BuilderSection.AdditionalDebugFields ->
+                        rust("""${section.formatterName}.field("_captured_headers", &self._captured_headers);""")
+
+                    is BuilderSection.AdditionalFieldsInBuild -> rust("_captured_headers: self._captured_headers,")
+                }
+            }
+        }
+}
@@ -0,0 +1,73 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.customize.TestUtilFeature
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
+import software.amazon.smithy.rust.codegen.core.rustlang.RustType
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.render
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.generators.BuilderGenerator
+import software.amazon.smithy.rust.codegen.core.smithy.generators.StructureCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.generators.StructureSection
+import software.amazon.smithy.rust.codegen.core.smithy.rustType
+import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticOutputTrait
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
+import software.amazon.smithy.rust.codegen.core.util.isEventStream
+
+/**
+ * Implements `FromReceiver` for outputs with an event stream member when the `test-util` feature is enabled, so
+ * that mocking libraries can construct those outputs from a list of canned events.
+ */
+class EventStreamTestUtilDecorator : ClientCodegenDecorator {
+    override val name: String = "EventStreamTestUtil"
+    override val order: Byte = 0
+
+    override fun structureCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<StructureCustomization>,
+    ): List<StructureCustomization> = baseCustomizations + EventStreamOutputFromReceiver(codegenContext)
+}
+
+private class EventStreamOutputFromReceiver(private val codegenContext: ClientCodegenContext) :
+    StructureCustomization() {
+    private val model = codegenContext.model
+    private val symbolProvider = codegenContext.symbolProvider
+
+    override fun section(section: StructureSection): Writable =
+        writable {
+            if (section !is StructureSection.AdditionalTraitImpls || !section.shape.hasTrait<SyntheticOutputTrait>()) {
+                return@writable
+            }
+            val member = section.shape.members().firstOrNull { it.isEventStream(model) } ?: return@writable
+            val receiverType = symbolProvider.toSymbol(member).rustType() as RustType.Application
+            val (eventType, errorType) = receiverType.args.map { it.render() }
+            val build =
+                if (BuilderGenerator.hasFallibleBuilder(section.shape, symbolProvider)) {
+                    ".build().expect(\"outputs with other required members can't be built from an event stream alone\")"
+                } else {
+                    ".build()"
+                }
+            Attribute(Attribute.cfg(Attribute.any(Attribute.feature(TestUtilFeature.name), writable("test")))).render(this)
+            rustTemplate(
+                """
+                impl #{FromReceiver}<$eventType, $errorType> for ${section.structName} {
+                    fn from_receiver(receiver: #{Receiver}<$eventType, $errorType>) -> Self {
+                        Self::builder().${symbolProvider.toMemberName(member)}(#{EventReceiver}::new(receiver))$build
+                    }
+                }
+                """,
+                "FromReceiver" to RuntimeType.smithyHttp(codegenContext.runtimeConfig).resolve("event_stream::FromReceiver"),
+                "Receiver" to RuntimeType.eventStreamReceiver(codegenContext.runtimeConfig),
+                "EventReceiver" to RuntimeType.eventReceiver(codegenContext.runtimeConfig),
+            )
+        }
+}
@@ -0,0 +1,217 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.aws.traits.protocols.AwsJson1_0Trait
+import software.amazon.smithy.aws.traits.protocols.AwsJson1_1Trait
+import software.amazon.smithy.aws.traits.protocols.RestJson1Trait
+import software.amazon.smithy.model.shapes.BlobShape
+import software.amazon.smithy.model.shapes.DocumentShape
+import software.amazon.smithy.model.shapes.ListShape
+import software.amazon.smithy.model.shapes.MemberShape
+import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.model.shapes.SimpleShape
+import software.amazon.smithy.model.shapes.StructureShape
+import software.amazon.smithy.model.shapes.UnionShape
+import software.amazon.smithy.model.traits.SparseTrait
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.client.smithy.generators.client.FluentClientCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.client.FluentClientSection
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.InlineDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.RustModule
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.protocols.HttpLocation
+import software.amazon.smithy.rust.codegen.core.smithy.protocols.restJsonFieldName
+import software.amazon.smithy.rust.codegen.core.util.dq
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
+import software.amazon.smithy.rust.codegen.core.util.outputShape
+
+private fun incrementalListOutput(runtimeConfig: RuntimeConfig): RuntimeType =
+    InlineDependency.forRustFile(
+        RustModule.public(
+            "incremental",
+            parent = ClientRustModule.client,
+            documentationOverride = "Types for deserializing large list responses incrementally.",
+        ),
+        "/inlineable/src/json_incremental_list.rs",
+        CargoDependency.smithyAsync(runtimeConfig),
+        CargoDependency.smithyJson(runtimeConfig),
+        CargoDependency.smithyTypes(runtimeConfig),
+    ).toType().resolve("IncrementalListOutput")
+
+/**
+ * Returns the output member that can be deserialized incrementally for [operationShape], if any.
+ *
+ * An operation is eligible when it uses a JSON protocol and its output is a JSON document with exactly one list member
+ * of structures or unions, and every other member is a small scalar. The list is assumed to be what makes the response
+ * large, so the rest of the response can still be deserialized in one go once the list has been read.
+ */
+fun incrementalJsonListMember(
+    codegenContext: ClientCodegenContext,
+    operationShape: OperationShape,
+): MemberShape? {
+    val model = codegenContext.model
+    if (codegenContext.protocol !in setOf(AwsJson1_0Trait.ID, AwsJson1_1Trait.ID, RestJson1Trait.ID)) {
+        return null
+    }
+    val outputShape = operationShape.outputShape(model)
+    val bindings = codegenContext.protocolImpl!!.httpBindingResolver.responseBindings(operationShape)
+    if (bindings.any { it.location != HttpLocation.DOCUMENT }) {
+        return null
+    }
+
+    val (lists, others) = outputShape.members().partition { model.expectShape(it.target) is ListShape }
+    val list = lists.singleOrNull() ?: return null
+    val listShape = model.expectShape(list.target, ListShape::class.java)
+    val itemShape = model.expectShape(listShape.member.target)
+    val smallOthers =
+        others.all { member ->
+            model.expectShape(member.target).let { it is SimpleShape && it !is BlobShape && it !is DocumentShape }
+        }
+    return list.takeIf {
+        smallOthers && !listShape.hasTrait<SparseTrait>() && (itemShape is StructureShape || itemShape is UnionShape)
+    }
+}
+
+/**
+ * Adds a `send_incremental` method to the fluent builders of operations that are eligible for incremental
+ * deserialization (see [incrementalJsonListMember]) when the `incrementalJsonLists` codegen setting is enabled.
+ *
+ * `send_incremental` hands out the items of the list as they're deserialized from the response body, rather than
+ * loading the whole body and materializing every item before returning.
+ */
+class IncrementalJsonListFluentBuilderMethod(
+    private val codegenContext: ClientCodegenContext,
+) : FluentClientCustomization() {
+    private val runtimeConfig = codegenContext.runtimeConfig
+    private val symbolProvider = codegenContext.symbolProvider
+
+    override fun section(section: FluentClientSection): Writable =
+        writable {
+            if (section !is FluentClientSection.FluentBuilderImpl ||
+                !codegenContext.settings.codegenConfig.incrementalJsonLists
+            ) {
+                return@writable
+            }
+            val operationShape = section.operationShape
+            val listMember = incrementalJsonListMember(codegenContext, operationShape) ?: return@writable
+            val listShape = codegenContext.model.expectShape(listMember.target, ListShape::class.java)
+            val fieldName =
+                when (codegenContext.protocol) {
+                    RestJson1Trait.ID -> restJsonFieldName(listMember)
+                    else -> listMember.memberName
+                }
+            val operationName = symbolProvider.toSymbol(operationShape).name
+            val smithyRuntimeApi = RuntimeType.smithyRuntimeApiClient(runtimeConfig)
+            val smithyTypes = RuntimeType.smithyTypes(runtimeConfig)
+            rustTemplate(
+                """
+                /// Sends the request, and deserializes the `$fieldName` list of the response incrementally.
+                ///
+                /// Rather than loading the whole response body and deserializing every item before returning,
+                /// the items are deserialized one at a time as the response body arrives, which bounds the
+                /// memory used by very large responses. Once every item has been read with
+                /// [`next`](#{IncrementalListOutput}::next), the rest of the response is available from
+                /// [`into_output`](#{IncrementalListOutput}::into_output).
+                pub async fn send_incremental(
+                    self,
+                ) -> #{Result}<#{IncrementalListOutput}<#{Item}, #{OperationOutput}>, #{SdkError}<#{OperationError}, #{HttpResponse}>> {
+                    ##[derive(::std::fmt::Debug)]
+                    struct IncrementalResponseDeserializer;
+                    impl #{DeserializeResponse} for IncrementalResponseDeserializer {
+                        fn deserialize_streaming(&self, response: &mut #{HttpResponse}) -> #{Option}<#{OutputOrError}> {
+                            // Error responses are small, so they're deserialized as usual
+                            if !response.status().is_success() {
+                                return #{None};
+                            }
+                            let body = ::std::mem::replace(response.body_mut(), #{SdkBody}::taken());
+                            let mut body_response = #{HttpResponse}::new(response.status(), body);
+                            *body_response.headers_mut() = response.headers().clone();
+                            #{Some}(#{Ok}(#{Output}::erase(body_response)))
+                        }
+
+                        fn deserialize_nonstreaming(&self, response: &#{HttpResponse}) -> #{OutputOrError} {
+                            #{DeserializeResponse}::deserialize_nonstreaming(&super::${operationName}ResponseDeserializer, response)
+                        }
+                    }
+
+                    ##[derive(::std::fmt::Debug)]
+                    struct IncrementalResponseDeserializerRuntimePlugin;
+                    impl #{RuntimePlugin} for IncrementalResponseDeserializerRuntimePlugin {
+                        fn config(&self) -> #{Option}<#{FrozenLayer}> {
+                            let mut cfg = #{Layer}::new("incremental_response_deserializer");
+                            cfg.store_put(#{SharedResponseDeserializer}::new(IncrementalResponseDeserializer));
+                            #{Some}(cfg.freeze())
+                        }
+                    }
+
+                    let input = self.inner.build().map_err(#{SdkError}::construction_failure)?;
+                    let runtime_plugins = #{Operation}::operation_runtime_plugins(
+                        self.handle.runtime_plugins.clone(),
+                        &self.handle.conf,
+                        self.config_override,
+                    )
+                    .with_operation_plugin(IncrementalResponseDeserializerRuntimePlugin);
+                    let map_err = |err: #{SdkError}<#{Error}, #{HttpResponse}>| {
+                        err.map_service_error(|err| {
+                            err.downcast::<#{OperationError}>().expect("correct error type")
+                        })
+                    };
+                    let context = #{Operation}::orchestrate_with_stop_point(&runtime_plugins, input, #{StopPoint}::None)
+                        .await
+                        .map_err(map_err)?;
+                    let response = *context
+                        .finalize()
+                        .map_err(map_err)?
+                        .downcast::<#{HttpResponse}>()
+                        .expect("correct output type");
+                    let (status, headers) = (response.status(), response.headers().clone());
+                    #{Ok}(#{IncrementalListOutput}::new(
+                        response.into_body(),
+                        ${fieldName.dq()},
+                        #{parse_item},
+                        move |rest| {
+                            let mut response = #{HttpResponse}::new(status, #{SdkBody}::from(rest.to_vec()));
+                            *response.headers_mut() = headers;
+                            let output = #{DeserializeResponse}::deserialize_nonstreaming(&super::${operationName}ResponseDeserializer, &response)
+                                .map_err(|err| #{DeserializeError}::custom_source("failed to deserialize the rest of the response", err))?;
+                            #{Ok}(*output.downcast::<#{OperationOutput}>().expect("correct output type"))
+                        },
+                    ))
+                }
+                """,
+                *preludeScope,
+                "DeserializeError" to
+                    CargoDependency.smithyJson(runtimeConfig).toType()
+                        .resolve("deserialize::error::DeserializeError"),
+                "DeserializeResponse" to smithyRuntimeApi.resolve("client::ser_de::DeserializeResponse"),
+                "Error" to smithyRuntimeApi.resolve("client::interceptors::context::Error"),
+                "FrozenLayer" to smithyTypes.resolve("config_bag::FrozenLayer"),
+                "HttpResponse" to smithyRuntimeApi.resolve("client::orchestrator::HttpResponse"),
+                "IncrementalListOutput" to incrementalListOutput(runtimeConfig),
+                "Item" to symbolProvider.toSymbol(codegenContext.model.expectShape(listShape.member.target)),
+                "Layer" to smithyTypes.resolve("config_bag::Layer"),
+                "Operation" to symbolProvider.toSymbol(operationShape),
+                "OperationError" to section.operationErrorType,
+                "OperationOutput" to symbolProvider.toSymbol(operationShape.outputShape(codegenContext.model)),
+                "Output" to smithyRuntimeApi.resolve("client::interceptors::context::Output"),
+                "OutputOrError" to smithyRuntimeApi.resolve("client::interceptors::context::OutputOrError"),
+                "RuntimePlugin" to RuntimeType.runtimePlugin(runtimeConfig),
+                "SdkBody" to RuntimeType.sdkBody(runtimeConfig),
+                "SdkError" to RuntimeType.sdkError(runtimeConfig),
+                "SharedResponseDeserializer" to smithyRuntimeApi.resolve("client::ser_de::SharedResponseDeserializer"),
+                "StopPoint" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::orchestrator::StopPoint"),
+                "parse_item" to codegenContext.protocolImpl!!.structuredDataParser().payloadParser(listShape.member),
+            )
+        }
+}
@@ -0,0 +1,131 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.codegen.core.CodegenException
+import software.amazon.smithy.model.shapes.ListShape
+import software.amazon.smithy.model.shapes.MemberShape
+import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.client.smithy.generators.client.FluentClientCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.client.FluentClientSection
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.InlineDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.RustModule
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.isOptional
+import software.amazon.smithy.rust.codegen.core.util.orNull
+import software.amazon.smithy.rust.codegen.core.util.outputShape
+
+private const val PARTIAL_SUCCESS_CONFIG = "partialSuccess"
+
+private fun partialSuccessModule(runtimeConfig: RuntimeConfig): RuntimeType =
+    InlineDependency.forRustFile(
+        RustModule.public(
+            "partial_success",
+            parent = ClientRustModule.client,
+            documentationOverride = "Types for checking the failed entries of batch operations.",
+        ),
+        "/inlineable/src/partial_success.rs",
+        CargoDependency.smithyRuntimeApiClient(runtimeConfig),
+    ).toType()
+
+/**
+ * Returns the output member listing the entries that failed in the batch operation [operationShape], if any.
+ *
+ * Batch operations are configured in the `partialSuccess` object of the `customizationConfig` settings, which maps
+ * operation shape IDs to the name of that member:
+ *
+ * ```json
+ * "customizationConfig": {
+ *     "partialSuccess": {
+ *         "com.example#BatchPutItems": "failedEntries"
+ *     }
+ * }
+ * ```
+ */
+fun partialSuccessFailuresMember(
+    codegenContext: ClientCodegenContext,
+    operationShape: OperationShape,
+): MemberShape? {
+    val memberName =
+        codegenContext.settings.customizationConfig
+            ?.getObjectMember(PARTIAL_SUCCESS_CONFIG)?.orNull()
+            ?.getStringMember(operationShape.id.toString())?.orNull()
+            ?.value
+            ?: return null
+    val outputShape = operationShape.outputShape(codegenContext.model)
+    val member =
+        outputShape.getMember(memberName).orNull()
+            ?: throw CodegenException("`${operationShape.id}` was configured for partial success, but its output has no `$memberName` member")
+    if (codegenContext.model.expectShape(member.target) !is ListShape) {
+        throw CodegenException("the `$memberName` member listing the failures of `${operationShape.id}` must target a list")
+    }
+    return member
+}
+
+/**
+ * Adds a `send_checked` method to the fluent builders of batch operations (see [partialSuccessFailuresMember]).
+ *
+ * Batch operations report the entries that failed in a successful response, which is easy to overlook. `send_checked`
+ * returns a `PartialSuccess` error when any entry failed, from which the output can still be recovered.
+ */
+class PartialSuccessFluentBuilderMethod(
+    private val codegenContext: ClientCodegenContext,
+) : FluentClientCustomization() {
+    private val runtimeConfig = codegenContext.runtimeConfig
+    private val symbolProvider = codegenContext.symbolProvider
+
+    override fun section(section: FluentClientSection): Writable =
+        writable {
+            if (section !is FluentClientSection.FluentBuilderImpl) {
+                return@writable
+            }
+            val operationShape = section.operationShape
+            val failuresMember = partialSuccessFailuresMember(codegenContext, operationShape) ?: return@writable
+            val listShape = codegenContext.model.expectShape(failuresMember.target, ListShape::class.java)
+            val fieldName = symbolProvider.toMemberName(failuresMember)
+            rustTemplate(
+                """
+                /// Sends the request, and checks that none of its entries failed.
+                ///
+                /// Unlike [`send`](Self::send), which returns the output even if some of its entries failed,
+                /// this returns a [`PartialSuccess`](#{PartialSuccess}) error when `$fieldName` isn't empty.
+                /// The output can be recovered from that error.
+                pub async fn send_checked(
+                    self,
+                ) -> #{Result}<#{OperationOutput}, #{SendCheckedError}<#{OperationError}, #{OperationOutput}, #{Failure}>> {
+                    fn failures(output: &#{OperationOutput}) -> &[#{Failure}] {
+                        #{failures:W}
+                    }
+                    let output = self.send().await?;
+                    #{PartialSuccess}::check(output, failures).map_err(#{SendCheckedError}::PartialSuccess)
+                }
+                """,
+                *preludeScope,
+                "Failure" to symbolProvider.toSymbol(listShape.member),
+                "OperationError" to section.operationErrorType,
+                "OperationOutput" to symbolProvider.toSymbol(operationShape.outputShape(codegenContext.model)),
+                "PartialSuccess" to partialSuccessModule(runtimeConfig).resolve("PartialSuccess"),
+                "SendCheckedError" to partialSuccessModule(runtimeConfig).resolve("SendCheckedError"),
+                "failures" to
+                    writable {
+                        if (symbolProvider.toSymbol(failuresMember).isOptional()) {
+                            rust("output.$fieldName.as_deref().unwrap_or_default()")
+                        } else {
+                            rust("&output.$fieldName")
+                        }
+                    },
+            )
+        }
+}
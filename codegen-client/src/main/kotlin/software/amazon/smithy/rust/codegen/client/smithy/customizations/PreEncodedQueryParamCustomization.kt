@@ -0,0 +1,76 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.model.shapes.CollectionShape
+import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.model.shapes.Shape
+import software.amazon.smithy.model.shapes.StringShape
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationSection
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.protocols.HttpLocation
+import software.amazon.smithy.rust.codegen.core.util.dq
+
+/**
+ * Adds a `<member>_pre_encoded()` method to `CustomizableOperation` for every string member bound to the query
+ * string, so that callers can pass through values that are already percent encoded without double encoding them.
+ */
+class PreEncodedQueryParamCustomization(
+    private val codegenContext: ClientCodegenContext,
+    private val operation: OperationShape,
+) : OperationCustomization() {
+    private val model = codegenContext.model
+    private val codegenScope by lazy {
+        arrayOf(
+            "PreEncodedQueryParam" to
+                RuntimeType.smithyHttp(codegenContext.runtimeConfig).resolve("query::PreEncodedQueryParam"),
+        )
+    }
+
+    private fun Shape.isStringOrStringList(): Boolean =
+        when (this) {
+            is StringShape -> true
+            is CollectionShape -> model.expectShape(member.target) is StringShape
+            else -> false
+        }
+
+    override fun section(section: OperationSection): Writable =
+        writable {
+            when (section) {
+                is OperationSection.CustomizableOperationImpl -> {
+                    val bindings =
+                        codegenContext.protocolImpl?.httpBindingResolver?.requestBindings(operation)
+                            ?.filter { it.location == HttpLocation.QUERY }
+                            ?.filter { model.expectShape(it.member.target).isStringOrStringList() }
+                            .orEmpty()
+                    for (binding in bindings) {
+                        val memberName = codegenContext.symbolProvider.toMemberName(binding.member)
+                        rustTemplate(
+                            """
+                            /// Send the value of `$memberName` (the `${binding.locationName}` query param) as-is instead of
+                            /// percent encoding it.
+                            ///
+                            /// The value must already be percent encoded, and may only contain unreserved characters
+                            /// (`A-Z`, `a-z`, `0-9`, `-`, `.`, `_` and `~`) and percent encoded octets. Space must be
+                            /// encoded as `%20`. Sending the request fails if the value isn't valid.
+                            pub fn ${memberName.removePrefix("r#")}_pre_encoded(self) -> Self {
+                                self.runtime_plugin(#{PreEncodedQueryParam}::new(${binding.locationName.dq()}))
+                            }
+                            """,
+                            *codegenScope,
+                        )
+                    }
+                }
+
+                else -> {}
+            }
+        }
+}
@@ -0,0 +1,94 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustBlockTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+
+class RandomSourceCustomization(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val runtimeConfig = codegenContext.runtimeConfig
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "IntoShared" to RuntimeType.smithyRuntimeApi(runtimeConfig).resolve("shared::IntoShared"),
+            "RandomSource" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::random::RandomSource"),
+            "SeededRandomSource" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::random::SeededRandomSource"),
+            "SharedRandomSource" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::random::SharedRandomSource"),
+        )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                is ServiceConfig.ConfigImpl -> {
+                    rust("/// Return the random source used for this service.")
+                    rustBlockTemplate(
+                        "pub fn random_source(&self) -> #{Option}<#{SharedRandomSource}>",
+                        *codegenScope,
+                    ) {
+                        rustTemplate(
+                            """self.runtime_components.random_source()""",
+                            *codegenScope,
+                        )
+                    }
+                }
+
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Sets the random source used for this service.
+                        ///
+                        /// The random source is used for retry jitter, invocation IDs, idempotency tokens, and multipart boundaries.
+                        /// Setting a seeded random source makes these values reproducible.
+                        pub fn random_source(
+                            mut self,
+                            random_source: impl #{RandomSource} + 'static,
+                        ) -> Self {
+                            self.set_random_source(#{Some}(#{IntoShared}::into_shared(random_source)));
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+
+                    rustTemplate(
+                        """
+                        /// Sets the random source used for this service.
+                        ///
+                        /// The random source is used for retry jitter, invocation IDs, idempotency tokens, and multipart boundaries.
+                        /// Setting a seeded random source makes these values reproducible.
+                        pub fn set_random_source(
+                            &mut self,
+                            random_source: #{Option}<#{SharedRandomSource}>,
+                        ) -> &mut Self {
+                            self.runtime_components.set_random_source(random_source);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                is ServiceConfig.DefaultForTests -> {
+                    rustTemplate(
+                        """
+                        ${section.configBuilderRef}
+                            .set_random_source(#{Some}(#{SharedRandomSource}::new(#{SeededRandomSource}::for_tests())));
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
@@ -0,0 +1,95 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.configReexport
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+
+/**
+ * Adds a `redirect_policy` config option. Redirects are returned as errors unless a policy is set, in which case the
+ * orchestrator follows `307` and `308` redirects to the hosts the policy allows, and signs them again.
+ */
+class RedirectPolicyDecorator : ClientCodegenDecorator {
+    override val name: String = "RedirectPolicy"
+    override val order: Byte = 0
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> = baseCustomizations + RedirectPolicyConfigCustomization(codegenContext)
+}
+
+private class RedirectPolicyConfigCustomization(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val rc = codegenContext.runtimeConfig
+    private val moduleUseName = codegenContext.moduleUseName()
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "RedirectPolicy" to configReexport(RuntimeType.smithyRuntime(rc).resolve("client::redirect::RedirectPolicy")),
+        )
+
+    override fun section(section: ServiceConfig): Writable =
+        writable {
+            when (section) {
+                ServiceConfig.ConfigImpl ->
+                    rustTemplate(
+                        """
+                        /// Returns the policy for following redirects, if one was set.
+                        pub fn redirect_policy(&self) -> #{Option}<&#{RedirectPolicy}> {
+                            self.config.load::<#{RedirectPolicy}>()
+                        }
+                        """,
+                        *codegenScope,
+                    )
+
+                ServiceConfig.BuilderImpl ->
+                    rustTemplate(
+                        """
+                        /// Follows `307` and `308` redirects to the hosts allowed by the given policy.
+                        ///
+                        /// Redirects aren't followed by default, and are returned as errors. Followed requests are
+                        /// signed again for their new host, and are only sent when their body can be replayed.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::{Config, RedirectPolicy};
+                        ///
+                        /// let config = Config::builder()
+                        ///     .redirect_policy(RedirectPolicy::new().allow_host("replica.example.com"))
+                        ///     .build();
+                        /// ```
+                        pub fn redirect_policy(mut self, redirect_policy: #{RedirectPolicy}) -> Self {
+                            self.set_redirect_policy(#{Some}(redirect_policy));
+                            self
+                        }
+
+                        /// Sets the policy for following redirects.
+                        pub fn set_redirect_policy(&mut self, redirect_policy: #{Option}<#{RedirectPolicy}>) -> &mut Self {
+                            self.config.store_or_unset(redirect_policy);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+
+                is ServiceConfig.BuilderFromConfigBag ->
+                    rustTemplate(
+                        "${section.builder}.set_redirect_policy(${section.configBag}.load::<#{RedirectPolicy}>().cloned());",
+                        *codegenScope,
+                    )
+
+                else -> {}
+            }
+        }
+}
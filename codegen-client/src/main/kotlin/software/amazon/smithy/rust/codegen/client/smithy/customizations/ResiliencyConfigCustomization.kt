@@ -8,8 +8,11 @@ package software.amazon.smithy.rust.codegen.client.smithy.customizations
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
 import software.amazon.smithy.rust.codegen.client.smithy.configReexport
+import software.amazon.smithy.rust.codegen.client.smithy.customize.TestUtilFeature
 import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
@@ -32,16 +35,26 @@ class ResiliencyConfigCustomization(codegenContext: ClientCodegenContext) : Conf
             "ClientRateLimiter" to retries.resolve("ClientRateLimiter"),
             "ClientRateLimiterPartition" to retries.resolve("ClientRateLimiterPartition"),
             "debug" to RuntimeType.Tracing.resolve("debug"),
+            "RateLimiter" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::rate_limit::RateLimiter"),
+            "InstantSleep" to CargoDependency.smithyAsync(runtimeConfig).toDevDependency().withFeature("test-util").toType().resolve("test_util::InstantSleep"),
             "IntoShared" to RuntimeType.smithyRuntimeApi(runtimeConfig).resolve("shared::IntoShared"),
+            "Layer" to RuntimeType.smithyTypes(runtimeConfig).resolve("config_bag::Layer"),
+            "PanicOnRealSleep" to CargoDependency.smithyRuntimeTestUtil(runtimeConfig).toType().resolve("client::test_util::sleep::PanicOnRealSleep"),
+            "resolve_retry_budget" to retries.resolve("resolve_retry_budget"),
+            "RetryBudgetSnapshot" to retries.resolve("RetryBudgetSnapshot"),
             "RetryConfig" to retryConfig.resolve("RetryConfig"),
+            "RetryGate" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::retries::gate::RetryGate"),
             "RetryMode" to RuntimeType.smithyTypes(runtimeConfig).resolve("retry::RetryMode"),
             "RetryPartition" to retries.resolve("RetryPartition"),
+            "RuntimePlugins" to RuntimeType.runtimePlugins(runtimeConfig),
             "SharedAsyncSleep" to configReexport(sleepModule.resolve("SharedAsyncSleep")),
+            "SharedRetryGate" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::retries::gate::SharedRetryGate"),
             "SharedRetryStrategy" to configReexport(RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::retries::SharedRetryStrategy")),
             "SharedTimeSource" to configReexport(RuntimeType.smithyAsync(runtimeConfig).resolve("time::SharedTimeSource")),
             "StandardRetryStrategy" to configReexport(retries.resolve("strategy::StandardRetryStrategy")),
             "SystemTime" to RuntimeType.std.resolve("time::SystemTime"),
             "TimeoutConfig" to timeoutModule.resolve("TimeoutConfig"),
+            "TokenBucket" to retries.resolve("TokenBucket"),
         )
 
     override fun section(section: ServiceConfig) =
@@ -72,6 +85,49 @@ class ResiliencyConfigCustomization(codegenContext: ClientCodegenContext) : Conf
                         pub fn retry_partition(&self) -> #{Option}<&#{RetryPartition}> {
                             self.config.load::<#{RetryPartition}>()
                         }
+
+                        /// Returns a reference to the client-side rate limiter contained in this config, if any.
+                        pub fn rate_limiter(&self) -> #{Option}<&#{RateLimiter}> {
+                            self.config.load::<#{RateLimiter}>()
+                        }
+
+                        /// Returns a reference to the retry gate contained in this config, if any.
+                        pub fn retry_gate(&self) -> #{Option}<&#{SharedRetryGate}> {
+                            self.config.load::<#{SharedRetryGate}>()
+                        }
+
+                        /// Returns the retry budget that retries are withdrawn from, if any.
+                        ///
+                        /// This is the budget set with the `retry_budget` config builder method. For the config of a
+                        /// client, it is otherwise the budget of the client's retry partition. Partitions split by
+                        /// endpoint or input have a budget per endpoint or key, so there is no single budget to return.
+                        pub fn retry_budget(&self) -> #{Option}<&#{TokenBucket}> {
+                            self.config.load::<#{TokenBucket}>()
+                        }
+
+                        /// Returns the current state of the [retry budget](Self::retry_budget), if any.
+                        ///
+                        /// This is cheap to call, e.g. to alert when the budget is nearly exhausted, since retries
+                        /// are no longer attempted once it is empty.
+                        pub fn retry_budget_snapshot(&self) -> #{Option}<#{RetryBudgetSnapshot}> {
+                            self.retry_budget().map(#{TokenBucket}::snapshot)
+                        }
+
+                        /// Records the retry budget that requests made with `plugins` use, so that it is returned
+                        /// by [`retry_budget`](Self::retry_budget).
+                        ///
+                        /// The budget isn't carried over by [`to_builder`](Self::to_builder), since clients created
+                        /// from the builder have a partition of their own by default.
+                        pub(crate) fn with_resolved_retry_budget(mut self, plugins: &#{RuntimePlugins}) -> Self {
+                            if self.retry_budget().is_none() {
+                                if let #{Some}(retry_budget) = #{resolve_retry_budget}(plugins) {
+                                    let mut layer = #{Layer}::from(self.cloneable.clone()).with_name("$moduleUseName::config::Config");
+                                    layer.store_put(retry_budget);
+                                    self.config = layer.freeze();
+                                }
+                            }
+                            self
+                        }
                         """,
                         *codegenScope,
                     )
@@ -267,7 +323,10 @@ class ResiliencyConfigCustomization(codegenContext: ClientCodegenContext) : Conf
                         """
                         /// Set the partition for retry-related state. When clients share a retry partition, they will
                         /// also share things like token buckets and client rate limiters. By default, all clients
-                        /// for the same service will share a partition.
+                        /// for the same service will share a partition. Use `RetryPartition::unique` to give a client
+                        /// a partition of its own, and `RetryPartition::by_endpoint` or `RetryPartition::by_input`
+                        /// to give each endpoint or tenant its own retry budget. A partition set with
+                        /// `RetryConfig::with_partition` takes precedence over this one.
                         pub fn retry_partition(mut self, retry_partition: #{RetryPartition}) -> Self {
                             self.set_retry_partition(Some(retry_partition));
                             self
@@ -280,7 +339,10 @@ class ResiliencyConfigCustomization(codegenContext: ClientCodegenContext) : Conf
                         """
                         /// Set the partition for retry-related state. When clients share a retry partition, they will
                         /// also share things like token buckets and client rate limiters. By default, all clients
-                        /// for the same service will share a partition.
+                        /// for the same service will share a partition. Use `RetryPartition::unique` to give a client
+                        /// a partition of its own, and `RetryPartition::by_endpoint` or `RetryPartition::by_input`
+                        /// to give each endpoint or tenant its own retry budget. A partition set with
+                        /// `RetryConfig::with_partition` takes precedence over this one.
                         pub fn set_retry_partition(&mut self, retry_partition: #{Option}<#{RetryPartition}>) -> &mut Self {
                             retry_partition.map(|r| self.config.store_put(r));
                             self
@@ -288,6 +350,135 @@ class ResiliencyConfigCustomization(codegenContext: ClientCodegenContext) : Conf
                         """,
                         *codegenScope,
                     )
+
+                    rustTemplate(
+                        """
+                        /// Limit requests to `ops_per_second` on average, and `burst` at once.
+                        ///
+                        /// Every attempt waits for a permit before it is sent, so retries count against the limit
+                        /// too. Use [`rate_limiter`](Self::rate_limiter) to limit each operation separately, to
+                        /// fail requests that would wait too long, or to share the limit with other clients.
+                        ///
+                        /// ## Panics
+                        ///
+                        /// Panics if `ops_per_second` isn't a positive number, or `burst` is zero.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::Config;
+                        ///
+                        /// // Send at most 10 requests per second
+                        /// let config = Config::builder().rate_limit(10.0, 1).build();
+                        /// ```
+                        pub fn rate_limit(self, ops_per_second: f64, burst: u32) -> Self {
+                            self.rate_limiter(#{RateLimiter}::new(ops_per_second, burst))
+                        }
+
+                        /// Set the client-side rate limiter.
+                        ///
+                        /// Clones of a rate limiter share their permits, so clients configured with the same
+                        /// rate limiter share the limit.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::Config;
+                        /// use $moduleUseName::config::retry::RateLimiter;
+                        /// use std::time::Duration;
+                        ///
+                        /// // Send at most 10 requests per second per operation, and fail requests
+                        /// // that would have to wait more than a second
+                        /// let rate_limiter = RateLimiter::new(10.0, 10)
+                        ///     .per_operation()
+                        ///     .fail_fast(Duration::from_secs(1));
+                        /// let config = Config::builder().rate_limiter(rate_limiter).build();
+                        /// ```
+                        pub fn rate_limiter(mut self, rate_limiter: #{RateLimiter}) -> Self {
+                            self.set_rate_limiter(Some(rate_limiter));
+                            self
+                        }
+
+                        /// Set the client-side rate limiter.
+                        ///
+                        /// Clones of a rate limiter share their permits, so clients configured with the same
+                        /// rate limiter share the limit.
+                        pub fn set_rate_limiter(&mut self, rate_limiter: #{Option}<#{RateLimiter}>) -> &mut Self {
+                            rate_limiter.map(|r| self.config.store_put(r));
+                            self
+                        }
+
+                        /// Set a retry gate that can deny or delay attempts, e.g. a circuit breaker.
+                        ///
+                        /// The gate is asked before the initial attempt and before every retry. If it denies
+                        /// the initial attempt, the request fails with a `RetryGateDenied` error. If it denies
+                        /// a retry, the error of the last attempt is returned.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::Config;
+                        /// use $moduleUseName::config::retry::CircuitBreaker;
+                        /// use std::time::Duration;
+                        ///
+                        /// // Stop sending requests for 30 seconds when half of the last 20 attempts failed
+                        /// let circuit_breaker = CircuitBreaker::new(0.5, 20, Duration::from_secs(30));
+                        /// let config = Config::builder().retry_gate(circuit_breaker).build();
+                        /// ```
+                        pub fn retry_gate(mut self, retry_gate: impl #{RetryGate} + 'static) -> Self {
+                            self.set_retry_gate(Some(#{SharedRetryGate}::new(retry_gate)));
+                            self
+                        }
+
+                        /// Set a retry gate that can deny or delay attempts, e.g. a circuit breaker.
+                        pub fn set_retry_gate(&mut self, retry_gate: #{Option}<#{SharedRetryGate}>) -> &mut Self {
+                            retry_gate.map(|g| self.config.store_put(g));
+                            self
+                        }
+
+                        /// Set the retry budget that retries are withdrawn from.
+                        ///
+                        /// By default, retries are withdrawn from the budget of the client's retry partition. Clones of
+                        /// a [`TokenBucket`](#{TokenBucket}) share their tokens, so giving the same budget to related
+                        /// clients, e.g. the read and write clients of the same backend, makes them share it. This takes
+                        /// precedence over the [retry partition](Self::retry_partition).
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $moduleUseName::config::Config;
+                        /// use $moduleUseName::config::retry::TokenBucket;
+                        ///
+                        /// let retry_budget = TokenBucket::new(500);
+                        /// retry_budget.observe(|event| println!("{:?}", event.snapshot()));
+                        /// let read_config = Config::builder().retry_budget(retry_budget.clone()).build();
+                        /// let write_config = Config::builder().retry_budget(retry_budget).build();
+                        /// ```
+                        pub fn retry_budget(mut self, retry_budget: #{TokenBucket}) -> Self {
+                            self.set_retry_budget(Some(retry_budget));
+                            self
+                        }
+
+                        /// Set the retry budget that retries are withdrawn from.
+                        pub fn set_retry_budget(&mut self, retry_budget: #{Option}<#{TokenBucket}>) -> &mut Self {
+                            retry_budget.map(|b| self.config.store_put(b));
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+
+                    Attribute(Attribute.cfg(Attribute.any(Attribute.feature(TestUtilFeature.name), writable("test")))).render(this)
+                    rustTemplate(
+                        """
+                        /// Panic when a component sleeps for real, rather than with the configured sleep implementation.
+                        ///
+                        /// [`with_test_defaults`](Self::with_test_defaults) configures sleeps that complete instantly, and
+                        /// this catches components that bypass them, e.g. an HTTP client with its own sleep implementation.
+                        /// Only sleeps started by the operation itself are caught, not sleeps started by tasks it spawns.
+                        pub fn panic_on_real_sleep(mut self, panic_on_real_sleep: bool) -> Self {
+                            self.config.store_put(#{PanicOnRealSleep}::new(panic_on_real_sleep));
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
                 }
 
                 is ServiceConfig.BuilderFromConfigBag -> {
@@ -303,6 +494,25 @@ class ResiliencyConfigCustomization(codegenContext: ClientCodegenContext) : Conf
                         "${section.builder}.set_retry_partition(${section.configBag}.load::<#{RetryPartition}>().cloned());",
                         *codegenScope,
                     )
+                    rustTemplate(
+                        "${section.builder}.set_rate_limiter(${section.configBag}.load::<#{RateLimiter}>().cloned());",
+                        *codegenScope,
+                    )
+                    rustTemplate(
+                        "${section.builder}.set_retry_gate(${section.configBag}.load::<#{SharedRetryGate}>().cloned());",
+                        *codegenScope,
+                    )
+                    rustTemplate(
+                        "${section.builder}.set_retry_budget(${section.configBag}.load::<#{TokenBucket}>().cloned());",
+                        *codegenScope,
+                    )
+                }
+
+                is ServiceConfig.DefaultForTests -> {
+                    rustTemplate(
+                        "${section.configBuilderRef}.set_sleep_impl(#{Some}(#{SharedAsyncSleep}::new(#{InstantSleep}::unlogged())));",
+                        *codegenScope,
+                    )
                 }
 
                 else -> emptySection
@@ -319,6 +529,10 @@ class ResiliencyReExportCustomization(codegenContext: ClientCodegenContext) {
                 "pub use #{sleep}::{Sleep};",
                 "sleep" to RuntimeType.smithyAsync(runtimeConfig).resolve("rt::sleep"),
             )
+            rustTemplate(
+                "pub use #{spawn}::{SdkRuntime, SharedTaskSpawner, TaskSpawner};",
+                "spawn" to RuntimeType.smithyAsync(runtimeConfig).resolve("rt::spawn"),
+            )
         }
         rustCrate.withModule(ClientRustModule.Config.retry) {
             rustTemplate(
@@ -330,6 +544,26 @@ class ResiliencyReExportCustomization(codegenContext: ClientCodegenContext) {
                 "pub use #{types_retry}::RetryPartition;",
                 "types_retry" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::retries"),
             )
+
+            rustTemplate(
+                "pub use #{rate_limit}::{RateLimitExceeded, RateLimiter};",
+                "rate_limit" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::rate_limit"),
+            )
+
+            rustTemplate(
+                "pub use #{gate}::{Permit, RetryGate, RetryGateDenied, SharedRetryGate};",
+                "gate" to RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::retries::gate"),
+            )
+
+            rustTemplate(
+                "pub use #{retries}::{RetryBudgetEvent, RetryBudgetSnapshot, TokenBucket};",
+                "retries" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::retries"),
+            )
+
+            rustTemplate(
+                "pub use #{retries}::CircuitBreaker;",
+                "retries" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::retries"),
+            )
         }
         rustCrate.withModule(ClientRustModule.Config.timeout) {
             rustTemplate(
@@ -0,0 +1,79 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustBlockTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+
+class SdkRuntimeCustomization(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val runtimeConfig = codegenContext.runtimeConfig
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "SdkRuntime" to RuntimeType.smithyAsync(runtimeConfig).resolve("rt::spawn::SdkRuntime"),
+        )
+
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                is ServiceConfig.ConfigImpl -> {
+                    rust("/// Return the runtime that background tasks are spawned on, if one was set.")
+                    rustBlockTemplate(
+                        "pub fn sdk_runtime(&self) -> #{Option}<#{SdkRuntime}>",
+                        *codegenScope,
+                    ) {
+                        rustTemplate(
+                            """self.runtime_components.sdk_runtime()""",
+                            *codegenScope,
+                        )
+                    }
+                }
+
+                ServiceConfig.BuilderImpl -> {
+                    rustTemplate(
+                        """
+                        /// Sets the runtime that background tasks are spawned on.
+                        ///
+                        /// With a runtime set, identities are refreshed in the background before they expire, and
+                        /// [`SdkRuntime::shutdown`](#{SdkRuntime}::shutdown) stops that work. The same runtime can
+                        /// be shared by several clients. Without one, identities are refreshed lazily when a request
+                        /// needs them.
+                        pub fn sdk_runtime(mut self, sdk_runtime: #{SdkRuntime}) -> Self {
+                            self.set_sdk_runtime(#{Some}(sdk_runtime));
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+
+                    rustTemplate(
+                        """
+                        /// Sets the runtime that background tasks are spawned on.
+                        ///
+                        /// With a runtime set, identities are refreshed in the background before they expire, and
+                        /// [`SdkRuntime::shutdown`](#{SdkRuntime}::shutdown) stops that work. The same runtime can
+                        /// be shared by several clients. Without one, identities are refreshed lazily when a request
+                        /// needs them.
+                        pub fn set_sdk_runtime(&mut self, sdk_runtime: #{Option}<#{SdkRuntime}>) -> &mut Self {
+                            self.runtime_components.set_sdk_runtime(sdk_runtime);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> emptySection
+            }
+        }
+}
@@ -0,0 +1,131 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.model.knowledge.TopDownIndex
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.configReexport
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.util.dq
+
+/**
+ * Adds the break-glass `override_serializer` and `override_deserializer` config options, which patch the requests and
+ * responses of a single operation at runtime, e.g. to work around a wire-format bug in a service until a fixed client
+ * is released.
+ */
+class SerDeOverrideDecorator : ClientCodegenDecorator {
+    override val name: String = "SerDeOverride"
+    override val order: Byte = 0
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> = baseCustomizations + SerDeOverrideConfigCustomization(codegenContext)
+}
+
+private class SerDeOverrideConfigCustomization(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val rc = codegenContext.runtimeConfig
+    private val serDeOverride = RuntimeType.smithyRuntime(rc).resolve("client::ser_de_override")
+    private val operationNames =
+        TopDownIndex.of(codegenContext.model).getContainedOperations(codegenContext.serviceShape)
+            .map { codegenContext.symbolProvider.toSymbol(it).name }
+            .sorted()
+    private val codegenScope =
+        arrayOf(
+            *preludeScope,
+            "BoxError" to RuntimeType.boxError(rc),
+            "Debug" to RuntimeType.Debug,
+            "DeserOverride" to configReexport(serDeOverride.resolve("DeserOverride")),
+            "HttpRequest" to RuntimeType.smithyRuntimeApiClient(rc).resolve("client::orchestrator::HttpRequest"),
+            "HttpResponse" to RuntimeType.smithyRuntimeApiClient(rc).resolve("client::orchestrator::HttpResponse"),
+            "SerDeOverrides" to serDeOverride.resolve("SerDeOverrides"),
+        )
+
+    override fun section(section: ServiceConfig): Writable =
+        writable {
+            when (section) {
+                ServiceConfig.BuilderImpl ->
+                    rustTemplate(
+                        """
+                        /// Overrides the serializer of the operation named `operation_name`, e.g. `"${operationNames.firstOrNull() ?: "Operation"}"`.
+                        ///
+                        /// **This is a break-glass tool.** It exists to patch requests when a service ships a wire-format
+                        /// bug, until a client with a fix is released. Remove the override as soon as possible. A `warn`
+                        /// level event is logged every time the override is used.
+                        ///
+                        /// The generated serializer still runs. `serializer` is called with the operation input and the
+                        /// request that the generated serializer produced, and returns the request to send. `I` must be the
+                        /// input type of the named operation, or its requests fail.
+                        ///
+                        /// ## Panics
+                        /// Panics if this service doesn't have an operation named `operation_name`.
+                        pub fn override_serializer<I>(
+                            mut self,
+                            operation_name: &str,
+                            serializer: impl Fn(&I, #{HttpRequest}) -> #{Result}<#{HttpRequest}, #{BoxError}> + #{Send} + #{Sync} + 'static,
+                        ) -> Self
+                        where
+                            I: #{Clone} + #{Send} + #{Sync} + #{Debug} + 'static,
+                        {
+                            let mut overrides = self.ser_de_overrides(operation_name);
+                            overrides.override_serializer(operation_name, serializer);
+                            self.config.store_put(overrides);
+                            self
+                        }
+
+                        /// Overrides the deserializer of the operation named `operation_name`, e.g. `"${operationNames.firstOrNull() ?: "Operation"}"`.
+                        ///
+                        /// **This is a break-glass tool.** It exists to patch responses when a service ships a wire-format
+                        /// bug, until a client with a fix is released. Remove the override as soon as possible. A `warn`
+                        /// level event is logged every time the override is used.
+                        ///
+                        /// `deserializer` is called with each response before the generated deserializer runs. It returns
+                        /// either [`DeserOverride::Deserialize`](#{DeserOverride}::Deserialize) with a response, which may
+                        /// have had its body rewritten, for the generated deserializer to deserialize, or
+                        /// [`DeserOverride::Replace`](#{DeserOverride}::Replace) with the output to return instead. `O` must
+                        /// be the output type of the named operation, or its requests fail. Responses with a streaming body
+                        /// aren't passed to the override.
+                        ///
+                        /// ## Panics
+                        /// Panics if this service doesn't have an operation named `operation_name`.
+                        pub fn override_deserializer<O>(
+                            mut self,
+                            operation_name: &str,
+                            deserializer: impl Fn(#{HttpResponse}) -> #{DeserOverride}<O> + #{Send} + #{Sync} + 'static,
+                        ) -> Self
+                        where
+                            O: #{Send} + #{Sync} + #{Debug} + 'static,
+                        {
+                            let mut overrides = self.ser_de_overrides(operation_name);
+                            overrides.override_deserializer(operation_name, deserializer);
+                            self.config.store_put(overrides);
+                            self
+                        }
+
+                        fn ser_de_overrides(&self, operation_name: &str) -> #{SerDeOverrides} {
+                            const OPERATIONS: &[&str] = &[${operationNames.joinToString { it.dq() }}];
+                            assert!(
+                                OPERATIONS.contains(&operation_name),
+                                "cannot override the serializer or deserializer of `{operation_name}`: this service has no \
+                                 operation with that name. Expected one of {OPERATIONS:?}"
+                            );
+                            self.config.load::<#{SerDeOverrides}>().cloned().unwrap_or_default()
+                        }
+                        """,
+                        *codegenScope,
+                    )
+
+                else -> {}
+            }
+        }
+}
@@ -0,0 +1,23 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.customizations.ServiceMetadataCustomization
+
+/** Adds `Config::service_metadata()`, exposing the constants of the `meta` module at runtime. */
+class ServiceMetadataConfigCustomization : ConfigCustomization() {
+    override fun section(section: ServiceConfig) =
+        writable {
+            when (section) {
+                is ServiceConfig.ConfigImpl -> ServiceMetadataCustomization.serviceMetadataFn(ClientRustModule.Meta)(this)
+                else -> emptySection
+            }
+        }
+}
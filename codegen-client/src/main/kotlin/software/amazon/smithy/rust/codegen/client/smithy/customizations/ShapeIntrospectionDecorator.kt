@@ -0,0 +1,231 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.model.shapes.BlobShape
+import software.amazon.smithy.model.shapes.BooleanShape
+import software.amazon.smithy.model.shapes.ByteShape
+import software.amazon.smithy.model.shapes.DocumentShape
+import software.amazon.smithy.model.shapes.DoubleShape
+import software.amazon.smithy.model.shapes.FloatShape
+import software.amazon.smithy.model.shapes.IntEnumShape
+import software.amazon.smithy.model.shapes.IntegerShape
+import software.amazon.smithy.model.shapes.ListShape
+import software.amazon.smithy.model.shapes.LongShape
+import software.amazon.smithy.model.shapes.MapShape
+import software.amazon.smithy.model.shapes.MemberShape
+import software.amazon.smithy.model.shapes.Shape
+import software.amazon.smithy.model.shapes.ShortShape
+import software.amazon.smithy.model.shapes.StringShape
+import software.amazon.smithy.model.shapes.StructureShape
+import software.amazon.smithy.model.shapes.TimestampShape
+import software.amazon.smithy.model.shapes.UnionShape
+import software.amazon.smithy.model.traits.EnumTrait
+import software.amazon.smithy.model.traits.SensitiveTrait
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.core.rustlang.RustModule
+import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.DirectedWalker
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+import software.amazon.smithy.rust.codegen.core.smithy.isOptional
+import software.amazon.smithy.rust.codegen.core.util.dq
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
+import software.amazon.smithy.rust.codegen.core.util.isEventStream
+import software.amazon.smithy.rust.codegen.core.util.isStreaming
+import software.amazon.smithy.rust.codegen.core.util.isTargetUnit
+import software.amazon.smithy.rust.codegen.core.util.isUnit
+
+/**
+ * When the `shapeIntrospection` codegen setting is enabled, implements `SmithyShape` for generated structures and
+ * unions, so that their fields can be listed and navigated by name without knowing their types.
+ */
+class ShapeIntrospectionDecorator : ClientCodegenDecorator {
+    override val name: String = "ShapeIntrospection"
+    override val order: Byte = 0
+
+    override fun extras(
+        codegenContext: ClientCodegenContext,
+        rustCrate: RustCrate,
+    ) {
+        if (!codegenContext.settings.codegenConfig.shapeIntrospection) {
+            return
+        }
+        val generator = ShapeIntrospectionGenerator(codegenContext)
+        rustCrate.withModule(ClientRustModule.primitives) {
+            rustTemplate("pub use #{shape};", "shape" to shapeModule(codegenContext))
+        }
+        rustCrate.withModule(RustModule.private("shape_introspection")) {
+            DirectedWalker(codegenContext.model).walkShapes(codegenContext.serviceShape).forEach { shape ->
+                when {
+                    shape is StructureShape && !shape.isUnit() -> generator.renderStructure(this, shape)
+                    shape is UnionShape && !shape.isEventStream() -> generator.renderUnion(this, shape)
+                    shape is StringShape && shape.hasTrait<EnumTrait>() -> generator.renderEnum(this, shape)
+                }
+            }
+        }
+    }
+}
+
+private fun shapeModule(codegenContext: ClientCodegenContext) =
+    RuntimeType.smithyTypes(codegenContext.runtimeConfig).resolve("shape")
+
+private class ShapeIntrospectionGenerator(codegenContext: ClientCodegenContext) {
+    private val model = codegenContext.model
+    private val symbolProvider = codegenContext.symbolProvider
+    private val codegenScope =
+        shapeModule(codegenContext).let { shape ->
+            arrayOf(
+                *preludeScope,
+                "AsFieldValue" to shape.resolve("AsFieldValue"),
+                "FieldInfo" to shape.resolve("FieldInfo"),
+                "FieldValueRef" to shape.resolve("FieldValueRef"),
+                "ShapeKind" to shape.resolve("ShapeKind"),
+                "SmithyShape" to shape.resolve("SmithyShape"),
+            )
+        }
+
+    fun renderStructure(
+        writer: RustWriter,
+        shape: StructureShape,
+    ) {
+        val members = shape.allMembers.values.toList()
+        renderShapeImpls(writer, shape, members) {
+            if (members.isEmpty()) {
+                rustTemplate("let _ = name;\n#{None}", *codegenScope)
+                return@renderShapeImpls
+            }
+            rustTemplate("match name {", *codegenScope)
+            members.forEach { member ->
+                val fieldName = symbolProvider.toMemberName(member)
+                val value =
+                    when {
+                        member.isStreaming(model) -> "#{Some}(#{FieldValueRef}::Stream)"
+                        symbolProvider.toSymbol(member).isOptional() ->
+                            "self.$fieldName.as_ref().map(#{AsFieldValue}::as_field_value)"
+                        else -> "#{Some}(#{AsFieldValue}::as_field_value(&self.$fieldName))"
+                    }
+                rustTemplate("${member.memberName.dq()} => $value,", *codegenScope)
+            }
+            rustTemplate("_ => #{None},", *codegenScope)
+            rust("}")
+        }
+    }
+
+    fun renderUnion(
+        writer: RustWriter,
+        shape: UnionShape,
+    ) {
+        val members = shape.allMembers.values.toList()
+        renderShapeImpls(writer, shape, members) {
+            rustTemplate("match (name, self) {", *codegenScope)
+            members.forEach { member ->
+                val variantName = symbolProvider.toMemberName(member)
+                val arm =
+                    when {
+                        member.isTargetUnit() -> "Self::$variantName) => #{Some}(#{FieldValueRef}::Null)"
+                        else -> "Self::$variantName(inner)) => #{Some}(#{AsFieldValue}::as_field_value(inner))"
+                    }
+                rustTemplate("(${member.memberName.dq()}, $arm,", *codegenScope)
+            }
+            rustTemplate("_ => #{None},", *codegenScope)
+            rust("}")
+        }
+    }
+
+    fun renderEnum(
+        writer: RustWriter,
+        shape: StringShape,
+    ) {
+        writer.rustTemplate(
+            """
+            impl #{AsFieldValue} for #{Shape} {
+                fn as_field_value(&self) -> #{FieldValueRef}<'_> {
+                    #{FieldValueRef}::String(self.as_str())
+                }
+            }
+            """,
+            *codegenScope,
+            "Shape" to symbolProvider.toSymbol(shape),
+        )
+    }
+
+    private fun renderShapeImpls(
+        writer: RustWriter,
+        shape: Shape,
+        members: List<MemberShape>,
+        getField: RustWriter.() -> Unit,
+    ) {
+        writer.rustTemplate(
+            """
+            impl #{SmithyShape} for #{Shape} {
+                fn shape_name(&self) -> &'static str {
+                    ${shape.id.name.dq()}
+                }
+
+                fn fields(&self) -> &'static [#{FieldInfo}] {
+                    const FIELDS: &[#{FieldInfo}] = &[#{field_infos}];
+                    FIELDS
+                }
+
+                fn get_field(&self, name: &str) -> #{Option}<#{FieldValueRef}<'_>> {
+                    #{get_field}
+                }
+            }
+
+            impl #{AsFieldValue} for #{Shape} {
+                fn as_field_value(&self) -> #{FieldValueRef}<'_> {
+                    #{FieldValueRef}::Shape(self)
+                }
+            }
+            """,
+            *codegenScope,
+            "Shape" to symbolProvider.toSymbol(shape),
+            "field_infos" to
+                writable {
+                    members.forEach { member ->
+                        val sensitive = member.getMemberTrait(model, SensitiveTrait::class.java).isPresent
+                        rustTemplate(
+                            "#{FieldInfo}::new(${member.memberName.dq()}, #{ShapeKind}::${shapeKind(member)}, $sensitive),",
+                            *codegenScope,
+                        )
+                    }
+                },
+            "get_field" to writable(getField),
+        )
+    }
+
+    private fun shapeKind(member: MemberShape): String {
+        if (member.isStreaming(model)) {
+            return "Stream"
+        }
+        return when (val target = model.expectShape(member.target)) {
+            is BlobShape -> "Blob"
+            is BooleanShape -> "Boolean"
+            is StringShape -> if (target.hasTrait<EnumTrait>()) "Enum" else "String"
+            is ByteShape -> "Byte"
+            is ShortShape -> "Short"
+            is IntEnumShape -> "IntEnum"
+            is IntegerShape -> "Integer"
+            is LongShape -> "Long"
+            is FloatShape -> "Float"
+            is DoubleShape -> "Double"
+            is TimestampShape -> "Timestamp"
+            is DocumentShape -> "Document"
+            is ListShape -> "List"
+            is MapShape -> "Map"
+            is StructureShape -> "Structure"
+            is UnionShape -> "Union"
+            else -> throw IllegalStateException("shape introspection doesn't support ${target.type} members")
+        }
+    }
+}
@@ -13,6 +13,8 @@ import software.amazon.smithy.rust.codegen.client.smithy.customizations.HttpChec
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.IdentityCacheConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.InterceptorConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.MetadataCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.PreEncodedQueryParamCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.RandomSourceCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.RequestCompressionGenerator
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.ResiliencyConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.ResiliencyReExportCustomization
@@ -20,6 +22,8 @@ import software.amazon.smithy.rust.codegen.client.smithy.customizations.RetryCla
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.RetryClassifierOperationCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.RetryClassifierServiceRuntimePluginCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.RetryModeFeatureTrackerRuntimePluginCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.SdkRuntimeCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.ServiceMetadataConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.customizations.TimeSourceCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.generators.ServiceRuntimePluginCustomization
@@ -30,6 +34,7 @@ import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
 import software.amazon.smithy.rust.codegen.core.smithy.customizations.AllowLintsCustomization
 import software.amazon.smithy.rust.codegen.core.smithy.customizations.CrateVersionCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.customizations.ServiceMetadataCustomization
 import software.amazon.smithy.rust.codegen.core.smithy.customizations.pubUseSmithyPrimitives
 import software.amazon.smithy.rust.codegen.core.smithy.customizations.pubUseSmithyPrimitivesEventStream
 import software.amazon.smithy.rust.codegen.core.smithy.generators.LibRsCustomization
@@ -55,7 +60,8 @@ class RequiredCustomizations : ClientCodegenDecorator {
             MetadataCustomization(codegenContext, operation) +
             HttpChecksumRequiredGenerator(codegenContext, operation) +
             RetryClassifierOperationCustomization(codegenContext, operation) +
-            RequestCompressionGenerator(codegenContext, operation)
+            RequestCompressionGenerator(codegenContext, operation) +
+            PreEncodedQueryParamCustomization(codegenContext, operation)
 
     override fun configCustomizations(
         codegenContext: ClientCodegenContext,
@@ -66,7 +72,10 @@ class RequiredCustomizations : ClientCodegenDecorator {
             IdentityCacheConfigCustomization(codegenContext) +
             InterceptorConfigCustomization(codegenContext) +
             TimeSourceCustomization(codegenContext) +
-            RetryClassifierConfigCustomization(codegenContext)
+            RandomSourceCustomization(codegenContext) +
+            SdkRuntimeCustomization(codegenContext) +
+            RetryClassifierConfigCustomization(codegenContext) +
+            ServiceMetadataConfigCustomization()
 
     override fun libRsCustomizations(
         codegenContext: ClientCodegenContext,
@@ -125,6 +134,7 @@ class RequiredCustomizations : ClientCodegenDecorator {
         ClientRustModule.Meta.also { metaModule ->
             rustCrate.withModule(metaModule) {
                 CrateVersionCustomization.extras(rustCrate, metaModule)
+                ServiceMetadataCustomization.extras(codegenContext, rustCrate, metaModule)
             }
         }
     }
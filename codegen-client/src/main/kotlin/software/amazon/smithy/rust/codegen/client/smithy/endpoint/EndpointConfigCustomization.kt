@@ -38,6 +38,9 @@ internal class EndpointConfigCustomization(
             "StaticUriEndpointResolver" to epRuntimeModule.resolve("StaticUriEndpointResolver"),
             "ServiceSpecificResolver" to codegenContext.serviceSpecificEndpointResolver(),
             "IntoShared" to RuntimeType.smithyRuntimeApi(runtimeConfig).resolve("shared::IntoShared"),
+            "ResolveEndpointAsync" to epModule.resolve("ResolveEndpointAsync"),
+            "PreflightTlsHostnameCheck" to epModule.resolve("PreflightTlsHostnameCheck"),
+            "TimeoutConfig" to RuntimeType.smithyTypes(runtimeConfig).resolve("timeout::TimeoutConfig"),
         )
 
     override fun section(section: ServiceConfig): Writable {
@@ -133,6 +136,39 @@ internal class EndpointConfigCustomization(
                             self
                         }
 
+                        /// Sets an endpoint resolver with access to the operation input.
+                        ///
+                        /// Unlike [`Self::endpoint_resolver`], this resolver is also given the input of the operation, which can
+                        /// be downcast to the operation's input type, and it may resolve the endpoint asynchronously. It runs once per
+                        /// operation before the input is serialized, and is bounded by the
+                        /// [endpoint resolution timeout](#{TimeoutConfig}::endpoint_resolution_timeout).
+                        ///
+                        /// Note: setting an endpoint resolver will replace any endpoint URL that has been set.
+                        ///
+                        /// ## Examples
+                        /// ```no_run
+                        /// use $endpointModule::{Endpoint, EndpointFuture, EndpointResolverParams, Params, ResolveEndpointAsync};
+                        /// use $moduleUseName::config::interceptors::Input;
+                        /// ##[derive(Debug)]
+                        /// struct RegistryResolver;
+                        /// impl ResolveEndpointAsync for RegistryResolver {
+                        ///     fn resolve_endpoint_async<'a>(&'a self, params: &'a EndpointResolverParams, input: &'a Input) -> EndpointFuture<'a> {
+                        ///         EndpointFuture::new(async move {
+                        ///             let _params = params.get::<Params>().ok_or("unexpected endpoint params")?;
+                        ///             // `input` can be downcast to the input type of the operation being called
+                        ///             let _ = input;
+                        ///             Ok(Endpoint::builder().url("https://myservice.registry.local").build())
+                        ///         })
+                        ///     }
+                        /// }
+                        /// let config = $moduleUseName::Config::builder().async_endpoint_resolver(RegistryResolver).build();
+                        /// let client = $moduleUseName::Client::from_conf(config);
+                        /// ```
+                        pub fn async_endpoint_resolver(mut self, endpoint_resolver: impl #{ResolveEndpointAsync} + 'static) -> Self {
+                            self.set_endpoint_resolver(#{Some}(#{SharedEndpointResolver}::new_async(endpoint_resolver)));
+                            self
+                        }
+
                         /// Sets the endpoint resolver to use when making requests.
                         ///
                         $defaultResolverDocs
@@ -146,6 +182,28 @@ internal class EndpointConfigCustomization(
                             self.runtime_components.set_endpoint_resolver(endpoint_resolver);
                             self
                         }
+
+                        /// Sets whether to check, before a request is sent, that the host of an `https` endpoint can be
+                        /// covered by a wildcard TLS certificate.
+                        ///
+                        /// When a host prefix adds more than one label to the host (for example, a bucket name with dots),
+                        /// TLS verification fails when connecting. With this check enabled, the request fails before it is
+                        /// serialized or sent with an error that explains which host can't be verified.
+                        ///
+                        /// Defaults to `false`.
+                        pub fn preflight_tls_hostname_check(mut self, preflight_tls_hostname_check: bool) -> Self {
+                            self.set_preflight_tls_hostname_check(#{Some}(preflight_tls_hostname_check));
+                            self
+                        }
+
+                        /// Sets whether to check, before a request is sent, that the host of an `https` endpoint can be
+                        /// covered by a wildcard TLS certificate.
+                        ///
+                        /// See [`Self::preflight_tls_hostname_check`] for more details.
+                        pub fn set_preflight_tls_hostname_check(&mut self, preflight_tls_hostname_check: #{Option}<bool>) -> &mut Self {
+                            self.config.store_or_unset(preflight_tls_hostname_check.map(#{PreflightTlsHostnameCheck}::new));
+                            self
+                        }
                         """,
                         *codegenScope,
                     )
@@ -87,8 +87,11 @@ class Types(runtimeConfig: RuntimeConfig) {
             "Endpoint" to smithyEndpoint,
             "EndpointFuture" to endpointFuture,
             "SharedEndpointResolver" to endpointRtApi.resolve("SharedEndpointResolver"),
+            "EndpointParamBindings" to endpointRtApi.resolve("EndpointParamBindings"),
+            "EndpointParamSource" to endpointRtApi.resolve("EndpointParamSource"),
             "EndpointResolverParams" to endpointRtApi.resolve("EndpointResolverParams"),
             "ResolveEndpoint" to endpointRtApi.resolve("ResolveEndpoint"),
+            "ResolveEndpointAsync" to endpointRtApi.resolve("ResolveEndpointAsync"),
         )
 }
 
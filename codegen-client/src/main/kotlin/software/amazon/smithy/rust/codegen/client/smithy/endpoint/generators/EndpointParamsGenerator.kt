@@ -9,6 +9,7 @@ import software.amazon.smithy.rulesengine.language.evaluation.value.BooleanValue
 import software.amazon.smithy.rulesengine.language.evaluation.value.StringValue
 import software.amazon.smithy.rulesengine.language.evaluation.value.Value
 import software.amazon.smithy.rulesengine.language.syntax.Identifier
+import software.amazon.smithy.rulesengine.language.syntax.parameters.ParameterType
 import software.amazon.smithy.rulesengine.language.syntax.parameters.Parameters
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
@@ -175,7 +176,7 @@ internal class EndpointParamsGenerator(
         //    pub struct Params {
         //        ... members: pub(crate) field
         //    }
-        writer.docs("Configuration parameters for resolving the correct endpoint")
+        renderParamsDocs(writer)
         writer.rustBlock("pub struct Params") {
             parameters.toList().forEach { parameter ->
                 // Render documentation for each parameter
@@ -222,6 +223,59 @@ internal class EndpointParamsGenerator(
         }
     }
 
+    /**
+     * Documents `Params` with an example resolving an endpoint with parameters built by hand, which is how custom
+     * resolvers are unit tested.
+     */
+    private fun renderParamsDocs(writer: RustWriter) {
+        val moduleName = codegenContext.moduleUseName()
+        // Parameters without a default must be set for `build()` to succeed. Otherwise, show how to set the first
+        // string parameter, like the region.
+        val exampleParameters =
+            parameters.toList().filter { it.isRequired && !it.default.isPresent }.ifEmpty {
+                parameters.toList().filter { it.type == ParameterType.STRING }.take(1)
+            }
+        val setters =
+            exampleParameters.joinToString("") { parameter ->
+                val value =
+                    when (parameter.type) {
+                        ParameterType.STRING ->
+                            if (parameter.builtIn.orNull() == "AWS::Region") "\"us-east-1\"" else "\"example\""
+                        ParameterType.BOOLEAN -> "false"
+                        ParameterType.STRING_ARRAY -> "vec![\"example\".to_string()]"
+                        else -> TODO("unexpected type: ${parameter.type}")
+                    }
+                "\n///     .${parameter.memberName()}($value)"
+            }
+        val docs =
+            """
+            /// Configuration parameters for resolving the correct endpoint
+            ///
+            /// The client sets these parameters from its config and from the input of each operation, and passes them
+            /// to its endpoint resolver. They can also be built with [`Params::builder`], for example to unit test a
+            /// custom resolver without sending requests:
+            ///
+            /// ```no_run
+            /// use $moduleName::config::endpoint::{Params, ResolveEndpoint};
+            ///
+            /// ## type BoxError = Box<dyn std::error::Error + Send + Sync>;
+            /// ## async fn example(resolver: impl ResolveEndpoint) -> Result<(), BoxError> {
+            /// let params = Params::builder()SETTERS
+            ///     .build()?;
+            /// let endpoint = resolver.resolve_endpoint(&params).await?;
+            /// println!("resolved {}", endpoint.url());
+            /// ## Ok(())
+            /// ## }
+            /// ```
+            ///
+            /// To check a resolver against a table of parameters and the URLs they should resolve to, pass
+            /// `resolver.into_shared_resolver()` to
+            /// `aws_smithy_runtime::client::endpoint::test_util::assert_endpoints`, enabled by the `test-util`
+            /// feature of `aws-smithy-runtime`.
+            """.trimIndent()
+        writer.rust(docs.replace("SETTERS", setters))
+    }
+
     private fun value(value: Value): String {
         return when (value) {
             is StringValue -> value.value.dq() + ".to_string()"
@@ -5,22 +5,30 @@
 
 package software.amazon.smithy.rust.codegen.client.smithy.endpoint.generators
 
+import software.amazon.smithy.codegen.core.CodegenException
 import software.amazon.smithy.jmespath.JmespathExpression
 import software.amazon.smithy.model.node.ArrayNode
 import software.amazon.smithy.model.node.BooleanNode
 import software.amazon.smithy.model.node.Node
 import software.amazon.smithy.model.node.StringNode
+import software.amazon.smithy.model.shapes.CollectionShape
+import software.amazon.smithy.model.shapes.EnumShape
 import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.model.shapes.Shape
 import software.amazon.smithy.model.traits.EndpointTrait
+import software.amazon.smithy.model.traits.EnumTrait
+import software.amazon.smithy.rulesengine.language.syntax.Identifier
+import software.amazon.smithy.rulesengine.language.syntax.parameters.ParameterType
 import software.amazon.smithy.rulesengine.language.syntax.parameters.Parameters
 import software.amazon.smithy.rulesengine.traits.ContextIndex
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.ClientContextConfigCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.EndpointTypesGenerator
-import software.amazon.smithy.rust.codegen.client.smithy.endpoint.rustName
+import software.amazon.smithy.rust.codegen.client.smithy.endpoint.symbol
 import software.amazon.smithy.rust.codegen.client.smithy.generators.EndpointTraitBindings
 import software.amazon.smithy.rust.codegen.client.smithy.generators.config.configParamNewtype
 import software.amazon.smithy.rust.codegen.client.smithy.generators.config.loadFromConfigBag
+import software.amazon.smithy.rust.codegen.client.smithy.generators.waiters.GeneratedExpression
 import software.amazon.smithy.rust.codegen.client.smithy.generators.waiters.RustJmespathShapeTraversalGenerator
 import software.amazon.smithy.rust.codegen.client.smithy.generators.waiters.TraversalBinding
 import software.amazon.smithy.rust.codegen.client.smithy.generators.waiters.TraversedShape
@@ -32,6 +40,7 @@ import software.amazon.smithy.rust.codegen.core.rustlang.asRef
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
 import software.amazon.smithy.rust.codegen.core.rustlang.rustBlockTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.stripOuter
 import software.amazon.smithy.rust.codegen.core.rustlang.withBlockTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
@@ -40,6 +49,7 @@ import software.amazon.smithy.rust.codegen.core.smithy.generators.enforceRequire
 import software.amazon.smithy.rust.codegen.core.smithy.rustType
 import software.amazon.smithy.rust.codegen.core.util.PANIC
 import software.amazon.smithy.rust.codegen.core.util.dq
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
 import software.amazon.smithy.rust.codegen.core.util.inputShape
 import software.amazon.smithy.rust.codegen.core.util.orNull
 import software.amazon.smithy.rust.codegen.core.util.toPascalCase
@@ -61,6 +71,8 @@ class EndpointParamsInterceptorGenerator(
                 "BoxError" to RuntimeType.boxError(rc),
                 "ConfigBag" to RuntimeType.configBag(rc),
                 "ContextAttachedError" to interceptors.resolve("error::ContextAttachedError"),
+                "EndpointParamBindings" to runtimeApi.resolve("client::endpoint::EndpointParamBindings"),
+                "EndpointParamSource" to runtimeApi.resolve("client::endpoint::EndpointParamSource"),
                 "EndpointResolverParams" to runtimeApi.resolve("client::endpoint::EndpointResolverParams"),
                 "HttpRequest" to orchestrator.resolve("HttpRequest"),
                 "HttpResponse" to orchestrator.resolve("HttpResponse"),
@@ -82,6 +94,8 @@ class EndpointParamsInterceptorGenerator(
         val operationName = symbolProvider.toSymbol(operationShape).name
         val operationInput = symbolProvider.toSymbol(operationShape.inputShape(model))
         val interceptorName = "${operationName}EndpointParamsInterceptor"
+        val bindings = paramBindings(operationShape, endpointTypesGenerator.params)
+        val mut = if (bindings.isEmpty()) "" else "mut "
         writer.rustTemplate(
             """
             ##[derive(Debug)]
@@ -103,11 +117,14 @@ class EndpointParamsInterceptorGenerator(
 
                     #{endpoint_prefix:W}
 
-                    let params = #{Params}::builder()
-                        #{param_setters}
+                    let ${mut}params = #{Params}::builder();
+                    let ${mut}bindings = #{EndpointParamBindings}::new();
+                    #{param_bindings}
+                    let params = params
                         .build()
                         .map_err(|err| #{ContextAttachedError}::new("endpoint params could not be built", err))?;
                     cfg.interceptor_state().store_put(#{EndpointResolverParams}::new(params));
+                    cfg.interceptor_state().store_put(bindings);
                     #{Ok}(())
                 }
             }
@@ -119,78 +136,106 @@ class EndpointParamsInterceptorGenerator(
             """,
             *codegenScope,
             "endpoint_prefix" to endpointPrefix(operationShape),
-            "param_setters" to paramSetters(operationShape, endpointTypesGenerator.params),
+            "param_bindings" to writable { bindings.forEach { it(this) } },
             "jmespath_getters" to jmesPathGetters(operationShape),
         )
     }
 
-    private fun paramSetters(
+    /**
+     * Returns the bindings that set the endpoint params for [operationShape], from lowest to highest precedence.
+     *
+     * A binding only sets its param when it has a value, so an unset input member leaves the param as
+     * a lower precedence binding (or the param's default) set it, rather than clearing it.
+     */
+    private fun paramBindings(
         operationShape: OperationShape,
         params: Parameters,
-    ) = writable {
+    ): List<Writable> {
         val idx = ContextIndex.of(codegenContext.model)
-        val memberParams = idx.getContextParams(operationShape).toList().sortedBy { it.first.memberName }
-        val builtInParams = params.toList().filter { it.isBuiltIn }
-        // first load builtins and their defaults
-        builtInParams.forEach { param ->
-            endpointTypesGenerator.builtInFor(param, "cfg")?.also { defaultValue ->
-                rust(".set_${param.name.rustName()}(#W)", defaultValue)
+        val bindings = mutableListOf<Writable>()
+
+        params.toList().filter { it.isBuiltIn }.forEach { param ->
+            endpointTypesGenerator.builtInFor(param, "cfg")?.also { value ->
+                bindings.add(binding(param.name.toString(), "BuiltIn", value))
             }
         }
 
         idx.getClientContextParams(codegenContext.serviceShape).orNull()?.parameters?.forEach { (name, param) ->
-            val setterName = EndpointParamsGenerator.setterName(name)
             val inner = ClientContextConfigCustomization.toSymbol(param.type, symbolProvider)
             val newtype = configParamNewtype(name.toPascalCase(), inner, codegenContext.runtimeConfig)
-            rustTemplate(
-                ".$setterName(cfg.#{load_from_service_config_layer})",
-                "load_from_service_config_layer" to loadFromConfigBag(inner.name, newtype),
-            )
+            val value = writable { rustTemplate("cfg.#{load}", "load" to loadFromConfigBag(inner.name, newtype)) }
+            bindings.add(binding(name, "ClientContextParam", value))
         }
 
-        idx.getStaticContextParams(operationShape).orNull()?.parameters?.forEach { (name, param) ->
-            val setterName = EndpointParamsGenerator.setterName(name)
-            val value = param.value.toWritable()
-            rust(".$setterName(#W)", value)
+        idx.getOperationContextParams(operationShape).orNull()?.parameters?.forEach { (name, _) ->
+            val getterName = EndpointParamsGenerator.getterName(name)
+            bindings.add(binding(name, "OperationContextParam", writable("$getterName(_input)")))
         }
 
-        idx.getOperationContextParams(operationShape).orNull()?.parameters?.forEach { (name, param) ->
-            val setterName = EndpointParamsGenerator.setterName(name)
-            val getterName = EndpointParamsGenerator.getterName(name)
-            val pathValue = param.path
-            val pathExpression = JmespathExpression.parse(pathValue)
-            val pathTraversal =
-                RustJmespathShapeTraversalGenerator(codegenContext).generate(
-                    pathExpression,
-                    listOf(
-                        TraversalBinding.Global(
-                            "input",
-                            TraversedShape.from(model, operationShape.inputShape(model)),
-                        ),
-                    ),
-                )
+        idx.getContextParams(operationShape).toList().sortedBy { it.first.memberName }.forEach { (memberShape, param) ->
+            val memberName = symbolProvider.toMemberName(memberShape)
+            val member = memberShape.enforceRequired(writable("_input.$memberName.clone()"), codegenContext)
+            val value =
+                when (val target = model.expectShape(memberShape.target)) {
+                    is CollectionShape ->
+                        if (model.expectShape(target.member.target).isStringEnum()) {
+                            writable {
+                                rustTemplate(
+                                    "#{member}.map(|v| v.iter().map(|v| v.as_str().to_string()).collect::<#{Vec}<_>>())",
+                                    *preludeScope,
+                                    "member" to member,
+                                )
+                            }
+                        } else {
+                            member
+                        }
 
-            when (pathTraversal.outputType) {
-                is RustType.Vec -> {
-                    rust(".$setterName($getterName(_input))")
+                    else ->
+                        if (target.isStringEnum()) {
+                            writable { rust("#W.map(|v| v.as_str().to_string())", member) }
+                        } else {
+                            member
+                        }
                 }
+            bindings.add(binding(param.name, "ContextParam", value))
+        }
 
-                else -> {
-                    rust(".$setterName($getterName(_input).cloned())")
-                }
-            }
+        // static values are fixed for the operation, so they're always set
+        idx.getStaticContextParams(operationShape).orNull()?.parameters?.forEach { (name, param) ->
+            bindings.add(
+                writable {
+                    rustTemplate(
+                        """
+                        params = params.${EndpointParamsGenerator.setterName(name)}(#{value});
+                        bindings.record(${name.dq()}, #{EndpointParamSource}::StaticContextParam);
+                        """,
+                        *codegenScope,
+                        "value" to param.value.toWritable(),
+                    )
+                },
+            )
         }
 
-        // lastly, allow these to be overridden by members
-        memberParams.forEach { (memberShape, param) ->
-            val memberName = codegenContext.symbolProvider.toMemberName(memberShape)
-            val member = memberShape.enforceRequired(writable("_input.$memberName.clone()"), codegenContext)
+        return bindings
+    }
 
+    private fun binding(
+        name: String,
+        source: String,
+        value: Writable,
+    ): Writable =
+        writable {
             rustTemplate(
-                ".${EndpointParamsGenerator.setterName(param.name)}(#{member})", "member" to member,
+                """
+                if let #{Some}(value) = #{value} {
+                    params = params.${EndpointParamsGenerator.setterName(name)}(#{Some}(value));
+                    bindings.record(${name.dq()}, #{EndpointParamSource}::$source);
+                }
+                """,
+                *codegenScope,
+                "value" to value,
             )
         }
-    }
 
     private fun jmesPathGetters(operationShape: OperationShape) =
         writable {
@@ -212,19 +257,48 @@ class EndpointParamsInterceptorGenerator(
                             ),
                         ),
                     )
+                val parameter =
+                    endpointTypesGenerator.params.get(Identifier.of(name)).orNull()
+                        ?: throw CodegenException("operationContextParams binds `$name`, which isn't an endpoint parameter")
 
                 rust("// Generated from JMESPath Expression: $pathValue")
                 rustBlockTemplate(
                     "fn $getterName(input: #{Input}) -> Option<#{Ret}>",
                     "Input" to input.rustType().asRef(),
-                    "Ret" to pathTraversal.outputType,
+                    "Ret" to parameter.symbol().rustType().stripOuter<RustType.Option>(),
                 ) {
                     pathTraversal.output(this)
-                    rust("Some(${pathTraversal.identifier})")
+                    rust("Some(${pathTraversal.toParamValue(name, parameter.type)})")
                 }
             }
         }
 
+    /** Converts the value selected by an operationContextParams path into the type of its endpoint parameter. */
+    private fun GeneratedExpression.toParamValue(
+        name: String,
+        type: ParameterType,
+    ): String {
+        var derefs = ""
+        var valueType = outputType
+        while (valueType is RustType.Reference) {
+            derefs += "*"
+            valueType = valueType.member
+        }
+        val selectsList = valueType is RustType.Vec
+        val elementIsEnum = isEnum() || (outputShape as? TraversedShape.Array)?.member is TraversedShape.Enum
+        return when {
+            type == ParameterType.STRING && !selectsList && isEnum() -> "$identifier.as_str().to_string()"
+            type == ParameterType.STRING && !selectsList && isString() -> "$identifier.to_string()"
+            type == ParameterType.BOOLEAN && valueType == RustType.Bool -> "$derefs$identifier"
+            type == ParameterType.STRING_ARRAY && selectsList && elementIsEnum ->
+                "$identifier.iter().map(|v| v.as_str().to_string()).collect::<Vec<_>>()"
+            type == ParameterType.STRING_ARRAY && selectsList -> "$identifier.iter().map(|v| v.to_string()).collect::<Vec<_>>()"
+            else -> throw CodegenException("operationContextParams path for `$name` selects a value that can't be used as a $type parameter")
+        }
+    }
+
+    private fun Shape.isStringEnum(): Boolean = this is EnumShape || this.hasTrait<EnumTrait>()
+
     private fun Node.toWritable(): Writable {
         val node = this
         return writable {
@@ -132,31 +132,6 @@ data class InfallibleEnumType(
                     *preludeScope,
                     "UnknownVariantError" to unknownVariantError(),
                 )
-
-                rustTemplate(
-                    """
-                    impl #{Display} for ${context.enumName} {
-                        fn fmt(&self, f: &mut #{Fmt}::Formatter) -> #{Fmt}::Result {
-                            match self {
-                                #{matchArms}
-                            }
-                        }
-                    }
-                    """,
-                    "Display" to RuntimeType.Display,
-                    "Fmt" to RuntimeType.stdFmt,
-                    "matchArms" to
-                        writable {
-                            context.sortedMembers.forEach { member ->
-                                rust(
-                                    """
-                                    ${context.enumName}::${member.derivedName()} => write!(f, ${member.value.dq()}),
-                                    """,
-                                )
-                            }
-                            rust("""${context.enumName}::Unknown(value) => write!(f, "{}", value)""")
-                        },
-                )
             }
         }
 
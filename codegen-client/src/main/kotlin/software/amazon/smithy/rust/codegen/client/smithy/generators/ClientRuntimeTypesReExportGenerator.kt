@@ -40,6 +40,10 @@ class ClientRuntimeTypesReExportGenerator(
                 pub use #{SharedEndpointResolver};
                 pub use #{EndpointFuture};
                 pub use #{Endpoint};
+                pub use #{EndpointParamBindings};
+                pub use #{EndpointParamSource};
+                pub use #{EndpointResolverParams};
+                pub use #{ResolveEndpointAsync};
                 """,
                 *Types(rc).toArray(),
             )
@@ -79,6 +83,7 @@ class ClientRuntimeTypesReExportGenerator(
                 pub use #{FinalizerInterceptorContextMut};
                 pub use #{FinalizerInterceptorContextRef};
                 pub use #{InterceptorContext};
+                pub use #{Input};
                 """,
                 "AfterDeserializationInterceptorContextRef" to RuntimeType.afterDeserializationInterceptorContextRef(rc),
                 "BeforeDeserializationInterceptorContextMut" to RuntimeType.beforeDeserializationInterceptorContextMut(rc),
@@ -90,6 +95,7 @@ class ClientRuntimeTypesReExportGenerator(
                 "FinalizerInterceptorContextMut" to RuntimeType.finalizerInterceptorContextMut(rc),
                 "FinalizerInterceptorContextRef" to RuntimeType.finalizerInterceptorContextRef(rc),
                 "InterceptorContext" to RuntimeType.interceptorContext(rc),
+                "Input" to smithyRuntimeApi.resolve("client::interceptors::context::Input"),
             )
         }
         rustCrate.withModule(ClientRustModule.Error) {
@@ -32,6 +32,8 @@ class ConfigOverrideRuntimePluginGenerator(
                 "Resolver" to RuntimeType.smithyRuntime(rc).resolve("client::config_override::Resolver"),
                 "RuntimeComponentsBuilder" to RuntimeType.runtimeComponentsBuilder(rc),
                 "RuntimePlugin" to RuntimeType.runtimePlugin(rc),
+                "ValidateOperationConfig" to
+                    RuntimeType.smithyRuntime(rc).resolve("client::config_validation::ValidateOperationConfig"),
             )
         }
 
@@ -45,6 +47,9 @@ class ConfigOverrideRuntimePluginGenerator(
             ///
             /// The `config` method will return a `FrozenLayer` by storing values from `config_override`.
             /// In the case of default values requested, they will be obtained from `client_config`.
+            ///
+            /// The client's config and runtime components are only read from, never written to, so the
+            /// override only applies to the operation invocation it was created for.
             ##[derive(Debug)]
             pub(crate) struct ConfigOverrideRuntimePlugin {
                 pub(crate) config: #{FrozenLayer},
@@ -66,6 +71,8 @@ class ConfigOverrideRuntimePluginGenerator(
                     #{config}
 
                     let _ = resolver;
+                    // The client's config was validated when the client was created, but not with this override
+                    layer.store_put(#{ValidateOperationConfig}::new());
                     Self {
                         config: #{Layer}::from(layer)
                             .with_name("$moduleUseName::config::ConfigOverrideRuntimePlugin").freeze(),
@@ -92,10 +92,12 @@ private fun ClientCodegenContext.errorCorrectedDefault(member: MemberShape): Wri
                     "Some(#{unknown})", *preludeScope,
                     "unknown" to
                         writable {
+                            // No variant was received, so there is no variant name to record
+                            val unknown = "#T::Unknown { variant_name: String::new() }"
                             if (memberSymbol.isRustBoxed()) {
-                                rust("Box::new(#T::Unknown)", targetSymbol)
+                                rust("Box::new($unknown)", targetSymbol)
                             } else {
-                                rust("#T::Unknown", targetSymbol)
+                                rust(unknown, targetSymbol)
                             }
                         },
                 )
@@ -39,6 +39,8 @@ sealed class OperationSection(name: String) : Section(name) {
         val responseStatusName: String,
         /** Name of the response headers map (for referring to it in Rust code) */
         val responseHeadersName: String,
+        /** Name of the response body bytes (for referring to it in Rust code) */
+        val responseBodyName: String,
     ) : OperationSection("PopulateErrorMetadataExtras")
 
     /**
@@ -126,6 +128,22 @@ sealed class OperationSection(name: String) : Section(name) {
         }
     }
 
+    /**
+     * Hook to send the input as a `multipart/form-data` body instead of the protocol's payload.
+     *
+     * Customizations add parts built from the input named [inputName] to the
+     * `aws_smithy_http::multipart::MultipartBodyBuilder` named [builderName], e.g.
+     * `$builderName = $builderName.part(...);`. When any customization writes to this section, the
+     * multipart body replaces the protocol's payload, and its content type, which includes the
+     * boundary, replaces the protocol's content type.
+     */
+    data class MultipartRequestBody(
+        override val customizations: List<OperationCustomization>,
+        val operationShape: OperationShape,
+        val inputName: String,
+        val builderName: String,
+    ) : OperationSection("MultipartRequestBody")
+
     data class CustomizableOperationImpl(
         override val customizations: List<OperationCustomization>,
         val operationShape: OperationShape,
@@ -207,7 +207,7 @@ open class OperationGenerator(
         ResponseDeserializerGenerator(codegenContext, protocol)
             .render(operationWriter, operationShape, operationCustomizations)
         RequestSerializerGenerator(codegenContext, protocol, bodyGenerator)
-            .render(operationWriter, operationShape)
+            .render(operationWriter, operationShape, operationCustomizations)
 
         EndpointParamsInterceptorGenerator(codegenContext)
             .render(operationWriter, operationShape)
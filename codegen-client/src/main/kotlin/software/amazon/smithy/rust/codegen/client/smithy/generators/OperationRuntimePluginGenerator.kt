@@ -7,7 +7,9 @@ package software.amazon.smithy.rust.codegen.client.smithy.generators
 
 import software.amazon.smithy.model.shapes.OperationShape
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.generators.protocol.multipartRequestBodyParts
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
+import software.amazon.smithy.rust.codegen.core.rustlang.isNotEmpty
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
@@ -34,6 +36,8 @@ class OperationRuntimePluginGenerator(
                 "FrozenLayer" to smithyTypes.resolve("config_bag::FrozenLayer"),
                 "IntoShared" to runtimeApi.resolve("shared::IntoShared"),
                 "Layer" to smithyTypes.resolve("config_bag::Layer"),
+                "MultipartRandomSourceInterceptor" to
+                    RuntimeType.smithyHttp(rc).resolve("multipart::MultipartRandomSourceInterceptor"),
                 "RetryClassifiers" to runtimeApi.resolve("client::retries::RetryClassifiers"),
                 "RuntimeComponentsBuilder" to RuntimeType.runtimeComponentsBuilder(codegenContext.runtimeConfig),
                 "RuntimePlugin" to RuntimeType.runtimePlugin(codegenContext.runtimeConfig),
@@ -103,6 +107,10 @@ class OperationRuntimePluginGenerator(
                 },
             "interceptors" to
                 writable {
+                    // Multipart boundaries are generated from the client's random source
+                    if (multipartRequestBodyParts(customizations, operationShape).isNotEmpty()) {
+                        rustTemplate(".with_interceptor(#{MultipartRandomSourceInterceptor}::new())", *codegenScope)
+                    }
                     writeCustomizations(
                         customizations,
                         OperationSection.AdditionalInterceptors(customizations, operationShape),
@@ -109,6 +109,11 @@ class PaginatorGenerator private constructor(
             "Stream" to RuntimeType.TokioStream.resolve("Stream"),
         )
 
+    private val pageType =
+        writable {
+            rustTemplate("#{Result}<#{Output}, #{SdkError}<#{Error}, #{HttpResponse}>>", *codegenScope)
+        }
+
     /** Generate the paginator struct & impl **/
     private fun generate() =
         writable {
@@ -194,15 +199,19 @@ class PaginatorGenerator private constructor(
                             }
                         })))
                     }
+
+                    #{send_with_prefetch}
                 }
                 """,
                 *codegenScope,
                 "items_fn" to itemsFn(),
+                "send_with_prefetch" to
+                    sendWithPrefetch(
+                        pageType,
+                        "self.send().prefetch(depth, #{pagination_stream}::prefetch::PrefetchSpawner::tokio())",
+                    ),
                 "output_token" to outputTokenLens,
-                "item_type" to
-                    writable {
-                        rustTemplate("#{Result}<#{Output}, #{SdkError}<#{Error}, #{HttpResponse}>>", *codegenScope)
-                    },
+                "item_type" to pageType,
                 "orchestrate" to
                     writable {
                         rustTemplate(
@@ -298,6 +307,18 @@ class PaginatorGenerator private constructor(
             null
         } else {
             RuntimeType.forInlineFun("${paginatorName}Items", module) {
+                val extractItems =
+                    NestedAccessorGenerator(codegenContext).generateOwnedAccessor(
+                        outputShape,
+                        paginationInfo.itemsMemberPath,
+                    )
+                val flattenedItemType =
+                    writable {
+                        rustTemplate(
+                            "#{Result}<${itemType()}, #{SdkError}<#{Error}, #{HttpResponse}>>",
+                            *codegenScope,
+                        )
+                    }
                 rustTemplate(
                     """
                     /// Flattened paginator for `$paginatorName`
@@ -315,26 +336,60 @@ class PaginatorGenerator private constructor(
                         pub fn send(self) -> #{pagination_stream}::PaginationStream<#{item_type}> {
                             #{pagination_stream}::TryFlatMap::new(self.0.send()).flat_map(|page| #{extract_items}(page).unwrap_or_default().into_iter())
                         }
+
+                        #{send_with_prefetch}
                     }
 
                     """,
-                    "extract_items" to
-                        NestedAccessorGenerator(codegenContext).generateOwnedAccessor(
-                            outputShape,
-                            paginationInfo.itemsMemberPath,
+                    "extract_items" to extractItems,
+                    "item_type" to flattenedItemType,
+                    "send_with_prefetch" to
+                        sendWithPrefetch(
+                            flattenedItemType,
+                            "#{pagination_stream}::TryFlatMap::new(self.0.send_with_prefetch(depth)).flat_map(|page| #{extract_items}(page).unwrap_or_default().into_iter())",
+                            "extract_items" to extractItems,
                         ),
-                    "item_type" to
-                        writable {
-                            rustTemplate(
-                                "#{Result}<${itemType()}, #{SdkError}<#{Error}, #{HttpResponse}>>",
-                                *codegenScope,
-                            )
-                        },
                     *codegenScope,
                 )
             }
         }
 
+    /**
+     * Generate a `send_with_prefetch` method that returns the stream created by [stream], prefetched
+     * `depth` pages ahead of the reader
+     */
+    private fun sendWithPrefetch(
+        itemType: Writable,
+        stream: String,
+        vararg args: Pair<String, Any>,
+    ) = writable {
+        rustTemplate(
+            """
+            /// Create the pagination stream, fetching up to `depth` pages ahead of the reader
+            ///
+            /// While a page is being processed, the next page is already being fetched by a task spawned onto
+            /// the current Tokio runtime. Since every request needs the pagination token from the page before it,
+            /// pages are still fetched one at a time. `depth` is clamped between 1 and
+            /// [`MAX_PREFETCH_DEPTH`](#{pagination_stream}::prefetch::MAX_PREFETCH_DEPTH).
+            ///
+            /// Dropping the stream cancels any request that is in flight.
+            ///
+            /// _Note:_ No requests will be dispatched until the stream is used
+            /// (e.g. with the [`.next().await`](aws_smithy_async::future::pagination_stream::PaginationStream::next) method).
+            ///
+            /// To prefetch with an async runtime other than Tokio, see
+            /// [`PaginationStream::prefetch`](aws_smithy_async::future::pagination_stream::PaginationStream::prefetch).
+            ##[cfg(feature = "rt-tokio")]
+            pub fn send_with_prefetch(self, depth: usize) -> #{pagination_stream}::PaginationStream<#{item_type}> {
+                $stream
+            }
+            """,
+            *codegenScope,
+            "item_type" to itemType,
+            *args,
+        )
+    }
+
     private fun pageSizeSetter() =
         writable {
             paginationInfo.pageSizeMember.orNull()?.also {
@@ -49,7 +49,7 @@ class ServiceGenerator(
             serviceConfigGenerator.render(this)
 
             // Enable users to opt in to the `test-util` feature in the runtime crate
-            rustCrate.mergeFeature(TestUtilFeature.copy(deps = listOf("aws-smithy-runtime/test-util")))
+            rustCrate.mergeFeature(TestUtilFeature.copy(deps = listOf("aws-smithy-async/test-util", "aws-smithy-runtime/test-util")))
 
             ServiceRuntimePluginGenerator(codegenContext)
                 .render(this, decorator.serviceRuntimePluginCustomizations(codegenContext, emptyList()))
@@ -36,6 +36,9 @@ class CustomizableOperationGenerator(
                 "CustomizableOperation" to
                     ClientRustModule.Client.customize.toType()
                         .resolve("CustomizableOperation"),
+                "ConditionalRequestInterceptor" to
+                    RuntimeType.smithyRuntime(runtimeConfig)
+                        .resolve("client::conditional::ConditionalRequestInterceptor"),
                 "CustomizableSend" to
                     ClientRustModule.Client.customize.toType()
                         .resolve("internal::CustomizableSend"),
@@ -154,6 +157,29 @@ class CustomizableOperationGenerator(
                             self
                         }
 
+                    /// Only performs the operation if the current ETag of the resource matches `etag`, by sending it
+                    /// in an `If-Match` header.
+                    ///
+                    /// `etag` is sent as-is, so it must include the quotes returned by the service, or be `*` to match
+                    /// any version of the resource. If the precondition fails, the operation fails with
+                    /// [`SdkError::PreconditionFailed`](#{SdkError}::PreconditionFailed), which holds the current ETag
+                    /// of the resource when the service returns one.
+                    pub fn if_match(self, etag: impl #{Into}<#{String}>) -> Self {
+                        self.interceptor(#{ConditionalRequestInterceptor}::if_match(etag))
+                    }
+
+                    /// Only performs the operation if the current ETag of the resource doesn't match `etag`, by sending
+                    /// it in an `If-None-Match` header.
+                    ///
+                    /// `etag` is sent as-is, so it must include the quotes returned by the service, or be `*` to only
+                    /// perform the operation if the resource doesn't exist. If the precondition fails, including when
+                    /// the service responds with `304 Not Modified`, the operation fails with
+                    /// [`SdkError::PreconditionFailed`](#{SdkError}::PreconditionFailed), which holds the current ETag
+                    /// of the resource when the service returns one.
+                    pub fn if_none_match(self, etag: impl #{Into}<#{String}>) -> Self {
+                        self.interceptor(#{ConditionalRequestInterceptor}::if_none_match(etag))
+                    }
+
                     /// Overrides config for a single operation invocation.
                     ///
                     /// `config_override` is applied to the operation configuration level.
@@ -27,8 +27,12 @@ import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
 import software.amazon.smithy.rust.codegen.core.smithy.customize.writeCustomizations
 import software.amazon.smithy.rust.codegen.core.smithy.expectRustMetadata
+import software.amazon.smithy.rust.codegen.core.smithy.generators.fromIterSetterArgument
+import software.amazon.smithy.rust.codegen.core.smithy.generators.fromIterSetterName
+import software.amazon.smithy.rust.codegen.core.smithy.generators.fromStrSetterName
 import software.amazon.smithy.rust.codegen.core.smithy.generators.getterName
 import software.amazon.smithy.rust.codegen.core.smithy.generators.setterName
+import software.amazon.smithy.rust.codegen.core.smithy.generators.targetsEnum
 import software.amazon.smithy.rust.codegen.core.smithy.rustType
 import software.amazon.smithy.rust.codegen.core.util.inputShape
 import software.amazon.smithy.rust.codegen.core.util.outputShape
@@ -132,6 +136,10 @@ class FluentBuilderGenerator(
                 /// By default, any retryable failures will be retried twice. Retry behavior
                 /// is configurable with the [RetryConfig](aws_smithy_types::retry::RetryConfig), which can be
                 /// set when configuring the client.
+                ///
+                /// If the config the request would be sent with, including any config override, has fatal
+                /// problems, this fails without sending anything, and the error's source is the
+                /// [`ConfigValidationReport`](crate::config::ConfigValidationReport).
                 pub async fn send(self) -> #{Result}<#{OperationOutput}, #{SdkError}<#{OperationError}, #{HttpResponse}>> {
                     let input = self.inner.build().map_err(#{SdkError}::construction_failure)?;
                     let runtime_plugins = #{Operation}::operation_runtime_plugins(
@@ -271,6 +279,12 @@ class FluentBuilderGenerator(
                 val setterName = member.setterName()
                 val optionalInputType = outerType.asOptional()
                 renderInputHelper(member, setterName, optionalInputType)
+                if (member.targetsEnum(model) && inputShape.members().none { it.setterName() == member.fromStrSetterName() }) {
+                    renderFromStrSetterHelper(member, memberName)
+                }
+                if (inputShape.members().none { it.setterName() == member.fromIterSetterName() }) {
+                    renderFromIterSetterHelper(member, memberName, outerType.stripOuter<RustType.Option>())
+                }
 
                 val getterName = member.getterName()
                 renderGetterHelper(member, getterName, optionalInputType)
@@ -408,6 +422,43 @@ class FluentBuilderGenerator(
         )
     }
 
+    private fun RustWriter.renderFromStrSetterHelper(
+        member: MemberShape,
+        memberName: String,
+    ) {
+        val setterName = member.fromStrSetterName()
+        docs("Sets [`$memberName`](Self::$memberName) from its raw string value, without checking that it's a known variant.")
+        deprecatedShape(member)
+        rustTemplate(
+            """
+            pub fn $setterName(mut self, input: #{Option}<&str>) -> Self {
+                self.inner = self.inner.$setterName(input);
+                self
+            }
+            """,
+            *preludeScope,
+        )
+    }
+
+    private fun RustWriter.renderFromIterSetterHelper(
+        member: MemberShape,
+        memberName: String,
+        coreType: RustType,
+    ) {
+        val (argument, _) = fromIterSetterArgument(coreType) ?: return
+        val setterName = member.fromIterSetterName()
+        docs("Sets [`$memberName`](Self::$memberName) from an iterator, converting each item into the collection's item type.")
+        deprecatedShape(member)
+        rust(
+            """
+            pub fn $setterName(mut self, input: $argument) -> Self {
+                self.inner = self.inner.$setterName(input);
+                self
+            }
+            """,
+        )
+    }
+
     /**
      * Generate and write Rust code for a getter method that returns a reference to the inner data.
      */
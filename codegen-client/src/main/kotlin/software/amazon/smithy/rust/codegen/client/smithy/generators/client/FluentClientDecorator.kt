@@ -9,6 +9,8 @@ import software.amazon.smithy.codegen.core.Symbol
 import software.amazon.smithy.model.shapes.OperationShape
 import software.amazon.smithy.model.shapes.ServiceShape
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.IncrementalJsonListFluentBuilderMethod
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.PartialSuccessFluentBuilderMethod
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
 import software.amazon.smithy.rust.codegen.core.rustlang.Feature
 import software.amazon.smithy.rust.codegen.core.rustlang.Writable
@@ -48,7 +50,12 @@ class FluentClientDecorator : ClientCodegenDecorator {
 
         FluentClientGenerator(
             codegenContext,
-            customizations = listOf(GenericFluentClient(codegenContext)),
+            customizations =
+                listOf(
+                    GenericFluentClient(codegenContext),
+                    IncrementalJsonListFluentBuilderMethod(codegenContext),
+                    PartialSuccessFluentBuilderMethod(codegenContext),
+                ),
         ).render(rustCrate)
 
         rustCrate.mergeFeature(Feature("rustls", default = true, listOf("aws-smithy-runtime/tls-rustls")))
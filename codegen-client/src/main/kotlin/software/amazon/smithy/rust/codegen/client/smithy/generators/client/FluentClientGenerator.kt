@@ -120,24 +120,29 @@ class FluentClientGenerator(
                 impl Client {
                     /// Creates a new client from the service [`Config`](crate::Config).
                     ///
-                    /// ## Panics
-                    ///
-                    /// This method will panic in the following cases:
+                    /// Other problems with the config, such as retries being enabled without a `sleep_impl`,
+                    /// don't cause a panic. They are validated here, once, and sending a request fails with
+                    /// the full [`ConfigValidationReport`](crate::config::ConfigValidationReport) if any of
+                    /// them is fatal. Call [`validation_report`](crate::Config::validation_report) to check a
+                    /// config up front.
                     ///
-                    /// - Retries or timeouts are enabled without a `sleep_impl` configured.
-                    /// - Identity caching is enabled without a `sleep_impl` and `time_source` configured.
-                    /// - No `behavior_version` is provided.
+                    /// ## Panics
                     ///
-                    /// The panic message for each of these will have instructions on how to resolve them.
+                    /// This method will panic if no `behavior_version` is provided and the
+                    /// `${BehaviorVersionLatest.name}` cargo feature isn't enabled.
                     ##[track_caller]
                     pub fn from_conf(conf: crate::Config) -> Self {
+                        let runtime_plugins = #{base_client_runtime_plugins}(conf.clone());
+                        let mut report = #{ConfigValidationReport}::new();
+                        #{validate_client_config}(&runtime_plugins, &mut report);
+                        let mut validation = #{Layer}::new("ClientConfigValidation");
+                        validation.store_put(#{ClientConfigValidation}::new(report));
+                        let runtime_plugins = runtime_plugins
+                            .with_client_plugin(#{StaticRuntimePlugin}::new().with_config(validation.freeze()));
                         let handle = Handle {
-                            conf: conf.clone(),
-                            runtime_plugins: #{base_client_runtime_plugins}(conf),
+                            conf: conf.with_resolved_retry_budget(&runtime_plugins),
+                            runtime_plugins,
                         };
-                        if let Err(err) = Self::validate_config(&handle) {
-                            panic!("Invalid client configuration: {err}");
-                        }
                         Self {
                             handle: #{Arc}::new(handle)
                         }
@@ -147,20 +152,11 @@ class FluentClientGenerator(
                     pub fn config(&self) -> &crate::Config {
                         &self.handle.conf
                     }
-
-                    fn validate_config(handle: &Handle) -> Result<(), #{BoxError}> {
-                        let mut cfg = #{ConfigBag}::base();
-                        handle.runtime_plugins
-                            .apply_client_configuration(&mut cfg)?
-                            .validate_base_client_config(&cfg)?;
-                        Ok(())
-                    }
                 }
                 """,
                 *preludeScope,
                 "Arc" to RuntimeType.Arc,
                 "base_client_runtime_plugins" to baseClientRuntimePluginsFn(codegenContext, customizations),
-                "BoxError" to RuntimeType.boxError(runtimeConfig),
                 "client_docs" to
                     writable {
                         customizations.forEach {
@@ -171,11 +167,23 @@ class FluentClientGenerator(
                             )(this)
                         }
                     },
-                "ConfigBag" to RuntimeType.configBag(runtimeConfig),
                 "RuntimePlugins" to RuntimeType.runtimePlugins(runtimeConfig),
                 "tracing" to CargoDependency.Tracing.toType(),
+                "ClientConfigValidation" to
+                    RuntimeType.smithyRuntime(runtimeConfig).resolve("client::config_validation::ClientConfigValidation"),
+                "ConfigValidationReport" to
+                    RuntimeType.smithyRuntimeApiClient(runtimeConfig)
+                        .resolve("client::config_validation::ConfigValidationReport"),
+                "Layer" to RuntimeType.smithyTypes(runtimeConfig).resolve("config_bag::Layer"),
+                "StaticRuntimePlugin" to
+                    RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::runtime_plugin::StaticRuntimePlugin"),
+                "validate_client_config" to
+                    RuntimeType.smithyRuntime(runtimeConfig).resolve("client::config_validation::validate_client_config"),
             )
         }
+        crate.withModule(ClientRustModule.config) {
+            renderValidateClientConfig(this, codegenContext, customizations)
+        }
 
         operations.forEach { operation ->
             val name = symbolProvider.toSymbol(operation).name
@@ -245,6 +253,49 @@ class FluentClientGenerator(
     }
 }
 
+/**
+ * Renders the validation pass behind `Config::validation_report()`.
+ *
+ * The client's runtime plugins are applied to a scratch config bag so that the defaults a client would pick up
+ * are taken into account. A missing behavior version is reported here, since the runtime plugins can't be
+ * created without one.
+ */
+private fun renderValidateClientConfig(
+    writer: RustWriter,
+    codegenContext: ClientCodegenContext,
+    customizations: List<FluentClientCustomization>,
+) {
+    val rc = codegenContext.runtimeConfig
+    val api = RuntimeType.smithyRuntimeApiClient(rc)
+    writer.rustTemplate(
+        """
+        pub(crate) fn validate_client_config(config: &crate::Config) -> #{ConfigValidationReport} {
+            let mut report = #{ConfigValidationReport}::new();
+            let mut config = config.clone();
+            if config.behavior_version.is_none() {
+                ##[cfg(not(feature = "${BehaviorVersionLatest.name}"))]
+                report.push(#{ConfigIssue}::fatal(
+                    #{ConfigIssueCode}::MissingBehaviorVersion,
+                    "A behavior major version must be set. Call `behavior_version` on the config builder, \
+                     e.g. with `BehaviorVersion::latest()`, or enable the `${BehaviorVersionLatest.name}` cargo feature.",
+                ));
+                // Validate the rest of the config as if the latest behavior version had been set
+                config.behavior_version = #{Some}(#{BehaviorVersion}::latest());
+            }
+            #{validate}(&#{base_client_runtime_plugins}(config), &mut report);
+            report
+        }
+        """,
+        *preludeScope,
+        "base_client_runtime_plugins" to baseClientRuntimePluginsFn(codegenContext, customizations),
+        "BehaviorVersion" to api.resolve("client::behavior_version::BehaviorVersion"),
+        "ConfigIssue" to api.resolve("client::config_validation::ConfigIssue"),
+        "ConfigIssueCode" to api.resolve("client::config_validation::ConfigIssueCode"),
+        "ConfigValidationReport" to api.resolve("client::config_validation::ConfigValidationReport"),
+        "validate" to RuntimeType.smithyRuntime(rc).resolve("client::config_validation::validate_client_config"),
+    )
+}
+
 private fun baseClientRuntimePluginsFn(
     codegenContext: ClientCodegenContext,
     customizations: List<FluentClientCustomization>,
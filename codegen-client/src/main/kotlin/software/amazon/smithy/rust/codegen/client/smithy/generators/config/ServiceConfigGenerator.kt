@@ -299,12 +299,18 @@ class ServiceConfigGenerator(
     private val runtimeConfig = codegenContext.runtimeConfig
     private val enableUserConfigurableRuntimePlugins = codegenContext.enableUserConfigurableRuntimePlugins
     private val smithyTypes = RuntimeType.smithyTypes(runtimeConfig)
+    private val configValidation =
+        RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::config_validation")
     val codegenScope =
         arrayOf(
             *preludeScope,
             "BoxError" to RuntimeType.boxError(runtimeConfig),
             "CloneableLayer" to smithyTypes.resolve("config_bag::CloneableLayer"),
             "ConfigBag" to RuntimeType.configBag(codegenContext.runtimeConfig),
+            "ConfigIssue" to configReexport(configValidation.resolve("ConfigIssue")),
+            "ConfigIssueCode" to configReexport(configValidation.resolve("ConfigIssueCode")),
+            "ConfigValidationReport" to configReexport(configValidation.resolve("ConfigValidationReport")),
+            "Severity" to configReexport(configValidation.resolve("Severity")),
             "Cow" to RuntimeType.Cow,
             "FrozenLayer" to configReexport(smithyTypes.resolve("config_bag::FrozenLayer")),
             "Layer" to configReexport(smithyTypes.resolve("config_bag::Layer")),
@@ -448,7 +454,22 @@ class ServiceConfigGenerator(
                         behavior_version: self.behavior_version,
                     }
                 }
+
+                /// Validates this config, and returns the problems found.
+                ///
+                /// Each [`ConfigIssue`](#{ConfigIssue}) has a machine-readable [`ConfigIssueCode`](#{ConfigIssueCode}),
+                /// a [`Severity`](#{Severity}), and a message saying which config builder method to call to fix it.
+                ///
+                /// Calling this is optional. The config is validated once when a client is created from it,
+                /// and every request fails with this report if any issue is fatal. The config of a request
+                /// with an operation config override is validated again, with the override applied, before
+                /// that request is sent. Validating applies the client's runtime plugins, so avoid calling
+                /// this on a hot path.
+                pub fn validation_report(&self) -> #{ConfigValidationReport} {
+                    validate_client_config(self)
+                }
                 """,
+                *codegenScope,
             )
             customizations.forEach {
                 it.section(ServiceConfig.ConfigImpl)(this)
@@ -538,7 +559,23 @@ class ServiceConfigGenerator(
 
             testUtilOnly.render(this)
             Attribute.AllowUnusedMut.render(this)
-            docs("Apply test defaults to the builder")
+            docs(
+                """
+                Apply test defaults to the builder
+
+                The test defaults replace every source of nondeterminism the orchestrator consumes, so that an
+                operation sends byte-identical requests every time it runs:
+                - Sleeps complete instantly, so retries and timeouts don't wait.
+                - The time source always returns 2009-02-13T23:31:30Z, 1234567890 seconds after the Unix epoch.
+                - The random source is seeded, so retry jitter and invocation IDs are the same on every run.
+                Set the `SMITHY_RANDOM_SEED` environment variable to use a different seed.
+                - Idempotency tokens, if the service has any, are `00000000-0000-4000-8000-000000000000`.
+                - The behavior version is the latest one.
+
+                Services may also install test credentials and a test user agent. Use
+                [`panic_on_real_sleep`](Self::panic_on_real_sleep) to catch components that sleep for real anyway.
+                """,
+            )
             rustBlock("pub fn apply_test_defaults(&mut self) -> &mut Self") {
                 customizations.forEach { it.section(ServiceConfig.DefaultForTests("self"))(this) }
                 rustTemplate("self.behavior_version = #{Some}(crate::config::BehaviorVersion::latest());", *preludeScope)
@@ -547,7 +584,7 @@ class ServiceConfigGenerator(
 
             testUtilOnly.render(this)
             Attribute.AllowUnusedMut.render(this)
-            docs("Apply test defaults to the builder")
+            docs("Apply test defaults to the builder, see [`apply_test_defaults`](Self::apply_test_defaults)")
             rustBlock("pub fn with_test_defaults(mut self) -> Self") {
                 rust("self.apply_test_defaults(); self")
             }
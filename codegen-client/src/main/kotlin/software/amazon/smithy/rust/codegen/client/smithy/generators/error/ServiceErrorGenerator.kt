@@ -183,6 +183,7 @@ class ServiceErrorGenerator(
             ) {
                 rustBlock("match err") {
                     rust("#T::ServiceError(context) => Self::from(context.into_err()),", sdkError)
+                    rust("#T::PreconditionFailed(context) => Self::from(context.into_err()),", sdkError)
                     rustTemplate(
                         """
                         _ => Error::Unhandled(
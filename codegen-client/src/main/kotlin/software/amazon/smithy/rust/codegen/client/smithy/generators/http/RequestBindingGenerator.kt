@@ -81,6 +81,7 @@ class RequestBindingGenerator(
         arrayOf(
             *preludeScope,
             "BuildError" to runtimeConfig.operationBuildError(),
+            "ConfigBag" to RuntimeType.configBag(runtimeConfig),
             "HttpRequestBuilder" to RuntimeType.HttpRequestBuilder,
             "Input" to symbolProvider.toSymbol(inputShape),
         )
@@ -98,6 +99,7 @@ class RequestBindingGenerator(
             """
             fn update_http_builder(
                 input: &#{Input},
+                _cfg: &#{ConfigBag},
                 builder: #{HttpRequestBuilder}
             ) -> #{Result}<#{HttpRequestBuilder}, #{BuildError}>
             """,
@@ -106,7 +108,7 @@ class RequestBindingGenerator(
             rustTemplate("let mut uri = #{String}::new();", *preludeScope)
             write("uri_base(input, &mut uri)?;")
             if (hasQuery) {
-                write("uri_query(input, &mut uri)?;")
+                write("uri_query(input, _cfg, &mut uri)?;")
             }
             if (addHeadersFn != null) {
                 rust(
@@ -154,16 +156,18 @@ class RequestBindingGenerator(
      *
      * This function uses aws_smithy_http::query::Query to append params to a query string:
      * ```rust
-     *    fn uri_query(input: &Input, mut output: &mut String) {
+     *    fn uri_query(input: &Input, _cfg: &ConfigBag, mut output: &mut String) {
      *      let mut query = aws_smithy_http::query::Query::new(&mut output);
      *      if let Some(inner_89) = &input.null_value {
-     *          query.push_kv("Null", &aws_smithy_http::query::fmt_string(&inner_89));
+     *          query.push_kv("Null", &aws_smithy_http::query::fmt_string_param(_cfg, "Null", &inner_89).map_err(...)?);
      *      }
      *      if let Some(inner_90) = &input.empty_string {
-     *          query.push_kv("Empty", &aws_smithy_http::query::fmt_string(&inner_90));
+     *          query.push_kv("Empty", &aws_smithy_http::query::fmt_string_param(_cfg, "Empty", &inner_90).map_err(...)?);
      *      }
      *    }
      *  ```
+     *
+     * String values are passed through as-is if their query param was marked as pre-encoded in the config bag.
      */
     private fun uriQuery(writer: RustWriter): Boolean {
         // Don't bother generating the function if we aren't going to make a query string
@@ -175,7 +179,7 @@ class RequestBindingGenerator(
         }
         val preloadedParams = literalParams.keys + dynamicParams.map { it.locationName }
         writer.rustBlockTemplate(
-            "fn uri_query(_input: &#{Input}, mut output: &mut #{String}) -> #{Result}<(), #{BuildError}>",
+            "fn uri_query(_input: &#{Input}, _cfg: &#{ConfigBag}, mut output: &mut #{String}) -> #{Result}<(), #{BuildError}>",
             *codegenScope,
         ) {
             write("let mut query = #T::new(output);", RuntimeType.queryFormat(runtimeConfig, "Writer"))
@@ -274,7 +278,7 @@ class RequestBindingGenerator(
     ) {
         listForEach(outerTarget, field) { innerField, targetId ->
             val target = model.expectShape(targetId)
-            val value = paramFmtFun(writer, target, memberShape, innerField)
+            val value = paramFmtFun(writer, target, memberShape, param.locationName, innerField)
             rust("""query.push_kv("${param.locationName}", $value);""")
         }
     }
@@ -286,12 +290,16 @@ class RequestBindingGenerator(
         writer: RustWriter,
         target: Shape,
         member: MemberShape,
+        locationName: String,
         targetName: String,
     ): String {
         return when {
             target.isStringShape -> {
-                val func = writer.format(RuntimeType.queryFormat(runtimeConfig, "fmt_string"))
-                "&$func($targetName)"
+                val func = writer.format(RuntimeType.queryFormat(runtimeConfig, "fmt_string_param"))
+                val buildError = writer.format(runtimeConfig.operationBuildError())
+                val memberName = symbolProvider.toMemberName(member).dq()
+                "&$func(_cfg, ${locationName.dq()}, $targetName)" +
+                    ".map_err(|err| $buildError::invalid_field($memberName, err.to_string()))?"
             }
 
             target.isTimestampShape -> {
@@ -331,14 +339,33 @@ class RequestBindingGenerator(
         }
         when {
             target.isStringShape -> {
-                val func = format(RuntimeType.labelFormat(runtimeConfig, "fmt_string"))
                 val encodingStrategy =
                     if (label.isGreedyLabel) {
                         RuntimeType.labelFormat(runtimeConfig, "EncodingStrategy::Greedy")
                     } else {
                         RuntimeType.labelFormat(runtimeConfig, "EncodingStrategy::Default")
                     }
-                rust("let $outputVar = $func($input, #T);", encodingStrategy)
+                // The label rules are shared with servers, so values that a server would reject are
+                // rejected before the request is sent.
+                rustTemplate(
+                    """
+                    let $outputVar = #{fmt_label}($input, #{strategy}, &#{LabelRules}::client()).map_err(|err| {
+                        if err.is_empty() {
+                            #{buildError:W}
+                        } else {
+                            #{invalidField:W}
+                        }
+                    })?;
+                    """,
+                    "fmt_label" to RuntimeType.labelFormat(runtimeConfig, "fmt_label"),
+                    "strategy" to encodingStrategy,
+                    "LabelRules" to RuntimeType.labelFormat(runtimeConfig, "LabelRules"),
+                    "buildError" to buildError,
+                    "invalidField" to
+                        OperationBuildError(runtimeConfig).invalidField(symbolProvider.toMemberName(member)) {
+                            rust("err.to_string()")
+                        },
+                )
             }
 
             target.isTimestampShape -> {
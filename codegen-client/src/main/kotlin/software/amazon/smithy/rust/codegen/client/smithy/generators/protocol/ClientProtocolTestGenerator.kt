@@ -153,6 +153,9 @@ class ClientProtocolTestGenerator(
 
     private val instantiator = ClientInstantiator(codegenContext, withinTest = true)
 
+    /** Whether request tests also fail on headers and query params that they don't expect. */
+    private val strict = codegenContext.settings.codegenConfig.strictProtocolTests
+
     private val codegenScope =
         arrayOf(
             "AssertEq" to RT.PrettyAssertions.resolve("assert_eq!"),
@@ -214,10 +217,16 @@ class ClientProtocolTestGenerator(
         rust("let _ = dbg!(result);")
         rust("""let http_request = request_receiver.expect_request();""")
 
-        checkQueryParams(this, httpRequestTestCase.queryParams)
+        checkQueryParams(this, httpRequestTestCase.queryParams, httpRequestTestCase.requireQueryParams)
         checkForbidQueryParams(this, httpRequestTestCase.forbidQueryParams)
         checkRequiredQueryParams(this, httpRequestTestCase.requireQueryParams)
-        checkHeaders(this, "http_request.headers()", httpRequestTestCase.headers)
+        checkHeaders(
+            this,
+            "http_request.headers()",
+            httpRequestTestCase.headers,
+            exact = strict,
+            ignore = httpRequestTestCase.requireHeaders,
+        )
         checkForbidHeaders(this, "http_request.headers()", httpRequestTestCase.forbidHeaders)
         checkRequiredHeaders(this, "http_request.headers()", httpRequestTestCase.requireHeaders)
 
@@ -428,14 +437,32 @@ class ClientProtocolTestGenerator(
         "forbid_query_params",
     )
 
+    /**
+     * Checks that the [queryParams] are present. In strict mode, also checks that no other query params are present,
+     * except for the [requiredKeys], which are checked separately.
+     */
     private fun checkQueryParams(
         rustWriter: RustWriter,
         queryParams: List<String>,
-    ) = basicCheck(
-        queryParams,
-        rustWriter,
-        "expected_query_params",
-        "&http_request",
-        "validate_query_string",
-    )
+        requiredKeys: List<String>,
+    ) {
+        if (!strict) {
+            return basicCheck(
+                queryParams,
+                rustWriter,
+                "expected_query_params",
+                "&http_request",
+                "validate_query_string",
+            )
+        }
+        rustWriter.rust(
+            "let expected_query_params: &[&str] = &[${queryParams.joinToString(",") { it.dq() }}];",
+        )
+        assertOk(rustWriter) {
+            rust(
+                "#T(&http_request, expected_query_params, &[${requiredKeys.joinToString(",") { it.dq() }}])",
+                RT.protocolTest(rc, "validate_query_string_exact"),
+            )
+        }
+    }
 }
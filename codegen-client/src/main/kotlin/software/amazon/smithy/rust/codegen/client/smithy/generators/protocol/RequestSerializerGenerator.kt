@@ -9,21 +9,41 @@ import software.amazon.smithy.model.shapes.BlobShape
 import software.amazon.smithy.model.shapes.OperationShape
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.ClientRustModule
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationSection
 import software.amazon.smithy.rust.codegen.client.smithy.generators.http.RequestBindingGenerator
 import software.amazon.smithy.rust.codegen.core.rustlang.InlineDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
 import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.isNotEmpty
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.customize.writeCustomizations
 import software.amazon.smithy.rust.codegen.core.smithy.generators.protocol.ProtocolPayloadGenerator
 import software.amazon.smithy.rust.codegen.core.smithy.protocols.Protocol
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.findStreamingMember
 import software.amazon.smithy.rust.codegen.core.util.inputShape
 
+/**
+ * Renders the parts that customizations add to the `multipart` builder of the request serializer for [operationShape].
+ *
+ * The input is sent as a `multipart/form-data` body if and only if this isn't empty.
+ */
+fun multipartRequestBodyParts(
+    customizations: List<OperationCustomization>,
+    operationShape: OperationShape,
+): Writable =
+    writable {
+        writeCustomizations(
+            customizations,
+            OperationSection.MultipartRequestBody(customizations, operationShape, "input", "multipart"),
+        )
+    }
+
 class RequestSerializerGenerator(
     private val codegenContext: ClientCodegenContext,
     private val protocol: Protocol,
@@ -45,6 +65,7 @@ class RequestSerializerGenerator(
             "HttpRequest" to runtimeApi.resolve("client::orchestrator::HttpRequest"),
             "HttpRequestBuilder" to RuntimeType.HttpRequestBuilder,
             "Input" to interceptorContext.resolve("Input"),
+            "MultipartBody" to RuntimeType.smithyHttp(codegenContext.runtimeConfig).resolve("multipart::MultipartBody"),
             "SerializeRequest" to runtimeApi.resolve("client::ser_de::SerializeRequest"),
             "SdkBody" to RuntimeType.sdkBody(codegenContext.runtimeConfig),
             "HeaderSerializationSettings" to
@@ -59,8 +80,11 @@ class RequestSerializerGenerator(
     fun render(
         writer: RustWriter,
         operationShape: OperationShape,
+        customizations: List<OperationCustomization> = emptyList(),
     ) {
         val inputShape = operationShape.inputShape(codegenContext.model)
+        val multipartParts = multipartRequestBodyParts(customizations, operationShape)
+        val isMultipart = multipartParts.isNotEmpty()
         val operationName = symbolProvider.toSymbol(operationShape).name
         val inputSymbol = symbolProvider.toSymbol(inputShape)
         val serializerName = nameOverride ?: "${operationName}RequestSerializer"
@@ -84,10 +108,24 @@ class RequestSerializerGenerator(
             """,
             *codegenScope,
             "ConcreteInput" to inputSymbol,
-            "create_http_request" to createHttpRequest(operationShape),
+            "create_http_request" to createHttpRequest(operationShape, includeContentType = !isMultipart),
             "generate_body" to
                 writable {
-                    if (bodyGenerator != null) {
+                    if (isMultipart) {
+                        rustTemplate(
+                            """
+                            {
+                                let mut multipart = #{MultipartBody}::builder().random_source_from_config(_cfg);
+                                #{parts}
+                                let multipart = multipart.build()?;
+                                request_builder = request_builder.header(#{http}::header::CONTENT_TYPE, multipart.content_type());
+                                multipart.into_sdk_body()
+                            }
+                            """,
+                            *codegenScope,
+                            "parts" to multipartParts,
+                        )
+                    } else if (bodyGenerator != null) {
                         val body =
                             writable {
                                 bodyGenerator.generatePayload(this, "input", operationShape)
@@ -127,7 +165,10 @@ class RequestSerializerGenerator(
     private fun needsContentLength(operationShape: OperationShape): Boolean =
         protocol.needsRequestContentLength(operationShape)
 
-    private fun createHttpRequest(operationShape: OperationShape): Writable =
+    private fun createHttpRequest(
+        operationShape: OperationShape,
+        includeContentType: Boolean,
+    ): Writable =
         writable {
             val httpBindingGenerator =
                 RequestBindingGenerator(
@@ -138,8 +179,8 @@ class RequestSerializerGenerator(
             httpBindingGenerator.renderUpdateHttpBuilder(this)
             val contentType = httpBindingResolver.requestContentType(operationShape)
 
-            rustTemplate("let mut builder = update_http_builder(&input, #{HttpRequestBuilder}::new())?;", *codegenScope)
-            if (contentType != null) {
+            rustTemplate("let mut builder = update_http_builder(&input, _cfg, #{HttpRequestBuilder}::new())?;", *codegenScope)
+            if (contentType != null && includeContentType) {
                 rustTemplate(
                     "builder = _header_serialization_settings.set_default_header(builder, #{http}::header::CONTENT_TYPE, ${contentType.dq()});",
                     *codegenScope,
@@ -0,0 +1,151 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+
+class CapturedResponseHeadersDecoratorTest {
+    private val model =
+        """
+        namespace com.example
+
+        use aws.protocols#restJson1
+
+        @restJson1
+        service HelloService {
+            operations: [SayHello],
+            version: "1"
+        }
+
+        @http(uri: "/", method: "POST")
+        operation SayHello {
+            output: SayHelloOutput,
+            errors: [Throttled]
+        }
+
+        structure SayHelloOutput {
+            greeting: String
+        }
+
+        @error("client")
+        @httpError(429)
+        structure Throttled {
+            message: String
+        }
+        """.asSmithyModel()
+
+    @Test
+    fun `listed headers are captured into outputs and errors`() {
+        clientIntegrationTest(model) { context, rustCrate ->
+            rustCrate.testModule {
+                rustTemplate(
+                    """
+                    use crate::operation::ProvideCapturedHeaders;
+
+                    fn client(status: u16, body: &'static str) -> crate::Client {
+                        let response = move |_: http::Request<#{SdkBody}>| {
+                            http::Response::builder()
+                                .status(status)
+                                .header("x-rate-limit-remaining", "10")
+                                .header("x-region", "us-west-2")
+                                .header("x-unlisted", "unlisted")
+                                .header("x-cache", "miss")
+                                .header("x-cache", "hit")
+                                .header("x-amzn-errortype", "Throttled")
+                                .body(#{SdkBody}::from(body))
+                                .unwrap()
+                        };
+                        let config = crate::Config::builder()
+                            .http_client(#{infallible_client_fn}(response))
+                            .endpoint_url("http://localhost:1234")
+                            .capture_response_headers(&[
+                                http::HeaderName::from_static("x-rate-limit-remaining"),
+                                http::HeaderName::from_static("x-region"),
+                                http::HeaderName::from_static("x-cache"),
+                            ])
+                            .build();
+                        crate::Client::from_conf(config)
+                    }
+
+                    fn assert_captured(captured: #{Option}<&crate::operation::CapturedHeaders>) {
+                        let captured = captured.expect("headers should be captured");
+                        assert_eq!(#{Some}("10"), captured.get("x-rate-limit-remaining"));
+                        assert_eq!(#{Some}("us-west-2"), captured.get("x-region"));
+                        assert_eq!(#{None}, captured.get("x-unlisted"));
+                        assert_eq!(&["miss", "hit"], captured.get_all("x-cache"));
+                    }
+                    """,
+                    *RuntimeType.preludeScope,
+                    "SdkBody" to RuntimeType.sdkBody(context.runtimeConfig),
+                    "infallible_client_fn" to
+                        CargoDependency.smithyRuntimeTestUtil(context.runtimeConfig)
+                            .toType().resolve("client::http::test_util::infallible_client_fn"),
+                )
+
+                tokioTest("headers_are_captured_on_success") {
+                    rustTemplate(
+                        """
+                        let output = client(200, r##"{"greeting": "hello"}"##).say_hello().send().await.unwrap();
+                        assert_eq!(#{Some}("hello"), output.greeting());
+                        assert_captured(output.captured_headers());
+                        """,
+                        *RuntimeType.preludeScope,
+                    )
+                }
+
+                tokioTest("headers_are_captured_on_modeled_errors") {
+                    rustTemplate(
+                        """
+                        let err = client(429, r##"{"message": "slow down"}"##)
+                            .say_hello()
+                            .send()
+                            .await
+                            .expect_err("should fail")
+                            .into_service_error();
+                        assert!(err.is_throttled(), "{err:?}");
+                        assert_captured(err.captured_headers());
+                        """,
+                        *RuntimeType.preludeScope,
+                    )
+                }
+
+                tokioTest("nothing_is_captured_by_default") {
+                    rustTemplate(
+                        """
+                        let response = |_: http::Request<#{SdkBody}>| {
+                            http::Response::builder()
+                                .status(200)
+                                .header("x-region", "us-west-2")
+                                .body(#{SdkBody}::from("{}"))
+                                .unwrap()
+                        };
+                        let client = crate::Client::from_conf(
+                            crate::Config::builder()
+                                .http_client(#{infallible_client_fn}(response))
+                                .endpoint_url("http://localhost:1234")
+                                .build()
+                        );
+                        let output = client.say_hello().send().await.unwrap();
+                        assert_eq!(#{None}, output.captured_headers());
+                        """,
+                        *RuntimeType.preludeScope,
+                        "SdkBody" to RuntimeType.sdkBody(context.runtimeConfig),
+                        "infallible_client_fn" to
+                            CargoDependency.smithyRuntimeTestUtil(context.runtimeConfig)
+                                .toType().resolve("client::http::test_util::infallible_client_fn"),
+                    )
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.model.node.ObjectNode
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+
+class PartialSuccessCustomizationTest {
+    private val model =
+        """
+        namespace com.example
+
+        use aws.protocols#restJson1
+
+        @restJson1
+        service PokemonService {
+            operations: [BatchCatchPokemon],
+            version: "1"
+        }
+
+        @http(uri: "/pokemon", method: "POST")
+        operation BatchCatchPokemon {
+            input: BatchCatchPokemonInput,
+            output: BatchCatchPokemonOutput
+        }
+
+        structure BatchCatchPokemonInput {
+            names: Names
+        }
+
+        structure BatchCatchPokemonOutput {
+            caught: Names,
+            failed: FailedEntries
+        }
+
+        list Names {
+            member: String
+        }
+
+        list FailedEntries {
+            member: FailedEntry
+        }
+
+        structure FailedEntry {
+            name: String,
+            message: String
+        }
+        """.asSmithyModel()
+
+    private val params =
+        IntegrationTestParams(
+            cargoCommand = "cargo test --features behavior-version-latest",
+            additionalSettings =
+                ObjectNode.builder().withMember(
+                    "customizationConfig",
+                    ObjectNode.builder().withMember(
+                        "partialSuccess",
+                        ObjectNode.builder().withMember("com.example#BatchCatchPokemon", "failed").build(),
+                    ).build(),
+                ).build(),
+        )
+
+    @Test
+    fun `send_checked returns the failed entries of batch operations`() {
+        clientIntegrationTest(model, params) { context, rustCrate ->
+            rustCrate.testModule {
+                rustTemplate(
+                    """
+                    fn client(response: &'static str) -> crate::Client {
+                        let http_client = #{infallible_client_fn}(move |_| {
+                            http::Response::builder()
+                                .status(200)
+                                .body(#{SdkBody}::from(response))
+                                .unwrap()
+                        });
+                        let config = crate::Config::builder()
+                            .http_client(http_client)
+                            .endpoint_url("http://localhost:1234")
+                            .build();
+                        crate::Client::from_conf(config)
+                    }
+
+                    const PARTIAL_SUCCESS: &str = r##"{
+                        "caught": ["pikachu"],
+                        "failed": [
+                            {"name": "mewtwo", "message": "broke free"},
+                            {"name": "ditto", "message": "transformed"}
+                        ]
+                    }"##;
+                    """,
+                    "SdkBody" to RuntimeType.sdkBody(context.runtimeConfig),
+                    "infallible_client_fn" to
+                        CargoDependency.smithyRuntimeTestUtil(context.runtimeConfig)
+                            .toType().resolve("client::http::test_util::infallible_client_fn"),
+                )
+
+                tokioTest("send_ignores_failed_entries") {
+                    rustTemplate(
+                        """
+                        let output = client(PARTIAL_SUCCESS).batch_catch_pokemon().send().await.unwrap();
+                        assert_eq!(&["pikachu".to_string()], output.caught());
+                        assert_eq!(2, output.failed().len());
+                        """,
+                    )
+                }
+
+                tokioTest("send_checked_returns_partial_successes") {
+                    rustTemplate(
+                        """
+                        use crate::client::partial_success::SendCheckedError;
+
+                        let err = client(PARTIAL_SUCCESS)
+                            .batch_catch_pokemon()
+                            .send_checked()
+                            .await
+                            .expect_err("two entries failed");
+                        let partial = match &err {
+                            SendCheckedError::PartialSuccess(partial) => partial,
+                            err => panic!("expected a partial success, got {err:?}"),
+                        };
+                        let failed: #{Vec}<_> = partial.failures().iter().map(|entry| entry.name().unwrap()).collect();
+                        assert_eq!(vec!["mewtwo", "ditto"], failed);
+                        assert_eq!("the operation succeeded, but 2 entries failed", err.to_string());
+
+                        let output = err.into_output().expect("the output is recoverable");
+                        assert_eq!(&["pikachu".to_string()], output.caught());
+                        """,
+                        *RuntimeType.preludeScope,
+                    )
+                }
+
+                tokioTest("send_checked_returns_complete_successes") {
+                    rustTemplate(
+                        """
+                        let output = client(r##"{"caught": ["pikachu"], "failed": []}"##)
+                            .batch_catch_pokemon()
+                            .send_checked()
+                            .await
+                            .unwrap();
+                        assert_eq!(&["pikachu".to_string()], output.caught());
+
+                        let output = client(r##"{"caught": ["pikachu"]}"##)
+                            .batch_catch_pokemon()
+                            .send_checked()
+                            .await
+                            .unwrap();
+                        assert_eq!(&["pikachu".to_string()], output.caught());
+                        """,
+                    )
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+
+class RedirectPolicyDecoratorTest {
+    private val model =
+        """
+        namespace com.example
+
+        use aws.protocols#restJson1
+
+        @restJson1
+        service HelloService {
+            operations: [SayHello],
+            version: "1"
+        }
+
+        @http(uri: "/", method: "POST")
+        operation SayHello {
+            input: SayHelloInput,
+            output: SayHelloOutput
+        }
+
+        structure SayHelloInput {
+            name: String
+        }
+
+        structure SayHelloOutput {
+            greeting: String
+        }
+        """.asSmithyModel()
+
+    @Test
+    fun `redirects are followed under the configured policy`() {
+        clientIntegrationTest(model) { context, rustCrate ->
+            rustCrate.testModule {
+                rustTemplate(
+                    """
+                    fn client(redirect_policy: #{Option}<crate::config::RedirectPolicy>) -> (crate::Client, #{Arc}<#{Mutex}<Vec<String>>>) {
+                        let sent = #{Arc}::new(#{Mutex}::new(Vec::new()));
+                        let response = {
+                            let sent = sent.clone();
+                            move |request: http::Request<#{SdkBody}>| {
+                                sent.lock().unwrap().push(request.uri().to_string());
+                                let response = http::Response::builder();
+                                match request.uri().host() {
+                                    #{Some}("primary.example.com") => response
+                                        .status(307)
+                                        .header("location", "http://replica.example.com/")
+                                        .body(#{SdkBody}::empty())
+                                        .unwrap(),
+                                    _ => response
+                                        .status(200)
+                                        .body(#{SdkBody}::from(r##"{"greeting": "hello"}"##))
+                                        .unwrap(),
+                                }
+                            }
+                        };
+                        let mut config = crate::Config::builder()
+                            .http_client(#{infallible_client_fn}(response))
+                            .endpoint_url("http://primary.example.com");
+                        config.set_redirect_policy(redirect_policy);
+                        (crate::Client::from_conf(config.build()), sent)
+                    }
+                    """,
+                    *RuntimeType.preludeScope,
+                    "Arc" to RuntimeType.Arc,
+                    "Mutex" to RuntimeType.std.resolve("sync::Mutex"),
+                    "SdkBody" to RuntimeType.sdkBody(context.runtimeConfig),
+                    "infallible_client_fn" to
+                        CargoDependency.smithyRuntimeTestUtil(context.runtimeConfig)
+                            .toType().resolve("client::http::test_util::infallible_client_fn"),
+                )
+
+                tokioTest("redirects_are_followed_to_allowed_hosts") {
+                    rustTemplate(
+                        """
+                        let policy = crate::config::RedirectPolicy::new().allow_host("replica.example.com");
+                        let (client, sent) = client(#{Some}(policy));
+                        assert!(client.config().redirect_policy().is_some());
+                        let output = client.say_hello().name("world").send().await.unwrap();
+                        assert_eq!(#{Some}("hello"), output.greeting());
+                        assert_eq!(
+                            vec!["http://primary.example.com/", "http://replica.example.com/"],
+                            *sent.lock().unwrap()
+                        );
+                        """,
+                        *RuntimeType.preludeScope,
+                    )
+                }
+
+                tokioTest("redirects_are_not_followed_by_default") {
+                    rustTemplate(
+                        """
+                        let (client, sent) = client(#{None});
+                        client.say_hello().send().await.expect_err("redirects are errors");
+                        assert_eq!(1, sent.lock().unwrap().len());
+                        """,
+                        *RuntimeType.preludeScope,
+                    )
+                }
+            }
+        }
+    }
+}
@@ -7,8 +7,13 @@ package software.amazon.smithy.rust.codegen.client.smithy.customizations
 
 import org.junit.jupiter.api.Test
 import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
 import software.amazon.smithy.rust.codegen.core.testutil.BasicTestModels
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
 import software.amazon.smithy.rust.codegen.core.testutil.unitTest
 
 internal class ResiliencyConfigCustomizationTest {
@@ -26,4 +31,106 @@ internal class ResiliencyConfigCustomizationTest {
             }
         }
     }
+
+    @Test
+    fun `rate limiter can be configured`() {
+        clientIntegrationTest(BasicTestModels.AwsJson10TestModel) { _, crate ->
+            crate.unitTest("rate_limiter") {
+                rustTemplate(
+                    """
+                    use crate::config::retry::RateLimiter;
+
+                    let conf = crate::Config::builder().rate_limit(10.0, 1).build();
+                    assert!(conf.rate_limiter().is_some());
+
+                    let rate_limiter = RateLimiter::new(5.0, 5).per_operation();
+                    let conf = crate::Config::builder().rate_limiter(rate_limiter).build();
+                    let conf = conf.to_builder().build();
+                    assert!(conf.rate_limiter().is_some());
+
+                    assert!(crate::Config::builder().build().rate_limiter().is_none());
+                    """,
+                )
+            }
+        }
+    }
+
+    @Test
+    fun `retry gate can be configured`() {
+        clientIntegrationTest(BasicTestModels.AwsJson10TestModel) { _, crate ->
+            crate.unitTest("retry_gate") {
+                rustTemplate(
+                    """
+                    use crate::config::retry::CircuitBreaker;
+                    use std::time::Duration;
+
+                    let circuit_breaker = CircuitBreaker::new(0.5, 20, Duration::from_secs(30));
+                    let conf = crate::Config::builder().retry_gate(circuit_breaker).build();
+                    let conf = conf.to_builder().build();
+                    assert!(conf.retry_gate().is_some());
+
+                    assert!(crate::Config::builder().build().retry_gate().is_none());
+                    """,
+                )
+            }
+        }
+    }
+
+    @Test
+    fun `clients can share a retry budget`() {
+        clientIntegrationTest(BasicTestModels.AwsJson10TestModel) { codegenContext, rustCrate ->
+            rustCrate.testModule {
+                addDependency(CargoDependency.Tokio.toDevDependency())
+                tokioTest("clients_can_share_a_retry_budget") {
+                    rustTemplate(
+                        """
+                        use crate::config::retry::{RetryConfig, TokenBucket};
+                        use std::time::Duration;
+
+                        let http_client = #{infallible_client_fn}(|_| {
+                            ::http::Response::builder().status(503).body(#{SdkBody}::empty()).unwrap()
+                        });
+                        let config = |retry_budget: #{Option}<TokenBucket>| {
+                            let mut builder = crate::Config::builder()
+                                .endpoint_url("http://localhost:1234")
+                                .http_client(http_client.clone())
+                                .retry_config(
+                                    RetryConfig::standard()
+                                        .with_max_attempts(2)
+                                        .with_initial_backoff(Duration::ZERO),
+                                );
+                            builder.set_retry_budget(retry_budget);
+                            builder.build()
+                        };
+
+                        let retry_budget = TokenBucket::new(100);
+                        let reads = crate::Client::from_conf(config(#{Some}(retry_budget.clone())));
+                        let writes = crate::Client::from_conf(config(#{Some}(retry_budget.clone())));
+                        reads.say_hello().send().await.expect_err("always fails");
+                        let snapshot = writes.config().retry_budget_snapshot().unwrap();
+                        assert!(snapshot.available() < 100, "{snapshot:?}");
+                        assert_eq!(1, snapshot.recent_withdrawals());
+                        assert_eq!(snapshot, retry_budget.snapshot());
+
+                        // clients have their own budget by default, which is only known once the client is created
+                        assert!(config(#{None}).retry_budget_snapshot().is_none());
+                        let other = crate::Client::from_conf(config(#{None}));
+                        let before = other.config().retry_budget_snapshot().expect("client has a budget");
+                        assert_eq!(before.capacity(), before.available());
+                        other.say_hello().send().await.expect_err("always fails");
+                        let after = other.config().retry_budget_snapshot().unwrap();
+                        assert!(after.available() < before.available(), "{after:?}");
+                        assert_eq!(snapshot, retry_budget.snapshot());
+                        assert!(other.config().to_builder().build().retry_budget().is_none());
+                        """,
+                        *preludeScope,
+                        "SdkBody" to RuntimeType.sdkBody(codegenContext.runtimeConfig),
+                        "infallible_client_fn" to
+                            RuntimeType.smithyRuntimeTestUtil(codegenContext.runtimeConfig)
+                                .resolve("infallible_client_fn"),
+                    )
+                }
+            }
+        }
+    }
 }
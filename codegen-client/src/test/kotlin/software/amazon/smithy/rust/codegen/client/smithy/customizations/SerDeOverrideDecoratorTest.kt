@@ -0,0 +1,148 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+import software.amazon.smithy.rust.codegen.core.testutil.unitTest
+
+class SerDeOverrideDecoratorTest {
+    private val model =
+        """
+        namespace com.example
+
+        use aws.protocols#restJson1
+
+        @restJson1
+        service ThingService {
+            operations: [GetThing, ListThings, Ping],
+            version: "1"
+        }
+
+        @http(uri: "/thing", method: "POST")
+        operation GetThing {
+            output := {
+                name: String
+            }
+        }
+
+        @http(uri: "/things", method: "POST")
+        operation ListThings {
+            output := {
+                names: Names
+            }
+        }
+
+        @http(uri: "/ping", method: "POST")
+        operation Ping {
+            output := {
+                message: String
+            }
+        }
+
+        list Names {
+            member: String
+        }
+        """.asSmithyModel(smithyVersion = "2")
+
+    @Test
+    fun `overrides patch a single operation`() {
+        clientIntegrationTest(model) { context, rustCrate ->
+            rustCrate.testModule {
+                rustTemplate(
+                    """
+                    use crate::config::DeserOverride;
+                    use crate::operation::get_thing::GetThingOutput;
+                    use crate::operation::list_things::ListThingsOutput;
+
+                    fn config() -> crate::config::Builder {
+                        // The service misspells the `name` field of every response
+                        let response = |request: http::Request<#{SdkBody}>| {
+                            let body = match request.uri().path() {
+                                "/thing" => r##"{"Nmae": "widget"}"##,
+                                "/things" => r##"{"names": ["real"]}"##,
+                                _ => r##"{"message": "Nmae"}"##,
+                            };
+                            http::Response::builder().status(200).body(#{SdkBody}::from(body)).unwrap()
+                        };
+                        crate::Config::builder()
+                            .http_client(#{infallible_client_fn}(response))
+                            .endpoint_url("http://localhost:1234")
+                    }
+                    """,
+                    *RuntimeType.preludeScope,
+                    "SdkBody" to RuntimeType.sdkBody(context.runtimeConfig),
+                    "infallible_client_fn" to
+                        CargoDependency.smithyRuntimeTestUtil(context.runtimeConfig)
+                            .toType().resolve("client::http::test_util::infallible_client_fn"),
+                )
+
+                tokioTest("responses_can_be_rewritten_or_replaced") {
+                    rustTemplate(
+                        """
+                        let config = config()
+                            .override_deserializer("GetThing", |mut response| {
+                                let body = std::str::from_utf8(response.body().bytes().unwrap()).unwrap();
+                                *response.body_mut() = #{SdkBody}::from(body.replace("Nmae", "name"));
+                                DeserOverride::<GetThingOutput>::Deserialize(response)
+                            })
+                            .override_deserializer("ListThings", |_| {
+                                DeserOverride::Replace(#{Ok}(ListThingsOutput::builder().names("replaced").build()))
+                            })
+                            .build();
+                        let client = crate::Client::from_conf(config);
+
+                        let output = client.get_thing().send().await.unwrap();
+                        assert_eq!(#{Some}("widget"), output.name());
+                        let output = client.list_things().send().await.unwrap();
+                        assert_eq!(&["replaced".to_string()], output.names());
+                        // other operations are untouched
+                        let output = client.ping().send().await.unwrap();
+                        assert_eq!(#{Some}("Nmae"), output.message());
+                        """,
+                        *RuntimeType.preludeScope,
+                        "SdkBody" to RuntimeType.sdkBody(context.runtimeConfig),
+                    )
+                }
+
+                tokioTest("requests_can_be_patched") {
+                    rustTemplate(
+                        """
+                        let config = config()
+                            .override_serializer("Ping", |_: &crate::operation::ping::PingInput, mut request| {
+                                request.set_uri("/thing").unwrap();
+                                #{Ok}(request)
+                            })
+                            .build();
+                        let output = crate::Client::from_conf(config).ping().send().await.unwrap();
+                        assert_eq!(#{None}, output.message());
+                        """,
+                        *RuntimeType.preludeScope,
+                    )
+                }
+
+                unitTest("operations_must_exist") {
+                    rustTemplate(
+                        """
+                        let result = std::panic::catch_unwind(|| {
+                            config().override_deserializer("GetThings", |response| {
+                                DeserOverride::<GetThingOutput>::Deserialize(response)
+                            })
+                        });
+                        assert!(result.is_err());
+                        """,
+                    )
+                }
+            }
+        }
+    }
+}
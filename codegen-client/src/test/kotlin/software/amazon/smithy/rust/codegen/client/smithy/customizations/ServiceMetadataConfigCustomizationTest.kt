@@ -0,0 +1,67 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import io.kotest.matchers.shouldNotBe
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.smithy.customizations.ServiceMetadataCustomization
+import software.amazon.smithy.rust.codegen.core.testutil.BasicTestModels
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.unitTest
+
+class ServiceMetadataConfigCustomizationTest {
+    private val changedModel =
+        """
+        namespace com.example
+        use aws.protocols#awsJson1_0
+        @awsJson1_0
+        service HelloService {
+            operations: [SayHello],
+            version: "2"
+        }
+        @optionalAuth
+        operation SayHello { input: TestInput }
+        structure TestInput {
+           foo: String,
+           bar: Integer,
+        }
+        """.asSmithyModel()
+
+    @Test
+    fun `service metadata is exposed as constants and through the config`() {
+        val hashes =
+            listOf(BasicTestModels.AwsJson10TestModel to "1", changedModel to "2").map { (model, version) ->
+                var hash = ""
+                clientIntegrationTest(model) { codegenContext, rustCrate ->
+                    hash = ServiceMetadataCustomization.modelSha256(codegenContext.model)
+                    rustCrate.testModule {
+                        unitTest("service_metadata") {
+                            rust(
+                                """
+                                assert_eq!("HelloService", crate::meta::SERVICE_NAME);
+                                assert_eq!("$version", crate::meta::SERVICE_VERSION);
+                                assert_eq!("aws.protocols##awsJson1_0", crate::meta::PROTOCOL);
+                                assert!(!crate::meta::SMITHY_RS_VERSION.is_empty());
+                                assert_eq!("$hash", crate::meta::MODEL_SHA256);
+
+                                let metadata = crate::config::Config::service_metadata();
+                                assert_eq!(crate::meta::SERVICE_METADATA, metadata);
+                                assert_eq!("HelloService", metadata.service_name);
+                                assert_eq!("$hash", metadata.model_sha256);
+                                """,
+                            )
+                        }
+                    }
+                }
+                hash
+            }
+
+        hashes[0] shouldNotBe hashes[1]
+    }
+}
@@ -0,0 +1,240 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.model.node.ObjectNode
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+import software.amazon.smithy.rust.codegen.core.testutil.unitTest
+
+class ShapeIntrospectionDecoratorTest {
+    private val model =
+        """
+        ${'$'}version: "2"
+        namespace com.example
+
+        use aws.protocols#restJson1
+
+        @restJson1
+        service HelloService {
+            operations: [GetItem],
+            version: "1"
+        }
+
+        @http(uri: "/item", method: "POST")
+        operation GetItem {
+            input: GetItemInput,
+            output: GetItemOutput
+        }
+
+        structure GetItemInput {
+            id: String
+        }
+
+        structure GetItemOutput {
+            item: Item
+            @httpHeader("x-request-count")
+            count: Integer
+        }
+
+        structure Item {
+            owner: Owner
+            versions: Versions
+            attributes: Attributes
+            status: Status
+            data: Blob
+        }
+
+        structure Owner {
+            name: String
+            @sensitive
+            secret: String
+        }
+
+        list Versions {
+            member: Version
+        }
+
+        structure Version {
+            tags: Tags
+        }
+
+        map Tags {
+            key: String
+            value: String
+        }
+
+        map Attributes {
+            key: String
+            value: Attribute
+        }
+
+        union Attribute {
+            text: String
+            number: Long
+            empty: Unit
+        }
+
+        enum Status {
+            ACTIVE
+            DELETED
+        }
+        """.asSmithyModel()
+
+    private val params =
+        IntegrationTestParams(
+            cargoCommand = "cargo test --features behavior-version-latest",
+            additionalSettings =
+                ObjectNode.builder().withMember(
+                    "codegen",
+                    ObjectNode.builder().withMember("shapeIntrospection", true).build(),
+                ).build(),
+        )
+
+    @Test
+    fun `generated shapes can be navigated by name`() {
+        clientIntegrationTest(model, params) { context, rustCrate ->
+            rustCrate.testModule {
+                rustTemplate(
+                    """
+                    use crate::primitives::shape::{get_path, FieldValueRef, ShapeKind, SmithyShape};
+
+                    /// Finds the first string anywhere in `value` that is equal to `needle`, and returns its path.
+                    fn find_string(value: FieldValueRef<'_>, needle: &str) -> #{Option}<String> {
+                        match value {
+                            FieldValueRef::String(s) if s == needle => #{Some}(String::new()),
+                            FieldValueRef::Shape(shape) => shape.fields().iter().find_map(|field| {
+                                let nested = find_string(shape.get_field(field.name())?, needle)?;
+                                #{Some}(join(field.name(), &nested))
+                            }),
+                            FieldValueRef::List(list) => (0..list.len()).find_map(|index| {
+                                let nested = find_string(list.get(index)?, needle)?;
+                                #{Some}(join(&index.to_string(), &nested))
+                            }),
+                            FieldValueRef::Map(map) => map
+                                .entries()
+                                .find_map(|(key, value)| #{Some}(join(key, &find_string(value, needle)?))),
+                            _ => #{None},
+                        }
+                    }
+
+                    fn join(segment: &str, rest: &str) -> String {
+                        if rest.is_empty() {
+                            segment.to_string()
+                        } else {
+                            format!("{segment}/{rest}")
+                        }
+                    }
+
+                    async fn get_item() -> crate::operation::get_item::GetItemOutput {
+                        let http_client = #{infallible_client_fn}(|_request| {
+                            http::Response::builder()
+                                .status(200)
+                                .header("x-request-count", "3")
+                                .body(#{SdkBody}::from(
+                                    r##"{
+                                        "item": {
+                                            "owner": {"name": "alice", "secret": "hunter2"},
+                                            "versions": [{"tags": {"a": "1"}}, {"tags": {"stage": "needle"}}],
+                                            "attributes": {"color": {"text": "blue"}, "none": {"empty": {}}},
+                                            "status": "ACTIVE",
+                                            "data": "aGVsbG8="
+                                        }
+                                    }"##,
+                                ))
+                                .unwrap()
+                        });
+                        let config = crate::Config::builder()
+                            .http_client(http_client)
+                            .endpoint_url("http://localhost:1234")
+                            .build();
+                        crate::Client::from_conf(config).get_item().send().await.unwrap()
+                    }
+                    """,
+                    *RuntimeType.preludeScope,
+                    "SdkBody" to RuntimeType.sdkBody(context.runtimeConfig),
+                    "infallible_client_fn" to
+                        CargoDependency.smithyRuntimeTestUtil(context.runtimeConfig)
+                            .toType().resolve("client::http::test_util::infallible_client_fn"),
+                )
+
+                tokioTest("deeply_nested_strings_can_be_found_by_path") {
+                    rustTemplate(
+                        """
+                        let output = get_item().await;
+                        assert_eq!(
+                            #{Some}("item/versions/1/tags/stage"),
+                            find_string(FieldValueRef::Shape(&output), "needle").as_deref()
+                        );
+                        assert_eq!(
+                            #{Some}("needle"),
+                            get_path(&output, "item/versions/1/tags/stage").and_then(|v| v.as_str())
+                        );
+                        assert_eq!(#{Some}("alice"), get_path(&output, "item/owner/name").and_then(|v| v.as_str()));
+                        assert_eq!(#{Some}("ACTIVE"), get_path(&output, "item/status").and_then(|v| v.as_str()));
+                        assert!(get_path(&output, "item/versions/2").is_none());
+                        assert!(get_path(&output, "item/unknown").is_none());
+                        """,
+                        *RuntimeType.preludeScope,
+                    )
+                }
+
+                tokioTest("scalars_unions_and_markers_are_exposed") {
+                    rustTemplate(
+                        """
+                        let output = get_item().await;
+                        assert!(matches!(get_path(&output, "count"), #{Some}(FieldValueRef::Integer(3))));
+                        assert!(matches!(get_path(&output, "item/data"), #{Some}(FieldValueRef::Blob)));
+                        assert_eq!(
+                            #{Some}("blue"),
+                            get_path(&output, "item/attributes/color/text").and_then(|v| v.as_str())
+                        );
+                        assert!(get_path(&output, "item/attributes/color/number").is_none());
+                        assert!(matches!(get_path(&output, "item/attributes/none/empty"), #{Some}(FieldValueRef::Null)));
+                        let attribute = get_path(&output, "item/attributes/color").unwrap().as_shape().unwrap();
+                        assert_eq!("Attribute", attribute.shape_name());
+                        """,
+                        *RuntimeType.preludeScope,
+                    )
+                }
+
+                unitTest("field_metadata_is_generated") {
+                    rustTemplate(
+                        """
+                        let owner = crate::types::Owner::builder().name("alice").build();
+                        assert_eq!("Owner", owner.shape_name());
+                        let fields: Vec<_> = owner
+                            .fields()
+                            .iter()
+                            .map(|field| (field.name(), field.kind(), field.is_sensitive()))
+                            .collect();
+                        assert_eq!(
+                            vec![("name", ShapeKind::String, false), ("secret", ShapeKind::String, true)],
+                            fields
+                        );
+                        assert!(owner.get_field("secret").is_none());
+
+                        let item = crate::types::Item::builder().build();
+                        let kinds: Vec<_> = item.fields().iter().map(|field| field.kind()).collect();
+                        assert_eq!(
+                            vec![ShapeKind::Structure, ShapeKind::List, ShapeKind::Map, ShapeKind::Enum, ShapeKind::Blob],
+                            kinds
+                        );
+                        """,
+                        *RuntimeType.preludeScope,
+                    )
+                }
+            }
+        }
+    }
+}
@@ -12,10 +12,14 @@ import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
 import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
 import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
 import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
 import software.amazon.smithy.rust.codegen.core.testutil.runWithWarnings
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
 import software.amazon.smithy.rust.codegen.core.util.CommandError
 import software.amazon.smithy.rust.codegen.core.util.runCommand
 
@@ -314,6 +318,35 @@ class EndpointsDecoratorTest {
                                 .withFeature("rt-tokio").toType().resolve("rt::sleep::TokioSleep"),
                     )
                 }
+                rustCrate.integrationTest("endpoint_resolver_test_util") {
+                    val moduleName = clientCodegenContext.moduleUseName()
+                    Attribute.TokioTest.render(this)
+                    rustTemplate(
+                        """
+                        async fn resolvers_can_be_tested_with_params_built_by_hand() {
+                            use aws_smithy_runtime::client::endpoint::test_util::assert_endpoints;
+                            use $moduleName::config::endpoint::{DefaultResolver, Params, ResolveEndpoint};
+
+                            let params = |region: &str| {
+                                Params::builder()
+                                    .region(region)
+                                    .a_bool_param(false)
+                                    .build()
+                                    .expect("the params are valid")
+                            };
+                            assert_eq!(Some("some-default"), params("us-east-1").built_in_with_default());
+                            assert_endpoints(
+                                &DefaultResolver::new().into_shared_resolver(),
+                                [
+                                    (params("us-east-1"), "https://www.us-east-1.example.com"),
+                                    (params("eu-west-1"), "https://www.eu-west-1.example.com"),
+                                ],
+                            )
+                            .await;
+                        }
+                        """,
+                    )
+                }
             }
         // the model has an intentionally failing test—ensure it fails
         val failure = shouldThrow<CommandError> { "cargo test".runWithWarnings(testDir) }
@@ -321,4 +354,225 @@ class EndpointsDecoratorTest {
         failure.output shouldContain "https://failingtest.com"
         "cargo clippy".runWithWarnings(testDir)
     }
+
+    private val bindingsModel =
+        """
+        namespace test
+
+        use smithy.rules#endpointRuleSet
+        use smithy.rules#staticContextParams
+        use smithy.rules#operationContextParams
+        use smithy.rules#contextParam
+        use aws.protocols#awsJson1_1
+
+        @awsJson1_1
+        @endpointRuleSet({
+            "version": "1.0",
+            "rules": [
+                {
+                    "conditions": [{"fn": "isSet", "argv": [{"ref": "AccountId"}]}],
+                    "type": "endpoint",
+                    "endpoint": {"url": "https://{AccountId}.accounts.{Stage}.example.com"}
+                },
+                {
+                    "conditions": [
+                        {"fn": "isSet", "argv": [{"ref": "TableName"}]},
+                        {"fn": "isSet", "argv": [{"ref": "Mode"}]},
+                        {"fn": "stringEquals", "argv": [{"ref": "Mode"}, "fast"]}
+                    ],
+                    "type": "endpoint",
+                    "endpoint": {"url": "https://fast.{Stage}.example.com/{TableName}"}
+                },
+                {
+                    "conditions": [{"fn": "isSet", "argv": [{"ref": "TableName"}]}],
+                    "type": "endpoint",
+                    "endpoint": {"url": "https://tables.{Stage}.example.com/{TableName}"}
+                },
+                {
+                    "conditions": [{"fn": "isSet", "argv": [{"ref": "ResourceNames"}]}],
+                    "type": "endpoint",
+                    "endpoint": {"url": "https://batch.{Stage}.example.com"}
+                },
+                {
+                    "conditions": [],
+                    "type": "endpoint",
+                    "endpoint": {"url": "https://{Stage}.example.com"}
+                }
+            ],
+            "parameters": {
+                "Stage": {"required": true, "type": "string", "default": "prod"},
+                "TableName": {"required": false, "type": "string"},
+                "Mode": {"required": false, "type": "string"},
+                "AccountId": {"required": false, "type": "string"},
+                "ResourceNames": {"required": false, "type": "stringArray"}
+            }
+        })
+        service TestService {
+            operations: [GetItem, ListItems]
+        }
+
+        @staticContextParams(Stage: {value: "beta"})
+        @operationContextParams(
+            AccountId: {path: "target.owner.accountId"}
+            ResourceNames: {path: "resources[*].name"}
+        )
+        operation GetItem {
+            input: GetItemInput
+        }
+
+        operation ListItems {}
+
+        @input
+        structure GetItemInput {
+            @contextParam(name: "TableName")
+            tableName: String
+            @contextParam(name: "Mode")
+            mode: Mode
+            target: Target
+            resources: ResourceList
+        }
+
+        enum Mode {
+            FAST = "fast"
+            SLOW = "slow"
+        }
+
+        structure Target {
+            owner: Owner
+        }
+
+        structure Owner {
+            accountId: String
+        }
+
+        list ResourceList {
+            member: Resource
+        }
+
+        structure Resource {
+            name: String
+        }
+        """.asSmithyModel(smithyVersion = "2.0", disableValidation = true)
+
+    @Test
+    fun `context params, static context params, and operation context params select the endpoint`() {
+        clientIntegrationTest(bindingsModel) { codegenContext, rustCrate ->
+            rustCrate.testModule {
+                addDependency(CargoDependency.Tokio.toDevDependency().withFeature("test-util"))
+                tokioTest("bindings_select_the_endpoint") {
+                    rustTemplate(
+                        """
+                        use crate::config::endpoint::{EndpointParamBindings, EndpointParamSource, Params};
+                        use crate::types::{Mode, Owner, Resource, Target};
+                        use ::std::sync::{Arc, Mutex};
+
+                        type Captured = Arc<Mutex<#{Option}<(Params, EndpointParamBindings)>>>;
+
+                        ##[derive(Debug)]
+                        struct CaptureParams(Captured);
+
+                        impl crate::config::Intercept for CaptureParams {
+                            fn name(&self) -> &'static str {
+                                "CaptureParams"
+                            }
+
+                            fn read_before_transmit(
+                                &self,
+                                _context: &crate::config::interceptors::BeforeTransmitInterceptorContextRef<'_>,
+                                _runtime_components: &crate::config::RuntimeComponents,
+                                cfg: &mut crate::config::ConfigBag,
+                            ) -> #{Result}<(), crate::error::BoxError> {
+                                let params = cfg
+                                    .load::<crate::config::endpoint::EndpointResolverParams>()
+                                    .and_then(|params| params.get::<Params>())
+                                    .expect("params are set")
+                                    .clone();
+                                let bindings = cfg.load::<EndpointParamBindings>().expect("bindings are set").clone();
+                                *self.0.lock().unwrap() = #{Some}((params, bindings));
+                                #{Ok}(())
+                            }
+                        }
+
+                        fn client() -> (crate::Client, #{CaptureRequestReceiver}, Captured) {
+                            let (http_client, request) = #{capture_request}(#{None});
+                            let captured = Captured::default();
+                            let config = crate::Config::builder()
+                                .http_client(http_client)
+                                .interceptor(CaptureParams(captured.clone()))
+                                .build();
+                            (crate::Client::from_conf(config), request, captured)
+                        }
+
+                        // Unset members leave their params unset rather than setting them to empty strings
+                        let (client, request, captured) = client();
+                        let _ = client.get_item().send().await;
+                        assert_eq!("https://beta.example.com", request.expect_request().uri());
+                        let (params, bindings) = captured.lock().unwrap().take().unwrap();
+                        assert_eq!(#{None}, params.table_name());
+                        assert_eq!(#{None}, params.account_id());
+                        assert_eq!(#{None}, params.resource_names());
+                        assert_eq!(#{Some}(EndpointParamSource::StaticContextParam), bindings.get("Stage"));
+                        assert_eq!(#{None}, bindings.get("TableName"));
+
+                        let (client, request, _) = client();
+                        let _ = client.get_item().table_name("orders").send().await;
+                        assert_eq!("https://tables.beta.example.com/orders", request.expect_request().uri());
+
+                        // Enum members are bound by their string value
+                        let (client, request, captured) = client();
+                        let _ = client.get_item().table_name("orders").mode(Mode::Fast).send().await;
+                        assert_eq!("https://fast.beta.example.com/orders", request.expect_request().uri());
+                        let (_, bindings) = captured.lock().unwrap().take().unwrap();
+                        assert_eq!(#{Some}(EndpointParamSource::ContextParam), bindings.get("Mode"));
+                        assert!(::std::format!("{bindings:?}").contains("\"TableName\": ContextParam"), "{bindings:?}");
+
+                        // Nested paths are followed
+                        let (client, request, captured) = client();
+                        let _ = client
+                            .get_item()
+                            .target(Target::builder().owner(Owner::builder().account_id("123456789012").build()).build())
+                            .send()
+                            .await;
+                        assert_eq!("https://123456789012.accounts.beta.example.com", request.expect_request().uri());
+                        let (_, bindings) = captured.lock().unwrap().take().unwrap();
+                        assert_eq!(#{Some}(EndpointParamSource::OperationContextParam), bindings.get("AccountId"));
+
+                        // A nested path that stops at an unset member leaves the param unset
+                        let (client, request, _) = client();
+                        let _ = client.get_item().target(Target::builder().build()).send().await;
+                        assert_eq!("https://beta.example.com", request.expect_request().uri());
+
+                        // Projections select a list of strings
+                        let (client, request, captured) = client();
+                        let _ = client
+                            .get_item()
+                            .resources(Resource::builder().name("a").build())
+                            .resources(Resource::builder().build())
+                            .resources(Resource::builder().name("b").build())
+                            .send()
+                            .await;
+                        assert_eq!("https://batch.beta.example.com", request.expect_request().uri());
+                        let (params, _) = captured.lock().unwrap().take().unwrap();
+                        assert_eq!(
+                            #{Some}(&["a".to_string(), "b".to_string()][..]),
+                            params.resource_names()
+                        );
+
+                        // Without a static value, the param's default is used
+                        let (client, request, captured) = client();
+                        let _ = client.list_items().send().await;
+                        assert_eq!("https://prod.example.com", request.expect_request().uri());
+                        let (_, bindings) = captured.lock().unwrap().take().unwrap();
+                        assert_eq!(#{None}, bindings.get("Stage"));
+                        """,
+                        *preludeScope,
+                        "capture_request" to RuntimeType.captureRequest(codegenContext.runtimeConfig),
+                        "CaptureRequestReceiver" to
+                            RuntimeType.smithyRuntimeTestUtil(codegenContext.runtimeConfig)
+                                .resolve("CaptureRequestReceiver"),
+                    )
+                }
+            }
+        }
+    }
 }
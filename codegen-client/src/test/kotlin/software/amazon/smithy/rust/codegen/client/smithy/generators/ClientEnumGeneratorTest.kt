@@ -8,11 +8,15 @@ package software.amazon.smithy.rust.codegen.client.smithy.generators
 import org.junit.jupiter.api.Test
 import software.amazon.smithy.model.Model
 import software.amazon.smithy.model.shapes.StringShape
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
 import software.amazon.smithy.rust.codegen.client.testutil.testClientCodegenContext
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.testutil.TestWorkspace
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.compileAndTest
+import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
 import software.amazon.smithy.rust.codegen.core.testutil.unitTest
 import software.amazon.smithy.rust.codegen.core.util.lookup
 
@@ -176,4 +180,153 @@ class ClientEnumGeneratorTest {
         }
         project.compileAndTest()
     }
+
+    @Test
+    fun `enums can be formatted and parsed back`() {
+        val model =
+            """
+            namespace test
+            @enum([
+                { value: "t2.nano", name: "T2_NANO" },
+                { value: "t2.micro", name: "T2_MICRO" },
+            ])
+            string InstanceType
+
+            @enum([
+                { value: "Foo" },
+                { value: "Bar" },
+            ])
+            string UnnamedEnum
+            """.asSmithyModel()
+
+        val context = testClientCodegenContext(model)
+        val project = TestWorkspace.testProject(context.symbolProvider)
+        val namedShape = model.lookup<StringShape>("test#InstanceType")
+        val unnamedShape = model.lookup<StringShape>("test#UnnamedEnum")
+        project.moduleFor(namedShape) {
+            rust("##![allow(deprecated)]")
+            ClientEnumGenerator(context, namedShape).render(this)
+            ClientEnumGenerator(context, unnamedShape).render(this)
+            unitTest(
+                "enums_can_be_formatted_and_parsed_back",
+                """
+                use std::str::FromStr;
+
+                fn as_ref_str(value: &impl AsRef<str>) -> &str {
+                    value.as_ref()
+                }
+
+                for value in ["t2.nano", "other"] {
+                    let parsed = InstanceType::from_str(value).unwrap();
+                    assert_eq!(format!("{}", parsed), value);
+                    assert_eq!(as_ref_str(&parsed), value);
+                    assert_eq!(InstanceType::from_str(&parsed.to_string()).unwrap(), parsed);
+                }
+
+                for value in ["Foo", "other"] {
+                    let parsed = UnnamedEnum::from_str(value).unwrap();
+                    assert_eq!(format!("{}", parsed), value);
+                    assert_eq!(UnnamedEnum::from_str(&parsed.to_string()).unwrap(), parsed);
+                }
+                """,
+            )
+        }
+        project.compileAndTest()
+    }
+
+    @Test
+    fun `enum members have raw string accessors`() {
+        val model =
+            """
+            namespace test
+            use aws.protocols#awsJson1_0
+
+            @awsJson1_0
+            service TestService {
+                operations: [PutInstance],
+                version: "1"
+            }
+
+            @optionalAuth
+            operation PutInstance { input: PutInstanceInput, output: PutInstanceOutput }
+
+            structure PutInstanceInput {
+                instanceType: InstanceType
+            }
+
+            structure PutInstanceOutput {
+                instanceType: InstanceType,
+                state: State
+            }
+
+            @enum([
+                { value: "t2.nano", name: "T2_NANO" },
+                { value: "t2.micro", name: "T2_MICRO" },
+            ])
+            string InstanceType
+
+            enum State {
+                RUNNING = "running"
+                STOPPED = "stopped"
+            }
+            """.asSmithyModel(smithyVersion = "2")
+
+        clientIntegrationTest(model) { codegenContext, rustCrate ->
+            rustCrate.integrationTest("enum_raw_string_accessors") {
+                val moduleName = codegenContext.moduleUseName()
+                rustTemplate(
+                    """
+                    use $moduleName::operation::put_instance::PutInstanceOutput;
+                    use $moduleName::types::{InstanceType, State};
+
+                    ##[test]
+                    fn known_values() {
+                        let output = PutInstanceOutput::builder()
+                            .set_instance_type_from_str(Some("t2.micro"))
+                            .state(State::Running)
+                            .build();
+                        assert_eq!(Some(&InstanceType::T2Micro), output.instance_type());
+                        assert_eq!(Some("t2.micro"), output.instance_type_as_str());
+                        assert_eq!(Some("running"), output.state_as_str());
+                    }
+
+                    ##[test]
+                    fn unknown_values() {
+                        let output = PutInstanceOutput::builder()
+                            .set_instance_type_from_str(Some("t3.large"))
+                            .set_state_from_str(Some("hibernating"))
+                            .build();
+                        assert_eq!(Some(&InstanceType::from("t3.large")), output.instance_type());
+                        assert_eq!(Some("t3.large"), output.instance_type_as_str());
+                        assert_eq!(Some("hibernating"), output.state_as_str());
+                    }
+
+                    ##[test]
+                    fn unset_values() {
+                        let output = PutInstanceOutput::builder()
+                            .state(State::Stopped)
+                            .set_state_from_str(None)
+                            .build();
+                        assert_eq!(None, output.instance_type_as_str());
+                        assert_eq!(None, output.state_as_str());
+                    }
+
+                    ##[test]
+                    fn fluent_builder() {
+                        let config = $moduleName::Config::builder()
+                            .endpoint_url("http://localhost:1234")
+                            .http_client(#{NeverClient}::new())
+                            .build();
+                        let client = $moduleName::Client::from_conf(config);
+                        let builder = client.put_instance().set_instance_type_from_str(Some("t3.large"));
+                        assert_eq!(&Some(InstanceType::from("t3.large")), builder.get_instance_type());
+                    }
+                    """,
+                    "NeverClient" to
+                        CargoDependency.smithyRuntimeTestUtil(codegenContext.runtimeConfig).toType()
+                            .resolve("client::http::test_util::NeverClient"),
+                )
+            }
+        }
+    }
 }
@@ -257,4 +257,190 @@ internal class ConfigOverrideRuntimePluginGeneratorTest {
             }
         }
     }
+
+    @Test
+    fun `operation overrides do not leak into other operations`() {
+        clientIntegrationTest(model) { clientCodegenContext, rustCrate ->
+            val runtimeConfig = clientCodegenContext.runtimeConfig
+            rustCrate.testModule {
+                addDependency(CargoDependency.Tokio.toDevDependency().withFeature("test-util"))
+                rustTemplate(
+                    """
+                    use std::sync::{Arc, Mutex};
+                    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+                    ##[derive(Clone, Debug, Default, PartialEq)]
+                    struct Observed {
+                        http_client: #{Option}<&'static str>,
+                        uri: #{Option}<#{String}>,
+                        time: #{Option}<SystemTime>,
+                        max_attempts: #{Option}<u32>,
+                        override_interceptor_ran: bool,
+                    }
+
+                    /// Observations keyed by request body, which contains the name of the call.
+                    ##[derive(Clone, Debug, Default)]
+                    struct Observations(Arc<Mutex<#{Vec}<(#{String}, Observed)>>>);
+
+                    impl Observations {
+                        fn update(&self, body: &[u8], f: impl #{FnOnce}(&mut Observed)) {
+                            let body = ::std::str::from_utf8(body).unwrap().to_string();
+                            let mut observations = self.0.lock().unwrap();
+                            let index = match observations.iter().position(|(b, _)| *b == body) {
+                                #{Some}(index) => index,
+                                #{None} => {
+                                    observations.push((body, Observed::default()));
+                                    observations.len() - 1
+                                }
+                            };
+                            f(&mut observations[index].1)
+                        }
+
+                        fn get(&self, name: &str) -> Observed {
+                            let name = format!("\"{name}\"");
+                            self.0.lock().unwrap().iter()
+                                .find(|(body, _)| body.contains(&name))
+                                .map(|(_, observed)| observed.clone())
+                                .unwrap_or_default()
+                        }
+                    }
+
+                    ##[derive(Debug)]
+                    struct RecordComponents(Observations, bool);
+
+                    impl #{Intercept} for RecordComponents {
+                        fn name(&self) -> &'static str {
+                            "RecordComponents"
+                        }
+
+                        fn read_before_transmit(
+                            &self,
+                            context: &#{BeforeTransmitInterceptorContextRef}<'_>,
+                            runtime_components: &#{RuntimeComponents},
+                            cfg: &mut #{ConfigBag},
+                        ) -> #{Result}<(), #{BoxError}> {
+                            let request = context.request();
+                            let is_override = self.1;
+                            self.0.update(request.body().bytes().unwrap(), |observed| {
+                                if is_override {
+                                    observed.override_interceptor_ran = true;
+                                } else {
+                                    observed.uri = #{Some}(request.uri().to_string());
+                                    observed.time = runtime_components.time_source().map(|ts| ts.now());
+                                    observed.max_attempts = cfg.load::<#{RetryConfig}>().map(|rc| rc.max_attempts());
+                                }
+                            });
+                            #{Ok}(())
+                        }
+                    }
+
+                    fn http_client(name: &'static str, observations: Observations) -> #{SharedHttpClient} {
+                        #{infallible_client_fn}(move |request| {
+                            observations.update(request.body().bytes().unwrap(), |observed| {
+                                observed.http_client = #{Some}(name)
+                            });
+                            http::Response::builder().status(200).body(#{SdkBody}::from("{}")).unwrap()
+                        })
+                    }
+
+                    fn client(observations: &Observations) -> crate::Client {
+                        let config = crate::Config::builder()
+                            .http_client(http_client("default", observations.clone()))
+                            .endpoint_url("http://default.localhost")
+                            .retry_config(#{RetryConfig}::standard().with_max_attempts(3))
+                            .time_source(#{StaticTimeSource}::new(UNIX_EPOCH + Duration::from_secs(1000)))
+                            .interceptor(RecordComponents(observations.clone(), false))
+                            .build();
+                        crate::Client::from_conf(config)
+                    }
+
+                    fn config_override(observations: &Observations) -> crate::config::Builder {
+                        crate::Config::builder()
+                            .http_client(http_client("override", observations.clone()))
+                            .endpoint_url("http://override.localhost")
+                            .retry_config(#{RetryConfig}::disabled())
+                            .time_source(#{StaticTimeSource}::new(UNIX_EPOCH + Duration::from_secs(2000)))
+                            .interceptor(RecordComponents(observations.clone(), true))
+                    }
+
+                    async fn call(client: &crate::Client, name: &str, config_override: #{Option}<crate::config::Builder>) {
+                        let fluent_builder = client.say_hello().foo(name);
+                        match config_override {
+                            #{Some}(config_override) => fluent_builder.customize().config_override(config_override).send().await,
+                            #{None} => fluent_builder.send().await,
+                        }
+                        .expect("success");
+                    }
+
+                    fn expected(http_client: &'static str) -> Observed {
+                        let overridden = http_client == "override";
+                        Observed {
+                            http_client: #{Some}(http_client),
+                            uri: #{Some}(format!("http://{http_client}.localhost/")),
+                            time: #{Some}(UNIX_EPOCH + Duration::from_secs(if overridden { 2000 } else { 1000 })),
+                            max_attempts: #{Some}(if overridden { 1 } else { 3 }),
+                            override_interceptor_ran: overridden,
+                        }
+                    }
+                    """,
+                    *preludeScope,
+                    "BeforeTransmitInterceptorContextRef" to
+                        RuntimeType.beforeTransmitInterceptorContextRef(runtimeConfig),
+                    "BoxError" to RuntimeType.boxError(runtimeConfig),
+                    "ConfigBag" to RuntimeType.configBag(runtimeConfig),
+                    "Intercept" to RuntimeType.intercept(runtimeConfig),
+                    "RetryConfig" to RuntimeType.smithyTypes(runtimeConfig).resolve("retry::RetryConfig"),
+                    "RuntimeComponents" to RuntimeType.runtimeComponents(runtimeConfig),
+                    "SdkBody" to RuntimeType.sdkBody(runtimeConfig),
+                    "SharedHttpClient" to
+                        RuntimeType.smithyRuntimeApiClient(runtimeConfig).resolve("client::http::SharedHttpClient"),
+                    "StaticTimeSource" to RuntimeType.smithyAsync(runtimeConfig).resolve("time::StaticTimeSource"),
+                    "infallible_client_fn" to
+                        CargoDependency.smithyRuntimeTestUtil(runtimeConfig)
+                            .toType().resolve("client::http::test_util::infallible_client_fn"),
+                )
+
+                tokioTest("overrides_do_not_leak_into_later_operations") {
+                    rustTemplate(
+                        """
+                        let observations = Observations::default();
+                        let client = client(&observations);
+
+                        call(&client, "before", #{None}).await;
+                        call(&client, "overridden", #{Some}(config_override(&observations))).await;
+                        call(&client, "after", #{None}).await;
+
+                        assert_eq!(expected("default"), observations.get("before"));
+                        assert_eq!(expected("override"), observations.get("overridden"));
+                        assert_eq!(expected("default"), observations.get("after"));
+                        """,
+                        *preludeScope,
+                    )
+                }
+
+                tokioTest("overrides_do_not_leak_into_concurrent_operations") {
+                    rustTemplate(
+                        """
+                        let observations = Observations::default();
+                        let client = client(&observations);
+
+                        let names: #{Vec}<#{String}> = (0..20).map(|i| format!("call-{i}")).collect();
+                        let calls = names.iter().enumerate().map(|(i, name)| {
+                            let config_override = (i % 2 == 0).then(|| config_override(&observations));
+                            call(&client, name, config_override)
+                        });
+                        #{join_all}(calls).await;
+
+                        for (i, name) in names.iter().enumerate() {
+                            let expected = expected(if i % 2 == 0 { "override" } else { "default" });
+                            assert_eq!(expected, observations.get(name), "{name}");
+                        }
+                        """,
+                        *preludeScope,
+                        "join_all" to CargoDependency.FuturesUtil.toType().resolve("future::join_all"),
+                    )
+                }
+            }
+        }
+    }
 }
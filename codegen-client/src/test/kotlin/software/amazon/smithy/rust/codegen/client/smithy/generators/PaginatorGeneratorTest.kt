@@ -12,9 +12,15 @@ import software.amazon.smithy.model.transform.ModelTransformer
 import software.amazon.smithy.rust.codegen.client.smithy.traits.IsTruncatedPaginatorTrait
 import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
 import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
 import software.amazon.smithy.rust.codegen.core.util.letIf
 
 internal class PaginatorGeneratorTest {
@@ -103,4 +109,62 @@ internal class PaginatorGeneratorTest {
             }
         }
     }
+
+    @Test
+    fun `prefetching paginators yield every page in order`() {
+        clientIntegrationTest(model) { codegenContext, rustCrate ->
+            rustCrate.testModule {
+                addDependency(CargoDependency.Tokio.toDevDependency())
+                tokioTest("prefetching_paginators_yield_every_page_in_order") {
+                    rustTemplate(
+                        """
+                        let http_client = #{infallible_client_fn}(|request| {
+                            let body = ::std::str::from_utf8(request.body().bytes().unwrap()).unwrap();
+                            let page = if body.contains(r##""nextToken":"2""##) {
+                                r##"{"inner": {"items": ["c"], "mapItems": {}}}"##
+                            } else if body.contains(r##""nextToken":"1""##) {
+                                r##"{"inner": {"token": "2", "items": ["b"], "mapItems": {}}}"##
+                            } else {
+                                r##"{"inner": {"token": "1", "items": ["a"], "mapItems": {}}}"##
+                            };
+                            ::http::Response::builder().status(200).body(#{SdkBody}::from(page)).unwrap()
+                        });
+                        let config = crate::Config::builder()
+                            .endpoint_url("http://localhost:1234")
+                            .http_client(http_client)
+                            .build();
+                        let client = crate::Client::from_conf(config);
+
+                        let pages = client
+                            .paginated_list()
+                            .into_paginator()
+                            .send_with_prefetch(1)
+                            .try_collect()
+                            .await
+                            .unwrap();
+                        assert_eq!(
+                            vec![#{Some}("1"), #{Some}("2"), #{None}],
+                            pages.iter().map(|page| page.inner().unwrap().token()).collect::<Vec<_>>()
+                        );
+
+                        let items = client
+                            .paginated_list()
+                            .into_paginator()
+                            .items()
+                            .send_with_prefetch(2)
+                            .try_collect()
+                            .await
+                            .unwrap();
+                        assert_eq!(vec!["a", "b", "c"], items);
+                        """,
+                        *preludeScope,
+                        "SdkBody" to RuntimeType.sdkBody(codegenContext.runtimeConfig),
+                        "infallible_client_fn" to
+                            RuntimeType.smithyRuntimeTestUtil(codegenContext.runtimeConfig)
+                                .resolve("infallible_client_fn"),
+                    )
+                }
+            }
+        }
+    }
 }
@@ -10,6 +10,7 @@ import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
 import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
@@ -60,4 +61,50 @@ class CustomizableOperationGeneratorTest {
         }
         clientIntegrationTest(model, test = test)
     }
+
+    @Test
+    fun `conditional requests send the ETag and fail with PreconditionFailed`() {
+        val test: (ClientCodegenContext, RustCrate) -> Unit = { codegenContext, rustCrate ->
+            rustCrate.integrationTest("conditional_requests") {
+                val moduleName = codegenContext.moduleUseName()
+                rustTemplate(
+                    """
+                    ##[#{tokio}::test]
+                    async fn precondition_failed() {
+                        let response = #{http}::Response::builder()
+                            .status(412)
+                            .header("etag", "\"v2\"")
+                            .body(#{SdkBody}::from("{}"))
+                            .unwrap();
+                        let (http_client, request) = #{capture_request}(Some(response));
+                        let config = $moduleName::Config::builder()
+                            .http_client(http_client)
+                            .endpoint_url("http://localhost:1234")
+                            .build();
+                        let client = $moduleName::Client::from_conf(config);
+                        let err = client
+                            .say_hello()
+                            .customize()
+                            .if_match("\"v1\"")
+                            .send()
+                            .await
+                            .expect_err("precondition failed");
+
+                        assert_eq!(Some("\"v1\""), request.expect_request().headers().get("if-match"));
+                        match err {
+                            #{SdkError}::PreconditionFailed(context) => assert_eq!(Some("\"v2\""), context.etag()),
+                            err => panic!("expected a precondition failure, got {err:?}"),
+                        }
+                    }
+                    """,
+                    "capture_request" to RuntimeType.captureRequest(codegenContext.runtimeConfig),
+                    "http" to RuntimeType.Http,
+                    "SdkBody" to RuntimeType.sdkBody(codegenContext.runtimeConfig),
+                    "SdkError" to RuntimeType.sdkError(codegenContext.runtimeConfig),
+                    "tokio" to RuntimeType.Tokio,
+                )
+            }
+        }
+        clientIntegrationTest(model, test = test)
+    }
 }
@@ -12,8 +12,13 @@ import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
 import software.amazon.smithy.rust.codegen.client.testutil.testSymbolProvider
 import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
 import software.amazon.smithy.rust.codegen.core.util.lookup
 
 class FluentClientGeneratorTest {
@@ -123,6 +128,99 @@ class FluentClientGeneratorTest {
         }
     }
 
+    @Test
+    fun `config problems are reported together and fail sending`() {
+        clientIntegrationTest(model) { codegenContext, rustCrate ->
+            val rc = codegenContext.runtimeConfig
+            val failingValidator =
+                writable {
+                    rustTemplate(
+                        """
+                        #{StaticRuntimePlugin}::new().with_runtime_components(
+                            #{RuntimeComponentsBuilder}::new("test").with_config_validator(
+                                #{SharedConfigValidator}::base_client_config_fn(|_, _| {
+                                    #{Err}("the widget is misconfigured".into())
+                                }),
+                            ),
+                        )
+                        """,
+                        *preludeScope,
+                        "RuntimeComponentsBuilder" to RuntimeType.runtimeComponentsBuilder(rc),
+                        "SharedConfigValidator" to
+                            RuntimeType.smithyRuntimeApiClient(rc)
+                                .resolve("client::runtime_components::SharedConfigValidator"),
+                        "StaticRuntimePlugin" to
+                            RuntimeType.smithyRuntimeApiClient(rc)
+                                .resolve("client::runtime_plugin::StaticRuntimePlugin"),
+                    )
+                }
+            rustCrate.testModule {
+                addDependency(CargoDependency.Tokio.toDevDependency().withFeature("test-util"))
+                tokioTest("clean_config_has_an_empty_report") {
+                    rustTemplate(
+                        """
+                        let (http_client, _request) = #{capture_request}(#{None});
+                        let config = crate::Config::builder()
+                            .endpoint_url("http://localhost:1234")
+                            .http_client(http_client)
+                            .behavior_version_latest()
+                            .build();
+                        assert!(config.validation_report().is_empty(), "{}", config.validation_report());
+                        """,
+                        *preludeScope,
+                        "capture_request" to RuntimeType.captureRequest(rc),
+                    )
+                }
+                tokioTest("every_problem_is_reported") {
+                    rustTemplate(
+                        """
+                        let config = crate::Config::builder()
+                            .endpoint_url("http://localhost:1234")
+                            .runtime_plugin(#{failing_validator})
+                            .build();
+                        let report = config.validation_report();
+                        let codes: #{Vec}<_> = report.codes().collect();
+                        let mut expected = vec![crate::config::ConfigIssueCode::InvalidConfiguration];
+                        if !cfg!(feature = "behavior-version-latest") {
+                            expected.insert(0, crate::config::ConfigIssueCode::MissingBehaviorVersion);
+                            let issue = &report.issues()[0];
+                            assert_eq!(crate::config::Severity::Fatal, issue.severity());
+                            assert!(issue.message().contains("`behavior_version`"), "{}", issue.message());
+                        }
+                        assert_eq!(expected, codes);
+                        """,
+                        *preludeScope,
+                        "failing_validator" to failingValidator,
+                    )
+                }
+                tokioTest("fatal_problems_fail_sending") {
+                    rustTemplate(
+                        """
+                        let (http_client, request) = #{capture_request}(#{None});
+                        let config = crate::Config::builder()
+                            .endpoint_url("http://localhost:1234")
+                            .http_client(http_client)
+                            .behavior_version_latest()
+                            .runtime_plugin(#{failing_validator})
+                            .build();
+                        assert!(config.validation_report().has_fatal_issues());
+
+                        let client = crate::Client::from_conf(config);
+                        let err = client.say_hello().send().await.expect_err("the config is invalid");
+                        let err = ::std::format!("{}", #{DisplayErrorContext}(&err));
+                        assert!(err.contains("[InvalidConfiguration] the widget is misconfigured"), "{err}");
+                        request.expect_no_request();
+                        """,
+                        *preludeScope,
+                        "capture_request" to RuntimeType.captureRequest(rc),
+                        "DisplayErrorContext" to RuntimeType.smithyTypes(rc).resolve("error::display::DisplayErrorContext"),
+                        "failing_validator" to failingValidator,
+                    )
+                }
+            }
+        }
+    }
+
     @Test
     fun `dead-code warning should not be issued when a service has no operations`() {
         val model =
@@ -0,0 +1,96 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.generators.client
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
+import software.amazon.smithy.rust.codegen.core.rustlang.DependencyScope
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
+
+class MockClientMacroTest {
+    private val model =
+        """
+        namespace com.example
+
+        use aws.protocols#restJson1
+
+        @restJson1
+        service PokemonService {
+            operations: [GetPokemon],
+            version: "1"
+        }
+
+        @readonly
+        @http(uri: "/pokemon/{id}", method: "GET")
+        operation GetPokemon {
+            input: GetPokemonInput,
+            output: GetPokemonOutput
+        }
+
+        structure GetPokemonInput {
+            @required
+            @httpLabel
+            id: String
+        }
+
+        structure GetPokemonOutput {
+            name: String
+        }
+        """.asSmithyModel()
+
+    @Test
+    fun `mock_client supports generated clients without a region`() {
+        clientIntegrationTest(
+            model,
+            IntegrationTestParams(cargoCommand = "cargo test --features behavior-version-latest,test-util"),
+        ) { codegenContext, rustCrate ->
+            val moduleName = codegenContext.moduleUseName()
+            val mocks =
+                codegenContext.runtimeConfig.smithyRuntimeCrate(
+                    "smithy-mocks-experimental",
+                    scope = DependencyScope.Dev,
+                ).toType()
+            rustCrate.integrationTest("mock_client_without_region") {
+                Attribute.TokioTest.render(this)
+                rustTemplate(
+                    """
+                    async fn mock_client_without_region() {
+                        use $moduleName::operation::get_pokemon::GetPokemonOutput;
+                        use $moduleName::Client;
+
+                        let pikachu = #{mock}!(Client::get_pokemon)
+                            .match_requests(|input| input.id() == Some("25"))
+                            .then_output(|| GetPokemonOutput::builder().name("pikachu").build());
+                        let eevee = #{mock}!(Client::get_pokemon)
+                            .match_requests(|input| input.id() == Some("133"))
+                            .then_output(|| GetPokemonOutput::builder().name("eevee").build());
+                        let client = #{mock_client}!(
+                            $moduleName,
+                            #{RuleMode}::Sequential,
+                            &[&pikachu, &eevee],
+                            without_region
+                        );
+
+                        let output = client.get_pokemon().id("25").send().await.unwrap();
+                        assert_eq!(Some("pikachu"), output.name());
+                        let output = client.get_pokemon().id("133").send().await.unwrap();
+                        assert_eq!(Some("eevee"), output.name());
+                        assert_eq!(1, pikachu.num_calls());
+                        assert_eq!(1, eevee.num_calls());
+                    }
+                    """,
+                    "RuleMode" to mocks.resolve("RuleMode"),
+                    "mock" to mocks.resolve("mock"),
+                    "mock_client" to mocks.resolve("mock_client"),
+                )
+            }
+        }
+    }
+}
@@ -150,7 +150,16 @@ class RequestBindingGeneratorTest {
                     TestRuntimeConfig.operationBuildError(),
                 ) {
                     bindingGen.renderUpdateHttpBuilder(this)
-                    rust("uri_query(self, output)")
+                    rust("uri_query(self, &#T::base(), output)", RuntimeType.configBag(TestRuntimeConfig))
+                }
+
+                rustBlock(
+                    "pub fn test_uri_query_with_config(&self, cfg: &#T, mut output: &mut String) -> Result<(), #T>",
+                    RuntimeType.configBag(TestRuntimeConfig),
+                    TestRuntimeConfig.operationBuildError(),
+                ) {
+                    bindingGen.renderUpdateHttpBuilder(this)
+                    rust("uri_query(self, cfg, output)")
                 }
 
                 rustBlock(
@@ -168,7 +177,7 @@ class RequestBindingGeneratorTest {
                 ) {
                     bindingGen.renderUpdateHttpBuilder(this)
                     rust("let builder = #T::new();", RuntimeType.HttpRequestBuilder)
-                    rust("update_http_builder(self, builder)")
+                    rust("update_http_builder(self, &#T::base(), builder)", RuntimeType.configBag(TestRuntimeConfig))
                 }
             }
         }
@@ -205,6 +214,38 @@ class RequestBindingGeneratorTest {
                 """,
             )
 
+            unitTest(
+                name = "pre_encoded_query_params",
+                test = """
+                    use aws_smithy_http::query::PreEncodedQueryParam;
+                    use aws_smithy_types::config_bag::{ConfigBag, Layer};
+
+                    let inp = PutObjectInput::builder()
+                        .bucket_name("buk")
+                        .key(aws_smithy_types::DateTime::from_secs(10123125))
+                        .upload_id("a%2Fb c")
+                        .some_value("a%2Fb")
+                        .build().expect("build should succeed");
+                    let mut layer = Layer::new("test");
+                    layer.store_append(PreEncodedQueryParam::new("paramName"));
+                    let cfg = ConfigBag::of_layers(vec![layer]);
+                    let mut o = String::new();
+                    inp.test_uri_query_with_config(&cfg, &mut o).expect("valid pre-encoded value");
+                    assert_eq!(o.as_str(), "?paramName=a%2Fb&uploadId=a%252Fb%20c");
+
+                    let inp = PutObjectInput::builder()
+                        .bucket_name("buk")
+                        .key(aws_smithy_types::DateTime::from_secs(10123125))
+                        .upload_id("some-valid-id")
+                        .some_value("a/b")
+                        .build().expect("build should succeed");
+                    let err = inp.test_uri_query_with_config(&cfg, &mut String::new()).expect_err("invalid pre-encoded value");
+                    let message = err.to_string();
+                    let expected = "invalid field in input: some_value";
+                    assert!(message.contains(expected), "expected '{message}' to contain '{expected}'");
+                """,
+            )
+
             unitTest(
                 name = "serialize_non_zero_values",
                 test = """
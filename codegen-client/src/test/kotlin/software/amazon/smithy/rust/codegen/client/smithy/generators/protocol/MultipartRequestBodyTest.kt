@@ -0,0 +1,161 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.generators.protocol
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationSection
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+
+internal class MultipartRequestBodyTest {
+    private val model =
+        """
+        namespace com.example
+        use aws.protocols#restJson1
+
+        @restJson1
+        service HelloService {
+            operations: [Upload],
+            version: "1"
+        }
+
+        @optionalAuth
+        @http(method: "POST", uri: "/upload")
+        operation Upload { input: UploadInput }
+
+        structure UploadInput {
+            title: String,
+            data: Blob,
+        }
+        """.asSmithyModel()
+
+    /** Sends the `title` and `data` members of the input as parts. */
+    private class MultipartCustomization(private val runtimeConfig: RuntimeConfig) : OperationCustomization() {
+        override fun section(section: OperationSection): Writable =
+            writable {
+                if (section is OperationSection.MultipartRequestBody) {
+                    rustTemplate(
+                        """
+                        if let #{Some}(title) = ${section.inputName}.title {
+                            ${section.builderName} = ${section.builderName}.part(#{Part}::new("title", title.into_bytes()));
+                        }
+                        if let #{Some}(data) = ${section.inputName}.data {
+                            ${section.builderName} = ${section.builderName}.part(
+                                #{Part}::new("data", data.into_inner()).file_name("data.bin"),
+                            );
+                        }
+                        """,
+                        *preludeScope,
+                        "Part" to RuntimeType.smithyHttp(runtimeConfig).resolve("multipart::Part"),
+                    )
+                }
+            }
+    }
+
+    @Test
+    fun `input members can be sent as multipart parts`() {
+        val decorator =
+            object : ClientCodegenDecorator {
+                override val name: String = "multipart"
+                override val order: Byte = 0
+
+                override fun classpathDiscoverable(): Boolean = false
+
+                override fun operationCustomizations(
+                    codegenContext: ClientCodegenContext,
+                    operation: OperationShape,
+                    baseCustomizations: List<OperationCustomization>,
+                ): List<OperationCustomization> =
+                    baseCustomizations + MultipartCustomization(codegenContext.runtimeConfig)
+            }
+        clientIntegrationTest(model, additionalDecorators = listOf(decorator)) { codegenContext, rustCrate ->
+            rustCrate.testModule {
+                addDependency(CargoDependency.Tokio.toDevDependency().withFeature("test-util"))
+                tokioTest("input_members_are_sent_as_parts") {
+                    rustTemplate(
+                        """
+                        let (http_client, request) = #{capture_request}(#{None});
+                        let config = crate::Config::builder()
+                            .endpoint_url("http://localhost:1234")
+                            .http_client(http_client)
+                            .build();
+                        let client = crate::Client::from_conf(config);
+                        let _ = client
+                            .upload()
+                            .title("report")
+                            .data(crate::primitives::Blob::new("some data"))
+                            .send()
+                            .await;
+
+                        let request = request.expect_request();
+                        let content_type = request.headers().get("content-type").unwrap();
+                        let boundary = content_type
+                            .strip_prefix("multipart/form-data; boundary=")
+                            .expect("multipart content type");
+                        let body = ::std::str::from_utf8(request.body().bytes().unwrap()).unwrap();
+                        assert_eq!(
+                            format!(
+                                "--{boundary}\r\nContent-Disposition: form-data; name=\"title\"\r\n\r\nreport\r\n\
+                                 --{boundary}\r\nContent-Disposition: form-data; name=\"data\"; filename=\"data.bin\"\r\n\r\nsome data\r\n\
+                                 --{boundary}--\r\n"
+                            ),
+                            body
+                        );
+                        """,
+                        *preludeScope,
+                        "capture_request" to RuntimeType.captureRequest(codegenContext.runtimeConfig),
+                    )
+                }
+
+                tokioTest("boundaries_are_drawn_from_the_random_source") {
+                    rustTemplate(
+                        """
+                        async fn boundary(seed: u64) -> #{String} {
+                            let (http_client, request) = #{capture_request}(#{None});
+                            let config = crate::Config::builder()
+                                .endpoint_url("http://localhost:1234")
+                                .http_client(http_client)
+                                .random_source(#{SeededRandomSource}::new(seed))
+                                .build();
+                            let client = crate::Client::from_conf(config);
+                            let _ = client
+                                .upload()
+                                .title("report")
+                                .data(crate::primitives::Blob::new("some data"))
+                                .send()
+                                .await;
+                            let request = request.expect_request();
+                            let content_type = request.headers().get("content-type").unwrap();
+                            content_type.strip_prefix("multipart/form-data; boundary=").unwrap().to_owned()
+                        }
+
+                        assert_eq!(boundary(42).await, boundary(42).await);
+                        assert_ne!(boundary(42).await, boundary(43).await);
+                        """,
+                        *preludeScope,
+                        "capture_request" to RuntimeType.captureRequest(codegenContext.runtimeConfig),
+                        "SeededRandomSource" to
+                            RuntimeType.smithyRuntime(codegenContext.runtimeConfig)
+                                .resolve("client::random::SeededRandomSource"),
+                    )
+                }
+            }
+        }
+    }
+}
@@ -8,6 +8,7 @@ package software.amazon.smithy.rust.codegen.client.smithy.generators.protocol
 import io.kotest.matchers.string.shouldContain
 import org.junit.jupiter.api.Test
 import org.junit.jupiter.api.assertThrows
+import software.amazon.smithy.model.node.ObjectNode
 import software.amazon.smithy.model.shapes.OperationShape
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
@@ -20,6 +21,7 @@ import software.amazon.smithy.rust.codegen.core.rustlang.Writable
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.util.CommandError
 import software.amazon.smithy.rust.codegen.core.util.dq
@@ -209,6 +211,7 @@ class ProtocolTestGeneratorTest {
         fakeRequestBuilder: String,
         fakeRequestBody: String = "${correctBody.dq()}.to_string()",
         fakeOutput: String = """Ok(crate::operation::say_hello::SayHelloOutput::builder().value("hey there!").build())""",
+        strict: Boolean = false,
     ): Path {
         val codegenDecorator =
             object : ClientCodegenDecorator {
@@ -237,6 +240,15 @@ class ProtocolTestGeneratorTest {
             }
         return clientIntegrationTest(
             model,
+            params =
+                IntegrationTestParams(
+                    cargoCommand = "cargo test --features behavior-version-latest",
+                    additionalSettings =
+                        ObjectNode.builder().withMember(
+                            "codegen",
+                            ObjectNode.builder().withMember("strictProtocolTests", strict).build(),
+                        ).build(),
+                ),
             additionalDecorators = listOf(codegenDecorator),
         )
     }
@@ -375,4 +387,55 @@ class ProtocolTestGeneratorTest {
         err.message shouldContain "say_hello_request ... FAILED"
         err.message shouldContain "invalid header value"
     }
+
+    @Test
+    fun `passing e2e protocol request test in strict mode`() {
+        testService(
+            """
+            .uri("/?Hi=Hello%20there&required")
+            .header("X-Greeting", "Hi")
+            .method("POST")
+            """,
+            strict = true,
+        )
+    }
+
+    @Test
+    fun `unexpected header in strict mode`() {
+        val err =
+            assertThrows<CommandError> {
+                testService(
+                    """
+                    .uri("/?Hi=Hello%20there&required")
+                    .header("X-Greeting", "Hi")
+                    .header("X-Amz-Meta-", "")
+                    .method("POST")
+                    """,
+                    strict = true,
+                )
+            }
+
+        err.message shouldContain "say_hello_request ... FAILED"
+        err.message shouldContain "unexpected headers present"
+        err.message shouldContain "x-amz-meta-"
+    }
+
+    @Test
+    fun `unexpected url parameter in strict mode`() {
+        val err =
+            assertThrows<CommandError> {
+                testService(
+                    """
+                    .uri("/?Hi=Hello%20there&required&extra=1")
+                    .header("X-Greeting", "Hi")
+                    .method("POST")
+                    """,
+                    strict = true,
+                )
+            }
+
+        err.message shouldContain "say_hello_request ... FAILED"
+        err.message shouldContain "unexpected query params present"
+        err.message shouldContain "extra=1"
+    }
 }
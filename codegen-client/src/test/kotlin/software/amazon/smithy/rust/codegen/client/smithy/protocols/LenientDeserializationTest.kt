@@ -0,0 +1,184 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.protocols
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+
+class LenientDeserializationTest {
+    private val model =
+        """
+        namespace com.example
+
+        use aws.protocols#restJson1
+
+        @restJson1
+        service HelloService {
+            operations: [ListItems],
+            version: "1"
+        }
+
+        @http(uri: "/", method: "GET")
+        @readonly
+        operation ListItems {
+            output: ListItemsOutput
+        }
+
+        structure ListItemsOutput {
+            @required
+            updatedAt: Timestamp,
+            items: ItemList
+        }
+
+        list ItemList {
+            member: Item
+        }
+
+        structure Item {
+            name: String,
+            createdAt: Timestamp
+        }
+        """.asSmithyModel()
+
+    @Test
+    fun `optional members that fail to parse are recorded when deserialization is lenient`() {
+        clientIntegrationTest(model) { context, rustCrate ->
+            val rc = context.runtimeConfig
+            rustCrate.testModule {
+                rustTemplate(
+                    """
+                    use std::sync::{Arc, Mutex};
+
+                    ##[derive(Clone, Debug, Default)]
+                    struct RecordFieldErrors {
+                        paths: Arc<Mutex<#{Option}<#{Vec}<#{String}>>>>,
+                    }
+
+                    impl #{Intercept} for RecordFieldErrors {
+                        fn name(&self) -> &'static str {
+                            "RecordFieldErrors"
+                        }
+
+                        fn read_after_deserialization(
+                            &self,
+                            _context: &#{AfterDeserializationInterceptorContextRef}<'_>,
+                            _runtime_components: &#{RuntimeComponents},
+                            cfg: &mut #{ConfigBag},
+                        ) -> #{Result}<(), #{BoxError}> {
+                            *self.paths.lock().unwrap() = cfg
+                                .load::<#{FieldErrors}>()
+                                .map(|errors| errors.errors().iter().map(|e| e.path().to_string()).collect());
+                            #{Ok}(())
+                        }
+                    }
+
+                    fn items(bad_item: usize) -> #{String} {
+                        let items: #{Vec}<_> = (0..100)
+                            .map(|i| {
+                                let created_at = if i == bad_item { "\"yesterday\"".to_string() } else { i.to_string() };
+                                format!(r##"{{"name": "item-{i}", "createdAt": {created_at}}}"##)
+                            })
+                            .collect();
+                        items.join(",")
+                    }
+
+                    fn client(body: #{String}, lenient: bool, record: RecordFieldErrors) -> crate::Client {
+                        let response = move |_: http::Request<#{SdkBody}>| {
+                            http::Response::builder()
+                                .status(200)
+                                .body(#{SdkBody}::from(body.clone()))
+                                .unwrap()
+                        };
+                        let mut config = crate::Config::builder()
+                            .http_client(#{infallible_client_fn}(response))
+                            .endpoint_url("http://localhost:1234")
+                            .interceptor(record);
+                        if lenient {
+                            config = config.runtime_plugin(#{LenientDeserialization}::new());
+                        }
+                        crate::Client::from_conf(config.build())
+                    }
+                    """,
+                    *RuntimeType.preludeScope,
+                    "AfterDeserializationInterceptorContextRef" to RuntimeType.afterDeserializationInterceptorContextRef(rc),
+                    "BoxError" to RuntimeType.boxError(rc),
+                    "ConfigBag" to RuntimeType.configBag(rc),
+                    "FieldErrors" to RuntimeType.smithyTypes(rc).resolve("field_errors::FieldErrors"),
+                    "Intercept" to RuntimeType.intercept(rc),
+                    "LenientDeserialization" to
+                        RuntimeType.smithyRuntime(rc).resolve("client::field_errors::LenientDeserialization"),
+                    "RuntimeComponents" to RuntimeType.runtimeComponents(rc),
+                    "SdkBody" to RuntimeType.sdkBody(rc),
+                    "infallible_client_fn" to
+                        CargoDependency.smithyRuntimeTestUtil(rc)
+                            .toType().resolve("client::http::test_util::infallible_client_fn"),
+                )
+
+                tokioTest("bad_optional_members_are_dropped_and_recorded") {
+                    rustTemplate(
+                        """
+                        let record = RecordFieldErrors::default();
+                        let body = format!(r##"{{"updatedAt": 1, "items": [{}]}}"##, items(42));
+                        let output = client(body, true, record.clone()).list_items().send().await.unwrap();
+
+                        let items = output.items();
+                        assert_eq!(100, items.len());
+                        assert_eq!(#{Some}("item-42"), items[42].name());
+                        assert_eq!(#{None}, items[42].created_at());
+                        assert_eq!(99, items.iter().filter(|item| item.created_at().is_some()).count());
+                        assert_eq!(
+                            #{Some}(vec!["/items/42/createdAt".to_string()]),
+                            *record.paths.lock().unwrap()
+                        );
+                        """,
+                        *RuntimeType.preludeScope,
+                    )
+                }
+
+                tokioTest("bad_required_members_still_fail") {
+                    rustTemplate(
+                        """
+                        let record = RecordFieldErrors::default();
+                        let body = format!(r##"{{"updatedAt": "yesterday", "items": [{}]}}"##, items(42));
+                        client(body, true, record.clone())
+                            .list_items()
+                            .send()
+                            .await
+                            .expect_err("required members are never dropped");
+                        """,
+                        *RuntimeType.preludeScope,
+                    )
+                }
+
+                tokioTest("deserialization_is_strict_by_default") {
+                    rustTemplate(
+                        """
+                        let record = RecordFieldErrors::default();
+                        let body = format!(r##"{{"updatedAt": 1, "items": [{}]}}"##, items(42));
+                        client(body, false, record.clone())
+                            .list_items()
+                            .send()
+                            .await
+                            .expect_err("a bad value fails the whole response");
+                        assert_eq!(#{None}, *record.paths.lock().unwrap());
+
+                        let body = format!(r##"{{"updatedAt": 1, "items": [{}]}}"##, items(usize::MAX));
+                        let output = client(body, false, record.clone()).list_items().send().await.unwrap();
+                        assert_eq!(100, output.items().len());
+                        """,
+                        *RuntimeType.preludeScope,
+                    )
+                }
+            }
+        }
+    }
+}
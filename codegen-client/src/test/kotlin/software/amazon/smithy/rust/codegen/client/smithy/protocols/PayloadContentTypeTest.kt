@@ -0,0 +1,160 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.protocols
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+
+internal class PayloadContentTypeTest {
+    private val model =
+        """
+        namespace com.example
+
+        use aws.protocols#restJson1
+
+        @restJson1
+        service PayloadService {
+            operations: [EchoText, EchoBytes],
+            version: "1"
+        }
+
+        @http(uri: "/text", method: "POST")
+        operation EchoText {
+            input: EchoTextInput,
+            output: EchoTextOutput
+        }
+
+        structure EchoTextInput {
+            @httpPayload
+            text: Utf8Text
+        }
+
+        structure EchoTextOutput {
+            @httpPayload
+            text: Utf8Text
+        }
+
+        @mediaType("text/plain; charset=utf-8")
+        string Utf8Text
+
+        @http(uri: "/bytes", method: "POST")
+        operation EchoBytes {
+            input: EchoBytesInput,
+            output: EchoBytesOutput
+        }
+
+        structure EchoBytesInput {
+            @httpPayload
+            bytes: VersionedBytes
+        }
+
+        structure EchoBytesOutput {
+            @httpPayload
+            bytes: VersionedBytes
+        }
+
+        @mediaType("application/octet-stream; version=2")
+        blob VersionedBytes
+        """.asSmithyModel()
+
+    @Test
+    fun `payload content types round trip with their parameters`() {
+        clientIntegrationTest(model) { context, rustCrate ->
+            rustCrate.testModule {
+                rustTemplate(
+                    """
+                    use crate::operation::ProvideCapturedHeaders;
+                    use std::sync::{Arc, Mutex};
+
+                    /// Returns a client echoing requests back, and the content type of the last request it received.
+                    fn echo_client() -> (crate::Client, Arc<Mutex<#{Option}<String>>>) {
+                        let received = Arc::new(Mutex::new(#{None}));
+                        let client_received = received.clone();
+                        let echo = move |request: http::Request<#{SdkBody}>| {
+                            let content_type = request.headers()["content-type"].to_str().unwrap().to_string();
+                            *client_received.lock().unwrap() = #{Some}(content_type.clone());
+                            http::Response::builder()
+                                .status(200)
+                                .header("content-type", content_type)
+                                .body(#{SdkBody}::from(request.body().bytes().unwrap()))
+                                .unwrap()
+                        };
+                        let config = crate::Config::builder()
+                            .http_client(#{infallible_client_fn}(echo))
+                            .endpoint_url("http://localhost:1234")
+                            .build();
+                        (crate::Client::from_conf(config), received)
+                    }
+                    """,
+                    *RuntimeType.preludeScope,
+                    "SdkBody" to RuntimeType.sdkBody(context.runtimeConfig),
+                    "infallible_client_fn" to
+                        CargoDependency.smithyRuntimeTestUtil(context.runtimeConfig)
+                            .toType().resolve("client::http::test_util::infallible_client_fn"),
+                )
+
+                tokioTest("string_payloads_keep_their_content_type_parameters") {
+                    rustTemplate(
+                        """
+                        let (client, received) = echo_client();
+                        let output = client.echo_text().text("pikachu").send().await.unwrap();
+                        assert_eq!(#{Some}("text/plain; charset=utf-8"), received.lock().unwrap().as_deref());
+                        assert_eq!(#{Some}("pikachu"), output.text());
+                        let captured = output.captured_headers().expect("the content type is captured");
+                        assert_eq!(#{Some}("text/plain; charset=utf-8"), captured.get("content-type"));
+
+                        let output = client
+                            .echo_text()
+                            .text("pikachu")
+                            .customize()
+                            .mutate_request(|request| {
+                                request.headers_mut().insert("content-type", "text/plain; charset=us-ascii");
+                            })
+                            .send()
+                            .await
+                            .unwrap();
+                        assert_eq!(#{Some}("text/plain; charset=us-ascii"), received.lock().unwrap().as_deref());
+                        assert_eq!(
+                            #{Some}("text/plain; charset=us-ascii"),
+                            output.captured_headers().unwrap().get("content-type")
+                        );
+                        """,
+                        *RuntimeType.preludeScope,
+                    )
+                }
+
+                tokioTest("blob_payloads_keep_their_content_type_parameters") {
+                    rustTemplate(
+                        """
+                        let (client, received) = echo_client();
+                        let output = client
+                            .echo_bytes()
+                            .bytes(#{Blob}::new("pikachu"))
+                            .send()
+                            .await
+                            .unwrap();
+                        assert_eq!(
+                            #{Some}("application/octet-stream; version=2"),
+                            received.lock().unwrap().as_deref()
+                        );
+                        assert_eq!(#{Some}(&#{Blob}::new("pikachu")), output.bytes());
+                        let captured = output.captured_headers().expect("the content type is captured");
+                        assert_eq!(#{Some}("application/octet-stream; version=2"), captured.get("content-type"));
+                        """,
+                        *RuntimeType.preludeScope,
+                        "Blob" to RuntimeType.blob(context.runtimeConfig),
+                    )
+                }
+            }
+        }
+    }
+}
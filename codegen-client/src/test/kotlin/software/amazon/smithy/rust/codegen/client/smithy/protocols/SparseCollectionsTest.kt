@@ -0,0 +1,118 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.protocols
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+
+internal class SparseCollectionsTest {
+    private val model =
+        """
+        namespace com.example
+
+        use aws.protocols#restJson1
+
+        @restJson1
+        service SparseService {
+            operations: [PutEntries],
+            version: "1"
+        }
+
+        @http(uri: "/entries", method: "PUT")
+        operation PutEntries {
+            input: Entries,
+            output: Entries
+        }
+
+        structure Entries {
+            sparseMap: SparseMap,
+            sparseList: SparseList,
+            denseMap: DenseMap
+        }
+
+        @sparse
+        map SparseMap {
+            key: String,
+            value: String
+        }
+
+        @sparse
+        list SparseList {
+            member: Integer
+        }
+
+        map DenseMap {
+            key: String,
+            value: String
+        }
+        """.asSmithyModel()
+
+    @Test
+    fun `null entries of sparse collections round trip`() {
+        clientIntegrationTest(model) { context, rustCrate ->
+            rustCrate.testModule {
+                tokioTest("null_entries_of_sparse_collections_round_trip") {
+                    rustTemplate(
+                        """
+                        let response = http::Response::builder()
+                            .status(200)
+                            .body(#{SdkBody}::from(
+                                r##"{"sparseMap": {"clear": null, "keep": "value"}, "sparseList": [1, null, 2], "denseMap": {"clear": null, "keep": "value"}}"##,
+                            ))
+                            .unwrap();
+                        let (http_client, rx) = #{capture_request}(#{Some}(response));
+                        let config = crate::Config::builder()
+                            .http_client(http_client)
+                            .endpoint_url("http://localhost:1234")
+                            .build();
+                        let client = crate::Client::from_conf(config);
+
+                        let output = client
+                            .put_entries()
+                            .sparse_map("clear", #{None})
+                            .sparse_map("keep", #{Some}("value".to_string()))
+                            .sparse_list(#{Some}(1))
+                            .sparse_list(#{None})
+                            .sparse_list(#{Some}(2))
+                            .dense_map("keep", "value")
+                            .send()
+                            .await
+                            .unwrap();
+
+                        let request = rx.expect_request();
+                        #{assert_ok}(#{validate_body}(
+                            request.body().bytes().unwrap(),
+                            r##"{"sparseMap": {"clear": null, "keep": "value"}, "sparseList": [1, null, 2], "denseMap": {"keep": "value"}}"##,
+                            #{MediaType}::Json,
+                        ));
+
+                        let sparse_map = output.sparse_map().unwrap();
+                        assert_eq!(2, sparse_map.len());
+                        assert_eq!(#{Some}(&#{None}), sparse_map.get("clear"));
+                        assert_eq!(#{Some}(&#{Some}("value".to_string())), sparse_map.get("keep"));
+                        assert_eq!(&[#{Some}(1), #{None}, #{Some}(2)], output.sparse_list());
+                        // Dense maps can't hold nulls, so they're still skipped
+                        let dense_map = output.dense_map().unwrap();
+                        assert_eq!(1, dense_map.len());
+                        assert_eq!(#{Some}(&"value".to_string()), dense_map.get("keep"));
+                        """,
+                        *RuntimeType.preludeScope,
+                        "SdkBody" to RuntimeType.sdkBody(context.runtimeConfig),
+                        "capture_request" to RuntimeType.captureRequest(context.runtimeConfig),
+                        "assert_ok" to RuntimeType.protocolTest(context.runtimeConfig, "assert_ok"),
+                        "validate_body" to RuntimeType.protocolTest(context.runtimeConfig, "validate_body"),
+                        "MediaType" to RuntimeType.protocolTest(context.runtimeConfig, "MediaType"),
+                    )
+                }
+            }
+        }
+    }
+}
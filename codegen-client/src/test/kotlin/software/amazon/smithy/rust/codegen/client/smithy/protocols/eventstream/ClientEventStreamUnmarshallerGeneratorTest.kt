@@ -36,7 +36,9 @@ class ClientEventStreamUnmarshallerGeneratorTest {
                     let message = msg("event", "NewUnmodeledMessageType", "application/octet-stream", b"hello, world!");
                     let result = $generator::new().unmarshall(&message);
                     assert!(result.is_ok(), "expected ok, got: {:?}", result);
-                    assert!(expect_event(result.unwrap()).is_unknown());
+                    let event = expect_event(result.unwrap());
+                    assert!(event.is_unknown());
+                    assert_eq!(Some("NewUnmodeledMessageType"), event.unknown_variant_name());
                     """,
                 )
 
@@ -142,7 +142,8 @@ private fun generateDateTimeTests(parsing: Boolean): List<TestCase> {
         DateTimeFormatterBuilder()
             .appendPattern("yyyy-MM-dd'T'HH:mm:ss")
             .optionalStart()
-            .appendFraction(ChronoField.MICRO_OF_SECOND, 0, 6, true)
+            // Sub-second nanos are formatted with as many digits as needed to represent them exactly
+            .appendFraction(ChronoField.NANO_OF_SECOND, 0, 9, true)
             .optionalEnd()
             .appendLiteral("Z")
             .toFormatter(Locale.ENGLISH)
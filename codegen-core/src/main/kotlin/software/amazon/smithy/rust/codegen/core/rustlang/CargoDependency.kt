@@ -104,9 +104,11 @@ class InlineDependency(
         fun eventReceiver(runtimeConfig: RuntimeConfig) =
             forInlineableRustFile(
                 "event_receiver",
+                CargoDependency.smithyAsync(runtimeConfig),
                 CargoDependency.smithyHttp(runtimeConfig),
                 CargoDependency.smithyRuntimeApi(runtimeConfig),
                 CargoDependency.smithyTypes(runtimeConfig),
+                CargoDependency.Tracing,
             )
 
         fun defaultAuthPlugin(runtimeConfig: RuntimeConfig) =
@@ -144,6 +146,7 @@ class InlineDependency(
             forInlineableRustFile(
                 "idempotency_token",
                 CargoDependency.FastRand,
+                CargoDependency.smithyRuntimeApiClient(runtimeConfig),
                 CargoDependency.smithyTypes(runtimeConfig),
             )
 
@@ -174,6 +177,9 @@ class InlineDependency(
 
         fun constrained(): InlineDependency = forRustFile(ConstrainedModule, "/inlineable/src/constrained.rs")
 
+        fun constrainedPattern(): InlineDependency =
+            forInlineableRustFile("constrained_pattern", CargoDependency.RegexAutomata)
+
         fun sdkFeatureTracker(runtimeConfig: RuntimeConfig): InlineDependency =
             forInlineableRustFile(
                 "sdk_feature_tracker",
@@ -287,6 +293,7 @@ data class CargoDependency(
         val OnceCell: CargoDependency = CargoDependency("once_cell", CratesIo("1.16"))
         val PercentEncoding: CargoDependency = CargoDependency("percent-encoding", CratesIo("2.0.0"))
         val Regex: CargoDependency = CargoDependency("regex", CratesIo("1.5.5"))
+        val RegexAutomata: CargoDependency = CargoDependency("regex-automata", CratesIo("0.4"))
         val RegexLite: CargoDependency = CargoDependency("regex-lite", CratesIo("0.1.5"))
         val Ring: CargoDependency = CargoDependency("ring", CratesIo("0.17.5"))
         val Sha2: CargoDependency = CargoDependency("sha2", CratesIo("0.10"))
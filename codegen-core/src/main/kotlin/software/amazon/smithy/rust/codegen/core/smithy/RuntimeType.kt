@@ -302,6 +302,9 @@ data class RuntimeType(val path: String, val dependency: RustDependency? = null)
         // codegen types
         val ConstrainedTrait = RuntimeType("crate::constrained::Constrained", InlineDependency.constrained())
         val MaybeConstrained = RuntimeType("crate::constrained::MaybeConstrained", InlineDependency.constrained())
+        val ConstrainedValueSummary = RuntimeType("crate::constrained::value_summary", InlineDependency.constrained())
+        val ConstrainedPatternMatcher =
+            RuntimeType("crate::constrained_pattern::PatternMatcher", InlineDependency.constrainedPattern())
 
         // smithy runtime types
         fun smithyAsync(runtimeConfig: RuntimeConfig) = CargoDependency.smithyAsync(runtimeConfig).toType()
@@ -423,9 +426,6 @@ data class RuntimeType(val path: String, val dependency: RustDependency? = null)
         fun eventStreamSender(runtimeConfig: RuntimeConfig): RuntimeType =
             smithyHttp(runtimeConfig).resolve("event_stream::EventStreamSender")
 
-        fun futuresStreamCompatByteStream(runtimeConfig: RuntimeConfig): RuntimeType =
-            smithyHttp(runtimeConfig).resolve("futures_stream_adapter::FuturesStreamCompatByteStream")
-
         fun errorMetadata(runtimeConfig: RuntimeConfig) = smithyTypes(runtimeConfig).resolve("error::ErrorMetadata")
 
         fun errorMetadataBuilder(runtimeConfig: RuntimeConfig) =
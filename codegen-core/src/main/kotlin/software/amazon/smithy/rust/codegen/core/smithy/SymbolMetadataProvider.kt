@@ -89,7 +89,11 @@ fun containerDefaultMetadata(
             // shape; any sensitive descendant should still be printed as redacted.
             shape.members().any { it.getMemberTrait(model, SensitiveTrait::class.java).isPresent }
 
-    if (isSensitive) {
+    // A derived `Debug` prints maps in their iteration order, which changes from one run to the next. Structures with
+    // map members get a hand-written `Debug` impl that sorts them instead.
+    val hasMapMembers = shape is StructureShape && shape.members().any { model.expectShape(it.target) is MapShape }
+
+    if (isSensitive || hasMapMembers) {
         derives.remove(RuntimeType.Debug)
     }
 
@@ -0,0 +1,110 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.core.smithy.customizations
+
+import software.amazon.smithy.model.Model
+import software.amazon.smithy.model.node.Node
+import software.amazon.smithy.model.shapes.ModelSerializer
+import software.amazon.smithy.rust.codegen.core.Version
+import software.amazon.smithy.rust.codegen.core.rustlang.RustModule
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.CodegenContext
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
+import software.amazon.smithy.rust.codegen.core.util.dq
+import java.security.MessageDigest
+
+/**
+ * Adds compile-time constants describing the Smithy service a crate was generated from, so that build tooling can
+ * tell which service, version, and protocol a generated crate corresponds to without parsing the model.
+ */
+object ServiceMetadataCustomization {
+    fun serviceMetadata(module: RustModule): RuntimeType = RuntimeType(module.fullyQualifiedPath() + "::ServiceMetadata")
+
+    /** Hex-encoded SHA-256 hash of the JSON AST of [model]. */
+    fun modelSha256(model: Model): String {
+        val json = Node.printJson(ModelSerializer.builder().build().serialize(model))
+        val bytes = MessageDigest.getInstance("SHA-256").digest(json.toByteArray())
+        return bytes.joinToString("") { byte -> String.format("%02x", byte) }
+    }
+
+    fun extras(
+        codegenContext: CodegenContext,
+        rustCrate: RustCrate,
+        module: RustModule,
+    ) = rustCrate.withModule(module) {
+        val service = codegenContext.serviceShape
+        rustTemplate(
+            """
+            /// The name of the Smithy service this crate was generated from.
+            pub const SERVICE_NAME: &str = ${service.id.name.literal()};
+
+            /// The version of the Smithy service this crate was generated from, as set in the model.
+            pub const SERVICE_VERSION: &str = ${(service.version ?: "").literal()};
+
+            /// The shape ID of the protocol this crate was generated for.
+            pub const PROTOCOL: &str = ${codegenContext.protocol.toString().literal()};
+
+            /// The smithy-rs revision that generated this crate.
+            pub const SMITHY_RS_VERSION: &str = ${Version.fromDefaultResource().gitHash.literal()};
+
+            /// Hex-encoded SHA-256 hash of the model this crate was generated from.
+            ///
+            /// Comparing the hashes of two generated crates tells whether they were generated from the same model.
+            pub const MODEL_SHA256: &str = ${modelSha256(codegenContext.model).literal()};
+
+            /// The metadata of the Smithy service this crate was generated from.
+            ///
+            /// Each field holds the value of the constant of the same name in this module.
+            ##[non_exhaustive]
+            ##[derive(#{Clone}, #{Copy}, #{Debug}, #{PartialEq}, #{Eq})]
+            pub struct ServiceMetadata {
+                /// See [`SERVICE_NAME`].
+                pub service_name: &'static str,
+                /// See [`SERVICE_VERSION`].
+                pub service_version: &'static str,
+                /// See [`PROTOCOL`].
+                pub protocol: &'static str,
+                /// See [`SMITHY_RS_VERSION`].
+                pub smithy_rs_version: &'static str,
+                /// See [`MODEL_SHA256`].
+                pub model_sha256: &'static str,
+            }
+
+            /// The metadata of the Smithy service this crate was generated from.
+            pub const SERVICE_METADATA: ServiceMetadata = ServiceMetadata {
+                service_name: SERVICE_NAME,
+                service_version: SERVICE_VERSION,
+                protocol: PROTOCOL,
+                smithy_rs_version: SMITHY_RS_VERSION,
+                model_sha256: MODEL_SHA256,
+            };
+            """,
+            *RuntimeType.preludeScope,
+            "Debug" to RuntimeType.Debug,
+        )
+    }
+
+    /** Renders a `service_metadata()` associated function returning the constants rendered in [extras]. */
+    fun serviceMetadataFn(module: RustModule): Writable =
+        writable {
+            rustTemplate(
+                """
+                /// Returns the metadata of the Smithy service this crate was generated from.
+                pub fn service_metadata() -> #{ServiceMetadata} {
+                    #{SERVICE_METADATA}
+                }
+                """,
+                "ServiceMetadata" to serviceMetadata(module),
+                "SERVICE_METADATA" to RuntimeType(module.fullyQualifiedPath() + "::SERVICE_METADATA"),
+            )
+        }
+
+    /** A Rust string literal of this string, escaped for use in a `rustTemplate`. */
+    private fun String.literal(): String = this.dq().replace("#", "##")
+}
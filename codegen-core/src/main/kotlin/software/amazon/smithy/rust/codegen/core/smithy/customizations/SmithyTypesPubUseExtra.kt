@@ -9,12 +9,15 @@ import software.amazon.smithy.model.Model
 import software.amazon.smithy.model.shapes.Shape
 import software.amazon.smithy.model.shapes.StructureShape
 import software.amazon.smithy.rust.codegen.core.rustlang.Feature
+import software.amazon.smithy.rust.codegen.core.rustlang.InlineDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.Writable
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.CodegenContext
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.eventReceiver
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.forInlineDependency
 import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
 import software.amazon.smithy.rust.codegen.core.util.hasEventStreamMember
 import software.amazon.smithy.rust.codegen.core.util.hasEventStreamOperations
@@ -98,6 +101,8 @@ fun pubUseSmithyPrimitives(
         }
     }
 
+private fun eventReceiverModule(rc: RuntimeConfig) = forInlineDependency(InlineDependency.eventReceiver(rc))
+
 /** Adds re-export statements for event-stream-related Smithy primitives */
 fun pubUseSmithyPrimitivesEventStream(
     codegenContext: CodegenContext,
@@ -112,9 +117,15 @@ fun pubUseSmithyPrimitivesEventStream(
                 pub use #{Header};
                 pub use #{HeaderValue};
                 pub use #{Message};
+                pub use #{ResumableReceiver};
+                pub use #{ResumeError};
+                pub use #{ResumePolicy};
                 pub use #{StrBytes};
                 """,
                 "EventReceiver" to eventReceiver(rc),
+                "ResumableReceiver" to eventReceiverModule(rc).resolve("ResumableReceiver"),
+                "ResumeError" to eventReceiverModule(rc).resolve("ResumeError"),
+                "ResumePolicy" to eventReceiverModule(rc).resolve("ResumePolicy"),
                 "Header" to RuntimeType.smithyTypes(rc).resolve("event_stream::Header"),
                 "HeaderValue" to RuntimeType.smithyTypes(rc).resolve("event_stream::HeaderValue"),
                 "Message" to RuntimeType.smithyTypes(rc).resolve("event_stream::Message"),
@@ -23,6 +23,7 @@ import software.amazon.smithy.rust.codegen.core.rustlang.deprecatedShape
 import software.amazon.smithy.rust.codegen.core.rustlang.docs
 import software.amazon.smithy.rust.codegen.core.rustlang.docsTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.documentShape
+import software.amazon.smithy.rust.codegen.core.rustlang.implInto
 import software.amazon.smithy.rust.codegen.core.rustlang.map
 import software.amazon.smithy.rust.codegen.core.rustlang.render
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
@@ -46,11 +47,9 @@ import software.amazon.smithy.rust.codegen.core.smithy.isOptional
 import software.amazon.smithy.rust.codegen.core.smithy.makeOptional
 import software.amazon.smithy.rust.codegen.core.smithy.rustType
 import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticInputTrait
-import software.amazon.smithy.rust.codegen.core.util.REDACTION
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.hasTrait
 import software.amazon.smithy.rust.codegen.core.util.letIf
-import software.amazon.smithy.rust.codegen.core.util.redactIfNecessary
 import software.amazon.smithy.rust.codegen.core.util.shouldRedact
 import software.amazon.smithy.rust.codegen.core.util.toSnakeCase
 
@@ -140,6 +139,31 @@ fun MemberShape.setterName() = "set_${this.memberName.toSnakeCase()}"
 // Getter names will never hit a reserved word and therefore never need escaping.
 fun MemberShape.getterName() = "get_${this.memberName.toSnakeCase()}"
 
+/** Name of the setter that takes the raw string value of an enum member, e.g. `set_foo_from_str` for `foo`. */
+fun MemberShape.fromStrSetterName() = "${this.setterName()}_from_str"
+
+/** Name of the setter that collects a list or map member from an iterator, e.g. `set_foo_from_iter` for `foo`. */
+fun MemberShape.fromIterSetterName() = "${this.setterName()}_from_iter"
+
+/**
+ * Returns the argument type and the collecting expression of a `set_foo_from_iter` setter for a collection of type
+ * [collection], reading from `input`, or `null` if [collection] isn't a list or a map.
+ *
+ * Items are converted with `Into`, so that e.g. a `BTreeMap<&str, &str>` can be passed for a `HashMap<String, String>`.
+ */
+fun fromIterSetterArgument(collection: RustType): Pair<String, String>? {
+    val intoIterator = RuntimeType.std.resolve("iter::IntoIterator").fullyQualifiedName()
+    return when (collection) {
+        is RustType.Vec ->
+            "impl $intoIterator<Item = ${collection.member.implInto()}>" to
+                "input.into_iter().map(|v| v.into()).collect()"
+        is RustType.HashMap ->
+            "impl $intoIterator<Item = (${collection.key.implInto()}, ${collection.member.implInto()})>" to
+                "input.into_iter().map(|(k, v)| (k.into(), v.into())).collect()"
+        else -> null
+    }
+}
+
 class BuilderGenerator(
     private val model: Model,
     private val symbolProvider: RustSymbolProvider,
@@ -292,6 +316,43 @@ class BuilderGenerator(
         }
     }
 
+    /**
+     * Render a `set_foo_from_str` method for an enum member. Any string is accepted, and values that aren't known
+     * variants are preserved as unknown variants.
+     */
+    private fun renderBuilderMemberFromStrSetterFn(
+        writer: RustWriter,
+        member: MemberShape,
+        memberName: String,
+    ) {
+        writer.docs("Sets [`$memberName`](Self::$memberName) from its raw string value, without checking that it's a known variant.")
+        writer.deprecatedShape(member)
+        writer.rustBlockTemplate(
+            "pub fn ${member.fromStrSetterName()}(mut self, input: #{Option}<&str>) -> Self",
+            *preludeScope,
+        ) {
+            rustTemplate("self.$memberName = input.map(#{From}::from); self", *preludeScope)
+        }
+    }
+
+    /**
+     * Render a `set_foo_from_iter` method for a list or map member. It accepts any iterator of items that convert into
+     * the collection's items, and leaves the exact-type `set_foo` method as is.
+     */
+    private fun renderBuilderMemberFromIterSetterFn(
+        writer: RustWriter,
+        coreType: RustType,
+        member: MemberShape,
+        memberName: String,
+    ) {
+        val (argument, collected) = fromIterSetterArgument(coreType) ?: return
+        writer.docs("Sets [`$memberName`](Self::$memberName) from an iterator, converting each item into the collection's item type.")
+        writer.deprecatedShape(member)
+        writer.rustBlock("pub fn ${member.fromIterSetterName()}(mut self, input: $argument) -> Self") {
+            rustTemplate("self.$memberName = #{Some}($collected); self", *preludeScope)
+        }
+    }
+
     /**
      * Render a `get_foo` method. This is useful as a target for code generation, because the argument type
      * is the same as the resulting member type, and is always optional.
@@ -344,6 +405,12 @@ class BuilderGenerator(
                 }
 
                 renderBuilderMemberSetterFn(this, outerType, member, memberName)
+                if (member.targetsEnum(model) && members.none { it.setterName() == member.fromStrSetterName() }) {
+                    renderBuilderMemberFromStrSetterFn(this, member, memberName)
+                }
+                if (members.none { it.setterName() == member.fromIterSetterName() }) {
+                    renderBuilderMemberFromIterSetterFn(this, coreType, member, memberName)
+                }
                 renderBuilderMemberGetterFn(this, outerType, member, memberName)
             }
             writeCustomizations(customizations, BuilderSection.AdditionalMethods(shape))
@@ -358,18 +425,11 @@ class BuilderGenerator(
                 members.forEach { member ->
                     val memberName = symbolProvider.toMemberName(member)
                     // If the struct is marked sensitive all fields get redacted, otherwise each field is determined on its own
-                    val fieldValue =
-                        if (shape.shouldRedact(model)) {
-                            REDACTION
-                        } else {
-                            member.redactIfNecessary(
-                                model,
-                                "self.$memberName",
-                            )
-                        }
-
-                    rust(
-                        "formatter.field(${memberName.dq()}, &$fieldValue);",
+                    renderDebugField(
+                        runtimeConfig,
+                        memberName,
+                        symbolProvider.toSymbol(member).makeOptional().rustType(),
+                        redact = shape.shouldRedact(model) || member.shouldRedact(model),
                     )
                 }
                 writeCustomizations(customizations, BuilderSection.AdditionalDebugFields(shape, "formatter"))
@@ -230,6 +230,7 @@ open class EnumGenerator(
             """,
             *preludeScope,
         )
+        renderDisplayImpl()
     }
 
     private fun RustWriter.renderUnnamedEnum() {
@@ -247,6 +248,24 @@ open class EnumGenerator(
         enumType.implFromForStrForUnnamedEnum(context)(this)
         // impl FromStr for Blah { ... }
         enumType.implFromStrForUnnamedEnum(context)(this)
+        // Unnamed enums don't implement `AsRef<str>`, since it would overlap with their
+        // `impl<T: AsRef<str>> From<T>`.
+        renderDisplayImpl()
+    }
+
+    /** Renders a `Display` implementation writing the same value as `as_str()`, including for unknown values. */
+    private fun RustWriter.renderDisplayImpl() {
+        rustTemplate(
+            """
+            impl #{Display} for ${context.enumName} {
+                fn fmt(&self, f: &mut #{Fmt}::Formatter<'_>) -> #{Fmt}::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+            """,
+            "Display" to RuntimeType.Display,
+            "Fmt" to RuntimeType.stdFmt,
+        )
     }
 
     private fun RustWriter.renderEnum() {
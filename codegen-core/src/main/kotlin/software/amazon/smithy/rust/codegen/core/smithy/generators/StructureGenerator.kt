@@ -8,7 +8,9 @@ package software.amazon.smithy.rust.codegen.core.smithy.generators
 import software.amazon.smithy.codegen.core.Symbol
 import software.amazon.smithy.model.Model
 import software.amazon.smithy.model.shapes.MemberShape
+import software.amazon.smithy.model.shapes.StringShape
 import software.amazon.smithy.model.shapes.StructureShape
+import software.amazon.smithy.model.traits.EnumTrait
 import software.amazon.smithy.model.traits.ErrorTrait
 import software.amazon.smithy.model.traits.SensitiveTrait
 import software.amazon.smithy.rust.codegen.core.rustlang.RustType
@@ -25,18 +27,20 @@ import software.amazon.smithy.rust.codegen.core.rustlang.render
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
 import software.amazon.smithy.rust.codegen.core.rustlang.rustBlock
 import software.amazon.smithy.rust.codegen.core.rustlang.stripOuter
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RustSymbolProvider
 import software.amazon.smithy.rust.codegen.core.smithy.customize.NamedCustomization
 import software.amazon.smithy.rust.codegen.core.smithy.customize.Section
 import software.amazon.smithy.rust.codegen.core.smithy.customize.writeCustomizations
 import software.amazon.smithy.rust.codegen.core.smithy.expectRustMetadata
+import software.amazon.smithy.rust.codegen.core.smithy.isOptional
 import software.amazon.smithy.rust.codegen.core.smithy.renamedFrom
 import software.amazon.smithy.rust.codegen.core.smithy.rustType
 import software.amazon.smithy.rust.codegen.core.util.REDACTION
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.getTrait
-import software.amazon.smithy.rust.codegen.core.util.redactIfNecessary
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
 import software.amazon.smithy.rust.codegen.core.util.shouldRedact
 
 /** StructureGenerator customization sections */
@@ -105,21 +109,13 @@ open class StructureGenerator(
             writer.rustBlock("fn fmt(&self, f: &mut #1T::Formatter<'_>) -> #1T::Result", RuntimeType.stdFmt) {
                 rust("""let mut formatter = f.debug_struct(${name.dq()});""")
 
-                members.forEach { member ->
-                    val memberName = symbolProvider.toMemberName(member)
+                forEachMember(members) { member, memberName, memberSymbol ->
                     // If the struct is marked sensitive all fields get redacted, otherwise each field is determined on its own
-                    val fieldValue =
-                        if (shape.shouldRedact(model)) {
-                            REDACTION
-                        } else {
-                            member.redactIfNecessary(
-                                model,
-                                "self.$memberName",
-                            )
-                        }
-
-                    rust(
-                        "formatter.field(${memberName.dq()}, &$fieldValue);",
+                    renderDebugField(
+                        symbolProvider.config.runtimeConfig,
+                        memberName,
+                        memberSymbol.rustType(),
+                        redact = shape.shouldRedact(model) || member.shouldRedact(model),
                     )
                 }
                 writeCustomizations(customizations, StructureSection.AdditionalDebugFields(shape, "formatter"))
@@ -175,6 +171,35 @@ open class StructureGenerator(
                         rust(".unwrap_or_default()")
                     }
                 }
+                if (member.targetsEnum(model)) {
+                    renderEnumAsStrAccessor(member, memberName, memberSymbol)
+                }
+            }
+        }
+    }
+
+    /**
+     * Render a `foo_as_str` accessor for an enum member `foo`, which returns the value as it was sent on the wire.
+     * This is also available for values that weren't known when the crate was generated.
+     */
+    private fun RustWriter.renderEnumAsStrAccessor(
+        member: MemberShape,
+        memberName: String,
+        memberSymbol: Symbol,
+    ) {
+        val accessorName = member.asStrAccessorName(memberName)
+        if (accessorMembers.any { symbolProvider.toMemberName(it) == accessorName }) {
+            return
+        }
+        docs("The raw string value of [`$memberName`](Self::$memberName).")
+        deprecatedShape(member)
+        if (memberSymbol.isOptional()) {
+            rustBlock("pub fn $accessorName(&self) -> ::std::option::Option<&str>") {
+                rust("self.$memberName.as_ref().map(|v| v.as_str())")
+            }
+        } else {
+            rustBlock("pub fn $accessorName(&self) -> &str") {
+                rust("self.$memberName.as_str()")
             }
         }
     }
@@ -238,6 +263,13 @@ open class StructureGenerator(
     }
 }
 
+/** Returns true if this member targets a string shape with the enum trait, including enum shapes. */
+fun MemberShape.targetsEnum(model: Model): Boolean =
+    model.expectShape(target).let { it is StringShape && it.hasTrait<EnumTrait>() }
+
+/** Name of the accessor for the raw string value of an enum member, e.g. `foo_as_str` for `foo`. */
+fun MemberShape.asStrAccessorName(memberName: String) = "${memberName.removePrefix("r#")}_as_str"
+
 /**
  * Search for lifetimes used by the members of the struct and generate a declaration.
  * e.g. `<'a, 'b>`
@@ -254,3 +286,26 @@ fun StructureShape.lifetimeDeclaration(symbolProvider: RustSymbolProvider): Stri
         ""
     }
 }
+
+/**
+ * Renders `formatter.field(...)` for a field of type [fieldType] in a hand-written `Debug` impl.
+ *
+ * Redacted fields print the [REDACTION] marker. Maps are printed with their entries sorted by key, so that the
+ * output doesn't depend on the map's iteration order.
+ */
+fun RustWriter.renderDebugField(
+    runtimeConfig: RuntimeConfig,
+    fieldName: String,
+    fieldType: RustType,
+    redact: Boolean,
+) {
+    val sortedMap = RuntimeType.smithyTypes(runtimeConfig).resolve("debug::SortedMap")
+    when {
+        redact -> rust("formatter.field(${fieldName.dq()}, &$REDACTION);")
+        fieldType is RustType.HashMap ->
+            rust("formatter.field(${fieldName.dq()}, &#T::new(&self.$fieldName));", sortedMap)
+        fieldType is RustType.Option && fieldType.member is RustType.HashMap ->
+            rust("formatter.field(${fieldName.dq()}, &self.$fieldName.as_ref().map(#T::new));", sortedMap)
+        else -> rust("formatter.field(${fieldName.dq()}, &self.$fieldName);")
+    }
+}
@@ -47,11 +47,13 @@ fun CodegenTarget.renderUnknownVariant() =
  * methods:
  * - `is_<variant>()`
  * - `as_<variant>()`
+ * - `try_into_<variant>()`
  *
  * for each variant.
  *
  * Finally, if `[renderUnknownVariant]` is true (the default), it will render an `Unknown` variant. This is used by
  * clients to allow response parsing to succeed, even if the server has added a new variant since the client was generated.
+ * The `Unknown` variant holds the name of the variant that was received.
  */
 open class UnionGenerator(
     val model: Model,
@@ -100,9 +102,16 @@ open class UnionGenerator(
                 rust("/// The `Unknown` variant represents cases where the server sent a value that wasn't recognized")
                 rust("/// by the client. This can happen when the server adds new functionality, but the client has not been updated.")
                 rust("/// To investigate this, consider turning on debug logging to print the raw HTTP response.")
-                // at some point in the future, we may start actually putting things like the raw data in here.
                 Attribute.NonExhaustive.render(this)
-                rust("Unknown,")
+                rustTemplate(
+                    """
+                    $UNKNOWN_VARIANT_NAME {
+                        /// The name of the variant that was received.
+                        variant_name: #{String},
+                    },
+                    """,
+                    *preludeScope,
+                )
             }
         }
     }
@@ -121,11 +130,22 @@ open class UnionGenerator(
                 rustBlock("pub fn is_$funcNamePart(&self) -> bool") {
                     rust("self.as_$funcNamePart().is_ok()")
                 }
+                if (sortedMembers.size == 1) {
+                    Attribute.AllowIrrefutableLetPatterns.render(this)
+                }
+                writer.renderTryIntoVariant(symbolProvider, member, variantName, funcNamePart, unionSymbol)
             }
             if (renderUnknownVariant) {
                 rust("/// Returns true if the enum instance is the `Unknown` variant.")
                 rustBlock("pub fn is_unknown(&self) -> bool") {
-                    rust("matches!(self, Self::Unknown)")
+                    rust("matches!(self, Self::$UNKNOWN_VARIANT_NAME { .. })")
+                }
+                rust("/// Returns the name of the variant that was received if the enum instance is the `Unknown` variant.")
+                rustBlockTemplate("pub fn unknown_variant_name(&self) -> #{Option}<&str>", *preludeScope) {
+                    rustTemplate(
+                        "if let Self::$UNKNOWN_VARIANT_NAME { variant_name } = self { #{Some}(variant_name) } else { #{None} }",
+                        *preludeScope,
+                    )
                 }
             }
         }
@@ -161,7 +181,10 @@ open class UnionGenerator(
                         }
                     }
                     if (renderUnknownVariant) {
-                        rust("${unionSymbol.name}::$UNKNOWN_VARIANT_NAME => f.debug_tuple(${UNKNOWN_VARIANT_NAME.dq()}).finish(),")
+                        rust(
+                            "${unionSymbol.name}::$UNKNOWN_VARIANT_NAME { variant_name } => " +
+                                "f.debug_struct(${UNKNOWN_VARIANT_NAME.dq()}).field(\"variant_name\", variant_name).finish(),",
+                        )
                     }
                 }
             }
@@ -227,3 +250,35 @@ private fun RustWriter.renderAsVariant(
         }
     }
 }
+
+private fun RustWriter.renderTryIntoVariant(
+    symbolProvider: SymbolProvider,
+    member: MemberShape,
+    variantName: String,
+    funcNamePart: String,
+    unionSymbol: Symbol,
+) {
+    if (member.isTargetUnit()) {
+        rust("/// Tries to convert the enum instance into [`$variantName`](#T::$variantName).", unionSymbol)
+        rust("/// Returns `Err(Self)` if it can't be converted.")
+        rustBlockTemplate("pub fn try_into_$funcNamePart(self) -> #{Result}<(), Self>", *preludeScope) {
+            rustTemplate(
+                "if let ${unionSymbol.name}::$variantName = self { #{Ok}(()) } else { #{Err}(self) }",
+                *preludeScope,
+            )
+        }
+    } else {
+        val memberSymbol = symbolProvider.toSymbol(member)
+        rust(
+            "/// Tries to convert the enum instance into [`$variantName`](#T::$variantName), taking ownership of the inner value.",
+            unionSymbol,
+        )
+        rust("/// Returns `Err(Self)` if it can't be converted.")
+        rustBlockTemplate("pub fn try_into_$funcNamePart(self) -> #{Result}<${memberSymbol.rustType().render()}, Self>", *preludeScope) {
+            rustTemplate(
+                "if let ${unionSymbol.name}::$variantName(val) = self { #{Ok}(val) } else { #{Err}(self) }",
+                *preludeScope,
+            )
+        }
+    }
+}
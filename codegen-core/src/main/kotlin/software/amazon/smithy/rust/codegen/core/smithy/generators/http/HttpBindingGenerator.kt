@@ -168,18 +168,50 @@ class HttpBindingGenerator(
         val outputSymbol = symbolProvider.toSymbol(binding.member)
         val target = model.expectShape(binding.member.target)
         check(target is MapShape)
+        val valueShape = model.expectShape(target.value.target)
+        // Plain string values are used as they are, so they can be streamed through without parsing.
+        val stringValues =
+            valueShape is StringShape &&
+                !valueShape.hasTrait<MediaTypeTrait>() &&
+                !valueShape.hasTrait<EnumTrait>() &&
+                symbolProvider.toSymbol(valueShape).rustType() == RustType.String
+        val returnTypeSymbol = outputSymbol.mapRustType { it.asOptional() }
+        if (stringValues) {
+            return protocolFunctions.deserializeFn(binding.member, fnNameSuffix = "prefix_header") { fnName ->
+                rustBlockTemplate(
+                    "pub(crate) fn $fnName(header_map: &#{Headers}) -> std::result::Result<#{Value}, #{header_util}::ParseError>",
+                    "Headers" to RuntimeType.headers(runtimeConfig),
+                    "Value" to returnTypeSymbol,
+                    "header_util" to headerUtil,
+                ) {
+                    rustTemplate(
+                        """
+                        let mut out = #{HashMap}::new();
+                        for (key, value) in #{header_util}::prefixed_headers(header_map, ${binding.locationName.dq()}) {
+                            if out.insert(key.to_string(), value.trim().to_string()).is_some() {
+                                return Err(#{header_util}::ParseError::new("expected a single value but found multiple"));
+                            }
+                        }
+                        let out: std::result::Result<_, #{header_util}::ParseError> = Ok(out);
+                        """,
+                        "HashMap" to RuntimeType.HashMap,
+                        "header_util" to headerUtil,
+                    )
+                    renderAfterDeserializingPrefixHeaders(binding)
+                }
+            }
+        }
         val inner =
             protocolFunctions.deserializeFn(binding.member, fnNameSuffix = "inner") { fnName ->
                 rustBlockTemplate(
                     "pub fn $fnName<'a>(headers: impl #{Iterator}<Item = &'a str>) -> std::result::Result<Option<#{Value}>, #{header_util}::ParseError>",
                     *preludeScope,
-                    "Value" to symbolProvider.toSymbol(model.expectShape(target.value.target)),
+                    "Value" to symbolProvider.toSymbol(valueShape),
                     "header_util" to headerUtil,
                 ) {
-                    deserializeFromHeader(model.expectShape(target.value.target), binding.member)
+                    deserializeFromHeader(valueShape, binding.member)
                 }
             }
-        val returnTypeSymbol = outputSymbol.mapRustType { it.asOptional() }
         return protocolFunctions.deserializeFn(binding.member, fnNameSuffix = "prefix_header") { fnName ->
             rustBlockTemplate(
                 "pub(crate) fn $fnName(header_map: &#{Headers}) -> std::result::Result<#{Value}, #{header_util}::ParseError>",
@@ -202,17 +234,20 @@ class HttpBindingGenerator(
                     """,
                     headerUtil, inner,
                 )
-
-                for (customization in customizations) {
-                    customization.section(
-                        HttpBindingSection.AfterDeserializingIntoAHashMapOfHttpPrefixHeaders(binding.member),
-                    )(this)
-                }
-                rust("out.map(Some)")
+                renderAfterDeserializingPrefixHeaders(binding)
             }
         }
     }
 
+    private fun RustWriter.renderAfterDeserializingPrefixHeaders(binding: HttpBindingDescriptor) {
+        for (customization in customizations) {
+            customization.section(
+                HttpBindingSection.AfterDeserializingIntoAHashMapOfHttpPrefixHeaders(binding.member),
+            )(this)
+        }
+        rust("out.map(Some)")
+    }
+
     /**
      * Generate a function to deserialize `[binding]` from the request / response payload.
      */
@@ -736,9 +771,13 @@ class HttpBindingGenerator(
                     HttpBindingSection.BeforeIteratingOverMapShapeBoundWithHttpPrefixHeaders(local.name, targetShape),
                 )(this)
             }
+            // Keys that only differ in case are serialized as a single, repeated header, so iterate over the map in a
+            // stable order to keep the request (and its signature) deterministic.
             rustTemplate(
                 """
-                for (k, v) in ${local.asRef()} {
+                let mut prefix_headers: ::std::vec::Vec<_> = ${local.name}.iter().collect();
+                prefix_headers.sort_by_cached_key(|(k, _)| k.to_string());
+                for (k, v) in prefix_headers {
                     use std::str::FromStr;
                     let header_name = http::header::HeaderName::from_str(&format!("{}{}", "${httpBinding.locationName}", &k)).map_err(|err| {
                         #{invalid_header_name:W}
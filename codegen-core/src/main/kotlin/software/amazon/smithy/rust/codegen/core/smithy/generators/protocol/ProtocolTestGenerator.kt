@@ -296,16 +296,24 @@ abstract class ProtocolTestGenerator {
         )
     }
 
+    /**
+     * Checks that the [headers] are present. With [exact], also checks that no other headers are present, except for
+     * the [ignore]d ones and the volatile ones ignored by `validate_headers_exact`.
+     */
     fun checkHeaders(
         rustWriter: RustWriter,
         actualExpression: String,
         headers: Map<String, String>,
+        exact: Boolean = false,
+        ignore: List<String> = listOf(),
     ) {
-        if (headers.isEmpty()) {
+        if (headers.isEmpty() && !exact) {
             return
         }
         val variableName = "expected_headers"
-        rustWriter.withBlock("let $variableName = [", "];") {
+        // An empty array needs a type annotation for its element type to be inferred.
+        val typeAnnotation = if (headers.isEmpty()) ": [(&str, &str); 0]" else ""
+        rustWriter.withBlock("let $variableName$typeAnnotation = [", "];") {
             writeWithNoFormatting(
                 headers.entries.joinToString(",") {
                     "(${it.key.dq()}, ${it.value.dq()})"
@@ -313,10 +321,17 @@ abstract class ProtocolTestGenerator {
             )
         }
         assertOk(rustWriter) {
-            write(
-                "#T($actualExpression, $variableName)",
-                RuntimeType.protocolTest(codegenContext.runtimeConfig, "validate_headers"),
-            )
+            if (exact) {
+                write(
+                    "#T($actualExpression, $variableName, &[${ignore.joinToString(",") { it.dq() }}])",
+                    RuntimeType.protocolTest(codegenContext.runtimeConfig, "validate_headers_exact"),
+                )
+            } else {
+                write(
+                    "#T($actualExpression, $variableName)",
+                    RuntimeType.protocolTest(codegenContext.runtimeConfig, "validate_headers"),
+                )
+            }
         }
     }
 
@@ -332,9 +332,10 @@ class CborParserGenerator(
                         true ->
                             rustTemplate(
                                 """
-                                _ => {
+                                unknown => {
+                                  let variant_name = unknown.to_string();
                                   decoder.skip()?;
-                                  #{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME}
+                                  #{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME} { variant_name }
                                 }
                                 """,
                                 "Union" to returnSymbolToParse.symbol,
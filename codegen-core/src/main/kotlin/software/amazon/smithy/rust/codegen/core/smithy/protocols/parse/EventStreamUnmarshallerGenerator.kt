@@ -163,7 +163,7 @@ class EventStreamUnmarshallerGenerator(
                 when (codegenTarget.renderUnknownVariant()) {
                     true ->
                         rustTemplate(
-                            "Ok(#{UnmarshalledMessage}::Event(#{Output}::${UnionGenerator.UNKNOWN_VARIANT_NAME}))",
+                            "Ok(#{UnmarshalledMessage}::Event(#{Output}::${UnionGenerator.UNKNOWN_VARIANT_NAME} { variant_name: _unknown_variant.to_string() }))",
                             "Output" to unionSymbol,
                             *codegenScope,
                         )
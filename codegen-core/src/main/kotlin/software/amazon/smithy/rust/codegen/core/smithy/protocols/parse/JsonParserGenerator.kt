@@ -114,11 +114,15 @@ class JsonParserGenerator(
             "expect_start_object" to smithyJson.resolve("deserialize::token::expect_start_object"),
             "expect_string_or_null" to smithyJson.resolve("deserialize::token::expect_string_or_null"),
             "expect_timestamp_or_null" to smithyJson.resolve("deserialize::token::expect_timestamp_or_null"),
+            "FieldErrorScope" to smithyJson.resolve("deserialize::field_errors::FieldErrorScope"),
+            "lenient_scalar" to smithyJson.resolve("deserialize::field_errors::lenient_scalar"),
             "json_token_iter" to smithyJson.resolve("deserialize::json_token_iter"),
             "Peekable" to RuntimeType.std.resolve("iter::Peekable"),
+            "skip_unknown_value" to smithyJson.resolve("deserialize::unknown_fields::skip_unknown_value"),
             "skip_value" to smithyJson.resolve("deserialize::token::skip_value"),
             "skip_to_end" to smithyJson.resolve("deserialize::token::skip_to_end"),
             "Token" to smithyJson.resolve("deserialize::Token"),
+            "UnknownFieldScope" to smithyJson.resolve("deserialize::unknown_fields::UnknownFieldScope"),
             "or_empty" to orEmptyJson(),
             *preludeScope,
         )
@@ -142,6 +146,10 @@ class JsonParserGenerator(
                 "Builder" to builderSymbol,
                 *codegenScope,
             ) {
+                rustTemplate("let _unknown_fields = #{UnknownFieldScope}::new(value);", *codegenScope)
+                codegenTarget.ifClient {
+                    rustTemplate("let _field_errors = #{FieldErrorScope}::new(value);", *codegenScope)
+                }
                 rustTemplate(
                     """
                     let mut tokens_owned = #{json_token_iter}(#{or_empty}(value)).peekable();
@@ -176,6 +184,10 @@ class JsonParserGenerator(
                         "#{or_empty}(input)"
                     }
 
+                rustTemplate("let _unknown_fields = #{UnknownFieldScope}::new(input);", *codegenScope)
+                codegenTarget.ifClient {
+                    rustTemplate("let _field_errors = #{FieldErrorScope}::new(input);", *codegenScope)
+                }
                 rustTemplate(
                     """
                     let mut tokens_owned = #{json_token_iter}($input).peekable();
@@ -255,7 +267,7 @@ class JsonParserGenerator(
                         when (codegenTarget) {
                             CodegenTarget.CLIENT -> {
                                 withBlock("builder = builder.${member.setterName()}(", ");") {
-                                    deserializeMember(member)
+                                    deserializeMember(member, lenient = !member.isRequired)
                                 }
                             }
 
@@ -279,13 +291,41 @@ class JsonParserGenerator(
                         }
                     }
                 }
-                rustTemplate("_ => #{skip_value}(tokens)?", *codegenScope)
+                rustTemplate("_ => #{skip_unknown_value}(tokens)?", *codegenScope)
+            }
+        }
+    }
+
+    /**
+     * Deserializes the value of [memberShape].
+     *
+     * When [lenient] is true and the member targets a scalar, the value is parsed with `lenient_scalar` on the
+     * client, so that if lenient deserialization is enabled at runtime, a value that fails to parse is recorded and
+     * left unset rather than failing the whole response. Only scalars are parsed leniently since they're a single
+     * token, so the token stream can always be resumed after them.
+     */
+    private fun RustWriter.deserializeMember(
+        memberShape: MemberShape,
+        lenient: Boolean = false,
+    ) {
+        val target = model.expectShape(memberShape.target)
+        val isScalar =
+            target is StringShape || target is BooleanShape || target is NumberShape ||
+                target is BlobShape || target is TimestampShape
+        if (lenient && isScalar && codegenTarget == CodegenTarget.CLIENT) {
+            withBlockTemplate("#{lenient_scalar}(tokens, |tokens| #{Ok}(", "))?", *codegenScope) {
+                deserializeMemberValue(memberShape, target)
             }
+        } else {
+            deserializeMemberValue(memberShape, target)
         }
     }
 
-    private fun RustWriter.deserializeMember(memberShape: MemberShape) {
-        when (val target = model.expectShape(memberShape.target)) {
+    private fun RustWriter.deserializeMemberValue(
+        memberShape: MemberShape,
+        target: Shape,
+    ) {
+        when (target) {
             is StringShape -> deserializeString(target)
             is BooleanShape -> rustTemplate("#{expect_bool_or_null}(tokens.next())?", *codegenScope)
             is NumberShape -> deserializeNumber(target)
@@ -409,7 +449,7 @@ class JsonParserGenerator(
                                         }
                                     } else {
                                         withBlock("let value =", ";") {
-                                            deserializeMember(shape.member)
+                                            deserializeMember(shape.member, lenient = true)
                                         }
                                         rust(
                                             """
@@ -464,7 +504,7 @@ class JsonParserGenerator(
                                 deserializeStringInner(keyTarget, "key")
                             }
                             withBlock("let value =", ";") {
-                                deserializeMember(shape.value)
+                                deserializeMember(shape.value, lenient = !isSparse)
                             }
                             if (isSparse) {
                                 rust("map.insert(key, value);")
@@ -608,7 +648,7 @@ class JsonParserGenerator(
                                                 """
                                                 _ => {
                                                   #{skip_value}(tokens)?;
-                                                  Some(#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME})
+                                                  Some(#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME} { variant_name: key.to_string() })
                                                 }
                                                 """,
                                                 "Union" to returnSymbolToParse.symbol,
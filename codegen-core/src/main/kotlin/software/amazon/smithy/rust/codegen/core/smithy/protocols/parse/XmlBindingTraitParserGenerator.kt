@@ -341,7 +341,7 @@ class XmlBindingTraitParserGenerator(
     /**
      * The core XML parsing abstraction: A loop that reads through the top level tags at the current scope &
      * generates a match expression
-     * When [ignoreUnexpected] is true, unexpected tags are ignored
+     * When [ignoreUnexpected] is true, unexpected tags are ignored (and recorded if unknown field recording is enabled)
      */
     private fun RustWriter.parseLoop(
         ctx: Ctx,
@@ -352,7 +352,7 @@ class XmlBindingTraitParserGenerator(
             rustBlock("match tag.start_el()") {
                 inner(ctx.copy(tag = "tag"))
                 if (ignoreUnexpected) {
-                    rust("_ => {}")
+                    rust("_ => tag.record_unknown_field()")
                 }
             }
         }
@@ -464,7 +464,7 @@ class XmlBindingTraitParserGenerator(
                             }
                         }
                         when (target.renderUnknownVariant()) {
-                            true -> rust("_unknown => base = Some(#T::${UnionGenerator.UNKNOWN_VARIANT_NAME}),", symbol)
+                            true -> rust("unknown => base = Some(#T::${UnionGenerator.UNKNOWN_VARIANT_NAME} { variant_name: unknown.local().to_string() }),", symbol)
                             false ->
                                 rustTemplate(
                                     """variant => return Err(#{XmlDecodeError}::custom(format!("unexpected union variant: {:?}", variant)))""",
@@ -459,7 +459,7 @@ class CborSerializerGenerator(
                         }
                         if (codegenTarget.renderUnknownVariant()) {
                             rustTemplate(
-                                "#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME} => return #{Err}(#{Error}::unknown_variant(${unionSymbol.name.dq()}))",
+                                "#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME} { .. } => return #{Err}(#{Error}::unknown_variant(${unionSymbol.name.dq()}))",
                                 "Union" to unionSymbol,
                                 *codegenScope,
                             )
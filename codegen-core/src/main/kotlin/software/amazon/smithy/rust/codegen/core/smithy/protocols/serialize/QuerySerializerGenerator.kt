@@ -376,7 +376,7 @@ abstract class QuerySerializerGenerator(private val codegenContext: CodegenConte
                         }
                         if (target.renderUnknownVariant()) {
                             rustTemplate(
-                                "#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME} => return Err(#{Error}::unknown_variant(${unionSymbol.name.dq()}))",
+                                "#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME} { .. } => return Err(#{Error}::unknown_variant(${unionSymbol.name.dq()}))",
                                 "Union" to unionSymbol,
                                 *codegenScope,
                             )
@@ -92,6 +92,27 @@ class ClientAdditionalSettings private constructor(settings: List<AdditionalSett
 
         // Additional settings that are specific to client generation should be defined here.
 
+        private data class TypeErasedServiceBuilder(val enabled: Boolean) : AdditionalSettings() {
+            override fun toObjectNode(): ObjectNode =
+                ObjectNode.builder()
+                    .withMember("typeErasedServiceBuilder", enabled)
+                    .build()
+        }
+
+        private data class RejectEmptyLabels(val enabled: Boolean) : AdditionalSettings() {
+            override fun toObjectNode(): ObjectNode =
+                ObjectNode.builder()
+                    .withMember("rejectEmptyLabels", enabled)
+                    .build()
+        }
+
+        private data class GreedyLabelTrailingSlash(val handling: String) : AdditionalSettings() {
+            override fun toObjectNode(): ObjectNode =
+                ObjectNode.builder()
+                    .withMember("greedyLabelTrailingSlash", handling)
+                    .build()
+        }
+
         companion object {
             fun builder() = Builder()
         }
@@ -110,6 +131,26 @@ class ServerAdditionalSettings private constructor(settings: List<AdditionalSett
                 return this
             }
 
+            fun typeErasedServiceBuilder(enabled: Boolean = true): Builder {
+                settings.add(TypeErasedServiceBuilder(enabled))
+                return this
+            }
+
+            fun rejectEmptyLabels(enabled: Boolean = true): Builder {
+                settings.add(RejectEmptyLabels(enabled))
+                return this
+            }
+
+            fun greedyLabelTrailingSlash(handling: String): Builder {
+                settings.add(GreedyLabelTrailingSlash(handling))
+                return this
+            }
+
+            fun openApi(enabled: Boolean = true): Builder {
+                settings.add(OpenApi(enabled))
+                return this
+            }
+
             override fun build(): ServerAdditionalSettings = ServerAdditionalSettings(settings)
         }
 
@@ -127,6 +168,34 @@ class ServerAdditionalSettings private constructor(settings: List<AdditionalSett
                     .build()
         }
 
+        private data class TypeErasedServiceBuilder(val enabled: Boolean) : AdditionalSettings() {
+            override fun toObjectNode(): ObjectNode =
+                ObjectNode.builder()
+                    .withMember("typeErasedServiceBuilder", enabled)
+                    .build()
+        }
+
+        private data class RejectEmptyLabels(val enabled: Boolean) : AdditionalSettings() {
+            override fun toObjectNode(): ObjectNode =
+                ObjectNode.builder()
+                    .withMember("rejectEmptyLabels", enabled)
+                    .build()
+        }
+
+        private data class GreedyLabelTrailingSlash(val handling: String) : AdditionalSettings() {
+            override fun toObjectNode(): ObjectNode =
+                ObjectNode.builder()
+                    .withMember("greedyLabelTrailingSlash", handling)
+                    .build()
+        }
+
+        private data class OpenApi(val enabled: Boolean) : AdditionalSettings() {
+            override fun toObjectNode(): ObjectNode =
+                ObjectNode.builder()
+                    .withMember("openApi", enabled)
+                    .build()
+        }
+
         companion object {
             fun builder() = Builder()
         }
@@ -245,6 +245,71 @@ internal class BuilderGeneratorTest {
         project.compileAndTest()
     }
 
+    @Test
+    fun `collection members can be set from any iterator`() {
+        val model =
+            """
+            namespace com.test
+            structure MyStruct {
+              names: StringList
+              tags: TagMap
+              groups: NestedList
+            }
+            list StringList {
+                member: String
+            }
+            map TagMap {
+                key: String
+                value: String
+            }
+            list NestedList {
+                member: StringList
+            }
+            """.asSmithyModel(smithyVersion = "2.0")
+
+        val provider = testSymbolProvider(model)
+        val project = TestWorkspace.testProject(provider)
+        val shape: StructureShape = model.lookup("com.test#MyStruct")
+        project.useShapeWriter(shape) {
+            generator(model, provider, this, shape).render()
+            BuilderGenerator(model, provider, shape, listOf()).render(this)
+            unitTest("set_collections_from_iter") {
+                rust(
+                    """
+                    use std::collections::{BTreeMap, HashMap};
+
+                    let s = Builder::default()
+                        .set_names_from_iter(["a", "b", "c"].into_iter().filter(|name| *name != "b"))
+                        .set_tags_from_iter(BTreeMap::from([("k1", "v1"), ("k2", "v2")]))
+                        .set_groups_from_iter([vec!["x".to_owned()]])
+                        .build();
+                    assert_eq!(Some(vec!["a".to_owned(), "c".to_owned()]), s.names);
+                    assert_eq!(
+                        Some(HashMap::from([("k1".to_owned(), "v1".to_owned()), ("k2".to_owned(), "v2".to_owned())])),
+                        s.tags
+                    );
+                    assert_eq!(Some(vec![vec!["x".to_owned()]]), s.groups);
+
+                    // The exact types are still accepted
+                    let s = Builder::default()
+                        .set_names_from_iter(vec!["a".to_owned()])
+                        .set_tags_from_iter(HashMap::from([("k".to_owned(), "v".to_owned())]))
+                        .build();
+                    assert_eq!(Some(vec!["a".to_owned()]), s.names);
+                    assert_eq!(Some(&"v".to_owned()), s.tags.as_ref().and_then(|tags| tags.get("k")));
+                    let s = Builder::default()
+                        .set_names(Some(vec!["a".to_owned()]))
+                        .set_tags(None)
+                        .build();
+                    assert_eq!(Some(vec!["a".to_owned()]), s.names);
+                    assert_eq!(None, s.tags);
+                    """,
+                )
+            }
+        }
+        project.compileAndTest()
+    }
+
     @Test
     fun `builder doesn't inherit attributes from struct`() {
         /**
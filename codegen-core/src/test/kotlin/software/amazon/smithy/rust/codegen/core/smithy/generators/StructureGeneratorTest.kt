@@ -16,6 +16,8 @@ import software.amazon.smithy.rust.codegen.core.rustlang.RustReservedWordConfig
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
 import software.amazon.smithy.rust.codegen.core.rustlang.rustBlock
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RustSymbolProvider
 import software.amazon.smithy.rust.codegen.core.smithy.transformers.RecursiveShapeBoxer
 import software.amazon.smithy.rust.codegen.core.testutil.TestWorkspace
@@ -95,6 +97,16 @@ class StructureGeneratorTest {
             list ListThatContainsSecrets {
                 member: Password
             }
+
+            structure Tagged {
+                name: String,
+                tags: Tags,
+            }
+
+            map Tags {
+                key: String
+                value: String
+            }
             """.asSmithyModel()
         val struct = model.lookup<StructureShape>("com.test#MyStruct")
         val structWithDoc = model.lookup<StructureShape>("com.test#StructWithDoc")
@@ -102,6 +114,7 @@ class StructureGeneratorTest {
         val credentials = model.lookup<StructureShape>("com.test#Credentials")
         val secretStructure = model.lookup<StructureShape>("com.test#SecretStructure")
         val structWithInnerSecretStructure = model.lookup<StructureShape>("com.test#StructWithInnerSecretStructure")
+        val tagged = model.lookup<StructureShape>("com.test#Tagged")
 
         val rustReservedWordConfig: RustReservedWordConfig =
             RustReservedWordConfig(
@@ -244,6 +257,52 @@ class StructureGeneratorTest {
         project.compileAndTest()
     }
 
+    @Test
+    fun `generate a deterministic debug implementation for structures with map members`() {
+        val provider = testSymbolProvider(model, rustReservedWordConfig = rustReservedWordConfig)
+        TestWorkspace.testProject().unitTest {
+            structureGenerator(model, provider, this, tagged).render()
+
+            rustTemplate(
+                """
+                fn tagged(keys: impl Iterator<Item = u32>) -> Tagged {
+                    Tagged {
+                        name: Some("pikachu".to_owned()),
+                        tags: Some(keys.map(|key| (format!("key-{key:02}"), key.to_string())).collect()),
+                    }
+                }
+
+                let forwards = tagged(0..50);
+                let backwards = tagged((0..50).rev());
+                assert_eq!(format!("{:?}", forwards), format!("{:?}", backwards));
+                assert_eq!(format!("{:#?}", forwards), format!("{:#?}", backwards));
+
+                let tagged = tagged([2, 1].into_iter());
+                assert_eq!(
+                    format!("{:?}", tagged),
+                    "Tagged { name: Some(\"pikachu\"), tags: Some({\"key-01\": \"1\", \"key-02\": \"2\"}) }"
+                );
+                let expected = concat!(
+                    "Tagged {\n",
+                    "    name: Some(\n",
+                    "        \"pikachu\",\n",
+                    "    ),\n",
+                    "    tags: Some(\n",
+                    "        {\n",
+                    "            \"key-01\": \"1\",\n",
+                    "            \"key-02\": \"2\",\n",
+                    "        },\n",
+                    "    ),\n",
+                    "}",
+                );
+                assert_eq!(expected, format!("{:?}", #{Stable}::new(&tagged)));
+                assert_eq!(expected, format!("{}", #{Stable}::new(&tagged)));
+                """,
+                "Stable" to RuntimeType.smithyTypes(provider.config.runtimeConfig).resolve("debug::Stable"),
+            )
+        }.compileAndTest()
+    }
+
     @Test
     fun `attach docs to everything`() {
         val model =
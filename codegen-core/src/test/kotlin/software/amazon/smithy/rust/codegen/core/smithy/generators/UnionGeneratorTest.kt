@@ -14,6 +14,7 @@ import software.amazon.smithy.rust.codegen.core.testutil.TestWorkspace
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.compileAndTest
 import software.amazon.smithy.rust.codegen.core.testutil.testSymbolProvider
+import software.amazon.smithy.rust.codegen.core.testutil.unitTest
 import software.amazon.smithy.rust.codegen.core.util.REDACTION
 import software.amazon.smithy.rust.codegen.core.util.lookup
 
@@ -70,6 +71,54 @@ class UnionGeneratorTest {
         )
     }
 
+    @Test
+    fun `generate accessors for structure and blob variants`() {
+        val model =
+            """
+            namespace test
+            structure Point {
+                x: Integer
+            }
+            union Shape {
+                name: String,
+                point: Point,
+                data: Blob
+            }
+            """.asSmithyModel()
+        val provider = testSymbolProvider(model)
+        val project = TestWorkspace.testProject(provider)
+        project.moduleFor(model.lookup("test#Shape")) {
+            StructureGenerator(model, provider, this, model.lookup("test#Point"), emptyList(), StructSettings(true)).render()
+            UnionGenerator(model, provider, this, model.lookup("test#Shape")).render()
+            unitTest(
+                "accessors",
+                """
+                let point = Shape::Point(Point { x: Some(1) });
+                assert!(point.is_point());
+                assert!(!point.is_data());
+                assert!(!point.is_unknown());
+                assert_eq!(Ok(&Point { x: Some(1) }), point.as_point());
+                assert_eq!(Err(&point), point.as_data());
+
+                let data = Shape::Data(aws_smithy_types::Blob::new("bytes"));
+                assert!(data.is_data());
+                assert_eq!(Ok(&aws_smithy_types::Blob::new("bytes")), data.as_data());
+                assert_eq!(Err(data.clone()), data.clone().try_into_name());
+                assert_eq!(Ok(aws_smithy_types::Blob::new("bytes")), data.try_into_data());
+
+                assert_eq!(Ok(Point { x: Some(1) }), point.try_into_point());
+                let name = Shape::Name("circle".to_string());
+                assert_eq!(Ok("circle".to_string()), name.try_into_name());
+
+                let unknown = Shape::Unknown { variant_name: "polygon".to_string() };
+                assert_eq!(Err(unknown.clone()), unknown.clone().try_into_point());
+                assert_eq!(Some("polygon"), unknown.unknown_variant_name());
+                """,
+            )
+        }
+        project.compileAndTest()
+    }
+
     @Test
     fun `documents are not optional in unions`() {
         val writer = generateUnion("union MyUnion { doc: Document, other: String }")
@@ -92,8 +141,10 @@ class UnionGeneratorTest {
         val writer = generateUnion("union MyUnion { a: String, b: String }", unknownVariant = true)
         writer.compileAndTest(
             """
-            let union = MyUnion::Unknown;
+            let union = MyUnion::Unknown { variant_name: "newVariant".to_string() };
             assert!(union.is_unknown());
+            assert_eq!(Some("newVariant"), union.unknown_variant_name());
+            assert_eq!(None, MyUnion::A("a".to_string()).unknown_variant_name());
 
             """,
         )
@@ -222,6 +273,7 @@ class UnionGeneratorTest {
             """
             let a = MyUnion::A;
             assert_eq!(Ok(()), a.as_a());
+            assert_eq!(Ok(()), a.try_into_a());
             """,
         )
     }
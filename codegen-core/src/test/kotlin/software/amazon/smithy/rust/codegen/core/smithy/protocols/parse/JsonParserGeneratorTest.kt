@@ -168,7 +168,9 @@ class JsonParserGeneratorTest {
                 // unknown variant
                 let input = br#"{ "top": { "choice": { "somenewvariant": "data" } } }"#;
                 let output = ${format(operationGenerator)}(input, test_output::OpOutput::builder()).unwrap().build();
-                assert!(output.top.unwrap().choice.is_unknown());
+                let choice = output.top.unwrap().choice;
+                assert!(choice.is_unknown());
+                assert_eq!(Some("somenewvariant"), choice.unknown_variant_name());
                 """,
             )
 
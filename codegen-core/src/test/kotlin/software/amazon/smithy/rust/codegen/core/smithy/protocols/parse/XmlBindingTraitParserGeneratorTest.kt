@@ -199,7 +199,9 @@ internal class XmlBindingTraitParserGeneratorTest {
                     </Top>
                     "#;
                     let output = ${format(operationParser)}(xml, test_output::OpOutput::builder()).unwrap().build();
-                    assert!(output.choice.unwrap().is_unknown());
+                    let choice = output.choice.unwrap();
+                    assert!(choice.is_unknown());
+                    assert_eq!(Some("NewVariantName"), choice.unknown_variant_name());
                 """,
             )
         }
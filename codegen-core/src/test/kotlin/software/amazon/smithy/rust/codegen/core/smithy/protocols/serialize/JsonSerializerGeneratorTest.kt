@@ -147,7 +147,7 @@ class JsonSerializerGeneratorTest {
 
                 let input = crate::test_input::OpInput::builder().top(
                     Top::builder()
-                        .choice(Choice::Unknown)
+                        .choice(Choice::Unknown { variant_name: "Unrecognized".to_string() })
                         .build()
                 ).build().unwrap();
                 ${format(operationGenerator)}(&input).expect_err("cannot serialize unknown variant");
@@ -319,7 +319,7 @@ class JsonSerializerGeneratorTest {
                 let input = crate::test_input::OpInput::builder().top(
                     Top::builder()
                         .field("Hello")
-                        .choice(Choice::Unknown)
+                        .choice(Choice::Unknown { variant_name: "Unrecognized".to_string() })
                         .extra(45)
                         .build()
                         $maybeUnwrap
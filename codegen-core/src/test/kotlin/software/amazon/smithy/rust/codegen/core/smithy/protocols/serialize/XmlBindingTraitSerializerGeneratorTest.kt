@@ -152,7 +152,7 @@ internal class XmlBindingTraitSerializerGeneratorTest {
                 use test_model::{Top, Choice};
                 let input = crate::test_input::OpInput::builder().payload(
                     Top::builder()
-                        .choice(Choice::Unknown)
+                        .choice(Choice::Unknown { variant_name: "Unrecognized".to_string() })
                         .build()
                 ).build().unwrap();
                 ${format(operationSerializer)}(&input.payload.unwrap()).expect_err("cannot serialize unknown variant");
@@ -320,7 +320,7 @@ internal class XmlBindingTraitSerializerGeneratorTest {
                 let input = crate::test_input::OpInput::builder().payload(
                     Top::builder()
                         .field("Hello")
-                        .choice(Choice::Unknown)
+                        .choice(Choice::Unknown { variant_name: "Unrecognized".to_string() })
                         .extra(45)
                         .build()
                         $maybeUnwrap
@@ -445,7 +445,7 @@ class SerializeImplGenerator(private val codegenContext: CodegenContext) {
                     }
                     if (codegenContext.target.renderUnknownVariant()) {
                         rustTemplate(
-                            "#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME} => serializer.serialize_str(\"unknown variant!\")",
+                            "#{Union}::${UnionGenerator.UNKNOWN_VARIANT_NAME} { .. } => serializer.serialize_str(\"unknown variant!\")",
                             "Union" to unionSymbol,
                         )
                     }
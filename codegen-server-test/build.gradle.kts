@@ -98,6 +98,25 @@ val allCodegenTests = "../codegen-core/common-test-models".let { commonModels ->
             "pokemon-service-awsjson-server-sdk",
             imports = listOf("$commonModels/pokemon-awsjson.smithy", "$commonModels/pokemon-common.smithy"),
         ),
+        CodegenTest(
+            "com.aws.example#PokemonService",
+            "pokemon-service-type-erased-server-sdk",
+            imports = listOf("$commonModels/pokemon.smithy", "$commonModels/pokemon-common.smithy"),
+            extraConfig = """, "codegen": { "typeErasedServiceBuilder": true } """,
+        ),
+        // The same 200-operation service with and without type erasure, to compare their compile times, see
+        // `examples/BENCHMARKS.md`.
+        CodegenTest(
+            "com.amazonaws.manyoperations#ManyOperationsService",
+            "many_operations",
+            imports = listOf("$commonModels/many-operations.smithy"),
+        ),
+        CodegenTest(
+            "com.amazonaws.manyoperations#ManyOperationsService",
+            "many_operations_type_erased",
+            imports = listOf("$commonModels/many-operations.smithy"),
+            extraConfig = """, "codegen": { "typeErasedServiceBuilder": true } """,
+        ),
     )
 }
 
@@ -11,6 +11,7 @@ import software.amazon.smithy.aws.traits.protocols.AwsJson1_1Trait
 import software.amazon.smithy.aws.traits.protocols.RestJson1Trait
 import software.amazon.smithy.rust.codegen.core.rustlang.Writable
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.withBlockTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
 import software.amazon.smithy.rust.codegen.core.smithy.generators.http.HttpBindingCustomization
@@ -21,6 +22,7 @@ import software.amazon.smithy.rust.codegen.core.smithy.protocols.ProtocolMap
 import software.amazon.smithy.rust.codegen.core.smithy.protocols.parse.JsonParserCustomization
 import software.amazon.smithy.rust.codegen.core.smithy.protocols.parse.JsonParserSection
 import software.amazon.smithy.rust.codegen.server.python.smithy.PythonServerRuntimeType
+import software.amazon.smithy.rust.codegen.server.smithy.ServerCargoDependency
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCodegenContext
 import software.amazon.smithy.rust.codegen.server.smithy.generators.protocol.ServerProtocolGenerator
 import software.amazon.smithy.rust.codegen.server.smithy.protocols.ServerAwsJsonFactory
@@ -99,7 +101,14 @@ class PythonServerStreamPayloadSerializerCustomization() : ServerHttpBoundProtoc
         when (section) {
             is ServerHttpBoundProtocolSection.WrapStreamPayload ->
                 writable {
-                    section.params.payloadGenerator.generatePayload(this, section.params.shapeName, section.params.shape)
+                    withBlockTemplate(
+                        "#{SmithyHttpServer}::body::Body::wrap_stream(",
+                        ")",
+                        "SmithyHttpServer" to
+                            ServerCargoDependency.smithyHttpServer(section.params.codegenContext.runtimeConfig).toType(),
+                    ) {
+                        section.params.payloadGenerator.generatePayload(this, section.params.shapeName, section.params.shape)
+                    }
                 }
 
             else -> emptySection
@@ -25,6 +25,7 @@ import software.amazon.smithy.model.traits.LengthTrait
 import software.amazon.smithy.model.traits.PatternTrait
 import software.amazon.smithy.model.traits.RangeTrait
 import software.amazon.smithy.model.traits.RequiredTrait
+import software.amazon.smithy.model.traits.SensitiveTrait
 import software.amazon.smithy.model.traits.UniqueItemsTrait
 import software.amazon.smithy.rust.codegen.core.rustlang.RustModule
 import software.amazon.smithy.rust.codegen.core.rustlang.RustReservedWords
@@ -37,6 +38,7 @@ import software.amazon.smithy.rust.codegen.core.smithy.module
 import software.amazon.smithy.rust.codegen.core.util.UNREACHABLE
 import software.amazon.smithy.rust.codegen.core.util.getTrait
 import software.amazon.smithy.rust.codegen.core.util.hasTrait
+import software.amazon.smithy.rust.codegen.core.util.shouldRedact
 import software.amazon.smithy.rust.codegen.core.util.toSnakeCase
 import software.amazon.smithy.rust.codegen.server.smithy.generators.serverBuilderModule
 import software.amazon.smithy.rust.codegen.server.smithy.traits.SyntheticStructureFromConstrainedMemberTrait
@@ -202,6 +204,19 @@ fun Shape.overriddenConstrainedMemberInfo(): Pair<Shape, MemberShape>? {
     return Pair(trait.container, trait.member)
 }
 
+/**
+ * Whether values of this constrained shape must be redacted, e.g. in constraint violation messages. Besides the
+ * shape itself being `@sensitive`, synthetic shapes extracted from a constrained member are redacted if that member
+ * or its container is `@sensitive`, or if the member targeted a `@sensitive` shape.
+ */
+fun Shape.shouldRedactConstrainedValue(model: Model): Boolean {
+    if (shouldRedact(model)) {
+        return true
+    }
+    val (container, member) = overriddenConstrainedMemberInfo() ?: return false
+    return container.hasTrait<SensitiveTrait>() || member.shouldRedact(model)
+}
+
 /**
  * Returns the parent and the inline module that this particular shape should go in.
  */
@@ -12,4 +12,4 @@ fun PatternTrait.validationErrorMessage() =
     "Value at '{}' failed to satisfy constraint: Member must satisfy regular expression pattern: {}"
 
 fun PatternTrait.shapeConstraintViolationDisplayMessage(shape: Shape) =
-    "Value provided for `${shape.id}` failed to satisfy the constraint: Member must match the regular expression pattern: {}"
+    "Value {} provided for `${shape.id}` failed to satisfy the constraint: Member must match the regular expression pattern: {}"
@@ -12,7 +12,7 @@ fun RangeTrait.validationErrorMessage() =
     "Value at '{}' failed to satisfy constraint: Member must be ${this.rangeDescription()}"
 
 fun RangeTrait.shapeConstraintViolationDisplayMessage(shape: Shape) =
-    "Value for `${shape.id}`failed to satisfy constraint: Member must be ${this.rangeDescription()}"
+    "Value {} provided for `${shape.id}` failed to satisfy constraint: Member must be ${this.rangeDescription()}"
 
 fun RangeTrait.rangeDescription() =
     if (this.min.isPresent && this.max.isPresent) {
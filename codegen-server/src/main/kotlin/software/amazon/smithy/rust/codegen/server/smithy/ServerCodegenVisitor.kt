@@ -63,6 +63,7 @@ import software.amazon.smithy.rust.codegen.server.smithy.generators.ConstrainedN
 import software.amazon.smithy.rust.codegen.server.smithy.generators.ConstrainedStringGenerator
 import software.amazon.smithy.rust.codegen.server.smithy.generators.ConstrainedTraitForEnumGenerator
 import software.amazon.smithy.rust.codegen.server.smithy.generators.MapConstraintViolationGenerator
+import software.amazon.smithy.rust.codegen.server.smithy.generators.OpenApiGenerator
 import software.amazon.smithy.rust.codegen.server.smithy.generators.PubCrateConstrainedCollectionGenerator
 import software.amazon.smithy.rust.codegen.server.smithy.generators.PubCrateConstrainedMapGenerator
 import software.amazon.smithy.rust.codegen.server.smithy.generators.ScopeMacroGenerator
@@ -671,6 +672,12 @@ open class ServerCodegenVisitor(
             ScopeMacroGenerator(codegenContext).render(this)
         }
 
+        if (codegenContext.settings.codegenConfig.openApi) {
+            rustCrate.withModule(ServerRustModule.OpenApi) {
+                OpenApiGenerator(codegenContext, serverProtocol).render(this)
+            }
+        }
+
         codegenDecorator.postprocessServiceGenerateAdditionalStructures(shape)
             .forEach { structureShape -> this.structureShape(structureShape) }
     }
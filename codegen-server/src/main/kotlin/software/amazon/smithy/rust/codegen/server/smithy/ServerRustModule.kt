@@ -43,6 +43,8 @@ object ServerRustModule {
     val Types = RustModule.public("types")
     val Service = RustModule.private("service")
     val Server = RustModule.public("server", inline = true)
+    val OpenApi = RustModule.public("openapi")
+    val Meta = RustModule.public("meta")
 
     val UnconstrainedModule =
         software.amazon.smithy.rust.codegen.core.smithy.UnconstrainedModule
@@ -62,6 +64,8 @@ class ServerModuleDocProvider(private val codegenContext: ServerCodegenContext)
             ServerRustModule.Output -> strDoc("Output structures for operations. Documentation on these types is copied from the model.")
             ServerRustModule.Types -> strDoc("Data primitives referenced by other data types.")
             ServerRustModule.Server -> strDoc("Contains the types that are re-exported from the `aws-smithy-http-server` crate.")
+            ServerRustModule.OpenApi -> strDoc("An OpenAPI description of this service, derived from its model.")
+            ServerRustModule.Meta -> strDoc("Information about the Smithy service this crate was generated from.")
             ServerRustModule.UnconstrainedModule -> strDoc("Unconstrained types for constrained shapes.")
             ServerRustModule.ConstrainedModule -> strDoc("Constrained types for constrained shapes.")
             else -> TODO("Document this module: $module")
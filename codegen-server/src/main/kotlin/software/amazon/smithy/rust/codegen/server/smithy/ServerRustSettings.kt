@@ -83,6 +83,12 @@ data class ServerRustSettings(
 /**
  * [publicConstrainedTypes]: Generate constrained wrapper newtypes for constrained shapes
  * [ignoreUnsupportedConstraints]: Generate model even though unsupported constraints are present
+ * [typeErasedServiceBuilder]: Box handlers when they're registered in the service builder, trading two dynamic
+ *  dispatches per request for much less monomorphized code in services with many operations
+ * [rejectEmptyLabels]: Reject requests that bind an empty value to an `httpLabel`, instead of binding `""`
+ * [greedyLabelTrailingSlash]: How a trailing `/` in the value of a greedy `httpLabel` is handled: `preserve` (the
+ *  default), `trim` or `reject`
+ * [openApi]: Generate an `openapi` module, whose `openapi_document()` returns an OpenAPI 3.0 description of the service
  */
 data class ServerCodegenConfig(
     override val formatTimeoutSeconds: Int = DEFAULT_FORMAT_TIMEOUT_SECONDS,
@@ -97,6 +103,10 @@ data class ServerCodegenConfig(
      */
     val experimentalCustomValidationExceptionWithReasonPleaseDoNotUse: String? = defaultExperimentalCustomValidationExceptionWithReasonPleaseDoNotUse,
     val addValidationExceptionToConstrainedOperations: Boolean = DEFAULT_ADD_VALIDATION_EXCEPTION_TO_CONSTRAINED_OPERATIONS,
+    val typeErasedServiceBuilder: Boolean = DEFAULT_TYPE_ERASED_SERVICE_BUILDER,
+    val rejectEmptyLabels: Boolean = DEFAULT_REJECT_EMPTY_LABELS,
+    val greedyLabelTrailingSlash: String = DEFAULT_GREEDY_LABEL_TRAILING_SLASH,
+    val openApi: Boolean = DEFAULT_OPEN_API,
 ) : CoreCodegenConfig(
         formatTimeoutSeconds, debugMode,
     ) {
@@ -105,6 +115,11 @@ data class ServerCodegenConfig(
         private const val DEFAULT_IGNORE_UNSUPPORTED_CONSTRAINTS = false
         private val defaultExperimentalCustomValidationExceptionWithReasonPleaseDoNotUse = null
         private const val DEFAULT_ADD_VALIDATION_EXCEPTION_TO_CONSTRAINED_OPERATIONS = false
+        private const val DEFAULT_TYPE_ERASED_SERVICE_BUILDER = false
+        private const val DEFAULT_REJECT_EMPTY_LABELS = false
+        private const val DEFAULT_GREEDY_LABEL_TRAILING_SLASH = "preserve"
+        private const val DEFAULT_OPEN_API = false
+        private val greedyLabelTrailingSlashValues = listOf("preserve", "trim", "reject")
 
         fun fromCodegenConfigAndNode(
             coreCodegenConfig: CoreCodegenConfig,
@@ -117,6 +132,15 @@ data class ServerCodegenConfig(
                 ignoreUnsupportedConstraints = node.get().getBooleanMemberOrDefault("ignoreUnsupportedConstraints", DEFAULT_IGNORE_UNSUPPORTED_CONSTRAINTS),
                 experimentalCustomValidationExceptionWithReasonPleaseDoNotUse = node.get().getStringMemberOrDefault("experimentalCustomValidationExceptionWithReasonPleaseDoNotUse", defaultExperimentalCustomValidationExceptionWithReasonPleaseDoNotUse),
                 addValidationExceptionToConstrainedOperations = node.get().getBooleanMemberOrDefault("addValidationExceptionToConstrainedOperations", DEFAULT_ADD_VALIDATION_EXCEPTION_TO_CONSTRAINED_OPERATIONS),
+                typeErasedServiceBuilder = node.get().getBooleanMemberOrDefault("typeErasedServiceBuilder", DEFAULT_TYPE_ERASED_SERVICE_BUILDER),
+                rejectEmptyLabels = node.get().getBooleanMemberOrDefault("rejectEmptyLabels", DEFAULT_REJECT_EMPTY_LABELS),
+                greedyLabelTrailingSlash =
+                    node.get().getStringMemberOrDefault("greedyLabelTrailingSlash", DEFAULT_GREEDY_LABEL_TRAILING_SLASH).also {
+                        check(it in greedyLabelTrailingSlashValues) {
+                            "`greedyLabelTrailingSlash` must be one of $greedyLabelTrailingSlashValues, but was `$it`"
+                        }
+                    },
+                openApi = node.get().getBooleanMemberOrDefault("openApi", DEFAULT_OPEN_API),
             )
         } else {
             ServerCodegenConfig(
@@ -103,7 +103,7 @@ class ValidationExceptionWithReasonConversionGenerator(private val codegenContex
                             is Pattern -> {
                                 rustTemplate(
                                     """
-                                    Self::Pattern(_) => crate::model::ValidationExceptionField {
+                                    Self::Pattern(..) => crate::model::ValidationExceptionField {
                                         message: #{MessageWritable:W},
                                         name: path,
                                         reason: crate::model::ValidationExceptionFieldReason::PatternNotValid,
@@ -11,6 +11,7 @@ import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
 import software.amazon.smithy.rust.codegen.core.smithy.customizations.AllowLintsCustomization
 import software.amazon.smithy.rust.codegen.core.smithy.customizations.CrateVersionCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.customizations.ServiceMetadataCustomization
 import software.amazon.smithy.rust.codegen.core.smithy.customizations.pubUseSmithyPrimitives
 import software.amazon.smithy.rust.codegen.core.smithy.generators.LibRsCustomization
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCodegenContext
@@ -78,5 +79,7 @@ class ServerRequiredCustomizations : ServerCodegenDecorator {
         rustCrate.withModule(ServerRustModule.root) {
             CrateVersionCustomization.extras(rustCrate, ServerRustModule.root)
         }
+
+        ServiceMetadataCustomization.extras(codegenContext, rustCrate, ServerRustModule.Meta)
     }
 }
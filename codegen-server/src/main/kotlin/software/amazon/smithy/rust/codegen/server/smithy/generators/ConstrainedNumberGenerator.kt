@@ -25,13 +25,14 @@ import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.expectRustMetadata
 import software.amazon.smithy.rust.codegen.core.smithy.makeMaybeConstrained
+import software.amazon.smithy.rust.codegen.core.util.REDACTION
 import software.amazon.smithy.rust.codegen.core.util.UNREACHABLE
 import software.amazon.smithy.rust.codegen.core.util.expectTrait
-import software.amazon.smithy.rust.codegen.core.util.redactIfNecessary
 import software.amazon.smithy.rust.codegen.server.smithy.InlineModuleCreator
 import software.amazon.smithy.rust.codegen.server.smithy.PubCrateConstraintViolationSymbolProvider
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCodegenContext
 import software.amazon.smithy.rust.codegen.server.smithy.shapeConstraintViolationDisplayMessage
+import software.amazon.smithy.rust.codegen.server.smithy.shouldRedactConstrainedValue
 import software.amazon.smithy.rust.codegen.server.smithy.traits.isReachableFromOperationInput
 import software.amazon.smithy.rust.codegen.server.smithy.validationErrorMessage
 
@@ -50,6 +51,7 @@ class ConstrainedNumberGenerator(
     val model = codegenContext.model
     val constrainedShapeSymbolProvider = codegenContext.constrainedShapeSymbolProvider
     val publicConstrainedTypes = codegenContext.settings.codegenConfig.publicConstrainedTypes
+    private val isSensitive = shape.shouldRedactConstrainedValue(model)
 
     private val unconstrainedType =
         when (shape) {
@@ -114,7 +116,7 @@ class ConstrainedNumberGenerator(
 
             impl #{Display} for $name {
                 fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                   ${shape.redactIfNecessary(model, "self.0")}.fmt(f)
+                   ${if (isSensitive) REDACTION else "self.0"}.fmt(f)
                 }
             }
 
@@ -145,7 +147,8 @@ class ConstrainedNumberGenerator(
 
                 impl #{Display} for ${constraintViolation.name} {
                     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                        write!(f, "${rangeInfo.rangeTrait.shapeConstraintViolationDisplayMessage(shape).replace("#", "##")}")
+                        let Self::Range(${if (isSensitive) "_" else "value"}) = self;
+                        write!(f, "${rangeInfo.rangeTrait.shapeConstraintViolationDisplayMessage(shape).replace("#", "##")}", ${if (isSensitive) REDACTION else "value"})
                     }
                 }
                     
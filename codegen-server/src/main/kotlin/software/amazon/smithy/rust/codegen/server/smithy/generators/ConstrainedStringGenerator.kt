@@ -10,7 +10,6 @@ import software.amazon.smithy.model.shapes.Shape
 import software.amazon.smithy.model.shapes.StringShape
 import software.amazon.smithy.model.traits.LengthTrait
 import software.amazon.smithy.model.traits.PatternTrait
-import software.amazon.smithy.model.traits.SensitiveTrait
 import software.amazon.smithy.model.traits.Trait
 import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
 import software.amazon.smithy.rust.codegen.core.rustlang.RustType
@@ -30,14 +29,14 @@ import software.amazon.smithy.rust.codegen.core.smithy.makeMaybeConstrained
 import software.amazon.smithy.rust.codegen.core.smithy.testModuleForShape
 import software.amazon.smithy.rust.codegen.core.testutil.unitTest
 import software.amazon.smithy.rust.codegen.core.util.PANIC
-import software.amazon.smithy.rust.codegen.core.util.hasTrait
+import software.amazon.smithy.rust.codegen.core.util.REDACTION
 import software.amazon.smithy.rust.codegen.core.util.orNull
-import software.amazon.smithy.rust.codegen.core.util.redactIfNecessary
 import software.amazon.smithy.rust.codegen.server.smithy.InlineModuleCreator
 import software.amazon.smithy.rust.codegen.server.smithy.PubCrateConstraintViolationSymbolProvider
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCargoDependency
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCodegenContext
 import software.amazon.smithy.rust.codegen.server.smithy.shapeConstraintViolationDisplayMessage
+import software.amazon.smithy.rust.codegen.server.smithy.shouldRedactConstrainedValue
 import software.amazon.smithy.rust.codegen.server.smithy.supportedStringConstraintTraits
 import software.amazon.smithy.rust.codegen.server.smithy.traits.isReachableFromOperationInput
 import software.amazon.smithy.rust.codegen.server.smithy.validationErrorMessage
@@ -69,7 +68,7 @@ class ConstrainedStringGenerator(
     private val stringConstraintsInfo: List<StringTraitInfo> =
         supportedStringConstraintTraits
             .mapNotNull { shape.getTrait(it).orNull() }
-            .map { StringTraitInfo.fromTrait(symbol, it, isSensitive = shape.hasTrait<SensitiveTrait>()) }
+            .map { StringTraitInfo.fromTrait(symbol, it, isSensitive = shape.shouldRedactConstrainedValue(model)) }
     private val constraintsInfo: List<TraitInfo> =
         stringConstraintsInfo
             .map(StringTraitInfo::toTraitInfo)
@@ -123,7 +122,7 @@ class ConstrainedStringGenerator(
 
             impl #{Display} for $name {
                 fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                   ${shape.redactIfNecessary(model, "self.0")}.fmt(f)
+                   ${if (shape.shouldRedactConstrainedValue(model)) REDACTION else "self.0"}.fmt(f)
                 }
             }
 
@@ -132,7 +131,22 @@ class ConstrainedStringGenerator(
                     value.into_inner()
                 }
             }
+
+            impl #{AsRef}<str> for $name {
+                fn as_ref(&self) -> &str {
+                    &self.0
+                }
+            }
+
+            impl std::ops::Deref for $name {
+                type Target = str;
+
+                fn deref(&self) -> &str {
+                    &self.0
+                }
+            }
             """,
+            "AsRef" to RuntimeType.AsRef,
             "ConstrainedTrait" to RuntimeType.ConstrainedTrait,
             "ConstraintViolation" to constraintViolation,
             "MaybeConstrained" to symbol.makeMaybeConstrained(),
@@ -273,20 +287,26 @@ data class Length(val lengthTrait: LengthTrait) : StringTraitInfo() {
         }
 }
 
+/**
+ * Pattern violations carry the whole value and the position, in characters, where it stops matching the pattern. The
+ * `regex` crate only tells whether a value matches, so the position is found by a lazy DFA of the pattern, which is only
+ * compiled once a value has failed to match.
+ */
 data class Pattern(val symbol: Symbol, val patternTrait: PatternTrait, val isSensitive: Boolean) : StringTraitInfo() {
     override fun toTraitInfo(): TraitInfo {
         return TraitInfo(
             tryFromCheck = { rust("let value = Self::check_pattern(value)?;") },
             constraintViolationVariant = {
                 docs("Error when a string doesn't satisfy its `@pattern`.")
-                docs("Contains the String that failed the pattern.")
-                rust("Pattern(String)")
+                docs("Contains the String that failed the pattern, and the position, in characters, of its first")
+                docs("character that no match of the pattern can continue with.")
+                rust("Pattern(String, usize)")
             },
             asValidationExceptionField = {
                 Attribute.AllowUnusedVariables.render(this)
                 rustTemplate(
                     """
-                    Self::Pattern(_) => crate::model::ValidationExceptionField {
+                    Self::Pattern(..) => crate::model::ValidationExceptionField {
                         message: #{ErrorMessage:W},
                         path
                     },
@@ -341,7 +361,9 @@ data class Pattern(val symbol: Symbol, val patternTrait: PatternTrait, val isSen
                     if regex.is_match(&string) {
                         Ok(string)
                     } else {
-                        Err($constraintViolation::Pattern(string))
+                        static MATCHER: #{OnceCell}::sync::Lazy<#{PatternMatcher}> = #{OnceCell}::sync::Lazy::new(|| #{PatternMatcher}::new(r##"$pattern"##));
+                        let position = MATCHER.mismatch_position(&string);
+                        Err($constraintViolation::Pattern(string, position))
                     }
                 }
 
@@ -355,6 +377,7 @@ data class Pattern(val symbol: Symbol, val patternTrait: PatternTrait, val isSen
                 """,
                 "Regex" to ServerCargoDependency.Regex.toType(),
                 "OnceCell" to ServerCargoDependency.OnceCell.toType(),
+                "PatternMatcher" to RuntimeType.ConstrainedPatternMatcher,
             )
         }
     }
@@ -363,13 +386,24 @@ data class Pattern(val symbol: Symbol, val patternTrait: PatternTrait, val isSen
         writable {
             val errorMessage = patternTrait.shapeConstraintViolationDisplayMessage(shape).replace("#", "##")
             val pattern = patternTrait.pattern.toString().replace("#", "##")
-            rustTemplate(
-                """
-                Self::Pattern(_) => {
-                    format!(r##"$errorMessage"##, r##"$pattern"##)
-                },
-                """,
-            )
+            if (isSensitive) {
+                rust(
+                    """
+                    Self::Pattern(..) => {
+                        format!(r##"$errorMessage"##, $REDACTION, r##"$pattern"##)
+                    },
+                    """,
+                )
+            } else {
+                rustTemplate(
+                    """
+                    Self::Pattern(value, position) => {
+                        format!(r##"$errorMessage (first non-matching character at position {})"##, #{value_summary}(value), r##"$pattern"##, position)
+                    },
+                    """,
+                    "value_summary" to RuntimeType.ConstrainedValueSummary,
+                )
+            }
         }
 }
 
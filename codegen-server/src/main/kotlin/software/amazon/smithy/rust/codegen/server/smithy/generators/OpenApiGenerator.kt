@@ -0,0 +1,452 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.server.smithy.generators
+
+import software.amazon.smithy.aws.traits.protocols.AwsJson1_0Trait
+import software.amazon.smithy.aws.traits.protocols.AwsJson1_1Trait
+import software.amazon.smithy.aws.traits.protocols.RestJson1Trait
+import software.amazon.smithy.aws.traits.protocols.RestXmlTrait
+import software.amazon.smithy.model.knowledge.TopDownIndex
+import software.amazon.smithy.model.node.ArrayNode
+import software.amazon.smithy.model.node.Node
+import software.amazon.smithy.model.node.ObjectNode
+import software.amazon.smithy.model.shapes.BigDecimalShape
+import software.amazon.smithy.model.shapes.BigIntegerShape
+import software.amazon.smithy.model.shapes.BlobShape
+import software.amazon.smithy.model.shapes.BooleanShape
+import software.amazon.smithy.model.shapes.ByteShape
+import software.amazon.smithy.model.shapes.CollectionShape
+import software.amazon.smithy.model.shapes.DocumentShape
+import software.amazon.smithy.model.shapes.DoubleShape
+import software.amazon.smithy.model.shapes.EnumShape
+import software.amazon.smithy.model.shapes.FloatShape
+import software.amazon.smithy.model.shapes.IntEnumShape
+import software.amazon.smithy.model.shapes.IntegerShape
+import software.amazon.smithy.model.shapes.LongShape
+import software.amazon.smithy.model.shapes.MapShape
+import software.amazon.smithy.model.shapes.MemberShape
+import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.model.shapes.Shape
+import software.amazon.smithy.model.shapes.ShapeId
+import software.amazon.smithy.model.shapes.ShortShape
+import software.amazon.smithy.model.shapes.StringShape
+import software.amazon.smithy.model.shapes.StructureShape
+import software.amazon.smithy.model.shapes.TimestampShape
+import software.amazon.smithy.model.shapes.UnionShape
+import software.amazon.smithy.model.traits.DefaultTrait
+import software.amazon.smithy.model.traits.DocumentationTrait
+import software.amazon.smithy.model.traits.ErrorTrait
+import software.amazon.smithy.model.traits.HttpErrorTrait
+import software.amazon.smithy.model.traits.JsonNameTrait
+import software.amazon.smithy.model.traits.LengthTrait
+import software.amazon.smithy.model.traits.PatternTrait
+import software.amazon.smithy.model.traits.RangeTrait
+import software.amazon.smithy.model.traits.RequiredTrait
+import software.amazon.smithy.model.traits.TimestampFormatTrait
+import software.amazon.smithy.model.traits.TitleTrait
+import software.amazon.smithy.model.traits.UniqueItemsTrait
+import software.amazon.smithy.model.traits.XmlNameTrait
+import software.amazon.smithy.protocol.traits.Rpcv2CborTrait
+import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
+import software.amazon.smithy.rust.codegen.core.rustlang.docs
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustBlock
+import software.amazon.smithy.rust.codegen.core.smithy.protocols.HttpBindingDescriptor
+import software.amazon.smithy.rust.codegen.core.smithy.protocols.HttpLocation
+import software.amazon.smithy.rust.codegen.core.smithy.transformers.operationErrors
+import software.amazon.smithy.rust.codegen.core.util.getTrait
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
+import software.amazon.smithy.rust.codegen.core.util.isEventStream
+import software.amazon.smithy.rust.codegen.core.util.isStreaming
+import software.amazon.smithy.rust.codegen.server.smithy.ServerCodegenContext
+import software.amazon.smithy.rust.codegen.server.smithy.generators.protocol.ServerProtocol
+
+/**
+ * Renders `openapi_document()`, which returns an [OpenAPI 3.0](https://spec.openapis.org/oas/v3.0.3) description of
+ * the service.
+ *
+ * The document is derived from the same HTTP bindings that the router and the (de)serializers are generated from, so
+ * it can't drift from the service's behavior: paths and methods come from the protocol's [HttpTrait]s, parameters and
+ * bodies from its HTTP bindings, and constraint traits are mapped to the equivalent JSON Schema keywords. OpenAPI has
+ * no way of describing streaming bodies, so they're described with the `x-smithy-streaming` and
+ * `x-smithy-event-stream` extensions.
+ */
+class OpenApiGenerator(
+    private val codegenContext: ServerCodegenContext,
+    private val protocol: ServerProtocol,
+) {
+    private val model = codegenContext.model
+    private val serviceShape = codegenContext.serviceShape
+    private val httpBindingResolver = protocol.httpBindingResolver
+    private val isAwsJson = codegenContext.protocol == AwsJson1_0Trait.ID || codegenContext.protocol == AwsJson1_1Trait.ID
+    private val operations =
+        TopDownIndex.of(model).getContainedOperations(serviceShape).sortedBy { it.id }
+
+    // Component schemas, keyed by name. `null` marks a schema that is being built, so that recursive shapes terminate.
+    private val schemas = mutableMapOf<String, Node?>()
+    private val schemaNames = mutableMapOf<ShapeId, String>()
+
+    fun render(writer: RustWriter) {
+        val document = Node.prettyPrintJson(document())
+        writer.docs(
+            """
+            Returns an [OpenAPI 3.0](https://spec.openapis.org/oas/v3.0.3) document that describes this service, as JSON.
+
+            Streaming blobs are marked with `"x-smithy-streaming": true`, and event streams with an
+            `x-smithy-event-stream` object that references the schema of the events.
+            """,
+        )
+        writer.rustBlock("pub fn openapi_document() -> &'static str") {
+            rust("r####\"\$L\"####", document)
+        }
+    }
+
+    fun document(): ObjectNode {
+        val paths = mutableMapOf<String, ObjectNode.Builder>()
+        val routes = mutableSetOf<Pair<String, String>>()
+        for (operation in operations) {
+            val httpTrait = httpBindingResolver.httpTrait(operation)
+            val method = httpTrait.method.lowercase()
+            var path =
+                httpTrait.uri.segments.joinToString("/", prefix = "/") { segment ->
+                    if (segment.isLabel) "{${segment.content}}" else segment.content
+                }
+            if (isAwsJson) {
+                // Every operation is a `POST /`, routed by its `X-Amz-Target` header
+                path = "$path#${awsJsonTarget(operation)}"
+            } else if (path to method in routes) {
+                // Operations that are only told apart by a query string literal
+                path = httpTrait.uri.toString()
+            }
+            routes.add(path to method)
+            paths.getOrPut(path) { ObjectNode.builder() }.withMember(method, operation(operation))
+        }
+
+        val info =
+            ObjectNode.builder()
+                .withMember("title", serviceShape.getTrait<TitleTrait>()?.value ?: serviceShape.id.name)
+                .withMember("version", serviceShape.version.ifEmpty { "1" })
+        serviceShape.getTrait<DocumentationTrait>()?.let { info.withMember("description", it.value) }
+
+        val builtPaths = ObjectNode.builder()
+        paths.forEach { (path, item) -> builtPaths.withMember(path, item.build()) }
+        val components = ObjectNode.builder()
+        schemas.toSortedMap().forEach { (name, schema) -> components.withMember(name, schema!!) }
+        return ObjectNode.builder()
+            .withMember("openapi", "3.0.3")
+            .withMember("info", info.build())
+            .withMember("paths", builtPaths.build())
+            .withMember("components", ObjectNode.builder().withMember("schemas", components.build()).build())
+            .build()
+    }
+
+    private fun awsJsonTarget(operation: OperationShape) = "${serviceShape.id.name}.${operation.id.name}"
+
+    private fun operation(operation: OperationShape): ObjectNode {
+        val builder = ObjectNode.builder().withMember("operationId", operation.id.name)
+        operation.getTrait<DocumentationTrait>()?.let { builder.withMember("description", it.value) }
+
+        val httpTrait = httpBindingResolver.httpTrait(operation)
+        val bindings = httpBindingResolver.requestBindings(operation)
+        val parameters = mutableListOf<Node>()
+        httpTrait.uri.queryLiterals.forEach { (name, value) ->
+            parameters.add(parameter(name, "query", true, enumSchema(listOf(value))))
+        }
+        if (isAwsJson) {
+            parameters.add(parameter("X-Amz-Target", "header", true, enumSchema(listOf(awsJsonTarget(operation)))))
+        }
+        for (binding in bindings) {
+            val location =
+                when (binding.location) {
+                    HttpLocation.LABEL -> "path"
+                    HttpLocation.QUERY, HttpLocation.QUERY_PARAMS -> "query"
+                    HttpLocation.HEADER, HttpLocation.PREFIX_HEADERS -> "header"
+                    else -> continue
+                }
+            val schema = memberSchema(binding.member, binding.location)
+            when (binding.location) {
+                // The names of these parameters aren't known up front, so they're described by an extension
+                HttpLocation.QUERY_PARAMS -> builder.withMember("x-smithy-query-params", schema)
+                HttpLocation.PREFIX_HEADERS ->
+                    builder.withMember(
+                        "x-smithy-prefix-headers",
+                        ObjectNode.builder().withMember("prefix", binding.locationName).withMember("schema", schema).build(),
+                    )
+                else ->
+                    parameters.add(
+                        parameter(
+                            binding.locationName,
+                            location,
+                            binding.location == HttpLocation.LABEL || binding.member.hasTrait<RequiredTrait>(),
+                            schema,
+                        ),
+                    )
+            }
+        }
+        if (parameters.isNotEmpty()) {
+            builder.withMember("parameters", ArrayNode.fromNodes(parameters))
+        }
+        content(bindings, httpBindingResolver.requestContentType(operation))?.let { content ->
+            val required =
+                bindings.any {
+                    (it.location == HttpLocation.DOCUMENT || it.location == HttpLocation.PAYLOAD) && it.member.hasTrait<RequiredTrait>()
+                }
+            builder.withMember(
+                "requestBody",
+                ObjectNode.builder().withMember("required", Node.from(required)).withMember("content", content).build(),
+            )
+        }
+
+        val responses = ObjectNode.builder()
+        responses.withMember(
+            httpTrait.code.toString(),
+            response(
+                "${operation.id.name} succeeded",
+                httpBindingResolver.responseBindings(operation),
+                httpBindingResolver.responseContentType(operation),
+            ),
+        )
+        // Errors are serialized with the status code of their `@httpError`, or the default for `@error`
+        operation.operationErrors(model)
+            .map { it as StructureShape }
+            .groupBy {
+                it.getTrait<HttpErrorTrait>()?.code ?: it.expectTrait(ErrorTrait::class.java).defaultHttpStatusCode
+            }
+            .toSortedMap()
+            .forEach { (code, errors) -> responses.withMember(code.toString(), errorResponse(errors)) }
+        return builder.withMember("responses", responses.build()).build()
+    }
+
+    private fun parameter(
+        name: String,
+        location: String,
+        required: Boolean,
+        schema: Node,
+    ) = ObjectNode.builder()
+        .withMember("name", name)
+        .withMember("in", location)
+        .withMember("required", Node.from(required))
+        .withMember("schema", schema)
+        .build()
+
+    private fun response(
+        description: String,
+        bindings: List<HttpBindingDescriptor>,
+        contentType: String?,
+    ): ObjectNode {
+        val builder = ObjectNode.builder().withMember("description", description)
+        val headers = ObjectNode.builder()
+        bindings.filter { it.location == HttpLocation.HEADER }.forEach {
+            headers.withMember(
+                it.locationName,
+                ObjectNode.builder().withMember("required", Node.from(it.member.hasTrait<RequiredTrait>()))
+                    .withMember("schema", memberSchema(it.member, it.location)).build(),
+            )
+        }
+        headers.build().takeUnless { it.isEmpty }?.let { builder.withMember("headers", it) }
+        content(bindings, contentType)?.let { builder.withMember("content", it) }
+        return builder.build()
+    }
+
+    private fun errorResponse(errors: List<StructureShape>): ObjectNode {
+        val schemas = errors.map { ref(it) }
+        val schema = schemas.singleOrNull() ?: ObjectNode.builder().withMember("oneOf", ArrayNode.fromNodes(schemas)).build()
+        val contentType =
+            when (codegenContext.protocol) {
+                RestXmlTrait.ID -> "application/xml"
+                AwsJson1_0Trait.ID -> "application/x-amz-json-1.0"
+                AwsJson1_1Trait.ID -> "application/x-amz-json-1.1"
+                Rpcv2CborTrait.ID -> "application/cbor"
+                else -> "application/json"
+            }
+        return ObjectNode.builder()
+            .withMember("description", errors.joinToString(" or ") { it.id.name })
+            .withMember(
+                "content",
+                ObjectNode.builder().withMember(contentType, ObjectNode.builder().withMember("schema", schema).build()).build(),
+            )
+            .build()
+    }
+
+    /** Returns the content of a request or response body, or `null` if it doesn't have one. */
+    private fun content(
+        bindings: List<HttpBindingDescriptor>,
+        contentType: String?,
+    ): ObjectNode? {
+        val payload = bindings.firstOrNull { it.location == HttpLocation.PAYLOAD }
+        val document = bindings.filter { it.location == HttpLocation.DOCUMENT }
+        val mediaType = ObjectNode.builder()
+        when {
+            payload != null -> {
+                val target = model.expectShape(payload.member.target)
+                if (payload.member.isEventStream(model)) {
+                    mediaType.withMember("schema", binarySchema())
+                    mediaType.withMember(
+                        "x-smithy-event-stream",
+                        ObjectNode.builder().withMember("events", ref(target)).build(),
+                    )
+                } else if (payload.member.isStreaming(model)) {
+                    mediaType.withMember("schema", binarySchema())
+                    mediaType.withMember("x-smithy-streaming", Node.from(true))
+                } else if (target is BlobShape) {
+                    mediaType.withMember("schema", binarySchema())
+                } else {
+                    mediaType.withMember("schema", memberSchema(payload.member, payload.location))
+                }
+            }
+            document.isNotEmpty() -> mediaType.withMember("schema", objectSchema(document.map { it.member }))
+            else -> return null
+        }
+        return ObjectNode.builder().withMember(contentType ?: "application/octet-stream", mediaType.build()).build()
+    }
+
+    private fun binarySchema() = ObjectNode.builder().withMember("type", "string").withMember("format", "binary").build()
+
+    private fun enumSchema(values: List<String>) =
+        ObjectNode.builder().withMember("type", "string")
+            .withMember("enum", ArrayNode.fromStrings(values)).build()
+
+    private fun ref(shape: Shape): ObjectNode {
+        val name =
+            schemaNames.getOrPut(shape.id) {
+                val name = shape.id.name
+                if (schemaNames.values.contains(name)) "${shape.id.namespace.replace('.', '_')}_$name" else name
+            }
+        if (!schemas.containsKey(name)) {
+            schemas[name] = null
+            schemas[name] = componentSchema(shape)
+        }
+        return ObjectNode.builder().withMember("\$ref", "#/components/schemas/$name").build()
+    }
+
+    private fun componentSchema(shape: Shape): ObjectNode {
+        val builder =
+            when (shape) {
+                is StructureShape -> objectSchema(shape.members()).toBuilder()
+                is UnionShape ->
+                    ObjectNode.builder().withMember(
+                        "oneOf",
+                        ArrayNode.fromNodes(
+                            // Exactly one member of a union is set
+                            shape.members().map {
+                                objectSchema(listOf(it)).toBuilder()
+                                    .withMember("required", ArrayNode.fromStrings(wireName(it)))
+                                    .build()
+                            },
+                        ),
+                    )
+                else -> simpleSchema(shape, HttpLocation.DOCUMENT).toBuilder()
+            }
+        shape.getTrait<DocumentationTrait>()?.let { builder.withMember("description", it.value) }
+        return builder.build()
+    }
+
+    private fun objectSchema(members: Collection<MemberShape>): ObjectNode {
+        val properties = ObjectNode.builder()
+        members.forEach { properties.withMember(wireName(it), memberSchema(it, HttpLocation.DOCUMENT)) }
+        val builder = ObjectNode.builder().withMember("type", "object").withMember("properties", properties.build())
+        members.filter { it.hasTrait<RequiredTrait>() }.map { wireName(it) }.takeIf { it.isNotEmpty() }?.let {
+            builder.withMember("required", ArrayNode.fromStrings(it))
+        }
+        return builder.build()
+    }
+
+    private fun wireName(member: MemberShape): String =
+        when (codegenContext.protocol) {
+            RestJson1Trait.ID -> member.getTrait<JsonNameTrait>()?.value
+            RestXmlTrait.ID -> member.getTrait<XmlNameTrait>()?.value
+            else -> null
+        } ?: member.memberName
+
+    private fun memberSchema(
+        member: MemberShape,
+        location: HttpLocation,
+    ): ObjectNode {
+        val target = model.expectShape(member.target)
+        val builder =
+            when (target) {
+                is StructureShape, is UnionShape, is EnumShape, is IntEnumShape -> return ref(target)
+                is TimestampShape -> timestampSchema(httpBindingResolver.timestampFormat(member, location, defaultTimestampFormat(location), model))
+                else -> simpleSchema(target, location).toBuilder()
+            }
+        // Constraints on the member take precedence over constraints on its target
+        constraints(member, builder)
+        member.getTrait<DocumentationTrait>()?.let { builder.withMember("description", it.value) }
+        member.getTrait<DefaultTrait>()?.toNode()?.takeUnless { it.isNullNode }?.let { builder.withMember("default", it) }
+        return builder.build()
+    }
+
+    private fun defaultTimestampFormat(location: HttpLocation) =
+        when (location) {
+            HttpLocation.HEADER, HttpLocation.PREFIX_HEADERS -> TimestampFormatTrait.Format.HTTP_DATE
+            HttpLocation.LABEL, HttpLocation.QUERY, HttpLocation.QUERY_PARAMS -> TimestampFormatTrait.Format.DATE_TIME
+            else -> protocol.defaultTimestampFormat
+        }
+
+    private fun timestampSchema(format: TimestampFormatTrait.Format): ObjectNode.Builder =
+        when (format) {
+            TimestampFormatTrait.Format.EPOCH_SECONDS -> ObjectNode.builder().withMember("type", "number")
+            TimestampFormatTrait.Format.DATE_TIME -> ObjectNode.builder().withMember("type", "string").withMember("format", "date-time")
+            else -> ObjectNode.builder().withMember("type", "string")
+        }
+
+    private fun simpleSchema(
+        shape: Shape,
+        location: HttpLocation,
+    ): ObjectNode {
+        val builder = ObjectNode.builder()
+        when (shape) {
+            is BooleanShape -> builder.withMember("type", "boolean")
+            is LongShape -> builder.withMember("type", "integer").withMember("format", "int64")
+            is BigIntegerShape -> builder.withMember("type", "integer")
+            is FloatShape -> builder.withMember("type", "number").withMember("format", "float")
+            is DoubleShape -> builder.withMember("type", "number").withMember("format", "double")
+            is BigDecimalShape -> builder.withMember("type", "number")
+            is IntEnumShape ->
+                builder.withMember("type", "integer")
+                    .withMember("enum", ArrayNode.fromNodes(shape.enumValues.values.map { Node.from(it) }))
+            is ByteShape, is ShortShape, is IntegerShape -> builder.withMember("type", "integer").withMember("format", "int32")
+            is EnumShape -> builder.withMember("type", "string").withMember("enum", ArrayNode.fromStrings(shape.enumValues.values))
+            is StringShape -> builder.withMember("type", "string")
+            is BlobShape -> builder.withMember("type", "string").withMember("format", "byte")
+            is TimestampShape -> return timestampSchema(defaultTimestampFormat(location)).build()
+            is DocumentShape -> {}
+            is CollectionShape -> {
+                builder.withMember("type", "array").withMember("items", memberSchema(shape.member, location))
+                if (shape.hasTrait<UniqueItemsTrait>()) {
+                    builder.withMember("uniqueItems", Node.from(true))
+                }
+            }
+            is MapShape -> builder.withMember("type", "object").withMember("additionalProperties", memberSchema(shape.value, location))
+            else -> {}
+        }
+        constraints(shape, builder)
+        return builder.build()
+    }
+
+    /** Maps the constraint traits of [shape] to the equivalent JSON Schema keywords. */
+    private fun constraints(
+        shape: Shape,
+        builder: ObjectNode.Builder,
+    ) {
+        val target = if (shape is MemberShape) model.expectShape(shape.target) else shape
+        shape.getTrait<LengthTrait>()?.let { length ->
+            val (min, max) =
+                when (target) {
+                    is CollectionShape -> "minItems" to "maxItems"
+                    is MapShape -> "minProperties" to "maxProperties"
+                    else -> "minLength" to "maxLength"
+                }
+            length.min.ifPresent { builder.withMember(min, Node.from(it)) }
+            length.max.ifPresent { builder.withMember(max, Node.from(it)) }
+        }
+        shape.getTrait<RangeTrait>()?.let { range ->
+            range.min.ifPresent { builder.withMember("minimum", Node.from(it)) }
+            range.max.ifPresent { builder.withMember("maximum", Node.from(it)) }
+        }
+        shape.getTrait<PatternTrait>()?.let { builder.withMember("pattern", it.value) }
+    }
+}
@@ -31,7 +31,12 @@ import software.amazon.smithy.rust.codegen.core.rustlang.withBlock
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
 import software.amazon.smithy.rust.codegen.core.smithy.expectRustMetadata
+import software.amazon.smithy.rust.codegen.core.smithy.generators.fromIterSetterName
+import software.amazon.smithy.rust.codegen.core.smithy.generators.fromStrSetterName
 import software.amazon.smithy.rust.codegen.core.smithy.generators.lifetimeDeclaration
+import software.amazon.smithy.rust.codegen.core.smithy.generators.renderDebugField
+import software.amazon.smithy.rust.codegen.core.smithy.generators.setterName
+import software.amazon.smithy.rust.codegen.core.smithy.generators.targetsEnum
 import software.amazon.smithy.rust.codegen.core.smithy.isOptional
 import software.amazon.smithy.rust.codegen.core.smithy.isRustBoxed
 import software.amazon.smithy.rust.codegen.core.smithy.makeMaybeConstrained
@@ -41,10 +46,9 @@ import software.amazon.smithy.rust.codegen.core.smithy.mapRustType
 import software.amazon.smithy.rust.codegen.core.smithy.module
 import software.amazon.smithy.rust.codegen.core.smithy.rustType
 import software.amazon.smithy.rust.codegen.core.smithy.traits.SyntheticInputTrait
-import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.hasTrait
 import software.amazon.smithy.rust.codegen.core.util.letIf
-import software.amazon.smithy.rust.codegen.core.util.redactIfNecessary
+import software.amazon.smithy.rust.codegen.core.util.shouldRedact
 import software.amazon.smithy.rust.codegen.core.util.toSnakeCase
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCodegenContext
 import software.amazon.smithy.rust.codegen.server.smithy.canReachConstrainedShape
@@ -215,6 +219,19 @@ class ServerBuilderGenerator(
             for (member in members) {
                 if (publicConstrainedTypes) {
                     renderBuilderMemberFn(this, member)
+                    if (members.none { it.setterName() == member.fromIterSetterName() }) {
+                        renderBuilderMemberFromIterSetterFn(
+                            this,
+                            member,
+                            symbolProvider.toSymbol(member),
+                            symbolProvider.toMemberName(member),
+                        )
+                    }
+                    if (takeInUnconstrainedTypes && member.targetsEnum(model) &&
+                        members.none { it.setterName() == member.fromStrSetterName() }
+                    ) {
+                        renderBuilderMemberFromStrSetterFn(this, member)
+                    }
                 }
 
                 if (takeInUnconstrainedTypes) {
@@ -363,6 +380,30 @@ class ServerBuilderGenerator(
         }
     }
 
+    /**
+     * Render a `set_foo_from_str` method for an enum member `foo`. Like the deserializers do, it stores the string as is
+     * and leaves checking that it's a known variant to `build()`.
+     *
+     * This method is only generated when the builder takes in unconstrained types, since otherwise there's nowhere to
+     * store a value that isn't a known variant.
+     */
+    private fun renderBuilderMemberFromStrSetterFn(
+        writer: RustWriter,
+        member: MemberShape,
+    ) {
+        val symbol = symbolProvider.toSymbol(member)
+        val memberName = symbolProvider.toMemberName(member)
+        val unconstrainedVariant = "${symbol.makeMaybeConstrained().rustType().namespace}::MaybeConstrained::Unconstrained"
+
+        writer.docs(
+            "Sets [`$memberName`](Self::$memberName) from its raw string value. `build()` fails if it isn't a known variant.",
+        )
+        writer.deprecatedShape(member)
+        writer.rustBlock("pub fn ${member.fromStrSetterName()}(mut self, input: Option<&str>) -> Self") {
+            rust("self.$memberName = input.map(|v| $unconstrainedVariant(v.to_owned())); self")
+        }
+    }
+
     /**
      * Returns whether the constrained builder member type (the type on which the `Constrained` trait is implemented)
      * is the final type the user sees when receiving the built struct. This is true when the corresponding constrained
@@ -459,10 +500,11 @@ class ServerBuilderGenerator(
                 rust("""let mut formatter = f.debug_struct("Builder");""")
                 members.forEach { member ->
                     val memberName = symbolProvider.toMemberName(member)
-                    val fieldValue = member.redactIfNecessary(model, "self.$memberName")
-
-                    rust(
-                        "formatter.field(${memberName.dq()}, &$fieldValue);",
+                    renderDebugField(
+                        runtimeConfig,
+                        memberName,
+                        builderMemberSymbol(member).rustType(),
+                        redact = member.shouldRedact(model),
                     )
                 }
                 rust("formatter.finish()")
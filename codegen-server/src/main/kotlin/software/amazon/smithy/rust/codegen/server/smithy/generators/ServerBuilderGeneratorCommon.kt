@@ -31,17 +31,25 @@ import software.amazon.smithy.model.shapes.ShortShape
 import software.amazon.smithy.model.shapes.StringShape
 import software.amazon.smithy.model.shapes.TimestampShape
 import software.amazon.smithy.model.traits.DefaultTrait
+import software.amazon.smithy.rust.codegen.core.rustlang.RustType
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
+import software.amazon.smithy.rust.codegen.core.rustlang.deprecatedShape
+import software.amazon.smithy.rust.codegen.core.rustlang.docs
 import software.amazon.smithy.rust.codegen.core.rustlang.map
 import software.amazon.smithy.rust.codegen.core.rustlang.qualifiedName
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustBlock
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.stripOuter
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
 import software.amazon.smithy.rust.codegen.core.smithy.RustSymbolProvider
 import software.amazon.smithy.rust.codegen.core.smithy.generators.PrimitiveInstantiator
+import software.amazon.smithy.rust.codegen.core.smithy.generators.fromIterSetterArgument
+import software.amazon.smithy.rust.codegen.core.smithy.generators.fromIterSetterName
+import software.amazon.smithy.rust.codegen.core.smithy.isOptional
 import software.amazon.smithy.rust.codegen.core.smithy.rustType
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.expectTrait
@@ -71,6 +79,28 @@ fun buildFnReturnType(
     }
 }
 
+/**
+ * Renders a `set_foo_from_iter` method for a list or map [member] `foo`, if its type [memberSymbol] is a `Vec` or a
+ * `HashMap` rather than a constrained wrapper type. The method collects the iterator and passes the result on to the
+ * `foo` method, so that it's stored the same way.
+ */
+fun renderBuilderMemberFromIterSetterFn(
+    writer: RustWriter,
+    member: MemberShape,
+    memberSymbol: Symbol,
+    memberName: String,
+) {
+    val (argument, collected) =
+        fromIterSetterArgument(memberSymbol.rustType().stripOuter<RustType.Option>()) ?: return
+    val value = if (memberSymbol.isOptional()) "Some($collected)" else collected
+
+    writer.docs("Sets [`$memberName`](Self::$memberName) from an iterator, converting each item into the collection's item type.")
+    writer.deprecatedShape(member)
+    writer.rustBlock("pub fn ${member.fromIterSetterName()}(self, input: $argument) -> Self") {
+        rust("self.$memberName($value)")
+    }
+}
+
 /**
  * Renders code to fall back to the modeled `@default` value on a [member] shape.
  * The code is expected to be interpolated right after a value of type `Option<T>`, where `T` is the type of the
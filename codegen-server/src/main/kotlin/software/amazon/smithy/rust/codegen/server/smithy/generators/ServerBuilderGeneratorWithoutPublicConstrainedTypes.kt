@@ -25,10 +25,15 @@ import software.amazon.smithy.rust.codegen.core.rustlang.withBlock
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
 import software.amazon.smithy.rust.codegen.core.smithy.expectRustMetadata
+import software.amazon.smithy.rust.codegen.core.smithy.generators.fromIterSetterName
 import software.amazon.smithy.rust.codegen.core.smithy.generators.lifetimeDeclaration
+import software.amazon.smithy.rust.codegen.core.smithy.generators.renderDebugField
+import software.amazon.smithy.rust.codegen.core.smithy.generators.setterName
 import software.amazon.smithy.rust.codegen.core.smithy.isOptional
 import software.amazon.smithy.rust.codegen.core.smithy.makeOptional
 import software.amazon.smithy.rust.codegen.core.smithy.module
+import software.amazon.smithy.rust.codegen.core.smithy.rustType
+import software.amazon.smithy.rust.codegen.core.util.shouldRedact
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCodegenContext
 import software.amazon.smithy.rust.codegen.server.smithy.generators.protocol.ServerProtocol
 import software.amazon.smithy.rust.codegen.server.smithy.withInMemoryInlineModule
@@ -140,9 +145,38 @@ class ServerBuilderGeneratorWithoutPublicConstrainedTypes(
         writer.rustBlock("impl Builder") {
             for (member in members) {
                 renderBuilderMemberFn(this, member)
+                if (members.none { it.setterName() == member.fromIterSetterName() }) {
+                    renderBuilderMemberFromIterSetterFn(
+                        this,
+                        member,
+                        symbolProvider.toSymbol(member),
+                        symbolProvider.toMemberName(member),
+                    )
+                }
             }
             renderBuildFn(this)
         }
+
+        if (!structureSymbol.expectRustMetadata().hasDebugDerive()) {
+            renderImplDebugForBuilder(writer)
+        }
+    }
+
+    private fun renderImplDebugForBuilder(writer: RustWriter) {
+        writer.rustBlock("impl #T for Builder", RuntimeType.Debug) {
+            writer.rustBlock("fn fmt(&self, f: &mut #1T::Formatter<'_>) -> #1T::Result", RuntimeType.stdFmt) {
+                rust("""let mut formatter = f.debug_struct("Builder");""")
+                members.forEach { member ->
+                    renderDebugField(
+                        runtimeConfig,
+                        symbolProvider.toMemberName(member),
+                        builderMemberSymbol(member).rustType(),
+                        redact = member.shouldRedact(model),
+                    )
+                }
+                rust("formatter.finish()")
+            }
+        }
     }
 
     private fun renderBuildFn(implBlockWriter: RustWriter) {
@@ -26,6 +26,8 @@ import software.amazon.smithy.rust.codegen.core.util.toSnakeCase
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCargoDependency
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCodegenContext
 import software.amazon.smithy.rust.codegen.server.smithy.generators.protocol.ServerProtocol
+import software.amazon.smithy.rust.codegen.server.smithy.generators.protocol.ServerRestJsonProtocol
+import software.amazon.smithy.rust.codegen.server.smithy.generators.protocol.ServerRestXmlProtocol
 import software.amazon.smithy.rust.codegen.server.smithy.ServerRustModule.Error as ErrorModule
 import software.amazon.smithy.rust.codegen.server.smithy.ServerRustModule.Input as InputModule
 import software.amazon.smithy.rust.codegen.server.smithy.ServerRustModule.Output as OutputModule
@@ -49,6 +51,7 @@ class ServerServiceGenerator(
     private val model = codegenContext.model
     private val symbolProvider = codegenContext.symbolProvider
     private val crateName = codegenContext.moduleUseName()
+    private val typeErasedServiceBuilder = codegenContext.settings.codegenConfig.typeErasedServiceBuilder
 
     private val service = codegenContext.serviceShape
     private val serviceId = service.id
@@ -103,6 +106,254 @@ class ServerServiceGenerator(
             Pair(functionName, functionBody)
         }
 
+    /**
+     * The bounds for applying the model plugins, the `UpgradePlugin` and the HTTP plugins to [innerService], the
+     * service of the [operationShape] operation.
+     */
+    private fun pluginBounds(
+        operationShape: OperationShape,
+        innerService: String,
+    ): Writable =
+        writable {
+            val structName = operationStructNames.getValue(operationShape)
+            rustTemplate(
+                """
+                ModelPl: #{SmithyHttpServer}::plugin::Plugin<
+                    $serviceName<L>,
+                    crate::operation_shape::$structName,
+                    $innerService
+                >,
+                #{SmithyHttpServer}::operation::UpgradePlugin::<UpgradeExtractors>: #{SmithyHttpServer}::plugin::Plugin<
+                    $serviceName<L>,
+                    crate::operation_shape::$structName,
+                    ModelPl::Output
+                >,
+                HttpPl: #{SmithyHttpServer}::plugin::Plugin<
+                    $serviceName<L>,
+                    crate::operation_shape::$structName,
+                    <
+                        #{SmithyHttpServer}::operation::UpgradePlugin::<UpgradeExtractors>
+                        as #{SmithyHttpServer}::plugin::Plugin<
+                            $serviceName<L>,
+                            crate::operation_shape::$structName,
+                            ModelPl::Output
+                        >
+                    >::Output
+                >,
+
+                HttpPl::Output: #{Tower}::Service<#{Http}::Request<Body>, Response = #{Http}::Response<#{SmithyHttpServer}::body::BoxBody>, Error = ::std::convert::Infallible> + Clone + Send + 'static,
+                <HttpPl::Output as #{Tower}::Service<#{Http}::Request<Body>>>::Future: Send + 'static,
+                """,
+                *codegenScope,
+            )
+        }
+
+    /** Applies the plugins to `svc`, and stores the resulting service in the builder. */
+    private val applyPlugins =
+        writable {
+            rustTemplate(
+                """
+                use #{SmithyHttpServer}::plugin::Plugin;
+                let svc = self.model_plugin.apply(svc);
+                let svc = #{SmithyHttpServer}::operation::UpgradePlugin::<UpgradeExtractors>::new().apply(svc);
+                let svc = self.http_plugin.apply(svc);
+                """,
+                *codegenScope,
+            )
+        }
+
+    /**
+     * The bounds and bodies of the handler and service setters of [operationShape].
+     *
+     * With the `typeErasedServiceBuilder` setting, handlers and services are boxed as soon as they're registered, so
+     * the model plugins and the `UpgradePlugin` are only instantiated once per operation and set of extractors. The
+     * upgraded service is boxed again into a `Route`, and the HTTP plugins are applied by a `_boxed` setter that isn't
+     * generic at all. Otherwise, the plugins are applied to the handler or service directly.
+     */
+    private fun setterBodies(operationShape: OperationShape): Array<Pair<String, Any>> {
+        val structName = operationStructNames.getValue(operationShape)
+        val fieldName = builderFieldNames.getValue(operationShape)
+        val operation = "crate::operation_shape::$structName"
+        // This spells out `BoxedOperationService`, since bounds on plugins over a type alias with projections of
+        // `OperationShape` aren't satisfied by the same bounds in callers.
+        val error = if (operationShape.errors.isEmpty()) "std::convert::Infallible" else "crate::error::${structName}Error"
+        val boxedService = { extractors: String ->
+            "#{Tower}::util::BoxCloneService<(crate::input::${structName}Input, $extractors), crate::output::${structName}Output, $error>"
+        }
+        if (!typeErasedServiceBuilder) {
+            return arrayOf(
+                "HandlerBounds" to pluginBounds(operationShape, "#{SmithyHttpServer}::operation::IntoService<$operation, HandlerType>"),
+                "ServiceBounds" to pluginBounds(operationShape, "#{SmithyHttpServer}::operation::Normalize<$operation, S>"),
+                "HandlerBody" to
+                    writable {
+                        rustTemplate(
+                            """
+                            use #{SmithyHttpServer}::operation::OperationShapeExt;
+                            let svc = $operation::from_handler(handler);
+                            #{ApplyPlugins:W}
+                            self.${fieldName}_custom(svc)
+                            """,
+                            "ApplyPlugins" to applyPlugins,
+                            *codegenScope,
+                        )
+                    },
+                "ServiceBody" to
+                    writable {
+                        rustTemplate(
+                            """
+                            use #{SmithyHttpServer}::operation::OperationShapeExt;
+                            let svc = $operation::from_service(service);
+                            #{ApplyPlugins:W}
+                            self.${fieldName}_custom(svc)
+                            """,
+                            "ApplyPlugins" to applyPlugins,
+                            *codegenScope,
+                        )
+                    },
+                "BoxedSetter" to writable { },
+            )
+        }
+
+        val httpPluginBounds =
+            writable {
+                rustTemplate(
+                    """
+                    HttpPl: #{SmithyHttpServer}::plugin::Plugin<
+                        $serviceName<L>,
+                        $operation,
+                        #{SmithyHttpServer}::routing::Route<Body>
+                    >,
+                    HttpPl::Output: #{Tower}::Service<#{Http}::Request<Body>, Response = #{Http}::Response<#{SmithyHttpServer}::body::BoxBody>, Error = ::std::convert::Infallible> + Clone + Send + 'static,
+                    <HttpPl::Output as #{Tower}::Service<#{Http}::Request<Body>>>::Future: Send + 'static,
+                    """,
+                    *codegenScope,
+                )
+            }
+        // The type of the service produced by the model plugins and the `UpgradePlugin`.
+        val upgraded = { extractors: String ->
+            """
+            <
+                #{SmithyHttpServer}::operation::UpgradePlugin::<UpgradeExtractors>
+                as #{SmithyHttpServer}::plugin::Plugin<
+                    $serviceName<L>,
+                    $operation,
+                    <ModelPl as #{SmithyHttpServer}::plugin::Plugin<$serviceName<L>, $operation, ${boxedService(extractors)}>>::Output
+                >
+            >::Output
+            """
+        }
+        val erasedBounds = { extractors: String ->
+            writable {
+                rustTemplate(
+                    """
+                    ModelPl: #{SmithyHttpServer}::plugin::Plugin<
+                        $serviceName<L>,
+                        $operation,
+                        ${boxedService(extractors)}
+                    >,
+                    #{SmithyHttpServer}::operation::UpgradePlugin::<UpgradeExtractors>: #{SmithyHttpServer}::plugin::Plugin<
+                        $serviceName<L>,
+                        $operation,
+                        ModelPl::Output
+                    >,
+                    ${upgraded(extractors)}: #{Tower}::Service<#{Http}::Request<Body>, Response = #{Http}::Response<#{SmithyHttpServer}::body::BoxBody>, Error = ::std::convert::Infallible> + Clone + Send + 'static,
+                    <${upgraded(extractors)} as #{Tower}::Service<#{Http}::Request<Body>>>::Future: Send + 'static,
+                    #{HttpBounds:W}
+                    """,
+                    "HttpBounds" to httpPluginBounds,
+                    *codegenScope,
+                )
+            }
+        }
+        // Boxes the service produced by the `UpgradePlugin`, whose type only depends on the extractors, into a
+        // `Route`, whose type depends on nothing. This is what keeps the `_boxed` setter free of generics.
+        val upgradeAndBox =
+            writable {
+                rustTemplate(
+                    """
+                    use #{SmithyHttpServer}::plugin::Plugin;
+                    let svc = self.model_plugin.apply(svc);
+                    let svc = #{SmithyHttpServer}::operation::UpgradePlugin::<UpgradeExtractors>::new().apply(svc);
+                    self.${fieldName}_boxed(#{SmithyHttpServer}::routing::Route::new(svc))
+                    """,
+                    *codegenScope,
+                )
+            }
+
+        return arrayOf(
+            "HandlerBounds" to
+                writable {
+                    rustTemplate(
+                        """
+                        HandlerType: Clone + Send + 'static,
+                        <HandlerType as #{SmithyHttpServer}::operation::Handler<$operation, HandlerExtractors>>::Future: Send + 'static,
+                        #{Bounds:W}
+                        """,
+                        "Bounds" to erasedBounds("HandlerExtractors"),
+                        *codegenScope,
+                    )
+                },
+            "ServiceBounds" to
+                writable {
+                    rustTemplate(
+                        """
+                        S: Clone + Send + 'static,
+                        <S as #{Tower}::Service<<S as #{SmithyHttpServer}::operation::OperationService<$operation, ServiceExtractors>>::Normalized>>::Future: Send + 'static,
+                        #{Bounds:W}
+                        """,
+                        "Bounds" to erasedBounds("ServiceExtractors"),
+                        *codegenScope,
+                    )
+                },
+            "HandlerBody" to
+                writable {
+                    rustTemplate(
+                        """
+                        use #{SmithyHttpServer}::operation::OperationShapeExt;
+                        let svc = $operation::from_handler_boxed(handler);
+                        #{UpgradeAndBox:W}
+                        """,
+                        "UpgradeAndBox" to upgradeAndBox,
+                        *codegenScope,
+                    )
+                },
+            "ServiceBody" to
+                writable {
+                    rustTemplate(
+                        """
+                        use #{SmithyHttpServer}::operation::OperationShapeExt;
+                        let svc = $operation::from_service_boxed(service);
+                        #{UpgradeAndBox:W}
+                        """,
+                        "UpgradeAndBox" to upgradeAndBox,
+                        *codegenScope,
+                    )
+                },
+            "BoxedSetter" to
+                writable {
+                    rustTemplate(
+                        """
+                        /// Applies the HTTP plugins to the boxed handler or service of the [`$structName`]($operation) operation.
+                        ///
+                        /// This isn't generic, and is kept out of line so that the code applying the HTTP plugins is
+                        /// shared rather than copied into every caller.
+                        ##[inline(never)]
+                        fn ${fieldName}_boxed(self, svc: #{SmithyHttpServer}::routing::Route<Body>) -> Self
+                        where
+                            #{HttpBounds:W}
+                        {
+                            use #{SmithyHttpServer}::plugin::Plugin;
+                            let svc = self.http_plugin.apply(svc);
+                            self.${fieldName}_custom(svc)
+                        }
+                        """,
+                        "HttpBounds" to httpPluginBounds,
+                        *codegenScope,
+                    )
+                },
+        )
+    }
+
     /** A `Writable` block containing all the `Handler` and `Operation` setters for the builder. */
     private fun builderSetters(): Writable =
         writable {
@@ -146,40 +397,10 @@ class ServerServiceGenerator(
                     where
                         HandlerType: #{SmithyHttpServer}::operation::Handler<crate::operation_shape::$structName, HandlerExtractors>,
 
-                        ModelPl: #{SmithyHttpServer}::plugin::Plugin<
-                            $serviceName<L>,
-                            crate::operation_shape::$structName,
-                            #{SmithyHttpServer}::operation::IntoService<crate::operation_shape::$structName, HandlerType>
-                        >,
-                        #{SmithyHttpServer}::operation::UpgradePlugin::<UpgradeExtractors>: #{SmithyHttpServer}::plugin::Plugin<
-                            $serviceName<L>,
-                            crate::operation_shape::$structName,
-                            ModelPl::Output
-                        >,
-                        HttpPl: #{SmithyHttpServer}::plugin::Plugin<
-                            $serviceName<L>,
-                            crate::operation_shape::$structName,
-                            <
-                                #{SmithyHttpServer}::operation::UpgradePlugin::<UpgradeExtractors>
-                                as #{SmithyHttpServer}::plugin::Plugin<
-                                    $serviceName<L>,
-                                    crate::operation_shape::$structName,
-                                    ModelPl::Output
-                                >
-                            >::Output
-                        >,
-
-                        HttpPl::Output: #{Tower}::Service<#{Http}::Request<Body>, Response = #{Http}::Response<#{SmithyHttpServer}::body::BoxBody>, Error = ::std::convert::Infallible> + Clone + Send + 'static,
-                        <HttpPl::Output as #{Tower}::Service<#{Http}::Request<Body>>>::Future: Send + 'static,
+                        #{HandlerBounds:W}
 
                     {
-                        use #{SmithyHttpServer}::operation::OperationShapeExt;
-                        use #{SmithyHttpServer}::plugin::Plugin;
-                        let svc = crate::operation_shape::$structName::from_handler(handler);
-                        let svc = self.model_plugin.apply(svc);
-                        let svc = #{SmithyHttpServer}::operation::UpgradePlugin::<UpgradeExtractors>::new().apply(svc);
-                        let svc = self.http_plugin.apply(svc);
-                        self.${fieldName}_custom(svc)
+                        #{HandlerBody:W}
                     }
 
                     /// Sets the [`$structName`](crate::operation_shape::$structName) operation.
@@ -210,42 +431,67 @@ class ServerServiceGenerator(
                     where
                         S: #{SmithyHttpServer}::operation::OperationService<crate::operation_shape::$structName, ServiceExtractors>,
 
-                        ModelPl: #{SmithyHttpServer}::plugin::Plugin<
-                            $serviceName<L>,
-                            crate::operation_shape::$structName,
-                            #{SmithyHttpServer}::operation::Normalize<crate::operation_shape::$structName, S>
-                        >,
-                        #{SmithyHttpServer}::operation::UpgradePlugin::<UpgradeExtractors>: #{SmithyHttpServer}::plugin::Plugin<
-                            $serviceName<L>,
-                            crate::operation_shape::$structName,
-                            ModelPl::Output
-                        >,
+                        #{ServiceBounds:W}
+
+                    {
+                        #{ServiceBody:W}
+                    }
+
+                    /// Sets the [`$structName`](crate::operation_shape::$structName) operation to a raw HTTP
+                    /// [`Service`](#{Tower}::Service), bypassing the Smithy contract for this operation only.
+                    ///
+                    /// This is an escape hatch meant for gradual migrations, where an existing hand-written service
+                    /// keeps serving some operations while the rest are cut over to generated handlers. Requests are
+                    /// still routed to `service` by the framework, and the HTTP plugins are applied to it. However,
+                    /// since the request is never deserialized into the operation input:
+                    ///
+                    /// - the model plugins are _not_ applied,
+                    /// - the protocol's `Content-Type` and `Accept` header checks are _not_ performed,
+                    /// - the input is _not_ validated against the constraint traits in the model, and
+                    /// - `service` is responsible for producing a response, including errors, that honors the protocol.
+                    ///
+                    /// Prefer [`$builderName::$fieldName`] once the operation has been migrated.
+                    ///
+                    /// ## Example
+                    ///
+                    /// ```no_run
+                    /// use $crateName::{$serviceName, ${serviceName}Config};
+                    ///
+                    /// let config = ${serviceName}Config::builder().build()$unwrapConfigBuilder;
+                    /// let svc = #{Tower}::util::service_fn(|_request: #{Http}::Request<#{SmithyHttpServer}::body::Body>| async {
+                    ///     let response = #{Http}::Response::builder()
+                    ///         .header("Content-Type", "application/json")
+                    ///         .body(#{SmithyHttpServer}::body::to_boxed("{}"))
+                    ///         .unwrap();
+                    ///     Ok::<_, std::convert::Infallible>(response)
+                    /// });
+                    /// let app = $serviceName::builder(config)
+                    ///     .${fieldName}_service_raw(svc)
+                    ///     /* Set other handlers */
+                    ///     .build()
+                    ///     .unwrap();
+                    /// ## let app: $serviceName<#{SmithyHttpServer}::routing::RoutingService<#{Router}<#{SmithyHttpServer}::routing::Route>, #{Protocol}>> = app;
+                    /// ```
+                    ///
+                    pub fn ${fieldName}_service_raw<S>(self, service: S) -> Self
+                    where
+                        S: #{Tower}::Service<#{Http}::Request<Body>, Response = #{Http}::Response<#{SmithyHttpServer}::body::BoxBody>, Error = ::std::convert::Infallible> + Clone + Send + 'static,
+                        S::Future: Send + 'static,
                         HttpPl: #{SmithyHttpServer}::plugin::Plugin<
                             $serviceName<L>,
                             crate::operation_shape::$structName,
-                            <
-                                #{SmithyHttpServer}::operation::UpgradePlugin::<UpgradeExtractors>
-                                as #{SmithyHttpServer}::plugin::Plugin<
-                                    $serviceName<L>,
-                                    crate::operation_shape::$structName,
-                                    ModelPl::Output
-                                >
-                            >::Output
+                            S
                         >,
-
                         HttpPl::Output: #{Tower}::Service<#{Http}::Request<Body>, Response = #{Http}::Response<#{SmithyHttpServer}::body::BoxBody>, Error = ::std::convert::Infallible> + Clone + Send + 'static,
                         <HttpPl::Output as #{Tower}::Service<#{Http}::Request<Body>>>::Future: Send + 'static,
-
                     {
-                        use #{SmithyHttpServer}::operation::OperationShapeExt;
                         use #{SmithyHttpServer}::plugin::Plugin;
-                        let svc = crate::operation_shape::$structName::from_service(service);
-                        let svc = self.model_plugin.apply(svc);
-                        let svc = #{SmithyHttpServer}::operation::UpgradePlugin::<UpgradeExtractors>::new().apply(svc);
-                        let svc = self.http_plugin.apply(svc);
+                        let svc = self.http_plugin.apply(service);
                         self.${fieldName}_custom(svc)
                     }
 
+                    #{BoxedSetter:W}
+
                     /// Sets the [`$structName`](crate::operation_shape::$structName) to a custom [`Service`](tower::Service).
                     /// not constrained by the Smithy contract.
                     fn ${fieldName}_custom<S>(mut self, svc: S) -> Self
@@ -262,6 +508,7 @@ class ServerServiceGenerator(
                     "Handler" to handler,
                     "HandlerFixed" to handlerFixed,
                     "HandlerImports" to handlerImports(crateName, operations),
+                    *setterBodies(operationShape),
                     *codegenScope,
                 )
 
@@ -337,6 +584,7 @@ class ServerServiceGenerator(
 
                         #{Router}::from_iter([#{RoutesArrayElements:W}])
                     };
+                    #{ApplyDotSegments:W}
                     let svc = #{SmithyHttpServer}::routing::RoutingService::new(router);
                     let svc = svc.map(|s| s.layer(self.layer));
                     Ok($serviceName { svc })
@@ -348,6 +596,7 @@ class ServerServiceGenerator(
                 "NullabilityChecks" to nullabilityChecks,
                 "RoutesArrayElements" to routesArrayElements,
                 "PatternInitializations" to patternInitializations(),
+                "ApplyDotSegments" to applyDotSegments(),
             )
         }
 
@@ -413,6 +662,7 @@ class ServerServiceGenerator(
                     >
                 {
                     let router = #{Router}::from_iter([#{Pairs:W}]);
+                    #{ApplyDotSegments:W}
                     let svc = self
                         .layer
                         .layer(#{SmithyHttpServer}::routing::RoutingService::new(router));
@@ -423,6 +673,7 @@ class ServerServiceGenerator(
                 "Protocol" to protocol.markerStruct(),
                 "Router" to protocol.routerType(),
                 "Pairs" to pairs,
+                "ApplyDotSegments" to applyDotSegments(),
             )
         }
 
@@ -439,11 +690,14 @@ class ServerServiceGenerator(
                     ${builderFields.joinToString(", ")},
                     layer: L,
                     http_plugin: HttpPl,
-                    model_plugin: ModelPl
+                    model_plugin: ModelPl,
+                    #{DotSegmentsField:W}
                 }
 
                 impl<$builderGenerics> $builderName<$builderGenerics> {
                     #{Setters:W}
+
+                    #{DotSegmentsSetter:W}
                 }
 
                 impl<$builderGenerics> $builderName<$builderGenerics> {
@@ -453,6 +707,8 @@ class ServerServiceGenerator(
                 }
                 """,
                 "Setters" to builderSetters(),
+                "DotSegmentsField" to dotSegmentsField(),
+                "DotSegmentsSetter" to dotSegmentsSetter(),
                 "BuildMethod" to buildMethod(),
                 "BuildUncheckedMethod" to buildUncheckedMethod(),
                 *codegenScope,
@@ -534,6 +790,7 @@ class ServerServiceGenerator(
                             layer: config.layers,
                             http_plugin: config.http_plugins,
                             model_plugin: config.model_plugins,
+                            #{DotSegmentsInit:W}
                         }
                     }
 
@@ -561,7 +818,8 @@ class ServerServiceGenerator(
                             #{NotSetFields2:W},
                             layer: #{Tower}::layer::util::Identity::new(),
                             http_plugin,
-                            model_plugin
+                            model_plugin,
+                            #{DotSegmentsInit:W}
                         }
                     }
 
@@ -672,12 +930,65 @@ class ServerServiceGenerator(
                 """,
                 "NotSetFields1" to notSetFields(),
                 "NotSetFields2" to notSetFields(),
+                "DotSegmentsInit" to dotSegmentsInit(),
                 "Router" to protocol.routerType(),
                 "Protocol" to protocol.markerStruct(),
                 *codegenScope,
             )
         }
 
+    /**
+     * Only the routers of REST protocols route on arbitrary path segments, so only their builders have a dot-segment
+     * policy.
+     */
+    private val hasDotSegmentPolicy = protocol is ServerRestJsonProtocol || protocol is ServerRestXmlProtocol
+
+    private fun dotSegmentsField(): Writable =
+        writable {
+            if (hasDotSegmentPolicy) {
+                rustTemplate("dot_segments: #{SmithyHttpServer}::routing::DotSegments,", *codegenScope)
+            }
+        }
+
+    private fun dotSegmentsInit(): Writable =
+        writable {
+            if (hasDotSegmentPolicy) {
+                rust("dot_segments: Default::default(),")
+            }
+        }
+
+    private fun dotSegmentsSetter(): Writable =
+        writable {
+            if (hasDotSegmentPolicy) {
+                rustTemplate(
+                    """
+                    /// Sets how request paths that contain dot-segments (`.` or `..`) are routed.
+                    ///
+                    /// Defaults to [`DotSegments::Reject`](#{SmithyHttpServer}::routing::DotSegments::Reject).
+                    /// Routes whose patterns could be confused under the policy are warned about when the service is built.
+                    pub fn dot_segments(mut self, dot_segments: #{SmithyHttpServer}::routing::DotSegments) -> Self {
+                        self.dot_segments = dot_segments;
+                        self
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+        }
+
+    /** Applies the builder's dot-segment policy to the `router` being built, warning about confusable routes once. */
+    private fun applyDotSegments(): Writable =
+        writable {
+            if (hasDotSegmentPolicy) {
+                rust(
+                    """
+                    let router = router.dot_segments(self.dot_segments);
+                    router.warn_about_confusable_routes();
+                    """,
+                )
+            }
+        }
+
     private fun missingOperationsError(): Writable =
         writable {
             rustTemplate(
@@ -18,9 +18,11 @@ import software.amazon.smithy.rust.codegen.core.rustlang.rustTypeParameters
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.smithy.customizations.ServiceMetadataCustomization
 import software.amazon.smithy.rust.codegen.core.util.toPascalCase
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCargoDependency
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCodegenContext
+import software.amazon.smithy.rust.codegen.server.smithy.ServerRustModule
 
 fun List<ConfigMethod>.isBuilderFallible() = this.any { it.isRequired }
 
@@ -221,6 +223,8 @@ class ServiceConfigGenerator(
                         #{BuilderRequiredMethodFlagsInit:W}
                     }
                 }
+
+                #{ServiceMetadataFn:W}
             }
 
             /// Builder returned by [`${serviceName}Config::builder()`].
@@ -293,6 +297,7 @@ class ServiceConfigGenerator(
             "BuilderRequiredMethodFlagsMove2" to builderRequiredMethodFlagsMove(),
             "BuilderRequiredMethodFlagsMove3" to builderRequiredMethodFlagsMove(),
             "BuilderBuildMethod" to builderBuildMethod(),
+            "ServiceMetadataFn" to ServiceMetadataCustomization.serviceMetadataFn(ServerRustModule.Meta),
         )
     }
 
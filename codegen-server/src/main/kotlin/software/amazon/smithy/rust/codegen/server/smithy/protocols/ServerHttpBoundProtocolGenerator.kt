@@ -91,11 +91,11 @@ sealed class ServerHttpBoundProtocolSection(name: String) : Section(name) {
     data class AfterTimestampDeserializedMember(val shape: MemberShape) : ServerHttpBoundProtocolSection("AfterTimestampDeserializedMember")
 
     /**
-     * Represent a section for rendering the serialized stream payload.
+     * Represent a section for rendering the serialized stream payload as an `http_body::Body`.
      *
-     * If the payload does not implement the `futures_core::stream::Stream`, which is the case for
-     * `aws_smithy_types::byte_stream::ByteStream`, the section needs to be overridden and renders a new-type wrapper
-     * around the payload to enable the `Stream` trait.
+     * The section must be overridden. `aws_smithy_types::byte_stream::ByteStream` payloads are rendered as their
+     * inner `SdkBody` so that trailers are preserved, and payloads that only implement
+     * `futures_core::stream::Stream` are wrapped with `hyper::Body::wrap_stream`.
      */
     data class WrapStreamPayload(val params: StreamPayloadSerializerParams) :
         ServerHttpBoundProtocolSection("WrapStreamPayload")
@@ -174,6 +174,7 @@ class ServerHttpBoundProtocolTraitImplGenerator(
             "HttpBody" to RuntimeType.HttpBody,
             "header_util" to RuntimeType.smithyHttp(runtimeConfig).resolve("header"),
             "Hyper" to RuntimeType.Hyper,
+            "label" to RuntimeType.smithyHttp(runtimeConfig).resolve("label"),
             "LazyStatic" to RuntimeType.LazyStatic,
             "Mime" to ServerCargoDependency.Mime.toType(),
             "Nom" to ServerCargoDependency.Nom.toType(),
@@ -524,7 +525,9 @@ class ServerHttpBoundProtocolTraitImplGenerator(
 
         operationShape.outputShape(model).findStreamingMember(model)?.let {
             val payloadGenerator = ServerHttpBoundProtocolPayloadGenerator(codegenContext, protocol)
-            withBlockTemplate("let body = #{SmithyHttpServer}::body::boxed(#{SmithyHttpServer}::body::Body::wrap_stream(", "));", *codegenScope) {
+            // The body is streamed as-is, so it is sent with chunked transfer encoding (HTTP/1.1) or as a sequence of
+            // DATA frames (HTTP/2) unless the payload has a known length, and any trailers it has are sent after it.
+            withBlockTemplate("let body = #{SmithyHttpServer}::body::boxed(", ");", *codegenScope) {
                 for (customization in customizations) {
                     customization.section(
                         ServerHttpBoundProtocolSection.WrapStreamPayload(
@@ -747,7 +750,7 @@ class ServerHttpBoundProtocolTraitImplGenerator(
                     """
                     #{SmithyHttpServer}::protocol::content_type_header_classifier_smithy(
                         &headers,
-                        Some("$expectedRequestContentType"),
+                        Some(${expectedRequestContentType.dq()}),
                     )?;
                     input = #{parser}(bytes.as_ref(), input)?;
                     """,
@@ -861,7 +864,7 @@ class ServerHttpBoundProtocolTraitImplGenerator(
                                                     if !bytes.is_empty() {
                                                         #{SmithyHttpServer}::protocol::content_type_header_classifier_smithy(
                                                             &headers,
-                                                            Some("$expectedRequestContentType"),
+                                                            Some(${expectedRequestContentType.dq()}),
                                                         )?;
                                                     }
                                                     """,
@@ -976,7 +979,7 @@ class ServerHttpBoundProtocolTraitImplGenerator(
                 .forEachIndexed { index, segment ->
                     val binding = pathBindings.find { it.memberName == segment.content }
                     if (binding != null && segment.isLabel) {
-                        val deserializer = generateParseStrFn(binding, true)
+                        val deserializer = generateParseStrFn(binding, true, segment.isGreedyLabel)
                         rustTemplate(
                             """
                             input = input.${binding.member.setterName()}(
@@ -1241,9 +1244,31 @@ class ServerHttpBoundProtocolTraitImplGenerator(
         )
     }
 
+    /**
+     * The [label rules](aws_smithy_http::label::LabelRules) that URI path labels are validated against, as configured
+     * by the `rejectEmptyLabels` and `greedyLabelTrailingSlash` codegen settings.
+     */
+    private fun labelRules(): Writable =
+        writable {
+            val codegenConfig = codegenContext.settings.codegenConfig
+            rustTemplate("#{label}::LabelRules::server()", *codegenScope)
+            if (codegenConfig.rejectEmptyLabels) {
+                rustTemplate(".with_empty_labels(#{label}::EmptyLabels::Reject)", *codegenScope)
+            }
+            when (codegenConfig.greedyLabelTrailingSlash) {
+                "trim" -> rustTemplate(".with_trailing_slash(#{label}::TrailingSlash::Trim)", *codegenScope)
+                "reject" -> rustTemplate(".with_trailing_slash(#{label}::TrailingSlash::Reject)", *codegenScope)
+            }
+        }
+
+    /**
+     * When [percentDecoding] is set, the value is a URI path label, which is validated against the [labelRules] and
+     * percent decoded. Query string values have already been decoded.
+     */
     private fun generateParseStrFn(
         binding: HttpBindingDescriptor,
         percentDecoding: Boolean,
+        greedyLabel: Boolean = false,
     ): RuntimeType {
         val output = unconstrainedShapeSymbolProvider.toSymbol(binding.member)
         return protocolFunctions.deserializeFn(binding.member) { fnName ->
@@ -1253,15 +1278,17 @@ class ServerHttpBoundProtocolTraitImplGenerator(
                 "O" to output,
             ) {
                 val target = model.expectShape(binding.member.target)
+                val labelStrategy = if (greedyLabel) "Greedy" else "Default"
 
                 when {
                     target.isStringShape -> {
                         if (percentDecoding) {
                             rustTemplate(
                                 """
-                                let value = #{PercentEncoding}::percent_decode_str(value).decode_utf8()?.into_owned();
+                                let value = #{label}::decode_label(value, #{label}::EncodingStrategy::$labelStrategy, &#{rules:W})?.into_owned();
                                 """,
                                 *codegenScope,
+                                "rules" to labelRules(),
                             )
                         } else {
                             rust("let value = value.to_owned();")
@@ -1280,11 +1307,12 @@ class ServerHttpBoundProtocolTraitImplGenerator(
                         if (percentDecoding) {
                             rustTemplate(
                                 """
-                                let value = #{PercentEncoding}::percent_decode_str(value).decode_utf8()?;
+                                let value = #{label}::decode_label(value, #{label}::EncodingStrategy::$labelStrategy, &#{rules:W})?;
                                 let value = #{DateTime}::from_str(value.as_ref(), #{format})?
                                 """,
                                 *codegenScope,
                                 "format" to timestampFormatType,
+                                "rules" to labelRules(),
                             )
                         } else {
                             rustTemplate(
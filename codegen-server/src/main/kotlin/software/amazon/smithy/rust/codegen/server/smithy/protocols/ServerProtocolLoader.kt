@@ -11,13 +11,14 @@ import software.amazon.smithy.aws.traits.protocols.RestJson1Trait
 import software.amazon.smithy.aws.traits.protocols.RestXmlTrait
 import software.amazon.smithy.protocol.traits.Rpcv2CborTrait
 import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.withBlock
 import software.amazon.smithy.rust.codegen.core.rustlang.withBlockTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
-import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.protocols.AwsJsonVersion
 import software.amazon.smithy.rust.codegen.core.smithy.protocols.ProtocolLoader
 import software.amazon.smithy.rust.codegen.core.smithy.protocols.ProtocolMap
 import software.amazon.smithy.rust.codegen.core.util.isOutputEventStream
+import software.amazon.smithy.rust.codegen.server.smithy.ServerCargoDependency
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCodegenContext
 import software.amazon.smithy.rust.codegen.server.smithy.generators.protocol.ServerProtocolGenerator
 
@@ -27,17 +28,20 @@ class StreamPayloadSerializerCustomization : ServerHttpBoundProtocolCustomizatio
             is ServerHttpBoundProtocolSection.WrapStreamPayload ->
                 writable {
                     if (section.params.shape.isOutputEventStream(section.params.codegenContext.model)) {
-                        // Event stream payload, of type `aws_smithy_http::event_stream::MessageStreamAdapter`, already
-                        // implements the `Stream` trait, so no need to wrap it in the new-type.
-                        section.params.payloadGenerator.generatePayload(this, section.params.shapeName, section.params.shape)
-                    } else {
-                        // Otherwise, the stream payload is `aws_smithy_types::byte_stream::ByteStream`. We wrap it in the
-                        // new-type to enable the `Stream` trait.
+                        // Event stream payload, of type `aws_smithy_http::event_stream::MessageStreamAdapter`,
+                        // implements the `Stream` trait.
                         withBlockTemplate(
-                            "#{FuturesStreamCompatByteStream}::new(",
+                            "#{SmithyHttpServer}::body::Body::wrap_stream(",
                             ")",
-                            "FuturesStreamCompatByteStream" to RuntimeType.futuresStreamCompatByteStream(section.params.codegenContext.runtimeConfig),
+                            "SmithyHttpServer" to
+                                ServerCargoDependency.smithyHttpServer(section.params.codegenContext.runtimeConfig).toType(),
                         ) {
+                            section.params.payloadGenerator.generatePayload(this, section.params.shapeName, section.params.shape)
+                        }
+                    } else {
+                        // Otherwise, the stream payload is `aws_smithy_types::byte_stream::ByteStream`. Its inner
+                        // `SdkBody` implements `http_body::Body`, including trailers.
+                        withBlock("(", ").into_inner()") {
                             section.params.payloadGenerator.generatePayload(
                                 this,
                                 section.params.shapeName,
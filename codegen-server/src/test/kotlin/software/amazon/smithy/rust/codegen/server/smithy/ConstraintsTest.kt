@@ -33,6 +33,7 @@ import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.unitTest
 import software.amazon.smithy.rust.codegen.core.util.lookup
 import software.amazon.smithy.rust.codegen.server.smithy.testutil.serverIntegrationTest
+import software.amazon.smithy.rust.codegen.server.smithy.transformers.ConstrainedMemberTransform
 import software.amazon.smithy.rust.codegen.server.smithy.testutil.serverTestSymbolProvider
 import java.io.File
 
@@ -226,6 +227,43 @@ class ConstraintsTest {
         primitiveBoolean.isDirectlyConstrained(symbolProvider) shouldBe false
     }
 
+    @Test
+    fun `it should redact values of shapes extracted from sensitive members`() {
+        val model =
+            """
+            namespace test
+
+            operation TestOperation {
+                input := {
+                    credentials: Credentials
+                    profile: Profile
+                }
+            }
+
+            @sensitive
+            structure Credentials {
+                @pattern("^[0-9]{4}$")
+                pin: String
+            }
+
+            structure Profile {
+                @length(max: 64)
+                secret: Secret
+
+                @pattern("^[a-z]+$")
+                nickname: String
+            }
+
+            @sensitive
+            string Secret
+            """.asSmithyModel(smithyVersion = "2")
+        val transformed = ConstrainedMemberTransform.transform(model)
+
+        transformed.lookup<StringShape>("test#CredentialsPin").shouldRedactConstrainedValue(transformed) shouldBe true
+        transformed.lookup<StringShape>("test#ProfileSecret").shouldRedactConstrainedValue(transformed) shouldBe true
+        transformed.lookup<StringShape>("test#ProfileNickname").shouldRedactConstrainedValue(transformed) shouldBe false
+    }
+
     private fun generateAndCompileServer(
         model: Model,
         pubConstraints: Boolean = true,
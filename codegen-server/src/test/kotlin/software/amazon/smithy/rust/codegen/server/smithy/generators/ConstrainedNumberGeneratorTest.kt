@@ -6,6 +6,7 @@
 package software.amazon.smithy.rust.codegen.server.smithy.generators
 
 import io.kotest.matchers.string.shouldContain
+import org.junit.jupiter.api.Test
 import org.junit.jupiter.api.extension.ExtensionContext
 import org.junit.jupiter.params.ParameterizedTest
 import org.junit.jupiter.params.provider.Arguments
@@ -157,4 +158,59 @@ class ConstrainedNumberGeneratorTest {
         // Check that the wrapped type is `pub(crate)`.
         writer.toString() shouldContain "pub struct $shapeName(pub(crate) $rustType);"
     }
+
+    @Test
+    fun `constraint violation messages have the offending value`() {
+        val model =
+            """
+            namespace test
+
+            @range(min: 1, max: 100)
+            integer Level
+
+            @sensitive
+            @range(min: 0, max: 9999)
+            integer Pin
+            """.asSmithyModel()
+
+        val codegenContext = serverTestCodegenContext(model)
+        val project = TestWorkspace.testProject(codegenContext.symbolProvider)
+
+        project.withModule(ServerRustModule.Model) {
+            listOf("Level", "Pin").forEach {
+                ConstrainedNumberGenerator(
+                    codegenContext,
+                    this.createTestInlineModuleCreator(),
+                    this,
+                    model.lookup<NumberShape>("test#$it"),
+                    SmithyValidationExceptionConversionGenerator(codegenContext),
+                ).render()
+            }
+
+            unitTest(
+                name = "range_violations_have_the_value",
+                test = """
+                    let error = Level::try_from(101).unwrap_err();
+                    assert_eq!(level::ConstraintViolation::Range(101), error);
+                    assert_eq!(
+                        "Value 101 provided for `test#Level` failed to satisfy constraint: Member must be between 1 and 100, inclusive",
+                        error.to_string()
+                    );
+                """,
+            )
+
+            unitTest(
+                name = "sensitive_range_violations_are_redacted",
+                test = """
+                    let error = Pin::try_from(-1234).unwrap_err();
+                    assert_eq!(
+                        "Value *** Sensitive Data Redacted *** provided for `test#Pin` failed to satisfy constraint: Member must be between 0 and 9999, inclusive",
+                        error.to_string()
+                    );
+                """,
+            )
+        }
+
+        project.compileAndTest()
+    }
 }
@@ -218,6 +218,105 @@ class ConstrainedStringGeneratorTest {
                     assert_eq!(format!("{}", constrained), "*** Sensitive Data Redacted ***")
                 """,
             )
+
+            unitTest(
+                name = "string_views",
+                test = """
+                    fn takes_str_ref(value: impl AsRef<str>) -> String {
+                        value.as_ref().to_owned()
+                    }
+
+                    let constrained = ConstrainedString::try_from("a non-sensitive string".to_owned()).unwrap();
+                    assert_eq!(takes_str_ref(&constrained), "a non-sensitive string");
+                    assert!(constrained.starts_with("a non"));
+                    assert_eq!(constrained.len(), 22);
+                """,
+            )
+        }
+
+        project.compileAndTest()
+    }
+
+    @Test
+    fun `constraint violation messages summarize the offending value`() {
+        val model =
+            """
+            namespace test
+
+            @length(min: 3, max: 8)
+            string Nickname
+
+            @pattern("^[a-z]+$")
+            string Species
+
+            @sensitive
+            @pattern("^[0-9]{4}$")
+            string Pin
+            """.asSmithyModel()
+
+        val codegenContext = serverTestCodegenContext(model)
+        val project = TestWorkspace.testProject(codegenContext.symbolProvider)
+
+        project.withModule(ServerRustModule.Model) {
+            val validationExceptionConversionGenerator = SmithyValidationExceptionConversionGenerator(codegenContext)
+            listOf("Nickname", "Species", "Pin").forEach {
+                ConstrainedStringGenerator(
+                    codegenContext,
+                    this.createTestInlineModuleCreator(),
+                    this,
+                    model.lookup<StringShape>("test#$it"),
+                    validationExceptionConversionGenerator,
+                ).render()
+            }
+
+            unitTest(
+                name = "length_violations_have_the_length",
+                test = """
+                    let error = Nickname::try_from("pikachu the great".to_owned()).unwrap_err();
+                    assert_eq!(nickname::ConstraintViolation::Length(17), error);
+                    assert_eq!(
+                        "Value with length 17 provided for 'test#Nickname' failed to satisfy constraint: Member must have length between 3 and 8, inclusive",
+                        error.to_string()
+                    );
+                """,
+            )
+
+            unitTest(
+                name = "pattern_violations_have_a_truncated_value_and_the_first_non_matching_position",
+                test = """
+                    let error = Species::try_from("Pikachu".to_owned()).unwrap_err();
+                    assert_eq!(species::ConstraintViolation::Pattern("Pikachu".to_owned(), 0), error);
+                    assert_eq!(
+                        r##"Value "Pikachu" provided for `test#Species` failed to satisfy the constraint: Member must match the regular expression pattern: ^[a-z]+$ (first non-matching character at position 0)"##,
+                        error.to_string()
+                    );
+
+                    let long = "pikachu ".repeat(10);
+                    let error = Species::try_from(long.clone()).unwrap_err();
+                    assert_eq!(species::ConstraintViolation::Pattern(long.clone(), 7), error);
+                    assert_eq!(
+                        format!(
+                            r##"Value {:?}... provided for `test#Species` failed to satisfy the constraint: Member must match the regular expression pattern: ^[a-z]+$ (first non-matching character at position 7)"##,
+                            &long[..64]
+                        ),
+                        error.to_string()
+                    );
+                """,
+            )
+
+            unitTest(
+                name = "sensitive_pattern_violations_are_redacted",
+                test = """
+                    let error = Pin::try_from("12345".to_owned()).unwrap_err();
+                    let message = error.to_string();
+                    assert_eq!(
+                        r##"Value *** Sensitive Data Redacted *** provided for `test#Pin` failed to satisfy the constraint: Member must match the regular expression pattern: ^[0-9]{4}$"##,
+                        message
+                    );
+                    assert!(!message.contains("12345"));
+                    assert!(!message.contains("position"));
+                """,
+            )
         }
 
         project.compileAndTest()
@@ -0,0 +1,189 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.server.smithy.generators
+
+import io.kotest.matchers.collections.shouldContainAll
+import io.kotest.matchers.shouldBe
+import io.kotest.matchers.string.shouldStartWith
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.aws.traits.protocols.RestJson1Trait
+import software.amazon.smithy.model.Model
+import software.amazon.smithy.model.node.Node
+import software.amazon.smithy.model.node.ObjectNode
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
+import software.amazon.smithy.rust.codegen.core.testutil.ServerAdditionalSettings
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.unitTest
+import software.amazon.smithy.rust.codegen.server.smithy.protocols.ServerRestJsonProtocol
+import software.amazon.smithy.rust.codegen.server.smithy.testutil.serverIntegrationTest
+import software.amazon.smithy.rust.codegen.server.smithy.testutil.serverTestCodegenContext
+import java.io.File
+
+class OpenApiGeneratorTest {
+    private fun ObjectNode.member(vararg path: String): ObjectNode =
+        path.fold(this) { node, name -> node.expectObjectMember(name) }
+
+    /** Checks the fields that OpenAPI 3.0 requires, and that every `$ref` points at a schema in the document. */
+    private fun assertValidOpenApi30(document: ObjectNode) {
+        document.expectStringMember("openapi").value shouldStartWith "3.0."
+        document.member("info").expectStringMember("title")
+        document.member("info").expectStringMember("version")
+        val schemas = document.member("components", "schemas")
+        document.member("paths").members.forEach { (path, item) ->
+            path.value shouldStartWith "/"
+            item.expectObjectNode().members.values.map { it.expectObjectNode() }.forEach { operation ->
+                operation.member("responses").members.keys.map { it.value.toInt() }.forEach { code ->
+                    (code in 100..599) shouldBe true
+                }
+                // Every path template parameter must be declared
+                val pathParameters =
+                    operation.getArrayMember("parameters").map { it.elements }.orElse(listOf())
+                        .map { it.expectObjectNode() }
+                        .filter { it.expectStringMember("in").value == "path" }
+                        .onEach { it.expectBooleanMember("required").value shouldBe true }
+                        .map { it.expectStringMember("name").value }
+                pathParameters shouldContainAll Regex("\\{([^}]+)}").findAll(path.value).map { it.groupValues[1] }.toList()
+            }
+        }
+
+        fun checkRefs(node: Node) {
+            when {
+                node.isObjectNode ->
+                    node.expectObjectNode().members.forEach { (name, value) ->
+                        if (name.value == "\$ref") {
+                            val ref = value.expectStringNode().value
+                            ref shouldStartWith "#/components/schemas/"
+                            schemas.containsMember(ref.removePrefix("#/components/schemas/")) shouldBe true
+                        } else {
+                            checkRefs(value)
+                        }
+                    }
+                node.isArrayNode -> node.expectArrayNode().elements.forEach(::checkRefs)
+            }
+        }
+        checkRefs(document)
+    }
+
+    @Test
+    fun `the pokemon service is described`() {
+        val model =
+            Model.assembler().discoverModels()
+                .addImport(File("../codegen-core/common-test-models/pokemon.smithy").toPath())
+                .addImport(File("../codegen-core/common-test-models/pokemon-common.smithy").toPath())
+                .assemble().unwrap()
+        val params =
+            IntegrationTestParams(
+                additionalSettings = ServerAdditionalSettings.builder().openApi().toObjectNode(),
+                addModuleToEventStreamAllowList = true,
+                service = "com.aws.example#PokemonService",
+            )
+
+        serverIntegrationTest(model, params) { codegenContext, rustCrate ->
+            val document = OpenApiGenerator(codegenContext, ServerRestJsonProtocol(codegenContext)).document()
+            assertValidOpenApi30(document)
+            document.member("info").expectStringMember("title").value shouldBe "Pokémon Service"
+
+            val getPokemonSpecies = document.member("paths", "/pokemon-species/{name}", "get")
+            getPokemonSpecies.expectStringMember("operationId").value shouldBe "GetPokemonSpecies"
+            getPokemonSpecies.member("responses", "200")
+            getPokemonSpecies.member("responses", "404", "content", "application/json", "schema")
+                .expectStringMember("\$ref").value shouldBe "#/components/schemas/ResourceNotFoundException"
+            // Streaming operations are described with an extension
+            document.member("paths").members.values.flatMap { it.expectObjectNode().members.values }
+                .flatMap { it.expectObjectNode().member("responses").members.values }
+                .mapNotNull { it.expectObjectNode().getObjectMember("content").orElse(null) }
+                .flatMap { it.members.values }
+                .any { it.expectObjectNode().containsMember("x-smithy-event-stream") } shouldBe true
+
+            rustCrate.testModule {
+                unitTest("openapi_document_is_json") {
+                    rustTemplate(
+                        """
+                        let document: #{serde_json}::Value = #{serde_json}::from_str(crate::openapi::openapi_document()).unwrap();
+                        assert_eq!("3.0.3", document["openapi"]);
+                        assert!(document["paths"]["/pokemon-species/{name}"]["get"]["responses"]["404"].is_object());
+                        """,
+                        "serde_json" to CargoDependency.SerdeJson.toType(),
+                    )
+                }
+            }
+        }
+    }
+
+    @Test
+    fun `constraints are mapped to json schema keywords`() {
+        val model =
+            """
+            namespace test
+
+            use aws.protocols#restJson1
+
+            @restJson1
+            service ThingService {
+                operations: [PutThing]
+            }
+
+            @http(uri: "/things/{id}", method: "PUT", code: 201)
+            operation PutThing {
+                input := {
+                    @required
+                    @httpLabel
+                    id: Id
+                    @httpQuery("limit")
+                    limit: Limit
+                    tags: Tags
+                    @jsonName("display_name")
+                    name: String
+                }
+                errors: [ThingNotFound]
+            }
+
+            @length(min: 1, max: 8)
+            @pattern("^[a-z]+$")
+            string Id
+
+            @range(min: 1, max: 100)
+            integer Limit
+
+            @length(max: 5)
+            @uniqueItems
+            list Tags {
+                member: String
+            }
+
+            @error("client")
+            @httpError(404)
+            structure ThingNotFound {
+                message: String
+            }
+            """.asSmithyModel(smithyVersion = "2")
+        val codegenContext = serverTestCodegenContext(model, protocolShapeId = RestJson1Trait.ID)
+        val document = OpenApiGenerator(codegenContext, ServerRestJsonProtocol(codegenContext)).document()
+        assertValidOpenApi30(document)
+
+        val putThing = document.member("paths", "/things/{id}", "put")
+        val parameters = putThing.expectArrayMember("parameters").map { it.expectObjectNode() }.associateBy { it.expectStringMember("name").value }
+        val id = parameters.getValue("id").member("schema")
+        id.expectNumberMember("minLength").value shouldBe 1L
+        id.expectNumberMember("maxLength").value shouldBe 8L
+        id.expectStringMember("pattern").value shouldBe "^[a-z]+$"
+        val limit = parameters.getValue("limit").member("schema")
+        limit.expectStringMember("type").value shouldBe "integer"
+        limit.expectNumberMember("minimum").value.toInt() shouldBe 1
+        limit.expectNumberMember("maximum").value.toInt() shouldBe 100
+
+        val body = putThing.member("requestBody", "content", "application/json", "schema", "properties")
+        body.containsMember("display_name") shouldBe true
+        val tags = body.member("tags")
+        tags.expectNumberMember("maxItems").value shouldBe 5L
+        tags.expectBooleanMember("uniqueItems").value shouldBe true
+
+        putThing.member("responses").members.keys.map { it.value } shouldBe listOf("201", "404")
+    }
+}
@@ -19,12 +19,14 @@ import software.amazon.smithy.rust.codegen.core.smithy.meta
 import software.amazon.smithy.rust.codegen.core.testutil.TestWorkspace
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.compileAndTest
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
 import software.amazon.smithy.rust.codegen.core.testutil.unitTest
 import software.amazon.smithy.rust.codegen.core.util.lookup
 import software.amazon.smithy.rust.codegen.server.smithy.ServerRustModule
 import software.amazon.smithy.rust.codegen.server.smithy.customizations.SmithyValidationExceptionConversionGenerator
 import software.amazon.smithy.rust.codegen.server.smithy.generators.protocol.ServerRestJsonProtocol
 import software.amazon.smithy.rust.codegen.server.smithy.renderInlineMemoryModules
+import software.amazon.smithy.rust.codegen.server.smithy.testutil.serverIntegrationTest
 import software.amazon.smithy.rust.codegen.server.smithy.testutil.serverTestCodegenContext
 
 class ServerBuilderGeneratorTest {
@@ -158,4 +160,152 @@ class ServerBuilderGeneratorTest {
         })
         project.compileAndTest()
     }
+
+    @Test
+    fun `enum members have raw string accessors and setters`() {
+        val model =
+            """
+            namespace test
+
+            use aws.protocols#restJson1
+            use smithy.framework#ValidationException
+
+            @restJson1
+            service TestService {
+                operations: [PutInstance]
+            }
+
+            @http(method: "POST", uri: "/instance")
+            operation PutInstance {
+                input: PutInstanceInput,
+                output: PutInstanceOutput,
+                errors: [ValidationException]
+            }
+
+            structure PutInstanceInput {
+                @required
+                state: State,
+                previousState: State
+            }
+
+            structure PutInstanceOutput {
+                state: State
+            }
+
+            enum State {
+                RUNNING = "running"
+                STOPPED = "stopped"
+            }
+            """.asSmithyModel(smithyVersion = "2")
+
+        serverIntegrationTest(model) { _, rustCrate ->
+            rustCrate.testModule {
+                unitTest("enum_raw_string_accessors") {
+                    rust(
+                        """
+                        use crate::model::State;
+
+                        let input = crate::input::PutInstanceInput::builder()
+                            .set_state_from_str(Some("running"))
+                            .set_previous_state_from_str(None)
+                            .build()
+                            .unwrap();
+                        assert_eq!(&State::Running, input.state());
+                        assert_eq!("running", input.state_as_str());
+                        assert_eq!(None, input.previous_state_as_str());
+
+                        let output = crate::output::PutInstanceOutput { state: Some(State::Stopped) };
+                        assert_eq!(Some("stopped"), output.state_as_str());
+                        """,
+                    )
+                }
+
+                unitTest("enum_from_str_setter_is_validated_on_build") {
+                    rust(
+                        """
+                        let err = crate::input::PutInstanceInput::builder()
+                            .set_state_from_str(Some("hibernating"))
+                            .build()
+                            .expect_err("not a known variant");
+                        assert!(matches!(err, crate::input::put_instance_input::ConstraintViolation::State(_)));
+                        """,
+                    )
+                }
+            }
+        }
+    }
+
+    @Test
+    fun `collection members can be set from any iterator`() {
+        val model =
+            """
+            namespace test
+
+            use aws.protocols#restJson1
+            use smithy.framework#ValidationException
+
+            @restJson1
+            service TestService {
+                operations: [PutInstance]
+            }
+
+            @http(method: "POST", uri: "/instance")
+            operation PutInstance {
+                input: PutInstanceInput,
+                errors: [ValidationException]
+            }
+
+            structure PutInstanceInput {
+                names: Names,
+                tags: Tags,
+                @required
+                ports: Ports
+            }
+
+            list Names {
+                member: String
+            }
+
+            map Tags {
+                key: String,
+                value: String
+            }
+
+            list Ports {
+                member: Integer
+            }
+            """.asSmithyModel(smithyVersion = "2")
+
+        serverIntegrationTest(model) { _, rustCrate ->
+            rustCrate.testModule {
+                unitTest("set_collections_from_iter") {
+                    rust(
+                        """
+                        use std::collections::{BTreeMap, HashMap};
+
+                        let input = crate::input::PutInstanceInput::builder()
+                            .set_names_from_iter(["a", "b"].into_iter().map(|name| name.to_uppercase()))
+                            .set_tags_from_iter(BTreeMap::from([("env", "prod")]))
+                            .set_ports_from_iter(vec![80, 443])
+                            .build()
+                            .unwrap();
+                        assert_eq!(Some(vec!["A".to_owned(), "B".to_owned()]), input.names);
+                        assert_eq!(Some(HashMap::from([("env".to_owned(), "prod".to_owned())])), input.tags);
+                        assert_eq!(vec![80, 443], input.ports);
+
+                        // The exact types are still accepted
+                        let input = crate::input::PutInstanceInput::builder()
+                            .names(Some(vec!["a".to_owned()]))
+                            .tags(None)
+                            .ports(vec![8080])
+                            .build()
+                            .unwrap();
+                        assert_eq!(Some(vec!["a".to_owned()]), input.names);
+                        assert_eq!(vec![8080], input.ports);
+                        """,
+                    )
+                }
+            }
+        }
+    }
 }
@@ -57,6 +57,24 @@ class ServerEnumGeneratorTest {
         )
     }
 
+    @Test
+    fun `it generates Display and AsRef for enums`() {
+        ServerEnumGenerator(
+            codegenContext,
+            shape,
+            SmithyValidationExceptionConversionGenerator(codegenContext),
+        ).render(writer)
+        writer.compileAndTest(
+            """
+            use std::str::FromStr;
+            let instance = InstanceType::T2Micro;
+            assert_eq!(format!("{}", instance), "t2.micro");
+            assert_eq!(AsRef::<str>::as_ref(&instance), "t2.micro");
+            assert_eq!(InstanceType::from_str(&instance.to_string()).unwrap(), instance);
+            """,
+        )
+    }
+
     @Test
     fun `it generates enums without the unknown variant`() {
         ServerEnumGenerator(
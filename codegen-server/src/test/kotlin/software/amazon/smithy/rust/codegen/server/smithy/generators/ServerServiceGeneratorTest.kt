@@ -7,6 +7,8 @@ package software.amazon.smithy.rust.codegen.server.smithy.generators
 
 import org.junit.jupiter.api.Test
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
+import software.amazon.smithy.rust.codegen.core.testutil.ServerAdditionalSettings
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.testModule
 import software.amazon.smithy.rust.codegen.server.smithy.testutil.serverIntegrationTest
@@ -36,4 +38,42 @@ internal class ServerServiceGeneratorTest {
             }
         }
     }
+
+    @Test
+    fun `handlers and services can be registered with the type-erased service builder`() {
+        val model = File("../codegen-core/common-test-models/simple.smithy").readText().asSmithyModel()
+        val params =
+            IntegrationTestParams(
+                additionalSettings = ServerAdditionalSettings.builder().typeErasedServiceBuilder().toObjectNode(),
+            )
+
+        serverIntegrationTest(model, params) { _, rustCrate ->
+            rustCrate.testModule {
+                // No actual tests: we just want to check that this compiles.
+                rust(
+                    """
+                    async fn handler(
+                        input: crate::input::OperationInput,
+                        _state: crate::server::Extension<u32>,
+                    ) -> crate::output::OperationOutput {
+                        crate::output::OperationOutput { message: input.message }
+                    }
+
+                    fn _build_service_from_handler() -> crate::SimpleService {
+                        let config = crate::SimpleServiceConfig::builder().build();
+                        crate::SimpleService::builder(config).operation(handler).build().unwrap().boxed()
+                    }
+
+                    fn _build_service_from_service() -> crate::SimpleService {
+                        let config = crate::SimpleServiceConfig::builder().build();
+                        let service = tower::service_fn(|input: crate::input::OperationInput| async move {
+                            Ok::<_, std::convert::Infallible>(crate::output::OperationOutput { message: input.message })
+                        });
+                        crate::SimpleService::builder(config).operation_service(service).build().unwrap().boxed()
+                    }
+                    """,
+                )
+            }
+        }
+    }
 }
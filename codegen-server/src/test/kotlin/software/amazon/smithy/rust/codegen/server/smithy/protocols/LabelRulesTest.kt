@@ -0,0 +1,119 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.server.smithy.protocols
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
+import software.amazon.smithy.rust.codegen.core.testutil.ServerAdditionalSettings
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+import software.amazon.smithy.rust.codegen.server.smithy.testutil.serverIntegrationTest
+
+internal class LabelRulesTest {
+    private val model =
+        """
+        namespace com.example
+
+        use aws.protocols#restJson1
+
+        @restJson1
+        service ObjectService {
+            operations: [GetObjectMeta]
+        }
+
+        @readonly
+        @http(uri: "/objects/{key+}/meta", method: "GET")
+        operation GetObjectMeta {
+            input := {
+                @required
+                @httpLabel
+                key: String
+            }
+            output := {
+                @httpHeader("x-key")
+                key: String
+            }
+        }
+        """.asSmithyModel(smithyVersion = "2")
+
+    private val handler =
+        """
+        use aws_smithy_http_server::body::Body;
+        use tower::Service as _;
+
+        async fn get_object_meta(input: crate::input::GetObjectMetaInput) -> crate::output::GetObjectMetaOutput {
+            crate::output::GetObjectMetaOutput { key: Some(input.key) }
+        }
+
+        async fn call(path: &str) -> http::Response<aws_smithy_http_server::body::BoxBody> {
+            let config = crate::ObjectServiceConfig::builder().build();
+            let mut service = crate::ObjectService::builder(config)
+                .get_object_meta(get_object_meta)
+                .build()
+                .unwrap();
+            let request = http::Request::builder().method("GET").uri(path).body(Body::empty()).unwrap();
+            service.call(request).await.unwrap()
+        }
+        """
+
+    @Test
+    fun `greedy labels are percent decoded after routing`() {
+        serverIntegrationTest(model) { _, rustCrate ->
+            rustCrate.testModule {
+                rust(handler)
+
+                tokioTest("encoded_slashes_are_part_of_the_label") {
+                    rust(
+                        """
+                        let response = call("/objects/a%2Fb/c//meta").await;
+                        assert_eq!(200, response.status());
+                        assert_eq!("a/b/c/", response.headers()["x-key"]);
+
+                        // empty labels are bound to `""` by default
+                        let response = call("/objects//meta").await;
+                        assert_eq!(200, response.status());
+                        assert_eq!("", response.headers()["x-key"]);
+                        """,
+                    )
+                }
+            }
+        }
+    }
+
+    @Test
+    fun `empty labels and trailing slashes can be rejected`() {
+        val params =
+            IntegrationTestParams(
+                additionalSettings =
+                    ServerAdditionalSettings.builder()
+                        .rejectEmptyLabels()
+                        .greedyLabelTrailingSlash("trim")
+                        .toObjectNode(),
+            )
+
+        serverIntegrationTest(model, params) { _, rustCrate ->
+            rustCrate.testModule {
+                rust(handler)
+
+                tokioTest("configured_label_rules_are_applied") {
+                    rust(
+                        """
+                        let response = call("/objects/a/b//meta").await;
+                        assert_eq!(200, response.status());
+                        assert_eq!("a/b", response.headers()["x-key"]);
+
+                        assert_eq!(400, call("/objects//meta").await.status());
+                        // the value is empty once the trailing slash is trimmed
+                        assert_eq!(400, call("/objects///meta").await.status());
+                        """,
+                    )
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.server.smithy.protocols
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+import software.amazon.smithy.rust.codegen.server.smithy.testutil.serverIntegrationTest
+
+internal class PayloadContentTypeTest {
+    private val model =
+        """
+        namespace com.example
+
+        use aws.protocols#restJson1
+
+        @restJson1
+        service PayloadService {
+            operations: [EchoText, EchoBytes]
+        }
+
+        @http(uri: "/text", method: "POST")
+        operation EchoText {
+            input := {
+                @httpPayload
+                text: Utf8Text
+            }
+            output := {
+                @httpPayload
+                text: Utf8Text
+            }
+        }
+
+        @mediaType("text/plain; charset=utf-8")
+        string Utf8Text
+
+        @http(uri: "/bytes", method: "POST")
+        operation EchoBytes {
+            input := {
+                @httpPayload
+                bytes: VersionedBytes
+            }
+            output := {
+                @httpPayload
+                bytes: VersionedBytes
+            }
+        }
+
+        @mediaType("application/octet-stream; version=2")
+        blob VersionedBytes
+        """.asSmithyModel(smithyVersion = "2")
+
+    @Test
+    fun `payload content types round trip with their parameters`() {
+        serverIntegrationTest(model) { _, rustCrate ->
+            rustCrate.testModule {
+                rust(
+                    """
+                    use aws_smithy_http_server::body::{Body, BoxBody};
+                    use aws_smithy_http_server::request::raw::RawHeaders;
+                    use aws_smithy_http_server::{AddExtensionLayer, Extension};
+                    use std::sync::{Arc, Mutex};
+                    use tower::Service as _;
+                    use crate::{input, output};
+
+                    type Received = Arc<Mutex<Option<String>>>;
+
+                    async fn echo_text(
+                        input: input::EchoTextInput,
+                        headers: RawHeaders,
+                        received: Extension<Received>,
+                    ) -> output::EchoTextOutput {
+                        *received.lock().unwrap() = headers.content_type().map(str::to_string);
+                        output::EchoTextOutput { text: input.text }
+                    }
+
+                    async fn echo_bytes(
+                        input: input::EchoBytesInput,
+                        headers: RawHeaders,
+                        received: Extension<Received>,
+                    ) -> output::EchoBytesOutput {
+                        *received.lock().unwrap() = headers.content_type().map(str::to_string);
+                        output::EchoBytesOutput { bytes: input.bytes }
+                    }
+
+                    async fn call(uri: &str, content_type: &str, body: &'static str) -> (http::Response<BoxBody>, Option<String>) {
+                        let received = Received::default();
+                        let config = crate::PayloadServiceConfig::builder()
+                            .layer(AddExtensionLayer::new(received.clone()))
+                            .build();
+                        let mut service = crate::PayloadService::builder(config)
+                            .echo_text(echo_text)
+                            .echo_bytes(echo_bytes)
+                            .build()
+                            .unwrap();
+                        let request = http::Request::builder()
+                            .method("POST")
+                            .uri(uri)
+                            .header("content-type", content_type)
+                            .body(Body::from(body))
+                            .unwrap();
+                        let response = service.call(request).await.unwrap();
+                        let received = received.lock().unwrap().clone();
+                        (response, received)
+                    }
+
+                    async fn body(response: http::Response<BoxBody>) -> String {
+                        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+                        String::from_utf8(body.to_vec()).unwrap()
+                    }
+                    """,
+                )
+
+                tokioTest("string_payloads_keep_their_content_type_parameters") {
+                    rust(
+                        """
+                        for content_type in ["text/plain; charset=utf-8", "text/plain;charset=UTF-8", "text/plain"] {
+                            let (response, received) = call("/text", content_type, "pikachu").await;
+                            assert_eq!(200, response.status(), "{content_type}");
+                            assert_eq!(Some(content_type), received.as_deref());
+                            assert_eq!("text/plain; charset=utf-8", response.headers()["content-type"]);
+                            assert_eq!("pikachu", body(response).await);
+                        }
+
+                        let (response, received) = call("/text", "application/json; charset=utf-8", "pikachu").await;
+                        assert_eq!(415, response.status());
+                        assert_eq!(None, received);
+                        """,
+                    )
+                }
+
+                tokioTest("blob_payloads_keep_their_content_type_parameters") {
+                    rust(
+                        """
+                        let (response, received) = call("/bytes", "application/octet-stream; version=2", "pikachu").await;
+                        assert_eq!(200, response.status());
+                        assert_eq!(Some("application/octet-stream; version=2"), received.as_deref());
+                        assert_eq!("application/octet-stream; version=2", response.headers()["content-type"]);
+                        assert_eq!("pikachu", body(response).await);
+
+                        let (response, received) = call("/bytes", "text/plain; version=2", "pikachu").await;
+                        assert_eq!(415, response.status());
+                        assert_eq!(None, received);
+                        """,
+                    )
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.server.smithy.protocols
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+import software.amazon.smithy.rust.codegen.server.smithy.ServerCargoDependency
+import software.amazon.smithy.rust.codegen.server.smithy.testutil.serverIntegrationTest
+
+internal class StreamingPayloadTrailersTest {
+    private val model =
+        """
+        namespace com.example
+
+        use aws.protocols#restJson1
+
+        @restJson1
+        service StreamingService {
+            operations: [StreamReport]
+        }
+
+        @readonly
+        @http(uri: "/report", method: "GET")
+        operation StreamReport {
+            input := {}
+            output := {
+                @httpPayload
+                report: StreamingBlob = ""
+            }
+        }
+
+        @streaming
+        blob StreamingBlob
+        """.asSmithyModel(smithyVersion = "2")
+
+    @Test
+    fun `streaming payloads of unknown length are sent with their trailers`() {
+        serverIntegrationTest(model) { _, rustCrate ->
+            rustCrate.testModule {
+                rust(
+                    """
+                    use aws_smithy_http_server::body::{Body, HttpBody};
+                    use std::sync::{Arc, Mutex};
+                    use tower::Service as _;
+                    use crate::types::ByteStream;
+                    use crate::{input, output};
+
+                    const CHUNKS: usize = 10_000;
+
+                    fn checksum(checksum: u64, chunk: &[u8]) -> u64 {
+                        chunk.iter().fold(checksum, |checksum, byte| checksum.wrapping_mul(31).wrapping_add(*byte as u64))
+                    }
+
+                    async fn stream_report(_input: input::StreamReportInput) -> output::StreamReportOutput {
+                        let (mut sender, body) = Body::channel();
+                        let sum = Arc::new(Mutex::new(None));
+                        tokio::spawn({
+                            let sum = sum.clone();
+                            async move {
+                                let mut checksum_so_far = 0;
+                                for i in 0..CHUNKS {
+                                    let chunk = format!("chunk {i}\n");
+                                    checksum_so_far = checksum(checksum_so_far, chunk.as_bytes());
+                                    sender.send_data(chunk.into()).await.unwrap();
+                                }
+                                // The body ends when `sender` is dropped, after the checksum is available.
+                                *sum.lock().unwrap() = Some(checksum_so_far);
+                            }
+                        });
+                        let report = ByteStream::from_body_0_4(body).with_trailers(|| async move {
+                            let sum = sum.lock().unwrap().expect("trailers are requested after the data");
+                            let mut trailers = http::HeaderMap::new();
+                            trailers.insert("x-checksum", sum.into());
+                            trailers
+                        });
+                        output::StreamReportOutput { report }
+                    }
+
+                    fn find(haystack: &[u8], needle: &[u8]) -> usize {
+                        haystack.windows(needle.len()).position(|window| window == needle).expect("needle is in haystack")
+                    }
+
+                    /// Decodes a chunked HTTP/1.1 message body into its data and the raw trailer section.
+                    fn decode_chunked(mut body: &[u8]) -> (Vec<u8>, String) {
+                        let mut data = Vec::new();
+                        loop {
+                            let line_end = find(body, b"\r\n");
+                            let size = std::str::from_utf8(&body[..line_end]).unwrap();
+                            let size = usize::from_str_radix(size.split(';').next().unwrap().trim(), 16).unwrap();
+                            body = &body[line_end + 2..];
+                            if size == 0 {
+                                return (data, String::from_utf8(body.to_vec()).unwrap());
+                            }
+                            data.extend_from_slice(&body[..size]);
+                            body = &body[size + 2..];
+                        }
+                    }
+                    """,
+                )
+
+                tokioTest("streams_chunks_and_trailers") {
+                    rust(
+                        """
+                        let config = crate::StreamingServiceConfig::builder().build();
+                        let mut service = crate::StreamingService::builder(config)
+                            .stream_report(stream_report)
+                            .build()
+                            .unwrap();
+                        let request = http::Request::builder()
+                            .method("GET")
+                            .uri("/report")
+                            .body(Body::empty())
+                            .unwrap();
+                        let response = service.call(request).await.unwrap();
+                        assert_eq!(200, response.status());
+                        assert!(response.headers().get("content-length").is_none());
+
+                        let mut body = response.into_body();
+                        assert_eq!(None, body.size_hint().exact());
+                        let (mut chunks, mut expected_checksum) = (0, 0);
+                        while let Some(chunk) = body.data().await {
+                            expected_checksum = checksum(expected_checksum, &chunk.unwrap());
+                            chunks += 1;
+                        }
+                        assert_eq!(CHUNKS, chunks);
+                        let trailers = body.trailers().await.unwrap().expect("the checksum trailer should be sent");
+                        assert_eq!(expected_checksum.to_string(), trailers["x-checksum"]);
+                        """,
+                    )
+                }
+
+                tokioTest("sends_chunks_and_trailers_over_http_1_1") {
+                    rustTemplate(
+                        """
+                        use std::io::{Read, Write};
+                        use tower::ServiceExt as _;
+
+                        let config = crate::StreamingServiceConfig::builder().build();
+                        let service = crate::StreamingService::builder(config)
+                            .stream_report(stream_report)
+                            .build()
+                            .unwrap()
+                            // HTTP/1.1 servers only send the trailer fields named in the `Trailer` header.
+                            .map_response(|mut response: http::Response<_>| {
+                                response.headers_mut().insert("trailer", http::HeaderValue::from_static("x-checksum"));
+                                response
+                            });
+                        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+                        let address = listener.local_addr().unwrap();
+                        let server = #{Hyper}::Server::from_tcp(listener)
+                            .unwrap()
+                            .http1_only(true)
+                            .serve(tower::make::Shared::new(service));
+                        tokio::spawn(server);
+
+                        // A raw client, to see exactly what is sent over the wire.
+                        let response = tokio::task::spawn_blocking(move || {
+                            let mut stream = std::net::TcpStream::connect(address).unwrap();
+                            stream
+                                .write_all(b"GET /report HTTP/1.1\r\nHost: localhost\r\nTE: trailers\r\nConnection: close\r\n\r\n")
+                                .unwrap();
+                            let mut response = Vec::new();
+                            stream.read_to_end(&mut response).unwrap();
+                            response
+                        })
+                        .await
+                        .unwrap();
+
+                        let head_end = find(&response, b"\r\n\r\n");
+                        let head = String::from_utf8(response[..head_end].to_vec()).unwrap().to_ascii_lowercase();
+                        assert!(head.starts_with("http/1.1 200"), "{head}");
+                        assert!(head.contains("\r\ntransfer-encoding: chunked"), "{head}");
+                        assert!(head.contains("\r\ntrailer: x-checksum"), "{head}");
+                        assert!(!head.contains("\r\ncontent-length:"), "{head}");
+
+                        let (data, trailers) = decode_chunked(&response[head_end + 4..]);
+                        assert_eq!(CHUNKS, data.split(|byte| *byte == b'\n').filter(|line| !line.is_empty()).count());
+                        let expected_checksum = checksum(0, &data);
+                        assert_eq!(format!("x-checksum: {expected_checksum}\r\n\r\n"), trailers.to_ascii_lowercase());
+                        """,
+                        "Hyper" to ServerCargoDependency.HyperDev.toType(),
+                    )
+                }
+            }
+        }
+    }
+}
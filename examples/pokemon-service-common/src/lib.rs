@@ -11,7 +11,8 @@ use std::{
     collections::HashMap,
     convert::TryInto,
     process::Child,
-    sync::{atomic::AtomicUsize, Arc},
+    sync::{atomic::AtomicUsize, Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 use async_stream::stream;
@@ -22,10 +23,11 @@ use pokemon_service_server_sdk::{
     error, input, model,
     model::CapturingPayload,
     output,
-    server::Extension,
+    server::{request::raw::RawHeaders, Extension},
     types::{Blob, ByteStream, SdkBody},
 };
 use rand::{seq::SliceRandom, Rng};
+use tokio::sync::RwLock;
 use tracing_subscriber::{prelude::*, EnvFilter};
 
 const PIKACHU_ENGLISH_FLAVOR_TEXT: &str =
@@ -69,6 +71,54 @@ struct PokemonTranslations {
     jp: String,
 }
 
+/// Upper bounds of the buckets of a [`LatencyHistogram`]. The last bucket has no upper bound.
+const LATENCY_BUCKET_BOUNDS: [Duration; 4] = [
+    Duration::from_millis(1),
+    Duration::from_millis(10),
+    Duration::from_millis(100),
+    Duration::from_secs(1),
+];
+
+/// Histogram of the latencies of the calls to an operation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    counts: [usize; LATENCY_BUCKET_BOUNDS.len() + 1],
+}
+
+impl LatencyHistogram {
+    fn record(&mut self, duration: Duration) {
+        let bucket = LATENCY_BUCKET_BOUNDS
+            .iter()
+            .position(|bound| duration <= *bound)
+            .unwrap_or(LATENCY_BUCKET_BOUNDS.len());
+        self.counts[bucket] += 1;
+    }
+
+    /// Returns the number of calls that took at most 1ms, 10ms, 100ms, 1s, and longer than 1s.
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+}
+
+/// Statistics about the calls to an operation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OperationStatistics {
+    calls: usize,
+    latencies: LatencyHistogram,
+}
+
+impl OperationStatistics {
+    /// Returns the number of calls to the operation.
+    pub fn calls(&self) -> usize {
+        self.calls
+    }
+
+    /// Returns the histogram of the latencies of the calls to the operation.
+    pub fn latencies(&self) -> &LatencyHistogram {
+        &self.latencies
+    }
+}
+
 /// PokémonService shared state.
 ///
 /// Some applications may want to manage state between handlers. Imagine having a database connection pool
@@ -129,6 +179,35 @@ struct PokemonTranslations {
 pub struct State {
     pokemons_translations: HashMap<String, PokemonTranslations>,
     call_count: AtomicUsize,
+    /// Statistics about the calls to each operation, by operation name.
+    operation_statistics: Mutex<HashMap<&'static str, OperationStatistics>>,
+    /// The passcode of each trainer, by name.
+    passcodes: HashMap<String, String>,
+    /// The Pokémon captured by each trainer, by name.
+    storage: RwLock<HashMap<String, Vec<String>>>,
+}
+
+impl State {
+    /// Returns the name of the trainer whose passcode is `passcode`.
+    fn trainer_with_passcode(&self, passcode: &str) -> Option<&str> {
+        self.passcodes
+            .iter()
+            .find(|(_, trainer_passcode)| *trainer_passcode == passcode)
+            .map(|(trainer, _)| trainer.as_str())
+    }
+
+    /// Records a call to `operation` that took `duration`.
+    pub fn record_call(&self, operation: &'static str, duration: Duration) {
+        let mut operation_statistics = self.operation_statistics.lock().unwrap();
+        let statistics = operation_statistics.entry(operation).or_default();
+        statistics.calls += 1;
+        statistics.latencies.record(duration);
+    }
+
+    /// Returns the statistics about the calls to each operation, by operation name.
+    pub fn operation_statistics(&self) -> HashMap<&'static str, OperationStatistics> {
+        self.operation_statistics.lock().unwrap().clone()
+    }
 }
 
 impl Default for State {
@@ -143,9 +222,14 @@ impl Default for State {
                 jp: String::from(PIKACHU_JAPANESE_FLAVOR_TEXT),
             },
         );
+        let mut passcodes = HashMap::new();
+        passcodes.insert(String::from("ash"), String::from("pikachu123"));
         Self {
             pokemons_translations,
             call_count: Default::default(),
+            operation_statistics: Default::default(),
+            passcodes,
+            storage: Default::default(),
         }
     }
 }
@@ -155,13 +239,14 @@ pub async fn get_pokemon_species(
     input: input::GetPokemonSpeciesInput,
     state: Extension<Arc<State>>,
 ) -> Result<output::GetPokemonSpeciesOutput, error::GetPokemonSpeciesError> {
+    let started = Instant::now();
     state
         .0
         .call_count
         .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
     // We only support retrieving information about Pikachu.
     let pokemon = state.0.pokemons_translations.get(&input.name);
-    match pokemon.as_ref() {
+    let result = match pokemon.as_ref() {
         Some(pokemon) => {
             tracing::debug!("Requested Pokémon is {}", input.name);
             let flavor_text_entries = vec![
@@ -196,24 +281,37 @@ pub async fn get_pokemon_species(
                 },
             ))
         }
-    }
+    };
+    state.0.record_call("GetPokemonSpecies", started.elapsed());
+    result
 }
 
 /// Retrieves the user's storage.
 pub async fn get_storage(
     input: input::GetStorageInput,
-    _state: Extension<Arc<State>>,
+    state: Extension<Arc<State>>,
 ) -> Result<output::GetStorageOutput, error::GetStorageError> {
+    let started = Instant::now();
     tracing::debug!("attempting to authenticate storage user");
 
-    // We currently only support Ash and he has nothing stored
-    if !(input.user == "ash" && input.passcode == "pikachu123") {
+    let result = if state.0.passcodes.get(&input.user) != Some(&input.passcode) {
         tracing::debug!("authentication failed");
-        return Err(error::GetStorageError::StorageAccessNotAuthorized(
+        Err(error::GetStorageError::StorageAccessNotAuthorized(
             error::StorageAccessNotAuthorized {},
-        ));
-    }
-    Ok(output::GetStorageOutput { collection: vec![] })
+        ))
+    } else {
+        let collection = state
+            .0
+            .storage
+            .read()
+            .await
+            .get(&input.user)
+            .cloned()
+            .unwrap_or_default();
+        Ok(output::GetStorageOutput { collection })
+    };
+    state.0.record_call("GetStorage", started.elapsed());
+    result
 }
 
 /// Calculates and reports metrics about this server instance.
@@ -221,6 +319,15 @@ pub async fn get_server_statistics(
     _input: input::GetServerStatisticsInput,
     state: Extension<Arc<State>>,
 ) -> output::GetServerStatisticsOutput {
+    let started = Instant::now();
+    for (operation, statistics) in state.0.operation_statistics() {
+        tracing::debug!(
+            operation,
+            calls = statistics.calls(),
+            latencies = ?statistics.latencies().counts(),
+            "operation statistics"
+        );
+    }
     // Read the current calls count.
     let counter = state.0.call_count.load(std::sync::atomic::Ordering::SeqCst);
     let calls_count = counter
@@ -230,23 +337,45 @@ pub async fn get_server_statistics(
         })
         .unwrap_or(0);
     tracing::debug!("This instance served {} requests", counter);
-    output::GetServerStatisticsOutput { calls_count }
+    state
+        .0
+        .record_call("GetServerStatistics", started.elapsed());
+    output::GetServerStatisticsOutput {
+        calls_count,
+        active_connections: None,
+        active_streaming_operations: None,
+        active_streaming_operations_by_operation: None,
+        streamed_bytes: None,
+    }
 }
 
 /// Attempts to capture a Pokémon.
+///
+/// The Pokémon captured by a trainer who sent their passcode in the `passcode` header are added to
+/// their storage.
 pub async fn capture_pokemon(
     mut input: input::CapturePokemonInput,
+    state: Extension<Arc<State>>,
+    headers: RawHeaders,
 ) -> Result<output::CapturePokemonOutput, error::CapturePokemonError> {
+    let started = Instant::now();
     if input.region != "Kanto" {
+        state.0.record_call("CapturePokemon", started.elapsed());
         return Err(error::CapturePokemonError::UnsupportedRegionError(
             error::UnsupportedRegionError {
                 region: input.region,
             },
         ));
     }
+    let trainer = headers
+        .get("passcode")
+        .and_then(|passcode| passcode.to_str().ok())
+        .and_then(|passcode| state.0.trainer_with_passcode(passcode))
+        .map(str::to_owned);
+    // Only the time taken to start capturing is recorded, since the events are streamed afterwards.
+    state.0.record_call("CapturePokemon", started.elapsed());
     let output_stream = stream! {
         loop {
-            use std::time::Duration;
             match input.events.recv().await {
                 Ok(maybe_event) => match maybe_event {
                     Some(event) => {
@@ -278,6 +407,9 @@ pub async fn capture_pokemon(
                                         .name()
                                         .unwrap_or("")
                                         .to_string();
+                                    if let Some(trainer) = &trainer {
+                                        state.0.storage.write().await.entry(trainer.clone()).or_default().push(pokemon.clone());
+                                    }
                                     let pokedex: Vec<u8> = (0..255).collect();
                                     yield Ok(crate::model::CapturePokemonEvents::Event(
                                         crate::model::CaptureEvent {
@@ -375,4 +507,57 @@ mod tests {
         let stats = get_server_statistics(input, Extension(state.clone())).await;
         assert_eq!(1, stats.calls_count);
     }
+
+    #[tokio::test]
+    async fn calls_are_recorded_by_operation() {
+        let state = Arc::new(State::default());
+
+        let input = input::GetPokemonSpeciesInput {
+            name: String::from("pikachu"),
+        };
+        get_pokemon_species(input, Extension(state.clone()))
+            .await
+            .unwrap();
+        let input = input::GetStorageInput {
+            user: String::from("ash"),
+            passcode: String::from("pikachu123"),
+        };
+        get_storage(input, Extension(state.clone())).await.unwrap();
+        let input = input::GetStorageInput {
+            user: String::from("ash"),
+            passcode: String::from("pikachu321"),
+        };
+        get_storage(input, Extension(state.clone()))
+            .await
+            .unwrap_err();
+
+        let statistics = state.operation_statistics();
+        assert_eq!(1, statistics["GetPokemonSpecies"].calls());
+        assert_eq!(2, statistics["GetStorage"].calls());
+        assert!(!statistics.contains_key("GetServerStatistics"));
+        assert_eq!(
+            2,
+            statistics["GetStorage"]
+                .latencies()
+                .counts()
+                .iter()
+                .sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn latencies_are_recorded_in_buckets() {
+        let state = State::default();
+        state.record_call("DoNothing", Duration::from_micros(10));
+        state.record_call("DoNothing", Duration::from_millis(10));
+        state.record_call("DoNothing", Duration::from_millis(50));
+        state.record_call("DoNothing", Duration::from_secs(2));
+
+        let statistics = state.operation_statistics();
+        assert_eq!(4, statistics["DoNothing"].calls());
+        assert_eq!(
+            &[1, 1, 1, 0, 1],
+            statistics["DoNothing"].latencies().counts()
+        );
+    }
 }
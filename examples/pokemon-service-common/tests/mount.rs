@@ -0,0 +1,145 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use pokemon_service_client::{Client, Config};
+use pokemon_service_common::{check_health, get_pokemon_species, State};
+use pokemon_service_server_sdk::{
+    server::{
+        body::{to_boxed, BoxBody},
+        routing::{Route, RouterBuilder},
+        AddExtensionLayer,
+    },
+    PokemonService, PokemonServiceConfig,
+};
+use tower::{service_fn, Layer, Service};
+
+/// A layer logging the path of every request it sees.
+#[derive(Clone, Default)]
+struct AccessLogLayer(Arc<Mutex<Vec<String>>>);
+
+impl AccessLogLayer {
+    fn paths(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl<S> Layer<S> for AccessLogLayer {
+    type Service = AccessLog<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AccessLog {
+            inner,
+            log: self.0.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct AccessLog<S> {
+    inner: S,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl<S, B> Service<http::Request<B>> for AccessLog<S>
+where
+    S: Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        self.log
+            .lock()
+            .unwrap()
+            .push(request.uri().path().to_owned());
+        self.inner.call(request)
+    }
+}
+
+/// Builds a Pokémon service whose requests are logged by `access_log`.
+fn pokemon_service(access_log: AccessLogLayer) -> Route {
+    let config = PokemonServiceConfig::builder()
+        .layer(AddExtensionLayer::new(Arc::new(State::default())))
+        .layer(access_log)
+        .build();
+    let app = PokemonService::builder(config)
+        .get_pokemon_species(get_pokemon_species)
+        .check_health(check_health)
+        .build_unchecked();
+    Route::new(app)
+}
+
+fn client(base_url: String) -> Client {
+    Client::from_conf(Config::builder().endpoint_url(base_url).build())
+}
+
+#[tokio::test]
+async fn services_are_mounted_at_their_prefixes() {
+    let admin_log = AccessLogLayer::default();
+    let public_log = AccessLogLayer::default();
+    let health_check = service_fn(|_request| async {
+        Ok::<_, Infallible>(http::Response::<BoxBody>::new(to_boxed("ok")))
+    });
+    let app = RouterBuilder::new()
+        .mount("/admin", pokemon_service(admin_log.clone()))
+        .mount("/v1", pokemon_service(public_log.clone()))
+        .route_raw("/healthz", health_check)
+        .build()
+        .unwrap();
+    let server =
+        hyper::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(app.into_make_service());
+    let address = server.local_addr();
+    tokio::spawn(server);
+
+    let admin = client(format!("http://{address}/admin"));
+    let public = client(format!("http://{address}/v1"));
+    let species = admin
+        .get_pokemon_species()
+        .name("pikachu")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!("pikachu", species.name());
+    public
+        .get_pokemon_species()
+        .name("pikachu")
+        .send()
+        .await
+        .unwrap();
+    public.check_health().send().await.unwrap();
+
+    let http_client = hyper::Client::new();
+    let health = http_client
+        .get(format!("http://{address}/healthz").parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(200, health.status());
+    let body = hyper::body::to_bytes(health.into_body()).await.unwrap();
+    assert_eq!("ok", body);
+    let unmounted = http_client
+        .get(format!("http://{address}/v2/ping").parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(404, unmounted.status());
+
+    // Each service only sees its own requests, without their prefix.
+    assert_eq!(vec!["/pokemon-species/pikachu"], admin_log.paths());
+    assert_eq!(
+        vec!["/pokemon-species/pikachu", "/ping"],
+        public_log.paths()
+    );
+}
@@ -0,0 +1,202 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::{
+    convert::Infallible,
+    future::{ready, Ready},
+    task::{Context, Poll},
+};
+
+use pokemon_service_server_sdk::{
+    server::{
+        body::{to_boxed, Body, BoxBody},
+        routing::DotSegments,
+    },
+    PokemonService, PokemonServiceConfig,
+};
+use tower::{Service, ServiceExt};
+
+/// A raw service that responds with the name of the operation it was registered for, and the
+/// path of the request it received.
+#[derive(Clone)]
+struct RespondWithName(&'static str);
+
+impl Service<http::Request<Body>> for RespondWithName {
+    type Response = http::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+        let response = http::Response::builder()
+            .header("x-operation", self.0)
+            .body(to_boxed(request.uri().path().to_owned()))
+            .unwrap();
+        ready(Ok(response))
+    }
+}
+
+async fn call(dot_segments: DotSegments, path: &str) -> http::Response<BoxBody> {
+    let config = PokemonServiceConfig::builder().build();
+    let app = PokemonService::builder(config)
+        .dot_segments(dot_segments)
+        .get_pokemon_species_service_raw(RespondWithName("GetPokemonSpecies"))
+        .get_server_statistics_service_raw(RespondWithName("GetServerStatistics"))
+        .check_health_service_raw(RespondWithName("CheckHealth"))
+        .build_unchecked();
+    let request = http::Request::get(path).body(Body::empty()).unwrap();
+    app.oneshot(request).await.unwrap()
+}
+
+enum Expected {
+    Routed(&'static str),
+    Status(u16),
+}
+
+use Expected::{Routed, Status};
+
+#[tokio::test]
+async fn hostile_paths_are_routed_according_to_the_dot_segment_policy() {
+    // (path, `Reject`, `Normalize`, `Preserve`)
+    let cases = [
+        // A label that is a dot-segment would route to a different path once normalized.
+        (
+            "/pokemon-species/..",
+            Status(400),
+            Status(400),
+            Routed("GetPokemonSpecies"),
+        ),
+        (
+            "/pokemon-species/.",
+            Status(400),
+            Status(400),
+            Routed("GetPokemonSpecies"),
+        ),
+        (
+            "/pokemon-species/%2e%2e",
+            Status(400),
+            Status(400),
+            Routed("GetPokemonSpecies"),
+        ),
+        (
+            "/pokemon-species/%2E.",
+            Status(400),
+            Status(400),
+            Routed("GetPokemonSpecies"),
+        ),
+        // Paths that only match a route once normalized.
+        (
+            "/pokemon-species/../stats",
+            Status(400),
+            Routed("GetServerStatistics"),
+            Status(404),
+        ),
+        (
+            "/stats/../ping",
+            Status(400),
+            Routed("CheckHealth"),
+            Status(404),
+        ),
+        ("/./ping", Status(400), Routed("CheckHealth"), Status(404)),
+        (
+            "/pokedex/../pokemon-species/pikachu",
+            Status(400),
+            Routed("GetPokemonSpecies"),
+            Status(404),
+        ),
+        (
+            "/pokemon-species/pikachu/..",
+            Status(400),
+            Status(404),
+            Status(404),
+        ),
+        // Encoded slashes that reveal a traversal once decoded.
+        (
+            "/pokemon-species/..%2fstats",
+            Status(400),
+            Status(400),
+            Routed("GetPokemonSpecies"),
+        ),
+        (
+            "/pokemon-species/..%5Cstats",
+            Status(400),
+            Status(400),
+            Routed("GetPokemonSpecies"),
+        ),
+        (
+            "/pokemon-species/%2e%2e%2Fstats",
+            Status(400),
+            Status(400),
+            Routed("GetPokemonSpecies"),
+        ),
+        // Paths without dot-segments.
+        ("//stats", Status(404), Status(404), Status(404)),
+        (
+            "/pokemon-species/pika..chu",
+            Routed("GetPokemonSpecies"),
+            Routed("GetPokemonSpecies"),
+            Routed("GetPokemonSpecies"),
+        ),
+        (
+            "/pokemon-species/...",
+            Routed("GetPokemonSpecies"),
+            Routed("GetPokemonSpecies"),
+            Routed("GetPokemonSpecies"),
+        ),
+    ];
+
+    for (path, reject, normalize, preserve) in cases {
+        for (policy, expected) in [
+            (DotSegments::Reject, reject),
+            (DotSegments::Normalize, normalize),
+            (DotSegments::Preserve, preserve),
+        ] {
+            let response = call(policy, path).await;
+            let operation = response.headers().get("x-operation");
+            match expected {
+                Routed(name) => assert_eq!(
+                    Some(name),
+                    operation.map(|name| name.to_str().unwrap()),
+                    "{path} with {policy:?}"
+                ),
+                Status(status) => {
+                    assert_eq!(None, operation, "{path} with {policy:?}");
+                    assert_eq!(status, response.status().as_u16(), "{path} with {policy:?}");
+                }
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn rejection_is_the_default() {
+    let config = PokemonServiceConfig::builder().build();
+    let app = PokemonService::builder(config)
+        .get_server_statistics_service_raw(RespondWithName("GetServerStatistics"))
+        .build_unchecked();
+    let request = http::Request::get("/ping/../stats")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn handlers_receive_the_normalized_path() {
+    let response = call(
+        DotSegments::Normalize,
+        "/pokedex/./../pokemon-species/%2E/pikachu?lang=en",
+    )
+    .await;
+    assert_eq!(
+        response.headers().get("x-operation").unwrap(),
+        "GetPokemonSpecies"
+    );
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    assert_eq!(&body[..], b"/pokemon-species/pikachu");
+}
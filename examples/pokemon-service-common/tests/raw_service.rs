@@ -0,0 +1,69 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::convert::Infallible;
+
+use pokemon_service_server_sdk::{
+    server::body::{to_boxed, Body, BoxBody},
+    PokemonService, PokemonServiceConfig,
+};
+use tower::Service;
+
+use aws_smithy_runtime::client::http::test_util::{capture_request, CaptureRequestReceiver};
+use pokemon_service_client::{Client, Config};
+use pokemon_service_common::do_nothing;
+
+async fn legacy_get_server_statistics(
+    _request: http::Request<Body>,
+) -> Result<http::Response<BoxBody>, Infallible> {
+    let response = http::Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(to_boxed(r#"{"calls_count":42}"#))
+        .unwrap();
+    Ok(response)
+}
+
+fn client_with_capture() -> (Client, CaptureRequestReceiver) {
+    let (http_client, rcvr) = capture_request(None);
+    let config = Config::builder()
+        .http_client(http_client)
+        .endpoint_url("http://localhost:1234")
+        .build();
+    (Client::from_conf(config), rcvr)
+}
+
+#[tokio::test]
+async fn raw_service_coexists_with_generated_handlers() {
+    let config = PokemonServiceConfig::builder().build();
+    let mut app = PokemonService::builder(config)
+        .get_server_statistics_service_raw(tower::service_fn(legacy_get_server_statistics))
+        .do_nothing(do_nothing)
+        .build_unchecked();
+
+    // The operation wired to the raw service is routed to it, and its hand-built response is returned as-is.
+    let request = {
+        let (client, rcvr) = client_with_capture();
+        let _ = client.get_server_statistics().send().await;
+        rcvr.expect_request()
+    };
+    let response = app.call(request.try_into_http02x().unwrap()).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("Content-Type").unwrap(),
+        "application/json"
+    );
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    assert_eq!(&body[..], br#"{"calls_count":42}"#);
+
+    // The other operations are still served by their generated handlers.
+    let request = {
+        let (client, rcvr) = client_with_capture();
+        let _ = client.do_nothing().send().await;
+        rcvr.expect_request()
+    };
+    let response = app.call(request.try_into_http02x().unwrap()).await.unwrap();
+    assert_eq!(response.status(), 200);
+}
@@ -0,0 +1,117 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::{net::SocketAddr, sync::Arc};
+
+use async_stream::stream;
+use pokemon_service_client::{
+    types::{AttemptCapturingPokemonEvent, CapturingEvent, CapturingPayload},
+    Client, Config,
+};
+use pokemon_service_common::{capture_pokemon, get_storage, State};
+use pokemon_service_server_sdk::{server::AddExtensionLayer, PokemonService, PokemonServiceConfig};
+
+/// Serves `capture_pokemon` and `get_storage` on a random port, and returns a client for them.
+fn serve() -> Client {
+    let config = PokemonServiceConfig::builder()
+        .layer(AddExtensionLayer::new(Arc::new(State::default())))
+        .build();
+    let app = PokemonService::builder(config)
+        .capture_pokemon(capture_pokemon)
+        .get_storage(get_storage)
+        .build_unchecked();
+    let server =
+        hyper::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(app.into_make_service());
+    let address = server.local_addr();
+    tokio::spawn(server);
+
+    let config = Config::builder()
+        .endpoint_url(format!("http://{address}"))
+        .build();
+    Client::from_conf(config)
+}
+
+/// Captures `name` with a Master Ball, which never fails, sending `passcode` if there is one.
+async fn capture(client: &Client, name: &'static str, passcode: Option<&'static str>) {
+    let input_stream = stream! {
+        yield Ok(AttemptCapturingPokemonEvent::Event(
+            CapturingEvent::builder()
+            .payload(CapturingPayload::builder()
+                .name(name)
+                .pokeball("Master Ball")
+                .build())
+            .build()
+        ));
+    };
+    let mut output = client
+        .capture_pokemon()
+        .region("Kanto")
+        .events(input_stream.into())
+        .customize()
+        .mutate_request(move |request| {
+            if let Some(passcode) = passcode {
+                request.headers_mut().insert("passcode", passcode);
+            }
+        })
+        .send()
+        .await
+        .unwrap();
+    let capture = output
+        .events
+        .recv()
+        .await
+        .unwrap()
+        .expect("a capture event");
+    assert_eq!(Some(name), capture.as_event().unwrap().name.as_deref());
+}
+
+async fn storage(client: &Client) -> Vec<String> {
+    client
+        .get_storage()
+        .user("ash")
+        .passcode("pikachu123")
+        .send()
+        .await
+        .unwrap()
+        .collection
+}
+
+#[tokio::test]
+async fn captured_pokemon_are_stored() {
+    let client = serve();
+    assert!(storage(&client).await.is_empty());
+
+    capture(&client, "Pikachu", Some("pikachu123")).await;
+    capture(&client, "Charizard", Some("pikachu123")).await;
+
+    assert_eq!(vec!["Pikachu", "Charizard"], storage(&client).await);
+}
+
+#[tokio::test]
+async fn anonymous_captures_are_not_stored() {
+    let client = serve();
+
+    capture(&client, "Pikachu", None).await;
+    capture(&client, "Regieleki", Some("unknown")).await;
+
+    assert!(storage(&client).await.is_empty());
+}
+
+#[tokio::test]
+async fn storage_requires_the_passcode_of_the_user() {
+    let client = serve();
+
+    let error = client
+        .get_storage()
+        .user("ash")
+        .passcode("pikachu321")
+        .send()
+        .await
+        .unwrap_err();
+    assert!(error
+        .as_service_error()
+        .expect("a modeled error")
+        .is_storage_access_not_authorized());
+}
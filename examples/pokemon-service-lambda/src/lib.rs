@@ -5,9 +5,9 @@
 
 use std::sync::Arc;
 
-use pokemon_service_common::State;
+use pokemon_service_common::{get_storage, State};
 use pokemon_service_server_sdk::{
-    error::{GetStorageError, StorageAccessNotAuthorized},
+    error::GetStorageError,
     input::GetStorageInput,
     output::GetStorageOutput,
     server::{request::lambda::Context, Extension},
@@ -16,17 +16,9 @@ use pokemon_service_server_sdk::{
 /// Retrieves the user's storage and logs the lambda request ID.
 pub async fn get_storage_lambda(
     input: GetStorageInput,
-    _state: Extension<Arc<State>>,
+    state: Extension<Arc<State>>,
     context: Context,
 ) -> Result<GetStorageOutput, GetStorageError> {
     tracing::debug!(request_id = %context.request_id, "attempting to authenticate storage user");
-
-    // We currently only support Ash and he has nothing stored
-    if !(input.user == "ash" && input.passcode == "pikachu123") {
-        tracing::debug!("authentication failed");
-        return Err(GetStorageError::StorageAccessNotAuthorized(
-            StorageAccessNotAuthorized {},
-        ));
-    }
-    Ok(GetStorageOutput { collection: vec![] })
+    get_storage(input, state).await
 }
@@ -3,13 +3,24 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use std::net::{IpAddr, SocketAddr};
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use pokemon_service_common::State;
 
 use pokemon_service_server_sdk::{
     error::{GetStorageError, StorageAccessNotAuthorized},
-    input::{DoNothingInput, GetStorageInput},
-    output::{DoNothingOutput, GetStorageOutput},
-    server::request::{connect_info::ConnectInfo, request_id::ServerRequestId},
+    input::{DoNothingInput, GetServerStatisticsInput, GetStorageInput},
+    operation_shape::GetStorage,
+    output::{DoNothingOutput, GetServerStatisticsOutput, GetStorageOutput},
+    server::{
+        auth::{AuthError, AuthPolicy, AuthRequirement},
+        connection_stats::ConnectionStats,
+        request::{connect_info::ConnectInfo, raw::RawHeaders, request_id::ServerRequestId},
+        Extension,
+    },
 };
 
 // Defaults shared between `main.rs` and `/tests`.
@@ -25,15 +36,93 @@ pub async fn do_nothing_but_log_request_ids(
     DoNothingOutput {}
 }
 
-/// Retrieves the user's storage. No authentication required for locals.
+/// Reports metrics about this server instance, like `get_server_statistics`, along with the
+/// [`ConnectionStats`] of the server.
+///
+/// The `x-feature-flags` header isn't part of the model: it is set by the gateway in front of the
+/// service, and read from the [`RawHeaders`] of the request.
+pub async fn get_server_statistics_with_feature_flags(
+    input: GetServerStatisticsInput,
+    state: Extension<Arc<State>>,
+    connection_stats: Extension<Arc<ConnectionStats>>,
+    headers: RawHeaders,
+) -> GetServerStatisticsOutput {
+    let flags = headers
+        .get("x-feature-flags")
+        .and_then(|flags| flags.to_str().ok());
+    if let Some(flags) = flags {
+        tracing::debug!(%flags, "feature flags");
+    }
+    let to_long = |value: u64| i64::try_from(value).unwrap_or(i64::MAX);
+    let connection_stats = connection_stats.0;
+    GetServerStatisticsOutput {
+        active_connections: Some(to_long(connection_stats.active_connections())),
+        active_streaming_operations: Some(to_long(connection_stats.active_streaming_operations())),
+        active_streaming_operations_by_operation: Some(
+            connection_stats
+                .active_streaming_operations_by_operation()
+                .into_iter()
+                .map(|(operation, active)| (operation.name().to_owned(), to_long(active)))
+                .collect(),
+        ),
+        streamed_bytes: Some(to_long(connection_stats.streamed_bytes())),
+        ..pokemon_service_common::get_server_statistics(input, state).await
+    }
+}
+
+/// A trainer authenticated by the `passcode` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trainer {
+    pub name: &'static str,
+    /// Whether the trainer is allowed to look at their Pokémon storage.
+    pub storage_access: bool,
+}
+
+/// Authenticates the trainer whose passcode is in the `passcode` header.
+///
+/// Requests without a `passcode` header are anonymous.
+pub fn authenticate_trainer(
+    parts: &http::request::Parts,
+) -> impl std::future::Future<Output = Result<Option<Trainer>, AuthError>> {
+    let passcode = parts.headers.get("passcode").cloned();
+    async move {
+        let Some(passcode) = passcode else {
+            return Ok(None);
+        };
+        match passcode.as_bytes() {
+            b"pikachu123" => Ok(Some(Trainer {
+                name: "ash",
+                storage_access: true,
+            })),
+            b"onix123" => Ok(Some(Trainer {
+                name: "brock",
+                storage_access: false,
+            })),
+            _ => {
+                tracing::debug!("authentication failed");
+                Err(AuthError::unauthenticated("unknown passcode"))
+            }
+        }
+    }
+}
+
+/// Every operation can be called anonymously, except `GetStorage` which requires a trainer with
+/// storage access.
+pub fn auth_policy() -> AuthPolicy<Trainer> {
+    AuthPolicy::new(AuthRequirement::anonymous()).operation::<GetStorage>(
+        AuthRequirement::capability("storage", |trainer: &Trainer| trainer.storage_access),
+    )
+}
+
+/// Retrieves the trainer's storage. Trainers in our local gym get to see all of their Pokémon.
 pub async fn get_storage_with_local_approved(
     input: GetStorageInput,
+    trainer: Extension<Trainer>,
     connect_info: ConnectInfo<SocketAddr>,
 ) -> Result<GetStorageOutput, GetStorageError> {
-    tracing::debug!("attempting to authenticate storage user");
-
-    if !(input.user == "ash" && input.passcode == "pikachu123") {
-        tracing::debug!("authentication failed");
+    // The trainer is authenticated, but can only look at their own storage
+    if input.user != trainer.name {
+        tracing::debug!(trainer = trainer.name, user = %input.user, "storage of another trainer");
         return Err(GetStorageError::StorageAccessNotAuthorized(
             StorageAccessNotAuthorized {},
         ));
@@ -6,10 +6,12 @@
 mod authz;
 mod plugin;
 
-use std::{net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 use clap::Parser;
 use pokemon_service_server_sdk::server::{
+    auth::{AuthExt, AuthnLayer},
+    connection_stats::{ConnectionStats, ConnectionStatsLayer, TrackConnections},
     extension::OperationExtensionExt,
     instrumentation::InstrumentExt,
     layer::alb_health_check::AlbHealthCheckLayer,
@@ -22,11 +24,12 @@ use hyper::StatusCode;
 use plugin::PrintExt;
 
 use pokemon_service::{
-    do_nothing_but_log_request_ids, get_storage_with_local_approved, DEFAULT_ADDRESS, DEFAULT_PORT,
+    auth_policy, authenticate_trainer, do_nothing_but_log_request_ids,
+    get_server_statistics_with_feature_flags, get_storage_with_local_approved, DEFAULT_ADDRESS,
+    DEFAULT_PORT,
 };
 use pokemon_service_common::{
-    capture_pokemon, check_health, get_pokemon_species, get_server_statistics, setup_tracing,
-    stream_pokemon_radio, State,
+    capture_pokemon, check_health, get_pokemon_species, setup_tracing, stream_pokemon_radio, State,
 };
 use pokemon_service_server_sdk::{scope, PokemonService, PokemonServiceConfig};
 
@@ -66,7 +69,16 @@ pub async fn main() {
         // `Response::extensions`, or infer routing failure when it's missing.
         .insert_operation_extension()
         // Adds `tracing` spans and events to the request lifecycle.
-        .instrument();
+        .instrument()
+        // Rejects requests that don't meet the auth requirement of their operation, before they reach the handler.
+        .auth(auth_policy());
+
+    // Count the open connections and event streams, reported by `GetServerStatistics` and logged
+    // every minute.
+    let connection_stats = Arc::new(ConnectionStats::new());
+    connection_stats
+        .clone()
+        .report_every(Duration::from_secs(60));
 
     let authz_plugin = AuthorizationPlugin::new();
     let model_plugins = ModelPlugins::new().push(authz_plugin);
@@ -74,12 +86,17 @@ pub async fn main() {
     let config = PokemonServiceConfig::builder()
         // Set up shared state and middlewares.
         .layer(AddExtensionLayer::new(Arc::new(State::default())))
+        .layer(AddExtensionLayer::new(connection_stats.clone()))
+        // Count the operations streaming their response, using the `OperationExtension`.
+        .layer(ConnectionStatsLayer::new(connection_stats.clone()))
         // Handle `/ping` health check requests.
         .layer(AlbHealthCheckLayer::from_handler("/ping", |_req| async {
             StatusCode::OK
         }))
         // Add server request IDs.
         .layer(ServerRequestIdProviderLayer::new())
+        // Authenticate trainers, so that handlers can take an `Extension<Trainer>`.
+        .layer(AuthnLayer::new(authenticate_trainer))
         .http_plugin(http_plugins)
         .model_plugin(model_plugins)
         .build();
@@ -90,7 +107,7 @@ pub async fn main() {
         // return the operation's output.
         .get_pokemon_species(get_pokemon_species)
         .get_storage(get_storage_with_local_approved)
-        .get_server_statistics(get_server_statistics)
+        .get_server_statistics(get_server_statistics_with_feature_flags)
         .capture_pokemon(capture_pokemon)
         .do_nothing(do_nothing_but_log_request_ids)
         .check_health(check_health)
@@ -99,8 +116,11 @@ pub async fn main() {
         .expect("failed to build an instance of PokemonService");
 
     // Using `into_make_service_with_connect_info`, rather than `into_make_service`, to adjoin the `SocketAddr`
-    // connection info.
-    let make_app = app.into_make_service_with_connect_info::<SocketAddr>();
+    // connection info, and count the connections it serves.
+    let make_app = TrackConnections::new(
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+        connection_stats,
+    );
 
     // Bind the application to a socket.
     let bind: SocketAddr = format!("{}:{}", args.address, args.port)
@@ -0,0 +1,92 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+pub mod common;
+
+use async_stream::stream;
+use serial_test::serial;
+use tokio::sync::oneshot;
+
+use pokemon_service_client::{
+    operation::capture_pokemon::CapturePokemonOutput,
+    types::{AttemptCapturingPokemonEvent, CapturingEvent, CapturingPayload},
+    Client,
+};
+
+/// Opens a `CapturePokemon` event stream which captures `name` with a Master Ball, and stays open
+/// until `close` is sent.
+async fn open_stream(
+    client: &Client,
+    name: &'static str,
+    close: oneshot::Receiver<()>,
+) -> CapturePokemonOutput {
+    let input_stream = stream! {
+        yield Ok(AttemptCapturingPokemonEvent::Event(
+            CapturingEvent::builder()
+            .payload(CapturingPayload::builder()
+                .name(name)
+                .pokeball("Master Ball")
+                .build())
+            .build()
+        ));
+        let _ = close.await;
+    };
+    let mut output = client
+        .capture_pokemon()
+        .region("Kanto")
+        .events(input_stream.into())
+        .send()
+        .await
+        .unwrap();
+    let capture = output
+        .events
+        .recv()
+        .await
+        .unwrap()
+        .expect("a capture event");
+    assert_eq!(Some(name), capture.as_event().unwrap().name.as_deref());
+    output
+}
+
+#[tokio::test]
+#[serial]
+async fn open_event_streams_are_counted() {
+    let _child = common::run_server().await;
+    let client = common::client();
+
+    let (close_first, first_closed) = oneshot::channel();
+    let (close_second, second_closed) = oneshot::channel();
+    let (mut first, mut second) = tokio::join!(
+        open_stream(&client, "Pikachu", first_closed),
+        open_stream(&client, "Charizard", second_closed),
+    );
+
+    let stats = client.get_server_statistics().send().await.unwrap();
+    assert_eq!(Some(2), stats.active_streaming_operations);
+    assert_eq!(
+        Some(&2),
+        stats
+            .active_streaming_operations_by_operation
+            .as_ref()
+            .and_then(|by_operation| by_operation.get("CapturePokemon"))
+    );
+    // Both streams, and the `GetServerStatistics` request, have a connection of their own
+    assert!(stats.active_connections.unwrap() >= 3, "{stats:?}");
+    assert!(stats.streamed_bytes.unwrap() > 0, "{stats:?}");
+
+    close_first.send(()).unwrap();
+    close_second.send(()).unwrap();
+    assert!(first.events.recv().await.unwrap().is_none());
+    assert!(second.events.recv().await.unwrap().is_none());
+
+    let stats = client.get_server_statistics().send().await.unwrap();
+    assert_eq!(Some(0), stats.active_streaming_operations);
+    assert_eq!(
+        Some(true),
+        stats
+            .active_streaming_operations_by_operation
+            .map(|by_operation| by_operation.is_empty())
+    );
+}
@@ -32,22 +32,6 @@ async fn simple_integration_test() {
     let service_statistics_out = client.get_server_statistics().send().await.unwrap();
     assert_eq!(1, service_statistics_out.calls_count);
 
-    let storage_err = client
-        .get_storage()
-        .user("ash")
-        .passcode("pikachu321")
-        .send()
-        .await;
-    let has_not_authorized_error = if let Err(SdkError::ServiceError(context)) = storage_err {
-        matches!(
-            context.err(),
-            GetStorageError::StorageAccessNotAuthorized(StorageAccessNotAuthorized { .. }),
-        )
-    } else {
-        false
-    };
-    assert!(has_not_authorized_error, "expected NotAuthorized error");
-
     let storage_out = client
         .get_storage()
         .user("ash")
@@ -90,6 +74,73 @@ async fn simple_integration_test() {
     assert_eq!(result.status(), 200);
 }
 
+#[tokio::test]
+#[serial]
+async fn auth() {
+    let _child = common::run_server().await;
+    let client = common::client();
+
+    // Anonymous requests are allowed for operations other than `GetStorage`.
+    client
+        .get_pokemon_species()
+        .name("pikachu")
+        .send()
+        .await
+        .expect("anonymous requests are permitted");
+
+    // Unknown passcodes are rejected before reaching the handler.
+    let storage_err = client
+        .get_storage()
+        .user("ash")
+        .passcode("pikachu321")
+        .send()
+        .await;
+    let status = match storage_err {
+        Err(SdkError::ServiceError(context)) => context.raw().status().as_u16(),
+        other => panic!("expected a service error, got {other:?}"),
+    };
+    assert_eq!(401, status);
+
+    // Brock is authenticated, but isn't allowed to access storage.
+    let storage_err = client
+        .get_storage()
+        .user("brock")
+        .passcode("onix123")
+        .send()
+        .await;
+    let status = match storage_err {
+        Err(SdkError::ServiceError(context)) => context.raw().status().as_u16(),
+        other => panic!("expected a service error, got {other:?}"),
+    };
+    assert_eq!(403, status);
+
+    // Ash is allowed to access storage, but only his own.
+    let storage_err = client
+        .get_storage()
+        .user("brock")
+        .passcode("pikachu123")
+        .send()
+        .await;
+    let has_not_authorized_error = if let Err(SdkError::ServiceError(context)) = storage_err {
+        matches!(
+            context.err(),
+            GetStorageError::StorageAccessNotAuthorized(StorageAccessNotAuthorized { .. }),
+        )
+    } else {
+        false
+    };
+    assert!(has_not_authorized_error, "expected NotAuthorized error");
+
+    let storage_out = client
+        .get_storage()
+        .user("ash")
+        .passcode("pikachu123")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(4, storage_out.collection.len());
+}
+
 #[tokio::test]
 #[serial]
 async fn health_check() {
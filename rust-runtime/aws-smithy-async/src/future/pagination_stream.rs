@@ -12,6 +12,7 @@ use std::task::{Context, Poll};
 
 pub mod collect;
 pub mod fn_stream;
+pub mod prefetch;
 use fn_stream::FnStream;
 
 /// Stream specifically made to support paginators.
@@ -132,9 +133,12 @@ impl<Page, Err> TryFlatMap<Page, Err> {
 
 #[cfg(test)]
 mod test {
+    use crate::future::pagination_stream::prefetch::PrefetchSpawner;
     use crate::future::pagination_stream::{FnStream, PaginationStream, TryFlatMap};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
     use std::sync::{Arc, Mutex};
     use std::time::Duration;
+    use tokio::time::Instant;
 
     /// basic test of FnStream functionality
     #[tokio::test]
@@ -306,4 +310,151 @@ mod test {
                 .await
         )
     }
+
+    /// Sets the flag when dropped, unless it was disarmed first.
+    struct DropFlag(Option<Arc<AtomicBool>>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            if let Some(flag) = self.0.take() {
+                flag.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// A stream of `pages` pages, each of which takes `delay` to fetch. Page 3 is an error if
+    /// `fail_on_third` is set.
+    fn paginated(
+        pages: usize,
+        delay: Duration,
+        fail_on_third: bool,
+        started: Arc<AtomicUsize>,
+        cancelled: Arc<AtomicBool>,
+    ) -> PaginationStream<Result<usize, String>> {
+        PaginationStream::new(FnStream::new(move |tx| {
+            Box::pin(async move {
+                for page in 0..pages {
+                    started.fetch_add(1, Ordering::SeqCst);
+                    let mut in_flight = DropFlag(Some(cancelled.clone()));
+                    tokio::time::sleep(delay).await;
+                    in_flight.0 = None;
+                    let result = if fail_on_third && page == 2 {
+                        Err(format!("failed to fetch page {page}"))
+                    } else {
+                        Ok(page)
+                    };
+                    let done = result.is_err();
+                    if tx.send(result).await.is_err() || done {
+                        return;
+                    }
+                }
+            })
+        }))
+    }
+
+    fn tokio_spawner() -> PrefetchSpawner {
+        PrefetchSpawner::new(|task| {
+            tokio::spawn(task);
+        })
+    }
+
+    /// Returns the number of whole seconds it takes to process every page of `stream`.
+    async fn time_to_process(mut stream: PaginationStream<Result<usize, String>>) -> u64 {
+        let start = Instant::now();
+        while let Some(page) = stream.next().await {
+            page.expect("success");
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        start.elapsed().as_secs()
+    }
+
+    #[tokio::test]
+    async fn prefetch_fetches_next_page_while_processing() {
+        tokio::time::pause();
+        let new_stream = || {
+            paginated(
+                5,
+                Duration::from_secs(1),
+                false,
+                Default::default(),
+                Default::default(),
+            )
+        };
+
+        assert_eq!(10, time_to_process(new_stream()).await);
+        // Only the first page is waited on; every other page is fetched while the previous one
+        // is processed.
+        assert_eq!(
+            6,
+            time_to_process(new_stream().prefetch(1, tokio_spawner())).await
+        );
+    }
+
+    #[tokio::test]
+    async fn prefetch_preserves_order_and_errors() {
+        tokio::time::pause();
+        let started = Arc::new(AtomicUsize::new(0));
+        let stream = paginated(
+            5,
+            Duration::from_millis(10),
+            true,
+            started.clone(),
+            Default::default(),
+        )
+        .prefetch(3, tokio_spawner());
+        assert_eq!(
+            vec![Ok(0), Ok(1), Err("failed to fetch page 2".to_string())],
+            stream.collect::<Vec<_>>().await
+        );
+        assert_eq!(3, started.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn prefetch_is_lazy_and_bounded() {
+        tokio::time::pause();
+        let started = Arc::new(AtomicUsize::new(0));
+        let mut stream = paginated(
+            5,
+            Duration::from_secs(1),
+            false,
+            started.clone(),
+            Default::default(),
+        )
+        .prefetch(2, tokio_spawner());
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        assert_eq!(0, started.load(Ordering::SeqCst));
+
+        assert_eq!(Some(Ok(0)), stream.next().await);
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        // the first page, plus two pages ahead of the reader
+        assert_eq!(3, started.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn dropping_prefetch_stream_cancels_pending_fetch() {
+        tokio::time::pause();
+        let started = Arc::new(AtomicUsize::new(0));
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let mut stream = paginated(
+            5,
+            Duration::from_secs(1),
+            false,
+            started.clone(),
+            cancelled.clone(),
+        )
+        .prefetch(1, tokio_spawner());
+        assert_eq!(Some(Ok(0)), stream.next().await);
+        assert_eq!(Some(Ok(1)), stream.next().await);
+        // the third page is being fetched
+        tokio::task::yield_now().await;
+        assert_eq!(3, started.load(Ordering::SeqCst));
+        assert!(!cancelled.load(Ordering::SeqCst));
+
+        drop(stream);
+        tokio::task::yield_now().await;
+        assert!(cancelled.load(Ordering::SeqCst));
+
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        assert_eq!(3, started.load(Ordering::SeqCst));
+    }
 }
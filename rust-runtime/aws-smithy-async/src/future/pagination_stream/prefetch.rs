@@ -0,0 +1,114 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Utilities to fetch pages of a [`PaginationStream`] ahead of the reader.
+
+use crate::future::pagination_stream::fn_stream::FnStream;
+use crate::future::pagination_stream::PaginationStream;
+use std::fmt;
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// The largest supported prefetch depth.
+///
+/// Pagination tokens chain, so pages are always fetched one at a time. Fetching more than a
+/// few pages ahead of the reader only buffers more pages in memory.
+pub const MAX_PREFETCH_DEPTH: usize = 4;
+
+type Task = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Spawns the background task that prefetches pages for [`PaginationStream::prefetch`].
+///
+/// The SDK is runtime agnostic, so the spawn function must be provided. With the `rt-tokio`
+/// feature enabled, [`PrefetchSpawner::tokio`] spawns the task onto the current Tokio runtime.
+///
+/// ```no_run
+/// use aws_smithy_async::future::pagination_stream::prefetch::PrefetchSpawner;
+///
+/// # fn spawn<F: std::future::Future<Output = ()> + Send + 'static>(_f: F) {}
+/// let spawner = PrefetchSpawner::new(|task| {
+///     // e.g. `async_std::task::spawn(task);`
+///     spawn(task);
+/// });
+/// ```
+#[derive(Clone)]
+pub struct PrefetchSpawner(Arc<dyn Fn(Task) + Send + Sync>);
+
+impl PrefetchSpawner {
+    /// Creates a new `PrefetchSpawner` from the given spawn function.
+    pub fn new(
+        spawn: impl Fn(Pin<Box<dyn Future<Output = ()> + Send + 'static>>) + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(spawn))
+    }
+
+    /// Creates a `PrefetchSpawner` that spawns the task onto the current Tokio runtime.
+    ///
+    /// # Panics
+    ///
+    /// The stream will panic when it is first polled if it isn't polled from within a Tokio runtime.
+    #[cfg(feature = "rt-tokio")]
+    pub fn tokio() -> Self {
+        Self::new(|task| {
+            tokio::spawn(task);
+        })
+    }
+
+    fn spawn(&self, task: Task) {
+        (self.0)(task)
+    }
+}
+
+impl fmt::Debug for PrefetchSpawner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PrefetchSpawner")
+    }
+}
+
+impl<Item: Send + 'static> PaginationStream<Item> {
+    /// Returns a stream that fetches up to `depth` items ahead of the reader.
+    ///
+    /// While the reader processes an item, the next item is already being fetched by a background
+    /// task created with `spawner`. Items, including errors, are yielded in the order they were
+    /// produced. `depth` is clamped between 1 and [`MAX_PREFETCH_DEPTH`].
+    ///
+    /// As with the original stream, nothing is fetched until the returned stream is first
+    /// polled. Dropping the returned stream cancels any fetch that is in progress.
+    pub fn prefetch(self, depth: usize, spawner: PrefetchSpawner) -> PaginationStream<Item> {
+        let depth = depth.clamp(1, MAX_PREFETCH_DEPTH);
+        PaginationStream::new(FnStream::new(move |tx| {
+            Box::pin(async move {
+                let (buffer_tx, mut buffer_rx) = mpsc::channel(depth);
+                spawner.spawn(Box::pin(fetch_ahead(self, buffer_tx)));
+                while let Some(item) = buffer_rx.recv().await {
+                    if tx.send(item).await.is_err() {
+                        // receiving end was dropped
+                        return;
+                    }
+                }
+            })
+        }))
+    }
+}
+
+/// Moves items from `stream` into `buffer` until either one is exhausted.
+///
+/// A buffer slot is reserved before each item is requested, so at most `depth` items are fetched
+/// ahead of the reader. When the reader drops the buffer, the item being fetched is dropped
+/// immediately rather than after it completes.
+async fn fetch_ahead<Item>(mut stream: PaginationStream<Item>, buffer: mpsc::Sender<Item>) {
+    let buffer = &buffer;
+    let forward = pin!(async move {
+        while let Ok(slot) = buffer.reserve().await {
+            match stream.next().await {
+                Some(item) => slot.send(item),
+                None => return,
+            }
+        }
+    });
+    let _ = futures_util::future::select(forward, pin!(buffer.closed())).await;
+}
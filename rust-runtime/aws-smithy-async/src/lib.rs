@@ -21,6 +21,7 @@
 
 pub mod future;
 pub mod rt;
+pub mod sync;
 #[cfg(feature = "test-util")]
 pub mod test_util;
 pub mod time;
@@ -6,3 +6,4 @@
 //! Async runtime agnostic traits and implementations.
 
 pub mod sleep;
+pub mod spawn;
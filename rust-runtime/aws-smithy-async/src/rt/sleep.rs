@@ -6,6 +6,7 @@
 //! Provides an [`AsyncSleep`] trait that returns a future that sleeps for a given duration,
 //! and implementations of `AsyncSleep` for different async runtimes.
 
+use std::cell::Cell;
 use std::fmt::{Debug, Formatter};
 use std::future::Future;
 use std::pin::Pin;
@@ -95,7 +96,18 @@ impl Sleep {
     /// Create a new [`Sleep`] future
     ///
     /// The provided future will be Boxed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called while polling a [`ForbidRealSleep`] future that forbids sleeps, unless
+    /// called through the sleep implementation passed to [`ForbidRealSleep::allow`].
     pub fn new(future: impl Future<Output = ()> + Send + Sync + 'static) -> Sleep {
+        if SLEEP_FORBIDDEN.with(Cell::get) && !SLEEP_ALLOWED.with(Cell::get) {
+            panic!(
+                "a sleep was started while polling a `ForbidRealSleep` future. \
+                 A component isn't using the configured sleep implementation."
+            );
+        }
         Sleep(Box::pin(future))
     }
 }
@@ -133,3 +145,128 @@ impl AsyncSleep for TokioSleep {
 fn sleep_tokio() -> Arc<dyn AsyncSleep> {
     Arc::new(TokioSleep::new())
 }
+
+thread_local! {
+    static SLEEP_FORBIDDEN: Cell<bool> = const { Cell::new(false) };
+    static SLEEP_ALLOWED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Restores a thread-local flag to its previous value when dropped, even on panic.
+struct Reset(&'static std::thread::LocalKey<Cell<bool>>, bool);
+
+impl Reset {
+    fn set(flag: &'static std::thread::LocalKey<Cell<bool>>, value: bool) -> Self {
+        Self(flag, flag.with(|cell| cell.replace(value)))
+    }
+}
+
+impl Drop for Reset {
+    fn drop(&mut self) {
+        let previous = self.1;
+        self.0.with(|cell| cell.set(previous));
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// A future that panics if a sleep is started while it is polled, other than through the
+    /// sleep implementation returned by [`ForbidRealSleep::allow`].
+    ///
+    /// Tests that configure a sleep implementation that completes instantly can use this to catch
+    /// components that sleep for real instead, and make the test slow and nondeterministic. Any
+    /// [`AsyncSleep`] implementation is caught, since they all create a [`Sleep`], but futures
+    /// that sleep without going through [`AsyncSleep`], like a direct call to
+    /// `tokio::time::sleep`, aren't. Only sleeps started while polling this future are caught, not
+    /// sleeps started by spawned tasks.
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct ForbidRealSleep<F> {
+        #[pin]
+        inner: F,
+        forbidden: bool,
+    }
+}
+
+impl<F> ForbidRealSleep<F> {
+    /// Wraps `inner`, forbidding real sleeps while it is polled if `forbidden` is `true`.
+    pub fn new(inner: F, forbidden: bool) -> Self {
+        Self { inner, forbidden }
+    }
+}
+
+impl ForbidRealSleep<()> {
+    /// Wraps the configured `sleep_impl` so that its sleeps are allowed while polling a
+    /// `ForbidRealSleep` future.
+    pub fn allow(sleep_impl: SharedAsyncSleep) -> SharedAsyncSleep {
+        SharedAsyncSleep::new(AllowedSleep(sleep_impl))
+    }
+}
+
+/// Sleep implementation whose sleeps are allowed by [`ForbidRealSleep`].
+#[derive(Debug)]
+struct AllowedSleep(SharedAsyncSleep);
+
+impl AsyncSleep for AllowedSleep {
+    fn sleep(&self, duration: Duration) -> Sleep {
+        let _reset = Reset::set(&SLEEP_ALLOWED, true);
+        self.0.sleep(duration)
+    }
+}
+
+impl<F: Future> Future for ForbidRealSleep<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let _reset = Reset::set(&SLEEP_FORBIDDEN, *this.forbidden);
+        this.inner.poll(cx)
+    }
+}
+
+#[cfg(all(test, feature = "rt-tokio"))]
+mod tests {
+    use super::{AsyncSleep, ForbidRealSleep, SharedAsyncSleep, Sleep, TokioSleep};
+    use std::time::Duration;
+
+    #[derive(Debug)]
+    struct NeverSleep;
+
+    impl AsyncSleep for NeverSleep {
+        fn sleep(&self, _duration: Duration) -> Sleep {
+            Sleep::new(async {})
+        }
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "a sleep was started while polling a `ForbidRealSleep` future")]
+    async fn forbidden_real_sleeps_panic() {
+        ForbidRealSleep::new(TokioSleep::new().sleep(Duration::from_millis(1)), false).await;
+        ForbidRealSleep::new(
+            async { TokioSleep::new().sleep(Duration::from_millis(1)).await },
+            true,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "a sleep was started while polling a `ForbidRealSleep` future")]
+    async fn sleeps_bypassing_the_allowed_sleep_impl_panic() {
+        let _allowed = ForbidRealSleep::allow(SharedAsyncSleep::new(NeverSleep));
+        ForbidRealSleep::new(
+            async { NeverSleep.sleep(Duration::from_secs(1)).await },
+            true,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn sleeps_through_the_allowed_sleep_impl_are_allowed() {
+        let allowed = ForbidRealSleep::allow(SharedAsyncSleep::new(NeverSleep));
+        ForbidRealSleep::new(async { allowed.sleep(Duration::from_secs(1)).await }, true).await;
+    }
+
+    #[tokio::test]
+    async fn real_sleeps_are_allowed_again_after_polling() {
+        ForbidRealSleep::new(async {}, true).await;
+        TokioSleep::new().sleep(Duration::from_millis(1)).await;
+    }
+}
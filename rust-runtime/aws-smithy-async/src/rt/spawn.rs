@@ -0,0 +1,330 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Provides a [`TaskSpawner`] trait that runs futures in the background, and [`SdkRuntime`],
+//! which keeps track of the background tasks of SDK components so that they can be shut down.
+//!
+//! One `SdkRuntime` can be shared by every client of an application, so that all of their
+//! background work runs on the same async runtime:
+//!
+//! ```rust
+//! # #[cfg(feature = "rt-tokio")]
+//! # async fn example() {
+//! use aws_smithy_async::rt::spawn::SdkRuntime;
+//! use std::time::Duration;
+//!
+//! let runtime = SdkRuntime::tokio();
+//! // ... pass `runtime` to the config of each client ...
+//!
+//! // Before the application exits, stop the background tasks:
+//! runtime
+//!     .shutdown(Duration::from_secs(5))
+//!     .await
+//!     .expect("background tasks stopped in time");
+//! # }
+//! ```
+
+use crate::future::timeout::{TimedOutError, Timeout};
+use crate::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+use futures_util::future;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+
+/// A future that runs in the background, as returned by [`TaskSpawner::spawn`].
+pub type BackgroundTask = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Trait with a `spawn` function that runs a future in the background.
+pub trait TaskSpawner: Debug + Send + Sync {
+    /// Runs `task` to completion in the background.
+    ///
+    /// The task must be polled independently of the caller, which won't await it.
+    fn spawn(&self, task: BackgroundTask);
+}
+
+impl<T> TaskSpawner for Box<T>
+where
+    T: TaskSpawner,
+    T: ?Sized,
+{
+    fn spawn(&self, task: BackgroundTask) {
+        T::spawn(self, task)
+    }
+}
+
+impl<T> TaskSpawner for Arc<T>
+where
+    T: TaskSpawner,
+    T: ?Sized,
+{
+    fn spawn(&self, task: BackgroundTask) {
+        T::spawn(self, task)
+    }
+}
+
+/// Wrapper type for sharable `TaskSpawner`
+#[derive(Clone, Debug)]
+pub struct SharedTaskSpawner(Arc<dyn TaskSpawner>);
+
+impl SharedTaskSpawner {
+    /// Create a new `SharedTaskSpawner` from `TaskSpawner`
+    pub fn new(spawner: impl TaskSpawner + 'static) -> Self {
+        Self(Arc::new(spawner))
+    }
+}
+
+impl AsRef<dyn TaskSpawner> for SharedTaskSpawner {
+    fn as_ref(&self) -> &(dyn TaskSpawner + 'static) {
+        self.0.as_ref()
+    }
+}
+
+impl From<Arc<dyn TaskSpawner>> for SharedTaskSpawner {
+    fn from(spawner: Arc<dyn TaskSpawner>) -> Self {
+        SharedTaskSpawner(spawner)
+    }
+}
+
+impl TaskSpawner for SharedTaskSpawner {
+    fn spawn(&self, task: BackgroundTask) {
+        self.0.spawn(task)
+    }
+}
+
+/// Implementation of [`TaskSpawner`] for Tokio.
+///
+/// Tasks are spawned on the given runtime handle, or on the runtime of the caller if no handle is set.
+#[non_exhaustive]
+#[cfg(feature = "rt-tokio")]
+#[derive(Clone, Debug, Default)]
+pub struct TokioTaskSpawner {
+    handle: Option<tokio::runtime::Handle>,
+}
+
+#[cfg(feature = "rt-tokio")]
+impl TokioTaskSpawner {
+    /// Create a new [`TaskSpawner`] that spawns tasks on the Tokio runtime of the caller.
+    ///
+    /// Spawning a task outside of a Tokio runtime panics.
+    pub fn new() -> TokioTaskSpawner {
+        Default::default()
+    }
+
+    /// Create a new [`TaskSpawner`] that spawns tasks on the Tokio runtime of `handle`.
+    pub fn with_handle(handle: tokio::runtime::Handle) -> TokioTaskSpawner {
+        TokioTaskSpawner {
+            handle: Some(handle),
+        }
+    }
+}
+
+#[cfg(feature = "rt-tokio")]
+impl TaskSpawner for TokioTaskSpawner {
+    fn spawn(&self, task: BackgroundTask) {
+        match &self.handle {
+            Some(handle) => drop(handle.spawn(task)),
+            None => drop(tokio::spawn(task)),
+        }
+    }
+}
+
+/// Handle to the async runtime shared by SDK components for their background tasks.
+///
+/// Components that do work in the background, such as refreshing credentials before they expire,
+/// spawn it with [`SdkRuntime::spawn_background`]. When no `SdkRuntime` is configured, those
+/// components do the same work lazily, on the next request that needs it.
+///
+/// Cloning an `SdkRuntime` is cheap, and clones share their background tasks: shutting down one clone
+/// shuts down all of them.
+#[derive(Clone)]
+pub struct SdkRuntime {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    spawner: SharedTaskSpawner,
+    sleep_impl: SharedAsyncSleep,
+    shutdown: watch::Sender<bool>,
+    // Each background task holds a clone of this sender, so once it's dropped,
+    // the receiver is closed when the last background task stops.
+    running: Mutex<Option<mpsc::Sender<()>>>,
+    stopped: Mutex<Option<mpsc::Receiver<()>>>,
+}
+
+impl Debug for SdkRuntime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SdkRuntime")
+            .field("spawner", &self.inner.spawner)
+            .field("sleep_impl", &self.inner.sleep_impl)
+            .field("is_shut_down", &self.is_shut_down())
+            .finish()
+    }
+}
+
+impl SdkRuntime {
+    /// Creates a new `SdkRuntime` that spawns tasks with `spawner`.
+    ///
+    /// `sleep_impl` is used to time out [`SdkRuntime::shutdown`].
+    pub fn new(spawner: impl TaskSpawner + 'static, sleep_impl: impl AsyncSleep + 'static) -> Self {
+        let (running, stopped) = mpsc::channel(1);
+        Self {
+            inner: Arc::new(Inner {
+                spawner: SharedTaskSpawner::new(spawner),
+                sleep_impl: SharedAsyncSleep::new(sleep_impl),
+                shutdown: watch::channel(false).0,
+                running: Mutex::new(Some(running)),
+                stopped: Mutex::new(Some(stopped)),
+            }),
+        }
+    }
+
+    /// Creates a new `SdkRuntime` that spawns tasks on the Tokio runtime of the caller.
+    #[cfg(feature = "rt-tokio")]
+    pub fn tokio() -> Self {
+        Self::new(TokioTaskSpawner::new(), crate::rt::sleep::TokioSleep::new())
+    }
+
+    /// Creates a new `SdkRuntime` that spawns tasks on the Tokio runtime of `handle`.
+    #[cfg(feature = "rt-tokio")]
+    pub fn from_tokio_handle(handle: tokio::runtime::Handle) -> Self {
+        Self::new(
+            TokioTaskSpawner::with_handle(handle),
+            crate::rt::sleep::TokioSleep::new(),
+        )
+    }
+
+    /// Returns the task spawner.
+    pub fn spawner(&self) -> SharedTaskSpawner {
+        self.inner.spawner.clone()
+    }
+
+    /// Returns the async sleep implementation.
+    pub fn sleep_impl(&self) -> SharedAsyncSleep {
+        self.inner.sleep_impl.clone()
+    }
+
+    /// Returns `true` once [`SdkRuntime::shutdown`] has been called.
+    pub fn is_shut_down(&self) -> bool {
+        *self.inner.shutdown.borrow()
+    }
+
+    /// Runs `task` in the background until it completes or the runtime is shut down.
+    ///
+    /// Returns `false`, without spawning the task, if the runtime has already been shut down.
+    pub fn spawn_background(&self, task: impl Future<Output = ()> + Send + 'static) -> bool {
+        // Subscribing under the same lock that `shutdown` takes guarantees that every
+        // spawned task sees the shutdown signal.
+        let (guard, mut shutdown) = match self.inner.running.lock().unwrap().as_ref() {
+            Some(running) => (running.clone(), self.inner.shutdown.subscribe()),
+            None => return false,
+        };
+        self.inner.spawner.spawn(Box::pin(async move {
+            let _guard = guard;
+            let shutdown = async move {
+                let _ = shutdown.changed().await;
+            };
+            future::select(Box::pin(task), Box::pin(shutdown)).await;
+        }));
+        true
+    }
+
+    /// Signals every background task to stop, and waits up to `timeout` for them to stop.
+    ///
+    /// A background task stops the next time it's polled after shutdown. No new tasks can be spawned
+    /// after this is called. Only the first call waits for the tasks: later calls return immediately.
+    pub async fn shutdown(&self, timeout: Duration) -> Result<(), TimedOutError> {
+        let stopped = {
+            let mut running = self.inner.running.lock().unwrap();
+            running.take();
+            self.inner.shutdown.send_replace(true);
+            self.inner.stopped.lock().unwrap().take()
+        };
+        let Some(mut stopped) = stopped else {
+            return Ok(());
+        };
+        Timeout::new(stopped.recv(), self.inner.sleep_impl.sleep(timeout))
+            .await
+            .map(|_| ())
+    }
+}
+
+#[cfg(all(test, feature = "rt-tokio"))]
+mod tests {
+    use super::{BackgroundTask, SdkRuntime, TaskSpawner, TokioTaskSpawner};
+    use crate::rt::sleep::TokioSleep;
+    use std::future::pending;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// Spawns tasks with Tokio, counting them.
+    #[derive(Debug, Default)]
+    struct CountingSpawner(Arc<AtomicUsize>);
+
+    impl TaskSpawner for CountingSpawner {
+        fn spawn(&self, task: BackgroundTask) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            TokioTaskSpawner::new().spawn(task)
+        }
+    }
+
+    /// Keeps the tasks it's given without ever polling them.
+    #[derive(Debug, Default)]
+    struct StalledSpawner(Mutex<Vec<BackgroundTask>>);
+
+    impl TaskSpawner for StalledSpawner {
+        fn spawn(&self, task: BackgroundTask) {
+            self.0.lock().unwrap().push(task);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn background_tasks_run_with_the_configured_spawner() {
+        let spawned = Arc::new(AtomicUsize::new(0));
+        let runtime = SdkRuntime::new(CountingSpawner(spawned.clone()), TokioSleep::new());
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        assert!(runtime.spawn_background(async move {
+            tx.send("done").unwrap();
+        }));
+        assert_eq!("done", rx.await.unwrap());
+        assert_eq!(1, spawned.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_stops_background_tasks() {
+        let runtime = SdkRuntime::tokio();
+        for _ in 0..3 {
+            assert!(runtime.spawn_background(pending()));
+        }
+        tokio::task::yield_now().await;
+
+        runtime.shutdown(Duration::from_secs(1)).await.unwrap();
+        assert!(runtime.is_shut_down());
+        assert!(!runtime.spawn_background(async {}));
+        // Later calls don't wait
+        runtime.shutdown(Duration::ZERO).await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_without_background_tasks_returns_immediately() {
+        SdkRuntime::tokio().shutdown(Duration::ZERO).await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn shutdown_times_out_when_tasks_dont_stop() {
+        let runtime = SdkRuntime::new(StalledSpawner::default(), TokioSleep::new());
+        assert!(runtime.spawn_background(pending()));
+
+        let started = tokio::time::Instant::now();
+        runtime
+            .shutdown(Duration::from_secs(2))
+            .await
+            .expect_err("the task is never polled, so it can't stop");
+        assert!(started.elapsed() >= Duration::from_secs(2));
+    }
+}
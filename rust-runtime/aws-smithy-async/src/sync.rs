@@ -0,0 +1,275 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Synchronization utilities that don't depend on an async runtime.
+
+#[cfg(loom)]
+use loom::sync::atomic::{AtomicPtr, AtomicUsize};
+#[cfg(loom)]
+use loom::sync::Mutex;
+#[cfg(loom)]
+use loom::thread::yield_now;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::atomic::Ordering::SeqCst;
+#[cfg(not(loom))]
+use std::sync::atomic::{AtomicPtr, AtomicUsize};
+use std::sync::Arc;
+#[cfg(not(loom))]
+use std::sync::Mutex;
+#[cfg(not(loom))]
+use std::thread::yield_now;
+
+/// A cell for small pieces of mutable state shared between concurrent requests, such as counters,
+/// the last token seen, or a clock skew offset kept by an interceptor.
+///
+/// Readers get an immutable snapshot of the value with [`SharedCell::snapshot`], which is
+/// lock-free: it never blocks or waits, even while the value is being updated. Writers modify the
+/// value with [`SharedCell::update`], which applies to a copy of the value, and then atomically
+/// swaps the copy in. A snapshot outstanding during an update is left untouched.
+///
+/// No lock guard is ever handed out, so unlike a [`std::sync::Mutex`], a `SharedCell` can't be held
+/// across an `.await` and block the executor. Unlike `tokio::sync::Mutex`, it doesn't tie its user
+/// to a particular async runtime. Writers take turns, and each waits for the snapshots that were
+/// being taken when it swapped the value in, so the closure passed to `update` should be brief.
+///
+/// # Examples
+///
+/// ```
+/// use aws_smithy_async::sync::SharedCell;
+///
+/// let requests_sent = SharedCell::new(0_u64);
+/// requests_sent.update(|count| *count += 1);
+/// assert_eq!(1, *requests_sent.snapshot());
+/// ```
+pub struct SharedCell<T> {
+    // Created with `Arc::into_raw`, and owns one strong reference to the current value
+    value: AtomicPtr<T>,
+    // Snapshots in progress, split by the parity of `epoch` so that writers waiting on one half
+    // aren't starved by new snapshots, which count in the other half
+    readers: [AtomicUsize; 2],
+    epoch: AtomicUsize,
+    // Serializes writers, which never block readers
+    writer: Mutex<()>,
+    _value: PhantomData<Arc<T>>,
+}
+
+impl<T> SharedCell<T> {
+    /// Creates a new `SharedCell` holding `value`.
+    pub fn new(value: T) -> Self {
+        Self {
+            value: AtomicPtr::new(Arc::into_raw(Arc::new(value)).cast_mut()),
+            readers: [AtomicUsize::new(0), AtomicUsize::new(0)],
+            epoch: AtomicUsize::new(0),
+            writer: Mutex::new(()),
+            _value: PhantomData,
+        }
+    }
+
+    /// Returns a snapshot of the current value.
+    ///
+    /// The snapshot doesn't observe updates made after it was taken.
+    pub fn snapshot(&self) -> Arc<T> {
+        let readers = &self.readers[self.epoch.load(SeqCst) % 2];
+        readers.fetch_add(1, SeqCst);
+        let value = self.value.load(SeqCst);
+        // SAFETY: `value` came from `Arc::into_raw`, and it's still alive: a writer that swapped
+        // it out waits for this snapshot to leave `readers` before releasing its reference.
+        let snapshot = unsafe {
+            Arc::increment_strong_count(value);
+            Arc::from_raw(value)
+        };
+        readers.fetch_sub(1, SeqCst);
+        snapshot
+    }
+
+    /// Replaces the current value with `value`.
+    pub fn set(&self, value: T) {
+        let _writer = self
+            .writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        self.replace(value);
+    }
+
+    // Must be called by the writer holding `self.writer`
+    fn replace(&self, value: T) {
+        let previous = self
+            .value
+            .swap(Arc::into_raw(Arc::new(value)).cast_mut(), SeqCst);
+        // A snapshot that loaded `previous` incremented a reader count before the swap, since
+        // all of these operations are `SeqCst`. Wait for both halves to drain of the snapshots
+        // in progress, switching new snapshots to the other half first.
+        for _ in 0..2 {
+            let readers = &self.readers[self.epoch.fetch_add(1, SeqCst) % 2];
+            while readers.load(SeqCst) != 0 {
+                yield_now();
+            }
+        }
+        // SAFETY: `previous` came from `Arc::into_raw`, and the cell's reference to it was
+        // transferred to this writer by the swap.
+        drop(unsafe { Arc::from_raw(previous) });
+    }
+}
+
+impl<T: Clone> SharedCell<T> {
+    /// Modifies the current value with `f`, returning the result of `f`.
+    ///
+    /// `f` modifies a copy of the current value, which replaces it once `f` returns. `f` must not
+    /// block or call back into this cell. If it panics, the value is left unchanged, and the cell
+    /// remains usable.
+    pub fn update<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let _writer = self
+            .writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut value = T::clone(&self.snapshot());
+        let result = f(&mut value);
+        self.replace(value);
+        result
+    }
+}
+
+impl<T> Drop for SharedCell<T> {
+    fn drop(&mut self) {
+        // SAFETY: the pointer came from `Arc::into_raw`, and the cell owns that reference
+        drop(unsafe { Arc::from_raw(self.value.load(SeqCst)) });
+    }
+}
+
+impl<T: Default> Default for SharedCell<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SharedCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedCell").field(&self.snapshot()).finish()
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use super::SharedCell;
+    use std::sync::Arc;
+
+    #[test]
+    fn snapshots_are_not_affected_by_later_updates() {
+        let cell = SharedCell::new(vec![1]);
+        let before = cell.snapshot();
+        cell.update(|value| value.push(2));
+        assert_eq!(vec![1], *before);
+        assert_eq!(vec![1, 2], *cell.snapshot());
+
+        cell.set(vec![3]);
+        assert_eq!(vec![3], *cell.snapshot());
+    }
+
+    #[test]
+    fn cell_remains_usable_after_a_panicking_update() {
+        let cell = Arc::new(SharedCell::new(0));
+        let result = std::thread::spawn({
+            let cell = cell.clone();
+            move || cell.update(|_| panic!("update failed"))
+        })
+        .join();
+        assert!(result.is_err());
+
+        let value = cell.update(|value| {
+            *value += 1;
+            *value
+        });
+        assert_eq!(1, value);
+    }
+
+    #[test]
+    fn snapshots_race_with_updates() {
+        let cell = Arc::new(SharedCell::new((0, 0)));
+        let writers: Vec<_> = (0..4)
+            .map(|_| {
+                let cell = cell.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..1000 {
+                        cell.update(|(a, b)| {
+                            *a += 1;
+                            *b += 1;
+                        });
+                    }
+                })
+            })
+            .collect();
+        while writers.iter().any(|writer| !writer.is_finished()) {
+            let snapshot = cell.snapshot();
+            assert_eq!(snapshot.0, snapshot.1);
+        }
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        assert_eq!((4000, 4000), *cell.snapshot());
+    }
+}
+
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::SharedCell;
+    use std::sync::Arc;
+
+    #[test]
+    fn concurrent_updates_are_not_lost() {
+        loom::model(|| {
+            let cell = Arc::new(SharedCell::new(0));
+            let threads: Vec<_> = (0..2)
+                .map(|_| {
+                    let cell = cell.clone();
+                    loom::thread::spawn(move || cell.update(|value| *value += 1))
+                })
+                .collect();
+            for thread in threads {
+                thread.join().unwrap();
+            }
+            assert_eq!(2, *cell.snapshot());
+        });
+    }
+
+    #[test]
+    fn snapshots_observe_whole_updates() {
+        loom::model(|| {
+            let cell = Arc::new(SharedCell::new((0, 0)));
+            let writer = {
+                let cell = cell.clone();
+                loom::thread::spawn(move || {
+                    cell.update(|(a, b)| {
+                        *a += 1;
+                        *b += 1;
+                    })
+                })
+            };
+            let snapshot = cell.snapshot();
+            assert_eq!(snapshot.0, snapshot.1);
+            writer.join().unwrap();
+            assert_eq!((1, 1), *cell.snapshot());
+        });
+    }
+
+    #[test]
+    fn snapshots_outlive_replaced_values() {
+        loom::model(|| {
+            let cell = Arc::new(SharedCell::new(vec![0]));
+            let writers: Vec<_> = (1..3)
+                .map(|i| {
+                    let cell = cell.clone();
+                    loom::thread::spawn(move || cell.set(vec![i]))
+                })
+                .collect();
+            let snapshot = cell.snapshot();
+            assert_eq!(1, snapshot.len());
+            for writer in writers {
+                writer.join().unwrap();
+            }
+            assert!([0, 1, 2].contains(&snapshot[0]));
+        });
+    }
+}
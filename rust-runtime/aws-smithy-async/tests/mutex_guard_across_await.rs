@@ -0,0 +1,163 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Guards against holding a `std::sync::Mutex` (or `RwLock`) guard across an `.await` in the
+//! runtime crates, which blocks the executor thread. Shared state should use
+//! `aws_smithy_async::sync::SharedCell` instead, or release the guard before awaiting.
+//!
+//! This is a line-based heuristic rather than a real lint: it flags a `let` binding of a
+//! synchronous lock guard that is followed by an `.await` before the guard is dropped or goes out
+//! of scope.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Crates under `rust-runtime` that are scanned.
+const RUNTIME_CRATES: &[&str] = &[
+    "aws-smithy-async",
+    "aws-smithy-http",
+    "aws-smithy-mocks-experimental",
+    "aws-smithy-runtime",
+    "aws-smithy-runtime-api",
+];
+
+struct Guard {
+    name: String,
+    depth: usize,
+    line: usize,
+}
+
+/// Returns the name bound by `line` if it's a `let` statement binding a synchronous lock guard.
+fn guard_binding(line: &str) -> Option<String> {
+    let statement = line.trim().strip_prefix("let ")?.strip_suffix(';')?;
+    let (binding, value) = statement.split_once(" = ")?;
+    let value = value.strip_suffix(".unwrap()").unwrap_or(value);
+    let value = match value.rfind(".expect(") {
+        Some(index) => &value[..index],
+        None => value,
+    };
+    // Guards that are used and dropped within the statement aren't bound, and `.lock().await`
+    // acquires an async mutex, which is fine to hold across an await.
+    let acquires_guard = [".lock()", ".read()", ".write()"]
+        .iter()
+        .any(|acquire| value.ends_with(acquire));
+    if !acquires_guard {
+        return None;
+    }
+    let name = binding.trim_start_matches("mut ").split(':').next()?.trim();
+    Some(name.to_string())
+}
+
+fn violations(source: &str) -> Vec<usize> {
+    let mut violations = Vec::new();
+    let mut guards: Vec<Guard> = Vec::new();
+    let mut depth = 0_usize;
+    for (index, line) in source.lines().enumerate() {
+        let code = line.split("//").next().unwrap_or_default();
+        if let Some(name) = guard_binding(code) {
+            guards.push(Guard {
+                name,
+                depth,
+                line: index + 1,
+            });
+        }
+        if code.contains(".await") {
+            violations.extend(guards.iter().map(|guard| guard.line));
+            guards.clear();
+        }
+        guards.retain(|guard| !code.contains(&format!("drop({})", guard.name)));
+        for c in code.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth = depth.saturating_sub(1);
+                    guards.retain(|guard| guard.depth <= depth);
+                }
+                _ => {}
+            }
+        }
+    }
+    violations
+}
+
+fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            rust_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+#[test]
+fn no_sync_lock_guard_is_held_across_an_await() {
+    let runtime_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let mut files = Vec::new();
+    for krate in RUNTIME_CRATES {
+        rust_files(&runtime_dir.join(krate).join("src"), &mut files);
+    }
+
+    let found: Vec<String> = files
+        .iter()
+        .flat_map(|path| {
+            let source = fs::read_to_string(path).unwrap();
+            violations(&source)
+                .into_iter()
+                .map(move |line| format!("{}:{line}", path.display()))
+        })
+        .collect();
+    assert!(
+        found.is_empty(),
+        "a std lock guard is held across an `.await` (use `SharedCell` or drop the guard first):\n{}",
+        found.join("\n")
+    );
+}
+
+#[test]
+fn detects_guard_held_across_await() {
+    let held = r#"
+        async fn f(state: &std::sync::Mutex<u32>) {
+            let mut guard = state.lock().unwrap();
+            *guard += 1;
+            tokio::task::yield_now().await;
+        }
+    "#;
+    assert_eq!(vec![3], violations(held));
+
+    let dropped = r#"
+        async fn f(state: &std::sync::Mutex<u32>) {
+            let mut guard = state.lock().unwrap();
+            *guard += 1;
+            drop(guard);
+            tokio::task::yield_now().await;
+        }
+    "#;
+    assert!(violations(dropped).is_empty());
+
+    let scoped = r#"
+        async fn f(state: &std::sync::Mutex<u32>) {
+            {
+                let mut guard = state.lock().unwrap();
+                *guard += 1;
+            }
+            tokio::task::yield_now().await;
+        }
+    "#;
+    assert!(violations(scoped).is_empty());
+
+    let async_mutex = r#"
+        async fn f(state: &tokio::sync::Mutex<u32>) {
+            let mut guard = state.lock().await;
+            *guard += 1;
+            tokio::task::yield_now().await;
+        }
+    "#;
+    assert!(violations(async_mutex).is_empty());
+}
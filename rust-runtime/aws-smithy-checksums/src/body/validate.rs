@@ -22,6 +22,11 @@ use std::task::{Context, Poll};
 pin_project! {
     /// A body-wrapper that will calculate the `InnerBody`'s checksum and emit an error if it
     /// doesn't match the precalculated checksum.
+    ///
+    /// The error is emitted in place of the end of the body, so readers that collect the whole
+    /// body (like `ByteStream::collect`) fail. When there is no precalculated checksum to validate
+    /// against (for example, because a response had no checksum header), the body shouldn't be
+    /// wrapped, so that no checksum is calculated.
     pub struct ChecksumBody<InnerBody> {
         #[pin]
         inner: InnerBody,
@@ -154,12 +159,60 @@ impl http_body::Body for ChecksumBody<SdkBody> {
 #[cfg(test)]
 mod tests {
     use crate::body::validate::{ChecksumBody, Error};
-    use crate::ChecksumAlgorithm;
+    use crate::{Checksum, ChecksumAlgorithm};
     use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::byte_stream::ByteStream;
     use bytes::{Buf, Bytes};
     use bytes_utils::SegmentedBuf;
+    use http::{HeaderMap, HeaderValue};
     use http_body::Body;
+    use std::collections::VecDeque;
     use std::io::Read;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    const ALGORITHMS: [ChecksumAlgorithm; 6] = [
+        ChecksumAlgorithm::Crc32,
+        ChecksumAlgorithm::Crc32c,
+        ChecksumAlgorithm::Crc64Nvme,
+        ChecksumAlgorithm::Md5,
+        ChecksumAlgorithm::Sha1,
+        ChecksumAlgorithm::Sha256,
+    ];
+
+    fn calculate_checksum(algorithm: ChecksumAlgorithm, input: &[u8]) -> Bytes {
+        let mut checksum = algorithm.into_impl();
+        checksum.update(input);
+        checksum.finalize()
+    }
+
+    /// A body that returns its data in the given chunks.
+    struct ChunkedBody(VecDeque<Bytes>);
+
+    impl Body for ChunkedBody {
+        type Data = Bytes;
+        type Error = aws_smithy_types::body::Error;
+
+        fn poll_data(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            Poll::Ready(self.0.pop_front().map(Ok))
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<HeaderMap<HeaderValue>>, Self::Error>> {
+            Poll::Ready(Ok(None))
+        }
+    }
+
+    fn chunked_body(chunks: &[&'static str]) -> SdkBody {
+        SdkBody::from_body_0_4(ChunkedBody(
+            chunks.iter().map(|chunk| Bytes::from(*chunk)).collect(),
+        ))
+    }
 
     fn calculate_crc32_checksum(input: &str) -> Bytes {
         let checksum = crc32fast::hash(input.as_bytes());
@@ -197,6 +250,24 @@ mod tests {
         panic!("didn't hit expected error condition");
     }
 
+    #[tokio::test]
+    async fn test_checksum_validated_body_succeeds_on_crc64nvme_match() {
+        let input_text = "This is some test text for an SdkBody";
+        let mut digest = crc64fast_nvme::Digest::new();
+        digest.write(input_text.as_bytes());
+        let actual_checksum = Bytes::copy_from_slice(&digest.sum64().to_be_bytes());
+        let body = SdkBody::from(input_text);
+        let http_checksum = "crc64nvme"
+            .parse::<ChecksumAlgorithm>()
+            .unwrap()
+            .into_impl();
+        let mut body = ChecksumBody::new(body, http_checksum, actual_checksum);
+
+        while let Some(buf) = body.data().await {
+            buf.expect("checksum matches");
+        }
+    }
+
     #[tokio::test]
     async fn test_checksum_validated_body_succeeds_on_match() {
         let input_text = "This is some test text for an SdkBody";
@@ -218,4 +289,92 @@ mod tests {
         // Verify data is complete and unaltered
         assert_eq!(input_text, output_text);
     }
+
+    #[tokio::test]
+    async fn test_checksum_validated_body_errors_on_corrupted_payload() {
+        let input_text = "This is some test text for an SdkBody";
+        let corrupted_text = "This is some test text for an SdkBodz";
+        for algorithm in ALGORITHMS {
+            let expected = calculate_checksum(algorithm, input_text.as_bytes());
+            let mut body = ChecksumBody::new(
+                SdkBody::from(corrupted_text),
+                algorithm.into_impl(),
+                expected.clone(),
+            );
+
+            let mut error = None;
+            while let Some(data) = body.data().await {
+                if let Err(e) = data {
+                    error = Some(e);
+                    break;
+                }
+            }
+            let error = error.unwrap_or_else(|| panic!("{algorithm:?} didn't detect corruption"));
+            assert_eq!(
+                &Error::ChecksumMismatch {
+                    expected,
+                    actual: calculate_checksum(algorithm, corrupted_text.as_bytes()),
+                },
+                error.downcast_ref::<Error>().unwrap(),
+                "{algorithm:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checksum_validated_body_fails_collection_on_mismatch() {
+        let body = ChecksumBody::new(
+            SdkBody::from("This is some test text for an SdkBody"),
+            ChecksumAlgorithm::Crc32.into_impl(),
+            Bytes::from_static(&[0x00, 0x00, 0x00, 0x00]),
+        );
+
+        let err = ByteStream::new(SdkBody::from_body_0_4(body))
+            .collect()
+            .await
+            .expect_err("checksum mismatch");
+        let source = std::error::Error::source(&err).expect("the mismatch is the source");
+        assert_eq!(
+            "body checksum mismatch. expected body checksum to be 00000000 but it was 99b01f72",
+            source.to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_checksum_validated_body_handles_empty_bodies() {
+        for algorithm in ALGORITHMS {
+            let expected = calculate_checksum(algorithm, b"");
+            let body = ChecksumBody::new(SdkBody::empty(), algorithm.into_impl(), expected);
+            let collected = ByteStream::new(SdkBody::from_body_0_4(body))
+                .collect()
+                .await
+                .unwrap_or_else(|err| panic!("{algorithm:?}: {err}"));
+            assert!(collected.into_bytes().is_empty());
+
+            let body = ChecksumBody::new(
+                SdkBody::empty(),
+                algorithm.into_impl(),
+                calculate_checksum(algorithm, b"not empty"),
+            );
+            ByteStream::new(SdkBody::from_body_0_4(body))
+                .collect()
+                .await
+                .expect_err("checksum mismatch");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checksum_validated_body_handles_multiple_chunks() {
+        let chunks = ["This is some ", "test text ", "for an ", "SdkBody"];
+        let input_text = chunks.concat();
+        for algorithm in ALGORITHMS {
+            let expected = calculate_checksum(algorithm, input_text.as_bytes());
+            let body = ChecksumBody::new(chunked_body(&chunks), algorithm.into_impl(), expected);
+            let collected = ByteStream::new(SdkBody::from_body_0_4(body))
+                .collect()
+                .await
+                .unwrap_or_else(|err| panic!("{algorithm:?}: {err}"));
+            assert_eq!(input_text.as_bytes(), &collected.into_bytes()[..]);
+        }
+    }
 }
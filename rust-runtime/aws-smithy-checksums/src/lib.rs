@@ -27,6 +27,7 @@ pub mod http;
 // Valid checksum algorithm names
 pub const CRC_32_NAME: &str = "crc32";
 pub const CRC_32_C_NAME: &str = "crc32c";
+pub const CRC_64_NVME_NAME: &str = "crc64nvme";
 pub const SHA_1_NAME: &str = "sha1";
 pub const SHA_256_NAME: &str = "sha256";
 pub const MD5_NAME: &str = "md5";
@@ -36,6 +37,7 @@ pub const MD5_NAME: &str = "md5";
 pub enum ChecksumAlgorithm {
     Crc32,
     Crc32c,
+    Crc64Nvme,
     Md5,
     Sha1,
     Sha256,
@@ -47,6 +49,7 @@ impl FromStr for ChecksumAlgorithm {
     /// Create a new `ChecksumAlgorithm` from an algorithm name. Valid algorithm names are:
     /// - "crc32"
     /// - "crc32c"
+    /// - "crc64nvme"
     /// - "sha1"
     /// - "sha256"
     /// - "md5"
@@ -57,6 +60,8 @@ impl FromStr for ChecksumAlgorithm {
             Ok(Self::Crc32)
         } else if checksum_algorithm.eq_ignore_ascii_case(CRC_32_C_NAME) {
             Ok(Self::Crc32c)
+        } else if checksum_algorithm.eq_ignore_ascii_case(CRC_64_NVME_NAME) {
+            Ok(Self::Crc64Nvme)
         } else if checksum_algorithm.eq_ignore_ascii_case(SHA_1_NAME) {
             Ok(Self::Sha1)
         } else if checksum_algorithm.eq_ignore_ascii_case(SHA_256_NAME) {
@@ -75,6 +80,7 @@ impl ChecksumAlgorithm {
         match self {
             Self::Crc32 => Box::<Crc32>::default(),
             Self::Crc32c => Box::<Crc32c>::default(),
+            Self::Crc64Nvme => Box::<Crc64Nvme>::default(),
             Self::Md5 => Box::<Md5>::default(),
             Self::Sha1 => Box::<Sha1>::default(),
             Self::Sha256 => Box::<Sha256>::default(),
@@ -86,6 +92,7 @@ impl ChecksumAlgorithm {
         match self {
             Self::Crc32 => CRC_32_NAME,
             Self::Crc32c => CRC_32_C_NAME,
+            Self::Crc64Nvme => CRC_64_NVME_NAME,
             Self::Md5 => MD5_NAME,
             Self::Sha1 => SHA_1_NAME,
             Self::Sha256 => SHA_256_NAME,
@@ -183,6 +190,45 @@ impl Checksum for Crc32c {
     }
 }
 
+#[derive(Default)]
+struct Crc64Nvme {
+    hasher: crc64fast_nvme::Digest,
+}
+
+// `crc64fast_nvme::Digest` doesn't implement `Debug`
+impl std::fmt::Debug for Crc64Nvme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Crc64Nvme").finish_non_exhaustive()
+    }
+}
+
+impl Crc64Nvme {
+    fn update(&mut self, bytes: &[u8]) {
+        self.hasher.write(bytes);
+    }
+
+    fn finalize(self) -> Bytes {
+        Bytes::copy_from_slice(self.hasher.sum64().to_be_bytes().as_slice())
+    }
+
+    // Size of the checksum in bytes
+    fn size() -> u64 {
+        8
+    }
+}
+
+impl Checksum for Crc64Nvme {
+    fn update(&mut self, bytes: &[u8]) {
+        Self::update(self, bytes)
+    }
+    fn finalize(self: Box<Self>) -> Bytes {
+        Self::finalize(*self)
+    }
+    fn size(&self) -> u64 {
+        Self::size()
+    }
+}
+
 #[derive(Debug, Default)]
 struct Sha1 {
     hasher: sha1::Sha1,
@@ -293,10 +339,10 @@ impl Checksum for Md5 {
 mod tests {
     use super::{
         http::{
-            CRC_32_C_HEADER_NAME, CRC_32_HEADER_NAME, MD5_HEADER_NAME, SHA_1_HEADER_NAME,
-            SHA_256_HEADER_NAME,
+            CRC_32_C_HEADER_NAME, CRC_32_HEADER_NAME, CRC_64_NVME_HEADER_NAME, MD5_HEADER_NAME,
+            SHA_1_HEADER_NAME, SHA_256_HEADER_NAME,
         },
-        Crc32, Crc32c, Md5, Sha1, Sha256,
+        Crc32, Crc32c, Crc64Nvme, Md5, Sha1, Sha256,
     };
 
     use crate::http::HttpChecksum;
@@ -349,6 +395,19 @@ mod tests {
         assert_eq!(decoded_checksum, expected_checksum);
     }
 
+    #[test]
+    fn test_crc64nvme_checksum() {
+        let mut checksum = Crc64Nvme::default();
+        checksum.update(TEST_DATA.as_bytes());
+        let checksum_result = Box::new(checksum).headers();
+        let encoded_checksum = checksum_result.get(CRC_64_NVME_HEADER_NAME).unwrap();
+        let decoded_checksum = base64_encoded_checksum_to_hex_string(encoded_checksum);
+
+        let expected_checksum = "0xAECAF3AF9C98A855";
+
+        assert_eq!(decoded_checksum, expected_checksum);
+    }
+
     #[test]
     fn test_sha1_checksum() {
         let mut checksum = Sha1::default();
@@ -389,6 +448,36 @@ mod tests {
         assert_eq!(decoded_checksum, expected_checksum);
     }
 
+    #[test]
+    fn test_incremental_updates_match_a_single_update() {
+        use crate::Checksum;
+
+        let data = TEST_DATA.repeat(1000);
+        for algorithm in [
+            ChecksumAlgorithm::Crc32,
+            ChecksumAlgorithm::Crc32c,
+            ChecksumAlgorithm::Crc64Nvme,
+            ChecksumAlgorithm::Md5,
+            ChecksumAlgorithm::Sha1,
+            ChecksumAlgorithm::Sha256,
+        ] {
+            let mut whole = algorithm.into_impl();
+            whole.update(data.as_bytes());
+
+            let mut chunked = algorithm.into_impl();
+            for chunk in data.as_bytes().chunks(7) {
+                chunked.update(chunk);
+            }
+
+            assert_eq!(
+                whole.finalize(),
+                chunked.finalize(),
+                "{} checksums differ",
+                algorithm.as_str()
+            );
+        }
+    }
+
     #[test]
     fn test_checksum_algorithm_returns_error_for_unknown() {
         let error = "some invalid checksum algorithm"
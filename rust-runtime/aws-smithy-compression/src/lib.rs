@@ -24,6 +24,7 @@ use std::str::FromStr;
 pub mod body;
 mod gzip;
 pub mod http;
+pub mod policy;
 
 // Valid compression algorithm names
 /// The name of the `gzip` algorithm.
@@ -0,0 +1,371 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Rules deciding whether a request is compressed, and a record of that decision.
+
+use crate::CompressionOptions;
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::fmt;
+
+/// Media types whose content is already compressed, and that aren't compressed again by default.
+const COMPRESSED_MEDIA_TYPES: &[&str] = &[
+    "application/gzip",
+    "application/x-gzip",
+    "application/zip",
+    "application/x-bzip2",
+    "application/x-xz",
+    "application/x-7z-compressed",
+    "application/vnd.rar",
+    "application/zstd",
+    "audio/aac",
+    "audio/mpeg",
+    "audio/ogg",
+    "image/avif",
+    "image/gif",
+    "image/jpeg",
+    "image/png",
+    "image/webp",
+    "video/*",
+];
+
+/// Properties of a serialized request that a [`CompressionPolicy`] decides on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestProperties<'a> {
+    content_type: Option<&'a str>,
+    size: Option<u64>,
+    streaming: bool,
+}
+
+impl<'a> RequestProperties<'a> {
+    /// Describes a request with the given `Content-Type`, and a body of the given size, if known.
+    ///
+    /// `streaming` is `true` if the body is streamed, rather than held in memory.
+    pub fn new(content_type: Option<&'a str>, size: Option<u64>, streaming: bool) -> Self {
+        Self {
+            content_type,
+            size,
+            streaming,
+        }
+    }
+}
+
+/// Decides which requests are worth compressing, beyond [`CompressionOptions`].
+///
+/// A request is compressed if compression is [enabled](CompressionOptions::is_enabled) and:
+/// - its `Content-Type` isn't excluded, see [`with_content_type`](Self::with_content_type). Media
+///   types that are already compressed, such as `application/gzip` or `image/png`, are excluded by
+///   default.
+/// - its body is at least [`min_compression_size_bytes`](CompressionOptions::min_compression_size_bytes)
+///   long, or [`min_streaming_size_bytes`](Self::with_min_streaming_size_bytes) long if it is
+///   streamed. Streaming bodies of unknown size are compressed.
+/// - its body isn't longer than [`max_size_bytes`](Self::with_max_size_bytes), if set. Compressing
+///   large bodies can add more latency than it saves in transfer time.
+///
+/// Evaluating a policy doesn't allocate, so it can be done for every request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompressionPolicy {
+    // Later rules take precedence over earlier ones, and all of them over `COMPRESSED_MEDIA_TYPES`
+    content_types: Vec<(String, bool)>,
+    min_streaming_size_bytes: Option<u64>,
+    max_size_bytes: Option<u64>,
+}
+
+impl CompressionPolicy {
+    /// Creates the default policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether requests with the given media type are compressed.
+    ///
+    /// `media_type` is matched against the `Content-Type` of the request, without its parameters
+    /// and ignoring case. It can be a range like `video/*`. Rules added later take precedence, so
+    /// `.with_content_type("image/*", false).with_content_type("image/svg+xml", true)` compresses
+    /// SVG images but no other images.
+    pub fn with_content_type(mut self, media_type: impl Into<String>, compress: bool) -> Self {
+        self.content_types.push((media_type.into(), compress));
+        self
+    }
+
+    /// Sets the minimum size of streaming bodies to compress.
+    ///
+    /// By default, this is the same as
+    /// [`min_compression_size_bytes`](CompressionOptions::min_compression_size_bytes).
+    pub fn with_min_streaming_size_bytes(mut self, min_streaming_size_bytes: u64) -> Self {
+        self.min_streaming_size_bytes = Some(min_streaming_size_bytes);
+        self
+    }
+
+    /// Sets the size above which bodies aren't compressed.
+    ///
+    /// By default, bodies are compressed no matter how large they are.
+    pub fn with_max_size_bytes(mut self, max_size_bytes: u64) -> Self {
+        self.max_size_bytes = Some(max_size_bytes);
+        self
+    }
+
+    /// Decides whether a request is compressed, returning why it isn't.
+    pub fn evaluate(
+        &self,
+        options: &CompressionOptions,
+        request: RequestProperties<'_>,
+    ) -> Result<(), SkipReason> {
+        if !options.is_enabled() {
+            return Err(SkipReason::Disabled);
+        }
+        if let Some(content_type) = request.content_type {
+            if !self.compresses_content_type(content_type) {
+                return Err(SkipReason::ContentType);
+            }
+        }
+        let Some(size) = request.size else {
+            return Ok(());
+        };
+        let min_size = match (request.streaming, self.min_streaming_size_bytes) {
+            (true, Some(min_streaming_size_bytes)) => min_streaming_size_bytes,
+            _ => options.min_compression_size_bytes() as u64,
+        };
+        if size < min_size {
+            return Err(SkipReason::BelowMinSize);
+        }
+        if matches!(self.max_size_bytes, Some(max_size_bytes) if size > max_size_bytes) {
+            return Err(SkipReason::AboveMaxSize);
+        }
+        Ok(())
+    }
+
+    fn compresses_content_type(&self, content_type: &str) -> bool {
+        let essence = content_type
+            .split_once(';')
+            .map_or(content_type, |(essence, _)| essence)
+            .trim();
+        let rule = self
+            .content_types
+            .iter()
+            .rev()
+            .find(|(media_type, _)| media_type_matches(media_type, essence));
+        match rule {
+            Some((_, compress)) => *compress,
+            None => !COMPRESSED_MEDIA_TYPES
+                .iter()
+                .any(|media_type| media_type_matches(media_type, essence)),
+        }
+    }
+}
+
+impl Storable for CompressionPolicy {
+    type Storer = StoreReplace<Self>;
+}
+
+fn media_type_matches(pattern: &str, essence: &str) -> bool {
+    match pattern.strip_suffix("/*") {
+        Some(type_) => essence
+            .split_once('/')
+            .is_some_and(|(essence_type, _)| essence_type.eq_ignore_ascii_case(type_)),
+        None => pattern.eq_ignore_ascii_case(essence),
+    }
+}
+
+/// Why a request wasn't compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SkipReason {
+    /// Compression is disabled.
+    Disabled,
+    /// The content type of the request is excluded by the [`CompressionPolicy`].
+    ContentType,
+    /// The body is smaller than the minimum size to compress.
+    BelowMinSize,
+    /// The body is larger than the maximum size to compress.
+    AboveMaxSize,
+}
+
+impl SkipReason {
+    /// Returns the reason as a string, for use in logs and metrics.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Disabled => "disabled",
+            Self::ContentType => "content_type",
+            Self::BelowMinSize => "below_min_size",
+            Self::AboveMaxSize => "above_max_size",
+        }
+    }
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Whether a request was compressed, recorded in the config bag of the request.
+///
+/// Interceptors can load it from the config bag after the request is sent, for example in
+/// `read_after_attempt`, to measure how much compression saves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionDecision {
+    skip_reason: Option<SkipReason>,
+    original_size: Option<u64>,
+    compressed_size: Option<u64>,
+}
+
+impl CompressionDecision {
+    /// A request whose body of `original_size` was compressed into `compressed_size`.
+    ///
+    /// Either size is `None` if it isn't known before the request is sent, as with streaming bodies.
+    pub fn engaged(original_size: Option<u64>, compressed_size: Option<u64>) -> Self {
+        Self {
+            skip_reason: None,
+            original_size,
+            compressed_size,
+        }
+    }
+
+    /// A request whose body of `original_size` wasn't compressed, because of `reason`.
+    pub fn skipped(reason: SkipReason, original_size: Option<u64>) -> Self {
+        Self {
+            skip_reason: Some(reason),
+            original_size,
+            compressed_size: None,
+        }
+    }
+
+    /// Returns `true` if the request was compressed.
+    pub fn is_engaged(&self) -> bool {
+        self.skip_reason.is_none()
+    }
+
+    /// Returns why the request wasn't compressed, if it wasn't.
+    pub fn skip_reason(&self) -> Option<SkipReason> {
+        self.skip_reason
+    }
+
+    /// Returns the size of the body before compression, if known.
+    pub fn original_size(&self) -> Option<u64> {
+        self.original_size
+    }
+
+    /// Returns the size of the compressed body, if the request was compressed and the size is known.
+    pub fn compressed_size(&self) -> Option<u64> {
+        self.compressed_size
+    }
+}
+
+impl Storable for CompressionDecision {
+    type Storer = StoreReplace<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CompressionPolicy, RequestProperties, SkipReason};
+    use crate::CompressionOptions;
+    use pretty_assertions::assert_eq;
+
+    fn options() -> CompressionOptions {
+        CompressionOptions::default()
+            .with_min_compression_size_bytes(100)
+            .unwrap()
+    }
+
+    fn in_memory(content_type: &str, size: u64) -> RequestProperties<'_> {
+        RequestProperties::new(Some(content_type), Some(size), false)
+    }
+
+    #[test]
+    fn disabled_compression_skips_every_request() {
+        let options = options().with_enabled(false);
+        assert_eq!(
+            Err(SkipReason::Disabled),
+            CompressionPolicy::new().evaluate(&options, in_memory("application/json", 1000))
+        );
+    }
+
+    #[test]
+    fn compressed_media_types_are_skipped_by_default() {
+        let policy = CompressionPolicy::new();
+        let evaluate = |content_type| policy.evaluate(&options(), in_memory(content_type, 1000));
+
+        assert_eq!(Ok(()), evaluate("application/json"));
+        assert_eq!(Ok(()), evaluate("application/x-amz-json-1.1"));
+        assert_eq!(Err(SkipReason::ContentType), evaluate("application/gzip"));
+        assert_eq!(Err(SkipReason::ContentType), evaluate("IMAGE/PNG"));
+        assert_eq!(
+            Err(SkipReason::ContentType),
+            evaluate("video/mp4; codecs=\"avc1\"")
+        );
+        // Requests without a content type are compressed
+        assert_eq!(
+            Ok(()),
+            policy.evaluate(&options(), RequestProperties::new(None, Some(1000), false))
+        );
+    }
+
+    #[test]
+    fn later_content_type_rules_take_precedence() {
+        let policy = CompressionPolicy::new()
+            .with_content_type("image/*", false)
+            .with_content_type("image/svg+xml", true)
+            .with_content_type("text/csv", false);
+        let evaluate = |content_type| policy.evaluate(&options(), in_memory(content_type, 1000));
+
+        assert_eq!(Ok(()), evaluate("image/svg+xml"));
+        assert_eq!(Err(SkipReason::ContentType), evaluate("image/bmp"));
+        assert_eq!(
+            Err(SkipReason::ContentType),
+            evaluate("text/csv; charset=utf-8")
+        );
+        assert_eq!(Ok(()), evaluate("text/plain"));
+
+        let policy = CompressionPolicy::new().with_content_type("image/png", true);
+        assert_eq!(
+            Ok(()),
+            policy.evaluate(&options(), in_memory("image/png", 1000))
+        );
+    }
+
+    #[test]
+    fn streaming_bodies_have_their_own_minimum_size() {
+        let policy = CompressionPolicy::new().with_min_streaming_size_bytes(1000);
+        let evaluate = |size, streaming| {
+            policy.evaluate(
+                &options(),
+                RequestProperties::new(Some("text/plain"), size, streaming),
+            )
+        };
+
+        assert_eq!(Err(SkipReason::BelowMinSize), evaluate(Some(99), false));
+        assert_eq!(Ok(()), evaluate(Some(100), false));
+        assert_eq!(Err(SkipReason::BelowMinSize), evaluate(Some(999), true));
+        assert_eq!(Ok(()), evaluate(Some(1000), true));
+        assert_eq!(Ok(()), evaluate(None, true));
+
+        // Without a streaming minimum, streaming bodies use the minimum of in-memory ones
+        let policy = CompressionPolicy::new();
+        assert_eq!(
+            Err(SkipReason::BelowMinSize),
+            policy.evaluate(
+                &options(),
+                RequestProperties::new(Some("text/plain"), Some(99), true)
+            )
+        );
+    }
+
+    #[test]
+    fn bodies_above_the_maximum_size_are_skipped() {
+        let policy = CompressionPolicy::new().with_max_size_bytes(1000);
+        let evaluate = |size, streaming| {
+            policy.evaluate(
+                &options(),
+                RequestProperties::new(Some("text/plain"), size, streaming),
+            )
+        };
+
+        assert_eq!(Ok(()), evaluate(Some(1000), false));
+        assert_eq!(Err(SkipReason::AboveMaxSize), evaluate(Some(1001), false));
+        assert_eq!(Err(SkipReason::AboveMaxSize), evaluate(Some(1001), true));
+        // The size of a streaming body may not be known before it's sent
+        assert_eq!(Ok(()), evaluate(None, true));
+    }
+}
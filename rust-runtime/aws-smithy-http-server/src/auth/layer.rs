@@ -0,0 +1,148 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tower::{Layer, Service};
+
+use super::{AuthError, Authenticator};
+
+/// Records that the request carries credentials that aren't valid, for [`AuthService`](super::AuthService)
+/// to reject it.
+#[derive(Debug, Clone)]
+pub(super) struct AuthnFailure(pub(super) AuthError);
+
+/// A [`Layer`] applying [`AuthnService`], which authenticates requests with an [`Authenticator`].
+///
+/// See the [module documentation](crate::auth) for how it is used.
+pub struct AuthnLayer<A, P> {
+    authenticator: A,
+    _principal: PhantomData<fn() -> P>,
+}
+
+impl<A, P> AuthnLayer<A, P> {
+    /// Creates a layer authenticating requests with `authenticator`.
+    pub fn new(authenticator: A) -> Self
+    where
+        A: Authenticator<P>,
+    {
+        Self {
+            authenticator,
+            _principal: PhantomData,
+        }
+    }
+}
+
+/// We manually implement `Clone` instead of adding `#[derive(Clone)]` because we don't require
+/// `P` to be cloneable.
+impl<A, P> Clone for AuthnLayer<A, P>
+where
+    A: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            authenticator: self.authenticator.clone(),
+            _principal: PhantomData,
+        }
+    }
+}
+
+impl<A, P> std::fmt::Debug for AuthnLayer<A, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthnLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S, A, P> Layer<S> for AuthnLayer<A, P>
+where
+    A: Clone,
+{
+    type Service = AuthnService<S, A, P>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthnService {
+            inner,
+            authenticator: self.authenticator.clone(),
+            _principal: PhantomData,
+        }
+    }
+}
+
+/// A middleware [`Service`] that authenticates requests, and inserts the principal of authenticated
+/// requests into the request extensions.
+pub struct AuthnService<S, A, P> {
+    inner: S,
+    authenticator: A,
+    _principal: PhantomData<fn() -> P>,
+}
+
+impl<S, A, P> Clone for AuthnService<S, A, P>
+where
+    S: Clone,
+    A: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            authenticator: self.authenticator.clone(),
+            _principal: PhantomData,
+        }
+    }
+}
+
+impl<S, A, P> std::fmt::Debug for AuthnService<S, A, P>
+where
+    S: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthnService")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, A, P, B> Service<http::Request<B>> for AuthnService<S, A, P>
+where
+    S: Service<http::Request<B>> + Clone + Send + 'static,
+    S::Future: Send,
+    A: Authenticator<P>,
+    P: Send + Sync + 'static,
+    B: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let (mut parts, body) = req.into_parts();
+        let authentication = self.authenticator.authenticate(&parts);
+
+        // Replacing the service is necessary to avoid readiness problems.
+        // https://docs.rs/tower/latest/tower/trait.Service.html#be-careful-when-cloning-inner-services
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            match authentication.await {
+                Ok(Some(principal)) => {
+                    parts.extensions.insert(principal);
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    tracing::debug!(%error, "failed to authenticate request");
+                    parts.extensions.insert(AuthnFailure(error));
+                }
+            }
+            inner.call(http::Request::from_parts(parts, body)).await
+        })
+    }
+}
@@ -0,0 +1,298 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Authentication of requests and per-operation authorization.
+//!
+//! Authentication is split in two parts:
+//!
+//! - [`AuthnLayer`], applied _around_ the [`Router`](crate::routing::Router), runs an [`Authenticator`]
+//!   on every request. The principal it produces is inserted into the request extensions, so
+//!   handlers can take it as an [`Extension<P>`](crate::Extension).
+//! - [`AuthPlugin`], an HTTP plugin, evaluates the [`AuthRequirement`] that an [`AuthPolicy`] declares
+//!   for each operation, before the request reaches the handler. Requests that don't meet it are
+//!   rejected with a `401 Unauthorized` or a `403 Forbidden` [`AuthError`], rendered for the
+//!   protocol of the service.
+//!
+//! [`AuthnLayer`] never rejects requests itself, since the protocol of the request isn't known
+//! before routing. Failed authentications are recorded and rejected by [`AuthPlugin`], regardless
+//! of the requirement of the operation, so both must be used together.
+//!
+//! # Example
+//!
+//! ```
+//! use aws_smithy_http_server::auth::{AuthError, AuthExt, AuthPolicy, AuthRequirement, AuthnLayer};
+//! use aws_smithy_http_server::plugin::HttpPlugins;
+//! # use aws_smithy_http_server::{operation::OperationShape, shape_id::ShapeId};
+//! # pub struct GetStorage;
+//! # impl OperationShape for GetStorage {
+//! #     const ID: ShapeId = ShapeId::new("namespace#GetStorage", "namespace", "GetStorage");
+//! #     type Input = ();
+//! #     type Output = ();
+//! #     type Error = ();
+//! # }
+//!
+//! #[derive(Clone)]
+//! struct User {
+//!     name: String,
+//!     admin: bool,
+//! }
+//!
+//! // Requests without an `authorization` header are anonymous.
+//! let authn_layer = AuthnLayer::new(|parts: &http::request::Parts| {
+//!     let token = parts.headers.get("authorization").cloned();
+//!     async move {
+//!         match token.as_ref().map(|token| token.as_bytes()) {
+//!             None => Ok(None),
+//!             Some(b"Bearer ash") => Ok(Some(User { name: "ash".into(), admin: true })),
+//!             Some(_) => Err(AuthError::unauthenticated("invalid token")),
+//!         }
+//!     }
+//! });
+//!
+//! // Every operation requires an authenticated user, and `GetStorage` requires an admin.
+//! let policy = AuthPolicy::new(AuthRequirement::authenticated())
+//!     .operation::<GetStorage>(AuthRequirement::capability("admin", |user: &User| user.admin));
+//! let http_plugins = HttpPlugins::new().auth(policy);
+//! ```
+//!
+//! `authn_layer` is then added to the service config with `layer`, and `http_plugins` with
+//! `http_plugin`.
+
+mod layer;
+mod policy;
+
+pub use layer::{AuthnLayer, AuthnService};
+pub use policy::{AuthExt, AuthPlugin, AuthPolicy, AuthRequirement, AuthService};
+
+use crate::body::{to_boxed, BoxBody};
+use crate::extension::RuntimeErrorExtension;
+use crate::protocol::aws_json_10::AwsJson1_0;
+use crate::protocol::aws_json_11::AwsJson1_1;
+use crate::protocol::rest_json_1::RestJson1;
+use crate::protocol::rest_xml::RestXml;
+use crate::protocol::rpc_v2_cbor::RpcV2Cbor;
+use crate::response::IntoResponse;
+use aws_smithy_json::serialize::JsonObjectWriter;
+use aws_smithy_xml::encode::XmlWriter;
+use http::header::{HeaderValue, CONTENT_TYPE};
+use http::StatusCode;
+use std::fmt;
+use std::future::Future;
+
+const INVALID_AUTH_RESPONSE_PANIC_MESSAGE: &str = "invalid HTTP response for `AuthError`; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues";
+
+/// Authenticates requests, producing a principal of type `P`.
+///
+/// This is implemented for async closures taking the [`Parts`](http::request::Parts) of the request.
+/// The returned future must not borrow the parts, so clone what is needed out of them first.
+///
+/// The future resolves to:
+/// - `Ok(Some(principal))` when the request is authenticated.
+/// - `Ok(None)` when the request doesn't carry credentials, and is anonymous.
+/// - `Err(error)` when the request carries credentials that aren't valid.
+pub trait Authenticator<P> {
+    /// The future returned by [`authenticate`](Authenticator::authenticate).
+    type Future: Future<Output = Result<Option<P>, AuthError>> + Send + 'static;
+
+    /// Authenticates the request with the given `parts`.
+    fn authenticate(&self, parts: &http::request::Parts) -> Self::Future;
+}
+
+impl<P, F, Fut> Authenticator<P> for F
+where
+    F: Fn(&http::request::Parts) -> Fut,
+    Fut: Future<Output = Result<Option<P>, AuthError>> + Send + 'static,
+{
+    type Future = Fut;
+
+    fn authenticate(&self, parts: &http::request::Parts) -> Self::Future {
+        self(parts)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AuthErrorKind {
+    Unauthenticated,
+    Forbidden,
+}
+
+/// A request was rejected because it isn't authenticated, or isn't allowed to call the operation.
+///
+/// See the [module documentation](crate::auth) for when it is returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthError {
+    kind: AuthErrorKind,
+    message: String,
+}
+
+impl AuthError {
+    /// The request isn't authenticated, and is answered with a `401 Unauthorized`.
+    pub fn unauthenticated(message: impl Into<String>) -> Self {
+        Self {
+            kind: AuthErrorKind::Unauthenticated,
+            message: message.into(),
+        }
+    }
+
+    /// The request is authenticated but isn't allowed to call the operation, and is answered with a
+    /// `403 Forbidden`.
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self {
+            kind: AuthErrorKind::Forbidden,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the status code of the response.
+    pub fn status_code(&self) -> StatusCode {
+        match self.kind {
+            AuthErrorKind::Unauthenticated => StatusCode::UNAUTHORIZED,
+            AuthErrorKind::Forbidden => StatusCode::FORBIDDEN,
+        }
+    }
+
+    /// Returns the message sent to the client.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Name of the error rendered by the protocol specific [`IntoResponse`] implementations.
+    pub fn name(&self) -> &'static str {
+        match self.kind {
+            AuthErrorKind::Unauthenticated => "UnauthorizedException",
+            AuthErrorKind::Forbidden => "AccessDeniedException",
+        }
+    }
+
+    fn json_body(&self, error_type: Option<&str>) -> String {
+        let mut out = String::new();
+        let mut object = JsonObjectWriter::new(&mut out);
+        if let Some(error_type) = error_type {
+            object.key("__type").string(error_type);
+        }
+        object.key("message").string(&self.message);
+        object.finish();
+        out
+    }
+
+    fn into_protocol_response(
+        self,
+        content_type: &'static str,
+        body: impl Into<bytes::Bytes>,
+    ) -> http::Response<BoxBody> {
+        http::Response::builder()
+            .status(self.status_code())
+            .header(CONTENT_TYPE, content_type)
+            .extension(RuntimeErrorExtension::new(self.name().to_string()))
+            .body(to_boxed(body.into()))
+            .expect(INVALID_AUTH_RESPONSE_PANIC_MESSAGE)
+    }
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name(), self.message)
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+impl IntoResponse<RestJson1> for AuthError {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let body = self.json_body(None);
+        let name = self.name();
+        let mut response = self.into_protocol_response("application/json", body);
+        response
+            .headers_mut()
+            .insert("x-amzn-errortype", HeaderValue::from_static(name));
+        response
+    }
+}
+
+impl IntoResponse<AwsJson1_0> for AuthError {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let body = self.json_body(Some(self.name()));
+        self.into_protocol_response("application/x-amz-json-1.0", body)
+    }
+}
+
+impl IntoResponse<AwsJson1_1> for AuthError {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let body = self.json_body(Some(self.name()));
+        self.into_protocol_response("application/x-amz-json-1.1", body)
+    }
+}
+
+impl IntoResponse<RestXml> for AuthError {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let mut body = String::new();
+        {
+            let mut writer = XmlWriter::new(&mut body);
+            let mut error_response = writer.start_el("ErrorResponse").finish();
+            let mut error = error_response.start_el("Error").finish();
+            error.start_el("Code").finish().data(self.name());
+            error.start_el("Message").finish().data(&self.message);
+            error.finish();
+            error_response.finish();
+        }
+        self.into_protocol_response("application/xml", body)
+    }
+}
+
+impl IntoResponse<RpcV2Cbor> for AuthError {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let mut encoder = aws_smithy_cbor::Encoder::new(Vec::new());
+        encoder
+            .map(2)
+            .str("__type")
+            .str(self.name())
+            .str("message")
+            .str(&self.message);
+        let body = encoder.into_writer();
+        self.into_protocol_response("application/cbor", body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::test_helpers::get_body_as_string;
+
+    #[tokio::test]
+    async fn rest_json_sets_error_type_header() {
+        let response = IntoResponse::<RestJson1>::into_response(AuthError::unauthenticated("missing token"));
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()["x-amzn-errortype"], "UnauthorizedException");
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        assert_eq!(
+            get_body_as_string(response.into_body()).await,
+            r#"{"message":"missing token"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn aws_json_puts_error_type_in_body() {
+        let response = IntoResponse::<AwsJson1_0>::into_response(AuthError::forbidden("not an admin"));
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/x-amz-json-1.0");
+        assert_eq!(
+            get_body_as_string(response.into_body()).await,
+            r#"{"__type":"AccessDeniedException","message":"not an admin"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn rest_xml_renders_error_response() {
+        let response = IntoResponse::<RestXml>::into_response(AuthError::forbidden("not an admin"));
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            get_body_as_string(response.into_body()).await,
+            "<ErrorResponse><Error><Code>AccessDeniedException</Code><Message>not an admin</Message></Error></ErrorResponse>"
+        );
+    }
+}
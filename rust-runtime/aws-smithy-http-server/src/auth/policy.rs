@@ -0,0 +1,403 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::future::{ready, Ready};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use tower::Service;
+
+use super::layer::AuthnFailure;
+use super::AuthError;
+use crate::body::BoxBody;
+use crate::operation::OperationShape;
+use crate::plugin::{Either, HttpMarker, HttpPlugins, Plugin, PluginStack};
+use crate::response::IntoResponse;
+use crate::service::ServiceShape;
+use crate::shape_id::ShapeId;
+
+enum RequirementKind<P> {
+    Anonymous,
+    Authenticated,
+    Capability {
+        name: Cow<'static, str>,
+        check: Arc<dyn Fn(&P) -> bool + Send + Sync>,
+    },
+}
+
+/// What an operation requires from the principal of a request, see [`AuthPolicy`].
+pub struct AuthRequirement<P> {
+    kind: RequirementKind<P>,
+}
+
+impl<P> AuthRequirement<P> {
+    /// Anonymous requests are allowed, as well as authenticated ones.
+    pub fn anonymous() -> Self {
+        Self {
+            kind: RequirementKind::Anonymous,
+        }
+    }
+
+    /// Requests must be authenticated.
+    pub fn authenticated() -> Self {
+        Self {
+            kind: RequirementKind::Authenticated,
+        }
+    }
+
+    /// Requests must be authenticated, and `check` must return `true` for their principal.
+    ///
+    /// `name` names the capability in the message of the `403 Forbidden` response.
+    pub fn capability(name: impl Into<Cow<'static, str>>, check: impl Fn(&P) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            kind: RequirementKind::Capability {
+                name: name.into(),
+                check: Arc::new(check),
+            },
+        }
+    }
+
+    fn evaluate(&self, principal: Option<&P>) -> Result<(), AuthError> {
+        match (&self.kind, principal) {
+            (RequirementKind::Anonymous, _) => Ok(()),
+            (_, None) => Err(AuthError::unauthenticated("authentication is required")),
+            (RequirementKind::Authenticated, Some(_)) => Ok(()),
+            (RequirementKind::Capability { name, check }, Some(principal)) => {
+                if check(principal) {
+                    Ok(())
+                } else {
+                    Err(AuthError::forbidden(format!("missing the `{name}` capability")))
+                }
+            }
+        }
+    }
+}
+
+impl<P> Clone for AuthRequirement<P> {
+    fn clone(&self) -> Self {
+        let kind = match &self.kind {
+            RequirementKind::Anonymous => RequirementKind::Anonymous,
+            RequirementKind::Authenticated => RequirementKind::Authenticated,
+            RequirementKind::Capability { name, check } => RequirementKind::Capability {
+                name: name.clone(),
+                check: check.clone(),
+            },
+        };
+        Self { kind }
+    }
+}
+
+impl<P> std::fmt::Debug for AuthRequirement<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            RequirementKind::Anonymous => f.write_str("Anonymous"),
+            RequirementKind::Authenticated => f.write_str("Authenticated"),
+            RequirementKind::Capability { name, .. } => f.debug_tuple("Capability").field(name).finish(),
+        }
+    }
+}
+
+/// The [`AuthRequirement`] of every operation of a service.
+///
+/// Operations without their own requirement use the default requirement.
+pub struct AuthPolicy<P> {
+    default: AuthRequirement<P>,
+    operations: HashMap<ShapeId, AuthRequirement<P>>,
+}
+
+impl<P> AuthPolicy<P> {
+    /// Creates a policy where every operation has the `default` requirement.
+    pub fn new(default: AuthRequirement<P>) -> Self {
+        Self {
+            default,
+            operations: HashMap::new(),
+        }
+    }
+
+    /// Sets the requirement of the `Op` operation.
+    pub fn operation<Op>(mut self, requirement: AuthRequirement<P>) -> Self
+    where
+        Op: OperationShape,
+    {
+        self.operations.insert(Op::ID, requirement);
+        self
+    }
+
+    /// Returns the requirement of the operation with the given ID.
+    pub fn requirement(&self, operation: &ShapeId) -> &AuthRequirement<P> {
+        self.operations.get(operation).unwrap_or(&self.default)
+    }
+}
+
+impl<P> std::fmt::Debug for AuthPolicy<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthPolicy")
+            .field("default", &self.default)
+            .field("operations", &self.operations)
+            .finish()
+    }
+}
+
+/// A [`Plugin`] which applies [`AuthService`] to every operation, enforcing an [`AuthPolicy`].
+pub struct AuthPlugin<P> {
+    policy: Arc<AuthPolicy<P>>,
+}
+
+impl<P> AuthPlugin<P> {
+    /// Creates a plugin enforcing `policy`.
+    pub fn new(policy: AuthPolicy<P>) -> Self {
+        Self {
+            policy: Arc::new(policy),
+        }
+    }
+}
+
+impl<P> std::fmt::Debug for AuthPlugin<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthPlugin").field("policy", &self.policy).finish()
+    }
+}
+
+impl<Ser, Op, T, P> Plugin<Ser, Op, T> for AuthPlugin<P>
+where
+    Ser: ServiceShape,
+    Op: OperationShape,
+{
+    type Output = AuthService<Ser::Protocol, P, T>;
+
+    fn apply(&self, inner: T) -> Self::Output {
+        AuthService {
+            inner,
+            operation: Op::ID,
+            requirement: self.policy.requirement(&Op::ID).clone(),
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<P> HttpMarker for AuthPlugin<P> {}
+
+/// A [`Service`] rejecting requests that don't meet the [`AuthRequirement`] of the operation.
+pub struct AuthService<Protocol, P, S> {
+    inner: S,
+    operation: ShapeId,
+    requirement: AuthRequirement<P>,
+    _protocol: PhantomData<fn() -> Protocol>,
+}
+
+impl<Protocol, P, S> Clone for AuthService<Protocol, P, S>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            operation: self.operation.clone(),
+            requirement: self.requirement.clone(),
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<Protocol, P, S> std::fmt::Debug for AuthService<Protocol, P, S>
+where
+    S: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthService")
+            .field("inner", &self.inner)
+            .field("operation", &self.operation)
+            .field("requirement", &self.requirement)
+            .finish()
+    }
+}
+
+impl<Protocol, P, S, B> Service<http::Request<B>> for AuthService<Protocol, P, S>
+where
+    S: Service<http::Request<B>, Response = http::Response<BoxBody>>,
+    P: Send + Sync + 'static,
+    AuthError: IntoResponse<Protocol>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<S::Response, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        // Credentials that aren't valid are rejected even if the operation allows anonymous requests
+        let outcome = match req.extensions().get::<AuthnFailure>() {
+            Some(AuthnFailure(error)) => Err(error.clone()),
+            None => self.requirement.evaluate(req.extensions().get::<P>()),
+        };
+        match outcome {
+            Ok(()) => Either::Right {
+                value: self.inner.call(req),
+            },
+            Err(error) => {
+                tracing::debug!(operation = %self.operation.absolute(), %error, "rejected request");
+                Either::Left {
+                    value: ready(Ok(error.into_response())),
+                }
+            }
+        }
+    }
+}
+
+/// An extension trait for applying [`AuthPlugin`].
+pub trait AuthExt<CurrentPlugin> {
+    /// Rejects requests that don't meet the requirement `policy` declares for their operation.
+    ///
+    /// See the [module documentation](crate::auth) for details.
+    fn auth<P>(self, policy: AuthPolicy<P>) -> HttpPlugins<PluginStack<AuthPlugin<P>, CurrentPlugin>>;
+}
+
+impl<CurrentPlugin> AuthExt<CurrentPlugin> for HttpPlugins<CurrentPlugin> {
+    fn auth<P>(self, policy: AuthPolicy<P>) -> HttpPlugins<PluginStack<AuthPlugin<P>, CurrentPlugin>> {
+        self.push(AuthPlugin::new(policy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use http::StatusCode;
+    use tower::{service_fn, Layer, ServiceExt};
+
+    use super::*;
+    use crate::auth::AuthnLayer;
+    use crate::body::{empty, to_boxed};
+    use crate::protocol::rest_json_1::RestJson1;
+    use crate::protocol::test_helpers::get_body_as_string;
+
+    struct PokemonService;
+
+    impl ServiceShape for PokemonService {
+        const ID: ShapeId = ShapeId::new("com.example#PokemonService", "com.example", "PokemonService");
+        const VERSION: Option<&'static str> = None;
+        type Protocol = RestJson1;
+        type Operations = ();
+    }
+
+    macro_rules! operation {
+        ($name:ident) => {
+            struct $name;
+
+            impl OperationShape for $name {
+                const ID: ShapeId = ShapeId::new(
+                    concat!("com.example#", stringify!($name)),
+                    "com.example",
+                    stringify!($name),
+                );
+                type Input = ();
+                type Output = ();
+                type Error = ();
+            }
+        };
+    }
+
+    operation!(GetStorage);
+    operation!(GetStatistics);
+    operation!(GetSpecies);
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Trainer {
+        name: &'static str,
+        storage_access: bool,
+    }
+
+    fn policy() -> AuthPolicy<Trainer> {
+        AuthPolicy::new(AuthRequirement::anonymous())
+            .operation::<GetStatistics>(AuthRequirement::authenticated())
+            .operation::<GetStorage>(AuthRequirement::capability("storage", |trainer: &Trainer| {
+                trainer.storage_access
+            }))
+    }
+
+    /// Routes a request to `Op`, whose handler answers with the name of the trainer, if any.
+    async fn call<Op: OperationShape>(passcode: Option<&'static str>) -> (StatusCode, String) {
+        let handler = service_fn(|req: http::Request<()>| async move {
+            let body = match req.extensions().get::<Trainer>() {
+                Some(trainer) => to_boxed(trainer.name),
+                None => empty(),
+            };
+            Ok::<_, Infallible>(http::Response::new(body))
+        });
+        let operation = Plugin::<PokemonService, Op, _>::apply(&AuthPlugin::new(policy()), handler);
+        let authn = AuthnLayer::new(|parts: &http::request::Parts| {
+            let passcode = parts.headers.get("passcode").cloned();
+            async move {
+                match passcode.as_ref().map(|passcode| passcode.as_bytes()) {
+                    None => Ok(None),
+                    Some(b"pikachu123") => Ok(Some(Trainer {
+                        name: "ash",
+                        storage_access: true,
+                    })),
+                    Some(b"onix123") => Ok(Some(Trainer {
+                        name: "brock",
+                        storage_access: false,
+                    })),
+                    Some(_) => Err(AuthError::unauthenticated("unknown passcode")),
+                }
+            }
+        });
+
+        let mut request = http::Request::builder();
+        if let Some(passcode) = passcode {
+            request = request.header("passcode", passcode);
+        }
+        let response = authn.layer(operation).oneshot(request.body(()).unwrap()).await.unwrap();
+        let status = response.status();
+        (status, get_body_as_string(response.into_body()).await)
+    }
+
+    #[tokio::test]
+    async fn allowed() {
+        assert_eq!(
+            (StatusCode::OK, "ash".to_string()),
+            call::<GetStorage>(Some("pikachu123")).await
+        );
+        assert_eq!(
+            (StatusCode::OK, "brock".to_string()),
+            call::<GetStatistics>(Some("onix123")).await
+        );
+    }
+
+    #[tokio::test]
+    async fn denied() {
+        let (status, body) = call::<GetStorage>(Some("onix123")).await;
+        assert_eq!(StatusCode::FORBIDDEN, status);
+        assert_eq!(r#"{"message":"missing the `storage` capability"}"#, body);
+
+        let (status, body) = call::<GetStorage>(None).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, status);
+        assert_eq!(r#"{"message":"authentication is required"}"#, body);
+
+        let (status, _) = call::<GetStatistics>(None).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, status);
+    }
+
+    #[tokio::test]
+    async fn anonymous_permitted() {
+        assert_eq!((StatusCode::OK, String::new()), call::<GetSpecies>(None).await);
+        assert_eq!(
+            (StatusCode::OK, "brock".to_string()),
+            call::<GetSpecies>(Some("onix123")).await
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_credentials_are_rejected_even_when_anonymous_is_permitted() {
+        let (status, body) = call::<GetSpecies>(Some("wrong")).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, status);
+        assert_eq!(r#"{"message":"unknown passcode"}"#, body);
+    }
+}
@@ -0,0 +1,156 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Turning panics in handlers into `500 Internal Server Error` responses.
+//!
+//! Without it, a panicking handler unwinds into hyper, and what happens next depends on how the
+//! server is run: the connection may be closed without a response, or the panic may take down the
+//! task serving other requests on that connection. [`CatchPanicPlugin`], an HTTP plugin, catches
+//! panics raised while calling the handler of an operation or polling its future, and answers the
+//! request with the `InternalFailureException` of the protocol of the service instead. The panic
+//! message is never sent to the client. The connection is left open and keeps serving requests.
+//!
+//! Every caught panic is logged in a `tracing` event with the operation, the [`ServerRequestId`] if
+//! the request has one, the panic message and, if [enabled](CatchPanicPlugin::capture_backtraces),
+//! a backtrace. It is then passed to the [`on_panic`](CatchPanicPlugin::on_panic) callback, for
+//! example to count panics in a metric.
+//!
+//! # Streaming responses
+//!
+//! Once the handler has responded, the status and headers of the response may already have been
+//! sent, so a panic while producing the response body can't be turned into an error response. The
+//! response body is aborted instead: with HTTP/1.1 the connection is closed, and with HTTP/2 only
+//! the stream of the response is reset.
+//!
+//! # Example
+//!
+//! ```
+//! use aws_smithy_http_server::catch_panic::{CatchPanicExt, CatchPanicPlugin};
+//! use aws_smithy_http_server::plugin::HttpPlugins;
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//! use std::sync::Arc;
+//!
+//! let panics = Arc::new(AtomicU64::new(0));
+//! let plugin = CatchPanicPlugin::new().on_panic({
+//!     let panics = panics.clone();
+//!     move |_panic| {
+//!         panics.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! });
+//! let http_plugins = HttpPlugins::new().catch_panics(plugin);
+//! ```
+//!
+//! `http_plugins` is then added to the service config with `http_plugin`. Push it last, so that it
+//! also catches panics in the other plugins.
+//!
+//! [`ServerRequestId`]: crate::request::request_id::ServerRequestId
+
+mod service;
+
+pub use service::{CatchPanicExt, CatchPanicPlugin, CatchPanicService};
+
+use crate::shape_id::ShapeId;
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::{Cell, RefCell};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Once;
+
+/// A panic caught by [`CatchPanicService`].
+#[derive(Debug)]
+pub struct HandlerPanic {
+    operation: ShapeId,
+    request_id: Option<String>,
+    message: String,
+    backtrace: Option<Backtrace>,
+    response_started: bool,
+}
+
+impl HandlerPanic {
+    /// Returns the ID of the operation whose handler panicked.
+    pub fn operation(&self) -> &ShapeId {
+        &self.operation
+    }
+
+    /// Returns the [`ServerRequestId`](crate::request::request_id::ServerRequestId) of the request,
+    /// if it has one.
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    /// Returns the panic message, or `"Box<dyn Any>"` if the panic payload isn't a string.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns where the handler panicked, if [backtraces are captured](CatchPanicPlugin::capture_backtraces).
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.backtrace.as_ref()
+    }
+
+    /// Returns `true` if the handler panicked while producing the response body, after it had
+    /// responded. The response body was then aborted rather than replaced by an error response.
+    pub fn response_started(&self) -> bool {
+        self.response_started
+    }
+}
+
+/// What is left of a panic once it has been caught.
+struct CaughtPanic {
+    message: String,
+    backtrace: Option<Backtrace>,
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+thread_local! {
+    // Set while `catch` runs a closure that wants the backtrace of its panics.
+    static CAPTURE_BACKTRACE: Cell<bool> = const { Cell::new(false) };
+    static CAPTURED_BACKTRACE: RefCell<Option<Backtrace>> = const { RefCell::new(None) };
+}
+
+/// Wraps the panic hook with one that captures the backtrace of panics raised inside `catch`.
+///
+/// The payload of a caught panic doesn't say where it was raised: only the panic hook, which runs on
+/// the panicking thread before unwinding starts, can capture a backtrace.
+fn install_backtrace_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if CAPTURE_BACKTRACE.with(Cell::get) {
+                CAPTURED_BACKTRACE.with(|backtrace| *backtrace.borrow_mut() = Some(Backtrace::force_capture()));
+            }
+            previous(info);
+        }));
+    });
+}
+
+/// Runs `f`, catching its panics, and capturing their backtrace if `capture_backtrace` is set.
+fn catch<T>(capture_backtrace: bool, f: impl FnOnce() -> T) -> Result<T, CaughtPanic> {
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CAPTURE_BACKTRACE.with(|capture| capture.set(self.0));
+        }
+    }
+
+    let _restore = Restore(CAPTURE_BACKTRACE.with(|capture| capture.replace(capture_backtrace)));
+    catch_unwind(AssertUnwindSafe(f)).map_err(|payload| CaughtPanic {
+        message: panic_message(&*payload),
+        // A panic caught further down may have left its backtrace behind
+        backtrace: CAPTURED_BACKTRACE
+            .with(|backtrace| backtrace.borrow_mut().take())
+            .filter(|_| capture_backtrace),
+    })
+}
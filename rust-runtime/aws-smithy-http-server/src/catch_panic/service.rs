@@ -0,0 +1,476 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http_body::Body as HttpBody;
+use pin_project_lite::pin_project;
+use tower::Service;
+
+use super::{catch, install_backtrace_hook, CaughtPanic, HandlerPanic};
+use crate::body::BoxBody;
+use crate::operation::OperationShape;
+use crate::plugin::{HttpMarker, HttpPlugins, Plugin, PluginStack};
+use crate::response::IntoResponse;
+use crate::runtime_error::InternalFailureException;
+use crate::service::ServiceShape;
+use crate::shape_id::ShapeId;
+
+type OnPanic = Arc<dyn Fn(&HandlerPanic) + Send + Sync>;
+
+/// A [`Plugin`] which applies [`CatchPanicService`] to every operation.
+///
+/// See the [module documentation](crate::catch_panic) for details.
+#[derive(Clone, Default)]
+pub struct CatchPanicPlugin {
+    on_panic: Option<OnPanic>,
+    capture_backtraces: bool,
+}
+
+impl CatchPanicPlugin {
+    /// Creates a plugin that logs caught panics, without their backtrace.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `on_panic` with every caught panic, after it has been logged.
+    pub fn on_panic(mut self, on_panic: impl Fn(&HandlerPanic) + Send + Sync + 'static) -> Self {
+        self.on_panic = Some(Arc::new(on_panic));
+        self
+    }
+
+    /// Captures the backtrace of caught panics, to log it and pass it to the
+    /// [`on_panic`](Self::on_panic) callback.
+    ///
+    /// Backtraces are captured by wrapping the panic hook of the process, which is done the first
+    /// time a service capturing backtraces is created. The previous panic hook still runs for every
+    /// panic.
+    pub fn capture_backtraces(mut self, capture_backtraces: bool) -> Self {
+        self.capture_backtraces = capture_backtraces;
+        self
+    }
+}
+
+impl fmt::Debug for CatchPanicPlugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CatchPanicPlugin")
+            .field("on_panic", &self.on_panic.is_some())
+            .field("capture_backtraces", &self.capture_backtraces)
+            .finish()
+    }
+}
+
+impl<Ser, Op, T> Plugin<Ser, Op, T> for CatchPanicPlugin
+where
+    Ser: ServiceShape,
+    Op: OperationShape,
+{
+    type Output = CatchPanicService<Ser::Protocol, T>;
+
+    fn apply(&self, inner: T) -> Self::Output {
+        if self.capture_backtraces {
+            install_backtrace_hook();
+        }
+        CatchPanicService {
+            inner,
+            reporter: Arc::new(Reporter {
+                operation: Op::ID,
+                on_panic: self.on_panic.clone(),
+                capture_backtraces: self.capture_backtraces,
+            }),
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl HttpMarker for CatchPanicPlugin {}
+
+/// An extension trait for applying [`CatchPanicPlugin`].
+pub trait CatchPanicExt<CurrentPlugin> {
+    /// Answers requests whose handler panics with a `500 Internal Server Error`.
+    ///
+    /// See the [module documentation](crate::catch_panic) for details.
+    fn catch_panics(self, plugin: CatchPanicPlugin) -> HttpPlugins<PluginStack<CatchPanicPlugin, CurrentPlugin>>;
+}
+
+impl<CurrentPlugin> CatchPanicExt<CurrentPlugin> for HttpPlugins<CurrentPlugin> {
+    fn catch_panics(self, plugin: CatchPanicPlugin) -> HttpPlugins<PluginStack<CatchPanicPlugin, CurrentPlugin>> {
+        self.push(plugin)
+    }
+}
+
+/// Logs caught panics of an operation and passes them to the `on_panic` callback.
+struct Reporter {
+    operation: ShapeId,
+    on_panic: Option<OnPanic>,
+    capture_backtraces: bool,
+}
+
+impl Reporter {
+    fn report(&self, request_id: Option<String>, caught: CaughtPanic, response_started: bool) {
+        let panic = HandlerPanic {
+            operation: self.operation.clone(),
+            request_id,
+            message: caught.message,
+            backtrace: caught.backtrace,
+            response_started,
+        };
+        tracing::error!(
+            operation = %panic.operation.absolute(),
+            request_id = panic.request_id(),
+            panic.message = panic.message(),
+            panic.backtrace = panic.backtrace().map(tracing::field::display),
+            response_started,
+            "handler panicked"
+        );
+        if let Some(on_panic) = &self.on_panic {
+            on_panic(&panic);
+        }
+    }
+}
+
+/// A [`Service`] answering requests whose handler panics with the `InternalFailureException` of
+/// `Protocol`.
+///
+/// See the [module documentation](crate::catch_panic) for details.
+pub struct CatchPanicService<Protocol, S> {
+    inner: S,
+    reporter: Arc<Reporter>,
+    _protocol: PhantomData<fn() -> Protocol>,
+}
+
+impl<Protocol, S> Clone for CatchPanicService<Protocol, S>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            reporter: self.reporter.clone(),
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<Protocol, S> fmt::Debug for CatchPanicService<Protocol, S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CatchPanicService")
+            .field("inner", &self.inner)
+            .field("operation", &self.reporter.operation)
+            .finish()
+    }
+}
+
+#[cfg(feature = "request-id")]
+fn request_id<B>(req: &http::Request<B>) -> Option<String> {
+    req.extensions()
+        .get::<crate::request::request_id::ServerRequestId>()
+        .map(ToString::to_string)
+}
+
+#[cfg(not(feature = "request-id"))]
+fn request_id<B>(_req: &http::Request<B>) -> Option<String> {
+    None
+}
+
+impl<Protocol, S, B> Service<http::Request<B>> for CatchPanicService<Protocol, S>
+where
+    S: Service<http::Request<B>, Response = http::Response<BoxBody>>,
+    S::Future: Send + 'static,
+    InternalFailureException: IntoResponse<Protocol>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let request_id = request_id(&req);
+        let reporter = self.reporter.clone();
+        let handler = match catch(reporter.capture_backtraces, || self.inner.call(req)) {
+            Ok(handler) => handler,
+            Err(caught) => {
+                reporter.report(request_id, caught, false);
+                return Box::pin(async { Ok(InternalFailureException.into_response()) });
+            }
+        };
+
+        Box::pin(async move {
+            let caught = CatchUnwind {
+                inner: handler,
+                capture_backtrace: reporter.capture_backtraces,
+            }
+            .await;
+            match caught {
+                Ok(response) => Ok(response?.map(|body| {
+                    CatchPanicBody {
+                        inner: body,
+                        reporter,
+                        request_id,
+                        panicked: false,
+                    }
+                    .boxed_unsync()
+                })),
+                Err(caught) => {
+                    reporter.report(request_id, caught, false);
+                    Ok(InternalFailureException.into_response())
+                }
+            }
+        })
+    }
+}
+
+pin_project! {
+    /// Resolves to the output of `inner`, or to the panic raised while polling it.
+    struct CatchUnwind<F> {
+        #[pin]
+        inner: F,
+        capture_backtrace: bool,
+    }
+}
+
+impl<F> Future for CatchUnwind<F>
+where
+    F: Future,
+{
+    type Output = Result<F::Output, CaughtPanic>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        match catch(*this.capture_backtrace, || this.inner.poll(cx)) {
+            Ok(poll) => poll.map(Ok),
+            Err(caught) => Poll::Ready(Err(caught)),
+        }
+    }
+}
+
+/// The error aborting a response body whose handler panicked.
+#[derive(Debug)]
+struct ResponseBodyPanicked;
+
+impl fmt::Display for ResponseBodyPanicked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the handler panicked while producing the response body")
+    }
+}
+
+impl std::error::Error for ResponseBodyPanicked {}
+
+pin_project! {
+    /// A response body that is aborted if polling it panics.
+    struct CatchPanicBody {
+        #[pin]
+        inner: BoxBody,
+        reporter: Arc<Reporter>,
+        request_id: Option<String>,
+        // The inner body must not be polled again after it panicked
+        panicked: bool,
+    }
+}
+
+impl CatchPanicBody {
+    fn aborted(reporter: &Reporter, request_id: &mut Option<String>, caught: CaughtPanic) -> crate::Error {
+        reporter.report(request_id.take(), caught, true);
+        crate::Error::new(ResponseBodyPanicked)
+    }
+}
+
+impl HttpBody for CatchPanicBody {
+    type Data = Bytes;
+    type Error = crate::Error;
+
+    fn poll_data(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = self.project();
+        if *this.panicked {
+            return Poll::Ready(None);
+        }
+        let inner = this.inner;
+        match catch(this.reporter.capture_backtraces, || inner.poll_data(cx)) {
+            Ok(data) => data,
+            Err(caught) => {
+                *this.panicked = true;
+                Poll::Ready(Some(Err(Self::aborted(this.reporter, this.request_id, caught))))
+            }
+        }
+    }
+
+    fn poll_trailers(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        let this = self.project();
+        if *this.panicked {
+            return Poll::Ready(Ok(None));
+        }
+        let inner = this.inner;
+        match catch(this.reporter.capture_backtraces, || inner.poll_trailers(cx)) {
+            Ok(trailers) => trailers,
+            Err(caught) => {
+                *this.panicked = true;
+                Poll::Ready(Err(Self::aborted(this.reporter, this.request_id, caught)))
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.panicked || self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::sync::Mutex;
+
+    use http::StatusCode;
+    use tokio::net::TcpStream;
+    use tower::{service_fn, ServiceExt};
+
+    use super::*;
+    use crate::body::{boxed, to_boxed};
+    use crate::protocol::rest_json_1::RestJson1;
+    use crate::protocol::test_helpers::get_body_as_string;
+    use crate::test_helpers::send_request;
+
+    struct PokemonService;
+
+    impl ServiceShape for PokemonService {
+        const ID: ShapeId = ShapeId::new("com.example#PokemonService", "com.example", "PokemonService");
+        const VERSION: Option<&'static str> = None;
+        type Protocol = RestJson1;
+        type Operations = ();
+    }
+
+    struct GetPokemonSpecies;
+
+    impl OperationShape for GetPokemonSpecies {
+        const ID: ShapeId = ShapeId::new("com.example#GetPokemonSpecies", "com.example", "GetPokemonSpecies");
+        type Input = ();
+        type Output = ();
+        type Error = ();
+    }
+
+    /// A handler that panics when the path of the request is `/panic`.
+    async fn handler(req: http::Request<hyper::Body>) -> Result<http::Response<BoxBody>, Infallible> {
+        if req.uri().path() == "/panic" {
+            panic!("the secret password is hunter2");
+        }
+        Ok(http::Response::new(to_boxed("pikachu")))
+    }
+
+    fn panicking_call(_req: http::Request<()>) -> std::future::Ready<Result<http::Response<BoxBody>, Infallible>> {
+        panic!("panicked before returning a future")
+    }
+
+    #[derive(Debug)]
+    struct Recorded {
+        operation: ShapeId,
+        message: String,
+        has_backtrace: bool,
+        response_started: bool,
+    }
+
+    /// Applies a plugin recording the panics it catches.
+    fn apply<S>(inner: S) -> (CatchPanicService<RestJson1, S>, Arc<Mutex<Vec<Recorded>>>) {
+        let panics = Arc::new(Mutex::new(Vec::new()));
+        let plugin = CatchPanicPlugin::new().capture_backtraces(true).on_panic({
+            let panics = panics.clone();
+            move |panic| {
+                panics.lock().unwrap().push(Recorded {
+                    operation: panic.operation().clone(),
+                    message: panic.message().to_string(),
+                    has_backtrace: panic.backtrace().is_some(),
+                    response_started: panic.response_started(),
+                })
+            }
+        });
+        let service = Plugin::<PokemonService, GetPokemonSpecies, _>::apply(&plugin, inner);
+        (service, panics)
+    }
+
+    #[tokio::test]
+    async fn panicking_handlers_get_an_internal_failure_response() {
+        let (service, panics) = apply(service_fn(handler));
+        let request = http::Request::get("/panic").body(hyper::Body::empty()).unwrap();
+        let response = service.oneshot(request).await.unwrap();
+
+        assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+        assert_eq!(response.headers()["x-amzn-errortype"], "InternalFailureException");
+        assert_eq!("{}", get_body_as_string(response.into_body()).await);
+
+        let panics = panics.lock().unwrap();
+        assert_eq!(1, panics.len());
+        assert_eq!(GetPokemonSpecies::ID, panics[0].operation);
+        assert_eq!("the secret password is hunter2", panics[0].message);
+        assert!(panics[0].has_backtrace);
+        assert!(!panics[0].response_started);
+    }
+
+    #[tokio::test]
+    async fn panics_when_calling_the_handler_are_caught() {
+        let (service, panics) = apply(service_fn(panicking_call));
+        let response = service.oneshot(http::Request::new(())).await.unwrap();
+
+        assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+        assert_eq!("panicked before returning a future", panics.lock().unwrap()[0].message);
+    }
+
+    #[tokio::test]
+    async fn panics_in_streaming_responses_abort_the_body() {
+        let (service, panics) = apply(service_fn(|_req: http::Request<()>| async {
+            let mut sent = 0;
+            let events = futures_util::stream::iter([Ok::<_, Infallible>("event;"), Ok("event;")]);
+            let events = futures_util::StreamExt::map(events, move |event| {
+                sent += 1;
+                if sent == 2 {
+                    panic!("the stream broke");
+                }
+                event
+            });
+            Ok::<_, Infallible>(http::Response::new(boxed(hyper::Body::wrap_stream(events))))
+        }));
+        let response = service.oneshot(http::Request::new(())).await.unwrap();
+
+        assert_eq!(StatusCode::OK, response.status());
+        let error = hyper::body::to_bytes(response.into_body()).await.unwrap_err();
+        assert!(error.to_string().contains("panicked"), "{error}");
+        let panics = panics.lock().unwrap();
+        assert_eq!("the stream broke", panics[0].message);
+        assert!(panics[0].response_started);
+    }
+
+    #[tokio::test]
+    async fn connection_remains_usable_after_a_panic() {
+        let (service, _panics) = apply(service_fn(handler));
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = hyper::Server::from_tcp(listener)
+            .unwrap()
+            .serve(tower::make::Shared::new(service));
+        tokio::spawn(server);
+
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        let response = send_request(&mut stream, "/panic").await;
+        assert!(response.starts_with("HTTP/1.1 500"), "{response}");
+        assert!(!response.contains("hunter2"), "{response}");
+
+        let response = send_request(&mut stream, "/").await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.ends_with("pikachu"), "{response}");
+    }
+}
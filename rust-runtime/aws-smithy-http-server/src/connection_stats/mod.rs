@@ -0,0 +1,246 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Live statistics about the connections and streams a server is serving.
+//!
+//! [`ConnectionStats`] is a registry of counters that the server keeps up to date, and that the
+//! application can read at any time, for example to export them to its metrics system:
+//!
+//! - the number of open connections, counted by wrapping the make service passed to hyper in
+//!   [`TrackConnections`];
+//! - the number of operations whose response is being streamed, by operation, counted by
+//!   [`ConnectionStatsLayer`];
+//! - the number of bytes written in streamed responses since the server started, also counted by
+//!   [`ConnectionStatsLayer`].
+//!
+//! An operation is streaming from the moment its handler responds with a body of unknown length,
+//! like an event stream, until that body ends or is dropped. The operation is identified by the
+//! [`OperationExtension`] of the response, so [`OperationExtensionPlugin`] must be applied; responses
+//! without it aren't counted.
+//!
+//! Every update is a single atomic operation on the hot path. The counters of an operation are
+//! registered the first time it streams.
+//!
+//! [`ConnectionStats::report_every`] can additionally log the statistics in a `tracing` event
+//! periodically.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use aws_smithy_http_server::connection_stats::{ConnectionStats, ConnectionStatsLayer, TrackConnections};
+//! use aws_smithy_http_server::routing::IntoMakeService;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use tower::Layer;
+//! # use aws_smithy_http_server::body::{boxed, Body};
+//! # use std::convert::Infallible;
+//! # async fn run() {
+//! # let app = tower::service_fn(|_req: http::Request<Body>| async {
+//! #     Ok::<_, Infallible>(http::Response::new(boxed(Body::empty())))
+//! # });
+//!
+//! let stats = Arc::new(ConnectionStats::new());
+//! stats.clone().report_every(Duration::from_secs(60));
+//!
+//! // With a generated service, the layer is added to the service config with `layer` instead.
+//! let app = ConnectionStatsLayer::new(stats.clone()).layer(app);
+//! let make_app = TrackConnections::new(IntoMakeService::new(app), stats.clone());
+//! hyper::Server::bind(&([127, 0, 0, 1], 13734).into())
+//!     .serve(make_app)
+//!     .await
+//!     .unwrap();
+//! # }
+//! ```
+//!
+//! [`OperationExtension`]: crate::extension::OperationExtension
+//! [`OperationExtensionPlugin`]: crate::extension::OperationExtensionPlugin
+
+mod service;
+
+pub use service::{
+    ConnectionStatsFuture, ConnectionStatsLayer, ConnectionStatsService, TrackConnections, TrackConnectionsFuture,
+    TrackedConnection,
+};
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use crate::shape_id::ShapeId;
+
+/// Counters of the connections and streams served by a server.
+///
+/// See the [module documentation](crate::connection_stats) for details.
+#[derive(Debug, Default)]
+pub struct ConnectionStats {
+    active_connections: AtomicU64,
+    streamed_bytes: AtomicU64,
+    streaming_operations: RwLock<HashMap<ShapeId, Arc<AtomicU64>>>,
+}
+
+impl ConnectionStats {
+    /// Creates a registry with every counter at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of open connections.
+    pub fn active_connections(&self) -> u64 {
+        self.active_connections.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of operations whose response is being streamed.
+    pub fn active_streaming_operations(&self) -> u64 {
+        self.streaming_operations
+            .read()
+            .unwrap()
+            .values()
+            .map(|active| active.load(Ordering::Relaxed))
+            .sum()
+    }
+
+    /// Returns the number of operations whose response is being streamed, for every operation
+    /// which has at least one.
+    pub fn active_streaming_operations_by_operation(&self) -> HashMap<ShapeId, u64> {
+        self.streaming_operations
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(operation, active)| (operation.clone(), active.load(Ordering::Relaxed)))
+            .filter(|(_, active)| *active > 0)
+            .collect()
+    }
+
+    /// Returns the number of bytes written in streamed responses since the server started.
+    pub fn streamed_bytes(&self) -> u64 {
+        self.streamed_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Logs the statistics in a `tracing` event every `period`, until every other reference to
+    /// them has been dropped.
+    ///
+    /// The reports are sent from a task spawned onto the current Tokio runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub fn report_every(self: Arc<Self>, period: Duration) -> tokio::task::JoinHandle<()> {
+        let stats = Arc::downgrade(&self);
+        drop(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            // The first tick completes immediately
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let Some(stats) = stats.upgrade() else {
+                    return;
+                };
+                tracing::info!(
+                    active_connections = stats.active_connections(),
+                    active_streaming_operations = stats.active_streaming_operations(),
+                    streamed_bytes = stats.streamed_bytes(),
+                    "connection stats"
+                );
+            }
+        })
+    }
+
+    fn connection_opened(self: &Arc<Self>) -> ActiveConnection {
+        self.active_connections.fetch_add(1, Ordering::Relaxed);
+        ActiveConnection(self.clone())
+    }
+
+    fn streaming_started(self: &Arc<Self>, operation: &ShapeId) -> ActiveStream {
+        let existing = self.streaming_operations.read().unwrap().get(operation).cloned();
+        let active = existing.unwrap_or_else(|| {
+            self.streaming_operations
+                .write()
+                .unwrap()
+                .entry(operation.clone())
+                .or_default()
+                .clone()
+        });
+        active.fetch_add(1, Ordering::Relaxed);
+        ActiveStream {
+            stats: self.clone(),
+            active,
+        }
+    }
+}
+
+/// Counts a connection as open until it is dropped.
+#[derive(Debug)]
+struct ActiveConnection(Arc<ConnectionStats>);
+
+impl Drop for ActiveConnection {
+    fn drop(&mut self) {
+        self.0.active_connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Counts an operation as streaming until it is dropped, and records the bytes it streams.
+#[derive(Debug)]
+struct ActiveStream {
+    stats: Arc<ConnectionStats>,
+    active: Arc<AtomicU64>,
+}
+
+impl ActiveStream {
+    fn streamed(&self, bytes: usize) {
+        self.stats.streamed_bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+}
+
+impl Drop for ActiveStream {
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAPTURE_POKEMON: ShapeId =
+        ShapeId::new("com.aws.example#CapturePokemon", "com.aws.example", "CapturePokemon");
+    const STREAM_POKEMON_RADIO: ShapeId = ShapeId::new(
+        "com.aws.example#StreamPokemonRadio",
+        "com.aws.example",
+        "StreamPokemonRadio",
+    );
+
+    #[test]
+    fn counters_follow_guards() {
+        let stats = Arc::new(ConnectionStats::new());
+        let connection = stats.connection_opened();
+        let capture = stats.streaming_started(&CAPTURE_POKEMON);
+        let radio = stats.streaming_started(&STREAM_POKEMON_RADIO);
+        let other_capture = stats.streaming_started(&CAPTURE_POKEMON);
+        capture.streamed(10);
+        radio.streamed(5);
+
+        assert_eq!(1, stats.active_connections());
+        assert_eq!(3, stats.active_streaming_operations());
+        assert_eq!(
+            HashMap::from([(CAPTURE_POKEMON, 2), (STREAM_POKEMON_RADIO, 1)]),
+            stats.active_streaming_operations_by_operation()
+        );
+        assert_eq!(15, stats.streamed_bytes());
+
+        drop((connection, capture, radio));
+        assert_eq!(0, stats.active_connections());
+        assert_eq!(1, stats.active_streaming_operations());
+        assert_eq!(
+            HashMap::from([(CAPTURE_POKEMON, 1)]),
+            stats.active_streaming_operations_by_operation()
+        );
+
+        drop(other_capture);
+        assert_eq!(0, stats.active_streaming_operations());
+        assert_eq!(15, stats.streamed_bytes());
+    }
+}
@@ -0,0 +1,330 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures_util::ready;
+use http_body::Body as HttpBody;
+use pin_project_lite::pin_project;
+use tower::{Layer, Service};
+
+use super::{ActiveConnection, ActiveStream, ConnectionStats};
+use crate::body::{boxed, BoxBody};
+use crate::extension::OperationExtension;
+
+/// A [`Layer`] applying [`ConnectionStatsService`], which counts streaming operations and the bytes
+/// they stream.
+///
+/// See the [module documentation](crate::connection_stats) for details.
+#[derive(Debug, Clone)]
+pub struct ConnectionStatsLayer {
+    stats: Arc<ConnectionStats>,
+}
+
+impl ConnectionStatsLayer {
+    /// Creates a layer recording the streaming operations it sees in `stats`.
+    pub fn new(stats: Arc<ConnectionStats>) -> Self {
+        Self { stats }
+    }
+}
+
+impl<S> Layer<S> for ConnectionStatsLayer {
+    type Service = ConnectionStatsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConnectionStatsService {
+            inner,
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+/// A [`Service`] counting the operations whose response is streamed, and the bytes they stream.
+///
+/// See the [module documentation](crate::connection_stats) for details.
+#[derive(Debug, Clone)]
+pub struct ConnectionStatsService<S> {
+    inner: S,
+    stats: Arc<ConnectionStats>,
+}
+
+impl<S, B> Service<http::Request<B>> for ConnectionStatsService<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<BoxBody>>,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = S::Error;
+    type Future = ConnectionStatsFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        ConnectionStatsFuture {
+            inner: self.inner.call(req),
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+pin_project! {
+    /// The [`Service::Future`] of [`ConnectionStatsService`].
+    pub struct ConnectionStatsFuture<Fut> {
+        #[pin]
+        inner: Fut,
+        stats: Arc<ConnectionStats>,
+    }
+}
+
+impl<Fut, E> Future for ConnectionStatsFuture<Fut>
+where
+    Fut: Future<Output = Result<http::Response<BoxBody>, E>>,
+{
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let response = ready!(this.inner.poll(cx))?;
+        let body = response.body();
+        if body.is_end_stream() || body.size_hint().exact().is_some() {
+            return Poll::Ready(Ok(response));
+        }
+        let Some(OperationExtension(operation)) = response.extensions().get::<OperationExtension>() else {
+            return Poll::Ready(Ok(response));
+        };
+        let stream = this.stats.streaming_started(operation);
+        Poll::Ready(Ok(response.map(|inner| {
+            boxed(ConnectionStatsBody {
+                inner,
+                stream: Some(stream),
+            })
+        })))
+    }
+}
+
+pin_project! {
+    /// The body of a streamed response, which counts its operation as streaming until it ends.
+    struct ConnectionStatsBody {
+        #[pin]
+        inner: BoxBody,
+        stream: Option<ActiveStream>,
+    }
+}
+
+impl HttpBody for ConnectionStatsBody {
+    type Data = Bytes;
+    type Error = crate::Error;
+
+    fn poll_data(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = self.project();
+        let data = ready!(this.inner.poll_data(cx));
+        match &data {
+            Some(Ok(data)) => {
+                if let Some(stream) = this.stream {
+                    stream.streamed(data.len());
+                }
+            }
+            // The operation stops streaming as soon as the body ends, rather than when hyper drops it
+            _ => *this.stream = None,
+        }
+        Poll::Ready(data)
+    }
+
+    fn poll_trailers(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        let this = self.project();
+        let trailers = ready!(this.inner.poll_trailers(cx));
+        *this.stream = None;
+        Poll::Ready(trailers)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Wraps a make service, like [`IntoMakeService`](crate::routing::IntoMakeService), to count the
+/// connections it serves.
+///
+/// A connection is counted as open from the moment the make service has created its service,
+/// until hyper drops that service when the connection closes.
+///
+/// See the [module documentation](crate::connection_stats) for details.
+#[derive(Debug, Clone)]
+pub struct TrackConnections<M> {
+    make_service: M,
+    stats: Arc<ConnectionStats>,
+}
+
+impl<M> TrackConnections<M> {
+    /// Counts the connections served by `make_service` in `stats`.
+    pub fn new(make_service: M, stats: Arc<ConnectionStats>) -> Self {
+        Self { make_service, stats }
+    }
+}
+
+impl<M, T> Service<T> for TrackConnections<M>
+where
+    M: Service<T>,
+{
+    type Response = TrackedConnection<M::Response>;
+    type Error = M::Error;
+    type Future = TrackConnectionsFuture<M::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.make_service.poll_ready(cx)
+    }
+
+    fn call(&mut self, target: T) -> Self::Future {
+        TrackConnectionsFuture {
+            inner: self.make_service.call(target),
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+pin_project! {
+    /// The [`Service::Future`] of [`TrackConnections`].
+    pub struct TrackConnectionsFuture<Fut> {
+        #[pin]
+        inner: Fut,
+        stats: Arc<ConnectionStats>,
+    }
+}
+
+impl<Fut, S, E> Future for TrackConnectionsFuture<Fut>
+where
+    Fut: Future<Output = Result<S, E>>,
+{
+    type Output = Result<TrackedConnection<S>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let inner = ready!(this.inner.poll(cx))?;
+        Poll::Ready(Ok(TrackedConnection {
+            inner,
+            _connection: Arc::new(this.stats.connection_opened()),
+        }))
+    }
+}
+
+/// The service serving a connection counted by [`TrackConnections`].
+///
+/// The connection is counted as open until this service and all its clones are dropped.
+#[derive(Debug, Clone)]
+pub struct TrackedConnection<S> {
+    inner: S,
+    _connection: Arc<ActiveConnection>,
+}
+
+impl<S, R> Service<R> for TrackedConnection<S>
+where
+    S: Service<R>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        self.inner.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use futures_util::stream;
+    use tower::{service_fn, ServiceExt};
+
+    use super::*;
+    use crate::body::Body;
+    use crate::shape_id::ShapeId;
+
+    const CAPTURE_POKEMON: ShapeId =
+        ShapeId::new("com.aws.example#CapturePokemon", "com.aws.example", "CapturePokemon");
+
+    fn response(body: Body, operation: Option<ShapeId>) -> http::Response<BoxBody> {
+        let mut response = http::Response::new(boxed(body));
+        if let Some(operation) = operation {
+            response.extensions_mut().insert(OperationExtension(operation));
+        }
+        response
+    }
+
+    fn streaming_body() -> Body {
+        Body::wrap_stream(stream::iter([
+            Ok::<_, Infallible>(Bytes::from_static(b"pika")),
+            Ok(Bytes::from_static(b"chu")),
+        ]))
+    }
+
+    async fn call(stats: &Arc<ConnectionStats>, response: http::Response<BoxBody>) -> http::Response<BoxBody> {
+        let mut response = Some(response);
+        let svc = service_fn(move |_: http::Request<()>| {
+            let response = response.take().unwrap();
+            async move { Ok::<_, Infallible>(response) }
+        });
+        ConnectionStatsLayer::new(stats.clone())
+            .layer(svc)
+            .oneshot(http::Request::new(()))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn streamed_responses_are_counted_until_they_end() {
+        let stats = Arc::new(ConnectionStats::new());
+        let first = call(&stats, response(streaming_body(), Some(CAPTURE_POKEMON))).await;
+        let second = call(&stats, response(streaming_body(), Some(CAPTURE_POKEMON))).await;
+        assert_eq!(2, stats.active_streaming_operations());
+
+        let body = hyper::body::to_bytes(first.into_body()).await.unwrap();
+        assert_eq!(&b"pikachu"[..], body);
+        assert_eq!(1, stats.active_streaming_operations());
+        assert_eq!(7, stats.streamed_bytes());
+
+        drop(second);
+        assert_eq!(0, stats.active_streaming_operations());
+        assert_eq!(7, stats.streamed_bytes());
+    }
+
+    #[tokio::test]
+    async fn buffered_and_unrouted_responses_are_not_counted() {
+        let stats = Arc::new(ConnectionStats::new());
+        let _buffered = call(&stats, response(Body::from("pikachu"), Some(CAPTURE_POKEMON))).await;
+        let _unrouted = call(&stats, response(streaming_body(), None)).await;
+        assert_eq!(0, stats.active_streaming_operations());
+    }
+
+    #[tokio::test]
+    async fn connections_are_counted_until_their_service_is_dropped() {
+        let stats = Arc::new(ConnectionStats::new());
+        let mut make_service = TrackConnections::new(
+            service_fn(|_: ()| async { Ok::<_, Infallible>(service_fn(|_: ()| async { Ok::<_, Infallible>(()) })) }),
+            stats.clone(),
+        );
+        let connection = make_service.ready().await.unwrap().call(()).await.unwrap();
+        let clone = connection.clone();
+        assert_eq!(1, stats.active_connections());
+
+        drop(connection);
+        assert_eq!(1, stats.active_connections());
+        drop(clone);
+        assert_eq!(0, stats.active_connections());
+    }
+}
@@ -15,7 +15,10 @@
 #[macro_use]
 pub(crate) mod macros;
 
+pub mod auth;
 pub mod body;
+pub mod catch_panic;
+pub mod connection_stats;
 pub(crate) mod error;
 pub mod extension;
 pub mod instrumentation;
@@ -32,8 +35,11 @@ pub mod response;
 pub mod routing;
 #[doc(hidden)]
 pub mod runtime_error;
+pub mod server_identification;
 pub mod service;
 pub mod shape_id;
+pub mod throttling;
+pub mod timeout;
 
 #[doc(inline)]
 pub(crate) use self::error::Error;
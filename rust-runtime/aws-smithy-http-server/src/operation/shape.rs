@@ -5,6 +5,9 @@
 
 use std::marker::PhantomData;
 
+use tower::util::BoxCloneService;
+use tower::Service;
+
 use super::{Handler, IntoService, Normalize, OperationService};
 use crate::shape_id::ShapeId;
 
@@ -24,6 +27,17 @@ pub trait OperationShape {
     type Error;
 }
 
+/// A type-erased [`Service`] taking the input of the `Op` operation along with the `Exts` extractors.
+///
+/// Unlike [`IntoService`] and [`Normalize`], its type doesn't depend on the handler or service it
+/// wraps, so plugins applied to it are instantiated with much smaller types. This comes at the cost of
+/// one dynamic dispatch and one allocation per request.
+pub type BoxedOperationService<Op, Exts> = BoxCloneService<
+    (<Op as OperationShape>::Input, Exts),
+    <Op as OperationShape>::Output,
+    <Op as OperationShape>::Error,
+>;
+
 /// An extension trait over [`OperationShape`].
 pub trait OperationShapeExt: OperationShape {
     /// Creates a new [`Service`](tower::Service), [`IntoService`], for well-formed [`Handler`]s.
@@ -50,6 +64,63 @@ pub trait OperationShapeExt: OperationShape {
             _operation: PhantomData,
         }
     }
+
+    /// Creates a new type-erased [`Service`], [`BoxedOperationService`], for well-formed [`Handler`]s.
+    fn from_handler_boxed<H, Exts>(handler: H) -> BoxedOperationService<Self, Exts>
+    where
+        H: Handler<Self, Exts> + Clone + Send + 'static,
+        H::Future: Send + 'static,
+        Self: Sized + Send + 'static,
+    {
+        BoxCloneService::new(Self::from_handler(handler))
+    }
+
+    /// Creates a new type-erased [`Service`], [`BoxedOperationService`], for well-formed
+    /// [`Service`]s.
+    fn from_service_boxed<S, Exts>(svc: S) -> BoxedOperationService<Self, Exts>
+    where
+        S: OperationService<Self, Exts> + Clone + Send + 'static,
+        <S as Service<S::Normalized>>::Future: Send + 'static,
+        Self: Sized + Send + 'static,
+    {
+        BoxCloneService::new(Self::from_service(svc))
+    }
 }
 
 impl<S> OperationShapeExt for S where S: OperationShape {}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use tower::{Service, ServiceExt};
+
+    use super::*;
+
+    struct Echo;
+
+    impl OperationShape for Echo {
+        const ID: ShapeId = ShapeId::new("com.example#Echo", "com.example", "Echo");
+
+        type Input = String;
+        type Output = String;
+        type Error = Infallible;
+    }
+
+    async fn echo(input: String, suffix: &'static str) -> String {
+        input + suffix
+    }
+
+    #[tokio::test]
+    async fn boxed_handlers_and_services_behave_like_unboxed_ones() {
+        let handler = |input: String| echo(input, "!");
+        let mut boxed: BoxedOperationService<Echo, ()> = Echo::from_handler_boxed(handler);
+        let output = boxed.ready().await.unwrap().call(("hi".to_string(), ())).await;
+        assert_eq!(Ok("hi!".to_string()), output);
+
+        let service = tower::service_fn(|input: String| async move { Ok::<_, Infallible>(echo(input, "?").await) });
+        let boxed: BoxedOperationService<Echo, ()> = Echo::from_service_boxed(service);
+        let output = boxed.oneshot(("hi".to_string(), ())).await;
+        assert_eq!(Ok("hi?".to_string()), output);
+    }
+}
@@ -68,8 +68,12 @@ fn content_type_header_classifier(
                 .parse::<mime::Mime>()
                 // `expected_content_type` comes from the codegen.
                 .expect("BUG: MIME parsing failed, `expected_content_type` is not valid; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues");
-        debug_assert_eq!(
-            mime, expected_content_type,
+        // The modeled media type may have parameters, e.g. `text/plain; charset=utf-8`.
+        debug_assert!(
+            expected_content_type
+                .split(';')
+                .next()
+                .is_some_and(|essence| essence.trim().eq_ignore_ascii_case(mime.essence_str())),
             "BUG: expected `content-type` header value we own from codegen should coincide with its mime type; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues",
         );
         mime
@@ -94,7 +98,9 @@ fn content_type_header_classifier(
         (Some(actual_content_type), Some(expected_content_type)) => {
             let expected_mime = parse_expected_mime(expected_content_type);
             let found_mime = parse_mime(actual_content_type)?;
-            if expected_mime != found_mime.essence_str() {
+            // Only the essence is checked: the parameters of the modeled media type, like its
+            // `charset`, aren't required, and the request can have parameters of its own.
+            if expected_mime.essence_str() != found_mime.essence_str() {
                 Err(MissingContentTypeReason::UnexpectedMimeType {
                     expected_mime: Some(expected_mime),
                     found_mime: Some(found_mime),
@@ -249,6 +255,26 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn valid_content_type_header_classifier_modeled_params() {
+        let expected = Some("text/plain; charset=utf-8");
+        for content_type in ["text/plain; charset=utf-8", "text/plain;charset=UTF-8", "text/plain"] {
+            let request = req_content_type_smithy(content_type);
+            assert!(
+                content_type_header_classifier_smithy(&request, expected).is_ok(),
+                "{content_type}"
+            );
+        }
+
+        let request = req_content_type_smithy("application/octet-stream; charset=utf-8");
+        let result = content_type_header_classifier_smithy(&request, expected);
+        assert_unexpected_mime_type(
+            result,
+            Some(parse_mime(expected.unwrap()).unwrap()),
+            Some(parse_mime("application/octet-stream; charset=utf-8").unwrap()),
+        );
+    }
+
     #[test]
     fn valid_accept_header_classifier_multiple_values() {
         let valid_request = req_accept("text/strings, application/json, invalid");
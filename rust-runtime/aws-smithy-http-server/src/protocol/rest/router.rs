@@ -6,8 +6,11 @@
 use std::convert::Infallible;
 
 use crate::body::BoxBody;
+use crate::routing::dot_segments::{is_encoded_dot_segment, path_hazard, remove_dot_segments, PathHazard};
 use crate::routing::request_spec::Match;
+use crate::routing::request_spec::PathSegment;
 use crate::routing::request_spec::RequestSpec;
+use crate::routing::DotSegments;
 use crate::routing::Route;
 use crate::routing::Router;
 use tower::Layer;
@@ -17,6 +20,7 @@ use thiserror::Error;
 
 /// An AWS REST routing error.
 #[derive(Debug, Error, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     /// Operation not found.
     #[error("operation not found")]
@@ -24,6 +28,9 @@ pub enum Error {
     /// Method was not allowed.
     #[error("method was not allowed")]
     MethodNotAllowed,
+    /// The request path has dot-segments that are rejected by the router's [`DotSegments`] policy.
+    #[error("request path contains dot-segments")]
+    InvalidPath,
 }
 
 /// A [`Router`] supporting [AWS restJson1] and [AWS restXml] protocols.
@@ -33,9 +40,99 @@ pub enum Error {
 #[derive(Debug, Clone)]
 pub struct RestRouter<S> {
     routes: Vec<(RequestSpec, S)>,
+    dot_segments: DotSegments,
 }
 
 impl<S> RestRouter<S> {
+    /// Sets how request paths that contain dot-segments are routed.
+    ///
+    /// Defaults to [`DotSegments::Reject`].
+    pub fn dot_segments(mut self, dot_segments: DotSegments) -> Self {
+        self.dot_segments = dot_segments;
+        self
+    }
+
+    /// Emits a warning for every route whose pattern could be confused by the dot-segment policy.
+    ///
+    /// Generated services call this once, when they are built with their final policy.
+    pub fn warn_about_confusable_routes(&self) {
+        for (request_spec, _route) in &self.routes {
+            let segments = request_spec.path_segments();
+            let route = request_spec.path_pattern();
+            let has_dot_segment_literal = segments
+                .iter()
+                .any(|segment| matches!(segment, PathSegment::Literal(literal) if is_encoded_dot_segment(literal)));
+            match self.dot_segments {
+                DotSegments::Reject | DotSegments::Normalize if has_dot_segment_literal => tracing::warn!(
+                    %route,
+                    policy = ?self.dot_segments,
+                    "route has a literal dot-segment, so it can never be matched"
+                ),
+                DotSegments::Normalize if segments.iter().any(|segment| matches!(segment, PathSegment::Greedy)) => {
+                    tracing::warn!(
+                        %route,
+                        "greedy label values are normalized before routing, so a `..` in one removes the segment before it"
+                    )
+                }
+                DotSegments::Preserve
+                    if segments
+                        .iter()
+                        .any(|segment| !matches!(segment, PathSegment::Literal(_))) =>
+                {
+                    tracing::warn!(
+                        %route,
+                        "label values can be `.` or `..` since dot-segments are preserved; validate them before using them to build paths"
+                    )
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the index of the route that matches `request` as if its path was `path`.
+    fn find<B>(&self, request: &http::Request<B>, path: &str) -> Result<usize, Error> {
+        let mut method_allowed = true;
+
+        for (index, (request_spec, _route)) in self.routes.iter().enumerate() {
+            match request_spec.matches_path(request, path) {
+                // Match found.
+                Match::Yes => return Ok(index),
+                // Match found, but method disallowed.
+                Match::MethodNotAllowed => method_allowed = false,
+                // Continue looping to see if another route matches.
+                Match::No => continue,
+            }
+        }
+
+        if method_allowed {
+            Err(Error::NotFound)
+        } else {
+            Err(Error::MethodNotAllowed)
+        }
+    }
+
+    /// Returns the index of the route that matches `request` under the dot-segment policy, and
+    /// the path to rewrite the request with, if it was normalized.
+    fn find_route<B>(&self, request: &http::Request<B>) -> Result<(usize, Option<String>), Error> {
+        let path = request.uri().path();
+        match (path_hazard(path), self.dot_segments) {
+            (PathHazard::None, _) | (_, DotSegments::Preserve) => Ok((self.find(request, path)?, None)),
+            (PathHazard::EncodedTraversal, _) | (_, DotSegments::Reject) => Err(Error::InvalidPath),
+            (PathHazard::DotSegments, DotSegments::Normalize) => {
+                let normalized = remove_dot_segments(path);
+                let index = match self.find(request, path) {
+                    // Normalizing must not route the request to a different operation.
+                    Ok(raw_index) => match self.find(request, &normalized) {
+                        Ok(index) if index == raw_index => index,
+                        _ => return Err(Error::InvalidPath),
+                    },
+                    Err(_) => self.find(request, &normalized)?,
+                };
+                Ok((index, Some(normalized)))
+            }
+        }
+    }
+
     /// Applies a [`Layer`] uniformly to all routes.
     pub fn layer<L>(self, layer: L) -> RestRouter<L::Service>
     where
@@ -47,6 +144,7 @@ impl<S> RestRouter<S> {
                 .into_iter()
                 .map(|(request_spec, route)| (request_spec, layer.layer(route)))
                 .collect(),
+            dot_segments: self.dot_segments,
         }
     }
 
@@ -59,6 +157,7 @@ impl<S> RestRouter<S> {
     {
         RestRouter {
             routes: self.routes.into_iter().map(|(spec, s)| (spec, Route::new(s))).collect(),
+            dot_segments: self.dot_segments,
         }
     }
 }
@@ -71,24 +170,22 @@ where
     type Error = Error;
 
     fn match_route(&self, request: &http::Request<B>) -> Result<S, Self::Error> {
-        let mut method_allowed = true;
-
-        for (request_spec, route) in &self.routes {
-            match request_spec.matches(request) {
-                // Match found.
-                Match::Yes => return Ok(route.clone()),
-                // Match found, but method disallowed.
-                Match::MethodNotAllowed => method_allowed = false,
-                // Continue looping to see if another route matches.
-                Match::No => continue,
-            }
-        }
+        let (index, _normalized_path) = self.find_route(request)?;
+        Ok(self.routes[index].1.clone())
+    }
 
-        if method_allowed {
-            Err(Error::NotFound)
-        } else {
-            Err(Error::MethodNotAllowed)
+    fn route(&self, request: &mut http::Request<B>) -> Result<S, Self::Error> {
+        let (index, normalized_path) = self.find_route(request)?;
+        if let Some(path) = normalized_path {
+            let path_and_query = match request.uri().query() {
+                Some(query) => format!("{path}?{query}"),
+                None => path,
+            };
+            let mut parts = request.uri().clone().into_parts();
+            parts.path_and_query = Some(path_and_query.try_into().map_err(|_| Error::InvalidPath)?);
+            *request.uri_mut() = http::Uri::from_parts(parts).map_err(|_| Error::InvalidPath)?;
         }
+        Ok(self.routes[index].1.clone())
     }
 }
 
@@ -102,7 +199,10 @@ impl<S> FromIterator<(RequestSpec, S)> for RestRouter<S> {
         // and pick the first one that matches.
         routes.sort_by_key(|(request_spec, _route)| std::cmp::Reverse(request_spec.rank()));
 
-        Self { routes }
+        Self {
+            routes,
+            dot_segments: DotSegments::default(),
+        }
     }
 }
 
@@ -261,4 +361,64 @@ mod tests {
             assert_eq!(router.match_route(&req(&method, uri, None)).unwrap(), svc_name);
         }
     }
+
+    fn dot_segment_router() -> RestRouter<&'static str> {
+        let request_specs: Vec<(RequestSpec, &'static str)> = vec![
+            (
+                RequestSpec::from_parts(
+                    Method::GET,
+                    vec![PathSegment::Literal(String::from("a")), PathSegment::Label],
+                    Vec::new(),
+                ),
+                "A",
+            ),
+            (
+                RequestSpec::from_parts(Method::GET, vec![PathSegment::Literal(String::from("b"))], Vec::new()),
+                "B",
+            ),
+        ];
+        request_specs.into_iter().collect()
+    }
+
+    #[test]
+    fn dot_segments_are_rejected_by_default() {
+        let router = dot_segment_router();
+        for uri in ["/a/..", "/a/.", "/a/%2e%2E", "/a/../b", "/./b", "/a/..%2fb"] {
+            let res = router.match_route(&req(&Method::GET, uri, None));
+            assert_eq!(res.unwrap_err(), Error::InvalidPath, "{uri}");
+        }
+        assert_eq!(router.match_route(&req(&Method::GET, "/a/..c", None)).unwrap(), "A");
+    }
+
+    #[test]
+    fn normalized_paths_route_to_the_same_operation() {
+        let router = dot_segment_router().dot_segments(DotSegments::Normalize);
+
+        let mut request = req(&Method::GET, "/./b/../a/x?q=1", None);
+        assert_eq!(router.route(&mut request).unwrap(), "A");
+        assert_eq!(request.uri().path_and_query().unwrap().as_str(), "/a/x?q=1");
+
+        let mut request = req(&Method::GET, "/a/x/..", None);
+        assert_eq!(router.route(&mut request).unwrap_err(), Error::NotFound);
+
+        // The raw path matches `A`, but the normalized path `/b` matches `B`.
+        let mut request = req(&Method::GET, "/a/../b", None);
+        assert_eq!(router.route(&mut request).unwrap_err(), Error::InvalidPath);
+        assert_eq!(request.uri().path(), "/a/../b");
+
+        let mut request = req(&Method::GET, "/a/..%5cb", None);
+        assert_eq!(router.route(&mut request).unwrap_err(), Error::InvalidPath);
+    }
+
+    #[test]
+    fn preserved_dot_segments_are_label_values() {
+        let router = dot_segment_router().dot_segments(DotSegments::Preserve);
+        for uri in ["/a/..", "/a/%2e", "/a/..%2fb"] {
+            let mut request = req(&Method::GET, uri, None);
+            assert_eq!(router.route(&mut request).unwrap(), "A", "{uri}");
+            assert_eq!(request.uri().path(), uri);
+        }
+        let res = router.match_route(&req(&Method::GET, "/a/../b", None));
+        assert_eq!(res.unwrap_err(), Error::NotFound);
+    }
 }
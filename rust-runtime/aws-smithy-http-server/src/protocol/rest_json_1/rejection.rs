@@ -150,6 +150,12 @@ pub enum RequestRejection {
     #[error("request URI cannot be percent decoded into valid UTF-8")]
     PercentEncodedUriNotValidUtf8(#[from] core::str::Utf8Error),
 
+    /// Used when a URI path label doesn't follow the
+    /// [`LabelRules`](aws_smithy_http::label::LabelRules) the server was generated with, or
+    /// cannot be percent decoded.
+    #[error("invalid request URI path label: {0}")]
+    InvalidLabel(#[from] aws_smithy_http::label::InvalidLabel),
+
     /// Used when failing to deserialize strings from a URL query string and from URI path labels
     /// into an [`aws_smithy_types::DateTime`].
     #[error("error parsing timestamp from request URI: {0}")]
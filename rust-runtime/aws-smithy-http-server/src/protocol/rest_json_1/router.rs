@@ -6,7 +6,7 @@
 use crate::body::BoxBody;
 use crate::extension::RuntimeErrorExtension;
 use crate::response::IntoResponse;
-use crate::routing::{method_disallowed, UNKNOWN_OPERATION_EXCEPTION};
+use crate::routing::{invalid_path, method_disallowed, UNKNOWN_OPERATION_EXCEPTION};
 
 use super::RestJson1;
 
@@ -27,6 +27,7 @@ impl IntoResponse<RestJson1> for Error {
                 .body(crate::body::to_boxed("{}"))
                 .expect("invalid HTTP response for REST JSON 1 routing error; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues"),
             Error::MethodNotAllowed => method_disallowed(),
+            Error::InvalidPath => invalid_path(),
         }
     }
 }
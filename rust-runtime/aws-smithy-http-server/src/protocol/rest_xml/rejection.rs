@@ -51,6 +51,9 @@ pub enum RequestRejection {
     #[error("request URI cannot be percent decoded into valid UTF-8")]
     PercentEncodedUriNotValidUtf8(#[from] core::str::Utf8Error),
 
+    #[error("invalid request URI path label: {0}")]
+    InvalidLabel(#[from] aws_smithy_http::label::InvalidLabel),
+
     #[error("error parsing timestamp from request URI: {0}")]
     DateTimeParse(#[from] aws_smithy_types::date_time::DateTimeParseError),
 
@@ -7,7 +7,7 @@ use crate::body::empty;
 use crate::body::BoxBody;
 use crate::extension::RuntimeErrorExtension;
 use crate::response::IntoResponse;
-use crate::routing::{method_disallowed, UNKNOWN_OPERATION_EXCEPTION};
+use crate::routing::{invalid_path, method_disallowed, UNKNOWN_OPERATION_EXCEPTION};
 
 use super::RestXml;
 
@@ -27,6 +27,7 @@ impl IntoResponse<RestXml> for Error {
                 .body(empty())
                 .expect("invalid HTTP response for REST XML routing error; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues"),
             Error::MethodNotAllowed => method_disallowed(),
+            Error::InvalidPath => invalid_path(),
         }
     }
 }
@@ -0,0 +1,189 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Extractors for the parts of the [`http::Request`] that aren't modelled.
+//!
+//! Sometimes a handler needs a header that isn't part of the API, for example one injected by a
+//! gateway in front of the service. Rather than modelling it, or replacing the handler with a raw
+//! [`tower::Service`], the handler can take a [`RawHeaders`] or a [`RequestParts`] after its input:
+//!
+//! ```rust,ignore
+//! async fn handler(input: ModelInput, headers: RawHeaders) -> ModelOutput {
+//!     let flags = headers.get("x-feature-flags");
+//!     /* ... */
+//! }
+//! ```
+//!
+//! Both are copies of the request taken before the input is deserialized, so they include the
+//! headers the input is bound to. For example, an operation with a string or blob `@httpPayload`
+//! can read the `content-type` the payload was sent with, parameters included, with
+//! [`RawHeaders::content_type`]. Unlike [`Extension`](crate::Extension), extracting them leaves
+//! the request as it is: a handler can take both of them, or take them alongside other extractors.
+
+use std::{convert::Infallible, ops::Deref};
+
+use http::{header::CONTENT_TYPE, request::Parts, HeaderMap, Method, Uri, Version};
+
+use super::FromParts;
+
+/// Extractor for the headers of the [`http::Request`].
+#[derive(Clone, Debug)]
+pub struct RawHeaders(pub HeaderMap);
+
+impl RawHeaders {
+    /// Returns the headers.
+    pub fn into_inner(self) -> HeaderMap {
+        self.0
+    }
+
+    /// Returns the `content-type` of the request as it was sent, parameters included.
+    ///
+    /// Returns `None` if the header is missing or isn't visible ASCII.
+    pub fn content_type(&self) -> Option<&str> {
+        content_type(&self.0)
+    }
+}
+
+impl Deref for RawHeaders {
+    type Target = HeaderMap;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<P> FromParts<P> for RawHeaders {
+    type Rejection = Infallible;
+
+    fn from_parts(parts: &mut Parts) -> Result<Self, Self::Rejection> {
+        Ok(RawHeaders(parts.headers.clone()))
+    }
+}
+
+/// Extractor for the method, URI, version and headers of the [`http::Request`].
+///
+/// The request extensions are left out: use [`Extension`](crate::Extension) to extract them.
+#[derive(Clone, Debug)]
+pub struct RequestParts {
+    method: Method,
+    uri: Uri,
+    version: Version,
+    headers: HeaderMap,
+}
+
+impl RequestParts {
+    /// Returns the method of the request.
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// Returns the URI of the request.
+    pub fn uri(&self) -> &Uri {
+        &self.uri
+    }
+
+    /// Returns the HTTP version of the request.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Returns the headers of the request.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Returns the `content-type` of the request as it was sent, parameters included.
+    ///
+    /// Returns `None` if the header is missing or isn't visible ASCII.
+    pub fn content_type(&self) -> Option<&str> {
+        content_type(&self.headers)
+    }
+}
+
+fn content_type(headers: &HeaderMap) -> Option<&str> {
+    headers.get(CONTENT_TYPE)?.to_str().ok()
+}
+
+impl<P> FromParts<P> for RequestParts {
+    type Rejection = Infallible;
+
+    fn from_parts(parts: &mut Parts) -> Result<Self, Self::Rejection> {
+        Ok(RequestParts {
+            method: parts.method.clone(),
+            uri: parts.uri.clone(),
+            version: parts.version,
+            headers: parts.headers.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use http::{Method, Request, Version};
+
+    use super::{RawHeaders, RequestParts};
+    use crate::{
+        operation::{Handler, OperationShape},
+        request::FromParts,
+        shape_id::ShapeId,
+        Extension,
+    };
+
+    struct Op;
+
+    struct Input;
+
+    impl OperationShape for Op {
+        const ID: ShapeId = ShapeId::new("com.example#Op", "com.example", "Op");
+
+        type Input = Input;
+        type Output = ();
+        type Error = Infallible;
+    }
+
+    // What the service builder requires of a handler, protocol aside
+    fn assert_handler<Exts: FromParts<()>, H: Handler<Op, Exts>>(_handler: H) {}
+
+    #[test]
+    fn handlers_with_and_without_raw_extractors_bind() {
+        async fn input_only(_input: Input) {}
+        async fn raw_headers(_input: Input, _headers: RawHeaders) {}
+        async fn request_parts(_input: Input, _parts: RequestParts) {}
+        async fn extension_and_raw_headers(_input: Input, _state: Extension<u32>, _headers: RawHeaders) {}
+        async fn both(_input: Input, _parts: RequestParts, _state: Extension<u32>, _headers: RawHeaders) {}
+
+        assert_handler(input_only);
+        assert_handler(raw_headers);
+        assert_handler(request_parts);
+        assert_handler(extension_and_raw_headers);
+        assert_handler(both);
+    }
+
+    #[test]
+    fn extraction_leaves_the_request_intact() {
+        let (mut parts, _) = Request::builder()
+            .method(Method::POST)
+            .uri("/pokemon?region=kanto")
+            .header("x-feature-flags", "shiny")
+            .header("content-type", "text/plain; charset=utf-8")
+            .body(())
+            .unwrap()
+            .into_parts();
+
+        let (request_parts, RawHeaders(headers)) =
+            <(RequestParts, RawHeaders) as FromParts<()>>::from_parts(&mut parts).unwrap();
+
+        assert_eq!(&Method::POST, request_parts.method());
+        assert_eq!("/pokemon?region=kanto", *request_parts.uri());
+        assert_eq!(Version::HTTP_11, request_parts.version());
+        assert_eq!("shiny", request_parts.headers()["x-feature-flags"]);
+        assert_eq!("shiny", headers["x-feature-flags"]);
+        assert_eq!(Some("text/plain; charset=utf-8"), request_parts.content_type());
+        // The input is deserialized from the same parts afterwards
+        assert_eq!("shiny", parts.headers["x-feature-flags"]);
+    }
+}
@@ -0,0 +1,183 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+/// How a router handles request paths that contain dot-segments.
+///
+/// A dot-segment is a path segment that is `.` or `..`. Percent-encoded dots are treated
+/// identically to literal ones, so `%2e%2e`, `.%2E` and `..` are all the same dot-segment. A
+/// segment with a percent-encoded `/` or `\` (such as `..%2f`) isn't a dot-segment, since encoded
+/// slashes don't separate segments, but it's rejected under every policy other than
+/// [`DotSegments::Preserve`] if decoding it reveals a dot-segment.
+///
+/// Empty segments (as in `//`) aren't dot-segments; they're significant when routing and are
+/// never collapsed.
+///
+/// Routing with the AWS JSON protocols requires a request path of exactly `/`, and RPC v2 CBOR
+/// routes on the service and operation segments of the path, which can't be dot-segments, so
+/// this policy only applies to routers of REST protocols.
+///
+/// Routers used to route dot-segments like any other segment, which is now
+/// [`DotSegments::Preserve`]. Services relying on label values of `.` or `..` must opt back into
+/// it, since the default is [`DotSegments::Reject`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DotSegments {
+    /// Requests whose path contains a dot-segment are rejected with `400 Bad Request`.
+    #[default]
+    Reject,
+    /// Dot-segments are removed from the path, as described in [RFC 3986 section 5.2.4], before
+    /// routing the request, and the request is passed on with the normalized path.
+    ///
+    /// A request whose raw path matches a route is rejected with `400 Bad Request` if its
+    /// normalized path doesn't match the same route, so normalizing can never route a request
+    /// to a different operation than its raw path would. A request whose raw path doesn't match
+    /// any route is routed using its normalized path.
+    ///
+    /// [RFC 3986 section 5.2.4]: https://www.rfc-editor.org/rfc/rfc3986#section-5.2.4
+    Normalize,
+    /// Dot-segments are routed like any other segment, so label values can be `.` or `..`.
+    Preserve,
+}
+
+/// Dot-segment hazards found in a request path.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum PathHazard {
+    /// The path has no dot-segments.
+    None,
+    /// The path has dot-segments that can be normalized.
+    DotSegments,
+    /// The path has a segment that contains a dot-segment once encoded slashes are decoded.
+    EncodedTraversal,
+}
+
+/// Decodes the percent-encoded dots and slashes in a path segment, leaving anything else as is.
+fn decode_dots_and_slashes(segment: &str) -> String {
+    let mut decoded = String::with_capacity(segment.len());
+    let mut rest = segment;
+    while let Some(index) = rest.find('%') {
+        decoded.push_str(&rest[..index]);
+        rest = &rest[index..];
+        let replacement = match rest.get(1..3).map(str::to_ascii_lowercase).as_deref() {
+            Some("2e") => Some('.'),
+            Some("2f") => Some('/'),
+            Some("5c") => Some('\\'),
+            _ => None,
+        };
+        match replacement {
+            Some(replacement) => {
+                decoded.push(replacement);
+                rest = &rest[3..];
+            }
+            None => {
+                decoded.push('%');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn is_dot_segment(decoded: &str) -> bool {
+    decoded == "." || decoded == ".."
+}
+
+/// Returns the dot-segment hazards in `path`.
+pub(crate) fn path_hazard(path: &str) -> PathHazard {
+    let mut hazard = PathHazard::None;
+    for segment in path.split('/') {
+        if !segment.contains('.') && !segment.contains('%') {
+            continue;
+        }
+        let decoded = decode_dots_and_slashes(segment);
+        if is_dot_segment(&decoded) {
+            hazard = PathHazard::DotSegments;
+        } else if decoded.split(['/', '\\']).any(is_dot_segment) {
+            return PathHazard::EncodedTraversal;
+        }
+    }
+    hazard
+}
+
+/// Returns true if `segment` is a dot-segment, once percent-encoded dots are decoded.
+pub(crate) fn is_encoded_dot_segment(segment: &str) -> bool {
+    is_dot_segment(&decode_dots_and_slashes(segment))
+}
+
+/// Removes the dot-segments from `path`, which must start with `/`.
+///
+/// Unlike [RFC 3986 section 5.2.4], empty segments are preserved, and percent-encoded dots are
+/// treated like literal ones.
+///
+/// [RFC 3986 section 5.2.4]: https://www.rfc-editor.org/rfc/rfc3986#section-5.2.4
+pub(crate) fn remove_dot_segments(path: &str) -> String {
+    let mut output: Vec<&str> = Vec::new();
+    let mut segments = path.split('/').skip(1).peekable();
+    while let Some(segment) = segments.next() {
+        match decode_dots_and_slashes(segment).as_str() {
+            "." => {}
+            ".." => {
+                output.pop();
+            }
+            _ => output.push(segment),
+        }
+        // A trailing dot-segment refers to a directory, so the path keeps a trailing slash.
+        if segments.peek().is_none() && is_encoded_dot_segment(segment) {
+            output.push("");
+        }
+    }
+    format!("/{}", output.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hazards() {
+        let cases = [
+            ("/", PathHazard::None),
+            ("/a/b", PathHazard::None),
+            ("/a//b/", PathHazard::None),
+            ("/a/.../b", PathHazard::None),
+            ("/a/b..c/.d", PathHazard::None),
+            ("/a/%2e%2e%2e", PathHazard::None),
+            ("/a/%20%2", PathHazard::None),
+            ("/a/./b", PathHazard::DotSegments),
+            ("/a/..", PathHazard::DotSegments),
+            ("/a/%2e%2E/b", PathHazard::DotSegments),
+            ("/a/.%2e", PathHazard::DotSegments),
+            ("/a/%2E", PathHazard::DotSegments),
+            ("/a/..%2fb", PathHazard::EncodedTraversal),
+            ("/a/b%2F%2e%2e", PathHazard::EncodedTraversal),
+            ("/a/..%5Cb", PathHazard::EncodedTraversal),
+            ("/../a/..%2fb", PathHazard::EncodedTraversal),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(expected, path_hazard(path), "{path}");
+        }
+    }
+
+    #[test]
+    fn dot_segments_are_removed() {
+        let cases = [
+            ("/", "/"),
+            ("/a/b", "/a/b"),
+            ("/a/./b", "/a/b"),
+            ("/a/../b", "/b"),
+            ("/a/b/..", "/a/"),
+            ("/a/b/.", "/a/b/"),
+            ("/../../a", "/a"),
+            ("/..", "/"),
+            ("/a//../b", "/a/b"),
+            ("/a//b", "/a//b"),
+            ("/a/%2E%2e/b", "/b"),
+            ("/a/.%2e/.../b", "/.../b"),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(expected, remove_dot_segments(path), "{path}");
+        }
+    }
+}
@@ -7,11 +7,13 @@
 //!
 //! [Smithy specification]: https://smithy.io/2.0/spec/http-bindings.html
 
+mod dot_segments;
 mod into_make_service;
 mod into_make_service_with_connect_info;
 #[cfg(feature = "aws-lambda")]
 #[cfg_attr(docsrs, doc(cfg(feature = "aws-lambda")))]
 mod lambda_handler;
+mod mount;
 
 #[doc(hidden)]
 pub mod request_spec;
@@ -50,8 +52,10 @@ pub use self::lambda_handler::LambdaHandler;
 
 #[allow(deprecated)]
 pub use self::{
+    dot_segments::DotSegments,
     into_make_service::IntoMakeService,
     into_make_service_with_connect_info::{Connected, IntoMakeServiceWithConnectInfo},
+    mount::{MountError, MountedRouter, MountedRouterFuture, RouterBuilder},
     route::Route,
 };
 
@@ -64,6 +68,13 @@ pub(crate) fn method_disallowed() -> http::Response<BoxBody> {
     responses
 }
 
+/// Constructs common response to a request path that is rejected before routing.
+pub(crate) fn invalid_path() -> http::Response<BoxBody> {
+    let mut responses = http::Response::default();
+    *responses.status_mut() = http::StatusCode::BAD_REQUEST;
+    responses
+}
+
 /// An interface for retrieving an inner [`Service`] given a [`http::Request`].
 pub trait Router<B> {
     type Service;
@@ -71,6 +82,14 @@ pub trait Router<B> {
 
     /// Matches a [`http::Request`] to a target [`Service`].
     fn match_route(&self, request: &http::Request<B>) -> Result<Self::Service, Self::Error>;
+
+    /// Matches a [`http::Request`] to a target [`Service`], rewriting the request first if the
+    /// router normalizes it before matching.
+    ///
+    /// Defaults to [`Router::match_route`].
+    fn route(&self, request: &mut http::Request<B>) -> Result<Self::Service, Self::Error> {
+        self.match_route(request)
+    }
 }
 
 /// A [`Service`] using the [`Router`] `R` to redirect messages to specific routes.
@@ -189,9 +208,9 @@ where
         Poll::Ready(Ok(()))
     }
 
-    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
         tracing::debug!("inside routing service call");
-        match self.router.match_route(&req) {
+        match self.router.route(&mut req) {
             // Successfully routed, use the routes `Service::call`.
             Ok(ok) => RoutingFuture::from_oneshot(ok.oneshot(req)),
             // Failed to route, use the `R::Error`s `IntoResponse<P>`.
@@ -0,0 +1,407 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Serving several services on one listener by mounting them at path prefixes.
+
+use std::{
+    convert::Infallible,
+    fmt,
+    future::{ready, Ready},
+    task::{Context, Poll},
+};
+
+use futures_util::future::Either;
+use http::{uri::PathAndQuery, Request, Response, Uri};
+use thiserror::Error;
+use tower::Service;
+
+use crate::body::{Body, BoxBody};
+
+use super::{
+    into_make_service::IntoMakeService, into_make_service_with_connect_info::IntoMakeServiceWithConnectInfo,
+    route::RouteFuture, Route,
+};
+
+/// An error returned by [`RouterBuilder::build`] when the mounted services can't be told apart.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MountError {
+    /// The prefix or path isn't of the form `/segment[/segment...]`.
+    #[error("`{0}` is not a valid mount path: it must start with `/`, not end with `/`, and have no query")]
+    InvalidPath(String),
+    /// Two services were mounted at the same prefix, or two raw routes have the same path.
+    #[error("`{0}` is mounted more than once")]
+    Conflict(String),
+    /// A prefix or raw route path is nested within the prefix of a mounted service.
+    #[error("`{nested}` is nested within the mounted prefix `{prefix}`")]
+    Nested {
+        /// The prefix of the mounted service.
+        prefix: String,
+        /// The prefix or raw route path nested within it.
+        nested: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MountKind {
+    /// A service routing every request whose path starts with the prefix, with the prefix removed.
+    Prefix,
+    /// A service routing requests whose path is exactly the given one.
+    Raw,
+}
+
+struct Mount<B> {
+    path: String,
+    kind: MountKind,
+    route: Route<B>,
+}
+
+impl<B> fmt::Debug for Mount<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mount")
+            .field("path", &self.path)
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B> Clone for Mount<B> {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            kind: self.kind,
+            route: self.route.clone(),
+        }
+    }
+}
+
+/// Builds a [`MountedRouter`], which serves several services on one listener.
+///
+/// Each service is mounted at a path prefix. A request whose path starts with a prefix is routed
+/// to the service mounted at it, with the prefix removed from its path, so the service routes it
+/// as if it were mounted at `/`. Services keep their own plugins and layers, which only see the
+/// requests routed to them. Raw routes serve requests whose path is exactly the given one, which
+/// is useful for health checks and metrics endpoints.
+///
+/// ```rust,ignore
+/// let app = RouterBuilder::new()
+///     .mount("/admin", admin_service)
+///     .mount("/v1", public_service)
+///     .route_raw("/healthz", health_check)
+///     .build()?;
+/// let server = hyper::Server::bind(&address).serve(app.into_make_service());
+/// ```
+///
+/// Since the prefix is removed before the mounted service routes the request, the dot-segments of
+/// a request path are handled by the mounted service's own policy, and can never route a request
+/// to another mounted service.
+pub struct RouterBuilder<B = Body> {
+    mounts: Vec<Mount<B>>,
+}
+
+impl<B> fmt::Debug for RouterBuilder<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RouterBuilder").field("mounts", &self.mounts).finish()
+    }
+}
+
+impl<B> Default for RouterBuilder<B> {
+    fn default() -> Self {
+        Self { mounts: Vec::new() }
+    }
+}
+
+impl<B> RouterBuilder<B> {
+    /// Creates a builder with no mounted services.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mounts `service` at `prefix`.
+    ///
+    /// Requests whose path is `prefix`, or starts with `prefix` followed by `/`, are routed to
+    /// `service` with `prefix` removed from their path.
+    pub fn mount<S>(self, prefix: impl Into<String>, service: S) -> Self
+    where
+        S: Service<Request<B>, Response = Response<BoxBody>, Error = Infallible> + Clone + Send + 'static,
+        S::Future: Send + 'static,
+    {
+        self.push(prefix.into(), MountKind::Prefix, Route::new(service))
+    }
+
+    /// Routes requests whose path is exactly `path` to `service`, leaving the request untouched.
+    pub fn route_raw<S>(self, path: impl Into<String>, service: S) -> Self
+    where
+        S: Service<Request<B>, Response = Response<BoxBody>, Error = Infallible> + Clone + Send + 'static,
+        S::Future: Send + 'static,
+    {
+        self.push(path.into(), MountKind::Raw, Route::new(service))
+    }
+
+    fn push(mut self, path: String, kind: MountKind, route: Route<B>) -> Self {
+        self.mounts.push(Mount { path, kind, route });
+        self
+    }
+
+    /// Builds the [`MountedRouter`].
+    ///
+    /// Returns an error if a path is invalid, if two services are mounted at the same path, or
+    /// if a path is nested within the prefix of a mounted service, since it would then be
+    /// ambiguous which service a request is meant for.
+    pub fn build(self) -> Result<MountedRouter<B>, MountError> {
+        for mount in &self.mounts {
+            let valid = mount.path.starts_with('/')
+                && (mount.path.len() > 1 || mount.kind == MountKind::Raw)
+                && (!mount.path.ends_with('/') || mount.path.len() == 1)
+                && !mount.path.contains(['?', '#'])
+                && mount.path.parse::<PathAndQuery>().is_ok();
+            if !valid {
+                return Err(MountError::InvalidPath(mount.path.clone()));
+            }
+        }
+        for (index, mount) in self.mounts.iter().enumerate() {
+            for other in &self.mounts[index + 1..] {
+                if mount.path == other.path {
+                    return Err(MountError::Conflict(mount.path.clone()));
+                }
+                for (outer, inner) in [(mount, other), (other, mount)] {
+                    if outer.kind == MountKind::Prefix && strip_prefix(&inner.path, &outer.path).is_some() {
+                        return Err(MountError::Nested {
+                            prefix: outer.path.clone(),
+                            nested: inner.path.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(MountedRouter { mounts: self.mounts })
+    }
+}
+
+/// Returns the rest of `path` if it is `prefix`, or starts with `prefix` followed by `/`.
+fn strip_prefix<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    match path.strip_prefix(prefix)? {
+        "" => Some("/"),
+        rest if rest.starts_with('/') => Some(rest),
+        _ => None,
+    }
+}
+
+/// A [`Service`] serving several services on one listener, built by a [`RouterBuilder`].
+///
+/// Requests that don't match any mounted service are rejected with `404 Not Found`.
+pub struct MountedRouter<B = Body> {
+    mounts: Vec<Mount<B>>,
+}
+
+impl<B> fmt::Debug for MountedRouter<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MountedRouter").field("mounts", &self.mounts).finish()
+    }
+}
+
+impl<B> Clone for MountedRouter<B> {
+    fn clone(&self) -> Self {
+        Self {
+            mounts: self.mounts.clone(),
+        }
+    }
+}
+
+impl<B> MountedRouter<B> {
+    /// Converts this router into a [`MakeService`], that is a [`Service`] whose
+    /// response is another service.
+    ///
+    /// [`MakeService`]: tower::make::MakeService
+    pub fn into_make_service(self) -> IntoMakeService<Self> {
+        IntoMakeService::new(self)
+    }
+
+    /// Converts this router into a [`MakeService`], that is a [`Service`] whose
+    /// response is another service, and enables connection information to be
+    /// passed to the mounted services.
+    ///
+    /// [`MakeService`]: tower::make::MakeService
+    pub fn into_make_service_with_connect_info<C>(self) -> IntoMakeServiceWithConnectInfo<Self, C> {
+        IntoMakeServiceWithConnectInfo::new(self)
+    }
+
+    /// Finds the route for `request`, removing the mount prefix from its path.
+    fn route(&self, request: &mut Request<B>) -> Option<Route<B>> {
+        let path = request.uri().path();
+        if let Some(mount) = self
+            .mounts
+            .iter()
+            .find(|mount| mount.kind == MountKind::Raw && mount.path == path)
+        {
+            return Some(mount.route.clone());
+        }
+        let (mount, rest) = self.mounts.iter().find_map(|mount| match mount.kind {
+            MountKind::Prefix => strip_prefix(path, &mount.path).map(|rest| (mount, rest)),
+            MountKind::Raw => None,
+        })?;
+        let path_and_query = match request.uri().query() {
+            Some(query) => format!("{rest}?{query}"),
+            None => rest.to_owned(),
+        };
+        let mut parts = request.uri().clone().into_parts();
+        parts.path_and_query = Some(
+            path_and_query
+                .parse()
+                .expect("removing a prefix from a valid path leaves a valid path"),
+        );
+        *request.uri_mut() = Uri::from_parts(parts).expect("only the path was changed");
+        Some(mount.route.clone())
+    }
+}
+
+opaque_future! {
+    /// Response future for [`MountedRouter`].
+    pub type MountedRouterFuture<B> = Either<RouteFuture<B>, Ready<Result<Response<BoxBody>, Infallible>>>;
+}
+
+impl<B> Service<Request<B>> for MountedRouter<B> {
+    type Response = Response<BoxBody>;
+    type Error = Infallible;
+    type Future = MountedRouterFuture<B>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        match self.route(&mut request) {
+            Some(mut route) => MountedRouterFuture::new(Either::Left(route.call(request))),
+            None => {
+                tracing::debug!(path = %request.uri().path(), "no service is mounted at the request path");
+                let mut response = Response::new(crate::body::empty());
+                *response.status_mut() = http::StatusCode::NOT_FOUND;
+                MountedRouterFuture::new(Either::Right(ready(Ok(response))))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::to_boxed;
+    use http::StatusCode;
+    use tower::{service_fn, ServiceExt};
+
+    /// A service responding with its name and the URI of the request it got.
+    fn echo(name: &'static str) -> Route {
+        Route::new(service_fn(move |request: Request<Body>| async move {
+            let body = format!("{name} {}", request.uri());
+            Ok::<_, Infallible>(Response::new(to_boxed(body)))
+        }))
+    }
+
+    async fn call(router: &MountedRouter, uri: &str) -> (StatusCode, String) {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    fn router() -> MountedRouter {
+        RouterBuilder::new()
+            .mount("/admin", echo("admin"))
+            .mount("/v1", echo("public"))
+            .route_raw("/healthz", echo("health"))
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn prefixes_are_removed_before_routing() {
+        let router = router();
+        assert_eq!(
+            (StatusCode::OK, "admin /users/1".to_owned()),
+            call(&router, "/admin/users/1").await
+        );
+        assert_eq!(
+            (StatusCode::OK, "public /pokemon?name=pikachu".to_owned()),
+            call(&router, "/v1/pokemon?name=pikachu").await
+        );
+        assert_eq!((StatusCode::OK, "public /".to_owned()), call(&router, "/v1").await);
+        assert_eq!((StatusCode::OK, "public /".to_owned()), call(&router, "/v1/").await);
+        assert_eq!(
+            (StatusCode::OK, "health /healthz".to_owned()),
+            call(&router, "/healthz").await
+        );
+    }
+
+    #[tokio::test]
+    async fn unmounted_paths_are_not_found() {
+        let router = router();
+        for uri in ["/", "/v2/pokemon", "/v1pokemon", "/healthz/more", "/administrator"] {
+            assert_eq!(StatusCode::NOT_FOUND, call(&router, uri).await.0, "{uri}");
+        }
+    }
+
+    #[test]
+    fn conflicting_mounts_are_rejected() {
+        let err = RouterBuilder::new()
+            .mount("/v1", echo("a"))
+            .mount("/v1", echo("b"))
+            .build()
+            .unwrap_err();
+        assert_eq!(MountError::Conflict("/v1".into()), err);
+
+        let err = RouterBuilder::new()
+            .mount("/v1", echo("a"))
+            .route_raw("/v1", echo("b"))
+            .build()
+            .unwrap_err();
+        assert_eq!(MountError::Conflict("/v1".into()), err);
+    }
+
+    #[test]
+    fn nested_mounts_are_rejected() {
+        let err = RouterBuilder::new()
+            .mount("/v1/admin", echo("a"))
+            .mount("/v1", echo("b"))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            MountError::Nested {
+                prefix: "/v1".into(),
+                nested: "/v1/admin".into()
+            },
+            err
+        );
+
+        let err = RouterBuilder::new()
+            .mount("/v1", echo("a"))
+            .route_raw("/v1/healthz", echo("b"))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            MountError::Nested {
+                prefix: "/v1".into(),
+                nested: "/v1/healthz".into()
+            },
+            err
+        );
+
+        // Prefixes that only share a beginning aren't nested.
+        RouterBuilder::new()
+            .mount("/v1", echo("a"))
+            .mount("/v10", echo("b"))
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn invalid_paths_are_rejected() {
+        for prefix in ["", "/", "v1", "/v1/", "/v1?a=b"] {
+            let err = RouterBuilder::new().mount(prefix, echo("a")).build().unwrap_err();
+            assert_eq!(MountError::InvalidPath(prefix.into()), err);
+        }
+        RouterBuilder::new().route_raw("/", echo("a")).build().unwrap();
+    }
+}
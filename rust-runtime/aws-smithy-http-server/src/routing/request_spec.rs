@@ -161,12 +161,43 @@ impl RequestSpec {
         self.uri_spec.path_and_query.path_segments.0.len() + self.uri_spec.path_and_query.query_segments.0.len()
     }
 
+    /// The path segments of the URI pattern.
+    pub(crate) fn path_segments(&self) -> &[PathSegment] {
+        &self.uri_spec.path_and_query.path_segments.0
+    }
+
+    /// Renders the path of the URI pattern, with `{label}` and `{label+}` standing in for labels
+    /// and greedy labels.
+    pub(crate) fn path_pattern(&self) -> String {
+        let pattern: String = self
+            .path_segments()
+            .iter()
+            .map(|segment| match segment {
+                PathSegment::Literal(literal) => format!("/{literal}"),
+                PathSegment::Label => String::from("/{label}"),
+                PathSegment::Greedy => String::from("/{label+}"),
+            })
+            .collect();
+        if pattern.is_empty() {
+            String::from("/")
+        } else {
+            pattern
+        }
+    }
+
+    // Helper function to match a request on its own path.
+    #[cfg(test)]
     pub(crate) fn matches<B>(&self, req: &Request<B>) -> Match {
+        self.matches_path(req, req.uri().path())
+    }
+
+    /// Matches the request as if its path was `path`.
+    pub(crate) fn matches_path<B>(&self, req: &Request<B>, path: &str) -> Match {
         if let Some(_host_prefix) = &self.uri_spec.host_prefix {
             todo!("Look at host prefix");
         }
 
-        if !self.uri_path_regex.is_match(req.uri().path()) {
+        if !self.uri_path_regex.is_match(path) {
             return Match::No;
         }
 
@@ -505,4 +536,63 @@ mod tests {
             spec.matches(&req(&Method::GET, "/ReDosLiteral/abc/(a+)+", None))
         );
     }
+
+    // The same label values are serialized as a client would, then routed and decoded as a server
+    // would. Routing happens on the raw path, so a `/` in the value of a default label (sent as
+    // `%2F`) doesn't separate path segments.
+    #[test]
+    fn label_values_round_trip_from_client_to_server() {
+        use aws_smithy_http::label::{decode_label, fmt_label, EncodingStrategy, LabelRules};
+
+        let spec = |label| {
+            RequestSpec::from_parts(
+                Method::GET,
+                vec![
+                    PathSegment::Literal(String::from("objects")),
+                    label,
+                    PathSegment::Literal(String::from("meta")),
+                ],
+                Vec::new(),
+            )
+        };
+        let label_spec = spec(PathSegment::Label);
+        let greedy_spec = spec(PathSegment::Greedy);
+
+        // (label value, whether the label is greedy)
+        let cases = [
+            ("a", false),
+            ("a", true),
+            ("a/b", false),
+            ("a/b", true),
+            ("a%2Fb", false),
+            ("a%2Fb", true),
+            ("a b?c#d", false),
+            ("a/b c/d+e", true),
+            ("a/", true),
+            ("a//b", true),
+            ("ünïcödé", false),
+        ];
+        for (value, greedy) in cases {
+            let (strategy, spec) = if greedy {
+                (EncodingStrategy::Greedy, &greedy_spec)
+            } else {
+                (EncodingStrategy::Default, &label_spec)
+            };
+            let raw = fmt_label(value, strategy.clone(), &LabelRules::client()).unwrap();
+            let path = format!("/objects/{raw}/meta");
+            assert_eq!(Match::Yes, spec.matches(&req(&Method::GET, &path, None)), "{path}");
+
+            let raw = path.strip_prefix("/objects/").unwrap().strip_suffix("/meta").unwrap();
+            let decoded = decode_label(raw, strategy, &LabelRules::server()).unwrap();
+            assert_eq!(value, decoded, "{path}");
+        }
+
+        // Clients don't send empty labels, since they produce empty path segments. Servers route
+        // them, and bind them to `""` unless configured to reject them.
+        assert!(fmt_label("", EncodingStrategy::Default, &LabelRules::client()).is_err());
+        assert_eq!(
+            Match::Yes,
+            label_spec.matches(&req(&Method::GET, "/objects//meta", None))
+        );
+    }
 }
@@ -0,0 +1,381 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Identifying the server runtime in a `Server` response header.
+//!
+//! When enabled, every response carries a [`Server`](http::header::SERVER) header naming the version of
+//! `aws-smithy-http-server` and the protocol of the service, for example `smithy-rs-server/0.63.4 restJson1`,
+//! followed by an optional product token of the application. This is meant for debugging a fleet of services
+//! running different versions of the runtime, and is disabled by default.
+//!
+//! [`ServerIdentification`] can be installed in two forms, which can be combined:
+//!
+//! - As a [`Layer`] applied around the service with [`ServerIdentification::layer`]. The header is then also
+//!   set on responses to requests that couldn't be routed to an operation.
+//! - As a HTTP [`Plugin`] with [`ServerIdentification::plugin`], so that it can be
+//!   [scoped](crate::plugin::Scoped) to some operations. The protocol is taken from the service.
+//!
+//! The header is only set when the response doesn't already have one, so installing both forms doesn't
+//! duplicate it, and a `Server` header set by a handler or by another plugin is left as is.
+//!
+//! ```
+//! use aws_smithy_http_server::plugin::HttpPlugins;
+//! use aws_smithy_http_server::protocol::rest_json_1::RestJson1;
+//! use aws_smithy_http_server::server_identification::ServerIdentification;
+//!
+//! let identification = ServerIdentification::new()
+//!     .enable(true)
+//!     .extra_token("pokemon-svc/2.3");
+//!
+//! // Set the header on the responses of every operation...
+//! let http_plugins = HttpPlugins::new().push(identification.plugin());
+//! // ...and on responses to requests that weren't routed, by applying the layer around the service.
+//! let layer = identification.layer::<RestJson1>();
+//! # let _ = (http_plugins, layer);
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::TryFuture;
+use http::header::SERVER;
+use http::HeaderValue;
+use tower::{Layer, Service};
+
+use crate::plugin::{HttpMarker, Plugin};
+use crate::protocol::aws_json_10::AwsJson1_0;
+use crate::protocol::aws_json_11::AwsJson1_1;
+use crate::protocol::rest_json_1::RestJson1;
+use crate::protocol::rest_xml::RestXml;
+use crate::protocol::rpc_v2_cbor::RpcV2Cbor;
+use crate::service::ServiceShape;
+
+/// The product token identifying this version of the server runtime.
+pub const SERVER_PRODUCT: &str = concat!("smithy-rs-server/", env!("CARGO_PKG_VERSION"));
+
+/// A protocol with a name that [`ServerIdentification`] can put in the `Server` header.
+pub trait ProtocolName {
+    /// The name of the protocol, as used in the Smithy protocol trait.
+    const NAME: &'static str;
+}
+
+impl ProtocolName for RestJson1 {
+    const NAME: &'static str = "restJson1";
+}
+
+impl ProtocolName for RestXml {
+    const NAME: &'static str = "restXml";
+}
+
+impl ProtocolName for AwsJson1_0 {
+    const NAME: &'static str = "awsJson1_0";
+}
+
+impl ProtocolName for AwsJson1_1 {
+    const NAME: &'static str = "awsJson1_1";
+}
+
+impl ProtocolName for RpcV2Cbor {
+    const NAME: &'static str = "rpcv2Cbor";
+}
+
+/// Configures the `Server` response header identifying the server runtime.
+///
+/// See the [module documentation](crate::server_identification) for how to install it.
+#[derive(Clone, Debug, Default)]
+pub struct ServerIdentification {
+    enabled: bool,
+    extra_token: Option<String>,
+}
+
+impl ServerIdentification {
+    /// Creates a disabled configuration, which leaves responses unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables or disables the `Server` header.
+    pub fn enable(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Appends a product token of the application, for example `pokemon-svc/2.3`, to the header.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the token contains characters that aren't allowed in a header value.
+    pub fn extra_token(mut self, token: impl Into<String>) -> Self {
+        let token = token.into();
+        assert!(
+            HeaderValue::from_str(&token).is_ok(),
+            "`{token}` contains characters that aren't allowed in a `Server` header"
+        );
+        self.extra_token = Some(token);
+        self
+    }
+
+    /// Returns the value of the `Server` header for `protocol`, or `None` when disabled.
+    pub fn header_value(&self, protocol: &str) -> Option<HeaderValue> {
+        if !self.enabled {
+            return None;
+        }
+        let value = match &self.extra_token {
+            Some(token) => format!("{SERVER_PRODUCT} {protocol} {token}"),
+            None => format!("{SERVER_PRODUCT} {protocol}"),
+        };
+        Some(HeaderValue::try_from(value).expect("the extra token was checked when it was set"))
+    }
+
+    /// Returns a [`Layer`] setting the header on every response of a service using the protocol `P`.
+    pub fn layer<P: ProtocolName>(&self) -> ServerIdentificationLayer<P> {
+        ServerIdentificationLayer {
+            header_value: self.header_value(P::NAME),
+            _protocol: PhantomData,
+        }
+    }
+
+    /// Returns a HTTP [`Plugin`] setting the header on the responses of the operations it's applied to.
+    pub fn plugin(&self) -> ServerIdentificationPlugin {
+        ServerIdentificationPlugin {
+            identification: self.clone(),
+        }
+    }
+}
+
+/// A [`Layer`] applying [`ServerIdentificationService`], created with [`ServerIdentification::layer`].
+pub struct ServerIdentificationLayer<P> {
+    header_value: Option<HeaderValue>,
+    _protocol: PhantomData<P>,
+}
+
+impl<P> Clone for ServerIdentificationLayer<P> {
+    fn clone(&self) -> Self {
+        Self {
+            header_value: self.header_value.clone(),
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<P> fmt::Debug for ServerIdentificationLayer<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerIdentificationLayer")
+            .field("header_value", &self.header_value)
+            .finish()
+    }
+}
+
+impl<S, P> Layer<S> for ServerIdentificationLayer<P> {
+    type Service = ServerIdentificationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ServerIdentificationService {
+            inner,
+            header_value: self.header_value.clone(),
+        }
+    }
+}
+
+/// A HTTP [`Plugin`] applying [`ServerIdentificationService`], created with [`ServerIdentification::plugin`].
+#[derive(Clone, Debug)]
+pub struct ServerIdentificationPlugin {
+    identification: ServerIdentification,
+}
+
+impl<Ser, Op, T> Plugin<Ser, Op, T> for ServerIdentificationPlugin
+where
+    Ser: ServiceShape,
+    Ser::Protocol: ProtocolName,
+{
+    type Output = ServerIdentificationService<T>;
+
+    fn apply(&self, inner: T) -> Self::Output {
+        ServerIdentificationService {
+            inner,
+            header_value: self.identification.header_value(Ser::Protocol::NAME),
+        }
+    }
+}
+
+impl HttpMarker for ServerIdentificationPlugin {}
+
+/// A middleware [`Service`] setting the `Server` header on responses that don't have one.
+#[derive(Clone, Debug)]
+pub struct ServerIdentificationService<S> {
+    inner: S,
+    header_value: Option<HeaderValue>,
+}
+
+impl<S, B, RespB> Service<http::Request<B>> for ServerIdentificationService<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<RespB>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = ServerIdentificationFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        ServerIdentificationFuture {
+            header_value: self.header_value.clone(),
+            fut: self.inner.call(req),
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// Future for [`ServerIdentificationService`].
+    pub struct ServerIdentificationFuture<Fut> {
+        header_value: Option<HeaderValue>,
+        #[pin]
+        fut: Fut,
+    }
+}
+
+impl<Fut, RespB> Future for ServerIdentificationFuture<Fut>
+where
+    Fut: TryFuture<Ok = http::Response<RespB>>,
+{
+    type Output = Result<Fut::Ok, Fut::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let header_value = this.header_value;
+        this.fut.try_poll(cx).map_ok(|mut res| {
+            if let Some(header_value) = header_value.take() {
+                res.headers_mut().entry(SERVER).or_insert(header_value);
+            }
+            res
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::{empty, BoxBody};
+    use crate::protocol::rest::router::RestRouter;
+    use crate::routing::{Route, RoutingService};
+    use crate::shape_id::ShapeId;
+    use http::StatusCode;
+    use std::convert::Infallible;
+    use tower::{service_fn, ServiceExt};
+
+    struct TestService;
+
+    impl ServiceShape for TestService {
+        const ID: ShapeId = ShapeId::new("test#TestService", "test", "TestService");
+        const VERSION: Option<&'static str> = None;
+        type Protocol = AwsJson1_0;
+        type Operations = ();
+    }
+
+    fn handler(
+    ) -> impl Service<http::Request<BoxBody>, Response = http::Response<BoxBody>, Error = Infallible, Future = impl Send>
+           + Clone {
+        service_fn(|_req: http::Request<BoxBody>| async {
+            let mut response = http::Response::new(empty());
+            response
+                .headers_mut()
+                .insert("x-handler", HeaderValue::from_static("hi"));
+            Ok::<_, Infallible>(response)
+        })
+    }
+
+    async fn server_headers<S>(svc: S) -> Vec<String>
+    where
+        S: Service<http::Request<BoxBody>, Response = http::Response<BoxBody>, Error = Infallible>,
+    {
+        let response = svc.oneshot(http::Request::new(empty())).await.unwrap();
+        response
+            .headers()
+            .get_all(SERVER)
+            .iter()
+            .map(|value| value.to_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn disabled_by_default() {
+        let svc = ServerIdentification::new().layer::<RestJson1>().layer(handler());
+        assert!(server_headers(svc).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn the_header_names_the_runtime_version_protocol_and_extra_token() {
+        let identification = ServerIdentification::new().enable(true);
+        let svc = identification.layer::<RestJson1>().layer(handler());
+        assert_eq!(
+            vec![format!("smithy-rs-server/{} restJson1", env!("CARGO_PKG_VERSION"))],
+            server_headers(svc).await
+        );
+
+        let identification = identification.extra_token("pokemon-svc/2.3");
+        let svc = Plugin::<TestService, (), _>::apply(&identification.plugin(), handler());
+        assert_eq!(
+            vec![format!(
+                "smithy-rs-server/{} awsJson1_0 pokemon-svc/2.3",
+                env!("CARGO_PKG_VERSION")
+            )],
+            server_headers(svc).await
+        );
+    }
+
+    #[tokio::test]
+    async fn the_header_is_set_once_when_the_layer_and_plugin_are_both_installed() {
+        let identification = ServerIdentification::new().enable(true).extra_token("pokemon-svc/2.3");
+        let operation = Plugin::<TestService, (), _>::apply(&identification.plugin(), handler());
+        let svc = identification.layer::<RestJson1>().layer(operation);
+        // The plugin runs closest to the operation, so its value wins
+        assert_eq!(
+            vec![format!(
+                "smithy-rs-server/{} awsJson1_0 pokemon-svc/2.3",
+                env!("CARGO_PKG_VERSION")
+            )],
+            server_headers(svc).await
+        );
+    }
+
+    #[tokio::test]
+    async fn the_header_composes_with_other_response_mutations() {
+        let identification = ServerIdentification::new().enable(true);
+        let operation = Plugin::<TestService, (), _>::apply(&identification.plugin(), handler());
+        let svc = tower::util::MapResponseLayer::new(|mut response: http::Response<BoxBody>| {
+            response
+                .headers_mut()
+                .insert("x-mutated", HeaderValue::from_static("yes"));
+            response
+        })
+        .layer(operation);
+
+        let response = svc.oneshot(http::Request::new(empty())).await.unwrap();
+        assert_eq!("hi", response.headers()["x-handler"]);
+        assert_eq!("yes", response.headers()["x-mutated"]);
+        assert!(response.headers()[SERVER].to_str().unwrap().ends_with(" awsJson1_0"));
+    }
+
+    #[tokio::test]
+    async fn unrouted_responses_carry_the_header() {
+        let router: RestRouter<Route<BoxBody>> = std::iter::empty().collect();
+        let svc = ServerIdentification::new()
+            .enable(true)
+            .layer::<RestJson1>()
+            .layer(RoutingService::<_, RestJson1>::new(router));
+
+        let response = svc.oneshot(http::Request::new(empty())).await.unwrap();
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+        assert!(response.headers()[SERVER]
+            .to_str()
+            .unwrap()
+            .starts_with("smithy-rs-server/"));
+    }
+}
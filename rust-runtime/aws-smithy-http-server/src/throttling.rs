@@ -0,0 +1,312 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Throttling responses telling clients when to retry.
+//!
+//! Layers and plugins that reject requests because the service is overloaded (concurrency limits, load
+//! shedding, rate limiting) can use [`ThrottlingResponse`] instead of building a response by hand for
+//! every protocol:
+//!
+//! - Before routing, the protocol of the request isn't known yet. Use
+//!   [`ThrottlingResponse::into_http_response`], which only relies on HTTP semantics: a status code and a
+//!   `Retry-After` header.
+//! - After routing, for example in a [`Plugin`](crate::plugin::Plugin), use the
+//!   [`IntoResponse`] implementation for the protocol to render a `ThrottlingException` clients recognize.
+//! - When the service models its own throttling error, use [`ThrottlingResponse::into_modeled_response`]
+//!   so the error is serialized like any other error returned by a handler.
+//!
+//! ```
+//! use aws_smithy_http_server::protocol::rest_json_1::RestJson1;
+//! use aws_smithy_http_server::response::IntoResponse;
+//! use aws_smithy_http_server::throttling::ThrottlingResponse;
+//! use std::time::Duration;
+//!
+//! let throttled = ThrottlingResponse::too_many_requests(Duration::from_secs(2)).with_scope("account");
+//! let response = IntoResponse::<RestJson1>::into_response(throttled);
+//! assert_eq!(response.status(), 429);
+//! assert_eq!(response.headers()["retry-after"], "2");
+//! ```
+
+use crate::body::{empty, to_boxed, BoxBody};
+use crate::extension::RuntimeErrorExtension;
+use crate::protocol::aws_json_10::AwsJson1_0;
+use crate::protocol::aws_json_11::AwsJson1_1;
+use crate::protocol::rest_json_1::RestJson1;
+use crate::protocol::rest_xml::RestXml;
+use crate::protocol::rpc_v2_cbor::RpcV2Cbor;
+use crate::response::IntoResponse;
+use aws_smithy_json::serialize::JsonObjectWriter;
+use aws_smithy_xml::encode::XmlWriter;
+use http::header::{HeaderValue, CONTENT_TYPE, RETRY_AFTER};
+use http::{HeaderMap, StatusCode};
+use std::time::Duration;
+
+/// Name of the error rendered by the protocol specific [`IntoResponse`] implementations.
+const THROTTLING_EXCEPTION: &str = "ThrottlingException";
+
+/// Header carrying the [scope](ThrottlingResponse::with_scope) of the limit that was hit, if any.
+pub const THROTTLING_SCOPE_HEADER: &str = "x-amzn-throttling-scope";
+
+const INVALID_THROTTLING_RESPONSE_PANIC_MESSAGE: &str = "invalid HTTP response for `ThrottlingResponse`; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues";
+
+/// A request was rejected because of throttling, and the client should retry after some time.
+///
+/// See the [module documentation](crate::throttling) for how to render it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThrottlingResponse {
+    status: StatusCode,
+    retry_after: Duration,
+    scope: Option<String>,
+}
+
+impl ThrottlingResponse {
+    /// The client sent too many requests, and is answered with a `429 Too Many Requests`.
+    pub fn too_many_requests(retry_after: Duration) -> Self {
+        Self {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            retry_after,
+            scope: None,
+        }
+    }
+
+    /// The service is overloaded regardless of who is calling it, and answers with a
+    /// `503 Service Unavailable`.
+    pub fn service_unavailable(retry_after: Duration) -> Self {
+        Self {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            retry_after,
+            scope: None,
+        }
+    }
+
+    /// Sets the scope of the limit that was hit, for example `"account"` or the name of an operation.
+    ///
+    /// The scope is sent in the [`THROTTLING_SCOPE_HEADER`] header and in the error message.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Returns the status code of the response.
+    pub fn status_code(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Returns how long the client should wait before retrying.
+    pub fn retry_after(&self) -> Duration {
+        self.retry_after
+    }
+
+    /// Returns the scope of the limit that was hit, if any.
+    pub fn scope(&self) -> Option<&str> {
+        self.scope.as_deref()
+    }
+
+    /// Renders a response with an empty body, relying on HTTP semantics only.
+    ///
+    /// This is meant for layers running before routing, which can't know the protocol of the request.
+    pub fn into_http_response(self) -> http::Response<BoxBody> {
+        let mut response = http::Response::new(empty());
+        *response.status_mut() = self.status;
+        self.insert_headers(response.headers_mut());
+        response
+    }
+
+    /// Renders `error`, the service's own throttling error, and adds the `Retry-After` header to it.
+    ///
+    /// The status code and body are the ones of the serialized `error`.
+    pub fn into_modeled_response<P, E>(self, error: E) -> http::Response<BoxBody>
+    where
+        E: IntoResponse<P>,
+    {
+        let mut response = error.into_response();
+        self.insert_headers(response.headers_mut());
+        response
+    }
+
+    /// Inserts the `Retry-After` and scope headers, overwriting existing values.
+    pub fn insert_headers(&self, headers: &mut HeaderMap) {
+        headers.insert(RETRY_AFTER, HeaderValue::from(retry_after_secs(self.retry_after)));
+        if let Some(scope) = self
+            .scope
+            .as_deref()
+            .and_then(|scope| HeaderValue::from_str(scope).ok())
+        {
+            headers.insert(THROTTLING_SCOPE_HEADER, scope);
+        }
+    }
+
+    fn message(&self) -> String {
+        let retry_after = retry_after_secs(self.retry_after);
+        match &self.scope {
+            Some(scope) => format!("request throttled ({scope}); retry after {retry_after} seconds"),
+            None => format!("request throttled; retry after {retry_after} seconds"),
+        }
+    }
+
+    fn json_body(&self, error_type: Option<&str>) -> String {
+        let mut out = String::new();
+        let mut object = JsonObjectWriter::new(&mut out);
+        if let Some(error_type) = error_type {
+            object.key("__type").string(error_type);
+        }
+        object.key("message").string(&self.message());
+        object.finish();
+        out
+    }
+
+    fn into_protocol_response(
+        self,
+        content_type: &'static str,
+        body: impl Into<bytes::Bytes>,
+    ) -> http::Response<BoxBody> {
+        let mut response = http::Response::builder()
+            .status(self.status)
+            .header(CONTENT_TYPE, content_type)
+            .extension(RuntimeErrorExtension::new(THROTTLING_EXCEPTION.to_string()))
+            .body(to_boxed(body.into()))
+            .expect(INVALID_THROTTLING_RESPONSE_PANIC_MESSAGE);
+        self.insert_headers(response.headers_mut());
+        response
+    }
+}
+
+/// `Retry-After` only supports whole seconds, so round up to avoid retrying too early.
+fn retry_after_secs(retry_after: Duration) -> u64 {
+    retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0)
+}
+
+impl IntoResponse<RestJson1> for ThrottlingResponse {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let body = self.json_body(None);
+        let mut response = self.into_protocol_response("application/json", body);
+        response
+            .headers_mut()
+            .insert("x-amzn-errortype", HeaderValue::from_static(THROTTLING_EXCEPTION));
+        response
+    }
+}
+
+impl IntoResponse<AwsJson1_0> for ThrottlingResponse {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let body = self.json_body(Some(THROTTLING_EXCEPTION));
+        self.into_protocol_response("application/x-amz-json-1.0", body)
+    }
+}
+
+impl IntoResponse<AwsJson1_1> for ThrottlingResponse {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let body = self.json_body(Some(THROTTLING_EXCEPTION));
+        self.into_protocol_response("application/x-amz-json-1.1", body)
+    }
+}
+
+impl IntoResponse<RestXml> for ThrottlingResponse {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let mut body = String::new();
+        {
+            let mut writer = XmlWriter::new(&mut body);
+            let mut error_response = writer.start_el("ErrorResponse").finish();
+            let mut error = error_response.start_el("Error").finish();
+            error.start_el("Code").finish().data(THROTTLING_EXCEPTION);
+            error.start_el("Message").finish().data(&self.message());
+            error.finish();
+            error_response.finish();
+        }
+        self.into_protocol_response("application/xml", body)
+    }
+}
+
+impl IntoResponse<RpcV2Cbor> for ThrottlingResponse {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let mut encoder = aws_smithy_cbor::Encoder::new(Vec::new());
+        encoder
+            .map(2)
+            .str("__type")
+            .str(THROTTLING_EXCEPTION)
+            .str("message")
+            .str(&self.message());
+        let body = encoder.into_writer();
+        self.into_protocol_response("application/cbor", body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::test_helpers::get_body_as_string;
+
+    #[tokio::test]
+    async fn pre_routing_fallback_uses_http_semantics() {
+        let response = ThrottlingResponse::service_unavailable(Duration::from_millis(1500)).into_http_response();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[RETRY_AFTER], "2");
+        assert!(response.headers().get(CONTENT_TYPE).is_none());
+        assert!(response.headers().get(THROTTLING_SCOPE_HEADER).is_none());
+        assert_eq!(get_body_as_string(response.into_body()).await, "");
+    }
+
+    #[tokio::test]
+    async fn rest_json_1() {
+        let throttled = ThrottlingResponse::too_many_requests(Duration::from_secs(3)).with_scope("account");
+        let response = IntoResponse::<RestJson1>::into_response(throttled);
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RETRY_AFTER], "3");
+        assert_eq!(response.headers()[THROTTLING_SCOPE_HEADER], "account");
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        assert_eq!(response.headers()["x-amzn-errortype"], THROTTLING_EXCEPTION);
+        assert_eq!(
+            response.extensions().get::<RuntimeErrorExtension>().unwrap().as_str(),
+            THROTTLING_EXCEPTION
+        );
+        assert_eq!(
+            get_body_as_string(response.into_body()).await,
+            r#"{"message":"request throttled (account); retry after 3 seconds"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn aws_json_11() {
+        let throttled = ThrottlingResponse::too_many_requests(Duration::from_secs(1));
+        let response = IntoResponse::<AwsJson1_1>::into_response(throttled);
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RETRY_AFTER], "1");
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/x-amz-json-1.1");
+        assert!(response.headers().get("x-amzn-errortype").is_none());
+        assert_eq!(
+            get_body_as_string(response.into_body()).await,
+            r#"{"__type":"ThrottlingException","message":"request throttled; retry after 1 seconds"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn modeled_errors_keep_their_serialization() {
+        struct SlowDown;
+
+        impl IntoResponse<AwsJson1_1> for SlowDown {
+            fn into_response(self) -> http::Response<BoxBody> {
+                http::Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(to_boxed(r#"{"__type":"SlowDown"}"#))
+                    .unwrap()
+            }
+        }
+
+        let response = ThrottlingResponse::too_many_requests(Duration::ZERO)
+            .with_scope("GetItem")
+            .into_modeled_response::<AwsJson1_1, _>(SlowDown);
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.headers()[RETRY_AFTER], "0");
+        assert_eq!(response.headers()[THROTTLING_SCOPE_HEADER], "GetItem");
+        assert_eq!(
+            get_body_as_string(response.into_body()).await,
+            r#"{"__type":"SlowDown"}"#
+        );
+    }
+}
@@ -0,0 +1,279 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Per-operation timeouts for handlers.
+//!
+//! [`TimeoutPlugin`], an HTTP plugin, bounds how long the handler of each operation can take,
+//! according to the [`Timeout`] a [`TimeoutPolicy`] declares for it. When the timeout elapses, the
+//! handler is cancelled and the request is answered with a `504 Gateway Timeout` [`TimeoutError`],
+//! rendered for the protocol of the service. The operation and the elapsed time are recorded in a
+//! `tracing` event.
+//!
+//! Operations with a streaming response use [`Timeout::streaming`] instead, which bounds the time
+//! until the first event of the response is sent and, optionally, the total duration of the
+//! response. Once the response has been sent, a timeout can't change its status anymore, so the
+//! response body is aborted instead.
+//!
+//! # Cancellation
+//!
+//! Handlers are cancelled by dropping their future: whatever the handler is awaiting when the
+//! timeout elapses never completes, and code after that `.await` point never runs. Work that was
+//! spawned onto another task, or that blocks the thread without yielding, keeps running. Handlers
+//! that need to clean up, or that want to give up on a slow downstream call early, can take an
+//! `Option<Extension<Deadline>>` and check how much time they have left with [`Deadline`].
+//!
+//! # Example
+//!
+//! ```
+//! use aws_smithy_http_server::plugin::HttpPlugins;
+//! use aws_smithy_http_server::timeout::{Timeout, TimeoutExt, TimeoutPolicy};
+//! use std::time::Duration;
+//! # use aws_smithy_http_server::{operation::OperationShape, shape_id::ShapeId};
+//! # pub struct GetStorage;
+//! # impl OperationShape for GetStorage {
+//! #     const ID: ShapeId = ShapeId::new("namespace#GetStorage", "namespace", "GetStorage");
+//! #     type Input = ();
+//! #     type Output = ();
+//! #     type Error = ();
+//! # }
+//! # pub struct StreamPokemonRadio;
+//! # impl OperationShape for StreamPokemonRadio {
+//! #     const ID: ShapeId = ShapeId::new("namespace#StreamPokemonRadio", "namespace", "StreamPokemonRadio");
+//! #     type Input = ();
+//! #     type Output = ();
+//! #     type Error = ();
+//! # }
+//!
+//! // Every operation must respond within 5 seconds, except `GetStorage` which gets 30 seconds, and
+//! // `StreamPokemonRadio` which must start streaming within a second but can stream for as long as it wants.
+//! let policy = TimeoutPolicy::new(Timeout::new(Duration::from_secs(5)))
+//!     .operation::<GetStorage>(Timeout::new(Duration::from_secs(30)))
+//!     .operation::<StreamPokemonRadio>(Timeout::streaming(Some(Duration::from_secs(1)), None));
+//! let http_plugins = HttpPlugins::new().timeouts(policy);
+//! ```
+//!
+//! `http_plugins` is then added to the service config with `http_plugin`.
+
+mod policy;
+mod service;
+
+pub use policy::{Timeout, TimeoutExt, TimeoutPlugin, TimeoutPolicy};
+pub use service::TimeoutService;
+
+use crate::body::{to_boxed, BoxBody};
+use crate::extension::RuntimeErrorExtension;
+use crate::protocol::aws_json_10::AwsJson1_0;
+use crate::protocol::aws_json_11::AwsJson1_1;
+use crate::protocol::rest_json_1::RestJson1;
+use crate::protocol::rest_xml::RestXml;
+use crate::protocol::rpc_v2_cbor::RpcV2Cbor;
+use crate::response::IntoResponse;
+use aws_smithy_json::serialize::JsonObjectWriter;
+use aws_smithy_xml::encode::XmlWriter;
+use http::header::{HeaderValue, CONTENT_TYPE};
+use http::StatusCode;
+use std::fmt;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Name of the error rendered by the protocol specific [`IntoResponse`] implementations.
+const TIMEOUT_EXCEPTION: &str = "TimeoutException";
+
+const INVALID_TIMEOUT_RESPONSE_PANIC_MESSAGE: &str = "invalid HTTP response for `TimeoutError`; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues";
+
+/// The deadline by which the handler must respond, inserted into the request extensions by
+/// [`TimeoutService`].
+///
+/// Operations whose [`Timeout`] is [disabled](Timeout::disabled) have no deadline, so handlers
+/// should take it as an `Option<Extension<Deadline>>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deadline {
+    at: Instant,
+}
+
+impl Deadline {
+    pub(crate) fn new(at: Instant) -> Self {
+        Self { at }
+    }
+
+    /// Returns the instant at which the handler is cancelled.
+    pub fn instant(&self) -> Instant {
+        self.at
+    }
+
+    /// Returns how much time is left before the handler is cancelled.
+    pub fn remaining(&self) -> Duration {
+        self.at.saturating_duration_since(Instant::now())
+    }
+
+    /// Returns `true` if the handler is cancelled within `margin`.
+    pub fn is_near(&self, margin: Duration) -> bool {
+        self.remaining() <= margin
+    }
+
+    /// Waits until the handler is `margin` away from being cancelled.
+    ///
+    /// This is meant to be raced against slow work, for example with `tokio::select!`, to give up
+    /// on it while there's still time to respond.
+    pub async fn near(&self, margin: Duration) {
+        let at = self.at.checked_sub(margin).unwrap_or_else(Instant::now);
+        tokio::time::sleep_until(at).await
+    }
+}
+
+/// A handler didn't respond before its [`Timeout`] elapsed.
+///
+/// See the [module documentation](crate::timeout) for when it is returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeoutError {
+    elapsed: Duration,
+}
+
+impl TimeoutError {
+    /// Creates an error for a handler that was cancelled after running for `elapsed`.
+    pub fn new(elapsed: Duration) -> Self {
+        Self { elapsed }
+    }
+
+    /// Returns how long the handler ran before it was cancelled.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the status code of the response.
+    pub fn status_code(&self) -> StatusCode {
+        StatusCode::GATEWAY_TIMEOUT
+    }
+
+    fn message(&self) -> String {
+        format!("operation timed out after {} ms", self.elapsed.as_millis())
+    }
+
+    fn json_body(&self, error_type: Option<&str>) -> String {
+        let mut out = String::new();
+        let mut object = JsonObjectWriter::new(&mut out);
+        if let Some(error_type) = error_type {
+            object.key("__type").string(error_type);
+        }
+        object.key("message").string(&self.message());
+        object.finish();
+        out
+    }
+
+    fn into_protocol_response(
+        self,
+        content_type: &'static str,
+        body: impl Into<bytes::Bytes>,
+    ) -> http::Response<BoxBody> {
+        http::Response::builder()
+            .status(self.status_code())
+            .header(CONTENT_TYPE, content_type)
+            .extension(RuntimeErrorExtension::new(TIMEOUT_EXCEPTION.to_string()))
+            .body(to_boxed(body.into()))
+            .expect(INVALID_TIMEOUT_RESPONSE_PANIC_MESSAGE)
+    }
+}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{TIMEOUT_EXCEPTION}: {}", self.message())
+    }
+}
+
+impl std::error::Error for TimeoutError {}
+
+impl IntoResponse<RestJson1> for TimeoutError {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let body = self.json_body(None);
+        let mut response = self.into_protocol_response("application/json", body);
+        response
+            .headers_mut()
+            .insert("x-amzn-errortype", HeaderValue::from_static(TIMEOUT_EXCEPTION));
+        response
+    }
+}
+
+impl IntoResponse<AwsJson1_0> for TimeoutError {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let body = self.json_body(Some(TIMEOUT_EXCEPTION));
+        self.into_protocol_response("application/x-amz-json-1.0", body)
+    }
+}
+
+impl IntoResponse<AwsJson1_1> for TimeoutError {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let body = self.json_body(Some(TIMEOUT_EXCEPTION));
+        self.into_protocol_response("application/x-amz-json-1.1", body)
+    }
+}
+
+impl IntoResponse<RestXml> for TimeoutError {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let mut body = String::new();
+        {
+            let mut writer = XmlWriter::new(&mut body);
+            let mut error_response = writer.start_el("ErrorResponse").finish();
+            let mut error = error_response.start_el("Error").finish();
+            error.start_el("Code").finish().data(TIMEOUT_EXCEPTION);
+            error.start_el("Message").finish().data(&self.message());
+            error.finish();
+            error_response.finish();
+        }
+        self.into_protocol_response("application/xml", body)
+    }
+}
+
+impl IntoResponse<RpcV2Cbor> for TimeoutError {
+    fn into_response(self) -> http::Response<BoxBody> {
+        let mut encoder = aws_smithy_cbor::Encoder::new(Vec::new());
+        encoder
+            .map(2)
+            .str("__type")
+            .str(TIMEOUT_EXCEPTION)
+            .str("message")
+            .str(&self.message());
+        let body = encoder.into_writer();
+        self.into_protocol_response("application/cbor", body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::test_helpers::get_body_as_string;
+
+    #[tokio::test]
+    async fn rest_json_sets_error_type_header() {
+        let response = IntoResponse::<RestJson1>::into_response(TimeoutError::new(Duration::from_millis(1500)));
+
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(response.headers()["x-amzn-errortype"], TIMEOUT_EXCEPTION);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        assert_eq!(
+            get_body_as_string(response.into_body()).await,
+            r#"{"message":"operation timed out after 1500 ms"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn aws_json_puts_error_type_in_body() {
+        let response = IntoResponse::<AwsJson1_0>::into_response(TimeoutError::new(Duration::from_secs(2)));
+
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(
+            get_body_as_string(response.into_body()).await,
+            r#"{"__type":"TimeoutException","message":"operation timed out after 2000 ms"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn deadline_is_near() {
+        let deadline = Deadline::new(Instant::now() + Duration::from_secs(60));
+        assert!(!deadline.is_near(Duration::from_secs(1)));
+        assert!(deadline.is_near(Duration::from_secs(120)));
+        // The deadline is already within the margin, so waiting for it returns immediately.
+        deadline.near(Duration::from_secs(120)).await;
+    }
+}
@@ -0,0 +1,161 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::TimeoutService;
+use crate::operation::OperationShape;
+use crate::plugin::{HttpMarker, HttpPlugins, Plugin, PluginStack};
+use crate::service::ServiceShape;
+use crate::shape_id::ShapeId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimeoutKind {
+    Disabled,
+    Unary(Duration),
+    Streaming {
+        first_event: Option<Duration>,
+        total: Option<Duration>,
+    },
+}
+
+/// How long the handler of an operation can take, see [`TimeoutPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeout {
+    kind: TimeoutKind,
+}
+
+impl Timeout {
+    /// The handler must respond within `duration`.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            kind: TimeoutKind::Unary(duration),
+        }
+    }
+
+    /// The handler can take as long as it wants.
+    pub fn disabled() -> Self {
+        Self {
+            kind: TimeoutKind::Disabled,
+        }
+    }
+
+    /// The handler streams its response, and must send the first event of the response body within
+    /// `first_event`, and the whole response within `total`. `None` disables either limit.
+    ///
+    /// A handler that hasn't responded when either limit elapses is cancelled like with
+    /// [`Timeout::new`]. A response body that is still streaming when either limit elapses is
+    /// aborted, since its status has already been sent.
+    pub fn streaming(first_event: Option<Duration>, total: Option<Duration>) -> Self {
+        Self {
+            kind: TimeoutKind::Streaming { first_event, total },
+        }
+    }
+
+    /// Returns the limit on the time until the handler responds.
+    pub(super) fn handler(&self) -> Option<Duration> {
+        match self.kind {
+            TimeoutKind::Disabled => None,
+            TimeoutKind::Unary(duration) => Some(duration),
+            TimeoutKind::Streaming { first_event, total } => match (first_event, total) {
+                (Some(first_event), Some(total)) => Some(first_event.min(total)),
+                (first_event, total) => first_event.or(total),
+            },
+        }
+    }
+
+    /// Returns the limits on the time until the first event of the response body is sent, and until
+    /// the whole response body is sent.
+    pub(super) fn body(&self) -> (Option<Duration>, Option<Duration>) {
+        match self.kind {
+            TimeoutKind::Disabled | TimeoutKind::Unary(_) => (None, None),
+            TimeoutKind::Streaming { first_event, total } => (first_event, total),
+        }
+    }
+}
+
+/// The [`Timeout`] of every operation of a service.
+///
+/// Operations without their own timeout use the default timeout.
+#[derive(Debug, Clone)]
+pub struct TimeoutPolicy {
+    default: Timeout,
+    operations: HashMap<ShapeId, Timeout>,
+}
+
+impl TimeoutPolicy {
+    /// Creates a policy where every operation has the `default` timeout.
+    pub fn new(default: Timeout) -> Self {
+        Self {
+            default,
+            operations: HashMap::new(),
+        }
+    }
+
+    /// Sets the timeout of the `Op` operation.
+    pub fn operation<Op>(mut self, timeout: Timeout) -> Self
+    where
+        Op: OperationShape,
+    {
+        self.operations.insert(Op::ID, timeout);
+        self
+    }
+
+    /// Returns the timeout of the operation with the given ID.
+    pub fn timeout(&self, operation: &ShapeId) -> Timeout {
+        self.operations.get(operation).copied().unwrap_or(self.default)
+    }
+}
+
+/// A [`Plugin`] which applies [`TimeoutService`] to every operation, enforcing a [`TimeoutPolicy`].
+#[derive(Debug, Clone)]
+pub struct TimeoutPlugin {
+    policy: Arc<TimeoutPolicy>,
+}
+
+impl TimeoutPlugin {
+    /// Creates a plugin enforcing `policy`.
+    pub fn new(policy: TimeoutPolicy) -> Self {
+        Self {
+            policy: Arc::new(policy),
+        }
+    }
+}
+
+impl<Ser, Op, T> Plugin<Ser, Op, T> for TimeoutPlugin
+where
+    Ser: ServiceShape,
+    Op: OperationShape,
+{
+    type Output = TimeoutService<Ser::Protocol, T>;
+
+    fn apply(&self, inner: T) -> Self::Output {
+        TimeoutService {
+            inner,
+            operation: Op::ID,
+            timeout: self.policy.timeout(&Op::ID),
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl HttpMarker for TimeoutPlugin {}
+
+/// An extension trait for applying [`TimeoutPlugin`].
+pub trait TimeoutExt<CurrentPlugin> {
+    /// Cancels handlers that take longer than the timeout `policy` declares for their operation.
+    ///
+    /// See the [module documentation](crate::timeout) for details.
+    fn timeouts(self, policy: TimeoutPolicy) -> HttpPlugins<PluginStack<TimeoutPlugin, CurrentPlugin>>;
+}
+
+impl<CurrentPlugin> TimeoutExt<CurrentPlugin> for HttpPlugins<CurrentPlugin> {
+    fn timeouts(self, policy: TimeoutPolicy) -> HttpPlugins<PluginStack<TimeoutPlugin, CurrentPlugin>> {
+        self.push(TimeoutPlugin::new(policy))
+    }
+}
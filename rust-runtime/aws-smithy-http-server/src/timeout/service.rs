@@ -0,0 +1,345 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http_body::Body as HttpBody;
+use pin_project_lite::pin_project;
+use tokio::time::{Instant, Sleep};
+use tower::Service;
+
+use super::{Deadline, Timeout, TimeoutError};
+use crate::body::BoxBody;
+use crate::response::IntoResponse;
+use crate::shape_id::ShapeId;
+
+/// A [`Service`] cancelling the handler of an operation when its [`Timeout`] elapses.
+///
+/// See the [module documentation](crate::timeout) for details.
+pub struct TimeoutService<Protocol, S> {
+    pub(super) inner: S,
+    pub(super) operation: ShapeId,
+    pub(super) timeout: Timeout,
+    pub(super) _protocol: PhantomData<fn() -> Protocol>,
+}
+
+impl<Protocol, S> Clone for TimeoutService<Protocol, S>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            operation: self.operation.clone(),
+            timeout: self.timeout,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<Protocol, S> std::fmt::Debug for TimeoutService<Protocol, S>
+where
+    S: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimeoutService")
+            .field("inner", &self.inner)
+            .field("operation", &self.operation)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl<Protocol, S, B> Service<http::Request<B>> for TimeoutService<Protocol, S>
+where
+    S: Service<http::Request<B>, Response = http::Response<BoxBody>>,
+    S::Future: Send + 'static,
+    TimeoutError: IntoResponse<Protocol>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        let start = Instant::now();
+        let deadline = self.timeout.handler().map(|timeout| start + timeout);
+        if let Some(deadline) = deadline {
+            req.extensions_mut().insert(Deadline::new(deadline));
+        }
+        let handler = self.inner.call(req);
+        let operation = self.operation.clone();
+        let (first_event, total) = self.timeout.body();
+
+        Box::pin(async move {
+            let response = match deadline {
+                None => handler.await?,
+                Some(deadline) => match tokio::time::timeout_at(deadline, handler).await {
+                    Ok(response) => response?,
+                    Err(_) => {
+                        let elapsed = start.elapsed();
+                        tracing::warn!(
+                            operation = %operation.absolute(),
+                            elapsed_ms = elapsed.as_millis() as u64,
+                            "handler timed out"
+                        );
+                        return Ok(TimeoutError::new(elapsed).into_response());
+                    }
+                },
+            };
+            if first_event.is_none() && total.is_none() {
+                return Ok(response);
+            }
+            Ok(response.map(|body| {
+                TimeoutBody {
+                    inner: body,
+                    first_event: first_event.map(|timeout| tokio::time::sleep_until(start + timeout)),
+                    total: total.map(|timeout| tokio::time::sleep_until(start + timeout)),
+                    operation,
+                    start,
+                }
+                .boxed_unsync()
+            }))
+        })
+    }
+}
+
+pin_project! {
+    /// A streaming response body that is aborted if its first event or its end take too long.
+    struct TimeoutBody {
+        #[pin]
+        inner: BoxBody,
+        #[pin]
+        first_event: Option<Sleep>,
+        #[pin]
+        total: Option<Sleep>,
+        operation: ShapeId,
+        start: Instant,
+    }
+}
+
+impl TimeoutBody {
+    fn expired(operation: &ShapeId, start: Instant, limit: &str) -> crate::Error {
+        let elapsed = start.elapsed();
+        tracing::warn!(
+            operation = %operation.absolute(),
+            elapsed_ms = elapsed.as_millis() as u64,
+            "response stream timed out waiting for its {limit}; aborting it"
+        );
+        crate::Error::new(TimeoutError::new(elapsed))
+    }
+}
+
+fn is_elapsed(sleep: Pin<&mut Option<Sleep>>, cx: &mut Context<'_>) -> bool {
+    sleep.as_pin_mut().map_or(false, |sleep| sleep.poll(cx).is_ready())
+}
+
+impl HttpBody for TimeoutBody {
+    type Data = Bytes;
+    type Error = crate::Error;
+
+    fn poll_data(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let mut this = self.project();
+        if is_elapsed(this.total.as_mut(), cx) {
+            return Poll::Ready(Some(Err(Self::expired(this.operation, *this.start, "end"))));
+        }
+        if is_elapsed(this.first_event.as_mut(), cx) {
+            return Poll::Ready(Some(Err(Self::expired(this.operation, *this.start, "first event"))));
+        }
+        let data = this.inner.poll_data(cx);
+        if let Poll::Ready(Some(Ok(_))) = data {
+            this.first_event.set(None);
+        }
+        data
+    }
+
+    fn poll_trailers(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        let mut this = self.project();
+        if is_elapsed(this.total.as_mut(), cx) {
+            return Poll::Ready(Err(Self::expired(this.operation, *this.start, "end")));
+        }
+        this.inner.poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::time::Duration;
+
+    use http::StatusCode;
+    use tokio::net::TcpStream;
+    use tower::{service_fn, ServiceExt};
+
+    use super::*;
+    use crate::body::{boxed, to_boxed};
+    use crate::operation::OperationShape;
+    use crate::plugin::Plugin;
+    use crate::protocol::rest_json_1::RestJson1;
+    use crate::protocol::test_helpers::get_body_as_string;
+    use crate::service::ServiceShape;
+    use crate::test_helpers::send_request;
+    use crate::timeout::{TimeoutPlugin, TimeoutPolicy};
+
+    struct PokemonService;
+
+    impl ServiceShape for PokemonService {
+        const ID: ShapeId = ShapeId::new("com.example#PokemonService", "com.example", "PokemonService");
+        const VERSION: Option<&'static str> = None;
+        type Protocol = RestJson1;
+        type Operations = ();
+    }
+
+    macro_rules! operation {
+        ($name:ident) => {
+            struct $name;
+
+            impl OperationShape for $name {
+                const ID: ShapeId = ShapeId::new(
+                    concat!("com.example#", stringify!($name)),
+                    "com.example",
+                    stringify!($name),
+                );
+                type Input = ();
+                type Output = ();
+                type Error = ();
+            }
+        };
+    }
+
+    operation!(GetSpecies);
+    operation!(GetStorage);
+    operation!(StreamRadio);
+
+    fn policy() -> TimeoutPolicy {
+        TimeoutPolicy::new(Timeout::new(Duration::from_millis(50)))
+            .operation::<GetStorage>(Timeout::disabled())
+            .operation::<StreamRadio>(Timeout::streaming(
+                Some(Duration::from_millis(50)),
+                Some(Duration::from_millis(200)),
+            ))
+    }
+
+    /// A handler that responds after sleeping for the duration in its path, in milliseconds, and
+    /// answers with whether it was given a deadline.
+    async fn sleepy_handler(req: http::Request<hyper::Body>) -> Result<http::Response<BoxBody>, Infallible> {
+        let millis: u64 = req.uri().path().trim_start_matches('/').parse().unwrap_or(0);
+        tokio::time::sleep(Duration::from_millis(millis)).await;
+        let body = match req.extensions().get::<Deadline>() {
+            Some(_) => "deadline",
+            None => "no deadline",
+        };
+        Ok(http::Response::new(to_boxed(body)))
+    }
+
+    async fn call<Op: OperationShape>(path: &str) -> (StatusCode, String) {
+        let operation =
+            Plugin::<PokemonService, Op, _>::apply(&TimeoutPlugin::new(policy()), service_fn(sleepy_handler));
+        let request = http::Request::get(path).body(hyper::Body::empty()).unwrap();
+        let response = operation.oneshot(request).await.unwrap();
+        let status = response.status();
+        (status, get_body_as_string(response.into_body()).await)
+    }
+
+    #[tokio::test]
+    async fn fast_handlers_see_their_deadline() {
+        assert_eq!((StatusCode::OK, "deadline".to_string()), call::<GetSpecies>("/0").await);
+    }
+
+    #[tokio::test]
+    async fn slow_handlers_are_cancelled() {
+        let start = Instant::now();
+        let (status, body) = call::<GetSpecies>("/10000").await;
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(StatusCode::GATEWAY_TIMEOUT, status);
+        assert!(body.starts_with(r#"{"message":"operation timed out after "#), "{body}");
+    }
+
+    #[tokio::test]
+    async fn operations_can_disable_their_timeout() {
+        assert_eq!(
+            (StatusCode::OK, "no deadline".to_string()),
+            call::<GetStorage>("/100").await
+        );
+    }
+
+    /// Answers with a streaming body whose events are sent every `interval`.
+    async fn stream(first_event: Duration, interval: Duration, events: usize) -> Result<Bytes, crate::Error> {
+        let handler = service_fn(move |_req: http::Request<()>| async move {
+            let (mut sender, body) = hyper::Body::channel();
+            tokio::spawn(async move {
+                tokio::time::sleep(first_event).await;
+                for _ in 0..events {
+                    if sender.send_data(Bytes::from_static(b"event;")).await.is_err() {
+                        return;
+                    }
+                    tokio::time::sleep(interval).await;
+                }
+            });
+            Ok::<_, Infallible>(http::Response::new(boxed(body)))
+        });
+        let operation = Plugin::<PokemonService, StreamRadio, _>::apply(&TimeoutPlugin::new(policy()), handler);
+        let response = operation.oneshot(http::Request::new(())).await.unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        hyper::body::to_bytes(response.into_body()).await
+    }
+
+    #[tokio::test]
+    async fn streaming_responses_within_their_limits_are_sent() {
+        let body = stream(Duration::ZERO, Duration::from_millis(10), 3).await.unwrap();
+        assert_eq!(&body[..], b"event;event;event;");
+    }
+
+    #[tokio::test]
+    async fn streaming_responses_are_aborted_without_a_first_event() {
+        let error = stream(Duration::from_secs(10), Duration::ZERO, 1).await.unwrap_err();
+        assert!(error.to_string().contains("timed out"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn streaming_responses_are_aborted_when_too_long() {
+        let error = stream(Duration::ZERO, Duration::from_millis(50), 100)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("timed out"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn connection_remains_usable_after_a_timeout() {
+        let operation =
+            Plugin::<PokemonService, GetSpecies, _>::apply(&TimeoutPlugin::new(policy()), service_fn(sleepy_handler));
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = hyper::Server::from_tcp(listener)
+            .unwrap()
+            .serve(tower::make::Shared::new(operation));
+        tokio::spawn(server);
+
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        let start = Instant::now();
+        let response = send_request(&mut stream, "/10000").await;
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(response.starts_with("HTTP/1.1 504"), "{response}");
+
+        let response = send_request(&mut stream, "/0").await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.ends_with("deadline"), "{response}");
+    }
+}
@@ -0,0 +1,75 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_http::header::{
+    decode_base64_into, headers_for_prefix, one_or_none, prefixed_headers, total_header_bytes,
+};
+use aws_smithy_runtime_api::http::Headers;
+use aws_smithy_types::base64;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::collections::HashMap;
+
+/// A response with 500 base64 encoded metadata headers, of about 1 KB each
+fn metadata_headers() -> Headers {
+    let value = base64::encode("metadata ".repeat(80));
+    let mut headers = Headers::new();
+    for i in 0..500 {
+        headers.insert(format!("x-amz-meta-key-{i}"), value.clone());
+    }
+    headers
+}
+
+fn bench_prefix_headers(c: &mut Criterion) {
+    let headers = metadata_headers();
+    let mut group = c.benchmark_group("Prefix headers");
+
+    group.bench_function("headers_for_prefix", |b| {
+        b.iter(|| {
+            headers_for_prefix(headers.iter().map(|(name, _)| name), "x-amz-meta-")
+                .map(|(key, name)| {
+                    one_or_none::<String>(headers.get_all(name))
+                        .map(|value| (key.to_string(), value.unwrap()))
+                })
+                .collect::<Result<HashMap<_, _>, _>>()
+                .unwrap()
+        })
+    });
+    group.bench_function("prefixed_headers", |b| {
+        b.iter(|| {
+            prefixed_headers(&headers, "x-amz-meta-")
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<HashMap<_, _>>()
+        })
+    });
+    group.finish()
+}
+
+fn bench_base64_headers(c: &mut Criterion) {
+    let headers = metadata_headers();
+    let mut group = c.benchmark_group("Base64 headers");
+
+    group.bench_function("decode", |b| {
+        b.iter(|| {
+            prefixed_headers(&headers, "x-amz-meta-")
+                .map(|(_, value)| base64::decode(value).unwrap().len())
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("decode_base64_into", |b| {
+        let mut buf = Vec::new();
+        b.iter(|| {
+            prefixed_headers(&headers, "x-amz-meta-")
+                .map(|(_, value)| decode_base64_into(value, &mut buf).unwrap().len())
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("total_header_bytes", |b| {
+        b.iter(|| total_header_bytes(&headers))
+    });
+    group.finish()
+}
+
+criterion_group!(benches, bench_prefix_headers, bench_base64_headers);
+criterion_main!(benches);
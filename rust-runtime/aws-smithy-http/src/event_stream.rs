@@ -14,7 +14,7 @@ mod sender;
 pub type BoxError = Box<dyn StdError + Send + Sync + 'static>;
 
 #[doc(inline)]
-pub use sender::{EventStreamSender, MessageStreamAdapter, MessageStreamError};
+pub use sender::{EventStreamSender, MessageStreamAdapter, MessageStreamError, SendError, Sender};
 
 #[doc(inline)]
-pub use receiver::{Receiver, ReceiverError};
+pub use receiver::{FromReceiver, Receiver, ReceiverError};
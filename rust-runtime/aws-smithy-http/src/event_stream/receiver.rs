@@ -3,8 +3,9 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use aws_smithy_eventstream::error::Error as EventStreamError;
 use aws_smithy_eventstream::frame::{
-    DecodedFrame, MessageFrameDecoder, UnmarshallMessage, UnmarshalledMessage,
+    write_message_to, DecodedFrame, MessageFrameDecoder, UnmarshallMessage, UnmarshalledMessage,
 };
 use aws_smithy_runtime_api::client::result::{ConnectorError, SdkError};
 use aws_smithy_types::body::SdkBody;
@@ -12,10 +13,12 @@ use aws_smithy_types::event_stream::{Message, RawMessage};
 use bytes::Buf;
 use bytes::Bytes;
 use bytes_utils::SegmentedBuf;
+use std::collections::VecDeque;
 use std::error::Error as StdError;
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem;
+use std::sync::Mutex;
 use tracing::trace;
 
 /// Wrapper around SegmentedBuf that tracks the state of the stream.
@@ -108,6 +111,44 @@ impl fmt::Display for ReceiverError {
 
 impl StdError for ReceiverError {}
 
+/// Operation outputs that can be created from an event stream [`Receiver`].
+///
+/// Generated clients implement this for outputs with an event stream member when the `test-util`
+/// feature is enabled, so that tests can return canned events with [`Receiver::from_events`].
+pub trait FromReceiver<T, E> {
+    /// Creates the output with `receiver` as its event stream.
+    fn from_receiver(receiver: Receiver<T, E>) -> Self;
+}
+
+/// Unmarshaller that ignores the message contents and returns canned events in order.
+struct CannedEvents<T, E> {
+    events: Mutex<VecDeque<Result<T, E>>>,
+}
+
+impl<T, E> fmt::Debug for CannedEvents<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CannedEvents").finish_non_exhaustive()
+    }
+}
+
+impl<T, E> UnmarshallMessage for CannedEvents<T, E> {
+    type Output = T;
+    type Error = E;
+
+    fn unmarshall(
+        &self,
+        _message: &Message,
+    ) -> Result<UnmarshalledMessage<Self::Output, Self::Error>, EventStreamError> {
+        match self.events.lock().unwrap().pop_front() {
+            Some(Ok(event)) => Ok(UnmarshalledMessage::Event(event)),
+            Some(Err(err)) => Ok(UnmarshalledMessage::Error(err)),
+            None => Err(EventStreamError::unmarshalling(
+                "more messages than canned events; this is a bug",
+            )),
+        }
+    }
+}
+
 /// Receives Smithy-modeled messages out of an Event Stream.
 #[derive(Debug)]
 pub struct Receiver<T, E> {
@@ -139,6 +180,28 @@ impl<T, E> Receiver<T, E> {
         }
     }
 
+    /// Creates a `Receiver` that yields `events` in order and then ends, for use in tests.
+    ///
+    /// An `Err` is returned as a service error, and terminates the stream like a modeled exception
+    /// sent by a service would.
+    pub fn from_events(events: Vec<Result<T, E>>) -> Self
+    where
+        T: Send + 'static,
+        E: Send + 'static,
+    {
+        let mut body = Vec::new();
+        for _ in &events {
+            write_message_to(&Message::new(Bytes::new()), &mut body)
+                .expect("an empty message can always be written");
+        }
+        Self::new(
+            CannedEvents {
+                events: Mutex::new(events.into()),
+            },
+            SdkBody::from(body),
+        )
+    }
+
     fn unmarshall(&self, message: Message) -> Result<Option<T>, SdkError<E, RawMessage>> {
         match self.unmarshaller.unmarshall(&message) {
             Ok(unmarshalled) => match unmarshalled {
@@ -533,6 +596,42 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn receive_canned_events() {
+        let mut receiver = Receiver::<TestMessage, FakeError>::from_events(vec![
+            Ok(TestMessage("one".into())),
+            Ok(TestMessage("two".into())),
+        ]);
+        assert!(receiver.try_recv_initial().await.unwrap().is_none());
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        assert_eq!(
+            TestMessage("two".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        assert_eq!(None, receiver.recv().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn canned_errors_terminate_the_stream() {
+        let mut receiver = Receiver::<TestMessage, FakeError>::from_events(vec![
+            Ok(TestMessage("one".into())),
+            Err(FakeError),
+            Ok(TestMessage("unreachable".into())),
+        ]);
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        assert!(matches!(
+            receiver.recv().await,
+            Err(SdkError::ServiceError(_))
+        ));
+        assert_eq!(None, receiver.recv().await.unwrap());
+    }
+
     fn assert_send_and_sync<T: Send + Sync>() {}
 
     #[tokio::test]
@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use aws_smithy_async::rt::sleep::{default_async_sleep, AsyncSleep, SharedAsyncSleep, Sleep};
 use aws_smithy_eventstream::frame::{write_message_to, MarshallMessage, SignMessage};
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_smithy_types::error::ErrorMetadata;
@@ -11,14 +12,68 @@ use futures_core::Stream;
 use std::error::Error as StdError;
 use std::fmt;
 use std::fmt::Debug;
+use std::future::Future;
 use std::marker::PhantomData;
+use std::mem;
 use std::pin::Pin;
 use std::task::{Context, Poll};
-use tracing::trace;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{trace, warn};
+
+/// An item of the input stream of an [`EventStreamSender`].
+enum Input<T, E> {
+    Event(Result<T, E>),
+    /// A request to flush the events that came before, acknowledged once they're handed to the transport.
+    Flush(oneshot::Sender<()>),
+}
+
+/// Adapts a stream of events to a stream of [`Input`]s.
+struct Events<S: ?Sized>(Pin<Box<S>>);
+
+impl<T, E, S> Stream for Events<S>
+where
+    S: Stream<Item = Result<T, E>> + ?Sized,
+{
+    type Item = Input<T, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0
+            .as_mut()
+            .poll_next(cx)
+            .map(|event| event.map(Input::Event))
+    }
+}
+
+/// Adapts the receiving half of an [`EventStreamSender::channel`] to a stream of [`Input`]s.
+struct ChannelInput<T, E>(mpsc::Receiver<Input<T, E>>);
+
+impl<T, E> Stream for ChannelInput<T, E> {
+    type Item = Input<T, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.poll_recv(cx)
+    }
+}
 
 /// Input type for Event Streams.
+///
+/// # Flushing
+///
+/// By default, every event is encoded into its own frame, and handed to the transport as soon as the
+/// event stream yields it. Throughput-sensitive streams can coalesce events with [`batch`](Self::batch)
+/// instead, trading latency for fewer, larger writes.
+///
+/// # Backpressure
+///
+/// Events are only pulled from the event stream when the transport is ready to send more data. When the
+/// peer stops reading, for example because its HTTP/2 flow control window is exhausted, no more events
+/// are pulled, and a [`channel`](Self::channel) fills up until [`Sender::send`] waits for room, rather
+/// than buffering events without bounds.
 pub struct EventStreamSender<T, E> {
-    input_stream: Pin<Box<dyn Stream<Item = Result<T, E>> + Send + Sync>>,
+    input_stream: Pin<Box<dyn Stream<Item = Input<T, E>> + Send + Sync>>,
+    batch: Option<(usize, Duration)>,
+    sleep_impl: Option<SharedAsyncSleep>,
 }
 
 impl<T, E> Debug for EventStreamSender<T, E> {
@@ -29,6 +84,50 @@ impl<T, E> Debug for EventStreamSender<T, E> {
     }
 }
 
+impl<T, E> EventStreamSender<T, E>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+{
+    /// Creates an event stream fed by a [`Sender`], with room for `buffer` events that haven't been
+    /// handed to the transport yet.
+    ///
+    /// The event stream ends when every [`Sender`] is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is zero.
+    pub fn channel(buffer: usize) -> (Sender<T, E>, Self) {
+        let (tx, rx) = mpsc::channel(buffer);
+        (
+            Sender { chan: tx },
+            EventStreamSender {
+                input_stream: Box::pin(ChannelInput(rx)),
+                batch: None,
+                sleep_impl: None,
+            },
+        )
+    }
+}
+
+impl<T, E> EventStreamSender<T, E> {
+    /// Coalesces events into a single write to the transport, which is made when `max_events` events
+    /// are waiting, when the oldest waiting event is `max_delay` old, or when the event stream is flushed.
+    ///
+    /// The delay is measured with the [`sleep_impl`](Self::sleep_impl), or the default sleep
+    /// implementation of the `rt-tokio` feature. Without either, events are written immediately.
+    pub fn batch(mut self, max_events: usize, max_delay: Duration) -> Self {
+        self.batch = Some((max_events.max(1), max_delay));
+        self
+    }
+
+    /// Sets the sleep implementation used to bound the delay of a [`batch`](Self::batch).
+    pub fn sleep_impl(mut self, sleep_impl: impl AsyncSleep + 'static) -> Self {
+        self.sleep_impl = Some(SharedAsyncSleep::new(sleep_impl));
+        self
+    }
+}
+
 impl<T, E: StdError + Send + Sync + 'static> EventStreamSender<T, E> {
     #[doc(hidden)]
     pub fn into_body_stream(
@@ -37,7 +136,27 @@ impl<T, E: StdError + Send + Sync + 'static> EventStreamSender<T, E> {
         error_marshaller: impl MarshallMessage<Input = E> + Send + Sync + 'static,
         signer: impl SignMessage + Send + Sync + 'static,
     ) -> MessageStreamAdapter<T, E> {
-        MessageStreamAdapter::new(marshaller, error_marshaller, signer, self.input_stream)
+        let mut adapter = MessageStreamAdapter::from_input(
+            marshaller,
+            error_marshaller,
+            signer,
+            self.input_stream,
+        );
+        if let Some((max_events, max_delay)) = self.batch {
+            match self.sleep_impl.or_else(default_async_sleep) {
+                Some(sleep_impl) => {
+                    adapter.batch = Some(Batch {
+                        max_events,
+                        max_delay,
+                        sleep_impl,
+                    })
+                }
+                None => warn!(
+                    "event stream batching was requested without a sleep implementation; events will be written immediately"
+                ),
+            }
+        }
+        adapter
     }
 }
 
@@ -47,11 +166,76 @@ where
 {
     fn from(stream: S) -> Self {
         EventStreamSender {
-            input_stream: Box::pin(stream),
+            input_stream: Box::pin(Events(Box::pin(stream))),
+            batch: None,
+            sleep_impl: None,
+        }
+    }
+}
+
+/// Sends events into an event stream created with [`EventStreamSender::channel`].
+pub struct Sender<T, E> {
+    chan: mpsc::Sender<Input<T, E>>,
+}
+
+impl<T, E> Clone for Sender<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            chan: self.chan.clone(),
         }
     }
 }
 
+impl<T, E> Debug for Sender<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name_t = std::any::type_name::<T>();
+        let name_e = std::any::type_name::<E>();
+        write!(f, "Sender<{name_t}, {name_e}>")
+    }
+}
+
+impl<T, E> Sender<T, E> {
+    /// Sends an event, waiting for room in the channel if the transport isn't keeping up.
+    pub async fn send(&self, event: T) -> Result<(), SendError> {
+        self.send_input(Input::Event(Ok(event))).await
+    }
+
+    /// Sends a modeled error of the event stream.
+    pub async fn send_error(&self, error: E) -> Result<(), SendError> {
+        self.send_input(Input::Event(Err(error))).await
+    }
+
+    /// Waits until every event sent before has been handed to the transport.
+    ///
+    /// Events that are waiting in a [batch](EventStreamSender::batch) are written right away.
+    pub async fn flush(&self) -> Result<(), SendError> {
+        let (tx, rx) = oneshot::channel();
+        self.send_input(Input::Flush(tx)).await?;
+        rx.await.map_err(|_| SendError { _private: () })
+    }
+
+    async fn send_input(&self, input: Input<T, E>) -> Result<(), SendError> {
+        self.chan
+            .send(input)
+            .await
+            .map_err(|_| SendError { _private: () })
+    }
+}
+
+/// The event stream of a [`Sender`] was closed, for example because the connection was closed.
+#[derive(Debug)]
+pub struct SendError {
+    _private: (),
+}
+
+impl fmt::Display for SendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the event stream was closed")
+    }
+}
+
+impl StdError for SendError {}
+
 /// An error that occurs within a message stream.
 #[derive(Debug)]
 pub struct MessageStreamError {
@@ -104,6 +288,15 @@ impl fmt::Display for MessageStreamError {
     }
 }
 
+/// How events are coalesced by a [`MessageStreamAdapter`].
+struct Batch {
+    max_events: usize,
+    max_delay: Duration,
+    sleep_impl: SharedAsyncSleep,
+}
+
+type AdapterError<E> = SdkError<E, aws_smithy_runtime_api::client::orchestrator::HttpResponse>;
+
 /// Adapts a `Stream<SmithyMessageType>` to a signed `Stream<Bytes>` by using the provided
 /// message marshaller and signer implementations.
 ///
@@ -114,7 +307,17 @@ pub struct MessageStreamAdapter<T, E: StdError + Send + Sync + 'static> {
     marshaller: Box<dyn MarshallMessage<Input = T> + Send + Sync>,
     error_marshaller: Box<dyn MarshallMessage<Input = E> + Send + Sync>,
     signer: Box<dyn SignMessage + Send + Sync>,
-    stream: Pin<Box<dyn Stream<Item = Result<T, E>> + Send>>,
+    stream: Pin<Box<dyn Stream<Item = Input<T, E>> + Send>>,
+    batch: Option<Batch>,
+    // Frames waiting to be written as one batch, and how many there are
+    batched: Vec<u8>,
+    batched_events: usize,
+    batch_deadline: Option<Sleep>,
+    // Flushes to acknowledge when the batched frames are written
+    flushes: Vec<oneshot::Sender<()>>,
+    // An error to yield once the batched frames before it are written
+    deferred_error: Option<AdapterError<E>>,
+    stream_ended: bool,
     end_signal_sent: bool,
     _phantom: PhantomData<E>,
 }
@@ -128,66 +331,152 @@ impl<T, E: StdError + Send + Sync + 'static> MessageStreamAdapter<T, E> {
         error_marshaller: impl MarshallMessage<Input = E> + Send + Sync + 'static,
         signer: impl SignMessage + Send + Sync + 'static,
         stream: Pin<Box<dyn Stream<Item = Result<T, E>> + Send>>,
+    ) -> Self
+    where
+        T: 'static,
+    {
+        Self::from_input(
+            marshaller,
+            error_marshaller,
+            signer,
+            Box::pin(Events(stream)),
+        )
+    }
+
+    fn from_input(
+        marshaller: impl MarshallMessage<Input = T> + Send + Sync + 'static,
+        error_marshaller: impl MarshallMessage<Input = E> + Send + Sync + 'static,
+        signer: impl SignMessage + Send + Sync + 'static,
+        stream: Pin<Box<dyn Stream<Item = Input<T, E>> + Send>>,
     ) -> Self {
         MessageStreamAdapter {
             marshaller: Box::new(marshaller),
             error_marshaller: Box::new(error_marshaller),
             signer: Box::new(signer),
             stream,
+            batch: None,
+            batched: Vec::new(),
+            batched_events: 0,
+            batch_deadline: None,
+            flushes: Vec::new(),
+            deferred_error: None,
+            stream_ended: false,
             end_signal_sent: false,
             _phantom: Default::default(),
         }
     }
+
+    /// Marshalls, signs, and encodes an event into a frame.
+    #[allow(clippy::result_large_err)]
+    fn write_event(&mut self, event: Result<T, E>) -> Result<Vec<u8>, AdapterError<E>> {
+        let message = match event {
+            Ok(message) => self
+                .marshaller
+                .marshall(message)
+                .map_err(SdkError::construction_failure)?,
+            Err(message) => self
+                .error_marshaller
+                .marshall(message)
+                .map_err(SdkError::construction_failure)?,
+        };
+
+        trace!(unsigned_message = ?message, "signing event stream message");
+        let message = self
+            .signer
+            .sign(message)
+            .map_err(SdkError::construction_failure)?;
+
+        let mut buffer = Vec::new();
+        write_message_to(&message, &mut buffer).map_err(SdkError::construction_failure)?;
+        trace!(signed_message = ?buffer, "sending signed event stream message");
+        Ok(buffer)
+    }
+
+    /// Hands the batched frames to the transport, and acknowledges the flushes waiting for them.
+    fn take_batch(&mut self) -> Poll<Option<Result<Bytes, AdapterError<E>>>> {
+        self.batched_events = 0;
+        self.batch_deadline = None;
+        for flush in self.flushes.drain(..) {
+            let _ = flush.send(());
+        }
+        Poll::Ready(Some(Ok(Bytes::from(mem::take(&mut self.batched)))))
+    }
+
+    fn poll_end_signal(&mut self) -> Poll<Option<Result<Bytes, AdapterError<E>>>> {
+        if self.end_signal_sent {
+            return Poll::Ready(None);
+        }
+        self.end_signal_sent = true;
+        let mut buffer = Vec::new();
+        match self.signer.sign_empty() {
+            Some(sign) => {
+                let message = sign.map_err(SdkError::construction_failure)?;
+                write_message_to(&message, &mut buffer).map_err(SdkError::construction_failure)?;
+                trace!(signed_message = ?buffer, "sending signed empty message to terminate the event stream");
+                Poll::Ready(Some(Ok(Bytes::from(buffer))))
+            }
+            None => Poll::Ready(None),
+        }
+    }
 }
 
 impl<T, E: StdError + Send + Sync + 'static> Stream for MessageStreamAdapter<T, E> {
-    type Item =
-        Result<Bytes, SdkError<E, aws_smithy_runtime_api::client::orchestrator::HttpResponse>>;
+    type Item = Result<Bytes, AdapterError<E>>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        match self.stream.as_mut().poll_next(cx) {
-            Poll::Ready(message_option) => {
-                if let Some(message_result) = message_option {
-                    let message = match message_result {
-                        Ok(message) => self
-                            .marshaller
-                            .marshall(message)
-                            .map_err(SdkError::construction_failure)?,
-                        Err(message) => self
-                            .error_marshaller
-                            .marshall(message)
-                            .map_err(SdkError::construction_failure)?,
+        let this = &mut *self;
+        if let Some(err) = this.deferred_error.take() {
+            return Poll::Ready(Some(Err(err)));
+        }
+        loop {
+            if this.stream_ended {
+                if this.batched_events > 0 {
+                    return this.take_batch();
+                }
+                return this.poll_end_signal();
+            }
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Input::Event(event))) => {
+                    let frame = match this.write_event(event) {
+                        Ok(frame) => frame,
+                        Err(err) if this.batched_events > 0 => {
+                            this.deferred_error = Some(err);
+                            return this.take_batch();
+                        }
+                        Err(err) => return Poll::Ready(Some(Err(err))),
                     };
-
-                    trace!(unsigned_message = ?message, "signing event stream message");
-                    let message = self
-                        .signer
-                        .sign(message)
-                        .map_err(SdkError::construction_failure)?;
-
-                    let mut buffer = Vec::new();
-                    write_message_to(&message, &mut buffer)
-                        .map_err(SdkError::construction_failure)?;
-                    trace!(signed_message = ?buffer, "sending signed event stream message");
-                    Poll::Ready(Some(Ok(Bytes::from(buffer))))
-                } else if !self.end_signal_sent {
-                    self.end_signal_sent = true;
-                    let mut buffer = Vec::new();
-                    match self.signer.sign_empty() {
-                        Some(sign) => {
-                            let message = sign.map_err(SdkError::construction_failure)?;
-                            write_message_to(&message, &mut buffer)
-                                .map_err(SdkError::construction_failure)?;
-                            trace!(signed_message = ?buffer, "sending signed empty message to terminate the event stream");
-                            Poll::Ready(Some(Ok(Bytes::from(buffer))))
+                    let Some(batch) = &this.batch else {
+                        return Poll::Ready(Some(Ok(Bytes::from(frame))));
+                    };
+                    this.batched.extend_from_slice(&frame);
+                    this.batched_events += 1;
+                    if this.batched_events >= batch.max_events {
+                        return this.take_batch();
+                    }
+                    if this.batch_deadline.is_none() {
+                        this.batch_deadline = Some(batch.sleep_impl.sleep(batch.max_delay));
+                    }
+                }
+                Poll::Ready(Some(Input::Flush(flush))) => {
+                    this.flushes.push(flush);
+                    if this.batched_events > 0 {
+                        return this.take_batch();
+                    }
+                    // Every event before the flush has already been written
+                    for flush in this.flushes.drain(..) {
+                        let _ = flush.send(());
+                    }
+                }
+                Poll::Ready(None) => this.stream_ended = true,
+                Poll::Pending => {
+                    if let Some(deadline) = this.batch_deadline.as_mut() {
+                        if Pin::new(deadline).poll(cx).is_ready() {
+                            return this.take_batch();
                         }
-                        None => Poll::Ready(None),
                     }
-                } else {
-                    Poll::Ready(None)
+                    return Poll::Pending;
                 }
             }
-            Poll::Pending => Poll::Pending,
         }
     }
 }
@@ -197,6 +486,7 @@ mod tests {
     use super::MarshallMessage;
     use crate::event_stream::{EventStreamSender, MessageStreamAdapter};
     use async_stream::stream;
+    use aws_smithy_async::rt::sleep::TokioSleep;
     use aws_smithy_eventstream::error::Error as EventStreamError;
     use aws_smithy_eventstream::frame::{
         read_message_from, write_message_to, NoOpSigner, SignMessage, SignMessageError,
@@ -207,6 +497,8 @@ mod tests {
     use futures_core::Stream;
     use futures_util::stream::StreamExt;
     use std::error::Error as StdError;
+    use std::time::Duration;
+    use tokio::time::Instant;
 
     #[derive(Debug, Eq, PartialEq)]
     struct TestMessage(String);
@@ -340,4 +632,132 @@ mod tests {
             yield Err(TestServiceError);
         });
     }
+    fn channel_adapter(
+        sender: EventStreamSender<TestMessage, TestServiceError>,
+    ) -> MessageStreamAdapter<TestMessage, TestServiceError> {
+        sender.into_body_stream(Marshaller, ErrorMarshaller, NoOpSigner {})
+    }
+
+    /// Returns the payloads of the frames written in one chunk.
+    fn payloads(mut chunk: Bytes) -> Vec<String> {
+        let mut payloads = Vec::new();
+        while !chunk.is_empty() {
+            let message = read_message_from(&mut chunk).unwrap();
+            payloads.push(String::from_utf8(message.payload().to_vec()).unwrap());
+        }
+        payloads
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn events_are_written_immediately_by_default() {
+        let (tx, sender) = EventStreamSender::channel(4);
+        let mut adapter = channel_adapter(sender);
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            tx.send(TestMessage("a".into())).await.unwrap();
+            tx.send(TestMessage("b".into())).await.unwrap();
+        });
+
+        let start = Instant::now();
+        assert_eq!(vec!["a"], payloads(adapter.next().await.unwrap().unwrap()));
+        assert_eq!(Duration::from_millis(10), start.elapsed());
+        assert_eq!(vec!["b"], payloads(adapter.next().await.unwrap().unwrap()));
+        assert_eq!(Duration::from_millis(10), start.elapsed());
+        assert!(adapter.next().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn batched_events_are_written_within_max_delay() {
+        let (tx, sender) = EventStreamSender::channel(4);
+        let sender = sender
+            .batch(10, Duration::from_millis(50))
+            .sleep_impl(TokioSleep::new());
+        let mut adapter = channel_adapter(sender);
+
+        tokio::spawn(async move {
+            tx.send(TestMessage("a".into())).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            tx.send(TestMessage("b".into())).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            tx.send(TestMessage("c".into())).await.unwrap();
+            // Ending the stream would write the batch right away
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        });
+
+        let start = Instant::now();
+        assert_eq!(
+            vec!["a", "b"],
+            payloads(adapter.next().await.unwrap().unwrap())
+        );
+        assert_eq!(Duration::from_millis(50), start.elapsed());
+        // The delay starts with the first event of the next batch
+        assert_eq!(vec!["c"], payloads(adapter.next().await.unwrap().unwrap()));
+        assert_eq!(Duration::from_millis(170), start.elapsed());
+        assert!(adapter.next().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn full_batches_are_written_immediately() {
+        let (tx, sender) = EventStreamSender::channel(4);
+        let sender = sender
+            .batch(2, Duration::from_secs(3600))
+            .sleep_impl(TokioSleep::new());
+        let mut adapter = channel_adapter(sender);
+
+        for event in ["a", "b", "c"] {
+            tx.send(TestMessage(event.into())).await.unwrap();
+        }
+        drop(tx);
+
+        let start = Instant::now();
+        assert_eq!(
+            vec!["a", "b"],
+            payloads(adapter.next().await.unwrap().unwrap())
+        );
+        // The end of the stream writes the last, partial batch
+        assert_eq!(vec!["c"], payloads(adapter.next().await.unwrap().unwrap()));
+        assert!(adapter.next().await.is_none());
+        assert_eq!(Duration::ZERO, start.elapsed());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn flush_writes_the_batch() {
+        let (tx, sender) = EventStreamSender::channel(4);
+        let sender = sender
+            .batch(10, Duration::from_secs(3600))
+            .sleep_impl(TokioSleep::new());
+        let mut adapter = channel_adapter(sender);
+
+        let handler = tokio::spawn(async move {
+            tx.send(TestMessage("a".into())).await.unwrap();
+            tx.flush().await.unwrap();
+            Instant::now()
+        });
+
+        let start = Instant::now();
+        assert_eq!(vec!["a"], payloads(adapter.next().await.unwrap().unwrap()));
+        assert_eq!(Duration::ZERO, start.elapsed());
+        assert_eq!(start, handler.await.unwrap());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_blocked_transport_exerts_backpressure() {
+        let (tx, sender) = EventStreamSender::channel(2);
+        let mut adapter = channel_adapter(sender);
+
+        tx.send(TestMessage("a".into())).await.unwrap();
+        tx.send(TestMessage("b".into())).await.unwrap();
+        // The transport isn't pulling events, e.g. because the HTTP/2 flow control window is exhausted
+        let blocked =
+            tokio::time::timeout(Duration::from_secs(1), tx.send(TestMessage("c".into())));
+        assert!(blocked.await.is_err(), "the channel should be full");
+
+        assert_eq!(vec!["a"], payloads(adapter.next().await.unwrap().unwrap()));
+        tx.send(TestMessage("c".into())).await.unwrap();
+
+        drop(adapter);
+        let err = tx.send(TestMessage("d".into())).await.unwrap_err();
+        assert_eq!("the event stream was closed", err.to_string());
+    }
 }
@@ -85,6 +85,56 @@ pub fn headers_for_prefix<'a>(
         .map(move |k| (&k[key.len()..], k))
 }
 
+/// Returns an iterator over the headers whose name starts with `prefix`, ignoring case, as pairs of
+/// the unprefixed header name and the header value.
+///
+/// Unlike [`headers_for_prefix`], the values come along with the names, so the caller can stream
+/// through large groups of prefixed headers without looking each of them up again, or collecting
+/// them into a map. A header with many values is yielded once per value.
+///
+/// This works with anything that iterates over `(&str, value)` pairs, like `&Headers`, or an
+/// `http::HeaderMap` iterated with `.iter().map(|(name, value)| (name.as_str(), value))`.
+pub fn prefixed_headers<'a, V>(
+    headers: impl IntoIterator<Item = (&'a str, V)>,
+    prefix: &'a str,
+) -> impl Iterator<Item = (&'a str, V)> {
+    headers.into_iter().filter_map(move |(name, value)| {
+        let matches = name
+            .get(..prefix.len())
+            .map_or(false, |start| start.eq_ignore_ascii_case(prefix));
+        matches.then(|| (&name[prefix.len()..], value))
+    })
+}
+
+/// Decodes a base64 header value into `buf`, replacing its contents, and returns the decoded bytes
+///
+/// `buf`'s allocation is reused, so decoding many values with the same buffer only allocates
+/// when a value is larger than all the previous ones. Whitespace around the value is ignored.
+pub fn decode_base64_into<'b>(
+    value: impl AsRef<[u8]>,
+    buf: &'b mut Vec<u8>,
+) -> Result<&'b [u8], ParseError> {
+    buf.clear();
+    aws_smithy_types::base64::decode_append(value.as_ref().trim_ascii(), buf).map_err(|err| {
+        buf.clear();
+        ParseError::new("failed to decode base64").with_source(err)
+    })?;
+    Ok(buf)
+}
+
+/// Returns the number of bytes taken by the names and values of `headers`
+///
+/// A name is counted once for each of its values, as it would be sent on the wire by HTTP/1.1.
+/// Separators and line endings aren't counted.
+pub fn total_header_bytes<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+    headers: impl IntoIterator<Item = (K, V)>,
+) -> usize {
+    headers
+        .into_iter()
+        .map(|(name, value)| name.as_ref().len() + value.as_ref().len())
+        .sum()
+}
+
 /// Convert a `HeaderValue` into a `Vec<T>` where `T: FromStr`
 pub fn read_many_from_str<'a, T: FromStr>(
     values: impl Iterator<Item = &'a str>,
@@ -333,9 +383,9 @@ pub fn append_merge_header_maps(
 mod test {
     use super::quote_header_value;
     use crate::header::{
-        append_merge_header_maps, headers_for_prefix, many_dates, read_many_from_str,
-        read_many_primitive, set_request_header_if_absent, set_response_header_if_absent,
-        ParseError,
+        append_merge_header_maps, decode_base64_into, headers_for_prefix, many_dates,
+        prefixed_headers, read_many_from_str, read_many_primitive, set_request_header_if_absent,
+        set_response_header_if_absent, total_header_bytes, ParseError,
     };
     use aws_smithy_runtime_api::http::Request;
     use aws_smithy_types::error::display::DisplayErrorContext;
@@ -677,6 +727,73 @@ mod test {
         assert_eq!(resp.get("a"), Some(&vec![123_i16, 456_i16]));
     }
 
+    #[test]
+    fn test_prefixed_headers() {
+        let test_request = Request::try_from(
+            http_02x::Request::builder()
+                .header("X-Prefix-A", "123")
+                .header("X-Prefix-B", "")
+                .header("X-Prefix-C", "1")
+                .header("X-Prefix-C", "2")
+                .header("X-Other", "3")
+                .header("X-Prefix", "4")
+                .body(())
+                .unwrap(),
+        )
+        .unwrap();
+        let mut found: Vec<_> = prefixed_headers(test_request.headers(), "X-Prefix-").collect();
+        found.sort();
+        assert_eq!(vec![("a", "123"), ("b", ""), ("c", "1"), ("c", "2")], found);
+
+        let mut header_map = HeaderMap::new();
+        header_map.insert("x-meta-key", HeaderValue::from_static("value"));
+        let found: Vec<_> = prefixed_headers(
+            header_map
+                .iter()
+                .map(|(name, value)| (name.as_str(), value)),
+            "x-meta-",
+        )
+        .collect();
+        assert_eq!(vec![("key", &HeaderValue::from_static("value"))], found);
+    }
+
+    #[test]
+    fn test_decode_base64_into() {
+        let mut buf = Vec::new();
+        // "✓ café" encoded as UTF-8, then base64
+        let decoded = decode_base64_into(HeaderValue::from_static(" 4pyTIGNhZsOp "), &mut buf)
+            .expect("valid base64");
+        assert_eq!("✓ café", std::str::from_utf8(decoded).unwrap());
+
+        let decoded = decode_base64_into("", &mut buf).expect("empty values are valid");
+        assert!(decoded.is_empty());
+
+        let decoded = decode_base64_into("YQ==", &mut buf).unwrap();
+        assert_eq!(b"a", decoded);
+
+        decode_base64_into("✓", &mut buf).expect_err("unicode isn't base64");
+        assert!(buf.is_empty(), "the buffer is cleared on failure");
+    }
+
+    #[test]
+    fn test_total_header_bytes() {
+        let mut header_map = HeaderMap::new();
+        assert_eq!(0, total_header_bytes(&header_map));
+        header_map.insert("x-a", HeaderValue::from_static("12345"));
+        header_map.append("x-a", HeaderValue::from_static("6"));
+        header_map.insert("x-empty", HeaderValue::from_static(""));
+        assert_eq!(3 + 5 + 3 + 1 + 7, total_header_bytes(&header_map));
+
+        let headers = Request::try_from(
+            http_02x::Request::builder()
+                .header("x-a", "12345")
+                .body(())
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(8, total_header_bytes(headers.headers()));
+    }
+
     #[test]
     fn test_quote_header_value() {
         assert_eq!("", &quote_header_value(""));
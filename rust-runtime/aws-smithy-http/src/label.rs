@@ -5,11 +5,26 @@
 
 //! Formatting values as Smithy
 //! [httpLabel](https://smithy.io/2.0/spec/http-bindings.html#httplabel-trait)
+//!
+//! Clients and servers share the rules for label values defined here, so that a value a client
+//! sends is the value a server sees:
+//!
+//! - A `/` in the value of a default label is sent as `%2F`. A `/` in the value of a greedy
+//!   label is sent as is, and separates path segments. Servers route requests on the raw path,
+//!   before anything is percent decoded, so `%2F` never separates path segments. It is decoded
+//!   back into `/` only after the label value has been extracted.
+//! - Empty values are rejected by clients, since they would produce an empty path segment (`//`),
+//!   and may be rejected by servers. See [`EmptyLabels`].
+//! - A trailing `/` in the value of a greedy label may be preserved, trimmed, or rejected. See
+//!   [`TrailingSlash`].
 
 use crate::urlencode::BASE_SET;
 use aws_smithy_types::date_time::{DateTimeFormatError, Format};
 use aws_smithy_types::DateTime;
 use percent_encoding::AsciiSet;
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
 
 const GREEDY: &AsciiSet = &BASE_SET.remove(b'/');
 
@@ -38,9 +53,168 @@ pub fn fmt_timestamp(t: &DateTime, format: Format) -> Result<String, DateTimeFor
     Ok(fmt_string(t.fmt(format)?, EncodingStrategy::Default))
 }
 
+/// Validate a given `httpLabel` against [`LabelRules`], and format it according to an
+/// [`EncodingStrategy`]
+///
+/// This is used by clients when serializing a request.
+pub fn fmt_label(
+    value: &str,
+    strategy: EncodingStrategy,
+    rules: &LabelRules,
+) -> Result<String, InvalidLabel> {
+    let value = rules.apply(value, &strategy)?;
+    Ok(fmt_string(value, strategy))
+}
+
+/// Validate the raw value of an `httpLabel`, as found in a request's path, against [`LabelRules`],
+/// and percent decode it
+///
+/// This is used by servers when deserializing a request, after it was routed.
+pub fn decode_label<'a>(
+    raw: &'a str,
+    strategy: EncodingStrategy,
+    rules: &LabelRules,
+) -> Result<Cow<'a, str>, InvalidLabel> {
+    let raw = rules.apply(raw, &strategy)?;
+    percent_encoding::percent_decode_str(raw)
+        .decode_utf8()
+        .map_err(|err| InvalidLabel::new(InvalidLabelKind::NotUtf8(err)))
+}
+
+/// How empty `httpLabel` values are handled.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum EmptyLabels {
+    /// Empty values are rejected.
+    #[default]
+    Reject,
+    /// Empty values are allowed.
+    Allow,
+}
+
+/// How a trailing `/` in the value of a greedy `httpLabel` is handled.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TrailingSlash {
+    /// The trailing `/` is kept as part of the value.
+    #[default]
+    Preserve,
+    /// A single trailing `/` is removed from the value.
+    Trim,
+    /// Values with a trailing `/` are rejected.
+    Reject,
+}
+
+/// The rules that `httpLabel` values must follow.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LabelRules {
+    empty_labels: EmptyLabels,
+    trailing_slash: TrailingSlash,
+}
+
+impl LabelRules {
+    /// The rules used by clients: empty values are rejected, and trailing slashes are preserved.
+    pub const fn client() -> Self {
+        Self {
+            empty_labels: EmptyLabels::Reject,
+            trailing_slash: TrailingSlash::Preserve,
+        }
+    }
+
+    /// The default rules used by servers: empty values are allowed, and trailing slashes are
+    /// preserved.
+    pub const fn server() -> Self {
+        Self {
+            empty_labels: EmptyLabels::Allow,
+            trailing_slash: TrailingSlash::Preserve,
+        }
+    }
+
+    /// Sets how empty values are handled.
+    pub const fn with_empty_labels(mut self, empty_labels: EmptyLabels) -> Self {
+        self.empty_labels = empty_labels;
+        self
+    }
+
+    /// Sets how a trailing `/` in the value of a greedy label is handled.
+    pub const fn with_trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+        self.trailing_slash = trailing_slash;
+        self
+    }
+
+    fn apply<'a>(
+        &self,
+        value: &'a str,
+        strategy: &EncodingStrategy,
+    ) -> Result<&'a str, InvalidLabel> {
+        let value = if *strategy == EncodingStrategy::Greedy && value.ends_with('/') {
+            match self.trailing_slash {
+                TrailingSlash::Preserve => value,
+                TrailingSlash::Trim => &value[..value.len() - 1],
+                TrailingSlash::Reject => {
+                    return Err(InvalidLabel::new(InvalidLabelKind::TrailingSlash))
+                }
+            }
+        } else {
+            value
+        };
+        if value.is_empty() && self.empty_labels == EmptyLabels::Reject {
+            return Err(InvalidLabel::new(InvalidLabelKind::Empty));
+        }
+        Ok(value)
+    }
+}
+
+#[derive(Debug)]
+enum InvalidLabelKind {
+    Empty,
+    TrailingSlash,
+    NotUtf8(std::str::Utf8Error),
+}
+
+/// An `httpLabel` value doesn't follow the [`LabelRules`].
+#[derive(Debug)]
+pub struct InvalidLabel {
+    kind: InvalidLabelKind,
+}
+
+impl InvalidLabel {
+    fn new(kind: InvalidLabelKind) -> Self {
+        Self { kind }
+    }
+
+    /// Returns true if the label value was empty.
+    pub fn is_empty(&self) -> bool {
+        matches!(self.kind, InvalidLabelKind::Empty)
+    }
+}
+
+impl fmt::Display for InvalidLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use InvalidLabelKind::*;
+        match &self.kind {
+            Empty => write!(f, "label value cannot be empty"),
+            TrailingSlash => write!(f, "greedy label value cannot end with `/`"),
+            NotUtf8(_) => write!(f, "label value cannot be percent decoded into valid UTF-8"),
+        }
+    }
+}
+
+impl Error for InvalidLabel {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            InvalidLabelKind::NotUtf8(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::label::{fmt_string, EncodingStrategy};
+    use crate::label::{
+        decode_label, fmt_label, fmt_string, EmptyLabels, EncodingStrategy, LabelRules,
+        TrailingSlash,
+    };
     use http_02x::Uri;
     use proptest::proptest;
 
@@ -50,6 +224,73 @@ mod test {
         assert_eq!(fmt_string("a/b", EncodingStrategy::Greedy), "a/b");
     }
 
+    #[test]
+    fn empty_labels() {
+        let err = fmt_label("", EncodingStrategy::Default, &LabelRules::client()).unwrap_err();
+        assert!(err.is_empty());
+        assert_eq!("label value cannot be empty", err.to_string());
+        assert!(fmt_label("", EncodingStrategy::Greedy, &LabelRules::client()).is_err());
+
+        assert_eq!(
+            "",
+            decode_label("", EncodingStrategy::Default, &LabelRules::server()).unwrap()
+        );
+        let strict = LabelRules::server().with_empty_labels(EmptyLabels::Reject);
+        assert!(decode_label("", EncodingStrategy::Default, &strict)
+            .unwrap_err()
+            .is_empty());
+    }
+
+    #[test]
+    fn encoded_slashes_are_decoded_after_routing() {
+        let rules = LabelRules::server();
+        assert_eq!(
+            "a/b",
+            decode_label("a%2Fb", EncodingStrategy::Default, &rules).unwrap()
+        );
+        assert_eq!(
+            "a/b/c",
+            decode_label("a/b%2Fc", EncodingStrategy::Greedy, &rules).unwrap()
+        );
+        // a literal `%2F` in a value round trips
+        let raw = fmt_label("a%2Fb", EncodingStrategy::Greedy, &LabelRules::client()).unwrap();
+        assert_eq!("a%252Fb", raw);
+        assert_eq!(
+            "a%2Fb",
+            decode_label(&raw, EncodingStrategy::Greedy, &rules).unwrap()
+        );
+        assert!(decode_label("%FF", EncodingStrategy::Default, &rules).is_err());
+    }
+
+    #[test]
+    fn trailing_slashes() {
+        let greedy = EncodingStrategy::Greedy;
+        let preserve = LabelRules::server();
+        assert_eq!("a/", decode_label("a/", greedy.clone(), &preserve).unwrap());
+
+        let trim = preserve.with_trailing_slash(TrailingSlash::Trim);
+        assert_eq!("a", decode_label("a/", greedy.clone(), &trim).unwrap());
+        assert_eq!("a", fmt_label("a/", greedy.clone(), &trim).unwrap());
+        // only a trailing slash of a greedy label is trimmed
+        assert_eq!(
+            "a%2F",
+            fmt_label("a/", EncodingStrategy::Default, &trim).unwrap()
+        );
+        // a value that is only a slash becomes empty
+        assert!(fmt_label(
+            "/",
+            greedy.clone(),
+            &LabelRules::client().with_trailing_slash(TrailingSlash::Trim)
+        )
+        .is_err());
+
+        let reject = preserve.with_trailing_slash(TrailingSlash::Reject);
+        assert_eq!(
+            "greedy label value cannot end with `/`",
+            decode_label("a/", greedy, &reject).unwrap_err().to_string()
+        );
+    }
+
     proptest! {
         #[test]
         fn test_encode_request(s: String) {
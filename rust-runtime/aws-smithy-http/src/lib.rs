@@ -18,6 +18,7 @@
 //! - Endpoint support
 //! - HTTP header deserialization
 //! - Event streams
+//! - `multipart/form-data` bodies
 //!
 //! | Feature        | Description |
 //! |----------------|-------------|
@@ -36,6 +37,7 @@ pub mod endpoint;
 pub mod futures_stream_adapter;
 pub mod header;
 pub mod label;
+pub mod multipart;
 pub mod operation;
 pub mod query;
 #[doc(hidden)]
@@ -0,0 +1,693 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! `multipart/form-data` request bodies, as described by [RFC 7578](https://www.rfc-editor.org/rfc/rfc7578).
+//!
+//! A [`MultipartBody`] is made of [`Part`]s, each of which is either held in memory or streamed
+//! from a [`ByteStream`]. Streamed parts are never buffered: the body is written as the parts are
+//! read.
+//!
+//! ```
+//! use aws_smithy_http::multipart::{MultipartBody, Part};
+//! use aws_smithy_types::byte_stream::ByteStream;
+//!
+//! # fn example(file: ByteStream) -> Result<(), Box<dyn std::error::Error>> {
+//! let body = MultipartBody::builder()
+//!     .part(Part::new("metadata", br#"{"title":"report"}"#.to_vec()).content_type("application/json"))
+//!     .part(Part::new("file", file).file_name("report.pdf").content_type("application/pdf"))
+//!     .build()?;
+//! let content_type = body.content_type();
+//! let body = body.into_sdk_body();
+//! # let _ = (content_type, body);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Generated clients generate boundaries from their configured random source, which
+//! [`MultipartRandomSourceInterceptor`] makes available to the request serializer.
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::BeforeSerializationInterceptorContextMut;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::random::{RandomSource, SharedRandomSource};
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::body::{Error as BodyError, SdkBody};
+use aws_smithy_types::byte_stream::ByteStream;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use bytes::{BufMut, Bytes, BytesMut};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+// RFC 2046 limits boundaries to 70 characters
+const MAX_BOUNDARY_LEN: usize = 70;
+const GENERATED_BOUNDARY_PREFIX: &str = "smithy-boundary-";
+const GENERATED_BOUNDARY_RANDOM_LEN: usize = 32;
+const GENERATED_BOUNDARY_ALPHABET: &[u8; 62] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// A part of a [`MultipartBody`].
+#[derive(Debug)]
+pub struct Part {
+    name: Cow<'static, str>,
+    file_name: Option<Cow<'static, str>>,
+    content_type: Option<Cow<'static, str>>,
+    headers: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+    body: SdkBody,
+}
+
+impl Part {
+    /// Creates a form field called `name` with the given contents.
+    ///
+    /// In-memory contents (e.g. `Vec<u8>` or `Bytes`) are copied into the body, and a streaming
+    /// `ByteStream` is streamed as the body is sent.
+    pub fn new(name: impl Into<Cow<'static, str>>, body: impl Into<ByteStream>) -> Self {
+        Self {
+            name: name.into(),
+            file_name: None,
+            content_type: None,
+            headers: Vec::new(),
+            body: body.into().into_inner(),
+        }
+    }
+
+    /// Sets the file name sent in the `Content-Disposition` header of this part.
+    pub fn file_name(mut self, file_name: impl Into<Cow<'static, str>>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Sets the `Content-Type` header of this part.
+    ///
+    /// Parts without a content type are `text/plain` according to RFC 7578.
+    pub fn content_type(mut self, content_type: impl Into<Cow<'static, str>>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Adds a header to this part.
+    pub fn header(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        value: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    fn validate(&self) -> Result<(), MultipartError> {
+        let content_type = self
+            .content_type
+            .as_ref()
+            .map(|value| ("Content-Type", value.as_ref()));
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, value)| (name.as_ref(), value.as_ref()));
+        for (name, value) in content_type.into_iter().chain(headers) {
+            if name.is_empty() || !name.bytes().all(is_token_char) {
+                return Err(MultipartError::invalid_header(&self.name, name));
+            }
+            if value.contains(['\r', '\n']) {
+                return Err(MultipartError::invalid_header(&self.name, name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the delimiter and headers that precede the contents of this part.
+    fn write_head(&self, boundary: &str, out: &mut BytesMut) {
+        out.put_slice(b"--");
+        out.put_slice(boundary.as_bytes());
+        out.put_slice(b"\r\nContent-Disposition: form-data; name=\"");
+        write_escaped(&self.name, out);
+        out.put_u8(b'"');
+        if let Some(file_name) = &self.file_name {
+            out.put_slice(b"; filename=\"");
+            write_escaped(file_name, out);
+            out.put_u8(b'"');
+        }
+        out.put_slice(b"\r\n");
+        if let Some(content_type) = &self.content_type {
+            out.put_slice(b"Content-Type: ");
+            out.put_slice(content_type.as_bytes());
+            out.put_slice(b"\r\n");
+        }
+        for (name, value) in &self.headers {
+            out.put_slice(name.as_bytes());
+            out.put_slice(b": ");
+            out.put_slice(value.as_bytes());
+            out.put_slice(b"\r\n");
+        }
+        out.put_slice(b"\r\n");
+    }
+}
+
+/// Escapes a field or file name the way browsers do, so it can't end the quoted string.
+fn write_escaped(value: &str, out: &mut BytesMut) {
+    for byte in value.bytes() {
+        match byte {
+            b'"' => out.put_slice(b"%22"),
+            b'\r' => out.put_slice(b"%0D"),
+            b'\n' => out.put_slice(b"%0A"),
+            byte => out.put_u8(byte),
+        }
+    }
+}
+
+fn is_token_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte)
+}
+
+fn is_boundary_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"'()+_,-./:=? ".contains(&byte)
+}
+
+/// Builder for [`MultipartBody`].
+#[derive(Debug, Default)]
+pub struct MultipartBodyBuilder {
+    boundary: Option<Cow<'static, str>>,
+    random_source: Option<SharedRandomSource>,
+    parts: Vec<Part>,
+}
+
+impl MultipartBodyBuilder {
+    /// Sets the boundary that separates the parts.
+    ///
+    /// By default, a random boundary that doesn't occur in any in-memory part is generated. A
+    /// boundary set here must not occur in any in-memory part either, or building fails.
+    pub fn boundary(mut self, boundary: impl Into<Cow<'static, str>>) -> Self {
+        self.boundary = Some(boundary.into());
+        self
+    }
+
+    /// Sets the random source that boundaries are generated from.
+    ///
+    /// Without one, boundaries are generated from a thread-local generator.
+    pub fn random_source(mut self, random_source: impl RandomSource + 'static) -> Self {
+        self.random_source = Some(random_source.into_shared());
+        self
+    }
+
+    /// Generates boundaries from the client's random source, if
+    /// [`MultipartRandomSourceInterceptor`] stored it in `cfg`.
+    pub fn random_source_from_config(mut self, cfg: &ConfigBag) -> Self {
+        if let Some(MultipartRandomSource(random_source)) = cfg.load::<MultipartRandomSource>() {
+            self.random_source = Some(random_source.clone());
+        }
+        self
+    }
+
+    /// Adds a part to the body.
+    pub fn part(mut self, part: Part) -> Self {
+        self.parts.push(part);
+        self
+    }
+
+    /// Builds the body.
+    ///
+    /// This fails if there are no parts, if a part has an invalid header, or if the boundary set
+    /// with [`boundary`](Self::boundary) is invalid or occurs in an in-memory part.
+    pub fn build(self) -> Result<MultipartBody, MultipartError> {
+        let random_source = self.random_source.clone();
+        self.build_with(|| generate_boundary(random_source.as_ref()))
+    }
+
+    fn build_with(
+        self,
+        mut generate_boundary: impl FnMut() -> String,
+    ) -> Result<MultipartBody, MultipartError> {
+        if self.parts.is_empty() {
+            return Err(MultipartError::new(MultipartErrorKind::NoParts));
+        }
+        for part in &self.parts {
+            part.validate()?;
+        }
+        let boundary = match self.boundary {
+            Some(boundary) => {
+                let valid = (1..=MAX_BOUNDARY_LEN).contains(&boundary.len())
+                    && boundary.bytes().all(is_boundary_char)
+                    && !boundary.ends_with(' ');
+                if !valid {
+                    return Err(MultipartError::new(MultipartErrorKind::InvalidBoundary));
+                }
+                if occurs_in_parts(&self.parts, &boundary) {
+                    return Err(MultipartError::new(MultipartErrorKind::BoundaryCollision));
+                }
+                boundary.into_owned()
+            }
+            // Streamed parts can't be checked without buffering them, but a random boundary is
+            // vanishingly unlikely to occur in them.
+            None => loop {
+                let boundary = generate_boundary();
+                if !occurs_in_parts(&self.parts, &boundary) {
+                    break boundary;
+                }
+                tracing::debug!("the multipart boundary occurred in a part, generating another");
+            },
+        };
+        Ok(MultipartBody {
+            boundary,
+            parts: self.parts,
+        })
+    }
+}
+
+fn generate_boundary(random_source: Option<&SharedRandomSource>) -> String {
+    let mut random = [0; GENERATED_BOUNDARY_RANDOM_LEN];
+    match random_source {
+        Some(random_source) => random_source.fill_bytes(&mut random),
+        None => fastrand::fill(&mut random),
+    }
+    let random: String = random
+        .iter()
+        .map(|byte| {
+            GENERATED_BOUNDARY_ALPHABET[usize::from(*byte) % GENERATED_BOUNDARY_ALPHABET.len()]
+                as char
+        })
+        .collect();
+    format!("{GENERATED_BOUNDARY_PREFIX}{random}")
+}
+
+/// Returns true if a delimiter made from `boundary` occurs in any of the in-memory parts.
+fn occurs_in_parts(parts: &[Part], boundary: &str) -> bool {
+    let delimiter = format!("--{boundary}");
+    parts
+        .iter()
+        .filter_map(|part| part.body.bytes())
+        .any(|bytes| {
+            bytes
+                .windows(delimiter.len())
+                .any(|window| window == delimiter.as_bytes())
+        })
+}
+
+/// The client's random source, stored by [`MultipartRandomSourceInterceptor`].
+#[derive(Clone, Debug)]
+struct MultipartRandomSource(SharedRandomSource);
+
+impl Storable for MultipartRandomSource {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Makes the client's random source available to request serializers that build a
+/// [`MultipartBody`].
+///
+/// Request serializers only have access to the config bag, so this interceptor stores the random
+/// source from the runtime components in it before serialization. Serializers then pass the
+/// config bag to [`MultipartBodyBuilder::random_source_from_config`].
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct MultipartRandomSourceInterceptor;
+
+impl MultipartRandomSourceInterceptor {
+    /// Creates a new `MultipartRandomSourceInterceptor`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Intercept for MultipartRandomSourceInterceptor {
+    fn name(&self) -> &'static str {
+        "MultipartRandomSourceInterceptor"
+    }
+
+    fn modify_before_serialization(
+        &self,
+        _context: &mut BeforeSerializationInterceptorContextMut<'_>,
+        runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        if let Some(random_source) = runtime_components.random_source() {
+            cfg.interceptor_state()
+                .store_put(MultipartRandomSource(random_source));
+        }
+        Ok(())
+    }
+}
+
+/// A `multipart/form-data` body.
+///
+/// When every part is held in memory, the body is in memory too, so it can be signed like any
+/// other payload. Otherwise, the body is streamed, and it can be retried only if every streamed
+/// part can be.
+#[derive(Debug)]
+pub struct MultipartBody {
+    boundary: String,
+    parts: Vec<Part>,
+}
+
+impl MultipartBody {
+    /// Returns a builder for a `MultipartBody`.
+    pub fn builder() -> MultipartBodyBuilder {
+        MultipartBodyBuilder::default()
+    }
+
+    /// Returns the boundary that separates the parts.
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Returns the value of the `Content-Type` header for this body, including the boundary.
+    pub fn content_type(&self) -> String {
+        if self.boundary.bytes().all(is_token_char) {
+            format!("multipart/form-data; boundary={}", self.boundary)
+        } else {
+            format!("multipart/form-data; boundary=\"{}\"", self.boundary)
+        }
+    }
+
+    /// Converts this body into an [`SdkBody`].
+    pub fn into_sdk_body(self) -> SdkBody {
+        let mut segments = Vec::new();
+        let mut head = BytesMut::new();
+        for (index, part) in self.parts.into_iter().enumerate() {
+            if index > 0 {
+                head.put_slice(b"\r\n");
+            }
+            part.write_head(&self.boundary, &mut head);
+            match part.body.bytes() {
+                Some(bytes) => head.put_slice(bytes),
+                None => {
+                    segments.push(Segment::Bytes(head.split().freeze()));
+                    segments.push(Segment::Body(part.body));
+                }
+            }
+        }
+        head.put_slice(b"\r\n--");
+        head.put_slice(self.boundary.as_bytes());
+        head.put_slice(b"--\r\n");
+        segments.push(Segment::Bytes(head.freeze()));
+
+        if let [Segment::Bytes(bytes)] = segments.as_slice() {
+            return SdkBody::from(bytes.clone());
+        }
+        let retryable = segments.iter().all(|segment| match segment {
+            Segment::Bytes(_) => true,
+            Segment::Body(body) => body.try_clone().is_some(),
+        });
+        if retryable {
+            SdkBody::retryable(move || {
+                let segments = segments.iter().map(Segment::replay).collect();
+                SdkBody::from_body_0_4(MultipartStream { segments })
+            })
+        } else {
+            SdkBody::from_body_0_4(MultipartStream {
+                segments: segments.into(),
+            })
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Segment {
+    Bytes(Bytes),
+    Body(SdkBody),
+}
+
+impl Segment {
+    fn replay(&self) -> Self {
+        match self {
+            Segment::Bytes(bytes) => Segment::Bytes(bytes.clone()),
+            Segment::Body(body) => Segment::Body(
+                body.try_clone()
+                    .expect("checked that every part can be replayed"),
+            ),
+        }
+    }
+
+    fn len(&self) -> Option<u64> {
+        match self {
+            Segment::Bytes(bytes) => Some(bytes.len() as u64),
+            Segment::Body(body) => body.content_length(),
+        }
+    }
+}
+
+/// The body of a multipart request with at least one streamed part.
+#[derive(Debug)]
+struct MultipartStream {
+    segments: VecDeque<Segment>,
+}
+
+impl http_body_04x::Body for MultipartStream {
+    type Data = Bytes;
+    type Error = BodyError;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        loop {
+            let body = match self.segments.front_mut() {
+                None => return Poll::Ready(None),
+                Some(Segment::Body(body)) => body,
+                Some(Segment::Bytes(_)) => match self.segments.pop_front() {
+                    Some(Segment::Bytes(bytes)) => return Poll::Ready(Some(Ok(bytes))),
+                    _ => unreachable!("the front segment is bytes"),
+                },
+            };
+            match Pin::new(body).poll_data(cx) {
+                Poll::Ready(None) => {
+                    self.segments.pop_front();
+                }
+                Poll::Ready(Some(Ok(data))) if data.is_empty() => {}
+                other => return other,
+            }
+        }
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http_02x::HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(None))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    fn size_hint(&self) -> http_body_04x::SizeHint {
+        let lengths: Option<Vec<u64>> = self.segments.iter().map(Segment::len).collect();
+        match lengths {
+            Some(lengths) => http_body_04x::SizeHint::with_exact(lengths.iter().sum()),
+            None => {
+                let mut size_hint = http_body_04x::SizeHint::new();
+                size_hint.set_lower(self.segments.iter().filter_map(Segment::len).sum());
+                size_hint
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum MultipartErrorKind {
+    NoParts,
+    InvalidBoundary,
+    BoundaryCollision,
+    InvalidHeader { part: String, header: String },
+}
+
+/// A [`MultipartBody`] couldn't be built.
+#[derive(Debug)]
+pub struct MultipartError {
+    kind: MultipartErrorKind,
+}
+
+impl MultipartError {
+    fn new(kind: MultipartErrorKind) -> Self {
+        Self { kind }
+    }
+
+    fn invalid_header(part: &str, header: &str) -> Self {
+        Self::new(MultipartErrorKind::InvalidHeader {
+            part: part.into(),
+            header: header.into(),
+        })
+    }
+}
+
+impl fmt::Display for MultipartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use MultipartErrorKind::*;
+        match &self.kind {
+            NoParts => write!(f, "a multipart body must have at least one part"),
+            InvalidBoundary => write!(
+                f,
+                "a multipart boundary must be 1 to {MAX_BOUNDARY_LEN} characters allowed by RFC 2046, and must not end with a space"
+            ),
+            BoundaryCollision => write!(f, "the multipart boundary occurs in the contents of a part"),
+            InvalidHeader { part, header } => {
+                write!(f, "the `{header}` header of multipart part `{part}` is invalid")
+            }
+        }
+    }
+}
+
+impl Error for MultipartError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn collect(body: SdkBody) -> String {
+        let bytes = ByteStream::new(body).collect().await.unwrap().into_bytes();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn in_memory_parts_are_framed_in_an_in_memory_body() {
+        let body = MultipartBody::builder()
+            .boundary("xyz")
+            .part(Part::new("meta", b"{}".to_vec()).content_type("application/json"))
+            .part(
+                Part::new("fi\"le", b"data".to_vec())
+                    .file_name("a.txt")
+                    .header("X-Custom", "1"),
+            )
+            .build()
+            .unwrap();
+        assert_eq!("multipart/form-data; boundary=xyz", body.content_type());
+
+        let body = body.into_sdk_body();
+        assert!(body.bytes().is_some());
+        assert_eq!(
+            "--xyz\r\n\
+             Content-Disposition: form-data; name=\"meta\"\r\n\
+             Content-Type: application/json\r\n\
+             \r\n\
+             {}\r\n\
+             --xyz\r\n\
+             Content-Disposition: form-data; name=\"fi%22le\"; filename=\"a.txt\"\r\n\
+             X-Custom: 1\r\n\
+             \r\n\
+             data\r\n\
+             --xyz--\r\n",
+            collect(body).await
+        );
+    }
+
+    #[tokio::test]
+    async fn streamed_parts_keep_the_body_replayable() {
+        let streamed = SdkBody::retryable(|| SdkBody::from_body_0_4(SdkBody::from("streamed")));
+        let body = MultipartBody::builder()
+            .boundary("xyz")
+            .part(Part::new("stream", streamed))
+            .build()
+            .unwrap()
+            .into_sdk_body();
+        assert!(body.bytes().is_none());
+        let expected = "--xyz\r\n\
+                        Content-Disposition: form-data; name=\"stream\"\r\n\
+                        \r\n\
+                        streamed\r\n\
+                        --xyz--\r\n";
+        assert_eq!(Some(expected.len() as u64), body.content_length());
+
+        let replay = body.try_clone().expect("replayable");
+        assert_eq!(expected, collect(body).await);
+        assert_eq!(expected, collect(replay).await);
+    }
+
+    #[test]
+    fn generated_boundaries_that_occur_in_a_part_are_regenerated() {
+        let mut candidates = vec!["second".to_string(), "first".to_string()];
+        let body = MultipartBody::builder()
+            .part(Part::new("file", b"contains --first".to_vec()))
+            .build_with(|| candidates.pop().unwrap())
+            .unwrap();
+        assert_eq!("second", body.boundary());
+    }
+
+    #[derive(Debug)]
+    struct Counter;
+
+    impl RandomSource for Counter {
+        fn fill_bytes(&self, dest: &mut [u8]) {
+            for (index, byte) in dest.iter_mut().enumerate() {
+                *byte = index as u8;
+            }
+        }
+
+        fn next_u64(&self) -> u64 {
+            0
+        }
+    }
+
+    #[test]
+    fn generated_boundaries_are_drawn_from_the_random_source() {
+        let build = || {
+            MultipartBody::builder()
+                .random_source(Counter)
+                .part(Part::new("file", Vec::new()))
+                .build()
+                .unwrap()
+        };
+        assert_eq!(
+            "smithy-boundary-ABCDEFGHIJKLMNOPQRSTUVWXYZabcdef",
+            build().boundary()
+        );
+        assert_eq!(build().boundary(), build().boundary());
+    }
+
+    #[test]
+    fn the_random_source_is_loaded_from_the_config_bag() {
+        let mut cfg = ConfigBag::base();
+        let builder = MultipartBody::builder().random_source_from_config(&cfg);
+        assert!(builder.random_source.is_none());
+
+        cfg.interceptor_state()
+            .store_put(MultipartRandomSource(SharedRandomSource::new(Counter)));
+        let body = MultipartBody::builder()
+            .random_source_from_config(&cfg)
+            .part(Part::new("file", Vec::new()))
+            .build()
+            .unwrap();
+        assert_eq!(
+            "smithy-boundary-ABCDEFGHIJKLMNOPQRSTUVWXYZabcdef",
+            body.boundary()
+        );
+    }
+
+    #[test]
+    fn configured_boundaries_are_validated() {
+        let build = |boundary: &'static str, contents: &'static [u8]| {
+            MultipartBody::builder()
+                .boundary(boundary)
+                .part(Part::new("file", contents.to_vec()))
+                .build()
+                .map(|body| body.content_type())
+        };
+        assert!(matches!(
+            build("first", b"contains --first").unwrap_err().kind,
+            MultipartErrorKind::BoundaryCollision
+        ));
+        assert!(matches!(
+            build("a\r\nb", b"").unwrap_err().kind,
+            MultipartErrorKind::InvalidBoundary
+        ));
+        assert_eq!(
+            "multipart/form-data; boundary=\"with space\"",
+            build("with space", b"").unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_headers_are_rejected() {
+        let err = MultipartBody::builder()
+            .part(Part::new("file", Vec::new()).content_type("text/plain\r\nX-Injected: 1"))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            "the `Content-Type` header of multipart part `file` is invalid",
+            err.to_string()
+        );
+    }
+}
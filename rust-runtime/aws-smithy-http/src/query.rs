@@ -9,15 +9,123 @@
 //! [httpQuery](https://smithy.io/2.0/spec/http-bindings.html#httpquery-trait)
 
 use crate::urlencode::BASE_SET;
+use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugin;
+use aws_smithy_types::config_bag::{ConfigBag, FrozenLayer, Layer, Storable, StoreAppend};
 use aws_smithy_types::date_time::{DateTimeFormatError, Format};
 use aws_smithy_types::DateTime;
 use percent_encoding::utf8_percent_encode;
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
 
 /// Format a given string as a query string.
+///
+/// Every character other than an RFC 3986 unreserved character is percent encoded. In particular,
+/// space is encoded as `%20` and `+` as `%2B`.
 pub fn fmt_string<T: AsRef<str>>(t: T) -> String {
     utf8_percent_encode(t.as_ref(), BASE_SET).to_string()
 }
 
+/// Validate a string that is already percent encoded so that it can be used in a query string as-is.
+///
+/// The string may only contain RFC 3986 unreserved characters (`A-Z`, `a-z`, `0-9`, `-`, `.`, `_`
+/// and `~`) and percent encoded octets (`%` followed by two hex digits). This is exactly what
+/// [`fmt_string`] produces. Anything else, including space and `+`, is rejected.
+pub fn fmt_pre_encoded_string(t: &str) -> Result<&str, InvalidPreEncodedValue> {
+    let bytes = t.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => index += 1,
+            b'%' if bytes.len() > index + 2
+                && bytes[index + 1].is_ascii_hexdigit()
+                && bytes[index + 2].is_ascii_hexdigit() =>
+            {
+                index += 3
+            }
+            _ => return Err(InvalidPreEncodedValue { index }),
+        }
+    }
+    Ok(t)
+}
+
+/// Format the string value of the query param `name`.
+///
+/// The value is percent encoded with [`fmt_string`] unless `name` was marked with
+/// [`PreEncodedQueryParam`], in which case it is validated with [`fmt_pre_encoded_string`] and
+/// used as-is.
+pub fn fmt_string_param<'a, T: AsRef<str> + ?Sized>(
+    cfg: &ConfigBag,
+    name: &str,
+    value: &'a T,
+) -> Result<Cow<'a, str>, InvalidPreEncodedValue> {
+    if cfg
+        .load::<PreEncodedQueryParam>()
+        .any(|param| param.name() == name)
+    {
+        fmt_pre_encoded_string(value.as_ref()).map(Cow::Borrowed)
+    } else {
+        Ok(Cow::Owned(fmt_string(value)))
+    }
+}
+
+/// A pre-encoded query string value wasn't valid percent encoding.
+#[derive(Debug)]
+pub struct InvalidPreEncodedValue {
+    index: usize,
+}
+
+impl fmt::Display for InvalidPreEncodedValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "pre-encoded query string value is not valid percent encoding (at byte {})",
+            self.index
+        )
+    }
+}
+
+impl Error for InvalidPreEncodedValue {}
+
+/// Marks the value of a query param as already percent encoded.
+///
+/// By default, every query param value is percent encoded, so a value that is already
+/// percent encoded (for example, a continuation token that was copied from a URL) ends up
+/// double encoded (`%2F` becomes `%252F`). When this is in the config bag, the value of the
+/// query param with the given name is sent as-is instead. It must still be valid percent
+/// encoding, see [`fmt_pre_encoded_string`], or the request will fail to serialize.
+///
+/// The name is the query param name that the member is bound to with `@httpQuery`,
+/// not the name of the member.
+#[derive(Clone, Debug)]
+pub struct PreEncodedQueryParam {
+    name: Cow<'static, str>,
+}
+
+impl PreEncodedQueryParam {
+    /// Marks the value of the query param `name` as already percent encoded.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self { name: name.into() }
+    }
+
+    /// Returns the name of the query param.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Storable for PreEncodedQueryParam {
+    type Storer = StoreAppend<Self>;
+}
+
+impl RuntimePlugin for PreEncodedQueryParam {
+    fn config(&self) -> Option<FrozenLayer> {
+        let mut layer = Layer::new("PreEncodedQueryParam");
+        layer.store_append(self.clone());
+        Some(layer.freeze())
+    }
+}
+
 /// Format a given [`DateTime`] as a query string.
 pub fn fmt_timestamp(t: &DateTime, format: Format) -> Result<String, DateTimeFormatError> {
     Ok(fmt_string(t.fmt(format)?))
@@ -64,7 +172,10 @@ impl<'a> Writer<'a> {
 
 #[cfg(test)]
 mod test {
-    use crate::query::{fmt_string, Writer};
+    use crate::query::{
+        fmt_pre_encoded_string, fmt_string, fmt_string_param, PreEncodedQueryParam, Writer,
+    };
+    use aws_smithy_types::config_bag::{ConfigBag, Layer};
     use http_02x::Uri;
     use proptest::proptest;
 
@@ -88,10 +199,71 @@ mod test {
         assert_eq!(out, "?a&b=c");
     }
 
+    #[test]
+    fn space_and_plus() {
+        // space is always `%20` and `+` is always `%2B` so that servers can't confuse them
+        for (input, encoded) in [
+            (" ", "%20"),
+            ("+", "%2B"),
+            ("a b+c", "a%20b%2Bc"),
+            ("%20", "%2520"),
+            ("%2B", "%252B"),
+        ] {
+            assert_eq!(fmt_string(input), encoded, "{input:?}");
+        }
+        // pre-encoded values must use the same encoding
+        for (input, valid) in [
+            (" ", false),
+            ("+", false),
+            ("a+b", false),
+            ("%20", true),
+            ("%2B", true),
+            ("a%20b%2Bc", true),
+        ] {
+            assert_eq!(fmt_pre_encoded_string(input).is_ok(), valid, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn pre_encoded_values() {
+        assert_eq!(
+            fmt_pre_encoded_string("abc-._~%2F%2f%E2%9C%93").unwrap(),
+            "abc-._~%2F%2f%E2%9C%93"
+        );
+        for invalid in ["%", "%2", "%zz", "a/b", "a&b=c", "a%2", "y̆", "%%20"] {
+            assert!(fmt_pre_encoded_string(invalid).is_err(), "{invalid:?}");
+        }
+        assert_eq!(
+            fmt_pre_encoded_string("ab%2").unwrap_err().to_string(),
+            "pre-encoded query string value is not valid percent encoding (at byte 2)"
+        );
+    }
+
+    #[test]
+    fn pre_encoded_params() {
+        let mut layer = Layer::new("test");
+        layer.store_append(PreEncodedQueryParam::new("token"));
+        let cfg = ConfigBag::of_layers(vec![layer]);
+
+        assert_eq!(fmt_string_param(&cfg, "token", "a%2Fb").unwrap(), "a%2Fb");
+        assert_eq!(fmt_string_param(&cfg, "other", "a%2Fb").unwrap(), "a%252Fb");
+        assert!(fmt_string_param(&cfg, "token", "a/b").is_err());
+        assert_eq!(
+            fmt_string_param(&ConfigBag::base(), "token", "a%2Fb").unwrap(),
+            "a%252Fb"
+        );
+    }
+
     proptest! {
         #[test]
         fn test_encode_request(s: String) {
             let _: Uri = format!("http://host.example.com/?{}", fmt_string(s)).parse().expect("all strings should be encoded properly");
         }
+
+        #[test]
+        fn encoded_strings_are_valid_pre_encoded_strings(s: String) {
+            let encoded = fmt_string(s);
+            assert_eq!(fmt_pre_encoded_string(&encoded).unwrap(), encoded);
+        }
     }
 }
@@ -131,6 +131,15 @@ mod test {
         assert_eq!("key=val%25ue&ano%25ther=value", query_writer.build_query());
     }
 
+    #[test]
+    fn space_and_plus() {
+        let uri = Uri::from_static("http://www.example.com");
+        let mut query_writer = QueryWriter::new(&uri);
+        query_writer.insert("a b", "c d");
+        query_writer.insert("e+f", "g+h");
+        assert_eq!("a%20b=c%20d&e%2Bf=g%2Bh", query_writer.build_query());
+    }
+
     #[test]
     // This test ensures that the percent encoding applied to queries always produces a valid URI if
     // the starting URI is valid
@@ -0,0 +1,140 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_http::multipart::{MultipartBody, Part};
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::byte_stream::ByteStream;
+use bytes::Bytes;
+use hyper::service::{make_service_fn, service_fn};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::mpsc;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+const CHUNKS: usize = 80;
+
+/// A part's header lines and contents.
+type ParsedPart = (Vec<String>, Vec<u8>);
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// A strict parser for `multipart/form-data` bodies.
+fn parse_multipart(content_type: &str, body: &[u8]) -> Vec<ParsedPart> {
+    let boundary = content_type
+        .strip_prefix("multipart/form-data; boundary=")
+        .expect("multipart content type");
+    let delimiter = format!("\r\n--{boundary}");
+    let body = [b"\r\n".as_slice(), body].concat();
+    let mut rest = body
+        .strip_prefix(delimiter.as_bytes())
+        .expect("body starts with a delimiter");
+    let mut parts = Vec::new();
+    loop {
+        if let Some(epilogue) = rest.strip_prefix(b"--") {
+            assert_eq!(b"\r\n", epilogue);
+            return parts;
+        }
+        rest = rest.strip_prefix(b"\r\n").expect("CRLF after delimiter");
+        let end = find(rest, delimiter.as_bytes()).expect("next delimiter");
+        let part = &rest[..end];
+        let headers_end = find(part, b"\r\n\r\n").expect("end of part headers");
+        let headers = std::str::from_utf8(&part[..headers_end])
+            .unwrap()
+            .split("\r\n")
+            .map(String::from)
+            .collect();
+        parts.push((headers, part[headers_end + 4..].to_vec()));
+        rest = &rest[end + delimiter.len()..];
+    }
+}
+
+/// Starts a server that parses one multipart request and sends its parts back to the test.
+fn start_server() -> (SocketAddr, mpsc::Receiver<Vec<ParsedPart>>) {
+    let (tx, rx) = mpsc::channel();
+    let make_service = make_service_fn(move |_| {
+        let tx = tx.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request: hyper::Request<hyper::Body>| {
+                let tx = tx.clone();
+                async move {
+                    let content_type = request.headers()["content-type"]
+                        .to_str()
+                        .unwrap()
+                        .to_owned();
+                    let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                    tx.send(parse_multipart(&content_type, &body)).unwrap();
+                    Ok::<_, Infallible>(hyper::Response::new(hyper::Body::empty()))
+                }
+            }))
+        }
+    });
+    let server = hyper::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+    let addr = server.local_addr();
+    tokio::spawn(server);
+    (addr, rx)
+}
+
+fn chunk(index: usize) -> Bytes {
+    Bytes::from(vec![(index % 251) as u8; CHUNK_SIZE])
+}
+
+#[tokio::test]
+async fn streamed_part_round_trips_through_a_server() {
+    let (addr, parts) = start_server();
+
+    // A streamed part whose length isn't known up front
+    let stream =
+        futures_util::stream::iter((0..CHUNKS).map(|index| Ok::<_, Infallible>(chunk(index))));
+    let file = ByteStream::new(SdkBody::from_body_0_4(hyper::Body::wrap_stream(stream)));
+    // Looks like a delimiter, but isn't one for the generated boundary
+    let metadata = b"{\"note\":\"\r\n--smithy-boundary-\"}".to_vec();
+    let body = MultipartBody::builder()
+        .part(Part::new("metadata", metadata.clone()).content_type("application/json"))
+        .part(
+            Part::new("file", file)
+                .file_name("data.bin")
+                .content_type("application/octet-stream"),
+        )
+        .build()
+        .unwrap();
+    let content_type = body.content_type();
+    let body = body.into_sdk_body();
+    assert!(body.bytes().is_none(), "the streamed part isn't buffered");
+
+    let client = hyper::Client::builder().build_http::<SdkBody>();
+    let request = http_02x::Request::post(format!("http://{addr}/upload"))
+        .header("content-type", content_type)
+        .body(body)
+        .unwrap();
+    let response = client.request(request).await.unwrap();
+    assert_eq!(200, response.status().as_u16());
+
+    let parts = parts.recv().unwrap();
+    assert_eq!(2, parts.len());
+    assert_eq!(
+        vec![
+            "Content-Disposition: form-data; name=\"metadata\"",
+            "Content-Type: application/json"
+        ],
+        parts[0].0
+    );
+    assert_eq!(metadata, parts[0].1);
+    assert_eq!(
+        vec![
+            "Content-Disposition: form-data; name=\"file\"; filename=\"data.bin\"",
+            "Content-Type: application/octet-stream"
+        ],
+        parts[1].0
+    );
+    let expected: Vec<u8> = (0..CHUNKS)
+        .flat_map(|index| chunk(index).to_vec())
+        .collect();
+    assert_eq!(5 * 1024 * 1024, expected.len());
+    assert!(expected == parts[1].1, "streamed part was corrupted");
+}
@@ -8,7 +8,10 @@ use aws_smithy_types::Number;
 use ErrorKind::*;
 
 pub mod error;
+pub mod field_errors;
+pub mod incremental;
 pub mod token;
+pub mod unknown_fields;
 
 pub use token::{EscapeError, EscapedStr, Offset, Token};
 
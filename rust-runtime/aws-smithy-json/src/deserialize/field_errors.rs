@@ -0,0 +1,163 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Lenient deserialization of optional scalar values.
+//!
+//! See [`aws_smithy_types::field_errors`] for how lenient deserialization is enabled.
+
+use crate::deserialize::error::DeserializeError as Error;
+use crate::deserialize::token::skip_to_end;
+use crate::deserialize::unknown_fields::pointer_to;
+use crate::deserialize::Token;
+use aws_smithy_types::field_errors::{self, FieldError, MAX_RECORDED_FIELD_ERRORS};
+use std::cell::RefCell;
+use std::iter::Peekable;
+
+thread_local! {
+    // Offsets of the values that failed to parse along with their errors. These are converted into
+    // JSON pointers by the enclosing `FieldErrorScope` since nested parsers don't have the whole
+    // document.
+    static PENDING_ERRORS: RefCell<Vec<(usize, Error)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Parses a scalar value with `parse`, which must consume exactly one token.
+///
+/// If deserialization is lenient and the value fails to parse, the error is recorded so that the
+/// enclosing [`FieldErrorScope`] can report it, the rest of the value is skipped, and `None` is
+/// returned. Errors in the JSON document itself (as opposed to values that don't match the model)
+/// are never recovered from.
+pub fn lenient_scalar<'a, I, T>(
+    tokens: &mut Peekable<I>,
+    parse: impl FnOnce(&mut Peekable<I>) -> Result<Option<T>, Error>,
+) -> Result<Option<T>, Error>
+where
+    I: Iterator<Item = Result<Token<'a>, Error>>,
+{
+    let (offset, compound) = match tokens.peek() {
+        Some(Ok(token)) => (
+            token.offset().0,
+            matches!(token, Token::StartObject { .. } | Token::StartArray { .. }),
+        ),
+        _ => return parse(tokens),
+    };
+    match parse(tokens) {
+        Err(err) if field_errors::is_lenient() => {
+            if compound {
+                // `parse` consumed the start of an object or array, so skip the rest of it
+                skip_to_end(tokens)?;
+            }
+            PENDING_ERRORS.with(|pending| {
+                let mut pending = pending.borrow_mut();
+                if pending.len() < MAX_RECORDED_FIELD_ERRORS {
+                    pending.push((offset, err));
+                }
+            });
+            Ok(None)
+        }
+        result => result,
+    }
+}
+
+/// Reports the field errors recorded while parsing `document` when dropped.
+#[derive(Debug)]
+pub struct FieldErrorScope<'a> {
+    document: &'a [u8],
+    start: Option<usize>,
+}
+
+impl<'a> FieldErrorScope<'a> {
+    /// Creates a new scope for the given JSON document.
+    pub fn new(document: &'a [u8]) -> Self {
+        let start = field_errors::is_lenient()
+            .then(|| PENDING_ERRORS.with(|pending| pending.borrow().len()));
+        Self { document, start }
+    }
+}
+
+impl Drop for FieldErrorScope<'_> {
+    fn drop(&mut self) {
+        let start = match self.start {
+            Some(start) => start,
+            None => return,
+        };
+        let errors: Vec<(usize, Error)> =
+            PENDING_ERRORS.with(|pending| pending.borrow_mut().drain(start..).collect());
+        for (offset, err) in errors {
+            field_errors::record(|| {
+                let path =
+                    pointer_to(self.document, offset).unwrap_or_else(|| format!("@{offset}"));
+                FieldError::new(path, err)
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::deserialize::json_token_iter;
+    use crate::deserialize::token::{
+        expect_start_array, expect_start_object, expect_timestamp_or_null,
+    };
+    use aws_smithy_types::date_time::Format;
+    use aws_smithy_types::field_errors::deserialize_leniently;
+    use aws_smithy_types::DateTime;
+
+    // Mimics a generated parser for a list of optional timestamps
+    fn parse(doc: &[u8]) -> Result<Vec<Option<DateTime>>, Error> {
+        let _scope = FieldErrorScope::new(doc);
+        let mut tokens = json_token_iter(doc).peekable();
+        let tokens = &mut tokens;
+        expect_start_object(tokens.next())?;
+        let mut items = Vec::new();
+        loop {
+            match tokens.next().transpose()? {
+                Some(Token::EndObject { .. }) => break,
+                Some(Token::ObjectKey { .. }) => {
+                    expect_start_array(tokens.next())?;
+                    loop {
+                        if let Some(Ok(Token::EndArray { .. })) = tokens.peek() {
+                            tokens.next();
+                            break;
+                        }
+                        items.push(lenient_scalar(tokens, |tokens| {
+                            expect_timestamp_or_null(tokens.next(), Format::DateTime)
+                        })?);
+                    }
+                }
+                other => panic!("unexpected token: {other:?}"),
+            }
+        }
+        Ok(items)
+    }
+
+    const DOC: &[u8] = br#"{"items": ["2024-01-01T00:00:00Z", "not a timestamp", {"a": [1]}, "2024-01-02T00:00:00Z"]}"#;
+
+    #[test]
+    fn strict_by_default() {
+        assert!(parse(DOC).is_err());
+        PENDING_ERRORS.with(|pending| assert!(pending.borrow().is_empty()));
+    }
+
+    #[test]
+    fn lenient_parsing_drops_and_records_bad_values() {
+        let (items, errors) = deserialize_leniently(|| parse(DOC));
+        let items = items.unwrap();
+        assert_eq!(4, items.len());
+        assert!(items[0].is_some());
+        assert!(items[1].is_none());
+        assert!(items[2].is_none());
+        assert!(items[3].is_some());
+        let paths: Vec<_> = errors.errors().iter().map(FieldError::path).collect();
+        assert_eq!(vec!["/items/1", "/items/2"], paths);
+    }
+
+    #[test]
+    fn malformed_documents_still_fail() {
+        let (result, errors) = deserialize_leniently(|| parse(br#"{"items": ["#));
+        assert!(result.is_err());
+        assert!(errors.is_empty());
+    }
+}
@@ -0,0 +1,333 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Incremental reading of a large list out of a JSON document that arrives in chunks.
+//!
+//! [`json_token_iter`](crate::deserialize::json_token_iter) needs the whole document in memory.
+//! For documents whose size is dominated by a single list in the top-level object, such as
+//! the results of a `List` operation, [`ListItemReader`] splits that list into its items as the
+//! document arrives so that only one item needs to be held in memory at a time. Everything
+//! else in the document is kept so it can be parsed normally once the document is complete.
+
+use crate::deserialize::error::{DeserializeError as Error, DeserializeErrorKind as ErrorKind};
+
+/// Pull-based reader that splits a list member of a top-level JSON object into its items.
+///
+/// Chunks of the document are fed in with [`push`](ListItemReader::push), and complete list
+/// items are pulled out with [`next_item`](ListItemReader::next_item). Each item is returned as
+/// the raw JSON bytes of that item, ready to be parsed with
+/// [`json_token_iter`](crate::deserialize::json_token_iter). Once the document has been fully
+/// pushed, [`finish`](ListItemReader::finish) returns the rest of the document with the list
+/// emptied out.
+///
+/// The reader only finds the boundaries of values. It doesn't validate the document, so errors
+/// in the items or in the rest of the document are reported when they're parsed.
+///
+/// # Examples
+/// ```
+/// use aws_smithy_json::deserialize::incremental::ListItemReader;
+///
+/// let mut reader = ListItemReader::new("Items");
+/// reader.push(br#"{"Items": [{"id": 1}, {"i"#);
+/// assert_eq!(Some(br#"{"id": 1}"#.to_vec()), reader.next_item().unwrap());
+/// assert_eq!(None, reader.next_item().unwrap());
+///
+/// reader.push(br#"d": 2}], "NextToken": "abc"}"#);
+/// assert_eq!(Some(br#"{"id": 2}"#.to_vec()), reader.next_item().unwrap());
+/// assert_eq!(None, reader.next_item().unwrap());
+/// assert_eq!(
+///     br#"{"Items": [], "NextToken": "abc"}"#.to_vec(),
+///     reader.finish().unwrap()
+/// );
+/// ```
+#[derive(Debug)]
+pub struct ListItemReader {
+    list_member: String,
+    // Bytes that have been pushed but not yet scanned, plus the partial item being scanned
+    buffer: Vec<u8>,
+    // Index of the next byte to scan in `buffer`
+    position: usize,
+    // Number of bytes that have been dropped from the front of `buffer`
+    discarded: usize,
+    // The document outside of the list
+    rest: Vec<u8>,
+    // Nesting depth of the document outside of the list
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    top_level_object: bool,
+    expecting_key: bool,
+    // Offset in `rest` of the top-level object key that is being read
+    key_start: Option<usize>,
+    // Whether the next value in the top-level object is the value of the list member
+    list_member_value: bool,
+    in_list: bool,
+    // Offset in `buffer` of the list item that is being read
+    item_start: Option<usize>,
+    // Nesting depth within the list item that is being read
+    item_depth: usize,
+}
+
+impl ListItemReader {
+    /// Creates a reader for the list that is the value of `list_member` in the top-level object.
+    pub fn new(list_member: impl Into<String>) -> Self {
+        Self {
+            list_member: list_member.into(),
+            buffer: Vec::new(),
+            position: 0,
+            discarded: 0,
+            rest: Vec::new(),
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            top_level_object: false,
+            expecting_key: false,
+            key_start: None,
+            list_member_value: false,
+            in_list: false,
+            item_start: None,
+            item_depth: 0,
+        }
+    }
+
+    /// Pushes the next chunk of the document.
+    pub fn push(&mut self, chunk: &[u8]) {
+        // Drop everything that has been scanned and isn't part of an unfinished item
+        let keep_from = self.item_start.unwrap_or(self.position);
+        self.buffer.drain(..keep_from);
+        self.discarded += keep_from;
+        self.position -= keep_from;
+        self.item_start = self.item_start.map(|_| 0);
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Returns the next complete list item, or `None` if more of the document needs to be pushed.
+    pub fn next_item(&mut self) -> Result<Option<Vec<u8>>, Error> {
+        while self.position < self.buffer.len() {
+            let byte = self.buffer[self.position];
+            if !self.in_list {
+                self.scan_document(byte)?;
+            } else if let Some(item_start) = self.item_start {
+                if let Some(item) = self.scan_item(byte, item_start)? {
+                    return Ok(Some(item));
+                }
+            } else {
+                self.scan_between_items(byte);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Finishes reading the document, and returns the document with the list emptied out.
+    ///
+    /// Returns an error if the document is incomplete.
+    pub fn finish(self) -> Result<Vec<u8>, Error> {
+        let complete = !self.in_list
+            && self.position == self.buffer.len()
+            && self.depth == 0
+            && !self.in_string;
+        if complete {
+            Ok(self.rest)
+        } else {
+            Err(Error::new(
+                ErrorKind::UnexpectedEos,
+                Some(self.discarded + self.buffer.len()),
+            ))
+        }
+    }
+
+    fn unexpected(&self, byte: u8, expected: &'static str) -> Error {
+        Error::new(
+            ErrorKind::UnexpectedToken(byte as char, expected),
+            Some(self.discarded + self.position),
+        )
+    }
+
+    // Returns true if the string that `byte` is part of continues after `byte`
+    fn scan_string(&mut self, byte: u8) -> bool {
+        if self.escaped {
+            self.escaped = false;
+        } else if byte == b'\\' {
+            self.escaped = true;
+        } else if byte == b'"' {
+            self.in_string = false;
+        }
+        self.in_string
+    }
+
+    fn scan_document(&mut self, byte: u8) -> Result<(), Error> {
+        if self.in_string {
+            self.rest.push(byte);
+            if !self.scan_string(byte) {
+                if let Some(key_start) = self.key_start.take() {
+                    let key = &self.rest[key_start + 1..self.rest.len() - 1];
+                    self.list_member_value = key == self.list_member.as_bytes();
+                }
+            }
+            self.position += 1;
+            return Ok(());
+        }
+        let list_member_value = self.list_member_value;
+        if !matches!(byte, b' ' | b'\t' | b'\r' | b'\n' | b':') {
+            self.list_member_value = false;
+        }
+        match byte {
+            b'[' if list_member_value && self.depth == 1 => self.in_list = true,
+            b'"' => {
+                self.in_string = true;
+                if self.depth == 1 && self.top_level_object && self.expecting_key {
+                    self.expecting_key = false;
+                    self.key_start = Some(self.rest.len());
+                }
+            }
+            b'{' | b'[' => {
+                if self.depth == 0 && byte == b'{' {
+                    self.top_level_object = true;
+                    self.expecting_key = true;
+                }
+                self.depth += 1;
+            }
+            b'}' | b']' => {
+                self.depth = self
+                    .depth
+                    .checked_sub(1)
+                    .ok_or_else(|| self.unexpected(byte, "a JSON value"))?;
+            }
+            b',' => self.expecting_key = self.depth == 1,
+            _ => {}
+        }
+        self.rest.push(byte);
+        self.position += 1;
+        Ok(())
+    }
+
+    fn scan_between_items(&mut self, byte: u8) {
+        match byte {
+            b' ' | b'\t' | b'\r' | b'\n' | b',' => self.position += 1,
+            b']' => {
+                self.position += 1;
+                self.in_list = false;
+                self.rest.push(byte);
+            }
+            _ => self.item_start = Some(self.position),
+        }
+    }
+
+    fn scan_item(&mut self, byte: u8, item_start: usize) -> Result<Option<Vec<u8>>, Error> {
+        if self.in_string {
+            self.scan_string(byte);
+            self.position += 1;
+            return Ok(None);
+        }
+        match byte {
+            b'"' => self.in_string = true,
+            b'{' | b'[' => self.item_depth += 1,
+            b']' | b',' if self.item_depth == 0 => {
+                // The delimiter after the item is left for `scan_between_items`
+                self.item_start = None;
+                let item = self.buffer[item_start..self.position].trim_ascii_end();
+                return Ok(Some(item.to_vec()));
+            }
+            b'}' | b']' => {
+                self.item_depth = self
+                    .item_depth
+                    .checked_sub(1)
+                    .ok_or_else(|| self.unexpected(byte, "a list item, ',', or ']'"))?;
+            }
+            _ => {}
+        }
+        self.position += 1;
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ListItemReader;
+    use crate::deserialize::error::DeserializeError as Error;
+
+    /// Reads `document` in chunks of `chunk_size` bytes, and returns the items and the rest.
+    fn read(document: &str, chunk_size: usize) -> Result<(Vec<String>, String), Error> {
+        let mut reader = ListItemReader::new("Items");
+        let mut items = Vec::new();
+        for chunk in document.as_bytes().chunks(chunk_size) {
+            reader.push(chunk);
+            while let Some(item) = reader.next_item()? {
+                items.push(String::from_utf8(item).unwrap());
+            }
+        }
+        let rest = String::from_utf8(reader.finish()?).unwrap();
+        Ok((items, rest))
+    }
+
+    fn assert_reads(document: &str, expected_items: &[&str], expected_rest: &str) {
+        for chunk_size in 1..=document.len().max(1) {
+            let (items, rest) = read(document, chunk_size).unwrap();
+            assert_eq!(expected_items, items, "chunk size {chunk_size}");
+            assert_eq!(expected_rest, rest, "chunk size {chunk_size}");
+        }
+    }
+
+    #[test]
+    fn splits_list_items() {
+        assert_reads(
+            r#"{"NextToken": "abc", "Items": [ {"id": 1, "tags": ["a", "b"]} , {"id": 2} ], "Count": 2}"#,
+            &[r#"{"id": 1, "tags": ["a", "b"]}"#, r#"{"id": 2}"#],
+            r#"{"NextToken": "abc", "Items": [], "Count": 2}"#,
+        );
+    }
+
+    #[test]
+    fn strings_can_contain_delimiters() {
+        assert_reads(
+            r#"{"Items": ["a,]", "b\"}]", {"x": "\\"}]}"#,
+            &[r#""a,]""#, r#""b\"}]""#, r#"{"x": "\\"}"#],
+            r#"{"Items": []}"#,
+        );
+    }
+
+    #[test]
+    fn only_the_top_level_member_is_split() {
+        assert_reads(
+            r#"{"Nested": {"Items": [1, 2]}, "Other": "Items", "Items": [3]}"#,
+            &["3"],
+            r#"{"Nested": {"Items": [1, 2]}, "Other": "Items", "Items": []}"#,
+        );
+    }
+
+    #[test]
+    fn missing_and_empty_lists() {
+        assert_reads(r#"{"Items": []}"#, &[], r#"{"Items": []}"#);
+        assert_reads(r#"{"Items": null}"#, &[], r#"{"Items": null}"#);
+        assert_reads(r#"{"Other": [1]}"#, &[], r#"{"Other": [1]}"#);
+        assert_reads("", &[], "");
+    }
+
+    #[test]
+    fn incomplete_documents_fail_to_finish() {
+        for document in [
+            r#"{"Items": [1, 2"#,
+            r#"{"Items": [{"#,
+            r#"{"Other": "x"#,
+            "{",
+        ] {
+            let err = read(document, 4).expect_err("incomplete document");
+            assert!(
+                err.to_string().contains("unexpected end of stream"),
+                "{document}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn unbalanced_closing_brackets_fail() {
+        let err = read(r#"{"Items": [1}]}"#, 100).expect_err("unbalanced");
+        assert_eq!(
+            "Error at offset 12: unexpected token '}'. Expected one of a list item, ',', or ']'",
+            err.to_string()
+        );
+        read("}", 100).expect_err("unbalanced");
+    }
+}
@@ -0,0 +1,199 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Recording of unknown JSON object fields as JSON pointers (RFC 6901).
+//!
+//! See [`aws_smithy_types::unknown_fields`] for how recording is enabled.
+
+use crate::deserialize::error::DeserializeError as Error;
+use crate::deserialize::token::skip_value;
+use crate::deserialize::{json_token_iter, Token};
+use aws_smithy_types::unknown_fields::{self, MAX_RECORDED_UNKNOWN_FIELDS};
+use std::cell::RefCell;
+use std::iter::Peekable;
+
+thread_local! {
+    // Offsets of the values of skipped unknown fields. These are converted into JSON pointers
+    // by the enclosing `UnknownFieldScope` since nested parsers don't have the whole document.
+    static PENDING_OFFSETS: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Skips the value of an object field that isn't part of the model.
+///
+/// If unknown fields are being recorded, the location of the value is recorded so that the
+/// enclosing [`UnknownFieldScope`] can report it.
+pub fn skip_unknown_value<'a, I>(tokens: &mut Peekable<I>) -> Result<(), Error>
+where
+    I: Iterator<Item = Result<Token<'a>, Error>>,
+{
+    if unknown_fields::is_recording() {
+        if let Some(Ok(token)) = tokens.peek() {
+            let offset = token.offset().0;
+            PENDING_OFFSETS.with(|pending| {
+                let mut pending = pending.borrow_mut();
+                if pending.len() < MAX_RECORDED_UNKNOWN_FIELDS {
+                    pending.push(offset);
+                }
+            });
+        }
+    }
+    skip_value(tokens)
+}
+
+/// Reports the unknown fields skipped while parsing `document` when dropped.
+#[derive(Debug)]
+pub struct UnknownFieldScope<'a> {
+    document: &'a [u8],
+    start: Option<usize>,
+}
+
+impl<'a> UnknownFieldScope<'a> {
+    /// Creates a new scope for the given JSON document.
+    pub fn new(document: &'a [u8]) -> Self {
+        let start = unknown_fields::is_recording()
+            .then(|| PENDING_OFFSETS.with(|pending| pending.borrow().len()));
+        Self { document, start }
+    }
+}
+
+impl Drop for UnknownFieldScope<'_> {
+    fn drop(&mut self) {
+        let start = match self.start {
+            Some(start) => start,
+            None => return,
+        };
+        let offsets: Vec<usize> =
+            PENDING_OFFSETS.with(|pending| pending.borrow_mut().drain(start..).collect());
+        for offset in offsets {
+            unknown_fields::record(|| {
+                pointer_to(self.document, offset).unwrap_or_else(|| format!("@{offset}"))
+            });
+        }
+    }
+}
+
+enum Frame {
+    Object(Option<String>),
+    Array(Option<usize>),
+}
+
+/// Returns the JSON pointer of the value that starts at `offset` in `document`.
+pub(crate) fn pointer_to(document: &[u8], offset: usize) -> Option<String> {
+    let mut frames: Vec<Frame> = Vec::new();
+    for token in json_token_iter(document) {
+        let token = token.ok()?;
+        match &token {
+            Token::ObjectKey { key, .. } => {
+                if let Some(Frame::Object(current)) = frames.last_mut() {
+                    *current = Some(key.to_unescaped().ok()?.into_owned());
+                }
+                continue;
+            }
+            Token::EndObject { .. } | Token::EndArray { .. } => {
+                frames.pop();
+                continue;
+            }
+            _ => {}
+        }
+        // Every other token starts a value
+        if let Some(Frame::Array(index)) = frames.last_mut() {
+            *index = Some(index.map(|i| i + 1).unwrap_or_default());
+        }
+        if token.offset().0 == offset {
+            return Some(frames.iter().fold(String::new(), |mut pointer, frame| {
+                pointer.push('/');
+                match frame {
+                    Frame::Object(key) => {
+                        let key = key.as_deref().unwrap_or_default();
+                        pointer.push_str(&key.replace('~', "~0").replace('/', "~1"))
+                    }
+                    Frame::Array(index) => pointer.push_str(&index.unwrap_or_default().to_string()),
+                }
+                pointer
+            }));
+        }
+        match token {
+            Token::StartObject { .. } => frames.push(Frame::Object(None)),
+            Token::StartArray { .. } => frames.push(Frame::Array(None)),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::deserialize::token::expect_start_object;
+    use aws_smithy_types::unknown_fields::record_unknown_fields;
+
+    #[test]
+    fn pointers() {
+        let doc = br#"{"a": {"b~/c": [1, {"d": true}]}, "e": null}"#;
+        let find = |needle: &str| {
+            let offset = std::str::from_utf8(doc).unwrap().find(needle).unwrap();
+            pointer_to(doc, offset)
+        };
+        assert_eq!(Some("".into()), pointer_to(doc, 0));
+        assert_eq!(Some("/a".into()), find(r#"{"b"#));
+        assert_eq!(Some("/a/b~0~1c".into()), find("[1"));
+        assert_eq!(Some("/a/b~0~1c/0".into()), find("1,"));
+        assert_eq!(Some("/a/b~0~1c/1/d".into()), find("true"));
+        assert_eq!(Some("/e".into()), find("null"));
+        assert_eq!(None, pointer_to(doc, 1));
+    }
+
+    // Mimics a generated parser that only knows about the `known` field
+    fn parse(doc: &[u8]) -> Result<(), Error> {
+        let _scope = UnknownFieldScope::new(doc);
+        let mut tokens = json_token_iter(doc).peekable();
+        let tokens = &mut tokens;
+        expect_start_object(tokens.next())?;
+        loop {
+            match tokens.next().transpose()? {
+                Some(Token::EndObject { .. }) => break,
+                Some(Token::ObjectKey { key, .. }) => match key.as_escaped_str() {
+                    "known" => parse_nested(tokens)?,
+                    _ => skip_unknown_value(tokens)?,
+                },
+                other => panic!("unexpected token: {other:?}"),
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_nested<'a>(
+        tokens: &mut Peekable<impl Iterator<Item = Result<Token<'a>, Error>>>,
+    ) -> Result<(), Error> {
+        expect_start_object(tokens.next())?;
+        loop {
+            match tokens.next().transpose()? {
+                Some(Token::EndObject { .. }) => break,
+                Some(Token::ObjectKey { .. }) => skip_unknown_value(tokens)?,
+                other => panic!("unexpected token: {other:?}"),
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn records_unknown_fields() {
+        let doc = br#"{"known": {"extra1": [1, 2]}, "extra2": {"x": 1}}"#;
+        let (result, paths) = record_unknown_fields(|| parse(doc));
+        result.unwrap();
+        assert_eq!(vec!["/known/extra1", "/extra2"], paths);
+
+        // nothing leaks into subsequent parses
+        let (result, paths) = record_unknown_fields(|| parse(br#"{"known": {}}"#));
+        result.unwrap();
+        assert!(paths.is_empty());
+    }
+
+    #[test]
+    fn nothing_recorded_when_disabled() {
+        parse(br#"{"known": {"extra1": 1}}"#).unwrap();
+        PENDING_OFFSETS.with(|pending| assert!(pending.borrow().is_empty()));
+    }
+}
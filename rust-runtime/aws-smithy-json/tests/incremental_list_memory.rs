@@ -0,0 +1,121 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Incrementally reading a large list must hold only one chunk and one item in memory at a time.
+
+use aws_smithy_json::deserialize::incremental::ListItemReader;
+use aws_smithy_json::deserialize::token::{expect_number_or_null, expect_start_object, skip_value};
+use aws_smithy_json::deserialize::{json_token_iter, Token};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+        PEAK.fetch_max(allocated, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const ITEMS: u64 = 100_000;
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Produces the chunks of `{"Items": [{"Id": 0, "Name": "item-0"}, ...], "NextToken": "next"}`
+/// without ever holding the whole document in memory.
+fn chunks() -> impl Iterator<Item = Vec<u8>> {
+    let mut next_item = 0;
+    let mut done = false;
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        let mut chunk = Vec::with_capacity(CHUNK_SIZE + 64);
+        if next_item == 0 {
+            chunk.extend_from_slice(br#"{"Items": ["#);
+        }
+        while chunk.len() < CHUNK_SIZE && next_item < ITEMS {
+            if next_item > 0 {
+                chunk.push(b',');
+            }
+            chunk.extend_from_slice(
+                format!(r#"{{"Id": {next_item}, "Name": "item-{next_item}"}}"#).as_bytes(),
+            );
+            next_item += 1;
+        }
+        if next_item == ITEMS {
+            chunk.extend_from_slice(br#"], "NextToken": "next"}"#);
+            done = true;
+        }
+        Some(chunk)
+    })
+}
+
+fn parse_id(item: &[u8]) -> u64 {
+    let mut tokens = json_token_iter(item).peekable();
+    expect_start_object(tokens.next()).unwrap();
+    let mut id = None;
+    while let Some(token) = tokens.next() {
+        match token.unwrap() {
+            Token::ObjectKey { key, .. } if key.as_escaped_str() == "Id" => {
+                id = expect_number_or_null(tokens.next())
+                    .unwrap()
+                    .map(|n| u64::try_from(n).unwrap());
+            }
+            Token::ObjectKey { .. } => skip_value(&mut tokens).unwrap(),
+            Token::EndObject { .. } => break,
+            other => panic!("unexpected token: {other:?}"),
+        }
+    }
+    id.expect("every item has an ID")
+}
+
+#[test]
+fn peak_memory_is_bounded_and_items_arrive_before_the_document_ends() {
+    let baseline = ALLOCATED.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+
+    let mut reader = ListItemReader::new("Items");
+    let mut document_size = 0;
+    let mut items_read = 0;
+    let mut items_read_before_last_chunk = 0;
+    let mut chunks = chunks().peekable();
+    while let Some(chunk) = chunks.next() {
+        document_size += chunk.len();
+        if chunks.peek().is_none() {
+            items_read_before_last_chunk = items_read;
+        }
+        reader.push(&chunk);
+        drop(chunk);
+        while let Some(item) = reader.next_item().unwrap() {
+            assert_eq!(items_read, parse_id(&item));
+            items_read += 1;
+        }
+    }
+    let rest = reader.finish().unwrap();
+
+    assert_eq!(ITEMS, items_read);
+    assert!(items_read_before_last_chunk > ITEMS * 9 / 10);
+    assert_eq!(br#"{"Items": [], "NextToken": "next"}"#.to_vec(), rest);
+
+    let peak = PEAK.load(Ordering::SeqCst) - baseline;
+    assert!(document_size > 3_000_000, "document size: {document_size}");
+    assert!(
+        peak < 64 * 1024,
+        "peak memory {peak} for a document of {document_size} bytes"
+    );
+}
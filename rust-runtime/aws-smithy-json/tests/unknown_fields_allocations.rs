@@ -0,0 +1,68 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Unknown field recording must not allocate when it is disabled.
+
+use aws_smithy_json::deserialize::error::DeserializeError as Error;
+use aws_smithy_json::deserialize::token::{expect_start_object, skip_value};
+use aws_smithy_json::deserialize::unknown_fields::{skip_unknown_value, UnknownFieldScope};
+use aws_smithy_json::deserialize::{json_token_iter, Token};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const DOC: &[u8] = br#"{"known": 1, "extra": {"nested": [1, 2, 3]}, "another": "x"}"#;
+
+fn parse(doc: &[u8], record: bool) -> Result<(), Error> {
+    let _scope = record.then(|| UnknownFieldScope::new(doc));
+    let mut tokens = json_token_iter(doc).peekable();
+    let tokens = &mut tokens;
+    expect_start_object(tokens.next())?;
+    loop {
+        match tokens.next().transpose()? {
+            Some(Token::EndObject { .. }) => break,
+            Some(Token::ObjectKey { key, .. }) => match key.as_escaped_str() {
+                "known" => skip_value(tokens)?,
+                _ if record => skip_unknown_value(tokens)?,
+                _ => skip_value(tokens)?,
+            },
+            other => panic!("unexpected token: {other:?}"),
+        }
+    }
+    Ok(())
+}
+
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    f();
+    ALLOCATIONS.load(Ordering::SeqCst) - before
+}
+
+#[test]
+fn disabled_recording_allocates_nothing_extra() {
+    // warm up the thread locals
+    parse(DOC, true).unwrap();
+
+    let baseline = allocations(|| parse(DOC, false).unwrap());
+    let disabled = allocations(|| parse(DOC, true).unwrap());
+    assert_eq!(baseline, disabled);
+}
@@ -0,0 +1,130 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Example of checking that retry and timeout settings handle a misbehaving service
+//!
+//! A generated client is tested the same way: the rules of a scenario are created with
+//! `|| mock!(Client::get_item)`, and its interceptor is added to the client's config.
+
+use aws_smithy_async::rt::sleep::TokioSleep;
+use aws_smithy_mocks_experimental::scenarios::{Expectation, Scenario};
+use aws_smithy_mocks_experimental::{MockResponseInterceptor, RuleBuilder};
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::retries::classifiers::{
+    HttpStatusCodeClassifier, TransientErrorClassifier,
+};
+use aws_smithy_runtime_api::client::orchestrator::{HttpResponse, OrchestratorError};
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::retry::RetryConfig;
+use aws_smithy_types::timeout::TimeoutConfig;
+use std::fmt;
+use std::future::pending;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// How long an attempt may take before it times out.
+const ATTEMPT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How many attempts a request may take.
+const MAX_ATTEMPTS: u32 = 3;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    // Don't wait in real time for the backoff between attempts, or for slow responses.
+    tokio::time::pause();
+
+    // The service throttles two attempts: the third attempt succeeds.
+    let (mocks, expectation) =
+        Scenario::ThrottleThenSucceed { throttles: 2 }.build(get_item_rule, item);
+    get_item(mocks, &expectation).await;
+
+    // The first two attempts time out: the third one responds just in time.
+    let (mocks, expectation) = Scenario::SlowResponsesNearTimeout {
+        timeouts: 2,
+        attempt_timeout: ATTEMPT_TIMEOUT,
+    }
+    .build(get_item_rule, item);
+    get_item(mocks, &expectation).await;
+}
+
+/// Gets an item from a service that misbehaves as `mocks` say, and checks that the client
+/// behaved as expected.
+async fn get_item(mocks: MockResponseInterceptor, expectation: &Expectation) {
+    let started = Instant::now();
+    let result = get_item_operation(mocks).invoke("item").await;
+    expectation.observe(&result);
+    expectation.assert_met(started.elapsed());
+    println!(
+        "got {:?} after {} attempts and {:?}",
+        result.unwrap(),
+        expectation.attempts(),
+        started.elapsed()
+    );
+}
+
+#[derive(Debug)]
+struct GetItemError;
+
+impl fmt::Display for GetItemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the item couldn't be retrieved")
+    }
+}
+
+impl std::error::Error for GetItemError {}
+
+#[derive(Debug)]
+#[allow(dead_code)]
+struct GetItemOutput(&'static str);
+
+fn item() -> GetItemOutput {
+    GetItemOutput("item")
+}
+
+fn get_item_rule() -> RuleBuilder<&'static str, GetItemOutput, GetItemError> {
+    RuleBuilder::new(
+        || "",
+        pending::<Result<GetItemOutput, SdkError<GetItemError, HttpResponse>>>,
+    )
+}
+
+/// The operation under test, with the retry and timeout settings of the application.
+fn get_item_operation(
+    mocks: MockResponseInterceptor,
+) -> Operation<&'static str, GetItemOutput, GetItemError> {
+    Operation::builder()
+        .service_name("items")
+        .operation_name("GetItem")
+        .no_auth()
+        .standard_retry(&RetryConfig::standard().with_max_attempts(MAX_ATTEMPTS))
+        .retry_classifier(HttpStatusCodeClassifier::default())
+        .retry_classifier(TransientErrorClassifier::<GetItemError>::new())
+        .timeout_config(
+            TimeoutConfig::builder()
+                .operation_attempt_timeout(ATTEMPT_TIMEOUT)
+                .build(),
+        )
+        .sleep_impl(TokioSleep::new())
+        .endpoint_url("http://localhost:1234")
+        .http_client(infallible_client_fn(|_| {
+            http_02x::Response::builder().status(200).body("").unwrap()
+        }))
+        .interceptor(mocks)
+        .serializer(|input: &'static str| {
+            Ok(http_02x::Request::new(SdkBody::from(input))
+                .try_into()
+                .unwrap())
+        })
+        .deserializer(|response: &HttpResponse| {
+            if response.status().is_success() {
+                Ok(GetItemOutput("unmocked"))
+            } else {
+                Err(OrchestratorError::operation(GetItemError))
+            }
+        })
+        .build()
+}
@@ -9,22 +9,40 @@
 use std::collections::VecDeque;
 use std::fmt::{Debug, Formatter};
 use std::future::Future;
+use std::io;
 use std::marker::PhantomData;
+use std::mem;
+use std::pin::Pin;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
 
+use aws_smithy_async::rt::sleep::Sleep;
+use aws_smithy_async::sync::SharedCell;
+use aws_smithy_http::event_stream::{FromReceiver, Receiver};
 use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpClient,
+    SharedHttpConnector,
+};
 use aws_smithy_runtime_api::client::interceptors::context::{
-    BeforeDeserializationInterceptorContextMut, BeforeSerializationInterceptorContextMut, Error,
-    FinalizerInterceptorContextMut, Input, Output,
+    BeforeDeserializationInterceptorContextMut, BeforeSerializationInterceptorContextMut,
+    BeforeTransmitInterceptorContextMut, Error, FinalizerInterceptorContextMut, Input, Output,
 };
 use aws_smithy_runtime_api::client::interceptors::Intercept;
-use aws_smithy_runtime_api::client::orchestrator::{HttpResponse, OrchestratorError};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse, OrchestratorError};
+use aws_smithy_runtime_api::client::result::ConnectorError;
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_runtime_api::http::{Response, StatusCode};
+use aws_smithy_runtime_api::shared::IntoShared;
 use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::byte_stream::ByteStream;
 use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use bytes::Bytes;
+
+pub mod scenarios;
 
 // why do we need a macro for this?
 // We want customers to be able to provide an ergonomic way to say the method they're looking for,
@@ -48,6 +66,64 @@ use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
 ///   .then_output(||GetObjectOutput::builder().body(ByteStream::from_static(b"12345-abcde")).build());
 /// ```
 ///
+/// **Mock a streaming download**. The output closure is called every time the rule matches, so a
+/// rule that returns a `ByteStream` can be matched any number of times:
+/// ```rust,ignore
+/// use aws_sdk_s3::operation::get_object::GetObjectOutput;
+/// use aws_sdk_s3::Client;
+/// use aws_smithy_types::byte_stream::ByteStream;
+/// use aws_smithy_mocks_experimental::mock;
+/// let get_object = mock!(Client::get_object)
+///   .then_output(||GetObjectOutput::builder().body(ByteStream::from_static(b"12345-abcde")).build());
+/// ```
+///
+/// **Match a streaming upload on its body**:
+/// ```rust,ignore
+/// use aws_sdk_s3::operation::put_object::PutObjectOutput;
+/// use aws_sdk_s3::Client;
+/// use aws_smithy_mocks_experimental::mock;
+/// let put_object = mock!(Client::put_object)
+///   .match_request_body(|body|body == b"12345-abcde")
+///   .then_output(||PutObjectOutput::builder().build());
+/// ```
+///
+/// **Mock an event stream** (this requires the `test-util` feature of the client):
+/// ```rust,ignore
+/// use aws_sdk_s3::types::{SelectObjectContentEventStream, EndEvent};
+/// use aws_sdk_s3::Client;
+/// use aws_smithy_mocks_experimental::mock;
+/// let select_object_content = mock!(Client::select_object_content)
+///   .then_event_stream(||vec![Ok(SelectObjectContentEventStream::End(EndEvent::builder().build()))]);
+/// ```
+///
+/// **Mock a timeout while sending the request**, which is retried like a real one:
+/// ```rust,ignore
+/// use aws_sdk_s3::Client;
+/// use aws_smithy_mocks_experimental::mock;
+/// use aws_smithy_runtime_api::client::result::ConnectorError;
+/// let get_object_timeout = mock!(Client::get_object)
+///   .then_dispatch_failure(||ConnectorError::timeout("connection timed out".into()));
+/// ```
+///
+/// **Mock a slow response**, to test timeouts:
+/// ```rust,ignore
+/// use aws_sdk_s3::operation::get_object::GetObjectOutput;
+/// use aws_sdk_s3::Client;
+/// use aws_smithy_mocks_experimental::mock;
+/// use std::time::Duration;
+/// let slow_get_object = mock!(Client::get_object)
+///   .then_output(||GetObjectOutput::builder().build())
+///   .delay(Duration::from_secs(5));
+/// ```
+///
+/// **Mock a connection that drops while the response body is being received**:
+/// ```rust,ignore
+/// use aws_sdk_s3::Client;
+/// use aws_smithy_mocks_experimental::mock;
+/// let get_object_truncated = mock!(Client::get_object)
+///   .then_http_response_truncated(200, &b"12345"[..], 11);
+/// ```
+///
 /// **Mock and return an error**:
 /// ```rust,ignore
 /// use aws_sdk_s3::operation::get_object::GetObjectError;
@@ -79,6 +155,12 @@ macro_rules! mock {
 
 /// `mock_client!` macro produces a Client configured with a number of Rules and appropriate test default configuration.
 ///
+/// The client sends its requests to a mock HTTP client, and the rules replace its responses.
+///
+/// By default, the client is configured for an AWS SDK crate, which requires a region. For other
+/// generated clients, pass `without_region` as the last argument: the client then only gets its
+/// test defaults and an endpoint URL, so its config must have an `endpoint_url` method.
+///
 /// # Examples
 /// **Create a client that uses a mock failure and then a success**:
 /// ```rust,ignore
@@ -94,33 +176,106 @@ macro_rules! mock {
 ///   .then_error(||GetObjectError::NoSuchKey(NoSuchKey::builder().build()));
 /// let client = mock_client!(aws_sdk_s3, RuleMode::Sequential, &[&get_object_error_path, &get_object_happy_path]);
 /// ```
+///
+/// **Create a client for a generated crate that isn't an AWS SDK crate**:
+/// ```rust,ignore
+/// use my_service::operation::get_item::GetItemOutput;
+/// use my_service::Client;
+/// use aws_smithy_mocks_experimental::{mock_client, mock, RuleMode};
+/// let get_item = mock!(Client::get_item).then_output(||GetItemOutput::builder().build());
+/// let client = mock_client!(my_service, RuleMode::Sequential, &[&get_item], without_region);
+/// ```
 #[macro_export]
 macro_rules! mock_client {
     ($aws_crate: ident, $rules: expr) => {
-        mock_client!($aws_crate, $crate::RuleMode::Sequential, $rules)
+        $crate::mock_client!($aws_crate, $crate::RuleMode::Sequential, $rules)
     };
-    ($aws_crate: ident, $rule_mode: expr, $rules: expr) => {{
-        let mut mock_response_interceptor =
-            $crate::MockResponseInterceptor::new().rule_mode($rule_mode);
-        for rule in $rules {
-            mock_response_interceptor = mock_response_interceptor.with_rule(rule)
-        }
+    ($aws_crate: ident, $rule_mode: expr, $rules: expr) => {
         $aws_crate::client::Client::from_conf(
             $aws_crate::config::Config::builder()
                 .with_test_defaults()
                 .region($aws_crate::config::Region::from_static("us-east-1"))
-                .interceptor(mock_response_interceptor)
+                .http_client($crate::create_mock_http_client())
+                .interceptor($crate::__mock_response_interceptor!($rule_mode, $rules))
                 .build(),
         )
+    };
+    ($crate_name: ident, $rule_mode: expr, $rules: expr, without_region) => {
+        $crate_name::client::Client::from_conf(
+            $crate_name::config::Config::builder()
+                .with_test_defaults()
+                .endpoint_url("http://localhost:1234")
+                .http_client($crate::create_mock_http_client())
+                .interceptor($crate::__mock_response_interceptor!($rule_mode, $rules))
+                .build(),
+        )
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __mock_response_interceptor {
+    ($rule_mode: expr, $rules: expr) => {{
+        let mut mock_response_interceptor =
+            $crate::MockResponseInterceptor::new().rule_mode($rule_mode);
+        for rule in $rules {
+            mock_response_interceptor = mock_response_interceptor.with_rule(rule)
+        }
+        mock_response_interceptor
     }};
 }
 
+/// Returns an HTTP client that responds to every request with an empty `200 OK` response.
+///
+/// The rules of a [`MockResponseInterceptor`] replace these responses, so a client using both
+/// never sends requests over the network. This client is also what collects streaming request
+/// bodies for rules that match on them with
+/// [`match_request_body`](RuleBuilder::match_request_body).
+pub fn create_mock_http_client() -> SharedHttpClient {
+    #[derive(Clone, Debug)]
+    struct MockHttpClient;
+
+    impl HttpConnector for MockHttpClient {
+        fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+            HttpConnectorFuture::new(async move {
+                let mut request = request
+                    .try_into_http02x()
+                    .map_err(|err| ConnectorError::other(err.into(), None))?;
+                if let Some(deferred) = request.extensions().get::<DeferredBodyMatch>().cloned() {
+                    let body = buffer_request_body(request.body_mut()).await?;
+                    deferred.matched.set(BodyMatch::Matched(matching_rule(
+                        deferred.candidates.iter().cloned(),
+                        Some(&body),
+                    )));
+                }
+                Ok(HttpResponse::new(
+                    StatusCode::try_from(200).expect("valid status code"),
+                    SdkBody::empty(),
+                ))
+            })
+        }
+    }
+
+    impl HttpClient for MockHttpClient {
+        fn http_connector(
+            &self,
+            _settings: &HttpConnectorSettings,
+            _components: &RuntimeComponents,
+        ) -> SharedHttpConnector {
+            self.clone().into_shared()
+        }
+    }
+
+    MockHttpClient.into_shared()
+}
+
 type MatchFn = Arc<dyn Fn(&Input) -> bool + Send + Sync>;
+type BodyMatchFn = Arc<dyn Fn(&[u8]) -> bool + Send + Sync>;
 type OutputFn = Arc<dyn Fn() -> Result<Output, OrchestratorError<Error>> + Send + Sync>;
 
 impl Debug for MockResponseInterceptor {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} rules", self.rules.lock().unwrap().len())
+        write!(f, "{} rules", self.rules.snapshot().len())
     }
 }
 
@@ -128,20 +283,29 @@ impl Debug for MockResponseInterceptor {
 enum MockOutput {
     HttpResponse(Arc<dyn Fn() -> Result<HttpResponse, BoxError> + Send + Sync>),
     ModeledResponse(OutputFn),
+    DispatchFailure(Arc<dyn Fn() -> ConnectorError + Send + Sync>),
 }
 
 /// RuleMode describes how rules will be interpreted.
 /// - In RuleMode::MatchAny, the first matching rule will be applied, and the rules will remain unchanged.
 /// - In RuleMode::Sequential, the first matching rule will be applied, and that rule will be removed from the list of rules.
-#[derive()]
+///   Retries of a request are answered by the same rule.
+/// - In RuleMode::SequentialPerAttempt, rules are applied and removed like in RuleMode::Sequential, but every retry of a
+///   request consumes the next rule, so a rule that fails the request can be followed by the rule its retry should match.
+#[derive(Clone, Copy)]
 pub enum RuleMode {
     MatchAny,
     Sequential,
+    SequentialPerAttempt,
 }
 
 /// Interceptor which produces mock responses based on a list of rules
+///
+/// Clones of an interceptor share their rules, so a clone can be kept to check that every rule was
+/// used once the interceptor has been given to a client.
+#[derive(Clone)]
 pub struct MockResponseInterceptor {
-    rules: Arc<Mutex<VecDeque<Rule>>>,
+    rules: Arc<SharedCell<VecDeque<Rule>>>,
     rule_mode: RuleMode,
     must_match: bool,
 }
@@ -155,6 +319,7 @@ impl Default for MockResponseInterceptor {
 pub struct RuleBuilder<I, O, E> {
     _ty: PhantomData<(I, O, E)>,
     input_filter: MatchFn,
+    body_filter: Option<BodyMatchFn>,
 }
 
 impl<I, O, E> RuleBuilder<I, O, E>
@@ -171,6 +336,7 @@ where
         Self {
             _ty: Default::default(),
             input_filter: Arc::new(|i: &Input| i.downcast_ref::<I>().is_some()),
+            body_filter: None,
         }
     }
 
@@ -185,6 +351,24 @@ where
         self
     }
 
+    /// Add an additional filter on the body of the serialized request.
+    ///
+    /// This makes it possible to match on inputs with streaming members, like a `ByteStream`,
+    /// which can't be inspected by [`match_requests`](Self::match_requests).
+    ///
+    /// Bodies that are in memory are matched on right away. Streaming bodies, like one created
+    /// from a file, are collected into memory by the HTTP client from [`create_mock_http_client`],
+    /// which replaces them with the collected (replayable) body, so the rule is picked once the
+    /// request is sent. A streaming body sent with any other HTTP client can't be matched on,
+    /// and panics.
+    pub fn match_request_body(
+        mut self,
+        filter: impl Fn(&[u8]) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.body_filter = Some(Arc::new(filter));
+        self
+    }
+
     /// If the rule matches, then return a specific HTTP response.
     ///
     /// This is the recommended way of testing error behavior.
@@ -194,18 +378,81 @@ where
     ) -> Rule {
         Rule::new(
             self.input_filter,
+            self.body_filter,
             MockOutput::HttpResponse(Arc::new(move || Ok(response()))),
         )
     }
 
+    /// If the rule matches, then return a response with the given status whose `Content-Length`
+    /// is `declared_length`, but whose body fails like a reset connection after `partial_body`.
+    ///
+    /// This simulates a connection dropping after the response headers, and part of the body,
+    /// were received. For a non-streaming operation the body is read in full before it is
+    /// deserialized, so the attempt fails with a response error, which is retried. For a streaming
+    /// operation the output is returned as soon as the headers are received, and the error is only
+    /// returned to the caller reading the body.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `partial_body` isn't shorter than `declared_length`.
+    pub fn then_http_response_truncated(
+        self,
+        status: u16,
+        partial_body: impl Into<Bytes>,
+        declared_length: u64,
+    ) -> Rule {
+        let status = StatusCode::try_from(status).expect("valid status code");
+        let partial_body = partial_body.into();
+        assert!(
+            (partial_body.len() as u64) < declared_length,
+            "a truncated response must declare a longer body than it sends"
+        );
+        self.then_http_response(move || {
+            let mut response = HttpResponse::new(
+                status,
+                SdkBody::from_body_0_4(TruncatedBody {
+                    partial_body: Some(partial_body.clone()),
+                }),
+            );
+            response
+                .headers_mut()
+                .insert("content-length", declared_length.to_string());
+            response
+        })
+    }
+
     /// If a rule matches, then return a specific output
+    ///
+    /// `output` is called every time the rule matches, and the output is never cloned. This means
+    /// that outputs with streaming members, like a `ByteStream`, can be returned by a rule that
+    /// matches more than once.
     pub fn then_output(self, output: impl Fn() -> O + Send + Sync + 'static) -> Rule {
         Rule::new(
             self.input_filter,
+            self.body_filter,
             MockOutput::ModeledResponse(Arc::new(move || Ok(Output::erase(output())))),
         )
     }
 
+    /// If a rule matches, then return an output whose event stream yields `events` and then ends.
+    ///
+    /// `events` is called every time the rule matches. An `Err` event terminates the stream, the
+    /// same way a modeled exception sent by the service would.
+    ///
+    /// The output type must implement [`FromReceiver`], which generated clients do for operations
+    /// with event stream outputs when their `test-util` feature is enabled.
+    pub fn then_event_stream<T, EE>(
+        self,
+        events: impl Fn() -> Vec<Result<T, EE>> + Send + Sync + 'static,
+    ) -> Rule
+    where
+        O: FromReceiver<T, EE>,
+        T: Send + 'static,
+        EE: Send + 'static,
+    {
+        self.then_output(move || O::from_receiver(Receiver::from_events(events())))
+    }
+
     /// If a rule matches, then return a specific error
     ///
     /// Although this _basically_ works, using `then_http_response` is strongly recommended to
@@ -214,18 +461,103 @@ where
     pub fn then_error(self, output: impl Fn() -> E + Send + Sync + 'static) -> Rule {
         Rule::new(
             self.input_filter,
+            self.body_filter,
             MockOutput::ModeledResponse(Arc::new(move || {
                 Err(OrchestratorError::operation(Error::erase(output())))
             })),
         )
     }
+
+    /// If a rule matches, then fail the attempt as if the request couldn't be sent, e.g. because
+    /// of a timeout or a connection reset.
+    ///
+    /// The orchestrator sees a [`ConnectorError`] rather than a response, so the attempt is
+    /// retried according to the classification of the error, and the request fails with
+    /// [`SdkError::DispatchFailure`] if it isn't.
+    ///
+    /// Use [`RuleMode::SequentialPerAttempt`] to have the retries of the request answered by the
+    /// rules that follow this one, e.g. to fail twice and then succeed.
+    pub fn then_dispatch_failure(
+        self,
+        error: impl Fn() -> ConnectorError + Send + Sync + 'static,
+    ) -> Rule {
+        Rule::new(
+            self.input_filter,
+            self.body_filter,
+            MockOutput::DispatchFailure(Arc::new(error)),
+        )
+    }
+}
+
+/// A response body that waits for a delay before it starts sending `inner`.
+struct DelayedBody {
+    delay: Option<Sleep>,
+    inner: SdkBody,
+}
+
+impl http_body_04x::Body for DelayedBody {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        if let Some(delay) = self.delay.as_mut() {
+            if Pin::new(delay).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            self.delay = None;
+        }
+        Pin::new(&mut self.inner).poll_data(cx)
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http_02x::HeaderMap>, Self::Error>> {
+        Pin::new(&mut self.inner).poll_trailers(cx)
+    }
+}
+
+/// A response body that sends part of the body, and then fails like a connection that was reset.
+struct TruncatedBody {
+    partial_body: Option<Bytes>,
+}
+
+impl http_body_04x::Body for TruncatedBody {
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        Poll::Ready(Some(match self.partial_body.take() {
+            Some(partial_body) => Ok(partial_body),
+            None => Err(io::Error::new(
+                io::ErrorKind::ConnectionReset,
+                "connection reset before the response body was complete",
+            )),
+        }))
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http_02x::HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(None))
+    }
 }
 
 #[derive(Clone)]
 pub struct Rule {
     matcher: MatchFn,
+    body_matcher: Option<BodyMatchFn>,
     output: MockOutput,
+    delay: Option<Duration>,
     used_count: Arc<AtomicUsize>,
+    added_count: Arc<AtomicUsize>,
 }
 
 impl Debug for Rule {
@@ -235,13 +567,32 @@ impl Debug for Rule {
 }
 
 impl Rule {
-    fn new(matcher: MatchFn, output: MockOutput) -> Self {
+    fn new(matcher: MatchFn, body_matcher: Option<BodyMatchFn>, output: MockOutput) -> Self {
         Self {
             matcher,
+            body_matcher,
             output,
+            delay: None,
             used_count: Default::default(),
+            added_count: Default::default(),
         }
     }
+
+    /// Delays the response of this rule by `delay`.
+    ///
+    /// The delay is waited for asynchronously with the sleep implementation of the client, while
+    /// the response body is read. With a `tokio` sleep implementation, it can be skipped through
+    /// by pausing time in the test. A delay longer than the timeouts of the client makes the
+    /// attempt, or the operation, time out. The rule is still counted by
+    /// [`num_calls`](Self::num_calls) when it does.
+    ///
+    /// For an operation with a streaming response, the output is returned without waiting, and the
+    /// delay is only observed by the caller reading the body.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
     fn record_usage(&self) {
         self.used_count.fetch_add(1, Ordering::Relaxed);
     }
@@ -250,6 +601,14 @@ impl Rule {
     pub fn num_calls(&self) -> usize {
         self.used_count.load(Ordering::Relaxed)
     }
+
+    /// Returns `true` if this rule has been hit at least once for every time it was added to an
+    /// interceptor with [`MockResponseInterceptor::with_rule`].
+    ///
+    /// A rule that was never added is exhausted once it has been hit.
+    pub fn is_exhausted(&self) -> bool {
+        self.num_calls() >= self.added_count.load(Ordering::Relaxed).max(1)
+    }
 }
 
 #[derive(Debug)]
@@ -258,6 +617,64 @@ impl Storable for ActiveRule {
     type Storer = StoreReplace<ActiveRule>;
 }
 
+/// Rules that matched the input, which still need to be matched against the request body.
+#[derive(Debug)]
+struct CandidateRules(Vec<Rule>);
+impl Storable for CandidateRules {
+    type Storer = StoreReplace<CandidateRules>;
+}
+
+/// Rules that match on the body of a request whose body is streaming.
+///
+/// Interceptor hooks can't wait for a streaming body, so the HTTP client from
+/// [`create_mock_http_client`] collects the body and picks the rule that matches it.
+#[derive(Clone, Debug)]
+struct DeferredBodyMatch {
+    candidates: Arc<Vec<Rule>>,
+    matched: Arc<SharedCell<BodyMatch>>,
+}
+impl Storable for DeferredBodyMatch {
+    type Storer = StoreReplace<DeferredBodyMatch>;
+}
+
+#[derive(Debug)]
+enum BodyMatch {
+    /// The HTTP client hasn't collected the body
+    Pending,
+    /// The rule that matched the collected body, if any
+    Matched(Option<Rule>),
+}
+
+/// Returns the first rule that matches `body`, skipping the body matchers if there's no body.
+fn matching_rule(candidates: impl IntoIterator<Item = Rule>, body: Option<&[u8]>) -> Option<Rule> {
+    candidates
+        .into_iter()
+        .find(|rule| match (&rule.body_matcher, body) {
+            (Some(body_matcher), Some(body)) => body_matcher(body),
+            _ => true,
+        })
+}
+
+/// Collects a streaming request body into memory, and replaces it with the collected body.
+async fn buffer_request_body(body: &mut SdkBody) -> Result<Bytes, ConnectorError> {
+    let streaming = mem::replace(body, SdkBody::taken());
+    let bytes = ByteStream::new(streaming)
+        .collect()
+        .await
+        .map_err(|err| ConnectorError::io(err.into()))?
+        .into_bytes();
+    *body = SdkBody::from(bytes.clone());
+    Ok(bytes)
+}
+
+/// In [`RuleMode::SequentialPerAttempt`], whether each of the rules that followed the first rule when the
+/// request was serialized matches its input. Retries consume these rules in order.
+#[derive(Debug, Clone)]
+struct RetryRuleMatches(VecDeque<bool>);
+impl Storable for RetryRuleMatches {
+    type Storer = StoreReplace<RetryRuleMatches>;
+}
+
 impl MockResponseInterceptor {
     pub fn new() -> Self {
         Self {
@@ -270,10 +687,35 @@ impl MockResponseInterceptor {
     ///
     /// Rules are matched in order—this rule will only apply if all previous rules do not match.
     pub fn with_rule(self, rule: &Rule) -> Self {
-        self.rules.lock().unwrap().push_back(rule.clone());
+        rule.added_count.fetch_add(1, Ordering::Relaxed);
+        self.rules.update(|rules| rules.push_back(rule.clone()));
         self
     }
 
+    /// Returns the number of rules that haven't been used yet.
+    ///
+    /// In `RuleMode::Sequential` and `RuleMode::SequentialPerAttempt`, these are the rules that are
+    /// still waiting for a request. In `RuleMode::MatchAny`, these are the rules that haven't
+    /// matched any request.
+    pub fn remaining_rules(&self) -> usize {
+        let rules = self.rules.snapshot();
+        match self.rule_mode {
+            RuleMode::Sequential | RuleMode::SequentialPerAttempt => rules.len(),
+            RuleMode::MatchAny => rules.iter().filter(|rule| rule.num_calls() == 0).count(),
+        }
+    }
+
+    /// Panics if any rule hasn't been used yet, see [`remaining_rules`](Self::remaining_rules).
+    ///
+    /// Call this at the end of a test to catch rules that were never used, e.g. because of a
+    /// mistake in their matcher.
+    pub fn expect_all_rules_consumed(&self) {
+        let remaining = self.remaining_rules();
+        if remaining > 0 {
+            panic!("{remaining} rule(s) were never used");
+        }
+    }
+
     /// Set the RuleMode to use when evaluating rules.
     ///
     /// See `RuleMode` enum for modes and how they are applied.
@@ -286,6 +728,26 @@ impl MockResponseInterceptor {
         self.must_match = false;
         self
     }
+
+    /// Uses `rule` to answer the request, if it matched the request body.
+    fn activate(&self, rule: Option<Rule>, cfg: &mut ConfigBag) {
+        match rule {
+            Some(rule) => {
+                cfg.interceptor_state().store_put(ActiveRule(rule));
+            }
+            None => {
+                if matches!(
+                    self.rule_mode,
+                    RuleMode::Sequential | RuleMode::SequentialPerAttempt
+                ) {
+                    panic!("In order matching was enforced but the next rule did not match the request body");
+                }
+                if self.must_match {
+                    panic!("must_match was enabled but no rules matches the request body");
+                }
+            }
+        }
+    }
 }
 
 impl Intercept for MockResponseInterceptor {
@@ -299,11 +761,11 @@ impl Intercept for MockResponseInterceptor {
         _runtime_components: &RuntimeComponents,
         cfg: &mut ConfigBag,
     ) -> Result<(), BoxError> {
-        let mut rules = self.rules.lock().unwrap();
-        let rule = match self.rule_mode {
-            RuleMode::Sequential => {
-                let rule = rules
-                    .pop_front()
+        let candidates: Vec<Rule> = match self.rule_mode {
+            RuleMode::Sequential | RuleMode::SequentialPerAttempt => {
+                let rule = self
+                    .rules
+                    .update(|rules| rules.pop_front())
                     .expect("no more rules but a new request was received");
                 if !(rule.matcher)(context.input()) {
                     panic!(
@@ -311,48 +773,129 @@ impl Intercept for MockResponseInterceptor {
                         context.input()
                     );
                 }
-                Some(rule)
+                if matches!(self.rule_mode, RuleMode::SequentialPerAttempt) {
+                    // The input is gone by the time the request is retried, so the rules retries
+                    // could consume are matched against it now.
+                    let retry_rule_matches = self
+                        .rules
+                        .snapshot()
+                        .iter()
+                        .map(|rule| (rule.matcher)(context.input()))
+                        .collect();
+                    cfg.interceptor_state()
+                        .store_put(RetryRuleMatches(retry_rule_matches));
+                }
+                vec![rule]
             }
-            RuleMode::MatchAny => rules
+            RuleMode::MatchAny => self
+                .rules
+                .snapshot()
                 .iter()
-                .find(|rule| (rule.matcher)(context.input()))
-                .cloned(),
+                .filter(|rule| (rule.matcher)(context.input()))
+                .cloned()
+                .collect(),
         };
-        match rule {
-            Some(rule) => {
-                cfg.interceptor_state().store_put(ActiveRule(rule.clone()));
-            }
-            None => {
-                if self.must_match {
-                    panic!(
-                        "must_match was enabled but no rules matches {:?}",
-                        context.input()
-                    );
+        if candidates.is_empty() && self.must_match {
+            panic!(
+                "must_match was enabled but no rules matches {:?}",
+                context.input()
+            );
+        }
+        // The input is consumed by serialization, so rules that also match on the request body
+        // are picked once the request has been serialized.
+        cfg.interceptor_state()
+            .store_put(CandidateRules(candidates));
+        Ok(())
+    }
+
+    fn modify_before_transmit(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let candidates = match cfg.load::<CandidateRules>() {
+            Some(candidates) => candidates.0.clone(),
+            None => return Ok(()),
+        };
+        let retrying = cfg.load::<ActiveRule>().is_some();
+        let candidates = match self.rule_mode {
+            RuleMode::SequentialPerAttempt if retrying => {
+                let mut retry_rule_matches = cfg
+                    .load::<RetryRuleMatches>()
+                    .cloned()
+                    .expect("set in `modify_before_serialization`");
+                let (rule, matches) = self
+                    .rules
+                    .update(|rules| rules.pop_front())
+                    .zip(retry_rule_matches.0.pop_front())
+                    .expect("no more rules but a request was retried");
+                if !matches {
+                    panic!("In order matching was enforced but the next rule did not match the retried request");
                 }
+                cfg.interceptor_state().store_put(retry_rule_matches);
+                vec![rule]
             }
-        }
+            _ => candidates,
+        };
+        let matches_body = candidates.iter().any(|rule| rule.body_matcher.is_some());
+        cfg.interceptor_state().unset::<DeferredBodyMatch>();
+        let rule = match context.request().body().bytes() {
+            Some(body) => matching_rule(candidates, matches_body.then_some(body)),
+            None if matches_body => {
+                // The mock HTTP client picks the rule once it has collected the body
+                let deferred = DeferredBodyMatch {
+                    candidates: Arc::new(candidates),
+                    matched: Arc::new(SharedCell::new(BodyMatch::Pending)),
+                };
+                context.request_mut().add_extension(deferred.clone());
+                cfg.interceptor_state().store_put(deferred);
+                return Ok(());
+            }
+            None => matching_rule(candidates, None),
+        };
+        self.activate(rule, cfg);
         Ok(())
     }
 
     fn modify_before_deserialization(
         &self,
         context: &mut BeforeDeserializationInterceptorContextMut<'_>,
-        _runtime_components: &RuntimeComponents,
+        runtime_components: &RuntimeComponents,
         cfg: &mut ConfigBag,
     ) -> Result<(), BoxError> {
+        if let Some(deferred) = cfg.load::<DeferredBodyMatch>().cloned() {
+            cfg.interceptor_state().unset::<DeferredBodyMatch>();
+            match &*deferred.matched.snapshot() {
+                BodyMatch::Matched(rule) => self.activate(rule.clone(), cfg),
+                BodyMatch::Pending => panic!(
+                    "a rule matches on a streaming request body, which can only be collected by \
+                     the HTTP client from `create_mock_http_client`"
+                ),
+            }
+        }
         if let Some(rule) = cfg.load::<ActiveRule>() {
             let rule = &rule.0;
-            let result = match &rule.output {
-                MockOutput::HttpResponse(output_fn) => output_fn(),
-                _ => return Ok(()),
-            };
+            // Rules are counted as soon as they're used, so that a rule whose delay makes the
+            // operation time out is counted too
             rule.record_usage();
-
-            match result {
-                Ok(http_response) => *context.response_mut() = http_response,
-                Err(e) => context
-                    .inner_mut()
-                    .set_output_or_error(Err(OrchestratorError::response(e))),
+            if let MockOutput::HttpResponse(output_fn) = &rule.output {
+                match output_fn() {
+                    Ok(http_response) => *context.response_mut() = http_response,
+                    Err(e) => context
+                        .inner_mut()
+                        .set_output_or_error(Err(OrchestratorError::response(e))),
+                }
+            }
+            if let Some(delay) = rule.delay {
+                let sleep_impl = runtime_components
+                    .sleep_impl()
+                    .ok_or("a sleep implementation is required to delay mocked responses")?;
+                let body = mem::replace(context.response_mut().body_mut(), SdkBody::taken());
+                *context.response_mut().body_mut() = SdkBody::from_body_0_4(DelayedBody {
+                    delay: Some(sleep_impl.sleep(delay)),
+                    inner: body,
+                });
             }
         }
         Ok(())
@@ -366,12 +909,23 @@ impl Intercept for MockResponseInterceptor {
     ) -> Result<(), BoxError> {
         if let Some(rule) = _cfg.load::<ActiveRule>() {
             let rule = &rule.0;
+            // Don't replace the error of an attempt that timed out while its response was delayed
+            if let Some(Err(err)) = context.inner().output_or_error() {
+                if err.is_timeout_error() {
+                    return Ok(());
+                }
+            }
             let result = match &rule.output {
                 MockOutput::ModeledResponse(output_fn) => output_fn(),
+                MockOutput::DispatchFailure(error_fn) => {
+                    context
+                        .inner_mut()
+                        .set_output_or_error(Err(OrchestratorError::connector(error_fn())));
+                    return Ok(());
+                }
                 _ => return Ok(()),
             };
 
-            rule.record_usage();
             if result.is_err() {
                 // the orchestrator will panic of no response is present
                 context.inner_mut().set_response(Response::new(
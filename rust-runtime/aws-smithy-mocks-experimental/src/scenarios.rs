@@ -0,0 +1,377 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Canned misbehavior scenarios for testing retry and timeout configuration.
+//!
+//! A [`Scenario`] describes how a service misbehaves, e.g. by throttling a few requests before
+//! succeeding. [`Scenario::build`] turns it into a [`MockResponseInterceptor`] that plays the
+//! scenario for any operation, and an [`Expectation`] of how a correctly configured client
+//! behaves when it runs into it. Once the test has made its calls, the expectation asserts the
+//! number of attempts that were made, the outcome of each call, and how long the calls took.
+//!
+//! ```rust,ignore
+//! use aws_sdk_s3::operation::get_object::GetObjectOutput;
+//! use aws_sdk_s3::Client;
+//! use aws_smithy_mocks_experimental::mock;
+//! use aws_smithy_mocks_experimental::scenarios::Scenario;
+//!
+//! let (mocks, expectation) = Scenario::ThrottleThenSucceed { throttles: 2 }
+//!     .build(|| mock!(Client::get_object), || GetObjectOutput::builder().build());
+//! let client = Client::from_conf(config.interceptor(mocks).build());
+//!
+//! let started = tokio::time::Instant::now();
+//! let result = client.get_object().bucket("bucket").key("key").send().await;
+//! expectation.observe(&result);
+//! expectation.assert_met(started.elapsed());
+//! ```
+//!
+//! Scenarios delay responses and clients back off between attempts, so tests should use a sleep
+//! implementation that doesn't wait in real time, e.g. `tokio`'s with time paused.
+//!
+//! See the `retry-scenarios` example of this crate for a fully worked example.
+
+use std::fmt::{self, Debug};
+use std::io;
+use std::ops::{Bound, RangeBounds};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::{ConnectorError, SdkError};
+use aws_smithy_runtime_api::http::StatusCode;
+use aws_smithy_types::body::SdkBody;
+
+use crate::{MockResponseInterceptor, Rule, RuleBuilder, RuleMode};
+
+/// A way for a service to misbehave.
+///
+/// Each scenario documents the client configuration it expects. Use the `with_*` methods of the
+/// [`Expectation`] returned by [`build`](Self::build) to test other configurations.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub enum Scenario {
+    /// The service throttles `throttles` attempts with a `503 Service Unavailable` response, and
+    /// then succeeds.
+    ///
+    /// The client is expected to retry until it succeeds, which requires it to make at least
+    /// `throttles + 1` attempts.
+    ThrottleThenSucceed {
+        /// The number of attempts that are throttled.
+        throttles: usize,
+    },
+
+    /// The service responds to every attempt with a `500 Internal Server Error`.
+    ///
+    /// The client is expected to give up with a service error once it has made `max_attempts`
+    /// attempts.
+    PermanentServerError {
+        /// The maximum number of attempts the client is configured to make.
+        max_attempts: usize,
+    },
+
+    /// The service responds to `timeouts` attempts just after `attempt_timeout`, and then to one
+    /// attempt just before it.
+    ///
+    /// The client is expected to time out `timeouts` attempts, and to succeed on the next one.
+    /// This requires an attempt timeout of `attempt_timeout` and at least `timeouts + 1` attempts.
+    /// The calls take at least `timeouts` attempt timeouts, plus the time the last attempt took.
+    SlowResponsesNearTimeout {
+        /// The number of attempts that time out.
+        timeouts: usize,
+        /// The attempt timeout the client is configured with.
+        attempt_timeout: Duration,
+    },
+
+    /// The connection is reset while `resets` attempts are sent, and then the service succeeds.
+    ///
+    /// The client is expected to retry until it succeeds, which requires it to make at least
+    /// `resets + 1` attempts.
+    ConnectionResetStorm {
+        /// The number of attempts whose connection is reset.
+        resets: usize,
+    },
+
+    /// The service rejects the first call with a `403 Forbidden` `ExpiredTokenException`, and
+    /// then succeeds.
+    ///
+    /// The client is expected not to retry the rejected call. Instead, the test makes a second
+    /// call, e.g. after refreshing its credentials, which succeeds.
+    ExpiredCredentialsOnce,
+}
+
+impl Scenario {
+    /// Returns an interceptor that plays this scenario, and the expected behavior of the client.
+    ///
+    /// `rule` is called for each rule of the scenario, so that the scenario can be played for
+    /// any operation, e.g. with `|| mock!(Client::get_object)`. Responses that succeed return
+    /// the output of `output`.
+    ///
+    /// The interceptor must be added to the client before the test makes its calls, and each
+    /// call must be [observed](Expectation::observe).
+    pub fn build<I, O, E>(
+        &self,
+        rule: impl Fn() -> RuleBuilder<I, O, E>,
+        output: impl Fn() -> O + Send + Sync + 'static,
+    ) -> (MockResponseInterceptor, Expectation)
+    where
+        I: Send + Sync + Debug + 'static,
+        O: Send + Sync + Debug + 'static,
+        E: Send + Sync + Debug + std::error::Error + 'static,
+    {
+        let success = rule().then_output(output);
+        let (mocks, rules, attempts, outcomes, min_elapsed) = match *self {
+            Scenario::ThrottleThenSucceed { throttles } => {
+                let throttled = rule().then_http_response(|| response(503, "Service Unavailable"));
+                let mocks = sequence(&[(&throttled, throttles), (&success, 1)]);
+                let rules = vec![throttled, success];
+                (mocks, rules, throttles + 1, vec![Outcome::Success], None)
+            }
+            Scenario::PermanentServerError { max_attempts } => {
+                let failed = rule().then_http_response(|| response(500, "Internal Server Error"));
+                let mocks = MockResponseInterceptor::new().with_rule(&failed);
+                (
+                    mocks,
+                    vec![failed],
+                    max_attempts,
+                    vec![Outcome::ServiceError],
+                    None,
+                )
+            }
+            Scenario::SlowResponsesNearTimeout {
+                timeouts,
+                attempt_timeout,
+            } => {
+                let margin = attempt_timeout / 10;
+                let too_slow = rule()
+                    .then_output(|| panic!("the attempt should have timed out"))
+                    .delay(attempt_timeout + margin);
+                let success = success.delay(attempt_timeout - margin);
+                let mocks = sequence(&[(&too_slow, timeouts), (&success, 1)]);
+                let min_elapsed = attempt_timeout * timeouts as u32 + attempt_timeout - margin;
+                (
+                    mocks,
+                    vec![too_slow, success],
+                    timeouts + 1,
+                    vec![Outcome::Success],
+                    Some(min_elapsed),
+                )
+            }
+            Scenario::ConnectionResetStorm { resets } => {
+                let reset = rule().then_dispatch_failure(|| {
+                    ConnectorError::io(
+                        io::Error::new(io::ErrorKind::ConnectionReset, "connection reset").into(),
+                    )
+                });
+                let mocks = sequence(&[(&reset, resets), (&success, 1)]);
+                (
+                    mocks,
+                    vec![reset, success],
+                    resets + 1,
+                    vec![Outcome::Success],
+                    None,
+                )
+            }
+            Scenario::ExpiredCredentialsOnce => {
+                let expired = rule().then_http_response(expired_token_response);
+                let mocks = sequence(&[(&expired, 1), (&success, 1)]);
+                (
+                    mocks,
+                    vec![expired, success],
+                    2,
+                    vec![Outcome::ServiceError, Outcome::Success],
+                    None,
+                )
+            }
+        };
+        let expectation = Expectation {
+            scenario: self.clone(),
+            rules,
+            attempts,
+            outcomes,
+            elapsed: (
+                min_elapsed.map_or(Bound::Unbounded, Bound::Included),
+                Bound::Unbounded,
+            ),
+            observed: Default::default(),
+        };
+        (mocks, expectation)
+    }
+}
+
+/// Returns an interceptor that uses each rule the given number of times, in order.
+fn sequence(rules: &[(&Rule, usize)]) -> MockResponseInterceptor {
+    let mut mocks = MockResponseInterceptor::new().rule_mode(RuleMode::SequentialPerAttempt);
+    for (rule, times) in rules {
+        for _ in 0..*times {
+            mocks = mocks.with_rule(rule);
+        }
+    }
+    mocks
+}
+
+fn response(status: u16, body: &'static str) -> HttpResponse {
+    HttpResponse::new(
+        StatusCode::try_from(status).expect("valid status code"),
+        SdkBody::from(body),
+    )
+}
+
+fn expired_token_response() -> HttpResponse {
+    let mut response = response(
+        403,
+        r#"{"__type":"ExpiredTokenException","message":"The security token included in the request is expired"}"#,
+    );
+    response
+        .headers_mut()
+        .insert("x-amzn-errortype", "ExpiredTokenException");
+    response
+}
+
+/// The outcome of a call, as returned by the client.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The call succeeded.
+    Success,
+    /// The call failed with [`SdkError::ConstructionFailure`].
+    ConstructionFailure,
+    /// The call failed with [`SdkError::TimeoutError`].
+    TimeoutError,
+    /// The call failed with [`SdkError::DispatchFailure`].
+    DispatchFailure,
+    /// The call failed with [`SdkError::ResponseError`].
+    ResponseError,
+    /// The call failed with [`SdkError::ServiceError`].
+    ServiceError,
+    /// The call failed with [`SdkError::PreconditionFailed`].
+    PreconditionFailed,
+    /// The call failed with another kind of error.
+    Other,
+}
+
+impl Outcome {
+    /// Returns the outcome of a call that returned `result`.
+    pub fn of<O, E, R>(result: &Result<O, SdkError<E, R>>) -> Self {
+        match result {
+            Ok(_) => Outcome::Success,
+            Err(SdkError::ConstructionFailure(_)) => Outcome::ConstructionFailure,
+            Err(SdkError::TimeoutError(_)) => Outcome::TimeoutError,
+            Err(SdkError::DispatchFailure(_)) => Outcome::DispatchFailure,
+            Err(SdkError::ResponseError(_)) => Outcome::ResponseError,
+            Err(SdkError::ServiceError(_)) => Outcome::ServiceError,
+            Err(SdkError::PreconditionFailed(_)) => Outcome::PreconditionFailed,
+            Err(_) => Outcome::Other,
+        }
+    }
+}
+
+/// How a client is expected to behave when it runs into a [`Scenario`].
+///
+/// Every call the test makes must be passed to [`observe`](Self::observe). Then,
+/// [`assert_met`](Self::assert_met) checks the behavior of the client.
+#[derive(Debug)]
+pub struct Expectation {
+    scenario: Scenario,
+    rules: Vec<Rule>,
+    attempts: usize,
+    outcomes: Vec<Outcome>,
+    elapsed: (Bound<Duration>, Bound<Duration>),
+    observed: Mutex<Vec<Outcome>>,
+}
+
+impl Expectation {
+    /// Expects the client to make `attempts` attempts in total, across all calls.
+    pub fn with_attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Expects the calls the test makes to have `outcomes`, in order.
+    pub fn with_outcomes(mut self, outcomes: impl IntoIterator<Item = Outcome>) -> Self {
+        self.outcomes = outcomes.into_iter().collect();
+        self
+    }
+
+    /// Expects the calls the test makes to take a time within `elapsed`.
+    ///
+    /// This replaces the range set by the scenario, which only has a lower bound when the
+    /// scenario delays responses, since the time the client backs off for is random.
+    pub fn with_elapsed(mut self, elapsed: impl RangeBounds<Duration>) -> Self {
+        self.elapsed = (elapsed.start_bound().cloned(), elapsed.end_bound().cloned());
+        self
+    }
+
+    /// Records the result of a call.
+    pub fn observe<O, E, R>(&self, result: &Result<O, SdkError<E, R>>) {
+        self.observed.lock().unwrap().push(Outcome::of(result));
+    }
+
+    /// Returns the number of attempts the client has made so far.
+    pub fn attempts(&self) -> usize {
+        self.rules.iter().map(Rule::num_calls).sum()
+    }
+
+    /// Panics if the client didn't behave as expected.
+    ///
+    /// `elapsed` is the time the calls took, as measured by the test, e.g. with
+    /// `tokio::time::Instant` when time is paused.
+    pub fn assert_met(&self, elapsed: Duration) {
+        let mut failures = Vec::new();
+        let attempts = self.attempts();
+        if attempts != self.attempts {
+            failures.push(format!(
+                "expected {} attempt(s), but the client made {attempts}",
+                self.attempts
+            ));
+        }
+        let observed = self.observed.lock().unwrap();
+        if *observed != self.outcomes {
+            failures.push(format!(
+                "expected the calls to have outcomes {:?}, but they had {:?}",
+                self.outcomes, *observed
+            ));
+        }
+        if !self.elapsed.contains(&elapsed) {
+            failures.push(format!(
+                "expected the calls to take {}, but they took {elapsed:?}",
+                DisplayRange(&self.elapsed)
+            ));
+        }
+        if !failures.is_empty() {
+            panic!(
+                "the client didn't behave as expected in {:?}:\n  - {}",
+                self.scenario,
+                failures.join("\n  - ")
+            );
+        }
+    }
+}
+
+/// Displays a range of durations the way a person would describe it.
+struct DisplayRange<'a>(&'a (Bound<Duration>, Bound<Duration>));
+
+impl fmt::Display for DisplayRange<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            (Bound::Unbounded, Bound::Unbounded) => write!(f, "any time"),
+            (Bound::Included(start), Bound::Unbounded) => write!(f, "at least {start:?}"),
+            (Bound::Excluded(start), Bound::Unbounded) => write!(f, "more than {start:?}"),
+            (Bound::Unbounded, Bound::Included(end)) => write!(f, "at most {end:?}"),
+            (Bound::Unbounded, Bound::Excluded(end)) => write!(f, "less than {end:?}"),
+            (start, end) => {
+                match start {
+                    Bound::Included(start) => write!(f, "between {start:?} (inclusive)")?,
+                    Bound::Excluded(start) => write!(f, "between {start:?} (exclusive)")?,
+                    Bound::Unbounded => unreachable!(),
+                }
+                match end {
+                    Bound::Included(end) => write!(f, " and {end:?} (inclusive)"),
+                    Bound::Excluded(end) => write!(f, " and {end:?} (exclusive)"),
+                    Bound::Unbounded => unreachable!(),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_async::rt::sleep::TokioSleep;
+use aws_smithy_mocks_experimental::{MockResponseInterceptor, Rule, RuleBuilder, RuleMode};
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_runtime_api::http::StatusCode;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::timeout::TimeoutConfig;
+use std::fmt;
+use std::future::pending;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct TestError;
+
+impl fmt::Display for TestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TestError")
+    }
+}
+
+impl std::error::Error for TestError {}
+
+#[derive(Debug, PartialEq)]
+struct GetItemOutput(String);
+
+fn rule() -> RuleBuilder<&'static str, GetItemOutput, TestError> {
+    RuleBuilder::new(
+        || "",
+        pending::<Result<GetItemOutput, SdkError<TestError, HttpResponse>>>,
+    )
+}
+
+fn item_response() -> HttpResponse {
+    HttpResponse::new(StatusCode::try_from(200).unwrap(), SdkBody::from("item"))
+}
+
+/// An operation that times out after a second, with `rules` applied in order.
+fn operation(rules: &[&Rule]) -> Operation<&'static str, GetItemOutput, TestError> {
+    let mut mocks = MockResponseInterceptor::new().rule_mode(RuleMode::Sequential);
+    for rule in rules {
+        mocks = mocks.with_rule(rule);
+    }
+    Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .no_retry()
+        .timeout_config(
+            TimeoutConfig::builder()
+                .operation_timeout(Duration::from_secs(1))
+                .build(),
+        )
+        .sleep_impl(TokioSleep::new())
+        .endpoint_url("http://localhost:1234")
+        .http_client(infallible_client_fn(|_| {
+            http_02x::Response::builder().status(200).body("").unwrap()
+        }))
+        .interceptor(mocks)
+        .serializer(|input: &'static str| {
+            Ok(http_02x::Request::new(SdkBody::from(input))
+                .try_into()
+                .unwrap())
+        })
+        .deserializer(|response: &HttpResponse| {
+            let body = response.body().bytes().expect("the body was read");
+            Ok(GetItemOutput(String::from_utf8(body.to_vec()).unwrap()))
+        })
+        .build()
+}
+
+#[tokio::test(start_paused = true)]
+async fn responses_delayed_past_the_timeout_time_out() {
+    let slow_get_item = rule()
+        .then_http_response(item_response)
+        .delay(Duration::from_secs(5));
+    let operation = operation(&[&slow_get_item]);
+
+    let err = operation
+        .invoke("")
+        .await
+        .expect_err("the response is too slow");
+    assert!(matches!(err, SdkError::TimeoutError(_)), "{err:?}");
+    assert_eq!(1, slow_get_item.num_calls());
+}
+
+#[tokio::test(start_paused = true)]
+async fn responses_delayed_within_the_timeout_succeed() {
+    let slow_get_item = rule()
+        .then_http_response(item_response)
+        .delay(Duration::from_millis(500));
+    let operation = operation(&[&slow_get_item]);
+
+    let started = tokio::time::Instant::now();
+    let output = operation.invoke("").await.unwrap();
+    assert_eq!(GetItemOutput("item".into()), output);
+    assert!(started.elapsed() >= Duration::from_millis(500));
+    assert_eq!(1, slow_get_item.num_calls());
+}
+
+#[tokio::test(start_paused = true)]
+async fn delayed_modeled_outputs_time_out() {
+    let slow_get_item = rule()
+        .then_output(|| GetItemOutput("item".into()))
+        .delay(Duration::from_secs(5));
+    let operation = operation(&[&slow_get_item]);
+
+    let err = operation
+        .invoke("")
+        .await
+        .expect_err("the response is too slow");
+    assert!(matches!(err, SdkError::TimeoutError(_)), "{err:?}");
+    assert_eq!(1, slow_get_item.num_calls());
+}
@@ -0,0 +1,190 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_async::test_util::InstantSleep;
+use aws_smithy_mocks_experimental::{MockResponseInterceptor, RuleBuilder, RuleMode};
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::retries::classifiers::TransientErrorClassifier;
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::{ConnectorError, SdkError};
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::retry::RetryConfig;
+use std::fmt;
+use std::future::pending;
+
+#[derive(Debug)]
+struct TestError;
+
+impl fmt::Display for TestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TestError")
+    }
+}
+
+impl std::error::Error for TestError {}
+
+#[derive(Debug, PartialEq)]
+struct GetItemOutput(&'static str);
+
+/// Equivalent of `mock!` for an operation that takes the key of an item as its input.
+fn rule() -> RuleBuilder<&'static str, GetItemOutput, TestError> {
+    RuleBuilder::new(
+        || "",
+        pending::<Result<GetItemOutput, SdkError<TestError, HttpResponse>>>,
+    )
+}
+
+fn operation(mocks: MockResponseInterceptor) -> Operation<&'static str, GetItemOutput, TestError> {
+    Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .no_retry()
+        .endpoint_url("http://localhost:1234")
+        .http_client(infallible_client_fn(|_| {
+            http_02x::Response::builder().status(200).body("").unwrap()
+        }))
+        .interceptor(mocks)
+        .serializer(|key: &'static str| {
+            Ok(http_02x::Request::new(SdkBody::from(key))
+                .try_into()
+                .unwrap())
+        })
+        .deserializer(|_| Ok(GetItemOutput("unmocked")))
+        .build()
+}
+
+/// Like [`operation`], but retried up to three times.
+fn retried_operation(
+    mocks: MockResponseInterceptor,
+) -> Operation<&'static str, GetItemOutput, TestError> {
+    Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .standard_retry(&RetryConfig::standard().with_max_attempts(3))
+        .retry_classifier(TransientErrorClassifier::<TestError>::new())
+        .sleep_impl(InstantSleep::unlogged())
+        .endpoint_url("http://localhost:1234")
+        .http_client(infallible_client_fn(|_| {
+            http_02x::Response::builder().status(200).body("").unwrap()
+        }))
+        .interceptor(mocks)
+        .serializer(|key: &'static str| {
+            Ok(http_02x::Request::new(SdkBody::from(key))
+                .try_into()
+                .unwrap())
+        })
+        .deserializer(|_| Ok(GetItemOutput("unmocked")))
+        .build()
+}
+
+fn timeout() -> ConnectorError {
+    ConnectorError::timeout("connection timed out".into())
+}
+
+#[tokio::test]
+async fn sequential_rules_are_consumed_in_order() {
+    let get_item = rule().then_output(|| GetItemOutput("item"));
+    let mocks = MockResponseInterceptor::new()
+        .rule_mode(RuleMode::Sequential)
+        .with_rule(&get_item)
+        .with_rule(&get_item);
+    let operation = operation(mocks.clone());
+
+    operation.invoke("a").await.unwrap();
+    assert_eq!(1, mocks.remaining_rules());
+    // The rule was added twice but only used once so far
+    assert!(!get_item.is_exhausted());
+
+    operation.invoke("b").await.unwrap();
+    assert_eq!(0, mocks.remaining_rules());
+    assert!(get_item.is_exhausted());
+    mocks.expect_all_rules_consumed();
+}
+
+#[tokio::test]
+#[should_panic(expected = "1 rule(s) were never used")]
+async fn unused_match_any_rules_are_reported() {
+    let get_a = rule()
+        .match_requests(|key| *key == "a")
+        .then_output(|| GetItemOutput("a"));
+    // A mistake in the matcher means this rule can never match
+    let get_b = rule()
+        .match_requests(|key| *key == "B")
+        .then_output(|| GetItemOutput("b"));
+    let mocks = MockResponseInterceptor::new()
+        .with_rule(&get_a)
+        .with_rule(&get_b)
+        .allow_passthrough();
+    let operation = operation(mocks.clone());
+
+    assert_eq!(GetItemOutput("a"), operation.invoke("a").await.unwrap());
+    assert_eq!(
+        GetItemOutput("unmocked"),
+        operation.invoke("b").await.unwrap()
+    );
+    assert!(get_a.is_exhausted());
+    assert!(!get_b.is_exhausted());
+    mocks.expect_all_rules_consumed();
+}
+
+#[tokio::test]
+async fn sequential_rules_answer_every_attempt_of_a_request() {
+    let timeouts = rule().then_dispatch_failure(timeout);
+    let get_item = rule().then_output(|| GetItemOutput("item"));
+    let mocks = MockResponseInterceptor::new()
+        .rule_mode(RuleMode::Sequential)
+        .with_rule(&timeouts)
+        .with_rule(&get_item);
+    let operation = retried_operation(mocks.clone());
+
+    let err = operation
+        .invoke("a")
+        .await
+        .expect_err("every attempt timed out");
+    assert!(matches!(err, SdkError::DispatchFailure(_)), "{err:?}");
+    assert_eq!(3, timeouts.num_calls());
+    assert_eq!(1, mocks.remaining_rules());
+}
+
+#[tokio::test]
+async fn dispatch_failures_are_retried() {
+    let timeouts = rule().then_dispatch_failure(timeout);
+    let get_item = rule().then_output(|| GetItemOutput("item"));
+    let mocks = MockResponseInterceptor::new()
+        .rule_mode(RuleMode::SequentialPerAttempt)
+        .with_rule(&timeouts)
+        .with_rule(&timeouts)
+        .with_rule(&get_item);
+    let operation = retried_operation(mocks.clone());
+
+    assert_eq!(GetItemOutput("item"), operation.invoke("a").await.unwrap());
+    assert_eq!(2, timeouts.num_calls());
+    assert_eq!(1, get_item.num_calls());
+    mocks.expect_all_rules_consumed();
+}
+
+#[tokio::test]
+async fn dispatch_failures_fail_the_request_once_attempts_run_out() {
+    let timeouts = rule().then_dispatch_failure(timeout);
+    let mocks = MockResponseInterceptor::new()
+        .rule_mode(RuleMode::SequentialPerAttempt)
+        .with_rule(&timeouts)
+        .with_rule(&timeouts)
+        .with_rule(&timeouts);
+    let operation = retried_operation(mocks);
+
+    let err = operation
+        .invoke("a")
+        .await
+        .expect_err("every attempt timed out");
+    match err {
+        SdkError::DispatchFailure(failure) => assert!(failure.is_timeout()),
+        other => panic!("expected a dispatch failure, got {other:?}"),
+    }
+    assert_eq!(3, timeouts.num_calls());
+}
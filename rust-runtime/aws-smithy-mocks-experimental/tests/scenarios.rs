@@ -0,0 +1,223 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_async::rt::sleep::TokioSleep;
+use aws_smithy_mocks_experimental::scenarios::{Outcome, Scenario};
+use aws_smithy_mocks_experimental::{MockResponseInterceptor, RuleBuilder};
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::retries::classifiers::{
+    HttpStatusCodeClassifier, TransientErrorClassifier,
+};
+use aws_smithy_runtime_api::client::orchestrator::{HttpResponse, OrchestratorError};
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::retry::RetryConfig;
+use aws_smithy_types::timeout::TimeoutConfig;
+use std::fmt;
+use std::future::pending;
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[derive(Debug)]
+struct TestError;
+
+impl fmt::Display for TestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TestError")
+    }
+}
+
+impl std::error::Error for TestError {}
+
+#[derive(Debug, PartialEq)]
+struct GetItemOutput(&'static str);
+
+fn rule() -> RuleBuilder<&'static str, GetItemOutput, TestError> {
+    RuleBuilder::new(
+        || "",
+        pending::<Result<GetItemOutput, SdkError<TestError, HttpResponse>>>,
+    )
+}
+
+fn item() -> GetItemOutput {
+    GetItemOutput("item")
+}
+
+/// An operation that makes up to `max_attempts` attempts, which time out after `attempt_timeout`.
+fn operation(
+    mocks: MockResponseInterceptor,
+    max_attempts: u32,
+    attempt_timeout: Duration,
+) -> Operation<&'static str, GetItemOutput, TestError> {
+    Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .standard_retry(&RetryConfig::standard().with_max_attempts(max_attempts))
+        .retry_classifier(HttpStatusCodeClassifier::default())
+        .retry_classifier(TransientErrorClassifier::<TestError>::new())
+        .timeout_config(
+            TimeoutConfig::builder()
+                .operation_attempt_timeout(attempt_timeout)
+                .build(),
+        )
+        .sleep_impl(TokioSleep::new())
+        .endpoint_url("http://localhost:1234")
+        .http_client(infallible_client_fn(|_| {
+            http_02x::Response::builder().status(200).body("").unwrap()
+        }))
+        .interceptor(mocks)
+        .serializer(|input: &'static str| {
+            Ok(http_02x::Request::new(SdkBody::from(input))
+                .try_into()
+                .unwrap())
+        })
+        .deserializer(|response: &HttpResponse| {
+            if response.status().is_success() {
+                Ok(GetItemOutput("unmocked"))
+            } else {
+                Err(OrchestratorError::operation(TestError))
+            }
+        })
+        .build()
+}
+
+#[tokio::test(start_paused = true)]
+async fn throttle_then_succeed() {
+    let (mocks, expectation) = Scenario::ThrottleThenSucceed { throttles: 2 }.build(rule, item);
+    let operation = operation(mocks, 3, Duration::from_secs(10));
+
+    let started = Instant::now();
+    let result = operation.invoke("").await;
+    expectation.observe(&result);
+
+    assert_eq!(GetItemOutput("item"), result.unwrap());
+    assert_eq!(3, expectation.attempts());
+    expectation.assert_met(started.elapsed());
+}
+
+#[tokio::test(start_paused = true)]
+async fn permanent_server_error() {
+    let (mocks, expectation) = Scenario::PermanentServerError { max_attempts: 4 }.build(rule, item);
+    let operation = operation(mocks, 4, Duration::from_secs(10));
+
+    let started = Instant::now();
+    let result = operation.invoke("").await;
+    expectation.observe(&result);
+
+    assert!(
+        matches!(result, Err(SdkError::ServiceError(_))),
+        "{result:?}"
+    );
+    assert_eq!(4, expectation.attempts());
+    expectation.assert_met(started.elapsed());
+}
+
+#[tokio::test(start_paused = true)]
+async fn slow_responses_near_timeout() {
+    let attempt_timeout = Duration::from_secs(2);
+    let (mocks, expectation) = Scenario::SlowResponsesNearTimeout {
+        timeouts: 2,
+        attempt_timeout,
+    }
+    .build(rule, item);
+    let operation = operation(mocks, 3, attempt_timeout);
+
+    let started = Instant::now();
+    let result = operation.invoke("").await;
+    expectation.observe(&result);
+
+    assert_eq!(GetItemOutput("item"), result.unwrap());
+    assert!(started.elapsed() >= Duration::from_millis(5800));
+    expectation.assert_met(started.elapsed());
+}
+
+#[tokio::test(start_paused = true)]
+async fn connection_reset_storm() {
+    let (mocks, expectation) = Scenario::ConnectionResetStorm { resets: 4 }.build(rule, item);
+    let operation = operation(mocks, 5, Duration::from_secs(10));
+
+    let started = Instant::now();
+    let result = operation.invoke("").await;
+    expectation.observe(&result);
+
+    assert_eq!(GetItemOutput("item"), result.unwrap());
+    expectation.assert_met(started.elapsed());
+}
+
+#[tokio::test(start_paused = true)]
+async fn expired_credentials_once() {
+    let (mocks, expectation) = Scenario::ExpiredCredentialsOnce.build(rule, item);
+    let operation = operation(mocks, 3, Duration::from_secs(10));
+
+    let started = Instant::now();
+    let rejected = operation.invoke("").await;
+    expectation.observe(&rejected);
+    let retried = operation.invoke("").await;
+    expectation.observe(&retried);
+
+    assert!(
+        matches!(rejected, Err(SdkError::ServiceError(_))),
+        "{rejected:?}"
+    );
+    assert_eq!(GetItemOutput("item"), retried.unwrap());
+    expectation.assert_met(started.elapsed());
+}
+
+#[tokio::test(start_paused = true)]
+#[should_panic(
+    expected = "the client didn't behave as expected in PermanentServerError { \
+    max_attempts: 3 }:\n  - expected 3 attempt(s), but the client made 2\n  - expected the calls \
+    to take at most 1s, but they took 5s"
+)]
+async fn unmet_expectations_are_reported() {
+    let (mocks, expectation) = Scenario::PermanentServerError { max_attempts: 3 }.build(rule, item);
+    let expectation = expectation.with_elapsed(..=Duration::from_secs(1));
+    let operation = operation(mocks, 2, Duration::from_secs(10));
+
+    let result = operation.invoke("").await;
+    expectation.observe(&result);
+    expectation.assert_met(Duration::from_secs(5));
+}
+
+#[tokio::test(start_paused = true)]
+#[should_panic(
+    expected = "expected the calls to have outcomes [Success], but they had \
+    [TimeoutError]"
+)]
+async fn unexpected_outcomes_are_reported() {
+    let attempt_timeout = Duration::from_secs(2);
+    let (mocks, expectation) = Scenario::SlowResponsesNearTimeout {
+        timeouts: 1,
+        attempt_timeout,
+    }
+    .build(rule, item);
+    let operation = operation(mocks, 1, attempt_timeout);
+
+    let result = operation.invoke("").await;
+    expectation.observe(&result);
+    expectation
+        .with_attempts(1)
+        .assert_met(Duration::from_secs(4));
+}
+
+#[tokio::test(start_paused = true)]
+async fn expectations_can_be_adjusted_to_the_client_configuration() {
+    let (mocks, expectation) = Scenario::ConnectionResetStorm { resets: 3 }.build(rule, item);
+    let expectation = expectation
+        .with_attempts(2)
+        .with_outcomes([Outcome::DispatchFailure]);
+    let operation = operation(mocks, 2, Duration::from_secs(10));
+
+    let result = operation.invoke("").await;
+    expectation.observe(&result);
+
+    assert!(
+        matches!(result, Err(SdkError::DispatchFailure(_))),
+        "{result:?}"
+    );
+    expectation.assert_met(Duration::ZERO);
+}
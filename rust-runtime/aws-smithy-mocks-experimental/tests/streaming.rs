@@ -0,0 +1,211 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_http::event_stream::{FromReceiver, Receiver};
+use aws_smithy_mocks_experimental::{
+    create_mock_http_client, MockResponseInterceptor, Rule, RuleBuilder, RuleMode,
+};
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime_api::client::http::SharedHttpClient;
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::byte_stream::ByteStream;
+use std::fmt;
+use std::future::pending;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug)]
+struct TestError;
+
+impl fmt::Display for TestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TestError")
+    }
+}
+
+impl std::error::Error for TestError {}
+
+#[derive(Debug)]
+struct GetObjectOutput {
+    body: ByteStream,
+}
+
+#[derive(Debug)]
+struct PutObjectOutput {
+    etag: &'static str,
+}
+
+#[derive(Debug, PartialEq)]
+struct Event(&'static str);
+
+#[derive(Debug)]
+struct SubscribeOutput {
+    events: Receiver<Event, TestError>,
+}
+
+impl FromReceiver<Event, TestError> for SubscribeOutput {
+    fn from_receiver(events: Receiver<Event, TestError>) -> Self {
+        Self { events }
+    }
+}
+
+/// Equivalent of `mock!` for an operation that takes `body` as its input.
+fn rule<O>() -> RuleBuilder<&'static str, O, TestError>
+where
+    O: fmt::Debug + Send + Sync + 'static,
+{
+    RuleBuilder::new(
+        || "",
+        pending::<Result<O, SdkError<TestError, HttpResponse>>>,
+    )
+}
+
+fn operation<O>(
+    rules: &[&Rule],
+    mode: RuleMode,
+    sent_bodies: Arc<Mutex<Vec<Vec<u8>>>>,
+    unmocked: fn() -> O,
+) -> Operation<&'static str, O, TestError>
+where
+    O: fmt::Debug + Send + Sync + 'static,
+{
+    let http_client = infallible_client_fn(move |request| {
+        if let Some(body) = request.body().bytes() {
+            sent_bodies.lock().unwrap().push(body.to_vec());
+        }
+        http_02x::Response::builder().status(200).body("").unwrap()
+    });
+    operation_with_body(rules, mode, http_client, unmocked, SdkBody::from)
+}
+
+fn operation_with_body<O>(
+    rules: &[&Rule],
+    mode: RuleMode,
+    http_client: SharedHttpClient,
+    unmocked: fn() -> O,
+    body: fn(&'static str) -> SdkBody,
+) -> Operation<&'static str, O, TestError>
+where
+    O: fmt::Debug + Send + Sync + 'static,
+{
+    let mut mocks = MockResponseInterceptor::new().rule_mode(mode);
+    for rule in rules {
+        mocks = mocks.with_rule(rule);
+    }
+    Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .no_retry()
+        .endpoint_url("http://localhost:1234")
+        .http_client(http_client)
+        .interceptor(mocks)
+        .serializer(move |input: &'static str| {
+            Ok(http_02x::Request::new(body(input)).try_into().unwrap())
+        })
+        .deserializer(move |_| Ok(unmocked()))
+        .build()
+}
+
+#[tokio::test]
+async fn streaming_output_from_one_rule_can_be_returned_twice() {
+    let get_object = rule().then_output(|| GetObjectOutput {
+        body: ByteStream::from_static(b"test-test-test"),
+    });
+    let operation = operation(&[&get_object], RuleMode::MatchAny, Arc::default(), || {
+        GetObjectOutput {
+            body: ByteStream::from_static(b"unmocked"),
+        }
+    });
+
+    for _ in 0..2 {
+        let output = operation.invoke("").await.expect("success");
+        let data = output.body.collect().await.unwrap().to_vec();
+        assert_eq!(b"test-test-test", data.as_slice());
+    }
+    assert_eq!(2, get_object.num_calls());
+}
+
+#[tokio::test]
+async fn rules_can_match_on_in_memory_request_bodies() {
+    let first = rule()
+        .match_request_body(|body| body == b"first")
+        .then_output(|| PutObjectOutput { etag: "1" });
+    let second = rule()
+        .match_request_body(|body| body == b"second")
+        .then_output(|| PutObjectOutput { etag: "2" });
+    let sent_bodies = Arc::default();
+    let operation = operation(
+        &[&first, &second],
+        RuleMode::MatchAny,
+        Arc::clone(&sent_bodies),
+        || PutObjectOutput { etag: "unmocked" },
+    );
+
+    assert_eq!("2", operation.invoke("second").await.unwrap().etag);
+    assert_eq!("1", operation.invoke("first").await.unwrap().etag);
+    assert_eq!((1, 1), (first.num_calls(), second.num_calls()));
+    // matching doesn't consume the body
+    assert_eq!(
+        vec![b"second".to_vec(), b"first".to_vec()],
+        *sent_bodies.lock().unwrap()
+    );
+}
+
+#[tokio::test]
+#[should_panic(expected = "did not match the request body")]
+async fn sequential_rules_must_match_the_request_body() {
+    let put_object = rule()
+        .match_request_body(|body| body == b"expected")
+        .then_output(|| PutObjectOutput { etag: "1" });
+    let operation = operation(&[&put_object], RuleMode::Sequential, Arc::default(), || {
+        PutObjectOutput { etag: "unmocked" }
+    });
+    let _ = operation.invoke("unexpected").await;
+}
+
+#[tokio::test]
+async fn rules_can_match_on_streaming_request_bodies() {
+    let first = rule()
+        .match_request_body(|body| body == b"first")
+        .then_output(|| PutObjectOutput { etag: "1" });
+    let second = rule()
+        .match_request_body(|body| body == b"second")
+        .then_output(|| PutObjectOutput { etag: "2" });
+    let operation = operation_with_body(
+        &[&first, &second],
+        RuleMode::MatchAny,
+        // Collects streaming bodies, so that rules can match on them
+        create_mock_http_client(),
+        || PutObjectOutput { etag: "unmocked" },
+        // A streaming body that can't be inspected without reading it
+        |body| SdkBody::from_body_0_4(SdkBody::from(body)),
+    );
+
+    assert_eq!("2", operation.invoke("second").await.unwrap().etag);
+    assert_eq!("1", operation.invoke("first").await.unwrap().etag);
+    assert_eq!((1, 1), (first.num_calls(), second.num_calls()));
+}
+
+#[tokio::test]
+async fn event_streams_yield_canned_events_then_close() {
+    let subscribe =
+        rule::<SubscribeOutput>().then_event_stream(|| vec![Ok(Event("one")), Ok(Event("two"))]);
+    let operation = operation(&[&subscribe], RuleMode::MatchAny, Arc::default(), || {
+        SubscribeOutput {
+            events: Receiver::from_events(Vec::new()),
+        }
+    });
+
+    for _ in 0..2 {
+        let mut output = operation.invoke("").await.expect("success");
+        assert_eq!(Some(Event("one")), output.events.recv().await.unwrap());
+        assert_eq!(Some(Event("two")), output.events.recv().await.unwrap());
+        assert_eq!(None, output.events.recv().await.unwrap());
+    }
+    assert_eq!(2, subscribe.num_calls());
+}
@@ -0,0 +1,143 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_async::test_util::InstantSleep;
+use aws_smithy_mocks_experimental::{MockResponseInterceptor, Rule, RuleBuilder, RuleMode};
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::{Operation, OperationBuilder};
+use aws_smithy_runtime::client::retries::classifiers::TransientErrorClassifier;
+use aws_smithy_runtime_api::client::interceptors::context::{Error, Output};
+use aws_smithy_runtime_api::client::orchestrator::{HttpResponse, OrchestratorError};
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_runtime_api::client::ser_de::DeserializeResponse;
+use aws_smithy_runtime_api::http::StatusCode;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::byte_stream::ByteStream;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::retry::RetryConfig;
+use std::fmt;
+use std::future::pending;
+use std::mem;
+
+#[derive(Debug)]
+struct TestError;
+
+impl fmt::Display for TestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TestError")
+    }
+}
+
+impl std::error::Error for TestError {}
+
+#[derive(Debug, PartialEq)]
+struct GetItemOutput(String);
+
+#[derive(Debug)]
+struct GetObjectOutput(ByteStream);
+
+fn rule<O>() -> RuleBuilder<&'static str, O, TestError>
+where
+    O: fmt::Debug + Send + Sync + 'static,
+{
+    RuleBuilder::new(
+        || "",
+        pending::<Result<O, SdkError<TestError, HttpResponse>>>,
+    )
+}
+
+fn full_response() -> HttpResponse {
+    HttpResponse::new(
+        StatusCode::try_from(200).unwrap(),
+        SdkBody::from("hello world"),
+    )
+}
+
+/// An operation that is retried up to three times, with `rules` applied in order.
+fn operation(rules: &[&Rule]) -> OperationBuilder<&'static str> {
+    let mut mocks = MockResponseInterceptor::new().rule_mode(RuleMode::SequentialPerAttempt);
+    for rule in rules {
+        mocks = mocks.with_rule(rule);
+    }
+    Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .standard_retry(&RetryConfig::standard().with_max_attempts(3))
+        .retry_classifier(TransientErrorClassifier::<TestError>::new())
+        .sleep_impl(InstantSleep::unlogged())
+        .endpoint_url("http://localhost:1234")
+        .http_client(infallible_client_fn(|_| {
+            http_02x::Response::builder().status(200).body("").unwrap()
+        }))
+        .interceptor(mocks)
+        .serializer(|input: &'static str| {
+            Ok(http_02x::Request::new(SdkBody::from(input))
+                .try_into()
+                .unwrap())
+        })
+}
+
+/// Hands the response body to the caller as it arrives, like the deserializer of an operation
+/// with a streaming output.
+#[derive(Debug)]
+struct StreamingDeserializer;
+
+impl DeserializeResponse for StreamingDeserializer {
+    fn deserialize_streaming(
+        &self,
+        response: &mut HttpResponse,
+    ) -> Option<Result<Output, OrchestratorError<Error>>> {
+        let body = mem::replace(response.body_mut(), SdkBody::taken());
+        Some(Ok(Output::erase(GetObjectOutput(ByteStream::new(body)))))
+    }
+
+    fn deserialize_nonstreaming(
+        &self,
+        _response: &HttpResponse,
+    ) -> Result<Output, OrchestratorError<Error>> {
+        unreachable!("the output is always streaming")
+    }
+}
+
+#[tokio::test]
+async fn truncated_responses_of_non_streaming_operations_are_retried() {
+    let truncated = rule::<GetItemOutput>().then_http_response_truncated(200, "hello", 11);
+    let full = rule::<GetItemOutput>().then_http_response(full_response);
+    let operation: Operation<_, GetItemOutput, TestError> = operation(&[&truncated, &full])
+        .deserializer(|response| {
+            let body = response.body().bytes().expect("the body was read in full");
+            Ok(GetItemOutput(String::from_utf8(body.to_vec()).unwrap()))
+        })
+        .build();
+
+    let output = operation.invoke("").await.unwrap();
+    assert_eq!(GetItemOutput("hello world".into()), output);
+    assert_eq!(1, truncated.num_calls());
+    assert_eq!(1, full.num_calls());
+}
+
+#[tokio::test]
+async fn truncated_responses_of_streaming_operations_fail_the_body() {
+    let truncated = rule::<GetObjectOutput>().then_http_response_truncated(200, "hello", 11);
+    let full = rule::<GetObjectOutput>().then_http_response(full_response);
+    let operation: Operation<_, GetObjectOutput, TestError> = operation(&[&truncated, &full])
+        .deserializer_impl(StreamingDeserializer)
+        .build();
+
+    let output = operation
+        .invoke("")
+        .await
+        .expect("the headers were received");
+    let error = output
+        .0
+        .collect()
+        .await
+        .expect_err("the connection was reset");
+    let message = format!("{}", DisplayErrorContext(&error));
+    assert!(message.contains("connection reset"), "{message}");
+    assert_eq!(1, truncated.num_calls());
+    assert_eq!(0, full.num_calls());
+}
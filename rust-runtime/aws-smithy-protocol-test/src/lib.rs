@@ -21,13 +21,15 @@ use crate::xml::try_xml_equivalent;
 use assert_json_diff::assert_json_eq_no_panic;
 use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
 use aws_smithy_runtime_api::http::Headers;
+use aws_smithy_types::date_time::Format;
+use aws_smithy_types::DateTime;
 use http::{HeaderMap, Uri};
 use pretty_assertions::Comparison;
 use std::borrow::Cow;
 use std::collections::HashSet;
 use std::fmt::{self, Debug};
 use thiserror::Error;
-use urlencoded::try_url_encoded_form_equivalent;
+use urlencoded::{percent_decode, try_url_encoded_form_equivalent};
 
 /// Helper trait for tests for float comparisons
 ///
@@ -69,10 +71,18 @@ pub enum ProtocolTestFailure {
         expected: String,
         found: Vec<String>,
     },
+    #[error("unexpected query params present: {found:?}")]
+    UnexpectedQueryParams { found: Vec<String> },
     #[error("forbidden query param present: `{expected}`")]
     ForbiddenQueryParam { expected: String },
     #[error("required query param missing: `{expected}`")]
     RequiredQueryParam { expected: String },
+    #[error("invalid query param value for key `{key}`: expected `{expected}`, found {found:?}")]
+    InvalidQueryParam {
+        key: String,
+        expected: String,
+        found: Vec<String>,
+    },
 
     #[error("invalid header value for key `{key}`: expected `{expected}`, found `{found}`")]
     InvalidHeader {
@@ -82,6 +92,8 @@ pub enum ProtocolTestFailure {
     },
     #[error("missing required header: `{expected}`")]
     MissingHeader { expected: String },
+    #[error("unexpected headers present: {found:?}")]
+    UnexpectedHeaders { found: Vec<String> },
     #[error("Header `{forbidden}` was forbidden but found: `{found}`")]
     ForbiddenHeader { forbidden: String, found: String },
     #[error(
@@ -95,6 +107,8 @@ pub enum ProtocolTestFailure {
     },
     #[error("Expected body to be valid {expected} but instead: {found}")]
     InvalidBodyFormat { expected: String, found: String },
+    #[error("expected value `{value}` is not a valid {kind}")]
+    InvalidExpectedValue { kind: String, value: String },
 }
 
 /// Check that the protocol test succeeded & print the pretty error
@@ -171,6 +185,79 @@ pub fn validate_query_string(
     Ok(())
 }
 
+/// Like [`validate_query_string`], but also fails if the request has query params that aren't in
+/// `expected_params`, or has an expected param more times than it's expected.
+///
+/// Params whose key is in `ignore_keys` are not checked.
+pub fn validate_query_string_exact(
+    request: &HttpRequest,
+    expected_params: &[&str],
+    ignore_keys: &[&str],
+) -> Result<(), ProtocolTestFailure> {
+    validate_query_string(request, expected_params)?;
+    let query = request
+        .uri()
+        .rsplit_once('?')
+        .map(|s| s.1)
+        .unwrap_or_default();
+    let mut unexpected: Vec<&str> = query
+        .split('&')
+        .filter(|param| !param.is_empty())
+        .filter(|param| !ignore_keys.contains(&QueryParam::parse(param).key))
+        .collect();
+    for param in expected_params {
+        if let Some(index) = unexpected.iter().position(|actual| actual == param) {
+            unexpected.remove(index);
+        }
+    }
+    if !unexpected.is_empty() {
+        return Err(ProtocolTestFailure::UnexpectedQueryParams {
+            found: unexpected.into_iter().map(|s| s.to_string()).collect(),
+        });
+    }
+    Ok(())
+}
+
+/// Like [`validate_query_string`], but compares the values of the params as `expected_params` says
+///
+/// Values are percent-decoded before being compared. If a key is repeated, one of its values has
+/// to match.
+pub fn validate_query_string_typed(
+    request: &HttpRequest,
+    expected_params: &[(&str, ValueSpec<'_>)],
+) -> Result<(), ProtocolTestFailure> {
+    let actual_params = extract_params(request.uri());
+    for (key, spec) in expected_params {
+        let mut found: Vec<String> = actual_params
+            .iter()
+            .filter_map(|param| param.split_once('='))
+            .filter(|(actual_key, _)| percent_decode(actual_key) == *key)
+            .map(|(_, value)| percent_decode(value))
+            .collect();
+        if found.is_empty() {
+            let mut params: Vec<String> = actual_params.iter().map(|s| s.to_string()).collect();
+            params.sort();
+            return Err(ProtocolTestFailure::MissingQueryParam {
+                expected: format!("{}={}", key, spec.expected()),
+                found: params,
+            });
+        }
+        let mut matched = false;
+        for value in &found {
+            matched |= spec.matches(value)?;
+        }
+        if !matched {
+            found.sort();
+            return Err(ProtocolTestFailure::InvalidQueryParam {
+                key: key.to_string(),
+                expected: spec.expected().to_string(),
+                found,
+            });
+        }
+    }
+    Ok(())
+}
+
 pub fn forbid_query_params(
     request: &HttpRequest,
     forbid_params: &[&str],
@@ -219,6 +306,9 @@ pub fn require_query_params(
 mod sealed {
     pub trait GetNormalizedHeader {
         fn get_header(&self, key: &str) -> Option<String>;
+
+        /// The lowercase names of all headers, once per value.
+        fn header_names(&self) -> Vec<String>;
     }
 }
 
@@ -230,6 +320,10 @@ impl<'a> GetNormalizedHeader for &'a Headers {
             Some(self.get_all(key).collect::<Vec<_>>().join(", "))
         }
     }
+
+    fn header_names(&self) -> Vec<String> {
+        self.iter().map(|(key, _)| key.to_lowercase()).collect()
+    }
 }
 
 impl<'a> GetNormalizedHeader for &'a HeaderMap {
@@ -246,6 +340,12 @@ impl<'a> GetNormalizedHeader for &'a HeaderMap {
             )
         }
     }
+
+    fn header_names(&self) -> Vec<String> {
+        self.iter()
+            .map(|(key, _)| key.as_str().to_owned())
+            .collect()
+    }
 }
 
 pub fn validate_headers<'a>(
@@ -274,6 +374,55 @@ pub fn validate_headers<'a>(
     Ok(())
 }
 
+/// Headers whose value changes from one request to the next, and which are therefore not checked
+/// by [`validate_headers_exact`].
+pub const DEFAULT_IGNORED_HEADERS: &[&str] = &[
+    "amz-sdk-invocation-id",
+    "amz-sdk-request",
+    "content-length",
+    "date",
+    "host",
+    "user-agent",
+    "x-amz-date",
+    "x-amz-user-agent",
+];
+
+/// Like [`validate_headers`], but also fails if there are headers that aren't in
+/// `expected_headers`.
+///
+/// Headers in [`DEFAULT_IGNORED_HEADERS`] or `ignore_headers` are not checked. Since the values of
+/// a repeated header are joined before being compared, a header sent twice when it's expected
+/// once fails as an [`InvalidHeader`](ProtocolTestFailure::InvalidHeader).
+pub fn validate_headers_exact<'a>(
+    actual_headers: impl GetNormalizedHeader,
+    expected_headers: impl IntoIterator<Item = (impl AsRef<str> + 'a, impl AsRef<str> + 'a)>,
+    ignore_headers: &[&str],
+) -> Result<(), ProtocolTestFailure> {
+    let expected_headers: Vec<_> = expected_headers.into_iter().collect();
+    let expected_keys: HashSet<String> = expected_headers
+        .iter()
+        .map(|(key, _)| key.as_ref().to_lowercase())
+        .collect();
+    let ignored = |key: &str| {
+        DEFAULT_IGNORED_HEADERS
+            .iter()
+            .chain(ignore_headers)
+            .any(|ignored| ignored.eq_ignore_ascii_case(key))
+    };
+    let mut unexpected: Vec<String> = actual_headers
+        .header_names()
+        .into_iter()
+        .filter(|key| !expected_keys.contains(key) && !ignored(key))
+        .collect();
+    validate_headers(actual_headers, expected_headers)?;
+    if !unexpected.is_empty() {
+        unexpected.sort();
+        unexpected.dedup();
+        return Err(ProtocolTestFailure::UnexpectedHeaders { found: unexpected });
+    }
+    Ok(())
+}
+
 pub fn forbid_headers(
     headers: impl GetNormalizedHeader,
     forbidden_headers: &[&str],
@@ -305,6 +454,121 @@ pub fn require_headers(
     Ok(())
 }
 
+/// The expected value of a header or query param, and how it's compared with the actual value
+///
+/// Values that aren't [`Exact`](ValueSpec::Exact) are compared by what they mean rather than how
+/// they're written, since the Smithy spec allows several serializations of the same number or
+/// timestamp.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValueSpec<'a> {
+    /// Compared as a string, exactly
+    Exact(&'a str),
+    /// Compared as a floating point number, or as a comma-separated list of them
+    ///
+    /// `NaN`, `Infinity` and `-Infinity` are parsed as such, and all `NaN` values are equal. Zero and
+    /// negative zero are not equal.
+    Float(&'a str),
+    /// Compared as a point in time, in any of the epoch-seconds, date-time and http-date formats
+    Timestamp(&'a str),
+}
+
+impl<'a> ValueSpec<'a> {
+    fn expected(&self) -> &'a str {
+        match *self {
+            ValueSpec::Exact(value) | ValueSpec::Float(value) | ValueSpec::Timestamp(value) => {
+                value
+            }
+        }
+    }
+
+    /// Returns whether `actual` matches this spec, or an error if the expected value can't be
+    /// parsed.
+    fn matches(&self, actual: &str) -> Result<bool, ProtocolTestFailure> {
+        let invalid = |kind: &str, value: &str| ProtocolTestFailure::InvalidExpectedValue {
+            kind: kind.to_string(),
+            value: value.to_string(),
+        };
+        match self {
+            ValueSpec::Exact(expected) => Ok(actual == *expected),
+            ValueSpec::Float(expected) => {
+                let expected = parse_floats(expected).ok_or_else(|| invalid("float", expected))?;
+                Ok(parse_floats(actual).is_some_and(|actual| {
+                    actual.len() == expected.len()
+                        && actual
+                            .iter()
+                            .zip(&expected)
+                            .all(|(actual, expected)| floats_match(*actual, *expected))
+                }))
+            }
+            ValueSpec::Timestamp(expected) => {
+                let expected =
+                    parse_timestamp(expected).ok_or_else(|| invalid("timestamp", expected))?;
+                Ok(parse_timestamp(actual) == Some(expected))
+            }
+        }
+    }
+}
+
+fn parse_floats(value: &str) -> Option<Vec<f64>> {
+    // `f64::from_str` accepts `NaN`, `Infinity` and `-Infinity`, whatever their case
+    value.split(',').map(|v| v.trim().parse().ok()).collect()
+}
+
+fn floats_match(actual: f64, expected: f64) -> bool {
+    actual.float_equals(&expected)
+        && (actual.is_nan() || actual.is_sign_negative() == expected.is_sign_negative())
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime> {
+    let value = value.trim();
+    [
+        Format::EpochSeconds,
+        Format::DateTimeWithOffset,
+        Format::HttpDate,
+    ]
+    .into_iter()
+    .find_map(|format| DateTime::from_str(value, format).ok())
+}
+
+/// Like [`validate_headers`], but compares the values of the headers as `expected_headers` says
+///
+/// ```
+/// # use aws_smithy_protocol_test::{validate_headers_typed, ValueSpec};
+/// # use aws_smithy_runtime_api::http::Headers;
+/// let mut headers = Headers::new();
+/// headers.insert("X-Float", "1");
+/// headers.insert("X-Timestamp", "1576540098");
+/// validate_headers_typed(
+///     &headers,
+///     &[
+///         ("X-Float", ValueSpec::Float("1.0")),
+///         ("X-Timestamp", ValueSpec::Timestamp("Mon, 16 Dec 2019 23:48:18 GMT")),
+///     ],
+/// )
+/// .unwrap();
+/// ```
+pub fn validate_headers_typed(
+    actual_headers: impl GetNormalizedHeader,
+    expected_headers: &[(&str, ValueSpec<'_>)],
+) -> Result<(), ProtocolTestFailure> {
+    for (key, spec) in expected_headers {
+        let Some(actual_value) = actual_headers.get_header(key) else {
+            return Err(ProtocolTestFailure::MissingHeader {
+                expected: key.to_string(),
+            });
+        };
+        if !spec.matches(&actual_value)? {
+            return Err(ProtocolTestFailure::InvalidHeader {
+                key: key.to_string(),
+                expected: spec.expected().to_string(),
+                found: actual_value,
+            });
+        }
+    }
+    Ok(())
+}
+
 #[derive(Clone)]
 pub enum MediaType {
     /// JSON media types are deserialized and compared
@@ -313,8 +577,10 @@ pub enum MediaType {
     Xml,
     /// CBOR media types are decoded from base64 to binary and compared
     Cbor,
-    /// For x-www-form-urlencoded, do some map order comparison shenanigans
+    /// For x-www-form-urlencoded, the decoded key/value pairs are compared in any order
     UrlEncodedForm,
+    /// Binary media types are compared byte for byte
+    Blob,
     /// Other media types are compared literally
     Other(String),
 }
@@ -327,6 +593,7 @@ impl<T: AsRef<str>> From<T> for MediaType {
             "application/xml" => MediaType::Xml,
             "application/cbor" => MediaType::Cbor,
             "application/x-www-form-urlencoded" => MediaType::UrlEncodedForm,
+            "application/octet-stream" => MediaType::Blob,
             other => MediaType::Other(other.to_string()),
         }
     }
@@ -357,6 +624,7 @@ pub fn validate_body<T: AsRef<[u8]> + Debug>(
             found: "input was not valid UTF-8".to_owned(),
         }),
         (MediaType::Cbor, _) => try_cbor_eq(actual_body, expected_body),
+        (MediaType::Blob, _) => try_blob_eq(expected_body.as_bytes(), actual_body.as_ref()),
         (MediaType::Other(media_type), Ok(actual_body)) => {
             if actual_body != expected_body {
                 Err(ProtocolTestFailure::BodyDidNotMatch {
@@ -415,6 +683,42 @@ fn try_json_eq(expected: &str, actual: &str) -> Result<(), ProtocolTestFailure>
     }
 }
 
+fn try_blob_eq(expected: &[u8], actual: &[u8]) -> Result<(), ProtocolTestFailure> {
+    if expected == actual {
+        return Ok(());
+    }
+    let first_difference = expected
+        .iter()
+        .zip(actual)
+        .take_while(|(expected, actual)| expected == actual)
+        .count();
+    let hint = format!(
+        "blobs differ from byte {} (expected {} bytes, found {})",
+        first_difference,
+        expected.len(),
+        actual.len()
+    );
+    let comparison = match (std::str::from_utf8(expected), std::str::from_utf8(actual)) {
+        (Ok(expected), Ok(actual)) => pretty_comparison(expected, actual),
+        _ => pretty_comparison(&hex_lines(expected), &hex_lines(actual)),
+    };
+    Err(ProtocolTestFailure::BodyDidNotMatch { comparison, hint })
+}
+
+/// Formats bytes as hex, 16 bytes per line, so that differing lines stand out in a comparison
+fn hex_lines(bytes: &[u8]) -> String {
+    bytes
+        .chunks(16)
+        .map(|line| {
+            line.iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Compares two `ciborium::value::Value` instances for semantic equality.
 ///
 /// This function recursively compares two CBOR values, correctly handling arrays and maps
@@ -572,7 +876,9 @@ pub fn decode_body_data(body: &[u8], media_type: MediaType) -> Cow<'_, [u8]> {
 mod tests {
     use crate::{
         forbid_headers, forbid_query_params, require_headers, require_query_params, validate_body,
-        validate_headers, validate_query_string, FloatEquals, MediaType, ProtocolTestFailure,
+        validate_headers, validate_headers_exact, validate_headers_typed, validate_query_string,
+        validate_query_string_exact, validate_query_string_typed, FloatEquals, MediaType,
+        ProtocolTestFailure, ValueSpec,
     };
     use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
     use aws_smithy_runtime_api::http::Headers;
@@ -606,6 +912,31 @@ mod tests {
         validate_query_string(&request, &["hell=a%20"]).expect_err("no parameter should match");
     }
 
+    #[test]
+    fn test_validate_query_string_exact() {
+        let request = make_request("/foo?a=b&c&d=efg");
+        validate_query_string_exact(&request, &["a=b", "c", "d=efg"], &[])
+            .expect("exactly the expected params are present");
+        validate_query_string_exact(&request, &["c", "d=efg", "a=b"], &[])
+            .expect("order does not matter");
+        assert_eq!(
+            validate_query_string_exact(&request, &["a=b", "c"], &[]),
+            Err(ProtocolTestFailure::UnexpectedQueryParams {
+                found: vec!["d=efg".to_owned()]
+            })
+        );
+        validate_query_string_exact(&request, &["a=b", "c"], &["d"])
+            .expect("ignored keys are not checked");
+
+        let request = make_request("/foo?a=b&a=b");
+        assert_eq!(
+            validate_query_string_exact(&request, &["a=b"], &[]),
+            Err(ProtocolTestFailure::UnexpectedQueryParams {
+                found: vec!["a=b".to_owned()]
+            })
+        );
+    }
+
     #[test]
     fn test_forbid_query_param() {
         let request = make_request("/foo?a=b&c&d=efg&hello=a%20b");
@@ -648,6 +979,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_headers_exact() {
+        let mut headers = Headers::new();
+        headers.append("content-type", "application/json");
+        headers.append("x-amz-meta-", "");
+
+        validate_headers_exact(
+            &headers,
+            [("Content-Type", "application/json"), ("x-amz-meta-", "")],
+            &[],
+        )
+        .expect("exactly the expected headers are present");
+        assert_eq!(
+            validate_headers_exact(&headers, [("Content-Type", "application/json")], &[]),
+            Err(ProtocolTestFailure::UnexpectedHeaders {
+                found: vec!["x-amz-meta-".to_owned()]
+            })
+        );
+        validate_headers_exact(
+            &headers,
+            [("Content-Type", "application/json")],
+            &["X-Amz-Meta-"],
+        )
+        .expect("ignored headers are not checked");
+    }
+
+    #[test]
+    fn test_validate_headers_exact_duplicated_header() {
+        let mut headers = Headers::new();
+        headers.append("content-type", "application/json");
+        headers.append("content-type", "application/json");
+
+        assert_eq!(
+            validate_headers_exact(&headers, [("Content-Type", "application/json")], &[]),
+            Err(ProtocolTestFailure::InvalidHeader {
+                key: "Content-Type".to_owned(),
+                expected: "application/json".to_owned(),
+                found: "application/json, application/json".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_headers_exact_default_ignore_list() {
+        let mut headers = Headers::new();
+        headers.append("content-type", "application/json");
+        headers.append("date", "Mon, 16 Dec 2019 23:48:18 GMT");
+
+        validate_headers_exact(&headers, [("Content-Type", "application/json")], &[])
+            .expect("the date header is ignored by default");
+    }
+
     #[test]
     fn test_forbidden_headers() {
         let mut headers = Headers::new();
@@ -670,6 +1053,185 @@ mod tests {
         require_headers(&headers, &["X-Bar"]).expect_err("header not present");
     }
 
+    #[test]
+    fn test_forbidden_headers_multi_valued() {
+        let mut headers = Headers::new();
+        headers.append("X-Foo", "foo");
+        headers.append("x-foo", "bar");
+        let failure = forbid_headers(&headers, &["x-FOO"]).expect_err("should be error");
+        assert_eq!(
+            failure,
+            ProtocolTestFailure::ForbiddenHeader {
+                forbidden: "x-FOO".to_string(),
+                found: "x-FOO: foo, bar".to_string()
+            }
+        );
+        assert_eq!(
+            "Header `x-FOO` was forbidden but found: `x-FOO: foo, bar`",
+            failure.to_string()
+        );
+    }
+
+    #[test]
+    fn test_required_headers_multi_valued() {
+        let mut headers = Headers::new();
+        headers.append("X-Foo", "foo");
+        headers.append("x-foo", "bar");
+        headers.append("x-empty", "");
+        require_headers(&headers, &["x-FOO", "X-Empty"])
+            .expect("headers are present, whatever their case and value");
+        assert_eq!(
+            require_headers(&headers, &["X-Foo", "X-Bar"]),
+            Err(ProtocolTestFailure::MissingHeader {
+                expected: "X-Bar".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn test_required_and_forbidden_headers_http0x() {
+        let request = http::Request::builder()
+            .header("x-foo", "foo")
+            .header("x-foo", "bar")
+            .body(())
+            .unwrap();
+        require_headers(request.headers(), &["X-Foo"]).expect("header present");
+        assert_eq!(
+            forbid_headers(request.headers(), &["X-Foo"]),
+            Err(ProtocolTestFailure::ForbiddenHeader {
+                forbidden: "X-Foo".to_string(),
+                found: "X-Foo: foo, bar".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_headers_typed_floats() {
+        let mut headers = Headers::new();
+        headers.append("x-float", "1");
+        headers.append("x-exponent", "1e1");
+        headers.append("x-list", "1,2.50");
+
+        validate_headers_typed(&headers, &[("X-Float", ValueSpec::Float("1.0"))])
+            .expect("1 and 1.0 are the same float");
+        validate_headers_typed(&headers, &[("X-Exponent", ValueSpec::Float("10.0"))])
+            .expect("1e1 and 10.0 are the same float");
+        validate_headers_typed(&headers, &[("X-List", ValueSpec::Float("1.0, 2.5"))])
+            .expect("lists are compared member by member");
+        validate_headers_typed(&headers, &[("X-List", ValueSpec::Float("1.0"))])
+            .expect_err("all list members must be specified");
+        assert_eq!(
+            validate_headers_typed(&headers, &[("X-Float", ValueSpec::Exact("1.0"))]),
+            Err(ProtocolTestFailure::InvalidHeader {
+                key: "X-Float".to_owned(),
+                expected: "1.0".to_owned(),
+                found: "1".to_owned(),
+            })
+        );
+        assert_eq!(
+            validate_headers_typed(&headers, &[("X-Float", ValueSpec::Float("one"))]),
+            Err(ProtocolTestFailure::InvalidExpectedValue {
+                kind: "float".to_owned(),
+                value: "one".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_headers_typed_special_floats() {
+        // From the `RestJsonSupports{NaN,Infinity,NegativeInfinity}FloatHeaderInputs` tests
+        for value in ["NaN", "Infinity", "-Infinity"] {
+            let mut headers = Headers::new();
+            headers.append("x-float", value);
+            headers.append("x-double", value);
+            validate_headers_typed(
+                &headers,
+                &[
+                    ("X-Float", ValueSpec::Float(value)),
+                    ("X-Double", ValueSpec::Float(value)),
+                ],
+            )
+            .expect("special floats are equal to themselves");
+        }
+
+        let mut headers = Headers::new();
+        headers.append("x-float", "inf");
+        headers.append("x-nan", "nan");
+        validate_headers_typed(&headers, &[("X-Float", ValueSpec::Float("Infinity"))])
+            .expect("infinity can be spelled differently");
+        validate_headers_typed(&headers, &[("X-Float", ValueSpec::Float("-Infinity"))])
+            .expect_err("the sign of infinity matters");
+        validate_headers_typed(&headers, &[("X-Nan", ValueSpec::Float("NaN"))])
+            .expect("all NaNs are equal");
+        validate_headers_typed(&headers, &[("X-Nan", ValueSpec::Float("1.0"))])
+            .expect_err("NaN isn't equal to a number");
+    }
+
+    #[test]
+    fn test_validate_headers_typed_negative_zero() {
+        let mut headers = Headers::new();
+        headers.append("x-float", "-0");
+        validate_headers_typed(&headers, &[("X-Float", ValueSpec::Float("-0.0"))])
+            .expect("-0 and -0.0 are the same float");
+        validate_headers_typed(&headers, &[("X-Float", ValueSpec::Float("0.0"))])
+            .expect_err("negative zero isn't zero");
+    }
+
+    #[test]
+    fn test_validate_headers_typed_timestamps() {
+        let mut headers = Headers::new();
+        headers.append("x-epoch", "1576540098");
+        headers.append("x-date-time", "2019-12-16T23:48:18.000Z");
+
+        validate_headers_typed(
+            &headers,
+            &[
+                (
+                    "X-Epoch",
+                    ValueSpec::Timestamp("Mon, 16 Dec 2019 23:48:18 GMT"),
+                ),
+                ("X-Date-Time", ValueSpec::Timestamp("2019-12-16T23:48:18Z")),
+            ],
+        )
+        .expect("timestamps are compared whatever their format");
+        validate_headers_typed(&headers, &[("X-Epoch", ValueSpec::Timestamp("1576540099"))])
+            .expect_err("timestamps are different");
+        validate_headers_typed(
+            &headers,
+            &[("X-Missing", ValueSpec::Timestamp("1576540098"))],
+        )
+        .expect_err("header is missing");
+    }
+
+    #[test]
+    fn test_validate_query_string_typed() {
+        // From the `RestJsonSupports{NaN,Infinity}FloatQueryValues` tests, with other spellings
+        let request = make_request(
+            "/foo?Float=nan&Double=inf&Exponent=1e1&Timestamp=2019-12-16T23%3A48%3A18.000Z&List=1&List=2",
+        );
+        validate_query_string_typed(
+            &request,
+            &[
+                ("Float", ValueSpec::Float("NaN")),
+                ("Double", ValueSpec::Float("Infinity")),
+                ("Exponent", ValueSpec::Float("10.0")),
+                ("Timestamp", ValueSpec::Timestamp("2019-12-16T23:48:18Z")),
+                ("List", ValueSpec::Float("2.0")),
+            ],
+        )
+        .expect("values are compared by what they mean");
+        assert_eq!(
+            validate_query_string_typed(&request, &[("List", ValueSpec::Float("3.0"))]),
+            Err(ProtocolTestFailure::InvalidQueryParam {
+                key: "List".to_owned(),
+                expected: "3.0".to_owned(),
+                found: vec!["1".to_owned(), "2".to_owned()],
+            })
+        );
+        validate_query_string_typed(&request, &[("Missing", ValueSpec::Exact("a"))])
+            .expect_err("param is missing");
+    }
+
     #[test]
     fn test_validate_json_body() {
         let expected = r#"{"abc": 5 }"#;
@@ -681,6 +1243,24 @@ mod tests {
         validate_body(actual, expected, MediaType::Json).expect_err("bodies do not match");
     }
 
+    #[test]
+    fn test_validate_nested_json_body() {
+        let expected = r#"{"a": {"b": [1, {"c": "d", "e": null}]}, "f": true}"#;
+        let actual = r#"{"f": true, "a": {"b": [1, {"e": null, "c": "d"}]}}"#;
+        validate_body(actual, expected, MediaType::Json).expect("key order doesn't matter");
+
+        let actual = r#"{"f": true, "a": {"b": [1, {"e": null, "c": "x"}]}}"#;
+        match validate_body(actual, expected, MediaType::Json) {
+            Err(ProtocolTestFailure::BodyDidNotMatch { hint, .. }) => {
+                assert!(hint.contains(".a.b[1].c"), "{hint}")
+            }
+            other => panic!("expected a body mismatch, found {:?}", other),
+        }
+
+        let actual = r#"{"f": true, "a": {"b": [{"e": null, "c": "d"}, 1]}}"#;
+        validate_body(actual, expected, MediaType::Json).expect_err("array order matters");
+    }
+
     #[test]
     fn test_validate_cbor_body() {
         let base64_encode = |v: &[u8]| base64_simd::STANDARD.encode_to_string(v);
@@ -735,6 +1315,54 @@ mod tests {
         validate_body(actual, expected, MediaType::Xml).expect_err("inputs are different");
     }
 
+    #[test]
+    fn test_validate_xml_body_namespaces() {
+        let expected = r#"<Foo xmlns="https://example.com/ns" b="2" a="1">
+            <Bar xmlns:p="https://example.com/p" p:attr="x">hi</Bar>
+        </Foo>"#;
+        let actual = r#"<Foo a="1" xmlns="https://example.com/ns" b="2"><Bar p:attr="x" xmlns:p="https://example.com/p">hi</Bar></Foo>"#;
+        validate_body(actual, expected, MediaType::Xml)
+            .expect("attribute order and whitespace don't matter");
+
+        let actual = r#"<Foo xmlns="https://example.com/other" a="1" b="2"><Bar xmlns:p="https://example.com/p" p:attr="x">hi</Bar></Foo>"#;
+        validate_body(actual, expected, MediaType::Xml)
+            .expect_err("default namespaces are different");
+
+        let actual = r#"<Foo xmlns="https://example.com/ns" a="1" b="2"><Bar xmlns:p="https://example.com/other" p:attr="x">hi</Bar></Foo>"#;
+        validate_body(actual, expected, MediaType::Xml)
+            .expect_err("prefixed namespaces are different");
+    }
+
+    #[test]
+    fn test_validate_url_encoded_form_body() {
+        let expected = "Action=Op&Version=2020-01-01&Name=a%20b&Tags.member.1=x";
+        let actual = "Tags.member.1=x&Action=Op&Name=a+b&Version=2020-01-01";
+        validate_body(actual, expected, MediaType::UrlEncodedForm)
+            .expect("order and encoding don't matter");
+
+        let actual = "Action=Op&Version=2020-01-01&Name=a%2Bb&Tags.member.1=x";
+        validate_body(actual, expected, MediaType::UrlEncodedForm)
+            .expect_err("an encoded `+` isn't a space");
+    }
+
+    #[test]
+    fn test_validate_blob_body() {
+        validate_body(b"\x00\x01binary", "\x00\x01binary", MediaType::Blob).expect("bytes match");
+        validate_body(b"binary ", "binary", MediaType::Blob)
+            .expect_err("blobs are compared exactly");
+
+        match validate_body(
+            &[0xff, 0x00][..],
+            "\u{0}",
+            MediaType::from("application/octet-stream"),
+        ) {
+            Err(ProtocolTestFailure::BodyDidNotMatch { hint, .. }) => {
+                assert_eq!("blobs differ from byte 0 (expected 1 bytes, found 2)", hint)
+            }
+            other => panic!("expected a body mismatch, found {:?}", other),
+        }
+    }
+
     #[test]
     fn test_validate_non_json_body() {
         let expected = r#"asdf"#;
@@ -6,23 +6,55 @@
 use crate::{pretty_comparison, ProtocolTestFailure};
 use regex_lite::Regex;
 
+/// Decodes a percent-encoded form key or value, where `+` stands for a space
+///
+/// Invalid escapes are kept as they are, so that they show up in the comparison.
+pub(crate) fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' => {
+                let escaped = input
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                if let Some(byte) = escaped {
+                    out.push(byte);
+                    i += 2;
+                } else {
+                    out.push(b'%');
+                }
+            }
+            other => out.push(other),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
 fn rewrite_url_encoded_map_keys(input: &str) -> (String, String) {
-    let mut itr = input.split('=');
-    let (key, value) = (itr.next().unwrap(), itr.next().unwrap());
+    let (key, value) = input.split_once('=').unwrap_or((input, ""));
+    let (key, value) = (percent_decode(key), percent_decode(value));
 
     let regex = Regex::new(r"^(.+)\.\d+\.(.+)$").unwrap();
-    if let Some(captures) = regex.captures(key) {
+    if let Some(captures) = regex.captures(&key) {
         let rewritten_key = format!(
             "{}.N.{}",
             captures.get(1).unwrap().as_str(),
             captures.get(2).unwrap().as_str()
         );
-        (rewritten_key, value.to_string())
+        (rewritten_key, value)
     } else {
-        (key.to_string(), value.to_string())
+        (key, value)
     }
 }
 
+/// Normalizes a form body into its sorted, decoded key/value pairs, one per line
+///
+/// Since the pairs are sorted, the bodies are compared as multisets of pairs: the order of the
+/// pairs, and how their keys and values are percent-encoded, don't matter.
 fn rewrite_url_encoded_body(input: &str) -> String {
     let mut entries: Vec<(String, String)> = input
         .split('&')
@@ -30,9 +62,7 @@ fn rewrite_url_encoded_body(input: &str) -> String {
         .filter(|s| !s.is_empty())
         .map(rewrite_url_encoded_map_keys)
         .collect();
-    if entries.len() > 2 {
-        entries[2..].sort_by(|a, b| a.1.cmp(&b.1));
-    }
+    entries.sort();
     let entries: Vec<String> = entries
         .into_iter()
         .map(|kv| format!("{}={}", kv.0, kv.1))
@@ -51,14 +81,15 @@ pub(crate) fn try_url_encoded_form_equivalent(
     } else {
         Err(ProtocolTestFailure::BodyDidNotMatch {
             comparison: pretty_comparison(&expected, &actual),
-            hint: "".into(),
+            hint: "form pairs are compared decoded and sorted".into(),
         })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::urlencoded::try_url_encoded_form_equivalent;
+    use crate::urlencoded::{percent_decode, try_url_encoded_form_equivalent};
+    use crate::ProtocolTestFailure;
 
     #[test]
     fn test_url_encoded_form_equivalent() {
@@ -98,4 +129,48 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn pairs_are_compared_as_multisets() {
+        assert_eq!(
+            Ok(()),
+            try_url_encoded_form_equivalent(
+                "Action=Something&Version=test&List.member.1=b&List.member.2=a",
+                "Version=test&List.member.2=a&Action=Something&List.member.1=b",
+            )
+        );
+
+        // The same pair twice isn't the same as once
+        assert!(try_url_encoded_form_equivalent(
+            "Action=Something&A=1&A=1",
+            "Action=Something&A=1"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn percent_encoding_differences() {
+        assert_eq!("a b/c", percent_decode("a+b%2fc"));
+        assert_eq!("100%", percent_decode("100%"));
+        assert_eq!("%zz", percent_decode("%zz"));
+
+        assert_eq!(
+            Ok(()),
+            try_url_encoded_form_equivalent(
+                "Action=Something&Value=a%20b%2Fc&Key%2E1=%e2%9c%93",
+                "Action=Something&Value=a+b%2fc&Key.1=\u{2713}",
+            )
+        );
+
+        // An encoded `&` is part of the value, not a separator
+        let err = try_url_encoded_form_equivalent(
+            "Action=Something&Value=a%26b",
+            "Action=Something&Value=a&b",
+        )
+        .expect_err("`&` must be encoded in values");
+        assert!(
+            matches!(err, ProtocolTestFailure::BodyDidNotMatch { .. }),
+            "{err}"
+        );
+    }
 }
@@ -449,4 +449,14 @@ mod tests {
         QueryWriter::new(&mut out, "Some Action", "1 2").finish();
         assert_eq!("Action=Some%20Action&Version=1%202", out);
     }
+
+    #[test]
+    fn space_and_plus() {
+        let mut out = String::new();
+        let mut writer = QueryWriter::new(&mut out, "SomeAction", "1.0");
+        writer.prefix("Space").string("c d");
+        writer.prefix("Plus").string("g+h");
+        writer.finish();
+        assert_eq!("Action=SomeAction&Version=1.0&Space=c%20d&Plus=g%2Bh", out);
+    }
 }
@@ -93,6 +93,8 @@ macro_rules! new_type_future {
 
 pub mod auth;
 
+pub mod config_validation;
+
 pub mod connection;
 
 pub mod connector_metadata;
@@ -110,6 +112,8 @@ pub mod interceptors;
 
 pub mod orchestrator;
 
+pub mod random;
+
 pub mod result;
 
 pub mod retries;
@@ -11,11 +11,13 @@ use crate::client::orchestrator::HttpRequest;
 use crate::client::runtime_components::sealed::ValidateConfig;
 use crate::client::runtime_components::{GetIdentityResolver, RuntimeComponents};
 use crate::impl_shared_conversions;
-use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreAppend, StoreReplace};
 use aws_smithy_types::type_erasure::TypeErasedBox;
 use aws_smithy_types::Document;
 use std::borrow::Cow;
 use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 
 /// Auth schemes for the HTTP `Authorization` header.
@@ -204,6 +206,46 @@ pub trait Sign: Send + Sync + fmt::Debug {
     ) -> Result<(), BoxError>;
 }
 
+/// Work that a signer depends on, which the orchestrator waits for before signing a request.
+///
+/// [`Sign`] is synchronous, so a signer can't wait for work started ahead of time, like hashing
+/// a large request payload in parallel with endpoint and identity resolution, without blocking.
+/// When this is in the config bag, the orchestrator awaits it right before signing each attempt,
+/// so that the signer finds the result of the work ready.
+///
+/// Pending work is stored with [`Layer::store_append`](aws_smithy_types::config_bag::Layer::store_append),
+/// so that several interceptors can each start their own work. The orchestrator awaits all of it.
+#[derive(Clone)]
+pub struct PendingSigningWork(
+    Arc<dyn Fn() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>,
+);
+
+impl PendingSigningWork {
+    /// Creates a new `PendingSigningWork` from a function returning a future that completes
+    /// once the work is done.
+    pub fn new<F>(wait: impl Fn() -> F + Send + Sync + 'static) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        Self(Arc::new(move || Box::pin(wait())))
+    }
+
+    /// Returns a future that completes once the work is done.
+    pub fn wait(&self) -> impl Future<Output = ()> + Send + 'static {
+        (self.0)()
+    }
+}
+
+impl fmt::Debug for PendingSigningWork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PendingSigningWork")
+    }
+}
+
+impl Storable for PendingSigningWork {
+    type Storer = StoreAppend<Self>;
+}
+
 /// Endpoint configuration for the selected auth scheme.
 ///
 /// The configuration held by this struct originates from the endpoint rule set in the service model.
@@ -0,0 +1,171 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Reports of problems found while validating a client's configuration.
+//!
+//! A client config is validated once, when it is built. Rather than stopping at the first
+//! problem, validation collects every problem it finds into a [`ConfigValidationReport`], so
+//! that they can all be fixed at once.
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+
+/// A machine-readable code identifying a kind of config problem.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConfigIssueCode {
+    /// No behavior major version was set.
+    MissingBehaviorVersion,
+    /// No HTTP client was configured, so requests can't be sent.
+    MissingHttpClient,
+    /// A feature that requires an async sleep implementation is enabled, but none was configured.
+    MissingSleepImpl,
+    /// Retries are enabled, but no async sleep implementation was configured.
+    RetriesEnabledWithoutSleep,
+    /// A feature that requires a time source is enabled, but none was configured.
+    MissingTimeSource,
+    /// A config validator rejected the configuration.
+    InvalidConfiguration,
+}
+
+impl ConfigIssueCode {
+    /// Returns the name of this code, e.g. `"MissingSleepImpl"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MissingBehaviorVersion => "MissingBehaviorVersion",
+            Self::MissingHttpClient => "MissingHttpClient",
+            Self::MissingSleepImpl => "MissingSleepImpl",
+            Self::RetriesEnabledWithoutSleep => "RetriesEnabledWithoutSleep",
+            Self::MissingTimeSource => "MissingTimeSource",
+            Self::InvalidConfiguration => "InvalidConfiguration",
+        }
+    }
+}
+
+impl fmt::Display for ConfigIssueCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How serious a config problem is.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// Requests sent with this config will fail.
+    Fatal,
+    /// Some requests may work with this config, but it is likely a mistake.
+    Warning,
+}
+
+/// A single problem found in a client's configuration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigIssue {
+    code: ConfigIssueCode,
+    severity: Severity,
+    message: Cow<'static, str>,
+}
+
+impl ConfigIssue {
+    /// Creates a fatal issue.
+    ///
+    /// The message should say how to fix the problem, e.g. which config builder method to call.
+    pub fn fatal(code: ConfigIssueCode, message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            code,
+            severity: Severity::Fatal,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an issue that doesn't prevent requests from being sent.
+    ///
+    /// The message should say how to fix the problem, e.g. which config builder method to call.
+    pub fn warning(code: ConfigIssueCode, message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            code,
+            severity: Severity::Warning,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the code identifying this issue.
+    pub fn code(&self) -> ConfigIssueCode {
+        self.code
+    }
+
+    /// Returns the severity of this issue.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Returns true if requests sent with this config will fail.
+    pub fn is_fatal(&self) -> bool {
+        self.severity == Severity::Fatal
+    }
+
+    /// Returns a description of the issue, and how to fix it.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.code, self.message)
+    }
+}
+
+/// Every problem found while validating a client's configuration.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConfigValidationReport {
+    issues: Vec<ConfigIssue>,
+}
+
+impl ConfigValidationReport {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an issue to the report.
+    pub fn push(&mut self, issue: ConfigIssue) -> &mut Self {
+        self.issues.push(issue);
+        self
+    }
+
+    /// Returns the issues in the report, in the order they were found.
+    pub fn issues(&self) -> &[ConfigIssue] {
+        &self.issues
+    }
+
+    /// Returns the codes of the issues in the report.
+    pub fn codes(&self) -> impl Iterator<Item = ConfigIssueCode> + '_ {
+        self.issues.iter().map(ConfigIssue::code)
+    }
+
+    /// Returns true if no issues were found.
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns true if any of the issues will cause requests to fail.
+    pub fn has_fatal_issues(&self) -> bool {
+        self.issues.iter().any(ConfigIssue::is_fatal)
+    }
+}
+
+impl fmt::Display for ConfigValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid client configuration")?;
+        for issue in &self.issues {
+            write!(f, "\n  {issue}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ConfigValidationReport {}
@@ -8,13 +8,21 @@
 use std::fmt::{Debug, Formatter};
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Metadata that tracks the state of an active connection.
+///
+/// HTTP clients that capture it add it to the extensions of their responses, where interceptors
+/// can find it with [`extension`](crate::http::Response::extension). Errors make it available with
+/// [`SdkError::connection_metadata`](crate::client::result::SdkError::connection_metadata).
 #[derive(Clone)]
 pub struct ConnectionMetadata {
     is_proxied: bool,
     remote_addr: Option<SocketAddr>,
     local_addr: Option<SocketAddr>,
+    reused: Option<bool>,
+    age: Option<Duration>,
+    handshake_duration: Option<Duration>,
     poison_fn: Arc<dyn Fn() + Send + Sync>,
 }
 
@@ -41,8 +49,11 @@ impl ConnectionMetadata {
         Self {
             is_proxied,
             remote_addr,
-            // need to use builder to set this field
+            // need to use builder to set these fields
             local_addr: None,
+            reused: None,
+            age: None,
+            handshake_duration: None,
             poison_fn: Arc::new(poison),
         }
     }
@@ -61,6 +72,24 @@ impl ConnectionMetadata {
     pub fn local_addr(&self) -> Option<SocketAddr> {
         self.local_addr
     }
+
+    /// Returns whether the connection was reused from a pool, rather than established for this
+    /// request, if that's known.
+    pub fn reused(&self) -> Option<bool> {
+        self.reused
+    }
+
+    /// Returns how long the connection had been established for when the request was sent on it,
+    /// if that's known.
+    pub fn age(&self) -> Option<Duration> {
+        self.age
+    }
+
+    /// Returns how long establishing the connection took, including any TLS handshake, if the
+    /// connection was established for this request and that's known.
+    pub fn handshake_duration(&self) -> Option<Duration> {
+        self.handshake_duration
+    }
 }
 
 impl Debug for ConnectionMetadata {
@@ -69,6 +98,9 @@ impl Debug for ConnectionMetadata {
             .field("is_proxied", &self.is_proxied)
             .field("remote_addr", &self.remote_addr)
             .field("local_addr", &self.local_addr)
+            .field("reused", &self.reused)
+            .field("age", &self.age)
+            .field("handshake_duration", &self.handshake_duration)
             .finish()
     }
 }
@@ -79,6 +111,9 @@ pub struct ConnectionMetadataBuilder {
     is_proxied: Option<bool>,
     remote_addr: Option<SocketAddr>,
     local_addr: Option<SocketAddr>,
+    reused: Option<bool>,
+    age: Option<Duration>,
+    handshake_duration: Option<Duration>,
     poison_fn: Option<Arc<dyn Fn() + Send + Sync>>,
 }
 
@@ -88,6 +123,9 @@ impl Debug for ConnectionMetadataBuilder {
             .field("is_proxied", &self.is_proxied)
             .field("remote_addr", &self.remote_addr)
             .field("local_addr", &self.local_addr)
+            .field("reused", &self.reused)
+            .field("age", &self.age)
+            .field("handshake_duration", &self.handshake_duration)
             .finish()
     }
 }
@@ -134,6 +172,42 @@ impl ConnectionMetadataBuilder {
         self
     }
 
+    /// Set whether the connection was reused from a pool, rather than established for this request.
+    pub fn reused(mut self, reused: bool) -> Self {
+        self.set_reused(Some(reused));
+        self
+    }
+
+    /// Set whether the connection was reused from a pool, rather than established for this request.
+    pub fn set_reused(&mut self, reused: Option<bool>) -> &mut Self {
+        self.reused = reused;
+        self
+    }
+
+    /// Set how long the connection had been established for when the request was sent on it.
+    pub fn age(mut self, age: Duration) -> Self {
+        self.set_age(Some(age));
+        self
+    }
+
+    /// Set how long the connection had been established for when the request was sent on it.
+    pub fn set_age(&mut self, age: Option<Duration>) -> &mut Self {
+        self.age = age;
+        self
+    }
+
+    /// Set how long establishing the connection took, including any TLS handshake.
+    pub fn handshake_duration(mut self, handshake_duration: Duration) -> Self {
+        self.set_handshake_duration(Some(handshake_duration));
+        self
+    }
+
+    /// Set how long establishing the connection took, including any TLS handshake.
+    pub fn set_handshake_duration(&mut self, handshake_duration: Option<Duration>) -> &mut Self {
+        self.handshake_duration = handshake_duration;
+        self
+    }
+
     /// Set a closure which will poison the associated connection.
     ///
     /// A poisoned connection will not be reused for subsequent requests by the pool
@@ -166,6 +240,9 @@ impl ConnectionMetadataBuilder {
                 .expect("is_proxied should be set for ConnectionMetadata"),
             remote_addr: self.remote_addr,
             local_addr: self.local_addr,
+            reused: self.reused,
+            age: self.age,
+            handshake_duration: self.handshake_duration,
             poison_fn: self
                 .poison_fn
                 .expect("poison_fn should be set for ConnectionMetadata"),
@@ -238,6 +315,9 @@ mod tests {
 
         assert_eq!(metadata1.local_addr(), None);
         assert_eq!(metadata1.remote_addr(), None);
+        assert_eq!(metadata1.reused(), None);
+        assert_eq!(metadata1.age(), None);
+        assert_eq!(metadata1.handshake_duration(), None);
 
         let metadata2 = ConnectionMetadataBuilder::new()
             .proxied(true)
@@ -6,6 +6,7 @@
 //! APIs needed to configure endpoint resolution for clients.
 
 use crate::box_error::BoxError;
+use crate::client::interceptors::context::Input;
 use crate::client::runtime_components::sealed::ValidateConfig;
 use crate::impl_shared_conversions;
 use aws_smithy_types::config_bag::{Storable, StoreReplace};
@@ -46,10 +47,132 @@ impl Storable for EndpointResolverParams {
     type Storer = StoreReplace<Self>;
 }
 
+/// Where the value of an endpoint parameter came from.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EndpointParamSource {
+    /// A built-in parameter loaded from the client config, such as the region.
+    BuiltIn,
+    /// A parameter set on the client config from the service's `@clientContextParams` trait.
+    ClientContextParam,
+    /// A value fixed for the operation by its `@staticContextParams` trait.
+    StaticContextParam,
+    /// An operation input member bound with the `@contextParam` trait.
+    ContextParam,
+    /// A value selected from the operation input by the `@operationContextParams` trait.
+    OperationContextParam,
+}
+
+/// Records which bindings contributed a value to each endpoint parameter.
+///
+/// Generated clients store this next to the [`EndpointResolverParams`] so that debug logging can
+/// show where each parameter value came from. Parameters that no binding set a value for
+/// (including ones bound to unset input members) don't appear here.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct EndpointParamBindings {
+    bindings: Vec<(&'static str, EndpointParamSource)>,
+}
+
+impl EndpointParamBindings {
+    /// Creates an empty set of bindings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `source` set the value of the parameter named `name`.
+    ///
+    /// Bindings are recorded from lowest to highest precedence, so this replaces any
+    /// source previously recorded for the same parameter.
+    pub fn record(&mut self, name: &'static str, source: EndpointParamSource) -> &mut Self {
+        match self.bindings.iter_mut().find(|(n, _)| *n == name) {
+            Some(binding) => binding.1 = source,
+            None => self.bindings.push((name, source)),
+        }
+        self
+    }
+
+    /// Returns the source that set the value of the parameter named `name`, if any.
+    pub fn get(&self, name: &str) -> Option<EndpointParamSource> {
+        self.bindings
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, source)| *source)
+    }
+
+    /// Returns the parameter names and sources, in the order they were first recorded.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, EndpointParamSource)> + '_ {
+        self.bindings.iter().copied()
+    }
+}
+
+impl fmt::Debug for EndpointParamBindings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl Storable for EndpointParamBindings {
+    type Storer = StoreReplace<Self>;
+}
+
 /// Configurable endpoint resolver implementation.
 pub trait ResolveEndpoint: Send + Sync + fmt::Debug {
     /// Asynchronously resolves an endpoint to use from the given endpoint parameters.
     fn resolve_endpoint<'a>(&'a self, params: &'a EndpointResolverParams) -> EndpointFuture<'a>;
+
+    /// Resolves an endpoint with access to the operation input.
+    ///
+    /// Implement [`ResolveEndpointAsync`] rather than overriding this method.
+    #[doc(hidden)]
+    fn resolve_endpoint_with_input<'a>(
+        &'a self,
+        params: &'a EndpointResolverParams,
+        _input: &'a Input,
+    ) -> EndpointFuture<'a> {
+        self.resolve_endpoint(params)
+    }
+
+    /// Returns true if this resolver must be given the operation input.
+    #[doc(hidden)]
+    fn resolves_with_input(&self) -> bool {
+        false
+    }
+}
+
+/// Configurable endpoint resolver with access to the operation input.
+///
+/// In addition to the endpoint parameters, this resolver is given the operation input. Like the
+/// input given to interceptors, it is type-erased and can be downcast to the concrete input type
+/// of the operation. This makes it possible to pick an endpoint based on a member of the input,
+/// or by looking it up in an async source such as a service registry.
+///
+/// Resolvers with access to the input run once per operation, before the input is serialized,
+/// and all attempts of the operation use the resolved endpoint. Resolution is bounded by the
+/// [endpoint resolution timeout](aws_smithy_types::timeout::TimeoutConfig::endpoint_resolution_timeout).
+/// If resolution fails or times out, the operation fails with a construction failure.
+///
+/// Every [`ResolveEndpoint`] implementation is also a `ResolveEndpointAsync` that ignores the
+/// input. Use [`SharedEndpointResolver::new_async`] to configure a resolver implementing this trait.
+pub trait ResolveEndpointAsync: Send + Sync + fmt::Debug {
+    /// Asynchronously resolves an endpoint to use from the given endpoint parameters and operation input.
+    fn resolve_endpoint_async<'a>(
+        &'a self,
+        params: &'a EndpointResolverParams,
+        input: &'a Input,
+    ) -> EndpointFuture<'a>;
+}
+
+impl<T> ResolveEndpointAsync for T
+where
+    T: ResolveEndpoint + ?Sized,
+{
+    fn resolve_endpoint_async<'a>(
+        &'a self,
+        params: &'a EndpointResolverParams,
+        input: &'a Input,
+    ) -> EndpointFuture<'a> {
+        self.resolve_endpoint_with_input(params, input)
+    }
 }
 
 /// Shared endpoint resolver.
@@ -63,12 +186,57 @@ impl SharedEndpointResolver {
     pub fn new(endpoint_resolver: impl ResolveEndpoint + 'static) -> Self {
         Self(Arc::new(endpoint_resolver))
     }
+
+    /// Creates a new [`SharedEndpointResolver`] from a resolver with access to the operation input.
+    pub fn new_async(endpoint_resolver: impl ResolveEndpointAsync + 'static) -> Self {
+        Self(Arc::new(WithInput(endpoint_resolver)))
+    }
 }
 
 impl ResolveEndpoint for SharedEndpointResolver {
     fn resolve_endpoint<'a>(&'a self, params: &'a EndpointResolverParams) -> EndpointFuture<'a> {
         self.0.resolve_endpoint(params)
     }
+
+    fn resolve_endpoint_with_input<'a>(
+        &'a self,
+        params: &'a EndpointResolverParams,
+        input: &'a Input,
+    ) -> EndpointFuture<'a> {
+        self.0.resolve_endpoint_with_input(params, input)
+    }
+
+    fn resolves_with_input(&self) -> bool {
+        self.0.resolves_with_input()
+    }
+}
+
+/// Adapts a [`ResolveEndpointAsync`] so that it can be shared as a [`SharedEndpointResolver`].
+#[derive(Debug)]
+struct WithInput<R>(R);
+
+impl<R> ResolveEndpoint for WithInput<R>
+where
+    R: ResolveEndpointAsync,
+{
+    fn resolve_endpoint<'a>(&'a self, _params: &'a EndpointResolverParams) -> EndpointFuture<'a> {
+        EndpointFuture::ready(Err(error::ResolveEndpointError::message(
+            "this endpoint resolver requires the operation input",
+        )
+        .into()))
+    }
+
+    fn resolve_endpoint_with_input<'a>(
+        &'a self,
+        params: &'a EndpointResolverParams,
+        input: &'a Input,
+    ) -> EndpointFuture<'a> {
+        self.0.resolve_endpoint_async(params, input)
+    }
+
+    fn resolves_with_input(&self) -> bool {
+        true
+    }
 }
 
 impl ValidateConfig for SharedEndpointResolver {}
@@ -101,6 +269,37 @@ impl Storable for EndpointPrefix {
     type Storer = StoreReplace<Self>;
 }
 
+/// Whether to check, before the request is serialized, that the host of an `https` endpoint
+/// can be covered by a wildcard TLS certificate.
+///
+/// A wildcard certificate only covers a single label, so a host prefix that adds several labels
+/// to the host (for example, a bucket name containing dots) fails TLS verification when
+/// the connection is made. When this check is enabled, such requests fail with a construction
+/// failure instead, before any connection is attempted.
+///
+/// Enabling the check resolves the endpoint once per operation, before serialization, rather than
+/// once per attempt.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PreflightTlsHostnameCheck {
+    enabled: bool,
+}
+
+impl PreflightTlsHostnameCheck {
+    /// Creates a new `PreflightTlsHostnameCheck`.
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// Returns true if the check is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl Storable for PreflightTlsHostnameCheck {
+    type Storer = StoreReplace<Self>;
+}
+
 /// Errors related to endpoint resolution and validation
 pub mod error {
     use crate::box_error::BoxError;
@@ -105,6 +105,22 @@ impl ResolveCachedIdentity for SharedIdentityCache {
         self.0
             .resolve_cached_identity(resolver, runtime_components, config_bag)
     }
+
+    fn validate_base_client_config(
+        &self,
+        runtime_components: &RuntimeComponentsBuilder,
+        cfg: &ConfigBag,
+    ) -> Result<(), BoxError> {
+        self.0.validate_base_client_config(runtime_components, cfg)
+    }
+
+    fn validate_final_config(
+        &self,
+        runtime_components: &RuntimeComponents,
+        cfg: &ConfigBag,
+    ) -> Result<(), BoxError> {
+        self.0.validate_final_config(runtime_components, cfg)
+    }
 }
 
 impl ValidateConfig for SharedIdentityResolver {}
@@ -65,6 +65,14 @@ macro_rules! interceptor_trait_fn {
 ///   of the SDK ’s request execution pipeline. Hooks are either "read" hooks, which make it possible
 ///   to read in-flight request or response messages, or "read/write" hooks, which make it possible
 ///   to modify in-flight request or output messages.
+///
+/// ## Shared state
+/// A single interceptor is shared by all the requests made with a client, concurrently. Mutable
+/// state kept by an interceptor, such as a counter or a clock skew offset, should be stored in an
+/// [`aws_smithy_async::sync::SharedCell`] rather than behind a `std::sync::Mutex`, whose guard
+/// blocks the executor thread if it's held across an `.await`, or a `tokio::sync::Mutex`, which
+/// ties the interceptor to the Tokio runtime. State that only matters to a single execution
+/// belongs in the [`ConfigBag`] instead.
 pub trait Intercept: fmt::Debug + Send + Sync {
     /// The name of this interceptor, used in error messages for debugging.
     fn name(&self) -> &'static str;
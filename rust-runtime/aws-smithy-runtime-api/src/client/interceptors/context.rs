@@ -29,6 +29,7 @@
 
 use crate::client::orchestrator::{HttpRequest, HttpResponse, OrchestratorError};
 use crate::client::result::SdkError;
+use crate::client::retries::{ReplayInfo, RequestAttempts};
 use aws_smithy_types::config_bag::ConfigBag;
 use aws_smithy_types::type_erasure::{TypeErasedBox, TypeErasedError};
 use phase::Phase;
@@ -301,6 +302,22 @@ impl<I, O, E> InterceptorContext<I, O, E> {
         self.phase = Phase::Transmit;
     }
 
+    /// Return to the BeforeTransmit phase with the next request of a redirect.
+    ///
+    /// The redirected request is signed and goes through the transmit interceptors again before
+    /// the orchestrator re-enters the Transmit phase.
+    ///
+    /// Note: This method is intended for internal use only.
+    pub fn enter_redirect(&mut self, request: Request) {
+        debug!("following a redirect; returning to the \'before transmit\' phase");
+        debug_assert!(
+            self.phase.is_transmit(),
+            "called enter_redirect but phase is not 'transmit'"
+        );
+        self.request = Some(request);
+        self.phase = Phase::BeforeTransmit;
+    }
+
     /// Advance to the BeforeDeserialization phase.
     ///
     /// Note: This method is intended for internal use only.
@@ -364,17 +381,36 @@ impl<I, O, E> InterceptorContext<I, O, E> {
     /// Returns false if rewinding isn't possible
     ///
     /// Note: This method is intended for internal use only.
-    pub fn rewind(&mut self, _cfg: &mut ConfigBag) -> RewindResult {
+    pub fn rewind(&mut self, cfg: &mut ConfigBag) -> RewindResult {
+        let attempt = cfg
+            .load::<RequestAttempts>()
+            .map_or(1, RequestAttempts::attempts)
+            + 1;
         // If request_checkpoint was never set, but we've already made one attempt,
         // then this is not a retryable request
         let request_checkpoint = match (self.request_checkpoint.as_ref(), self.tainted) {
-            (None, true) => return RewindResult::Impossible,
+            (None, true) => {
+                debug!(attempt, "request body can't be replayed; skipping attempt");
+                cfg.interceptor_state()
+                    .store_append(ReplayInfo::new(attempt, None, None));
+                return RewindResult::Impossible;
+            }
             (_, false) => {
                 self.tainted = true;
                 return RewindResult::Unnecessary;
             }
             (Some(req), _) => req.try_clone(),
         };
+        if let Some(body) = request_checkpoint.as_ref().map(|request| request.body()) {
+            let replay_info = ReplayInfo::new(attempt, body.replay_source(), body.content_length());
+            debug!(
+                attempt,
+                source = ?replay_info.source(),
+                bytes_replayed = ?replay_info.bytes_replayed(),
+                "replaying request body"
+            );
+            cfg.interceptor_state().store_append(replay_info);
+        }
 
         // Otherwise, rewind to the saved request checkpoint
         self.phase = Phase::BeforeTransmit;
@@ -432,7 +468,9 @@ where
         result: Result<O, OrchestratorError<E>>,
     ) -> Result<O, SdkError<E, HttpResponse>> {
         let response = self.response.take();
-        result.map_err(|error| OrchestratorError::into_sdk_error(error, &self.phase, response))
+        result.map_err(|error| {
+            OrchestratorError::into_sdk_error(error, &self.phase, response).with_response_summary()
+        })
     }
 
     /// Mark this context as failed due to errors during the operation. Any errors already contained
@@ -583,6 +621,14 @@ mod tests {
         context.set_output_or_error(Err(OrchestratorError::operation(error)));
 
         assert_eq!(context.rewind(&mut cfg), RewindResult::Occurred);
+        assert_eq!(
+            vec![&ReplayInfo::new(
+                2,
+                Some(aws_smithy_types::body::ReplaySource::MemoryClone),
+                Some(0)
+            )],
+            cfg.load::<ReplayInfo>().collect::<Vec<_>>()
+        );
 
         // Now after rewinding, the test header should be its original value
         assert_eq!(
@@ -71,6 +71,8 @@ enum ErrorKind<E> {
     Interceptor { source: InterceptorError },
     /// An error returned by a service.
     Operation { err: E },
+    /// An error returned by a service because the precondition of a conditional request failed.
+    PreconditionFailed { err: E },
     /// An error that occurs when a request times out.
     Timeout { source: BoxError },
     /// An error that occurs when request dispatch fails.
@@ -106,17 +108,38 @@ impl<E> OrchestratorError<E> {
 
     /// True if the underlying error is an operation error.
     pub fn is_operation_error(&self) -> bool {
-        matches!(self.kind, ErrorKind::Operation { .. })
+        matches!(
+            self.kind,
+            ErrorKind::Operation { .. } | ErrorKind::PreconditionFailed { .. }
+        )
     }
 
     /// Return this orchestrator error as an operation error if possible.
     pub fn as_operation_error(&self) -> Option<&E> {
         match &self.kind {
-            ErrorKind::Operation { err } => Some(err),
+            ErrorKind::Operation { err } | ErrorKind::PreconditionFailed { err } => Some(err),
             _ => None,
         }
     }
 
+    /// Marks an operation error as the result of a failed precondition of a conditional request.
+    ///
+    /// The error is then returned as [`SdkError::PreconditionFailed`]. Errors other than operation
+    /// errors are returned unchanged.
+    pub fn into_precondition_failed(self) -> Self {
+        match self.kind {
+            ErrorKind::Operation { err } => Self {
+                kind: ErrorKind::PreconditionFailed { err },
+            },
+            kind => Self { kind },
+        }
+    }
+
+    /// True if the underlying error is an operation error for a failed precondition.
+    pub fn is_precondition_failed(&self) -> bool {
+        matches!(self.kind, ErrorKind::PreconditionFailed { .. })
+    }
+
     /// Create an interceptor error with the given source.
     pub fn interceptor(source: InterceptorError) -> Self {
         Self {
@@ -199,6 +222,9 @@ impl<E> OrchestratorError<E> {
                 debug_assert!(phase.is_after_deserialization(), "operation errors are a result of successfully receiving and parsing a response from the server. Therefore, we must be in the 'After Deserialization' phase.");
                 SdkError::service_error(err, response.expect("phase has a response"))
             }
+            ErrorKind::PreconditionFailed { err } => {
+                SdkError::precondition_failed(err, response.expect("phase has a response"))
+            }
             ErrorKind::Connector { source } => SdkError::dispatch_failure(source),
             ErrorKind::Timeout { source } => SdkError::timeout_error(source),
             ErrorKind::Response { source } => SdkError::response_error(source, response.unwrap()),
@@ -220,6 +246,9 @@ impl<E> OrchestratorError<E> {
         let kind = match self.kind {
             ErrorKind::Connector { source } => ErrorKind::Connector { source },
             ErrorKind::Operation { err } => ErrorKind::Operation { err: map(err) },
+            ErrorKind::PreconditionFailed { err } => {
+                ErrorKind::PreconditionFailed { err: map(err) }
+            }
             ErrorKind::Interceptor { source } => ErrorKind::Interceptor { source },
             ErrorKind::Response { source } => ErrorKind::Response { source },
             ErrorKind::Timeout { source } => ErrorKind::Timeout { source },
@@ -236,7 +265,7 @@ where
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         Some(match &self.kind {
             ErrorKind::Connector { source } => source as _,
-            ErrorKind::Operation { err } => err as _,
+            ErrorKind::Operation { err } | ErrorKind::PreconditionFailed { err } => err as _,
             ErrorKind::Interceptor { source } => source as _,
             ErrorKind::Response { source } => source.as_ref(),
             ErrorKind::Timeout { source } => source.as_ref(),
@@ -250,6 +279,7 @@ impl<E> fmt::Display for OrchestratorError<E> {
         f.write_str(match self.kind {
             ErrorKind::Connector { .. } => "connector error",
             ErrorKind::Operation { .. } => "operation error",
+            ErrorKind::PreconditionFailed { .. } => "precondition failed",
             ErrorKind::Interceptor { .. } => "interceptor error",
             ErrorKind::Response { .. } => "response error",
             ErrorKind::Timeout { .. } => "timeout",
@@ -0,0 +1,81 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Interfaces for sources of randomness.
+//!
+//! The client draws from the random source in its runtime components whenever it needs randomness,
+//! such as for retry jitter, invocation IDs, idempotency tokens, and multipart boundaries.
+//! Replacing it with a seeded source makes these values reproducible.
+
+use crate::impl_shared_conversions;
+use std::fmt;
+use std::sync::Arc;
+
+/// A source of random values.
+pub trait RandomSource: fmt::Debug + Send + Sync {
+    /// Fills `dest` with random bytes.
+    fn fill_bytes(&self, dest: &mut [u8]);
+
+    /// Returns a random `u64`.
+    fn next_u64(&self) -> u64;
+
+    /// Returns a random `f64` in the range `[0, 1)`.
+    fn next_f64(&self) -> f64 {
+        // Use the 53 most significant bits, since that is the precision of an `f64`
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Shared instance of [`RandomSource`].
+#[derive(Clone, Debug)]
+pub struct SharedRandomSource(Arc<dyn RandomSource>);
+
+impl SharedRandomSource {
+    /// Creates a new `SharedRandomSource`.
+    pub fn new(random_source: impl RandomSource + 'static) -> Self {
+        Self(Arc::new(random_source))
+    }
+}
+
+impl RandomSource for SharedRandomSource {
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn next_u64(&self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn next_f64(&self) -> f64 {
+        self.0.next_f64()
+    }
+}
+
+impl_shared_conversions!(convert SharedRandomSource from RandomSource using SharedRandomSource::new);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Fixed(u64);
+
+    impl RandomSource for Fixed {
+        fn fill_bytes(&self, dest: &mut [u8]) {
+            dest.fill(0);
+        }
+
+        fn next_u64(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn next_f64_is_in_unit_range() {
+        assert_eq!(0.0, Fixed(0).next_f64());
+        assert_eq!(0.5, Fixed(1 << 63).next_f64());
+        assert!(Fixed(u64::MAX).next_f64() < 1.0);
+    }
+}
@@ -6,6 +6,8 @@
 //! Types for [error](SdkError) responses.
 
 use crate::client::connection::ConnectionMetadata;
+use crate::client::orchestrator::HttpResponse;
+use crate::http::{StatusCode, Version};
 use aws_smithy_types::error::metadata::{ProvideErrorMetadata, EMPTY_ERROR_METADATA};
 use aws_smithy_types::error::operation::BuildError;
 use aws_smithy_types::error::ErrorMetadata;
@@ -107,6 +109,7 @@ pub mod builders {
             ResponseError {
                 source: self.source.expect("source is required"),
                 raw: self.raw.expect("a raw response is required"),
+                summary: None,
             }
         }
     }
@@ -162,6 +165,7 @@ pub mod builders {
             ServiceError {
                 source: self.source.expect("source is required"),
                 raw: self.raw.expect("a raw response is required"),
+                summary: None,
             }
         }
     }
@@ -234,6 +238,58 @@ impl DispatchFailure {
     pub fn as_connector_error(&self) -> Option<&ConnectorError> {
         Some(&self.source)
     }
+
+    /// Returns metadata about the connection the request was dispatched on, if one was established
+    /// and the HTTP client captured it.
+    pub fn connection_metadata(&self) -> Option<&ConnectionMetadata> {
+        self.source.connection_metadata()
+    }
+}
+
+/// Maximum number of characters of the content type that are kept to render an error
+const CONTENT_TYPE_SNIPPET_LEN: usize = 64;
+
+/// Returns the start of the `Content-Type` of `response`, if it has one.
+fn content_type_snippet(response: &HttpResponse) -> Option<&str> {
+    let content_type = response.headers().get("content-type")?;
+    Some(
+        match content_type.char_indices().nth(CONTENT_TYPE_SNIPPET_LEN) {
+            Some((end, _)) => &content_type[..end],
+            None => content_type,
+        },
+    )
+}
+
+/// The status line and content type of an HTTP response.
+///
+/// These are rendered with errors even when the body couldn't be parsed, since a response that
+/// comes from a proxy or a load balancer rather than the service often doesn't have a parseable
+/// body.
+#[derive(Clone, Debug)]
+struct ResponseSummary {
+    status: StatusCode,
+    version: Version,
+    content_type: Option<String>,
+}
+
+impl ResponseSummary {
+    fn new(response: &HttpResponse) -> Self {
+        Self {
+            status: response.status(),
+            version: response.version(),
+            content_type: content_type_snippet(response).map(str::to_owned),
+        }
+    }
+}
+
+impl Display for ResponseSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.version, self.status)?;
+        if let Some(content_type) = &self.content_type {
+            write!(f, ", content-type: {content_type}")?;
+        }
+        Ok(())
+    }
 }
 
 /// Error context for [`SdkError::ResponseError`]
@@ -243,6 +299,8 @@ pub struct ResponseError<R> {
     source: BoxError,
     /// Raw response that was available
     raw: R,
+    /// Status line and content type of the raw response, if it is an HTTP response
+    summary: Option<ResponseSummary>,
 }
 
 impl<R> ResponseError<R> {
@@ -262,6 +320,32 @@ impl<R> ResponseError<R> {
     }
 }
 
+impl ResponseError<HttpResponse> {
+    /// Returns the HTTP status code of the response
+    pub fn http_status(&self) -> StatusCode {
+        self.raw.status()
+    }
+
+    /// Returns the HTTP version of the response
+    pub fn http_version(&self) -> Version {
+        self.raw.version()
+    }
+
+    /// Returns the start of the `Content-Type` of the response, if it has one
+    ///
+    /// An unexpected content type, such as `text/html`, usually means that the response came from
+    /// a proxy or a load balancer rather than the service.
+    pub fn raw_content_type(&self) -> Option<&str> {
+        content_type_snippet(&self.raw)
+    }
+
+    /// Returns metadata about the connection the response was received on, if the HTTP client
+    /// captured it.
+    pub fn connection_metadata(&self) -> Option<&ConnectionMetadata> {
+        self.raw.extension::<ConnectionMetadata>()
+    }
+}
+
 /// Error context for [`SdkError::ServiceError`]
 #[derive(Debug)]
 pub struct ServiceError<E, R> {
@@ -269,6 +353,8 @@ pub struct ServiceError<E, R> {
     source: E,
     /// Raw response from the service
     raw: R,
+    /// Status line and content type of the raw response, if it is an HTTP response
+    summary: Option<ResponseSummary>,
 }
 
 impl<E, R> ServiceError<E, R> {
@@ -298,6 +384,70 @@ impl<E, R> ServiceError<E, R> {
     }
 }
 
+impl<E> ServiceError<E, HttpResponse> {
+    /// Returns the HTTP status code of the response
+    pub fn http_status(&self) -> StatusCode {
+        self.raw.status()
+    }
+
+    /// Returns the HTTP version of the response
+    pub fn http_version(&self) -> Version {
+        self.raw.version()
+    }
+
+    /// Returns the start of the `Content-Type` of the response, if it has one
+    ///
+    /// An unexpected content type, such as `text/html`, usually means that the response came from
+    /// a proxy or a load balancer rather than the service.
+    pub fn raw_content_type(&self) -> Option<&str> {
+        content_type_snippet(&self.raw)
+    }
+
+    /// Returns metadata about the connection the response was received on, if the HTTP client
+    /// captured it.
+    pub fn connection_metadata(&self) -> Option<&ConnectionMetadata> {
+        self.raw.extension::<ConnectionMetadata>()
+    }
+}
+
+/// Error context for [`SdkError::PreconditionFailed`]
+#[derive(Debug)]
+pub struct PreconditionFailed<E, R> {
+    /// Service error the response was deserialized into
+    source: E,
+    /// Raw response from the service
+    raw: R,
+}
+
+impl<E, R> PreconditionFailed<E, R> {
+    /// Returns the underlying error of type `E`
+    pub fn err(&self) -> &E {
+        &self.source
+    }
+
+    /// Converts this error context into the underlying error `E`
+    pub fn into_err(self) -> E {
+        self.source
+    }
+
+    /// Returns a reference to the raw response
+    pub fn raw(&self) -> &R {
+        &self.raw
+    }
+
+    /// Converts this error context into the raw response
+    pub fn into_raw(self) -> R {
+        self.raw
+    }
+}
+
+impl<E> PreconditionFailed<E, crate::http::Response> {
+    /// Returns the current ETag of the resource, if the service returned one
+    pub fn etag(&self) -> Option<&str> {
+        self.raw.headers().get("etag")
+    }
+}
+
 /// Constructs the unhandled variant of a code generated error.
 ///
 /// This trait exists so that [`SdkError::into_service_error`] can be infallible.
@@ -334,6 +484,14 @@ pub enum SdkError<E, R> {
 
     /// An error response was received from the service
     ServiceError(ServiceError<E, R>),
+
+    /// A conditional request was not performed because its precondition failed.
+    ///
+    /// This is only returned for requests made conditional with the `if_match` or `if_none_match`
+    /// methods of `customize()`, when the service responds with a `412 Precondition Failed`, or
+    /// a `304 Not Modified` for `if_none_match`. Other requests report these responses as a
+    /// [`ServiceError`](SdkError::ServiceError).
+    PreconditionFailed(PreconditionFailed<E, R>),
 }
 
 impl<E, R> SdkError<E, R> {
@@ -361,17 +519,28 @@ impl<E, R> SdkError<E, R> {
         Self::ResponseError(ResponseError {
             source: source.into(),
             raw,
+            summary: None,
         })
     }
 
     /// Construct a `SdkError` for a service failure
     pub fn service_error(source: E, raw: R) -> Self {
-        Self::ServiceError(ServiceError { source, raw })
+        Self::ServiceError(ServiceError {
+            source,
+            raw,
+            summary: None,
+        })
+    }
+
+    /// Construct a `SdkError` for a failed precondition of a conditional request
+    pub fn precondition_failed(source: E, raw: R) -> Self {
+        Self::PreconditionFailed(PreconditionFailed { source, raw })
     }
 
     /// Returns the underlying service error `E` if there is one
     ///
-    /// If the `SdkError` is not a `ServiceError` (for example, the error is a network timeout),
+    /// If the `SdkError` is not a `ServiceError` or a `PreconditionFailed` (for example, the error is a
+    /// network timeout),
     /// then it will be converted into an unhandled variant of `E`. This makes it easy to match
     /// on the service's error response while simultaneously bubbling up transient failures.
     /// For example, handling the `NoSuchKey` error for S3's `GetObject` operation may look as
@@ -408,6 +577,7 @@ impl<E, R> SdkError<E, R> {
     {
         match self {
             Self::ServiceError(context) => context.source,
+            Self::PreconditionFailed(context) => context.source,
             _ => E::create_unhandled_error(self.into(), None),
         }
     }
@@ -438,6 +608,7 @@ impl<E, R> SdkError<E, R> {
     pub fn as_service_error(&self) -> Option<&E> {
         match self {
             Self::ServiceError(err) => Some(&err.source),
+            Self::PreconditionFailed(err) => Some(&err.source),
             _ => None,
         }
     }
@@ -455,6 +626,7 @@ impl<E, R> SdkError<E, R> {
             SdkError::ResponseError(context) => Ok(context.source),
             SdkError::DispatchFailure(context) => Ok(context.source.into()),
             SdkError::ServiceError(context) => Ok(context.source.into()),
+            SdkError::PreconditionFailed(context) => Ok(context.source.into()),
         }
     }
 
@@ -463,16 +635,18 @@ impl<E, R> SdkError<E, R> {
         match self {
             SdkError::ServiceError(inner) => Some(inner.raw()),
             SdkError::ResponseError(inner) => Some(inner.raw()),
+            SdkError::PreconditionFailed(inner) => Some(inner.raw()),
             _ => None,
         }
     }
 
-    /// Maps the service error type in `SdkError::ServiceError`
+    /// Maps the service error type in `SdkError::ServiceError` and `SdkError::PreconditionFailed`
     pub fn map_service_error<E2>(self, map: impl FnOnce(E) -> E2) -> SdkError<E2, R> {
         match self {
             SdkError::ServiceError(context) => SdkError::<E2, R>::ServiceError(ServiceError {
                 source: map(context.source),
                 raw: context.raw,
+                summary: context.summary,
             }),
             SdkError::ConstructionFailure(context) => {
                 SdkError::<E2, R>::ConstructionFailure(context)
@@ -480,8 +654,45 @@ impl<E, R> SdkError<E, R> {
             SdkError::DispatchFailure(context) => SdkError::<E2, R>::DispatchFailure(context),
             SdkError::ResponseError(context) => SdkError::<E2, R>::ResponseError(context),
             SdkError::TimeoutError(context) => SdkError::<E2, R>::TimeoutError(context),
+            SdkError::PreconditionFailed(context) => {
+                SdkError::<E2, R>::PreconditionFailed(PreconditionFailed {
+                    source: map(context.source),
+                    raw: context.raw,
+                })
+            }
+        }
+    }
+}
+
+impl<E> SdkError<E, HttpResponse> {
+    /// Returns metadata about the connection the request was sent on, such as the address of the
+    /// remote host, if the HTTP client captured it.
+    ///
+    /// HTTP clients that support this capture it when a connection is established,
+    /// so it's unavailable for requests that were never dispatched.
+    pub fn connection_metadata(&self) -> Option<&ConnectionMetadata> {
+        match self {
+            SdkError::DispatchFailure(context) => context.connection_metadata(),
+            SdkError::ResponseError(context) => context.connection_metadata(),
+            SdkError::ServiceError(context) => context.connection_metadata(),
+            SdkError::PreconditionFailed(context) => context.raw.extension::<ConnectionMetadata>(),
+            SdkError::ConstructionFailure(_) | SdkError::TimeoutError(_) => None,
         }
     }
+
+    /// Keeps the status line and content type of the raw response to display them with the error.
+    pub(crate) fn with_response_summary(mut self) -> Self {
+        match &mut self {
+            SdkError::ResponseError(context) => {
+                context.summary = Some(ResponseSummary::new(&context.raw))
+            }
+            SdkError::ServiceError(context) => {
+                context.summary = Some(ResponseSummary::new(&context.raw))
+            }
+            _ => {}
+        }
+        self
+    }
 }
 
 impl<E, R> Display for SdkError<E, R> {
@@ -490,8 +701,15 @@ impl<E, R> Display for SdkError<E, R> {
             SdkError::ConstructionFailure(_) => write!(f, "failed to construct request"),
             SdkError::TimeoutError(_) => write!(f, "request has timed out"),
             SdkError::DispatchFailure(_) => write!(f, "dispatch failure"),
-            SdkError::ResponseError(_) => write!(f, "response error"),
-            SdkError::ServiceError(_) => write!(f, "service error"),
+            SdkError::ResponseError(context) => match &context.summary {
+                Some(summary) => write!(f, "response error ({summary})"),
+                None => write!(f, "response error"),
+            },
+            SdkError::ServiceError(context) => match &context.summary {
+                Some(summary) => write!(f, "service error ({summary})"),
+                None => write!(f, "service error"),
+            },
+            SdkError::PreconditionFailed(_) => write!(f, "precondition failed"),
         }
     }
 }
@@ -508,6 +726,7 @@ where
             SdkError::ResponseError(context) => Some(context.source.as_ref()),
             SdkError::DispatchFailure(context) => Some(&context.source),
             SdkError::ServiceError(context) => Some(&context.source),
+            SdkError::PreconditionFailed(context) => Some(&context.source),
         }
     }
 }
@@ -529,6 +748,7 @@ where
             SdkError::DispatchFailure(_) => &EMPTY_ERROR_METADATA,
             SdkError::ResponseError(_) => &EMPTY_ERROR_METADATA,
             SdkError::ServiceError(err) => err.source.meta(),
+            SdkError::PreconditionFailed(err) => err.source.meta(),
         }
     }
 }
@@ -9,12 +9,14 @@
 //! used to limit the rate that requests are sent.
 
 pub mod classifiers;
+pub mod gate;
 
 use crate::box_error::BoxError;
 use crate::client::interceptors::context::InterceptorContext;
 use crate::client::runtime_components::sealed::ValidateConfig;
 use crate::client::runtime_components::RuntimeComponents;
-use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use aws_smithy_types::body::ReplaySource;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreAppend, StoreReplace};
 use std::fmt;
 use std::sync::Arc;
 use std::time::Duration;
@@ -156,6 +158,51 @@ impl Storable for RequestAttempts {
     type Storer = StoreReplace<Self>;
 }
 
+/// How the request body was replayed for a retry attempt.
+///
+/// The orchestrator appends one `ReplayInfo` to the `ConfigBag` for each retry attempt, so the
+/// history of an operation can be read with `cfg.load::<ReplayInfo>()`, most recent attempt first.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayInfo {
+    attempt: u32,
+    source: Option<ReplaySource>,
+    bytes_replayed: Option<u64>,
+}
+
+impl ReplayInfo {
+    /// Creates a new [`ReplayInfo`].
+    ///
+    /// A `source` of `None` means that the body couldn't be replayed, and that the attempt was skipped.
+    pub fn new(attempt: u32, source: Option<ReplaySource>, bytes_replayed: Option<u64>) -> Self {
+        Self {
+            attempt,
+            source,
+            bytes_replayed,
+        }
+    }
+
+    /// Returns the attempt number the body was replayed for.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Returns how the body was replayed, or `None` if it couldn't be replayed and the attempt
+    /// was skipped.
+    pub fn source(&self) -> Option<ReplaySource> {
+        self.source
+    }
+
+    /// Returns the number of bytes replayed, if the length of the body is known.
+    pub fn bytes_replayed(&self) -> Option<u64> {
+        self.bytes_replayed
+    }
+}
+
+impl Storable for ReplayInfo {
+    type Storer = StoreAppend<Self>;
+}
+
 #[cfg(feature = "test-util")]
 mod test_util {
     use super::ErrorKind;
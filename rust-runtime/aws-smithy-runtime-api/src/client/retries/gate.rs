@@ -0,0 +1,106 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Retry gates let application state veto or delay request attempts.
+//!
+//! A [`RetryGate`] is stored in the config bag as a [`SharedRetryGate`], and is consulted by the
+//! retry strategy before the initial attempt and before every retry. Its answer is combined with
+//! the retry strategy's own decision, and the most restrictive answer wins: a gate can prevent or
+//! delay an attempt, but it can't cause an attempt that the retry strategy wouldn't have made.
+
+use crate::client::retries::classifiers::{RetryAction, RetryReason};
+use crate::impl_shared_conversions;
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A retry gate's answer to whether an attempt may be made.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Permit {
+    /// The attempt may be made.
+    Allow,
+    /// The attempt must not be made, for the given reason.
+    Deny(Cow<'static, str>),
+    /// The attempt may be made, but no sooner than after the given delay.
+    DelayBy(Duration),
+}
+
+impl_shared_conversions!(convert SharedRetryGate from RetryGate using SharedRetryGate::new);
+
+/// Vetoes or delays request attempts based on application state, e.g. a circuit breaker.
+pub trait RetryGate: Send + Sync + fmt::Debug {
+    /// Decides whether `attempt` may be made.
+    ///
+    /// Attempts are numbered from 1, which is the initial attempt. `prior_error` is the reason the
+    /// previous attempt is being retried, and is `None` for the initial attempt.
+    fn permit_attempt(&self, attempt: u32, prior_error: Option<&RetryReason>) -> Permit;
+
+    /// Records how the result of an attempt was classified.
+    ///
+    /// This is called once per attempt, whether or not it is retried. The default implementation
+    /// does nothing.
+    fn record_attempt(&self, result: &RetryAction) {
+        let _result = result;
+    }
+}
+
+/// A shared retry gate.
+#[derive(Clone, Debug)]
+pub struct SharedRetryGate(Arc<dyn RetryGate>);
+
+impl SharedRetryGate {
+    /// Creates a new [`SharedRetryGate`] from a retry gate.
+    pub fn new(retry_gate: impl RetryGate + 'static) -> Self {
+        Self(Arc::new(retry_gate))
+    }
+}
+
+impl RetryGate for SharedRetryGate {
+    fn permit_attempt(&self, attempt: u32, prior_error: Option<&RetryReason>) -> Permit {
+        self.0.permit_attempt(attempt, prior_error)
+    }
+
+    fn record_attempt(&self, result: &RetryAction) {
+        self.0.record_attempt(result)
+    }
+}
+
+impl Storable for SharedRetryGate {
+    type Storer = StoreReplace<Self>;
+}
+
+/// The initial attempt of a request was denied by a [`RetryGate`].
+#[derive(Debug)]
+pub struct RetryGateDenied {
+    reason: Cow<'static, str>,
+}
+
+impl RetryGateDenied {
+    /// Creates a new `RetryGateDenied` error with the reason the gate gave.
+    pub fn new(reason: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            reason: reason.into(),
+        }
+    }
+
+    /// Returns the reason the gate gave for denying the attempt.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for RetryGateDenied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the request was denied by the retry gate: {}",
+            self.reason
+        )
+    }
+}
+
+impl std::error::Error for RetryGateDenied {}
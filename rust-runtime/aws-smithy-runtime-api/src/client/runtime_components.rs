@@ -21,11 +21,13 @@ use crate::client::identity::{
     ResolveCachedIdentity, ResolveIdentity, SharedIdentityCache, SharedIdentityResolver,
 };
 use crate::client::interceptors::{Intercept, SharedInterceptor};
+use crate::client::random::{RandomSource, SharedRandomSource};
 use crate::client::retries::classifiers::{ClassifyRetry, SharedRetryClassifier};
 use crate::client::retries::{RetryStrategy, SharedRetryStrategy};
 use crate::impl_shared_conversions;
 use crate::shared::IntoShared;
 use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+use aws_smithy_async::rt::spawn::SdkRuntime;
 use aws_smithy_async::time::{SharedTimeSource, TimeSource};
 use aws_smithy_types::config_bag::ConfigBag;
 use std::collections::HashMap;
@@ -376,6 +378,10 @@ declare_runtime_components! {
 
         sleep_impl: Option<SharedAsyncSleep>,
 
+        random_source: Option<SharedRandomSource>,
+
+        sdk_runtime: Option<SdkRuntime>,
+
         config_validators: Vec<SharedConfigValidator>,
     }
 }
@@ -453,6 +459,16 @@ impl RuntimeComponents {
         self.time_source.as_ref().map(|s| s.value.clone())
     }
 
+    /// Returns the random source.
+    pub fn random_source(&self) -> Option<SharedRandomSource> {
+        self.random_source.as_ref().map(|s| s.value.clone())
+    }
+
+    /// Returns the runtime that background tasks are spawned on.
+    pub fn sdk_runtime(&self) -> Option<SdkRuntime> {
+        self.sdk_runtime.as_ref().map(|s| s.value.clone())
+    }
+
     /// Returns the config validators.
     pub fn config_validators(&self) -> impl Iterator<Item = SharedConfigValidator> + '_ {
         self.config_validators.iter().map(|s| s.value.clone())
@@ -521,6 +537,8 @@ impl RuntimeComponentsBuilder {
             retry_strategy: Some(rc.retry_strategy),
             time_source: rc.time_source,
             sleep_impl: rc.sleep_impl,
+            random_source: rc.random_source,
+            sdk_runtime: rc.sdk_runtime,
             config_validators: rc.config_validators,
         }
     }
@@ -826,6 +844,49 @@ impl RuntimeComponentsBuilder {
         self
     }
 
+    /// Returns the random source.
+    pub fn random_source(&self) -> Option<SharedRandomSource> {
+        self.random_source.as_ref().map(|s| s.value.clone())
+    }
+
+    /// Sets the random source.
+    pub fn set_random_source(&mut self, random_source: Option<SharedRandomSource>) -> &mut Self {
+        self.random_source = self.tracked(random_source);
+        self
+    }
+
+    /// Sets the random source.
+    pub fn with_random_source(
+        mut self,
+        random_source: Option<impl RandomSource + 'static>,
+    ) -> Self {
+        self.set_random_source(random_source.map(IntoShared::into_shared));
+        self
+    }
+
+    /// Returns the runtime that background tasks are spawned on.
+    pub fn sdk_runtime(&self) -> Option<SdkRuntime> {
+        self.sdk_runtime.as_ref().map(|s| s.value.clone())
+    }
+
+    /// Sets the runtime that background tasks are spawned on.
+    ///
+    /// Components that do work in the background, such as refreshing identities before they expire,
+    /// only do so when a runtime is set. Otherwise, they do that work lazily.
+    pub fn set_sdk_runtime(&mut self, sdk_runtime: Option<SdkRuntime>) -> &mut Self {
+        self.sdk_runtime = self.tracked(sdk_runtime);
+        self
+    }
+
+    /// Sets the runtime that background tasks are spawned on.
+    ///
+    /// Components that do work in the background, such as refreshing identities before they expire,
+    /// only do so when a runtime is set. Otherwise, they do that work lazily.
+    pub fn with_sdk_runtime(mut self, sdk_runtime: Option<SdkRuntime>) -> Self {
+        self.set_sdk_runtime(sdk_runtime);
+        self
+    }
+
     /// Returns the config validators.
     pub fn config_validators(&self) -> impl Iterator<Item = SharedConfigValidator> + '_ {
         self.config_validators.iter().map(|s| s.value.clone())
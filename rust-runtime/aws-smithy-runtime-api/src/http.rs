@@ -14,4 +14,4 @@ mod response;
 pub use error::HttpError;
 pub use headers::{HeaderValue, Headers, HeadersIter};
 pub use request::{Request, RequestParts};
-pub use response::{Response, StatusCode};
+pub use response::{Response, StatusCode, Version};
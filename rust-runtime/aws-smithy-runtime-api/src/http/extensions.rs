@@ -21,6 +21,13 @@ impl Extensions {
         self.extensions_1x.insert(extension.clone());
         self.extensions_02x.insert(extension);
     }
+
+    /// Returns the extension of type `T`, if there is one
+    pub(crate) fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.extensions_02x
+            .get::<T>()
+            .or_else(|| self.extensions_1x.get::<T>())
+    }
 }
 
 impl From<http_02x::Extensions> for Extensions {
@@ -88,10 +88,92 @@ impl fmt::Display for StatusCode {
     }
 }
 
+/// HTTP version of a response
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Version {
+    /// HTTP/0.9
+    Http09,
+    /// HTTP/1.0
+    Http10,
+    /// HTTP/1.1
+    #[default]
+    Http11,
+    /// HTTP/2
+    Http2,
+    /// HTTP/3
+    Http3,
+}
+
+#[cfg(feature = "http-02x")]
+impl From<http_02x::Version> for Version {
+    fn from(value: http_02x::Version) -> Self {
+        match value {
+            http_02x::Version::HTTP_09 => Self::Http09,
+            http_02x::Version::HTTP_10 => Self::Http10,
+            http_02x::Version::HTTP_2 => Self::Http2,
+            http_02x::Version::HTTP_3 => Self::Http3,
+            _ => Self::Http11,
+        }
+    }
+}
+
+#[cfg(feature = "http-02x")]
+impl From<Version> for http_02x::Version {
+    fn from(value: Version) -> Self {
+        match value {
+            Version::Http09 => Self::HTTP_09,
+            Version::Http10 => Self::HTTP_10,
+            Version::Http11 => Self::HTTP_11,
+            Version::Http2 => Self::HTTP_2,
+            Version::Http3 => Self::HTTP_3,
+        }
+    }
+}
+
+#[cfg(feature = "http-1x")]
+impl From<http_1x::Version> for Version {
+    fn from(value: http_1x::Version) -> Self {
+        match value {
+            http_1x::Version::HTTP_09 => Self::Http09,
+            http_1x::Version::HTTP_10 => Self::Http10,
+            http_1x::Version::HTTP_2 => Self::Http2,
+            http_1x::Version::HTTP_3 => Self::Http3,
+            _ => Self::Http11,
+        }
+    }
+}
+
+#[cfg(feature = "http-1x")]
+impl From<Version> for http_1x::Version {
+    fn from(value: Version) -> Self {
+        match value {
+            Version::Http09 => Self::HTTP_09,
+            Version::Http10 => Self::HTTP_10,
+            Version::Http11 => Self::HTTP_11,
+            Version::Http2 => Self::HTTP_2,
+            Version::Http3 => Self::HTTP_3,
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Version::Http09 => "HTTP/0.9",
+            Version::Http10 => "HTTP/1.0",
+            Version::Http11 => "HTTP/1.1",
+            Version::Http2 => "HTTP/2.0",
+            Version::Http3 => "HTTP/3.0",
+        })
+    }
+}
+
 /// An HTTP Response Type
 #[derive(Debug)]
 pub struct Response<B = SdkBody> {
     status: StatusCode,
+    version: Version,
     headers: Headers,
     body: B,
     extensions: Extensions,
@@ -109,6 +191,7 @@ impl<B> Response<B> {
                 http_02x::StatusCode::from_u16(self.status.into())
                     .expect("validated upon construction"),
             )
+            .version(self.version.into())
             .body(self.body)
             .expect("known valid");
         *res.headers_mut() = self.headers.http0_headermap();
@@ -127,6 +210,7 @@ impl<B> Response<B> {
                 http_1x::StatusCode::from_u16(self.status.into())
                     .expect("validated upon construction"),
             )
+            .version(self.version.into())
             .body(self.body)
             .expect("known valid");
         *res.headers_mut() = self.headers.http1_headermap();
@@ -138,16 +222,18 @@ impl<B> Response<B> {
     pub fn map<U>(self, f: impl Fn(B) -> U) -> Response<U> {
         Response {
             status: self.status,
+            version: self.version,
             body: f(self.body),
             extensions: self.extensions,
             headers: self.headers,
         }
     }
 
-    /// Returns a response with the given status and body
+    /// Returns an HTTP/1.1 response with the given status and body
     pub fn new(status: StatusCode, body: B) -> Self {
         Self {
             status,
+            version: Version::default(),
             body,
             extensions: Default::default(),
             headers: Default::default(),
@@ -164,6 +250,16 @@ impl<B> Response<B> {
         &mut self.status
     }
 
+    /// Returns the HTTP version
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Returns a mutable reference to the HTTP version
+    pub fn version_mut(&mut self) -> &mut Version {
+        &mut self.version
+    }
+
     /// Returns a reference to the header map
     pub fn headers(&self) -> &Headers {
         &self.headers
@@ -193,6 +289,11 @@ impl<B> Response<B> {
     pub fn add_extension<T: Send + Sync + Clone + 'static>(&mut self, extension: T) {
         self.extensions.insert(extension);
     }
+
+    /// Returns the response extension of type `T`, if there is one
+    pub fn extension<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.extensions.get::<T>()
+    }
 }
 
 impl Response<SdkBody> {
@@ -211,6 +312,7 @@ impl<B> TryFrom<http_02x::Response<B>> for Response<B> {
         let headers = Headers::try_from(parts.headers)?;
         Ok(Self {
             status: StatusCode::try_from(parts.status.as_u16()).expect("validated by http 0.x"),
+            version: parts.version.into(),
             body,
             extensions: parts.extensions.into(),
             headers,
@@ -227,6 +329,7 @@ impl<B> TryFrom<http_1x::Response<B>> for Response<B> {
         let headers = Headers::try_from(parts.headers)?;
         Ok(Self {
             status: StatusCode::try_from(parts.status.as_u16()).expect("validated by http 1.x"),
+            version: parts.version.into(),
             body,
             extensions: parts.extensions.into(),
             headers,
@@ -314,6 +417,23 @@ mod test {
         check_roundtrip(response);
     }
 
+    #[test]
+    fn versions_are_converted() {
+        let response = http_1x::Response::builder()
+            .status(503)
+            .version(http_1x::Version::HTTP_2)
+            .body(SdkBody::empty())
+            .unwrap();
+        let response = Response::try_from(response).unwrap();
+        assert_eq!(Version::Http2, response.version());
+        assert_eq!("HTTP/2.0", response.version().to_string());
+        let response = response.try_into_http02x().unwrap();
+        assert_eq!(http_02x::Version::HTTP_2, response.version());
+
+        let response = Response::new(StatusCode::try_from(200).unwrap(), SdkBody::empty());
+        assert_eq!(Version::Http11, response.version());
+    }
+
     #[test]
     #[should_panic]
     fn header_panics() {
@@ -6,6 +6,9 @@
 /// Smithy auth scheme implementations.
 pub mod auth;
 
+/// Validation of a client's configuration as a whole.
+pub mod config_validation;
+
 pub mod defaults;
 
 pub mod dns;
@@ -24,6 +27,12 @@ pub mod config_override;
 /// The client orchestrator implementation
 pub mod orchestrator;
 
+/// Built-in sources of randomness.
+pub mod random;
+
+/// Client-side rate limiting of requests.
+pub mod rate_limit;
+
 /// Smithy code related to retry handling and token buckets.
 ///
 /// This code defines when and how failed requests should be retried. It also defines the behavior
@@ -47,9 +56,37 @@ pub mod interceptors;
 /// Stalled stream protection for clients
 pub mod stalled_stream_protection;
 
+/// Capturing of allow-listed response headers into outputs and errors.
+pub mod captured_headers;
+
+/// Opt-in reporting of unmodeled response fields.
+pub mod unknown_fields;
+
+/// Opt-in lenient deserialization of responses.
+pub mod field_errors;
+
+/// Break-glass overrides of the generated serializer and deserializer of an operation.
+pub mod ser_de_override;
+
+/// Conditional requests based on the ETag of a resource.
+pub mod conditional;
+
+/// ETag-based caching of responses.
+pub mod response_cache;
+
+/// Opt-in following of HTTP redirects.
+pub mod redirect;
+
+/// Concurrent ranged downloads of large objects.
+pub mod ranged_download;
+
 /// Generic Smithy SDK feature identifies.
 #[doc(hidden)]
 pub mod sdk_feature;
 
+/// Adapters from clients to `tower` services.
+#[cfg(feature = "tower")]
+pub mod service;
+
 /// Smithy support-code for code generated waiters.
 pub mod waiters;
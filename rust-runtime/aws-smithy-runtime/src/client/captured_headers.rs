@@ -0,0 +1,145 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugin;
+use aws_smithy_runtime_api::http::Headers;
+use aws_smithy_types::captured_headers::CapturedHeaders;
+use aws_smithy_types::config_bag::{FrozenLayer, Layer, Storable, StoreReplace};
+
+/// The default maximum length in bytes of a captured header value.
+pub const DEFAULT_MAX_CAPTURED_VALUE_LEN: usize = 1024;
+
+/// Response headers to capture into operation outputs and errors, regardless of model bindings.
+///
+/// The listed headers are copied from every response before it is deserialized, so they are
+/// available to interceptors (as [`CapturedHeaders`] in the config bag) even when the response
+/// fails to parse. Generated clients attach them to outputs and to the
+/// [`ErrorMetadata`](aws_smithy_types::error::ErrorMetadata) of errors. Values longer than
+/// [`max_value_len`](CaptureResponseHeaders::max_value_len) bytes are truncated.
+///
+/// ```
+/// use aws_smithy_runtime::client::captured_headers::CaptureResponseHeaders;
+///
+/// let capture = CaptureResponseHeaders::new(&["x-rate-limit-remaining", "x-cache"]).max_value_len(256);
+/// # let _ = capture;
+/// ```
+#[derive(Clone, Debug)]
+pub struct CaptureResponseHeaders {
+    names: Vec<String>,
+    max_value_len: usize,
+}
+
+impl CaptureResponseHeaders {
+    /// Captures the headers in `names`.
+    pub fn new(names: &[impl AsRef<str>]) -> Self {
+        Self {
+            names: names
+                .iter()
+                .map(|name| name.as_ref().to_ascii_lowercase())
+                .collect(),
+            max_value_len: DEFAULT_MAX_CAPTURED_VALUE_LEN,
+        }
+    }
+
+    /// Sets the maximum length in bytes of a captured value. Longer values are truncated.
+    ///
+    /// Defaults to [`DEFAULT_MAX_CAPTURED_VALUE_LEN`].
+    pub fn max_value_len(mut self, max_value_len: usize) -> Self {
+        self.max_value_len = max_value_len;
+        self
+    }
+
+    /// Copies the allow-listed headers out of `headers`.
+    pub fn capture(&self, headers: &Headers) -> CapturedHeaders {
+        let mut captured = CapturedHeaders::new();
+        for name in &self.names {
+            for value in headers.get_all(name) {
+                captured.append(name, truncate(value, self.max_value_len));
+            }
+        }
+        captured
+    }
+}
+
+impl<T: AsRef<str>> From<&[T]> for CaptureResponseHeaders {
+    fn from(names: &[T]) -> Self {
+        Self::new(names)
+    }
+}
+
+impl<T: AsRef<str>, const N: usize> From<&[T; N]> for CaptureResponseHeaders {
+    fn from(names: &[T; N]) -> Self {
+        Self::new(names)
+    }
+}
+
+impl Storable for CaptureResponseHeaders {
+    type Storer = StoreReplace<Self>;
+}
+
+impl RuntimePlugin for CaptureResponseHeaders {
+    fn config(&self) -> Option<FrozenLayer> {
+        let mut layer = Layer::new("CaptureResponseHeaders");
+        layer.store_put(self.clone());
+        Some(layer.freeze())
+    }
+}
+
+fn truncate(value: &str, max_len: usize) -> &str {
+    if value.len() <= max_len {
+        return value;
+    }
+    let mut end = max_len;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    &value[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(headers: &[(&'static str, &'static str)]) -> Headers {
+        let mut map = Headers::new();
+        for (name, value) in headers {
+            map.append(*name, *value);
+        }
+        map
+    }
+
+    #[test]
+    fn only_listed_headers_are_captured() {
+        let capture = CaptureResponseHeaders::from(&["X-Rate-Limit", "x-region", "x-missing"]);
+        let captured = capture.capture(&headers(&[
+            ("x-rate-limit", "10"),
+            ("x-region", "us-west-2"),
+            ("x-unlisted", "secret"),
+        ]));
+        assert_eq!(Some("10"), captured.get("x-rate-limit"));
+        assert_eq!(Some("us-west-2"), captured.get("x-region"));
+        assert_eq!(None, captured.get("x-unlisted"));
+        assert_eq!(None, captured.get("x-missing"));
+    }
+
+    #[test]
+    fn multi_value_headers_are_captured_as_a_list() {
+        let capture = CaptureResponseHeaders::new(&["x-cache"]);
+        let captured = capture.capture(&headers(&[
+            ("x-cache", "miss"),
+            ("x-other", "1"),
+            ("x-cache", "hit"),
+        ]));
+        assert_eq!(&["miss", "hit"], captured.get_all("x-cache"));
+    }
+
+    #[test]
+    fn values_are_truncated() {
+        let capture = CaptureResponseHeaders::new(&["x-long"]).max_value_len(4);
+        let captured = capture.capture(&headers(&[("x-long", "abcdefgh"), ("x-long", "ab")]));
+        assert_eq!(&["abcd", "ab"], captured.get_all("x-long"));
+        assert_eq!("a", truncate("aé", 2));
+    }
+}
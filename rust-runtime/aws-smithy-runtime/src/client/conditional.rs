@@ -0,0 +1,142 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeTransmitInterceptorContextMut, FinalizerInterceptorContextMut,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::ConfigBag;
+
+const IF_MATCH: &str = "if-match";
+const IF_NONE_MATCH: &str = "if-none-match";
+
+const PRECONDITION_FAILED: u16 = 412;
+const NOT_MODIFIED: u16 = 304;
+
+/// Interceptor that makes a request conditional on the ETag of the resource.
+///
+/// The `If-Match` or `If-None-Match` header is set before the request is signed, so it is covered
+/// by the signature. When the service responds that the precondition failed, the error is
+/// returned as [`SdkError::PreconditionFailed`](aws_smithy_runtime_api::client::result::SdkError::PreconditionFailed),
+/// which holds the current ETag of the resource when the service returns one:
+/// - a `412 Precondition Failed` response fails both `If-Match` and `If-None-Match`.
+/// - a `304 Not Modified` response only fails `If-None-Match`, since that's the response to a
+///   `GET` or `HEAD` of a resource whose ETag matches.
+///
+/// Generated clients add it with the `if_match` and `if_none_match` methods of `customize()`.
+#[derive(Clone, Debug)]
+pub struct ConditionalRequestInterceptor {
+    header: &'static str,
+    etag: String,
+}
+
+impl ConditionalRequestInterceptor {
+    /// Only performs the request if the current ETag of the resource matches `etag`.
+    ///
+    /// `etag` is sent as-is, so it must include the quotes returned by the service, or be `*` to
+    /// match any version of the resource.
+    pub fn if_match(etag: impl Into<String>) -> Self {
+        Self {
+            header: IF_MATCH,
+            etag: etag.into(),
+        }
+    }
+
+    /// Only performs the request if the current ETag of the resource doesn't match `etag`.
+    ///
+    /// `etag` is sent as-is, so it must include the quotes returned by the service, or be `*` to
+    /// only perform the request if the resource doesn't exist.
+    pub fn if_none_match(etag: impl Into<String>) -> Self {
+        Self {
+            header: IF_NONE_MATCH,
+            etag: etag.into(),
+        }
+    }
+
+    fn precondition_failed(&self, status: u16) -> bool {
+        status == PRECONDITION_FAILED || (status == NOT_MODIFIED && self.header == IF_NONE_MATCH)
+    }
+}
+
+impl Intercept for ConditionalRequestInterceptor {
+    fn name(&self) -> &'static str {
+        "ConditionalRequestInterceptor"
+    }
+
+    fn modify_before_signing(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        context
+            .request_mut()
+            .headers_mut()
+            .try_insert(self.header, self.etag.clone())?;
+        Ok(())
+    }
+
+    fn modify_before_attempt_completion(
+        &self,
+        context: &mut FinalizerInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let precondition_failed = context
+            .response()
+            .map(|response| self.precondition_failed(response.status().as_u16()))
+            .unwrap_or_default();
+        if precondition_failed {
+            let ctx = context.inner_mut();
+            if let Some(output_or_error) = ctx.take_output_or_error() {
+                ctx.set_output_or_error(
+                    output_or_error.map_err(|err| err.into_precondition_failed()),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_runtime_api::client::interceptors::context::{Error, Input, InterceptorContext};
+    use aws_smithy_runtime_api::client::orchestrator::{HttpResponse, OrchestratorError};
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::body::SdkBody;
+
+    fn fail_with(interceptor: &ConditionalRequestInterceptor, status: u16) -> bool {
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.set_response(HttpResponse::new(
+            status.try_into().unwrap(),
+            SdkBody::empty(),
+        ));
+        ctx.set_output_or_error(Err(OrchestratorError::operation(Error::doesnt_matter())));
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut cfg = ConfigBag::base();
+        interceptor
+            .modify_before_attempt_completion(&mut (&mut ctx).into(), &rc, &mut cfg)
+            .unwrap();
+        ctx.output_or_error()
+            .unwrap()
+            .expect_err("still an error")
+            .is_precondition_failed()
+    }
+
+    #[test]
+    fn precondition_failed_responses_are_classified() {
+        let if_match = ConditionalRequestInterceptor::if_match("\"abc\"");
+        let if_none_match = ConditionalRequestInterceptor::if_none_match("\"abc\"");
+        assert!(fail_with(&if_match, 412));
+        assert!(fail_with(&if_none_match, 412));
+        assert!(fail_with(&if_none_match, 304));
+        assert!(!fail_with(&if_match, 304));
+        assert!(!fail_with(&if_match, 400));
+        assert!(!fail_with(&if_none_match, 404));
+    }
+}
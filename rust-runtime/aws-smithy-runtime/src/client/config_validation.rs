@@ -0,0 +1,321 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::client::config_validation::{
+    ConfigIssue, ConfigIssueCode, ConfigValidationReport,
+};
+use aws_smithy_runtime_api::client::identity::ResolveCachedIdentity;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugins;
+use aws_smithy_runtime_api::client::stalled_stream_protection::StalledStreamProtectionConfig;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use aws_smithy_types::retry::RetryConfig;
+use aws_smithy_types::timeout::TimeoutConfig;
+use std::error::Error;
+
+/// Validates the configuration that a client's runtime plugins produce, adding every problem
+/// found to `report`.
+///
+/// Unlike [`RuntimeComponentsBuilder::validate_base_client_config`], this doesn't stop at the
+/// first problem. Config validators that aren't known to this function are still run, but only
+/// when no other fatal issue was found, since their errors usually restate one of those issues.
+///
+/// This is intended to be called by generated clients once, when a client is created, and when
+/// their config's validation report is requested. A client stores the result in its runtime
+/// plugins as a [`ClientConfigValidation`].
+pub fn validate_client_config(plugins: &RuntimePlugins, report: &mut ConfigValidationReport) {
+    let mut cfg = ConfigBag::base();
+    match plugins.apply_client_configuration(&mut cfg) {
+        Ok(components) => validate_components(&components, &cfg, report),
+        Err(err) => {
+            report.push(invalid_configuration(&*err));
+        }
+    }
+}
+
+/// The result of validating a client's config when the client was created.
+///
+/// Every invocation of an operation without a config override fails with the report if it has
+/// fatal issues, without the config being validated again.
+#[derive(Clone, Debug)]
+pub struct ClientConfigValidation {
+    report: ConfigValidationReport,
+}
+
+impl ClientConfigValidation {
+    /// Records the report of a client's config validation. Warnings are logged right away.
+    pub fn new(report: ConfigValidationReport) -> Self {
+        if !report.has_fatal_issues() {
+            log_warnings(&report);
+        }
+        Self { report }
+    }
+
+    /// Returns the report if any of its issues is fatal.
+    pub(crate) fn check(&self) -> Result<(), ConfigValidationReport> {
+        if self.report.has_fatal_issues() {
+            return Err(self.report.clone());
+        }
+        Ok(())
+    }
+}
+
+impl Storable for ClientConfigValidation {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Marks an invocation whose config is overridden for the operation.
+///
+/// The client's config was validated when the client was created, but an override can introduce
+/// problems or fix them, so the orchestrator validates the merged config of these invocations.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct ValidateOperationConfig;
+
+impl ValidateOperationConfig {
+    /// Creates a new `ValidateOperationConfig` marker.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Storable for ValidateOperationConfig {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Validates the merged configuration that an operation with a config override is about to be
+/// invoked with.
+///
+/// Warnings are only logged, and the full report is returned as the error if any issue is fatal.
+pub(crate) fn validate_operation_config(
+    components: &RuntimeComponentsBuilder,
+    cfg: &ConfigBag,
+) -> Result<(), ConfigValidationReport> {
+    let mut report = ConfigValidationReport::new();
+    validate_components(components, cfg, &mut report);
+    if report.has_fatal_issues() {
+        return Err(report);
+    }
+    log_warnings(&report);
+    Ok(())
+}
+
+fn log_warnings(report: &ConfigValidationReport) {
+    for issue in report.issues() {
+        tracing::debug!("{issue}");
+    }
+}
+
+fn invalid_configuration(err: &(dyn Error + 'static)) -> ConfigIssue {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        message.push_str(": ");
+        message.push_str(&err.to_string());
+        source = err.source();
+    }
+    ConfigIssue::fatal(ConfigIssueCode::InvalidConfiguration, message)
+}
+
+fn validate_components(
+    components: &RuntimeComponentsBuilder,
+    cfg: &ConfigBag,
+    report: &mut ConfigValidationReport,
+) {
+    let issues_before = report.issues().len();
+
+    if components.http_client().is_none() {
+        report.push(ConfigIssue::warning(
+            ConfigIssueCode::MissingHttpClient,
+            "No HTTP client is configured, so requests can be presigned but not sent. \
+             Call `http_client` on the config builder, or enable the `rustls` crate feature.",
+        ));
+    }
+
+    let retries_enabled = cfg
+        .load::<RetryConfig>()
+        .is_some_and(RetryConfig::has_retry);
+    let identity_cache_invalid = components
+        .identity_cache()
+        .is_some_and(|cache| cache.validate_base_client_config(components, cfg).is_err());
+    let stalled_stream_protection_enabled = cfg
+        .load::<StalledStreamProtectionConfig>()
+        .is_some_and(StalledStreamProtectionConfig::is_enabled);
+
+    if components.sleep_impl().is_none() {
+        if retries_enabled {
+            report.push(ConfigIssue::fatal(
+                ConfigIssueCode::RetriesEnabledWithoutSleep,
+                "Retries are enabled, but no async sleep implementation is configured. Call \
+                 `sleep_impl` on the config builder, enable the `rt-tokio` crate feature, or \
+                 disable retries by calling `retry_config` with `RetryConfig::disabled()`.",
+            ));
+        }
+        let mut needs_sleep = Vec::new();
+        if cfg
+            .load::<TimeoutConfig>()
+            .is_some_and(TimeoutConfig::has_timeouts)
+        {
+            needs_sleep.push("timeouts (disable them with `timeout_config`)");
+        }
+        if stalled_stream_protection_enabled {
+            needs_sleep
+                .push("stalled stream protection (disable it with `stalled_stream_protection`)");
+        }
+        if identity_cache_invalid {
+            needs_sleep.push(
+                "identity caching (disable it by calling `identity_cache` with `IdentityCache::no_cache()`)",
+            );
+        }
+        if !needs_sleep.is_empty() {
+            report.push(ConfigIssue::fatal(
+                ConfigIssueCode::MissingSleepImpl,
+                format!(
+                    "An async sleep implementation is required for {}. Call `sleep_impl` on the \
+                     config builder, or enable the `rt-tokio` crate feature.",
+                    needs_sleep.join(", ")
+                ),
+            ));
+        }
+    }
+
+    if components.time_source().is_none() {
+        let mut needs_time = Vec::new();
+        if stalled_stream_protection_enabled {
+            needs_time.push("stalled stream protection");
+        }
+        if identity_cache_invalid {
+            needs_time.push("identity caching");
+        }
+        if !needs_time.is_empty() {
+            report.push(ConfigIssue::fatal(
+                ConfigIssueCode::MissingTimeSource,
+                format!(
+                    "A time source is required for {}. Call `time_source` on the config builder.",
+                    needs_time.join(", ")
+                ),
+            ));
+        }
+    }
+
+    let found_fatal = report.issues()[issues_before..]
+        .iter()
+        .any(ConfigIssue::is_fatal);
+    if !found_fatal {
+        if let Err(err) = components.validate_base_client_config(cfg) {
+            report.push(invalid_configuration(&*err));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::identity::IdentityCache;
+    use crate::client::retries::strategy::NeverRetryStrategy;
+    use aws_smithy_async::rt::sleep::TokioSleep;
+    use aws_smithy_async::time::SystemTimeSource;
+    use aws_smithy_runtime_api::client::http::{http_client_fn, SharedHttpClient};
+    use aws_smithy_runtime_api::client::runtime_components::SharedConfigValidator;
+    use aws_smithy_types::config_bag::Layer;
+    use std::time::Duration;
+
+    fn http_client() -> SharedHttpClient {
+        http_client_fn(|_, _| unreachable!("requests aren't sent"))
+    }
+
+    fn validate(components: RuntimeComponentsBuilder, layer: Layer) -> ConfigValidationReport {
+        let mut cfg = ConfigBag::base();
+        cfg.push_shared_layer(layer.freeze());
+        let mut report = ConfigValidationReport::new();
+        validate_components(&components, &cfg, &mut report);
+        report
+    }
+
+    #[test]
+    fn clean_config_has_an_empty_report() {
+        let components = RuntimeComponentsBuilder::new("test")
+            .with_http_client(Some(http_client()))
+            .with_retry_strategy(Some(NeverRetryStrategy::new()))
+            .with_identity_cache(Some(IdentityCache::lazy().build()))
+            .with_sleep_impl(Some(TokioSleep::new()))
+            .with_time_source(Some(SystemTimeSource::new()));
+        let mut layer = Layer::new("test");
+        layer.store_put(RetryConfig::standard());
+        layer.store_put(
+            TimeoutConfig::builder()
+                .operation_timeout(Duration::from_secs(5))
+                .build(),
+        );
+        layer.store_put(StalledStreamProtectionConfig::enabled().build());
+
+        let report = validate(components, layer);
+        assert!(report.is_empty(), "{report}");
+        assert!(!report.has_fatal_issues());
+    }
+
+    #[test]
+    fn simultaneous_issues_are_reported_together() {
+        let components = RuntimeComponentsBuilder::new("test")
+            .with_retry_strategy(Some(NeverRetryStrategy::new()))
+            .with_identity_cache(Some(IdentityCache::lazy().build()));
+        let mut layer = Layer::new("test");
+        layer.store_put(RetryConfig::standard());
+        layer.store_put(
+            TimeoutConfig::builder()
+                .operation_timeout(Duration::from_secs(5))
+                .build(),
+        );
+        layer.store_put(StalledStreamProtectionConfig::disabled());
+
+        let report = validate(components, layer);
+        assert_eq!(
+            vec![
+                ConfigIssueCode::MissingHttpClient,
+                ConfigIssueCode::RetriesEnabledWithoutSleep,
+                ConfigIssueCode::MissingSleepImpl,
+                ConfigIssueCode::MissingTimeSource,
+            ],
+            report.codes().collect::<Vec<_>>()
+        );
+        assert!(!report.issues()[0].is_fatal());
+        assert!(report.has_fatal_issues());
+        let sleep_issue = report.issues()[2].message();
+        assert!(sleep_issue.contains("timeouts"), "{sleep_issue}");
+        assert!(sleep_issue.contains("identity caching"), "{sleep_issue}");
+        assert!(!sleep_issue.contains("stalled stream"), "{sleep_issue}");
+        assert!(sleep_issue.contains("`sleep_impl`"), "{sleep_issue}");
+
+        let rendered = report.to_string();
+        assert!(
+            rendered.contains("[RetriesEnabledWithoutSleep] Retries are enabled"),
+            "{rendered}"
+        );
+    }
+
+    #[test]
+    fn other_validators_run_when_nothing_else_is_fatal() {
+        let components = RuntimeComponentsBuilder::new("test")
+            .with_http_client(Some(http_client()))
+            .with_config_validator(SharedConfigValidator::base_client_config_fn(|_, _| {
+                Err("the widget is misconfigured".into())
+            }));
+
+        let report = validate(components.clone(), Layer::new("test"));
+        assert_eq!(
+            vec![ConfigIssueCode::InvalidConfiguration],
+            report.codes().collect::<Vec<_>>()
+        );
+        assert_eq!("the widget is misconfigured", report.issues()[0].message());
+
+        let mut layer = Layer::new("test");
+        layer.store_put(RetryConfig::standard());
+        let report = validate(components, layer);
+        assert_eq!(
+            vec![ConfigIssueCode::RetriesEnabledWithoutSleep],
+            report.codes().collect::<Vec<_>>()
+        );
+    }
+}
@@ -11,6 +11,8 @@
 
 use crate::client::http::body::content_length_enforcement::EnforceContentLengthRuntimePlugin;
 use crate::client::identity::IdentityCache;
+use crate::client::random::DefaultRandomSource;
+use crate::client::retries::partition::RetryPartitionInterceptor;
 use crate::client::retries::strategy::StandardRetryStrategy;
 use crate::client::retries::RetryPartition;
 use aws_smithy_async::rt::sleep::default_async_sleep;
@@ -75,7 +77,13 @@ pub fn default_sleep_impl_plugin() -> Option<SharedRuntimePlugin> {
 }
 
 /// Runtime plugin that provides a default time source.
+///
+/// There is no default time source on `wasm32-unknown-unknown`, where
+/// [`SystemTime::now`](std::time::SystemTime::now) panics: one must be set on the client config.
 pub fn default_time_source_plugin() -> Option<SharedRuntimePlugin> {
+    if cfg!(all(target_family = "wasm", target_os = "unknown")) {
+        return None;
+    }
     Some(
         default_plugin("default_time_source_plugin", |components| {
             components.with_time_source(Some(SystemTimeSource::new()))
@@ -84,7 +92,20 @@ pub fn default_time_source_plugin() -> Option<SharedRuntimePlugin> {
     )
 }
 
+/// Runtime plugin that provides a default random source.
+pub fn default_random_source_plugin() -> Option<SharedRuntimePlugin> {
+    Some(
+        default_plugin("default_random_source_plugin", |components| {
+            components.with_random_source(Some(DefaultRandomSource::new()))
+        })
+        .into_shared(),
+    )
+}
+
 /// Runtime plugin that sets the default retry strategy, config (disabled), and partition.
+///
+/// The default partition is named `default_partition_name`, and is shared by every client using a
+/// partition with that name.
 pub fn default_retry_config_plugin(
     default_partition_name: impl Into<Cow<'static, str>>,
 ) -> Option<SharedRuntimePlugin> {
@@ -92,6 +113,7 @@ pub fn default_retry_config_plugin(
         default_plugin("default_retry_config_plugin", |components| {
             components
                 .with_retry_strategy(Some(StandardRetryStrategy::new()))
+                .with_interceptor(RetryPartitionInterceptor)
                 .with_config_validator(SharedConfigValidator::base_client_config_fn(
                     validate_retry_config,
                 ))
@@ -109,7 +131,18 @@ fn validate_retry_config(
     cfg: &ConfigBag,
 ) -> Result<(), BoxError> {
     if let Some(retry_config) = cfg.load::<RetryConfig>() {
-        if retry_config.has_retry() && components.sleep_impl().is_none() {
+        let unsupported_partition = retry_config.partition().filter(|partition| {
+            partition
+                .as_any()
+                .downcast_ref::<RetryPartition>()
+                .is_none()
+        });
+        if let Some(partition) = unsupported_partition {
+            Err(format!(
+                "The retry partition of the retry config, {partition:?}, isn't a `RetryPartition`."
+            )
+            .into())
+        } else if retry_config.has_retry() && components.sleep_impl().is_none() {
             Err("An async sleep implementation is required for retry to work. Please provide a `sleep_impl` on \
                  the config, or disable timeouts.".into())
         } else {
@@ -283,6 +316,7 @@ pub fn default_plugins(
         ),
         default_sleep_impl_plugin(),
         default_time_source_plugin(),
+        default_random_source_plugin(),
         default_timeout_config_plugin(),
         enforce_content_length_runtime_plugin(),
         default_stalled_stream_protection_config_plugin_v2(behavior_version),
@@ -296,6 +330,7 @@ pub fn default_plugins(
 mod tests {
     use super::*;
     use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugins;
+    use aws_smithy_types::retry::RetryConfigPartition;
 
     fn test_plugin_params(version: BehaviorVersion) -> DefaultPluginParams {
         DefaultPluginParams::new()
@@ -334,4 +369,27 @@ mod tests {
             "stalled stream protection on uploads MUST NOT be enabled before v2024_03_28"
         );
     }
+
+    #[test]
+    fn retry_config_partitions_must_be_retry_partitions() {
+        #[derive(Debug)]
+        struct NotARetryPartition;
+        impl RetryConfigPartition for NotARetryPartition {
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+        }
+
+        let components = RuntimeComponentsBuilder::new("test");
+        let validate = |retry_config: RetryConfig| {
+            let mut layer = Layer::new("test");
+            layer.store_put(retry_config);
+            validate_retry_config(&components, &ConfigBag::of_layers(vec![layer]))
+        };
+        validate(RetryConfig::disabled().with_partition(RetryPartition::by_endpoint()))
+            .expect("a `RetryPartition` is supported");
+        let err = validate(RetryConfig::disabled().with_partition(NotARetryPartition))
+            .expect_err("other partitions are rejected");
+        assert!(err.to_string().contains("NotARetryPartition"), "{err}");
+    }
 }
@@ -5,6 +5,9 @@
 
 //! Code for applying endpoints to a request.
 
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
 use aws_smithy_runtime_api::client::endpoint::{error::InvalidEndpointError, EndpointPrefix};
 use std::borrow::Cow;
 use std::result::Result as StdResult;
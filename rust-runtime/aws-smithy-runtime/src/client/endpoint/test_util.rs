@@ -0,0 +1,216 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Utilities for testing endpoint resolvers.
+
+use aws_smithy_runtime_api::client::endpoint::{EndpointResolverParams, ResolveEndpoint};
+use std::fmt::{self, Write};
+
+/// Checks that `resolver` resolves each set of endpoint parameters to the URL expected for it.
+///
+/// Every case is resolved, and all the cases that resolve to another URL, or fail to resolve, are
+/// reported together, with their parameters and where the resolved URL starts to differ.
+///
+/// A service-specific resolver, like the `DefaultResolver` of a generated client, can be passed
+/// with `into_shared_resolver()`, and its `Params` built with `Params::builder()`.
+///
+/// # Panics
+///
+/// Panics if any case doesn't resolve to the expected URL.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example(resolver: aws_smithy_runtime_api::client::endpoint::SharedEndpointResolver) {
+/// use aws_smithy_runtime::client::endpoint::test_util::assert_endpoints;
+/// # #[derive(Debug)]
+/// # struct Params { region: &'static str }
+///
+/// assert_endpoints(
+///     &resolver,
+///     [
+///         (Params { region: "us-east-1" }, "https://service.us-east-1.amazonaws.com"),
+///         (Params { region: "cn-north-1" }, "https://service.cn-north-1.amazonaws.com.cn"),
+///     ],
+/// )
+/// .await;
+/// # }
+/// ```
+pub async fn assert_endpoints<P, U>(
+    resolver: &dyn ResolveEndpoint,
+    cases: impl IntoIterator<Item = (P, U)>,
+) where
+    P: fmt::Debug + Send + Sync + 'static,
+    U: AsRef<str>,
+{
+    if let Some(report) = failures(resolver, cases).await {
+        panic!("{report}");
+    }
+}
+
+/// Resolves every case, and returns a report of those that failed, if any.
+async fn failures<P, U>(
+    resolver: &dyn ResolveEndpoint,
+    cases: impl IntoIterator<Item = (P, U)>,
+) -> Option<String>
+where
+    P: fmt::Debug + Send + Sync + 'static,
+    U: AsRef<str>,
+{
+    let mut total = 0;
+    let mut failed = Vec::new();
+    for (index, (params, expected)) in cases.into_iter().enumerate() {
+        total += 1;
+        let params = EndpointResolverParams::new(params);
+        let expected = expected.as_ref();
+        let mut report = String::new();
+        match resolver.resolve_endpoint(&params).await {
+            Ok(endpoint) if endpoint.url() == expected => continue,
+            Ok(endpoint) => {
+                let resolved = endpoint.url();
+                let column = expected
+                    .chars()
+                    .zip(resolved.chars())
+                    .take_while(|(expected, resolved)| expected == resolved)
+                    .count();
+                writeln!(report, "  expected: {expected}").unwrap();
+                writeln!(report, "  resolved: {resolved}").unwrap();
+                writeln!(report, "            {}^", " ".repeat(column)).unwrap();
+            }
+            Err(error) => {
+                writeln!(report, "  expected: {expected}").unwrap();
+                writeln!(report, "  failed:   {error}").unwrap();
+            }
+        }
+        let params = params.get::<P>().expect("the params were just set");
+        failed.push(format!("case {index}: {params:#?}\n{report}"));
+    }
+    if failed.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "{} of {total} endpoint test cases failed:\n\n{}",
+            failed.len(),
+            failed.join("\n")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_runtime_api::client::endpoint::EndpointFuture;
+    use aws_smithy_types::endpoint::Endpoint;
+
+    #[derive(Debug)]
+    struct Params {
+        region: &'static str,
+    }
+
+    /// Resolves `https://service.<region>.amazonaws.com`, and fails for the `aws-global` region.
+    #[derive(Debug)]
+    struct RegionalResolver;
+
+    impl ResolveEndpoint for RegionalResolver {
+        fn resolve_endpoint<'a>(
+            &'a self,
+            params: &'a EndpointResolverParams,
+        ) -> EndpointFuture<'a> {
+            let region = params.get::<Params>().expect("params are set").region;
+            EndpointFuture::ready(if region == "aws-global" {
+                Err("unsupported region".into())
+            } else {
+                Ok(Endpoint::builder()
+                    .url(format!("https://service.{region}.amazonaws.com"))
+                    .build())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn matching_cases_pass() {
+        assert_endpoints(
+            &RegionalResolver,
+            [
+                (
+                    Params {
+                        region: "us-east-1",
+                    },
+                    "https://service.us-east-1.amazonaws.com",
+                ),
+                (
+                    Params {
+                        region: "eu-west-1",
+                    },
+                    "https://service.eu-west-1.amazonaws.com",
+                ),
+            ],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn every_failed_case_is_reported() {
+        let report = failures(
+            &RegionalResolver,
+            [
+                (
+                    Params {
+                        region: "us-east-1",
+                    },
+                    "https://service.us-east-1.amazonaws.com",
+                ),
+                (
+                    Params {
+                        region: "cn-north-1",
+                    },
+                    "https://service.cn-north-1.amazonaws.com.cn",
+                ),
+                (
+                    Params {
+                        region: "aws-global",
+                    },
+                    "https://service.amazonaws.com",
+                ),
+            ],
+        )
+        .await
+        .expect("two cases fail");
+
+        pretty_assertions::assert_eq!(
+            "2 of 3 endpoint test cases failed:
+
+case 1: Params {
+    region: \"cn-north-1\",
+}
+  expected: https://service.cn-north-1.amazonaws.com.cn
+  resolved: https://service.cn-north-1.amazonaws.com
+                                                    ^
+
+case 2: Params {
+    region: \"aws-global\",
+}
+  expected: https://service.amazonaws.com
+  failed:   unsupported region
+",
+            report
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "1 of 1 endpoint test cases failed")]
+    async fn failed_cases_panic() {
+        assert_endpoints(
+            &RegionalResolver,
+            [(
+                Params {
+                    region: "us-west-2",
+                },
+                "https://service.us-east-1.amazonaws.com",
+            )],
+        )
+        .await;
+    }
+}
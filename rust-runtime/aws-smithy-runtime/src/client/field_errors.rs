@@ -0,0 +1,44 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugin;
+use aws_smithy_types::config_bag::{FrozenLayer, Layer, Storable, StoreReplace};
+
+/// Opt-in lenient deserialization of responses.
+///
+/// By default, a single malformed value fails the deserialization of the entire response. When
+/// this is present in the config bag, an optional member that fails to parse is left unset (and a
+/// list or map element that fails to parse is skipped) instead, while errors on required members
+/// still fail the response. Only scalar values in JSON response bodies are parsed leniently.
+///
+/// The errors of the dropped values are stored in the config bag as
+/// [`FieldErrors`](aws_smithy_types::field_errors::FieldErrors) after each attempt is deserialized,
+/// where an interceptor can read them in `read_after_deserialization`. At most
+/// [`MAX_RECORDED_FIELD_ERRORS`](aws_smithy_types::field_errors::MAX_RECORDED_FIELD_ERRORS)
+/// errors are recorded per response. Streaming responses are not parsed leniently.
+///
+/// This is a runtime plugin, so it can be added to a client's config or to a single operation.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct LenientDeserialization;
+
+impl LenientDeserialization {
+    /// Creates a new `LenientDeserialization` runtime plugin.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Storable for LenientDeserialization {
+    type Storer = StoreReplace<Self>;
+}
+
+impl RuntimePlugin for LenientDeserialization {
+    fn config(&self) -> Option<FrozenLayer> {
+        let mut layer = Layer::new("LenientDeserialization");
+        layer.store_put(self.clone());
+        Some(layer.freeze())
+    }
+}
@@ -31,21 +31,34 @@ use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 use std::sync::RwLock;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::io::{AsyncRead, AsyncWrite};
 
+mod header_limits;
+pub use header_limits::{HeaderLimit, HeaderLimitExceeded, HeaderLimits, MAX_RESPONSE_HEADERS};
+
+mod service_connector;
+pub use service_connector::ServiceConnector;
+
+mod connection_timing;
+use connection_timing::{ConnectionTiming, TimedConnector};
+
 #[cfg(feature = "tls-rustls")]
 mod default_connector {
     use aws_smithy_async::rt::sleep::SharedAsyncSleep;
     use aws_smithy_runtime_api::client::http::HttpConnectorSettings;
 
+    use std::time::Duration;
+
     // Creating a `with_native_roots` HTTP client takes 300ms on OS X. Cache this so that we
     // don't need to repeatedly incur that cost.
     pub(crate) static HTTPS_NATIVE_ROOTS: once_cell::sync::Lazy<
         hyper_rustls::HttpsConnector<hyper_0_14::client::HttpConnector>,
-    > = once_cell::sync::Lazy::new(default_tls);
+    > = once_cell::sync::Lazy::new(|| default_tls(tcp(None)));
 
-    fn default_tls() -> hyper_rustls::HttpsConnector<hyper_0_14::client::HttpConnector> {
+    fn default_tls(
+        tcp: hyper_0_14::client::HttpConnector,
+    ) -> hyper_rustls::HttpsConnector<hyper_0_14::client::HttpConnector> {
         use hyper_rustls::ConfigBuilderExt;
         hyper_rustls::HttpsConnectorBuilder::new()
                .with_tls_config(
@@ -70,7 +83,17 @@ mod default_connector {
             .https_or_http()
             .enable_http1()
             .enable_http2()
-            .build()
+            .wrap_connector(tcp)
+    }
+
+    /// The TCP connector that the default HTTPS connector wraps, with TCP keepalive
+    /// enabled when `keepalive` is set.
+    pub(super) fn tcp(keepalive: Option<Duration>) -> hyper_0_14::client::HttpConnector {
+        let mut tcp = hyper_0_14::client::HttpConnector::new();
+        // The TLS connector handles `https` URLs, so the TCP connector must accept them
+        tcp.enforce_http(false);
+        tcp.set_keepalive(keepalive);
+        tcp
     }
 
     pub(super) fn base(
@@ -91,6 +114,15 @@ mod default_connector {
     pub(super) fn https() -> hyper_rustls::HttpsConnector<hyper_0_14::client::HttpConnector> {
         HTTPS_NATIVE_ROOTS.clone()
     }
+
+    /// Same as [`https`], but with TCP keepalive enabled on every connection.
+    ///
+    /// Unlike [`https`], this loads the native roots every time it is called.
+    pub(super) fn https_with_tcp_keepalive(
+        keepalive: Duration,
+    ) -> hyper_rustls::HttpsConnector<hyper_0_14::client::HttpConnector> {
+        default_tls(tcp(Some(keepalive)))
+    }
 }
 
 /// Given `HttpConnectorSettings` and an `SharedAsyncSleep`, create a `SharedHttpConnector` from defaults depending on what cargo features are activated.
@@ -156,6 +188,7 @@ pub struct HyperConnectorBuilder {
     connector_settings: Option<HttpConnectorSettings>,
     sleep_impl: Option<SharedAsyncSleep>,
     client_builder: Option<hyper_0_14::client::Builder>,
+    header_limits: Option<HeaderLimits>,
 }
 
 impl HyperConnectorBuilder {
@@ -168,13 +201,18 @@ impl HyperConnectorBuilder {
         C::Future: Unpin + Send + 'static,
         C::Error: Into<BoxError>,
     {
-        let client_builder = self.client_builder.unwrap_or_default();
+        let mut client_builder = self.client_builder.unwrap_or_default();
+        // Only override the buffer size of a custom hyper builder when limits are set explicitly
+        if let Some(header_limits) = &self.header_limits {
+            client_builder.http1_max_buf_size(header_limits.response_header_bytes());
+        }
         let sleep_impl = self.sleep_impl.or_else(default_async_sleep);
         let (connect_timeout, read_timeout) = self
             .connector_settings
             .map(|c| (c.connect_timeout(), c.read_timeout()))
             .unwrap_or((None, None));
 
+        let tcp_connector = TimedConnector::new(tcp_connector);
         let connector = match connect_timeout {
             Some(duration) => timeout_middleware::ConnectTimeout::new(
                 tcp_connector,
@@ -197,6 +235,7 @@ impl HyperConnectorBuilder {
         HyperConnector {
             adapter: Box::new(Adapter {
                 client: read_timeout,
+                header_limits: self.header_limits.unwrap_or_default(),
             }),
         }
     }
@@ -240,6 +279,18 @@ impl HyperConnectorBuilder {
         self
     }
 
+    /// Set the limits on the number and size of request and response headers
+    pub fn header_limits(mut self, header_limits: HeaderLimits) -> Self {
+        self.header_limits = Some(header_limits);
+        self
+    }
+
+    /// Set the limits on the number and size of request and response headers
+    pub fn set_header_limits(&mut self, header_limits: Option<HeaderLimits>) -> &mut Self {
+        self.header_limits = header_limits;
+        self
+    }
+
     /// Override the Hyper client [`Builder`](hyper_0_14::client::Builder) used to construct this client.
     ///
     /// This enables changing settings like forcing HTTP2 and modifying other default client behavior.
@@ -267,18 +318,26 @@ struct Adapter<C> {
     client: timeout_middleware::HttpReadTimeout<
         hyper_0_14::Client<timeout_middleware::ConnectTimeout<C>, SdkBody>,
     >,
+    header_limits: HeaderLimits,
 }
 
 impl<C> fmt::Debug for Adapter<C> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Adapter")
             .field("client", &"** hyper client **")
+            .field("header_limits", &self.header_limits)
             .finish()
     }
 }
 
 /// Extract a smithy connection from a hyper CaptureConnection
-fn extract_smithy_connection(capture_conn: &CaptureConnection) -> Option<ConnectionMetadata> {
+///
+/// `request_started` tells whether the connection was reused: connections established before the
+/// request started can only come from the pool.
+fn extract_smithy_connection(
+    capture_conn: &CaptureConnection,
+    request_started: Instant,
+) -> Option<ConnectionMetadata> {
     let capture_conn = capture_conn.clone();
     if let Some(conn) = capture_conn.clone().connection_metadata().as_ref() {
         let mut extensions = http_02x::Extensions::new();
@@ -294,6 +353,15 @@ fn extract_smithy_connection(capture_conn: &CaptureConnection) -> Option<Connect
         builder
             .set_local_addr(http_info.map(|info| info.local_addr()))
             .set_remote_addr(http_info.map(|info| info.remote_addr()));
+        if let Some(connection_use) = extensions
+            .get::<ConnectionTiming>()
+            .map(|timing| timing.used_by(request_started))
+        {
+            builder
+                .set_reused(Some(connection_use.reused))
+                .set_age(Some(connection_use.age))
+                .set_handshake_duration(connection_use.handshake_duration);
+        }
 
         let smithy_connection = builder.build();
 
@@ -320,24 +388,43 @@ where
                 return HttpConnectorFuture::ready(Err(ConnectorError::other(err.into(), None)));
             }
         };
+        // Fail before hyper sees the request, since servers reject oversized headers with
+        // responses that are hard to diagnose
+        if let Err(err) = self.header_limits.check_request(request.headers()) {
+            return HttpConnectorFuture::ready(Err(ConnectorError::user(err.into())));
+        }
         let capture_connection = capture_connection(&mut request);
+        let request_started = Instant::now();
         if let Some(capture_smithy_connection) =
             request.extensions().get::<CaptureSmithyConnection>()
         {
-            capture_smithy_connection
-                .set_connection_retriever(move || extract_smithy_connection(&capture_connection));
+            let capture_connection = capture_connection.clone();
+            capture_smithy_connection.set_connection_retriever(move || {
+                extract_smithy_connection(&capture_connection, request_started)
+            });
         }
         let mut client = self.client.clone();
+        let header_limits = self.header_limits;
         let fut = client.call(request);
         HttpConnectorFuture::new(async move {
             let response = fut
                 .await
-                .map_err(downcast_error)?
+                .map_err(|err| match find_source::<hyper_0_14::Error>(err.as_ref()) {
+                    Some(hyper_err) if hyper_err.is_parse_too_large() => {
+                        ConnectorError::other(header_limits.response_exceeded(err).into(), None)
+                    }
+                    _ => downcast_error(err),
+                })?
                 .map(SdkBody::from_body_0_4);
-            match HttpResponse::try_from(response) {
-                Ok(response) => Ok(response),
-                Err(err) => Err(ConnectorError::other(err.into(), None)),
+            let mut response = HttpResponse::try_from(response)
+                .map_err(|err| ConnectorError::other(err.into(), None))?;
+            // Keep the connection metadata with the response, so that it can be found from
+            // interceptors and from errors
+            if let Some(metadata) = extract_smithy_connection(&capture_connection, request_started)
+            {
+                response.add_extension(metadata);
             }
+            Ok(response)
         })
     }
 }
@@ -420,6 +507,7 @@ impl From<&HttpConnectorSettings> for CacheKey {
 struct HyperClient<F> {
     connector_cache: RwLock<HashMap<CacheKey, SharedHttpConnector>>,
     client_builder: hyper_0_14::client::Builder,
+    header_limits: Option<HeaderLimits>,
     tcp_connector_fn: F,
 }
 
@@ -428,6 +516,7 @@ impl<F> fmt::Debug for HyperClient<F> {
         f.debug_struct("HyperClient")
             .field("connector_cache", &self.connector_cache)
             .field("client_builder", &self.client_builder)
+            .field("header_limits", &self.header_limits)
             .finish()
     }
 }
@@ -456,6 +545,7 @@ where
                     .hyper_builder(self.client_builder.clone())
                     .connector_settings(settings.clone());
                 builder.set_sleep_impl(components.sleep_impl());
+                builder.set_header_limits(self.header_limits);
 
                 let start = components.time_source().map(|ts| ts.now());
                 let tcp_connector = (self.tcp_connector_fn)();
@@ -549,6 +639,12 @@ where
 #[derive(Clone, Default, Debug)]
 pub struct HyperClientBuilder {
     client_builder: Option<hyper_0_14::client::Builder>,
+    header_limits: Option<HeaderLimits>,
+    tcp_keepalive: Option<Duration>,
+    pool_idle_timeout: Option<Duration>,
+    http2_keep_alive_interval: Option<Duration>,
+    http2_keep_alive_timeout: Option<Duration>,
+    http2_keep_alive_while_idle: Option<bool>,
 }
 
 impl HyperClientBuilder {
@@ -576,13 +672,133 @@ impl HyperClientBuilder {
         self
     }
 
+    /// Set the limits on the number and size of request and response headers.
+    ///
+    /// Raise these for services that are known to send or expect very large headers.
+    /// See [`HeaderLimits`] for the defaults.
+    pub fn header_limits(mut self, header_limits: HeaderLimits) -> Self {
+        self.header_limits = Some(header_limits);
+        self
+    }
+
+    /// Set the limits on the number and size of request and response headers.
+    ///
+    /// Raise these for services that are known to send or expect very large headers.
+    /// See [`HeaderLimits`] for the defaults.
+    pub fn set_header_limits(&mut self, header_limits: Option<HeaderLimits>) -> &mut Self {
+        self.header_limits = header_limits;
+        self
+    }
+
+    /// Enable TCP keepalive on every connection, sending keepalive probes after the
+    /// connection has been idle for the given duration.
+    ///
+    /// This only applies to clients created with [`build_https`](HyperClientBuilder::build_https).
+    /// When providing your own TCP connector to [`build`](HyperClientBuilder::build), configure
+    /// keepalive on that connector instead.
+    pub fn tcp_keepalive(mut self, tcp_keepalive: Duration) -> Self {
+        self.tcp_keepalive = Some(tcp_keepalive);
+        self
+    }
+
+    /// Enable TCP keepalive on every connection, sending keepalive probes after the
+    /// connection has been idle for the given duration.
+    ///
+    /// This only applies to clients created with [`build_https`](HyperClientBuilder::build_https).
+    /// When providing your own TCP connector to [`build`](HyperClientBuilder::build), configure
+    /// keepalive on that connector instead.
+    pub fn set_tcp_keepalive(&mut self, tcp_keepalive: Option<Duration>) -> &mut Self {
+        self.tcp_keepalive = tcp_keepalive;
+        self
+    }
+
+    /// Set how long an idle connection is kept in the connection pool before it is closed.
+    ///
+    /// Set this below the idle timeout of any load balancer between the client and the service
+    /// so that the client doesn't reuse connections that the load balancer has already dropped.
+    pub fn pool_idle_timeout(mut self, pool_idle_timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(pool_idle_timeout);
+        self
+    }
+
+    /// Set how long an idle connection is kept in the connection pool before it is closed.
+    ///
+    /// Set this below the idle timeout of any load balancer between the client and the service
+    /// so that the client doesn't reuse connections that the load balancer has already dropped.
+    pub fn set_pool_idle_timeout(&mut self, pool_idle_timeout: Option<Duration>) -> &mut Self {
+        self.pool_idle_timeout = pool_idle_timeout;
+        self
+    }
+
+    /// Send an HTTP/2 `PING` frame on every HTTP/2 connection at this interval to keep it alive.
+    ///
+    /// By default, no pings are sent.
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.http2_keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Send an HTTP/2 `PING` frame on every HTTP/2 connection at this interval to keep it alive.
+    ///
+    /// By default, no pings are sent.
+    pub fn set_http2_keep_alive_interval(&mut self, interval: Option<Duration>) -> &mut Self {
+        self.http2_keep_alive_interval = interval;
+        self
+    }
+
+    /// Set how long to wait for the acknowledgement of an HTTP/2 keepalive ping before
+    /// closing the connection.
+    ///
+    /// This has no effect unless [`http2_keep_alive_interval`](HyperClientBuilder::http2_keep_alive_interval)
+    /// is set. Hyper defaults this to 20 seconds.
+    pub fn http2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.http2_keep_alive_timeout = Some(timeout);
+        self
+    }
+
+    /// Set how long to wait for the acknowledgement of an HTTP/2 keepalive ping before
+    /// closing the connection.
+    ///
+    /// This has no effect unless [`http2_keep_alive_interval`](HyperClientBuilder::http2_keep_alive_interval)
+    /// is set. Hyper defaults this to 20 seconds.
+    pub fn set_http2_keep_alive_timeout(&mut self, timeout: Option<Duration>) -> &mut Self {
+        self.http2_keep_alive_timeout = timeout;
+        self
+    }
+
+    /// Set whether HTTP/2 keepalive pings are sent while there are no open streams on the connection.
+    ///
+    /// When `false` (the default), pings are only sent while a request is in flight.
+    pub fn http2_keep_alive_while_idle(mut self, enabled: bool) -> Self {
+        self.http2_keep_alive_while_idle = Some(enabled);
+        self
+    }
+
+    /// Set whether HTTP/2 keepalive pings are sent while there are no open streams on the connection.
+    ///
+    /// When `false` (the default), pings are only sent while a request is in flight.
+    pub fn set_http2_keep_alive_while_idle(&mut self, enabled: Option<bool>) -> &mut Self {
+        self.http2_keep_alive_while_idle = enabled;
+        self
+    }
+
     /// Create a hyper client with the default rustls HTTPS implementation.
     ///
     /// The trusted certificates will be loaded later when this becomes the selected
     /// HTTP client for a Smithy client.
     #[cfg(feature = "tls-rustls")]
     pub fn build_https(self) -> SharedHttpClient {
-        self.build_with_fn(default_connector::https)
+        match self.tcp_keepalive {
+            None => self.build_with_fn(default_connector::https),
+            Some(keepalive) => {
+                let connector = once_cell::sync::OnceCell::new();
+                self.build_with_fn(move || {
+                    connector
+                        .get_or_init(|| default_connector::https_with_tcp_keepalive(keepalive))
+                        .clone()
+                })
+            }
+        }
     }
 
     /// Create a [`SharedHttpClient`] from this builder and a given connector.
@@ -613,10 +829,29 @@ impl HyperClientBuilder {
     {
         SharedHttpClient::new(HyperClient {
             connector_cache: RwLock::new(HashMap::new()),
-            client_builder: self.client_builder.unwrap_or_default(),
+            client_builder: self.hyper_client_builder(),
+            header_limits: self.header_limits,
             tcp_connector_fn,
         })
     }
+
+    /// The Hyper client builder with the connection settings of this builder applied on top.
+    fn hyper_client_builder(&self) -> hyper_0_14::client::Builder {
+        let mut builder = self.client_builder.clone().unwrap_or_default();
+        if let Some(pool_idle_timeout) = self.pool_idle_timeout {
+            builder.pool_idle_timeout(pool_idle_timeout);
+        }
+        if let Some(interval) = self.http2_keep_alive_interval {
+            builder.http2_keep_alive_interval(interval);
+        }
+        if let Some(timeout) = self.http2_keep_alive_timeout {
+            builder.http2_keep_alive_timeout(timeout);
+        }
+        if let Some(enabled) = self.http2_keep_alive_while_idle {
+            builder.http2_keep_alive_while_idle(enabled);
+        }
+        builder
+    }
 }
 
 mod timeout_middleware {
@@ -995,7 +1230,10 @@ mod timeout_middleware {
 
 #[cfg(all(test, feature = "test-util"))]
 mod test {
-    use crate::client::http::hyper_014::{HyperClientBuilder, HyperConnector};
+    use crate::client::http::hyper_014::{
+        find_source, HeaderLimit, HeaderLimitExceeded, HeaderLimits, HyperClientBuilder,
+        HyperConnector,
+    };
     use crate::client::http::test_util::NeverTcpConnector;
     use aws_smithy_async::time::SystemTimeSource;
     use aws_smithy_runtime_api::box_error::BoxError;
@@ -1007,7 +1245,7 @@ mod test {
     use std::pin::Pin;
     use std::sync::atomic::{AtomicU32, Ordering};
     use std::sync::Arc;
-    use std::task::{Context, Poll};
+    use std::task::{Context, Poll, Waker};
     use std::time::Duration;
     use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 
@@ -1080,6 +1318,292 @@ mod test {
         assert!(err.is_io(), "{:?}", err);
     }
 
+    #[tokio::test]
+    async fn oversized_request_headers_fail_before_dispatch() {
+        // the request would fail with an IO error if it was sent
+        let connector = TestConnection {
+            inner: HangupStream,
+        };
+        let request = || {
+            let mut request = HttpRequest::get("https://example.com").unwrap();
+            request
+                .headers_mut()
+                .insert("x-big", "a".repeat(100 * 1024));
+            request
+        };
+
+        // Request headers aren't limited by default, so the request is sent
+        let adapter = HyperConnector::builder().build(connector.clone()).adapter;
+        let err = adapter.call(request()).await.expect_err("hangup");
+        assert!(err.is_io(), "{:?}", err);
+
+        let adapter = HyperConnector::builder()
+            .header_limits(HeaderLimits::new().max_request_header_bytes(64 * 1024))
+            .build(connector)
+            .adapter;
+        let err = adapter.call(request()).await.expect_err("too large");
+        assert!(err.is_user(), "{:?}", err);
+        let err = find_source::<HeaderLimitExceeded>(&err).expect("header limit error");
+        assert_eq!(HeaderLimit::RequestHeaderBytes, err.limit());
+        assert_eq!(&["x-big".to_string()], err.headers());
+    }
+
+    #[tokio::test]
+    async fn response_header_limits_can_be_raised() {
+        // 90 headers of 10 KiB exceed hyper's default limit of about 400 KiB, while staying under
+        // its `MAX_RESPONSE_HEADERS` cap, which raising the size limit doesn't lift
+        let connector = TestConnection {
+            inner: CannedResponseStream::with_headers(90, 10 * 1024),
+        };
+        let adapter = HyperConnector::builder().build(connector.clone()).adapter;
+        let err = adapter
+            .call(HttpRequest::get("https://example.com").unwrap())
+            .await
+            .expect_err("too large");
+        let err = find_source::<HeaderLimitExceeded>(&err).expect("header limit error");
+        assert_eq!(HeaderLimit::ResponseHeaders, err.limit());
+        assert!(
+            err.to_string().contains("417792 bytes"),
+            "unexpected message: {err}"
+        );
+
+        let adapter = HyperConnector::builder()
+            .header_limits(HeaderLimits::new().max_response_header_bytes(2 * 1024 * 1024))
+            .build(connector)
+            .adapter;
+        let response = adapter
+            .call(HttpRequest::get("https://example.com").unwrap())
+            .await
+            .expect("limit was raised");
+        assert_eq!(91, response.headers().len());
+
+        // hyper 0.14 can't accept more than `MAX_RESPONSE_HEADERS`, whatever the size limit
+        let connector = TestConnection {
+            inner: CannedResponseStream::with_headers(200, 10),
+        };
+        let adapter = HyperConnector::builder()
+            .header_limits(HeaderLimits::new().max_response_header_bytes(2 * 1024 * 1024))
+            .build(connector)
+            .adapter;
+        let err = adapter
+            .call(HttpRequest::get("https://example.com").unwrap())
+            .await
+            .expect_err("too many headers");
+        let err = find_source::<HeaderLimitExceeded>(&err).expect("header limit error");
+        assert_eq!(HeaderLimit::ResponseHeaders, err.limit());
+    }
+
+    #[test]
+    fn connection_settings_are_applied_to_the_hyper_builder() {
+        let builder = HyperClientBuilder::new()
+            .pool_idle_timeout(Duration::from_secs(50))
+            .http2_keep_alive_interval(Duration::from_secs(30))
+            .http2_keep_alive_timeout(Duration::from_secs(5))
+            .http2_keep_alive_while_idle(true)
+            .hyper_client_builder();
+        let debug = format!("{builder:?}");
+        for setting in [
+            "idle_timeout: Some(50s)",
+            "keep_alive_interval: Some(30s)",
+            "keep_alive_timeout: 5s",
+            "keep_alive_while_idle: true",
+        ] {
+            assert!(debug.contains(setting), "`{setting}` not in {debug}");
+        }
+    }
+
+    #[test]
+    fn connection_settings_override_a_custom_hyper_builder() {
+        let mut hyper_builder = hyper_0_14::Client::builder();
+        hyper_builder
+            .pool_idle_timeout(Duration::from_secs(10))
+            .http2_keep_alive_interval(Duration::from_secs(10));
+        let builder = HyperClientBuilder::new()
+            .hyper_builder(hyper_builder)
+            .pool_idle_timeout(Duration::from_secs(50))
+            .hyper_client_builder();
+        let debug = format!("{builder:?}");
+        assert!(debug.contains("idle_timeout: Some(50s)"), "{debug}");
+        // Settings that weren't overridden are left alone
+        assert!(debug.contains("keep_alive_interval: Some(10s)"), "{debug}");
+    }
+
+    #[cfg(feature = "tls-rustls")]
+    #[tokio::test]
+    async fn tcp_keepalive_is_set_on_the_socket() {
+        use hyper_0_14::service::Service;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let uri: http_02x::Uri = format!("http://{}", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+
+        let mut tcp = super::default_connector::tcp(Some(Duration::from_secs(42)));
+        let stream = tcp.call(uri.clone()).await.expect("connected");
+        let socket = socket2::SockRef::from(&stream);
+        assert!(socket.keepalive().unwrap());
+        assert_eq!(Duration::from_secs(42), socket.keepalive_time().unwrap());
+
+        let mut tcp = super::default_connector::tcp(None);
+        let stream = tcp.call(uri).await.expect("connected");
+        assert!(!socket2::SockRef::from(&stream).keepalive().unwrap());
+    }
+
+    #[tokio::test]
+    async fn connection_reuse_is_reported() {
+        use crate::client::http::connection_poisoning::CaptureSmithyConnection;
+        use aws_smithy_runtime_api::client::connection::ConnectionMetadata;
+        use aws_smithy_runtime_api::client::http::HttpConnector;
+        use aws_smithy_types::byte_stream::ByteStream;
+        use hyper_0_14::service::{make_service_fn, service_fn};
+        use std::convert::Infallible;
+
+        async fn send(connector: &HyperConnector, uri: &str) -> ConnectionMetadata {
+            let capture = CaptureSmithyConnection::new();
+            let mut request = HttpRequest::get(uri).unwrap();
+            request.add_extension(capture.clone());
+            let response = connector.call(request).await.expect("success");
+            // The connection only goes back to the pool once the body has been read
+            let body = ByteStream::new(response.into_body()).collect().await;
+            assert_eq!(b"pikachu", &body.unwrap().into_bytes()[..]);
+            capture.get().expect("the connection was captured")
+        }
+
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|_: http_02x::Request<hyper_0_14::Body>| async {
+                Ok::<_, Infallible>(http_02x::Response::new(hyper_0_14::Body::from("pikachu")))
+            }))
+        });
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(
+            hyper_0_14::Server::from_tcp(listener)
+                .unwrap()
+                .serve(make_service),
+        );
+
+        let connector = HyperConnector::builder().build(hyper_0_14::client::HttpConnector::new());
+        let first = send(&connector, &uri).await;
+        assert_eq!(Some(false), first.reused(), "{first:?}");
+        assert_eq!(Some(Duration::ZERO), first.age(), "{first:?}");
+        assert!(first.handshake_duration().is_some(), "{first:?}");
+
+        // Give the connection time to go back to the pool, and to age
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let second = send(&connector, &uri).await;
+        assert_eq!(Some(true), second.reused(), "{second:?}");
+        assert!(
+            second.age().unwrap() >= Duration::from_millis(20),
+            "{second:?}"
+        );
+        assert_eq!(None, second.handshake_duration(), "{second:?}");
+        assert_eq!(first.local_addr(), second.local_addr());
+    }
+
+    #[tokio::test]
+    async fn connection_metadata_is_added_to_responses() {
+        use aws_smithy_runtime_api::client::connection::ConnectionMetadata;
+        use aws_smithy_runtime_api::client::http::HttpConnector;
+        use hyper_0_14::service::{make_service_fn, service_fn};
+        use std::convert::Infallible;
+
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|_: http_02x::Request<hyper_0_14::Body>| async {
+                Ok::<_, Infallible>(http_02x::Response::new(hyper_0_14::Body::empty()))
+            }))
+        });
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let server_addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            hyper_0_14::Server::from_tcp(listener)
+                .unwrap()
+                .serve(make_service),
+        );
+
+        let connector = HyperConnector::builder().build(hyper_0_14::client::HttpConnector::new());
+        // Responses carry the metadata without the connection poisoning interceptor
+        let request = HttpRequest::get(format!("http://{server_addr}")).unwrap();
+        let response = connector.call(request).await.expect("success");
+
+        let metadata = response
+            .extension::<ConnectionMetadata>()
+            .expect("the connection was captured");
+        assert_eq!(Some(server_addr), metadata.remote_addr(), "{metadata:?}");
+        assert_eq!(Some(false), metadata.reused(), "{metadata:?}");
+    }
+
+    // ---- machinery to make a Hyper connector that responds with a canned response
+    #[derive(Clone)]
+    struct CannedResponseStream {
+        response: Arc<Vec<u8>>,
+        position: usize,
+        // hyper fails on responses that arrive before the request was written
+        request_written: bool,
+        read_waker: Option<Waker>,
+    }
+
+    impl CannedResponseStream {
+        fn with_headers(count: usize, size: usize) -> Self {
+            let mut response = "HTTP/1.1 200 OK\r\ncontent-length: 0\r\n".to_string();
+            for i in 0..count {
+                response.push_str(&format!("x-header-{i}: {}\r\n", "a".repeat(size)));
+            }
+            response.push_str("\r\n");
+            Self {
+                response: Arc::new(response.into_bytes()),
+                position: 0,
+                request_written: false,
+                read_waker: None,
+            }
+        }
+    }
+
+    impl Connection for CannedResponseStream {
+        fn connected(&self) -> Connected {
+            Connected::new()
+        }
+    }
+
+    impl AsyncRead for CannedResponseStream {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            if !self.request_written {
+                self.read_waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+            let remaining = &self.response[self.position..];
+            let len = remaining.len().min(buf.remaining());
+            buf.put_slice(&remaining[..len]);
+            self.position += len;
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl AsyncWrite for CannedResponseStream {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<Result<usize, Error>> {
+            self.request_written = true;
+            if let Some(waker) = self.read_waker.take() {
+                waker.wake();
+            }
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
     // ---- machinery to make a Hyper connector that responds with an IO Error
     #[derive(Clone)]
     struct HangupStream;
@@ -0,0 +1,174 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Records when connections were established, so that requests can tell whether they were sent
+//! on a new connection or on one reused from the pool.
+//!
+//! Hyper doesn't say whether a connection was pooled, but every connection carries the
+//! [`ConnectionTiming`] it was established with as an extra of its [`Connected`]. A connection
+//! established before a request was started has necessarily been reused for it.
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use hyper_0_14::client::connect::{Connected, Connection};
+use std::future::Future;
+use std::io::IoSlice;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+/// When a connection was established, and how long it took to establish it.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct ConnectionTiming {
+    established_at: Instant,
+    handshake_duration: Duration,
+}
+
+/// How a connection was used by a request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct ConnectionUse {
+    pub(super) reused: bool,
+    pub(super) age: Duration,
+    pub(super) handshake_duration: Option<Duration>,
+}
+
+impl ConnectionTiming {
+    /// Returns how a request started at `request_started` used this connection.
+    pub(super) fn used_by(&self, request_started: Instant) -> ConnectionUse {
+        let reused = self.established_at < request_started;
+        ConnectionUse {
+            reused,
+            age: request_started.saturating_duration_since(self.established_at),
+            handshake_duration: (!reused).then_some(self.handshake_duration),
+        }
+    }
+}
+
+/// Connector recording the [`ConnectionTiming`] of the connections made by its inner connector.
+#[derive(Clone, Debug)]
+pub(super) struct TimedConnector<C> {
+    inner: C,
+}
+
+impl<C> TimedConnector<C> {
+    pub(super) fn new(inner: C) -> Self {
+        Self { inner }
+    }
+}
+
+impl<C> hyper_0_14::service::Service<http_02x::Uri> for TimedConnector<C>
+where
+    C: hyper_0_14::service::Service<http_02x::Uri>,
+    C::Future: Send + 'static,
+    C::Error: Into<BoxError>,
+{
+    type Response = TimedConnection<C::Response>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(|err| err.into())
+    }
+
+    fn call(&mut self, uri: http_02x::Uri) -> Self::Future {
+        let started = Instant::now();
+        let connecting = self.inner.call(uri);
+        Box::pin(async move {
+            let inner = connecting.await.map_err(|err| err.into())?;
+            let established_at = Instant::now();
+            Ok(TimedConnection {
+                inner,
+                timing: ConnectionTiming {
+                    established_at,
+                    handshake_duration: established_at - started,
+                },
+            })
+        })
+    }
+}
+
+/// Connection carrying its [`ConnectionTiming`].
+#[derive(Debug)]
+pub(super) struct TimedConnection<T> {
+    inner: T,
+    timing: ConnectionTiming,
+}
+
+impl<T: Connection> Connection for TimedConnection<T> {
+    fn connected(&self) -> Connected {
+        self.inner.connected().extra(self.timing)
+    }
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for TimedConnection<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for TimedConnection<T> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, std::io::Error>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn connections_established_before_the_request_are_reused() {
+        let established_at = Instant::now();
+        let timing = ConnectionTiming {
+            established_at,
+            handshake_duration: Duration::from_millis(30),
+        };
+
+        assert_eq!(
+            ConnectionUse {
+                reused: false,
+                age: Duration::ZERO,
+                handshake_duration: Some(Duration::from_millis(30)),
+            },
+            timing.used_by(established_at - Duration::from_millis(40))
+        );
+        assert_eq!(
+            ConnectionUse {
+                reused: true,
+                age: Duration::from_secs(5),
+                handshake_duration: None,
+            },
+            timing.used_by(established_at + Duration::from_secs(5))
+        );
+    }
+}
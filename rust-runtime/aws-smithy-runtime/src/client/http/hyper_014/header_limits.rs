@@ -0,0 +1,290 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use std::error::Error;
+use std::fmt;
+
+// Matches hyper's default maximum buffer size
+const DEFAULT_MAX_RESPONSE_HEADER_BYTES: usize = 8192 + 4096 * 100;
+// hyper refuses to set a maximum buffer size below this
+const MIN_MAX_RESPONSE_HEADER_BYTES: usize = 8192;
+
+/// The maximum number of response headers that the hyper 0.14.x client accepts.
+///
+/// hyper 0.14.x parses response headers into a fixed-size array of 100 entries, so this limit
+/// can't be raised, not even with [`HeaderLimits::max_response_header_bytes`]. Responses with
+/// more headers fail with a [`HeaderLimitExceeded`] error for [`HeaderLimit::ResponseHeaders`].
+pub const MAX_RESPONSE_HEADERS: usize = 100;
+
+/// Limits on the number and size of the headers sent and received by a hyper client.
+///
+/// Request headers aren't limited unless a limit is set. When one is, request headers are
+/// checked before the request is sent, so that a request with oversized headers fails with a
+/// [`HeaderLimitExceeded`] error naming the offending headers, rather than being rejected by the
+/// service. Response headers that exceed the limits also fail with a [`HeaderLimitExceeded`]
+/// error, instead of an opaque hyper parse error.
+///
+/// Header sizes are counted as they're sent over HTTP/1.1: the name, the value, and four bytes
+/// for the `: ` separator and the line ending.
+///
+/// # Example
+///
+/// ```no_run
+/// use aws_smithy_runtime::client::http::hyper_014::{HeaderLimits, HyperClientBuilder};
+///
+/// // This service is known to send very large headers
+/// let http_client = HyperClientBuilder::new()
+///     .header_limits(HeaderLimits::new().max_response_header_bytes(1024 * 1024))
+///     .build_https();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HeaderLimits {
+    max_request_headers: Option<usize>,
+    max_request_header_bytes: Option<usize>,
+    max_response_header_bytes: usize,
+}
+
+impl Default for HeaderLimits {
+    fn default() -> Self {
+        Self {
+            max_request_headers: None,
+            max_request_header_bytes: None,
+            max_response_header_bytes: DEFAULT_MAX_RESPONSE_HEADER_BYTES,
+        }
+    }
+}
+
+impl HeaderLimits {
+    /// Creates the default limits: no limits on request headers, and hyper's default of about
+    /// 400 KiB of response headers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of headers in a request.
+    pub fn max_request_headers(mut self, max: usize) -> Self {
+        self.max_request_headers = Some(max);
+        self
+    }
+
+    /// Sets the maximum total size of the headers of a request, in bytes.
+    pub fn max_request_header_bytes(mut self, max: usize) -> Self {
+        self.max_request_header_bytes = Some(max);
+        self
+    }
+
+    /// Sets the maximum total size of the headers of a response, in bytes.
+    ///
+    /// This sets hyper's maximum HTTP/1 buffer size, so values below 8 KiB are raised to 8 KiB.
+    /// hyper checks the limit between reads, so slightly larger responses may be accepted. The
+    /// number of response headers is always limited to [`MAX_RESPONSE_HEADERS`].
+    pub fn max_response_header_bytes(mut self, max: usize) -> Self {
+        self.max_response_header_bytes = max.max(MIN_MAX_RESPONSE_HEADER_BYTES);
+        self
+    }
+
+    /// Returns the maximum number of headers in a request, if limited.
+    pub fn request_headers(&self) -> Option<usize> {
+        self.max_request_headers
+    }
+
+    /// Returns the maximum total size of the headers of a request in bytes, if limited.
+    pub fn request_header_bytes(&self) -> Option<usize> {
+        self.max_request_header_bytes
+    }
+
+    /// Returns the maximum total size of the headers of a response, in bytes.
+    pub fn response_header_bytes(&self) -> usize {
+        self.max_response_header_bytes
+    }
+
+    pub(super) fn check_request(
+        &self,
+        headers: &http_02x::HeaderMap,
+    ) -> Result<(), HeaderLimitExceeded> {
+        if let Some(max) = self.max_request_headers {
+            if headers.len() > max {
+                return Err(HeaderLimitExceeded {
+                    limit: HeaderLimit::RequestHeaders,
+                    max,
+                    actual: Some(headers.len()),
+                    headers: Vec::new(),
+                    source: None,
+                });
+            }
+        }
+        let Some(max_bytes) = self.max_request_header_bytes else {
+            return Ok(());
+        };
+        let mut sizes = headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), name.as_str().len() + value.len() + 4))
+            .collect::<Vec<_>>();
+        let total = sizes.iter().map(|(_, size)| size).sum::<usize>();
+        if total <= max_bytes {
+            return Ok(());
+        }
+        // Name the largest headers that account for the excess
+        sizes.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+        let mut excess = total - max_bytes;
+        let mut oversized = Vec::new();
+        for (name, size) in sizes {
+            if excess == 0 {
+                break;
+            }
+            if !oversized.iter().any(|oversized| oversized == name) {
+                oversized.push(name.to_string());
+            }
+            excess = excess.saturating_sub(size);
+        }
+        Err(HeaderLimitExceeded {
+            limit: HeaderLimit::RequestHeaderBytes,
+            max: max_bytes,
+            actual: Some(total),
+            headers: oversized,
+            source: None,
+        })
+    }
+
+    pub(super) fn response_exceeded(&self, source: BoxError) -> HeaderLimitExceeded {
+        HeaderLimitExceeded {
+            limit: HeaderLimit::ResponseHeaders,
+            max: self.max_response_header_bytes,
+            actual: None,
+            headers: Vec::new(),
+            source: Some(source),
+        }
+    }
+}
+
+/// A limit set by [`HeaderLimits`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeaderLimit {
+    /// The maximum number of headers in a request.
+    RequestHeaders,
+    /// The maximum total size of the headers of a request.
+    RequestHeaderBytes,
+    /// The maximum number, or total size, of the headers of a response.
+    ///
+    /// hyper doesn't report which of the two was exceeded.
+    ResponseHeaders,
+}
+
+/// The headers of a request or response exceeded the [`HeaderLimits`] of the HTTP client.
+///
+/// Requests that fail with this error are not retried.
+#[derive(Debug)]
+pub struct HeaderLimitExceeded {
+    limit: HeaderLimit,
+    max: usize,
+    actual: Option<usize>,
+    headers: Vec<String>,
+    source: Option<BoxError>,
+}
+
+impl HeaderLimitExceeded {
+    /// Returns the limit that was exceeded.
+    pub fn limit(&self) -> HeaderLimit {
+        self.limit
+    }
+
+    /// Returns the names of the largest request headers, which account for the excess when the
+    /// [total size](HeaderLimit::RequestHeaderBytes) of the request headers was exceeded.
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+}
+
+impl fmt::Display for HeaderLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let actual = self.actual.unwrap_or_default();
+        match self.limit {
+            HeaderLimit::RequestHeaders => write!(
+                f,
+                "the request has {actual} headers, which exceeds the limit of {} headers",
+                self.max
+            ),
+            HeaderLimit::RequestHeaderBytes => write!(
+                f,
+                "the request headers are {actual} bytes, which exceeds the limit of {} bytes. \
+                 The largest headers are: {}",
+                self.max,
+                self.headers.join(", ")
+            ),
+            HeaderLimit::ResponseHeaders => write!(
+                f,
+                "the response headers exceed the limit of {MAX_RESPONSE_HEADERS} headers or {} bytes. \
+                 The size limit can be raised with `HeaderLimits::max_response_header_bytes`",
+                self.max
+            ),
+        }
+    }
+}
+
+impl Error for HeaderLimitExceeded {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_ref().map(|err| err.as_ref() as _)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(headers: &[(&'static str, usize)]) -> http_02x::HeaderMap {
+        headers
+            .iter()
+            .map(|(name, size)| (name.parse().unwrap(), "a".repeat(*size).parse().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn request_headers_are_not_limited_by_default() {
+        let limits = HeaderLimits::new();
+        assert!(limits
+            .check_request(&headers(&[("x-a", 100), ("x-big", 100 * 1024)]))
+            .is_ok());
+        let many = (0..200)
+            .map(|i| {
+                (
+                    format!("x-header-{i}").parse().unwrap(),
+                    "a".parse().unwrap(),
+                )
+            })
+            .collect::<http_02x::HeaderMap>();
+        assert!(limits.check_request(&many).is_ok());
+    }
+
+    #[test]
+    fn request_headers_are_limited() {
+        let limits = HeaderLimits::new().max_request_header_bytes(64 * 1024);
+        assert!(limits
+            .check_request(&headers(&[("x-a", 100), ("x-b", 100)]))
+            .is_ok());
+
+        let err = limits
+            .check_request(&headers(&[
+                ("x-a", 100),
+                ("x-big", 100 * 1024),
+                ("x-b", 10),
+            ]))
+            .unwrap_err();
+        assert_eq!(HeaderLimit::RequestHeaderBytes, err.limit());
+        assert_eq!(&["x-big".to_string()], err.headers());
+        assert_eq!(
+            "the request headers are 102533 bytes, which exceeds the limit of 65536 bytes. \
+             The largest headers are: x-big",
+            err.to_string()
+        );
+
+        let err = limits
+            .max_request_headers(1)
+            .check_request(&headers(&[("x-a", 1), ("x-b", 1)]))
+            .unwrap_err();
+        assert_eq!(HeaderLimit::RequestHeaders, err.limit());
+    }
+}
@@ -0,0 +1,214 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use super::downcast_error;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::connector_metadata::ConnectorMetadata;
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+use aws_smithy_runtime_api::client::result::ConnectorError;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::body::SdkBody;
+use bytes::Bytes;
+use hyper_0_14::service::Service;
+use std::fmt;
+use std::future::poll_fn;
+
+/// [`HttpConnector`] and [`HttpClient`] that send requests through a [`Service`], like a
+/// [`hyper_0_14::Client`] or a `tower` service, whatever its response body is.
+///
+/// The response body only has to implement [`http_body_04x::Body<Data = Bytes>`]: it's wrapped
+/// into an [`SdkBody`] as is, so streamed responses aren't buffered.
+///
+/// The service is cloned for every request, and sees the same requests whatever the
+/// [`HttpConnectorSettings`], so it's responsible for its own timeouts. Errors are classified
+/// like the errors of the [`HyperConnector`](super::HyperConnector).
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_smithy_runtime::client::http::hyper_014::ServiceConnector;
+/// use aws_smithy_types::body::SdkBody;
+///
+/// let client = hyper_0_14::Client::builder().build_http::<SdkBody>();
+/// let http_client = ServiceConnector::new(client);
+/// // Pass `http_client` to the `http_client` method of a service config builder.
+/// ```
+#[derive(Clone)]
+pub struct ServiceConnector<S> {
+    service: S,
+}
+
+impl<S> ServiceConnector<S> {
+    /// Creates a connector sending requests through `service`.
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+impl<S> fmt::Debug for ServiceConnector<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServiceConnector").finish_non_exhaustive()
+    }
+}
+
+impl<S, B> HttpConnector for ServiceConnector<S>
+where
+    S: Service<http_02x::Request<SdkBody>, Response = http_02x::Response<B>>,
+    S: Clone + Send + Sync + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+    B: http_body_04x::Body<Data = Bytes> + Send + Sync + 'static,
+    B::Error: Into<BoxError> + 'static,
+{
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        let request = match request.try_into_http02x() {
+            Ok(request) => request,
+            Err(err) => {
+                return HttpConnectorFuture::ready(Err(ConnectorError::other(err.into(), None)));
+            }
+        };
+        let mut service = self.service.clone();
+        HttpConnectorFuture::new(async move {
+            poll_fn(|cx| service.poll_ready(cx))
+                .await
+                .map_err(|err| downcast_error(err.into()))?;
+            let response = service
+                .call(request)
+                .await
+                .map_err(|err| downcast_error(err.into()))?
+                .map(SdkBody::from_body_0_4);
+            HttpResponse::try_from(response).map_err(|err| ConnectorError::other(err.into(), None))
+        })
+    }
+}
+
+impl<S, B> HttpClient for ServiceConnector<S>
+where
+    S: Service<http_02x::Request<SdkBody>, Response = http_02x::Response<B>>,
+    S: Clone + Send + Sync + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+    B: http_body_04x::Body<Data = Bytes> + Send + Sync + 'static,
+    B::Error: Into<BoxError> + 'static,
+{
+    fn http_connector(
+        &self,
+        _settings: &HttpConnectorSettings,
+        _components: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        self.clone().into_shared()
+    }
+
+    fn connector_metadata(&self) -> Option<ConnectorMetadata> {
+        Some(ConnectorMetadata::new("service", None))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use http_body_04x::{Body, Full};
+    use hyper_0_14::service::{make_service_fn, service_fn};
+    use std::convert::Infallible;
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use tokio::sync::Notify;
+
+    fn request(uri: &str) -> HttpRequest {
+        http_02x::Request::builder()
+            .uri(uri)
+            .body(SdkBody::empty())
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn responses_with_another_body_are_converted() {
+        let connector = ServiceConnector::new(service_fn(
+            |request: http_02x::Request<SdkBody>| async move {
+                assert_eq!("/pokemon", request.uri().path());
+                Ok::<_, Infallible>(
+                    http_02x::Response::builder()
+                        .status(200)
+                        .header("content-type", "text/plain")
+                        .body(Full::new(Bytes::from_static(b"pikachu")))
+                        .unwrap(),
+                )
+            },
+        ));
+
+        let response = connector
+            .call(request("http://localhost/pokemon"))
+            .await
+            .unwrap();
+        assert_eq!(200, response.status().as_u16());
+        assert_eq!(Some("text/plain"), response.headers().get("content-type"));
+        assert_eq!(Some(&b"pikachu"[..]), response.body().bytes());
+    }
+
+    #[tokio::test]
+    async fn service_errors_are_connector_errors() {
+        let connector = ServiceConnector::new(service_fn(|_: http_02x::Request<SdkBody>| async {
+            Err::<http_02x::Response<Full<Bytes>>, _>("unreachable host")
+        }));
+
+        let err = connector
+            .call(request("http://localhost/pokemon"))
+            .await
+            .expect_err("the service fails");
+        assert!(err.is_other(), "{err:?}");
+        assert_eq!("unreachable host", err.into_source().to_string());
+    }
+
+    #[tokio::test]
+    async fn hyper_client_responses_are_streamed() {
+        // The server only sends its second chunk once the first one has been read
+        let first_chunk_read = Arc::new(Notify::new());
+        let server_first_chunk_read = first_chunk_read.clone();
+        let make_service = make_service_fn(move |_| {
+            let first_chunk_read = server_first_chunk_read.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |_: http_02x::Request<hyper_0_14::Body>| {
+                    let first_chunk_read = first_chunk_read.clone();
+                    async move {
+                        let (mut sender, body) = hyper_0_14::Body::channel();
+                        tokio::spawn(async move {
+                            sender.send_data(Bytes::from_static(b"pika")).await.unwrap();
+                            first_chunk_read.notified().await;
+                            sender.send_data(Bytes::from_static(b"chu")).await.unwrap();
+                        });
+                        Ok::<_, Infallible>(http_02x::Response::new(body))
+                    }
+                }))
+            }
+        });
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = hyper_0_14::Server::from_tcp(listener)
+            .unwrap()
+            .serve(make_service);
+        tokio::spawn(server);
+
+        let connector =
+            ServiceConnector::new(hyper_0_14::Client::builder().build_http::<SdkBody>());
+        let response = connector
+            .call(request(&format!("http://{addr}/pokemon")))
+            .await
+            .unwrap();
+        assert_eq!(200, response.status().as_u16());
+
+        let mut body = response.into_body();
+        assert_eq!(None, body.bytes(), "the response shouldn't be buffered");
+        assert_eq!(&b"pika"[..], body.data().await.unwrap().unwrap());
+        first_chunk_read.notify_one();
+        assert_eq!(&b"chu"[..], body.data().await.unwrap().unwrap());
+        assert!(body.data().await.is_none());
+    }
+}
@@ -18,12 +18,20 @@
 //! [`RecordingClient`](dvr::RecordingClient) and [`ReplayingClient`](dvr::ReplayingClient)
 //! can accomplish this, and the recorded traffic can be saved to JSON and checked in. Note: if
 //! the traffic recording has sensitive information in it, such as signatures or authorization,
-//! you will need to manually scrub this out if you intend to store the recording alongside
-//! your tests.
+//! you will need to scrub this out with [`Redactions`] if you intend to store the recording
+//! alongside your tests.
 //! - [`StaticReplayClient`]: If you want to have a set list of requests and their responses in a test,
 //! then the static replay client will be useful. On construction, it takes a list of request/response
 //! pairs that represent each expected request and the response for that test. At the end of the test,
 //! you can ask the client to verify that the requests matched the expectations.
+#![cfg_attr(
+    feature = "har",
+    doc = "- [`har`]: If you have captured traffic in a HAR file, e.g. from browser developer tools, then the"
+)]
+#![cfg_attr(
+    feature = "har",
+    doc = "[`HarImporter`](har::HarImporter) converts it into [`ReplayEvent`]s for the static replay client."
+)]
 //! - [`infallible_client_fn`]: Allows you to create a client from an infallible function
 //! that takes a request and returns a response.
 //! - [`NeverClient`]: Useful for testing timeouts, where you want the client to never respond.
@@ -42,9 +50,15 @@ pub use capture_request::{capture_request, CaptureRequestHandler, CaptureRequest
 #[cfg(feature = "connector-hyper-0-14-x")]
 pub mod dvr;
 
+mod redact;
+pub use redact::Redactions;
+
 mod replay;
 pub use replay::{ReplayEvent, StaticReplayClient};
 
+#[cfg(feature = "har")]
+pub mod har;
+
 mod infallible;
 pub use infallible::infallible_client_fn;
 
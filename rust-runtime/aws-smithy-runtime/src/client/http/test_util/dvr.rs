@@ -9,6 +9,7 @@
 //!
 //! DVR is an extremely experimental record & replay framework that supports multi-frame HTTP request / response traffic.
 
+use super::Redactions;
 use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
 use aws_smithy_runtime_api::http::Headers;
 use aws_smithy_types::base64;
@@ -153,6 +154,29 @@ impl<'a> From<&'a HttpRequest> for Request {
     }
 }
 
+impl Request {
+    pub(crate) fn redact(mut self, redactions: &Redactions) -> Self {
+        self.uri = redactions.uri(&self.uri).into_owned();
+        redact_headers(&mut self.headers, redactions);
+        self
+    }
+}
+
+impl Response {
+    pub(crate) fn redact(mut self, redactions: &Redactions) -> Self {
+        redact_headers(&mut self.headers, redactions);
+        self
+    }
+}
+
+fn redact_headers(headers: &mut IndexMap<String, Vec<String>>, redactions: &Redactions) {
+    for (name, values) in headers.iter_mut() {
+        for value in values.iter_mut() {
+            *value = redactions.header_value(name, value).to_string();
+        }
+    }
+}
+
 fn headers_to_map_http(headers: &Headers) -> IndexMap<String, Vec<String>> {
     let mut out: IndexMap<_, Vec<_>> = IndexMap::new();
     for (header_name, header_value) in headers.iter() {
@@ -343,6 +367,35 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn recordings_can_be_redacted() -> Result<(), Box<dyn Error>> {
+        let network_traffic = fs::read_to_string("test-data/example.com.json")?;
+        let network_traffic: NetworkTraffic = serde_json::from_str(&network_traffic)?;
+        let inner = ReplayingClient::new(network_traffic.events.clone());
+        let connection = RecordingClient::new(SharedHttpConnector::new(inner.clone()))
+            .with_redactions(Redactions::credentials());
+        let req = http_02x::Request::post("https://www.example.com/?X-Amz-Signature=abc&a=b")
+            .header("authorization", "secret")
+            .body(SdkBody::from("hello world"))
+            .unwrap();
+        connection.call(req.try_into().unwrap()).await.expect("ok");
+        let Action::Request { request } = connection.events()[0].action.clone() else {
+            panic!("unexpected event")
+        };
+        assert_eq!(
+            "https://www.example.com/?X-Amz-Signature=REDACTED&a=b",
+            request.uri
+        );
+        assert_eq!(
+            Some(&vec!["REDACTED".to_string()]),
+            request.headers.get("authorization")
+        );
+        // the request is sent unchanged
+        let requests = inner.take_requests().await;
+        assert_eq!("secret", requests[0].headers()["authorization"]);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn turtles_all_the_way_down() -> Result<(), Box<dyn Error>> {
         // create a replaying connection from a recording, wrap a recording connection around it,
@@ -377,4 +430,93 @@ mod tests {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn recordings_can_be_replayed_after_a_json_round_trip() -> Result<(), Box<dyn Error>> {
+        use aws_smithy_runtime_api::client::http::HttpConnectorFuture;
+
+        const RELAXED_HEADERS: &[&str] = &["x-amz-date", "authorization"];
+        let request = |date: &str, body: &'static str| -> HttpRequest {
+            http_02x::Request::post("https://www.example.com/pokemon")
+                .header("content-type", "application/json")
+                .header("x-amz-date", date)
+                .header("authorization", format!("signed at {date}"))
+                .body(SdkBody::from(body))
+                .unwrap()
+                .try_into()
+                .unwrap()
+        };
+        async fn read_body(response: &mut HttpResponse) -> Bytes {
+            ByteStream::new(response.take_body())
+                .collect()
+                .await
+                .unwrap()
+                .into_bytes()
+        }
+
+        /// Reads the request body like a real connection would, and responds with a canned response.
+        #[derive(Debug)]
+        struct CannedConnection;
+
+        impl HttpConnector for CannedConnection {
+            fn call(&self, mut request: HttpRequest) -> HttpConnectorFuture {
+                HttpConnectorFuture::new(async move {
+                    ByteStream::new(request.take_body())
+                        .collect()
+                        .await
+                        .unwrap();
+                    Ok(HttpResponse::new(
+                        201.try_into().unwrap(),
+                        SdkBody::from(r#"{"id":25}"#),
+                    ))
+                })
+            }
+        }
+
+        // Record traffic against the canned connection
+        let recording = RecordingClient::new(CannedConnection);
+        let mut response = recording
+            .call(request(
+                "20240101T000000Z",
+                r#"{"name":"pikachu","level":5}"#,
+            ))
+            .await
+            .expect("ok");
+        read_body(&mut response).await;
+        let recorded = serde_json::to_string(&recording.network_traffic())?;
+
+        // Replay it with a request signed at another time, and with its JSON fields reordered
+        let events = serde_json::from_str::<NetworkTraffic>(&recorded)?.events;
+        let replay = ReplayingClient::new(events.clone());
+        let mut response = replay
+            .call(request(
+                "20240202T000000Z",
+                r#"{"level":5,"name":"pikachu"}"#,
+            ))
+            .await
+            .expect("ok");
+        assert_eq!(201, response.status().as_u16());
+        assert_eq!(
+            r#"{"id":25}"#.as_bytes(),
+            &read_body(&mut response).await[..]
+        );
+        replay
+            .validate_body_and_headers_except(RELAXED_HEADERS, "application/json")
+            .await?;
+
+        // Bodies that are equivalent JSON don't match when they're compared exactly
+        let replay = ReplayingClient::new(events);
+        replay
+            .call(request(
+                "20240202T000000Z",
+                r#"{"level":5,"name":"pikachu"}"#,
+            ))
+            .await
+            .expect("ok");
+        replay
+            .validate_body_and_headers_except(RELAXED_HEADERS, "text/plain")
+            .await
+            .expect_err("the bodies aren't byte for byte equal");
+        Ok(())
+    }
 }
@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use super::super::Redactions;
 use super::{
     Action, BodyData, ConnectionId, Direction, Error, Event, NetworkTraffic, Request, Response,
     Version,
@@ -72,6 +73,7 @@ pub struct RecordingClient {
     pub(crate) data: Arc<Mutex<Vec<Event>>>,
     pub(crate) num_events: Arc<AtomicUsize>,
     pub(crate) inner: SharedHttpConnector,
+    pub(crate) redactions: Redactions,
 }
 
 #[cfg(feature = "tls-rustls")]
@@ -83,6 +85,7 @@ impl RecordingClient {
             data: Default::default(),
             num_events: Arc::new(AtomicUsize::new(0)),
             inner: SharedHttpConnector::new(HyperConnector::builder().build_https()),
+            redactions: Redactions::default(),
         }
     }
 }
@@ -94,9 +97,18 @@ impl RecordingClient {
             data: Default::default(),
             num_events: Arc::new(AtomicUsize::new(0)),
             inner: underlying_connector.into_shared(),
+            redactions: Redactions::default(),
         }
     }
 
+    /// Scrub the given header values and query parameters from the recorded traffic
+    ///
+    /// The request is sent unchanged, only the recording is redacted. Bodies are not redacted.
+    pub fn with_redactions(mut self, redactions: Redactions) -> Self {
+        self.redactions = redactions;
+        self
+    }
+
     /// Return the traffic recorded by this connection
     pub fn events(&self) -> MutexGuard<'_, Vec<Event>> {
         self.data.lock().unwrap()
@@ -199,7 +211,7 @@ impl HttpConnector for RecordingClient {
         self.data.lock().unwrap().push(Event {
             connection_id: event_id,
             action: Action::Request {
-                request: Request::from(&request),
+                request: Request::from(&request).redact(&self.redactions),
             },
         });
 
@@ -213,6 +225,7 @@ impl HttpConnector for RecordingClient {
             self.data.clone(),
         );
         let events = self.data.clone();
+        let redactions = self.redactions.clone();
         // create a channel we'll use to stream the data while reading it
         let resp_fut = self.inner.call(request);
         let fut = async move {
@@ -223,7 +236,7 @@ impl HttpConnector for RecordingClient {
                     events.lock().unwrap().push(Event {
                         connection_id: event_id,
                         action: Action::Response {
-                            response: Ok(Response::from(&resp)),
+                            response: Ok(Response::from(&resp).redact(&redactions)),
                         },
                     });
 
@@ -0,0 +1,424 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Import [HTTP Archive (HAR) 1.2](http://www.softwareishard.com/blog/har-12-spec/) files as
+//! replay traffic.
+//!
+//! HAR files can be exported from browser developer tools and most HTTP proxies, which makes them
+//! a convenient way to capture real traffic for a test. [`HarImporter`] converts the entries of
+//! a HAR file into [`ReplayEvent`]s for the [`StaticReplayClient`].
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use aws_smithy_runtime::client::http::test_util::har::HarImporter;
+//! use aws_smithy_runtime::client::http::test_util::Redactions;
+//!
+//! let import = HarImporter::new()
+//!     .url_filter(|url| url.contains(".amazonaws.com"))
+//!     .entries(2..)
+//!     .redactions(Redactions::credentials())
+//!     .import_file("tests/data/traffic.har")
+//!     .expect("valid HAR file");
+//! for warning in import.warnings() {
+//!     println!("skipped {warning}");
+//! }
+//! let http_client = import.into_replay_client();
+//! ```
+
+use super::{Redactions, ReplayEvent, StaticReplayClient};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+use aws_smithy_types::base64;
+use aws_smithy_types::body::SdkBody;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+use std::path::Path;
+use std::sync::Arc;
+
+type UrlFilter = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Converts the entries of a HAR file into [`ReplayEvent`]s.
+///
+/// Entries that can't be converted are skipped, and reported as [`HarWarning`]s.
+#[derive(Clone)]
+pub struct HarImporter {
+    url_filter: Option<UrlFilter>,
+    entries: (Bound<usize>, Bound<usize>),
+    redactions: Redactions,
+}
+
+impl fmt::Debug for HarImporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HarImporter")
+            .field(
+                "url_filter",
+                &self.url_filter.as_ref().map(|_| "** url filter **"),
+            )
+            .field("entries", &self.entries)
+            .field("redactions", &self.redactions)
+            .finish()
+    }
+}
+
+impl Default for HarImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HarImporter {
+    /// Creates an importer that imports every entry.
+    pub fn new() -> Self {
+        Self {
+            url_filter: None,
+            entries: (Bound::Unbounded, Bound::Unbounded),
+            redactions: Redactions::default(),
+        }
+    }
+
+    /// Only import the entries with a request URL that `url_filter` returns `true` for.
+    pub fn url_filter(mut self, url_filter: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        self.url_filter = Some(Arc::new(url_filter));
+        self
+    }
+
+    /// Only import the entries in the given range of positions in the HAR file, starting at zero.
+    ///
+    /// Positions are counted before the [URL filter](Self::url_filter) is applied.
+    pub fn entries(mut self, range: impl RangeBounds<usize>) -> Self {
+        self.entries = (range.start_bound().cloned(), range.end_bound().cloned());
+        self
+    }
+
+    /// Scrub the given header values and query parameters from the imported requests and responses.
+    pub fn redactions(mut self, redactions: Redactions) -> Self {
+        self.redactions = redactions;
+        self
+    }
+
+    /// Imports the HAR file at `path`.
+    pub fn import_file(&self, path: impl AsRef<Path>) -> Result<HarImport, Box<dyn Error>> {
+        self.import_str(&std::fs::read_to_string(path)?)
+    }
+
+    /// Imports a HAR document.
+    ///
+    /// Fails if `har` isn't a HAR document. Malformed entries are skipped instead, and reported
+    /// by [`HarImport::warnings`].
+    pub fn import_str(&self, har: &str) -> Result<HarImport, Box<dyn Error>> {
+        let har: Har = serde_json::from_str(har)?;
+        let mut import = HarImport::default();
+        for (index, entry) in har.log.entries.into_iter().enumerate() {
+            if !self.entries.contains(&index) {
+                continue;
+            }
+            let entry = match serde_json::from_value::<Entry>(entry) {
+                Ok(entry) => entry,
+                Err(err) => {
+                    import.warn(index, format!("malformed entry: {err}"));
+                    continue;
+                }
+            };
+            if let Some(url_filter) = &self.url_filter {
+                if !url_filter(&entry.request.url) {
+                    continue;
+                }
+            }
+            match entry.into_replay_event(&self.redactions) {
+                Ok(event) => import.events.push(event),
+                Err(message) => import.warn(index, message),
+            }
+        }
+        Ok(import)
+    }
+}
+
+/// The result of importing a HAR file with a [`HarImporter`].
+#[derive(Debug, Default)]
+pub struct HarImport {
+    events: Vec<ReplayEvent>,
+    warnings: Vec<HarWarning>,
+}
+
+impl HarImport {
+    /// Returns the imported request/response pairs, in the order they appear in the HAR file.
+    pub fn events(&self) -> &[ReplayEvent] {
+        &self.events
+    }
+
+    /// Returns a warning for every entry that was skipped because it couldn't be converted.
+    pub fn warnings(&self) -> &[HarWarning] {
+        &self.warnings
+    }
+
+    /// Consumes this import, returning the imported request/response pairs.
+    pub fn into_events(self) -> Vec<ReplayEvent> {
+        self.events
+    }
+
+    /// Consumes this import, returning a [`StaticReplayClient`] that replays the imported responses.
+    pub fn into_replay_client(self) -> StaticReplayClient {
+        StaticReplayClient::new(self.events)
+    }
+
+    fn warn(&mut self, index: usize, message: String) {
+        self.warnings.push(HarWarning { index, message });
+    }
+}
+
+/// An entry of a HAR file that was skipped by a [`HarImporter`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HarWarning {
+    index: usize,
+    message: String,
+}
+
+impl HarWarning {
+    /// Returns the position of the skipped entry in the HAR file, starting at zero.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns why the entry was skipped.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for HarWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HAR entry #{}: {}", self.index, self.message)
+    }
+}
+
+#[derive(Deserialize)]
+struct Har {
+    log: Log,
+}
+
+#[derive(Deserialize)]
+struct Log {
+    entries: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    request: Request,
+    response: Response,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Request {
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: Vec<NameValue>,
+    #[serde(default)]
+    query_string: Vec<NameValue>,
+    post_data: Option<Body>,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    status: u16,
+    #[serde(default)]
+    headers: Vec<NameValue>,
+    content: Option<Body>,
+}
+
+/// The `postData` of a request, or the `content` of a response.
+#[derive(Deserialize)]
+struct Body {
+    text: Option<String>,
+    encoding: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NameValue {
+    name: String,
+    value: String,
+}
+
+impl Entry {
+    fn into_replay_event(self, redactions: &Redactions) -> Result<ReplayEvent, String> {
+        let request = self.request.into_http(redactions)?;
+        let response = self.response.into_http(redactions)?;
+        Ok(ReplayEvent::new(request, response))
+    }
+}
+
+impl Request {
+    fn into_http(self, redactions: &Redactions) -> Result<HttpRequest, String> {
+        let mut url = self.url;
+        // Most tools include the query string in the URL too, so `queryString` is only used when
+        // the URL doesn't have one.
+        if !url.contains('?') && !self.query_string.is_empty() {
+            let query = self
+                .query_string
+                .iter()
+                .map(|param| {
+                    format!(
+                        "{}={}",
+                        aws_smithy_http::query::fmt_string(&param.name),
+                        aws_smithy_http::query::fmt_string(&param.value)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("&");
+            url = format!("{url}?{query}");
+        }
+        let mut builder = http_02x::Request::builder()
+            .method(self.method.as_str())
+            .uri(redactions.uri(&url).as_ref());
+        for header in self.headers.iter().filter(|h| !h.name.starts_with(':')) {
+            builder = builder.header(
+                &header.name,
+                redactions.header_value(&header.name, &header.value),
+            );
+        }
+        let request = builder
+            .body(body(self.post_data)?)
+            .map_err(|err| format!("invalid request: {err}"))?;
+        HttpRequest::try_from(request).map_err(|err| format!("invalid request: {err}"))
+    }
+}
+
+impl Response {
+    fn into_http(self, redactions: &Redactions) -> Result<HttpResponse, String> {
+        let body = body(self.content)?;
+        let mut builder = http_02x::Response::builder().status(self.status);
+        for header in &self.headers {
+            let name = header.name.to_ascii_lowercase();
+            // HAR response content is already decoded, so the length and encoding of the
+            // original response no longer apply.
+            match name.as_str() {
+                "content-encoding" => continue,
+                "content-length" => {
+                    let length = body.content_length().unwrap_or_default().to_string();
+                    builder = builder.header(name, length);
+                }
+                _ if name.starts_with(':') => continue,
+                _ => {
+                    builder =
+                        builder.header(name, redactions.header_value(&header.name, &header.value))
+                }
+            }
+        }
+        let response = builder
+            .body(body)
+            .map_err(|err| format!("invalid response: {err}"))?;
+        HttpResponse::try_from(response).map_err(|err| format!("invalid response: {err}"))
+    }
+}
+
+fn body(body: Option<Body>) -> Result<SdkBody, String> {
+    let Some(Body { text, encoding }) = body else {
+        return Ok(SdkBody::empty());
+    };
+    let text = text.unwrap_or_default();
+    match encoding.as_deref() {
+        None => Ok(SdkBody::from(text)),
+        Some("base64") => base64::decode(text)
+            .map(SdkBody::from)
+            .map_err(|err| format!("invalid base64 body: {err}")),
+        Some(encoding) => Err(format!("unsupported body encoding `{encoding}`")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HAR: &str = include_str!("../../../../test-data/example.har");
+
+    fn bodies(event: &ReplayEvent) -> (&[u8], &[u8]) {
+        (
+            event.request().body().bytes().unwrap(),
+            event.response().body().bytes().unwrap(),
+        )
+    }
+
+    #[test]
+    fn import_entries() {
+        let import = HarImporter::new().import_str(HAR).unwrap();
+        assert_eq!(3, import.events().len());
+
+        // query strings are appended when the URL doesn't have one
+        let [get, put, redirect] = import.events() else {
+            unreachable!()
+        };
+        assert_eq!(
+            "https://example.com/things?tag=a%20b&tag=c",
+            get.request().uri()
+        );
+        assert_eq!(
+            vec!["a", "b"],
+            get.request()
+                .headers()
+                .get_all("x-multi")
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(None, get.request().headers().get(":authority"));
+        let response_cookies = get.response().headers().get_all("set-cookie");
+        assert_eq!(vec!["a=1", "b=2"], response_cookies.collect::<Vec<_>>());
+        // the decoded body replaces the compressed one
+        assert_eq!(None, get.response().headers().get("content-encoding"));
+        assert_eq!(Some("7"), get.response().headers().get("content-length"));
+        assert_eq!((&b""[..], &b"[\"one\"]"[..]), bodies(get));
+
+        // base64 bodies are decoded
+        assert_eq!("PUT", put.request().method());
+        assert_eq!((&[0xde, 0xad, 0xbe, 0xef][..], &b"ok"[..]), bodies(put));
+        assert_eq!(301, redirect.response().status().as_u16());
+
+        assert_eq!(
+            vec![
+                "HAR entry #3: malformed entry: missing field `url`".to_string(),
+                "HAR entry #4: unsupported body encoding `gzip`".to_string(),
+            ],
+            import
+                .warnings()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn filter_and_redact_entries() {
+        let import = HarImporter::new()
+            .entries(1..4)
+            .url_filter(|url| url.contains("/things"))
+            .redactions(Redactions::credentials())
+            .import_str(HAR)
+            .unwrap();
+        assert_eq!(1, import.events().len());
+        let put = &import.events()[0];
+        assert_eq!(
+            "https://example.com/things/1?X-Amz-Signature=REDACTED",
+            put.request().uri()
+        );
+        assert_eq!(
+            Some("REDACTED"),
+            put.request().headers().get("authorization")
+        );
+        assert_eq!(1, import.warnings().len());
+        assert_eq!(3, import.warnings()[0].index());
+
+        let import = HarImporter::new().import_str(HAR).unwrap();
+        assert_eq!(
+            Some("AWS4-HMAC-SHA256 secret"),
+            import.events()[1].request().headers().get("authorization")
+        );
+    }
+
+    #[test]
+    fn reject_documents_that_arent_har() {
+        assert!(HarImporter::new().import_str(r#"{"entries": []}"#).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::borrow::Cow;
+
+const REDACTED: &str = "REDACTED";
+
+/// Header values and query parameters to scrub from recorded or imported traffic.
+///
+/// Redacted values are replaced with `REDACTED`, so that traffic recordings can be checked in
+/// alongside tests without leaking signatures or credentials.
+///
+/// # Example
+///
+/// ```rust
+/// use aws_smithy_runtime::client::http::test_util::Redactions;
+///
+/// let redactions = Redactions::credentials()
+///     .header("x-api-key")
+///     .query_param("token");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Redactions {
+    headers: Vec<String>,
+    query_params: Vec<String>,
+}
+
+impl Redactions {
+    /// Creates an empty set of redactions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a set of redactions that scrubs credentials, signatures, and cookies.
+    pub fn credentials() -> Self {
+        Self::new()
+            .header("authorization")
+            .header("x-amz-security-token")
+            .header("cookie")
+            .header("set-cookie")
+            .query_param("X-Amz-Credential")
+            .query_param("X-Amz-Security-Token")
+            .query_param("X-Amz-Signature")
+    }
+
+    /// Redacts the values of the header named `name`. Header names are case-insensitive.
+    pub fn header(mut self, name: impl Into<String>) -> Self {
+        self.headers.push(name.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Redacts the values of the query parameter named `name`. Query parameter names are
+    /// case-sensitive.
+    pub fn query_param(mut self, name: impl Into<String>) -> Self {
+        self.query_params.push(name.into());
+        self
+    }
+
+    /// Returns `value`, or `REDACTED` if the header named `name` is redacted.
+    pub(crate) fn header_value<'a>(&self, name: &str, value: &'a str) -> &'a str {
+        if self
+            .headers
+            .iter()
+            .any(|redacted| redacted.eq_ignore_ascii_case(name))
+        {
+            REDACTED
+        } else {
+            value
+        }
+    }
+
+    /// Returns `uri` with the values of redacted query parameters replaced with `REDACTED`.
+    pub(crate) fn uri<'a>(&self, uri: &'a str) -> Cow<'a, str> {
+        let Some((path, query)) = uri.split_once('?') else {
+            return Cow::Borrowed(uri);
+        };
+        if self.query_params.is_empty() {
+            return Cow::Borrowed(uri);
+        }
+        let query = query
+            .split('&')
+            .map(|param| match param.split_once('=') {
+                Some((name, _)) if self.query_params.iter().any(|redacted| redacted == name) => {
+                    Cow::Owned(format!("{name}={REDACTED}"))
+                }
+                _ => Cow::Borrowed(param),
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+        Cow::Owned(format!("{path}?{query}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redact_headers_and_query_params() {
+        let redactions = Redactions::credentials().header("X-Api-Key");
+        assert_eq!("REDACTED", redactions.header_value("Authorization", "AWS4"));
+        assert_eq!("REDACTED", redactions.header_value("x-api-key", "secret"));
+        assert_eq!("json", redactions.header_value("content-type", "json"));
+
+        assert_eq!(
+            "https://s3.amazonaws.com/b/k?X-Amz-Signature=REDACTED&versionId=1&flag",
+            redactions.uri("https://s3.amazonaws.com/b/k?X-Amz-Signature=abc&versionId=1&flag")
+        );
+        assert_eq!(
+            "https://s3.amazonaws.com/b/k",
+            redactions.uri("https://s3.amazonaws.com/b/k")
+        );
+    }
+}
@@ -12,7 +12,7 @@ use aws_smithy_types::config_bag::ConfigBag;
 
 mod lazy;
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
-pub use lazy::LazyCacheBuilder;
+pub use lazy::{LazyCacheBuilder, RefreshMode};
 
 /// Identity cache configuration.
 ///
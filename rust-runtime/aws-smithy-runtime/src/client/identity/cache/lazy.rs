@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::client::identity::IdentityCache;
 use crate::expiring_cache::ExpiringCache;
 use aws_smithy_async::future::timeout::Timeout;
 use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
@@ -16,10 +17,10 @@ use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_runtime_api::shared::IntoShared;
 use aws_smithy_types::config_bag::ConfigBag;
 use aws_smithy_types::DateTime;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
-use std::sync::RwLock;
-use std::time::Duration;
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::time::{Duration, SystemTime};
 use tracing::Instrument;
 
 const DEFAULT_LOAD_TIMEOUT: Duration = Duration::from_secs(5);
@@ -27,6 +28,22 @@ const DEFAULT_EXPIRATION: Duration = Duration::from_secs(15 * 60);
 const DEFAULT_BUFFER_TIME: Duration = Duration::from_secs(10);
 const DEFAULT_BUFFER_TIME_JITTER_FRACTION: fn() -> f64 = || fastrand::f64() * 0.5;
 
+/// What callers do while an identity that is about to expire is refreshed.
+///
+/// An identity is refreshed once the [buffer time](LazyCacheBuilder::buffer_time) before its
+/// expiration has been reached. Only one refresh is in flight at a time: the caller that starts
+/// it always waits for it to complete.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RefreshMode {
+    /// Other callers wait for the refresh to complete too, and use the refreshed identity.
+    #[default]
+    WaitForRefresh,
+    /// Other callers keep using the cached identity until the refresh completes, as long as it
+    /// hasn't expired yet.
+    UseStaleIdentity,
+}
+
 /// Builder for lazy identity caching.
 #[derive(Default, Debug)]
 pub struct LazyCacheBuilder {
@@ -36,6 +53,7 @@ pub struct LazyCacheBuilder {
     buffer_time: Option<Duration>,
     buffer_time_jitter_fraction: Option<fn() -> f64>,
     default_expiration: Option<Duration>,
+    refresh_mode: Option<RefreshMode>,
 }
 
 impl LazyCacheBuilder {
@@ -161,6 +179,22 @@ impl LazyCacheBuilder {
         self
     }
 
+    /// What callers do while an identity that is about to expire is refreshed.
+    ///
+    /// Defaults to [`RefreshMode::WaitForRefresh`].
+    pub fn refresh_mode(mut self, refresh_mode: RefreshMode) -> Self {
+        self.set_refresh_mode(Some(refresh_mode));
+        self
+    }
+
+    /// What callers do while an identity that is about to expire is refreshed.
+    ///
+    /// Defaults to [`RefreshMode::WaitForRefresh`].
+    pub fn set_refresh_mode(&mut self, refresh_mode: Option<RefreshMode>) -> &mut Self {
+        self.refresh_mode = refresh_mode;
+        self
+    }
+
     /// Builds a [`SharedIdentityCache`] from this builder.
     ///
     /// # Panics
@@ -178,6 +212,7 @@ impl LazyCacheBuilder {
             self.buffer_time_jitter_fraction
                 .unwrap_or(DEFAULT_BUFFER_TIME_JITTER_FRACTION),
             default_expiration,
+            self.refresh_mode.unwrap_or_default(),
         )
         .into_shared()
     }
@@ -187,6 +222,8 @@ impl LazyCacheBuilder {
 struct CachePartitions {
     partitions: RwLock<HashMap<IdentityCachePartition, ExpiringCache<Identity, BoxError>>>,
     buffer_time: Duration,
+    /// Partitions whose identity is refreshed in the background.
+    refreshing: Arc<Mutex<HashSet<IdentityCachePartition>>>,
 }
 
 impl CachePartitions {
@@ -194,6 +231,7 @@ impl CachePartitions {
         Self {
             partitions: RwLock::new(HashMap::new()),
             buffer_time,
+            refreshing: Default::default(),
         }
     }
 
@@ -223,6 +261,7 @@ struct LazyCache {
     buffer_time: Duration,
     buffer_time_jitter_fraction: fn() -> f64,
     default_expiration: Duration,
+    refresh_mode: RefreshMode,
 }
 
 impl LazyCache {
@@ -231,6 +270,7 @@ impl LazyCache {
         buffer_time: Duration,
         buffer_time_jitter_fraction: fn() -> f64,
         default_expiration: Duration,
+        refresh_mode: RefreshMode,
     ) -> Self {
         Self {
             partitions: CachePartitions::new(buffer_time),
@@ -238,8 +278,180 @@ impl LazyCache {
             buffer_time,
             buffer_time_jitter_fraction,
             default_expiration,
+            refresh_mode,
         }
     }
+
+    /// Returns the identity being refreshed if it can still be used.
+    fn stale_identity(
+        &self,
+        cache: &ExpiringCache<Identity, BoxError>,
+        now: SystemTime,
+    ) -> Option<Identity> {
+        if self.refresh_mode != RefreshMode::UseStaleIdentity {
+            return None;
+        }
+        cache.stale_while_loading().filter(|identity| {
+            identity
+                .expiration()
+                .map_or(true, |expiration| now < expiration)
+        })
+    }
+
+    /// Refreshes the identity of `partition` in the background if an
+    /// [`SdkRuntime`](aws_smithy_async::rt::spawn::SdkRuntime) is configured.
+    ///
+    /// The identity is refreshed one buffer time before it would be refreshed lazily, so that
+    /// callers don't have to wait for it. If it can't be refreshed in time, it's refreshed lazily.
+    fn refresh_in_background(
+        &self,
+        partition: IdentityCachePartition,
+        cache: ExpiringCache<Identity, BoxError>,
+        resolver: SharedIdentityResolver,
+        runtime_components: &RuntimeComponents,
+        expiration: SystemTime,
+    ) {
+        let Some(sdk_runtime) = runtime_components.sdk_runtime() else {
+            return;
+        };
+        let (time_source, sleep_impl) = (
+            runtime_components.time_source().expect("validated"),
+            runtime_components.sleep_impl().expect("validated"),
+        );
+        let refresh_at = expiration - self.buffer_time * 2;
+        if refresh_at <= time_source.now() {
+            return;
+        }
+        let Some(refreshing) = Refreshing::start(&self.partitions.refreshing, partition) else {
+            // A background task is already refreshing this partition
+            return;
+        };
+        // The identity cache is part of the runtime components, and the background task
+        // shouldn't keep it from being dropped.
+        let Ok(runtime_components) = runtime_components
+            .to_builder()
+            .with_identity_cache(Some(IdentityCache::no_cache()))
+            .build()
+        else {
+            return;
+        };
+        let refresh = BackgroundRefresh {
+            cache,
+            resolver,
+            runtime_components,
+            time_source,
+            sleep_impl,
+            load_timeout: self.load_timeout,
+            buffer_time: self.buffer_time,
+            buffer_time_jitter_fraction: self.buffer_time_jitter_fraction,
+            default_expiration: self.default_expiration,
+            refreshing,
+        };
+        let span = tracing::debug_span!("background_identity_refresh", partition = ?partition);
+        if !sdk_runtime.spawn_background(refresh.run(refresh_at).instrument(span)) {
+            tracing::debug!(
+                "the SDK runtime was shut down, so the identity will be refreshed lazily"
+            );
+        }
+    }
+}
+
+/// Marks a partition as refreshed in the background until dropped.
+struct Refreshing {
+    partitions: Weak<Mutex<HashSet<IdentityCachePartition>>>,
+    partition: IdentityCachePartition,
+}
+
+impl Refreshing {
+    fn start(
+        partitions: &Arc<Mutex<HashSet<IdentityCachePartition>>>,
+        partition: IdentityCachePartition,
+    ) -> Option<Self> {
+        partitions.lock().unwrap().insert(partition).then(|| Self {
+            partitions: Arc::downgrade(partitions),
+            partition,
+        })
+    }
+
+    /// Returns `true` if the cache that the partition belongs to was dropped.
+    fn cache_dropped(&self) -> bool {
+        self.partitions.strong_count() == 0
+    }
+}
+
+impl Drop for Refreshing {
+    fn drop(&mut self) {
+        if let Some(partitions) = self.partitions.upgrade() {
+            partitions.lock().unwrap().remove(&self.partition);
+        }
+    }
+}
+
+/// Loads identities into a cache partition before they expire, until a refresh fails, the cache is
+/// dropped, or the SDK runtime is shut down.
+struct BackgroundRefresh {
+    cache: ExpiringCache<Identity, BoxError>,
+    resolver: SharedIdentityResolver,
+    runtime_components: RuntimeComponents,
+    time_source: SharedTimeSource,
+    sleep_impl: SharedAsyncSleep,
+    load_timeout: Duration,
+    buffer_time: Duration,
+    buffer_time_jitter_fraction: fn() -> f64,
+    default_expiration: Duration,
+    refreshing: Refreshing,
+}
+
+impl BackgroundRefresh {
+    async fn run(self, mut refresh_at: SystemTime) {
+        loop {
+            // Don't refresh in a loop if identities expire within twice the buffer time
+            let wait = match refresh_at.duration_since(self.time_source.now()) {
+                Ok(wait) if !wait.is_zero() => wait,
+                _ => return,
+            };
+            self.sleep_impl.sleep(wait).await;
+            if self.refreshing.cache_dropped() {
+                return;
+            }
+            match self.load().await {
+                Ok((identity, expiration)) => {
+                    tracing::debug!(
+                        new_expiration=%DateTime::from(expiration),
+                        "refreshed identity in the background"
+                    );
+                    self.cache.replace(identity, expiration).await;
+                    refresh_at = expiration - self.buffer_time * 2;
+                }
+                Err(err) => {
+                    tracing::debug!(
+                        err=%err,
+                        "failed to refresh identity in the background, so it will be refreshed lazily"
+                    );
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Resolves an identity with an empty config bag, since there is no request to take it from.
+    async fn load(&self) -> Result<(Identity, SystemTime), BoxError> {
+        let config_bag = ConfigBag::base();
+        let identity = Timeout::new(
+            self.resolver
+                .resolve_identity(&self.runtime_components, &config_bag),
+            self.sleep_impl.sleep(self.load_timeout),
+        )
+        .await
+        .map_err(|_| TimedOutError(self.load_timeout))??;
+        let expiration = identity
+            .expiration()
+            .unwrap_or(self.time_source.now() + self.default_expiration);
+        let jitter = self
+            .buffer_time
+            .mul_f64((self.buffer_time_jitter_fraction)());
+        Ok((identity, expiration + jitter))
+    }
 }
 
 macro_rules! required_err {
@@ -306,9 +518,19 @@ impl ResolveCachedIdentity for LazyCache {
         let load_timeout = self.load_timeout;
         let partition = resolver.cache_partition();
         let cache = self.partitions.partition(partition);
+        let background_cache = cache.clone();
         let default_expiration = self.default_expiration;
 
         IdentityFuture::new(async move {
+            // Don't wait for the lock held by a refresh in flight if its result isn't needed yet
+            if let Some(identity) = self.stale_identity(&cache, now) {
+                tracing::debug!(
+                    cached_expiration=?identity.expiration(),
+                    now=?now,
+                    "using the cached identity while it is refreshed"
+                );
+                return Ok(identity);
+            }
             // Attempt to get cached identity, or clear the cache if they're expired
             if let Some(identity) = cache.yield_or_clear_if_expired(now).await {
                 tracing::debug!(
@@ -362,6 +584,13 @@ impl ResolveCachedIdentity for LazyCache {
                                 time_source.now().duration_since(start_time).unwrap_or_default()
                             );
 
+                            self.refresh_in_background(
+                                partition,
+                                background_cache,
+                                resolver.clone(),
+                                runtime_components,
+                                expiration + jitter,
+                            );
                             Ok((identity, expiration + jitter))
                         }
                         // Only instrument the the actual load future so that no span
@@ -391,6 +620,8 @@ impl fmt::Display for TimedOutError {
 mod tests {
     use super::*;
     use aws_smithy_async::rt::sleep::TokioSleep;
+    use aws_smithy_async::rt::spawn::{BackgroundTask, SdkRuntime, TaskSpawner, TokioTaskSpawner};
+    use aws_smithy_async::test_util::tick_advance_sleep::tick_advance_time_and_sleep;
     use aws_smithy_async::test_util::{instant_time_and_sleep, ManualTimeSource};
     use aws_smithy_async::time::TimeSource;
     use aws_smithy_runtime_api::client::identity::http::Token;
@@ -398,6 +629,7 @@ mod tests {
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::{Arc, Mutex};
     use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use tokio::sync::Notify;
     use tracing::info;
 
     const BUFFER_TIME_NO_JITTER: fn() -> f64 = || 0_f64;
@@ -458,6 +690,7 @@ mod tests {
             DEFAULT_BUFFER_TIME,
             buffer_time_jitter_fraction,
             DEFAULT_EXPIRATION,
+            RefreshMode::WaitForRefresh,
         );
         (cache, identity_resolver)
     }
@@ -503,6 +736,7 @@ mod tests {
             DEFAULT_BUFFER_TIME,
             BUFFER_TIME_NO_JITTER,
             DEFAULT_EXPIRATION,
+            RefreshMode::WaitForRefresh,
         );
         assert_eq!(
             epoch_secs(1000),
@@ -641,6 +875,7 @@ mod tests {
             DEFAULT_BUFFER_TIME,
             BUFFER_TIME_NO_JITTER,
             DEFAULT_EXPIRATION,
+            RefreshMode::WaitForRefresh,
         );
 
         let err: BoxError = cache
@@ -768,4 +1003,208 @@ mod tests {
         assert_eq!(1, resolver_a_calls.load(Ordering::Relaxed));
         assert_eq!(1, resolver_b_calls.load(Ordering::Relaxed));
     }
+
+    fn refresh_mode_cache(refresh_mode: RefreshMode) -> LazyCache {
+        LazyCache::new(
+            DEFAULT_LOAD_TIMEOUT,
+            DEFAULT_BUFFER_TIME,
+            BUFFER_TIME_NO_JITTER,
+            DEFAULT_EXPIRATION,
+            refresh_mode,
+        )
+    }
+
+    /// Resolves an identity expiring at 1000 first, and then one expiring at 2000 once `refreshed`
+    /// is notified.
+    fn refresh_blocked_until(refreshed: Arc<Notify>) -> SharedIdentityResolver {
+        let calls = AtomicUsize::new(0);
+        resolver_fn(move || {
+            if calls.fetch_add(1, Ordering::Relaxed) == 0 {
+                return IdentityFuture::ready(Ok(test_identity(1000)));
+            }
+            let refreshed = refreshed.clone();
+            IdentityFuture::new(async move {
+                refreshed.notified().await;
+                Ok(test_identity(2000))
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn refresh_happens_once_for_concurrent_callers() {
+        let time = ManualTimeSource::new(epoch_secs(100));
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time.clone()))
+            .with_sleep_impl(Some(TokioSleep::new()))
+            .build()
+            .unwrap();
+        let cache = refresh_mode_cache(RefreshMode::WaitForRefresh);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let resolver = resolver_fn({
+            let calls = calls.clone();
+            move || {
+                let expiration = 1000 * (calls.fetch_add(1, Ordering::Relaxed) as u64 + 1);
+                IdentityFuture::new(async move {
+                    tokio::task::yield_now().await;
+                    Ok(test_identity(expiration))
+                })
+            }
+        });
+
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        // Within the buffer time of the expiration
+        time.set_time(epoch_secs(995));
+        futures_util::future::join_all(
+            (0..5).map(|_| expect_identity(2000, &cache, &components, resolver.clone())),
+        )
+        .await;
+        assert_eq!(2, calls.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn callers_wait_for_refresh_by_default() {
+        let time = ManualTimeSource::new(epoch_secs(100));
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time.clone()))
+            .with_sleep_impl(Some(TokioSleep::new()))
+            .build()
+            .unwrap();
+        let cache = refresh_mode_cache(RefreshMode::WaitForRefresh);
+        let refreshed = Arc::new(Notify::new());
+        let resolver = refresh_blocked_until(refreshed.clone());
+
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        time.set_time(epoch_secs(995));
+        let mut refresh = Box::pin(expect_identity(2000, &cache, &components, resolver.clone()));
+        assert!(futures_util::poll!(&mut refresh).is_pending());
+        let mut other = Box::pin(expect_identity(2000, &cache, &components, resolver.clone()));
+        assert!(futures_util::poll!(&mut other).is_pending());
+
+        refreshed.notify_one();
+        refresh.await;
+        other.await;
+    }
+
+    #[tokio::test]
+    async fn stale_identity_is_used_while_refreshing() {
+        let time = ManualTimeSource::new(epoch_secs(100));
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time.clone()))
+            .with_sleep_impl(Some(TokioSleep::new()))
+            .build()
+            .unwrap();
+        let cache = refresh_mode_cache(RefreshMode::UseStaleIdentity);
+        let refreshed = Arc::new(Notify::new());
+        let resolver = refresh_blocked_until(refreshed.clone());
+
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        time.set_time(epoch_secs(995));
+        let mut refresh = Box::pin(expect_identity(2000, &cache, &components, resolver.clone()));
+        assert!(futures_util::poll!(&mut refresh).is_pending());
+        // The identity being refreshed hasn't actually expired yet
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+
+        refreshed.notify_one();
+        refresh.await;
+        expect_identity(2000, &cache, &components, resolver.clone()).await;
+    }
+
+    #[tokio::test]
+    async fn expired_stale_identity_is_not_used() {
+        let time = ManualTimeSource::new(epoch_secs(100));
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time.clone()))
+            .with_sleep_impl(Some(TokioSleep::new()))
+            .build()
+            .unwrap();
+        let cache = refresh_mode_cache(RefreshMode::UseStaleIdentity);
+        let refreshed = Arc::new(Notify::new());
+        let resolver = refresh_blocked_until(refreshed.clone());
+
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        time.set_time(epoch_secs(1000));
+        let mut refresh = Box::pin(expect_identity(2000, &cache, &components, resolver.clone()));
+        assert!(futures_util::poll!(&mut refresh).is_pending());
+        let mut other = Box::pin(expect_identity(2000, &cache, &components, resolver.clone()));
+        assert!(futures_util::poll!(&mut other).is_pending());
+
+        refreshed.notify_one();
+        refresh.await;
+        other.await;
+    }
+
+    /// Spawns tasks with Tokio, counting them.
+    #[derive(Debug, Default)]
+    struct CountingSpawner(Arc<AtomicUsize>);
+
+    impl TaskSpawner for CountingSpawner {
+        fn spawn(&self, task: BackgroundTask) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            TokioTaskSpawner::new().spawn(task)
+        }
+    }
+
+    #[tokio::test]
+    async fn identity_is_refreshed_in_the_background_with_an_sdk_runtime() {
+        let (time, sleep) = tick_advance_time_and_sleep();
+        let spawned = Arc::new(AtomicUsize::new(0));
+        let sdk_runtime = SdkRuntime::new(CountingSpawner(spawned.clone()), sleep.clone());
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time.clone()))
+            .with_sleep_impl(Some(sleep))
+            .with_sdk_runtime(Some(sdk_runtime.clone()))
+            .build()
+            .unwrap();
+        let (cache, resolver) = test_cache(
+            BUFFER_TIME_NO_JITTER,
+            vec![
+                Ok(test_identity(1000)),
+                Ok(test_identity(2000)),
+                Ok(test_identity(5000)),
+            ],
+        );
+
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        assert_eq!(1, spawned.load(Ordering::SeqCst));
+        // Let the background task start waiting for the refresh
+        tokio::task::yield_now().await;
+
+        // The refresh happens one buffer time before the identity would be refreshed lazily
+        time.tick(Duration::from_secs(985)).await;
+        expect_identity(2000, &cache, &components, resolver.clone()).await;
+        assert_eq!(1, spawned.load(Ordering::SeqCst));
+
+        sdk_runtime
+            .shutdown(Duration::from_secs(1))
+            .await
+            .expect("the background refresh stops when shut down");
+
+        // Once shut down, the identity is refreshed lazily
+        time.tick(Duration::from_secs(1000)).await;
+        expect_identity(2000, &cache, &components, resolver.clone()).await;
+        time.tick(Duration::from_secs(5)).await;
+        expect_identity(5000, &cache, &components, resolver.clone()).await;
+        assert_eq!(1, spawned.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn identity_is_refreshed_lazily_without_an_sdk_runtime() {
+        let (time, sleep) = tick_advance_time_and_sleep();
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time.clone()))
+            .with_sleep_impl(Some(sleep))
+            .build()
+            .unwrap();
+        let (cache, resolver) = test_cache(
+            BUFFER_TIME_NO_JITTER,
+            vec![Ok(test_identity(1000)), Ok(test_identity(2000))],
+        );
+
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        tokio::task::yield_now().await;
+        time.tick(Duration::from_secs(985)).await;
+        expect_identity(1000, &cache, &components, resolver.clone()).await;
+        time.tick(Duration::from_secs(5)).await;
+        expect_identity(2000, &cache, &components, resolver.clone()).await;
+    }
 }
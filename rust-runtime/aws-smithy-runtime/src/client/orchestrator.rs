@@ -4,14 +4,24 @@
  */
 
 use self::auth::orchestrate_auth;
+use crate::client::captured_headers::CaptureResponseHeaders;
+use crate::client::config_validation::{
+    validate_operation_config, ClientConfigValidation, ValidateOperationConfig,
+};
+use crate::client::field_errors::LenientDeserialization;
+use crate::client::http::connection_poisoning::CaptureSmithyConnection;
 use crate::client::interceptors::Interceptors;
 use crate::client::orchestrator::http::{log_response_body, read_body};
+use crate::client::rate_limit::RateLimiter;
+use crate::client::redirect::{FollowRedirects, RedirectPolicy};
+use crate::client::ser_de_override;
 use crate::client::timeout::{MaybeTimeout, MaybeTimeoutConfig, TimeoutKind};
+use crate::client::unknown_fields::UnknownFieldReporting;
 use crate::client::{
     http::body::minimum_throughput::MaybeUploadThroughputCheckFuture,
-    orchestrator::endpoints::orchestrate_endpoint,
+    orchestrator::endpoints::{orchestrate_endpoint, resolve_endpoint_before_serialization},
 };
-use aws_smithy_async::rt::sleep::AsyncSleep;
+use aws_smithy_async::rt::sleep::{AsyncSleep, ForbidRealSleep};
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::http::{HttpClient, HttpConnector, HttpConnectorSettings};
 use aws_smithy_runtime_api::client::interceptors::context::{
@@ -25,14 +35,19 @@ use aws_smithy_runtime_api::client::retries::{RequestAttempts, RetryStrategy, Sh
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugins;
 use aws_smithy_runtime_api::client::ser_de::{
-    DeserializeResponse, SerializeRequest, SharedRequestSerializer, SharedResponseDeserializer,
+    DeserializeResponse, SharedRequestSerializer, SharedResponseDeserializer,
 };
 use aws_smithy_types::body::SdkBody;
 use aws_smithy_types::byte_stream::ByteStream;
+use aws_smithy_types::captured_headers::{with_captured_headers, CapturedHeaders};
 use aws_smithy_types::config_bag::ConfigBag;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::error::metadata::{with_error_metadata_extras, ErrorMetadataExtras};
+use aws_smithy_types::field_errors::deserialize_leniently;
 use aws_smithy_types::timeout::{MergeTimeoutConfig, TimeoutConfig};
+use aws_smithy_types::unknown_fields::record_unknown_fields;
 use std::mem;
-use tracing::{debug, debug_span, instrument, trace, Instrument};
+use tracing::{debug, debug_span, instrument, trace, warn, Instrument};
 
 mod auth;
 
@@ -149,10 +164,26 @@ pub async fn invoke_with_stop_point(
             .map_err(SdkError::construction_failure)?;
         trace!(runtime_components = ?runtime_components);
 
+        #[cfg(feature = "test-util")]
+        let forbid_real_sleep = cfg
+            .load::<crate::client::test_util::sleep::PanicOnRealSleep>()
+            .is_some_and(|panic_on_real_sleep| panic_on_real_sleep.is_enabled());
+        #[cfg(not(feature = "test-util"))]
+        let forbid_real_sleep = false;
+        // Only sleeps made with the configured sleep implementation are allowed, so every
+        // component must be given the wrapped one
+        let runtime_components = if forbid_real_sleep {
+            let mut builder = runtime_components.to_builder();
+            builder.set_sleep_impl(runtime_components.sleep_impl().map(ForbidRealSleep::allow));
+            builder.build().map_err(SdkError::construction_failure)?
+        } else {
+            runtime_components
+        };
+
         let operation_timeout_config =
             MaybeTimeoutConfig::new(&runtime_components, cfg, TimeoutKind::Operation);
         trace!(operation_timeout_config = ?operation_timeout_config);
-        async {
+        let operation = async {
             // If running the pre-execution interceptors failed, then we skip running the op and run the
             // final interceptors instead.
             if !ctx.is_failed() {
@@ -165,8 +196,8 @@ pub async fn invoke_with_stop_point(
                 Ok(ctx)
             }
         }
-        .maybe_timeout(operation_timeout_config)
-        .await
+        .maybe_timeout(operation_timeout_config);
+        ForbidRealSleep::new(operation, forbid_real_sleep).await
     }
     // Include a random, internal-only, seven-digit ID for the operation invocation so that it can be correlated in the logs.
     .instrument(debug_span!("invoke", service = %service_name, operation = %operation_name, sdk_invocation_id = fastrand::u32(1_000_000..10_000_000)))
@@ -189,10 +220,9 @@ fn apply_configuration(
     continue_on_err!([ctx] => Interceptors::new(operation_rc_builder.interceptors()).read_before_execution(true, ctx, cfg));
 
     // The order below is important. Client interceptors must run before operation interceptors.
-    let components = RuntimeComponents::builder("merged orchestrator components")
+    let components_builder = RuntimeComponents::builder("merged orchestrator components")
         .merge_from(&client_rc_builder)
-        .merge_from(&operation_rc_builder)
-        .build()?;
+        .merge_from(&operation_rc_builder);
 
     // In an ideal world, we'd simply update `cfg.load` to behave this way. Unfortunately, we can't
     // do that without a breaking change. By overwriting the value in the config bag with a merged
@@ -204,6 +234,14 @@ fn apply_configuration(
     );
     cfg.interceptor_state().store_put(resolved_timeout_config);
 
+    // The client's config was validated when the client was created. Only a config override for
+    // this operation, which may introduce problems or fix them, needs the merged config validated.
+    if cfg.load::<ValidateOperationConfig>().is_some() {
+        validate_operation_config(&components_builder, cfg)?;
+    } else if let Some(validation) = cfg.load::<ClientConfigValidation>() {
+        validation.check()?;
+    }
+    let components = components_builder.build()?;
     components.validate_final_config(cfg)?;
     Ok(components)
 }
@@ -221,6 +259,9 @@ async fn try_op(
         read_before_serialization(ctx, runtime_components, cfg);
     });
 
+    // Endpoint resolvers that need the input must run before serialization consumes it
+    halt_on_err!([ctx] => resolve_endpoint_before_serialization(ctx, runtime_components, cfg).await.map_err(OrchestratorError::other));
+
     // Serialization
     ctx.enter_serialization_phase();
     {
@@ -230,7 +271,7 @@ async fn try_op(
             .expect("request serializer must be in the config bag")
             .clone();
         let input = ctx.take_input().expect("input set at this point");
-        let request = halt_on_err!([ctx] => request_serializer.serialize_input(input, cfg).map_err(OrchestratorError::other));
+        let request = halt_on_err!([ctx] => ser_de_override::serialize(&request_serializer, input, cfg).map_err(OrchestratorError::other));
         ctx.set_request(request);
     }
 
@@ -289,7 +330,7 @@ async fn try_op(
         // first time, but will fail on subsequent iterations if the request body wasn't retryable.
         trace!("checking if context can be rewound for attempt #{i}");
         if let RewindResult::Impossible = ctx.rewind(cfg) {
-            debug!("request cannot be retried since the request body cannot be cloned");
+            warn!("retries were suppressed because the request body can't be replayed; use an in-memory body, `ByteStream::from_path`, or `SdkBody::retryable` to make it retryable");
             break;
         }
         // Track which attempt we're currently on.
@@ -300,6 +341,11 @@ async fn try_op(
             debug!("delaying for {delay:?}");
             sleep.await;
         }
+        // Every attempt takes a rate limit permit, retries included. Like backoff, waiting for it
+        // isn't included in the attempt timeout.
+        if let Some(rate_limiter) = cfg.load::<RateLimiter>().cloned() {
+            halt_on_err!([ctx] => rate_limiter.acquire(runtime_components, cfg).await.map_err(OrchestratorError::other));
+        }
         let attempt_timeout_config =
             MaybeTimeoutConfig::new(runtime_components, cfg, TimeoutKind::OperationAttempt);
         trace!(attempt_timeout_config = ?attempt_timeout_config);
@@ -341,7 +387,15 @@ async fn try_op(
     }
 }
 
-#[instrument(skip_all, level = "debug")]
+#[instrument(
+    skip_all,
+    level = "debug",
+    fields(
+        connection.reused = tracing::field::Empty,
+        connection.age = tracing::field::Empty,
+        connection.handshake_duration = tracing::field::Empty,
+    )
+)]
 async fn try_attempt(
     ctx: &mut InterceptorContext,
     cfg: &mut ConfigBag,
@@ -357,6 +411,14 @@ async fn try_attempt(
         read_before_signing(ctx, runtime_components, cfg);
     });
 
+    // Redirects are signed again for their target, so keep the request from before it was signed
+    let mut follow_redirects = cfg.load::<RedirectPolicy>().map(|policy| {
+        FollowRedirects::new(
+            policy.clone(),
+            ctx.request().expect("set during serialization"),
+        )
+    });
+
     halt_on_err!([ctx] => orchestrate_auth(ctx, runtime_components, cfg).await.map_err(OrchestratorError::other));
 
     run_interceptors!(halt_on_err: {
@@ -371,33 +433,35 @@ async fn try_attempt(
         return;
     }
 
-    // The connection consumes the request but we need to keep a copy of it
-    // within the interceptor context, so we clone it here.
-    ctx.enter_transmit_phase();
-    let response = halt_on_err!([ctx] => {
-        let request = ctx.take_request().expect("set during serialization");
-        trace!(request = ?request, "transmitting request");
-        let http_client = halt_on_err!([ctx] => runtime_components.http_client().ok_or_else(||
-            OrchestratorError::other("No HTTP client was available to send this request. \
-                Enable the `rustls` crate feature or configure a HTTP client to fix this.")
-        ));
-        let timeout_config = cfg.load::<TimeoutConfig>().expect("timeout config must be set");
-        let settings = {
-            let mut builder = HttpConnectorSettings::builder();
-            builder.set_connect_timeout(timeout_config.connect_timeout());
-            builder.set_read_timeout(timeout_config.read_timeout());
-            builder.build()
+    let response = loop {
+        // The connection consumes the request but we need to keep a copy of it
+        // within the interceptor context, so we clone it here.
+        ctx.enter_transmit_phase();
+        let response = halt_on_err!([ctx] => transmit(ctx, cfg, runtime_components).await);
+        let Some(follow_redirects) = follow_redirects.as_mut() else {
+            break response;
         };
-        let connector = http_client.http_connector(&settings, runtime_components);
-        let response_future = MaybeUploadThroughputCheckFuture::new(
-            cfg,
-            runtime_components,
-            connector.call(request),
-        );
-        response_future.await.map_err(OrchestratorError::connector)
-    });
+        match halt_on_err!([ctx] => follow_redirects.next_request(&response).map_err(OrchestratorError::other))
+        {
+            Some(request) => {
+                // Each hop is signed for its target and goes through the signing and transmit
+                // interceptors again, so that it gets the same headers, body wrappers and hooks as
+                // the original request. It already carries the changes of `modify_before_signing`.
+                ctx.enter_redirect(request);
+                run_interceptors!(halt_on_err: read_before_signing(ctx, runtime_components, cfg));
+                halt_on_err!([ctx] => orchestrate_auth(ctx, runtime_components, cfg).await.map_err(OrchestratorError::other));
+                run_interceptors!(halt_on_err: {
+                    read_after_signing(ctx, runtime_components, cfg);
+                    modify_before_transmit(ctx, runtime_components, cfg);
+                    read_before_transmit(ctx, runtime_components, cfg);
+                });
+            }
+            None => break response,
+        }
+    };
     trace!(response = ?response, "received response from service");
     ctx.set_response(response);
+    record_connection(cfg);
     ctx.enter_before_deserialization_phase();
 
     run_interceptors!(halt_on_err: {
@@ -407,6 +471,16 @@ async fn try_attempt(
     });
 
     ctx.enter_deserialization_phase();
+    // Headers are captured before deserialization so that they're available even if parsing fails
+    let captured_headers = cfg
+        .load::<CaptureResponseHeaders>()
+        .map(|capture| capture.capture(ctx.response().expect("set during transmit").headers()));
+    if let Some(captured_headers) = &captured_headers {
+        cfg.interceptor_state().store_put(captured_headers.clone());
+    }
+    let error_metadata_extras = cfg.load::<ErrorMetadataExtras>().cloned();
+    let lenient = cfg.load::<LenientDeserialization>().is_some();
+    let mut field_errors = None;
     let output_or_error = async {
         let response = ctx.response_mut().expect("set during transmit");
         let response_deserializer = cfg
@@ -414,7 +488,11 @@ async fn try_attempt(
             .expect("a request deserializer must be in the config bag");
         let maybe_deserialized = {
             let _span = debug_span!("deserialize_streaming").entered();
-            response_deserializer.deserialize_streaming(response)
+            with_deserialization_context(
+                captured_headers.as_ref(),
+                error_metadata_extras.as_ref(),
+                || response_deserializer.deserialize_streaming(response),
+            )
         };
         match maybe_deserialized {
             Some(output_or_error) => output_or_error,
@@ -425,19 +503,122 @@ async fn try_attempt(
                 .and_then(|_| {
                     let _span = debug_span!("deserialize_nonstreaming").entered();
                     log_response_body(response, cfg);
-                    response_deserializer.deserialize_nonstreaming(response)
+                    if let Some(output_or_error) =
+                        ser_de_override::deserialize_override(cfg, response)
+                    {
+                        return output_or_error;
+                    }
+                    let deserialize = || match cfg.load::<UnknownFieldReporting>() {
+                        Some(reporting) => {
+                            let (output_or_error, unknown_fields) = record_unknown_fields(|| {
+                                response_deserializer.deserialize_nonstreaming(response)
+                            });
+                            reporting.report(cfg, unknown_fields);
+                            output_or_error
+                        }
+                        None => response_deserializer.deserialize_nonstreaming(response),
+                    };
+                    with_deserialization_context(
+                        captured_headers.as_ref(),
+                        error_metadata_extras.as_ref(),
+                        || {
+                            if !lenient {
+                                return deserialize();
+                            }
+                            let (output_or_error, errors) = deserialize_leniently(deserialize);
+                            field_errors = Some(errors);
+                            output_or_error
+                        },
+                    )
                 }),
         }
     }
     .instrument(debug_span!("deserialization"))
     .await;
     trace!(output_or_error = ?output_or_error);
+    if let Some(field_errors) = field_errors {
+        for error in field_errors.errors() {
+            debug!(
+                path = %error.path(),
+                error = %DisplayErrorContext(error),
+                "dropped a response value that failed to parse"
+            );
+        }
+        cfg.interceptor_state().store_put(field_errors);
+    }
     ctx.set_output_or_error(output_or_error);
 
     ctx.enter_after_deserialization_phase();
     run_interceptors!(halt_on_err: read_after_deserialization(ctx, runtime_components, cfg));
 }
 
+async fn transmit(
+    ctx: &mut InterceptorContext,
+    cfg: &mut ConfigBag,
+    runtime_components: &RuntimeComponents,
+) -> Result<HttpResponse, OrchestratorError<Error>> {
+    let request = ctx.take_request().expect("set during serialization");
+    trace!(request = ?request, "transmitting request");
+    let http_client = runtime_components.http_client().ok_or_else(|| {
+        OrchestratorError::other(
+            "No HTTP client was available to send this request. \
+            Enable the `rustls` crate feature or configure a HTTP client to fix this.",
+        )
+    })?;
+    let timeout_config = cfg
+        .load::<TimeoutConfig>()
+        .expect("timeout config must be set");
+    let settings = {
+        let mut builder = HttpConnectorSettings::builder();
+        builder.set_connect_timeout(timeout_config.connect_timeout());
+        builder.set_read_timeout(timeout_config.read_timeout());
+        builder.build()
+    };
+    let connector = http_client.http_connector(&settings, runtime_components);
+    let response_future =
+        MaybeUploadThroughputCheckFuture::new(cfg, runtime_components, connector.call(request));
+    response_future.await.map_err(OrchestratorError::connector)
+}
+
+fn with_deserialization_context<T>(
+    captured_headers: Option<&CapturedHeaders>,
+    error_metadata_extras: Option<&ErrorMetadataExtras>,
+    f: impl FnOnce() -> T,
+) -> T {
+    let with_extras = || match error_metadata_extras {
+        Some(extras) => with_error_metadata_extras(extras, f),
+        None => f(),
+    };
+    match captured_headers {
+        Some(captured_headers) => with_captured_headers(captured_headers, with_extras),
+        None => with_extras(),
+    }
+}
+
+/// Records how the attempt used its connection on the attempt's span, as far as the HTTP client
+/// reported it through the [`CaptureSmithyConnection`].
+fn record_connection(cfg: &ConfigBag) {
+    let Some(connection) = cfg
+        .load::<CaptureSmithyConnection>()
+        .and_then(CaptureSmithyConnection::get)
+    else {
+        return;
+    };
+    let span = tracing::Span::current();
+    if let Some(reused) = connection.reused() {
+        span.record("connection.reused", reused);
+    }
+    if let Some(age) = connection.age() {
+        span.record("connection.age", tracing::field::debug(age));
+    }
+    if let Some(handshake_duration) = connection.handshake_duration() {
+        span.record(
+            "connection.handshake_duration",
+            tracing::field::debug(handshake_duration),
+        );
+    }
+}
+
 #[instrument(skip_all, level = "debug")]
 async fn finally_attempt(
     ctx: &mut InterceptorContext,
@@ -1345,4 +1526,119 @@ mod tests {
             .read_after_execution_called
             .load(Ordering::Relaxed));
     }
+
+    #[tokio::test]
+    async fn config_is_validated_once_per_client_and_for_overrides() {
+        use crate::client::config_validation::{
+            validate_client_config, ClientConfigValidation, ValidateOperationConfig,
+        };
+        use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep, Sleep};
+        use aws_smithy_runtime_api::client::config_validation::{
+            ConfigIssueCode, ConfigValidationReport,
+        };
+        use aws_smithy_runtime_api::client::result::SdkError;
+        use aws_smithy_runtime_api::client::runtime_components::SharedConfigValidator;
+        use aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin;
+        use aws_smithy_types::retry::RetryConfig;
+        use std::sync::atomic::AtomicUsize;
+        use std::time::Duration;
+
+        #[derive(Debug)]
+        struct NoSleep;
+        impl AsyncSleep for NoSleep {
+            fn sleep(&self, _duration: Duration) -> Sleep {
+                Sleep::new(std::future::ready(()))
+            }
+        }
+
+        static VALIDATIONS: AtomicUsize = AtomicUsize::new(0);
+        fn count_validations(_: &RuntimeComponentsBuilder, _: &ConfigBag) -> Result<(), BoxError> {
+            VALIDATIONS.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+
+        let http_client = NeverClient::new();
+        // What a generated client does when it's created
+        let client_plugins = |retry_config: RetryConfig| {
+            let mut layer = Layer::new("client");
+            layer.store_put(retry_config);
+            let plugins = RuntimePlugins::new()
+                .with_client_plugin(
+                    StaticRuntimePlugin::new()
+                        .with_config(layer.freeze())
+                        .with_runtime_components(
+                            RuntimeComponentsBuilder::new("client")
+                                .with_http_client(Some(http_client.clone()))
+                                .with_config_validator(
+                                    SharedConfigValidator::base_client_config_fn(count_validations),
+                                ),
+                        ),
+                )
+                .with_operation_plugin(TestOperationRuntimePlugin::new())
+                .with_operation_plugin(NoAuthRuntimePlugin::new());
+            let mut report = ConfigValidationReport::new();
+            validate_client_config(&plugins, &mut report);
+            let mut layer = Layer::new("validation");
+            layer.store_put(ClientConfigValidation::new(report));
+            plugins.with_client_plugin(StaticRuntimePlugin::new().with_config(layer.freeze()))
+        };
+        let config_override = |plugins: RuntimePlugins, sleep_impl: Option<SharedAsyncSleep>| {
+            let mut layer = Layer::new("override");
+            layer.store_put(ValidateOperationConfig::new());
+            plugins.with_operation_plugin(
+                StaticRuntimePlugin::new()
+                    .with_config(layer.freeze())
+                    .with_runtime_components(
+                        RuntimeComponentsBuilder::new("override").with_sleep_impl(sleep_impl),
+                    ),
+            )
+        };
+        // Stopping before transmit, like presigning does, still checks the config
+        let invoke = |plugins: RuntimePlugins| async move {
+            invoke_with_stop_point(
+                "test",
+                "test",
+                Input::doesnt_matter(),
+                &plugins,
+                StopPoint::BeforeTransmit,
+            )
+            .await
+        };
+        let codes = |err: SdkError<_, _>| match &err {
+            SdkError::ConstructionFailure(_) => std::error::Error::source(&err)
+                .and_then(|source| source.downcast_ref::<ConfigValidationReport>())
+                .expect("the source is the validation report")
+                .codes()
+                .collect::<Vec<_>>(),
+            _ => panic!("expected a construction failure, got {err:?}"),
+        };
+
+        // A valid client config is validated once, not on every invocation
+        let valid = client_plugins(RetryConfig::disabled());
+        assert_eq!(1, VALIDATIONS.load(Ordering::Relaxed));
+        invoke(valid.clone()).await.expect("the config is valid");
+        invoke(valid.clone()).await.expect("the config is valid");
+        assert_eq!(1, VALIDATIONS.load(Ordering::Relaxed));
+
+        // The report of an invalid client config fails every invocation
+        let invalid = client_plugins(RetryConfig::standard());
+        assert_eq!(
+            vec![ConfigIssueCode::RetriesEnabledWithoutSleep],
+            codes(invoke(invalid.clone()).await.expect_err("no sleep impl"))
+        );
+
+        // The merged config of an invocation with a config override is validated
+        let mut layer = Layer::new("retries");
+        layer.store_put(RetryConfig::standard());
+        let broken = config_override(valid, None)
+            .with_operation_plugin(StaticRuntimePlugin::new().with_config(layer.freeze()));
+        assert_eq!(
+            vec![ConfigIssueCode::RetriesEnabledWithoutSleep],
+            codes(invoke(broken).await.expect_err("no sleep impl"))
+        );
+        let fixed = config_override(invalid, Some(SharedAsyncSleep::new(NoSleep)));
+        invoke(fixed).await.expect("the override fixes the config");
+        assert_eq!(2, VALIDATIONS.load(Ordering::Relaxed));
+        assert_eq!(http_client.num_calls(), 0);
+    }
 }
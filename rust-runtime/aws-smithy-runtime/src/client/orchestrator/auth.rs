@@ -8,7 +8,7 @@ use crate::client::identity::IdentityCache;
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::auth::{
     AuthScheme, AuthSchemeEndpointConfig, AuthSchemeId, AuthSchemeOptionResolverParams,
-    ResolveAuthSchemeOptions,
+    PendingSigningWork, ResolveAuthSchemeOptions,
 };
 use aws_smithy_runtime_api::client::identity::ResolveIdentity;
 use aws_smithy_runtime_api::client::identity::{IdentityCacheLocation, ResolveCachedIdentity};
@@ -162,6 +162,22 @@ pub(super) async fn orchestrate_auth(
                             .await?;
                         trace!(identity = ?identity, "resolved identity");
 
+                        // Work started ahead of time overlaps with identity resolution, and
+                        // must be done before the signer, which can't wait for it, runs
+                        let pending_work: Vec<_> = cfg
+                            .load::<PendingSigningWork>()
+                            .map(PendingSigningWork::wait)
+                            .collect();
+                        if !pending_work.is_empty() {
+                            trace!(
+                                count = pending_work.len(),
+                                "waiting for pending signing work"
+                            );
+                            for work in pending_work {
+                                work.await;
+                            }
+                        }
+
                         trace!("signing request");
                         let request = ctx.request_mut().expect("set during serialization");
                         signer.sign_http_request(
@@ -390,6 +406,108 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn every_pending_signing_work_is_awaited_before_signing() {
+        use aws_smithy_runtime_api::client::auth::PendingSigningWork;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        #[derive(Debug)]
+        struct TestIdentityResolver;
+        impl ResolveIdentity for TestIdentityResolver {
+            fn resolve_identity<'a>(
+                &'a self,
+                _runtime_components: &'a RuntimeComponents,
+                _config_bag: &'a ConfigBag,
+            ) -> IdentityFuture<'a> {
+                IdentityFuture::ready(Ok(Identity::new("doesntmatter", None)))
+            }
+        }
+
+        #[derive(Debug)]
+        struct TestSigner {
+            done: Arc<AtomicUsize>,
+        }
+        impl Sign for TestSigner {
+            fn sign_http_request(
+                &self,
+                _request: &mut HttpRequest,
+                _identity: &Identity,
+                _auth_scheme_endpoint_config: AuthSchemeEndpointConfig<'_>,
+                _runtime_components: &RuntimeComponents,
+                _config_bag: &ConfigBag,
+            ) -> Result<(), BoxError> {
+                assert_eq!(2, self.done.load(Ordering::SeqCst));
+                Ok(())
+            }
+        }
+
+        const TEST_SCHEME_ID: AuthSchemeId = AuthSchemeId::new("test-scheme");
+
+        #[derive(Debug)]
+        struct TestAuthScheme {
+            signer: TestSigner,
+        }
+        impl AuthScheme for TestAuthScheme {
+            fn scheme_id(&self) -> AuthSchemeId {
+                TEST_SCHEME_ID
+            }
+
+            fn identity_resolver(
+                &self,
+                identity_resolvers: &dyn GetIdentityResolver,
+            ) -> Option<SharedIdentityResolver> {
+                identity_resolvers.identity_resolver(self.scheme_id())
+            }
+
+            fn signer(&self) -> &dyn Sign {
+                &self.signer
+            }
+        }
+
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.enter_serialization_phase();
+        ctx.set_request(HttpRequest::empty());
+        let _ = ctx.take_input();
+        ctx.enter_before_transmit_phase();
+
+        let done = Arc::new(AtomicUsize::new(0));
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_auth_scheme(SharedAuthScheme::new(TestAuthScheme {
+                signer: TestSigner { done: done.clone() },
+            }))
+            .with_auth_scheme_option_resolver(Some(SharedAuthSchemeOptionResolver::new(
+                StaticAuthSchemeOptionResolver::new(vec![TEST_SCHEME_ID]),
+            )))
+            .with_identity_resolver(
+                TEST_SCHEME_ID,
+                SharedIdentityResolver::new(TestIdentityResolver),
+            )
+            .build()
+            .unwrap();
+
+        let mut layer: Layer = Layer::new("test");
+        layer.store_put(AuthSchemeOptionResolverParams::new("doesntmatter"));
+        layer.store_put(Endpoint::builder().url("dontcare").build());
+        // Two producers each store their own work, and neither replaces the other's
+        for _ in 0..2 {
+            let done = done.clone();
+            layer.store_append(PendingSigningWork::new(move || {
+                let done = done.clone();
+                async move {
+                    tokio::task::yield_now().await;
+                    done.fetch_add(1, Ordering::SeqCst);
+                }
+            }));
+        }
+        let cfg = ConfigBag::of_layers(vec![layer]);
+
+        orchestrate_auth(&mut ctx, &runtime_components, &cfg)
+            .await
+            .expect("success");
+        assert_eq!(2, done.load(Ordering::SeqCst));
+    }
+
     #[cfg(feature = "http-auth")]
     #[tokio::test]
     async fn select_best_scheme_for_available_identity_resolvers() {
@@ -3,15 +3,19 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use aws_smithy_async::future::timeout::Timeout;
+use aws_smithy_async::rt::sleep::AsyncSleep;
 use aws_smithy_runtime_api::client::endpoint::{
-    error::ResolveEndpointError, EndpointFuture, EndpointResolverParams, ResolveEndpoint,
+    error::ResolveEndpointError, EndpointFuture, EndpointParamBindings, EndpointResolverParams,
+    PreflightTlsHostnameCheck, ResolveEndpoint,
 };
 use aws_smithy_runtime_api::client::interceptors::context::InterceptorContext;
 use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_runtime_api::{box_error::BoxError, client::endpoint::EndpointPrefix};
-use aws_smithy_types::config_bag::ConfigBag;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
 use aws_smithy_types::endpoint::Endpoint;
+use aws_smithy_types::timeout::TimeoutConfig;
 use http_02x::header::HeaderName;
 use http_02x::uri::PathAndQuery;
 use http_02x::{HeaderValue, Uri};
@@ -67,6 +71,91 @@ impl From<StaticUriEndpointResolverParams> for EndpointResolverParams {
     }
 }
 
+/// An endpoint resolved before serialization, used by all attempts.
+#[derive(Clone, Debug)]
+struct EndpointResolvedBeforeSerialization(Endpoint);
+
+impl Storable for EndpointResolvedBeforeSerialization {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Resolves the endpoint before serialization if the endpoint resolver needs the operation input,
+/// or if the [`PreflightTlsHostnameCheck`] is enabled and the operation has an endpoint prefix.
+///
+/// Resolution is bounded by the endpoint resolution timeout, or the operation attempt timeout if
+/// that isn't set.
+pub(super) async fn resolve_endpoint_before_serialization(
+    ctx: &InterceptorContext,
+    runtime_components: &RuntimeComponents,
+    cfg: &mut ConfigBag,
+) -> Result<(), BoxError> {
+    let endpoint_resolver = runtime_components.endpoint_resolver();
+    let endpoint_prefix = cfg.load::<EndpointPrefix>().filter(|_| {
+        cfg.load::<PreflightTlsHostnameCheck>()
+            .map(PreflightTlsHostnameCheck::is_enabled)
+            .unwrap_or_default()
+    });
+    if !endpoint_resolver.resolves_with_input() && endpoint_prefix.is_none() {
+        return Ok(());
+    }
+    trace!("resolving endpoint before serialization");
+
+    let endpoint = {
+        let params = cfg
+            .load::<EndpointResolverParams>()
+            .expect("endpoint resolver params must be set");
+        let input = ctx.input().expect("input is set before serialization");
+        let resolve = endpoint_resolver.resolve_endpoint_with_input(params, input);
+        let timeout = cfg.load::<TimeoutConfig>().and_then(|timeout_config| {
+            timeout_config
+                .endpoint_resolution_timeout()
+                .or_else(|| timeout_config.operation_attempt_timeout())
+        });
+        match (timeout, runtime_components.sleep_impl()) {
+            (Some(timeout), Some(sleep_impl)) => Timeout::new(resolve, sleep_impl.sleep(timeout))
+                .await
+                .map_err(|_| {
+                    ResolveEndpointError::message(format!(
+                        "endpoint resolution timed out after {timeout:?}"
+                    ))
+                })??,
+            _ => resolve.await?,
+        }
+    };
+    tracing::debug!("resolved endpoint {:?} before serialization", endpoint);
+    if let Some(endpoint_prefix) = endpoint_prefix {
+        check_tls_hostname(&endpoint, endpoint_prefix)?;
+    }
+    cfg.interceptor_state()
+        .store_put(EndpointResolvedBeforeSerialization(endpoint));
+    Ok(())
+}
+
+/// Fails if the host prefix adds more than one label to the host of an `https` endpoint, since
+/// a wildcard TLS certificate for the endpoint's subdomains only covers a single label.
+fn check_tls_hostname(
+    endpoint: &Endpoint,
+    endpoint_prefix: &EndpointPrefix,
+) -> Result<(), BoxError> {
+    let uri = endpoint.url().parse::<Uri>()?;
+    if uri.scheme_str() != Some("https") {
+        return Ok(());
+    }
+    let prefix = endpoint_prefix.as_str().trim_end_matches('.');
+    let added_labels = prefix.split('.').count();
+    if added_labels > 1 {
+        let host = uri.host().unwrap_or_default();
+        return Err(ResolveEndpointError::message(format!(
+            "the host `{prefix}.{host}` can't be covered by a wildcard TLS certificate for \
+            `*.{host}` because the host prefix `{prefix}` contains dots, so TLS verification \
+            would fail when connecting. Use a name without dots (for example, a bucket name), or \
+            use path-style addressing if the service supports it"
+        ))
+        .into());
+    }
+    Ok(())
+}
+
 pub(super) async fn orchestrate_endpoint(
     ctx: &mut InterceptorContext,
     runtime_components: &RuntimeComponents,
@@ -78,13 +167,23 @@ pub(super) async fn orchestrate_endpoint(
         .load::<EndpointResolverParams>()
         .expect("endpoint resolver params must be set");
     let endpoint_prefix = cfg.load::<EndpointPrefix>();
-    tracing::debug!(endpoint_params = ?params, endpoint_prefix = ?endpoint_prefix, "resolving endpoint");
+    tracing::debug!(
+        endpoint_params = ?params,
+        endpoint_param_bindings = ?cfg.load::<EndpointParamBindings>(),
+        endpoint_prefix = ?endpoint_prefix,
+        "resolving endpoint"
+    );
     let request = ctx.request_mut().expect("set during serialization");
 
-    let endpoint = runtime_components
-        .endpoint_resolver()
-        .resolve_endpoint(params)
-        .await?;
+    let endpoint = match cfg.load::<EndpointResolvedBeforeSerialization>() {
+        Some(resolved) => resolved.0.clone(),
+        None => {
+            runtime_components
+                .endpoint_resolver()
+                .resolve_endpoint(params)
+                .await?
+        }
+    };
     tracing::debug!("will use endpoint {:?}", endpoint);
     apply_endpoint(request, &endpoint, endpoint_prefix)?;
 
@@ -21,8 +21,8 @@ use aws_smithy_runtime_api::client::http::HttpClient;
 use aws_smithy_runtime_api::client::identity::SharedIdentityResolver;
 use aws_smithy_runtime_api::client::interceptors::context::{Error, Input, Output};
 use aws_smithy_runtime_api::client::interceptors::Intercept;
-use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
 use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, OrchestratorError};
+use aws_smithy_runtime_api::client::orchestrator::{HttpResponse, Metadata};
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_smithy_runtime_api::client::retries::classifiers::ClassifyRetry;
 use aws_smithy_runtime_api::client::retries::SharedRetryStrategy;
@@ -39,10 +39,11 @@ use aws_smithy_runtime_api::{
 };
 use aws_smithy_types::config_bag::{ConfigBag, Layer};
 use aws_smithy_types::retry::RetryConfig;
-use aws_smithy_types::timeout::TimeoutConfig;
+use aws_smithy_types::timeout::{TimeoutConfig, TimeoutConfigBuilder};
 use std::borrow::Cow;
 use std::fmt;
 use std::marker::PhantomData;
+use std::time::Duration;
 
 struct FnSerializer<F, I> {
     f: F,
@@ -245,6 +246,35 @@ impl<I, O, E> OperationBuilder<I, O, E> {
         self
     }
 
+    /// Bounds how long the whole operation may take, retries and the backoff between them included.
+    ///
+    /// If the operation takes longer, it fails with [`SdkError::TimeoutError`].
+    /// Other timeouts from [`timeout_config`](Self::timeout_config) are kept.
+    pub fn operation_timeout(self, operation_timeout: Duration) -> Self {
+        self.update_timeout_config(|builder| builder.operation_timeout(operation_timeout))
+    }
+
+    /// Bounds how long a single attempt may take.
+    ///
+    /// An attempt that takes longer fails with a timeout error, which retry classifiers
+    /// can consider retryable. Other timeouts from [`timeout_config`](Self::timeout_config) are kept.
+    pub fn attempt_timeout(self, attempt_timeout: Duration) -> Self {
+        self.update_timeout_config(|builder| builder.operation_attempt_timeout(attempt_timeout))
+    }
+
+    fn update_timeout_config(
+        mut self,
+        update: impl FnOnce(TimeoutConfigBuilder) -> TimeoutConfigBuilder,
+    ) -> Self {
+        let builder = self
+            .config
+            .load::<TimeoutConfig>()
+            .map(TimeoutConfig::to_builder)
+            .unwrap_or_default();
+        self.config.store_put(update(builder).build());
+        self
+    }
+
     /// Disables auth for the operation.
     pub fn no_auth(mut self) -> Self {
         self.config
@@ -374,9 +404,11 @@ impl<I, O, E> OperationBuilder<I, O, E> {
     }
 
     /// Creates an `Operation` from the builder.
-    pub fn build(self) -> Operation<I, O, E> {
+    pub fn build(mut self) -> Operation<I, O, E> {
         let service_name = self.service_name.expect("service_name required");
         let operation_name = self.operation_name.expect("operation_name required");
+        self.config
+            .store_put(Metadata::new(operation_name.clone(), service_name.clone()));
 
         let mut runtime_plugins = RuntimePlugins::new()
             .with_client_plugins(default_plugins(
@@ -440,8 +472,11 @@ impl<I, O, E> OperationBuilder<I, O, E> {
 #[cfg(all(test, feature = "test-util"))]
 mod tests {
     use super::*;
-    use crate::client::http::test_util::{capture_request, ReplayEvent, StaticReplayClient};
-    use crate::client::retries::classifiers::HttpStatusCodeClassifier;
+    use crate::client::http::test_util::{
+        capture_request, NeverClient, ReplayEvent, StaticReplayClient,
+    };
+    use crate::client::retries::classifiers::{HttpStatusCodeClassifier, TransientErrorClassifier};
+    use aws_smithy_async::assert_elapsed;
     use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
     use aws_smithy_runtime_api::client::result::ConnectorError;
     use aws_smithy_types::body::SdkBody;
@@ -540,4 +575,121 @@ mod tests {
 
         connector.assert_requests_match(&[]);
     }
+
+    fn never_responding_operation(
+        http_client: NeverClient,
+        max_attempts: u32,
+    ) -> OperationBuilder<String, String, Infallible> {
+        Operation::builder()
+            .service_name("test")
+            .operation_name("test")
+            .http_client(http_client)
+            .endpoint_url("http://localhost:1234")
+            .no_auth()
+            // No backoff, so that the time taken only depends on the timeouts
+            .standard_retry(
+                &RetryConfig::standard()
+                    .with_max_attempts(max_attempts)
+                    .with_initial_backoff(Duration::ZERO),
+            )
+            .retry_classifier(TransientErrorClassifier::<Infallible>::new())
+            .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
+            .serializer(|input: String| Ok(HttpRequest::new(SdkBody::from(input.as_bytes()))))
+            .deserializer::<_, Infallible>(|_| unreachable!("the client never responds"))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn attempt_timeout_is_retried() {
+        let http_client = NeverClient::new();
+        let operation = never_responding_operation(http_client.clone(), 3)
+            .attempt_timeout(Duration::from_secs(2))
+            .build();
+
+        let started = tokio::time::Instant::now();
+        let err = operation
+            .invoke("what are you?".to_string())
+            .await
+            .expect_err("the client never responds");
+
+        assert!(matches!(err, SdkError::TimeoutError(_)), "{err:?}");
+        assert_eq!(3, http_client.num_calls());
+        assert_elapsed!(started, Duration::from_secs(6));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn operation_timeout_bounds_every_attempt() {
+        let http_client = NeverClient::new();
+        let operation = never_responding_operation(http_client.clone(), 10)
+            .attempt_timeout(Duration::from_secs(2))
+            .operation_timeout(Duration::from_secs(5))
+            .build();
+
+        let started = tokio::time::Instant::now();
+        let err = operation
+            .invoke("what are you?".to_string())
+            .await
+            .expect_err("the client never responds");
+
+        assert!(matches!(err, SdkError::TimeoutError(_)), "{err:?}");
+        // The third attempt is cut short by the operation timeout
+        assert_eq!(3, http_client.num_calls());
+        assert_elapsed!(started, Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn connection_metadata_is_available_from_service_errors() {
+        use crate::client::http::test_util::infallible_client_fn;
+        use aws_smithy_runtime_api::client::connection::ConnectionMetadata;
+        use std::net::SocketAddr;
+
+        #[derive(Debug)]
+        struct TeapotError;
+
+        impl fmt::Display for TeapotError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "I'm a teapot!")
+            }
+        }
+
+        impl std::error::Error for TeapotError {}
+
+        let remote_addr: SocketAddr = "192.0.2.1:443".parse().unwrap();
+        // Test HTTP clients can fabricate the metadata that a real HTTP client would capture
+        let http_client = infallible_client_fn(move |_| {
+            let mut response = http_02x::Response::builder()
+                .status(418)
+                .body(SdkBody::empty())
+                .unwrap();
+            response.extensions_mut().insert(
+                ConnectionMetadata::builder()
+                    .proxied(false)
+                    .remote_addr(remote_addr)
+                    .poison_fn(|| {})
+                    .build(),
+            );
+            response
+        });
+        let operation = Operation::builder()
+            .service_name("test")
+            .operation_name("test")
+            .http_client(http_client)
+            .endpoint_url("http://localhost:1234")
+            .no_auth()
+            .no_retry()
+            .timeout_config(TimeoutConfig::disabled())
+            .serializer(|input: String| Ok(HttpRequest::new(SdkBody::from(input.as_bytes()))))
+            .deserializer::<(), _>(|_| Err(OrchestratorError::operation(TeapotError)))
+            .build();
+
+        let err = operation
+            .invoke("what are you?".to_string())
+            .await
+            .expect_err("the service responds with an error");
+        assert!(matches!(err, SdkError::ServiceError(_)), "{err:?}");
+        assert_eq!(
+            Some(remote_addr),
+            err.connection_metadata()
+                .and_then(ConnectionMetadata::remote_addr)
+        );
+    }
 }
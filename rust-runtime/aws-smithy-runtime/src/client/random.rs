@@ -0,0 +1,141 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::client::random::RandomSource;
+use std::sync::Mutex;
+
+/// The environment variable that [`SeededRandomSource::for_tests`] reads its seed from.
+pub const RANDOM_SEED_ENV_VAR: &str = "SMITHY_RANDOM_SEED";
+
+/// The seed [`SeededRandomSource::for_tests`] uses if [`RANDOM_SEED_ENV_VAR`] isn't set.
+const DEFAULT_TEST_SEED: u64 = 0x5eed;
+
+/// The default random source, backed by the operating system's cryptographically secure random
+/// number generator.
+///
+/// Idempotency tokens and invocation IDs are drawn from this source, so they can't be predicted
+/// from earlier values.
+///
+/// `wasm32-unknown-unknown` has no operating system generator, so on that target this falls back
+/// to a thread-local generator that isn't cryptographically secure. Configure a random source
+/// backed by the host's generator there if unpredictable values are required.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct DefaultRandomSource;
+
+impl DefaultRandomSource {
+    /// Creates a new `DefaultRandomSource`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RandomSource for DefaultRandomSource {
+    /// # Panics
+    /// Panics if the operating system's random number generator fails.
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        getrandom::getrandom(dest).expect("the OS random number generator failed")
+    }
+
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        fastrand::fill(dest)
+    }
+
+    fn next_u64(&self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+}
+
+/// A deterministic random source that produces the same sequence of values for the same seed.
+///
+/// Use this to reproduce retry jitter, invocation IDs, and idempotency tokens exactly.
+#[derive(Debug)]
+pub struct SeededRandomSource {
+    seed: u64,
+    rng: Mutex<fastrand::Rng>,
+}
+
+impl SeededRandomSource {
+    /// Creates a new `SeededRandomSource` with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: Mutex::new(fastrand::Rng::with_seed(seed)),
+        }
+    }
+
+    /// Creates a `SeededRandomSource` for tests.
+    ///
+    /// The seed is read from the `SMITHY_RANDOM_SEED` environment variable if it is set, and a
+    /// fixed seed is used otherwise. The seed is logged so that a failing test run can be replayed
+    /// by setting the environment variable to it.
+    ///
+    /// # Panics
+    /// Panics if `SMITHY_RANDOM_SEED` is set but isn't a valid `u64`.
+    pub fn for_tests() -> Self {
+        let seed = match std::env::var(RANDOM_SEED_ENV_VAR) {
+            Ok(seed) => seed
+                .parse()
+                .unwrap_or_else(|_| panic!("{RANDOM_SEED_ENV_VAR} must be a u64, got `{seed}`")),
+            Err(_) => DEFAULT_TEST_SEED,
+        };
+        tracing::info!(
+            seed,
+            "using a seeded random source; set {RANDOM_SEED_ENV_VAR}={seed} to replay this run"
+        );
+        Self::new(seed)
+    }
+
+    /// Returns the seed of this random source.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl RandomSource for SeededRandomSource {
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        self.rng.lock().unwrap().fill(dest)
+    }
+
+    fn next_u64(&self) -> u64 {
+        self.rng.lock().unwrap().u64(..)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(source: &dyn RandomSource) -> (Vec<u64>, [u8; 16]) {
+        let numbers = (0..5).map(|_| source.next_u64()).collect();
+        let mut bytes = [0; 16];
+        source.fill_bytes(&mut bytes);
+        (numbers, bytes)
+    }
+
+    #[test]
+    fn same_seed_produces_same_values() {
+        assert_eq!(
+            values(&SeededRandomSource::new(42)),
+            values(&SeededRandomSource::new(42))
+        );
+        assert_ne!(
+            values(&SeededRandomSource::new(42)),
+            values(&SeededRandomSource::new(43))
+        );
+    }
+
+    #[test]
+    fn default_source_is_not_deterministic() {
+        assert_ne!(
+            values(&DefaultRandomSource::new()),
+            values(&DefaultRandomSource::new())
+        );
+    }
+}
@@ -0,0 +1,608 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::byte_stream::ByteStream;
+use bytes::{Buf, Bytes, BytesMut};
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::io;
+use std::ops::RangeInclusive;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll};
+use tokio::io::AsyncWrite;
+use tracing::debug;
+
+const DEFAULT_PART_SIZE: u64 = 8 * 1024 * 1024;
+const DEFAULT_CONCURRENCY: usize = 8;
+const DEFAULT_MAX_PART_ATTEMPTS: u32 = 3;
+
+type FetchFuture = Pin<Box<dyn Future<Output = Result<ByteStream, BoxError>> + Send>>;
+type Fetch = Arc<dyn Fn(RangeInclusive<u64>) -> FetchFuture + Send + Sync>;
+type PartFuture = Pin<Box<dyn Future<Output = Result<Bytes, RangedDownloadError>> + Send>>;
+
+/// Downloads an object of a known size with concurrent ranged requests, and reassembles it in
+/// order.
+///
+/// The object is split into parts of [`part_size`](RangedDownload::part_size) bytes, and each
+/// part is fetched by calling `fetch` with its (inclusive) byte range. Up to
+/// [`concurrency`](RangedDownload::concurrency) parts are fetched at once, and a part is only
+/// fetched once there's room for it, so at most `concurrency × part_size` bytes are buffered
+/// before they are consumed. The downloaded bytes are either read as a [`ByteStream`] with
+/// [`into_byte_stream`](RangedDownload::into_byte_stream), or written to an [`AsyncWrite`] with
+/// [`write_to`](RangedDownload::write_to).
+///
+/// `fetch` is usually an operation call, such as a `GetObject` with a `Range` header, and the
+/// total size is usually discovered with a `HeadObject` beforehand. Errors returned by `fetch`
+/// fail the download, since the operation's retry strategy has already retried them. If a body
+/// fails or ends early while it's read, the rest of the part is fetched again, up to
+/// [`max_part_attempts`](RangedDownload::max_part_attempts) times.
+///
+/// ```no_run
+/// use aws_smithy_runtime::client::ranged_download::RangedDownload;
+/// use aws_smithy_runtime_api::box_error::BoxError;
+/// use aws_smithy_types::byte_stream::ByteStream;
+/// use std::ops::RangeInclusive;
+///
+/// # async fn fetch(_: RangeInclusive<u64>) -> Result<ByteStream, BoxError> { unimplemented!() }
+/// # async fn example(total_size: u64) -> Result<(), Box<dyn std::error::Error>> {
+/// let download = RangedDownload::new(total_size, |range| fetch(range))
+///     .part_size(16 * 1024 * 1024)
+///     .concurrency(4);
+/// let mut file = tokio::fs::File::create("object").await?;
+/// download.write_to(&mut file).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct RangedDownload {
+    total_size: u64,
+    fetch: Fetch,
+    part_size: u64,
+    concurrency: usize,
+    max_part_attempts: u32,
+}
+
+impl fmt::Debug for RangedDownload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RangedDownload")
+            .field("total_size", &self.total_size)
+            .field("part_size", &self.part_size)
+            .field("concurrency", &self.concurrency)
+            .field("max_part_attempts", &self.max_part_attempts)
+            .finish()
+    }
+}
+
+impl RangedDownload {
+    /// Creates a download of `total_size` bytes, where `fetch` fetches the given range of them.
+    ///
+    /// By default, parts are 8 MiB, and 8 parts are fetched at once.
+    pub fn new<F, Fut>(total_size: u64, fetch: F) -> Self
+    where
+        F: Fn(RangeInclusive<u64>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<ByteStream, BoxError>> + Send + 'static,
+    {
+        Self {
+            total_size,
+            fetch: Arc::new(move |range| Box::pin(fetch(range))),
+            part_size: DEFAULT_PART_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+            max_part_attempts: DEFAULT_MAX_PART_ATTEMPTS,
+        }
+    }
+
+    /// Sets the size of the parts, in bytes. The last part may be smaller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `part_size` is zero.
+    pub fn part_size(mut self, part_size: u64) -> Self {
+        assert!(part_size > 0, "part_size must be at least 1");
+        self.part_size = part_size;
+        self
+    }
+
+    /// Sets the number of parts that are fetched or buffered at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `concurrency` is zero.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        assert!(concurrency > 0, "concurrency must be at least 1");
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Sets the number of times a part is fetched when its body fails while it's read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_part_attempts` is zero.
+    pub fn max_part_attempts(mut self, max_part_attempts: u32) -> Self {
+        assert!(
+            max_part_attempts > 0,
+            "max_part_attempts must be at least 1"
+        );
+        self.max_part_attempts = max_part_attempts;
+        self
+    }
+
+    /// Returns a [`ByteStream`] that yields the downloaded bytes in order.
+    ///
+    /// Parts are only fetched while the stream is polled. If a part fails, the stream yields a
+    /// [`RangedDownloadError`] and ends.
+    pub fn into_byte_stream(self) -> ByteStream {
+        ByteStream::new(SdkBody::from_body_0_4(PartsBody {
+            // `Parts` isn't `Sync`, but the body is only polled through `&mut`, so the lock is
+            // never contended
+            parts: Mutex::new(Parts::new(self)),
+        }))
+    }
+
+    /// Writes the downloaded bytes to `writer` in order, and returns the number of bytes written.
+    ///
+    /// Parts keep being fetched while `writer` is busy.
+    pub async fn write_to<W>(self, mut writer: W) -> Result<u64, RangedDownloadError>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut parts = Parts::new(self);
+        let mut written = 0;
+        while let Some(part) = poll_fn(|cx| parts.poll_next_part(cx)).await {
+            let mut part = part?;
+            while part.has_remaining() {
+                let n = poll_fn(|cx| {
+                    parts.poll_progress(cx);
+                    Pin::new(&mut writer).poll_write(cx, &part)
+                })
+                .await
+                .map_err(RangedDownloadError::write)?;
+                if n == 0 {
+                    return Err(RangedDownloadError::write(io::ErrorKind::WriteZero.into()));
+                }
+                part.advance(n);
+                written += n as u64;
+            }
+        }
+        poll_fn(|cx| Pin::new(&mut writer).poll_flush(cx))
+            .await
+            .map_err(RangedDownloadError::write)?;
+        Ok(written)
+    }
+}
+
+enum Part {
+    InFlight(PartFuture),
+    Done(Result<Bytes, RangedDownloadError>),
+}
+
+/// The parts that are being fetched, in order.
+struct Parts {
+    download: RangedDownload,
+    next_offset: u64,
+    remaining: u64,
+    parts: VecDeque<Part>,
+    failed: bool,
+}
+
+impl Parts {
+    fn new(download: RangedDownload) -> Self {
+        Self {
+            remaining: download.total_size,
+            download,
+            next_offset: 0,
+            parts: VecDeque::new(),
+            failed: false,
+        }
+    }
+
+    /// Starts fetching parts until `concurrency` parts are in flight or buffered, and polls the
+    /// ones in flight.
+    fn poll_progress(&mut self, cx: &mut Context<'_>) {
+        if self.failed {
+            return;
+        }
+        while self.parts.len() < self.download.concurrency
+            && self.next_offset < self.download.total_size
+        {
+            let end = (self.next_offset + self.download.part_size).min(self.download.total_size);
+            let range = self.next_offset..=end - 1;
+            self.next_offset = end;
+            self.parts.push_back(Part::InFlight(Box::pin(fetch_part(
+                self.download.fetch.clone(),
+                range,
+                self.download.max_part_attempts,
+            ))));
+        }
+        for part in self.parts.iter_mut() {
+            if let Part::InFlight(future) = part {
+                if let Poll::Ready(result) = future.as_mut().poll(cx) {
+                    *part = Part::Done(result);
+                }
+            }
+        }
+    }
+
+    fn poll_next_part(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, RangedDownloadError>>> {
+        self.poll_progress(cx);
+        match self.parts.front() {
+            None => Poll::Ready(None),
+            Some(Part::InFlight(_)) => Poll::Pending,
+            Some(Part::Done(_)) => {
+                let Some(Part::Done(result)) = self.parts.pop_front() else {
+                    unreachable!("checked above")
+                };
+                match &result {
+                    Ok(bytes) => self.remaining -= bytes.len() as u64,
+                    Err(_) => {
+                        // Stop fetching; the download can't be completed
+                        self.failed = true;
+                        self.parts.clear();
+                    }
+                }
+                Poll::Ready(Some(result))
+            }
+        }
+    }
+}
+
+/// Fetches the bytes of `range`, fetching the rest of them again if the body fails.
+async fn fetch_part(
+    fetch: Fetch,
+    range: RangeInclusive<u64>,
+    max_attempts: u32,
+) -> Result<Bytes, RangedDownloadError> {
+    let (start, end) = (*range.start(), *range.end());
+    let expected = end - start + 1;
+    let mut bytes = BytesMut::with_capacity(expected as usize);
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let mut body = fetch(start + bytes.len() as u64..=end)
+            .await
+            .map_err(|source| RangedDownloadError::part(range.clone(), source))?;
+        let body_error = loop {
+            match body.next().await {
+                Some(Ok(chunk)) => {
+                    if bytes.len() as u64 + chunk.len() as u64 > expected {
+                        return Err(RangedDownloadError::part(
+                            range,
+                            "the response contained more bytes than were requested".into(),
+                        ));
+                    }
+                    bytes.extend_from_slice(&chunk);
+                }
+                Some(Err(err)) => break err.into(),
+                None if bytes.len() as u64 == expected => return Ok(bytes.freeze()),
+                None => {
+                    break format!(
+                        "the response ended after {} of {} bytes",
+                        bytes.len(),
+                        expected
+                    )
+                    .into()
+                }
+            }
+        };
+        if attempts >= max_attempts {
+            return Err(RangedDownloadError::part(range, body_error));
+        }
+        debug!(
+            "fetching the rest of bytes {start}-{end} again after {} bytes: {body_error}",
+            bytes.len()
+        );
+    }
+}
+
+struct PartsBody {
+    parts: Mutex<Parts>,
+}
+
+impl http_body_04x::Body for PartsBody {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let parts = self
+            .get_mut()
+            .parts
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        parts
+            .poll_next_part(cx)
+            .map(|part| part.map(|result| result.map_err(Into::into)))
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http_02x::HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(None))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        let parts = self.parts.lock().unwrap_or_else(PoisonError::into_inner);
+        parts.failed || parts.remaining == 0
+    }
+
+    fn size_hint(&self) -> http_body_04x::SizeHint {
+        let parts = self.parts.lock().unwrap_or_else(PoisonError::into_inner);
+        http_body_04x::SizeHint::with_exact(parts.remaining)
+    }
+}
+
+/// An error that failed a [`RangedDownload`].
+#[derive(Debug)]
+pub struct RangedDownloadError {
+    kind: ErrorKind,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Part {
+        range: RangeInclusive<u64>,
+        source: BoxError,
+    },
+    Write(io::Error),
+}
+
+impl RangedDownloadError {
+    fn part(range: RangeInclusive<u64>, source: BoxError) -> Self {
+        Self {
+            kind: ErrorKind::Part { range, source },
+        }
+    }
+
+    fn write(source: io::Error) -> Self {
+        Self {
+            kind: ErrorKind::Write(source),
+        }
+    }
+
+    /// Returns the byte range of the part that failed, or `None` if writing the bytes failed.
+    pub fn range(&self) -> Option<RangeInclusive<u64>> {
+        match &self.kind {
+            ErrorKind::Part { range, .. } => Some(range.clone()),
+            ErrorKind::Write(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for RangedDownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ErrorKind::Part { range, .. } => write!(
+                f,
+                "failed to download bytes {}-{}",
+                range.start(),
+                range.end()
+            ),
+            ErrorKind::Write(_) => write!(f, "failed to write the downloaded bytes"),
+        }
+    }
+}
+
+impl std::error::Error for RangedDownloadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Part { source, .. } => Some(source.as_ref() as _),
+            ErrorKind::Write(source) => Some(source as _),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::Duration;
+
+    const TOTAL_SIZE: u64 = 1000;
+    const PART_SIZE: u64 = 64;
+    const CONCURRENCY: usize = 4;
+
+    fn object() -> Vec<u8> {
+        (0..TOTAL_SIZE).map(|i| (i % 251) as u8).collect()
+    }
+
+    /// A body that yields `chunks`, and then fails if `fail` is set.
+    struct TestBody {
+        chunks: VecDeque<Bytes>,
+        fail: bool,
+    }
+
+    impl http_body_04x::Body for TestBody {
+        type Data = Bytes;
+        type Error = BoxError;
+
+        fn poll_data(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            Poll::Ready(match self.chunks.pop_front() {
+                Some(chunk) => Some(Ok(chunk)),
+                None if self.fail => Some(Err("connection reset".into())),
+                None => None,
+            })
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<http_02x::HeaderMap>, Self::Error>> {
+            Poll::Ready(Ok(None))
+        }
+    }
+
+    /// Serves ranges of `object()`, responding to later parts sooner than earlier ones.
+    #[derive(Clone, Default)]
+    struct MockObject {
+        requests: Arc<Mutex<Vec<RangeInclusive<u64>>>>,
+        served: Arc<AtomicU64>,
+        // When set, the first response starting at this offset fails halfway through its body
+        fail_once_at: Option<u64>,
+        // When set, fetching a range starting at this offset fails
+        error_at: Option<u64>,
+    }
+
+    impl MockObject {
+        fn fetch(&self, range: RangeInclusive<u64>) -> FetchFuture {
+            let mock = self.clone();
+            Box::pin(async move {
+                let (start, end) = (*range.start(), *range.end());
+                let first_request = {
+                    let mut requests = mock.requests.lock().unwrap();
+                    let first_request = !requests.iter().any(|r| *r.start() == start);
+                    requests.push(range);
+                    first_request
+                };
+                tokio::time::sleep(Duration::from_millis(100 - start * 100 / TOTAL_SIZE)).await;
+                if mock.error_at == Some(start) {
+                    return Err("service unavailable".into());
+                }
+                let mut bytes = Bytes::from(object()).slice(start as usize..=end as usize);
+                let fail = first_request && mock.fail_once_at == Some(start);
+                if fail {
+                    bytes.truncate(bytes.len() / 2);
+                }
+                mock.served.fetch_add(bytes.len() as u64, Ordering::SeqCst);
+                let chunks = bytes.chunks(16).map(Bytes::copy_from_slice).collect();
+                Ok(ByteStream::new(SdkBody::from_body_0_4(TestBody {
+                    chunks,
+                    fail,
+                })))
+            })
+        }
+
+        fn download(&self) -> RangedDownload {
+            let mock = self.clone();
+            RangedDownload::new(TOTAL_SIZE, move |range| mock.fetch(range))
+                .part_size(PART_SIZE)
+                .concurrency(CONCURRENCY)
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn parts_are_yielded_in_order() {
+        let mock = MockObject::default();
+        let bytes = mock.download().into_byte_stream().collect().await.unwrap();
+        assert_eq!(object(), bytes.to_vec());
+
+        let mut requests = mock.requests.lock().unwrap().clone();
+        requests.sort_by_key(|r| *r.start());
+        assert_eq!(16, requests.len());
+        assert_eq!(0..=63, requests[0]);
+        assert_eq!(960..=999, requests[15]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn buffering_is_bounded() {
+        let mock = MockObject::default();
+        let mut stream = mock.download().into_byte_stream();
+        let mut consumed = 0;
+        let mut peak_buffered = 0;
+        while let Some(chunk) = stream.next().await {
+            consumed += chunk.unwrap().len() as u64;
+            // A slow consumer, so that fetched parts pile up if nothing stops them
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            let buffered = mock.served.load(Ordering::SeqCst) - consumed;
+            peak_buffered = peak_buffered.max(buffered);
+        }
+        assert_eq!(TOTAL_SIZE, consumed);
+        assert!(
+            peak_buffered <= CONCURRENCY as u64 * PART_SIZE,
+            "buffered {peak_buffered} bytes"
+        );
+        assert!(peak_buffered > PART_SIZE, "parts should be fetched ahead");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn mid_part_failures_fetch_the_rest_of_the_part() {
+        let mock = MockObject {
+            fail_once_at: Some(128),
+            ..Default::default()
+        };
+        let mut written = Vec::new();
+        let len = mock.download().write_to(&mut written).await.unwrap();
+        assert_eq!(TOTAL_SIZE, len);
+        assert_eq!(object(), written);
+
+        let requests = mock.requests.lock().unwrap();
+        let retried: Vec<_> = requests
+            .iter()
+            .filter(|r| *r.end() == 191)
+            .cloned()
+            .collect();
+        assert_eq!(vec![128..=191, 160..=191], retried);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn part_failures_fail_the_download() {
+        let mock = MockObject {
+            error_at: Some(256),
+            ..Default::default()
+        };
+        let err = mock
+            .download()
+            .write_to(&mut Vec::new())
+            .await
+            .expect_err("the part fails");
+        assert_eq!(Some(256..=319), err.range());
+        assert_eq!("service unavailable", err.source().unwrap().to_string());
+
+        let mut stream = mock.download().into_byte_stream();
+        let mut consumed = 0;
+        let err = loop {
+            match stream.next().await.expect("fails before the end") {
+                Ok(chunk) => consumed += chunk.len(),
+                Err(err) => break err,
+            }
+        };
+        assert_eq!(256, consumed);
+        assert!(format!("{:?}", err).contains("256"), "{err:?}");
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn repeated_mid_part_failures_fail_the_download() {
+        let mock = MockObject::default();
+        let fetch = mock.clone();
+        let download = RangedDownload::new(TOTAL_SIZE, move |range| {
+            let fetch = fetch.clone();
+            async move {
+                let body = fetch.fetch(range).await?;
+                let bytes = body.collect().await?.into_bytes();
+                // Every response ends early
+                Ok::<_, BoxError>(ByteStream::from(bytes.slice(..bytes.len() - 1)))
+            }
+        })
+        .part_size(PART_SIZE)
+        .max_part_attempts(2);
+        let err = download.write_to(&mut Vec::new()).await.unwrap_err();
+        assert_eq!(Some(0..=63), err.range());
+        assert_eq!(
+            "the response ended after 63 of 64 bytes",
+            err.source().unwrap().to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_downloads_fetch_nothing() {
+        let mock = MockObject::default();
+        let download = RangedDownload::new(0, move |range| mock.fetch(range));
+        let bytes = download.into_byte_stream().collect().await.unwrap();
+        assert!(bytes.into_bytes().is_empty());
+    }
+}
@@ -0,0 +1,243 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_async::rt::sleep::AsyncSleep;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::orchestrator::Metadata;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugin;
+use aws_smithy_types::config_bag::{ConfigBag, FrozenLayer, Layer, Storable, StoreReplace};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tracing::debug;
+
+/// Client-side limit on the rate at which requests are sent.
+///
+/// The limit is a token bucket that refills at `ops_per_second`, and holds up to `burst`
+/// permits. Every attempt takes a permit before it is sent, so retries count against the limit
+/// too. When the bucket is empty, requests wait for a permit in the order in which they asked for
+/// one. With [`fail_fast`](RateLimiter::fail_fast), requests that would wait too long fail with
+/// [`RateLimitExceeded`] instead.
+///
+/// By default, all the operations of a client share one bucket. Clones of a `RateLimiter` share
+/// their buckets, so clients configured with the same `RateLimiter` share the limit too.
+/// Waiting uses the time source and sleep implementation of the client.
+///
+/// ```
+/// use aws_smithy_runtime::client::rate_limit::RateLimiter;
+/// use std::time::Duration;
+///
+/// // 10 requests per second per operation, and fail requests that would wait more than a second
+/// let rate_limiter = RateLimiter::new(10.0, 10)
+///     .per_operation()
+///     .fail_fast(Duration::from_secs(1));
+/// # let _ = rate_limiter;
+/// ```
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    burst: u32,
+    per_operation: bool,
+    max_wait: Option<Duration>,
+    // The time at which each bucket will be full again
+    buckets: Arc<Mutex<HashMap<String, SystemTime>>>,
+}
+
+impl RateLimiter {
+    /// Limits requests to `ops_per_second` on average, and `burst` at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ops_per_second` isn't a positive number, or `burst` is zero.
+    pub fn new(ops_per_second: f64, burst: u32) -> Self {
+        assert!(
+            ops_per_second.is_finite() && ops_per_second > 0.0,
+            "ops_per_second must be a positive number"
+        );
+        assert!(burst > 0, "burst must be at least 1");
+        Self {
+            interval: Duration::from_secs_f64(1.0 / ops_per_second),
+            burst,
+            per_operation: false,
+            max_wait: None,
+            buckets: Default::default(),
+        }
+    }
+
+    /// Gives each operation its own bucket, rather than sharing one bucket across the client.
+    pub fn per_operation(mut self) -> Self {
+        self.per_operation = true;
+        self
+    }
+
+    /// Fails requests that would have to wait more than `max_wait` for a permit with
+    /// [`RateLimitExceeded`], rather than waiting.
+    pub fn fail_fast(mut self, max_wait: Duration) -> Self {
+        self.max_wait = Some(max_wait);
+        self
+    }
+
+    /// Returns how saturated the most saturated bucket is at `now`.
+    ///
+    /// This is `0.0` when no permits are in use, `1.0` when the burst is used up, and greater
+    /// than `1.0` when requests are waiting for permits.
+    pub fn saturation(&self, now: SystemTime) -> f64 {
+        let buckets = self.buckets.lock().unwrap();
+        buckets
+            .values()
+            .map(|full_at| self.saturation_of(*full_at, now))
+            .fold(0.0, f64::max)
+    }
+
+    fn saturation_of(&self, full_at: SystemTime, now: SystemTime) -> f64 {
+        let backlog = full_at.duration_since(now).unwrap_or_default();
+        backlog.as_secs_f64() / (self.interval * self.burst).as_secs_f64()
+    }
+
+    /// Reserves the next permit of the bucket for `key`, and returns how long to wait for it.
+    fn reserve(&self, key: &str, now: SystemTime) -> Result<(Duration, f64), RateLimitExceeded> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let full_at = buckets.get(key).copied().unwrap_or(now).max(now);
+        // The burst is available as long as the bucket is at most `burst - 1` permits from full
+        let backlog = full_at.duration_since(now).unwrap_or_default();
+        let wait = backlog.saturating_sub(self.interval * (self.burst - 1));
+        if let Some(max_wait) = self.max_wait {
+            if wait > max_wait {
+                return Err(RateLimitExceeded { wait, max_wait });
+            }
+        }
+        let full_at = full_at + self.interval;
+        buckets.insert(key.to_owned(), full_at);
+        Ok((wait, self.saturation_of(full_at, now)))
+    }
+
+    /// Waits for a permit to send the next attempt of the current operation.
+    pub(crate) async fn acquire(
+        &self,
+        runtime_components: &RuntimeComponents,
+        cfg: &ConfigBag,
+    ) -> Result<(), BoxError> {
+        let time_source = runtime_components
+            .time_source()
+            .ok_or("a time source is required to rate limit requests")?;
+        let key = match cfg.load::<Metadata>() {
+            Some(metadata) if self.per_operation => metadata.name(),
+            _ => "",
+        };
+        let (wait, saturation) = self.reserve(key, time_source.now())?;
+        debug!(wait = ?wait, saturation, "acquired rate limit permit");
+        if !wait.is_zero() {
+            let sleep_impl = runtime_components
+                .sleep_impl()
+                .ok_or("an async sleep implementation is required to rate limit requests")?;
+            sleep_impl.sleep(wait).await;
+        }
+        Ok(())
+    }
+}
+
+impl Storable for RateLimiter {
+    type Storer = StoreReplace<Self>;
+}
+
+impl RuntimePlugin for RateLimiter {
+    fn config(&self) -> Option<FrozenLayer> {
+        let mut layer = Layer::new("RateLimiter");
+        layer.store_put(self.clone());
+        Some(layer.freeze())
+    }
+}
+
+/// A request wasn't sent because it would have had to wait too long for a [`RateLimiter`] permit.
+#[derive(Debug)]
+pub struct RateLimitExceeded {
+    wait: Duration,
+    max_wait: Duration,
+}
+
+impl RateLimitExceeded {
+    /// Returns how long the request would have had to wait.
+    pub fn wait(&self) -> Duration {
+        self.wait
+    }
+}
+
+impl fmt::Display for RateLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the rate limit would have delayed the request by {:?}, which is more than the maximum of {:?}",
+            self.wait, self.max_wait
+        )
+    }
+}
+
+impl std::error::Error for RateLimitExceeded {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn burst_is_available_at_once() {
+        let limiter = RateLimiter::new(2.0, 3);
+        let now = SystemTime::UNIX_EPOCH;
+        let waits: Vec<_> = (0..5)
+            .map(|_| limiter.reserve("", now).unwrap().0)
+            .collect();
+        assert_eq!(
+            vec![
+                Duration::ZERO,
+                Duration::ZERO,
+                Duration::ZERO,
+                SECOND / 2,
+                SECOND
+            ],
+            waits
+        );
+    }
+
+    #[test]
+    fn bucket_refills_over_time() {
+        let limiter = RateLimiter::new(1.0, 2);
+        let start = SystemTime::UNIX_EPOCH;
+        limiter.reserve("", start).unwrap();
+        limiter.reserve("", start).unwrap();
+        assert_eq!(1.0, limiter.saturation(start));
+        assert_eq!(0.5, limiter.saturation(start + SECOND));
+        assert_eq!(0.0, limiter.saturation(start + SECOND * 5));
+
+        // Idle time doesn't let the bucket hold more than `burst` permits
+        let later = start + SECOND * 10;
+        assert_eq!(Duration::ZERO, limiter.reserve("", later).unwrap().0);
+        assert_eq!(Duration::ZERO, limiter.reserve("", later).unwrap().0);
+        assert_eq!(SECOND, limiter.reserve("", later).unwrap().0);
+    }
+
+    #[test]
+    fn fail_fast_doesnt_take_a_permit() {
+        let limiter = RateLimiter::new(1.0, 1).fail_fast(SECOND);
+        let now = SystemTime::UNIX_EPOCH;
+        assert_eq!(Duration::ZERO, limiter.reserve("", now).unwrap().0);
+        assert_eq!(SECOND, limiter.reserve("", now).unwrap().0);
+        let err = limiter.reserve("", now).expect_err("waits 2 seconds");
+        assert_eq!(SECOND * 2, err.wait());
+        // The failed request didn't push back the requests after it
+        assert_eq!(SECOND, limiter.reserve("", now + SECOND).unwrap().0);
+    }
+
+    #[test]
+    fn keys_have_separate_buckets() {
+        let limiter = RateLimiter::new(1.0, 1);
+        let now = SystemTime::UNIX_EPOCH;
+        assert_eq!(Duration::ZERO, limiter.reserve("a", now).unwrap().0);
+        assert_eq!(Duration::ZERO, limiter.reserve("b", now).unwrap().0);
+        assert_eq!(SECOND, limiter.reserve("a", now).unwrap().0);
+    }
+}
@@ -0,0 +1,333 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use http_02x::Uri;
+use std::error::Error;
+use std::fmt;
+use tracing::debug;
+
+const DEFAULT_MAX_REDIRECTS: u32 = 3;
+
+/// Opt-in policy for following `307 Temporary Redirect` and `308 Permanent Redirect` responses.
+///
+/// Without a policy, redirects are returned as errors. With a policy, a redirect is followed when
+/// its target has the same scheme as the request, and a host that is either on the allow-list, or
+/// in the same registrable domain as the request when [`same_registrable_domain`] is enabled.
+///
+/// Followed requests are signed again for their new host; the signature of the original request is
+/// never sent to another host. The request body is only sent again if it can be replayed, e.g. an
+/// in-memory body. Requests that can't be redirected fail with a [`RedirectError`] that lists the
+/// redirect chain.
+///
+/// [`same_registrable_domain`]: RedirectPolicy::same_registrable_domain
+///
+/// # Example
+///
+/// ```
+/// use aws_smithy_runtime::client::redirect::RedirectPolicy;
+///
+/// let policy = RedirectPolicy::new()
+///     .max_redirects(2)
+///     .allow_host("blue.example.com")
+///     .allow_host("green.example.com");
+/// # let _ = policy;
+/// ```
+#[derive(Clone, Debug)]
+pub struct RedirectPolicy {
+    max_redirects: u32,
+    allowed_hosts: Vec<String>,
+    same_registrable_domain: bool,
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        Self {
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            allowed_hosts: Vec::new(),
+            same_registrable_domain: false,
+        }
+    }
+}
+
+impl RedirectPolicy {
+    /// Creates a policy that follows up to three redirects, to hosts on its allow-list.
+    ///
+    /// The allow-list is empty, so add hosts with [`allow_host`](Self::allow_host), or enable
+    /// [`same_registrable_domain`](Self::same_registrable_domain).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of redirects to follow for one request.
+    pub fn max_redirects(mut self, max_redirects: u32) -> Self {
+        self.max_redirects = max_redirects;
+        self
+    }
+
+    /// Allows redirects to `host`. Host names are case-insensitive.
+    pub fn allow_host(mut self, host: impl Into<String>) -> Self {
+        self.allowed_hosts.push(host.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Allows redirects to hosts in the same registrable domain as the request.
+    ///
+    /// The registrable domain is approximated by the last two labels of the host name, e.g.
+    /// `example.com` for `bucket.s3.example.com`. Use [`allow_host`](Self::allow_host) instead for
+    /// hosts under a multi-label public suffix like `co.uk`. IP addresses only match themselves.
+    pub fn same_registrable_domain(mut self, enabled: bool) -> Self {
+        self.same_registrable_domain = enabled;
+        self
+    }
+
+    fn allows(&self, from: &Uri, to: &Uri) -> bool {
+        let (Some(from_host), Some(to_host)) = (from.host(), to.host()) else {
+            return false;
+        };
+        let (from_host, to_host) = (from_host.to_ascii_lowercase(), to_host.to_ascii_lowercase());
+        if self.allowed_hosts.contains(&to_host) {
+            return true;
+        }
+        self.same_registrable_domain
+            && registrable_domain(&from_host) == registrable_domain(&to_host)
+    }
+}
+
+impl Storable for RedirectPolicy {
+    type Storer = StoreReplace<Self>;
+}
+
+fn registrable_domain(host: &str) -> &str {
+    if host.starts_with('[') || host.parse::<std::net::Ipv4Addr>().is_ok() {
+        return host;
+    }
+    match host.rmatch_indices('.').nth(1) {
+        Some((index, _)) => &host[index + 1..],
+        None => host,
+    }
+}
+
+/// Why a redirect wasn't followed.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RedirectErrorKind {
+    /// The request was redirected more than [`RedirectPolicy::max_redirects`] times.
+    TooManyRedirects,
+    /// The redirect target isn't allowed by the [`RedirectPolicy`], or changes the scheme.
+    Disallowed,
+    /// The request body can't be replayed, so it can't be sent to the redirect target.
+    BodyNotReplayable,
+    /// The redirect response doesn't have a valid `Location` header.
+    InvalidLocation,
+}
+
+/// A redirect couldn't be followed under the [`RedirectPolicy`].
+#[derive(Debug)]
+pub struct RedirectError {
+    kind: RedirectErrorKind,
+    chain: Vec<String>,
+}
+
+impl RedirectError {
+    /// Returns why the redirect wasn't followed.
+    pub fn kind(&self) -> RedirectErrorKind {
+        self.kind
+    }
+
+    /// Returns the URIs of the redirect chain, starting with the original request and ending with
+    /// the redirect that wasn't followed.
+    pub fn chain(&self) -> &[String] {
+        &self.chain
+    }
+}
+
+impl fmt::Display for RedirectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self.kind {
+            RedirectErrorKind::TooManyRedirects => "too many redirects",
+            RedirectErrorKind::Disallowed => {
+                "the redirect target isn't allowed by the redirect policy"
+            }
+            RedirectErrorKind::BodyNotReplayable => {
+                "the request body can't be replayed, so it can't be sent to the redirect target. \
+                 Use an in-memory body, or `ByteStream::from_path`, to make it replayable"
+            }
+            RedirectErrorKind::InvalidLocation => {
+                "the redirect response has no valid `Location` header"
+            }
+        };
+        write!(
+            f,
+            "redirect not followed: {reason} (redirect chain: {})",
+            self.chain.join(" -> ")
+        )
+    }
+}
+
+impl Error for RedirectError {}
+
+/// Follows the redirects of one attempt under a [`RedirectPolicy`].
+pub(crate) struct FollowRedirects {
+    policy: RedirectPolicy,
+    // The request before it was signed, so that it can be signed again for the redirect target
+    unsigned_request: Option<HttpRequest>,
+    chain: Vec<String>,
+}
+
+impl FollowRedirects {
+    /// Starts following redirects of `unsigned_request`, which must not be signed yet.
+    pub(crate) fn new(policy: RedirectPolicy, unsigned_request: &HttpRequest) -> Self {
+        Self {
+            policy,
+            chain: vec![unsigned_request.uri().to_string()],
+            unsigned_request: unsigned_request.try_clone(),
+        }
+    }
+
+    /// Returns the unsigned request to send next if `response` is a redirect that should be
+    /// followed, or `None` if it isn't a redirect.
+    pub(crate) fn next_request(
+        &mut self,
+        response: &HttpResponse,
+    ) -> Result<Option<HttpRequest>, RedirectError> {
+        if !matches!(response.status().as_u16(), 307 | 308) {
+            return Ok(None);
+        }
+        let current: Uri = self
+            .chain
+            .last()
+            .expect("the chain starts with the original request")
+            .parse()
+            .expect("the request URI is valid");
+        let location = response
+            .headers()
+            .get("location")
+            .and_then(|location| resolve(&current, location));
+        let Some(location) = location else {
+            return Err(self.error(RedirectErrorKind::InvalidLocation));
+        };
+        self.chain.push(location.to_string());
+        if self.chain.len() as u32 > self.policy.max_redirects + 1 {
+            return Err(self.error(RedirectErrorKind::TooManyRedirects));
+        }
+        if location.scheme() != current.scheme() || !self.policy.allows(&current, &location) {
+            return Err(self.error(RedirectErrorKind::Disallowed));
+        }
+        let Some(mut request) = self.unsigned_request.take() else {
+            return Err(self.error(RedirectErrorKind::BodyNotReplayable));
+        };
+        debug!(from = %current, to = %location, "following redirect");
+        request
+            .set_uri(location)
+            .expect("the location was parsed as a URI");
+        request.headers_mut().remove("host");
+        self.unsigned_request = request.try_clone();
+        Ok(Some(request))
+    }
+
+    fn error(&self, kind: RedirectErrorKind) -> RedirectError {
+        RedirectError {
+            kind,
+            chain: self.chain.clone(),
+        }
+    }
+}
+
+/// Resolves the `Location` of a redirect against the URI of the redirected request.
+fn resolve(current: &Uri, location: &str) -> Option<Uri> {
+    let location: Uri = location.parse().ok()?;
+    if location.scheme().is_some() {
+        return location.authority().is_some().then_some(location);
+    }
+    // An absolute path on the same host
+    if !location.path().starts_with('/') {
+        return None;
+    }
+    let mut parts = current.clone().into_parts();
+    parts.path_and_query = location.path_and_query().cloned();
+    Uri::from_parts(parts).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_types::body::SdkBody;
+
+    fn redirect(location: &str) -> HttpResponse {
+        HttpResponse::try_from(
+            http_02x::Response::builder()
+                .status(307)
+                .header("location", location)
+                .body(SdkBody::empty())
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn request(uri: &str) -> HttpRequest {
+        HttpRequest::get(uri).unwrap()
+    }
+
+    #[test]
+    fn follows_allowed_redirects() {
+        let policy = RedirectPolicy::new()
+            .same_registrable_domain(true)
+            .max_redirects(2);
+        let mut follow = FollowRedirects::new(policy, &request("https://a.example.com/x?y=z"));
+
+        let ok = HttpResponse::new(200.try_into().unwrap(), SdkBody::empty());
+        assert!(follow.next_request(&ok).unwrap().is_none());
+        let next = follow.next_request(&redirect("/moved")).unwrap().unwrap();
+        assert_eq!("https://a.example.com/moved", next.uri());
+        let next = follow
+            .next_request(&redirect("https://b.example.com/moved"))
+            .unwrap()
+            .unwrap();
+        assert_eq!("https://b.example.com/moved", next.uri());
+
+        let err = follow
+            .next_request(&redirect("https://c.example.com/moved"))
+            .unwrap_err();
+        assert_eq!(RedirectErrorKind::TooManyRedirects, err.kind());
+        assert_eq!(
+            &[
+                "https://a.example.com/x?y=z",
+                "https://a.example.com/moved",
+                "https://b.example.com/moved",
+                "https://c.example.com/moved",
+            ],
+            err.chain()
+        );
+    }
+
+    #[test]
+    fn disallowed_redirects_fail() {
+        let disallowed = |policy: RedirectPolicy, location: &str| {
+            FollowRedirects::new(policy, &request("https://a.example.com/"))
+                .next_request(&redirect(location))
+                .unwrap_err()
+                .kind()
+        };
+        let same_domain = RedirectPolicy::new().same_registrable_domain(true);
+        assert_eq!(
+            RedirectErrorKind::Disallowed,
+            disallowed(same_domain.clone(), "https://a.example.org/")
+        );
+        assert_eq!(
+            RedirectErrorKind::Disallowed,
+            disallowed(same_domain.clone(), "http://a.example.com/")
+        );
+        assert_eq!(
+            RedirectErrorKind::Disallowed,
+            disallowed(RedirectPolicy::new(), "https://b.example.com/")
+        );
+        assert_eq!(
+            RedirectErrorKind::InvalidLocation,
+            disallowed(same_domain, "relative/path")
+        );
+    }
+}
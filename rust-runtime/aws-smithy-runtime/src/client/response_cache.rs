@@ -0,0 +1,515 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    AfterDeserializationInterceptorContextRef, BeforeDeserializationInterceptorContextMut,
+    BeforeTransmitInterceptorContextMut,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse, Metadata};
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::http::StatusCode;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use bytes::Bytes;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+const ETAG: &str = "etag";
+const IF_MATCH: &str = "if-match";
+const IF_NONE_MATCH: &str = "if-none-match";
+const NOT_MODIFIED: u16 = 304;
+
+/// Bodies larger than this aren't cached, unless [`ResponseCacheInterceptor::max_body_size`] is set.
+const DEFAULT_MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// Request headers that differ between identical requests, and are left out of the cache key.
+const VOLATILE_HEADERS: &[&str] = &[
+    "amz-sdk-invocation-id",
+    "amz-sdk-request",
+    "authorization",
+    "date",
+    "user-agent",
+    "x-amz-content-sha256",
+    "x-amz-date",
+    "x-amz-security-token",
+    "x-amz-user-agent",
+];
+
+/// Identifies a cached response: the name of the operation, and the canonical form of the serialized
+/// request.
+///
+/// The canonical request is made of the method, the URI, the headers sorted by name except for
+/// the ones that change with every request (such as the signature), and the body.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    operation: String,
+    canonical_request: Bytes,
+}
+
+impl CacheKey {
+    /// Returns the key of `request`, or `None` if its body is streaming.
+    fn from_request(operation: &str, request: &HttpRequest) -> Option<Self> {
+        let body = request.body().bytes()?;
+        let mut headers: Vec<_> = request
+            .headers()
+            .iter()
+            .filter(|(name, _)| !VOLATILE_HEADERS.contains(name))
+            .collect();
+        headers.sort();
+        let mut canonical_request = format!("{}\n{}\n", request.method(), request.uri());
+        for (name, value) in headers {
+            canonical_request.push_str(&format!("{name}:{value}\n"));
+        }
+        canonical_request.push('\n');
+        let mut canonical_request = canonical_request.into_bytes();
+        canonical_request.extend_from_slice(body);
+        Some(Self {
+            operation: operation.to_owned(),
+            canonical_request: canonical_request.into(),
+        })
+    }
+
+    /// Returns the name of the operation.
+    pub fn operation(&self) -> &str {
+        &self.operation
+    }
+
+    /// Returns the canonical form of the serialized request.
+    pub fn canonical_request(&self) -> &[u8] {
+        &self.canonical_request
+    }
+
+    fn size(&self) -> usize {
+        self.operation.len() + self.canonical_request.len()
+    }
+}
+
+/// A successful response, stored along with its ETag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CachedResponse {
+    etag: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Bytes,
+}
+
+impl CachedResponse {
+    /// Creates a cached response.
+    pub fn new(
+        etag: impl Into<String>,
+        status: u16,
+        headers: Vec<(String, String)>,
+        body: Bytes,
+    ) -> Self {
+        Self {
+            etag: etag.into(),
+            status,
+            headers,
+            body,
+        }
+    }
+
+    /// Returns the ETag of the response, which is sent in the `If-None-Match` header of the next
+    /// identical request.
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+
+    /// Returns the status code of the response.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns the headers of the response.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Returns the body of the response.
+    pub fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// Returns approximately how many bytes the response takes up.
+    pub fn size(&self) -> usize {
+        let headers: usize = self
+            .headers
+            .iter()
+            .map(|(name, value)| name.len() + value.len())
+            .sum();
+        self.etag.len() + headers + self.body.len()
+    }
+
+    fn to_response(&self) -> Result<HttpResponse, BoxError> {
+        let mut response = HttpResponse::new(
+            StatusCode::try_from(self.status)?,
+            SdkBody::from(self.body.clone()),
+        );
+        for (name, value) in &self.headers {
+            response
+                .headers_mut()
+                .try_append(name.clone(), value.clone())?;
+        }
+        Ok(response)
+    }
+}
+
+/// Storage for the responses cached by [`ResponseCacheInterceptor`].
+///
+/// Implementations must be safe to share between concurrent requests. A store that can't or won't
+/// keep a response can simply drop it in [`put`](ResponseCacheStore::put).
+pub trait ResponseCacheStore: fmt::Debug + Send + Sync {
+    /// Returns the response cached for `key`, if any.
+    fn get(&self, key: &CacheKey) -> Option<CachedResponse>;
+
+    /// Caches `response` for `key`, replacing the response previously cached for it.
+    fn put(&self, key: CacheKey, response: CachedResponse);
+
+    /// Removes the response cached for `key`, if any.
+    fn remove(&self, key: &CacheKey);
+}
+
+impl<T> ResponseCacheStore for Arc<T>
+where
+    T: ResponseCacheStore + ?Sized,
+{
+    fn get(&self, key: &CacheKey) -> Option<CachedResponse> {
+        (**self).get(key)
+    }
+
+    fn put(&self, key: CacheKey, response: CachedResponse) {
+        (**self).put(key, response)
+    }
+
+    fn remove(&self, key: &CacheKey) {
+        (**self).remove(key)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Lru {
+    entries: HashMap<CacheKey, (u64, CachedResponse)>,
+    // Keys by the tick at which they were last used, oldest first
+    recency: BTreeMap<u64, CacheKey>,
+    next_tick: u64,
+    size: usize,
+}
+
+impl Lru {
+    fn touch(&mut self, key: &CacheKey) {
+        let tick = self.next_tick;
+        if let Some((last_used, _)) = self.entries.get_mut(key) {
+            self.recency.remove(last_used);
+            *last_used = tick;
+            self.recency.insert(tick, key.clone());
+            self.next_tick += 1;
+        }
+    }
+
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some((last_used, response)) = self.entries.remove(key) {
+            self.recency.remove(&last_used);
+            self.size -= key.size() + response.size();
+        }
+    }
+}
+
+/// A [`ResponseCacheStore`] keeping responses in memory, and evicting the least recently used ones
+/// when the cached responses take up more than a given number of bytes.
+#[derive(Debug)]
+pub struct InMemoryResponseCache {
+    max_size: usize,
+    lru: Mutex<Lru>,
+}
+
+impl InMemoryResponseCache {
+    /// Creates a cache holding at most `max_size` bytes of responses.
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            lru: Default::default(),
+        }
+    }
+
+    /// Returns how many bytes the cached responses take up.
+    pub fn size(&self) -> usize {
+        self.lru.lock().unwrap().size
+    }
+
+    /// Returns how many responses are cached.
+    pub fn len(&self) -> usize {
+        self.lru.lock().unwrap().entries.len()
+    }
+
+    /// Returns `true` if no response is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ResponseCacheStore for InMemoryResponseCache {
+    fn get(&self, key: &CacheKey) -> Option<CachedResponse> {
+        let mut lru = self.lru.lock().unwrap();
+        lru.touch(key);
+        lru.entries.get(key).map(|(_, response)| response.clone())
+    }
+
+    fn put(&self, key: CacheKey, response: CachedResponse) {
+        let mut lru = self.lru.lock().unwrap();
+        lru.remove(&key);
+        let size = key.size() + response.size();
+        if size > self.max_size {
+            return;
+        }
+        while lru.size + size > self.max_size {
+            let (_, oldest) = lru.recency.pop_first().expect("the cache isn't empty");
+            lru.remove(&oldest);
+        }
+        let tick = lru.next_tick;
+        lru.next_tick += 1;
+        lru.recency.insert(tick, key.clone());
+        lru.entries.insert(key, (tick, response));
+        lru.size += size;
+    }
+
+    fn remove(&self, key: &CacheKey) {
+        self.lru.lock().unwrap().remove(key);
+    }
+}
+
+/// The cache key of the current request, and the response cached for it, if any.
+#[derive(Clone, Debug)]
+struct CacheLookup {
+    key: CacheKey,
+    cached: Option<CachedResponse>,
+}
+
+impl Storable for CacheLookup {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Interceptor that caches the responses of allow-listed operations, and revalidates them with
+/// their ETag.
+///
+/// The first time a request is sent, its response is cached if it's successful, has an `ETag`
+/// header, and its body is at most [`max_body_size`](Self::max_body_size) bytes. An identical
+/// request then sends the ETag in the `If-None-Match` header. When the service answers with a
+/// `304 Not Modified`, the cached response is deserialized instead, so the caller receives a
+/// normal output. Any other successful response replaces the cached one, and errors are never
+/// cached.
+///
+/// Requests with a streaming body, or that are already conditional, aren't cached.
+///
+/// ```
+/// use aws_smithy_runtime::client::response_cache::{InMemoryResponseCache, ResponseCacheInterceptor};
+///
+/// // Cache up to 16 MiB of `GetItem` responses
+/// let interceptor = ResponseCacheInterceptor::new(InMemoryResponseCache::new(16 * 1024 * 1024))
+///     .cache_operation("GetItem");
+/// # let _ = interceptor;
+/// ```
+#[derive(Clone, Debug)]
+pub struct ResponseCacheInterceptor {
+    store: Arc<dyn ResponseCacheStore>,
+    operations: HashSet<Cow<'static, str>>,
+    max_body_size: usize,
+}
+
+impl ResponseCacheInterceptor {
+    /// Creates an interceptor caching responses in `store`.
+    ///
+    /// No operation is cached until it is allow-listed with [`cache_operation`](Self::cache_operation).
+    pub fn new(store: impl ResponseCacheStore + 'static) -> Self {
+        Self {
+            store: Arc::new(store),
+            operations: HashSet::new(),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+
+    /// Caches the responses of the operation named `operation`, such as `"GetItem"`.
+    pub fn cache_operation(mut self, operation: impl Into<Cow<'static, str>>) -> Self {
+        self.operations.insert(operation.into());
+        self
+    }
+
+    /// Doesn't cache responses whose body is larger than `max_body_size` bytes.
+    ///
+    /// Defaults to 1 MiB.
+    pub fn max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    fn lookup(&self, request: &HttpRequest, cfg: &ConfigBag) -> Option<CacheLookup> {
+        let operation = cfg.load::<Metadata>()?.name();
+        if !self.operations.contains(operation)
+            || request.headers().contains_key(IF_MATCH)
+            || request.headers().contains_key(IF_NONE_MATCH)
+        {
+            return None;
+        }
+        let key = CacheKey::from_request(operation, request)?;
+        let cached = self.store.get(&key);
+        Some(CacheLookup { key, cached })
+    }
+
+    fn cacheable(&self, response: &HttpResponse) -> Option<CachedResponse> {
+        let etag = response.headers().get(ETAG)?;
+        let body = response.body().bytes()?;
+        if !response.status().is_success() || body.len() > self.max_body_size {
+            return None;
+        }
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.to_owned(), value.to_owned()))
+            .collect();
+        Some(CachedResponse::new(
+            etag,
+            response.status().as_u16(),
+            headers,
+            Bytes::copy_from_slice(body),
+        ))
+    }
+}
+
+impl Intercept for ResponseCacheInterceptor {
+    fn name(&self) -> &'static str {
+        "ResponseCacheInterceptor"
+    }
+
+    fn modify_before_signing(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        match self.lookup(context.request(), cfg) {
+            Some(lookup) => {
+                if let Some(cached) = &lookup.cached {
+                    context
+                        .request_mut()
+                        .headers_mut()
+                        .try_insert(IF_NONE_MATCH, cached.etag().to_owned())?;
+                }
+                cfg.interceptor_state().store_put(lookup);
+            }
+            None => {
+                cfg.interceptor_state().unset::<CacheLookup>();
+            }
+        }
+        Ok(())
+    }
+
+    fn modify_before_deserialization(
+        &self,
+        context: &mut BeforeDeserializationInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let cached = cfg
+            .load::<CacheLookup>()
+            .and_then(|lookup| lookup.cached.as_ref());
+        if let Some(cached) = cached {
+            if context.response().status().as_u16() == NOT_MODIFIED {
+                debug!(
+                    etag = cached.etag(),
+                    "response wasn't modified; serving it from the cache"
+                );
+                *context.response_mut() = cached.to_response()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_after_deserialization(
+        &self,
+        context: &AfterDeserializationInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let lookup = match cfg.load::<CacheLookup>() {
+            Some(lookup) if context.output_or_error().is_ok() => lookup,
+            _ => return Ok(()),
+        };
+        match self.cacheable(context.response()) {
+            Some(response) => self.store.put(lookup.key.clone(), response),
+            None => self.store.remove(&lookup.key),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(operation: &str) -> CacheKey {
+        CacheKey {
+            operation: operation.to_owned(),
+            canonical_request: Bytes::new(),
+        }
+    }
+
+    fn response(size: usize) -> CachedResponse {
+        CachedResponse::new("", 200, Vec::new(), Bytes::from(vec![0; size]))
+    }
+
+    #[test]
+    fn least_recently_used_responses_are_evicted() {
+        // Every entry takes up 10 bytes: a 1 byte operation name and a 9 byte body
+        let cache = InMemoryResponseCache::new(30);
+        cache.put(key("a"), response(9));
+        cache.put(key("b"), response(9));
+        cache.put(key("c"), response(9));
+        assert_eq!(30, cache.size());
+
+        // Using `a` makes `b` the least recently used response
+        assert!(cache.get(&key("a")).is_some());
+        cache.put(key("d"), response(9));
+        assert_eq!(3, cache.len());
+        assert!(cache.get(&key("b")).is_none());
+        assert!(cache.get(&key("a")).is_some());
+        assert!(cache.get(&key("c")).is_some());
+        assert!(cache.get(&key("d")).is_some());
+    }
+
+    #[test]
+    fn replaced_and_removed_responses_free_their_size() {
+        let cache = InMemoryResponseCache::new(100);
+        cache.put(key("a"), response(49));
+        cache.put(key("a"), response(9));
+        assert_eq!(10, cache.size());
+        cache.remove(&key("a"));
+        assert_eq!(0, cache.size());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn responses_larger_than_the_cache_are_not_kept() {
+        let cache = InMemoryResponseCache::new(10);
+        cache.put(key("a"), response(9));
+        cache.put(key("a"), response(10));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn volatile_headers_are_not_part_of_the_key() {
+        let request = |signature: &'static str, range: &'static str| {
+            let mut request = HttpRequest::new(SdkBody::from("body"));
+            request.headers_mut().insert("authorization", signature);
+            request.headers_mut().insert("range", range);
+            CacheKey::from_request("GetItem", &request).unwrap()
+        };
+        assert_eq!(request("a", "bytes=0-1"), request("b", "bytes=0-1"));
+        assert_ne!(request("a", "bytes=0-1"), request("a", "bytes=2-3"));
+    }
+}
@@ -9,29 +9,152 @@ pub mod classifiers;
 /// Smithy retry strategies.
 pub mod strategy;
 
+mod circuit_breaker;
 mod client_rate_limiter;
+pub(crate) mod partition;
 mod token_bucket;
 
+use aws_smithy_runtime_api::client::interceptors::context::Input;
 use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use aws_smithy_types::retry::RetryConfigPartition;
+use std::any::Any;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::sync::Arc;
 
+use partition::PartitionState;
+
+pub use circuit_breaker::CircuitBreaker;
 pub use client_rate_limiter::ClientRateLimiter;
-pub use token_bucket::TokenBucket;
+pub use partition::resolve_retry_budget;
+pub use token_bucket::{RetryBudgetEvent, RetryBudgetSnapshot, TokenBucket};
 
 pub use client_rate_limiter::ClientRateLimiterPartition;
 use std::borrow::Cow;
 
 /// Represents the retry partition, e.g. an endpoint, a region
+///
+/// Clients configured with equal partitions share their retry state: the [`TokenBucket`] that
+/// limits how many retries can be made, and the [`ClientRateLimiter`] used by adaptive retries.
+/// By default, all clients for the same service share a partition, and a client can be given a
+/// partition of its own with [`RetryPartition::unique`]. A partition can be further split by
+/// [endpoint](RetryPartition::by_endpoint) or by a [key taken from the input](RetryPartition::by_input)
+/// so that one failing endpoint or tenant doesn't exhaust the retry budget of the others.
+/// The retry state of split partitions is created lazily, and the least recently used ones are
+/// dropped once they are idle and there are too many of them.
+///
+/// The retry state lives in the partition itself, and is dropped with the last client using it.
 #[non_exhaustive]
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Clone)]
 pub struct RetryPartition {
     name: Cow<'static, str>,
+    split: PartitionSplit,
+    state: Arc<PartitionState>,
+}
+
+type InputKeyFn = Arc<dyn Fn(&Input) -> Option<String> + Send + Sync>;
+
+#[derive(Clone)]
+enum PartitionSplit {
+    None,
+    ByEndpoint,
+    ByInput(InputKeyFn),
+}
+
+impl fmt::Debug for PartitionSplit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => f.write_str("None"),
+            Self::ByEndpoint => f.write_str("ByEndpoint"),
+            Self::ByInput(_) => f.write_str("ByInput"),
+        }
+    }
 }
 
 impl RetryPartition {
     /// Creates a new `RetryPartition` from the given `name`.
+    ///
+    /// All clients using a partition with the same name share their retry state.
     pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
-        Self { name: name.into() }
+        let name = name.into();
+        Self {
+            state: PartitionState::named(&name),
+            name,
+            split: PartitionSplit::None,
+        }
+    }
+
+    /// Creates a `RetryPartition` split by endpoint host, so that each host gets its own retry
+    /// state. Clients calling the same host share it.
+    pub fn by_endpoint() -> Self {
+        Self::new("endpoint").split_by_endpoint()
+    }
+
+    /// Creates a `RetryPartition` named `name` that is split by the key `key_fn` returns for the
+    /// operation input, e.g. a tenant ID.
+    ///
+    /// Requests for which `key_fn` returns `None` use the retry state of the unsplit partition.
+    pub fn by_input(
+        name: impl Into<Cow<'static, str>>,
+        key_fn: impl Fn(&Input) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            split: PartitionSplit::ByInput(Arc::new(key_fn)),
+            ..Self::new(name)
+        }
+    }
+
+    /// Splits this partition by endpoint host.
+    pub fn split_by_endpoint(mut self) -> Self {
+        self.split = PartitionSplit::ByEndpoint;
+        self
+    }
+
+    /// Creates a partition named `name` that no other client will share, even if it has the same
+    /// name, so that the retries of a client don't use up the retry budget of other clients.
+    pub fn unique(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            split: PartitionSplit::None,
+            state: Default::default(),
+        }
+    }
+
+    /// Returns the name of this partition.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Debug for RetryPartition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPartition")
+            .field("name", &self.name)
+            .field("split", &self.split)
+            .finish()
+    }
+}
+
+impl PartialEq for RetryPartition {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.state, &other.state)
+            && self.name == other.name
+            && match (&self.split, &other.split) {
+                (PartitionSplit::None, PartitionSplit::None) => true,
+                (PartitionSplit::ByEndpoint, PartitionSplit::ByEndpoint) => true,
+                (PartitionSplit::ByInput(a), PartitionSplit::ByInput(b)) => Arc::ptr_eq(a, b),
+                _ => false,
+            }
+    }
+}
+
+impl Eq for RetryPartition {}
+
+impl Hash for RetryPartition {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        mem::discriminant(&self.split).hash(state);
     }
 }
 
@@ -44,3 +167,9 @@ impl fmt::Display for RetryPartition {
 impl Storable for RetryPartition {
     type Storer = StoreReplace<RetryPartition>;
 }
+
+impl RetryConfigPartition for RetryPartition {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
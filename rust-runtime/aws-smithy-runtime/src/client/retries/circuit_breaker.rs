@@ -0,0 +1,221 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_async::time::{SharedTimeSource, SystemTimeSource, TimeSource};
+use aws_smithy_runtime_api::client::retries::classifiers::{RetryAction, RetryReason};
+use aws_smithy_runtime_api::client::retries::gate::{Permit, RetryGate};
+use aws_smithy_runtime_api::shared::IntoShared;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// A [`RetryGate`] that stops requests to a dependency while too many of them fail.
+///
+/// The circuit breaker tracks the results of the last `window` attempts. An attempt fails when
+/// its result is classified as retryable; successes and errors that aren't retried count as
+/// successes. When at least `failure_rate` of the window failed, the breaker opens and denies
+/// every attempt for `open_for`. After that, it lets a single trial attempt through: if the
+/// trial succeeds, the breaker closes again, and if it fails, the breaker stays open for another
+/// `open_for`.
+///
+/// Clones of a `CircuitBreaker` share their state, so clients configured with the same breaker
+/// share it too.
+///
+/// ```
+/// use aws_smithy_runtime::client::retries::CircuitBreaker;
+/// use std::time::Duration;
+///
+/// // Open when half of the last 20 attempts failed, and try again after 30 seconds
+/// let circuit_breaker = CircuitBreaker::new(0.5, 20, Duration::from_secs(30));
+/// # let _ = circuit_breaker;
+/// ```
+#[derive(Clone, Debug)]
+pub struct CircuitBreaker {
+    failure_rate: f64,
+    window: usize,
+    open_for: Duration,
+    time_source: SharedTimeSource,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug)]
+enum State {
+    // Whether each of the most recent attempts failed
+    Closed { failures: VecDeque<bool> },
+    Open { until: SystemTime },
+    HalfOpen { trial_in_flight: bool },
+}
+
+impl State {
+    fn closed() -> Self {
+        State::Closed {
+            failures: VecDeque::new(),
+        }
+    }
+}
+
+impl CircuitBreaker {
+    /// Creates a circuit breaker that opens for `open_for` when at least `failure_rate` of the
+    /// last `window` attempts failed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `failure_rate` isn't in `(0.0, 1.0]`, or `window` is zero.
+    pub fn new(failure_rate: f64, window: usize, open_for: Duration) -> Self {
+        assert!(
+            failure_rate > 0.0 && failure_rate <= 1.0,
+            "failure_rate must be greater than 0 and at most 1"
+        );
+        assert!(window > 0, "window must be at least 1");
+        Self {
+            failure_rate,
+            window,
+            open_for,
+            time_source: SystemTimeSource::new().into_shared(),
+            state: Arc::new(Mutex::new(State::closed())),
+        }
+    }
+
+    /// Uses `time_source` to tell when the breaker should let a trial attempt through.
+    pub fn with_time_source(mut self, time_source: impl TimeSource + 'static) -> Self {
+        self.time_source = time_source.into_shared();
+        self
+    }
+
+    /// Returns true if the breaker is currently denying attempts.
+    pub fn is_open(&self) -> bool {
+        match *self.state.lock().unwrap() {
+            State::Closed { .. } => false,
+            State::Open { until } => self.time_source.now() < until,
+            State::HalfOpen { trial_in_flight } => trial_in_flight,
+        }
+    }
+
+    fn open(&self, state: &mut State) {
+        tracing::debug!("opening the circuit breaker for {:?}", self.open_for);
+        *state = State::Open {
+            until: self.time_source.now() + self.open_for,
+        };
+    }
+}
+
+impl RetryGate for CircuitBreaker {
+    fn permit_attempt(&self, _attempt: u32, _prior_error: Option<&RetryReason>) -> Permit {
+        let mut state = self.state.lock().unwrap();
+        match *state {
+            State::Closed { .. } => Permit::Allow,
+            State::Open { until } if self.time_source.now() < until => {
+                Permit::Deny("the circuit breaker is open".into())
+            }
+            State::Open { .. }
+            | State::HalfOpen {
+                trial_in_flight: false,
+            } => {
+                *state = State::HalfOpen {
+                    trial_in_flight: true,
+                };
+                Permit::Allow
+            }
+            State::HalfOpen {
+                trial_in_flight: true,
+            } => Permit::Deny("the circuit breaker is waiting for a trial attempt".into()),
+        }
+    }
+
+    fn record_attempt(&self, result: &RetryAction) {
+        let failed = matches!(result, RetryAction::RetryIndicated(_));
+        let mut state = self.state.lock().unwrap();
+        match &mut *state {
+            State::Closed { failures } => {
+                failures.push_back(failed);
+                if failures.len() > self.window {
+                    failures.pop_front();
+                }
+                let failure_count = failures.iter().filter(|failed| **failed).count();
+                if failures.len() == self.window
+                    && failure_count as f64 >= self.failure_rate * self.window as f64
+                {
+                    self.open(&mut state);
+                }
+            }
+            // Results of attempts that were sent before the breaker opened
+            State::Open { .. } => {}
+            State::HalfOpen { .. } if failed => self.open(&mut state),
+            State::HalfOpen { .. } => *state = State::closed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_async::test_util::ManualTimeSource;
+    use aws_smithy_types::retry::ErrorKind;
+    use std::time::UNIX_EPOCH;
+
+    const OPEN_FOR: Duration = Duration::from_secs(30);
+
+    fn breaker() -> (CircuitBreaker, ManualTimeSource) {
+        let time = ManualTimeSource::new(UNIX_EPOCH);
+        let breaker = CircuitBreaker::new(0.5, 4, OPEN_FOR).with_time_source(time.clone());
+        (breaker, time)
+    }
+
+    fn record(breaker: &CircuitBreaker, failed: bool) {
+        breaker.record_attempt(&if failed {
+            RetryAction::retryable_error(ErrorKind::ServerError)
+        } else {
+            RetryAction::NoActionIndicated
+        });
+    }
+
+    #[test]
+    fn opens_when_the_window_has_too_many_failures() {
+        let (breaker, _time) = breaker();
+        for failed in [true, false, false] {
+            record(&breaker, failed);
+        }
+        // The window isn't full yet
+        assert_eq!(Permit::Allow, breaker.permit_attempt(1, None));
+
+        record(&breaker, false);
+        assert!(!breaker.is_open());
+        // The oldest failure leaves the window, so there is still one failure out of four
+        record(&breaker, true);
+        assert!(!breaker.is_open());
+
+        record(&breaker, true);
+        assert!(breaker.is_open());
+        assert!(matches!(breaker.permit_attempt(1, None), Permit::Deny(_)));
+    }
+
+    #[test]
+    fn lets_a_single_trial_through_after_a_while() {
+        let (breaker, time) = breaker();
+        for _ in 0..4 {
+            record(&breaker, true);
+        }
+        time.advance(OPEN_FOR - Duration::from_secs(1));
+        assert!(matches!(breaker.permit_attempt(1, None), Permit::Deny(_)));
+
+        time.advance(Duration::from_secs(1));
+        assert_eq!(Permit::Allow, breaker.permit_attempt(1, None));
+        assert!(matches!(breaker.permit_attempt(1, None), Permit::Deny(_)));
+
+        // The trial fails, so the breaker opens again
+        record(&breaker, true);
+        assert!(breaker.is_open());
+        time.advance(OPEN_FOR);
+        assert_eq!(Permit::Allow, breaker.permit_attempt(1, None));
+
+        // The trial succeeds, so the breaker closes and starts over with an empty window
+        record(&breaker, false);
+        assert!(!breaker.is_open());
+        for _ in 0..3 {
+            record(&breaker, true);
+        }
+        assert_eq!(Permit::Allow, breaker.permit_attempt(1, None));
+    }
+}
@@ -64,6 +64,13 @@ where
 }
 
 /// Classifies response, timeout, and connector errors as retryable or not.
+///
+/// Response errors include failing to read the body of a non-streaming response, for example
+/// because the connection was dropped after the headers were received. These are retried: the body
+/// of a non-streaming response is read in full before it is deserialized, so the caller hasn't seen
+/// any of it. Like other transient errors, this doesn't depend on whether the operation is
+/// idempotent. The body of a streaming response is handed to the caller as it arrives instead, so
+/// failing to read it is an error returned by the body, and is never retried.
 #[derive(Debug, Default)]
 pub struct TransientErrorClassifier<E> {
     _inner: PhantomData<E>,
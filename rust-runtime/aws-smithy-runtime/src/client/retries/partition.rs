@@ -0,0 +1,382 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! The retry state of [`RetryPartition`]s, and its resolution for partitions split by endpoint or
+//! input.
+
+use crate::client::retries::{ClientRateLimiter, PartitionSplit, RetryPartition, TokenBucket};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::BeforeSerializationInterceptorContextRef;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugins;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use aws_smithy_types::endpoint::Endpoint;
+use aws_smithy_types::retry::RetryConfig;
+use once_cell::sync::OnceCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, Weak};
+
+/// The maximum number of split retry states kept per partition. Past that, idle ones are dropped.
+const MAX_SPLIT_STATES: usize = 256;
+
+/// The state of named partitions, so that clients using partitions with the same name share it.
+///
+/// This only holds weak references: the state is owned by the partitions, and dropped with them.
+static NAMED_PARTITIONS: Mutex<Option<HashMap<String, Weak<PartitionState>>>> = Mutex::new(None);
+
+/// The retry state of a [`RetryPartition`].
+#[derive(Debug, Default)]
+pub(crate) struct PartitionState {
+    unsplit: RetryState,
+    splits: BoundedPartitionMap<String, RetryState>,
+}
+
+impl PartitionState {
+    /// Returns the state of the partitions named `name`, creating it if no partition with that
+    /// name is alive.
+    pub(crate) fn named(name: &str) -> Arc<Self> {
+        let mut named = NAMED_PARTITIONS.lock().unwrap();
+        let named = named.get_or_insert_with(HashMap::new);
+        if let Some(state) = named.get(name).and_then(Weak::upgrade) {
+            return state;
+        }
+        named.retain(|_, state| state.strong_count() > 0);
+        let state = Arc::new(Self::default());
+        named.insert(name.to_string(), Arc::downgrade(&state));
+        state
+    }
+
+    fn retry_state(&self, key: Option<String>) -> RetryState {
+        match key {
+            None => self.unsplit.clone(),
+            Some(key) => self
+                .splits
+                .get_or_init(key, RetryState::default, RetryState::is_idle),
+        }
+    }
+}
+
+/// The token bucket and adaptive retry rate limiter of a partition, or of one of its splits.
+#[derive(Clone, Debug, Default)]
+struct RetryState {
+    token_bucket: TokenBucket,
+    // The rate limiter needs the current time to be created, so it's created on first use
+    rate_limiter: Arc<OnceCell<ClientRateLimiter>>,
+}
+
+impl RetryState {
+    fn is_idle(&self) -> bool {
+        self.token_bucket.is_idle()
+    }
+
+    /// Resolves the retry state to use for the current request.
+    fn from_config(cfg: &ConfigBag) -> Option<Self> {
+        let partition = configured_partition(cfg)?;
+        Some(partition.state.retry_state(split_key(partition, cfg)))
+    }
+}
+
+/// Returns the key `partition` is split by for the current request, if any.
+fn split_key(partition: &RetryPartition, cfg: &ConfigBag) -> Option<String> {
+    match &partition.split {
+        PartitionSplit::None => None,
+        PartitionSplit::ByEndpoint => cfg.load::<Endpoint>().and_then(endpoint_host),
+        PartitionSplit::ByInput(_) => cfg.load::<InputPartitionKey>().map(|key| key.0.clone()),
+    }
+}
+
+/// Returns the retry partition of the current request.
+///
+/// A partition set with [`RetryConfig::with_partition`] takes precedence over a [`RetryPartition`]
+/// put directly in the config bag.
+pub(crate) fn configured_partition(cfg: &ConfigBag) -> Option<&RetryPartition> {
+    cfg.load::<RetryConfig>()
+        .and_then(RetryConfig::partition)
+        .and_then(|partition| partition.as_any().downcast_ref::<RetryPartition>())
+        .or_else(|| cfg.load::<RetryPartition>())
+}
+
+/// Returns the token bucket to use for the current request.
+///
+/// A token bucket put directly in the config bag takes precedence over the one of the retry
+/// partition. Without either, retries aren't limited by a token bucket.
+pub(crate) fn token_bucket(cfg: &ConfigBag) -> Option<TokenBucket> {
+    if let Some(token_bucket) = cfg.load::<TokenBucket>() {
+        return Some(token_bucket.clone());
+    }
+    RetryState::from_config(cfg).map(|state| state.token_bucket)
+}
+
+/// Returns the adaptive retry rate limiter to use for the current request, creating it with
+/// `init` if this is its first use.
+///
+/// The rate limiter is keyed the same way as the token bucket of [`token_bucket`].
+pub(crate) fn client_rate_limiter(
+    cfg: &ConfigBag,
+    init: impl FnOnce() -> ClientRateLimiter,
+) -> Option<ClientRateLimiter> {
+    let state = RetryState::from_config(cfg)?;
+    let rate_limiter = state.rate_limiter.get_or_init(init).clone();
+    Some(rate_limiter)
+}
+
+/// Returns the token bucket that every request made with the given client runtime plugins
+/// withdraws from when retrying.
+///
+/// Returns `None` if the configured retry partition is split, since each endpoint or input key
+/// then has its own token bucket, or if the client configuration is invalid.
+pub fn resolve_retry_budget(plugins: &RuntimePlugins) -> Option<TokenBucket> {
+    let mut cfg = ConfigBag::base();
+    plugins.apply_client_configuration(&mut cfg).ok()?;
+    if cfg.load::<TokenBucket>().is_none()
+        && !matches!(configured_partition(&cfg)?.split, PartitionSplit::None)
+    {
+        return None;
+    }
+    token_bucket(&cfg)
+}
+
+fn endpoint_host(endpoint: &Endpoint) -> Option<String> {
+    let uri = endpoint.url().parse::<http_02x::Uri>().ok()?;
+    uri.host().map(str::to_ascii_lowercase)
+}
+
+/// The key returned by a [`RetryPartition::by_input`] function for the current input.
+#[derive(Clone, Debug)]
+struct InputPartitionKey(String);
+
+impl Storable for InputPartitionKey {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Computes the partition key of [`RetryPartition::by_input`] partitions while the input is
+/// still available.
+#[derive(Debug, Default)]
+pub(crate) struct RetryPartitionInterceptor;
+
+impl Intercept for RetryPartitionInterceptor {
+    fn name(&self) -> &'static str {
+        "RetryPartitionInterceptor"
+    }
+
+    fn read_before_serialization(
+        &self,
+        context: &BeforeSerializationInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let key = match configured_partition(cfg).map(|partition| &partition.split) {
+            Some(PartitionSplit::ByInput(key_fn)) => key_fn(context.input()),
+            _ => None,
+        };
+        if let Some(key) = key {
+            cfg.interceptor_state().store_put(InputPartitionKey(key));
+        }
+        Ok(())
+    }
+}
+
+/// A map of shared state that drops the least recently used idle values once it holds more than
+/// `max_len` of them.
+#[derive(Debug)]
+struct BoundedPartitionMap<K, V> {
+    inner: Mutex<Option<Inner<K, V>>>,
+    max_len: usize,
+}
+
+#[derive(Debug)]
+struct Inner<K, V> {
+    values: HashMap<K, (V, u64)>,
+    clock: u64,
+}
+
+impl<K, V> BoundedPartitionMap<K, V> {
+    fn new(max_len: usize) -> Self {
+        Self {
+            inner: Mutex::new(None),
+            max_len,
+        }
+    }
+}
+
+impl<K, V> Default for BoundedPartitionMap<K, V> {
+    fn default() -> Self {
+        Self::new(MAX_SPLIT_STATES)
+    }
+}
+
+impl<K, V> BoundedPartitionMap<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+{
+    /// Gets the value for `key`, initializing it with `init` if it doesn't exist.
+    ///
+    /// When a value is inserted while the map is full, the least recently used value for which
+    /// `is_idle` returns `true` is dropped. If no value is idle, the map grows past its limit.
+    fn get_or_init(&self, key: K, init: impl FnOnce() -> V, is_idle: impl Fn(&V) -> bool) -> V {
+        let mut inner = self.inner.lock().unwrap();
+        let inner = inner.get_or_insert_with(|| Inner {
+            values: HashMap::new(),
+            clock: 0,
+        });
+        inner.clock += 1;
+        let now = inner.clock;
+        if let Some((value, last_used)) = inner.values.get_mut(&key) {
+            *last_used = now;
+            return value.clone();
+        }
+        if inner.values.len() >= self.max_len {
+            let least_recently_used = inner
+                .values
+                .iter()
+                .filter(|(_, (value, _))| is_idle(value))
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = least_recently_used {
+                inner.values.remove(&key);
+            }
+        }
+        let value = init();
+        inner.values.insert(key, (value.clone(), now));
+        value
+    }
+
+    #[cfg(test)]
+    fn contains(&self, key: &K) -> bool {
+        self.inner
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(false, |inner| inner.values.contains_key(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_types::config_bag::Layer;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn idle_values_are_evicted_least_recently_used_first() {
+        let map = BoundedPartitionMap::<&str, Arc<AtomicBool>>::new(2);
+        let is_idle = |busy: &Arc<AtomicBool>| !busy.load(Ordering::Relaxed);
+        let a = map.get_or_init("a", Default::default, is_idle);
+        let _b = map.get_or_init("b", Default::default, is_idle);
+        a.store(true, Ordering::Relaxed);
+
+        // "a" is the least recently used, but it is busy, so "b" is evicted
+        let _c = map.get_or_init("c", Default::default, is_idle);
+        assert!(map.contains(&"a"));
+        assert!(!map.contains(&"b"));
+
+        // once nothing is idle, the map grows
+        let c = map.get_or_init("c", || unreachable!(), is_idle);
+        c.store(true, Ordering::Relaxed);
+        let _d = map.get_or_init("d", Default::default, is_idle);
+        assert!(map.contains(&"a") && map.contains(&"c") && map.contains(&"d"));
+    }
+
+    fn config(partition: RetryPartition, endpoint: Option<&'static str>) -> ConfigBag {
+        let mut layer = Layer::new("test");
+        layer.store_put(partition);
+        if let Some(url) = endpoint {
+            layer.store_put(Endpoint::builder().url(url).build());
+        }
+        ConfigBag::of_layers(vec![layer])
+    }
+
+    fn plugins(partition: RetryPartition, token_bucket: Option<TokenBucket>) -> RuntimePlugins {
+        let mut layer = Layer::new("test");
+        layer.store_put(partition);
+        if let Some(token_bucket) = token_bucket {
+            layer.store_put(token_bucket);
+        }
+        RuntimePlugins::new().with_client_plugin(
+            aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin::new()
+                .with_config(layer.freeze()),
+        )
+    }
+
+    #[test]
+    fn retry_budget_is_resolved_for_unsplit_partitions() {
+        let partition = RetryPartition::new("resolve-retry-budget-test");
+        let budget = resolve_retry_budget(&plugins(partition.clone(), None)).expect("unsplit");
+        // resolving again returns the same bucket
+        let again = resolve_retry_budget(&plugins(partition, None)).unwrap();
+        let _permit = budget.acquire(&aws_smithy_types::retry::ErrorKind::ServerError);
+        assert_eq!(495, again.snapshot().available());
+
+        assert!(resolve_retry_budget(&plugins(RetryPartition::by_endpoint(), None)).is_none());
+
+        // a token bucket put in the config takes precedence
+        let budget = resolve_retry_budget(&plugins(
+            RetryPartition::by_endpoint(),
+            Some(TokenBucket::new(10)),
+        ))
+        .unwrap();
+        assert_eq!(10, budget.snapshot().capacity());
+    }
+
+    #[test]
+    fn partitions_split_by_endpoint_are_keyed_by_host() {
+        let key = |partition: RetryPartition, endpoint| {
+            let cfg = config(partition, Some(endpoint));
+            split_key(configured_partition(&cfg).unwrap(), &cfg)
+        };
+        let a = key(RetryPartition::by_endpoint(), "https://A.example.com/path");
+        let b = key(RetryPartition::by_endpoint(), "https://a.example.com:443");
+        let c = key(RetryPartition::by_endpoint(), "https://b.example.com");
+        assert_eq!(Some("a.example.com"), a.as_deref());
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        let unsplit = key(RetryPartition::new("test"), "https://a.example.com");
+        assert_eq!(None, unsplit);
+    }
+
+    #[test]
+    fn rate_limiters_are_keyed_like_token_buckets() {
+        let partition = RetryPartition::by_endpoint();
+        let state =
+            |endpoint| RetryState::from_config(&config(partition.clone(), Some(endpoint))).unwrap();
+        let (a, also_a, b) = (
+            state("https://a.example.com"),
+            state("https://a.example.com/other"),
+            state("https://b.example.com"),
+        );
+        assert!(Arc::ptr_eq(&a.rate_limiter, &also_a.rate_limiter));
+        assert!(!Arc::ptr_eq(&a.rate_limiter, &b.rate_limiter));
+    }
+
+    #[test]
+    fn retry_config_partition_takes_precedence() {
+        let mut layer = Layer::new("test");
+        layer.store_put(RetryPartition::new("retry-config-precedence-test"));
+        layer.store_put(RetryConfig::standard().with_partition(RetryPartition::by_endpoint()));
+        let cfg = ConfigBag::of_layers(vec![layer]);
+        assert_eq!("endpoint", configured_partition(&cfg).unwrap().name());
+    }
+
+    #[test]
+    fn named_state_is_shared_while_in_use_and_then_dropped() {
+        let name = "named-state-test";
+        let a = RetryPartition::new(name);
+        let b = RetryPartition::new(name);
+        assert!(Arc::ptr_eq(&a.state, &b.state));
+        assert_eq!(a, b);
+
+        // unique partitions never share state, even with the same name
+        assert_ne!(RetryPartition::unique(name), RetryPartition::unique(name));
+
+        let state = Arc::downgrade(&a.state);
+        drop((a, b));
+        assert!(state.upgrade().is_none());
+    }
+}
@@ -4,7 +4,61 @@
  */
 
 mod never;
+mod shadow;
 pub(crate) mod standard;
 
 pub use never::NeverRetryStrategy;
+pub use shadow::{RetryDivergence, ShadowRetrySnapshot, ShadowRetryStrategy};
 pub use standard::StandardRetryStrategy;
+
+use aws_smithy_runtime_api::client::retries::classifiers::{RetryAction, RetryReason};
+use aws_smithy_runtime_api::client::retries::gate::{
+    Permit, RetryGate, RetryGateDenied, SharedRetryGate,
+};
+use aws_smithy_runtime_api::client::retries::ShouldAttempt;
+use aws_smithy_types::config_bag::ConfigBag;
+use std::time::Duration;
+use tracing::debug;
+
+/// Asks the retry gate, if one is configured, whether `attempt` may be made.
+///
+/// Returns the delay the gate requires before the attempt, or the reason the gate denied it.
+fn permit_attempt(
+    cfg: &ConfigBag,
+    attempt: u32,
+    prior_error: Option<&RetryReason>,
+) -> Result<Option<Duration>, RetryGateDenied> {
+    match cfg.load::<SharedRetryGate>() {
+        None => Ok(None),
+        Some(gate) => match gate.permit_attempt(attempt, prior_error) {
+            Permit::Allow => Ok(None),
+            Permit::Deny(reason) => {
+                debug!(attempt, %reason, "the retry gate denied the attempt");
+                Err(RetryGateDenied::new(reason))
+            }
+            Permit::DelayBy(delay) => {
+                debug!(attempt, "the retry gate requested a {delay:?} delay");
+                Ok(Some(delay))
+            }
+        },
+    }
+}
+
+/// Tells the retry gate, if one is configured, how the result of an attempt was classified.
+fn record_attempt(cfg: &ConfigBag, result: &RetryAction) {
+    if let Some(gate) = cfg.load::<SharedRetryGate>() {
+        gate.record_attempt(result);
+    }
+}
+
+/// Delays an attempt by at least `delay`, keeping a longer delay if there already is one.
+fn delay_at_least(should_attempt: ShouldAttempt, delay: Option<Duration>) -> ShouldAttempt {
+    match (should_attempt, delay) {
+        (should_attempt, None) => should_attempt,
+        (ShouldAttempt::Yes, Some(delay)) => ShouldAttempt::YesAfterDelay(delay),
+        (ShouldAttempt::YesAfterDelay(current), Some(delay)) => {
+            ShouldAttempt::YesAfterDelay(current.max(delay))
+        }
+        (ShouldAttempt::No, Some(_)) => ShouldAttempt::No,
+    }
+}
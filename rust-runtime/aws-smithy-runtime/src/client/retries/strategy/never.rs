@@ -3,8 +3,11 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::client::retries::classifiers::run_classifiers_on_ctx;
+use crate::client::retries::strategy::{delay_at_least, permit_attempt, record_attempt};
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::interceptors::context::InterceptorContext;
+use aws_smithy_runtime_api::client::retries::gate::SharedRetryGate;
 use aws_smithy_runtime_api::client::retries::{RetryStrategy, ShouldAttempt};
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_types::config_bag::ConfigBag;
@@ -25,17 +28,23 @@ impl RetryStrategy for NeverRetryStrategy {
     fn should_attempt_initial_request(
         &self,
         _runtime_components: &RuntimeComponents,
-        _cfg: &ConfigBag,
+        cfg: &ConfigBag,
     ) -> Result<ShouldAttempt, BoxError> {
-        Ok(ShouldAttempt::Yes)
+        let gate_delay = permit_attempt(cfg, 1, None)?;
+        Ok(delay_at_least(ShouldAttempt::Yes, gate_delay))
     }
 
     fn should_attempt_retry(
         &self,
-        _context: &InterceptorContext,
-        _runtime_components: &RuntimeComponents,
-        _cfg: &ConfigBag,
+        context: &InterceptorContext,
+        runtime_components: &RuntimeComponents,
+        cfg: &ConfigBag,
     ) -> Result<ShouldAttempt, BoxError> {
+        // Only classify the result when a retry gate needs to know about it
+        if cfg.load::<SharedRetryGate>().is_some() {
+            let retry_classifiers = runtime_components.retry_classifiers();
+            record_attempt(cfg, &run_classifiers_on_ctx(retry_classifiers, context));
+        }
         Ok(ShouldAttempt::No)
     }
 }
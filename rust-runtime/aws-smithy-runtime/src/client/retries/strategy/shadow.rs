@@ -0,0 +1,464 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::client::retries::{RetryPartition, TokenBucket};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::InterceptorContext;
+use aws_smithy_runtime_api::client::retries::{
+    RequestAttempts, RetryStrategy, SharedRetryStrategy, ShouldAttempt,
+};
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::config_bag::{ConfigBag, Layer};
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::retry::RetryConfig;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::debug;
+
+const DEFAULT_DELAY_TOLERANCE: Duration = Duration::from_secs(1);
+
+type Observer = Arc<dyn Fn(&RetryDivergence) + Send + Sync>;
+
+/// Retry strategy that runs a shadow retry strategy alongside a primary one, and records where
+/// their decisions diverge.
+///
+/// The primary strategy decides whether and when attempts are made. The shadow strategy is asked
+/// the same questions with the same inputs, but its decisions are only compared with the
+/// primary's and never acted on. This makes it possible to try out a new retry strategy or retry
+/// mode (for example, adaptive retries in place of standard retries) in production before
+/// switching to it.
+///
+/// The shadow strategy doesn't share any state with the primary one: it withdraws from its own
+/// [`TokenBucket`], uses its own client rate limiter, and isn't subject to the retry gate. It only
+/// sees the retry config (which can be replaced with
+/// [`with_shadow_retry_config`](ShadowRetryStrategy::with_shadow_retry_config)) and number of
+/// attempts from the config bag, and the runtime components without the random source. If the shadow strategy fails, the failure is counted and otherwise ignored.
+///
+/// Divergences are reported to the observers added with
+/// [`observe`](ShadowRetryStrategy::observe), logged with a `debug` level tracing event, and
+/// counted in [`snapshot`](ShadowRetryStrategy::snapshot). Clones of a `ShadowRetryStrategy` share
+/// their observers and counts.
+///
+/// # Examples
+///
+/// ```
+/// use aws_smithy_runtime::client::retries::strategy::{ShadowRetryStrategy, StandardRetryStrategy};
+/// use aws_smithy_types::retry::RetryConfig;
+///
+/// let primary = StandardRetryStrategy::new();
+/// let shadow = StandardRetryStrategy::new();
+/// let strategy = ShadowRetryStrategy::new(primary, shadow)
+///     .with_shadow_retry_config(RetryConfig::adaptive());
+/// strategy.observe(|divergence| println!("the adaptive retry mode diverged: {divergence:?}"));
+/// ```
+#[derive(Clone)]
+pub struct ShadowRetryStrategy {
+    primary: SharedRetryStrategy,
+    shadow: SharedRetryStrategy,
+    shadow_retry_config: Option<RetryConfig>,
+    shadow_token_bucket: TokenBucket,
+    shadow_partition: RetryPartition,
+    delay_tolerance: Duration,
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    evaluations: AtomicU64,
+    shadow_would_attempt: AtomicU64,
+    shadow_would_not_attempt: AtomicU64,
+    delay_differs: AtomicU64,
+    shadow_failures: AtomicU64,
+    observers: RwLock<Vec<Observer>>,
+}
+
+impl fmt::Debug for ShadowRetryStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShadowRetryStrategy")
+            .field("primary", &self.primary)
+            .field("shadow", &self.shadow)
+            .field("shadow_retry_config", &self.shadow_retry_config)
+            .field("shadow_token_bucket", &self.shadow_token_bucket)
+            .field("delay_tolerance", &self.delay_tolerance)
+            .field("snapshot", &self.snapshot())
+            .finish()
+    }
+}
+
+impl ShadowRetryStrategy {
+    /// Creates a new `ShadowRetryStrategy` that retries with `primary`, and compares its decisions
+    /// with those of `shadow`.
+    pub fn new(
+        primary: impl RetryStrategy + 'static,
+        shadow: impl RetryStrategy + 'static,
+    ) -> Self {
+        Self {
+            primary: primary.into_shared(),
+            shadow: shadow.into_shared(),
+            shadow_retry_config: None,
+            shadow_token_bucket: TokenBucket::default(),
+            shadow_partition: RetryPartition::unique("shadow"),
+            delay_tolerance: DEFAULT_DELAY_TOLERANCE,
+            inner: Default::default(),
+        }
+    }
+
+    /// Sets the retry config the shadow strategy sees, in place of the config of the client.
+    pub fn with_shadow_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.shadow_retry_config = Some(retry_config);
+        self
+    }
+
+    /// Sets the token bucket the shadow strategy withdraws from.
+    ///
+    /// By default, the shadow strategy has a token bucket of the default size to itself.
+    pub fn with_shadow_token_bucket(mut self, token_bucket: TokenBucket) -> Self {
+        self.shadow_token_bucket = token_bucket;
+        self
+    }
+
+    /// Sets how far apart the delays of the two strategies can be before they're considered to
+    /// diverge. Defaults to one second.
+    ///
+    /// Unless [`RetryConfig::with_use_static_exponential_base`] is set, retry delays are jittered,
+    /// so the delays of two identical strategies usually differ.
+    pub fn with_delay_tolerance(mut self, delay_tolerance: Duration) -> Self {
+        self.delay_tolerance = delay_tolerance;
+        self
+    }
+
+    /// Calls `observer` every time the decisions of the two strategies diverge.
+    ///
+    /// The observer is called on the task that sends the request, so it should return quickly.
+    pub fn observe(&self, observer: impl Fn(&RetryDivergence) + Send + Sync + 'static) {
+        self.inner
+            .observers
+            .write()
+            .unwrap()
+            .push(Arc::new(observer));
+    }
+
+    /// Returns the number of decisions compared so far, and how many of them diverged.
+    pub fn snapshot(&self) -> ShadowRetrySnapshot {
+        let load = |count: &AtomicU64| count.load(Ordering::Relaxed);
+        ShadowRetrySnapshot {
+            evaluations: load(&self.inner.evaluations),
+            shadow_would_attempt: load(&self.inner.shadow_would_attempt),
+            shadow_would_not_attempt: load(&self.inner.shadow_would_not_attempt),
+            delay_differs: load(&self.inner.delay_differs),
+            shadow_failures: load(&self.inner.shadow_failures),
+        }
+    }
+
+    /// Returns the config bag the shadow strategy is evaluated with.
+    fn shadow_cfg(&self, cfg: &ConfigBag) -> ConfigBag {
+        let mut layer = Layer::new("ShadowRetryStrategy");
+        if let Some(request_attempts) = cfg.load::<RequestAttempts>() {
+            layer.store_put(*request_attempts);
+        }
+        // A partition of its own keeps the shadow strategy off the primary's client rate limiter
+        layer.store_or_unset(
+            self.shadow_retry_config
+                .clone()
+                .or_else(|| cfg.load::<RetryConfig>().cloned())
+                .map(|retry_config| retry_config.with_partition(self.shadow_partition.clone())),
+        );
+        layer.store_put(self.shadow_partition.clone());
+        layer.store_put(self.shadow_token_bucket.clone());
+        ConfigBag::of_layers(vec![layer])
+    }
+
+    /// Returns the runtime components the shadow strategy is evaluated with.
+    fn shadow_runtime_components(
+        runtime_components: &RuntimeComponents,
+    ) -> Result<RuntimeComponents, BoxError> {
+        // Drawing from the random source would change the jitter of the primary's delays
+        let mut builder = runtime_components.to_builder();
+        builder.set_random_source(None);
+        Ok(builder.build()?)
+    }
+
+    fn compare(
+        &self,
+        attempt: u32,
+        primary: &ShouldAttempt,
+        shadow: Result<ShouldAttempt, BoxError>,
+    ) {
+        self.inner.evaluations.fetch_add(1, Ordering::Relaxed);
+        let shadow = match shadow {
+            Ok(shadow) => shadow,
+            Err(err) => {
+                self.inner.shadow_failures.fetch_add(1, Ordering::Relaxed);
+                debug!(
+                    attempt,
+                    error = %DisplayErrorContext(&*err),
+                    "the shadow retry strategy failed"
+                );
+                return;
+            }
+        };
+        let divergence = match (delay_of(primary), delay_of(&shadow)) {
+            (None, Some(shadow_delay)) => RetryDivergence::ShadowWouldAttempt {
+                attempt,
+                shadow_delay,
+            },
+            (Some(primary_delay), None) => RetryDivergence::ShadowWouldNotAttempt {
+                attempt,
+                primary_delay,
+            },
+            (Some(primary_delay), Some(shadow_delay))
+                if primary_delay.abs_diff(shadow_delay) > self.delay_tolerance =>
+            {
+                RetryDivergence::DelayDiffers {
+                    attempt,
+                    primary_delay,
+                    shadow_delay,
+                }
+            }
+            _ => return,
+        };
+        let count = match divergence {
+            RetryDivergence::ShadowWouldAttempt { .. } => &self.inner.shadow_would_attempt,
+            RetryDivergence::ShadowWouldNotAttempt { .. } => &self.inner.shadow_would_not_attempt,
+            RetryDivergence::DelayDiffers { .. } => &self.inner.delay_differs,
+        };
+        count.fetch_add(1, Ordering::Relaxed);
+        debug!("the shadow retry strategy diverged: {divergence:?}");
+        for observer in self.inner.observers.read().unwrap().iter() {
+            observer(&divergence);
+        }
+    }
+
+    fn evaluate_shadow(
+        &self,
+        runtime_components: &RuntimeComponents,
+        cfg: &ConfigBag,
+        evaluate: impl FnOnce(&RuntimeComponents, &ConfigBag) -> Result<ShouldAttempt, BoxError>,
+    ) -> Result<ShouldAttempt, BoxError> {
+        let runtime_components = Self::shadow_runtime_components(runtime_components)?;
+        evaluate(&runtime_components, &self.shadow_cfg(cfg))
+    }
+}
+
+/// Returns the delay before the attempt, or `None` if no attempt should be made.
+fn delay_of(should_attempt: &ShouldAttempt) -> Option<Duration> {
+    match should_attempt {
+        ShouldAttempt::Yes => Some(Duration::ZERO),
+        ShouldAttempt::YesAfterDelay(delay) => Some(*delay),
+        ShouldAttempt::No => None,
+    }
+}
+
+impl RetryStrategy for ShadowRetryStrategy {
+    fn should_attempt_initial_request(
+        &self,
+        runtime_components: &RuntimeComponents,
+        cfg: &ConfigBag,
+    ) -> Result<ShouldAttempt, BoxError> {
+        let primary = self
+            .primary
+            .should_attempt_initial_request(runtime_components, cfg)?;
+        let shadow = self.evaluate_shadow(runtime_components, cfg, |rc, cfg| {
+            self.shadow.should_attempt_initial_request(rc, cfg)
+        });
+        self.compare(1, &primary, shadow);
+        Ok(primary)
+    }
+
+    fn should_attempt_retry(
+        &self,
+        context: &InterceptorContext,
+        runtime_components: &RuntimeComponents,
+        cfg: &ConfigBag,
+    ) -> Result<ShouldAttempt, BoxError> {
+        let primary = self
+            .primary
+            .should_attempt_retry(context, runtime_components, cfg)?;
+        let shadow = self.evaluate_shadow(runtime_components, cfg, |rc, cfg| {
+            self.shadow.should_attempt_retry(context, rc, cfg)
+        });
+        let attempt = cfg
+            .load::<RequestAttempts>()
+            .map(|attempts| attempts.attempts() + 1)
+            .unwrap_or_default();
+        self.compare(attempt, &primary, shadow);
+        Ok(primary)
+    }
+}
+
+/// A decision of the shadow strategy of a [`ShadowRetryStrategy`] that differs from the
+/// primary's, passed to the observers added with [`ShadowRetryStrategy::observe`].
+///
+/// `attempt` is the number of the attempt that was being decided on, where the initial request is
+/// attempt 1.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RetryDivergence {
+    /// The shadow strategy would have made an attempt that the primary didn't make.
+    ShadowWouldAttempt {
+        /// The attempt number.
+        attempt: u32,
+        /// The delay the shadow strategy would have waited before the attempt.
+        shadow_delay: Duration,
+    },
+    /// The primary made an attempt that the shadow strategy wouldn't have made.
+    ShadowWouldNotAttempt {
+        /// The attempt number.
+        attempt: u32,
+        /// The delay the primary waited before the attempt.
+        primary_delay: Duration,
+    },
+    /// Both strategies would make the attempt, but their delays are further apart than the
+    /// tolerance.
+    DelayDiffers {
+        /// The attempt number.
+        attempt: u32,
+        /// The delay the primary waited before the attempt.
+        primary_delay: Duration,
+        /// The delay the shadow strategy would have waited before the attempt.
+        shadow_delay: Duration,
+    },
+}
+
+/// Counts of the decisions compared by a [`ShadowRetryStrategy`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShadowRetrySnapshot {
+    evaluations: u64,
+    shadow_would_attempt: u64,
+    shadow_would_not_attempt: u64,
+    delay_differs: u64,
+    shadow_failures: u64,
+}
+
+impl ShadowRetrySnapshot {
+    /// Returns the number of decisions compared, including those where the shadow strategy failed.
+    pub fn evaluations(&self) -> u64 {
+        self.evaluations
+    }
+
+    /// Returns the number of [`RetryDivergence::ShadowWouldAttempt`] divergences.
+    pub fn shadow_would_attempt(&self) -> u64 {
+        self.shadow_would_attempt
+    }
+
+    /// Returns the number of [`RetryDivergence::ShadowWouldNotAttempt`] divergences.
+    pub fn shadow_would_not_attempt(&self) -> u64 {
+        self.shadow_would_not_attempt
+    }
+
+    /// Returns the number of [`RetryDivergence::DelayDiffers`] divergences.
+    pub fn delay_differs(&self) -> u64 {
+        self.delay_differs
+    }
+
+    /// Returns the total number of divergences.
+    pub fn divergences(&self) -> u64 {
+        self.shadow_would_attempt + self.shadow_would_not_attempt + self.delay_differs
+    }
+
+    /// Returns the number of times the shadow strategy failed to make a decision.
+    pub fn shadow_failures(&self) -> u64 {
+        self.shadow_failures
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use super::*;
+    use crate::client::retries::strategy::StandardRetryStrategy;
+    use aws_smithy_runtime_api::client::interceptors::context::Input;
+    use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
+    use aws_smithy_runtime_api::client::retries::classifiers::SharedRetryClassifier;
+    use aws_smithy_runtime_api::client::retries::AlwaysRetry;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::retry::ErrorKind;
+    use std::sync::Mutex;
+
+    fn retry_config(max_attempts: u32, initial_backoff: Duration) -> RetryConfig {
+        RetryConfig::standard()
+            .with_use_static_exponential_base(true)
+            .with_max_attempts(max_attempts)
+            .with_initial_backoff(initial_backoff)
+    }
+
+    #[test]
+    fn divergences_are_recorded_without_affecting_the_primary() {
+        let rc = RuntimeComponentsBuilder::for_tests()
+            .with_retry_classifier(SharedRetryClassifier::new(AlwaysRetry(
+                ErrorKind::ThrottlingError,
+            )))
+            .build()
+            .unwrap();
+        let primary_bucket = TokenBucket::default();
+        let shadow_bucket = TokenBucket::default();
+        let mut layer = Layer::new("test");
+        layer.store_put(retry_config(3, Duration::from_secs(1)));
+        layer.store_put(primary_bucket.clone());
+        let mut cfg = ConfigBag::of_layers(vec![layer]);
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.set_output_or_error(Err(OrchestratorError::other("throttled")));
+
+        let strategy =
+            ShadowRetryStrategy::new(StandardRetryStrategy::new(), StandardRetryStrategy::new())
+                .with_shadow_retry_config(retry_config(5, Duration::from_millis(1500)))
+                .with_shadow_token_bucket(shadow_bucket.clone())
+                .with_delay_tolerance(Duration::from_millis(500));
+        let divergences = Arc::new(Mutex::new(Vec::new()));
+        strategy.observe({
+            let divergences = divergences.clone();
+            move |divergence| divergences.lock().unwrap().push(divergence.clone())
+        });
+
+        assert_eq!(
+            ShouldAttempt::Yes,
+            strategy.should_attempt_initial_request(&rc, &cfg).unwrap()
+        );
+        // The primary retries after 1s and then 2s, and gives up after three attempts. The shadow
+        // would retry after 1.5s (within the tolerance) and then 3s, and keep going.
+        let mut decisions = Vec::new();
+        for attempts in 1..=3 {
+            cfg.interceptor_state()
+                .store_put(RequestAttempts::new(attempts));
+            decisions.push(strategy.should_attempt_retry(&ctx, &rc, &cfg).unwrap());
+        }
+        assert_eq!(
+            vec![
+                ShouldAttempt::YesAfterDelay(Duration::from_secs(1)),
+                ShouldAttempt::YesAfterDelay(Duration::from_secs(2)),
+                ShouldAttempt::No,
+            ],
+            decisions
+        );
+        // Each strategy withdrew from its own bucket
+        assert_eq!(490, primary_bucket.snapshot().available());
+        assert_eq!(485, shadow_bucket.snapshot().available());
+
+        assert_eq!(
+            vec![
+                RetryDivergence::DelayDiffers {
+                    attempt: 3,
+                    primary_delay: Duration::from_secs(2),
+                    shadow_delay: Duration::from_secs(3),
+                },
+                RetryDivergence::ShadowWouldAttempt {
+                    attempt: 4,
+                    shadow_delay: Duration::from_secs(6),
+                },
+            ],
+            *divergences.lock().unwrap()
+        );
+        let snapshot = strategy.snapshot();
+        assert_eq!(4, snapshot.evaluations());
+        assert_eq!(1, snapshot.delay_differs());
+        assert_eq!(1, snapshot.shadow_would_attempt());
+        assert_eq!(0, snapshot.shadow_would_not_attempt());
+        assert_eq!(2, snapshot.divergences());
+        assert_eq!(0, snapshot.shadow_failures());
+    }
+}
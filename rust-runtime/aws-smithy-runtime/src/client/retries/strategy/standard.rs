@@ -3,36 +3,35 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use std::error::Error as StdError;
 use std::sync::Mutex;
 use std::time::{Duration, SystemTime};
 
-use tokio::sync::OwnedSemaphorePermit;
 use tracing::debug;
 
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::interceptors::context::InterceptorContext;
+use aws_smithy_runtime_api::client::random::RandomSource;
 use aws_smithy_runtime_api::client::retries::classifiers::{RetryAction, RetryReason};
 use aws_smithy_runtime_api::client::retries::{RequestAttempts, RetryStrategy, ShouldAttempt};
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
-use aws_smithy_types::retry::{ErrorKind, RetryConfig, RetryMode};
+use aws_smithy_types::error::ErrorMetadata;
+use aws_smithy_types::retry::{ErrorKind, RetryConfig, RetryDelayContext, RetryMode};
 
 use crate::client::retries::classifiers::run_classifiers_on_ctx;
 use crate::client::retries::client_rate_limiter::{ClientRateLimiter, RequestReason};
+use crate::client::retries::partition::{client_rate_limiter, token_bucket};
 use crate::client::retries::strategy::standard::ReleaseResult::{
     APermitWasReleased, NoPermitWasReleased,
 };
-use crate::client::retries::token_bucket::TokenBucket;
-use crate::client::retries::{ClientRateLimiterPartition, RetryPartition};
-use crate::static_partition_map::StaticPartitionMap;
-
-static CLIENT_RATE_LIMITER: StaticPartitionMap<ClientRateLimiterPartition, ClientRateLimiter> =
-    StaticPartitionMap::new();
+use crate::client::retries::strategy::{delay_at_least, permit_attempt, record_attempt};
+use crate::client::retries::token_bucket::RetryPermit;
 
 /// Retry strategy with exponential backoff, max attempts, and a token bucket.
 #[derive(Debug, Default)]
 pub struct StandardRetryStrategy {
-    retry_permit: Mutex<Option<OwnedSemaphorePermit>>,
+    retry_permit: Mutex<Option<RetryPermit>>,
 }
 
 impl Storable for StandardRetryStrategy {
@@ -56,7 +55,7 @@ impl StandardRetryStrategy {
         }
     }
 
-    fn set_retry_permit(&self, new_retry_permit: OwnedSemaphorePermit) {
+    fn set_retry_permit(&self, new_retry_permit: RetryPermit) {
         let mut old_retry_permit = self.retry_permit.lock().unwrap();
         if let Some(p) = old_retry_permit.replace(new_retry_permit) {
             // Whenever we set a new retry permit, and it replaces the old one, we need to "forget"
@@ -73,19 +72,14 @@ impl StandardRetryStrategy {
         let retry_config = cfg.load::<RetryConfig>().expect("retry config is required");
         if retry_config.mode() == RetryMode::Adaptive {
             if let Some(time_source) = runtime_components.time_source() {
-                let retry_partition = cfg.load::<RetryPartition>().expect("set in default config");
-                let seconds_since_unix_epoch = time_source
-                    .now()
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .expect("the present takes place after the UNIX_EPOCH")
-                    .as_secs_f64();
-                let client_rate_limiter_partition =
-                    ClientRateLimiterPartition::new(retry_partition.clone());
-                let client_rate_limiter = CLIENT_RATE_LIMITER
-                    .get_or_init(client_rate_limiter_partition, || {
-                        ClientRateLimiter::new(seconds_since_unix_epoch)
-                    });
-                return Some(client_rate_limiter);
+                return client_rate_limiter(cfg, || {
+                    let seconds_since_unix_epoch = time_source
+                        .now()
+                        .duration_since(SystemTime::UNIX_EPOCH)
+                        .expect("the present takes place after the UNIX_EPOCH")
+                        .as_secs_f64();
+                    ClientRateLimiter::new(seconds_since_unix_epoch)
+                });
             }
         }
         None
@@ -97,12 +91,13 @@ impl StandardRetryStrategy {
         cfg: &ConfigBag,
         retry_cfg: &RetryConfig,
         retry_reason: &RetryAction,
+        error_code: Option<&str>,
     ) -> Result<Duration, ShouldAttempt> {
         let request_attempts = cfg
             .load::<RequestAttempts>()
             .expect("at least one request attempt is made before any retry is attempted")
             .attempts();
-        let token_bucket = cfg.load::<TokenBucket>();
+        let token_bucket = token_bucket(cfg);
 
         match retry_reason {
             RetryAction::RetryIndicated(RetryReason::RetryableError { kind, retry_after }) => {
@@ -112,7 +107,16 @@ impl StandardRetryStrategy {
                     *kind == ErrorKind::ThrottlingError,
                 );
 
-                if let Some(delay) = *retry_after {
+                let delay_override = retry_cfg.delay_override(
+                    &RetryDelayContext::new(*kind, request_attempts)
+                        .with_error_code(error_code)
+                        .with_retry_after(*retry_after),
+                );
+                if let Some(delay) = delay_override {
+                    let delay = delay.min(retry_cfg.max_backoff());
+                    debug!("the delay override requested a {delay:?} delay before retrying");
+                    Ok(delay)
+                } else if let Some(delay) = *retry_after {
                     let delay = delay.min(retry_cfg.max_backoff());
                     debug!("explicit request from server to delay {delay:?} before retrying");
                     Ok(delay)
@@ -136,7 +140,10 @@ impl StandardRetryStrategy {
                     let base = if retry_cfg.use_static_exponential_base() {
                         1.0
                     } else {
-                        fastrand::f64()
+                        runtime_components
+                            .random_source()
+                            .map(|random_source| random_source.next_f64())
+                            .unwrap_or_else(fastrand::f64)
                     };
                     Ok(calculate_exponential_backoff(
                         // Generate a random base multiplier to create jitter
@@ -176,19 +183,25 @@ impl RetryStrategy for StandardRetryStrategy {
         runtime_components: &RuntimeComponents,
         cfg: &ConfigBag,
     ) -> Result<ShouldAttempt, BoxError> {
+        // Ask the retry gate first, so that a denied request doesn't take a rate limiter token
+        let gate_delay = permit_attempt(cfg, 1, None)?;
+
         if let Some(crl) = Self::adaptive_retry_rate_limiter(runtime_components, cfg) {
             let seconds_since_unix_epoch = get_seconds_since_unix_epoch(runtime_components);
             if let Err(delay) = crl.acquire_permission_to_send_a_request(
                 seconds_since_unix_epoch,
                 RequestReason::InitialRequest,
             ) {
-                return Ok(ShouldAttempt::YesAfterDelay(delay));
+                return Ok(delay_at_least(
+                    ShouldAttempt::YesAfterDelay(delay),
+                    gate_delay,
+                ));
             }
         } else {
             debug!("no client rate limiter configured, so no token is required for the initial request.");
         }
 
-        Ok(ShouldAttempt::Yes)
+        Ok(delay_at_least(ShouldAttempt::Yes, gate_delay))
     }
 
     fn should_attempt_retry(
@@ -199,11 +212,17 @@ impl RetryStrategy for StandardRetryStrategy {
     ) -> Result<ShouldAttempt, BoxError> {
         let retry_cfg = cfg.load::<RetryConfig>().expect("retry config is required");
 
-        // Check if we're out of attempts
         let request_attempts = cfg
             .load::<RequestAttempts>()
             .expect("at least one request attempt is made before any retry is attempted")
             .attempts();
+
+        // Run the classifier against the context to determine if we should retry
+        let retry_classifiers = runtime_components.retry_classifiers();
+        let classifier_result = run_classifiers_on_ctx(retry_classifiers, ctx);
+        record_attempt(cfg, &classifier_result);
+
+        // Check if we're out of attempts
         if request_attempts >= retry_cfg.max_attempts() {
             update_rate_limiter_if_exists(runtime_components, cfg, false);
 
@@ -215,22 +234,27 @@ impl RetryStrategy for StandardRetryStrategy {
             return Ok(ShouldAttempt::No);
         }
 
-        // Run the classifier against the context to determine if we should retry
-        let retry_classifiers = runtime_components.retry_classifiers();
-        let classifier_result = run_classifiers_on_ctx(retry_classifiers, ctx);
+        if let RetryAction::RetryIndicated(reason) = &classifier_result {
+            // Ask the retry gate before calculating the backoff, so that a denied retry doesn't
+            // take a retry permit. A denied retry ends the retry loop with the last attempt's error.
+            let gate_delay = match permit_attempt(cfg, request_attempts + 1, Some(reason)) {
+                Ok(gate_delay) => gate_delay,
+                Err(_) => return Ok(ShouldAttempt::No),
+            };
 
-        if classifier_result.should_retry() {
             // Calculate the appropriate backoff time.
             let backoff = match self.calculate_backoff(
                 runtime_components,
                 cfg,
                 retry_cfg,
                 &classifier_result,
+                error_code(ctx),
             ) {
                 Ok(value) => value,
                 // In some cases, backoff calculation will decide that we shouldn't retry at all.
                 Err(value) => return Ok(value),
             };
+            let backoff = backoff.max(gate_delay.unwrap_or_default());
             debug!(
                 "attempt #{request_attempts} failed with {:?}; retrying after {:?}",
                 classifier_result, backoff,
@@ -239,7 +263,7 @@ impl RetryStrategy for StandardRetryStrategy {
             Ok(ShouldAttempt::YesAfterDelay(backoff))
         } else {
             debug!("attempt #{request_attempts} succeeded, no retry necessary");
-            if let Some(tb) = cfg.load::<TokenBucket>() {
+            if let Some(tb) = token_bucket(cfg) {
                 // If this retry strategy is holding any permits, release them back to the bucket.
                 if let NoPermitWasReleased = self.release_retry_permit() {
                     // In the event that there was no retry permit to release, we generate new
@@ -256,6 +280,20 @@ impl RetryStrategy for StandardRetryStrategy {
     }
 }
 
+/// Returns the code of the error the attempt failed with, if it isn't modeled.
+///
+/// Errors that aren't modeled carry their [`ErrorMetadata`], either directly or as their source.
+fn error_code(ctx: &InterceptorContext) -> Option<&str> {
+    let error = ctx.output_or_error()?.err()?.as_operation_error()?;
+    error
+        .downcast_ref::<ErrorMetadata>()
+        .or_else(|| {
+            std::iter::successors(error.source(), |error| error.source())
+                .find_map(|error| error.downcast_ref::<ErrorMetadata>())
+        })
+        .and_then(ErrorMetadata::code)
+}
+
 fn update_rate_limiter_if_exists(
     runtime_components: &RuntimeComponents,
     cfg: &ConfigBag,
@@ -886,6 +924,73 @@ mod tests {
         assert_eq!(token_bucket.available_permits(), 480);
     }
 
+    #[test]
+    fn adaptive_retry_slows_down_after_throttling_and_recovers() {
+        use crate::client::retries::RetryPartition;
+        use aws_smithy_async::test_util::ManualTimeSource;
+        use std::time::SystemTime;
+
+        let time_source = ManualTimeSource::new(SystemTime::UNIX_EPOCH + Duration::from_secs(1000));
+        let rc =
+            || RuntimeComponentsBuilder::for_tests().with_time_source(Some(time_source.clone()));
+        let throttled_rc = rc()
+            .with_retry_classifier(AlwaysRetry(ErrorKind::ThrottlingError))
+            .build()
+            .unwrap();
+        let successful_rc = rc().build().unwrap();
+
+        let mut layer = Layer::new("test");
+        layer.store_put(RetryConfig::adaptive().with_max_attempts(3));
+        // The rate limiter is shared by every client in the partition, so don't share it with other tests
+        layer.store_put(RetryPartition::new(
+            "adaptive_retry_slows_down_after_throttling_and_recovers",
+        ));
+        layer.store_put(RequestAttempts::new(1));
+        let cfg = ConfigBag::of_layers(vec![layer]);
+        let strategy = StandardRetryStrategy::new();
+
+        // Requests aren't rate limited until the client is throttled
+        assert_eq!(
+            ShouldAttempt::Yes,
+            strategy
+                .should_attempt_initial_request(&successful_rc, &cfg)
+                .unwrap()
+        );
+
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.set_output_or_error(Err(OrchestratorError::other("throttled")));
+        assert_eq!(
+            ShouldAttempt::YesAfterDelay(Duration::from_secs(20)),
+            strategy
+                .should_attempt_retry(&ctx, &throttled_rc, &cfg)
+                .unwrap()
+        );
+        // Once throttled, new requests must wait for the slower send rate
+        assert_eq!(
+            ShouldAttempt::YesAfterDelay(Duration::from_secs(22)),
+            strategy
+                .should_attempt_initial_request(&successful_rc, &cfg)
+                .unwrap()
+        );
+
+        // Successful requests restore the send rate over time
+        time_source.advance(Duration::from_secs(60));
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        ctx.set_output_or_error(Ok(Output::doesnt_matter()));
+        assert_eq!(
+            ShouldAttempt::No,
+            strategy
+                .should_attempt_retry(&ctx, &successful_rc, &cfg)
+                .unwrap()
+        );
+        assert_eq!(
+            ShouldAttempt::Yes,
+            strategy
+                .should_attempt_initial_request(&successful_rc, &cfg)
+                .unwrap()
+        );
+    }
+
     const MAX_BACKOFF: Duration = Duration::from_secs(20);
 
     #[test]
@@ -5,7 +5,9 @@
 
 use aws_smithy_types::config_bag::{Storable, StoreReplace};
 use aws_smithy_types::retry::ErrorKind;
-use std::sync::Arc;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tracing::trace;
 
@@ -14,13 +16,35 @@ const RETRY_COST: u32 = 5;
 const RETRY_TIMEOUT_COST: u32 = RETRY_COST * 2;
 const PERMIT_REGENERATION_AMOUNT: usize = 1;
 
+type Observer = Arc<dyn Fn(&RetryBudgetEvent) + Send + Sync>;
+
 /// Token bucket used for standard and adaptive retry.
-#[derive(Clone, Debug)]
+///
+/// Every retry withdraws tokens from the bucket, and successful requests deposit them back. Once
+/// the bucket is empty, no more retries are attempted. Clones of a token bucket share their
+/// tokens, so a token bucket can be given to several clients to share one retry budget.
+#[derive(Clone)]
 pub struct TokenBucket {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
     semaphore: Arc<Semaphore>,
     max_permits: usize,
     timeout_retry_cost: u32,
     retry_cost: u32,
+    recent_withdrawals: AtomicU64,
+    observers: RwLock<Vec<Observer>>,
+}
+
+impl fmt::Debug for TokenBucket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenBucket")
+            .field("snapshot", &self.snapshot())
+            .field("timeout_retry_cost", &self.inner.timeout_retry_cost)
+            .field("retry_cost", &self.inner.retry_cost)
+            .finish()
+    }
 }
 
 impl Storable for TokenBucket {
@@ -29,12 +53,7 @@ impl Storable for TokenBucket {
 
 impl Default for TokenBucket {
     fn default() -> Self {
-        Self {
-            semaphore: Arc::new(Semaphore::new(DEFAULT_CAPACITY)),
-            max_permits: DEFAULT_CAPACITY,
-            timeout_retry_cost: RETRY_TIMEOUT_COST,
-            retry_cost: RETRY_COST,
-        }
+        Self::new(DEFAULT_CAPACITY)
     }
 }
 
@@ -42,35 +61,250 @@ impl TokenBucket {
     /// Creates a new `TokenBucket` with the given initial quota.
     pub fn new(initial_quota: usize) -> Self {
         Self {
-            semaphore: Arc::new(Semaphore::new(initial_quota)),
-            max_permits: initial_quota,
-            retry_cost: RETRY_COST,
-            timeout_retry_cost: RETRY_TIMEOUT_COST,
+            inner: Arc::new(Inner {
+                semaphore: Arc::new(Semaphore::new(initial_quota)),
+                max_permits: initial_quota,
+                retry_cost: RETRY_COST,
+                timeout_retry_cost: RETRY_TIMEOUT_COST,
+                recent_withdrawals: AtomicU64::new(0),
+                observers: RwLock::new(Vec::new()),
+            }),
         }
     }
 
-    pub(crate) fn acquire(&self, err: &ErrorKind) -> Option<OwnedSemaphorePermit> {
+    /// Returns the current state of this bucket.
+    ///
+    /// This only reads atomic counters, so it is cheap enough to call on every request.
+    pub fn snapshot(&self) -> RetryBudgetSnapshot {
+        RetryBudgetSnapshot {
+            capacity: self.inner.max_permits,
+            available: self.inner.semaphore.available_permits(),
+            recent_withdrawals: self.inner.recent_withdrawals.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Calls `observer` every time tokens are withdrawn from, or deposited into, this bucket.
+    ///
+    /// The observer is shared by all clones of this bucket, and is called on the task that
+    /// sends the request, so it should return quickly.
+    pub fn observe(&self, observer: impl Fn(&RetryBudgetEvent) + Send + Sync + 'static) {
+        self.inner
+            .observers
+            .write()
+            .unwrap()
+            .push(Arc::new(observer));
+    }
+
+    pub(crate) fn acquire(&self, err: &ErrorKind) -> Option<RetryPermit> {
         let retry_cost = if err == &ErrorKind::TransientError {
-            self.timeout_retry_cost
+            self.inner.timeout_retry_cost
         } else {
-            self.retry_cost
+            self.inner.retry_cost
         };
 
-        self.semaphore
+        let permit = self
+            .inner
+            .semaphore
             .clone()
             .try_acquire_many_owned(retry_cost)
-            .ok()
+            .ok()?;
+        self.inner
+            .recent_withdrawals
+            .fetch_add(1, Ordering::Relaxed);
+        self.notify(|snapshot| RetryBudgetEvent::Withdrawal {
+            amount: retry_cost as usize,
+            snapshot,
+        });
+        Some(RetryPermit {
+            permit: Some(permit),
+            amount: retry_cost as usize,
+            bucket: self.clone(),
+        })
     }
 
     pub(crate) fn regenerate_a_token(&self) {
-        if self.semaphore.available_permits() < (self.max_permits) {
+        if self.inner.semaphore.available_permits() < (self.inner.max_permits) {
             trace!("adding {PERMIT_REGENERATION_AMOUNT} back into the bucket");
-            self.semaphore.add_permits(PERMIT_REGENERATION_AMOUNT)
+            self.inner.semaphore.add_permits(PERMIT_REGENERATION_AMOUNT);
+            self.deposited(PERMIT_REGENERATION_AMOUNT);
         }
     }
 
+    fn deposited(&self, amount: usize) {
+        if self.inner.semaphore.available_permits() >= self.inner.max_permits {
+            self.inner.recent_withdrawals.store(0, Ordering::Relaxed);
+        }
+        self.notify(|snapshot| RetryBudgetEvent::Deposit { amount, snapshot });
+    }
+
+    fn notify(&self, event: impl FnOnce(RetryBudgetSnapshot) -> RetryBudgetEvent) {
+        let event = event(self.snapshot());
+        let snapshot = event.snapshot();
+        trace!(
+            capacity = snapshot.capacity,
+            available = snapshot.available,
+            recent_withdrawals = snapshot.recent_withdrawals,
+            "retry budget changed: {event:?}"
+        );
+        for observer in self.inner.observers.read().unwrap().iter() {
+            observer(&event);
+        }
+    }
+
+    /// Returns `true` if no request is using this bucket or holding a retry permit from it.
+    pub(crate) fn is_idle(&self) -> bool {
+        Arc::strong_count(&self.inner) == 1
+    }
+
     #[cfg(all(test, feature = "test-util"))]
     pub(crate) fn available_permits(&self) -> usize {
-        self.semaphore.available_permits()
+        self.inner.semaphore.available_permits()
+    }
+}
+
+/// Tokens withdrawn from a [`TokenBucket`] for a retry.
+///
+/// The tokens are deposited back into the bucket when the permit is dropped, unless it is
+/// [forgotten](RetryPermit::forget).
+pub(crate) struct RetryPermit {
+    permit: Option<OwnedSemaphorePermit>,
+    amount: usize,
+    bucket: TokenBucket,
+}
+
+impl RetryPermit {
+    /// Removes the tokens of this permit from the bucket forever.
+    pub(crate) fn forget(mut self) {
+        if let Some(permit) = self.permit.take() {
+            permit.forget();
+        }
+    }
+}
+
+impl fmt::Debug for RetryPermit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPermit")
+            .field("amount", &self.amount)
+            .finish()
+    }
+}
+
+impl Drop for RetryPermit {
+    fn drop(&mut self) {
+        if let Some(permit) = self.permit.take() {
+            drop(permit);
+            self.bucket.deposited(self.amount);
+        }
+    }
+}
+
+/// The state of a [`TokenBucket`] at a point in time.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryBudgetSnapshot {
+    capacity: usize,
+    available: usize,
+    recent_withdrawals: u64,
+}
+
+impl RetryBudgetSnapshot {
+    /// Returns the number of tokens the bucket holds when it is full.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of tokens currently in the bucket.
+    pub fn available(&self) -> usize {
+        self.available
+    }
+
+    /// Returns the number of retries that withdrew tokens since the bucket was last full.
+    pub fn recent_withdrawals(&self) -> u64 {
+        self.recent_withdrawals
+    }
+}
+
+/// A change to the tokens in a [`TokenBucket`], passed to the observers added with
+/// [`TokenBucket::observe`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RetryBudgetEvent {
+    /// Tokens were withdrawn for a retry.
+    Withdrawal {
+        /// The number of tokens withdrawn.
+        amount: usize,
+        /// The state of the bucket after the withdrawal.
+        snapshot: RetryBudgetSnapshot,
+    },
+    /// Tokens were deposited after a successful request.
+    Deposit {
+        /// The number of tokens deposited.
+        amount: usize,
+        /// The state of the bucket after the deposit.
+        snapshot: RetryBudgetSnapshot,
+    },
+}
+
+impl RetryBudgetEvent {
+    /// Returns the state of the bucket after this event.
+    pub fn snapshot(&self) -> RetryBudgetSnapshot {
+        match self {
+            Self::Withdrawal { snapshot, .. } | Self::Deposit { snapshot, .. } => *snapshot,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn snapshot_tracks_withdrawals_and_deposits() {
+        let bucket = TokenBucket::new(20);
+        let events = Arc::new(Mutex::new(Vec::new()));
+        bucket.observe({
+            let events = events.clone();
+            move |event| events.lock().unwrap().push(event.clone())
+        });
+
+        let first = bucket.acquire(&ErrorKind::ServerError).unwrap();
+        let second = bucket.acquire(&ErrorKind::TransientError).unwrap();
+        let snapshot = bucket.snapshot();
+        assert_eq!(20, snapshot.capacity());
+        assert_eq!(5, snapshot.available());
+        assert_eq!(2, snapshot.recent_withdrawals());
+        assert!(bucket.acquire(&ErrorKind::TransientError).is_none());
+
+        drop(second);
+        assert_eq!(15, bucket.snapshot().available());
+        assert_eq!(2, bucket.snapshot().recent_withdrawals());
+        first.forget();
+        for _ in 0..5 {
+            bucket.regenerate_a_token();
+        }
+        // the bucket is full again
+        assert_eq!(
+            RetryBudgetSnapshot {
+                capacity: 20,
+                available: 20,
+                recent_withdrawals: 0
+            },
+            bucket.snapshot()
+        );
+        bucket.regenerate_a_token();
+        assert_eq!(20, bucket.snapshot().available());
+
+        let events = events.lock().unwrap();
+        assert_eq!(8, events.len());
+        assert!(matches!(
+            events[1],
+            RetryBudgetEvent::Withdrawal { amount: 10, snapshot } if snapshot.available() == 5
+        ));
+        assert!(matches!(
+            events[2],
+            RetryBudgetEvent::Deposit { amount: 10, snapshot } if snapshot.available() == 15
+        ));
+        assert_eq!(20, events[7].snapshot().available());
     }
 }
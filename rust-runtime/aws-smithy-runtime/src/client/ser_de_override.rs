@@ -0,0 +1,295 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Break-glass overrides of the generated serializer and deserializer of an operation.
+//!
+//! When a service ships a wire-format bug, these make it possible to patch requests and
+//! responses before a client with a fix is released. They bypass the guarantees of the generated
+//! code, so they should only be used as a stopgap. A `warn` level event is logged every time an
+//! override is used.
+
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{Error, Input, Output};
+use aws_smithy_runtime_api::client::orchestrator::{
+    HttpRequest, HttpResponse, Metadata, OrchestratorError,
+};
+use aws_smithy_runtime_api::client::ser_de::SerializeRequest;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use std::any::type_name;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+type SerializerOverride = Arc<
+    dyn Fn(Input, &dyn SerializeRequest, &mut ConfigBag) -> Result<HttpRequest, BoxError>
+        + Send
+        + Sync,
+>;
+type DeserializerOverride = Arc<dyn Fn(HttpResponse) -> DeserOverride<Output> + Send + Sync>;
+
+/// What to do with a response, as decided by a deserializer override.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum DeserOverride<O> {
+    /// Deserialize the given response, which may have been rewritten, with the generated
+    /// deserializer.
+    Deserialize(HttpResponse),
+    /// Skip the generated deserializer, and return the given output, or fail with the given error.
+    Replace(Result<O, BoxError>),
+}
+
+/// Serializer and deserializer overrides, keyed by operation name.
+///
+/// Generated clients store this in their config with the `override_serializer` and
+/// `override_deserializer` config builder methods, which check that the operation exists.
+#[derive(Clone, Default)]
+pub struct SerDeOverrides {
+    serializers: HashMap<String, SerializerOverride>,
+    deserializers: HashMap<String, DeserializerOverride>,
+}
+
+impl SerDeOverrides {
+    /// Creates an empty set of overrides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the serializer of the operation named `operation_name`.
+    ///
+    /// The generated serializer still runs. `serializer` is called with the operation's input and
+    /// the request that the generated serializer produced, and returns the request to send. `I`
+    /// must be the input type of the operation, or requests for the operation fail.
+    pub fn override_serializer<I>(
+        &mut self,
+        operation_name: impl Into<String>,
+        serializer: impl Fn(&I, HttpRequest) -> Result<HttpRequest, BoxError> + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        I: Clone + Send + Sync + fmt::Debug + 'static,
+    {
+        let operation_name = operation_name.into();
+        let name = operation_name.clone();
+        self.serializers.insert(
+            operation_name,
+            Arc::new(move |input, generated, cfg| {
+                let input = input.downcast::<I>().map_err(|_| {
+                    format!(
+                        "the serializer override for `{name}` expects a `{}` input",
+                        type_name::<I>()
+                    )
+                })?;
+                let request = generated.serialize_input(Input::erase(input.clone()), cfg)?;
+                serializer(&input, request)
+            }),
+        );
+        self
+    }
+
+    /// Overrides the deserializer of the operation named `operation_name`.
+    ///
+    /// `deserializer` is called with the full response before the generated deserializer runs. It
+    /// can either return a rewritten response for the generated deserializer to deserialize, or
+    /// replace deserialization entirely. `O` must be the output type of the operation, or
+    /// requests for the operation fail. Responses with a streaming body aren't passed to the
+    /// override.
+    pub fn override_deserializer<O>(
+        &mut self,
+        operation_name: impl Into<String>,
+        deserializer: impl Fn(HttpResponse) -> DeserOverride<O> + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        O: Send + Sync + fmt::Debug + 'static,
+    {
+        self.deserializers.insert(
+            operation_name.into(),
+            Arc::new(move |response| match deserializer(response) {
+                DeserOverride::Deserialize(response) => DeserOverride::Deserialize(response),
+                DeserOverride::Replace(output) => DeserOverride::Replace(output.map(Output::erase)),
+            }),
+        );
+        self
+    }
+}
+
+impl fmt::Debug for SerDeOverrides {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SerDeOverrides")
+            .field("serializers", &self.serializers.keys())
+            .field("deserializers", &self.deserializers.keys())
+            .finish()
+    }
+}
+
+impl Storable for SerDeOverrides {
+    type Storer = StoreReplace<Self>;
+}
+
+fn operation_name(cfg: &ConfigBag) -> &str {
+    cfg.load::<Metadata>().map(Metadata::name).unwrap_or("")
+}
+
+/// Serializes `input` with `generated`, and the serializer override of the operation, if there is one.
+pub(crate) fn serialize(
+    generated: &dyn SerializeRequest,
+    input: Input,
+    cfg: &mut ConfigBag,
+) -> Result<HttpRequest, BoxError> {
+    let operation = operation_name(cfg);
+    let serializer_override = cfg
+        .load::<SerDeOverrides>()
+        .and_then(|overrides| overrides.serializers.get(operation))
+        .cloned();
+    match serializer_override {
+        Some(serializer_override) => {
+            tracing::warn!(
+                operation = %operation_name(cfg),
+                "the serializer of this operation is overridden; remove the override once the client is fixed"
+            );
+            serializer_override(input, generated, cfg)
+        }
+        None => generated.serialize_input(input, cfg),
+    }
+}
+
+/// Runs the deserializer override of the operation, if there is one.
+///
+/// Returns the output or error when the override replaced deserialization. Otherwise, `response`
+/// may have been rewritten by the override, and must be deserialized by the generated deserializer.
+pub(crate) fn deserialize_override(
+    cfg: &ConfigBag,
+    response: &mut HttpResponse,
+) -> Option<Result<Output, OrchestratorError<Error>>> {
+    let operation = operation_name(cfg);
+    let deserializer_override = cfg.load::<SerDeOverrides>()?.deserializers.get(operation)?;
+    tracing::warn!(
+        operation = %operation,
+        "the deserializer of this operation is overridden; remove the override once the client is fixed"
+    );
+    let status = response.status();
+    let original = std::mem::replace(response, HttpResponse::new(status, SdkBody::taken()));
+    match deserializer_override(original) {
+        DeserOverride::Deserialize(rewritten) => {
+            *response = rewritten;
+            None
+        }
+        DeserOverride::Replace(output) => Some(output.map_err(OrchestratorError::other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_runtime_api::http::StatusCode;
+    use aws_smithy_types::config_bag::Layer;
+    use tracing_test::traced_test;
+
+    #[derive(Debug)]
+    struct GeneratedSerializer;
+
+    impl SerializeRequest for GeneratedSerializer {
+        fn serialize_input(
+            &self,
+            input: Input,
+            _cfg: &mut ConfigBag,
+        ) -> Result<HttpRequest, BoxError> {
+            let input = input.downcast::<String>().expect("input is a String");
+            Ok(HttpRequest::new(SdkBody::from(input)))
+        }
+    }
+
+    fn cfg(operation: &'static str, overrides: SerDeOverrides) -> ConfigBag {
+        let mut layer = Layer::new("test");
+        layer.store_put(Metadata::new(operation, "Things"));
+        layer.store_put(overrides);
+        ConfigBag::of_layers(vec![layer])
+    }
+
+    fn response(body: &'static str) -> HttpResponse {
+        HttpResponse::new(StatusCode::try_from(200).unwrap(), SdkBody::from(body))
+    }
+
+    #[test]
+    #[traced_test]
+    fn serializer_overrides_patch_the_generated_request() {
+        let mut overrides = SerDeOverrides::new();
+        overrides.override_serializer("PutThing", |input: &String, mut request: HttpRequest| {
+            request
+                .headers_mut()
+                .insert("x-length", input.len().to_string());
+            Ok(request)
+        });
+
+        let mut patched = cfg("PutThing", overrides.clone());
+        let request = serialize(
+            &GeneratedSerializer,
+            Input::erase("abc".to_string()),
+            &mut patched,
+        )
+        .unwrap();
+        assert_eq!(
+            Some("abc"),
+            request
+                .body()
+                .bytes()
+                .map(|b| std::str::from_utf8(b).unwrap())
+        );
+        assert_eq!(Some("3"), request.headers().get("x-length"));
+        assert!(logs_contain(
+            "the serializer of this operation is overridden"
+        ));
+
+        let mut untouched = cfg("GetThing", overrides.clone());
+        let request = serialize(
+            &GeneratedSerializer,
+            Input::erase("abc".to_string()),
+            &mut untouched,
+        )
+        .unwrap();
+        assert_eq!(None, request.headers().get("x-length"));
+
+        let mut overrides = SerDeOverrides::new();
+        overrides.override_serializer("PutThing", |_: &u8, request| Ok(request));
+        let mut wrong_input = cfg("PutThing", overrides);
+        let err = serialize(
+            &GeneratedSerializer,
+            Input::erase("abc".to_string()),
+            &mut wrong_input,
+        )
+        .unwrap_err();
+        assert_eq!(
+            "the serializer override for `PutThing` expects a `u8` input",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn deserializer_overrides_rewrite_or_replace_responses() {
+        let mut overrides = SerDeOverrides::new();
+        overrides
+            .override_deserializer("GetThing", |mut response: HttpResponse| {
+                let body = std::str::from_utf8(response.body().bytes().unwrap()).unwrap();
+                *response.body_mut() = SdkBody::from(body.replace("Nmae", "Name"));
+                DeserOverride::<String>::Deserialize(response)
+            })
+            .override_deserializer("ListThings", |_| DeserOverride::Replace(Ok(vec!["thing"])));
+
+        let mut rewritten = response(r#"{"Nmae":"a"}"#);
+        assert!(
+            deserialize_override(&cfg("GetThing", overrides.clone()), &mut rewritten).is_none()
+        );
+        assert_eq!(Some(&b"{\"Name\":\"a\"}"[..]), rewritten.body().bytes());
+
+        let mut replaced = response("");
+        let output = deserialize_override(&cfg("ListThings", overrides.clone()), &mut replaced)
+            .unwrap()
+            .unwrap();
+        assert_eq!(vec!["thing"], output.downcast::<Vec<&str>>().unwrap());
+
+        let mut untouched = response("{}");
+        assert!(deserialize_override(&cfg("PutThing", overrides), &mut untouched).is_none());
+        assert_eq!(Some(&b"{}"[..]), untouched.body().bytes());
+    }
+}
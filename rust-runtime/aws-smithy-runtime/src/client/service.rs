@@ -0,0 +1,189 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::fmt;
+use std::future::Future;
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+/// [`Service`] that sends its requests with a client, so that a client operation can be
+/// composed with `tower` middleware, such as rate limits or load shedding.
+///
+/// The service is made of a client, and of a function that sends a request with a clone of that
+/// client. With a generated client, the request is usually the input builder of an operation,
+/// which is sent with its `send_with` method:
+///
+/// ```rust,ignore
+/// use aws_smithy_runtime::client::service::ClientService;
+/// use tower::ServiceBuilder;
+/// use tower::limit::RateLimitLayer;
+///
+/// let get_item = ClientService::new(client, |client: Client, input: GetItemInputBuilder| async move {
+///     input.send_with(&client).await
+/// });
+/// // At most 10 requests per second
+/// let mut get_item = ServiceBuilder::new()
+///     .layer(RateLimitLayer::new(10, Duration::from_secs(1)))
+///     .service(get_item);
+/// ```
+///
+/// Any other client works the same way, like an
+/// [`Operation`](crate::client::orchestrator::operation::Operation), which is sent with `invoke`.
+///
+/// # Readiness
+///
+/// [`poll_ready`](Service::poll_ready) always returns `Ready`: the client doesn't apply any
+/// backpressure of its own, since it can send any number of requests at the same time. Limits on
+/// how many requests are sent, and how fast, must come from layers wrapping this service.
+///
+/// # Concurrency
+///
+/// Every call sends its request with its own clone of the client, so the returned futures don't
+/// borrow the service and can run concurrently. Clones of the service share whatever their
+/// clients share, like the connection pool and the identity cache of a generated client.
+#[derive(Clone)]
+pub struct ClientService<C, F> {
+    client: C,
+    send: F,
+}
+
+impl<C, F> ClientService<C, F> {
+    /// Creates a service sending its requests with `send`, which is given a clone of `client`
+    /// for every request.
+    pub fn new(client: C, send: F) -> Self {
+        Self { client, send }
+    }
+
+    /// Returns the client requests are sent with.
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// Consumes the service, returning its client.
+    pub fn into_client(self) -> C {
+        self.client
+    }
+}
+
+impl<C, F> fmt::Debug for ClientService<C, F>
+where
+    C: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientService")
+            .field("client", &self.client)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C, F, Req, Fut, O, E> Service<Req> for ClientService<C, F>
+where
+    C: Clone,
+    F: Fn(C, Req) -> Fut,
+    Fut: Future<Output = Result<O, E>>,
+{
+    type Response = O;
+    type Error = E;
+    type Future = Fut;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Req) -> Self::Future {
+        (self.send)(self.client.clone(), request)
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use super::ClientService;
+    use crate::client::http::test_util::infallible_client_fn;
+    use crate::client::orchestrator::operation::Operation;
+    use aws_smithy_async::assert_elapsed;
+    use aws_smithy_runtime_api::client::orchestrator::{HttpResponse, OrchestratorError};
+    use aws_smithy_runtime_api::client::result::SdkError;
+    use aws_smithy_types::body::SdkBody;
+    use std::convert::Infallible;
+    use std::time::Duration;
+    use tower::limit::RateLimitLayer;
+    use tower::{Service, ServiceBuilder, ServiceExt};
+
+    type EchoOperation = Operation<String, String, Infallible>;
+
+    /// An operation whose output is its input, as echoed by a mocked HTTP client.
+    fn echo_operation() -> EchoOperation {
+        Operation::builder()
+            .service_name("test")
+            .operation_name("echo")
+            .no_auth()
+            .no_retry()
+            .endpoint_url("http://localhost:1234")
+            .http_client(infallible_client_fn(|request| {
+                http_02x::Response::builder()
+                    .status(200)
+                    .body(SdkBody::from(request.body().bytes().unwrap()))
+                    .unwrap()
+            }))
+            .serializer(|input: String| {
+                Ok(http_02x::Request::new(SdkBody::from(input))
+                    .try_into()
+                    .unwrap())
+            })
+            .deserializer(|response: &HttpResponse| {
+                let body = response.body().bytes().unwrap();
+                Ok::<_, OrchestratorError<Infallible>>(String::from_utf8(body.into()).unwrap())
+            })
+            .build()
+    }
+
+    async fn echo(
+        operation: EchoOperation,
+        input: String,
+    ) -> Result<String, SdkError<Infallible, HttpResponse>> {
+        operation.invoke(input).await
+    }
+
+    #[tokio::test]
+    async fn operations_are_invoked_through_the_service() {
+        let service = ClientService::new(echo_operation(), echo);
+        let output = service.oneshot("hello".to_string()).await.unwrap();
+        assert_eq!("hello", output);
+    }
+
+    #[tokio::test]
+    async fn clones_of_the_service_send_requests_concurrently() {
+        let service = ClientService::new(echo_operation(), echo);
+        let mut first = service.clone();
+        let mut second = service;
+        let (first, second) = tokio::join!(
+            first.call("first".to_string()),
+            second.call("second".to_string()),
+        );
+        assert_eq!("first", first.unwrap());
+        assert_eq!("second", second.unwrap());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limits_are_applied_by_layers() {
+        let mut service = ServiceBuilder::new()
+            .layer(RateLimitLayer::new(1, Duration::from_secs(1)))
+            .service(ClientService::new(echo_operation(), echo));
+
+        let started = tokio::time::Instant::now();
+        for input in ["a", "b", "c"] {
+            let output = service
+                .ready()
+                .await
+                .unwrap()
+                .call(input.to_string())
+                .await
+                .unwrap();
+            assert_eq!(input, output);
+        }
+        // The first request is sent right away, and the next ones one second apart
+        assert_elapsed!(started, Duration::from_secs(2));
+    }
+}
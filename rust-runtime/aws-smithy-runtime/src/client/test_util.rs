@@ -8,3 +8,6 @@ pub mod deserializer;
 
 /// Test request serializer implementations.
 pub mod serializer;
+
+/// Test sleep configuration.
+pub mod sleep;
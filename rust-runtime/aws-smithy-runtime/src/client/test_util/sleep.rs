@@ -0,0 +1,31 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+
+/// Whether operations panic when a component sleeps for real, rather than with the configured
+/// sleep implementation.
+///
+/// Tests that configure a sleep implementation that completes instantly can enable this to catch
+/// components that bypass it. See
+/// [`ForbidRealSleep`](aws_smithy_async::rt::sleep::ForbidRealSleep) for which sleeps are caught.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PanicOnRealSleep(bool);
+
+impl PanicOnRealSleep {
+    /// Creates a new `PanicOnRealSleep`.
+    pub fn new(enabled: bool) -> Self {
+        Self(enabled)
+    }
+
+    /// Returns `true` if operations panic when a component sleeps for real.
+    pub fn is_enabled(&self) -> bool {
+        self.0
+    }
+}
+
+impl Storable for PanicOnRealSleep {
+    type Storer = StoreReplace<Self>;
+}
@@ -0,0 +1,168 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::client::orchestrator::Metadata;
+use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugin;
+use aws_smithy_types::config_bag::{ConfigBag, FrozenLayer, Layer, Storable, StoreReplace};
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// The maximum number of distinct unknown fields that are logged per process.
+const MAX_WARNED_UNKNOWN_FIELDS: usize = 1024;
+
+static WARNED: Lazy<Mutex<HashSet<(String, String)>>> = Lazy::new(Default::default);
+
+type Callback = Arc<dyn Fn(&str, &str) + Send + Sync>;
+
+#[derive(Clone)]
+enum Mode {
+    Warn,
+    Callback(Callback),
+}
+
+/// Opt-in reporting of response fields that aren't part of the model ("model drift").
+///
+/// Deserializers skip fields they don't know about. When this is present in the config bag,
+/// the JSON pointer (for JSON responses) or element path (for XML responses) of each skipped field
+/// is reported, which makes it possible to notice when a service starts returning fields that
+/// the generated client doesn't model yet. At most
+/// [`MAX_RECORDED_UNKNOWN_FIELDS`](aws_smithy_types::unknown_fields::MAX_RECORDED_UNKNOWN_FIELDS)
+/// fields are reported per response. Streaming responses are not inspected.
+///
+/// This is a runtime plugin, so it can be added to a client's config or to a single operation:
+///
+/// ```no_run
+/// use aws_smithy_runtime::client::unknown_fields::UnknownFieldReporting;
+///
+/// let reporting = UnknownFieldReporting::callback(|path, operation| {
+///     println!("{operation} returned an unknown field at {path}");
+/// });
+/// # let _ = reporting;
+/// ```
+#[derive(Clone)]
+pub struct UnknownFieldReporting {
+    mode: Mode,
+}
+
+impl UnknownFieldReporting {
+    /// Logs unknown fields with a `warn` level tracing event.
+    ///
+    /// To avoid flooding logs, each unique field path is only logged once per operation per process.
+    pub fn warn() -> Self {
+        Self { mode: Mode::Warn }
+    }
+
+    /// Calls `on_unknown_field(path, operation)` for every unknown field in every response.
+    pub fn callback(on_unknown_field: impl Fn(&str, &str) + Send + Sync + 'static) -> Self {
+        Self {
+            mode: Mode::Callback(Arc::new(on_unknown_field)),
+        }
+    }
+
+    pub(crate) fn report(&self, cfg: &ConfigBag, paths: Vec<String>) {
+        if paths.is_empty() {
+            return;
+        }
+        let operation = cfg.load::<Metadata>().map(Metadata::name).unwrap_or("");
+        match &self.mode {
+            Mode::Warn => {
+                let mut warned = WARNED.lock().unwrap();
+                for path in paths {
+                    let key = (operation.to_string(), path);
+                    if warned.len() < MAX_WARNED_UNKNOWN_FIELDS && !warned.contains(&key) {
+                        tracing::warn!(
+                            operation = %key.0,
+                            path = %key.1,
+                            "response contained a field that isn't in the model"
+                        );
+                        warned.insert(key);
+                    }
+                }
+            }
+            Mode::Callback(callback) => {
+                for path in paths {
+                    callback(&path, operation);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for UnknownFieldReporting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = match self.mode {
+            Mode::Warn => "Warn",
+            Mode::Callback(_) => "Callback",
+        };
+        f.debug_struct("UnknownFieldReporting")
+            .field("mode", &mode)
+            .finish()
+    }
+}
+
+impl Storable for UnknownFieldReporting {
+    type Storer = StoreReplace<Self>;
+}
+
+impl RuntimePlugin for UnknownFieldReporting {
+    fn config(&self) -> Option<FrozenLayer> {
+        let mut layer = Layer::new("UnknownFieldReporting");
+        layer.store_put(self.clone());
+        Some(layer.freeze())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_test::traced_test;
+
+    fn cfg() -> ConfigBag {
+        let mut layer = Layer::new("test");
+        layer.store_put(Metadata::new("GetThing", "Things"));
+        ConfigBag::of_layers(vec![layer])
+    }
+
+    #[test]
+    fn callback_receives_every_path() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let reporting = UnknownFieldReporting::callback({
+            let seen = seen.clone();
+            move |path, operation| seen.lock().unwrap().push(format!("{operation}:{path}"))
+        });
+        for _ in 0..2 {
+            reporting.report(&cfg(), vec!["/a/b".into(), "/c".into()]);
+        }
+        assert_eq!(
+            vec![
+                "GetThing:/a/b",
+                "GetThing:/c",
+                "GetThing:/a/b",
+                "GetThing:/c"
+            ],
+            *seen.lock().unwrap()
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn warnings_are_only_logged_once_per_path() {
+        let reporting = UnknownFieldReporting::warn();
+        reporting.report(&cfg(), vec!["/warn-once".into()]);
+        reporting.report(&cfg(), vec!["/warn-once".into()]);
+        logs_assert(|lines| {
+            match lines
+                .iter()
+                .filter(|line| line.contains("/warn-once"))
+                .count()
+            {
+                1 => Ok(()),
+                n => Err(format!("expected one warning, found {n}")),
+            }
+        });
+    }
+}
@@ -5,7 +5,8 @@
 
 use std::future::Future;
 use std::marker::PhantomData;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 use tokio::sync::{OnceCell, RwLock};
 
@@ -20,6 +21,10 @@ pub struct ExpiringCache<T, E> {
     /// when the value is considered expired.
     buffer_time: Duration,
     value: Arc<RwLock<OnceCell<(T, SystemTime)>>>,
+    /// The last value cleared for expiring, until a new value has been loaded.
+    stale: Arc<Mutex<Option<T>>>,
+    /// Whether a new value is being loaded.
+    loading: Arc<AtomicBool>,
     _phantom: PhantomData<E>,
 }
 
@@ -28,11 +33,29 @@ impl<T, E> Clone for ExpiringCache<T, E> {
         Self {
             buffer_time: self.buffer_time,
             value: self.value.clone(),
+            stale: self.stale.clone(),
+            loading: self.loading.clone(),
             _phantom: Default::default(),
         }
     }
 }
 
+/// Marks the cache as loading until dropped.
+struct Loading(Arc<AtomicBool>);
+
+impl Loading {
+    fn start(loading: Arc<AtomicBool>) -> Self {
+        loading.store(true, Ordering::Release);
+        Self(loading)
+    }
+}
+
+impl Drop for Loading {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
 impl<T, E> ExpiringCache<T, E>
 where
     T: Clone,
@@ -42,6 +65,8 @@ where
         ExpiringCache {
             buffer_time,
             value: Arc::new(RwLock::new(OnceCell::new())),
+            stale: Default::default(),
+            loading: Default::default(),
             _phantom: Default::default(),
         }
     }
@@ -70,10 +95,43 @@ where
         Fut: Future<Output = Result<(T, SystemTime), E>>,
     {
         let lock = self.value.read().await;
-        let future = lock.get_or_try_init(f);
+        let future = lock.get_or_try_init(|| {
+            let loading = Loading::start(self.loading.clone());
+            let stale = self.stale.clone();
+            let future = f();
+            async move {
+                let _loading = loading;
+                let result = future.await;
+                if result.is_ok() {
+                    stale.lock().unwrap().take();
+                }
+                result
+            }
+        });
         future.await.map(|(value, _expiry)| value.clone())
     }
 
+    /// Returns the last value cleared by [`yield_or_clear_if_expired`](Self::yield_or_clear_if_expired)
+    /// if its replacement is being loaded.
+    pub fn stale_while_loading(&self) -> Option<T> {
+        if self.loading.load(Ordering::Acquire) {
+            self.stale.lock().unwrap().clone()
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the cached value with `value`, which expires at `expiration`.
+    ///
+    /// This is used to refresh a value before it expires: callers keep getting the current value
+    /// until it's replaced, rather than waiting for [`get_or_load`](Self::get_or_load).
+    pub async fn replace(&self, value: T, expiration: SystemTime) {
+        let mut lock = self.value.write().await;
+        *lock = OnceCell::new();
+        let _ = lock.set((value, expiration));
+        self.stale.lock().unwrap().take();
+    }
+
     /// If the value is expired, clears the cache. Otherwise, yields the current value.
     pub async fn yield_or_clear_if_expired(&self, now: SystemTime) -> Option<T> {
         // Short-circuit if the value is not expired
@@ -93,7 +151,8 @@ where
             // Also check that we're clearing the expired value and not a value
             // that has been refreshed by another thread.
             if expired(*expiration, self.buffer_time, now) {
-                *lock = OnceCell::new();
+                let (value, _expiration) = lock.take().expect("checked above");
+                *self.stale.lock().unwrap() = Some(value);
             }
         }
         None
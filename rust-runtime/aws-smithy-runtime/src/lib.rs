@@ -12,6 +12,7 @@
 //!
 //! - `http-auth`: Enables auth scheme and identity resolver implementations for HTTP API Key,
 //!   Basic Auth, Bearer Token, and Digest Auth.
+//! - `tower`: Enables adapting client operations into a [`tower_service::Service`].
 //! - `test-util`: Enables utilities for unit tests. DO NOT ENABLE IN PRODUCTION.
 
 #![warn(
@@ -0,0 +1,152 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_async::rt::sleep::TokioSleep;
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime_api::client::endpoint::{
+    EndpointFuture, EndpointResolverParams, ResolveEndpointAsync, SharedEndpointResolver,
+};
+use aws_smithy_runtime_api::client::interceptors::context::Input;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+use aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::endpoint::Endpoint;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::timeout::TimeoutConfig;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct TenantInput {
+    tenant_id: &'static str,
+}
+
+/// Routes requests to a host per tenant after looking it up asynchronously.
+#[derive(Debug)]
+struct TenantResolver {
+    lookup_delay: Duration,
+}
+
+impl ResolveEndpointAsync for TenantResolver {
+    fn resolve_endpoint_async<'a>(
+        &'a self,
+        _params: &'a EndpointResolverParams,
+        input: &'a Input,
+    ) -> EndpointFuture<'a> {
+        EndpointFuture::new(async move {
+            tokio::time::sleep(self.lookup_delay).await;
+            let input = input
+                .downcast_ref::<TenantInput>()
+                .ok_or("unexpected input type")?;
+            let host = match input.tenant_id {
+                "blue" => "blue.example.com",
+                _ => "green.example.com",
+            };
+            Ok(Endpoint::builder().url(format!("https://{host}")).build())
+        })
+    }
+}
+
+fn operation(
+    resolver: TenantResolver,
+    timeout_config: TimeoutConfig,
+    uris: Arc<Mutex<Vec<String>>>,
+) -> Operation<TenantInput, (), Infallible> {
+    Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .no_retry()
+        .endpoint_url("http://unused.localhost")
+        .runtime_plugin(
+            StaticRuntimePlugin::new().with_runtime_components(
+                RuntimeComponentsBuilder::new("async_endpoint_resolver")
+                    .with_endpoint_resolver(Some(SharedEndpointResolver::new_async(resolver))),
+            ),
+        )
+        .timeout_config(timeout_config)
+        .sleep_impl(TokioSleep::new())
+        .http_client(infallible_client_fn(move |request| {
+            uris.lock().unwrap().push(request.uri().to_string());
+            http_02x::Response::builder()
+                .status(200)
+                .body(SdkBody::empty())
+                .unwrap()
+        }))
+        .serializer(|_input: TenantInput| {
+            Ok(http_02x::Request::builder()
+                .uri("/tenant")
+                .body(SdkBody::empty())
+                .unwrap()
+                .try_into()
+                .unwrap())
+        })
+        .deserializer::<(), Infallible>(|_| Ok(()))
+        .build()
+}
+
+#[tokio::test]
+async fn async_resolver_routes_on_input_members() {
+    let uris = Arc::new(Mutex::new(Vec::new()));
+    let operation = operation(
+        TenantResolver {
+            lookup_delay: Duration::from_millis(1),
+        },
+        TimeoutConfig::builder().build(),
+        uris.clone(),
+    );
+
+    operation
+        .invoke(TenantInput { tenant_id: "blue" })
+        .await
+        .expect("success");
+    operation
+        .invoke(TenantInput { tenant_id: "green" })
+        .await
+        .expect("success");
+
+    assert_eq!(
+        vec![
+            "https://blue.example.com/tenant".to_string(),
+            "https://green.example.com/tenant".to_string()
+        ],
+        *uris.lock().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn slow_async_resolver_times_out_with_construction_failure() {
+    let uris = Arc::new(Mutex::new(Vec::new()));
+    let operation = operation(
+        TenantResolver {
+            lookup_delay: Duration::from_secs(60),
+        },
+        TimeoutConfig::builder()
+            .endpoint_resolution_timeout(Duration::from_millis(50))
+            .build(),
+        uris.clone(),
+    );
+
+    let err = operation
+        .invoke(TenantInput { tenant_id: "blue" })
+        .await
+        .expect_err("endpoint resolution should time out");
+
+    assert!(
+        matches!(err, SdkError::ConstructionFailure(_)),
+        "expected a construction failure, got {err:?}"
+    );
+    let message = format!("{}", DisplayErrorContext(&err));
+    assert!(
+        message.contains("endpoint resolution timed out"),
+        "{message}"
+    );
+    assert!(uris.lock().unwrap().is_empty());
+}
@@ -0,0 +1,144 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_async::rt::sleep::TokioSleep;
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::retries::classifiers::HttpStatusCodeClassifier;
+use aws_smithy_runtime::test_util::capture_test_logs::capture_test_logs;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::FinalizerInterceptorContextRef;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
+use aws_smithy_runtime_api::client::retries::ReplayInfo;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::body::{ReplaySource, SdkBody};
+use aws_smithy_types::byte_stream::ByteStream;
+use aws_smithy_types::config_bag::ConfigBag;
+use aws_smithy_types::retry::RetryConfig;
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Records the replay history of the operation once it completes.
+#[derive(Debug, Default)]
+struct RecordReplays(Arc<Mutex<Vec<ReplayInfo>>>);
+
+impl Intercept for RecordReplays {
+    fn name(&self) -> &'static str {
+        "RecordReplays"
+    }
+
+    fn read_after_execution(
+        &self,
+        _context: &FinalizerInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let mut history: Vec<_> = cfg.load::<ReplayInfo>().cloned().collect();
+        history.reverse();
+        *self.0.lock().unwrap() = history;
+        Ok(())
+    }
+}
+
+/// Sends the body created by `body` to a service that is always unavailable, and returns the
+/// replay history along with the number of requests sent.
+async fn send_until_retries_exhausted(
+    body: impl Fn() -> SdkBody + Send + Sync + 'static,
+) -> (Vec<ReplayInfo>, usize) {
+    let replays = Arc::new(Mutex::new(Vec::new()));
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let operation: Operation<(), (), Infallible> = Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .endpoint_url("http://localhost:1234")
+        .standard_retry(
+            &RetryConfig::standard()
+                .with_max_attempts(3)
+                .with_initial_backoff(Duration::ZERO),
+        )
+        .retry_classifier(HttpStatusCodeClassifier::default())
+        .sleep_impl(TokioSleep::new())
+        .interceptor(RecordReplays(replays.clone()))
+        .http_client(infallible_client_fn({
+            let attempts = attempts.clone();
+            move |_| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                http_02x::Response::builder()
+                    .status(503)
+                    .body(SdkBody::empty())
+                    .unwrap()
+            }
+        }))
+        .serializer(move |_| {
+            Ok(http_02x::Request::builder()
+                .method("PUT")
+                .body(body())
+                .unwrap()
+                .try_into()
+                .unwrap())
+        })
+        .deserializer(|_| Err(OrchestratorError::response("service unavailable".into())))
+        .build();
+
+    operation.invoke(()).await.expect_err("always unavailable");
+    let replays = replays.lock().unwrap().clone();
+    (replays, attempts.load(Ordering::SeqCst))
+}
+
+#[tokio::test]
+async fn file_backed_bodies_are_replayed_by_reopening_the_file() {
+    let path = std::env::temp_dir().join(format!("body-replay-{}", std::process::id()));
+    std::fs::write(&path, b"file contents").unwrap();
+    let body = ByteStream::from_path(&path).await.unwrap().into_inner();
+
+    let (replays, attempts) = send_until_retries_exhausted(move || body.try_clone().unwrap()).await;
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(3, attempts);
+    assert_eq!(
+        vec![
+            ReplayInfo::new(2, Some(ReplaySource::FileReopen), Some(13)),
+            ReplayInfo::new(3, Some(ReplaySource::FileReopen), Some(13)),
+        ],
+        replays
+    );
+}
+
+#[tokio::test]
+async fn in_memory_bodies_are_replayed_by_cloning() {
+    let (replays, attempts) = send_until_retries_exhausted(|| SdkBody::from("in memory")).await;
+
+    assert_eq!(3, attempts);
+    assert_eq!(
+        vec![
+            ReplayInfo::new(2, Some(ReplaySource::MemoryClone), Some(9)),
+            ReplayInfo::new(3, Some(ReplaySource::MemoryClone), Some(9)),
+        ],
+        replays
+    );
+}
+
+#[tokio::test]
+async fn one_shot_streams_suppress_retries_with_a_warning() {
+    let (_guard, logs) = capture_test_logs();
+
+    let (replays, attempts) =
+        send_until_retries_exhausted(|| SdkBody::from_body_0_4(SdkBody::from("one shot"))).await;
+
+    assert_eq!(1, attempts);
+    assert_eq!(vec![ReplayInfo::new(2, None, None)], replays);
+    let logs = logs.contents();
+    let warnings: Vec<_> = logs
+        .lines()
+        .filter(|line| line.contains("WARN") && line.contains("retries were suppressed"))
+        .collect();
+    assert_eq!(1, warnings.len(), "{logs}");
+}
@@ -0,0 +1,121 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_runtime::client::captured_headers::CaptureResponseHeaders;
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::AfterDeserializationInterceptorContextRef;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::captured_headers::{self, CapturedHeaders};
+use aws_smithy_types::config_bag::ConfigBag;
+use aws_smithy_types::error::ErrorMetadata;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Default)]
+struct SeenInConfig(Arc<Mutex<Option<CapturedHeaders>>>);
+
+impl Intercept for SeenInConfig {
+    fn name(&self) -> &'static str {
+        "SeenInConfig"
+    }
+
+    fn read_after_deserialization(
+        &self,
+        _context: &AfterDeserializationInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        *self.0.lock().unwrap() = cfg.load::<CapturedHeaders>().cloned();
+        Ok(())
+    }
+}
+
+fn operation(
+    status: u16,
+    seen_in_config: Arc<Mutex<Option<CapturedHeaders>>>,
+) -> Operation<(), Option<CapturedHeaders>, ErrorMetadata> {
+    Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .no_retry()
+        .endpoint_url("http://localhost:1234")
+        .http_client(infallible_client_fn(move |_| {
+            http_02x::Response::builder()
+                .status(status)
+                .header("x-rate-limit", "10")
+                .header("x-region", "us-west-2")
+                .header("x-unlisted", "unlisted")
+                .header("x-cache", "miss")
+                .header("x-cache", "hit")
+                .body("")
+                .unwrap()
+        }))
+        .runtime_plugin(CaptureResponseHeaders::new(&[
+            "x-rate-limit",
+            "X-Region",
+            "x-cache",
+        ]))
+        .interceptor(SeenInConfig(seen_in_config))
+        .serializer(|_| Ok(http_02x::Request::new(SdkBody::empty()).try_into().unwrap()))
+        .deserializer(|response| match response.status().as_u16() {
+            200 => Ok(captured_headers::current()),
+            400 => {
+                let mut builder = ErrorMetadata::builder().code("ModeledError");
+                if let Some(captured_headers) = captured_headers::current() {
+                    builder = builder.captured_headers(captured_headers);
+                }
+                Err(OrchestratorError::operation(builder.build()))
+            }
+            _ => Err(OrchestratorError::response("failed to parse".into())),
+        })
+        .build()
+}
+
+fn assert_captured(captured: Option<&CapturedHeaders>) {
+    let captured = captured.expect("headers should be captured");
+    assert_eq!(Some("10"), captured.get("x-rate-limit"));
+    assert_eq!(Some("us-west-2"), captured.get("x-region"));
+    assert_eq!(None, captured.get("x-unlisted"));
+    assert_eq!(&["miss", "hit"], captured.get_all("x-cache"));
+}
+
+#[tokio::test]
+async fn headers_are_captured_on_success() {
+    let seen_in_config = Arc::default();
+    let output = operation(200, Arc::clone(&seen_in_config))
+        .invoke(())
+        .await
+        .unwrap();
+    assert_captured(output.as_ref());
+    assert_captured(seen_in_config.lock().unwrap().as_ref());
+}
+
+#[tokio::test]
+async fn headers_are_captured_on_modeled_errors() {
+    let err = operation(400, Arc::default())
+        .invoke(())
+        .await
+        .expect_err("should fail");
+    let err = err.as_service_error().expect("modeled error");
+    assert_eq!(Some("ModeledError"), err.code());
+    assert_captured(err.captured_headers());
+}
+
+#[tokio::test]
+async fn headers_are_captured_when_parsing_fails() {
+    let seen_in_config = Arc::default();
+    operation(500, Arc::clone(&seen_in_config))
+        .invoke(())
+        .await
+        .expect_err("should fail");
+    assert_captured(seen_in_config.lock().unwrap().as_ref());
+}
@@ -0,0 +1,132 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_runtime::client::conditional::ConditionalRequestInterceptor;
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::error::ErrorMetadata;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Default)]
+struct SentHeaders {
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+}
+
+fn operation(
+    status: u16,
+    conditional: ConditionalRequestInterceptor,
+    sent: Arc<Mutex<SentHeaders>>,
+) -> Operation<(), (), ErrorMetadata> {
+    Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .no_retry()
+        .endpoint_url("http://localhost:1234")
+        .http_client(infallible_client_fn(move |request| {
+            let header = |name| {
+                request
+                    .headers()
+                    .get(name)
+                    .map(|value| value.to_str().unwrap().to_string())
+            };
+            *sent.lock().unwrap() = SentHeaders {
+                if_match: header("if-match"),
+                if_none_match: header("if-none-match"),
+            };
+            http_02x::Response::builder()
+                .status(status)
+                .header("etag", "\"v2\"")
+                .body("")
+                .unwrap()
+        }))
+        .interceptor(conditional)
+        .serializer(|_| Ok(http_02x::Request::new(SdkBody::empty()).try_into().unwrap()))
+        .deserializer(|response| match response.status().as_u16() {
+            200 => Ok(()),
+            _ => Err(OrchestratorError::operation(
+                ErrorMetadata::builder().code("PreconditionFailed").build(),
+            )),
+        })
+        .build()
+}
+
+#[tokio::test]
+async fn if_match_is_sent_and_412_is_a_precondition_failure() {
+    let sent = Arc::<Mutex<SentHeaders>>::default();
+    let err = operation(
+        412,
+        ConditionalRequestInterceptor::if_match("\"v1\""),
+        Arc::clone(&sent),
+    )
+    .invoke(())
+    .await
+    .expect_err("precondition failed");
+
+    assert_eq!(Some("\"v1\""), sent.lock().unwrap().if_match.as_deref());
+    assert_eq!(None, sent.lock().unwrap().if_none_match);
+    match err {
+        SdkError::PreconditionFailed(context) => {
+            assert_eq!(Some("\"v2\""), context.etag());
+            assert_eq!(Some("PreconditionFailed"), context.err().code());
+        }
+        err => panic!("expected a precondition failure, got {err:?}"),
+    }
+}
+
+#[tokio::test]
+async fn if_none_match_treats_304_as_a_precondition_failure() {
+    let sent = Arc::<Mutex<SentHeaders>>::default();
+    let err = operation(
+        304,
+        ConditionalRequestInterceptor::if_none_match("\"v2\""),
+        Arc::clone(&sent),
+    )
+    .invoke(())
+    .await
+    .expect_err("precondition failed");
+
+    assert_eq!(
+        Some("\"v2\""),
+        sent.lock().unwrap().if_none_match.as_deref()
+    );
+    assert!(matches!(err, SdkError::PreconditionFailed(_)), "{err:?}");
+    // The modeled error is still available as a service error
+    assert_eq!(
+        Some("PreconditionFailed"),
+        err.as_service_error().unwrap().code()
+    );
+}
+
+#[tokio::test]
+async fn other_errors_are_service_errors() {
+    let err = operation(
+        304,
+        ConditionalRequestInterceptor::if_match("\"v1\""),
+        Arc::default(),
+    )
+    .invoke(())
+    .await
+    .expect_err("not modified");
+    assert!(matches!(err, SdkError::ServiceError(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn successful_conditional_requests_succeed() {
+    operation(
+        200,
+        ConditionalRequestInterceptor::if_match("\"v2\""),
+        Arc::default(),
+    )
+    .invoke(())
+    .await
+    .expect("success");
+}
@@ -0,0 +1,347 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+//! Operation-level overrides must only apply to the invocation they were given to.
+//!
+//! These tests invoke operations the way generated clients do: the client-level runtime plugins
+//! are shared by every invocation, and an invocation with a config override adds its own
+//! operation-level plugin to a clone of them. Every invocation without an override must see
+//! the client defaults, regardless of what previous or concurrent invocations overrode.
+
+use aws_smithy_async::time::StaticTimeSource;
+use aws_smithy_runtime::client::auth::no_auth::{NoAuthScheme, NO_AUTH_SCHEME_ID};
+use aws_smithy_runtime::client::defaults::{default_plugins, DefaultPluginParams};
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::identity::no_auth::NoAuthIdentityResolver;
+use aws_smithy_runtime::client::identity::IdentityCache;
+use aws_smithy_runtime::client::orchestrator::endpoints::StaticUriEndpointResolver;
+use aws_smithy_runtime::client::orchestrator::invoke;
+use aws_smithy_runtime::client::retries::strategy::StandardRetryStrategy;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::auth::static_resolver::StaticAuthSchemeOptionResolver;
+use aws_smithy_runtime_api::client::auth::{
+    AuthScheme, AuthSchemeEndpointConfig, AuthSchemeId, AuthSchemeOptionResolverParams, Sign,
+};
+use aws_smithy_runtime_api::client::endpoint::EndpointResolverParams;
+use aws_smithy_runtime_api::client::http::SharedHttpClient;
+use aws_smithy_runtime_api::client::identity::{Identity, SharedIdentityResolver};
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeTransmitInterceptorContextRef, Error, Input, Output,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::orchestrator::{
+    HttpRequest, HttpResponse, Metadata, OrchestratorError,
+};
+use aws_smithy_runtime_api::client::runtime_components::{
+    GetIdentityResolver, RuntimeComponents, RuntimeComponentsBuilder,
+};
+use aws_smithy_runtime_api::client::runtime_plugin::{RuntimePlugins, StaticRuntimePlugin};
+use aws_smithy_runtime_api::client::ser_de::{
+    DeserializeResponse, SerializeRequest, SharedRequestSerializer, SharedResponseDeserializer,
+};
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::{ConfigBag, Layer};
+use aws_smithy_types::retry::RetryConfig;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const TEST_AUTH_SCHEME_ID: AuthSchemeId = AuthSchemeId::new("test_auth");
+const CALL_HEADER: &str = "x-call";
+const AUTH_HEADER: &str = "x-test-auth";
+
+/// What a single invocation used, keyed by the name it was invoked with.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Observed {
+    http_client: Option<&'static str>,
+    uri: Option<String>,
+    auth: Option<String>,
+    time: Option<SystemTime>,
+    max_attempts: Option<u32>,
+    override_interceptor_ran: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Observations(Arc<Mutex<HashMap<String, Observed>>>);
+
+impl Observations {
+    fn update(&self, call: &str, f: impl FnOnce(&mut Observed)) {
+        f(self.0.lock().unwrap().entry(call.to_string()).or_default())
+    }
+
+    fn get(&self, call: &str) -> Observed {
+        self.0
+            .lock()
+            .unwrap()
+            .get(call)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+fn call_name(request: &HttpRequest) -> &str {
+    request
+        .headers()
+        .get(CALL_HEADER)
+        .expect("set by serializer")
+}
+
+fn time(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+fn expected(http_client: &'static str) -> Observed {
+    let overridden = http_client == "override";
+    Observed {
+        http_client: Some(http_client),
+        uri: Some(format!("http://{http_client}.localhost/")),
+        auth: overridden.then(|| "signed".to_string()),
+        time: Some(if overridden { time(2000) } else { time(1000) }),
+        max_attempts: Some(if overridden { 1 } else { 3 }),
+        override_interceptor_ran: overridden,
+    }
+}
+
+#[derive(Debug)]
+struct CallSerializer;
+
+impl SerializeRequest for CallSerializer {
+    fn serialize_input(&self, input: Input, _cfg: &mut ConfigBag) -> Result<HttpRequest, BoxError> {
+        let call = input.downcast::<String>().expect("correct type");
+        let mut request = HttpRequest::new(SdkBody::empty());
+        request.set_uri("/")?;
+        request.headers_mut().insert(CALL_HEADER, *call);
+        Ok(request)
+    }
+}
+
+#[derive(Debug)]
+struct OkDeserializer;
+
+impl DeserializeResponse for OkDeserializer {
+    fn deserialize_nonstreaming(
+        &self,
+        _response: &HttpResponse,
+    ) -> Result<Output, OrchestratorError<Error>> {
+        Ok(Output::erase(()))
+    }
+}
+
+fn http_client(name: &'static str, observations: Observations) -> SharedHttpClient {
+    infallible_client_fn(move |request| {
+        let call = request.headers()[CALL_HEADER].to_str().unwrap().to_string();
+        observations.update(&call, |observed| observed.http_client = Some(name));
+        http_02x::Response::builder()
+            .status(200)
+            .body(SdkBody::empty())
+            .unwrap()
+    })
+}
+
+/// Records the components and config a client-level interceptor sees for each invocation.
+#[derive(Debug)]
+struct RecordComponents(Observations);
+
+impl Intercept for RecordComponents {
+    fn name(&self) -> &'static str {
+        "RecordComponents"
+    }
+
+    fn read_before_transmit(
+        &self,
+        context: &BeforeTransmitInterceptorContextRef<'_>,
+        runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let request = context.request();
+        self.0.update(call_name(request), |observed| {
+            observed.uri = Some(request.uri().to_string());
+            observed.auth = request.headers().get(AUTH_HEADER).map(str::to_string);
+            observed.time = runtime_components.time_source().map(|ts| ts.now());
+            observed.max_attempts = cfg.load::<RetryConfig>().map(RetryConfig::max_attempts);
+        });
+        Ok(())
+    }
+}
+
+/// An interceptor that is only registered by the override.
+#[derive(Debug)]
+struct OverrideInterceptor(Observations);
+
+impl Intercept for OverrideInterceptor {
+    fn name(&self) -> &'static str {
+        "OverrideInterceptor"
+    }
+
+    fn read_before_transmit(
+        &self,
+        context: &BeforeTransmitInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        self.0.update(call_name(context.request()), |observed| {
+            observed.override_interceptor_ran = true
+        });
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct TestSigner;
+
+impl Sign for TestSigner {
+    fn sign_http_request(
+        &self,
+        request: &mut HttpRequest,
+        _identity: &Identity,
+        _auth_scheme_endpoint_config: AuthSchemeEndpointConfig<'_>,
+        _runtime_components: &RuntimeComponents,
+        _config_bag: &ConfigBag,
+    ) -> Result<(), BoxError> {
+        request.headers_mut().insert(AUTH_HEADER, "signed");
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct TestAuthScheme(TestSigner);
+
+impl AuthScheme for TestAuthScheme {
+    fn scheme_id(&self) -> AuthSchemeId {
+        TEST_AUTH_SCHEME_ID
+    }
+
+    fn identity_resolver(
+        &self,
+        identity_resolvers: &dyn GetIdentityResolver,
+    ) -> Option<SharedIdentityResolver> {
+        identity_resolvers.identity_resolver(TEST_AUTH_SCHEME_ID)
+    }
+
+    fn signer(&self) -> &dyn Sign {
+        &self.0
+    }
+}
+
+/// The client-level runtime plugins, shared by every invocation.
+fn client_plugins(observations: &Observations) -> RuntimePlugins {
+    let mut config = Layer::new("client");
+    config.store_put(Metadata::new("test_operation", "test_service"));
+    config.store_put(EndpointResolverParams::new(()));
+    config.store_put(AuthSchemeOptionResolverParams::new(()));
+    config.store_put(RetryConfig::standard().with_max_attempts(3));
+    config.store_put(SharedRequestSerializer::new(CallSerializer));
+    config.store_put(SharedResponseDeserializer::new(OkDeserializer));
+
+    let components = RuntimeComponentsBuilder::new("client")
+        .with_http_client(Some(http_client("default", observations.clone())))
+        .with_endpoint_resolver(Some(StaticUriEndpointResolver::uri(
+            "http://default.localhost",
+        )))
+        .with_retry_strategy(Some(StandardRetryStrategy::new()))
+        .with_auth_scheme_option_resolver(Some(StaticAuthSchemeOptionResolver::new(vec![
+            NO_AUTH_SCHEME_ID,
+        ])))
+        .with_auth_scheme(NoAuthScheme::new())
+        .with_identity_resolver(NO_AUTH_SCHEME_ID, NoAuthIdentityResolver::new())
+        .with_identity_cache(Some(IdentityCache::no_cache()))
+        .with_time_source(Some(StaticTimeSource::new(time(1000))))
+        .with_interceptor(RecordComponents(observations.clone()));
+
+    RuntimePlugins::new()
+        .with_client_plugins(default_plugins(
+            DefaultPluginParams::new().with_retry_partition_name("test_service"),
+        ))
+        .with_client_plugin(
+            StaticRuntimePlugin::new()
+                .with_config(config.freeze())
+                .with_runtime_components(components),
+        )
+}
+
+/// An operation-level plugin overriding every component the client configures.
+fn override_plugin(observations: &Observations) -> StaticRuntimePlugin {
+    let mut config = Layer::new("override");
+    config.store_put(RetryConfig::disabled());
+
+    let components = RuntimeComponentsBuilder::new("override")
+        .with_http_client(Some(http_client("override", observations.clone())))
+        .with_endpoint_resolver(Some(StaticUriEndpointResolver::uri(
+            "http://override.localhost",
+        )))
+        .with_auth_scheme_option_resolver(Some(StaticAuthSchemeOptionResolver::new(vec![
+            TEST_AUTH_SCHEME_ID,
+        ])))
+        .with_auth_scheme(TestAuthScheme(TestSigner))
+        .with_identity_resolver(TEST_AUTH_SCHEME_ID, NoAuthIdentityResolver::new())
+        .with_time_source(Some(StaticTimeSource::new(time(2000))))
+        .with_interceptor(OverrideInterceptor(observations.clone()));
+
+    StaticRuntimePlugin::new()
+        .with_config(config.freeze())
+        .with_runtime_components(components)
+}
+
+async fn call(
+    client_plugins: &RuntimePlugins,
+    name: String,
+    config_override: Option<StaticRuntimePlugin>,
+) {
+    let mut runtime_plugins = client_plugins.clone();
+    if let Some(config_override) = config_override {
+        runtime_plugins = runtime_plugins.with_operation_plugin(config_override);
+    }
+    invoke(
+        "test_service",
+        "test_operation",
+        Input::erase(name),
+        &runtime_plugins,
+    )
+    .await
+    .expect("success");
+}
+
+#[tokio::test]
+async fn overrides_do_not_leak_into_later_invocations() {
+    let observations = Observations::default();
+    let client_plugins = client_plugins(&observations);
+
+    call(&client_plugins, "before".into(), None).await;
+    call(
+        &client_plugins,
+        "overridden".into(),
+        Some(override_plugin(&observations)),
+    )
+    .await;
+    call(&client_plugins, "after".into(), None).await;
+
+    assert_eq!(expected("default"), observations.get("before"));
+    assert_eq!(expected("override"), observations.get("overridden"));
+    assert_eq!(expected("default"), observations.get("after"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn overrides_do_not_leak_into_concurrent_invocations() {
+    let observations = Observations::default();
+    let client_plugins = client_plugins(&observations);
+
+    let calls = (0..50).map(|i| {
+        let client_plugins = client_plugins.clone();
+        let config_override = (i % 2 == 0).then(|| override_plugin(&observations));
+        tokio::spawn(
+            async move { call(&client_plugins, format!("call-{i}"), config_override).await },
+        )
+    });
+    for call in futures_util::future::join_all(calls).await {
+        call.expect("task should not panic");
+    }
+
+    for i in 0..50 {
+        let expected = expected(if i % 2 == 0 { "override" } else { "default" });
+        assert_eq!(expected, observations.get(&format!("call-{i}")), "call-{i}");
+    }
+}
@@ -0,0 +1,86 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_runtime_api::http::Version;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::error::ErrorMetadata;
+
+const LOAD_BALANCER_PAGE: &str =
+    "<html><body><h1>503 Service Temporarily Unavailable</h1></body></html>";
+
+/// An operation answered by a load balancer with an HTML page, which `deserialize_error` fails to
+/// parse.
+fn operation(
+    deserialize_error: fn(&[u8]) -> OrchestratorError<ErrorMetadata>,
+) -> Operation<(), (), ErrorMetadata> {
+    Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .no_retry()
+        .endpoint_url("http://localhost:1234")
+        .http_client(infallible_client_fn(|_| {
+            http_02x::Response::builder()
+                .status(503)
+                .header("content-type", "text/html; charset=utf-8")
+                .body(SdkBody::from(LOAD_BALANCER_PAGE))
+                .unwrap()
+        }))
+        .serializer(|_| Ok(http_02x::Request::new(SdkBody::empty()).try_into().unwrap()))
+        .deserializer(move |response| {
+            Err(deserialize_error(
+                response.body().bytes().expect("the body was read"),
+            ))
+        })
+        .build()
+}
+
+#[tokio::test]
+async fn unparseable_service_errors_keep_the_status_line_and_content_type() {
+    // Like a generated error parser that can't find an error code in the body
+    let error = operation(|_| OrchestratorError::operation(ErrorMetadata::default()))
+        .invoke(())
+        .await
+        .expect_err("the service is unavailable");
+
+    let rendered = error.to_string();
+    match error {
+        SdkError::ServiceError(context) => {
+            assert_eq!(503, context.http_status().as_u16());
+            assert_eq!(Version::Http11, context.http_version());
+            assert_eq!(Some("text/html; charset=utf-8"), context.raw_content_type());
+        }
+        other => panic!("expected a service error, got {other:?}"),
+    }
+    assert_eq!(
+        "service error (HTTP/1.1 503, content-type: text/html; charset=utf-8)",
+        rendered
+    );
+}
+
+#[tokio::test]
+async fn response_errors_keep_the_status_line_and_content_type() {
+    let error = operation(|_| OrchestratorError::response("expected a JSON body".into()))
+        .invoke(())
+        .await
+        .expect_err("the body isn't JSON");
+
+    let rendered = error.to_string();
+    match error {
+        SdkError::ResponseError(context) => {
+            assert_eq!(503, context.http_status().as_u16());
+            assert_eq!(Some("text/html; charset=utf-8"), context.raw_content_type());
+        }
+        other => panic!("expected a response error, got {other:?}"),
+    }
+    assert!(rendered.contains("503"), "{rendered}");
+    assert!(rendered.contains("text/html"), "{rendered}");
+}
@@ -0,0 +1,144 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_async::rt::sleep::{AsyncSleep, Sleep};
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::random::SeededRandomSource;
+use aws_smithy_runtime::client::retries::classifiers::HttpStatusCodeClassifier;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::BeforeTransmitInterceptorContextRef;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
+use aws_smithy_runtime_api::client::runtime_components::{
+    RuntimeComponents, RuntimeComponentsBuilder,
+};
+use aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::ConfigBag;
+use aws_smithy_types::retry::RetryConfig;
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Records the requested sleep durations and returns immediately.
+#[derive(Debug, Clone, Default)]
+struct RecordSleeps(Arc<Mutex<Vec<Duration>>>);
+
+impl AsyncSleep for RecordSleeps {
+    fn sleep(&self, duration: Duration) -> Sleep {
+        self.0.lock().unwrap().push(duration);
+        Sleep::new(async {})
+    }
+}
+
+/// Records whether a random source was set in the runtime components.
+#[derive(Debug, Default)]
+struct RecordRandomSource(Arc<AtomicBool>);
+
+impl Intercept for RecordRandomSource {
+    fn name(&self) -> &'static str {
+        "RecordRandomSource"
+    }
+
+    fn read_before_transmit(
+        &self,
+        _context: &BeforeTransmitInterceptorContextRef<'_>,
+        runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        self.0.store(
+            runtime_components.random_source().is_some(),
+            Ordering::SeqCst,
+        );
+        Ok(())
+    }
+}
+
+/// Sends a request to a service that is always unavailable, and returns the retry backoffs.
+async fn retry_backoffs(random_source: Option<SeededRandomSource>) -> Vec<Duration> {
+    let sleeps = RecordSleeps::default();
+    let mut builder = Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .endpoint_url("http://localhost:1234")
+        .standard_retry(&RetryConfig::standard().with_max_attempts(4))
+        .retry_classifier(HttpStatusCodeClassifier::default())
+        .sleep_impl(sleeps.clone())
+        .http_client(infallible_client_fn(|_| {
+            http_02x::Response::builder()
+                .status(503)
+                .body(SdkBody::empty())
+                .unwrap()
+        }))
+        .serializer(|_| {
+            Ok(http_02x::Request::builder()
+                .body(SdkBody::empty())
+                .unwrap()
+                .try_into()
+                .unwrap())
+        })
+        .deserializer::<(), Infallible>(|_| {
+            Err(OrchestratorError::response("service unavailable".into()))
+        });
+    if let Some(random_source) = random_source {
+        builder = builder.runtime_plugin(StaticRuntimePlugin::new().with_runtime_components(
+            RuntimeComponentsBuilder::new("random_source").with_random_source(Some(random_source)),
+        ));
+    }
+
+    builder
+        .build()
+        .invoke(())
+        .await
+        .expect_err("always unavailable");
+    let sleeps = sleeps.0.lock().unwrap().clone();
+    assert_eq!(3, sleeps.len());
+    sleeps
+}
+
+#[tokio::test]
+async fn seeded_random_source_makes_retry_jitter_reproducible() {
+    let first = retry_backoffs(Some(SeededRandomSource::new(1234))).await;
+    let second = retry_backoffs(Some(SeededRandomSource::new(1234))).await;
+    let other_seed = retry_backoffs(Some(SeededRandomSource::new(4321))).await;
+
+    assert_eq!(first, second);
+    assert_ne!(first, other_seed);
+}
+
+#[tokio::test]
+async fn default_random_source_is_set() {
+    let random_source_set = Arc::new(AtomicBool::new(false));
+    let operation: Operation<(), (), Infallible> = Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .no_retry()
+        .endpoint_url("http://localhost:1234")
+        .interceptor(RecordRandomSource(random_source_set.clone()))
+        .http_client(infallible_client_fn(|_| {
+            http_02x::Response::builder()
+                .status(200)
+                .body(SdkBody::empty())
+                .unwrap()
+        }))
+        .serializer(|_| {
+            Ok(http_02x::Request::builder()
+                .body(SdkBody::empty())
+                .unwrap()
+                .try_into()
+                .unwrap())
+        })
+        .deserializer::<(), Infallible>(|_| Ok(()))
+        .build();
+
+    operation.invoke(()).await.expect("success");
+    assert!(random_source_set.load(Ordering::SeqCst));
+}
@@ -0,0 +1,169 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_async::test_util::tick_advance_sleep::{
+    tick_advance_time_and_sleep, TickAdvanceSleep, TickAdvanceTime,
+};
+use aws_smithy_async::time::TimeSource;
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::rate_limit::{RateLimitExceeded, RateLimiter};
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::error::ErrorMetadata;
+use std::error::Error as _;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// The index and time of each request that was sent.
+type Sent = Arc<Mutex<Vec<(usize, Duration)>>>;
+
+fn operation(
+    name: &'static str,
+    rate_limiter: RateLimiter,
+    time: TickAdvanceTime,
+    sleep: TickAdvanceSleep,
+    sent: Sent,
+) -> Operation<usize, (), ErrorMetadata> {
+    let client_time = time.clone();
+    Operation::builder()
+        .service_name("test")
+        .operation_name(name)
+        .no_auth()
+        .no_retry()
+        .endpoint_url("http://localhost:1234")
+        .time_source(time)
+        .sleep_impl(sleep)
+        .http_client(infallible_client_fn(move |request| {
+            let index = request.headers()["x-index"]
+                .to_str()
+                .unwrap()
+                .parse()
+                .unwrap();
+            let now = client_time
+                .now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap();
+            sent.lock().unwrap().push((index, now));
+            http_02x::Response::builder().status(200).body("").unwrap()
+        }))
+        .runtime_plugin(rate_limiter)
+        .serializer(|index: usize| {
+            Ok(http_02x::Request::builder()
+                .header("x-index", index.to_string())
+                .body(SdkBody::empty())
+                .unwrap()
+                .try_into()
+                .unwrap())
+        })
+        .deserializer(|_| Ok(()))
+        .build()
+}
+
+#[tokio::test]
+async fn requests_are_spaced_out_in_order() {
+    let (time, sleep) = tick_advance_time_and_sleep();
+    let sent = Sent::default();
+    let operation = operation(
+        "test",
+        RateLimiter::new(10.0, 1),
+        time.clone(),
+        sleep,
+        sent.clone(),
+    );
+
+    let tasks: Vec<_> = (0..25)
+        .map(|index| {
+            let operation = operation.clone();
+            tokio::spawn(async move { operation.invoke(index).await })
+        })
+        .collect();
+    tokio::task::yield_now().await;
+    time.tick(Duration::from_secs(3)).await;
+    for task in tasks {
+        task.await.unwrap().expect("success");
+    }
+
+    let expected: Vec<_> = (0..25)
+        .map(|index| (index, Duration::from_millis(100) * index as u32))
+        .collect();
+    assert_eq!(expected, *sent.lock().unwrap());
+}
+
+#[tokio::test]
+async fn operations_can_have_their_own_limit() {
+    let (time, sleep) = tick_advance_time_and_sleep();
+    let sent = Sent::default();
+    let rate_limiter = RateLimiter::new(1.0, 1).per_operation();
+    let first = operation(
+        "first",
+        rate_limiter.clone(),
+        time.clone(),
+        sleep.clone(),
+        sent.clone(),
+    );
+    let second = operation("second", rate_limiter, time.clone(), sleep, sent.clone());
+
+    let tasks = vec![
+        tokio::spawn(async move { first.invoke(0).await }),
+        tokio::spawn(async move { second.invoke(1).await }),
+    ];
+    tokio::task::yield_now().await;
+    time.tick(Duration::from_secs(1)).await;
+    for task in tasks {
+        task.await.unwrap().expect("success");
+    }
+    assert_eq!(
+        vec![(0, Duration::ZERO), (1, Duration::ZERO)],
+        *sent.lock().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn fail_fast_fails_requests_that_would_wait_too_long() {
+    let (time, sleep) = tick_advance_time_and_sleep();
+    let sent = Sent::default();
+    let operation = operation(
+        "test",
+        RateLimiter::new(1.0, 1).fail_fast(Duration::from_secs(2)),
+        time.clone(),
+        sleep,
+        sent.clone(),
+    );
+
+    let tasks: Vec<_> = (0..5)
+        .map(|index| {
+            let operation = operation.clone();
+            tokio::spawn(async move { operation.invoke(index).await })
+        })
+        .collect();
+    tokio::task::yield_now().await;
+    time.tick(Duration::from_secs(3)).await;
+    let mut results = Vec::new();
+    for task in tasks {
+        results.push(task.await.unwrap());
+    }
+
+    assert!(results[..3].iter().all(Result::is_ok), "{results:?}");
+    for result in &results[3..] {
+        let err = result.as_ref().expect_err("would wait 3 seconds");
+        let rate_limit_exceeded = err
+            .source()
+            .and_then(|err| err.source())
+            .and_then(|err| err.downcast_ref::<RateLimitExceeded>())
+            .expect("rate limit exceeded");
+        assert_eq!(Duration::from_secs(3), rate_limit_exceeded.wait());
+    }
+    let sent_at: Vec<_> = sent.lock().unwrap().iter().map(|(_, at)| *at).collect();
+    assert_eq!(
+        vec![
+            Duration::ZERO,
+            Duration::from_secs(1),
+            Duration::from_secs(2)
+        ],
+        sent_at
+    );
+}
@@ -0,0 +1,341 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::identity::IdentityCache;
+use aws_smithy_runtime::client::orchestrator::operation::{Operation, OperationBuilder};
+use aws_smithy_runtime::client::redirect::{RedirectError, RedirectErrorKind, RedirectPolicy};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::auth::static_resolver::StaticAuthSchemeOptionResolver;
+use aws_smithy_runtime_api::client::auth::{
+    AuthScheme, AuthSchemeEndpointConfig, AuthSchemeId, AuthSchemeOptionResolverParams,
+    SharedAuthScheme, SharedAuthSchemeOptionResolver, Sign,
+};
+use aws_smithy_runtime_api::client::identity::{
+    Identity, IdentityFuture, ResolveIdentity, SharedIdentityResolver,
+};
+use aws_smithy_runtime_api::client::interceptors::context::BeforeTransmitInterceptorContextMut;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse, OrchestratorError};
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_runtime_api::client::runtime_components::{
+    GetIdentityResolver, RuntimeComponents, RuntimeComponentsBuilder,
+};
+use aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::{ConfigBag, Layer};
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::error::metadata::ProvideErrorMetadata;
+use aws_smithy_types::error::ErrorMetadata;
+use std::error::Error;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+const HOST_SCHEME_ID: AuthSchemeId = AuthSchemeId::new("host");
+
+#[derive(Debug)]
+struct TestIdentityResolver;
+
+impl ResolveIdentity for TestIdentityResolver {
+    fn resolve_identity<'a>(
+        &'a self,
+        _runtime_components: &'a RuntimeComponents,
+        _config_bag: &'a ConfigBag,
+    ) -> IdentityFuture<'a> {
+        IdentityFuture::ready(Ok(Identity::new("identity", None)))
+    }
+}
+
+/// Signs requests for their host, like SigV4 does.
+#[derive(Debug)]
+struct HostSigner;
+
+impl Sign for HostSigner {
+    fn sign_http_request(
+        &self,
+        request: &mut HttpRequest,
+        _identity: &Identity,
+        _auth_scheme_endpoint_config: AuthSchemeEndpointConfig<'_>,
+        _runtime_components: &RuntimeComponents,
+        _config_bag: &ConfigBag,
+    ) -> Result<(), BoxError> {
+        let host = request.uri().split('/').nth(2).unwrap().to_string();
+        request
+            .headers_mut()
+            .insert("authorization", format!("signed-for={host}"));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct HostAuthScheme(HostSigner);
+
+impl AuthScheme for HostAuthScheme {
+    fn scheme_id(&self) -> AuthSchemeId {
+        HOST_SCHEME_ID
+    }
+
+    fn identity_resolver(
+        &self,
+        identity_resolvers: &dyn GetIdentityResolver,
+    ) -> Option<SharedIdentityResolver> {
+        identity_resolvers.identity_resolver(self.scheme_id())
+    }
+
+    fn signer(&self) -> &dyn Sign {
+        &self.0
+    }
+}
+
+/// The URI and `authorization` header of each request that was sent.
+type Sent = Arc<Mutex<Vec<(String, String)>>>;
+
+/// An operation that is redirected from `a.example.com` to `location`.
+fn operation(
+    policy: Option<RedirectPolicy>,
+    location: &'static str,
+    body: fn() -> SdkBody,
+    sent: Sent,
+) -> Operation<(), (), ErrorMetadata> {
+    operation_builder(policy, location, body, sent).build()
+}
+
+fn operation_builder(
+    policy: Option<RedirectPolicy>,
+    location: &'static str,
+    body: fn() -> SdkBody,
+    sent: Sent,
+) -> OperationBuilder<(), (), ErrorMetadata> {
+    let mut layer = Layer::new("redirects");
+    layer.store_put(AuthSchemeOptionResolverParams::new(()));
+    if let Some(policy) = policy {
+        layer.store_put(policy);
+    }
+    let components = RuntimeComponentsBuilder::new("redirects")
+        .with_auth_scheme_option_resolver(Some(SharedAuthSchemeOptionResolver::new(
+            StaticAuthSchemeOptionResolver::new(vec![HOST_SCHEME_ID]),
+        )))
+        .with_auth_scheme(SharedAuthScheme::new(HostAuthScheme(HostSigner)))
+        .with_identity_cache(Some(IdentityCache::no_cache()))
+        .with_identity_resolver(
+            HOST_SCHEME_ID,
+            SharedIdentityResolver::new(TestIdentityResolver),
+        );
+    Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_retry()
+        .endpoint_url("https://a.example.com")
+        .runtime_plugin(
+            StaticRuntimePlugin::new()
+                .with_config(layer.freeze())
+                .with_runtime_components(components),
+        )
+        .http_client(infallible_client_fn(move |request| {
+            let authorization = request.headers()["authorization"].to_str().unwrap();
+            sent.lock()
+                .unwrap()
+                .push((request.uri().to_string(), authorization.to_string()));
+            let response = http_02x::Response::builder();
+            match request.uri().host() {
+                Some("a.example.com") => response
+                    .status(308)
+                    .header("location", location)
+                    .body("")
+                    .unwrap(),
+                _ => response.status(200).body("").unwrap(),
+            }
+        }))
+        .serializer(move |_| {
+            Ok(http_02x::Request::put("/object")
+                .body(body())
+                .unwrap()
+                .try_into()
+                .unwrap())
+        })
+        .deserializer(|response| match response.status().as_u16() {
+            200 => Ok(()),
+            status => Err(OrchestratorError::operation(
+                ErrorMetadata::builder().code(status.to_string()).build(),
+            )),
+        })
+}
+
+fn redirect_error(err: SdkError<ErrorMetadata, HttpResponse>) -> RedirectErrorKind {
+    let mut source = err.source();
+    while let Some(err) = source {
+        if let Some(err) = err.downcast_ref::<RedirectError>() {
+            return err.kind();
+        }
+        source = err.source();
+    }
+    panic!("expected a redirect error, got {err:?}")
+}
+
+#[tokio::test]
+async fn redirects_to_allowed_hosts_are_followed_and_signed_again() {
+    let sent = Sent::default();
+    let policy = RedirectPolicy::new().allow_host("b.example.com");
+    operation(
+        Some(policy),
+        "https://b.example.com/moved",
+        || SdkBody::from("replayable"),
+        sent.clone(),
+    )
+    .invoke(())
+    .await
+    .expect("the redirect is followed");
+
+    assert_eq!(
+        vec![
+            (
+                "https://a.example.com/object".to_string(),
+                "signed-for=a.example.com".to_string()
+            ),
+            (
+                "https://b.example.com/moved".to_string(),
+                "signed-for=b.example.com".to_string()
+            ),
+        ],
+        *sent.lock().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn redirects_are_not_followed_by_default() {
+    let sent = Sent::default();
+    let err = operation(
+        None,
+        "https://b.example.com/moved",
+        || SdkBody::from("replayable"),
+        sent.clone(),
+    )
+    .invoke(())
+    .await
+    .expect_err("the redirect is an error");
+
+    assert_eq!(Some("308"), err.code());
+    assert_eq!(1, sent.lock().unwrap().len());
+}
+
+#[tokio::test]
+async fn redirects_to_disallowed_hosts_fail() {
+    let sent = Sent::default();
+    let policy = RedirectPolicy::new().same_registrable_domain(true);
+    let err = operation(
+        Some(policy),
+        "https://b.example.org/moved",
+        || SdkBody::from("replayable"),
+        sent.clone(),
+    )
+    .invoke(())
+    .await
+    .expect_err("the redirect isn't allowed");
+
+    assert_eq!(RedirectErrorKind::Disallowed, redirect_error(err));
+    assert_eq!(1, sent.lock().unwrap().len());
+}
+
+#[tokio::test]
+async fn redirects_with_bodies_that_cant_be_replayed_fail() {
+    let sent = Sent::default();
+    let policy = RedirectPolicy::new().allow_host("b.example.com");
+    let err = operation(
+        Some(policy),
+        "https://b.example.com/moved",
+        || SdkBody::from_body_0_4(SdkBody::from("one shot")),
+        sent.clone(),
+    )
+    .invoke(())
+    .await
+    .expect_err("the body can't be sent again");
+
+    let message = format!("{}", DisplayErrorContext(&err));
+    assert!(
+        message.contains("the request body can't be replayed"),
+        "{message}"
+    );
+    assert_eq!(RedirectErrorKind::BodyNotReplayable, redirect_error(err));
+    assert_eq!(1, sent.lock().unwrap().len());
+}
+
+/// Adds headers before transmit, like the invocation ID and request info interceptors do.
+#[derive(Debug, Default)]
+struct SdkHeaders {
+    transmits: AtomicU32,
+}
+
+impl Intercept for SdkHeaders {
+    fn name(&self) -> &'static str {
+        "SdkHeaders"
+    }
+
+    fn modify_before_transmit(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let transmit = self.transmits.fetch_add(1, Ordering::SeqCst) + 1;
+        let headers = context.request_mut().headers_mut();
+        headers.insert("amz-sdk-invocation-id", "test-invocation-id");
+        headers.insert("amz-sdk-request", format!("attempt=1; transmit={transmit}"));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn redirected_requests_go_through_the_transmit_interceptors() {
+    let sent = Sent::default();
+    let sent_headers = Arc::new(Mutex::new(Vec::new()));
+    let policy = RedirectPolicy::new().allow_host("b.example.com");
+    let http_client = {
+        let sent_headers = sent_headers.clone();
+        infallible_client_fn(move |request| {
+            let header = |name| request.headers()[name].to_str().unwrap().to_string();
+            sent_headers
+                .lock()
+                .unwrap()
+                .push((header("amz-sdk-invocation-id"), header("amz-sdk-request")));
+            let response = http_02x::Response::builder();
+            match request.uri().host() {
+                Some("a.example.com") => response
+                    .status(308)
+                    .header("location", "https://b.example.com/moved")
+                    .body("")
+                    .unwrap(),
+                _ => response.status(200).body("").unwrap(),
+            }
+        })
+    };
+    operation_builder(
+        Some(policy),
+        "https://b.example.com/moved",
+        || SdkBody::from("replayable"),
+        sent,
+    )
+    .interceptor(SdkHeaders::default())
+    .http_client(http_client)
+    .build()
+    .invoke(())
+    .await
+    .expect("the redirect is followed");
+
+    assert_eq!(
+        vec![
+            (
+                "test-invocation-id".to_string(),
+                "attempt=1; transmit=1".to_string()
+            ),
+            (
+                "test-invocation-id".to_string(),
+                "attempt=1; transmit=2".to_string()
+            ),
+        ],
+        *sent_headers.lock().unwrap()
+    );
+}
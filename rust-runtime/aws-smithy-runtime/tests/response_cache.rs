@@ -0,0 +1,154 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::response_cache::{InMemoryResponseCache, ResponseCacheInterceptor};
+use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::error::ErrorMetadata;
+use std::sync::{Arc, Mutex};
+
+/// A resource served with its ETag, that answers `If-None-Match` requests for its current ETag
+/// with a `304 Not Modified`.
+#[derive(Debug)]
+struct Resource {
+    etag: &'static str,
+    body: &'static str,
+    status: u16,
+    // The `If-None-Match` header of every request
+    requests: Vec<Option<String>>,
+}
+
+impl Resource {
+    fn new(etag: &'static str, body: &'static str) -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Self {
+            etag,
+            body,
+            status: 200,
+            requests: Vec::new(),
+        }))
+    }
+}
+
+fn operation(
+    name: &'static str,
+    resource: Arc<Mutex<Resource>>,
+    cache: Arc<InMemoryResponseCache>,
+) -> Operation<String, String, ErrorMetadata> {
+    Operation::builder()
+        .service_name("test")
+        .operation_name(name)
+        .no_auth()
+        .no_retry()
+        .endpoint_url("http://localhost:1234")
+        .http_client(infallible_client_fn(move |request| {
+            let mut resource = resource.lock().unwrap();
+            let if_none_match = request
+                .headers()
+                .get("if-none-match")
+                .map(|value| value.to_str().unwrap().to_string());
+            let not_modified = if_none_match.as_deref() == Some(resource.etag);
+            resource.requests.push(if_none_match);
+            let response = http_02x::Response::builder().header("etag", resource.etag);
+            let response = match (resource.status, not_modified) {
+                (200, true) => response.status(304).body(""),
+                (status, _) => response.status(status).body(resource.body),
+            };
+            response.unwrap()
+        }))
+        .interceptor(
+            ResponseCacheInterceptor::new(cache)
+                .cache_operation("GetItem")
+                .max_body_size(16),
+        )
+        .serializer(|input: String| {
+            Ok(http_02x::Request::new(SdkBody::from(input))
+                .try_into()
+                .unwrap())
+        })
+        .deserializer(|response| match response.status().as_u16() {
+            200 => Ok(String::from_utf8(response.body().bytes().unwrap().to_vec()).unwrap()),
+            _ => Err(OrchestratorError::operation(
+                ErrorMetadata::builder().code("Failed").build(),
+            )),
+        })
+        .build()
+}
+
+#[tokio::test]
+async fn not_modified_responses_are_served_from_the_cache() {
+    let resource = Resource::new("\"v1\"", "pikachu");
+    let cache = Arc::new(InMemoryResponseCache::new(1024));
+    let get_item = operation("GetItem", resource.clone(), cache.clone());
+
+    assert_eq!("pikachu", get_item.invoke("id=1".into()).await.unwrap());
+    assert_eq!(1, cache.len());
+    assert_eq!("pikachu", get_item.invoke("id=1".into()).await.unwrap());
+    assert_eq!(
+        vec![None, Some("\"v1\"".to_string())],
+        resource.lock().unwrap().requests
+    );
+
+    // A different request isn't served the cached response
+    assert_eq!("pikachu", get_item.invoke("id=2".into()).await.unwrap());
+    assert_eq!(None, resource.lock().unwrap().requests[2]);
+    assert_eq!(2, cache.len());
+}
+
+#[tokio::test]
+async fn changed_etags_replace_the_cached_response() {
+    let resource = Resource::new("\"v1\"", "pikachu");
+    let cache = Arc::new(InMemoryResponseCache::new(1024));
+    let get_item = operation("GetItem", resource.clone(), cache.clone());
+
+    assert_eq!("pikachu", get_item.invoke("id=1".into()).await.unwrap());
+    {
+        let mut resource = resource.lock().unwrap();
+        resource.etag = "\"v2\"";
+        resource.body = "raichu";
+    }
+    assert_eq!("raichu", get_item.invoke("id=1".into()).await.unwrap());
+    assert_eq!("raichu", get_item.invoke("id=1".into()).await.unwrap());
+    assert_eq!(
+        vec![None, Some("\"v1\"".to_string()), Some("\"v2\"".to_string())],
+        resource.lock().unwrap().requests
+    );
+}
+
+#[tokio::test]
+async fn errors_and_large_bodies_are_not_cached() {
+    let resource = Resource::new("\"v1\"", "pikachu");
+    resource.lock().unwrap().status = 500;
+    let cache = Arc::new(InMemoryResponseCache::new(1024));
+    let get_item = operation("GetItem", resource.clone(), cache.clone());
+
+    get_item.invoke("id=1".into()).await.expect_err("500");
+    assert!(cache.is_empty());
+
+    {
+        let mut resource = resource.lock().unwrap();
+        resource.status = 200;
+        resource.body = "a body longer than the limit";
+    }
+    get_item.invoke("id=1".into()).await.unwrap();
+    get_item.invoke("id=1".into()).await.unwrap();
+    assert!(cache.is_empty());
+    assert_eq!(vec![None, None, None], resource.lock().unwrap().requests);
+}
+
+#[tokio::test]
+async fn operations_that_are_not_allow_listed_bypass_the_cache() {
+    let resource = Resource::new("\"v1\"", "pikachu");
+    let cache = Arc::new(InMemoryResponseCache::new(1024));
+    let list_items = operation("ListItems", resource.clone(), cache.clone());
+
+    assert_eq!("pikachu", list_items.invoke("id=1".into()).await.unwrap());
+    assert_eq!("pikachu", list_items.invoke("id=1".into()).await.unwrap());
+    assert!(cache.is_empty());
+    assert_eq!(vec![None, None], resource.lock().unwrap().requests);
+}
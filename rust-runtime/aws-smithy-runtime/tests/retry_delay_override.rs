@@ -0,0 +1,127 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_async::test_util::tick_advance_sleep::tick_advance_time_and_sleep;
+use aws_smithy_async::time::TimeSource;
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
+use aws_smithy_runtime_api::client::retries::AlwaysRetry;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::error::ErrorMetadata;
+use aws_smithy_types::retry::{ErrorKind, RetryConfig};
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// An error that isn't modeled, which keeps its metadata as its source like generated errors do.
+#[derive(Debug)]
+struct UnhandledError(ErrorMetadata);
+
+impl fmt::Display for UnhandledError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unhandled error")
+    }
+}
+
+impl Error for UnhandledError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+fn slot_not_ready() -> RetryConfig {
+    RetryConfig::standard()
+        .with_max_attempts(3)
+        .with_use_static_exponential_base(true)
+        .with_delay_override(|ctx| {
+            (ctx.error_code() == Some("SlotNotReady")).then(|| Duration::from_secs(5))
+        })
+}
+
+/// Invokes an operation that always fails with `code`, and returns when each attempt was sent.
+async fn attempt_times(retry_config: RetryConfig, code: &'static str) -> Vec<Duration> {
+    let (time, sleep) = tick_advance_time_and_sleep();
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let client_time = time.clone();
+    let client_sent = sent.clone();
+    let operation: Operation<(), (), UnhandledError> = Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .endpoint_url("http://localhost:1234")
+        .standard_retry(&retry_config)
+        .retry_classifier(AlwaysRetry(ErrorKind::ServerError))
+        .time_source(time.clone())
+        .sleep_impl(sleep)
+        .http_client(infallible_client_fn(move |_| {
+            let now = client_time
+                .now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap();
+            client_sent.lock().unwrap().push(now);
+            http_02x::Response::builder()
+                .status(500)
+                .body(SdkBody::empty())
+                .unwrap()
+        }))
+        .serializer(|_| Ok(http_02x::Request::new(SdkBody::empty()).try_into().unwrap()))
+        .deserializer(move |_| {
+            Err(OrchestratorError::operation(UnhandledError(
+                ErrorMetadata::builder().code(code).build(),
+            )))
+        })
+        .build();
+
+    let task = tokio::spawn(async move { operation.invoke(()).await });
+    tokio::task::yield_now().await;
+    time.tick(Duration::from_secs(120)).await;
+    task.await.unwrap().expect_err("always fails");
+    let sent = sent.lock().unwrap().clone();
+    sent
+}
+
+#[tokio::test]
+async fn overridden_error_codes_wait_for_the_override() {
+    assert_eq!(
+        vec![
+            Duration::ZERO,
+            Duration::from_secs(5),
+            Duration::from_secs(10)
+        ],
+        attempt_times(slot_not_ready(), "SlotNotReady").await
+    );
+}
+
+#[tokio::test]
+async fn other_error_codes_back_off_exponentially() {
+    assert_eq!(
+        vec![
+            Duration::ZERO,
+            Duration::from_secs(1),
+            Duration::from_secs(3)
+        ],
+        attempt_times(slot_not_ready(), "ServiceBusy").await
+    );
+}
+
+#[tokio::test]
+async fn overrides_are_capped_by_max_backoff() {
+    let retry_config = RetryConfig::standard()
+        .with_max_attempts(3)
+        .with_max_backoff(Duration::from_secs(20))
+        .with_delay_override(|_| Some(Duration::from_secs(3600)));
+    assert_eq!(
+        vec![
+            Duration::ZERO,
+            Duration::from_secs(20),
+            Duration::from_secs(40)
+        ],
+        attempt_times(retry_config, "SlotNotReady").await
+    );
+}
@@ -0,0 +1,149 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_async::rt::sleep::AsyncSleep;
+use aws_smithy_async::test_util::tick_advance_sleep::tick_advance_time_and_sleep;
+use aws_smithy_async::time::TimeSource;
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::retries::classifiers::HttpStatusCodeClassifier;
+use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
+use aws_smithy_runtime_api::client::retries::classifiers::RetryReason;
+use aws_smithy_runtime_api::client::retries::gate::{
+    Permit, RetryGate, RetryGateDenied, SharedRetryGate,
+};
+use aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::Layer;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::retry::RetryConfig;
+use std::convert::Infallible;
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// A retry gate that answers every attempt after the initial one with `retry`.
+#[derive(Debug)]
+struct TestGate {
+    initial: Permit,
+    retry: Permit,
+}
+
+impl RetryGate for TestGate {
+    fn permit_attempt(&self, attempt: u32, _prior_error: Option<&RetryReason>) -> Permit {
+        if attempt == 1 {
+            self.initial.clone()
+        } else {
+            self.retry.clone()
+        }
+    }
+}
+
+/// An operation that always fails with a retryable error, recording when each attempt was sent.
+fn operation(
+    gate: TestGate,
+    time: impl TimeSource + Clone + 'static,
+    sleep: impl AsyncSleep + 'static,
+    sent: Arc<Mutex<Vec<Duration>>>,
+) -> Operation<(), (), Infallible> {
+    let mut layer = Layer::new("retry_gate");
+    layer.store_put(SharedRetryGate::new(gate));
+    let client_time = time.clone();
+    Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .endpoint_url("http://localhost:1234")
+        .standard_retry(
+            &RetryConfig::standard()
+                .with_max_attempts(3)
+                .with_initial_backoff(Duration::ZERO),
+        )
+        .retry_classifier(HttpStatusCodeClassifier::default())
+        .time_source(time)
+        .sleep_impl(sleep)
+        .http_client(infallible_client_fn(move |_| {
+            let now = client_time
+                .now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap();
+            sent.lock().unwrap().push(now);
+            http_02x::Response::builder()
+                .status(503)
+                .body(SdkBody::empty())
+                .unwrap()
+        }))
+        .runtime_plugin(StaticRuntimePlugin::new().with_config(layer.freeze()))
+        .serializer(|_| Ok(http_02x::Request::new(SdkBody::empty()).try_into().unwrap()))
+        .deserializer(|_| Err(OrchestratorError::response("service unavailable".into())))
+        .build()
+}
+
+#[tokio::test]
+async fn gate_can_deny_the_initial_attempt() {
+    let (time, sleep) = tick_advance_time_and_sleep();
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let gate = TestGate {
+        initial: Permit::Deny("closed for maintenance".into()),
+        retry: Permit::Allow,
+    };
+    let operation = operation(gate, time, sleep, sent.clone());
+
+    let err = operation.invoke(()).await.expect_err("denied");
+    let mut source: Option<&(dyn Error + 'static)> = Some(&err);
+    let denied = std::iter::from_fn(|| {
+        let current = source?;
+        source = current.source();
+        Some(current)
+    })
+    .find_map(|err| err.downcast_ref::<RetryGateDenied>())
+    .expect("denied by the retry gate");
+    assert_eq!("closed for maintenance", denied.reason());
+    assert!(sent.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn gate_can_deny_retries() {
+    let (time, sleep) = tick_advance_time_and_sleep();
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let gate = TestGate {
+        initial: Permit::Allow,
+        retry: Permit::Deny("no retries".into()),
+    };
+    let operation = operation(gate, time, sleep, sent.clone());
+
+    let err = operation.invoke(()).await.expect_err("always fails");
+    // The error of the last attempt is returned rather than the denial
+    let err = format!("{}", DisplayErrorContext(&err));
+    assert!(err.contains("service unavailable"), "{err}");
+    assert_eq!(1, sent.lock().unwrap().len());
+}
+
+#[tokio::test]
+async fn gate_can_delay_retries() {
+    let (time, sleep) = tick_advance_time_and_sleep();
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let gate = TestGate {
+        initial: Permit::Allow,
+        retry: Permit::DelayBy(Duration::from_secs(5)),
+    };
+    let operation = operation(gate, time.clone(), sleep, sent.clone());
+
+    let task = tokio::spawn(async move { operation.invoke(()).await });
+    tokio::task::yield_now().await;
+    time.tick(Duration::from_secs(20)).await;
+    task.await.unwrap().expect_err("always fails");
+
+    assert_eq!(
+        vec![
+            Duration::ZERO,
+            Duration::from_secs(5),
+            Duration::from_secs(10)
+        ],
+        *sent.lock().unwrap()
+    );
+}
@@ -0,0 +1,293 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_async::rt::sleep::TokioSleep;
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::retries::classifiers::HttpStatusCodeClassifier;
+use aws_smithy_runtime::client::retries::{
+    RetryBudgetEvent, RetryBudgetSnapshot, RetryPartition, TokenBucket,
+};
+use aws_smithy_runtime_api::client::orchestrator::OrchestratorError;
+use aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::{Layer, Storable, StoreReplace};
+use aws_smithy_types::retry::RetryConfig;
+use std::convert::Infallible;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// An operation sending requests to `endpoint_url`. The endpoint either always fails with a
+/// retryable error, or fails every other attempt. `retry_state` is either a [`RetryPartition`],
+/// a [`TokenBucket`], or a [`RetryConfig`] with a partition.
+fn operation<T>(
+    endpoint_url: &str,
+    retry_state: Option<T>,
+    always_fail: bool,
+    attempts: Arc<AtomicUsize>,
+) -> Operation<(), (), Infallible>
+where
+    T: Storable<Storer = StoreReplace<T>> + Clone + Debug + Send + Sync + 'static,
+{
+    let mut builder = Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .endpoint_url(endpoint_url)
+        .standard_retry(
+            &RetryConfig::standard()
+                .with_max_attempts(3)
+                .with_initial_backoff(Duration::ZERO),
+        )
+        .retry_classifier(HttpStatusCodeClassifier::default())
+        .sleep_impl(TokioSleep::new())
+        .http_client(infallible_client_fn(move |_| {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+            let status = if always_fail || attempt & 1 == 0 {
+                503
+            } else {
+                200
+            };
+            http_02x::Response::builder()
+                .status(status)
+                .body(SdkBody::empty())
+                .unwrap()
+        }))
+        .serializer(|_| Ok(http_02x::Request::new(SdkBody::empty()).try_into().unwrap()))
+        .deserializer(|response| {
+            if response.status().is_success() {
+                Ok(())
+            } else {
+                Err(OrchestratorError::response("service unavailable".into()))
+            }
+        });
+    if let Some(retry_state) = retry_state {
+        let mut layer = Layer::new("retry_state");
+        layer.store_put(retry_state);
+        builder = builder.runtime_plugin(StaticRuntimePlugin::new().with_config(layer.freeze()));
+    }
+    builder.build()
+}
+
+/// Calls `operation`, which always fails, until it stops retrying.
+async fn exhaust_retries(operation: &Operation<(), (), Infallible>, attempts: &AtomicUsize) {
+    for _ in 0..1000 {
+        let before = attempts.load(Ordering::SeqCst);
+        operation.invoke(()).await.expect_err("always fails");
+        if attempts.load(Ordering::SeqCst) - before == 1 {
+            return;
+        }
+    }
+    panic!("retries should have been exhausted");
+}
+
+#[tokio::test]
+async fn failing_endpoint_does_not_exhaust_retries_of_healthy_endpoint() {
+    let failing_attempts = Arc::new(AtomicUsize::new(0));
+    let failing = operation(
+        "http://failing.partition-test.localhost",
+        Some(RetryPartition::by_endpoint()),
+        true,
+        failing_attempts.clone(),
+    );
+    let healthy_attempts = Arc::new(AtomicUsize::new(0));
+    let healthy = operation(
+        "http://healthy.partition-test.localhost",
+        Some(RetryPartition::by_endpoint()),
+        false,
+        healthy_attempts.clone(),
+    );
+
+    exhaust_retries(&failing, &failing_attempts).await;
+
+    for _ in 0..10 {
+        let before = healthy_attempts.load(Ordering::SeqCst);
+        healthy.invoke(()).await.expect("succeeds after a retry");
+        assert_eq!(2, healthy_attempts.load(Ordering::SeqCst) - before);
+    }
+}
+
+#[tokio::test]
+async fn partitions_can_be_set_on_the_retry_config() {
+    let retry_config = || {
+        Some(
+            RetryConfig::standard()
+                .with_max_attempts(3)
+                .with_initial_backoff(Duration::ZERO)
+                .with_partition(RetryPartition::by_endpoint()),
+        )
+    };
+    let failing_attempts = Arc::new(AtomicUsize::new(0));
+    let failing = operation(
+        "http://failing.retry-config-test.localhost",
+        retry_config(),
+        true,
+        failing_attempts.clone(),
+    );
+    let healthy_attempts = Arc::new(AtomicUsize::new(0));
+    let healthy = operation(
+        "http://healthy.retry-config-test.localhost",
+        retry_config(),
+        false,
+        healthy_attempts.clone(),
+    );
+
+    exhaust_retries(&failing, &failing_attempts).await;
+
+    let before = healthy_attempts.load(Ordering::SeqCst);
+    healthy.invoke(()).await.expect("succeeds after a retry");
+    assert_eq!(2, healthy_attempts.load(Ordering::SeqCst) - before);
+}
+
+#[tokio::test]
+async fn clients_with_the_same_named_partition_share_a_token_bucket() {
+    let partition = || Some(RetryPartition::new("shared-partition-test"));
+    let first_attempts = Arc::new(AtomicUsize::new(0));
+    let first = operation(
+        "http://first.localhost",
+        partition(),
+        true,
+        first_attempts.clone(),
+    );
+    let second_attempts = Arc::new(AtomicUsize::new(0));
+    let second = operation(
+        "http://second.localhost",
+        partition(),
+        true,
+        second_attempts.clone(),
+    );
+    let unshared_attempts = Arc::new(AtomicUsize::new(0));
+    let unshared = operation(
+        "http://second.localhost",
+        Some(RetryPartition::unique("shared-partition-test")),
+        true,
+        unshared_attempts.clone(),
+    );
+
+    exhaust_retries(&first, &first_attempts).await;
+
+    second.invoke(()).await.expect_err("always fails");
+    assert_eq!(1, second_attempts.load(Ordering::SeqCst));
+    // unique partitions aren't shared, even with a partition of the same name
+    unshared.invoke(()).await.expect_err("always fails");
+    assert_eq!(3, unshared_attempts.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn clients_of_the_same_service_share_a_partition_by_default() {
+    let first_attempts = Arc::new(AtomicUsize::new(0));
+    let first = operation(
+        "http://first.localhost",
+        None::<RetryPartition>,
+        true,
+        first_attempts.clone(),
+    );
+    let second_attempts = Arc::new(AtomicUsize::new(0));
+    let second = operation(
+        "http://second.localhost",
+        None::<RetryPartition>,
+        true,
+        second_attempts.clone(),
+    );
+
+    exhaust_retries(&first, &first_attempts).await;
+
+    second.invoke(()).await.expect_err("always fails");
+    assert_eq!(1, second_attempts.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn retry_budget_snapshot_reflects_withdrawals_and_recovery() {
+    let budget = TokenBucket::new(100);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    budget.observe({
+        let events = events.clone();
+        move |event| events.lock().unwrap().push(event.clone())
+    });
+    let failing_attempts = Arc::new(AtomicUsize::new(0));
+    let failing = operation(
+        "http://failing.localhost",
+        Some(budget.clone()),
+        true,
+        failing_attempts.clone(),
+    );
+    let flaky_attempts = Arc::new(AtomicUsize::new(0));
+    let flaky = operation(
+        "http://flaky.localhost",
+        Some(budget.clone()),
+        false,
+        flaky_attempts.clone(),
+    );
+
+    // two retries that both fail: the first permit is forgotten when the second one replaces it,
+    // and the second is held until the operation succeeds
+    failing.invoke(()).await.expect_err("always fails");
+    assert_eq!(3, failing_attempts.load(Ordering::SeqCst));
+    assert_eq!(80, budget.snapshot().available());
+    assert_eq!(2, budget.snapshot().recent_withdrawals());
+
+    // a retry that succeeds gives its tokens back
+    flaky.invoke(()).await.expect("succeeds after a retry");
+    assert_eq!(80, budget.snapshot().available());
+    assert_eq!(3, budget.snapshot().recent_withdrawals());
+    drop(failing);
+    assert_eq!(90, budget.snapshot().available());
+
+    // requests that succeed without retries regenerate the forgotten tokens
+    for _ in 0..10 {
+        flaky_attempts.fetch_add(1, Ordering::SeqCst);
+        flaky
+            .invoke(())
+            .await
+            .expect("succeeds on the first attempt");
+    }
+    let snapshot: RetryBudgetSnapshot = budget.snapshot();
+    assert_eq!(100, snapshot.available());
+    assert_eq!(0, snapshot.recent_withdrawals());
+
+    let events = events.lock().unwrap();
+    let summary = events
+        .iter()
+        .take(5)
+        .map(|event| match event {
+            RetryBudgetEvent::Withdrawal { amount, .. } => -(*amount as i64),
+            RetryBudgetEvent::Deposit { amount, .. } => *amount as i64,
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(vec![-10, -10, -10, 10, 10], summary);
+    assert_eq!(15, events.len());
+    assert_eq!(100, events[14].snapshot().available());
+}
+
+#[tokio::test]
+async fn clients_sharing_a_retry_budget_observe_each_others_consumption() {
+    let budget = TokenBucket::new(20);
+    let first_attempts = Arc::new(AtomicUsize::new(0));
+    let first = operation(
+        "http://reads.localhost",
+        Some(budget.clone()),
+        true,
+        first_attempts.clone(),
+    );
+    let second_attempts = Arc::new(AtomicUsize::new(0));
+    let second = operation(
+        "http://writes.localhost",
+        Some(budget.clone()),
+        true,
+        second_attempts.clone(),
+    );
+
+    exhaust_retries(&first, &first_attempts).await;
+    assert!(budget.snapshot().available() < 5, "{:?}", budget.snapshot());
+
+    second.invoke(()).await.expect_err("always fails");
+    assert_eq!(1, second_attempts.load(Ordering::SeqCst));
+    assert_eq!(20, budget.snapshot().capacity());
+}
@@ -0,0 +1,102 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime_api::client::endpoint::{EndpointPrefix, PreflightTlsHostnameCheck};
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::{FrozenLayer, Layer};
+use aws_smithy_types::error::display::DisplayErrorContext;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+/// Builds an operation that sends requests to `endpoint_url` with the given host prefix, and
+/// records the URIs of the requests that were sent.
+fn operation(
+    endpoint_url: &str,
+    prefix: &str,
+    uris: Arc<Mutex<Vec<String>>>,
+) -> Operation<(), (), Infallible> {
+    let mut layer = Layer::new("tls_hostname_preflight");
+    layer.store_put(EndpointPrefix::new(prefix).unwrap());
+    layer.store_put(PreflightTlsHostnameCheck::new(true));
+    Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .no_auth()
+        .no_retry()
+        .endpoint_url(endpoint_url)
+        .runtime_plugin(StaticRuntimePlugin::new().with_config(FrozenLayer::from(layer)))
+        .http_client(infallible_client_fn(move |request| {
+            uris.lock().unwrap().push(request.uri().to_string());
+            http_02x::Response::builder()
+                .status(200)
+                .body(SdkBody::empty())
+                .unwrap()
+        }))
+        .serializer(|_| {
+            Ok(http_02x::Request::builder()
+                .uri("/key")
+                .body(SdkBody::empty())
+                .unwrap()
+                .try_into()
+                .unwrap())
+        })
+        .deserializer::<(), Infallible>(|_| Ok(()))
+        .build()
+}
+
+#[tokio::test]
+async fn dotted_host_prefix_fails_before_connecting() {
+    let uris = Arc::new(Mutex::new(Vec::new()));
+    let err = operation("https://s3.example.com", "my.dotted.bucket.", uris.clone())
+        .invoke(())
+        .await
+        .expect_err("dotted bucket can't be covered by a wildcard certificate");
+
+    assert!(
+        matches!(err, SdkError::ConstructionFailure(_)),
+        "expected a construction failure, got {err:?}"
+    );
+    let message = format!("{}", DisplayErrorContext(&err));
+    assert!(
+        message.contains("the host `my.dotted.bucket.s3.example.com` can't be covered by a wildcard TLS certificate for `*.s3.example.com`"),
+        "{message}"
+    );
+    assert!(message.contains("path-style"), "{message}");
+    assert!(uris.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn single_label_host_prefix_passes() {
+    let uris = Arc::new(Mutex::new(Vec::new()));
+    operation("https://s3.example.com", "mybucket.", uris.clone())
+        .invoke(())
+        .await
+        .expect("success");
+
+    assert_eq!(
+        vec!["https://mybucket.s3.example.com/key".to_string()],
+        *uris.lock().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn http_endpoints_skip_the_check() {
+    let uris = Arc::new(Mutex::new(Vec::new()));
+    operation("http://s3.example.com", "my.dotted.bucket.", uris.clone())
+        .invoke(())
+        .await
+        .expect("success");
+
+    assert_eq!(
+        vec!["http://my.dotted.bucket.s3.example.com/key".to_string()],
+        *uris.lock().unwrap()
+    );
+}
@@ -31,6 +31,17 @@ pub fn decode(input: impl AsRef<str>) -> Result<Vec<u8>, DecodeError> {
     STANDARD.decode_to_vec(input.as_ref()).map_err(DecodeError)
 }
 
+/// Decode `input` from base64 using the standard base64 alphabet, appending the decoded bytes to `out`
+///
+/// Unlike [`decode`], this lets the caller reuse an allocation across many values.
+/// If input is not a valid base64 encoded string, this function will return `DecodeError`, and
+/// `out` may have been partially written to.
+pub fn decode_append(input: impl AsRef<[u8]>, out: &mut Vec<u8>) -> Result<(), DecodeError> {
+    STANDARD
+        .decode_append(input.as_ref(), out)
+        .map_err(DecodeError)
+}
+
 /// Encode `input` into base64 using the standard base64 alphabet
 pub fn encode(input: impl AsRef<[u8]>) -> String {
     STANDARD.encode_to_string(input.as_ref())
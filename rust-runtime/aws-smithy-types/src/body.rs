@@ -40,15 +40,30 @@ pin_project! {
         // In the event of retry, this function will be called to generate a new body. See
         // [`try_clone()`](SdkBody::try_clone)
         rebuild: Option<Arc<dyn (Fn() -> Inner) + Send + Sync>>,
+        // Where the body comes from when it is rebuilt. Set whenever `rebuild` is set.
+        replay_source: Option<ReplaySource>,
         bytes_contents: Option<Bytes>
     }
 }
 
+/// How an [`SdkBody`] is replayed when a request is retried.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplaySource {
+    /// The body is held in memory, and a copy of it is sent again.
+    MemoryClone,
+    /// The body is read from a file, which is opened again.
+    FileReopen,
+    /// The body is re-created by the function given to [`SdkBody::retryable`].
+    Factory,
+}
+
 impl Debug for SdkBody {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("SdkBody")
             .field("inner", &self.inner)
             .field("retryable", &self.rebuild.is_some())
+            .field("replay_source", &self.replay_source)
             .finish()
     }
 }
@@ -110,6 +125,7 @@ impl SdkBody {
         SdkBody {
             inner: initial.inner,
             rebuild: Some(Arc::new(move || f().inner)),
+            replay_source: Some(ReplaySource::Factory),
             bytes_contents: initial.bytes_contents,
         }
     }
@@ -120,6 +136,7 @@ impl SdkBody {
         Self {
             inner: Inner::Taken,
             rebuild: None,
+            replay_source: None,
             bytes_contents: None,
         }
     }
@@ -129,6 +146,7 @@ impl SdkBody {
         Self {
             inner: Inner::Once { inner: None },
             rebuild: Some(Arc::new(|| Inner::Once { inner: None })),
+            replay_source: Some(ReplaySource::MemoryClone),
             bytes_contents: Some(Bytes::new()),
         }
     }
@@ -186,6 +204,7 @@ impl SdkBody {
                 )),
             },
             rebuild: None,
+            replay_source: None,
             bytes_contents: None,
         }
     }
@@ -229,11 +248,25 @@ impl SdkBody {
             Self {
                 inner: next,
                 rebuild: self.rebuild.clone(),
+                replay_source: self.replay_source,
                 bytes_contents: self.bytes_contents.clone(),
             }
         })
     }
 
+    /// Returns how this body is replayed by [`try_clone`](SdkBody::try_clone), or `None` if it
+    /// can't be replayed.
+    pub fn replay_source(&self) -> Option<ReplaySource> {
+        self.replay_source
+    }
+
+    pub(crate) fn with_replay_source(mut self, replay_source: Option<ReplaySource>) -> Self {
+        if self.rebuild.is_some() {
+            self.replay_source = replay_source;
+        }
+        self
+    }
+
     /// Return `true` if this SdkBody is streaming, `false` if it is in-memory.
     pub fn is_streaming(&self) -> bool {
         matches!(self.inner, Inner::Dyn { .. })
@@ -295,7 +328,9 @@ impl SdkBody {
     /// returning the result.
     pub fn map(self, f: impl Fn(SdkBody) -> SdkBody + Sync + Send + 'static) -> SdkBody {
         if self.rebuild.is_some() {
+            let replay_source = self.replay_source;
             SdkBody::retryable(move || f(self.try_clone().unwrap()))
+                .with_replay_source(replay_source)
         } else {
             f(self)
         }
@@ -312,7 +347,9 @@ impl SdkBody {
     ) -> SdkBody {
         let contents = self.bytes_contents.clone();
         let mut out = if self.rebuild.is_some() {
+            let replay_source = self.replay_source;
             SdkBody::retryable(move || f(self.try_clone().unwrap()))
+                .with_replay_source(replay_source)
         } else {
             f(self)
         };
@@ -337,6 +374,7 @@ impl From<Bytes> for SdkBody {
             rebuild: Some(Arc::new(move || Inner::Once {
                 inner: Some(bytes.clone()),
             })),
+            replay_source: Some(ReplaySource::MemoryClone),
             bytes_contents: Some(b),
         }
     }
@@ -362,7 +400,7 @@ impl From<&[u8]> for SdkBody {
 
 #[cfg(test)]
 mod test {
-    use crate::body::SdkBody;
+    use crate::body::{ReplaySource, SdkBody};
     use std::pin::Pin;
 
     #[test]
@@ -405,6 +443,20 @@ mod test {
         assert!(format!("{:?}", body).contains("Once"));
     }
 
+    #[test]
+    fn replay_source_survives_map() {
+        let body = SdkBody::from("123");
+        assert_eq!(Some(ReplaySource::MemoryClone), body.replay_source());
+        let mapped = body.map(|body| body);
+        assert_eq!(Some(ReplaySource::MemoryClone), mapped.replay_source());
+        let cloned = mapped.try_clone().unwrap();
+        assert_eq!(Some(ReplaySource::MemoryClone), cloned.replay_source());
+
+        let factory = SdkBody::retryable(|| SdkBody::from("123"));
+        assert_eq!(Some(ReplaySource::Factory), factory.replay_source());
+        assert_eq!(None, SdkBody::taken().replay_source());
+    }
+
     #[test]
     fn sdk_body_is_send() {
         fn is_send<T: Send>() {}
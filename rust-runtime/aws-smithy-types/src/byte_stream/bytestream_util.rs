@@ -3,7 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use crate::body::SdkBody;
+use crate::body::{ReplaySource, SdkBody};
 use crate::byte_stream::{error::Error, error::ErrorKind, ByteStream};
 use std::cmp::min;
 use std::future::Future;
@@ -215,7 +215,9 @@ impl FsBuilder {
                 ))
             };
 
-            Ok(ByteStream::new(SdkBody::retryable(body_loader)))
+            Ok(ByteStream::new(
+                SdkBody::retryable(body_loader).with_replay_source(Some(ReplaySource::FileReopen)),
+            ))
         } else if let Some(mut file) = self.file {
             // When starting from a `File`, we need to do our own seeking
             if offset != 0 {
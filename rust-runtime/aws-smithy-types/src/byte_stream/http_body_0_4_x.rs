@@ -3,9 +3,15 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use crate::body::SdkBody;
+use crate::body::{Error, SdkBody};
 use crate::byte_stream::ByteStream;
 use bytes::Bytes;
+use http::HeaderMap;
+use pin_project_lite::pin_project;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Mutex, PoisonError};
+use std::task::{ready, Context, Poll};
 
 impl ByteStream {
     /// Construct a `ByteStream` from a type that implements [`http_body_0_4::Body<Data = Bytes>`](http_body_0_4::Body).
@@ -18,6 +24,104 @@ impl ByteStream {
     {
         ByteStream::new(SdkBody::from_body_0_4(body))
     }
+
+    /// Returns a `ByteStream` that sends the trailers produced by `trailers` after the data of this stream.
+    ///
+    /// `trailers` isn't called until all of the data has been read, so the trailers can depend on it, for
+    /// example, to send a checksum that was calculated while streaming. The trailers are merged with
+    /// any trailers this stream already has.
+    ///
+    /// The returned stream is not retryable. Whether trailers reach the peer depends on the HTTP
+    /// implementation. For example, hyper 0.14 sends trailers over HTTP/2 and, since 0.14.30, over
+    /// HTTP/1.1 with chunked encoding. Over HTTP/1.1, a hyper server only sends them when the
+    /// request has a `TE: trailers` header, and only sends the fields named in the `Trailer` header
+    /// of the response.
+    ///
+    /// _Note: This is only available when the `http-body-0-4-x` feature is enabled._
+    pub fn with_trailers<F, Fut>(self, trailers: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = HeaderMap> + Send + 'static,
+    {
+        ByteStream::new(SdkBody::from_body_0_4(WithTrailers {
+            body: self.into_inner(),
+            trailers: Mutex::new(Trailers::NotStarted(trailers)),
+        }))
+    }
+}
+
+pin_project! {
+    struct WithTrailers<F, Fut> {
+        #[pin]
+        body: SdkBody,
+        // Only ever accessed through `Mutex::get_mut`. This makes the body `Sync` without
+        // requiring the future to be `Sync`.
+        trailers: Mutex<Trailers<F, Fut>>,
+    }
+}
+
+enum Trailers<F, Fut> {
+    NotStarted(F),
+    Pending {
+        inner: Option<HeaderMap>,
+        future: Pin<Box<Fut>>,
+    },
+    Done,
+}
+
+impl<F, Fut> http_body_0_4::Body for WithTrailers<F, Fut>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = HeaderMap>,
+{
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        self.project().body.poll_data(cx)
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        let this = self.project();
+        let mut body = this.body;
+        let trailers = this
+            .trailers
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        loop {
+            match trailers {
+                Trailers::NotStarted(_) => {
+                    let inner = ready!(body.as_mut().poll_trailers(cx))?;
+                    let f = match std::mem::replace(trailers, Trailers::Done) {
+                        Trailers::NotStarted(f) => f,
+                        _ => unreachable!(),
+                    };
+                    *trailers = Trailers::Pending {
+                        inner,
+                        future: Box::pin(f()),
+                    };
+                }
+                Trailers::Pending { inner, future } => {
+                    let additional = ready!(future.as_mut().poll(cx));
+                    let mut merged = inner.take().unwrap_or_default();
+                    merged.extend(additional);
+                    *trailers = Trailers::Done;
+                    return Poll::Ready(Ok((!merged.is_empty()).then_some(merged)));
+                }
+                Trailers::Done => return Poll::Ready(Ok(None)),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> http_body_0_4::SizeHint {
+        http_body_0_4::Body::size_hint(&self.body)
+    }
 }
 
 #[cfg(feature = "hyper-0-14-x")]
@@ -30,8 +134,12 @@ impl From<hyper_0_14::Body> for ByteStream {
 #[cfg(test)]
 mod tests {
     use crate::body::SdkBody;
-    use crate::byte_stream::Inner;
+    use crate::byte_stream::{ByteStream, Inner};
     use bytes::Bytes;
+    use http::{HeaderMap, HeaderValue};
+    use http_body_0_4::Body;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
 
     #[tokio::test]
     async fn read_from_channel_body() {
@@ -48,6 +156,50 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn trailers_are_sent_after_the_data() {
+        let (mut sender, body) = hyper_0_14::Body::channel();
+        let length = Arc::new(AtomicUsize::new(0));
+        let byte_stream = ByteStream::from_body_0_4(body).with_trailers({
+            let length = length.clone();
+            || async move {
+                let mut trailers = HeaderMap::new();
+                trailers.insert("x-length", HeaderValue::from(length.load(Ordering::SeqCst)));
+                trailers
+            }
+        });
+        tokio::spawn(async move {
+            for chunk in ["data 1", "data 2"] {
+                sender.send_data(Bytes::from(chunk)).await.unwrap();
+                length.fetch_add(chunk.len(), Ordering::SeqCst);
+            }
+            let mut trailers = HeaderMap::new();
+            trailers.insert("x-inner", HeaderValue::from_static("inner"));
+            sender.send_trailers(trailers).await.unwrap();
+        });
+
+        let mut body = Box::pin(byte_stream.into_inner());
+        let mut data = Vec::new();
+        while let Some(chunk) = body.data().await {
+            data.extend_from_slice(&chunk.unwrap());
+        }
+        assert_eq!(b"data 1data 2".as_slice(), data);
+        let trailers = body.trailers().await.unwrap().expect("trailers");
+        assert_eq!("12", trailers["x-length"]);
+        assert_eq!("inner", trailers["x-inner"]);
+        assert_eq!(None, body.trailers().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn empty_trailers_are_not_sent() {
+        let byte_stream =
+            ByteStream::from_static(b"data").with_trailers(|| async { HeaderMap::new() });
+        let mut body = Box::pin(byte_stream.into_inner());
+        assert_eq!(b"data".as_slice(), body.data().await.unwrap().unwrap());
+        assert!(body.data().await.is_none());
+        assert_eq!(None, body.trailers().await.unwrap());
+    }
+
     #[cfg(feature = "rt-tokio")]
     #[tokio::test]
     async fn path_based_bytestreams() -> Result<(), Box<dyn std::error::Error>> {
@@ -0,0 +1,159 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Response headers captured into operation outputs and errors regardless of model bindings.
+//!
+//! Clients can be configured with a list of response header names to capture. The orchestrator
+//! copies those headers out of every response before it is deserialized, and makes them available
+//! to the deserializer with [`with_captured_headers`] so that generated code can attach them to
+//! outputs and to [`ErrorMetadata`](crate::error::ErrorMetadata).
+
+use crate::config_bag::{Storable, StoreReplace};
+use std::cell::RefCell;
+
+thread_local! {
+    static CURRENT: RefCell<Option<CapturedHeaders>> = const { RefCell::new(None) };
+}
+
+/// Trait to retrieve the response headers captured for an operation output or error.
+pub trait ProvideCapturedHeaders {
+    /// Returns the captured response headers, if header capture was configured.
+    fn captured_headers(&self) -> Option<&CapturedHeaders>;
+}
+
+/// Response headers that were captured because they were on the configured allow-list.
+///
+/// Header names are stored in lowercase and looked up case-insensitively. Every value of a
+/// multi-value header is kept, in the order it appeared in the response.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CapturedHeaders {
+    headers: Vec<(String, Vec<String>)>,
+}
+
+impl CapturedHeaders {
+    /// Creates an empty set of captured headers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a value for the header `name`.
+    pub fn append(&mut self, name: impl AsRef<str>, value: impl Into<String>) {
+        let name = name.as_ref().to_ascii_lowercase();
+        match self.headers.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, values)) => values.push(value.into()),
+            None => self.headers.push((name, vec![value.into()])),
+        }
+    }
+
+    /// Returns the first value of the header `name`, if it was captured.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.get_all(name).first().map(String::as_str)
+    }
+
+    /// Returns all the values of the header `name`, or an empty slice if it wasn't captured.
+    pub fn get_all(&self, name: &str) -> &[String] {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, values)| values.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over the captured header names and their values.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.headers
+            .iter()
+            .map(|(name, values)| (name.as_str(), values.as_slice()))
+    }
+
+    /// Returns true if no headers were captured.
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+}
+
+impl ProvideCapturedHeaders for CapturedHeaders {
+    fn captured_headers(&self) -> Option<&CapturedHeaders> {
+        Some(self)
+    }
+}
+
+impl Storable for CapturedHeaders {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Runs `f` with `captured` available from [`current`] on the current thread.
+///
+/// The headers that were available before are restored when `f` returns or panics.
+pub fn with_captured_headers<T>(captured: &CapturedHeaders, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.with(|current| current.replace(Some(captured.clone())));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Restores the headers that were available before [`with_captured_headers`] when dropped.
+struct Restore(Option<CapturedHeaders>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| current.replace(previous));
+    }
+}
+
+/// Returns the headers captured for the response that is being deserialized on the current thread.
+///
+/// This returns `None` when header capture isn't configured.
+pub fn current() -> Option<CapturedHeaders> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn multi_value_headers_are_preserved() {
+        let mut captured = CapturedHeaders::new();
+        captured.append("X-Rate-Limit", "10");
+        captured.append("x-cache", "hit");
+        captured.append("x-rate-limit", "20");
+        assert_eq!(Some("10"), captured.get("x-rate-limit"));
+        assert_eq!(&["10", "20"], captured.get_all("X-RATE-LIMIT"));
+        assert!(captured.get_all("x-unknown").is_empty());
+        assert_eq!(
+            vec!["x-rate-limit", "x-cache"],
+            captured.iter().map(|(name, _)| name).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn captured_headers_are_scoped() {
+        assert_eq!(None, current());
+        let mut outer = CapturedHeaders::new();
+        outer.append("x-region", "us-west-2");
+        with_captured_headers(&outer, || {
+            with_captured_headers(&CapturedHeaders::new(), || {
+                assert_eq!(Some(CapturedHeaders::new()), current());
+            });
+            assert_eq!(Some(outer.clone()), current());
+        });
+        assert_eq!(None, current());
+    }
+
+    #[test]
+    fn captured_headers_are_restored_after_a_panic() {
+        let mut outer = CapturedHeaders::new();
+        outer.append("x-region", "us-west-2");
+        with_captured_headers(&outer, || {
+            let panicked = std::panic::catch_unwind(|| {
+                with_captured_headers(&CapturedHeaders::new(), || panic!("deserialization failed"))
+            });
+            assert!(panicked.is_err());
+            assert_eq!(Some(outer.clone()), current());
+        });
+        assert_eq!(None, current());
+    }
+}
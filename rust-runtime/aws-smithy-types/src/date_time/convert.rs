@@ -0,0 +1,197 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Conversions between [`DateTime`] and the types of the [`chrono`](https://crates.io/crates/chrono)
+//! and [`time`](https://crates.io/crates/time) crates.
+//!
+//! Conversions preserve nanosecond precision. Both crates represent a smaller range of time than
+//! [`DateTime`], so converting a [`DateTime`] into them fails with a [`ConversionError`] when it's out
+//! of their range, while converting from them never fails.
+
+use crate::date_time::ConversionError;
+use crate::DateTime;
+
+/// Converts a [`DateTime`] into a [`chrono::DateTime`] in UTC.
+///
+/// Fails for times before `-262143-01-01T00:00:00Z` or after `+262142-12-31T23:59:59.999999999Z`,
+/// which `chrono` can't represent.
+#[cfg(feature = "convert-chrono")]
+impl TryFrom<DateTime> for chrono::DateTime<chrono::Utc> {
+    type Error = ConversionError;
+
+    fn try_from(date_time: DateTime) -> Result<Self, Self::Error> {
+        chrono::DateTime::from_timestamp(date_time.secs(), date_time.subsec_nanos()).ok_or(
+            ConversionError("the DateTime is out of the range supported by chrono::DateTime"),
+        )
+    }
+}
+
+#[cfg(feature = "convert-chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for DateTime {
+    fn from(date_time: chrono::DateTime<chrono::Utc>) -> Self {
+        // Leap seconds are represented by chrono with subsecond nanos beyond one second
+        let nanos = date_time.timestamp_subsec_nanos();
+        DateTime::from_secs_and_nanos(date_time.timestamp(), nanos.min(999_999_999))
+    }
+}
+
+#[cfg(feature = "convert-chrono")]
+impl From<chrono::DateTime<chrono::FixedOffset>> for DateTime {
+    fn from(date_time: chrono::DateTime<chrono::FixedOffset>) -> Self {
+        date_time.with_timezone(&chrono::Utc).into()
+    }
+}
+
+/// Converts a [`DateTime`] into a [`time::OffsetDateTime`] in UTC.
+///
+/// Fails for times before `-9999-01-01T00:00:00Z` or after `+9999-12-31T23:59:59.999999999Z`,
+/// unless the `large-dates` feature of `time` is enabled, which extends its range to six-digit years.
+#[cfg(feature = "convert-time")]
+impl TryFrom<DateTime> for time::OffsetDateTime {
+    type Error = ConversionError;
+
+    fn try_from(date_time: DateTime) -> Result<Self, Self::Error> {
+        time::OffsetDateTime::from_unix_timestamp_nanos(date_time.as_nanos()).map_err(|_| {
+            ConversionError("the DateTime is out of the range supported by time::OffsetDateTime")
+        })
+    }
+}
+
+#[cfg(feature = "convert-time")]
+impl From<time::OffsetDateTime> for DateTime {
+    fn from(date_time: time::OffsetDateTime) -> Self {
+        DateTime::from_nanos(date_time.unix_timestamp_nanos())
+            .expect("DateTime supports a greater range than OffsetDateTime")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::date_time::Format;
+    use crate::DateTime;
+    use proptest::prelude::*;
+
+    // 0001-01-01T00:00:00Z and 9999-12-31T23:59:59Z, the range of the RFC-3339 and HTTP date formatters
+    const MIN_FORMATTED_SECS: i64 = -62_135_596_800;
+    const MAX_FORMATTED_SECS: i64 = 253_402_300_799;
+
+    #[cfg(feature = "convert-chrono")]
+    #[test]
+    fn chrono_out_of_range() {
+        use chrono::Utc;
+
+        let err = chrono::DateTime::<Utc>::try_from(DateTime::from_secs(i64::MAX)).unwrap_err();
+        assert_eq!(
+            "the DateTime is out of the range supported by chrono::DateTime",
+            err.to_string()
+        );
+        assert!(chrono::DateTime::<Utc>::try_from(DateTime::from_secs(i64::MIN)).is_err());
+    }
+
+    #[cfg(feature = "convert-chrono")]
+    #[test]
+    fn chrono_before_epoch() {
+        use chrono::{TimeZone, Timelike, Utc};
+
+        // Sub-second nanos advance the time, even before the epoch
+        let date_time = DateTime::from_secs_and_nanos(-1, 250_000_000);
+        let expected = Utc
+            .with_ymd_and_hms(1969, 12, 31, 23, 59, 59)
+            .unwrap()
+            .with_nanosecond(250_000_000)
+            .unwrap();
+        assert_eq!(
+            expected,
+            chrono::DateTime::<Utc>::try_from(date_time).unwrap()
+        );
+        assert_eq!(date_time, DateTime::from(expected));
+
+        let fixed = expected.with_timezone(&chrono::FixedOffset::east_opt(3600).unwrap());
+        assert_eq!(date_time, DateTime::from(fixed));
+    }
+
+    #[cfg(feature = "convert-time")]
+    #[test]
+    fn time_out_of_range() {
+        let err = time::OffsetDateTime::try_from(DateTime::from_secs(MAX_FORMATTED_SECS + 1))
+            .unwrap_err();
+        assert_eq!(
+            "the DateTime is out of the range supported by time::OffsetDateTime",
+            err.to_string()
+        );
+        assert!(time::OffsetDateTime::try_from(DateTime::from_secs(i64::MIN)).is_err());
+    }
+
+    #[cfg(feature = "convert-time")]
+    #[test]
+    fn time_before_epoch() {
+        use time::{Date, Month, PrimitiveDateTime, Time};
+
+        let date_time = DateTime::from_secs_and_nanos(-1, 250_000_000);
+        let expected = PrimitiveDateTime::new(
+            Date::from_calendar_date(1969, Month::December, 31).unwrap(),
+            Time::from_hms_nano(23, 59, 59, 250_000_000).unwrap(),
+        )
+        .assume_utc();
+        assert_eq!(expected, time::OffsetDateTime::try_from(date_time).unwrap());
+        assert_eq!(date_time, DateTime::from(expected));
+    }
+
+    proptest! {
+        #[cfg(feature = "convert-chrono")]
+        #[test]
+        fn chrono_round_trip(
+            // About 260,000 years before and after the epoch, within the range of chrono
+            secs in -8_200_000_000_000i64..8_200_000_000_000i64,
+            nanos in 0..1_000_000_000u32,
+        ) {
+            let date_time = DateTime::from_secs_and_nanos(secs, nanos);
+            let chrono = chrono::DateTime::<chrono::Utc>::try_from(date_time).unwrap();
+            prop_assert_eq!(date_time.as_nanos(), chrono.timestamp() as i128 * 1_000_000_000 + chrono.timestamp_subsec_nanos() as i128);
+            prop_assert_eq!(date_time, DateTime::from(chrono));
+        }
+
+        #[cfg(feature = "convert-time")]
+        #[test]
+        fn time_round_trip(
+            // From the year -9960 to the year 9999, within the range of time
+            secs in -376_000_000_000i64..=MAX_FORMATTED_SECS,
+            nanos in 0..1_000_000_000u32,
+        ) {
+            let date_time = DateTime::from_secs_and_nanos(secs, nanos);
+            let time = time::OffsetDateTime::try_from(date_time).unwrap();
+            prop_assert_eq!(date_time.as_nanos(), time.unix_timestamp_nanos());
+            prop_assert_eq!(date_time, DateTime::from(time));
+        }
+
+        #[cfg(all(feature = "convert-chrono", feature = "convert-time"))]
+        #[test]
+        fn formatters_agree_with_chrono_and_time(
+            secs in MIN_FORMATTED_SECS..=MAX_FORMATTED_SECS,
+            nanos in 0..1_000_000_000u32,
+        ) {
+            use chrono::SecondsFormat;
+            use time::format_description::well_known::Rfc3339;
+
+            let date_time = DateTime::from_secs_and_nanos(secs, nanos);
+            let chrono = chrono::DateTime::<chrono::Utc>::try_from(date_time).unwrap();
+            let time = time::OffsetDateTime::try_from(date_time).unwrap();
+
+            let rfc3339 = chrono.to_rfc3339_opts(SecondsFormat::Nanos, true);
+            prop_assert_eq!(date_time, DateTime::from_str(&rfc3339, Format::DateTime).unwrap());
+            prop_assert_eq!(time, time::OffsetDateTime::parse(&rfc3339, &Rfc3339).unwrap());
+
+            // The RFC-3339 formatter truncates to microseconds
+            let truncated = DateTime::from_secs_and_nanos(secs, nanos / 1_000 * 1_000);
+            let formatted = date_time.fmt(Format::DateTime).unwrap();
+            prop_assert_eq!(truncated, DateTime::from(chrono::DateTime::parse_from_rfc3339(&formatted).unwrap()));
+            prop_assert_eq!(truncated, DateTime::from(time::OffsetDateTime::parse(&formatted, &Rfc3339).unwrap()));
+
+            // The HTTP date formatter truncates to seconds
+            let http_date = date_time.fmt(Format::HttpDate).unwrap();
+            prop_assert_eq!(&http_date, &chrono.format("%a, %d %b %Y %H:%M:%S GMT").to_string());
+        }
+    }
+}
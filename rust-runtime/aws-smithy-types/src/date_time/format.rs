@@ -7,8 +7,6 @@ use std::borrow::Cow;
 use std::error::Error;
 use std::fmt;
 
-const NANOS_PER_SECOND: u32 = 1_000_000_000;
-
 #[derive(Debug)]
 pub(super) enum DateTimeParseErrorKind {
     /// The given date-time string was invalid.
@@ -79,8 +77,22 @@ fn remove_trailing_zeros(string: &mut String) {
     }
 }
 
+/// Parses the digits of a fractional second (without the leading `.`) into nanoseconds.
+///
+/// Any number of digits is accepted, but digits beyond nanosecond precision are truncated.
+fn parse_subsecond_nanos(fraction: &str) -> Result<u32, DateTimeParseError> {
+    if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(DateTimeParseErrorKind::IntParseError.into());
+    }
+    let digits = &fraction[..fraction.len().min(9)];
+    let nanos: u32 = digits
+        .parse()
+        .map_err(|_| DateTimeParseErrorKind::IntParseError)?;
+    Ok(nanos * 10_u32.pow(9 - digits.len() as u32))
+}
+
 pub(crate) mod epoch_seconds {
-    use super::remove_trailing_zeros;
+    use super::{parse_subsecond_nanos, remove_trailing_zeros};
     use super::{DateTimeParseError, DateTimeParseErrorKind};
     use crate::DateTime;
     use std::str::FromStr;
@@ -97,6 +109,8 @@ pub(crate) mod epoch_seconds {
     }
 
     /// Parses the Smithy epoch seconds date-time format into a `DateTime`.
+    ///
+    /// Digits beyond nanosecond precision are truncated.
     pub(crate) fn parse(value: &str) -> Result<DateTime, DateTimeParseError> {
         let mut parts = value.splitn(2, '.');
         let (mut whole, mut decimal) = (0i64, 0u32);
@@ -111,18 +125,7 @@ pub(crate) mod epoch_seconds {
                 )
                 .into());
             }
-            if decimal_str.len() > 9 {
-                return Err(DateTimeParseErrorKind::Invalid(
-                    "decimal is longer than 9 digits".into(),
-                )
-                .into());
-            }
-            let missing_places = 9 - decimal_str.len() as isize;
-            decimal =
-                <u32>::from_str(decimal_str).map_err(|_| DateTimeParseErrorKind::IntParseError)?;
-            for _ in 0..missing_places {
-                decimal *= 10;
-            }
+            decimal = parse_subsecond_nanos(decimal_str)?;
         }
         Ok(DateTime::from_secs_and_nanos(whole, decimal))
     }
@@ -130,8 +133,8 @@ pub(crate) mod epoch_seconds {
 
 pub(crate) mod http_date {
     use crate::date_time::format::{
-        DateTimeFormatError, DateTimeFormatErrorKind, DateTimeParseError, DateTimeParseErrorKind,
-        NANOS_PER_SECOND,
+        parse_subsecond_nanos, DateTimeFormatError, DateTimeFormatErrorKind, DateTimeParseError,
+        DateTimeParseErrorKind,
     };
     use crate::DateTime;
     use std::str::FromStr;
@@ -148,7 +151,7 @@ pub(crate) mod http_date {
     ///
     /// Some notes:
     /// - HTTP date does not support years before `0001`—this will cause a panic.
-    /// - Subsecond nanos are not emitted
+    /// - Subsecond nanos are not emitted since IMF-fixdate only has second precision
     pub(crate) fn format(date_time: &DateTime) -> Result<String, DateTimeFormatError> {
         fn out_of_range<E: std::fmt::Display>(cause: E) -> DateTimeFormatError {
             DateTimeFormatErrorKind::OutOfRange(
@@ -246,12 +249,12 @@ pub(crate) mod http_date {
     ///
     /// This function has a few caveats:
     /// 1. It DOES NOT support the "deprecated" formats supported by HTTP date
-    /// 2. It supports up to 3 digits of subsecond precision
+    /// 2. It accepts fractional seconds, truncating digits beyond nanosecond precision
     ///
     /// Ok: "Mon, 16 Dec 2019 23:48:18 GMT"
     /// Ok: "Mon, 16 Dec 2019 23:48:18.123 GMT"
     /// Ok: "Mon, 16 Dec 2019 23:48:18.12 GMT"
-    /// Not Ok: "Mon, 16 Dec 2019 23:48:18.1234 GMT"
+    /// Ok: "Mon, 16 Dec 2019 23:48:18.1234567891 GMT"
     pub(crate) fn parse(s: &str) -> Result<DateTime, DateTimeParseError> {
         if !s.is_ascii() {
             return Err(DateTimeParseErrorKind::Invalid("date-time must be ASCII".into()).into());
@@ -284,12 +287,7 @@ pub(crate) mod http_date {
 
     fn parse_imf_fixdate(s: &[u8]) -> Result<DateTime, DateTimeParseError> {
         // Example: `Sun, 06 Nov 1994 08:49:37 GMT`
-        if s.len() < 29
-            || s.len() > 33
-            || !s.ends_with(b" GMT")
-            || s[16] != b' '
-            || s[19] != b':'
-            || s[22] != b':'
+        if s.len() < 29 || !s.ends_with(b" GMT") || s[16] != b' ' || s[19] != b':' || s[22] != b':'
         {
             return Err(DateTimeParseErrorKind::Invalid("incorrectly shaped string".into()).into());
         }
@@ -298,20 +296,12 @@ pub(crate) mod http_date {
                 // The date must end with " GMT", so read from the character after the `.`
                 // to 4 from the end
                 let fraction_slice = &s[26..s.len() - 4];
-                if fraction_slice.len() > 3 {
-                    // Only thousandths are supported
-                    return Err(DateTimeParseErrorKind::Invalid(
-                        "Smithy http-date only supports millisecond precision".into(),
-                    )
-                    .into());
-                }
-                let fraction: u32 = parse_slice(fraction_slice)?;
-                // We need to convert the fractional second to nanoseconds, so we need to scale
-                // according the the number of decimals provided
-                let multiplier = [10, 100, 1000];
-                fraction * (NANOS_PER_SECOND / multiplier[fraction_slice.len() - 1])
+                parse_subsecond_nanos(
+                    std::str::from_utf8(fraction_slice).expect("the date-time is ASCII"),
+                )?
             }
-            b' ' => 0,
+            // Nothing but " GMT" may follow the seconds
+            b' ' if s.len() == 29 => 0,
             _ => {
                 return Err(
                     DateTimeParseErrorKind::Invalid("incorrectly shaped string".into()).into(),
@@ -379,7 +369,8 @@ pub(crate) mod http_date {
 
 pub(crate) mod rfc3339 {
     use crate::date_time::format::{
-        DateTimeFormatError, DateTimeFormatErrorKind, DateTimeParseError, DateTimeParseErrorKind,
+        remove_trailing_zeros, DateTimeFormatError, DateTimeFormatErrorKind, DateTimeParseError,
+        DateTimeParseErrorKind,
     };
     use crate::DateTime;
     use time::format_description::well_known::Rfc3339;
@@ -431,6 +422,13 @@ pub(crate) mod rfc3339 {
     }
 
     /// Format a [DateTime] in the RFC-3339 date format
+    ///
+    /// The fractional second is omitted when it is zero, and is otherwise written with as many
+    /// digits as needed to represent it exactly, up to nanosecond precision.
+    ///
+    /// Ok: "2019-12-16T23:48:18Z"
+    /// Ok: "2019-12-16T23:48:18.52Z"
+    /// Ok: "2019-12-16T23:48:18.123456789Z"
     pub(crate) fn format(date_time: &DateTime) -> Result<String, DateTimeFormatError> {
         use std::fmt::Write;
         fn out_of_range<E: std::fmt::Display>(cause: E) -> DateTimeFormatError {
@@ -444,7 +442,7 @@ pub(crate) mod rfc3339 {
             )
             .into()
         }
-        let (year, month, day, hour, minute, second, micros) = {
+        let (year, month, day, hour, minute, second, nanos) = {
             let s = OffsetDateTime::from_unix_timestamp_nanos(date_time.as_nanos())
                 .map_err(out_of_range)?;
             (
@@ -454,7 +452,7 @@ pub(crate) mod rfc3339 {
                 s.hour(),
                 s.minute(),
                 s.second(),
-                s.microsecond(),
+                s.nanosecond(),
             )
         };
 
@@ -471,32 +469,20 @@ pub(crate) mod rfc3339 {
             year, month, day, hour, minute, second
         )
         .unwrap();
-        format_subsecond_fraction(&mut out, micros);
+        if nanos > 0 {
+            write!(out, ".{:09}", nanos).unwrap();
+            remove_trailing_zeros(&mut out);
+        }
         out.push('Z');
         Ok(out)
     }
-
-    /// Formats sub-second fraction for RFC-3339 (including the '.').
-    /// Expects to be called with a number of `micros` between 0 and 999_999 inclusive.
-    fn format_subsecond_fraction(into: &mut String, micros: u32) {
-        debug_assert!(micros < 1_000_000);
-        if micros > 0 {
-            into.push('.');
-            let (mut remaining, mut place) = (micros, 100_000);
-            while remaining > 0 {
-                let digit = (remaining / place) % 10;
-                into.push(char::from(b'0' + (digit as u8)));
-                remaining -= digit * place;
-                place /= 10;
-            }
-        }
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::date_time::format::rfc3339::AllowOffsets;
+    use crate::date_time::Format;
     use crate::DateTime;
     use lazy_static::lazy_static;
     use proptest::prelude::*;
@@ -615,7 +601,7 @@ mod tests {
             rfc3339::format(&DateTime::from_secs(-62_135_596_800)).unwrap()
         );
         assert_eq!(
-            "9999-12-31T23:59:59.999999Z",
+            "9999-12-31T23:59:59.999999999Z",
             rfc3339::format(&DateTime::from_secs_and_nanos(253402300799, 999_999_999)).unwrap()
         );
 
@@ -658,6 +644,8 @@ mod tests {
         assert!(epoch_seconds::parse("123.a").is_err());
         assert!(epoch_seconds::parse("123..").is_err());
         assert!(epoch_seconds::parse(".123").is_err());
+        assert!(epoch_seconds::parse("123.").is_err());
+        assert!(epoch_seconds::parse("123.1234567891a").is_err());
     }
 
     #[test]
@@ -718,16 +706,40 @@ mod tests {
     }
 
     #[test]
-    fn http_date_too_much_fraction() {
-        let fractional = "Mon, 16 Dec 2019 23:48:18.1212 GMT";
+    fn http_date_fraction_beyond_nanos_is_truncated() {
+        let fractional = "Mon, 16 Dec 2019 23:48:18.1234567891 GMT";
+        assert_eq!(
+            DateTime::from_secs_and_nanos(1576540098, 123_456_789),
+            http_date::parse(fractional).unwrap()
+        );
+    }
+
+    #[test]
+    fn http_date_trailing_characters() {
+        let date = "Mon, 16 Dec 2019 23:48:18 123 GMT";
         assert!(matches!(
-            http_date::parse(fractional),
+            http_date::parse(date),
             Err(DateTimeParseError {
                 kind: DateTimeParseErrorKind::Invalid(_)
             })
         ));
     }
 
+    #[test]
+    fn http_date_non_digit_fraction() {
+        for fractional in [
+            "Mon, 16 Dec 2019 23:48:18.+12 GMT",
+            "Mon, 16 Dec 2019 23:48:18.1234567891a GMT",
+        ] {
+            assert!(matches!(
+                http_date::parse(fractional),
+                Err(DateTimeParseError {
+                    kind: DateTimeParseErrorKind::IntParseError
+                })
+            ));
+        }
+    }
+
     #[test]
     fn http_date_bad_fraction() {
         let fractional = "Mon, 16 Dec 2019 23:48:18. GMT";
@@ -816,6 +828,104 @@ mod tests {
         }
     }
 
+    /// Sub-second nanos of `2019-12-16T23:48:18Z`, and their canonical `date-time` and
+    /// `epoch-seconds` representations. Every formatted value must parse back to the same
+    /// `DateTime`, whether it was formatted by a client and parsed by a server or vice versa.
+    const FRACTIONAL_SECONDS: &[(u32, &str, &str)] = &[
+        (0, "2019-12-16T23:48:18Z", "1576540098"),
+        (500_000_000, "2019-12-16T23:48:18.5Z", "1576540098.5"),
+        (520_000_000, "2019-12-16T23:48:18.52Z", "1576540098.52"),
+        (123_000_000, "2019-12-16T23:48:18.123Z", "1576540098.123"),
+        (1_000_000, "2019-12-16T23:48:18.001Z", "1576540098.001"),
+        (
+            123_456_000,
+            "2019-12-16T23:48:18.123456Z",
+            "1576540098.123456",
+        ),
+        (
+            123_456_789,
+            "2019-12-16T23:48:18.123456789Z",
+            "1576540098.123456789",
+        ),
+        (1, "2019-12-16T23:48:18.000000001Z", "1576540098.000000001"),
+    ];
+
+    #[test]
+    fn fractional_seconds_round_trip() {
+        for &(nanos, date_time, epoch_seconds) in FRACTIONAL_SECONDS {
+            let expected = DateTime::from_secs_and_nanos(1576540098, nanos);
+            assert_eq!(date_time, expected.fmt(Format::DateTime).unwrap());
+            assert_eq!(
+                expected,
+                DateTime::from_str(date_time, Format::DateTime).unwrap()
+            );
+            assert_eq!(epoch_seconds, expected.fmt(Format::EpochSeconds).unwrap());
+            assert_eq!(
+                expected,
+                DateTime::from_str(epoch_seconds, Format::EpochSeconds).unwrap()
+            );
+
+            // IMF-fixdate only has second precision, so the fraction is dropped
+            let http_date = expected.fmt(Format::HttpDate).unwrap();
+            assert_eq!("Mon, 16 Dec 2019 23:48:18 GMT", http_date);
+            assert_eq!(
+                DateTime::from_secs(1576540098),
+                DateTime::from_str(&http_date, Format::HttpDate).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn fractional_seconds_are_parsed_liberally() {
+        let test_cases = [
+            (Format::DateTime, "2019-12-16T23:48:18.520Z", 520_000_000),
+            (Format::DateTime, "2019-12-16T23:48:18.000000000Z", 0),
+            (
+                Format::DateTime,
+                "2019-12-16T23:48:18.1234567891Z",
+                123_456_789,
+            ),
+            (
+                Format::DateTime,
+                "2019-12-16T23:48:18.123456789999Z",
+                123_456_789,
+            ),
+            (Format::EpochSeconds, "1576540098.520", 520_000_000),
+            (Format::EpochSeconds, "1576540098.000000000", 0),
+            (Format::EpochSeconds, "1576540098.1234567891", 123_456_789),
+            (Format::EpochSeconds, "1576540098.123456789999", 123_456_789),
+            (
+                Format::HttpDate,
+                "Mon, 16 Dec 2019 23:48:18.5 GMT",
+                500_000_000,
+            ),
+            (
+                Format::HttpDate,
+                "Mon, 16 Dec 2019 23:48:18.520 GMT",
+                520_000_000,
+            ),
+            (
+                Format::HttpDate,
+                "Mon, 16 Dec 2019 23:48:18.123456 GMT",
+                123_456_000,
+            ),
+            (
+                Format::HttpDate,
+                "Mon, 16 Dec 2019 23:48:18.1234567891 GMT",
+                123_456_789,
+            ),
+        ];
+        for (format, input, nanos) in test_cases {
+            assert_eq!(
+                DateTime::from_secs_and_nanos(1576540098, nanos),
+                DateTime::from_str(input, format).unwrap(),
+                "failed to parse `{}` as {:?}",
+                input,
+                format
+            );
+        }
+    }
+
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(10000))]
 
@@ -823,5 +933,14 @@ mod tests {
         fn round_trip(secs in -10000000..9999999999i64, nanos in 0..1_000_000_000u32) {
             http_date_check_roundtrip(secs, nanos);
         }
+
+        #[test]
+        fn date_time_and_epoch_seconds_round_trip_losslessly(secs in -10000000..9999999999i64, nanos in 0..1_000_000_000u32) {
+            let date_time = DateTime::from_secs_and_nanos(secs, nanos);
+            let formatted = rfc3339::format(&date_time).unwrap();
+            assert_eq!(date_time, rfc3339::parse(&formatted, AllowOffsets::OffsetsForbidden).unwrap());
+            let formatted = epoch_seconds::format(&date_time);
+            assert_eq!(date_time, epoch_seconds::parse(&formatted).unwrap());
+        }
     }
 }
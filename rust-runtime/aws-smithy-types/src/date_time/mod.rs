@@ -17,6 +17,8 @@ use std::time::Duration;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
+#[cfg(any(feature = "convert-chrono", feature = "convert-time"))]
+mod convert;
 #[cfg(all(aws_sdk_unstable, feature = "serde-deserialize"))]
 mod de;
 mod format;
@@ -50,9 +52,18 @@ const NANOS_PER_SECOND_U32: u32 = 1_000_000_000;
 /// # }
 /// ```
 ///
-/// The [`aws-smithy-types-convert`](https://crates.io/crates/aws-smithy-types-convert) crate
-/// can be used for conversions to/from other libraries, such as
-/// [`time`](https://crates.io/crates/time) or [`chrono`](https://crates.io/crates/chrono).
+/// With the `convert-chrono` and `convert-time` features, it can also be converted to/from the types of
+/// [`chrono`](https://crates.io/crates/chrono) and [`time`](https://crates.io/crates/time) with
+/// `TryFrom` and `From`:
+/// ```rust
+/// # #[cfg(feature = "convert-chrono")]
+/// # fn doc_fn() -> Result<(), aws_smithy_types::date_time::ConversionError> {
+/// # use aws_smithy_types::date_time::DateTime;
+/// let chrono_date_time = chrono::DateTime::<chrono::Utc>::try_from(DateTime::from_secs(-5))?;
+/// assert_eq!(DateTime::from_secs(-5), DateTime::from(chrono_date_time));
+/// # Ok(())
+/// # }
+/// ```
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
 pub struct DateTime {
     pub(crate) seconds: i64,
@@ -80,6 +91,9 @@ impl DateTime {
     }
 
     /// Creates a `DateTime` from a number of nanoseconds since the Unix epoch.
+    ///
+    /// Negative values are before the epoch. Fails if the number of seconds doesn't fit in an `i64`.
+    #[doc(alias = "from_unix_timestamp_nanos")]
     pub fn from_nanos(epoch_nanos: i128) -> Result<Self, ConversionError> {
         let (seconds, subsecond_nanos) = epoch_nanos.div_mod_floor(&NANOS_PER_SECOND);
         let seconds = i64::try_from(seconds).map_err(|_| {
@@ -93,6 +107,8 @@ impl DateTime {
     }
 
     /// Returns the number of nanoseconds since the Unix epoch that this `DateTime` represents.
+    ///
+    /// This is the inverse of [`DateTime::from_nanos`], and never overflows.
     pub fn as_nanos(&self) -> i128 {
         let seconds = self.seconds as i128 * NANOS_PER_SECOND;
         seconds + self.subsecond_nanos as i128
@@ -368,6 +384,11 @@ impl fmt::Display for ConversionError {
 }
 
 /// Formats for representing a `DateTime` in the Smithy protocols.
+///
+/// Fractional seconds are parsed liberally: any number of digits is accepted, and digits beyond
+/// nanosecond precision are truncated. They are formatted without trailing zeros, so that
+/// `date-time` and `epoch-seconds` values round-trip losslessly. `http-date` values only have
+/// second precision, so their fractional seconds are dropped when formatting.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Format {
     /// RFC-3339 Date Time. If the date time has an offset, an error will be returned.
@@ -700,7 +721,7 @@ mod test {
     const MIN_RFC_3339_MILLIS: i64 = -62135596800000;
     const MAX_RFC_3339_MILLIS: i64 = 253402300799999;
 
-    // This test uses milliseconds, because `Format::DateTime` does not support nanoseconds.
+    // This test uses milliseconds, because RFC-3339 bounds are easiest to express in milliseconds.
     proptest! {
         #[test]
         fn ord_proptest(
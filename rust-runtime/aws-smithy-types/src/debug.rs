@@ -0,0 +1,147 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Helpers for deterministic `Debug` output.
+//!
+//! Generated types format their map members with [`SortedMap`], so that two equal values always have
+//! the same `Debug` output. [`Stable`] formats any value with one field per line, which keeps text
+//! diffs of two values readable:
+//!
+//! ```rust
+//! use aws_smithy_types::debug::Stable;
+//!
+//! #[derive(Debug)]
+//! struct Pokemon {
+//!     name: &'static str,
+//!     level: u8,
+//! }
+//!
+//! let pikachu = Pokemon { name: "pikachu", level: 5 };
+//! assert_eq!(
+//!     "Pokemon {\n    name: \"pikachu\",\n    level: 5,\n}",
+//!     format!("{:?}", Stable::new(&pikachu))
+//! );
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// Formats a map with its entries sorted by key.
+///
+/// `HashMap`s iterate in an order that changes from one run to the next. Entries are sorted by the
+/// `Debug` output of their keys, so keys don't need to implement `Ord`. The map itself is not modified.
+pub struct SortedMap<'a, K, V, S>(&'a HashMap<K, V, S>);
+
+impl<'a, K, V, S> SortedMap<'a, K, V, S> {
+    /// Wraps `map` to format its entries sorted by key.
+    pub fn new(map: &'a HashMap<K, V, S>) -> Self {
+        Self(map)
+    }
+}
+
+impl<K, V, S> fmt::Debug for SortedMap<'_, K, V, S>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut entries: Vec<_> = self
+            .0
+            .iter()
+            .map(|(key, value)| (format!("{key:?}"), key, value))
+            .collect();
+        entries.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+        f.debug_map()
+            .entries(entries.into_iter().map(|(_, key, value)| (key, value)))
+            .finish()
+    }
+}
+
+/// Formats a value with one field per line, whether or not the alternate flag (`{:#?}`) is set.
+///
+/// Both `Debug` and `Display` produce the same output. Combined with the sorted map members of generated
+/// types, two equal values always produce the same text, and line-based diffs of two different values
+/// only show the fields that changed.
+pub struct Stable<'a, T: ?Sized>(&'a T);
+
+impl<'a, T: ?Sized> Stable<'a, T> {
+    /// Wraps `value` to format it one field per line.
+    pub fn new(value: &'a T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Stable<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self.0)
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Display for Stable<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#?}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SortedMap, Stable};
+    use std::collections::HashMap;
+
+    fn map(keys: impl Iterator<Item = u32>) -> HashMap<String, u32> {
+        keys.map(|key| (format!("key-{key:02}"), key)).collect()
+    }
+
+    #[test]
+    fn entries_are_sorted_by_key() {
+        let map = map([3, 1, 2].into_iter());
+        assert_eq!(
+            r#"{"key-01": 1, "key-02": 2, "key-03": 3}"#,
+            format!("{:?}", SortedMap::new(&map))
+        );
+        assert_eq!(
+            "{}",
+            format!("{:?}", SortedMap::new(&HashMap::<String, u32>::new()))
+        );
+    }
+
+    #[test]
+    fn output_does_not_depend_on_insertion_order() {
+        let forwards = map(0..50);
+        let backwards = map((0..50).rev());
+        assert_eq!(
+            format!("{:?}", SortedMap::new(&forwards)),
+            format!("{:?}", SortedMap::new(&backwards))
+        );
+    }
+
+    #[test]
+    fn alternate_flag_is_honored() {
+        let map = map([2, 1].into_iter());
+        assert_eq!(
+            "{\n    \"key-01\": 1,\n    \"key-02\": 2,\n}",
+            format!("{:#?}", SortedMap::new(&map))
+        );
+    }
+
+    #[test]
+    fn stable_output_ignores_the_alternate_flag() {
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        struct Output {
+            name: Option<String>,
+            tags: Vec<&'static str>,
+        }
+
+        let output = Output {
+            name: Some("pikachu".into()),
+            tags: vec!["electric"],
+        };
+        let expected = "Output {\n    name: Some(\n        \"pikachu\",\n    ),\n    tags: [\n        \"electric\",\n    ],\n}";
+        assert_eq!(expected, format!("{:?}", Stable::new(&output)));
+        assert_eq!(expected, format!("{:#?}", Stable::new(&output)));
+        assert_eq!(expected, Stable::new(&output).to_string());
+    }
+}
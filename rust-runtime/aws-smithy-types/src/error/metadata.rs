@@ -5,10 +5,17 @@
 
 //! Error metadata
 
+use crate::captured_headers::{CapturedHeaders, ProvideCapturedHeaders};
+use crate::config_bag::{Storable, StoreReplace};
 use crate::retry::{ErrorKind, ProvideErrorKind};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt;
 
+thread_local! {
+    static CURRENT_EXTRAS: RefCell<Option<ErrorMetadataExtras>> = const { RefCell::new(None) };
+}
+
 /// Trait to retrieve error metadata from a result
 pub trait ProvideErrorMetadata {
     /// Returns error metadata, which includes the error code, message,
@@ -31,6 +38,7 @@ pub const EMPTY_ERROR_METADATA: ErrorMetadata = ErrorMetadata {
     code: None,
     message: None,
     extras: None,
+    captured_headers: None,
 };
 
 /// Generic Error type
@@ -43,6 +51,7 @@ pub struct ErrorMetadata {
     code: Option<String>,
     message: Option<String>,
     extras: Option<HashMap<&'static str, String>>,
+    captured_headers: Option<CapturedHeaders>,
 }
 
 impl ProvideErrorMetadata for ErrorMetadata {
@@ -107,6 +116,12 @@ impl Builder {
         self
     }
 
+    /// Sets the response headers that were captured for this error.
+    pub fn captured_headers(mut self, captured_headers: CapturedHeaders) -> Self {
+        self.inner.captured_headers = Some(captured_headers);
+        self
+    }
+
     /// Creates the error.
     pub fn build(self) -> ErrorMetadata {
         self.inner
@@ -129,6 +144,11 @@ impl ErrorMetadata {
             .and_then(|extras| extras.get(key).map(|k| k.as_str()))
     }
 
+    /// Returns the response headers that were captured for this error, if header capture was configured.
+    pub fn captured_headers(&self) -> Option<&CapturedHeaders> {
+        self.captured_headers.as_ref()
+    }
+
     /// Creates an `Error` builder.
     pub fn builder() -> Builder {
         Builder::default()
@@ -140,6 +160,12 @@ impl ErrorMetadata {
     }
 }
 
+impl ProvideCapturedHeaders for ErrorMetadata {
+    fn captured_headers(&self) -> Option<&CapturedHeaders> {
+        ErrorMetadata::captured_headers(self)
+    }
+}
+
 impl ProvideErrorKind for ErrorMetadata {
     fn retryable_error_kind(&self) -> Option<ErrorKind> {
         None
@@ -169,3 +195,74 @@ impl fmt::Display for ErrorMetadata {
 }
 
 impl std::error::Error for ErrorMetadata {}
+
+/// Values that aren't part of a response, but that should be available when an error is
+/// deserialized from it.
+///
+/// Interceptors store these in the config bag before deserialization. The orchestrator then
+/// makes them available with [`with_error_metadata_extras`], so that generated code can decide
+/// whether to add them to the [`ErrorMetadata`] of the error it deserializes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ErrorMetadataExtras {
+    extras: Vec<(&'static str, String)>,
+}
+
+impl ErrorMetadataExtras {
+    /// Creates an empty set of extras.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value for `key`, replacing any previous value.
+    pub fn insert(&mut self, key: &'static str, value: impl Into<String>) -> &mut Self {
+        let value = value.into();
+        match self.extras.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => *existing = value,
+            None => self.extras.push((key, value)),
+        }
+        self
+    }
+
+    /// Removes the value for `key`, if there is one.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let index = self.extras.iter().position(|(k, _)| *k == key)?;
+        Some(self.extras.remove(index).1)
+    }
+
+    /// Returns the value for `key`, if there is one.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.extras
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns an iterator over the keys and values, in the order they were first inserted.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        self.extras
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+    }
+
+    /// Returns true if there are no extras.
+    pub fn is_empty(&self) -> bool {
+        self.extras.is_empty()
+    }
+}
+
+impl Storable for ErrorMetadataExtras {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Runs `f` with `extras` available from [`current_error_metadata_extras`] on the current thread.
+pub fn with_error_metadata_extras<T>(extras: &ErrorMetadataExtras, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT_EXTRAS.with(|current| current.replace(Some(extras.clone())));
+    let result = f();
+    CURRENT_EXTRAS.with(|current| current.replace(previous));
+    result
+}
+
+/// Returns the extras for the response that is being deserialized on the current thread.
+pub fn current_error_metadata_extras() -> Option<ErrorMetadataExtras> {
+    CURRENT_EXTRAS.with(|current| current.borrow().clone())
+}
@@ -0,0 +1,167 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Lenient deserialization, where optional members that fail to parse are dropped and recorded
+//! instead of failing the whole response.
+//!
+//! By default, a single malformed value fails the deserialization of the entire response. When
+//! deserialization runs inside [`deserialize_leniently`], deserializers that support it leave an
+//! optional member that fails to parse unset (and skip a list or map element that fails to parse)
+//! and record a [`FieldError`] for it instead. Errors on required members still fail the
+//! deserialization.
+//!
+//! Lenient deserialization is scoped to the current thread and disabled by default. When it is
+//! disabled, deserializers only check [`is_lenient`] when a value fails to parse.
+
+use crate::config_bag::{Storable, StoreReplace};
+use std::cell::RefCell;
+use std::error::Error as StdError;
+use std::fmt;
+
+/// The maximum number of field errors recorded for a single deserialization.
+///
+/// Once this many errors have been recorded, further optional members that fail to parse are
+/// still dropped, but no error is recorded for them.
+pub const MAX_RECORDED_FIELD_ERRORS: usize = 32;
+
+thread_local! {
+    static RECORDED: RefCell<Option<FieldErrors>> = const { RefCell::new(None) };
+}
+
+/// A value that was dropped during lenient deserialization because it failed to parse.
+#[derive(Debug)]
+pub struct FieldError {
+    path: String,
+    source: Box<dyn StdError + Send + Sync + 'static>,
+}
+
+impl FieldError {
+    /// Creates a new `FieldError` for the value at `path`.
+    pub fn new(
+        path: impl Into<String>,
+        source: impl Into<Box<dyn StdError + Send + Sync + 'static>>,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            source: source.into(),
+        }
+    }
+
+    /// Returns the location of the dropped value in the response (a JSON pointer for JSON
+    /// documents).
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to parse the value at `{}`", self.path)
+    }
+}
+
+impl StdError for FieldError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(self.source.as_ref() as _)
+    }
+}
+
+/// The field errors recorded while leniently deserializing a response.
+#[derive(Debug, Default)]
+pub struct FieldErrors {
+    errors: Vec<FieldError>,
+}
+
+impl FieldErrors {
+    /// Returns the recorded errors, in the order they were encountered.
+    ///
+    /// At most [`MAX_RECORDED_FIELD_ERRORS`] errors are recorded.
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    /// Returns true if no errors were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl Storable for FieldErrors {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Runs `f` with lenient deserialization enabled on the current thread and returns its result
+/// along with the errors of the values that were dropped.
+pub fn deserialize_leniently<T>(f: impl FnOnce() -> T) -> (T, FieldErrors) {
+    let previous = RECORDED.with(|recorded| recorded.replace(Some(FieldErrors::default())));
+    let result = f();
+    let errors = RECORDED.with(|recorded| recorded.replace(previous));
+    (result, errors.unwrap_or_default())
+}
+
+/// Returns true if deserialization is lenient on the current thread.
+pub fn is_lenient() -> bool {
+    RECORDED.with(|recorded| recorded.borrow().is_some())
+}
+
+/// Records that a value was dropped.
+///
+/// `error` is only called if deserialization is lenient on the current thread and the
+/// per-deserialization limit hasn't been reached.
+pub fn record(error: impl FnOnce() -> FieldError) {
+    RECORDED.with(|recorded| {
+        if let Some(errors) = recorded.borrow_mut().as_mut() {
+            if errors.errors.len() < MAX_RECORDED_FIELD_ERRORS {
+                errors.errors.push(error());
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nothing_is_recorded_by_default() {
+        let mut called = false;
+        record(|| {
+            called = true;
+            FieldError::new("/a", "bad value")
+        });
+        assert!(!called);
+        assert!(!is_lenient());
+    }
+
+    #[test]
+    fn records_bounded_errors() {
+        let ((), errors) = deserialize_leniently(|| {
+            assert!(is_lenient());
+            for i in 0..(MAX_RECORDED_FIELD_ERRORS * 2) {
+                record(|| FieldError::new(format!("/items/{i}"), "bad value"));
+            }
+        });
+        assert_eq!(MAX_RECORDED_FIELD_ERRORS, errors.errors().len());
+        assert_eq!("/items/0", errors.errors()[0].path());
+        assert_eq!(
+            "bad value",
+            errors.errors()[0].source().unwrap().to_string()
+        );
+        assert!(!is_lenient());
+    }
+
+    #[test]
+    fn nested_deserializations_are_isolated() {
+        let ((inner, ()), outer) = deserialize_leniently(|| {
+            let (_, inner) =
+                deserialize_leniently(|| record(|| FieldError::new("/inner", "bad value")));
+            (inner, record(|| FieldError::new("/outer", "bad value")))
+        });
+        assert_eq!("/inner", inner.errors()[0].path());
+        assert_eq!(1, inner.errors().len());
+        assert_eq!("/outer", outer.errors()[0].path());
+        assert_eq!(1, outer.errors().len());
+    }
+}
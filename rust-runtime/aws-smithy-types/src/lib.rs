@@ -20,15 +20,20 @@
 pub mod base64;
 pub mod body;
 pub mod byte_stream;
+pub mod captured_headers;
 /// A typemap for storing configuration.
 pub mod config_bag;
 pub mod date_time;
+pub mod debug;
 pub mod endpoint;
 pub mod error;
 pub mod event_stream;
+pub mod field_errors;
 pub mod primitive;
 pub mod retry;
+pub mod shape;
 pub mod timeout;
+pub mod unknown_fields;
 
 /// Utilities for type erasure.
 pub mod type_erasure;
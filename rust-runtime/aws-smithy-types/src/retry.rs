@@ -6,8 +6,10 @@
 //! This module defines types that describe when to retry given a response.
 
 use crate::config_bag::{Storable, StoreReplace};
+use std::any::Any;
 use std::fmt;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 
 const VALID_RETRY_MODES: &[RetryMode] = &[RetryMode::Standard];
@@ -276,6 +278,8 @@ impl RetryConfigBuilder {
                 .unwrap_or(ReconnectMode::ReconnectOnTransientError),
             max_backoff: self.max_backoff.unwrap_or_else(|| Duration::from_secs(20)),
             use_static_exponential_base: false,
+            delay_override: None,
+            partition: None,
         }
     }
 }
@@ -290,6 +294,8 @@ pub struct RetryConfig {
     max_backoff: Duration,
     reconnect_mode: ReconnectMode,
     use_static_exponential_base: bool,
+    delay_override: Option<DelayOverride>,
+    partition: Option<Partition>,
 }
 
 impl Storable for RetryConfig {
@@ -326,6 +332,8 @@ impl RetryConfig {
             reconnect_mode: ReconnectMode::ReconnectOnTransientError,
             max_backoff: Duration::from_secs(20),
             use_static_exponential_base: false,
+            delay_override: None,
+            partition: None,
         }
     }
 
@@ -338,6 +346,8 @@ impl RetryConfig {
             reconnect_mode: ReconnectMode::ReconnectOnTransientError,
             max_backoff: Duration::from_secs(20),
             use_static_exponential_base: false,
+            delay_override: None,
+            partition: None,
         }
     }
 
@@ -399,6 +409,55 @@ impl RetryConfig {
         self
     }
 
+    /// Set a hook that overrides the delay before specific retries.
+    ///
+    /// Once a failed attempt has been classified as retryable, the hook is called with the
+    /// [`RetryDelayContext`] of the failure. If it returns a delay, that delay is used in place of
+    /// the exponential backoff, the rate limiter delay, and the delay requested by the server. The
+    /// delay is still capped by the [max backoff](Self::with_max_backoff), and the retry still
+    /// counts against the max attempts and the operation timeout. If it returns `None`, the delay
+    /// is computed as usual.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use aws_smithy_types::retry::RetryConfig;
+    /// use std::time::Duration;
+    ///
+    /// // This service asks to be retried after exactly 5 seconds when a slot isn't ready
+    /// let retry_config = RetryConfig::standard().with_delay_override(|ctx| {
+    ///     (ctx.error_code() == Some("SlotNotReady")).then(|| Duration::from_secs(5))
+    /// });
+    /// ```
+    pub fn with_delay_override(
+        mut self,
+        delay_override: impl Fn(&RetryDelayContext<'_>) -> Option<Duration> + Send + Sync + 'static,
+    ) -> Self {
+        self.delay_override = Some(DelayOverride(Arc::new(delay_override)));
+        self
+    }
+
+    /// Set the retry partition of clients configured with this retry config.
+    ///
+    /// Clients with equal retry partitions share their retry state, such as the token bucket that
+    /// limits how many retries can be made. `partition` is a `RetryPartition` from the
+    /// `aws-smithy-runtime` crate, see [`RetryConfigPartition`]. A partition set here takes
+    /// precedence over the retry partition set on the client config.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,ignore
+    /// use aws_smithy_runtime::client::retries::RetryPartition;
+    /// use aws_smithy_types::retry::RetryConfig;
+    ///
+    /// // Give each endpoint host its own retry budget
+    /// let retry_config = RetryConfig::standard().with_partition(RetryPartition::by_endpoint());
+    /// ```
+    pub fn with_partition(mut self, partition: impl RetryConfigPartition) -> Self {
+        self.partition = Some(Partition(Arc::new(partition)));
+        self
+    }
+
     /// Hint to the retry strategy whether to use a static exponential base.
     ///
     /// When a retry strategy uses exponential backoff, it calculates a random base. This causes the
@@ -450,6 +509,119 @@ impl RetryConfig {
     pub fn use_static_exponential_base(&self) -> bool {
         self.use_static_exponential_base
     }
+
+    /// Returns the delay before the retry described by `ctx`, if the
+    /// [delay override](Self::with_delay_override) overrides it.
+    ///
+    /// The returned delay isn't capped by the max backoff yet.
+    pub fn delay_override(&self, ctx: &RetryDelayContext<'_>) -> Option<Duration> {
+        self.delay_override
+            .as_ref()
+            .and_then(|delay_override| (delay_override.0)(ctx))
+    }
+
+    /// Returns the retry partition set with [`with_partition`](Self::with_partition).
+    pub fn partition(&self) -> Option<&dyn RetryConfigPartition> {
+        self.partition
+            .as_ref()
+            .map(|partition| partition.0.as_ref())
+    }
+}
+
+/// A retry partition that can be set on a [`RetryConfig`] with [`RetryConfig::with_partition`].
+///
+/// Retry partitions are defined by the `aws-smithy-runtime` crate, which depends on this one, so
+/// this trait stands in for them here. It is implemented by `RetryPartition`, and isn't meant to
+/// be implemented by other types: clients fail to build with a retry config whose partition isn't
+/// a `RetryPartition`.
+pub trait RetryConfigPartition: fmt::Debug + Send + Sync + 'static {
+    /// Returns this partition as [`Any`], so that its concrete type can be recovered.
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// The retry partition set with [`RetryConfig::with_partition`].
+#[derive(Clone, Debug)]
+struct Partition(Arc<dyn RetryConfigPartition>);
+
+// Two configs have the same partition if they share it.
+impl PartialEq for Partition {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// The hook set with [`RetryConfig::with_delay_override`].
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+struct DelayOverride(Arc<dyn Fn(&RetryDelayContext<'_>) -> Option<Duration> + Send + Sync>);
+
+impl fmt::Debug for DelayOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DelayOverride")
+    }
+}
+
+// Closures can't be compared: two configs have the same override if they share it.
+impl PartialEq for DelayOverride {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// A failed attempt that is about to be retried, as seen by a
+/// [delay override](RetryConfig::with_delay_override).
+#[derive(Clone, Debug)]
+pub struct RetryDelayContext<'a> {
+    error_kind: ErrorKind,
+    error_code: Option<&'a str>,
+    attempt: u32,
+    retry_after: Option<Duration>,
+}
+
+impl<'a> RetryDelayContext<'a> {
+    /// Creates a new `RetryDelayContext` for the failure of `attempt`, classified as `error_kind`.
+    pub fn new(error_kind: ErrorKind, attempt: u32) -> Self {
+        Self {
+            error_kind,
+            error_code: None,
+            attempt,
+            retry_after: None,
+        }
+    }
+
+    /// Sets the error code of the failure.
+    pub fn with_error_code(mut self, error_code: Option<&'a str>) -> Self {
+        self.error_code = error_code;
+        self
+    }
+
+    /// Sets the delay the server asked for before retrying.
+    pub fn with_retry_after(mut self, retry_after: Option<Duration>) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+
+    /// Returns the kind of error the failure was classified as.
+    pub fn error_kind(&self) -> ErrorKind {
+        self.error_kind
+    }
+
+    /// Returns the error code of the failure, if it has one.
+    ///
+    /// The standard retry strategy only knows the code of errors that aren't modeled by the service.
+    pub fn error_code(&self) -> Option<&'a str> {
+        self.error_code
+    }
+
+    /// Returns the number of the attempt that failed, starting at 1 for the initial request.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Returns the delay the server asked for before retrying, if any.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
 }
 
 #[cfg(test)]
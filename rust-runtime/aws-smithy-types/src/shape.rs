@@ -0,0 +1,490 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Dynamic navigation of generated shapes.
+//!
+//! When the `shapeIntrospection` codegen setting is enabled, generated structures and unions
+//! implement [`SmithyShape`], which lists their fields and returns the value of a field by name.
+//! This allows generic tooling (such as diffing two outputs or masking fields by name) to walk
+//! any generated shape without knowing its type. It's not a serialization mechanism: values are
+//! only borrowed, and the contents of blobs and streams aren't exposed.
+//!
+//! ```
+//! use aws_smithy_types::shape::{FieldValueRef, SmithyShape};
+//!
+//! fn print_strings(shape: &dyn SmithyShape) {
+//!     for field in shape.fields() {
+//!         if let Some(FieldValueRef::String(value)) = shape.get_field(field.name()) {
+//!             println!("{}.{} = {value}", shape.shape_name(), field.name());
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{DateTime, Document};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A generated shape whose fields can be navigated by name.
+pub trait SmithyShape {
+    /// Returns the name of the shape in the Smithy model.
+    fn shape_name(&self) -> &'static str;
+
+    /// Returns the fields of the shape, in the order they are defined in the model.
+    ///
+    /// For unions, these are the variants, and only the variant that is set has a value.
+    fn fields(&self) -> &'static [FieldInfo];
+
+    /// Returns the value of the field named `name` (the member name in the Smithy model), or
+    /// `None` if the shape has no such field or it isn't set.
+    fn get_field(&self, name: &str) -> Option<FieldValueRef<'_>>;
+}
+
+impl fmt::Debug for dyn SmithyShape + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.shape_name())
+    }
+}
+
+/// Returns the value at `path` in `shape`, where `path` is a list of `/`-separated segments.
+///
+/// Each segment is a field name for structures and unions, an index for lists, and a key for
+/// maps. For example, `items/3/owner/name` is the `name` of the `owner` of the fourth element of
+/// the `items` list. An empty path returns `shape` itself.
+pub fn get_path<'a>(shape: &'a dyn SmithyShape, path: &str) -> Option<FieldValueRef<'a>> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(FieldValueRef::Shape(shape), |value, segment| {
+            value.get(segment)
+        })
+}
+
+/// The kind of a field's shape.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ShapeKind {
+    /// A blob.
+    Blob,
+    /// A boolean.
+    Boolean,
+    /// A string.
+    String,
+    /// A string enum.
+    Enum,
+    /// An 8-bit integer.
+    Byte,
+    /// A 16-bit integer.
+    Short,
+    /// A 32-bit integer.
+    Integer,
+    /// An int enum.
+    IntEnum,
+    /// A 64-bit integer.
+    Long,
+    /// A 32-bit float.
+    Float,
+    /// A 64-bit float.
+    Double,
+    /// A timestamp.
+    Timestamp,
+    /// A document.
+    Document,
+    /// A list.
+    List,
+    /// A map.
+    Map,
+    /// A structure.
+    Structure,
+    /// A union.
+    Union,
+    /// A streaming blob or an event stream.
+    Stream,
+}
+
+/// Information about a field of a [`SmithyShape`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldInfo {
+    name: &'static str,
+    kind: ShapeKind,
+    sensitive: bool,
+}
+
+impl FieldInfo {
+    /// Creates a new `FieldInfo`.
+    pub const fn new(name: &'static str, kind: ShapeKind, sensitive: bool) -> Self {
+        Self {
+            name,
+            kind,
+            sensitive,
+        }
+    }
+
+    /// Returns the name of the field (the member name in the Smithy model).
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the kind of the field's shape.
+    pub fn kind(&self) -> ShapeKind {
+        self.kind
+    }
+
+    /// Returns true if the field or its shape is marked `@sensitive`.
+    pub fn is_sensitive(&self) -> bool {
+        self.sensitive
+    }
+}
+
+/// A borrowed field value.
+#[derive(Clone, Copy)]
+#[non_exhaustive]
+pub enum FieldValueRef<'a> {
+    /// An element of a sparse list or map that is null.
+    Null,
+    /// A boolean.
+    Boolean(bool),
+    /// An 8-bit integer.
+    Byte(i8),
+    /// A 16-bit integer.
+    Short(i16),
+    /// A 32-bit integer or int enum.
+    Integer(i32),
+    /// A 64-bit integer.
+    Long(i64),
+    /// A 32-bit float.
+    Float(f32),
+    /// A 64-bit float.
+    Double(f64),
+    /// A string, or the value of a string enum.
+    String(&'a str),
+    /// A timestamp.
+    Timestamp(&'a DateTime),
+    /// A document.
+    Document(&'a Document),
+    /// A blob. Its contents aren't exposed.
+    Blob,
+    /// A streaming blob or an event stream. Its contents aren't exposed.
+    Stream,
+    /// A structure or union.
+    Shape(&'a dyn SmithyShape),
+    /// A list.
+    List(&'a dyn ListValue),
+    /// A map.
+    Map(&'a dyn MapValue),
+}
+
+impl<'a> FieldValueRef<'a> {
+    /// Returns the value of the field, element, or entry identified by `segment`.
+    ///
+    /// `segment` is a field name for structures and unions, an index for lists, and a key for
+    /// maps. Other values have nothing to navigate into, and return `None`.
+    pub fn get(&self, segment: &str) -> Option<FieldValueRef<'a>> {
+        match *self {
+            FieldValueRef::Shape(shape) => shape.get_field(segment),
+            FieldValueRef::List(list) => list.get(segment.parse().ok()?),
+            FieldValueRef::Map(map) => map.get(segment),
+            _ => None,
+        }
+    }
+
+    /// Returns the string if this is a string or a string enum.
+    pub fn as_str(&self) -> Option<&'a str> {
+        match *self {
+            FieldValueRef::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the shape if this is a structure or union.
+    pub fn as_shape(&self) -> Option<&'a dyn SmithyShape> {
+        match *self {
+            FieldValueRef::Shape(shape) => Some(shape),
+            _ => None,
+        }
+    }
+
+    /// Returns the list if this is a list.
+    pub fn as_list(&self) -> Option<&'a dyn ListValue> {
+        match *self {
+            FieldValueRef::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    /// Returns the map if this is a map.
+    pub fn as_map(&self) -> Option<&'a dyn MapValue> {
+        match *self {
+            FieldValueRef::Map(map) => Some(map),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Debug for FieldValueRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValueRef::Null => f.write_str("Null"),
+            FieldValueRef::Boolean(value) => f.debug_tuple("Boolean").field(value).finish(),
+            FieldValueRef::Byte(value) => f.debug_tuple("Byte").field(value).finish(),
+            FieldValueRef::Short(value) => f.debug_tuple("Short").field(value).finish(),
+            FieldValueRef::Integer(value) => f.debug_tuple("Integer").field(value).finish(),
+            FieldValueRef::Long(value) => f.debug_tuple("Long").field(value).finish(),
+            FieldValueRef::Float(value) => f.debug_tuple("Float").field(value).finish(),
+            FieldValueRef::Double(value) => f.debug_tuple("Double").field(value).finish(),
+            FieldValueRef::String(value) => f.debug_tuple("String").field(value).finish(),
+            FieldValueRef::Timestamp(value) => f.debug_tuple("Timestamp").field(value).finish(),
+            FieldValueRef::Document(value) => f.debug_tuple("Document").field(value).finish(),
+            FieldValueRef::Blob => f.write_str("Blob"),
+            FieldValueRef::Stream => f.write_str("Stream"),
+            FieldValueRef::Shape(shape) => f.debug_tuple("Shape").field(shape).finish(),
+            FieldValueRef::List(list) => f.debug_struct("List").field("len", &list.len()).finish(),
+            FieldValueRef::Map(map) => f.debug_struct("Map").field("len", &map.len()).finish(),
+        }
+    }
+}
+
+/// A list whose elements can be navigated.
+pub trait ListValue {
+    /// Returns the number of elements.
+    fn len(&self) -> usize;
+
+    /// Returns true if there are no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at `index`.
+    fn get(&self, index: usize) -> Option<FieldValueRef<'_>>;
+}
+
+/// A map whose entries can be navigated.
+pub trait MapValue {
+    /// Returns the number of entries.
+    fn len(&self) -> usize;
+
+    /// Returns true if there are no entries.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the entries, in no particular order.
+    fn entries(&self) -> Box<dyn Iterator<Item = (&str, FieldValueRef<'_>)> + '_>;
+
+    /// Returns the value for `key`.
+    fn get(&self, key: &str) -> Option<FieldValueRef<'_>> {
+        self.entries()
+            .find(|(entry_key, _)| *entry_key == key)
+            .map(|(_, value)| value)
+    }
+}
+
+/// Conversion of a field into a [`FieldValueRef`].
+///
+/// This is implemented for the types of generated fields, and is used by generated
+/// [`SmithyShape`] implementations.
+pub trait AsFieldValue {
+    /// Returns the value of this field.
+    fn as_field_value(&self) -> FieldValueRef<'_>;
+}
+
+macro_rules! as_field_value {
+    ($($typ:ty => $variant:ident),+) => {
+        $(
+            impl AsFieldValue for $typ {
+                fn as_field_value(&self) -> FieldValueRef<'_> {
+                    FieldValueRef::$variant(*self)
+                }
+            }
+        )+
+    };
+}
+
+as_field_value!(
+    bool => Boolean,
+    i8 => Byte,
+    i16 => Short,
+    i32 => Integer,
+    i64 => Long,
+    f32 => Float,
+    f64 => Double
+);
+
+impl AsFieldValue for String {
+    fn as_field_value(&self) -> FieldValueRef<'_> {
+        FieldValueRef::String(self)
+    }
+}
+
+impl AsFieldValue for DateTime {
+    fn as_field_value(&self) -> FieldValueRef<'_> {
+        FieldValueRef::Timestamp(self)
+    }
+}
+
+impl AsFieldValue for Document {
+    fn as_field_value(&self) -> FieldValueRef<'_> {
+        FieldValueRef::Document(self)
+    }
+}
+
+impl AsFieldValue for crate::Blob {
+    fn as_field_value(&self) -> FieldValueRef<'_> {
+        FieldValueRef::Blob
+    }
+}
+
+impl<T: AsFieldValue> AsFieldValue for Option<T> {
+    fn as_field_value(&self) -> FieldValueRef<'_> {
+        match self {
+            Some(value) => value.as_field_value(),
+            None => FieldValueRef::Null,
+        }
+    }
+}
+
+impl<T: AsFieldValue + ?Sized> AsFieldValue for Box<T> {
+    fn as_field_value(&self) -> FieldValueRef<'_> {
+        (**self).as_field_value()
+    }
+}
+
+impl<T: AsFieldValue> AsFieldValue for Vec<T> {
+    fn as_field_value(&self) -> FieldValueRef<'_> {
+        FieldValueRef::List(self)
+    }
+}
+
+impl<T: AsFieldValue> ListValue for Vec<T> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn get(&self, index: usize) -> Option<FieldValueRef<'_>> {
+        self.as_slice().get(index).map(AsFieldValue::as_field_value)
+    }
+}
+
+impl<K: AsRef<str>, V: AsFieldValue, S> AsFieldValue for HashMap<K, V, S> {
+    fn as_field_value(&self) -> FieldValueRef<'_> {
+        FieldValueRef::Map(self)
+    }
+}
+
+impl<K: AsRef<str>, V: AsFieldValue, S> MapValue for HashMap<K, V, S> {
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+
+    fn entries(&self) -> Box<dyn Iterator<Item = (&str, FieldValueRef<'_>)> + '_> {
+        Box::new(
+            self.iter()
+                .map(|(key, value)| (key.as_ref(), value.as_field_value())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Owner {
+        name: Option<String>,
+    }
+
+    impl SmithyShape for Owner {
+        fn shape_name(&self) -> &'static str {
+            "Owner"
+        }
+
+        fn fields(&self) -> &'static [FieldInfo] {
+            const FIELDS: &[FieldInfo] = &[FieldInfo::new("name", ShapeKind::String, true)];
+            FIELDS
+        }
+
+        fn get_field(&self, name: &str) -> Option<FieldValueRef<'_>> {
+            match name {
+                "name" => self.name.as_ref().map(AsFieldValue::as_field_value),
+                _ => None,
+            }
+        }
+    }
+
+    impl AsFieldValue for Owner {
+        fn as_field_value(&self) -> FieldValueRef<'_> {
+            FieldValueRef::Shape(self)
+        }
+    }
+
+    struct Item {
+        owners: HashMap<String, Owner>,
+        sizes: Vec<Option<i32>>,
+    }
+
+    impl SmithyShape for Item {
+        fn shape_name(&self) -> &'static str {
+            "Item"
+        }
+
+        fn fields(&self) -> &'static [FieldInfo] {
+            const FIELDS: &[FieldInfo] = &[
+                FieldInfo::new("owners", ShapeKind::Map, false),
+                FieldInfo::new("sizes", ShapeKind::List, false),
+            ];
+            FIELDS
+        }
+
+        fn get_field(&self, name: &str) -> Option<FieldValueRef<'_>> {
+            match name {
+                "owners" => Some(self.owners.as_field_value()),
+                "sizes" => Some(self.sizes.as_field_value()),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn paths_navigate_shapes_lists_and_maps() {
+        let item = Item {
+            owners: HashMap::from([(
+                "primary".to_string(),
+                Owner {
+                    name: Some("alice".into()),
+                },
+            )]),
+            sizes: vec![Some(1), None],
+        };
+        assert_eq!(
+            Some("alice"),
+            get_path(&item, "owners/primary/name").and_then(|v| v.as_str())
+        );
+        assert!(matches!(
+            get_path(&item, "sizes/0"),
+            Some(FieldValueRef::Integer(1))
+        ));
+        assert!(matches!(
+            get_path(&item, "sizes/1"),
+            Some(FieldValueRef::Null)
+        ));
+        assert!(get_path(&item, "sizes/2").is_none());
+        assert!(get_path(&item, "sizes/first").is_none());
+        assert!(get_path(&item, "owners/secondary/name").is_none());
+        assert!(get_path(&item, "owners/primary/name/more").is_none());
+        assert_eq!(
+            "Item",
+            get_path(&item, "")
+                .unwrap()
+                .as_shape()
+                .unwrap()
+                .shape_name()
+        );
+
+        let owner = get_path(&item, "owners/primary")
+            .unwrap()
+            .as_shape()
+            .unwrap();
+        assert!(owner.fields()[0].is_sensitive());
+        assert_eq!("Shape(Owner)", format!("{:?}", FieldValueRef::Shape(owner)));
+    }
+}
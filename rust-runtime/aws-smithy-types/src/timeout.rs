@@ -65,6 +65,7 @@ pub struct TimeoutConfigBuilder {
     read_timeout: CanDisable<Duration>,
     operation_timeout: CanDisable<Duration>,
     operation_attempt_timeout: CanDisable<Duration>,
+    endpoint_resolution_timeout: CanDisable<Duration>,
 }
 
 impl TimeoutConfigBuilder {
@@ -195,6 +196,36 @@ impl TimeoutConfigBuilder {
         self
     }
 
+    /// Sets the endpoint resolution timeout.
+    ///
+    /// This is a limit on the amount of time an endpoint resolver with access to the operation input
+    /// may take to resolve an endpoint. When it is not set, the operation attempt timeout is used instead.
+    pub fn endpoint_resolution_timeout(mut self, endpoint_resolution_timeout: Duration) -> Self {
+        self.endpoint_resolution_timeout = endpoint_resolution_timeout.into();
+        self
+    }
+
+    /// Sets the endpoint resolution timeout.
+    ///
+    /// If `None` is passed, this will explicitly disable the endpoint resolution timeout. To disable all timeouts use [`TimeoutConfig::disabled`].
+    ///
+    /// This is a limit on the amount of time an endpoint resolver with access to the operation input
+    /// may take to resolve an endpoint. When it is not set, the operation attempt timeout is used instead.
+    pub fn set_endpoint_resolution_timeout(
+        &mut self,
+        endpoint_resolution_timeout: Option<Duration>,
+    ) -> &mut Self {
+        self.endpoint_resolution_timeout =
+            CanDisable::none_implies_disabled(endpoint_resolution_timeout);
+        self
+    }
+
+    /// Disables the endpoint resolution timeout
+    pub fn disable_endpoint_resolution_timeout(mut self) -> Self {
+        self.endpoint_resolution_timeout = CanDisable::Disabled;
+        self
+    }
+
     /// Merges two timeout config builders together.
     ///
     /// Values from `other` will only be used as a fallback for values
@@ -232,6 +263,9 @@ impl TimeoutConfigBuilder {
             operation_attempt_timeout: self
                 .operation_attempt_timeout
                 .merge_from_lower_priority(other.operation_attempt_timeout),
+            endpoint_resolution_timeout: self
+                .endpoint_resolution_timeout
+                .merge_from_lower_priority(other.endpoint_resolution_timeout),
         }
     }
 
@@ -242,6 +276,7 @@ impl TimeoutConfigBuilder {
             read_timeout: self.read_timeout,
             operation_timeout: self.operation_timeout,
             operation_attempt_timeout: self.operation_attempt_timeout,
+            endpoint_resolution_timeout: self.endpoint_resolution_timeout,
         }
     }
 }
@@ -253,6 +288,7 @@ impl From<TimeoutConfig> for TimeoutConfigBuilder {
             read_timeout: timeout_config.read_timeout,
             operation_timeout: timeout_config.operation_timeout,
             operation_attempt_timeout: timeout_config.operation_attempt_timeout,
+            endpoint_resolution_timeout: timeout_config.endpoint_resolution_timeout,
         }
     }
 }
@@ -294,6 +330,7 @@ pub struct TimeoutConfig {
     read_timeout: CanDisable<Duration>,
     operation_timeout: CanDisable<Duration>,
     operation_attempt_timeout: CanDisable<Duration>,
+    endpoint_resolution_timeout: CanDisable<Duration>,
 }
 
 impl Storable for TimeoutConfig {
@@ -367,6 +404,9 @@ impl TimeoutConfig {
         self.operation_attempt_timeout = self
             .operation_attempt_timeout
             .merge_from_lower_priority(other.operation_attempt_timeout);
+        self.endpoint_resolution_timeout = self
+            .endpoint_resolution_timeout
+            .merge_from_lower_priority(other.endpoint_resolution_timeout);
         self
     }
 
@@ -377,6 +417,7 @@ impl TimeoutConfig {
             read_timeout: CanDisable::Disabled,
             operation_timeout: CanDisable::Disabled,
             operation_attempt_timeout: CanDisable::Disabled,
+            endpoint_resolution_timeout: CanDisable::Disabled,
         }
     }
 
@@ -413,12 +454,21 @@ impl TimeoutConfig {
         self.operation_attempt_timeout.value()
     }
 
+    /// Returns this config's endpoint resolution timeout.
+    ///
+    /// This is a limit on the amount of time an endpoint resolver with access to the operation input
+    /// may take to resolve an endpoint. When it is not set, the operation attempt timeout is used instead.
+    pub fn endpoint_resolution_timeout(&self) -> Option<Duration> {
+        self.endpoint_resolution_timeout.value()
+    }
+
     /// Returns true if any of the possible timeouts are set.
     pub fn has_timeouts(&self) -> bool {
         self.connect_timeout.is_some()
             || self.read_timeout.is_some()
             || self.operation_timeout.is_some()
             || self.operation_attempt_timeout.is_some()
+            || self.endpoint_resolution_timeout.is_some()
     }
 }
 
@@ -0,0 +1,98 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Recording of unknown (unmodeled) fields skipped during deserialization.
+//!
+//! Deserializers skip fields that aren't part of the model. When recording is enabled with
+//! [`record_unknown_fields`], the location of each skipped field (a JSON pointer for JSON
+//! documents, an element path for XML documents) is collected so that drift between the model
+//! and the service can be detected.
+//!
+//! Recording is scoped to the current thread and disabled by default. When it is disabled,
+//! deserializers only check [`is_recording`] when they encounter an unknown field, and
+//! nothing is allocated.
+
+use std::cell::RefCell;
+
+/// The maximum number of unknown fields recorded for a single deserialization.
+pub const MAX_RECORDED_UNKNOWN_FIELDS: usize = 32;
+
+thread_local! {
+    static RECORDED: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with unknown field recording enabled on the current thread and returns its result
+/// along with the paths of the unknown fields that were skipped.
+///
+/// Paths are deduplicated and at most [`MAX_RECORDED_UNKNOWN_FIELDS`] are returned.
+pub fn record_unknown_fields<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    let previous = RECORDED.with(|recorded| recorded.replace(Some(Vec::new())));
+    let result = f();
+    let paths = RECORDED.with(|recorded| recorded.replace(previous));
+    (result, paths.unwrap_or_default())
+}
+
+/// Returns true if unknown fields are being recorded on the current thread.
+pub fn is_recording() -> bool {
+    RECORDED.with(|recorded| recorded.borrow().is_some())
+}
+
+/// Records the path of an unknown field.
+///
+/// `path` is only called if recording is enabled on the current thread and the per-deserialization
+/// limit hasn't been reached.
+pub fn record(path: impl FnOnce() -> String) {
+    RECORDED.with(|recorded| {
+        if let Some(paths) = recorded.borrow_mut().as_mut() {
+            if paths.len() < MAX_RECORDED_UNKNOWN_FIELDS {
+                let path = path();
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nothing_is_recorded_by_default() {
+        let mut called = false;
+        record(|| {
+            called = true;
+            "/a".into()
+        });
+        assert!(!called);
+        assert!(!is_recording());
+    }
+
+    #[test]
+    fn records_deduplicated_and_bounded_paths() {
+        let ((), paths) = record_unknown_fields(|| {
+            assert!(is_recording());
+            record(|| "/a".into());
+            record(|| "/a".into());
+            for i in 0..(MAX_RECORDED_UNKNOWN_FIELDS * 2) {
+                record(|| format!("/b/{i}"));
+            }
+        });
+        assert_eq!(MAX_RECORDED_UNKNOWN_FIELDS, paths.len());
+        assert_eq!("/a", paths[0]);
+        assert!(!is_recording());
+    }
+
+    #[test]
+    fn nested_recording_is_isolated() {
+        let ((inner, ()), outer) = record_unknown_fields(|| {
+            let (_, inner) = record_unknown_fields(|| record(|| "/inner".into()));
+            (inner, record(|| "/outer".into()))
+        });
+        assert_eq!(vec!["/inner".to_string()], inner);
+        assert_eq!(vec!["/outer".to_string()], outer);
+    }
+}
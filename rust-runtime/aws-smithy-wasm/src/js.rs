@@ -0,0 +1,357 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! HTTP client, sleep and time source for JavaScript environments
+//!
+//! On `wasm32-unknown-unknown`, there are no sockets, timers or clock: the browser, web worker or
+//! Node.js runtime hosting the module provides them through JavaScript instead. This module
+//! implements them with [`fetch`](https://developer.mozilla.org/en-US/docs/Web/API/fetch),
+//! `setTimeout` and `Date.now()` so that a generated client can run there:
+//!
+//! ```rust,ignore
+//! use aws_smithy_wasm::js::{FetchHttpClientBuilder, JsSleep, JsTimeSource};
+//!
+//! let config = my_service::Config::builder()
+//!     .http_client(FetchHttpClientBuilder::new().build())
+//!     .sleep_impl(JsSleep::new())
+//!     .time_source(JsTimeSource::new())
+//!     .build();
+//! ```
+//!
+//! The client must be built without its default features, which use Tokio and hyper.
+//!
+//! # Limitations
+//!
+//! Bodies are buffered rather than streamed. Requests with a streaming body fail with
+//! [`StreamingBodyUnsupported`], and responses are read in full before they are returned, which
+//! includes the responses of operations with a streaming output.
+//!
+//! The connect and read timeouts of the client aren't applied to `fetch`. Operation timeouts are,
+//! since they rely on [`JsSleep`].
+
+#[cfg(target_feature = "atomics")]
+compile_error!(
+    "the `wasm-js` feature doesn't support the `atomics` target feature: \
+     JavaScript values can't be shared between threads"
+);
+
+use aws_smithy_async::rt::sleep::{AsyncSleep, Sleep};
+use aws_smithy_async::time::TimeSource;
+use aws_smithy_runtime_api::client::connector_metadata::ConnectorMetadata;
+use aws_smithy_runtime_api::{
+    client::{
+        http::{
+            HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings,
+            SharedHttpClient, SharedHttpConnector,
+        },
+        orchestrator::HttpRequest,
+        result::ConnectorError,
+        runtime_components::RuntimeComponents,
+    },
+    http::Response,
+    shared::IntoShared,
+};
+use aws_smithy_types::body::SdkBody;
+use bytes::Bytes;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// Sends HTTP requests for the [`FetchHttpClient`].
+///
+/// [`GlobalFetch`] calls the `fetch` function of the JavaScript environment. Implement this trait
+/// to send requests some other way, for example to mock responses in tests.
+pub trait Fetch: fmt::Debug + Send + Sync {
+    /// Sends `request`, and returns its response with the body read in full.
+    fn fetch(&self, request: http::Request<Bytes>) -> FetchFuture;
+}
+
+/// Future returned by [`Fetch`].
+///
+/// Unlike most futures of the runtime, it doesn't need to be `Send`, since futures awaiting
+/// JavaScript promises aren't.
+pub type FetchFuture =
+    Pin<Box<dyn Future<Output = Result<http::Response<Bytes>, ConnectorError>> + 'static>>;
+
+/// Builder for [`FetchHttpClient`].
+#[derive(Default, Debug)]
+pub struct FetchHttpClientBuilder {
+    fetch: Option<Arc<dyn Fetch>>,
+}
+
+impl FetchHttpClientBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sends requests with `fetch` rather than [`GlobalFetch`].
+    pub fn fetch(mut self, fetch: impl Fetch + 'static) -> Self {
+        self.fetch = Some(Arc::new(fetch));
+        self
+    }
+
+    /// Builds the [`FetchHttpClient`].
+    pub fn build(self) -> SharedHttpClient {
+        let client = FetchHttpClient {
+            fetch: self.fetch.unwrap_or_else(|| Arc::new(GlobalFetch::new())),
+        };
+        client.into_shared()
+    }
+}
+
+/// An HTTP client that sends requests with `fetch`, for JavaScript environments such as browsers.
+///
+/// Request bodies are buffered: see the [module documentation](self) for the limitations.
+#[derive(Debug, Clone)]
+pub struct FetchHttpClient {
+    fetch: Arc<dyn Fetch>,
+}
+
+impl HttpClient for FetchHttpClient {
+    fn http_connector(
+        &self,
+        _settings: &HttpConnectorSettings,
+        _components: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        FetchHttpConnector {
+            fetch: self.fetch.clone(),
+        }
+        .into_shared()
+    }
+
+    fn connector_metadata(&self) -> Option<ConnectorMetadata> {
+        Some(ConnectorMetadata::new("fetch-http-client", None))
+    }
+}
+
+/// HTTP connector used in JavaScript environments
+#[derive(Debug)]
+struct FetchHttpConnector {
+    fetch: Arc<dyn Fetch>,
+}
+
+impl HttpConnector for FetchHttpConnector {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        tracing::trace!("FetchHttpConnector: sending request {request:?}");
+
+        let fetch = self.fetch.clone();
+        HttpConnectorFuture::new(SingleThreaded::new(async move {
+            let response = fetch.fetch(buffer_request(request)?).await?;
+            tracing::trace!("FetchHttpConnector: response received {response:?}");
+
+            Response::try_from(response.map(SdkBody::from))
+                .map_err(|err| ConnectorError::other(err.into(), None))
+        }))
+    }
+}
+
+fn buffer_request(request: HttpRequest) -> Result<http::Request<Bytes>, ConnectorError> {
+    let request = request
+        .try_into_http1x()
+        .map_err(|err| ConnectorError::user(err.into()))?;
+    let (parts, body) = request.into_parts();
+    let body = body
+        .bytes()
+        .map(Bytes::copy_from_slice)
+        .ok_or_else(|| ConnectorError::user(StreamingBodyUnsupported.into()))?;
+    Ok(http::Request::from_parts(parts, body))
+}
+
+/// Error returned by the [`FetchHttpClient`] for requests with a streaming body.
+///
+/// `fetch` is given the body of a request in full. To send a streaming body, such as a
+/// `ByteStream` read from a file, collect it into memory first.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct StreamingBodyUnsupported;
+
+impl fmt::Display for StreamingBodyUnsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the fetch HTTP client doesn't support streaming request bodies"
+        )
+    }
+}
+
+impl std::error::Error for StreamingBodyUnsupported {}
+
+/// Sends requests with the `fetch` function of the JavaScript environment.
+///
+/// This works in browsers and web workers, as well as in Node.js 18 or later.
+#[derive(Debug, Default, Clone)]
+#[non_exhaustive]
+pub struct GlobalFetch;
+
+impl GlobalFetch {
+    /// Creates a new `GlobalFetch`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl Fetch for GlobalFetch {
+    fn fetch(&self, request: http::Request<Bytes>) -> FetchFuture {
+        Box::pin(global_fetch(request))
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    // Unlike `Window::fetch_with_request`, this is also defined in web workers and Node.js
+    #[wasm_bindgen(js_name = fetch)]
+    fn fetch_with_request(request: &web_sys::Request) -> js_sys::Promise;
+}
+
+async fn global_fetch(
+    request: http::Request<Bytes>,
+) -> Result<http::Response<Bytes>, ConnectorError> {
+    let (parts, body) = request.into_parts();
+    let headers = web_sys::Headers::new().map_err(user_error)?;
+    for (name, value) in &parts.headers {
+        let value = value
+            .to_str()
+            .map_err(|err| ConnectorError::user(err.into()))?;
+        headers.append(name.as_str(), value).map_err(user_error)?;
+    }
+    let init = web_sys::RequestInit::new();
+    init.set_method(parts.method.as_str());
+    init.set_headers(&headers);
+    if !body.is_empty() {
+        init.set_body(&js_sys::Uint8Array::from(body.as_ref()));
+    }
+    let request = web_sys::Request::new_with_str_and_init(&parts.uri.to_string(), &init)
+        .map_err(user_error)?;
+
+    // `fetch` only rejects if the request couldn't be sent, not for error statuses
+    let response: web_sys::Response = JsFuture::from(fetch_with_request(&request))
+        .await
+        .map_err(io_error)?
+        .unchecked_into();
+    let mut builder = http::Response::builder().status(response.status());
+    let entries = js_sys::try_iter(&response.headers())
+        .map_err(io_error)?
+        .expect("headers are iterable");
+    for entry in entries {
+        let entry: js_sys::Array = entry.map_err(io_error)?.unchecked_into();
+        builder = builder.header(
+            entry.get(0).as_string().unwrap_or_default(),
+            entry.get(1).as_string().unwrap_or_default(),
+        );
+    }
+    let body = JsFuture::from(response.array_buffer().map_err(io_error)?)
+        .await
+        .map_err(io_error)?;
+    let body = Bytes::from(js_sys::Uint8Array::new(&body).to_vec());
+    builder
+        .body(body)
+        .map_err(|err| ConnectorError::other(err.into(), None))
+}
+
+fn user_error(value: JsValue) -> ConnectorError {
+    ConnectorError::user(JsError::from(value).into())
+}
+
+fn io_error(value: JsValue) -> ConnectorError {
+    ConnectorError::io(JsError::from(value).into())
+}
+
+/// A JavaScript exception or promise rejection
+#[derive(Debug)]
+struct JsError(String);
+
+impl From<JsValue> for JsError {
+    fn from(value: JsValue) -> Self {
+        let message = match value.dyn_ref::<js_sys::Error>() {
+            Some(error) => error.message().into(),
+            None => value.as_string().unwrap_or_else(|| format!("{value:?}")),
+        };
+        JsError(message)
+    }
+}
+
+impl fmt::Display for JsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for JsError {}
+
+/// Sleeps with JavaScript timers.
+#[derive(Debug, Default, Clone)]
+#[non_exhaustive]
+pub struct JsSleep;
+
+impl JsSleep {
+    /// Creates a new `JsSleep`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl AsyncSleep for JsSleep {
+    fn sleep(&self, duration: Duration) -> Sleep {
+        // `setTimeout` fires immediately when given more than `i32::MAX` milliseconds
+        let millis = duration.as_millis().min(i32::MAX as u128) as u32;
+        Sleep::new(SingleThreaded::new(
+            gloo_timers::future::TimeoutFuture::new(millis),
+        ))
+    }
+}
+
+/// Time source that delegates to `Date.now()`.
+///
+/// [`SystemTime::now`] panics on `wasm32-unknown-unknown`, so clients running there don't have a
+/// default time source.
+#[derive(Debug, Default, Clone)]
+#[non_exhaustive]
+pub struct JsTimeSource;
+
+impl JsTimeSource {
+    /// Creates a new `JsTimeSource`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl TimeSource for JsTimeSource {
+    fn now(&self) -> SystemTime {
+        // `Date.now()` is a whole number of milliseconds since the epoch
+        UNIX_EPOCH + Duration::from_millis(js_sys::Date::now() as u64)
+    }
+}
+
+/// Future that is `Send` and `Sync` even though the future it wraps isn't.
+///
+/// The runtime requires both of its futures, but futures awaiting JavaScript promises or timers
+/// are neither, since JavaScript values can't be shared between threads. Without the `atomics`
+/// target feature there's only one thread, so the future never leaves it.
+struct SingleThreaded<T>(Pin<Box<dyn Future<Output = T>>>);
+
+impl<T> SingleThreaded<T> {
+    fn new(future: impl Future<Output = T> + 'static) -> Self {
+        Self(Box::pin(future))
+    }
+}
+
+// SAFETY: WebAssembly without the `atomics` target feature is single threaded (see above)
+unsafe impl<T> Send for SingleThreaded<T> {}
+// SAFETY: WebAssembly without the `atomics` target feature is single threaded (see above)
+unsafe impl<T> Sync for SingleThreaded<T> {}
+
+impl<T> Future for SingleThreaded<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
@@ -18,3 +18,6 @@
 
 /// Tools for using Smithy SDKs in WASI environments
 pub mod wasi;
+
+#[cfg(all(feature = "wasm-js", target_family = "wasm", target_os = "unknown"))]
+pub mod js;
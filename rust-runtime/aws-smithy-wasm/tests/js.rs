@@ -0,0 +1,138 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "wasm-js", target_family = "wasm", target_os = "unknown"))]
+
+use aws_smithy_async::rt::sleep::AsyncSleep;
+use aws_smithy_async::time::TimeSource;
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorSettings, SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_wasm::js::{
+    Fetch, FetchFuture, FetchHttpClientBuilder, GlobalFetch, JsSleep, JsTimeSource,
+    StreamingBodyUnsupported,
+};
+use bytes::Bytes;
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen(inline_js = "
+export function mock_fetch() {
+    globalThis.fetch = async (request) => new Response(await request.arrayBuffer(), {
+        status: 201,
+        headers: { 'x-method': request.method, 'x-url': request.url },
+    });
+}
+")]
+extern "C" {
+    /// Replaces the global `fetch` with one that echoes the body, method and URL of the request.
+    fn mock_fetch();
+}
+
+/// Records the requests it's given, and responds to them with `hello`.
+#[derive(Clone, Debug, Default)]
+struct RecordingFetch(Arc<Mutex<Vec<http::Request<Bytes>>>>);
+
+impl Fetch for RecordingFetch {
+    fn fetch(&self, request: http::Request<Bytes>) -> FetchFuture {
+        self.0.lock().unwrap().push(request);
+        Box::pin(async {
+            Ok(http::Response::builder()
+                .status(200)
+                .header("content-type", "text/plain")
+                .body(Bytes::from_static(b"hello"))
+                .unwrap())
+        })
+    }
+}
+
+fn connector(fetch: impl Fetch + 'static) -> SharedHttpConnector {
+    let components = RuntimeComponentsBuilder::for_tests().build().unwrap();
+    FetchHttpClientBuilder::new()
+        .fetch(fetch)
+        .build()
+        .http_connector(&HttpConnectorSettings::builder().build(), &components)
+}
+
+fn request(body: SdkBody) -> HttpRequest {
+    http::Request::builder()
+        .method("PUT")
+        .uri("https://example.com/pokemon?region=kanto")
+        .header("content-type", "application/json")
+        .body(body)
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn requests_are_sent_with_fetch() {
+    let fetch = RecordingFetch::default();
+    let response = connector(fetch.clone())
+        .call(request(SdkBody::from("{}")))
+        .await
+        .unwrap();
+
+    assert_eq!(200, response.status().as_u16());
+    assert_eq!(Some("text/plain"), response.headers().get("content-type"));
+    assert_eq!(Some(&b"hello"[..]), response.body().bytes());
+    let requests = fetch.0.lock().unwrap();
+    assert_eq!(1, requests.len());
+    assert_eq!(http::Method::PUT, requests[0].method());
+    assert_eq!(
+        "https://example.com/pokemon?region=kanto",
+        requests[0].uri().to_string()
+    );
+    assert_eq!("application/json", requests[0].headers()["content-type"]);
+    assert_eq!(&b"{}"[..], requests[0].body());
+}
+
+#[wasm_bindgen_test]
+async fn streaming_request_bodies_are_rejected() {
+    let fetch = RecordingFetch::default();
+    let body = SdkBody::from_body_1_x(http_body_util::Full::new(Bytes::from_static(b"{}")));
+    let error = connector(fetch.clone())
+        .call(request(body))
+        .await
+        .expect_err("streaming bodies aren't supported");
+
+    assert!(error.is_user(), "{error:?}");
+    assert!(error
+        .source()
+        .and_then(|source| source.downcast_ref::<StreamingBodyUnsupported>())
+        .is_some());
+    assert!(fetch.0.lock().unwrap().is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn global_fetch_sends_the_request() {
+    mock_fetch();
+    let response = connector(GlobalFetch::new())
+        .call(request(SdkBody::from("{}")))
+        .await
+        .unwrap();
+
+    assert_eq!(201, response.status().as_u16());
+    assert_eq!(Some("PUT"), response.headers().get("x-method"));
+    assert_eq!(
+        Some("https://example.com/pokemon?region=kanto"),
+        response.headers().get("x-url")
+    );
+    assert_eq!(Some(&b"{}"[..]), response.body().bytes());
+}
+
+#[wasm_bindgen_test]
+async fn sleep_waits_for_a_timer() {
+    let time = JsTimeSource::new();
+    let start = time.now();
+    JsSleep::new().sleep(Duration::from_millis(20)).await;
+    assert!(time.now().duration_since(start).unwrap() >= Duration::from_millis(20));
+}
@@ -4,6 +4,7 @@
  */
 
 use crate::unescape::unescape;
+use aws_smithy_types::unknown_fields;
 use std::borrow::Cow;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
@@ -188,6 +189,8 @@ impl<'a> StartEl<'a> {
 pub struct Document<'a> {
     tokenizer: Tokenizer<'a>,
     depth: Depth,
+    // Names of the currently open elements. Only tracked when recording unknown fields.
+    path: Option<Vec<&'a str>>,
 }
 
 impl<'a> TryFrom<&'a [u8]> for Document<'a> {
@@ -205,6 +208,7 @@ impl<'inp> Document<'inp> {
         Document {
             tokenizer: Tokenizer::from(doc),
             depth: 0,
+            path: unknown_fields::is_recording().then(Vec::new),
         }
     }
 
@@ -281,15 +285,21 @@ impl<'inp> Iterator for Document<'inp> {
             Token::ElementEnd {
                 end: ElementEnd::Close(_, _),
                 ..
-            } => {
-                self.depth -= 1;
             }
-            Token::ElementEnd {
+            | Token::ElementEnd {
                 end: ElementEnd::Empty,
                 ..
-            } => self.depth -= 1,
-            t @ Token::ElementStart { .. } => {
+            } => {
+                self.depth -= 1;
+                if let Some(path) = &mut self.path {
+                    path.pop();
+                }
+            }
+            t @ Token::ElementStart { local, .. } => {
                 self.depth += 1;
+                if let Some(path) = &mut self.path {
+                    path.push(local.as_str());
+                }
                 // We want the startel and endel to have the same depth, but after the opener,
                 // the parser will be at depth 1. Return the previous depth:
                 return Some(Ok((XmlToken(t), self.depth - 1)));
@@ -343,6 +353,27 @@ impl<'inp> ScopedDecoder<'inp, '_> {
         Some(self.nested_decoder(next_tag))
     }
 
+    /// Records this tag as an unknown field if unknown fields are being recorded.
+    ///
+    /// The recorded path is made of the local names of this tag and its ancestors, e.g. `/Response/A/Unknown`.
+    /// See [`aws_smithy_types::unknown_fields`] for more information.
+    pub fn record_unknown_field(&self) {
+        if let Some(path) = &self.doc.path {
+            unknown_fields::record(|| {
+                let mut out = String::new();
+                for name in path
+                    .iter()
+                    .take(self.start_el.depth)
+                    .chain(std::iter::once(&self.start_el.name.local))
+                {
+                    out.push('/');
+                    out.push_str(name);
+                }
+                out
+            });
+        }
+    }
+
     fn nested_decoder<'a>(&'a mut self, start_el: StartEl<'inp>) -> ScopedDecoder<'inp, 'a> {
         ScopedDecoder {
             doc: self.doc,
@@ -514,6 +545,28 @@ mod test {
         );
     }
 
+    #[test]
+    fn record_unknown_fields() {
+        use aws_smithy_types::unknown_fields::record_unknown_fields;
+
+        let xml = r#"<Response><Known><Extra1>1</Extra1></Known><Extra2/></Response>"#;
+        let ((), paths) = record_unknown_fields(|| {
+            let mut doc = Document::new(xml);
+            let mut root = doc.root_element().unwrap();
+            while let Some(mut tag) = root.next_tag() {
+                match tag.start_el() {
+                    s if s.matches("Known") => {
+                        while let Some(tag) = tag.next_tag() {
+                            tag.record_unknown_field();
+                        }
+                    }
+                    _ => tag.record_unknown_field(),
+                }
+            }
+        });
+        assert_eq!(vec!["/Response/Known/Extra1", "/Response/Extra2"], paths);
+    }
+
     #[test]
     fn read_data_invalid() {
         let xml = r#"<Response><A></A></Response>"#;
@@ -68,13 +68,14 @@ where
     fn modify_before_serialization(
         &self,
         context: &mut BeforeSerializationInterceptorContextMut<'_>,
-        _runtime_components: &RuntimeComponents,
+        runtime_components: &RuntimeComponents,
         cfg: &mut ConfigBag,
     ) -> Result<(), BoxError> {
         let token_provider = cfg
             .load::<IdempotencyTokenProvider>()
             .expect("the idempotency provider must be set")
-            .clone();
+            .clone()
+            .with_random_source(runtime_components.random_source());
         (self.set_token)(token_provider, context.input_mut());
         Ok(())
     }
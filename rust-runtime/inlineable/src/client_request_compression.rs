@@ -5,6 +5,7 @@
 
 use aws_smithy_compression::body::compress::CompressedBody;
 use aws_smithy_compression::http::http_body_0_4_x::CompressRequest;
+use aws_smithy_compression::policy::{CompressionDecision, CompressionPolicy, RequestProperties};
 use aws_smithy_compression::{CompressionAlgorithm, CompressionOptions};
 use aws_smithy_runtime::client::sdk_feature::SmithySdkFeature;
 use aws_smithy_runtime_api::box_error::BoxError;
@@ -117,35 +118,37 @@ impl Intercept for RequestCompressionInterceptor {
         let options = state.options.clone().unwrap();
         let request = context.request_mut();
 
-        // Don't wrap a body if compression is disabled.
-        if !options.is_enabled() {
-            tracing::trace!("request compression is disabled and will not be applied");
+        let original_size = http_body::Body::size_hint(request.body()).exact();
+        let properties = RequestProperties::new(
+            request.headers().get(http::header::CONTENT_TYPE),
+            original_size,
+            request.body().is_streaming(),
+        );
+        let evaluation = match cfg.load::<CompressionPolicy>() {
+            Some(policy) => policy.evaluate(&options, properties),
+            None => CompressionPolicy::default().evaluate(&options, properties),
+        };
+        if let Err(reason) = evaluation {
+            tracing::trace!(
+                %reason,
+                original_size,
+                min_compression_size_bytes = options.min_compression_size_bytes(),
+                "request body will not be compressed"
+            );
+            cfg.interceptor_state()
+                .store_put(CompressionDecision::skipped(reason, original_size));
             return Ok(());
         }
 
-        // Don't wrap a body if it's below the minimum size
-        //
-        // Because compressing small amounts of data can actually increase its size,
-        // we check to see if the data is big enough to make compression worthwhile.
-        let size_hint = http_body::Body::size_hint(request.body()).exact();
-        if let Some(known_size) = size_hint {
-            if known_size < options.min_compression_size_bytes() as u64 {
-                tracing::trace!(
-                    min_compression_size_bytes = options.min_compression_size_bytes(),
-                    known_size,
-                    "request body is below minimum size and will not be compressed"
-                );
-                return Ok(());
-            }
-            tracing::trace!("compressing sized request body...");
-        } else {
-            tracing::trace!("compressing unsized request body...");
-        }
-
         wrap_request_body_in_compressed_body(
             request,
             CompressionAlgorithm::Gzip.into_impl_http_body_0_4_x(&options),
         )?;
+        // Only in-memory bodies are compressed up front, streaming ones are compressed as they're sent
+        let compressed_size = request.body().content_length();
+        tracing::debug!(original_size, compressed_size, "compressed request body");
+        cfg.interceptor_state()
+            .store_put(CompressionDecision::engaged(original_size, compressed_size));
         cfg.interceptor_state()
             .store_append::<SmithySdkFeature>(SmithySdkFeature::GzipRequestCompression);
 
@@ -223,8 +226,9 @@ impl Storable for RequestMinCompressionSizeBytes {
 mod tests {
     use super::wrap_request_body_in_compressed_body;
     use crate::client_request_compression::{
-        RequestCompressionInterceptor, RequestMinCompressionSizeBytes,
+        DisableRequestCompression, RequestCompressionInterceptor, RequestMinCompressionSizeBytes,
     };
+    use aws_smithy_compression::policy::{CompressionDecision, CompressionPolicy, SkipReason};
     use aws_smithy_compression::{CompressionAlgorithm, CompressionOptions};
     use aws_smithy_runtime::client::sdk_feature::SmithySdkFeature;
     use aws_smithy_runtime_api::client::interceptors::context::{Input, InterceptorContext};
@@ -284,10 +288,15 @@ mod tests {
     }
 
     fn context() -> InterceptorContext {
+        context_with_content_type("text/plain")
+    }
+
+    fn context_with_content_type(content_type: &str) -> InterceptorContext {
         let mut context = InterceptorContext::new(Input::doesnt_matter());
         context.enter_serialization_phase();
         context.set_request(
             http::Request::builder()
+                .header("content-type", content_type)
                 .body(SdkBody::from(UNCOMPRESSED_INPUT))
                 .unwrap()
                 .try_into()
@@ -320,4 +329,80 @@ mod tests {
             cfg.load::<SmithySdkFeature>().next().unwrap()
         );
     }
+
+    fn decision(mut layer: Layer, mut context: InterceptorContext) -> CompressionDecision {
+        layer.store_put(RequestMinCompressionSizeBytes::from(0));
+        let mut cfg = ConfigBag::of_layers(vec![layer]);
+        let ctx = Into::into(&context);
+
+        let sut = RequestCompressionInterceptor::new();
+        sut.read_before_execution(&ctx, &mut cfg).unwrap();
+
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut ctx = Into::into(&mut context);
+        sut.modify_before_retry_loop(&mut ctx, &rc, &mut cfg)
+            .unwrap();
+
+        *cfg.load::<CompressionDecision>()
+            .expect("decision is recorded")
+    }
+
+    #[test]
+    fn test_compression_decision_records_sizes() {
+        let decision = decision(Layer::new("test"), context());
+        assert_eq!(
+            CompressionDecision::engaged(
+                Some(UNCOMPRESSED_INPUT.len() as u64),
+                Some(COMPRESSED_OUTPUT.len() as u64)
+            ),
+            decision
+        );
+    }
+
+    #[test]
+    fn test_compression_decision_records_skip_reason() {
+        let original_size = Some(UNCOMPRESSED_INPUT.len() as u64);
+
+        let mut layer = Layer::new("test");
+        layer.store_put(DisableRequestCompression(true));
+        assert_eq!(
+            CompressionDecision::skipped(SkipReason::Disabled, original_size),
+            decision(layer, context())
+        );
+
+        assert_eq!(
+            CompressionDecision::skipped(SkipReason::ContentType, original_size),
+            decision(Layer::new("test"), context_with_content_type("image/png"))
+        );
+
+        let mut layer = Layer::new("test");
+        layer.store_put(CompressionPolicy::new().with_max_size_bytes(5));
+        assert_eq!(
+            CompressionDecision::skipped(SkipReason::AboveMaxSize, original_size),
+            decision(layer, context())
+        );
+    }
+
+    #[test]
+    fn test_skipped_request_is_not_modified() {
+        let mut layer = Layer::new("test");
+        layer.store_put(CompressionPolicy::new().with_content_type("text/plain", false));
+        layer.store_put(RequestMinCompressionSizeBytes::from(0));
+        let mut cfg = ConfigBag::of_layers(vec![layer]);
+        let mut context = context();
+        let ctx = Into::into(&context);
+
+        let sut = RequestCompressionInterceptor::new();
+        sut.read_before_execution(&ctx, &mut cfg).unwrap();
+
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut ctx = Into::into(&mut context);
+        sut.modify_before_retry_loop(&mut ctx, &rc, &mut cfg)
+            .unwrap();
+
+        let request = context.request().unwrap();
+        assert_eq!(None, request.headers().get("content-encoding"));
+        assert_eq!(Some(UNCOMPRESSED_INPUT), request.body().bytes());
+        assert!(cfg.load::<SmithySdkFeature>().next().is_none());
+    }
 }
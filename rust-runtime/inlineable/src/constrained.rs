@@ -13,3 +13,35 @@ pub(crate) enum MaybeConstrained<T: Constrained> {
     Constrained(T),
     Unconstrained(T::Unconstrained),
 }
+
+/// The number of characters of a value kept in constraint violation messages.
+const VALUE_SUMMARY_LEN: usize = 64;
+
+/// Summarizes a value that failed to satisfy a constraint for a constraint violation message:
+/// the value is quoted, and truncated after [`VALUE_SUMMARY_LEN`] characters.
+#[allow(dead_code)]
+pub(crate) fn value_summary(value: &str) -> String {
+    match value.char_indices().nth(VALUE_SUMMARY_LEN) {
+        Some((end, _)) => format!("{:?}...", &value[..end]),
+        None => format!("{value:?}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::value_summary;
+
+    #[test]
+    fn long_values_are_truncated() {
+        assert_eq!(r#""pikachu""#, value_summary("pikachu"));
+        assert_eq!(
+            format!("{:?}", "a".repeat(64)),
+            value_summary(&"a".repeat(64))
+        );
+        assert_eq!(
+            format!("{:?}...", "é".repeat(64)),
+            value_summary(&"é".repeat(65))
+        );
+        assert_eq!(r#""line\nbreak""#, value_summary("line\nbreak"));
+    }
+}
@@ -0,0 +1,91 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use regex_automata::hybrid::dfa::{Cache, DFA};
+use regex_automata::{Anchored, Input};
+
+/// Finds where a value stops matching a `@pattern`, for constraint violation messages.
+///
+/// The `regex` crate only tells whether a value matches a pattern. This walks the value through a
+/// lazy DFA of the pattern instead, anchored at the start of the value, until no match is possible
+/// anymore.
+#[derive(Debug)]
+pub(crate) struct PatternMatcher {
+    dfa: DFA,
+}
+
+impl PatternMatcher {
+    /// Compiles `pattern`, which must be supported by the `regex` crate.
+    pub(crate) fn new(pattern: &str) -> Self {
+        // Unicode word boundaries can't be evaluated by a DFA on non-ASCII input. Enabling them
+        // makes the DFA give up on the first non-ASCII character instead of failing to build.
+        let dfa = DFA::builder()
+            .configure(DFA::config().unicode_word_boundary(true))
+            .build(pattern)
+            .expect("patterns supported by the `regex` crate can be compiled into a lazy DFA");
+        Self { dfa }
+    }
+
+    /// Returns the position, in characters, of the first character of `value` that no match of
+    /// the pattern can continue with.
+    ///
+    /// This is the number of characters in `value` when all of them can be continued into a
+    /// match, which happens when `value` is a strict prefix of a match. For patterns with Unicode
+    /// word boundaries, the position is at most the position of the first non-ASCII character.
+    pub(crate) fn mismatch_position(&self, value: &str) -> usize {
+        let mut cache = self.dfa.create_cache();
+        let mut end = self.viable_prefix_len(&mut cache, value.as_bytes());
+        // The byte that can't be matched may be in the middle of a character: that character is
+        // the one that doesn't match.
+        while !value.is_char_boundary(end) {
+            end -= 1;
+        }
+        value[..end].chars().count()
+    }
+
+    /// Returns the length, in bytes, of the longest prefix of `haystack` that can be continued
+    /// into a match of the pattern.
+    fn viable_prefix_len(&self, cache: &mut Cache, haystack: &[u8]) -> usize {
+        let input = Input::new(haystack).anchored(Anchored::Yes);
+        let mut state = match self.dfa.start_state_forward(cache, &input) {
+            Ok(state) => state,
+            Err(_) => return 0,
+        };
+        for (position, byte) in haystack.iter().enumerate() {
+            state = match self.dfa.next_state(cache, state, *byte) {
+                Ok(state) => state,
+                Err(_) => return position,
+            };
+            if state.is_dead() || state.is_quit() {
+                return position;
+            }
+        }
+        haystack.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PatternMatcher;
+
+    #[test]
+    fn mismatch_position_is_the_first_character_that_cannot_match() {
+        let matcher = PatternMatcher::new("^[a-z]+-[0-9]{3}$");
+        assert_eq!(0, matcher.mismatch_position("Pikachu-025"));
+        assert_eq!(7, matcher.mismatch_position("pikachu_025"));
+        assert_eq!(10, matcher.mismatch_position("pikachu-02a"));
+        assert_eq!(11, matcher.mismatch_position("pikachu-0251"));
+        // A strict prefix of a match only stops matching at its end.
+        assert_eq!(9, matcher.mismatch_position("pikachu-0"));
+        assert_eq!(0, matcher.mismatch_position(""));
+    }
+
+    #[test]
+    fn mismatch_position_counts_characters() {
+        let matcher = PatternMatcher::new("^[a-zé]+$");
+        assert_eq!(4, matcher.mismatch_position("pokéMon"));
+        assert_eq!(3, matcher.mismatch_position("pokèmon"));
+    }
+}
@@ -3,9 +3,15 @@
  *  SPDX-License-Identifier: Apache-2.0
  */
 
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
 use aws_smithy_http::event_stream::Receiver;
+use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_smithy_types::event_stream::RawMessage;
+use std::error::Error as StdError;
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
 
 #[derive(Debug)]
 /// Receives unmarshalled events at a time out of an Event Stream.
@@ -26,3 +32,416 @@ impl<T, E> EventReceiver<T, E> {
         self.inner.recv().await
     }
 }
+
+/// Configures how a [`ResumableReceiver`] reconnects to a resumable event stream.
+pub struct ResumePolicy<T, K> {
+    resume_token: Box<dyn Fn(&T) -> Option<K> + Send + Sync>,
+    sleep_impl: SharedAsyncSleep,
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl<T, K> fmt::Debug for ResumePolicy<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResumePolicy")
+            .field("sleep_impl", &self.sleep_impl)
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, K> ResumePolicy<T, K> {
+    /// Creates a new `ResumePolicy`.
+    ///
+    /// `resume_token` extracts the token to resume from out of each received event, if it has one.
+    /// `sleep_impl` is used to back off between reconnects; the client's own sleep implementation
+    /// is available from `client.config().sleep_impl()`.
+    ///
+    /// By default, up to 3 reconnects are attempted after the stream fails, with an exponential
+    /// backoff starting at 100 milliseconds and capped at 5 seconds.
+    pub fn new(
+        resume_token: impl Fn(&T) -> Option<K> + Send + Sync + 'static,
+        sleep_impl: SharedAsyncSleep,
+    ) -> Self {
+        Self {
+            resume_token: Box::new(resume_token),
+            sleep_impl,
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+
+    /// Sets how many times in a row reconnecting is attempted before giving up.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets the backoff before the first reconnect. It doubles with each following attempt.
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the maximum backoff between reconnects.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2_u32.saturating_pow(attempt - 1))
+            .min(self.max_backoff)
+    }
+}
+
+/// An error returned by a [`ResumableReceiver`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ResumeError<E> {
+    /// The stream returned a service error, or failed and couldn't be resumed.
+    Stream(SdkError<E, RawMessage>),
+    /// The operation that produces the stream failed.
+    Connect(BoxError),
+}
+
+impl<E> fmt::Display for ResumeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResumeError::Stream(_) => write!(f, "failed to receive from the event stream"),
+            ResumeError::Connect(_) => write!(f, "failed to connect to the event stream"),
+        }
+    }
+}
+
+impl<E> StdError for ResumeError<E>
+where
+    E: StdError + 'static,
+{
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            ResumeError::Stream(err) => Some(err),
+            ResumeError::Connect(err) => Some(err.as_ref()),
+        }
+    }
+}
+
+/// Receives events from a resumable event stream, reconnecting when the stream fails.
+///
+/// The stream is created by calling `receiver_factory` with the resume token of the last event
+/// that was received (`None` for the first connection), which usually sends the operation that
+/// produces the stream again with that token. When the stream fails with anything other than a
+/// service error, it's recreated after backing off, and events continue to be returned from
+/// [`recv`](ResumableReceiver::recv) as if it was a single stream. Reconnecting is given up after
+/// [`max_attempts`](ResumePolicy::max_attempts) consecutive failures, returning the last error.
+///
+/// Depending on the service, some events may be received again after resuming. It's up to the
+/// application to recognize and skip duplicated events.
+///
+/// ## Examples
+/// ```ignore
+/// let policy = ResumePolicy::new(
+///     |event: &Event| event.as_update().ok().map(|update| update.event_id().to_string()),
+///     client.config().sleep_impl().expect("a sleep impl is configured"),
+/// );
+/// let mut events = ResumableReceiver::new(
+///     move |last_event_id| {
+///         let client = client.clone();
+///         async move {
+///             let output = client.subscribe().set_last_event_id(last_event_id).send().await;
+///             output.map(|output| output.events)
+///         }
+///     },
+///     policy,
+/// );
+/// while let Some(event) = events.recv().await? {
+///     println!("{event:?}");
+/// }
+/// ```
+pub struct ResumableReceiver<T, E, K, F> {
+    receiver_factory: F,
+    policy: ResumePolicy<T, K>,
+    receiver: Option<EventReceiver<T, E>>,
+    resume_token: Option<K>,
+    connected: bool,
+}
+
+impl<T, E, K, F> fmt::Debug for ResumableReceiver<T, E, K, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResumableReceiver")
+            .field("policy", &self.policy)
+            .field("connected", &self.connected)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, E, K, F, Fut, FE> ResumableReceiver<T, E, K, F>
+where
+    K: Clone,
+    F: FnMut(Option<K>) -> Fut,
+    Fut: Future<Output = Result<EventReceiver<T, E>, FE>>,
+    FE: Into<BoxError>,
+{
+    /// Creates a new `ResumableReceiver`. The first stream is created on the first call to
+    /// [`recv`](ResumableReceiver::recv).
+    pub fn new(receiver_factory: F, policy: ResumePolicy<T, K>) -> Self {
+        Self {
+            receiver_factory,
+            policy,
+            receiver: None,
+            resume_token: None,
+            connected: false,
+        }
+    }
+
+    /// Asynchronously tries to receive an event from the stream, reconnecting if it fails.
+    ///
+    /// If the stream has ended, it returns `Ok(None)`. If reconnecting is given up, the last
+    /// error is returned, and the next call to `recv` starts reconnecting again.
+    pub async fn recv(&mut self) -> Result<Option<T>, ResumeError<E>> {
+        let mut attempts = 0;
+        loop {
+            if self.receiver.is_none() {
+                if attempts > 0 {
+                    self.policy
+                        .sleep_impl
+                        .sleep(self.policy.backoff(attempts))
+                        .await;
+                }
+                match (self.receiver_factory)(self.resume_token.clone()).await {
+                    Ok(receiver) => {
+                        self.receiver = Some(receiver);
+                        self.connected = true;
+                    }
+                    Err(err) if !self.connected || attempts >= self.policy.max_attempts => {
+                        return Err(ResumeError::Connect(err.into()));
+                    }
+                    Err(err) => {
+                        let err: BoxError = err.into();
+                        tracing::debug!(attempts, error = %err, "failed to resume the event stream");
+                        attempts += 1;
+                        continue;
+                    }
+                }
+            }
+            let receiver = self.receiver.as_mut().expect("connected above");
+            match receiver.recv().await {
+                Ok(Some(event)) => {
+                    if let Some(token) = (self.policy.resume_token)(&event) {
+                        self.resume_token = Some(token);
+                    }
+                    return Ok(Some(event));
+                }
+                Ok(None) => return Ok(None),
+                Err(err @ SdkError::ServiceError(_)) => return Err(ResumeError::Stream(err)),
+                Err(err) => {
+                    self.receiver = None;
+                    if attempts >= self.policy.max_attempts {
+                        return Err(ResumeError::Stream(err));
+                    }
+                    tracing::debug!(attempts, "event stream failed; resuming it");
+                    attempts += 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "gated-tests"))]
+mod test {
+    use super::{EventReceiver, ResumableReceiver, ResumeError, ResumePolicy};
+    use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep, Sleep};
+    use aws_smithy_eventstream::error::Error as EventStreamError;
+    use aws_smithy_eventstream::frame::{write_message_to, UnmarshallMessage, UnmarshalledMessage};
+    use aws_smithy_http::event_stream::Receiver;
+    use aws_smithy_runtime_api::box_error::BoxError;
+    use aws_smithy_runtime_api::client::result::SdkError;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::event_stream::Message;
+    use bytes::Bytes;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    #[derive(Debug)]
+    struct TokioSleep;
+
+    impl AsyncSleep for TokioSleep {
+        fn sleep(&self, duration: Duration) -> Sleep {
+            Sleep::new(tokio::time::sleep(duration))
+        }
+    }
+
+    /// Unmarshalls each message payload as an event ID, and `0` as a service error.
+    #[derive(Debug)]
+    struct EventIds;
+
+    impl UnmarshallMessage for EventIds {
+        type Output = u32;
+        type Error = String;
+
+        fn unmarshall(
+            &self,
+            message: &Message,
+        ) -> Result<UnmarshalledMessage<u32, String>, EventStreamError> {
+            let id: u32 = std::str::from_utf8(message.payload())
+                .unwrap()
+                .parse()
+                .unwrap();
+            Ok(match id {
+                0 => UnmarshalledMessage::Error("service error".into()),
+                id => UnmarshalledMessage::Event(id),
+            })
+        }
+    }
+
+    /// Returns a stream of the given events that ends cleanly if `complete`, and is cut off in
+    /// the middle of a message otherwise.
+    fn stream(events: &[u32], complete: bool) -> EventReceiver<u32, String> {
+        let mut body = Vec::new();
+        for id in events {
+            write_message_to(&Message::new(Bytes::from(id.to_string())), &mut body).unwrap();
+        }
+        if !complete {
+            let mut partial = Vec::new();
+            write_message_to(&Message::new(Bytes::from_static(b"999")), &mut partial).unwrap();
+            body.extend_from_slice(&partial[..partial.len() / 2]);
+        }
+        EventReceiver::new(Receiver::new(EventIds, SdkBody::from(body)))
+    }
+
+    fn policy() -> ResumePolicy<u32, u32> {
+        ResumePolicy::new(|id: &u32| Some(*id), SharedAsyncSleep::new(TokioSleep))
+    }
+
+    async fn recv_all<F, Fut>(
+        receiver: &mut ResumableReceiver<u32, String, u32, F>,
+    ) -> Result<Vec<u32>, ResumeError<String>>
+    where
+        F: FnMut(Option<u32>) -> Fut,
+        Fut: std::future::Future<Output = Result<EventReceiver<u32, String>, BoxError>>,
+    {
+        let mut events = Vec::new();
+        while let Some(event) = receiver.recv().await? {
+            events.push(event);
+        }
+        Ok(events)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn resumes_from_the_last_resume_token() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut receiver = ResumableReceiver::new(
+            {
+                let calls = calls.clone();
+                move |token: Option<u32>| {
+                    calls.lock().unwrap().push(token);
+                    async move {
+                        Ok::<_, BoxError>(match token {
+                            None => stream(&[1, 2, 3], false),
+                            Some(3) => stream(&[4, 5, 6], false),
+                            Some(_) => stream(&[7], true),
+                        })
+                    }
+                }
+            },
+            policy(),
+        );
+
+        let start = Instant::now();
+        assert_eq!(
+            vec![1, 2, 3, 4, 5, 6, 7],
+            recv_all(&mut receiver).await.unwrap()
+        );
+        assert_eq!(vec![None, Some(3), Some(6)], *calls.lock().unwrap());
+        // Receiving an event resets the backoff
+        assert_eq!(Duration::from_millis(200), start.elapsed());
+        assert!(receiver.recv().await.unwrap().is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gives_up_after_max_attempts() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut receiver = ResumableReceiver::new(
+            {
+                let calls = calls.clone();
+                move |token: Option<u32>| {
+                    calls.lock().unwrap().push(token);
+                    async move {
+                        match token {
+                            None => Ok(stream(&[1, 2, 3], false)),
+                            Some(_) => Err(BoxError::from("connection refused")),
+                        }
+                    }
+                }
+            },
+            policy()
+                .max_attempts(2)
+                .max_backoff(Duration::from_millis(150)),
+        );
+
+        let start = Instant::now();
+        for expected in 1..=3 {
+            assert_eq!(Some(expected), receiver.recv().await.unwrap());
+        }
+        match receiver.recv().await {
+            Err(ResumeError::Connect(err)) => assert_eq!("connection refused", err.to_string()),
+            other => panic!("expected a connect error, got {other:?}"),
+        }
+        assert_eq!(vec![None, Some(3), Some(3)], *calls.lock().unwrap());
+        assert_eq!(Duration::from_millis(250), start.elapsed());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn returns_the_stream_error_when_resumed_streams_keep_failing() {
+        let mut receiver = ResumableReceiver::new(
+            |token: Option<u32>| async move {
+                Ok::<_, BoxError>(match token {
+                    None => stream(&[1], false),
+                    Some(_) => stream(&[], false),
+                })
+            },
+            policy().max_attempts(1),
+        );
+        assert_eq!(Some(1), receiver.recv().await.unwrap());
+        assert!(matches!(
+            receiver.recv().await,
+            Err(ResumeError::Stream(SdkError::ResponseError(_)))
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn does_not_resume_after_service_errors_or_failed_first_connections() {
+        let mut receiver = ResumableReceiver::new(
+            |_token: Option<u32>| async { Ok::<_, BoxError>(stream(&[1, 0], true)) },
+            policy(),
+        );
+        assert_eq!(Some(1), receiver.recv().await.unwrap());
+        assert!(matches!(
+            receiver.recv().await,
+            Err(ResumeError::Stream(SdkError::ServiceError(_)))
+        ));
+
+        let calls = Arc::new(Mutex::new(0));
+        let mut receiver = ResumableReceiver::new(
+            {
+                let calls = calls.clone();
+                move |_token: Option<u32>| {
+                    *calls.lock().unwrap() += 1;
+                    async { Err::<EventReceiver<u32, String>, _>(BoxError::from("not found")) }
+                }
+            },
+            policy(),
+        );
+        assert!(matches!(
+            receiver.recv().await,
+            Err(ResumeError::Connect(_))
+        ));
+        assert_eq!(1, *calls.lock().unwrap());
+    }
+}
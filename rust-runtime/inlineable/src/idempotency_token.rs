@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use aws_smithy_runtime_api::client::random::{RandomSource, SharedRandomSource};
 use aws_smithy_types::config_bag::{Storable, StoreReplace};
 use std::sync::Mutex;
 
@@ -34,10 +35,12 @@ pub(crate) fn uuid_v4(input: u128) -> String {
 /// IdempotencyTokenProvider generates idempotency tokens for idempotent API requests
 ///
 /// Generally, customers will not need to interact with this at all. A sensible default will be
-/// provided automatically during config construction. However, if you need deterministic behavior
-/// for testing, two options are available:
+/// provided automatically during config construction. The default draws tokens from the random
+/// source of the client's runtime components. However, if you need deterministic behavior
+/// for testing, three options are available:
 /// 1. Utilize the From<&'static str>` implementation to hard code an idempotency token
 /// 2. Seed the token provider with [`IdempotencyTokenProvider::with_seed`](IdempotencyTokenProvider::with_seed)
+/// 3. Configure the client with a seeded random source
 #[derive(Debug)]
 pub struct IdempotencyTokenProvider {
     inner: Inner,
@@ -47,10 +50,15 @@ pub struct IdempotencyTokenProvider {
 enum Inner {
     Static(&'static str),
     Random(Mutex<fastrand::Rng>),
+    // Draws from the given random source, or from the random source of the runtime components if
+    // it hasn't been bound to one yet
+    RandomSource(Option<SharedRandomSource>),
 }
 
 pub fn default_provider() -> IdempotencyTokenProvider {
-    IdempotencyTokenProvider::random()
+    IdempotencyTokenProvider {
+        inner: Inner::RandomSource(None),
+    }
 }
 
 impl From<&'static str> for IdempotencyTokenProvider {
@@ -71,6 +79,22 @@ impl IdempotencyTokenProvider {
                 let input: u128 = rng.lock().unwrap().u128(..);
                 uuid_v4(input)
             }
+            Inner::RandomSource(Some(random_source)) => {
+                let mut bytes = [0u8; 16];
+                random_source.fill_bytes(&mut bytes);
+                uuid_v4(u128::from_le_bytes(bytes))
+            }
+            Inner::RandomSource(None) => uuid_v4(fastrand::u128(..)),
+        }
+    }
+
+    /// Binds a provider that draws from the runtime components' random source to `random_source`.
+    pub(crate) fn with_random_source(self, random_source: Option<SharedRandomSource>) -> Self {
+        match self.inner {
+            Inner::RandomSource(None) => Self {
+                inner: Inner::RandomSource(random_source),
+            },
+            inner => Self { inner },
         }
     }
 
@@ -98,6 +122,9 @@ impl Clone for IdempotencyTokenProvider {
         match &self.inner {
             Inner::Static(token) => IdempotencyTokenProvider::fixed(token),
             Inner::Random(_) => IdempotencyTokenProvider::random(),
+            Inner::RandomSource(random_source) => IdempotencyTokenProvider {
+                inner: Inner::RandomSource(random_source.clone()),
+            },
         }
     }
 }
@@ -0,0 +1,164 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_async::future::pagination_stream::fn_stream::FnStream;
+use aws_smithy_json::deserialize::error::DeserializeError;
+use aws_smithy_json::deserialize::incremental::ListItemReader;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::byte_stream::ByteStream;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+type ParseRest<O> = Box<dyn FnOnce(&[u8]) -> Result<O, DeserializeError> + Send + Sync>;
+
+/// A response whose list of items is deserialized incrementally as the response body arrives.
+///
+/// Items are parsed out of the response body one at a time and handed out by
+/// [`next`](Self::next), so only a single item needs to be held in memory at once. Once every
+/// item has been read, the rest of the response is available from
+/// [`into_output`](Self::into_output). The list in that output is always empty.
+pub struct IncrementalListOutput<T, O> {
+    items: FnStream<Result<T, DeserializeError>>,
+    rest: Arc<Mutex<Option<Vec<u8>>>>,
+    parse_rest: ParseRest<O>,
+}
+
+impl<T, O> fmt::Debug for IncrementalListOutput<T, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IncrementalListOutput")
+            .field("items", &self.items)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, O> IncrementalListOutput<T, O>
+where
+    T: Send + 'static,
+{
+    pub(crate) fn new(
+        body: SdkBody,
+        list_member: &'static str,
+        parse_item: fn(&[u8]) -> Result<T, DeserializeError>,
+        parse_rest: impl FnOnce(&[u8]) -> Result<O, DeserializeError> + Send + Sync + 'static,
+    ) -> Self {
+        let rest = Arc::new(Mutex::new(None));
+        let items = FnStream::new({
+            let rest = rest.clone();
+            move |tx| {
+                Box::pin(async move {
+                    let mut body = ByteStream::new(body);
+                    let mut reader = ListItemReader::new(list_member);
+                    loop {
+                        // Hand out every complete item before reading the next chunk of the body
+                        loop {
+                            let item = match reader.next_item() {
+                                Ok(Some(item)) => parse_item(&item),
+                                Ok(None) => break,
+                                Err(err) => Err(err),
+                            };
+                            let failed = item.is_err();
+                            if tx.send(item).await.is_err() || failed {
+                                return;
+                            }
+                        }
+                        match body.next().await {
+                            Some(Ok(chunk)) => reader.push(&chunk),
+                            Some(Err(err)) => {
+                                let err = DeserializeError::custom_source(
+                                    "failed to read the response body",
+                                    err,
+                                );
+                                let _ = tx.send(Err(err)).await;
+                                return;
+                            }
+                            None => break,
+                        }
+                    }
+                    match reader.finish() {
+                        Ok(document) => *rest.lock().unwrap() = Some(document),
+                        Err(err) => {
+                            let _ = tx.send(Err(err)).await;
+                        }
+                    }
+                })
+            }
+        });
+        Self {
+            items,
+            rest,
+            parse_rest: Box::new(parse_rest),
+        }
+    }
+
+    /// Returns the next item of the list, or `None` once every item has been read.
+    ///
+    /// Reading stops after the first error.
+    pub async fn next(&mut self) -> Option<Result<T, DeserializeError>> {
+        self.items.next().await
+    }
+
+    /// Returns the rest of the response.
+    ///
+    /// Returns an error if the items haven't all been read, or if reading them failed.
+    pub fn into_output(self) -> Result<O, DeserializeError> {
+        let rest = self.rest.lock().unwrap().take();
+        match rest {
+            Some(rest) => (self.parse_rest)(&rest),
+            None => Err(DeserializeError::custom(
+                "every item must be read before the rest of the response is available",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IncrementalListOutput;
+    use aws_smithy_json::deserialize::error::DeserializeError;
+    use aws_smithy_types::body::SdkBody;
+
+    fn parse_number(item: &[u8]) -> Result<u64, DeserializeError> {
+        std::str::from_utf8(item)
+            .ok()
+            .and_then(|item| item.parse().ok())
+            .ok_or_else(|| DeserializeError::custom("not a number"))
+    }
+
+    fn output(body: &'static str) -> IncrementalListOutput<u64, String> {
+        IncrementalListOutput::new(SdkBody::from(body), "Items", parse_number, |rest| {
+            Ok(String::from_utf8(rest.to_vec()).unwrap())
+        })
+    }
+
+    #[tokio::test]
+    async fn items_then_rest() {
+        let mut output = output(r#"{"Items": [1, 2, 3], "NextToken": "abc"}"#);
+        let mut items = Vec::new();
+        while let Some(item) = output.next().await {
+            items.push(item.unwrap());
+        }
+        assert_eq!(vec![1, 2, 3], items);
+        assert_eq!(
+            r#"{"Items": [], "NextToken": "abc"}"#,
+            output.into_output().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn rest_requires_every_item_to_be_read() {
+        let mut output = output(r#"{"Items": [1, 2, 3]}"#);
+        output.next().await.unwrap().unwrap();
+        output.into_output().expect_err("items remain");
+    }
+
+    #[tokio::test]
+    async fn item_errors_end_the_stream() {
+        let mut output = output(r#"{"Items": [1, "two", 3]}"#);
+        assert_eq!(1, output.next().await.unwrap().unwrap());
+        output.next().await.unwrap().expect_err("not a number");
+        assert!(output.next().await.is_none());
+        output.into_output().expect_err("reading failed");
+    }
+}
@@ -17,6 +17,8 @@ mod client_idempotency_token;
 #[allow(unused)]
 mod constrained;
 #[allow(dead_code)]
+mod constrained_pattern;
+#[allow(dead_code)]
 mod ec2_query_errors;
 #[allow(unused)]
 mod event_receiver;
@@ -24,6 +26,10 @@ mod event_receiver;
 mod idempotency_token;
 #[allow(dead_code)]
 mod json_errors;
+#[allow(dead_code)]
+mod json_incremental_list;
+#[allow(dead_code)]
+mod partial_success;
 #[allow(unused)]
 mod rest_xml_unwrapped_errors;
 #[allow(unused)]
@@ -0,0 +1,169 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::SdkError;
+use std::error::Error;
+use std::fmt;
+
+/// The successful output of a batch operation in which some entries failed.
+///
+/// The output is kept whole, so the entries that succeeded can still be read from it, and it can
+/// be recovered with [`into_output`](Self::into_output).
+pub struct PartialSuccess<O, F> {
+    output: O,
+    failures: fn(&O) -> &[F],
+}
+
+impl<O, F> PartialSuccess<O, F> {
+    /// Returns `output` if none of its entries failed, and a `PartialSuccess` otherwise.
+    pub(crate) fn check(output: O, failures: fn(&O) -> &[F]) -> Result<O, Self> {
+        if failures(&output).is_empty() {
+            Ok(output)
+        } else {
+            Err(Self { output, failures })
+        }
+    }
+
+    /// Returns the entries that failed.
+    pub fn failures(&self) -> &[F] {
+        (self.failures)(&self.output)
+    }
+
+    /// Returns the output of the operation.
+    pub fn output(&self) -> &O {
+        &self.output
+    }
+
+    /// Consumes the error, returning the output of the operation.
+    pub fn into_output(self) -> O {
+        self.output
+    }
+}
+
+impl<O: fmt::Debug, F> fmt::Debug for PartialSuccess<O, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PartialSuccess")
+            .field("output", &self.output)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<O, F> fmt::Display for PartialSuccess<O, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.failures().len() {
+            1 => write!(f, "the operation succeeded, but 1 entry failed"),
+            n => write!(f, "the operation succeeded, but {n} entries failed"),
+        }
+    }
+}
+
+impl<O: fmt::Debug, F> Error for PartialSuccess<O, F> {}
+
+/// Error returned by the `send_checked` method of the fluent builders of batch operations.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum SendCheckedError<E, O, F> {
+    /// The operation failed.
+    Request(SdkError<E, HttpResponse>),
+    /// The operation succeeded, but some of its entries failed.
+    PartialSuccess(PartialSuccess<O, F>),
+}
+
+impl<E, O, F> SendCheckedError<E, O, F> {
+    /// Returns the partial success, if the operation succeeded.
+    pub fn as_partial_success(&self) -> Option<&PartialSuccess<O, F>> {
+        match self {
+            Self::PartialSuccess(partial) => Some(partial),
+            Self::Request(_) => None,
+        }
+    }
+
+    /// Consumes the error, returning the output of the operation if it succeeded.
+    pub fn into_output(self) -> Option<O> {
+        match self {
+            Self::PartialSuccess(partial) => Some(partial.into_output()),
+            Self::Request(_) => None,
+        }
+    }
+}
+
+impl<E, O, F> From<SdkError<E, HttpResponse>> for SendCheckedError<E, O, F> {
+    fn from(err: SdkError<E, HttpResponse>) -> Self {
+        Self::Request(err)
+    }
+}
+
+impl<E, O, F> From<PartialSuccess<O, F>> for SendCheckedError<E, O, F> {
+    fn from(partial: PartialSuccess<O, F>) -> Self {
+        Self::PartialSuccess(partial)
+    }
+}
+
+impl<E, O, F> fmt::Display for SendCheckedError<E, O, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Request(_) => write!(f, "the operation failed"),
+            Self::PartialSuccess(partial) => fmt::Display::fmt(partial, f),
+        }
+    }
+}
+
+impl<E, O, F> Error for SendCheckedError<E, O, F>
+where
+    E: Error + 'static,
+    O: fmt::Debug + 'static,
+    F: fmt::Debug + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Request(err) => Some(err),
+            Self::PartialSuccess(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Output {
+        succeeded: Vec<&'static str>,
+        failed: Vec<&'static str>,
+    }
+
+    fn failed(output: &Output) -> &[&'static str] {
+        &output.failed
+    }
+
+    #[test]
+    fn outputs_without_failures_are_returned() {
+        let output = Output {
+            succeeded: vec!["pikachu"],
+            failed: vec![],
+        };
+        let output = PartialSuccess::check(output, failed).unwrap();
+        assert_eq!(vec!["pikachu"], output.succeeded);
+    }
+
+    #[test]
+    fn outputs_with_failures_are_recoverable_from_the_error() {
+        let output = Output {
+            succeeded: vec!["pikachu"],
+            failed: vec!["eevee", "ditto"],
+        };
+        let partial = PartialSuccess::check(output, failed).unwrap_err();
+        assert_eq!(&["eevee", "ditto"], partial.failures());
+        assert_eq!(
+            "the operation succeeded, but 2 entries failed",
+            partial.to_string()
+        );
+
+        let err = SendCheckedError::<std::io::Error, _, _>::from(partial);
+        assert!(err.as_partial_success().is_some());
+        assert_eq!(vec!["pikachu"], err.into_output().unwrap().succeeded);
+    }
+}